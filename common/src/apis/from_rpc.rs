@@ -15,7 +15,9 @@ use chrono::{DateTime, Duration, Utc};
 
 use rpc::flame::v1 as rpc;
 
+use super::ids::SessionID;
 use super::types::*;
+use crate::ctx::NodeConfig;
 use crate::FlameError;
 
 impl From<rpc::ResourceRequirement> for ResourceRequirement {
@@ -27,11 +29,50 @@ impl From<rpc::ResourceRequirement> for ResourceRequirement {
     }
 }
 
+impl From<rpc::NodeConfig> for NodeConfig {
+    fn from(config: rpc::NodeConfig) -> Self {
+        Self {
+            slots: config.slots,
+            labels: config.labels,
+            log_level: config.log_level,
+            cache_max_memory: config.cache_max_memory,
+            cache_max_objects: config.cache_max_objects.map(|n| n as usize),
+            prefetch: config.prefetch,
+        }
+    }
+}
+
 impl From<rpc::NodeInfo> for NodeInfo {
     fn from(info: rpc::NodeInfo) -> Self {
         Self {
             arch: info.arch,
             os: info.os,
+            numa_nodes: info.numa_nodes.into_iter().map(NumaNode::from).collect(),
+            gpu_groups: info.gpu_groups.into_iter().map(GpuGroup::from).collect(),
+            prefetched: info.prefetched,
+        }
+    }
+}
+
+impl From<rpc::NumaNode> for NumaNode {
+    fn from(numa: rpc::NumaNode) -> Self {
+        Self {
+            id: numa.id,
+            cpus: numa.cpus,
+        }
+    }
+}
+
+impl From<rpc::GpuGroup> for GpuGroup {
+    fn from(group: rpc::GpuGroup) -> Self {
+        let link = match group.link() {
+            rpc::GpuLink::Pcie => GpuLink::Pcie,
+            rpc::GpuLink::NvLink => GpuLink::NvLink,
+        };
+        Self {
+            gpus: group.gpus,
+            numa_node: group.numa_node,
+            link,
         }
     }
 }
@@ -42,6 +83,7 @@ impl From<rpc::NodeState> for NodeState {
             rpc::NodeState::Unknown => NodeState::Unknown,
             rpc::NodeState::Ready => NodeState::Ready,
             rpc::NodeState::NotReady => NodeState::NotReady,
+            rpc::NodeState::Lost => NodeState::Lost,
         }
     }
 }
@@ -52,6 +94,7 @@ impl From<i32> for NodeState {
             0 => NodeState::Unknown,
             1 => NodeState::Ready,
             2 => NodeState::NotReady,
+            3 => NodeState::Lost,
             _ => NodeState::Unknown,
         }
     }
@@ -94,9 +137,18 @@ impl TryFrom<rpc::Task> for TaskContext {
             .ok_or(FlameError::InvalidConfig("spec".to_string()))?;
 
         Ok(TaskContext {
-            task_id: metadata.id.clone(),
-            session_id: spec.session_id.to_string(),
+            task_id: metadata.id.parse().map_err(|_| {
+                FlameError::InvalidConfig(format!("invalid task id <{}>", metadata.id))
+            })?,
+            session_id: SessionID::from(spec.session_id.as_str()),
+            overrides: TaskOverrides::from(&spec),
             input: spec.input.map(TaskInput::from),
+            input_ref: spec.input_ref.map(ObjectReference::from),
+            checkpoint: task
+                .status
+                .as_ref()
+                .and_then(|status| status.checkpoint.clone()),
+            trace_id: task.status.and_then(|status| status.trace_id),
         })
     }
 }
@@ -127,6 +179,11 @@ impl TryFrom<rpc::Application> for ApplicationContext {
                 .map(|e| (e.name, e.value))
                 .collect(),
             url: spec.url.clone(),
+            health_probe: spec.health_probe.map(HealthProbe::from),
+            hooks: spec.hooks.map(LifecycleHooks::from).unwrap_or_default(),
+            python_env: spec.python_env.map(PythonEnvironment::from),
+            max_instances_per_node: spec.max_instances_per_node,
+            topology: TopologyPolicy::from(spec.topology()),
         })
     }
 }
@@ -145,10 +202,13 @@ impl TryFrom<(rpc::Application, rpc::Session)> for SessionContext {
         let application = ApplicationContext::try_from(app)?;
 
         Ok(SessionContext {
-            session_id: metadata.id,
+            session_id: SessionID::from(metadata.id),
             application,
             slots: spec.slots,
             common_data: spec.common_data.map(CommonData::from),
+            scratch_size: spec.scratch_size,
+            scratch_dir: None,
+            result_policy: ResultPolicy::try_from(spec.result_policy).unwrap_or_default(),
         })
     }
 }
@@ -193,6 +253,7 @@ impl TryFrom<&rpc::Application> for Application {
                 FlameError::InvalidState("invalid creation time".to_string()),
             )?,
             shim: Shim::from(spec.shim()),
+            fairness: FairnessPolicy::from(spec.fairness()),
             image: spec.image.clone(),
             description: spec.description.clone(),
             labels: spec.labels.clone(),
@@ -212,6 +273,20 @@ impl TryFrom<&rpc::Application> for Application {
                 .unwrap_or(DEFAULT_DELAY_RELEASE),
             schema: spec.schema.map(ApplicationSchema::from),
             url: spec.url.clone(),
+            task_environments: spec.task_environments.clone(),
+            task_arguments: spec.task_arguments.clone(),
+            bundle_size: spec.bundle_size.unwrap_or(DEFAULT_BUNDLE_SIZE).max(1),
+            max_instances_per_node: spec.max_instances_per_node,
+            session_defaults: spec
+                .session_defaults
+                .map(SessionDefaults::from)
+                .unwrap_or_default(),
+            health_probe: spec.health_probe.map(HealthProbe::from),
+            hooks: spec.hooks.map(LifecycleHooks::from).unwrap_or_default(),
+            python_env: spec.python_env.map(PythonEnvironment::from),
+            codec: PayloadCodec::from(spec.codec()),
+            payload_type: spec.payload_type,
+            topology: TopologyPolicy::from(spec.topology()),
         })
     }
 }
@@ -220,6 +295,7 @@ impl From<rpc::ApplicationSpec> for ApplicationAttributes {
     fn from(spec: rpc::ApplicationSpec) -> Self {
         Self {
             shim: Shim::from(spec.shim()),
+            fairness: FairnessPolicy::from(spec.fairness()),
             image: spec.image.clone(),
             description: spec.description.clone(),
             labels: spec.labels.clone(),
@@ -239,6 +315,113 @@ impl From<rpc::ApplicationSpec> for ApplicationAttributes {
                 .unwrap_or(DEFAULT_DELAY_RELEASE),
             schema: spec.schema.map(ApplicationSchema::from),
             url: spec.url.clone(),
+            task_environments: spec.task_environments.clone(),
+            task_arguments: spec.task_arguments.clone(),
+            bundle_size: spec.bundle_size.unwrap_or(DEFAULT_BUNDLE_SIZE).max(1),
+            max_instances_per_node: spec.max_instances_per_node,
+            session_defaults: spec
+                .session_defaults
+                .map(SessionDefaults::from)
+                .unwrap_or_default(),
+            health_probe: spec.health_probe.map(HealthProbe::from),
+            hooks: spec.hooks.map(LifecycleHooks::from).unwrap_or_default(),
+            python_env: spec.python_env.map(PythonEnvironment::from),
+            codec: PayloadCodec::from(spec.codec()),
+            payload_type: spec.payload_type,
+            topology: TopologyPolicy::from(spec.topology()),
+        }
+    }
+}
+
+impl From<rpc::SessionDefaults> for SessionDefaults {
+    fn from(defaults: rpc::SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<rpc::HealthProbe> for HealthProbe {
+    fn from(probe: rpc::HealthProbe) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<rpc::LifecycleHooks> for LifecycleHooks {
+    fn from(hooks: rpc::LifecycleHooks) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.map(LifecycleHook::from),
+            post_bind: hooks.post_bind.map(LifecycleHook::from),
+            pre_release: hooks.pre_release.map(LifecycleHook::from),
+        }
+    }
+}
+
+impl From<rpc::LifecycleHook> for LifecycleHook {
+    fn from(hook: rpc::LifecycleHook) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<rpc::PythonEnvironment> for PythonEnvironment {
+    fn from(env: rpc::PythonEnvironment) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
+        }
+    }
+}
+
+impl From<&rpc::TaskSpec> for TaskOverrides {
+    fn from(spec: &rpc::TaskSpec) -> Self {
+        Self {
+            environments: spec
+                .environments
+                .iter()
+                .map(|e| (e.name.clone(), e.value.clone()))
+                .collect(),
+            arguments: spec.arguments.clone(),
+            timeout: spec.timeout,
+            max_attempts: spec.max_attempts,
+            priority: spec.priority,
+            deadline: spec.deadline,
+        }
+    }
+}
+
+impl From<rpc::TaskArtifact> for TaskArtifact {
+    fn from(artifact: rpc::TaskArtifact) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(bytes::Bytes::from),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+impl From<rpc::ObjectReference> for ObjectReference {
+    fn from(object: rpc::ObjectReference) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
         }
     }
 }
@@ -266,6 +449,7 @@ impl From<rpc::SessionState> for SessionState {
         match s {
             rpc::SessionState::Open => SessionState::Open,
             rpc::SessionState::Closed => SessionState::Closed,
+            rpc::SessionState::Closing => SessionState::Closing,
         }
     }
 }
@@ -279,6 +463,61 @@ impl TryFrom<i32> for SessionState {
     }
 }
 
+impl From<rpc::ReplayPolicy> for ReplayPolicy {
+    fn from(p: rpc::ReplayPolicy) -> Self {
+        match p {
+            rpc::ReplayPolicy::KeepResults => ReplayPolicy::KeepResults,
+            rpc::ReplayPolicy::RetryFailed => ReplayPolicy::RetryFailed,
+        }
+    }
+}
+
+impl TryFrom<i32> for ReplayPolicy {
+    type Error = FlameError;
+    fn try_from(p: i32) -> Result<Self, Self::Error> {
+        let policy = rpc::ReplayPolicy::try_from(p)
+            .map_err(|_| FlameError::InvalidConfig("invalid replay policy".to_string()))?;
+        Ok(Self::from(policy))
+    }
+}
+
+impl From<rpc::ResultPolicy> for ResultPolicy {
+    fn from(p: rpc::ResultPolicy) -> Self {
+        match p {
+            rpc::ResultPolicy::Inline => ResultPolicy::Inline,
+            rpc::ResultPolicy::Discard => ResultPolicy::Discard,
+            rpc::ResultPolicy::ObjectCache => ResultPolicy::ObjectCache,
+        }
+    }
+}
+
+impl TryFrom<i32> for ResultPolicy {
+    type Error = FlameError;
+    fn try_from(p: i32) -> Result<Self, Self::Error> {
+        let policy = rpc::ResultPolicy::try_from(p)
+            .map_err(|_| FlameError::InvalidConfig("invalid result policy".to_string()))?;
+        Ok(Self::from(policy))
+    }
+}
+
+impl From<rpc::EventOwnerKind> for EventOwnerKind {
+    fn from(kind: rpc::EventOwnerKind) -> Self {
+        match kind {
+            rpc::EventOwnerKind::EventOwnerSession => EventOwnerKind::Session,
+            rpc::EventOwnerKind::EventOwnerTask => EventOwnerKind::Task,
+        }
+    }
+}
+
+impl TryFrom<i32> for EventOwnerKind {
+    type Error = FlameError;
+    fn try_from(kind: i32) -> Result<Self, Self::Error> {
+        let kind = rpc::EventOwnerKind::try_from(kind)
+            .map_err(|_| FlameError::InvalidConfig("invalid event owner".to_string()))?;
+        Ok(Self::from(kind))
+    }
+}
+
 impl From<rpc::TaskState> for TaskState {
     fn from(s: rpc::TaskState) -> Self {
         match s {
@@ -287,6 +526,7 @@ impl From<rpc::TaskState> for TaskState {
             rpc::TaskState::Succeed => TaskState::Succeed,
             rpc::TaskState::Failed => TaskState::Failed,
             rpc::TaskState::Cancelled => TaskState::Cancelled,
+            rpc::TaskState::Quarantined => TaskState::Quarantined,
         }
     }
 }
@@ -300,6 +540,97 @@ impl TryFrom<i32> for TaskState {
     }
 }
 
+impl From<rpc::FailureReason> for FailureReason {
+    fn from(r: rpc::FailureReason) -> Self {
+        match r {
+            rpc::FailureReason::UnknownReason => FailureReason::Unknown,
+            rpc::FailureReason::ApplicationError => FailureReason::ApplicationError,
+            rpc::FailureReason::ShimCrash => FailureReason::ShimCrash,
+            rpc::FailureReason::Timeout => FailureReason::Timeout,
+            rpc::FailureReason::Preempted => FailureReason::Preempted,
+            rpc::FailureReason::NodeLost => FailureReason::NodeLost,
+            rpc::FailureReason::InputInvalid => FailureReason::InputInvalid,
+            rpc::FailureReason::DeadlineExceeded => FailureReason::DeadlineExceeded,
+        }
+    }
+}
+
+impl TryFrom<i32> for FailureReason {
+    type Error = FlameError;
+    fn try_from(r: i32) -> Result<Self, Self::Error> {
+        let reason = rpc::FailureReason::try_from(r)
+            .map_err(|_| FlameError::InvalidState("invalid failure reason".to_string()))?;
+        Ok(Self::from(reason))
+    }
+}
+
+impl From<rpc::JobState> for JobState {
+    fn from(s: rpc::JobState) -> Self {
+        match s {
+            rpc::JobState::JobRunning => JobState::Running,
+            rpc::JobState::JobSucceed => JobState::Succeed,
+            rpc::JobState::JobFailed => JobState::Failed,
+            rpc::JobState::JobCancelled => JobState::Cancelled,
+        }
+    }
+}
+
+impl TryFrom<i32> for JobState {
+    type Error = FlameError;
+    fn try_from(s: i32) -> Result<Self, Self::Error> {
+        let state = rpc::JobState::try_from(s)
+            .map_err(|_| FlameError::InvalidState("invalid job state".to_string()))?;
+        Ok(Self::from(state))
+    }
+}
+
+impl From<rpc::CompletionPolicy> for CompletionPolicy {
+    fn from(p: rpc::CompletionPolicy) -> Self {
+        match p {
+            rpc::CompletionPolicy::AllTasks => CompletionPolicy::AllTasks,
+            rpc::CompletionPolicy::AnyTask => CompletionPolicy::AnyTask,
+        }
+    }
+}
+
+impl TryFrom<i32> for CompletionPolicy {
+    type Error = FlameError;
+    fn try_from(p: i32) -> Result<Self, Self::Error> {
+        let policy = rpc::CompletionPolicy::try_from(p)
+            .map_err(|_| FlameError::InvalidConfig("invalid completion policy".to_string()))?;
+        Ok(Self::from(policy))
+    }
+}
+
+impl TryFrom<rpc::CreateJobRequest> for JobAttributes {
+    type Error = FlameError;
+    fn try_from(req: rpc::CreateJobRequest) -> Result<Self, Self::Error> {
+        let spec = req
+            .job
+            .ok_or(FlameError::InvalidConfig("job spec".to_string()))?;
+        let inputs = match spec.count {
+            Some(_) if !spec.inputs.is_empty() => {
+                return Err(FlameError::InvalidConfig(
+                    "the inputs and count of job are exclusive".to_string(),
+                ))
+            }
+            Some(count) => JobInputs::Count(count),
+            None => JobInputs::List(spec.inputs.into_iter().map(TaskInput::from).collect()),
+        };
+
+        Ok(Self {
+            id: SessionID::from(req.job_id),
+            application: spec.application,
+            slots: spec.slots,
+            inputs,
+            parallelism: spec.parallelism,
+            completion_policy: CompletionPolicy::try_from(spec.completion_policy)?,
+            max_failed: spec.max_failed,
+            common_data: spec.common_data.map(CommonData::from),
+        })
+    }
+}
+
 impl TryFrom<String> for Shim {
     type Error = FlameError;
     fn try_from(s: String) -> Result<Self, Self::Error> {
@@ -330,6 +661,67 @@ impl TryFrom<i32> for Shim {
     }
 }
 
+impl From<rpc::FairnessPolicy> for FairnessPolicy {
+    fn from(p: rpc::FairnessPolicy) -> Self {
+        match p {
+            rpc::FairnessPolicy::RoundRobin => Self::RoundRobin,
+            rpc::FairnessPolicy::Proportional => Self::Proportional,
+        }
+    }
+}
+
+impl TryFrom<i32> for FairnessPolicy {
+    type Error = FlameError;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        let p = rpc::FairnessPolicy::try_from(v)
+            .map_err(|_| FlameError::InvalidState("invalid fairness policy".to_string()))?;
+        Ok(Self::from(p))
+    }
+}
+
+impl From<rpc::PayloadCodec> for PayloadCodec {
+    fn from(c: rpc::PayloadCodec) -> Self {
+        match c {
+            rpc::PayloadCodec::Raw => Self::Raw,
+            rpc::PayloadCodec::Json => Self::Json,
+            rpc::PayloadCodec::Msgpack => Self::Msgpack,
+            rpc::PayloadCodec::Arrow => Self::Arrow,
+            rpc::PayloadCodec::Protobuf => Self::Protobuf,
+        }
+    }
+}
+
+impl TryFrom<i32> for PayloadCodec {
+    type Error = FlameError;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        let c = rpc::PayloadCodec::try_from(v)
+            .map_err(|_| FlameError::InvalidState("invalid payload codec".to_string()))?;
+        Ok(Self::from(c))
+    }
+}
+
+impl From<rpc::TopologyPolicy> for TopologyPolicy {
+    fn from(p: rpc::TopologyPolicy) -> Self {
+        match p {
+            rpc::TopologyPolicy::Ignore => Self::Ignore,
+            rpc::TopologyPolicy::NumaAligned => Self::NumaAligned,
+            rpc::TopologyPolicy::GpuAligned => Self::GpuAligned,
+        }
+    }
+}
+
+impl TryFrom<i32> for TopologyPolicy {
+    type Error = FlameError;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        let p = rpc::TopologyPolicy::try_from(v)
+            .map_err(|_| FlameError::InvalidState("invalid topology policy".to_string()))?;
+        Ok(Self::from(p))
+    }
+}
+
 impl From<rpc::ExecutorState> for ExecutorState {
     fn from(s: rpc::ExecutorState) -> Self {
         match s {
@@ -367,11 +759,55 @@ impl From<rpc::TaskResult> for TaskResult {
         } else {
             TaskState::Succeed
         };
+        // The shims report the application errors without a reason.
+        let failure_reason = (state == TaskState::Failed).then(|| {
+            result
+                .failure_reason
+                .map(|r| FailureReason::try_from(r).unwrap_or_default())
+                .unwrap_or(FailureReason::ApplicationError)
+        });
 
         Self {
             state,
+            failure_reason,
             output: result.output.map(TaskOutput::from),
+            output_ref: result.output_ref.map(ObjectReference::from),
             message: result.message,
+            artifacts: result
+                .artifacts
+                .into_iter()
+                .map(TaskArtifact::from)
+                .collect(),
+            usage: result.usage.map(TaskUsage::from),
+            postmortem: result.postmortem.map(TaskPostmortem::from),
+            codec: result.codec.and_then(|c| PayloadCodec::try_from(c).ok()),
+        }
+    }
+}
+
+impl From<rpc::TaskUsage> for TaskUsage {
+    fn from(usage: rpc::TaskUsage) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+impl From<rpc::TaskPostmortem> for TaskPostmortem {
+    fn from(pm: rpc::TaskPostmortem) -> Self {
+        Self {
+            executor_id: pm.executor_id,
+            node: pm.node,
+            exit_code: pm.exit_code,
+            signal: pm.signal,
+            core_dumped: pm.core_dumped,
+            stderr_tail: pm.stderr_tail,
+            load_average: pm.load_average,
+            memory_available: pm.memory_available,
+            creation_time: DateTime::from_timestamp(pm.creation_time, 0).unwrap_or_default(),
         }
     }
 }
@@ -394,7 +830,17 @@ impl TryFrom<TaskResult> for rpc::TaskResult {
         Ok(Self {
             return_code,
             output: result.output.map(TaskOutput::into),
+            output_ref: result.output_ref.map(rpc::ObjectReference::from),
             message: result.message,
+            artifacts: result
+                .artifacts
+                .into_iter()
+                .map(rpc::TaskArtifact::from)
+                .collect(),
+            failure_reason: result.failure_reason.map(i32::from),
+            usage: result.usage.map(rpc::TaskUsage::from),
+            postmortem: result.postmortem.map(rpc::TaskPostmortem::from),
+            codec: result.codec.map(i32::from),
         })
     }
 }
@@ -0,0 +1,198 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The typed IDs of the sessions and tasks, so the IDs of different objects
+//! are not mixed up in the signatures, e.g. a session ID passed as a task ID.
+//!
+//! They're serialized as the raw IDs, i.e. a string and an integer, in serde,
+//! the protos and the storage engines.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// The ID of a session, e.g. "ssn-1".
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+#[serde(transparent)]
+pub struct SessionID(String);
+
+impl SessionID {
+    pub fn new(id: impl Into<String>) -> Self {
+        SessionID(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SessionID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for SessionID {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SessionID(s.to_string()))
+    }
+}
+
+impl Deref for SessionID {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SessionID {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SessionID {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SessionID {
+    fn from(id: String) -> Self {
+        SessionID(id)
+    }
+}
+
+impl From<&str> for SessionID {
+    fn from(id: &str) -> Self {
+        SessionID(id.to_string())
+    }
+}
+
+impl From<&String> for SessionID {
+    fn from(id: &String) -> Self {
+        SessionID(id.clone())
+    }
+}
+
+impl From<SessionID> for String {
+    fn from(id: SessionID) -> Self {
+        id.0
+    }
+}
+
+impl PartialEq<str> for SessionID {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SessionID {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SessionID {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+/// The ID of a task, which is unique in its session; the IDs start from 1,
+/// and 0 is the owner of the session events.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+#[serde(transparent)]
+pub struct TaskID(i64);
+
+impl TaskID {
+    pub const fn new(id: i64) -> Self {
+        TaskID(id)
+    }
+
+    pub const fn value(&self) -> i64 {
+        self.0
+    }
+
+    /// The next task ID in the session.
+    pub const fn next(&self) -> Self {
+        TaskID(self.0 + 1)
+    }
+}
+
+impl fmt::Display for TaskID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for TaskID {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<i64>().map(TaskID)
+    }
+}
+
+impl From<i64> for TaskID {
+    fn from(id: i64) -> Self {
+        TaskID(id)
+    }
+}
+
+impl From<TaskID> for i64 {
+    fn from(id: TaskID) -> Self {
+        id.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_session_id() {
+        let id: SessionID = "ssn-1".parse().unwrap();
+        assert_eq!(id, "ssn-1");
+        assert_eq!(id.to_string(), "ssn-1");
+        assert_eq!(String::from(id.clone()), "ssn-1".to_string());
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"ssn-1\"");
+
+        let ssns = HashMap::from([(id.clone(), 1)]);
+        assert_eq!(ssns.get("ssn-1"), Some(&1));
+    }
+
+    #[test]
+    fn test_task_id() {
+        let id: TaskID = "42".parse().unwrap();
+        assert_eq!(id, TaskID::new(42));
+        assert_eq!(id.next().value(), 43);
+        assert_eq!(id.to_string(), "42");
+        assert!("task-1".parse::<TaskID>().is_err());
+        assert_eq!(serde_json::to_string(&id).unwrap(), "42");
+        assert_eq!(serde_json::from_str::<TaskID>("42").unwrap(), id);
+    }
+}
@@ -12,14 +12,20 @@ limitations under the License.
 */
 
 mod from_rpc;
+mod ids;
 mod session;
 mod to_rpc;
 mod types;
+mod yaml;
 
+pub use ids::{SessionID, TaskID};
 pub use types::*;
+pub use yaml::load_applications;
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
     #[test]
@@ -29,6 +35,8 @@ mod tests {
             ("cpu=1,mem=1k", (1, 1024)),
             ("cpu=1,memory=1m", (1, 1024 * 1024)),
             ("cpu=1,memory=1g", (1, 1024 * 1024 * 1024)),
+            ("cpu=2,memory=4Gi", (2, 4 * 1024 * 1024 * 1024)),
+            ("cpu=2,memory=512MiB", (2, 512 * 1024 * 1024)),
         ];
 
         for (input, expected) in cases {
@@ -38,6 +46,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resreq_to_slots() {
+        let unit = ResourceRequirement::from("cpu=1,mem=2g");
+        let resreq = ResourceRequirement::from("cpu=8,mem=8g");
+        assert_eq!(resreq.to_slots(&unit), 4);
+
+        let unit = ResourceRequirement::from("cpu=2");
+        assert_eq!(resreq.to_slots(&unit), 4);
+
+        let reserved = ResourceRequirement::from("cpu=10,mem=1g");
+        let left = resreq.saturating_sub(&reserved);
+        assert_eq!(left.cpu, 0);
+        assert_eq!(left.memory, 7 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_parse_cgroup_limit() {
+        assert_eq!(types::parse_cgroup_limit("max\n"), None);
+        assert_eq!(types::parse_cgroup_limit("4294967296\n"), Some(4294967296));
+        assert_eq!(types::parse_cgroup_limit(""), None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_cpu_max() {
+        assert_eq!(types::parse_cgroup_cpu_max("max 100000\n"), None);
+        assert_eq!(types::parse_cgroup_cpu_max("200000 100000\n"), Some(2));
+        // The fraction of a CPU is rounded up.
+        assert_eq!(types::parse_cgroup_cpu_max("150000 100000"), Some(2));
+        assert_eq!(types::parse_cgroup_cpu_max("50000 100000"), Some(1));
+        // No quota of cgroup v1.
+        assert_eq!(types::parse_cgroup_cpu_max("-1 100000"), None);
+        assert_eq!(types::parse_cgroup_cpu_max(""), None);
+    }
+
     #[test]
     fn test_shim_default() {
         let shim = Shim::default();
@@ -60,4 +102,511 @@ mod tests {
         let attrs = ApplicationAttributes::default();
         assert_eq!(attrs.shim, Shim::Host);
     }
+
+    #[test]
+    fn test_validate_task_overrides() {
+        let app = Application {
+            name: "flmtest".to_string(),
+            task_environments: vec!["LOG_LEVEL".to_string()],
+            task_arguments: vec!["--level".to_string(), "--verbose".to_string()],
+            ..Application::default()
+        };
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            arguments: vec!["--level=3".to_string(), "--verbose".to_string()],
+            timeout: Some(60),
+            ..TaskOverrides::default()
+        };
+        assert!(app.validate_task_overrides(&overrides).is_ok());
+
+        let overrides = TaskOverrides {
+            timeout: Some(0),
+            ..TaskOverrides::default()
+        };
+        assert!(app.validate_task_overrides(&overrides).is_err());
+
+        let overrides = TaskOverrides {
+            deadline: Some(0),
+            ..TaskOverrides::default()
+        };
+        assert!(app.validate_task_overrides(&overrides).is_err());
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("PATH".to_string(), "/tmp".to_string())]),
+            ..TaskOverrides::default()
+        };
+        assert!(app.validate_task_overrides(&overrides).is_err());
+
+        let overrides = TaskOverrides {
+            arguments: vec!["--output=/tmp".to_string()],
+            ..TaskOverrides::default()
+        };
+        assert!(app.validate_task_overrides(&overrides).is_err());
+    }
+
+    #[test]
+    fn test_validate_payload_codec() {
+        // The payloads of the raw codec are never validated.
+        assert!(PayloadCodec::Raw
+            .validate(Some(PayloadCodec::Json), Some(b"\x00"))
+            .is_ok());
+
+        assert!(PayloadCodec::Json
+            .validate(Some(PayloadCodec::Json), Some(br#"{"n": 1}"#))
+            .is_ok());
+        assert!(PayloadCodec::Json.validate(None, None).is_ok());
+        assert!(PayloadCodec::Json.validate(None, Some(b"{")).is_err());
+        assert!(PayloadCodec::Json
+            .validate(Some(PayloadCodec::Msgpack), Some(b"{}"))
+            .is_err());
+        assert!(PayloadCodec::Arrow
+            .validate(Some(PayloadCodec::Arrow), Some(b"\xff"))
+            .is_ok());
+
+        let attrs = ApplicationAttributes {
+            codec: PayloadCodec::Protobuf,
+            ..ApplicationAttributes::default()
+        };
+        assert!(attrs.validate_codec().is_err());
+        let attrs = ApplicationAttributes {
+            payload_type: Some("example.v1.Matrix".to_string()),
+            ..attrs
+        };
+        assert!(attrs.validate_codec().is_ok());
+        let attrs = ApplicationAttributes {
+            codec: PayloadCodec::Json,
+            ..attrs
+        };
+        assert!(attrs.validate_codec().is_err());
+    }
+
+    #[test]
+    fn test_validate_health_probe() {
+        let probe = HealthProbe {
+            command: Some("test -S $FLAME_INSTANCE_ENDPOINT".to_string()),
+            ..HealthProbe::default()
+        };
+        assert!(probe.validate().is_ok());
+        assert_eq!(probe.timeout(), std::time::Duration::from_secs(5));
+
+        let probe = HealthProbe {
+            grpc_service: Some(String::new()),
+            failure_threshold: Some(1),
+            ..HealthProbe::default()
+        };
+        assert!(probe.validate().is_ok());
+        assert_eq!(probe.failure_threshold(), 1);
+
+        // Exactly one of the command and the gRPC check.
+        assert!(HealthProbe::default().validate().is_err());
+        let probe = HealthProbe {
+            command: Some("true".to_string()),
+            grpc_service: Some(String::new()),
+            ..HealthProbe::default()
+        };
+        assert!(probe.validate().is_err());
+
+        let probe = HealthProbe {
+            command: Some("true".to_string()),
+            period_seconds: Some(0),
+            ..HealthProbe::default()
+        };
+        assert!(probe.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_lifecycle_hooks() {
+        let hooks = LifecycleHooks {
+            pre_bind: Some(LifecycleHook {
+                command: "fetch-license".to_string(),
+                timeout_seconds: Some(10),
+            }),
+            ..LifecycleHooks::default()
+        };
+        assert!(hooks.validate().is_ok());
+        assert!(!hooks.is_empty());
+        assert!(LifecycleHooks::default().is_empty());
+
+        let hooks = LifecycleHooks {
+            post_bind: Some(LifecycleHook {
+                command: " ".to_string(),
+                timeout_seconds: None,
+            }),
+            ..LifecycleHooks::default()
+        };
+        assert!(hooks.validate().is_err());
+
+        let hooks = LifecycleHooks {
+            pre_release: Some(LifecycleHook {
+                command: "true".to_string(),
+                timeout_seconds: Some(0),
+            }),
+            ..LifecycleHooks::default()
+        };
+        assert!(hooks.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_python_environment() {
+        let env = PythonEnvironment {
+            requirements: Some("requirements.txt".to_string()),
+            python_version: Some("3.12".to_string()),
+            ..PythonEnvironment::default()
+        };
+        assert!(env.validate().is_ok());
+
+        let env = PythonEnvironment {
+            project: Some("/opt/matrix".to_string()),
+            ..PythonEnvironment::default()
+        };
+        assert!(env.validate().is_ok());
+
+        // Either the project or the requirements, but not both.
+        assert!(PythonEnvironment::default().validate().is_err());
+        let env = PythonEnvironment {
+            project: Some("/opt/matrix".to_string()),
+            requirements: Some("requirements.txt".to_string()),
+            ..PythonEnvironment::default()
+        };
+        assert!(env.validate().is_err());
+
+        let env = PythonEnvironment {
+            project: Some("/opt/matrix".to_string()),
+            python_version: Some(" ".to_string()),
+            ..PythonEnvironment::default()
+        };
+        assert!(env.validate().is_err());
+    }
+
+    #[test]
+    fn test_session_attributes_with_defaults() {
+        let defaults = SessionDefaults {
+            slots: Some(4),
+            max_instances: Some(8),
+            max_task_attempts: Some(5),
+            ..SessionDefaults::default()
+        };
+
+        // The unspecified attributes take the defaults of the application.
+        let attr = SessionAttributes {
+            id: "ssn-1".into(),
+            slots: 0,
+            batch_size: 0,
+            ..SessionAttributes::default()
+        }
+        .with_defaults(&defaults)
+        .unwrap();
+        assert_eq!(attr.slots, 4);
+        assert_eq!(attr.batch_size, DEFAULT_BATCH_SIZE);
+        assert_eq!(attr.max_instances, Some(8));
+        // The settings of the tasks are resolved when they're used.
+        assert_eq!(attr.max_task_attempts, None);
+
+        // The specified attributes override the defaults of the application.
+        let attr = SessionAttributes {
+            id: "ssn-2".into(),
+            slots: 2,
+            max_instances: Some(2),
+            ..SessionAttributes::default()
+        }
+        .with_defaults(&defaults)
+        .unwrap();
+        assert_eq!(attr.slots, 2);
+        assert_eq!(attr.max_instances, Some(2));
+
+        // The defaulted max_instances must not be less than min_instances.
+        let attr = SessionAttributes {
+            id: "ssn-3".into(),
+            min_instances: 10,
+            ..SessionAttributes::default()
+        };
+        assert!(attr.with_defaults(&defaults).is_err());
+
+        let defaults = SessionDefaults {
+            slots: Some(0),
+            ..SessionDefaults::default()
+        };
+        assert!(defaults.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_task_artifacts() {
+        let inline = TaskArtifact {
+            name: "summary".to_string(),
+            data: Some(bytes::Bytes::from("ok")),
+            object_ref: None,
+        };
+        let object = TaskArtifact {
+            name: "model".to_string(),
+            data: None,
+            object_ref: Some("grpc://cache/ssn/model".to_string()),
+        };
+        assert!(TaskArtifact::validate_all(&[inline.clone(), object.clone()]).is_ok());
+
+        assert!(TaskArtifact::validate_all(&[inline.clone(), inline.clone()]).is_err());
+        let unnamed = TaskArtifact {
+            name: String::new(),
+            ..inline.clone()
+        };
+        assert!(TaskArtifact::validate_all(&[unnamed]).is_err());
+        let both = TaskArtifact {
+            object_ref: object.object_ref.clone(),
+            ..inline.clone()
+        };
+        assert!(TaskArtifact::validate_all(&[both]).is_err());
+        let empty = TaskArtifact {
+            data: None,
+            ..inline
+        };
+        assert!(TaskArtifact::validate_all(&[empty]).is_err());
+    }
+
+    #[test]
+    fn test_validate_object_reference() {
+        let object = ObjectReference {
+            endpoint: "grpc://127.0.0.1:9090".to_string(),
+            key: "app/ssn-1/input-1".to_string(),
+            checksum: Some("sha256:9f86d0".to_string()),
+        };
+        assert!(object.validate().is_ok());
+
+        let no_checksum = ObjectReference {
+            checksum: None,
+            ..object.clone()
+        };
+        assert!(no_checksum.validate().is_ok());
+
+        let no_key = ObjectReference {
+            key: String::new(),
+            ..object.clone()
+        };
+        assert!(no_key.validate().is_err());
+        let bad_checksum = ObjectReference {
+            checksum: Some("9f86d0".to_string()),
+            ..object
+        };
+        assert!(bad_checksum.validate().is_err());
+    }
+
+    #[test]
+    fn test_task_duration_stats() {
+        let mut stats = TaskDurationStats::default();
+        assert_eq!(stats.expected(), None);
+
+        for ms in [100, 300] {
+            stats.observe(chrono::Duration::milliseconds(ms));
+        }
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.mean, 200.0);
+        assert_eq!((stats.min, stats.max), (100, 300));
+        assert_eq!(stats.recent, 140.0);
+        assert_eq!(stats.expected(), None);
+
+        for _ in 2..MIN_TASK_DURATION_SAMPLES {
+            stats.observe(chrono::Duration::milliseconds(140));
+        }
+        assert_eq!(stats.expected(), Some(140.0));
+    }
+
+    #[test]
+    fn test_task_result_failure_reason() {
+        let result = TaskResult::from(rpc::flame::v1::TaskResult {
+            return_code: -1,
+            ..Default::default()
+        });
+        assert_eq!(result.state, TaskState::Failed);
+        assert_eq!(result.failure_reason, Some(FailureReason::ApplicationError));
+
+        let result = TaskResult::from(rpc::flame::v1::TaskResult {
+            return_code: -1,
+            failure_reason: Some(rpc::flame::v1::FailureReason::InputInvalid as i32),
+            ..Default::default()
+        });
+        assert_eq!(result.failure_reason, Some(FailureReason::InputInvalid));
+        assert!(!FailureReason::InputInvalid.is_infrastructure());
+        assert!(!FailureReason::DeadlineExceeded.is_infrastructure());
+        assert!(FailureReason::NodeLost.is_infrastructure());
+
+        let result = TaskResult::from(rpc::flame::v1::TaskResult::default());
+        assert_eq!(result.failure_reason, None);
+    }
+
+    #[test]
+    fn test_job_attributes_from_rpc() {
+        let req = rpc::flame::v1::CreateJobRequest {
+            job_id: "job-1".to_string(),
+            job: Some(rpc::flame::v1::JobSpec {
+                application: "app".to_string(),
+                slots: 1,
+                count: Some(3),
+                parallelism: Some(2),
+                completion_policy: rpc::flame::v1::CompletionPolicy::AnyTask as i32,
+                ..Default::default()
+            }),
+        };
+        let attr = JobAttributes::try_from(req.clone()).unwrap();
+        assert_eq!(attr.id, SessionID::from("job-1"));
+        assert_eq!(attr.completion_policy, CompletionPolicy::AnyTask);
+        assert_eq!(attr.inputs.len(), 3);
+        assert_eq!(attr.inputs.get(2), Some(TaskInput::from("2")));
+        assert_eq!(attr.inputs.get(3), None);
+        assert!(attr.validate().is_ok());
+
+        // The inputs and the count are exclusive.
+        let mut both = req.clone();
+        both.job.as_mut().unwrap().inputs = vec![b"0".to_vec()];
+        assert!(JobAttributes::try_from(both).is_err());
+
+        let mut empty = req.clone();
+        empty.job.as_mut().unwrap().count = None;
+        assert!(JobAttributes::try_from(empty).unwrap().validate().is_err());
+
+        let mut no_parallelism = req;
+        no_parallelism.job.as_mut().unwrap().parallelism = Some(0);
+        assert!(JobAttributes::try_from(no_parallelism)
+            .unwrap()
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_failure_category() {
+        assert_eq!(failure_category(None), "Unknown");
+        assert_eq!(failure_category(Some("  \n")), "Unknown");
+        assert_eq!(
+            failure_category(Some("ValueError: invalid input")),
+            "ValueError"
+        );
+        assert_eq!(failure_category(Some("Task timeout")), "Task timeout");
+        assert_eq!(
+            failure_category(Some(
+                "Traceback (most recent call last):\n  File \"app.py\", line 1\nKeyError: 'x'\n"
+            )),
+            "KeyError"
+        );
+    }
+
+    #[test]
+    fn test_session_summary() {
+        let now = chrono::Utc::now();
+        let mut ssn = Session {
+            id: "ssn-1".into(),
+            ..Default::default()
+        };
+        for (id, state, age) in [
+            (1, TaskState::Succeed, Some(60)),
+            (2, TaskState::Failed, Some(120)),
+            (3, TaskState::Succeed, Some(600)),
+            (4, TaskState::Running, None),
+        ] {
+            ssn.update_task(&Task {
+                id: TaskID::new(id),
+                ssn_id: ssn.id.clone(),
+                version: 1,
+                state,
+                completion_time: age.map(|secs| now - chrono::Duration::seconds(secs)),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+
+        let mut summary = ssn.summary(chrono::Duration::minutes(5), now).unwrap();
+        assert_eq!(summary.tasks.get(&TaskState::Succeed), Some(&2));
+        assert_eq!(summary.tasks.get(&TaskState::Running), Some(&1));
+        assert_eq!(summary.completed, 2);
+        assert_eq!(summary.throughput(), 0.4);
+        assert_eq!(summary.average_duration, None);
+
+        for message in ["KeyError: 'x'", "ValueError: y", "ValueError: z"] {
+            summary.add_failure(Some(FailureReason::ApplicationError), Some(message));
+        }
+        summary.add_failure(Some(FailureReason::ShimCrash), Some("transport error"));
+        let failures: Vec<_> = summary
+            .failures
+            .iter()
+            .map(|f| (f.category.as_str(), f.count))
+            .collect();
+        assert_eq!(
+            failures,
+            vec![("ValueError", 2), ("KeyError", 1), ("ShimCrash", 1)]
+        );
+        assert_eq!(
+            summary.failures[0].message.as_deref(),
+            Some("ValueError: y")
+        );
+    }
+
+    #[test]
+    fn test_pop_pending_task_by_priority() {
+        let mut ssn = Session {
+            id: "ssn-1".into(),
+            ..Default::default()
+        };
+        for (id, priority) in [(1, None), (2, Some(5)), (3, Some(9)), (4, Some(0))] {
+            ssn.update_task(&Task {
+                id: TaskID::new(id),
+                ssn_id: ssn.id.clone(),
+                version: 1,
+                overrides: TaskOverrides {
+                    priority,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap();
+        }
+        assert_eq!(ssn.task_priorities.len(), 3);
+
+        // The higher tasks go first, and the lower one than the session last.
+        let mut popped = vec![];
+        while let Some(task_ptr) = ssn.pop_pending_task(0, 1, 1) {
+            popped.push(task_ptr.lock().unwrap().id.value());
+        }
+        assert_eq!(popped, vec![3, 2, 1, 4]);
+        assert!(ssn.task_priorities.is_empty());
+    }
+
+    #[test]
+    fn test_event_page() {
+        let now = chrono::Utc::now();
+        let event = |code, secs| Event {
+            code,
+            message: None,
+            creation_time: now + chrono::Duration::seconds(secs),
+        };
+        let filter = EventFilter {
+            since: Some(now),
+            codes: vec![1, 2],
+            owner: Some(EventOwnerKind::Task),
+            ..Default::default()
+        };
+        assert!(filter.matches(TaskID::new(1), 1, now));
+        assert!(!filter.matches(SESSION_EVENT_OWNER, 1, now));
+        assert!(!filter.matches(TaskID::new(1), 3, now));
+        assert!(!filter.matches(TaskID::new(1), 1, now - chrono::Duration::seconds(1)));
+
+        let events = vec![
+            SessionEvent::new(TaskID::new(2), event(1, 1)),
+            SessionEvent::new(SESSION_EVENT_OWNER, event(2, 0)),
+            SessionEvent::new(TaskID::new(1), event(3, 1)),
+            SessionEvent::new(TaskID::new(1), event(4, 1)),
+        ];
+        let codes = |page: &EventPage| page.events.iter().map(|e| e.event.code).collect::<Vec<_>>();
+
+        let page = EventPage::new(events.clone(), 3, None).unwrap();
+        assert_eq!(codes(&page), vec![2, 3, 4]);
+        assert_eq!(page.events[0].task_id, None);
+        assert_eq!(page.next_page_token.as_deref(), Some("3"));
+
+        let page = EventPage::new(events.clone(), 3, Some("3")).unwrap();
+        assert_eq!(codes(&page), vec![1]);
+        assert_eq!(page.next_page_token, None);
+
+        assert!(EventPage::new(events.clone(), 3, Some("10"))
+            .unwrap()
+            .events
+            .is_empty());
+        assert!(EventPage::new(events, 3, Some("x")).is_err());
+    }
 }
@@ -13,8 +13,11 @@ limitations under the License.
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Duration, Utc};
+
 use stdng::lock_ptr;
 
+use super::ids::TaskID;
 use super::types::*;
 use crate::FlameError;
 
@@ -23,6 +26,16 @@ impl Session {
         self.status.state == SessionState::Closed
     }
 
+    /// Whether the session was closed longer than its TTL after completion.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        match (self.completion_time, self.ttl_after_completion) {
+            (Some(completion_time), Some(ttl)) => {
+                self.is_closed() && completion_time + Duration::seconds(ttl as i64) <= now
+            }
+            _ => false,
+        }
+    }
+
     pub fn update_task(&mut self, task: &Task) -> Result<(), FlameError> {
         let task_ptr = TaskPtr::new(task.clone().into());
 
@@ -63,6 +76,15 @@ impl Session {
             .unwrap()
             .insert(task.id, task_ptr);
 
+        match task.overrides.priority {
+            Some(priority) if task.state == TaskState::Pending => {
+                self.task_priorities.insert(task.id, priority);
+            }
+            _ => {
+                self.task_priorities.remove(&task.id);
+            }
+        }
+
         let pending_count = self
             .tasks_index
             .get(&TaskState::Pending)
@@ -83,23 +105,111 @@ impl Session {
         Ok(())
     }
 
-    pub fn pop_pending_task(&mut self, batch_index: u32, batch_size: u32) -> Option<TaskPtr> {
-        let pending_tasks = self.tasks_index.get_mut(&TaskState::Pending)?;
+    /// Reopen the closed session; the failed tasks are reset to pending by `RetryFailed`.
+    /// Returns the ids of the tasks to run again.
+    pub fn reopen(&mut self, policy: ReplayPolicy) -> Result<Vec<TaskID>, FlameError> {
+        self.status.state = SessionState::Open;
+        self.completion_time = None;
+        self.version += 1;
 
-        if batch_size <= 1 {
-            let task_id = *pending_tasks.keys().next()?;
-            return pending_tasks.remove(&task_id);
+        if policy != ReplayPolicy::RetryFailed {
+            return Ok(vec![]);
         }
 
-        let mut sorted_task_ids: Vec<_> = pending_tasks.keys().copied().collect();
-        sorted_task_ids.sort();
+        let mut failed = vec![];
+        if let Some(tasks) = self.tasks_index.get(&TaskState::Failed) {
+            for task_ptr in tasks.values() {
+                let mut task = lock_ptr!(task_ptr)?.clone();
+                task.state = TaskState::Pending;
+                task.completion_time = None;
+                task.version += 1;
+                failed.push(task);
+            }
+        }
 
-        for task_id in sorted_task_ids {
-            if (task_id as u32) % batch_size == batch_index {
-                return pending_tasks.remove(&task_id);
+        let mut task_ids = vec![];
+        for task in failed {
+            self.update_task(&task)?;
+            task_ids.push(task.id);
+        }
+        task_ids.sort();
+
+        Ok(task_ids)
+    }
+
+    /// Summarize the tasks of the session at `now`; the failures are not
+    /// included, as their messages are in the events of the tasks.
+    pub fn summary(
+        &self,
+        window: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<SessionSummary, FlameError> {
+        let mut summary = SessionSummary {
+            session_id: self.id.clone(),
+            window,
+            average_duration: (self.task_durations.count > 0).then_some(self.task_durations.mean),
+            ..Default::default()
+        };
+        for (state, tasks) in &self.tasks_index {
+            summary.tasks.insert(*state, tasks.len() as i32);
+        }
+
+        let since = now - window;
+        for task_ptr in self.tasks.values() {
+            let task = lock_ptr!(task_ptr)?;
+            let completed = matches!(task.state, TaskState::Succeed | TaskState::Failed);
+            if completed && task.completion_time.is_some_and(|t| t >= since) {
+                summary.completed += 1;
             }
         }
-        None
+
+        Ok(summary)
+    }
+
+    /// Pops a pending task of the batch; the tasks with their own priority
+    /// higher than `priority`, i.e. the one of the session, go first, and the
+    /// lower ones last.
+    pub fn pop_pending_task(
+        &mut self,
+        batch_index: u32,
+        batch_size: u32,
+        priority: u32,
+    ) -> Option<TaskPtr> {
+        let pending_tasks = self.tasks_index.get_mut(&TaskState::Pending)?;
+        let in_batch =
+            |id: &TaskID| batch_size <= 1 || (id.value() as u32) % batch_size == batch_index;
+        let not_lower = |id: &TaskID| self.task_priorities.get(id).is_none_or(|p| *p >= priority);
+
+        let prioritized = self
+            .task_priorities
+            .iter()
+            .filter(|(id, _)| in_batch(id))
+            .max_by_key(|(id, p)| (**p, std::cmp::Reverse(**id)))
+            .map(|(id, p)| (*id, *p));
+
+        let task_id = match prioritized {
+            Some((id, p)) if p > priority => id,
+            _ if batch_size <= 1 => pending_tasks
+                .keys()
+                .find(|id| not_lower(id))
+                .copied()
+                .or(prioritized.map(|(id, _)| id))?,
+            _ => {
+                let mut sorted_task_ids: Vec<_> = pending_tasks
+                    .keys()
+                    .filter(|id| in_batch(id) && not_lower(id))
+                    .copied()
+                    .collect();
+                sorted_task_ids.sort();
+                sorted_task_ids
+                    .first()
+                    .copied()
+                    .or(prioritized.map(|(id, _)| id))?
+            }
+        };
+
+        self.task_priorities.remove(&task_id);
+        pending_tasks.remove(&task_id)
     }
 
     pub fn validate_spec(&self, attr: &SessionAttributes) -> Result<(), FlameError> {
@@ -133,6 +243,42 @@ impl Session {
                 self.id, self.batch_size, attr.batch_size
             )));
         }
+        if self.scratch_size != attr.scratch_size {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: scratch_size differs (expected {:?}, got {:?})",
+                self.id, self.scratch_size, attr.scratch_size
+            )));
+        }
+        if self.max_task_attempts != attr.max_task_attempts {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: max_task_attempts differs (expected {:?}, got {:?})",
+                self.id, self.max_task_attempts, attr.max_task_attempts
+            )));
+        }
+        if self.task_timeout != attr.task_timeout {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: task_timeout differs (expected {:?}, got {:?})",
+                self.id, self.task_timeout, attr.task_timeout
+            )));
+        }
+        if self.task_priority != attr.task_priority {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: task_priority differs (expected {:?}, got {:?})",
+                self.id, self.task_priority, attr.task_priority
+            )));
+        }
+        if self.priority != attr.priority {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: priority differs (expected {}, got {})",
+                self.id, self.priority, attr.priority
+            )));
+        }
+        if self.result_policy != attr.result_policy {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> spec mismatch: result_policy differs (expected {}, got {})",
+                self.id, self.result_policy, attr.result_policy
+            )));
+        }
         Ok(())
     }
 }
@@ -154,6 +300,17 @@ impl Clone for Session {
             min_instances: self.min_instances,
             max_instances: self.max_instances,
             batch_size: self.batch_size,
+            scratch_size: self.scratch_size,
+            max_task_attempts: self.max_task_attempts,
+            task_timeout: self.task_timeout,
+            task_priority: self.task_priority,
+            task_priorities: HashMap::new(),
+            priority: self.priority,
+            result_policy: self.result_policy,
+            ttl_after_completion: self.ttl_after_completion,
+            task_durations: self.task_durations,
+            task_usage: self.task_usage.clone(),
+            trace_id: self.trace_id.clone(),
         };
 
         for (id, t) in &self.tasks {
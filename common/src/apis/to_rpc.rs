@@ -11,9 +11,12 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::HashMap;
+
 use rpc::flame::v1 as rpc;
 
 use super::types::*;
+use crate::ctx::NodeConfig;
 
 impl From<ResourceRequirement> for rpc::ResourceRequirement {
     fn from(req: ResourceRequirement) -> Self {
@@ -25,11 +28,58 @@ impl From<ResourceRequirement> for rpc::ResourceRequirement {
     }
 }
 
+impl From<&NodeConfig> for rpc::NodeConfig {
+    fn from(config: &NodeConfig) -> Self {
+        Self {
+            slots: config.slots,
+            labels: config.labels.clone(),
+            log_level: config.log_level.clone(),
+            cache_max_memory: config.cache_max_memory,
+            cache_max_objects: config.cache_max_objects.map(|n| n as u64),
+            prefetch: config.prefetch.clone(),
+        }
+    }
+}
+
 impl From<NodeInfo> for rpc::NodeInfo {
     fn from(info: NodeInfo) -> Self {
         Self {
             arch: info.arch,
             os: info.os,
+            numa_nodes: info
+                .numa_nodes
+                .into_iter()
+                .map(rpc::NumaNode::from)
+                .collect(),
+            gpu_groups: info
+                .gpu_groups
+                .into_iter()
+                .map(rpc::GpuGroup::from)
+                .collect(),
+            prefetched: info.prefetched,
+        }
+    }
+}
+
+impl From<NumaNode> for rpc::NumaNode {
+    fn from(numa: NumaNode) -> Self {
+        Self {
+            id: numa.id,
+            cpus: numa.cpus,
+        }
+    }
+}
+
+impl From<GpuGroup> for rpc::GpuGroup {
+    fn from(group: GpuGroup) -> Self {
+        let link = match group.link {
+            GpuLink::Pcie => rpc::GpuLink::Pcie,
+            GpuLink::NvLink => rpc::GpuLink::NvLink,
+        };
+        Self {
+            gpus: group.gpus,
+            numa_node: group.numa_node,
+            link: link.into(),
         }
     }
 }
@@ -40,6 +90,7 @@ impl From<NodeState> for rpc::NodeState {
             NodeState::Unknown => rpc::NodeState::Unknown,
             NodeState::Ready => rpc::NodeState::Ready,
             NodeState::NotReady => rpc::NodeState::NotReady,
+            NodeState::Lost => rpc::NodeState::Lost,
         }
     }
 }
@@ -50,6 +101,7 @@ impl From<NodeState> for i32 {
             NodeState::Unknown => 0,
             NodeState::Ready => 1,
             NodeState::NotReady => 2,
+            NodeState::Lost => 3,
         }
     }
 }
@@ -63,6 +115,10 @@ impl From<Node> for rpc::Node {
             info: Some(node.info.into()),
             addresses: vec![],
             last_heartbeat_time: 0,
+            saturation: None,
+            events: vec![],
+            executors: 0,
+            max_executors: None,
         });
 
         Self {
@@ -84,6 +140,16 @@ impl From<&Node> for rpc::Node {
     }
 }
 
+impl From<&JoinToken> for rpc::JoinToken {
+    fn from(token: &JoinToken) -> Self {
+        Self {
+            token: token.token.clone(),
+            node_name: token.node_name.clone(),
+            expiration_time: token.expiration_time.timestamp(),
+        }
+    }
+}
+
 impl From<Event> for rpc::Event {
     fn from(event: Event) -> Self {
         Self {
@@ -97,9 +163,14 @@ impl From<Event> for rpc::Event {
 impl From<TaskContext> for rpc::TaskContext {
     fn from(ctx: TaskContext) -> Self {
         Self {
-            task_id: ctx.task_id.clone(),
-            session_id: ctx.session_id.clone(),
+            task_id: ctx.task_id.to_string(),
+            session_id: ctx.session_id.into(),
             input: ctx.input.map(|d| d.into()),
+            environments: environments(&ctx.overrides.environments),
+            arguments: ctx.overrides.arguments.clone(),
+            checkpoint: ctx.checkpoint.clone(),
+            input_ref: ctx.input_ref.map(rpc::ObjectReference::from),
+            deadline: ctx.overrides.deadline,
         }
     }
 }
@@ -107,9 +178,11 @@ impl From<TaskContext> for rpc::TaskContext {
 impl From<SessionContext> for rpc::SessionContext {
     fn from(ctx: SessionContext) -> Self {
         Self {
-            session_id: ctx.session_id.clone(),
+            session_id: ctx.session_id.into(),
             application: Some(ctx.application.into()),
             common_data: ctx.common_data.map(|d| d.into()),
+            scratch_dir: ctx.scratch_dir.clone(),
+            scratch_size: ctx.scratch_size,
         }
     }
 }
@@ -127,6 +200,33 @@ impl From<ApplicationContext> for rpc::ApplicationContext {
     }
 }
 
+impl From<TaskUsage> for rpc::TaskUsage {
+    fn from(usage: TaskUsage) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+impl From<TaskPostmortem> for rpc::TaskPostmortem {
+    fn from(pm: TaskPostmortem) -> Self {
+        Self {
+            executor_id: pm.executor_id,
+            node: pm.node,
+            exit_code: pm.exit_code,
+            signal: pm.signal,
+            core_dumped: pm.core_dumped,
+            stderr_tail: pm.stderr_tail,
+            load_average: pm.load_average,
+            memory_available: pm.memory_available,
+            creation_time: pm.creation_time.timestamp(),
+        }
+    }
+}
+
 impl From<Task> for rpc::Task {
     fn from(task: Task) -> Self {
         rpc::Task::from(&task)
@@ -144,12 +244,33 @@ impl From<&Task> for rpc::Task {
             session_id: task.ssn_id.to_string(),
             input: task.input.clone().map(TaskInput::into),
             output: task.output.clone().map(TaskOutput::into),
+            environments: environments(&task.overrides.environments),
+            arguments: task.overrides.arguments.clone(),
+            artifacts: task
+                .artifacts
+                .iter()
+                .cloned()
+                .map(rpc::TaskArtifact::from)
+                .collect(),
+            input_ref: task.input_ref.clone().map(rpc::ObjectReference::from),
+            output_ref: task.output_ref.clone().map(rpc::ObjectReference::from),
+            timeout: task.overrides.timeout,
+            max_attempts: task.overrides.max_attempts,
+            priority: task.overrides.priority,
+            deadline: task.overrides.deadline,
+            codec: None,
         });
         let status = Some(rpc::TaskStatus {
             state: task.state as i32,
             creation_time: task.creation_time.timestamp(),
             completion_time: task.completion_time.map(|s| s.timestamp()),
             events: task.events.clone().into_iter().map(Event::into).collect(),
+            failure_reason: task.failure_reason.map(i32::from),
+            attempts: task.attempts,
+            usage: task.usage.map(rpc::TaskUsage::from),
+            postmortem: task.postmortem.clone().map(rpc::TaskPostmortem::from),
+            checkpoint: task.checkpoint.clone(),
+            trace_id: task.trace_id.clone(),
         });
         rpc::Task {
             metadata,
@@ -159,6 +280,18 @@ impl From<&Task> for rpc::Task {
     }
 }
 
+impl From<TaskDurationStats> for rpc::TaskDurationStats {
+    fn from(stats: TaskDurationStats) -> Self {
+        Self {
+            count: stats.count,
+            mean: stats.mean,
+            min: stats.min,
+            max: stats.max,
+            recent: stats.recent,
+        }
+    }
+}
+
 impl From<Session> for rpc::Session {
     fn from(ssn: Session) -> Self {
         rpc::Session::from(&ssn)
@@ -176,7 +309,11 @@ impl From<&Session> for rpc::Session {
             running: 0,
             succeed: 0,
             cancelled: 0,
+            quarantined: 0,
             events: ssn.events.clone().into_iter().map(Event::into).collect(),
+            task_durations: Some(ssn.task_durations.into()),
+            unschedulable: ssn.status.unschedulable.clone(),
+            trace_id: ssn.trace_id.clone(),
         };
         for (s, v) in &ssn.tasks_index {
             match s {
@@ -185,6 +322,7 @@ impl From<&Session> for rpc::Session {
                 TaskState::Succeed => status.succeed = v.len() as i32,
                 TaskState::Failed => status.failed = v.len() as i32,
                 TaskState::Cancelled => status.cancelled = v.len() as i32,
+                TaskState::Quarantined => status.quarantined = v.len() as i32,
             }
         }
 
@@ -200,6 +338,13 @@ impl From<&Session> for rpc::Session {
                 min_instances: ssn.min_instances,
                 max_instances: ssn.max_instances,
                 batch_size: ssn.batch_size,
+                scratch_size: ssn.scratch_size,
+                max_task_attempts: ssn.max_task_attempts,
+                task_timeout: ssn.task_timeout,
+                task_priority: ssn.task_priority,
+                priority: ssn.priority,
+                result_policy: ssn.result_policy.into(),
+                ttl_after_completion: ssn.ttl_after_completion,
             }),
             status: Some(status),
         }
@@ -216,6 +361,62 @@ impl From<ApplicationSchema> for rpc::ApplicationSchema {
     }
 }
 
+impl From<&SessionDefaults> for rpc::SessionDefaults {
+    fn from(defaults: &SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<&HealthProbe> for rpc::HealthProbe {
+    fn from(probe: &HealthProbe) -> Self {
+        Self {
+            command: probe.command.clone(),
+            grpc_service: probe.grpc_service.clone(),
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<&LifecycleHooks> for rpc::LifecycleHooks {
+    fn from(hooks: &LifecycleHooks) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.as_ref().map(rpc::LifecycleHook::from),
+            post_bind: hooks.post_bind.as_ref().map(rpc::LifecycleHook::from),
+            pre_release: hooks.pre_release.as_ref().map(rpc::LifecycleHook::from),
+        }
+    }
+}
+
+impl From<&LifecycleHook> for rpc::LifecycleHook {
+    fn from(hook: &LifecycleHook) -> Self {
+        Self {
+            command: hook.command.clone(),
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<&PythonEnvironment> for rpc::PythonEnvironment {
+    fn from(env: &PythonEnvironment) -> Self {
+        Self {
+            project: env.project.clone(),
+            requirements: env.requirements.clone(),
+            python_version: env.python_version.clone(),
+        }
+    }
+}
+
 impl From<Application> for rpc::Application {
     fn from(app: Application) -> Self {
         rpc::Application::from(&app)
@@ -231,17 +432,25 @@ impl From<&Application> for rpc::Application {
             labels: app.labels.clone(),
             command: app.command.clone(),
             arguments: app.arguments.to_vec(),
-            environments: app
-                .environments
-                .clone()
-                .into_iter()
-                .map(|(k, v)| rpc::Environment { name: k, value: v })
-                .collect(),
+            environments: environments(&app.environments),
             working_directory: app.working_directory.clone(),
             max_instances: Some(app.max_instances),
             delay_release: Some(app.delay_release.num_seconds()),
             schema: app.schema.clone().map(rpc::ApplicationSchema::from),
             url: app.url.clone(),
+            task_environments: app.task_environments.clone(),
+            task_arguments: app.task_arguments.clone(),
+            bundle_size: Some(app.bundle_size),
+            max_instances_per_node: app.max_instances_per_node,
+            session_defaults: (!app.session_defaults.is_empty())
+                .then(|| rpc::SessionDefaults::from(&app.session_defaults)),
+            health_probe: app.health_probe.as_ref().map(rpc::HealthProbe::from),
+            fairness: rpc::FairnessPolicy::from(app.fairness).into(),
+            hooks: (!app.hooks.is_empty()).then(|| rpc::LifecycleHooks::from(&app.hooks)),
+            python_env: app.python_env.as_ref().map(rpc::PythonEnvironment::from),
+            codec: rpc::PayloadCodec::from(app.codec).into(),
+            payload_type: app.payload_type.clone(),
+            topology: rpc::TopologyPolicy::from(app.topology).into(),
         });
         let metadata = Some(rpc::Metadata {
             id: app.name.clone(),
@@ -280,6 +489,7 @@ impl From<SessionState> for rpc::SessionState {
         match state {
             SessionState::Open => rpc::SessionState::Open,
             SessionState::Closed => rpc::SessionState::Closed,
+            SessionState::Closing => rpc::SessionState::Closing,
         }
     }
 }
@@ -290,6 +500,68 @@ impl From<SessionState> for i32 {
     }
 }
 
+impl From<ReplayPolicy> for rpc::ReplayPolicy {
+    fn from(policy: ReplayPolicy) -> Self {
+        match policy {
+            ReplayPolicy::KeepResults => rpc::ReplayPolicy::KeepResults,
+            ReplayPolicy::RetryFailed => rpc::ReplayPolicy::RetryFailed,
+        }
+    }
+}
+
+impl From<ReplayPolicy> for i32 {
+    fn from(p: ReplayPolicy) -> Self {
+        p as i32
+    }
+}
+
+impl From<ResultPolicy> for rpc::ResultPolicy {
+    fn from(policy: ResultPolicy) -> Self {
+        match policy {
+            ResultPolicy::Inline => rpc::ResultPolicy::Inline,
+            ResultPolicy::Discard => rpc::ResultPolicy::Discard,
+            ResultPolicy::ObjectCache => rpc::ResultPolicy::ObjectCache,
+        }
+    }
+}
+
+impl From<ResultPolicy> for i32 {
+    fn from(p: ResultPolicy) -> Self {
+        p as i32
+    }
+}
+
+impl From<EventOwnerKind> for rpc::EventOwnerKind {
+    fn from(kind: EventOwnerKind) -> Self {
+        match kind {
+            EventOwnerKind::Session => rpc::EventOwnerKind::EventOwnerSession,
+            EventOwnerKind::Task => rpc::EventOwnerKind::EventOwnerTask,
+        }
+    }
+}
+
+impl From<EventOwnerKind> for i32 {
+    fn from(kind: EventOwnerKind) -> Self {
+        kind as i32
+    }
+}
+
+impl From<&EventPage> for rpc::EventList {
+    fn from(page: &EventPage) -> Self {
+        Self {
+            events: page
+                .events
+                .iter()
+                .map(|e| rpc::SessionEvent {
+                    task_id: e.task_id.map(|id| id.to_string()),
+                    event: Some(rpc::Event::from(e.event.clone())),
+                })
+                .collect(),
+            next_page_token: page.next_page_token.clone(),
+        }
+    }
+}
+
 impl From<TaskState> for rpc::TaskState {
     fn from(state: TaskState) -> Self {
         match state {
@@ -298,10 +570,90 @@ impl From<TaskState> for rpc::TaskState {
             TaskState::Succeed => rpc::TaskState::Succeed,
             TaskState::Failed => rpc::TaskState::Failed,
             TaskState::Cancelled => rpc::TaskState::Cancelled,
+            TaskState::Quarantined => rpc::TaskState::Quarantined,
+        }
+    }
+}
+
+impl From<JobState> for rpc::JobState {
+    fn from(state: JobState) -> Self {
+        match state {
+            JobState::Running => rpc::JobState::JobRunning,
+            JobState::Succeed => rpc::JobState::JobSucceed,
+            JobState::Failed => rpc::JobState::JobFailed,
+            JobState::Cancelled => rpc::JobState::JobCancelled,
         }
     }
 }
 
+impl From<CompletionPolicy> for rpc::CompletionPolicy {
+    fn from(policy: CompletionPolicy) -> Self {
+        match policy {
+            CompletionPolicy::AllTasks => rpc::CompletionPolicy::AllTasks,
+            CompletionPolicy::AnyTask => rpc::CompletionPolicy::AnyTask,
+        }
+    }
+}
+
+impl From<&Job> for rpc::Job {
+    fn from(job: &Job) -> Self {
+        let attr = &job.attributes;
+        let (inputs, count) = match &attr.inputs {
+            JobInputs::List(inputs) => (inputs.iter().map(|i| i.to_vec()).collect(), None),
+            JobInputs::Count(count) => (vec![], Some(*count)),
+        };
+
+        rpc::Job {
+            metadata: Some(rpc::Metadata {
+                id: attr.id.to_string(),
+                name: attr.id.to_string(),
+            }),
+            spec: Some(rpc::JobSpec {
+                application: attr.application.clone(),
+                slots: attr.slots,
+                inputs,
+                count,
+                parallelism: attr.parallelism,
+                completion_policy: rpc::CompletionPolicy::from(attr.completion_policy) as i32,
+                max_failed: attr.max_failed,
+                common_data: attr.common_data.clone().map(CommonData::into),
+            }),
+            status: Some(rpc::JobStatus {
+                state: rpc::JobState::from(job.state) as i32,
+                creation_time: job.creation_time.timestamp(),
+                completion_time: job.completion_time.map(|t| t.timestamp()),
+                session_id: attr.id.to_string(),
+                total: job.total(),
+                submitted: job.submitted,
+                succeed: job.succeed,
+                failed: job.failed,
+                message: job.message.clone(),
+            }),
+        }
+    }
+}
+
+impl From<FailureReason> for rpc::FailureReason {
+    fn from(reason: FailureReason) -> Self {
+        match reason {
+            FailureReason::Unknown => rpc::FailureReason::UnknownReason,
+            FailureReason::ApplicationError => rpc::FailureReason::ApplicationError,
+            FailureReason::ShimCrash => rpc::FailureReason::ShimCrash,
+            FailureReason::Timeout => rpc::FailureReason::Timeout,
+            FailureReason::Preempted => rpc::FailureReason::Preempted,
+            FailureReason::NodeLost => rpc::FailureReason::NodeLost,
+            FailureReason::InputInvalid => rpc::FailureReason::InputInvalid,
+            FailureReason::DeadlineExceeded => rpc::FailureReason::DeadlineExceeded,
+        }
+    }
+}
+
+impl From<FailureReason> for i32 {
+    fn from(r: FailureReason) -> Self {
+        r as i32
+    }
+}
+
 impl From<TaskState> for i32 {
     fn from(s: TaskState) -> Self {
         s as i32
@@ -323,6 +675,55 @@ impl From<Shim> for i32 {
     }
 }
 
+impl From<FairnessPolicy> for rpc::FairnessPolicy {
+    fn from(p: FairnessPolicy) -> Self {
+        match p {
+            FairnessPolicy::RoundRobin => Self::RoundRobin,
+            FairnessPolicy::Proportional => Self::Proportional,
+        }
+    }
+}
+
+impl From<FairnessPolicy> for i32 {
+    fn from(p: FairnessPolicy) -> Self {
+        p as i32
+    }
+}
+
+impl From<PayloadCodec> for rpc::PayloadCodec {
+    fn from(c: PayloadCodec) -> Self {
+        match c {
+            PayloadCodec::Raw => Self::Raw,
+            PayloadCodec::Json => Self::Json,
+            PayloadCodec::Msgpack => Self::Msgpack,
+            PayloadCodec::Arrow => Self::Arrow,
+            PayloadCodec::Protobuf => Self::Protobuf,
+        }
+    }
+}
+
+impl From<PayloadCodec> for i32 {
+    fn from(c: PayloadCodec) -> Self {
+        c as i32
+    }
+}
+
+impl From<TopologyPolicy> for rpc::TopologyPolicy {
+    fn from(p: TopologyPolicy) -> Self {
+        match p {
+            TopologyPolicy::Ignore => Self::Ignore,
+            TopologyPolicy::NumaAligned => Self::NumaAligned,
+            TopologyPolicy::GpuAligned => Self::GpuAligned,
+        }
+    }
+}
+
+impl From<TopologyPolicy> for i32 {
+    fn from(p: TopologyPolicy) -> Self {
+        p as i32
+    }
+}
+
 impl From<ExecutorState> for rpc::ExecutorState {
     fn from(s: ExecutorState) -> Self {
         match s {
@@ -373,3 +774,92 @@ impl From<TaskGID> for EventOwner {
         Self::from(&gid)
     }
 }
+
+impl From<TaskArtifact> for rpc::TaskArtifact {
+    fn from(artifact: TaskArtifact) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(Into::into),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+impl From<ObjectReference> for rpc::ObjectReference {
+    fn from(object: ObjectReference) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
+        }
+    }
+}
+
+fn environments(envs: &HashMap<String, String>) -> Vec<rpc::Environment> {
+    envs.iter()
+        .map(|(k, v)| rpc::Environment {
+            name: k.clone(),
+            value: v.clone(),
+        })
+        .collect()
+}
+
+impl From<&SlotRecommendation> for rpc::SlotRecommendation {
+    fn from(r: &SlotRecommendation) -> Self {
+        Self {
+            application: r.application.clone(),
+            session_id: r.session_id.as_ref().map(|id| id.to_string()),
+            tasks: r.tasks,
+            cpu: r.cpu,
+            memory: r.memory,
+            slots: r.slots,
+            recommended_slots: r.recommended_slots,
+            message: r.message(),
+        }
+    }
+}
+
+impl From<&TaskSettings> for rpc::TaskSettings {
+    fn from(settings: &TaskSettings) -> Self {
+        Self {
+            session_id: settings.session_id.to_string(),
+            task_id: settings.task_id.map(|id| id.to_string()),
+            timeout: settings.timeout.value,
+            timeout_source: settings.timeout.source.to_string(),
+            max_attempts: settings.max_attempts.value,
+            max_attempts_source: settings.max_attempts.source.to_string(),
+            priority: settings.priority.value,
+            priority_source: settings.priority.source.to_string(),
+        }
+    }
+}
+
+impl From<&SessionSummary> for rpc::SessionSummary {
+    fn from(summary: &SessionSummary) -> Self {
+        let count = |state| summary.tasks.get(&state).copied().unwrap_or(0);
+
+        Self {
+            session_id: summary.session_id.to_string(),
+            pending: count(TaskState::Pending),
+            running: count(TaskState::Running),
+            succeed: count(TaskState::Succeed),
+            failed: count(TaskState::Failed),
+            cancelled: count(TaskState::Cancelled),
+            quarantined: count(TaskState::Quarantined),
+            window: summary.window.num_minutes() as u32,
+            completed: summary.completed,
+            throughput: summary.throughput(),
+            average_duration: summary.average_duration,
+            failures: summary
+                .failures
+                .iter()
+                .map(|f| rpc::TaskFailureSummary {
+                    category: f.category.clone(),
+                    count: f.count,
+                    message: f.message.clone(),
+                    reason: f.reason.map(i32::from),
+                })
+                .collect(),
+        }
+    }
+}
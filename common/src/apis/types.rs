@@ -11,7 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{env, fmt};
 
 use chrono::{DateTime, Duration, Utc};
@@ -19,11 +19,78 @@ use chrono::{DateTime, Duration, Utc};
 use rustix::system;
 use stdng::MutexPtr;
 
+use crate::apis::{SessionID, TaskID};
+use crate::FlameError;
+
 pub const DEFAULT_MAX_INSTANCES: u32 = 1_000_000;
 pub const DEFAULT_DELAY_RELEASE: Duration = Duration::seconds(60);
+pub const DEFAULT_BUNDLE_SIZE: u32 = 1;
+pub const DEFAULT_SLOTS: u32 = 1;
+pub const DEFAULT_BATCH_SIZE: u32 = 1;
+/// The default window in minutes of the throughput in the session summary.
+pub const DEFAULT_SUMMARY_WINDOW: u32 = 5;
+pub const DEFAULT_PROBE_PERIOD_SECONDS: u32 = 10;
+pub const DEFAULT_PROBE_TIMEOUT_SECONDS: u32 = 5;
+pub const DEFAULT_PROBE_FAILURE_THRESHOLD: u32 = 3;
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u32 = 60;
+/// The quantile of the tasks whose resource usage the recommended slots fit.
+pub const USAGE_RECOMMENDATION_QUANTILE: f64 = 0.95;
+/// The min number of measured tasks to recommend the slots.
+pub const MIN_USAGE_SAMPLES: u64 = 10;
+
+/// The task id of the session events, i.e. the events not owned by any task.
+pub const SESSION_EVENT_OWNER: TaskID = TaskID::new(0);
+/// The code of the event that the session is throttled by the instance limits.
+pub const SESSION_THROTTLED_EVENT: i32 = 100;
+/// The code of the event that the session is not throttled anymore.
+pub const SESSION_RESUMED_EVENT: i32 = 101;
+/// The code of the event that the session is closed because its client lease expired.
+pub const SESSION_LEASE_EXPIRED_EVENT: i32 = 102;
+/// The code of the event explaining why an executor was shuffled from or to the session.
+pub const SESSION_SHUFFLED_EVENT: i32 = 103;
+/// The code of the event that a scheduling latency of the session or task breached its SLO.
+pub const SLO_BREACHED_EVENT: i32 = 104;
+/// The code of the event that an instance of the session failed its health probe.
+pub const INSTANCE_UNHEALTHY_EVENT: i32 = 105;
+/// The code of the event with the output of a lifecycle hook of an instance of the session.
+pub const INSTANCE_HOOK_EVENT: i32 = 106;
+/// The code of the event that a lifecycle hook of an instance of the session failed.
+pub const INSTANCE_HOOK_FAILED_EVENT: i32 = 107;
+/// The code of the event that no ready node can run the application of the session.
+pub const SESSION_UNSCHEDULABLE_EVENT: i32 = 108;
+/// The code of the event that the session can be scheduled again.
+pub const SESSION_SCHEDULABLE_EVENT: i32 = 109;
+/// The code of the event that an executor is being bound to the session.
+pub const EXECUTOR_BINDING_EVENT: i32 = 110;
+/// The code of the event that an executor was bound to the session.
+pub const EXECUTOR_BOUND_EVENT: i32 = 111;
+/// The code of the event that an executor failed to be bound to the session.
+pub const EXECUTOR_BIND_FAILED_EVENT: i32 = 112;
+/// The code of the event that an executor was unbound from the session.
+pub const EXECUTOR_UNBOUND_EVENT: i32 = 113;
+/// The code of the event that the session was created, with the trace ID of the request.
+pub const SESSION_CREATED_EVENT: i32 = 114;
+/// The code of the event with the progress of the closing session, i.e. its running tasks left.
+pub const SESSION_CLOSING_EVENT: i32 = 115;
+/// The code of the event that the session was closed.
+pub const SESSION_CLOSED_EVENT: i32 = 116;
+/// The code of the event that the node is not ready, i.e. it missed its heartbeats.
+pub const NODE_NOT_READY_EVENT: i32 = 117;
+/// The code of the event that the node was lost, and its executors were released.
+pub const NODE_LOST_EVENT: i32 = 118;
+/// The code of the event that the node is ready again.
+pub const NODE_READY_EVENT: i32 = 119;
+/// The code of the event linking the session and the session which resubmitted its tasks.
+pub const SESSION_RESUBMITTED_EVENT: i32 = 120;
+/// The code of the event that the session of a job was closed because the job
+/// was lost by a restart of the session manager.
+pub const SESSION_JOB_LOST_EVENT: i32 = 121;
+
+/// The events of a page of the event queries by default.
+pub const DEFAULT_EVENT_PAGE_SIZE: u32 = 100;
+/// The max events of a page of the event queries.
+pub const MAX_EVENT_PAGE_SIZE: u32 = 1000;
 
-pub type SessionID = String;
-pub type TaskID = i64;
 pub type ExecutorID = String;
 pub type ApplicationID = String;
 pub type TaskPtr = MutexPtr<Task>;
@@ -42,6 +109,16 @@ pub struct EventOwner {
     pub session_id: SessionID,
 }
 
+impl EventOwner {
+    /// The owner of the events of the session itself.
+    pub fn session(session_id: SessionID) -> Self {
+        Self {
+            task_id: SESSION_EVENT_OWNER,
+            session_id,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Event {
     pub code: i32,
@@ -49,11 +126,225 @@ pub struct Event {
     pub creation_time: DateTime<Utc>,
 }
 
+/// The owner of the events, i.e. the session itself or its tasks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum EventOwnerKind {
+    Session = 0,
+    Task = 1,
+}
+
+/// The filter of the events of a session; all its events match the empty one.
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    /// The events created at or after the time.
+    pub since: Option<DateTime<Utc>>,
+    /// The events created before the time.
+    pub until: Option<DateTime<Utc>>,
+    /// The codes of the events; any code if empty.
+    pub codes: Vec<i32>,
+    pub owner: Option<EventOwnerKind>,
+    pub task_id: Option<TaskID>,
+}
+
+impl EventFilter {
+    /// Whether the event of the owner, i.e. a task or `SESSION_EVENT_OWNER`, matches.
+    pub fn matches(&self, task_id: TaskID, code: i32, creation_time: DateTime<Utc>) -> bool {
+        let owner = match task_id {
+            SESSION_EVENT_OWNER => EventOwnerKind::Session,
+            _ => EventOwnerKind::Task,
+        };
+
+        self.since.is_none_or(|since| creation_time >= since)
+            && self.until.is_none_or(|until| creation_time < until)
+            && (self.codes.is_empty() || self.codes.contains(&code))
+            && self.owner.is_none_or(|kind| kind == owner)
+            && self.task_id.is_none_or(|id| id == task_id)
+    }
+}
+
+/// An event of a session, or of one of its tasks.
+#[derive(Clone, Debug)]
+pub struct SessionEvent {
+    /// The task of the event, or None for the session itself.
+    pub task_id: Option<TaskID>,
+    pub event: Event,
+}
+
+impl SessionEvent {
+    pub fn new(task_id: TaskID, event: Event) -> Self {
+        Self {
+            task_id: (task_id != SESSION_EVENT_OWNER).then_some(task_id),
+            event,
+        }
+    }
+}
+
+/// A page of the events of a session; the next page is queried by its token.
+#[derive(Clone, Debug, Default)]
+pub struct EventPage {
+    pub events: Vec<SessionEvent>,
+    /// The token of the next page, or None if it's the last one.
+    pub next_page_token: Option<String>,
+}
+
+impl EventPage {
+    /// The page of the events from the token, i.e. the offset of its first
+    /// event; the events are ordered by their creation time and their tasks,
+    /// so the pages are stable while new events are recorded.
+    pub fn new(
+        mut events: Vec<SessionEvent>,
+        page_size: u32,
+        page_token: Option<&str>,
+    ) -> Result<Self, FlameError> {
+        let offset = match page_token {
+            Some(token) => token
+                .parse::<usize>()
+                .map_err(|_| FlameError::InvalidConfig(format!("invalid page token <{token}>")))?,
+            None => 0,
+        };
+        let page_size = match page_size {
+            0 => DEFAULT_EVENT_PAGE_SIZE,
+            n => n.min(MAX_EVENT_PAGE_SIZE),
+        } as usize;
+
+        // The sort is stable, so the events of a task keep their order.
+        events.sort_by_key(|e| (e.event.creation_time, e.task_id));
+        let end = offset.saturating_add(page_size).min(events.len());
+        let next_page_token = (end < events.len()).then(|| end.to_string());
+
+        Ok(Self {
+            events: events
+                .into_iter()
+                .skip(offset)
+                .take(end.saturating_sub(offset))
+                .collect(),
+            next_page_token,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TaskResult {
     pub state: TaskState,
     pub output: Option<TaskOutput>,
+    /// The output put into the object cache instead of the inline output, by
+    /// the result policy of the session.
+    pub output_ref: Option<ObjectReference>,
     pub message: Option<String>,
+    pub artifacts: Vec<TaskArtifact>,
+    /// The reason of the failure, only for the failed tasks.
+    pub failure_reason: Option<FailureReason>,
+    /// The resources used by the instance to run the task, if measured.
+    pub usage: Option<TaskUsage>,
+    /// The postmortem of the instance if it crashed while running the task.
+    pub postmortem: Option<TaskPostmortem>,
+    /// The codec which the output is encoded by, if tagged.
+    pub codec: Option<PayloadCodec>,
+}
+
+/// The resources used by the instance of an executor to run a task.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaskUsage {
+    /// The CPU time, i.e. user and system, in milliseconds.
+    pub cpu_time_ms: u64,
+    /// The peak resident memory in bytes.
+    pub max_rss_bytes: u64,
+    /// The bytes read from the storage.
+    pub read_bytes: u64,
+    /// The bytes written to the storage.
+    pub write_bytes: u64,
+}
+
+/// The postmortem of the instance of an executor which exited while running a
+/// task, e.g. it was killed by the OOM killer; it's kept with the task after
+/// the task is dispatched again.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskPostmortem {
+    pub executor_id: ExecutorID,
+    pub node: String,
+    /// The exit code of the instance, if it exited normally.
+    pub exit_code: Option<i32>,
+    /// The signal which killed the instance, if any.
+    pub signal: Option<i32>,
+    pub core_dumped: bool,
+    /// The tail of the stderr of the instance.
+    pub stderr_tail: Option<String>,
+    /// The 1-minute load average of the node when the instance exited.
+    pub load_average: Option<f64>,
+    /// The available memory in bytes of the node when the instance exited.
+    pub memory_available: Option<u64>,
+    pub creation_time: DateTime<Utc>,
+}
+
+/// A named output artifact of a task: either a small inline payload or a
+/// reference to an object in the object cache.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskArtifact {
+    pub name: String,
+    pub data: Option<bytes::Bytes>,
+    pub object_ref: Option<String>,
+}
+
+impl TaskArtifact {
+    /// Checks that the artifacts have unique, non-empty names and exactly one
+    /// of the inline data or the object reference.
+    pub fn validate_all(artifacts: &[TaskArtifact]) -> Result<(), FlameError> {
+        let mut names = HashSet::new();
+        for artifact in artifacts {
+            if artifact.name.is_empty() {
+                return Err(FlameError::InvalidConfig(
+                    "artifact name can not be empty".to_string(),
+                ));
+            }
+            if !names.insert(artifact.name.as_str()) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "duplicated artifact <{}>",
+                    artifact.name
+                )));
+            }
+            if artifact.data.is_some() == artifact.object_ref.is_some() {
+                return Err(FlameError::InvalidConfig(format!(
+                    "artifact <{}> must have either data or object_ref",
+                    artifact.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A reference to an object in the object cache, e.g. the input of a task which
+/// is too large to be transferred through the session manager.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectReference {
+    pub endpoint: String,
+    pub key: String,
+    /// The checksum of the object, e.g. `sha256:<hex>`, verified after fetching it.
+    pub checksum: Option<String>,
+}
+
+impl ObjectReference {
+    pub fn validate(&self) -> Result<(), FlameError> {
+        if self.endpoint.is_empty() || self.key.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "object reference must have endpoint and key".to_string(),
+            ));
+        }
+        if let Some(checksum) = &self.checksum {
+            match checksum.split_once(':') {
+                Some((algorithm, digest)) if !algorithm.is_empty() && !digest.is_empty() => {}
+                _ => {
+                    return Err(FlameError::InvalidConfig(format!(
+                    "invalid checksum <{checksum}> of object <{}>, it must be <algorithm>:<digest>",
+                    self.key
+                )))
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
@@ -70,6 +361,72 @@ pub enum Shim {
     Wasm = 1,
 }
 
+/// How the instances of an application are shared among its sessions when
+/// they compete for the `max_instances` of the application.
+#[derive(Clone, Debug, Default, Copy, PartialEq, Eq, Hash, strum_macros::Display)]
+pub enum FairnessPolicy {
+    /// The sessions take turns, i.e. each one gets an equal share.
+    #[default]
+    RoundRobin = 0,
+    /// Each session gets a share proportional to its pending tasks.
+    Proportional = 1,
+}
+
+/// The codec of the payloads of an application, i.e. the inputs and outputs
+/// of its tasks, by which the SDKs encode and decode them.
+#[derive(Clone, Debug, Default, Copy, PartialEq, Eq, Hash, strum_macros::Display)]
+pub enum PayloadCodec {
+    /// Opaque bytes, which are not validated.
+    #[default]
+    Raw = 0,
+    Json = 1,
+    Msgpack = 2,
+    /// Arrow IPC streams.
+    Arrow = 3,
+    /// Protobuf messages of the `payload_type` of the application.
+    Protobuf = 4,
+}
+
+/// How the instances of an application are placed within the GPU and NUMA
+/// topology of the nodes, for the performance-sensitive applications.
+#[derive(Clone, Debug, Default, Copy, PartialEq, Eq, Hash, strum_macros::Display)]
+pub enum TopologyPolicy {
+    /// The topology of the nodes is ignored.
+    #[default]
+    Ignore = 0,
+    /// Each instance takes a NUMA node of its node.
+    NumaAligned = 1,
+    /// Each instance takes a GPU group, and the CPUs of its NUMA node.
+    GpuAligned = 2,
+}
+
+impl PayloadCodec {
+    /// Checks the payload tagged by the codec against this codec; the payload
+    /// without tag is taken as of this codec. Only the JSON documents are
+    /// validated by their content, the others by their tags.
+    pub fn validate(
+        &self,
+        tag: Option<PayloadCodec>,
+        payload: Option<&[u8]>,
+    ) -> Result<(), FlameError> {
+        if *self == PayloadCodec::Raw {
+            return Ok(());
+        }
+        if let Some(tag) = tag.filter(|tag| tag != self) {
+            return Err(FlameError::InvalidConfig(format!(
+                "payload of codec <{tag}> is not of codec <{self}>"
+            )));
+        }
+        if let (PayloadCodec::Json, Some(payload)) = (self, payload) {
+            serde_json::from_slice::<serde_json::Value>(payload).map_err(|e| {
+                FlameError::InvalidConfig(format!("payload is not a JSON document: {e}"))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ApplicationSchema {
     pub input: Option<String>,
@@ -77,6 +434,227 @@ pub struct ApplicationSchema {
     pub common_data: Option<String>,
 }
 
+/// The defaults of the sessions of an application; they're applied at session
+/// creation to the attributes that the session leaves unspecified.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionDefaults {
+    pub slots: Option<u32>,
+    pub min_instances: Option<u32>,
+    pub max_instances: Option<u32>,
+    pub batch_size: Option<u32>,
+    pub scratch_size: Option<u64>,
+    pub max_task_attempts: Option<u32>,
+    pub task_timeout: Option<u64>,
+    pub task_priority: Option<u32>,
+}
+
+impl SessionDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks the defaults are valid for any session, e.g. non-zero slots.
+    pub fn validate(&self) -> Result<(), FlameError> {
+        for (name, value) in [
+            ("slots", self.slots),
+            ("batch_size", self.batch_size),
+            ("max_task_attempts", self.max_task_attempts),
+        ] {
+            if value == Some(0) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "session default <{name}> must be greater than 0"
+                )));
+            }
+        }
+        if self.task_timeout == Some(0) {
+            return Err(FlameError::InvalidConfig(
+                "session default <task_timeout> must be greater than 0".to_string(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (self.min_instances, self.max_instances) {
+            if min > max {
+                return Err(FlameError::InvalidConfig(format!(
+                    "session default <min_instances> ({min}) is greater than <max_instances> ({max})"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The health probe of the instances of an application, which is run by the
+/// executor manager periodically against the bound instances; the instance is
+/// unhealthy if the probe fails `failure_threshold` times in a row.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HealthProbe {
+    /// The command run by `sh -c` in the working directory of the instance;
+    /// the probe succeeds if it exits with 0.
+    pub command: Option<String>,
+    /// The service of the standard gRPC health check of the instance, or the
+    /// empty string for the instance itself; the probe succeeds if it's serving.
+    pub grpc_service: Option<String>,
+    pub period_seconds: Option<u32>,
+    pub timeout_seconds: Option<u32>,
+    pub failure_threshold: Option<u32>,
+}
+
+impl HealthProbe {
+    pub fn period(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.period_seconds.unwrap_or(DEFAULT_PROBE_PERIOD_SECONDS) as u64
+        )
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.timeout_seconds
+                .unwrap_or(DEFAULT_PROBE_TIMEOUT_SECONDS) as u64,
+        )
+    }
+
+    pub fn failure_threshold(&self) -> u32 {
+        self.failure_threshold
+            .unwrap_or(DEFAULT_PROBE_FAILURE_THRESHOLD)
+    }
+
+    /// Checks the probe has exactly one of the command and the gRPC check,
+    /// and non-zero period, timeout and threshold.
+    pub fn validate(&self) -> Result<(), FlameError> {
+        if self.command.is_some() == self.grpc_service.is_some() {
+            return Err(FlameError::InvalidConfig(
+                "health probe must have exactly one of <command> and <grpc_service>".to_string(),
+            ));
+        }
+        if self
+            .command
+            .as_deref()
+            .is_some_and(|cmd| cmd.trim().is_empty())
+        {
+            return Err(FlameError::InvalidConfig(
+                "health probe <command> must not be empty".to_string(),
+            ));
+        }
+        for (name, value) in [
+            ("period_seconds", self.period_seconds),
+            ("timeout_seconds", self.timeout_seconds),
+            ("failure_threshold", self.failure_threshold),
+        ] {
+            if value == Some(0) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "health probe <{name}> must be greater than 0"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A lifecycle hook of the instances of an application, i.e. a command run by
+/// `sh -c` in the working directory of the instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifecycleHook {
+    pub command: String,
+    pub timeout_seconds: Option<u32>,
+}
+
+impl LifecycleHook {
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.timeout_seconds.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS) as u64
+        )
+    }
+
+    fn validate(&self, name: &str) -> Result<(), FlameError> {
+        if self.command.trim().is_empty() {
+            return Err(FlameError::InvalidConfig(format!(
+                "hook <{name}> must have a command"
+            )));
+        }
+        if self.timeout_seconds == Some(0) {
+            return Err(FlameError::InvalidConfig(format!(
+                "hook <{name}> timeout_seconds must be greater than 0"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// The lifecycle hooks of the instances of an application, which are run by
+/// the executor manager on the node around the startup and the stop of the
+/// instance, e.g. mounting datasets, warming caches or fetching licenses.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LifecycleHooks {
+    /// Run before the instance starts; the executor fails to bind if it fails.
+    pub pre_bind: Option<LifecycleHook>,
+    /// Run after the instance entered the session.
+    pub post_bind: Option<LifecycleHook>,
+    /// Run before the instance leaves the session and stops.
+    pub pre_release: Option<LifecycleHook>,
+}
+
+impl LifecycleHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_bind.is_none() && self.post_bind.is_none() && self.pre_release.is_none()
+    }
+
+    /// Checks each hook has a command and a non-zero timeout.
+    pub fn validate(&self) -> Result<(), FlameError> {
+        for (name, hook) in [
+            ("pre_bind", &self.pre_bind),
+            ("post_bind", &self.post_bind),
+            ("pre_release", &self.pre_release),
+        ] {
+            if let Some(hook) = hook {
+                hook.validate(name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The Python environment of the host instances of an application, defined by
+/// either its uv project or its requirements file on the node; the relative
+/// paths are resolved against the working directory of the instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PythonEnvironment {
+    /// The path of the uv project, i.e. the pyproject.toml or its directory.
+    pub project: Option<String>,
+    /// The path of the requirements file.
+    pub requirements: Option<String>,
+    /// The Python version of the environment, e.g. "3.12".
+    pub python_version: Option<String>,
+}
+
+impl PythonEnvironment {
+    /// Checks exactly one of the project and the requirements is set.
+    pub fn validate(&self) -> Result<(), FlameError> {
+        let is_set = |path: &Option<String>| path.as_ref().is_some_and(|p| !p.trim().is_empty());
+        match (is_set(&self.project), is_set(&self.requirements)) {
+            (true, false) | (false, true) => {}
+            _ => {
+                return Err(FlameError::InvalidConfig(
+                    "python_env must have either a project or a requirements".to_string(),
+                ))
+            }
+        }
+        if self
+            .python_version
+            .as_ref()
+            .is_some_and(|v| v.trim().is_empty())
+        {
+            return Err(FlameError::InvalidConfig(
+                "python_env python_version must not be empty".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Application {
     pub name: String,
@@ -95,6 +673,66 @@ pub struct Application {
     pub delay_release: Duration,
     pub schema: Option<ApplicationSchema>,
     pub url: Option<String>,
+    /// The environment variables that tasks are allowed to override.
+    pub task_environments: Vec<String>,
+    /// The arguments (by name, e.g. `--level`) that tasks are allowed to pass.
+    pub task_arguments: Vec<String>,
+    /// The max number of tasks handed to an executor in one launch.
+    pub bundle_size: u32,
+    /// The max number of instances on each node; unlimited if None.
+    pub max_instances_per_node: Option<u32>,
+    /// The defaults of the sessions of the application.
+    pub session_defaults: SessionDefaults,
+    /// The health probe of the instances of the application, if any.
+    pub health_probe: Option<HealthProbe>,
+    /// How the instances are shared among the sessions of the application.
+    pub fairness: FairnessPolicy,
+    /// The lifecycle hooks of the instances of the application.
+    pub hooks: LifecycleHooks,
+    /// The Python environment of the host instances of the application, if any.
+    pub python_env: Option<PythonEnvironment>,
+    /// The codec of the inputs and outputs of the tasks.
+    pub codec: PayloadCodec,
+    /// The full name of the protobuf message of the payloads, for the
+    /// `Protobuf` codec.
+    pub payload_type: Option<String>,
+    /// How the instances are placed within the topology of the nodes.
+    pub topology: TopologyPolicy,
+}
+
+impl Application {
+    /// Checks the overrides of a task against the allowlists of the application.
+    pub fn validate_task_overrides(&self, overrides: &TaskOverrides) -> Result<(), FlameError> {
+        if overrides.timeout == Some(0)
+            || overrides.max_attempts == Some(0)
+            || overrides.deadline == Some(0)
+        {
+            return Err(FlameError::InvalidConfig(
+                "task must have non-zero timeout, max_attempts and deadline".to_string(),
+            ));
+        }
+
+        for name in overrides.environments.keys() {
+            if !self.task_environments.contains(name) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "environment <{name}> is not allowed to be overridden by tasks of application <{}>",
+                    self.name
+                )));
+            }
+        }
+
+        for arg in &overrides.arguments {
+            let name = arg.split_once('=').map_or(arg.as_str(), |(name, _)| name);
+            if !self.task_arguments.iter().any(|a| a == name) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "argument <{name}> is not allowed to be passed by tasks of application <{}>",
+                    self.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -111,6 +749,31 @@ pub struct ApplicationAttributes {
     pub delay_release: Duration,
     pub schema: Option<ApplicationSchema>,
     pub url: Option<String>,
+    /// The environment variables that tasks are allowed to override.
+    pub task_environments: Vec<String>,
+    /// The arguments (by name, e.g. `--level`) that tasks are allowed to pass.
+    pub task_arguments: Vec<String>,
+    /// The max number of tasks handed to an executor in one launch.
+    pub bundle_size: u32,
+    /// The max number of instances on each node; unlimited if None.
+    pub max_instances_per_node: Option<u32>,
+    /// The defaults of the sessions of the application.
+    pub session_defaults: SessionDefaults,
+    /// The health probe of the instances of the application, if any.
+    pub health_probe: Option<HealthProbe>,
+    /// How the instances are shared among the sessions of the application.
+    pub fairness: FairnessPolicy,
+    /// The lifecycle hooks of the instances of the application.
+    pub hooks: LifecycleHooks,
+    /// The Python environment of the host instances of the application, if any.
+    pub python_env: Option<PythonEnvironment>,
+    /// The codec of the inputs and outputs of the tasks.
+    pub codec: PayloadCodec,
+    /// The full name of the protobuf message of the payloads, for the
+    /// `Protobuf` codec.
+    pub payload_type: Option<String>,
+    /// How the instances are placed within the topology of the nodes.
+    pub topology: TopologyPolicy,
 }
 
 impl Default for ApplicationAttributes {
@@ -128,6 +791,33 @@ impl Default for ApplicationAttributes {
             delay_release: DEFAULT_DELAY_RELEASE,
             schema: Some(ApplicationSchema::default()),
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: DEFAULT_BUNDLE_SIZE,
+            max_instances_per_node: None,
+            session_defaults: SessionDefaults::default(),
+            health_probe: None,
+            fairness: FairnessPolicy::default(),
+            hooks: LifecycleHooks::default(),
+            python_env: None,
+            codec: PayloadCodec::default(),
+            payload_type: None,
+            topology: TopologyPolicy::default(),
+        }
+    }
+}
+
+impl ApplicationAttributes {
+    /// Checks that the payload type is set for, and only for, the `Protobuf` codec.
+    pub fn validate_codec(&self) -> Result<(), FlameError> {
+        match (self.codec, &self.payload_type) {
+            (PayloadCodec::Protobuf, None) => Err(FlameError::InvalidConfig(
+                "payload_type is required by the Protobuf codec".to_string(),
+            )),
+            (PayloadCodec::Protobuf, Some(_)) | (_, None) => Ok(()),
+            (codec, Some(_)) => Err(FlameError::InvalidConfig(format!(
+                "payload_type is not supported by the {codec} codec"
+            ))),
         }
     }
 }
@@ -141,19 +831,96 @@ pub struct SessionAttributes {
     pub min_instances: u32,
     pub max_instances: Option<u32>,
     pub batch_size: u32,
+    /// The size limit in bytes of the shared scratch directory on each node;
+    /// no scratch directory is created if None.
+    pub scratch_size: Option<u64>,
+    /// The attempts of a task before it's quarantined; the settings of the
+    /// application, then of the cluster, if None.
+    pub max_task_attempts: Option<u32>,
+    /// The timeout in seconds of running a task; the settings of the
+    /// application, then of the cluster, if None.
+    pub task_timeout: Option<u64>,
+    /// The priority of the tasks; the settings of the application, then of
+    /// the cluster, if None.
+    pub task_priority: Option<u32>,
+    /// The priority of the session in scheduling, the higher the earlier.
+    pub priority: u32,
+    /// What happens to the outputs of the completed tasks.
+    pub result_policy: ResultPolicy,
+    /// The seconds to keep the session after it's closed, then it's deleted
+    /// with its tasks and events; it's kept forever if None.
+    pub ttl_after_completion: Option<u64>,
+    /// The trace ID of the request which created the session, if any.
+    pub trace_id: Option<String>,
 }
 
 impl Default for SessionAttributes {
     fn default() -> Self {
         Self {
-            id: String::new(),
+            id: SessionID::default(),
             application: String::new(),
-            slots: 1,
+            slots: DEFAULT_SLOTS,
             common_data: None,
             min_instances: 0,
             max_instances: None,
-            batch_size: 1,
+            batch_size: DEFAULT_BATCH_SIZE,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::default(),
+            ttl_after_completion: None,
+            trace_id: None,
+        }
+    }
+}
+
+impl SessionAttributes {
+    /// Applies the defaults of the application to the attributes left
+    /// unspecified, i.e. zero or None, then the defaults of Flame; and checks
+    /// the resulting attributes at admission. The settings of the tasks, e.g.
+    /// `max_task_attempts`, are left to be resolved when they're used, so the
+    /// updated settings of the application and the cluster apply.
+    pub fn with_defaults(mut self, defaults: &SessionDefaults) -> Result<Self, FlameError> {
+        if self.slots == 0 {
+            self.slots = defaults.slots.unwrap_or(DEFAULT_SLOTS);
         }
+        if self.min_instances == 0 {
+            self.min_instances = defaults.min_instances.unwrap_or_default();
+        }
+        if self.max_instances.is_none() {
+            self.max_instances = defaults.max_instances;
+        }
+        if self.batch_size == 0 {
+            self.batch_size = defaults.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+        }
+        if self.scratch_size.is_none() {
+            self.scratch_size = defaults.scratch_size;
+        }
+
+        if self.slots == 0 || self.batch_size == 0 {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> must have non-zero slots and batch_size",
+                self.id
+            )));
+        }
+        if self.max_task_attempts == Some(0) || self.task_timeout == Some(0) {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> must have non-zero max_task_attempts and task_timeout",
+                self.id
+            )));
+        }
+        if let Some(max) = self.max_instances {
+            if self.min_instances > max {
+                return Err(FlameError::InvalidConfig(format!(
+                    "session <{}> has min_instances ({}) greater than max_instances ({max})",
+                    self.id, self.min_instances
+                )));
+            }
+        }
+
+        Ok(self)
     }
 }
 
@@ -162,11 +929,305 @@ pub enum SessionState {
     #[default]
     Open = 0,
     Closed = 1,
+    /// No new tasks are accepted, and the running tasks are drained before
+    /// the session is closed.
+    Closing = 2,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct SessionStatus {
     pub state: SessionState,
+    /// The reason why no ready node can run the application of the session,
+    /// e.g. its binary is missing on all of them; `None` if it's schedulable.
+    pub unschedulable: Option<String>,
+}
+
+/// The policy of the tasks when a closed session is reopened.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum ReplayPolicy {
+    /// Keep the results of the completed tasks, and only accept new tasks.
+    #[default]
+    KeepResults = 0,
+    /// Reset the failed tasks to pending to run them again.
+    RetryFailed = 1,
+}
+
+/// What happens to the outputs of the completed tasks of a session.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum ResultPolicy {
+    /// Keep the outputs in the storage of the session manager.
+    #[default]
+    Inline = 0,
+    /// Deliver the outputs to the watchers of the tasks without persisting
+    /// them, e.g. for the high-volume workloads whose outputs are consumed
+    /// once.
+    Discard = 1,
+    /// Put the outputs into the object cache by the executor manager, and keep
+    /// their references instead.
+    ObjectCache = 2,
+}
+
+/// The number of completed tasks before the duration statistics are used to adapt dispatching.
+pub const MIN_TASK_DURATION_SAMPLES: u64 = 8;
+/// The weight of the latest task in the moving average of the task durations.
+const TASK_DURATION_EWMA_ALPHA: f64 = 0.2;
+
+/// The statistics of the durations of the completed tasks in a session, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TaskDurationStats {
+    pub count: u64,
+    pub mean: f64,
+    pub min: u64,
+    pub max: u64,
+    /// The exponentially weighted moving average, which follows the recent tasks.
+    pub recent: f64,
+}
+
+impl TaskDurationStats {
+    pub fn observe(&mut self, duration: Duration) {
+        let ms = duration.num_milliseconds().max(0) as u64;
+
+        if self.count == 0 {
+            self.min = ms;
+            self.max = ms;
+            self.recent = ms as f64;
+        } else {
+            self.min = self.min.min(ms);
+            self.max = self.max.max(ms);
+            self.recent += TASK_DURATION_EWMA_ALPHA * (ms as f64 - self.recent);
+        }
+        self.count += 1;
+        self.mean += (ms as f64 - self.mean) / self.count as f64;
+    }
+
+    /// Returns the expected duration of the next tasks, or None if there are not
+    /// enough completed tasks yet.
+    pub fn expected(&self) -> Option<f64> {
+        (self.count >= MIN_TASK_DURATION_SAMPLES).then_some(self.recent)
+    }
+}
+
+/// The aggregated statistics of the tasks in a session.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SessionSummary {
+    pub session_id: SessionID,
+    /// The number of tasks in each state.
+    pub tasks: HashMap<TaskState, i32>,
+    /// The window of the throughput.
+    pub window: Duration,
+    /// The tasks completed within the window.
+    pub completed: u64,
+    /// The average duration in milliseconds of the completed tasks.
+    pub average_duration: Option<f64>,
+    /// The failed tasks by category, the most frequent first.
+    pub failures: Vec<TaskFailureSummary>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskFailureSummary {
+    pub category: String,
+    pub count: u64,
+    /// The message of the first failure of the category.
+    pub message: Option<String>,
+    pub reason: Option<FailureReason>,
+}
+
+impl SessionSummary {
+    /// The completed tasks per minute within the window.
+    pub fn throughput(&self) -> f64 {
+        let minutes = self.window.num_seconds() as f64 / 60.0;
+        if minutes > 0.0 {
+            self.completed as f64 / minutes
+        } else {
+            0.0
+        }
+    }
+
+    /// Count a failed task in the category of its message; the infrastructure
+    /// failures are categorized by their reasons instead.
+    pub fn add_failure(&mut self, reason: Option<FailureReason>, message: Option<&str>) {
+        let category = match reason {
+            Some(reason) if reason.is_infrastructure() => reason.to_string(),
+            _ => failure_category(message),
+        };
+        match self
+            .failures
+            .iter_mut()
+            .find(|f| f.reason == reason && f.category == category)
+        {
+            Some(failure) => failure.count += 1,
+            None => self.failures.push(TaskFailureSummary {
+                category,
+                count: 1,
+                message: message.map(String::from),
+                reason,
+            }),
+        }
+
+        self.failures.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.category.cmp(&b.category))
+        });
+    }
+}
+
+/// The granularity of the CPUs in the usage statistics in milli-CPUs, i.e.
+/// 1/10 of a CPU.
+const USAGE_CPU_STEP: u64 = 100;
+/// The granularity of the memory in the usage statistics, i.e. 1MiB.
+const USAGE_MEMORY_STEP: u64 = 1024 * 1024;
+
+/// The resource usage of the measured tasks, for the right-sizing of the slots;
+/// the usages are rounded up to their granularity, so the statistics stay small.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskUsageStats {
+    pub count: u64,
+    /// The number of tasks by their average CPUs, in the steps of `USAGE_CPU_STEP`.
+    cpu: BTreeMap<u64, u64>,
+    /// The number of tasks by their peak memory, in the steps of `USAGE_MEMORY_STEP`.
+    memory: BTreeMap<u64, u64>,
+}
+
+impl TaskUsageStats {
+    /// Observes the usage of a task run in the duration; the average CPUs are
+    /// its CPU time over the duration.
+    pub fn observe(&mut self, usage: &TaskUsage, duration: Duration) {
+        let ms = duration.num_milliseconds().max(1) as u64;
+        let milli_cpus = usage.cpu_time_ms.saturating_mul(1000) / ms;
+
+        *self
+            .cpu
+            .entry(milli_cpus.div_ceil(USAGE_CPU_STEP))
+            .or_default() += 1;
+        *self
+            .memory
+            .entry(usage.max_rss_bytes.div_ceil(USAGE_MEMORY_STEP))
+            .or_default() += 1;
+        self.count += 1;
+    }
+
+    pub fn merge(&mut self, other: &TaskUsageStats) {
+        for (step, n) in &other.cpu {
+            *self.cpu.entry(*step).or_default() += n;
+        }
+        for (step, n) in &other.memory {
+            *self.memory.entry(*step).or_default() += n;
+        }
+        self.count += other.count;
+    }
+
+    /// The CPUs and the memory in bytes that the quantile of the tasks used at
+    /// most, or None if no task was measured.
+    pub fn quantile(&self, q: f64) -> Option<(f64, u64)> {
+        let rank = ((self.count as f64 * q).ceil() as u64).max(1);
+        let find = |steps: &BTreeMap<u64, u64>| {
+            let mut seen = 0;
+            steps.iter().find_map(|(step, n)| {
+                seen += n;
+                (seen >= rank).then_some(*step)
+            })
+        };
+
+        let cpu = find(&self.cpu)?;
+        let memory = find(&self.memory)?;
+        Some((
+            (cpu * USAGE_CPU_STEP) as f64 / 1000.0,
+            memory * USAGE_MEMORY_STEP,
+        ))
+    }
+}
+
+/// The slots recommended to an application or a session by the resource usage
+/// of its tasks.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SlotRecommendation {
+    pub application: ApplicationID,
+    /// The session, or None for all the sessions of the application.
+    pub session_id: Option<SessionID>,
+    /// The number of measured tasks.
+    pub tasks: u64,
+    /// The CPUs used at most by the quantile of the tasks.
+    pub cpu: f64,
+    /// The memory in bytes used at most by the quantile of the tasks.
+    pub memory: u64,
+    /// The current slots.
+    pub slots: u32,
+    pub recommended_slots: u32,
+}
+
+impl SlotRecommendation {
+    /// Recommends the min slots fitting the usage of `USAGE_RECOMMENDATION_QUANTILE`
+    /// of the tasks, or None if there are not enough measured tasks.
+    pub fn new(
+        application: ApplicationID,
+        session_id: Option<SessionID>,
+        slots: u32,
+        stats: &TaskUsageStats,
+        unit: &ResourceRequirement,
+    ) -> Option<Self> {
+        if stats.count < MIN_USAGE_SAMPLES {
+            return None;
+        }
+        let (cpu, memory) = stats.quantile(USAGE_RECOMMENDATION_QUANTILE)?;
+
+        // A zero dimension of the slot unit does not constrain the slots.
+        let cpu_slots = match unit.cpu {
+            0 => 1,
+            n => (cpu / n as f64).ceil() as u32,
+        };
+        let memory_slots = match unit.memory {
+            0 => 1,
+            n => memory.div_ceil(n) as u32,
+        };
+
+        Some(Self {
+            application,
+            session_id,
+            tasks: stats.count,
+            cpu,
+            memory,
+            slots,
+            recommended_slots: cpu_slots.max(memory_slots).max(1),
+        })
+    }
+
+    /// The recommendation for the humans, e.g. `95% of tasks used <=1.5 CPUs
+    /// and <=512MiB memory; consider slots=2`.
+    pub fn message(&self) -> String {
+        let usage = format!(
+            "{}% of tasks used <={} CPUs and <={}MiB memory",
+            (USAGE_RECOMMENDATION_QUANTILE * 100.0).round(),
+            self.cpu,
+            self.memory / USAGE_MEMORY_STEP
+        );
+        if self.recommended_slots == self.slots {
+            format!("{usage}; slots={} fits", self.slots)
+        } else {
+            format!("{usage}; consider slots={}", self.recommended_slots)
+        }
+    }
+}
+
+const MAX_FAILURE_CATEGORY_LEN: usize = 64;
+
+/// The category of a failure by its message, i.e. the text before the first colon
+/// of the first line, e.g. `ValueError` of `ValueError: invalid input`; the last
+/// line is used for the Python tracebacks.
+pub fn failure_category(message: Option<&str>) -> String {
+    let mut lines = message
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty());
+    let line = match lines.next() {
+        Some(l) if l.starts_with("Traceback") => lines.next_back().unwrap_or(l),
+        Some(l) => l,
+        None => return "Unknown".to_string(),
+    };
+
+    let category = line.split(':').next().unwrap_or(line).trim();
+    category.chars().take(MAX_FAILURE_CATEGORY_LEN).collect()
 }
 
 #[derive(Debug, Default)]
@@ -185,6 +1246,24 @@ pub struct Session {
     pub min_instances: u32,
     pub max_instances: Option<u32>,
     pub batch_size: u32,
+    pub scratch_size: Option<u64>,
+    /// The settings of the tasks of the session, see `SessionAttributes`.
+    pub max_task_attempts: Option<u32>,
+    pub task_timeout: Option<u64>,
+    pub task_priority: Option<u32>,
+    /// The priorities of the pending tasks with their own priority.
+    pub task_priorities: HashMap<TaskID, u32>,
+    /// The priority of the session in scheduling, the higher the earlier.
+    pub priority: u32,
+    /// What happens to the outputs of the completed tasks.
+    pub result_policy: ResultPolicy,
+    /// The seconds to keep the session after it's closed, see `SessionAttributes`.
+    pub ttl_after_completion: Option<u64>,
+    pub task_durations: TaskDurationStats,
+    /// The resource usage of the measured tasks.
+    pub task_usage: TaskUsageStats,
+    /// The trace ID of the request which created the session, if any.
+    pub trace_id: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
@@ -195,6 +1274,8 @@ pub enum TaskState {
     Succeed = 2,
     Failed = 3,
     Cancelled = 4,
+    /// The task lost its executors too many times, and waits to be released.
+    Quarantined = 5,
 }
 
 impl TaskState {
@@ -203,37 +1284,157 @@ impl TaskState {
     }
 }
 
+/// The reason of a task failure.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum FailureReason {
+    #[default]
+    Unknown = 0,
+    ApplicationError = 1,
+    ShimCrash = 2,
+    Timeout = 3,
+    Preempted = 4,
+    NodeLost = 5,
+    InputInvalid = 6,
+    DeadlineExceeded = 7,
+}
+
+impl FailureReason {
+    /// Whether the failure is caused by the infrastructure instead of the
+    /// application, i.e. the task may succeed if it runs again.
+    pub fn is_infrastructure(&self) -> bool {
+        matches!(
+            self,
+            Self::ShimCrash | Self::Timeout | Self::Preempted | Self::NodeLost
+        )
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TaskGID {
     pub ssn_id: SessionID,
     pub task_id: TaskID,
 }
 
+/// The environment variables and arguments of a task, which are injected by
+/// the shim for that invocation only; and the settings of the task, which
+/// override the ones of its session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskOverrides {
+    pub environments: HashMap<String, String>,
+    pub arguments: Vec<String>,
+    /// The timeout in seconds of running the task; it's the effective timeout
+    /// of the task when it's launched to the executor.
+    pub timeout: Option<u64>,
+    pub max_attempts: Option<u32>,
+    pub priority: Option<u32>,
+    /// The deadline in milliseconds of the task since its creation; it's the
+    /// remaining budget of the task when it's launched to the executor.
+    pub deadline: Option<u64>,
+}
+
+impl TaskOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The attributes of a new task in the session.
+#[derive(Clone, Debug, Default)]
+pub struct TaskAttributes {
+    pub input: Option<TaskInput>,
+    /// The input in the object cache instead of the inline input.
+    pub input_ref: Option<ObjectReference>,
+    pub overrides: TaskOverrides,
+    /// The ID of the request which created the task.
+    pub trace_id: Option<String>,
+}
+
+/// The level of the settings hierarchy which a setting of the tasks is
+/// resolved from, the later ones overriding the earlier ones.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SettingSource {
+    #[default]
+    Cluster,
+    Application,
+    Session,
+    Task,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+/// The effective settings of a task, or of the tasks of a session without
+/// their own settings.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TaskSettings {
+    pub session_id: SessionID,
+    pub task_id: Option<TaskID>,
+    /// The timeout in seconds of running the task; no timeout if None.
+    pub timeout: Setting<Option<u64>>,
+    /// The attempts of the task before it's quarantined.
+    pub max_attempts: Setting<u32>,
+    /// The priority of the task among the pending tasks of its session.
+    pub priority: Setting<u32>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Task {
     pub id: TaskID,
     pub ssn_id: SessionID,
     pub version: u32,
     pub input: Option<TaskInput>,
+    /// The input in the object cache instead of the inline input, if any.
+    pub input_ref: Option<ObjectReference>,
     pub output: Option<TaskOutput>,
+    /// The output in the object cache instead of the inline output, if any.
+    pub output_ref: Option<ObjectReference>,
+    pub artifacts: Vec<TaskArtifact>,
+    pub overrides: TaskOverrides,
     pub creation_time: DateTime<Utc>,
     pub completion_time: Option<DateTime<Utc>>,
     pub events: Vec<Event>,
     pub state: TaskState,
+    /// The reason of the failure, only for the failed tasks.
+    pub failure_reason: Option<FailureReason>,
+    /// The times the task was dispatched again after losing its executor.
+    pub attempts: u32,
+    /// The resources used to run the task, if measured.
+    pub usage: Option<TaskUsage>,
+    /// The latest checkpoint of the task in the object cache, which the next
+    /// invocation resumes from after a retry.
+    pub checkpoint: Option<String>,
+    /// The postmortem of the latest crash of the instance running the task.
+    pub postmortem: Option<TaskPostmortem>,
+    /// The trace ID of the request which created the task, if any.
+    pub trace_id: Option<String>,
 }
 
 impl Default for Task {
     fn default() -> Self {
         Self {
-            id: 0,
-            ssn_id: String::new(),
+            id: TaskID::default(),
+            ssn_id: SessionID::default(),
             version: 0,
             input: None,
+            input_ref: None,
             output: None,
+            output_ref: None,
+            artifacts: Vec::new(),
+            overrides: TaskOverrides::default(),
             creation_time: Utc::now(),
             completion_time: None,
             events: Vec::new(),
             state: TaskState::default(),
+            failure_reason: None,
+            attempts: 0,
+            usage: None,
+            checkpoint: None,
+            postmortem: None,
+            trace_id: None,
         }
     }
 }
@@ -251,6 +1452,128 @@ impl Task {
     }
 }
 
+/// The state of a job, i.e. of the tasks it runs in its session.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum JobState {
+    #[default]
+    Running = 0,
+    Succeed = 1,
+    Failed = 2,
+    Cancelled = 3,
+}
+
+impl JobState {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Running)
+    }
+}
+
+/// When a job succeeds: all its tasks completed, or any of them succeeded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
+pub enum CompletionPolicy {
+    #[default]
+    AllTasks = 0,
+    AnyTask = 1,
+}
+
+/// The inputs of the tasks of a job: the given inputs, or a generator of
+/// `count` tasks whose input is their index, e.g. "0", "1".
+#[derive(Clone, Debug)]
+pub enum JobInputs {
+    List(Vec<TaskInput>),
+    Count(u32),
+}
+
+impl JobInputs {
+    /// The number of the tasks of the job.
+    pub fn len(&self) -> u32 {
+        match self {
+            Self::List(inputs) => inputs.len() as u32,
+            Self::Count(count) => *count,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The input of the task at the index.
+    pub fn get(&self, index: u32) -> Option<TaskInput> {
+        match self {
+            Self::List(inputs) => inputs.get(index as usize).cloned(),
+            Self::Count(count) if index < *count => Some(TaskInput::from(index.to_string())),
+            Self::Count(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct JobAttributes {
+    /// The ID of the job, which is also the ID of its session.
+    pub id: SessionID,
+    pub application: String,
+    pub slots: u32,
+    pub inputs: JobInputs,
+    /// The max tasks of the job pending or running at the same time; no limit if None.
+    pub parallelism: Option<u32>,
+    pub completion_policy: CompletionPolicy,
+    /// The failed tasks tolerated before the job fails.
+    pub max_failed: u32,
+    pub common_data: Option<CommonData>,
+}
+
+impl JobAttributes {
+    pub fn validate(&self) -> Result<(), FlameError> {
+        if self.inputs.is_empty() {
+            return Err(FlameError::InvalidConfig("job has no task".to_string()));
+        }
+        if self.parallelism == Some(0) {
+            return Err(FlameError::InvalidConfig(
+                "parallelism must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub attributes: JobAttributes,
+    pub state: JobState,
+    pub creation_time: DateTime<Utc>,
+    pub completion_time: Option<DateTime<Utc>>,
+    /// The tasks submitted to the session, i.e. the index of the next input.
+    pub submitted: u32,
+    pub succeed: u32,
+    /// The failed tasks, including the cancelled ones.
+    pub failed: u32,
+    /// Why the job failed or was cancelled.
+    pub message: Option<String>,
+}
+
+impl Job {
+    pub fn new(attributes: JobAttributes) -> Self {
+        Self {
+            attributes,
+            state: JobState::Running,
+            creation_time: Utc::now(),
+            completion_time: None,
+            submitted: 0,
+            succeed: 0,
+            failed: 0,
+            message: None,
+        }
+    }
+
+    pub fn id(&self) -> &SessionID {
+        &self.attributes.id
+    }
+
+    pub fn total(&self) -> u32 {
+        self.attributes.inputs.len()
+    }
+}
+
 #[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash, strum_macros::Display)]
 pub enum ExecutorState {
     #[default]
@@ -266,17 +1589,30 @@ pub enum ExecutorState {
 
 #[derive(Clone, Debug)]
 pub struct TaskContext {
-    pub task_id: String,
-    pub session_id: String,
+    pub task_id: TaskID,
+    pub session_id: SessionID,
     pub input: Option<TaskInput>,
+    /// The input in the object cache, which the instance fetches by itself.
+    pub input_ref: Option<ObjectReference>,
+    pub overrides: TaskOverrides,
+    /// The latest checkpoint of the task to resume from, if any.
+    pub checkpoint: Option<String>,
+    /// The trace ID of the request which created the task, if any.
+    pub trace_id: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 pub struct SessionContext {
-    pub session_id: String,
+    pub session_id: SessionID,
     pub application: ApplicationContext,
     pub slots: u32,
     pub common_data: Option<CommonData>,
+    /// The size limit in bytes of the shared scratch directory, if requested.
+    pub scratch_size: Option<u64>,
+    /// The shared scratch directory on this node, set by the executor manager.
+    pub scratch_dir: Option<String>,
+    /// What happens to the outputs of the completed tasks.
+    pub result_policy: ResultPolicy,
 }
 
 #[derive(Clone, Debug)]
@@ -289,6 +1625,16 @@ pub struct ApplicationContext {
     pub working_directory: Option<String>,
     pub environments: HashMap<String, String>,
     pub url: Option<String>,
+    /// The health probe of the instances of the application, if any.
+    pub health_probe: Option<HealthProbe>,
+    /// The lifecycle hooks of the instances of the application.
+    pub hooks: LifecycleHooks,
+    /// The Python environment of the host instances of the application, if any.
+    pub python_env: Option<PythonEnvironment>,
+    /// The max number of instances on each node; unlimited if None.
+    pub max_instances_per_node: Option<u32>,
+    /// How the instances are placed within the topology of the node.
+    pub topology: TopologyPolicy,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, strum_macros::Display)]
@@ -296,13 +1642,54 @@ pub enum NodeState {
     #[default]
     Unknown = 0,
     Ready = 1,
+    /// No heartbeat within the NotReady threshold; no executor is allocated on it.
     NotReady = 2,
+    /// No heartbeat within the Lost threshold; its executors are released, and
+    /// their tasks are requeued.
+    Lost = 3,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct NodeInfo {
     pub arch: String,
     pub os: String,
+    /// The NUMA nodes of the node, detected by the executor manager at start.
+    pub numa_nodes: Vec<NumaNode>,
+    /// The GPU groups of the node, detected by the executor manager at start.
+    pub gpu_groups: Vec<GpuGroup>,
+    /// The applications whose artifacts are prefetched on the node.
+    pub prefetched: Vec<String>,
+}
+
+/// A NUMA node of a node, with its CPUs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumaNode {
+    pub id: u32,
+    /// The CPUs as a cpulist, e.g. "0-15,32-47".
+    pub cpus: String,
+}
+
+/// How the GPUs of a group are connected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, strum_macros::Display)]
+pub enum GpuLink {
+    /// Under the same PCIe switch or host bridge.
+    #[default]
+    #[strum(serialize = "PCIe")]
+    Pcie = 0,
+    #[strum(serialize = "NVLink")]
+    NvLink = 1,
+}
+
+/// A group of GPUs connected by NVLink or the same PCIe switch, so the
+/// instances in the group avoid the contention across the sockets and the
+/// PCIe switches.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GpuGroup {
+    /// The indexes of the GPUs, e.g. for `CUDA_VISIBLE_DEVICES`.
+    pub gpus: Vec<u32>,
+    /// The NUMA node which the GPUs are attached to, if known.
+    pub numa_node: Option<u32>,
+    pub link: GpuLink,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -320,6 +1707,15 @@ pub struct Node {
     pub state: NodeState,
 }
 
+/// A one-time token for a node to join the cluster.
+#[derive(Clone, Debug)]
+pub struct JoinToken {
+    pub token: String,
+    /// The node which joins with the token; any node if not set.
+    pub node_name: Option<String>,
+    pub expiration_time: DateTime<Utc>,
+}
+
 #[cfg(not(target_os = "linux"))]
 fn uname() -> String {
     String::from("unknown-node")
@@ -340,6 +1736,62 @@ fn totalram() -> u64 {
     system::sysinfo().totalram
 }
 
+#[cfg(not(target_os = "linux"))]
+fn memory_limit() -> Option<u64> {
+    None
+}
+
+/// Returns the memory limit of the cgroup (v2 or v1) the process runs in, if any.
+#[cfg(target_os = "linux")]
+fn memory_limit() -> Option<u64> {
+    [
+        "/sys/fs/cgroup/memory.max",
+        "/sys/fs/cgroup/memory/memory.limit_in_bytes",
+    ]
+    .iter()
+    .find_map(|path| std::fs::read_to_string(path).ok())
+    .and_then(|limit| parse_cgroup_limit(&limit))
+}
+
+#[cfg(any(target_os = "linux", test))]
+pub(crate) fn parse_cgroup_limit(limit: &str) -> Option<u64> {
+    match limit.trim() {
+        "max" => None,
+        limit => limit.parse().ok(),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_limit() -> Option<u64> {
+    None
+}
+
+/// Returns the CPU quota of the cgroup (v2 or v1) the process runs in, in
+/// CPUs rounded up, if any.
+#[cfg(target_os = "linux")]
+fn cpu_limit() -> Option<u64> {
+    if let Ok(max) = std::fs::read_to_string("/sys/fs/cgroup/cpu.max") {
+        return parse_cgroup_cpu_max(&max);
+    }
+
+    let quota = std::fs::read_to_string("/sys/fs/cgroup/cpu/cpu.cfs_quota_us").ok()?;
+    let period = std::fs::read_to_string("/sys/fs/cgroup/cpu/cpu.cfs_period_us").ok()?;
+    parse_cgroup_cpu_max(&format!("{} {}", quota.trim(), period.trim()))
+}
+
+/// Parses the quota and the period of the CPU of the cgroup, e.g. `200000 100000`
+/// of `cpu.max`, into CPUs; no quota if `max`, or `-1` of cgroup v1.
+#[cfg(any(target_os = "linux", test))]
+pub(crate) fn parse_cgroup_cpu_max(max: &str) -> Option<u64> {
+    let mut parts = max.split_whitespace();
+    let quota: i64 = parts.next()?.parse().ok()?;
+    let period: i64 = parts.next()?.parse().ok()?;
+    if quota <= 0 || period <= 0 {
+        return None;
+    }
+    Some((quota as u64).div_ceil(period as u64))
+}
+
 impl Node {
     pub fn new() -> Self {
         let name = uname();
@@ -352,18 +1804,25 @@ impl Node {
         node
     }
 
+    /// Re-detects the resources of the node; the CPU quota (`cpu.max`) and the
+    /// memory limit (`memory.max`) of the container (cgroup) are respected, so
+    /// the capacity follows hot-plug and limit changes. The topology of the node is kept, which is detected
+    /// by the executor manager once.
     pub fn refresh(&mut self) {
-        let memory = totalram();
-        let cpu = num_cpus::get() as u64;
+        let memory = match memory_limit() {
+            Some(limit) => totalram().min(limit),
+            None => totalram(),
+        };
+        let cpu = match cpu_limit() {
+            Some(limit) => (num_cpus::get() as u64).min(limit),
+            None => num_cpus::get() as u64,
+        };
         let capacity = ResourceRequirement { cpu, memory };
         let allocatable = capacity.clone();
-        let info = NodeInfo {
-            arch: env::consts::ARCH.to_string(),
-            os: env::consts::OS.to_string(),
-        };
         self.capacity = capacity;
         self.allocatable = allocatable;
-        self.info = info;
+        self.info.arch = env::consts::ARCH.to_string();
+        self.info.os = env::consts::OS.to_string();
     }
 }
 
@@ -404,14 +1863,26 @@ impl ResourceRequirement {
     }
 
     pub fn to_slots(&self, unit: &ResourceRequirement) -> u32 {
-        (self.cpu / unit.cpu).min(self.memory / unit.memory) as u32
+        // A zero dimension in the slot unit does not constrain the slots.
+        let cpu_slots = self.cpu.checked_div(unit.cpu).unwrap_or(u64::MAX);
+        let memory_slots = self.memory.checked_div(unit.memory).unwrap_or(u64::MAX);
+        cpu_slots.min(memory_slots).min(u32::MAX as u64) as u32
+    }
+
+    pub fn saturating_sub(&self, other: &ResourceRequirement) -> Self {
+        Self {
+            cpu: self.cpu.saturating_sub(other.cpu),
+            memory: self.memory.saturating_sub(other.memory),
+        }
     }
 
     pub(crate) fn parse_memory(s: &str) -> u64 {
+        let s = s.to_lowercase();
+        // Binary suffixes, e.g. "4Gi" or "4GiB", are treated the same as "4g".
+        let s = s.trim_end_matches("ib").trim_end_matches('i');
         if s.is_empty() {
             return 0;
         }
-        let s = s.to_lowercase();
         let v = s[..s.len() - 1].parse::<u64>().unwrap_or(0);
         let unit = s[s.len() - 1..].to_string();
         match unit.as_str() {
@@ -0,0 +1,404 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::Duration;
+use serde_derive::Deserialize;
+
+use crate::apis::{
+    ApplicationAttributes, ApplicationSchema, FairnessPolicy, HealthProbe, LifecycleHook,
+    LifecycleHooks, PayloadCodec, PythonEnvironment, SessionDefaults, Shim, TopologyPolicy,
+};
+use crate::FlameError;
+
+// The application YAML of `flmctl register`, e.g.
+//
+// metadata:
+//   name: pi
+// spec:
+//   command: /opt/pi-server
+//   max_instances: 10
+
+#[derive(Debug, Clone, Deserialize)]
+struct ApplicationYaml {
+    metadata: MetadataYaml,
+    spec: SpecYaml,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MetadataYaml {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SpecYaml {
+    shim: Option<String>,
+    image: Option<String>,
+    description: Option<String>,
+    labels: Option<Vec<String>>,
+    command: Option<String>,
+    arguments: Option<Vec<String>>,
+    environments: Option<HashMap<String, String>>,
+    working_directory: Option<String>,
+    max_instances: Option<u32>,
+    /// Delay in seconds before releasing the idle instances
+    delay_release: Option<i64>,
+    schema: Option<SchemaYaml>,
+    url: Option<String>,
+    task_environments: Option<Vec<String>>,
+    task_arguments: Option<Vec<String>>,
+    bundle_size: Option<u32>,
+    max_instances_per_node: Option<u32>,
+    session_defaults: Option<SessionDefaultsYaml>,
+    health_probe: Option<HealthProbeYaml>,
+    fairness: Option<String>,
+    hooks: Option<LifecycleHooksYaml>,
+    python_env: Option<PythonEnvironmentYaml>,
+    codec: Option<String>,
+    payload_type: Option<String>,
+    topology: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SchemaYaml {
+    input: Option<String>,
+    output: Option<String>,
+    common_data: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SessionDefaultsYaml {
+    slots: Option<u32>,
+    min_instances: Option<u32>,
+    max_instances: Option<u32>,
+    batch_size: Option<u32>,
+    scratch_size: Option<u64>,
+    max_task_attempts: Option<u32>,
+    task_timeout: Option<u64>,
+    task_priority: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct HealthProbeYaml {
+    command: Option<String>,
+    grpc_service: Option<String>,
+    period_seconds: Option<u32>,
+    timeout_seconds: Option<u32>,
+    failure_threshold: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LifecycleHooksYaml {
+    pre_bind: Option<LifecycleHookYaml>,
+    post_bind: Option<LifecycleHookYaml>,
+    pre_release: Option<LifecycleHookYaml>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LifecycleHookYaml {
+    command: String,
+    timeout_seconds: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PythonEnvironmentYaml {
+    project: Option<String>,
+    requirements: Option<String>,
+    python_version: Option<String>,
+}
+
+impl From<LifecycleHookYaml> for LifecycleHook {
+    fn from(hook: LifecycleHookYaml) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl TryFrom<SpecYaml> for ApplicationAttributes {
+    type Error = FlameError;
+
+    fn try_from(spec: SpecYaml) -> Result<Self, Self::Error> {
+        let defaults = ApplicationAttributes::default();
+        let shim = match spec.shim.as_deref() {
+            Some("Host") | Some("host") | None => Shim::Host,
+            Some("Wasm") | Some("wasm") | Some("WASM") => Shim::Wasm,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "invalid shim <{other}>, must be 'Host' or 'Wasm'"
+                )))
+            }
+        };
+
+        let fairness = match spec.fairness.as_deref() {
+            Some("RoundRobin") | Some("round-robin") | None => FairnessPolicy::RoundRobin,
+            Some("Proportional") | Some("proportional") => FairnessPolicy::Proportional,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "invalid fairness <{other}>, must be 'RoundRobin' or 'Proportional'"
+                )))
+            }
+        };
+
+        let codec = match spec.codec.as_deref() {
+            Some("Raw") | Some("raw") | None => PayloadCodec::Raw,
+            Some("Json") | Some("json") => PayloadCodec::Json,
+            Some("Msgpack") | Some("msgpack") => PayloadCodec::Msgpack,
+            Some("Arrow") | Some("arrow") => PayloadCodec::Arrow,
+            Some("Protobuf") | Some("protobuf") => PayloadCodec::Protobuf,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                "invalid codec <{other}>, must be 'Raw', 'Json', 'Msgpack', 'Arrow' or 'Protobuf'"
+            )))
+            }
+        };
+
+        let topology = match spec.topology.as_deref() {
+            Some("Ignore") | Some("ignore") | None => TopologyPolicy::Ignore,
+            Some("NumaAligned") | Some("numa-aligned") => TopologyPolicy::NumaAligned,
+            Some("GpuAligned") | Some("gpu-aligned") => TopologyPolicy::GpuAligned,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "invalid topology <{other}>, must be 'Ignore', 'NumaAligned' or 'GpuAligned'"
+                )))
+            }
+        };
+
+        Ok(Self {
+            shim,
+            image: spec.image,
+            description: spec.description,
+            labels: spec.labels.unwrap_or_default(),
+            command: spec.command,
+            arguments: spec.arguments.unwrap_or_default(),
+            environments: spec.environments.unwrap_or_default(),
+            working_directory: spec.working_directory,
+            max_instances: spec.max_instances.unwrap_or(defaults.max_instances),
+            delay_release: spec
+                .delay_release
+                .map(Duration::seconds)
+                .unwrap_or(defaults.delay_release),
+            schema: spec
+                .schema
+                .map(|schema| ApplicationSchema {
+                    input: schema.input,
+                    output: schema.output,
+                    common_data: schema.common_data,
+                })
+                .or(defaults.schema),
+            url: spec.url,
+            task_environments: spec.task_environments.unwrap_or_default(),
+            task_arguments: spec.task_arguments.unwrap_or_default(),
+            bundle_size: spec.bundle_size.unwrap_or(defaults.bundle_size),
+            max_instances_per_node: spec.max_instances_per_node,
+            session_defaults: spec
+                .session_defaults
+                .map(|d| SessionDefaults {
+                    slots: d.slots,
+                    min_instances: d.min_instances,
+                    max_instances: d.max_instances,
+                    batch_size: d.batch_size,
+                    scratch_size: d.scratch_size,
+                    max_task_attempts: d.max_task_attempts,
+                    task_timeout: d.task_timeout,
+                    task_priority: d.task_priority,
+                })
+                .unwrap_or_default(),
+            health_probe: spec.health_probe.map(|p| HealthProbe {
+                command: p.command,
+                grpc_service: p.grpc_service,
+                period_seconds: p.period_seconds,
+                timeout_seconds: p.timeout_seconds,
+                failure_threshold: p.failure_threshold,
+            }),
+            fairness,
+            hooks: spec
+                .hooks
+                .map(|h| LifecycleHooks {
+                    pre_bind: h.pre_bind.map(LifecycleHook::from),
+                    post_bind: h.post_bind.map(LifecycleHook::from),
+                    pre_release: h.pre_release.map(LifecycleHook::from),
+                })
+                .unwrap_or_default(),
+            python_env: spec.python_env.map(|e| PythonEnvironment {
+                project: e.project,
+                requirements: e.requirements,
+                python_version: e.python_version,
+            }),
+            codec,
+            payload_type: spec.payload_type,
+            topology,
+        })
+    }
+}
+
+/// Loads the applications from the `.yaml`/`.yml` files in the directory, in the
+/// order of the file names; a file may have several applications separated by `---`.
+/// Nothing is loaded if the directory does not exist.
+pub fn load_applications(dir: &Path) -> Result<Vec<(String, ApplicationAttributes)>, FlameError> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = fs::read_dir(dir)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to read <{}>: {e}", dir.display())))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && matches!(
+                    p.extension().and_then(|ext| ext.to_str()),
+                    Some("yaml") | Some("yml")
+                )
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut apps: Vec<(String, ApplicationAttributes)> = vec![];
+    for file in files {
+        let contents = fs::read_to_string(&file).map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to read <{}>: {e}", file.display()))
+        })?;
+        for doc in contents
+            .split("\n---\n")
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            let app: ApplicationYaml = serde_yaml::from_str(doc).map_err(|e| {
+                FlameError::InvalidConfig(format!("invalid application <{}>: {e}", file.display()))
+            })?;
+            let name = app.metadata.name;
+            if apps.iter().any(|(n, _)| n == &name) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "application <{name}> is defined more than once in <{}>",
+                    dir.display()
+                )));
+            }
+            let attr = ApplicationAttributes::try_from(app.spec).map_err(|e| {
+                FlameError::InvalidConfig(format!(
+                    "application <{name}> in <{}>: {e}",
+                    file.display()
+                ))
+            })?;
+            attr.session_defaults.validate()?;
+            if let Some(probe) = &attr.health_probe {
+                probe.validate()?;
+            }
+            attr.hooks.validate()?;
+            if let Some(env) = &attr.python_env {
+                env.validate()?;
+            }
+
+            apps.push((name, attr));
+        }
+    }
+
+    Ok(apps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_applications() -> Result<(), FlameError> {
+        let tmp_dir = TempDir::new().unwrap();
+        assert!(load_applications(&tmp_dir.path().join("missing"))?.is_empty());
+
+        fs::write(
+            tmp_dir.path().join("10-pi.yaml"),
+            r#"---
+metadata:
+  name: pi
+spec:
+  command: /opt/pi-server
+  max_instances: 10
+  delay_release: 5
+---
+metadata:
+  name: matrix
+spec:
+  command: /opt/matrix-server
+  fairness: Proportional
+  codec: Json
+  topology: GpuAligned
+  session_defaults:
+    slots: 2
+  health_probe:
+    command: curl -sf http://localhost:8080/healthz
+    period_seconds: 30
+  hooks:
+    pre_bind:
+      command: mount-dataset /data/matrix
+      timeout_seconds: 300
+    pre_release:
+      command: umount /data/matrix
+  python_env:
+    requirements: requirements.txt
+    python_version: "3.12"
+"#,
+        )
+        .unwrap();
+        fs::write(tmp_dir.path().join("README.md"), "not an application").unwrap();
+
+        let apps = load_applications(tmp_dir.path())?;
+        assert_eq!(apps.len(), 2);
+        let (name, pi) = &apps[0];
+        assert_eq!(name, "pi");
+        assert_eq!(pi.command.as_deref(), Some("/opt/pi-server"));
+        assert_eq!(pi.max_instances, 10);
+        assert_eq!(pi.delay_release, Duration::seconds(5));
+        let (name, matrix) = &apps[1];
+        assert_eq!(name, "matrix");
+        assert_eq!(matrix.session_defaults.slots, Some(2));
+        assert_eq!(matrix.fairness, FairnessPolicy::Proportional);
+        assert_eq!(pi.fairness, FairnessPolicy::RoundRobin);
+        assert_eq!(matrix.codec, PayloadCodec::Json);
+        assert_eq!(pi.codec, PayloadCodec::Raw);
+        assert_eq!(matrix.topology, TopologyPolicy::GpuAligned);
+        assert_eq!(pi.topology, TopologyPolicy::Ignore);
+        let probe = matrix.health_probe.as_ref().unwrap();
+        assert_eq!(probe.period(), std::time::Duration::from_secs(30));
+        assert_eq!(probe.failure_threshold(), 3);
+        assert!(pi.health_probe.is_none());
+        let pre_bind = matrix.hooks.pre_bind.as_ref().unwrap();
+        assert_eq!(pre_bind.timeout(), std::time::Duration::from_secs(300));
+        assert_eq!(
+            matrix.hooks.pre_release.as_ref().unwrap().timeout(),
+            std::time::Duration::from_secs(60)
+        );
+        assert!(matrix.hooks.post_bind.is_none());
+        assert!(pi.hooks.is_empty());
+        let python_env = matrix.python_env.as_ref().unwrap();
+        assert_eq!(python_env.requirements.as_deref(), Some("requirements.txt"));
+        assert_eq!(python_env.python_version.as_deref(), Some("3.12"));
+        assert!(pi.python_env.is_none());
+        assert_eq!(
+            matrix.bundle_size,
+            ApplicationAttributes::default().bundle_size
+        );
+
+        fs::write(
+            tmp_dir.path().join("20-pi.yml"),
+            "metadata:\n  name: pi\nspec:\n  command: /opt/pi\n",
+        )
+        .unwrap();
+        assert!(load_applications(tmp_dir.path()).is_err());
+
+        Ok(())
+    }
+}
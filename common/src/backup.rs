@@ -0,0 +1,289 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The layout of the storage backups, shared by the session manager which
+//! takes them and `flmadm restore` which replays them.
+//!
+//! Each backup is a directory named by its creation time under the backup
+//! root, e.g. `flame-20250101T000000Z`, with a manifest and the engine data:
+//! the database file of sqlite, or the data directory of filesystem.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::FlameError;
+
+/// The file name of the backup manifest.
+pub const BACKUP_MANIFEST: &str = "manifest.yaml";
+/// The file or directory name of the engine data in a backup.
+pub const BACKUP_DATA: &str = "data";
+
+const BACKUP_PREFIX: &str = "flame-";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// The version of the storage layout in the backups.
+pub const BACKUP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The kind of storage engine of a backup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum BackupEngine {
+    Sqlite,
+    Filesystem,
+}
+
+/// The location of the engine data of a storage URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageLocation {
+    pub engine: BackupEngine,
+    /// The database file of sqlite, or the base directory of filesystem.
+    pub path: PathBuf,
+}
+
+impl StorageLocation {
+    /// Parse the storage URL, the relative paths are resolved against `home`,
+    /// i.e. `FLAME_HOME`; the `none` storage has nothing to back up.
+    pub fn from_url(url: &str, home: &Path) -> Result<Self, FlameError> {
+        let (engine, path) = if url == "none" {
+            return Err(FlameError::InvalidConfig(
+                "the <none> storage can not be backed up".to_string(),
+            ));
+        } else if let Some(p) = ["filesystem://", "file://", "fs://"]
+            .iter()
+            .find_map(|prefix| url.strip_prefix(prefix))
+        {
+            (BackupEngine::Filesystem, p)
+        } else if let Some(p) = url
+            .strip_prefix("sqlite://")
+            .or_else(|| url.strip_prefix("sqlite:"))
+        {
+            (
+                BackupEngine::Sqlite,
+                p.split('?').next().unwrap_or_default(),
+            )
+        } else {
+            return Err(FlameError::InvalidConfig(format!(
+                "unsupported storage <{url}>"
+            )));
+        };
+
+        let path = Path::new(path);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            home.join(path)
+        };
+
+        Ok(Self { engine, path })
+    }
+}
+
+/// The manifest of a backup.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// The version of Flame which took the backup.
+    pub version: String,
+    pub engine: BackupEngine,
+    pub creation_time: DateTime<Utc>,
+}
+
+impl BackupManifest {
+    pub fn new(engine: BackupEngine, creation_time: DateTime<Utc>) -> Self {
+        Self {
+            version: BACKUP_VERSION.to_string(),
+            engine,
+            creation_time,
+        }
+    }
+
+    /// The directory name of the backup.
+    pub fn name(&self) -> String {
+        format!(
+            "{BACKUP_PREFIX}{}",
+            self.creation_time.format(BACKUP_TIME_FORMAT)
+        )
+    }
+
+    pub fn load(dir: &Path) -> Result<Self, FlameError> {
+        let path = dir.join(BACKUP_MANIFEST);
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to read <{}>: {e}", path.display()))
+        })?;
+
+        serde_yaml::from_str(&contents).map_err(|e| {
+            FlameError::InvalidConfig(format!("invalid manifest <{}>: {e}", path.display()))
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), FlameError> {
+        let contents =
+            serde_yaml::to_string(self).map_err(|e| FlameError::Internal(e.to_string()))?;
+        fs::write(dir.join(BACKUP_MANIFEST), contents)?;
+
+        Ok(())
+    }
+
+    /// Check whether the backup can be restored by this version; the storage
+    /// layout only changes with the minor version.
+    pub fn validate(&self) -> Result<(), FlameError> {
+        let minor = |v: &str| v.split('.').take(2).collect::<Vec<_>>().join(".");
+        if minor(&self.version) != minor(BACKUP_VERSION) {
+            return Err(FlameError::VersionMismatch(format!(
+                "backup of version <{}> can not be restored by version <{}>",
+                self.version, BACKUP_VERSION
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// List the backups under the root, ordered by creation time.
+pub fn list_backups(root: &Path) -> Result<Vec<(PathBuf, BackupManifest)>, FlameError> {
+    let mut backups = vec![];
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let is_backup = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(BACKUP_PREFIX));
+        if !is_backup || !path.join(BACKUP_MANIFEST).is_file() {
+            continue;
+        }
+        match BackupManifest::load(&path) {
+            Ok(manifest) => backups.push((path, manifest)),
+            Err(e) => tracing::warn!("Skip backup <{}>: {e}", path.display()),
+        }
+    }
+
+    backups.sort_by_key(|(_, m)| m.creation_time);
+
+    Ok(backups)
+}
+
+/// Find the backup to restore: the path itself if it is a backup, otherwise
+/// the latest backup under it which was taken no later than `before`.
+pub fn find_backup(
+    path: &Path,
+    before: Option<DateTime<Utc>>,
+) -> Result<(PathBuf, BackupManifest), FlameError> {
+    if path.join(BACKUP_MANIFEST).is_file() {
+        return Ok((path.to_path_buf(), BackupManifest::load(path)?));
+    }
+
+    list_backups(path)?
+        .into_iter()
+        .rev()
+        .find(|(_, m)| before.is_none_or(|t| m.creation_time <= t))
+        .ok_or(FlameError::NotFound(format!(
+            "no backup found in <{}>",
+            path.display()
+        )))
+}
+
+/// Remove the oldest backups under the root, keeping the latest `retention` ones.
+pub fn prune_backups(root: &Path, retention: usize) -> Result<Vec<PathBuf>, FlameError> {
+    let backups = list_backups(root)?;
+    let expired = backups.len().saturating_sub(retention);
+
+    let mut removed = vec![];
+    for (path, _) in backups.into_iter().take(expired) {
+        fs::remove_dir_all(&path)?;
+        removed.push(path);
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn create_backup(root: &Path, secs: i64) -> BackupManifest {
+        let manifest =
+            BackupManifest::new(BackupEngine::Sqlite, Utc.timestamp_opt(secs, 0).unwrap());
+        let dir = root.join(manifest.name());
+        fs::create_dir_all(&dir).unwrap();
+        manifest.save(&dir).unwrap();
+        manifest
+    }
+
+    #[test]
+    fn test_storage_location() {
+        let home = Path::new("/usr/local/flame");
+
+        let loc = StorageLocation::from_url("sqlite:///var/lib/flame/flame.db", home).unwrap();
+        assert_eq!(loc.engine, BackupEngine::Sqlite);
+        assert_eq!(loc.path, PathBuf::from("/var/lib/flame/flame.db"));
+
+        let loc = StorageLocation::from_url("sqlite://flame.db?mode=rwc", home).unwrap();
+        assert_eq!(loc.path, PathBuf::from("/usr/local/flame/flame.db"));
+
+        let loc = StorageLocation::from_url("fs://data", home).unwrap();
+        assert_eq!(loc.engine, BackupEngine::Filesystem);
+        assert_eq!(loc.path, PathBuf::from("/usr/local/flame/data"));
+
+        assert!(StorageLocation::from_url("none", home).is_err());
+        assert!(StorageLocation::from_url("postgres://db", home).is_err());
+    }
+
+    #[test]
+    fn test_manifest_validate() {
+        let mut manifest = BackupManifest::new(BackupEngine::Filesystem, Utc::now());
+        assert!(manifest.validate().is_ok());
+
+        manifest.version = "0.0.1".to_string();
+        assert!(matches!(
+            manifest.validate(),
+            Err(FlameError::VersionMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_find_and_prune_backups() {
+        let root = TempDir::new().unwrap();
+        let backups: Vec<_> = [100, 200, 300]
+            .iter()
+            .map(|secs| create_backup(root.path(), *secs))
+            .collect();
+        fs::create_dir_all(root.path().join("flame-incomplete")).unwrap();
+
+        let (path, manifest) = find_backup(root.path(), None).unwrap();
+        assert_eq!(manifest, backups[2]);
+        assert_eq!(find_backup(&path, None).unwrap().1, backups[2]);
+
+        let before = Utc.timestamp_opt(250, 0).unwrap();
+        assert_eq!(
+            find_backup(root.path(), Some(before)).unwrap().1,
+            backups[1]
+        );
+        let before = Utc.timestamp_opt(50, 0).unwrap();
+        assert!(find_backup(root.path(), Some(before)).is_err());
+
+        let removed = prune_backups(root.path(), 2).unwrap();
+        assert_eq!(removed, vec![root.path().join(backups[0].name())]);
+        let remaining: Vec<_> = list_backups(root.path())
+            .unwrap()
+            .into_iter()
+            .map(|(_, m)| m)
+            .collect();
+        assert_eq!(remaining, backups[1..].to_vec());
+    }
+}
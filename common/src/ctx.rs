@@ -13,7 +13,7 @@ limitations under the License.
 
 use std::fmt::{Display, Formatter};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use bytesize::ByteSize;
 use serde_derive::{Deserialize, Serialize};
@@ -21,7 +21,7 @@ use tonic::transport::server::ServerTlsConfig;
 use tonic::transport::{Certificate, ClientTlsConfig, Identity};
 
 use crate::apis::{ResourceRequirement, Shim};
-use crate::FlameError;
+use crate::{FlameError, FLAME_HOME};
 
 const DEFAULT_FLAME_CONF: &str = "flame-cluster.yaml";
 const DEFAULT_CONTEXT_NAME: &str = "flame";
@@ -36,18 +36,71 @@ const DEFAULT_FLAME_CACHE_ENDPOINT: &str = "http://127.0.0.1:9090";
 const DEFAULT_FLAME_CACHE_NETWORK_INTERFACE: &str = "eth0";
 const DEFAULT_EVICTION_POLICY: &str = "lru";
 const DEFAULT_MAX_MEMORY: &str = "1G";
+const DEFAULT_ORPHAN_GRACE_PERIOD: u64 = 600;
+const DEFAULT_BACKUP_INTERVAL: u64 = 3600;
+const DEFAULT_BACKUP_RETENTION: usize = 24;
+const DEFAULT_HISTORY_ROUNDS: u64 = 60;
+const DEFAULT_HISTORY_RETENTION: usize = 1440;
+const DEFAULT_EXPORTER_TABLE: &str = "flame_tasks";
+const DEFAULT_EXPORTER_BATCH_SIZE: usize = 1000;
+const DEFAULT_EXPORTER_FLUSH_INTERVAL: u64 = 5;
+const DEFAULT_EXPORTER_MAX_RETRIES: u32 = 3;
+const DEFAULT_EXPORTER_BUFFER_SIZE: usize = 100_000;
+const DEFAULT_MEMORY_CHECK_INTERVAL: u64 = 5;
+const DEFAULT_LEASE_GRACE_PERIOD: u64 = 60;
+const DEFAULT_MAX_TASK_ATTEMPTS: u32 = 3;
+const DEFAULT_RUNTIME_THREADS: usize = 1;
+const DEFAULT_JOIN_TOKEN_TTL: u64 = 3600;
+const DEFAULT_NODE_NOT_READY_TIMEOUT: u64 = 30;
+const DEFAULT_NODE_LOST_TIMEOUT: u64 = 300;
+const DEFAULT_LOG_MAX_SIZE: &str = "100M";
+const DEFAULT_LOG_MAX_FILES: usize = 5;
+const DEFAULT_SCHEDULER_PLUGINS: [&str; 7] = [
+    "priority",
+    "appshare",
+    "fairshare",
+    "gang",
+    "shim",
+    "availability",
+    "topology",
+];
 
 // ============================================================
 // YAML deserialization structs (serde layer)
 // ============================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameClusterContextYaml {
     pub cluster: FlameClusterYaml,
     pub cache: Option<FlameCacheYaml>,
+    /// TLS policy of all the listeners and clients with TLS
+    pub tls_policy: Option<FlameTlsPolicyYaml>,
+    /// Local directories of the session manager, executor manager and shims
+    pub paths: Option<FlamePathsYaml>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlamePathsYaml {
+    /// Root of the working directories of the executors
+    pub work_dir: Option<String>,
+    /// Directory of the session events
+    pub events_dir: Option<String>,
+    /// Directory of the package caches of the applications, e.g. uv and pip
+    pub cache_dir: Option<String>,
+    /// Directory of the log files
+    pub log_dir: Option<String>,
+    /// Directory of the application YAMLs registered at startup
+    pub applications_dir: Option<String>,
+    /// Directory of the sockets of the executor instances
+    pub socket_dir: Option<String>,
+    /// Root of the scratch directories of the sessions
+    pub scratch_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameClusterYaml {
     pub name: String,
     pub endpoint: String,
@@ -62,26 +115,268 @@ struct FlameClusterYaml {
     pub tls: Option<FlameTlsYaml>,
     /// Resource limits configuration
     pub limits: Option<FlameLimitsYaml>,
+    /// Encryption at rest of the payloads in storage
+    pub encryption: Option<FlameEncryptionYaml>,
+    /// Register the gRPC reflection service, e.g. for grpcurl in dev environments
+    pub reflection: Option<bool>,
+    /// Port of the plain HTTP endpoint of the health checks and the build info
+    pub http_port: Option<u16>,
+    /// Periodic backups of the storage engine
+    pub backup: Option<FlameBackupYaml>,
+    /// Periodic snapshots of the cluster state for the post-incident analysis
+    pub history: Option<FlameHistoryYaml>,
+    /// Continuous export of the task records to an analytical database
+    pub exporter: Option<FlameExporterYaml>,
+    /// Grace period in seconds of the client leases of the sessions
+    pub lease_grace_period: Option<u64>,
+    /// Attempts of a task losing its executor before it's quarantined
+    pub max_task_attempts: Option<u32>,
+    /// Timeout in seconds of running a task
+    pub task_timeout: Option<u64>,
+    /// Priority of the pending tasks of a session
+    pub task_priority: Option<u32>,
+    /// Safety limits of the shuffle action
+    pub shuffle: Option<FlameShuffleYaml>,
+    /// SLO thresholds of the scheduling latencies
+    pub slo: Option<FlameSloYaml>,
+    /// Memory guardrails of the session manager
+    pub memory: Option<FlameMemoryYaml>,
+    /// Node-level configuration distributed to the executor managers
+    pub nodes: Option<Vec<FlameNodeConfigYaml>>,
+    /// Worker threads and metrics of the runtimes of the session manager
+    pub runtimes: Option<FlameRuntimesYaml>,
+    /// Join tokens and credentials of the nodes joining the cluster
+    pub join: Option<FlameJoinYaml>,
+    /// Admin token of the administrative RPCs, e.g. minting the join tokens
+    pub admin: Option<FlameAdminYaml>,
+    /// Heartbeat thresholds of the nodes
+    pub node_health: Option<FlameNodeHealthYaml>,
+    /// Plugins of the scheduler
+    pub scheduler: Option<FlameSchedulerYaml>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameExecutorsYaml {
     pub shim: Option<String>,
     pub limits: Option<FlameExecutorLimitsYaml>,
+    /// Idle timeout in seconds of the bound executors without tasks
+    pub idle_timeout: Option<u64>,
+    /// Bundled tasks whose object cache inputs are prefetched ahead of the running one
+    pub prefetch: Option<usize>,
+    /// Maximum shims started in parallel on the node
+    pub bootstrap_concurrency: Option<usize>,
+    /// Grace period in seconds of the preempted instances to checkpoint the running task
+    pub preempt_grace_period: Option<u64>,
+    /// OCI registries of the Wasm modules of the applications
+    pub registry: Option<FlameRegistryYaml>,
+    /// User of the instances of the host shim, e.g. `flame`
+    pub user: Option<String>,
+    /// Group of the instances of the host shim
+    pub group: Option<String>,
+    /// Rotation of the stdout and stderr logs of the instances
+    pub logs: Option<FlameLogsYaml>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameLogsYaml {
+    /// Size of a log to rotate it (string with units: "100M", "1G")
+    pub max_size: Option<String>,
+    /// Age in seconds of a log to rotate it
+    pub max_age: Option<u64>,
+    /// Rotated logs kept for each log
+    pub max_files: Option<usize>,
+    /// Compress the rotated logs by zstd
+    pub compress: Option<bool>,
+    /// Total size of the logs of the instances on a node (string with units: "10G")
+    pub max_disk: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameRegistryYaml {
+    /// Path to the credentials of the registries in the format of docker's config.json
+    pub auth_file: Option<String>,
+    /// Path to the PEM-encoded CA certificates to verify the registries
+    pub ca_file: Option<String>,
+    /// Registries accessed by plain http, e.g. "localhost:5000"
+    pub insecure: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameExecutorLimitsYaml {
     pub max_executors: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameLimitsYaml {
     pub max_sessions: Option<usize>,
     pub max_executors: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameShuffleYaml {
+    /// Maximum executors rebalanced in one scheduling cycle
+    pub max_executors: Option<u32>,
+    /// Cooldown in seconds of a session after it was involved in a shuffle
+    pub cooldown: Option<u64>,
+    /// Only record the intended shuffles without unbinding any executor
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameSloYaml {
+    /// Milliseconds from a session becoming dispatchable to its first executor bound
+    pub bind_latency: Option<u64>,
+    /// Milliseconds from a task's creation to its dispatch
+    pub dispatch_latency: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameNodeHealthYaml {
+    /// Seconds without heartbeat before the node is NotReady
+    pub not_ready_timeout: Option<u64>,
+    /// Seconds without heartbeat before the node is Lost
+    pub lost_timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameSchedulerYaml {
+    /// The enabled plugins, in the order of their session and node orders
+    pub plugins: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameMemoryYaml {
+    /// Memory usage to shed load above (string with units: "1G", "512M")
+    pub soft_limit: Option<String>,
+    /// Memory usage to checkpoint and restart above (string with units: "1G", "512M")
+    pub hard_limit: Option<String>,
+    /// Interval in seconds between two checks of the memory usage
+    pub interval: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameRuntimesYaml {
+    /// Worker threads of the frontend runtime
+    pub frontend: Option<usize>,
+    /// Worker threads of the backend runtime
+    pub backend: Option<usize>,
+    /// Worker threads of the scheduler runtime
+    pub scheduler: Option<usize>,
+    /// Worker threads of the provider runtime
+    pub provider: Option<usize>,
+    /// Interval in seconds between two reports of the runtime metrics
+    pub metrics_interval: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameNodeConfigYaml {
+    /// Name of the nodes, where `*` matches any characters, e.g. "gpu-*"
+    pub name: String,
+    /// Slots of the nodes, overriding the auto-detected ones
+    pub slots: Option<u32>,
+    pub labels: Option<Vec<String>>,
+    /// Log level of the executor managers, e.g. "debug" or "flame=debug,info"
+    pub log_level: Option<String>,
+    pub cache: Option<FlameNodeCacheYaml>,
+    /// Applications whose artifacts are prefetched by the nodes, e.g. "ml-*"
+    pub prefetch: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameNodeCacheYaml {
+    /// Maximum memory for cached objects (string with units: "1G", "512M", "1024K")
+    pub max_memory: Option<String>,
+    /// Maximum number of objects in memory
+    pub max_objects: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameEncryptionYaml {
+    /// The id of the key to encrypt new payloads; the first key if not set
+    pub active_key: Option<String>,
+    /// The keys to decrypt payloads, including the retired ones
+    pub keys: Vec<FlameEncryptionKeyYaml>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameEncryptionKeyYaml {
+    pub id: String,
+    /// Path to the file of the 256-bit key, in raw bytes or hex
+    pub key_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameBackupYaml {
+    /// Directory of the backups
+    pub path: String,
+    /// Interval in seconds between two backups
+    pub interval: Option<u64>,
+    /// Number of the latest backups to keep
+    pub retention: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameHistoryYaml {
+    /// Directory of the snapshots
+    pub path: String,
+    /// Number of the scheduling rounds between two snapshots
+    pub rounds: Option<u64>,
+    /// Number of the latest snapshots to keep
+    pub retention: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameExporterYaml {
+    /// URL of the database, e.g. `http://clickhouse:8123` or `postgres://timescale/flame`
+    pub endpoint: String,
+    /// Table of the task records
+    pub table: Option<String>,
+    /// Maximum records written in one batch
+    pub batch_size: Option<usize>,
+    /// Interval in seconds between two flushes
+    pub flush_interval: Option<u64>,
+    /// Retries of a failed batch before it's kept for the next flush
+    pub max_retries: Option<u32>,
+    /// Maximum records buffered while the database is unavailable
+    pub buffer_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameAdminYaml {
+    /// Path to the file of the admin token, at least 256 bits
+    pub token_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlameJoinYaml {
+    /// Path to the file of the secret to sign the node credentials, at least 256 bits
+    pub secret_file: String,
+    /// Default time to live in seconds of the join tokens
+    pub token_ttl: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameTlsYaml {
     /// Path to PEM-encoded server certificate
     pub cert_file: Option<String>,
@@ -92,16 +387,31 @@ struct FlameTlsYaml {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlameTlsPolicyYaml {
+    /// Minimum TLS version: "1.2" or "1.3"
+    pub min_version: Option<String>,
+    /// Permitted cipher suites, e.g. "TLS13_AES_256_GCM_SHA384"; all supported ones if not set
+    pub cipher_suites: Option<Vec<String>>,
+    /// Only permit FIPS-approved algorithms
+    pub fips: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameCacheYaml {
     pub endpoint: Option<String>,
     pub network_interface: Option<String>,
     pub storage: Option<String>,
     pub eviction: Option<FlameEvictionYaml>,
+    /// Grace period in seconds before the objects of the deleted sessions are removed
+    pub orphan_grace_period: Option<u64>,
     /// TLS configuration for Object Cache (optional, independent from cluster.tls)
     pub tls: Option<FlameTlsYaml>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 struct FlameEvictionYaml {
     /// Eviction policy: "lru" or "none"
     pub policy: Option<String>,
@@ -119,6 +429,31 @@ struct FlameEvictionYaml {
 pub struct FlameClusterContext {
     pub cluster: FlameCluster,
     pub cache: Option<FlameCache>,
+    pub paths: FlamePaths,
+}
+
+/// The local directories of the components.
+///
+/// The defaults are under `FLAME_HOME` if it is set, otherwise under
+/// `working_directory()` (the events are in the current directory).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlamePaths {
+    /// Root of the working directories of the executors
+    pub work: PathBuf,
+    /// Directory of the session events
+    pub events: PathBuf,
+    /// Directory of the package caches of the applications, e.g. uv and pip
+    pub cache: PathBuf,
+    /// Directory of the log files
+    pub logs: PathBuf,
+    /// Directory of the application YAMLs, e.g. baked into the installation
+    /// image, which are registered or updated by the session manager at startup
+    pub applications: PathBuf,
+    /// Directory of the sockets of the executor instances; keep it short, as
+    /// the path of a Unix socket is limited to ~100 bytes
+    pub sockets: PathBuf,
+    /// Root of the scratch directories shared by the instances of a session
+    pub scratch: PathBuf,
 }
 
 #[derive(Debug, Clone)]
@@ -136,11 +471,124 @@ pub struct FlameCluster {
     pub tls: Option<FlameTls>,
     /// Resource limits configuration
     pub limits: FlameLimits,
+    /// Encryption at rest of the payloads in storage (optional)
+    pub encryption: Option<FlameEncryption>,
+    /// Whether the gRPC reflection service is registered
+    pub reflection: bool,
+    /// The port of the plain HTTP endpoint of `/healthz`, `/readyz` and
+    /// `/buildinfo` for the load balancers and the uptime monitors; disabled
+    /// if not set.
+    pub http_port: Option<u16>,
+    /// Periodic backups of the storage engine (optional)
+    pub backup: Option<FlameBackup>,
+    /// Periodic snapshots of the cluster state (optional)
+    pub history: Option<FlameHistory>,
+    /// Continuous export of the task records to an analytical database (optional)
+    pub exporter: Option<FlameExporter>,
+    /// Grace period in seconds of the client leases; the session is closed if
+    /// its client does not renew the lease within it.
+    pub lease_grace_period: u64,
+    /// The attempts of a task which lost its executor, e.g. the shim crashed
+    /// or the node was lost, before it's quarantined as a poison task.
+    pub max_task_attempts: u32,
+    /// The timeout in seconds of running a task, after which it's failed with
+    /// `Timeout` and its instance is released; no timeout if not set.
+    pub task_timeout: Option<u64>,
+    /// The priority of the tasks; the pending tasks of a session with higher
+    /// priorities are dispatched first.
+    pub task_priority: u32,
+    /// Safety limits of the shuffle action, which rebalances the executors
+    /// from the overused sessions to the underused ones.
+    pub shuffle: FlameShuffle,
+    /// SLO thresholds of the scheduling latencies.
+    pub slo: FlameSlo,
+    /// Memory guardrails of the session manager (optional)
+    pub memory: Option<FlameMemory>,
+    /// Node-level configuration distributed to the executor managers, in order.
+    pub nodes: Vec<FlameNodeConfig>,
+    /// Worker threads and metrics of the runtimes of the session manager.
+    pub runtimes: FlameRuntimes,
+    /// The join tokens and credentials of the nodes (optional); any node can
+    /// register if not set.
+    pub join: Option<FlameJoin>,
+    /// The admin token of the administrative RPCs (optional); the join tokens
+    /// are not minted if not set.
+    pub admin: Option<FlameAdmin>,
+    /// The heartbeat thresholds of the nodes becoming NotReady and Lost.
+    pub node_health: FlameNodeHealth,
+    /// The plugins of the scheduler.
+    pub scheduler: FlameScheduler,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct FlameExecutors {
     pub shim: Shim,
+    /// Idle timeout in seconds after which a bound executor without tasks is
+    /// unbound and released; it never applies before the `delay_release` of
+    /// the application, nor to the `min_instances` of the session. Disabled
+    /// if not set.
+    pub idle_timeout: Option<u64>,
+    /// The bundled tasks after the running one whose inputs in the object
+    /// cache are prefetched, overlapping the IO with the running task;
+    /// disabled if 0.
+    pub prefetch: usize,
+    /// The maximum shims started in parallel on the node, e.g. when many
+    /// executors are created at once; the CPUs of the node if not set.
+    pub bootstrap_concurrency: Option<usize>,
+    /// The grace period in seconds of a preempted instance: its running task
+    /// is interrupted, and the instance is asked to checkpoint its state by
+    /// `on_preempt` within the period before the task is requeued. If not set,
+    /// the running task completes before the executor is unbound.
+    pub preempt_grace_period: Option<u64>,
+    /// The OCI registries where the Wasm modules of the applications are
+    /// pulled from, i.e. by their `image`.
+    pub registry: FlameRegistry,
+    /// The user the instances of the host shim run as, e.g. a dedicated
+    /// unprivileged user; the user of the executor manager if not set. The
+    /// executor manager must run as root, or with CAP_SETUID, CAP_SETGID and
+    /// CAP_CHOWN, to switch the user.
+    pub user: Option<String>,
+    /// The group the instances of the host shim run as; the primary group of
+    /// the `user` if not set.
+    pub group: Option<String>,
+    /// The rotation of the stdout and stderr logs of the instances of the
+    /// host shim.
+    pub logs: FlameLogs,
+}
+
+/// The rotation of the stdout and stderr logs of the instances on each node,
+/// so the long-lived instances don't exhaust the disk of the node: a log is
+/// rotated when it's larger than `max_size` or older than `max_age`, and only
+/// the latest `max_files` rotated logs are kept, compressed if `compress`. The
+/// oldest rotated logs on the node are removed above `max_disk`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlameLogs {
+    /// The size in bytes of a log to rotate it.
+    pub max_size: u64,
+    /// The age in seconds of a log to rotate it; not rotated by time if not set.
+    pub max_age: Option<u64>,
+    /// The rotated logs kept for each log.
+    pub max_files: usize,
+    /// Whether the rotated logs are compressed by zstd.
+    pub compress: bool,
+    /// The total size in bytes of the logs of the instances on the node;
+    /// unlimited if not set.
+    pub max_disk: Option<u64>,
+}
+
+/// The OCI registries of the Wasm modules referred by the `image` of the
+/// applications, e.g. `ghcr.io/org/app:v1`.
+#[derive(Debug, Clone, Default)]
+pub struct FlameRegistry {
+    /// The credentials of the registries in the format of docker's
+    /// config.json, i.e. `auths.<registry>.auth`; `$DOCKER_CONFIG/config.json`
+    /// or `~/.docker/config.json` if not set.
+    pub auth_file: Option<String>,
+    /// The CA certificates to verify the registries; the CA bundle of the
+    /// system if not set.
+    pub ca_file: Option<String>,
+    /// The registries accessed by plain http, e.g. a local one for development.
+    pub insecure: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -149,6 +597,290 @@ pub struct FlameLimits {
     pub max_executors: u32,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct FlameShuffle {
+    /// The maximum executors rebalanced in one scheduling cycle; unlimited if
+    /// not set.
+    pub max_executors: Option<u32>,
+    /// The cooldown in seconds of a session after it gave or received an
+    /// executor by a shuffle, before it's shuffled again; disabled if 0.
+    pub cooldown: u64,
+    /// Record the intended shuffles as events without unbinding any executor,
+    /// e.g. to evaluate the shuffles before enabling them.
+    pub dry_run: bool,
+}
+
+/// The SLO thresholds in milliseconds of the scheduling latencies; the latencies
+/// above them are logged as warnings and recorded as events. Not checked if not set.
+#[derive(Debug, Clone, Default)]
+pub struct FlameSlo {
+    /// From a session becoming dispatchable, i.e. its first task was created,
+    /// to its first executor bound.
+    pub bind_latency: Option<u64>,
+    /// From a task's creation to its dispatch to an executor.
+    pub dispatch_latency: Option<u64>,
+}
+
+/// The heartbeat thresholds in seconds of the nodes, since their last
+/// heartbeat: no executor is allocated on the NotReady nodes, and the
+/// executors of the Lost nodes are released, i.e. their tasks are requeued.
+#[derive(Debug, Clone)]
+pub struct FlameNodeHealth {
+    pub not_ready_timeout: u64,
+    pub lost_timeout: u64,
+}
+
+/// The plugins of the scheduler, by their names in the registry of the
+/// session manager, e.g. `fairshare`. The plugins not listed are disabled, and
+/// the order of the sessions and the nodes is decided by the first plugin with
+/// an opinion, in the listed order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlameScheduler {
+    pub plugins: Vec<String>,
+}
+
+/// Memory guardrails of the session manager, against its RSS.
+///
+/// Above the soft limit, the new sessions are rejected and the closed sessions
+/// are evicted from the cache until the usage is below it again; above the
+/// hard limit, the storage is checkpointed and the session manager exits to be
+/// restarted by its supervisor, instead of being OOM-killed.
+#[derive(Debug, Clone, Default)]
+pub struct FlameMemory {
+    /// The soft limit in bytes; not checked if not set.
+    pub soft_limit: Option<u64>,
+    /// The hard limit in bytes; not checked if not set.
+    pub hard_limit: Option<u64>,
+    /// Interval in seconds between two checks of the memory usage
+    pub interval: u64,
+}
+
+/// The worker threads of the runtimes of the session manager, one per
+/// component, so a busy component does not starve the others.
+///
+/// The frontend serves the clients, e.g. creating tasks and watching them, so
+/// it needs more threads for high-throughput clients; the backend serves the
+/// executor managers, and takes the CPUs left by the others if not set. The
+/// scheduler and the provider run a single loop, which rarely needs more than
+/// one thread. The runtime metrics, e.g. the busy ratio of the workers, are
+/// reported periodically if `metrics_interval` is set: a runtime which is
+/// busy most of the time needs more threads.
+#[derive(Debug, Clone)]
+pub struct FlameRuntimes {
+    pub frontend: usize,
+    /// The CPUs left by the other runtimes if not set.
+    pub backend: Option<usize>,
+    pub scheduler: usize,
+    pub provider: usize,
+    /// Interval in seconds between two reports of the runtime metrics; not
+    /// reported if not set.
+    pub metrics_interval: Option<u64>,
+}
+
+impl FlameRuntimes {
+    /// The worker threads of the backend runtime, by default the CPUs left by
+    /// the other runtimes and at least one.
+    pub fn backend_threads(&self, cpus: usize) -> usize {
+        self.backend.unwrap_or_else(|| {
+            cpus.saturating_sub(self.frontend + self.scheduler + self.provider)
+                .max(1)
+        })
+    }
+}
+
+impl Default for FlameRuntimes {
+    fn default() -> Self {
+        FlameRuntimes {
+            frontend: DEFAULT_RUNTIME_THREADS,
+            backend: None,
+            scheduler: DEFAULT_RUNTIME_THREADS,
+            provider: DEFAULT_RUNTIME_THREADS,
+            metrics_interval: None,
+        }
+    }
+}
+
+/// The node-level configuration of the nodes matching the name, where `*`
+/// matches any characters, e.g. "gpu-*".
+#[derive(Debug, Clone, Default)]
+pub struct FlameNodeConfig {
+    pub name: String,
+    pub config: NodeConfig,
+}
+
+/// The node-level configuration distributed by the session manager to the
+/// executor managers, overriding their local configuration; the executor
+/// manager persists it locally to apply it before connecting at restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeConfig {
+    /// The slots of the node, overriding the auto-detected ones.
+    pub slots: Option<u32>,
+    pub labels: Vec<String>,
+    /// The log level of the executor manager, in the `RUST_LOG` syntax.
+    pub log_level: Option<String>,
+    /// The max memory in bytes of the object cache of the node.
+    pub cache_max_memory: Option<u64>,
+    /// The max objects in memory of the object cache of the node.
+    pub cache_max_objects: Option<usize>,
+    /// The applications whose artifacts, e.g. their Wasm modules or Python
+    /// environments, are prefetched by the node when it registers, where `*`
+    /// matches any characters; the session manager resolves them to the
+    /// registered applications before sending the configuration.
+    #[serde(default)]
+    pub prefetch: Vec<String>,
+}
+
+impl NodeConfig {
+    /// Overrides the fields set in the other configuration; the labels and the
+    /// prefetched applications are merged.
+    fn merge(&mut self, other: &NodeConfig) {
+        self.slots = other.slots.or(self.slots);
+        self.log_level = other.log_level.clone().or(self.log_level.take());
+        self.cache_max_memory = other.cache_max_memory.or(self.cache_max_memory);
+        self.cache_max_objects = other.cache_max_objects.or(self.cache_max_objects);
+        for label in &other.labels {
+            if !self.labels.contains(label) {
+                self.labels.push(label.clone());
+            }
+        }
+        for pattern in &other.prefetch {
+            if !self.prefetch.contains(pattern) {
+                self.prefetch.push(pattern.clone());
+            }
+        }
+    }
+
+    /// Whether the artifacts of the application are prefetched by the node.
+    pub fn prefetches(&self, app: &str) -> bool {
+        self.prefetch
+            .iter()
+            .any(|pattern| matches_name(pattern, app))
+    }
+
+    /// Overrides the eviction settings of the object cache.
+    pub fn apply_cache(&self, cache: &mut FlameCache) {
+        if let Some(max_memory) = self.cache_max_memory {
+            cache.eviction.max_memory = max_memory;
+        }
+        if let Some(max_objects) = self.cache_max_objects {
+            cache.eviction.max_objects = Some(max_objects);
+        }
+    }
+}
+
+/// Whether the name matches the pattern, where `*` matches any characters.
+fn matches_name(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` in the pattern.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Encryption configuration of the payloads in storage.
+///
+/// Task input/output and session common data are encrypted by the active key
+/// before being stored; all keys are kept to decrypt the payloads encrypted
+/// before a key rotation.
+#[derive(Debug, Clone)]
+pub struct FlameEncryption {
+    /// The id of the key to encrypt new payloads
+    pub active_key: String,
+    pub keys: Vec<FlameEncryptionKey>,
+}
+
+/// Periodic backups of the storage engine; the oldest backups beyond the
+/// retention are removed after each backup.
+#[derive(Debug, Clone)]
+pub struct FlameBackup {
+    pub path: String,
+    /// Interval in seconds between two backups
+    pub interval: u64,
+    /// Number of the latest backups to keep
+    pub retention: usize,
+}
+
+/// Periodic snapshots of the cluster state taken by the scheduler, i.e. its
+/// nodes, sessions and executors, to tell what the cluster looked like at a
+/// point in time; the oldest snapshots beyond the retention are removed.
+#[derive(Debug, Clone)]
+pub struct FlameHistory {
+    pub path: String,
+    /// Number of the scheduling rounds between two snapshots
+    pub rounds: u64,
+    /// Number of the latest snapshots to keep
+    pub retention: usize,
+}
+
+/// The database the task records are exported to, see `FlameExporter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExporterKind {
+    /// ClickHouse by its HTTP interface, i.e. `http://`.
+    ClickHouse,
+    /// TimescaleDB, or PostgreSQL, i.e. `postgres://`.
+    Timescale,
+}
+
+/// The continuous export of the records of the completed tasks, e.g. their
+/// application, node and durations, to an analytical database for the
+/// historical dashboards; the records are buffered in memory, and written in
+/// batches every flush interval.
+#[derive(Debug, Clone)]
+pub struct FlameExporter {
+    pub kind: ExporterKind,
+    pub endpoint: String,
+    pub table: String,
+    /// Maximum records written in one batch
+    pub batch_size: usize,
+    /// Interval in seconds between two flushes
+    pub flush_interval: u64,
+    /// Retries of a failed batch before it's kept for the next flush
+    pub max_retries: u32,
+    /// Maximum records buffered; the oldest ones are dropped beyond it
+    pub buffer_size: usize,
+}
+
+/// The secure join of the nodes: a node registers with a one-time join token
+/// minted by the administrator, e.g. `flmctl join-token`, and receives the
+/// credential of the node, which is signed by the secret and presented by the
+/// node at its later registrations and heartbeats.
+#[derive(Debug, Clone)]
+pub struct FlameJoin {
+    /// Path to the file of the secret to sign the node credentials
+    pub secret_file: String,
+    /// Default time to live in seconds of the join tokens
+    pub token_ttl: u64,
+}
+
+/// The credential of the administrators, presented by the clients, e.g.
+/// `flmctl join-token`, in the `x-flame-admin-token` metadata of the
+/// administrative RPCs.
+#[derive(Debug, Clone)]
+pub struct FlameAdmin {
+    /// Path to the file of the admin token
+    pub token_file: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlameEncryptionKey {
+    pub id: String,
+    /// Path to the file of the 256-bit key, in raw bytes or hex
+    pub key_file: String,
+}
+
 /// TLS configuration for Flame services.
 ///
 /// When this struct is present and valid (cert_file + key_file configured),
@@ -161,6 +893,29 @@ pub struct FlameTls {
     pub key_file: String,
     /// Path to PEM-encoded CA certificate (optional)
     pub ca_file: Option<String>,
+    /// The cluster TLS policy; tonic's TLS support is used if not set
+    pub policy: Option<FlameTlsPolicy>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum_macros::Display)]
+pub enum TlsVersion {
+    #[default]
+    #[strum(serialize = "1.2")]
+    V1_2,
+    #[strum(serialize = "1.3")]
+    V1_3,
+}
+
+/// TLS policy of the listeners and clients, e.g. for regulated environments.
+///
+/// Note: `fips` restricts the algorithms to the FIPS-approved ones, it does not
+/// make the crypto library a validated module.
+#[derive(Debug, Clone, Default)]
+pub struct FlameTlsPolicy {
+    pub min_version: TlsVersion,
+    /// Permitted cipher suites; all supported ones if empty
+    pub cipher_suites: Vec<String>,
+    pub fips: bool,
 }
 
 impl FlameTls {
@@ -208,6 +963,9 @@ pub struct FlameCache {
     pub network_interface: String,
     pub storage: Option<String>,
     pub eviction: FlameEviction,
+    /// Grace period in seconds of the objects whose sessions are not known by
+    /// the session manager, e.g. deleted, before they're removed.
+    pub orphan_grace_period: u64,
     /// TLS configuration for Object Cache (optional, independent from cluster.tls)
     pub tls: Option<FlameTls>,
 }
@@ -224,6 +982,16 @@ impl FlameCluster {
     pub fn requires_tls(&self) -> bool {
         self.endpoint.starts_with("https://")
     }
+
+    /// The node-level configuration of the node, merged from all the matching
+    /// ones in order, i.e. the later ones override the earlier ones.
+    pub fn node_config(&self, node: &str) -> NodeConfig {
+        let mut config = NodeConfig::default();
+        for nc in self.nodes.iter().filter(|nc| matches_name(&nc.name, node)) {
+            config.merge(&nc.config);
+        }
+        config
+    }
 }
 
 /// Eviction configuration for the cache.
@@ -237,6 +1005,58 @@ pub struct FlameEviction {
     pub max_objects: Option<usize>,
 }
 
+impl Default for FlamePaths {
+    fn default() -> Self {
+        match std::env::var(FLAME_HOME) {
+            Ok(home) => {
+                let home = Path::new(&home);
+                FlamePaths {
+                    work: home.join("work"),
+                    events: home.join("events"),
+                    cache: home.join("data").join("cache"),
+                    logs: home.join("logs"),
+                    applications: home.join("conf").join("applications.d"),
+                    sockets: home.join("run"),
+                    scratch: home.join("data").join("scratch"),
+                }
+            }
+            Err(_) => {
+                let work = crate::working_directory();
+                FlamePaths {
+                    events: PathBuf::from("events"),
+                    cache: work.join("cache"),
+                    logs: work.join("logs"),
+                    applications: work.join("applications.d"),
+                    sockets: work.join("sockets"),
+                    scratch: work.join("scratch"),
+                    work,
+                }
+            }
+        }
+    }
+}
+
+impl FlamePaths {
+    /// Create the directories if they do not exist.
+    pub fn create_dirs(&self) -> Result<(), FlameError> {
+        for (name, path) in [
+            ("work", &self.work),
+            ("events", &self.events),
+            ("cache", &self.cache),
+            ("log", &self.logs),
+        ] {
+            fs::create_dir_all(path).map_err(|e| {
+                FlameError::InvalidConfig(format!(
+                    "failed to create {name} directory <{}>: {e}",
+                    path.display()
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Default for FlameEviction {
     fn default() -> Self {
         let default_max_memory =
@@ -333,8 +1153,10 @@ impl FlameClusterContext {
 
         let contents =
             fs::read_to_string(fp.clone()).map_err(|e| FlameError::Internal(e.to_string()))?;
-        let ctx: FlameClusterContextYaml =
-            serde_yaml::from_str(&contents).map_err(|e| FlameError::Internal(e.to_string()))?;
+        // Unknown keys and mismatched types are reported with their location,
+        // e.g. "cluster: unknown field `slots` ... at line 4 column 3".
+        let ctx: FlameClusterContextYaml = serde_yaml::from_str(&contents)
+            .map_err(|e| FlameError::InvalidConfig(format!("invalid configuration <{fp}>: {e}")))?;
 
         tracing::debug!("Load FlameClusterContext from <{fp}>: {ctx:?}");
 
@@ -345,10 +1167,36 @@ impl FlameClusterContext {
 impl TryFrom<FlameClusterContextYaml> for FlameClusterContext {
     type Error = FlameError;
     fn try_from(ctx: FlameClusterContextYaml) -> Result<Self, Self::Error> {
-        let cluster = FlameCluster::try_from(ctx.cluster)?;
+        let mut cluster = FlameCluster::try_from(ctx.cluster)?;
+        let mut cache = ctx.cache.map(FlameCache::try_from).transpose()?;
+
+        if let Some(policy) = ctx.tls_policy {
+            let policy = FlameTlsPolicy::try_from(policy)?;
+            let tls_list = [
+                cluster.tls.as_mut(),
+                cache.as_mut().and_then(|c| c.tls.as_mut()),
+            ];
+            if tls_list.iter().all(Option::is_none) {
+                return Err(FlameError::InvalidConfig(
+                    "tls_policy is set but neither cluster.tls nor cache.tls is configured"
+                        .to_string(),
+                ));
+            }
+            for tls in tls_list.into_iter().flatten() {
+                tls.policy = Some(policy.clone());
+            }
+        }
+
+        let paths = ctx
+            .paths
+            .map(FlamePaths::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(FlameClusterContext {
             cluster,
-            cache: ctx.cache.map(FlameCache::try_from).transpose()?,
+            cache,
+            paths,
         })
     }
 }
@@ -366,6 +1214,52 @@ impl TryFrom<FlameClusterYaml> for FlameCluster {
 
         let limits = cluster.limits.map(FlameLimits::from).unwrap_or_default();
 
+        let encryption = cluster
+            .encryption
+            .map(FlameEncryption::try_from)
+            .transpose()?;
+
+        let backup = cluster.backup.map(FlameBackup::try_from).transpose()?;
+
+        let history = cluster.history.map(FlameHistory::try_from).transpose()?;
+
+        let exporter = cluster.exporter.map(FlameExporter::try_from).transpose()?;
+
+        let shuffle = cluster.shuffle.map(FlameShuffle::from).unwrap_or_default();
+
+        let slo = cluster.slo.map(FlameSlo::from).unwrap_or_default();
+
+        let memory = cluster.memory.map(FlameMemory::try_from).transpose()?;
+
+        let join = cluster.join.map(FlameJoin::try_from).transpose()?;
+
+        let admin = cluster.admin.map(FlameAdmin::try_from).transpose()?;
+
+        let node_health = cluster
+            .node_health
+            .map(FlameNodeHealth::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let scheduler = cluster
+            .scheduler
+            .map(FlameScheduler::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let runtimes = cluster
+            .runtimes
+            .map(FlameRuntimes::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let nodes = cluster
+            .nodes
+            .unwrap_or_default()
+            .into_iter()
+            .map(FlameNodeConfig::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(FlameCluster {
             name: cluster.name,
             endpoint: cluster.endpoint,
@@ -378,6 +1272,31 @@ impl TryFrom<FlameClusterYaml> for FlameCluster {
             executors,
             tls,
             limits,
+            encryption,
+            reflection: cluster.reflection.unwrap_or(false),
+            http_port: cluster.http_port,
+            backup,
+            history,
+            exporter,
+            lease_grace_period: cluster
+                .lease_grace_period
+                .filter(|period| *period > 0)
+                .unwrap_or(DEFAULT_LEASE_GRACE_PERIOD),
+            max_task_attempts: cluster
+                .max_task_attempts
+                .filter(|attempts| *attempts > 0)
+                .unwrap_or(DEFAULT_MAX_TASK_ATTEMPTS),
+            task_timeout: cluster.task_timeout.filter(|timeout| *timeout > 0),
+            task_priority: cluster.task_priority.unwrap_or_default(),
+            shuffle,
+            slo,
+            memory,
+            nodes,
+            runtimes,
+            join,
+            admin,
+            node_health,
+            scheduler,
         })
     }
 }
@@ -387,10 +1306,80 @@ impl TryFrom<FlameExecutorsYaml> for FlameExecutors {
     fn try_from(executors: FlameExecutorsYaml) -> Result<Self, Self::Error> {
         Ok(FlameExecutors {
             shim: Shim::try_from(executors.shim.unwrap_or(DEFAULT_SHIM.to_string()))?,
+            idle_timeout: executors.idle_timeout.filter(|timeout| *timeout > 0),
+            prefetch: executors.prefetch.unwrap_or_default(),
+            bootstrap_concurrency: executors.bootstrap_concurrency.filter(|limit| *limit > 0),
+            preempt_grace_period: executors.preempt_grace_period.filter(|period| *period > 0),
+            registry: executors
+                .registry
+                .map(FlameRegistry::from)
+                .unwrap_or_default(),
+            user: executors.user.filter(|user| !user.is_empty()),
+            group: executors.group.filter(|group| !group.is_empty()),
+            logs: executors
+                .logs
+                .map(FlameLogs::try_from)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
 
+impl TryFrom<FlameLogsYaml> for FlameLogs {
+    type Error = FlameError;
+    fn try_from(yaml: FlameLogsYaml) -> Result<Self, Self::Error> {
+        let max_size = match yaml.max_size.as_deref() {
+            Some(size) => parse_memory_size(size)?,
+            None => FlameLogs::default().max_size,
+        };
+        if max_size == 0 {
+            return Err(FlameError::InvalidConfig(
+                "executors.logs.max_size must be positive".to_string(),
+            ));
+        }
+        let max_disk = yaml
+            .max_disk
+            .as_deref()
+            .map(parse_memory_size)
+            .transpose()?;
+        if max_disk.is_some_and(|disk| disk < max_size) {
+            return Err(FlameError::InvalidConfig(
+                "executors.logs.max_disk must not be less than executors.logs.max_size".to_string(),
+            ));
+        }
+
+        Ok(FlameLogs {
+            max_size,
+            max_age: yaml.max_age.filter(|age| *age > 0),
+            max_files: yaml.max_files.unwrap_or(DEFAULT_LOG_MAX_FILES),
+            compress: yaml.compress.unwrap_or(true),
+            max_disk,
+        })
+    }
+}
+
+impl Default for FlameLogs {
+    fn default() -> Self {
+        FlameLogs {
+            max_size: parse_memory_size(DEFAULT_LOG_MAX_SIZE).unwrap_or(100 * 1024 * 1024),
+            max_age: None,
+            max_files: DEFAULT_LOG_MAX_FILES,
+            compress: true,
+            max_disk: None,
+        }
+    }
+}
+
+impl From<FlameRegistryYaml> for FlameRegistry {
+    fn from(yaml: FlameRegistryYaml) -> Self {
+        FlameRegistry {
+            auth_file: yaml.auth_file,
+            ca_file: yaml.ca_file,
+            insecure: yaml.insecure.unwrap_or_default(),
+        }
+    }
+}
+
 impl From<FlameLimitsYaml> for FlameLimits {
     fn from(yaml: FlameLimitsYaml) -> Self {
         FlameLimits {
@@ -400,6 +1389,25 @@ impl From<FlameLimitsYaml> for FlameLimits {
     }
 }
 
+impl From<FlameShuffleYaml> for FlameShuffle {
+    fn from(yaml: FlameShuffleYaml) -> Self {
+        FlameShuffle {
+            max_executors: yaml.max_executors,
+            cooldown: yaml.cooldown.unwrap_or_default(),
+            dry_run: yaml.dry_run.unwrap_or(false),
+        }
+    }
+}
+
+impl From<FlameSloYaml> for FlameSlo {
+    fn from(yaml: FlameSloYaml) -> Self {
+        FlameSlo {
+            bind_latency: yaml.bind_latency.filter(|ms| *ms > 0),
+            dispatch_latency: yaml.dispatch_latency.filter(|ms| *ms > 0),
+        }
+    }
+}
+
 impl Default for FlameLimits {
     fn default() -> Self {
         FlameLimits {
@@ -421,6 +1429,45 @@ impl Default for FlameCluster {
             executors: FlameExecutors::default(),
             tls: None,
             limits: FlameLimits::default(),
+            encryption: None,
+            reflection: false,
+            http_port: None,
+            backup: None,
+            history: None,
+            exporter: None,
+            lease_grace_period: DEFAULT_LEASE_GRACE_PERIOD,
+            max_task_attempts: DEFAULT_MAX_TASK_ATTEMPTS,
+            task_timeout: None,
+            task_priority: 0,
+            shuffle: FlameShuffle::default(),
+            slo: FlameSlo::default(),
+            memory: None,
+            nodes: vec![],
+            runtimes: FlameRuntimes::default(),
+            join: None,
+            admin: None,
+            node_health: FlameNodeHealth::default(),
+            scheduler: FlameScheduler::default(),
+        }
+    }
+}
+
+impl Default for FlameScheduler {
+    fn default() -> Self {
+        FlameScheduler {
+            plugins: DEFAULT_SCHEDULER_PLUGINS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Default for FlameNodeHealth {
+    fn default() -> Self {
+        FlameNodeHealth {
+            not_ready_timeout: DEFAULT_NODE_NOT_READY_TIMEOUT,
+            lost_timeout: DEFAULT_NODE_LOST_TIMEOUT,
         }
     }
 }
@@ -442,21 +1489,398 @@ impl TryFrom<FlameTlsYaml> for FlameTls {
             cert_file,
             key_file,
             ca_file: yaml.ca_file,
+            policy: None,
         })
     }
 }
 
-impl TryFrom<FlameCacheYaml> for FlameCache {
+impl TryFrom<FlameTlsPolicyYaml> for FlameTlsPolicy {
     type Error = FlameError;
-    fn try_from(cache: FlameCacheYaml) -> Result<Self, Self::Error> {
-        let tls = cache.tls.map(FlameTls::try_from).transpose()?;
+    fn try_from(yaml: FlameTlsPolicyYaml) -> Result<Self, Self::Error> {
+        let min_version = match yaml.min_version.as_deref() {
+            None | Some("1.2") => TlsVersion::V1_2,
+            Some("1.3") => TlsVersion::V1_3,
+            Some(v) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "invalid tls_policy.min_version <{v}>, expected \"1.2\" or \"1.3\""
+                )))
+            }
+        };
 
-        Ok(FlameCache {
-            endpoint: cache
-                .endpoint
-                .unwrap_or(DEFAULT_FLAME_CACHE_ENDPOINT.to_string()),
-            network_interface: cache
-                .network_interface
+        let policy = FlameTlsPolicy {
+            min_version,
+            cipher_suites: yaml.cipher_suites.unwrap_or_default(),
+            fips: yaml.fips.unwrap_or(false),
+        };
+
+        // Validate the cipher suites at startup instead of at the first handshake.
+        crate::tls::crypto_provider(&policy)?;
+
+        Ok(policy)
+    }
+}
+
+impl TryFrom<FlameEncryptionYaml> for FlameEncryption {
+    type Error = FlameError;
+    fn try_from(yaml: FlameEncryptionYaml) -> Result<Self, Self::Error> {
+        let first_key = yaml
+            .keys
+            .first()
+            .ok_or_else(|| FlameError::InvalidConfig("encryption.keys is required".to_string()))?;
+        let active_key = yaml.active_key.unwrap_or(first_key.id.clone());
+
+        let mut keys = vec![];
+        for key in yaml.keys {
+            if key.id.is_empty() || key.id.len() > u8::MAX as usize {
+                return Err(FlameError::InvalidConfig(format!(
+                    "invalid encryption key id <{}>",
+                    key.id
+                )));
+            }
+            if keys.iter().any(|k: &FlameEncryptionKey| k.id == key.id) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "duplicated encryption key <{}>",
+                    key.id
+                )));
+            }
+            keys.push(FlameEncryptionKey {
+                id: key.id,
+                key_file: key.key_file,
+            });
+        }
+
+        if !keys.iter().any(|k| k.id == active_key) {
+            return Err(FlameError::InvalidConfig(format!(
+                "active encryption key <{active_key}> not found"
+            )));
+        }
+
+        Ok(FlameEncryption { active_key, keys })
+    }
+}
+
+impl TryFrom<FlamePathsYaml> for FlamePaths {
+    type Error = FlameError;
+    fn try_from(yaml: FlamePathsYaml) -> Result<Self, Self::Error> {
+        let defaults = FlamePaths::default();
+        let path = |name: &str, value: Option<String>, default: PathBuf| match value {
+            None => Ok(default),
+            Some(value) if Path::new(&value).is_absolute() => Ok(PathBuf::from(value)),
+            Some(value) => Err(FlameError::InvalidConfig(format!(
+                "paths.{name} <{value}> must be an absolute path"
+            ))),
+        };
+
+        Ok(FlamePaths {
+            work: path("work_dir", yaml.work_dir, defaults.work)?,
+            events: path("events_dir", yaml.events_dir, defaults.events)?,
+            cache: path("cache_dir", yaml.cache_dir, defaults.cache)?,
+            logs: path("log_dir", yaml.log_dir, defaults.logs)?,
+            applications: path(
+                "applications_dir",
+                yaml.applications_dir,
+                defaults.applications,
+            )?,
+            sockets: path("socket_dir", yaml.socket_dir, defaults.sockets)?,
+            scratch: path("scratch_dir", yaml.scratch_dir, defaults.scratch)?,
+        })
+    }
+}
+
+impl TryFrom<FlameBackupYaml> for FlameBackup {
+    type Error = FlameError;
+    fn try_from(yaml: FlameBackupYaml) -> Result<Self, Self::Error> {
+        if yaml.path.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "backup.path is required".to_string(),
+            ));
+        }
+
+        let interval = yaml.interval.unwrap_or(DEFAULT_BACKUP_INTERVAL);
+        let retention = yaml.retention.unwrap_or(DEFAULT_BACKUP_RETENTION);
+        if interval == 0 || retention == 0 {
+            return Err(FlameError::InvalidConfig(
+                "backup.interval and backup.retention must be positive".to_string(),
+            ));
+        }
+
+        Ok(FlameBackup {
+            path: yaml.path,
+            interval,
+            retention,
+        })
+    }
+}
+
+impl TryFrom<FlameHistoryYaml> for FlameHistory {
+    type Error = FlameError;
+    fn try_from(yaml: FlameHistoryYaml) -> Result<Self, Self::Error> {
+        if yaml.path.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "history.path is required".to_string(),
+            ));
+        }
+
+        let rounds = yaml.rounds.unwrap_or(DEFAULT_HISTORY_ROUNDS);
+        let retention = yaml.retention.unwrap_or(DEFAULT_HISTORY_RETENTION);
+        if rounds == 0 || retention == 0 {
+            return Err(FlameError::InvalidConfig(
+                "history.rounds and history.retention must be positive".to_string(),
+            ));
+        }
+
+        Ok(FlameHistory {
+            path: yaml.path,
+            rounds,
+            retention,
+        })
+    }
+}
+
+impl TryFrom<FlameExporterYaml> for FlameExporter {
+    type Error = FlameError;
+    fn try_from(yaml: FlameExporterYaml) -> Result<Self, Self::Error> {
+        let kind = match yaml.endpoint.split_once("://") {
+            Some(("http", _)) => ExporterKind::ClickHouse,
+            Some(("postgres" | "postgresql", _)) => ExporterKind::Timescale,
+            _ => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "unsupported exporter.endpoint <{}>, expected http:// or postgres://",
+                    yaml.endpoint
+                )))
+            }
+        };
+
+        let table = yaml
+            .table
+            .unwrap_or_else(|| DEFAULT_EXPORTER_TABLE.to_string());
+        if table.is_empty()
+            || !table
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            return Err(FlameError::InvalidConfig(format!(
+                "invalid exporter.table <{table}>"
+            )));
+        }
+
+        let batch_size = yaml.batch_size.unwrap_or(DEFAULT_EXPORTER_BATCH_SIZE);
+        let flush_interval = yaml
+            .flush_interval
+            .unwrap_or(DEFAULT_EXPORTER_FLUSH_INTERVAL);
+        let buffer_size = yaml.buffer_size.unwrap_or(DEFAULT_EXPORTER_BUFFER_SIZE);
+        if batch_size == 0 || flush_interval == 0 {
+            return Err(FlameError::InvalidConfig(
+                "exporter.batch_size and exporter.flush_interval must be positive".to_string(),
+            ));
+        }
+        if buffer_size < batch_size {
+            return Err(FlameError::InvalidConfig(
+                "exporter.buffer_size must not be less than exporter.batch_size".to_string(),
+            ));
+        }
+
+        Ok(FlameExporter {
+            kind,
+            endpoint: yaml.endpoint,
+            table,
+            batch_size,
+            flush_interval,
+            max_retries: yaml.max_retries.unwrap_or(DEFAULT_EXPORTER_MAX_RETRIES),
+            buffer_size,
+        })
+    }
+}
+
+impl TryFrom<FlameAdminYaml> for FlameAdmin {
+    type Error = FlameError;
+    fn try_from(yaml: FlameAdminYaml) -> Result<Self, Self::Error> {
+        if yaml.token_file.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "admin.token_file is required".to_string(),
+            ));
+        }
+
+        Ok(FlameAdmin {
+            token_file: yaml.token_file,
+        })
+    }
+}
+
+impl TryFrom<FlameJoinYaml> for FlameJoin {
+    type Error = FlameError;
+    fn try_from(yaml: FlameJoinYaml) -> Result<Self, Self::Error> {
+        if yaml.secret_file.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "join.secret_file is required".to_string(),
+            ));
+        }
+
+        Ok(FlameJoin {
+            secret_file: yaml.secret_file,
+            token_ttl: yaml
+                .token_ttl
+                .filter(|ttl| *ttl > 0)
+                .unwrap_or(DEFAULT_JOIN_TOKEN_TTL),
+        })
+    }
+}
+
+impl TryFrom<FlameNodeHealthYaml> for FlameNodeHealth {
+    type Error = FlameError;
+    fn try_from(yaml: FlameNodeHealthYaml) -> Result<Self, Self::Error> {
+        let not_ready_timeout = yaml
+            .not_ready_timeout
+            .unwrap_or(DEFAULT_NODE_NOT_READY_TIMEOUT);
+        let lost_timeout = yaml.lost_timeout.unwrap_or(DEFAULT_NODE_LOST_TIMEOUT);
+        if not_ready_timeout == 0 {
+            return Err(FlameError::InvalidConfig(
+                "node_health.not_ready_timeout must be positive".to_string(),
+            ));
+        }
+        if lost_timeout <= not_ready_timeout {
+            return Err(FlameError::InvalidConfig(
+                "node_health.lost_timeout must be greater than node_health.not_ready_timeout"
+                    .to_string(),
+            ));
+        }
+
+        Ok(FlameNodeHealth {
+            not_ready_timeout,
+            lost_timeout,
+        })
+    }
+}
+
+impl TryFrom<FlameSchedulerYaml> for FlameScheduler {
+    type Error = FlameError;
+    fn try_from(yaml: FlameSchedulerYaml) -> Result<Self, Self::Error> {
+        let Some(plugins) = yaml.plugins else {
+            return Ok(FlameScheduler::default());
+        };
+        if plugins.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "scheduler.plugins must not be empty".to_string(),
+            ));
+        }
+        for (i, name) in plugins.iter().enumerate() {
+            if plugins[..i].contains(name) {
+                return Err(FlameError::InvalidConfig(format!(
+                    "duplicated plugin <{name}> in scheduler.plugins"
+                )));
+            }
+        }
+
+        Ok(FlameScheduler { plugins })
+    }
+}
+
+impl TryFrom<FlameMemoryYaml> for FlameMemory {
+    type Error = FlameError;
+    fn try_from(yaml: FlameMemoryYaml) -> Result<Self, Self::Error> {
+        let soft_limit = yaml
+            .soft_limit
+            .as_deref()
+            .map(parse_memory_size)
+            .transpose()?;
+        let hard_limit = yaml
+            .hard_limit
+            .as_deref()
+            .map(parse_memory_size)
+            .transpose()?;
+        if soft_limit.is_none() && hard_limit.is_none() {
+            return Err(FlameError::InvalidConfig(
+                "memory.soft_limit or memory.hard_limit is required".to_string(),
+            ));
+        }
+        if let (Some(soft), Some(hard)) = (soft_limit, hard_limit) {
+            if soft >= hard {
+                return Err(FlameError::InvalidConfig(
+                    "memory.soft_limit must be less than memory.hard_limit".to_string(),
+                ));
+            }
+        }
+
+        let interval = yaml.interval.unwrap_or(DEFAULT_MEMORY_CHECK_INTERVAL);
+        if interval == 0 {
+            return Err(FlameError::InvalidConfig(
+                "memory.interval must be positive".to_string(),
+            ));
+        }
+
+        Ok(FlameMemory {
+            soft_limit,
+            hard_limit,
+            interval,
+        })
+    }
+}
+
+impl TryFrom<FlameRuntimesYaml> for FlameRuntimes {
+    type Error = FlameError;
+    fn try_from(yaml: FlameRuntimesYaml) -> Result<Self, Self::Error> {
+        let threads = [
+            ("frontend", yaml.frontend),
+            ("backend", yaml.backend),
+            ("scheduler", yaml.scheduler),
+            ("provider", yaml.provider),
+        ];
+        if let Some((name, _)) = threads.iter().find(|(_, n)| *n == Some(0)) {
+            return Err(FlameError::InvalidConfig(format!(
+                "runtimes.{name} must be positive"
+            )));
+        }
+
+        Ok(FlameRuntimes {
+            frontend: yaml.frontend.unwrap_or(DEFAULT_RUNTIME_THREADS),
+            backend: yaml.backend,
+            scheduler: yaml.scheduler.unwrap_or(DEFAULT_RUNTIME_THREADS),
+            provider: yaml.provider.unwrap_or(DEFAULT_RUNTIME_THREADS),
+            metrics_interval: yaml.metrics_interval.filter(|interval| *interval > 0),
+        })
+    }
+}
+
+impl TryFrom<FlameNodeConfigYaml> for FlameNodeConfig {
+    type Error = FlameError;
+    fn try_from(yaml: FlameNodeConfigYaml) -> Result<Self, Self::Error> {
+        if yaml.name.is_empty() {
+            return Err(FlameError::InvalidConfig(
+                "nodes.name is required".to_string(),
+            ));
+        }
+        let cache = yaml.cache;
+        let cache_max_memory = cache
+            .as_ref()
+            .and_then(|c| c.max_memory.as_deref())
+            .map(parse_memory_size)
+            .transpose()?;
+
+        Ok(FlameNodeConfig {
+            name: yaml.name,
+            config: NodeConfig {
+                slots: yaml.slots,
+                labels: yaml.labels.unwrap_or_default(),
+                log_level: yaml.log_level,
+                cache_max_memory,
+                cache_max_objects: cache.and_then(|c| c.max_objects),
+                prefetch: yaml.prefetch.unwrap_or_default(),
+            },
+        })
+    }
+}
+
+impl TryFrom<FlameCacheYaml> for FlameCache {
+    type Error = FlameError;
+    fn try_from(cache: FlameCacheYaml) -> Result<Self, Self::Error> {
+        let tls = cache.tls.map(FlameTls::try_from).transpose()?;
+
+        Ok(FlameCache {
+            endpoint: cache
+                .endpoint
+                .unwrap_or(DEFAULT_FLAME_CACHE_ENDPOINT.to_string()),
+            network_interface: cache
+                .network_interface
                 .unwrap_or(DEFAULT_FLAME_CACHE_NETWORK_INTERFACE.to_string()),
             storage: cache.storage,
             eviction: cache
@@ -464,6 +1888,9 @@ impl TryFrom<FlameCacheYaml> for FlameCache {
                 .map(FlameEviction::try_from)
                 .transpose()?
                 .unwrap_or_default(),
+            orphan_grace_period: cache
+                .orphan_grace_period
+                .unwrap_or(DEFAULT_ORPHAN_GRACE_PERIOD),
             tls,
         })
     }
@@ -504,10 +1931,22 @@ cluster:
   slot: "cpu=1,mem=1g"
   policy: priority
   storage: sqlite://flame.db
+  max_task_attempts: 5
+  task_timeout: 600
   executors:
     shim: host
+    idle_timeout: 300
+    preempt_grace_period: 30
+    user: flame
+    registry:
+      insecure: ["localhost:5000"]
   limits:
     max_executors: 10
+  shuffle:
+    max_executors: 2
+    cooldown: 30
+  slo:
+    dispatch_latency: 5000
         "#;
 
         let tmp_dir = TempDir::new().unwrap();
@@ -523,7 +1962,540 @@ cluster:
         assert_eq!(ctx.cluster.policy, "priority");
         assert_eq!(ctx.cluster.storage, "sqlite://flame.db");
         assert_eq!(ctx.cluster.executors.shim, Shim::Host);
+        assert_eq!(ctx.cluster.executors.idle_timeout, Some(300));
+        assert_eq!(ctx.cluster.executors.preempt_grace_period, Some(30));
+        assert_eq!(ctx.cluster.executors.user.as_deref(), Some("flame"));
+        assert_eq!(ctx.cluster.executors.group, None);
+        assert_eq!(
+            ctx.cluster.executors.registry.insecure,
+            vec!["localhost:5000".to_string()]
+        );
+        assert_eq!(ctx.cluster.executors.registry.auth_file, None);
+        assert_eq!(ctx.cluster.max_task_attempts, 5);
+        assert_eq!(ctx.cluster.task_timeout, Some(600));
+        assert_eq!(ctx.cluster.task_priority, 0);
         assert_eq!(ctx.cluster.limits.max_executors, 10);
+        assert_eq!(ctx.cluster.shuffle.max_executors, Some(2));
+        assert_eq!(ctx.cluster.shuffle.cooldown, 30);
+        assert!(!ctx.cluster.shuffle.dry_run);
+        assert_eq!(ctx.cluster.slo.bind_latency, None);
+        assert_eq!(ctx.cluster.slo.dispatch_latency, Some(5000));
+        assert!(ctx.cluster.encryption.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_encryption() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  encryption:
+    active_key: key-2
+    keys:
+      - id: key-1
+        key_file: /etc/flame/keys/key-1
+      - id: key-2
+        key_file: /etc/flame/keys/key-2
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let encryption = ctx.cluster.encryption.unwrap();
+        assert_eq!(encryption.active_key, "key-2");
+        assert_eq!(encryption.keys.len(), 2);
+        assert_eq!(encryption.keys[0].key_file, "/etc/flame/keys/key-1");
+
+        let unknown_key = context_string.replace("active_key: key-2", "active_key: key-3");
+        fs::write(&tmp_file, unknown_key).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_paths() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+paths:
+  work_dir: /data/flame/work
+  log_dir: /var/log/flame
+  applications_dir: /etc/flame/applications.d
+  socket_dir: /run/flame
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        assert_eq!(ctx.paths.work, PathBuf::from("/data/flame/work"));
+        assert_eq!(ctx.paths.logs, PathBuf::from("/var/log/flame"));
+        assert_eq!(
+            ctx.paths.applications,
+            PathBuf::from("/etc/flame/applications.d")
+        );
+        assert_eq!(ctx.paths.sockets, PathBuf::from("/run/flame"));
+
+        let relative = context_string.replace("/var/log/flame", "logs");
+        fs::write(&tmp_file, relative).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        let paths = FlamePaths {
+            work: tmp_dir.path().join("work"),
+            events: tmp_dir.path().join("events"),
+            cache: tmp_dir.path().join("cache"),
+            logs: tmp_dir.path().join("logs"),
+            applications: tmp_dir.path().join("applications.d"),
+            sockets: tmp_dir.path().join("sockets"),
+            scratch: tmp_dir.path().join("scratch"),
+        };
+        paths.create_dirs()?;
+        assert!(paths.work.is_dir() && paths.events.is_dir());
+        assert!(paths.cache.is_dir() && paths.logs.is_dir());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_backup() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  backup:
+    path: /var/lib/flame/backups
+    retention: 3
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let backup = ctx.cluster.backup.unwrap();
+        assert_eq!(backup.path, "/var/lib/flame/backups");
+        assert_eq!(backup.interval, DEFAULT_BACKUP_INTERVAL);
+        assert_eq!(backup.retention, 3);
+
+        let no_retention = context_string.replace("retention: 3", "retention: 0");
+        fs::write(&tmp_file, no_retention).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_history() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  history:
+    path: /var/lib/flame/history
+    rounds: 10
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let history = ctx.cluster.history.unwrap();
+        assert_eq!(history.path, "/var/lib/flame/history");
+        assert_eq!(history.rounds, 10);
+        assert_eq!(history.retention, DEFAULT_HISTORY_RETENTION);
+
+        let no_rounds = context_string.replace("rounds: 10", "rounds: 0");
+        fs::write(&tmp_file, no_rounds).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_exporter() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  exporter:
+    endpoint: "http://clickhouse:8123"
+    batch_size: 500
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let exporter = ctx.cluster.exporter.unwrap();
+        assert_eq!(exporter.kind, ExporterKind::ClickHouse);
+        assert_eq!(exporter.table, DEFAULT_EXPORTER_TABLE);
+        assert_eq!(exporter.batch_size, 500);
+        assert_eq!(exporter.flush_interval, DEFAULT_EXPORTER_FLUSH_INTERVAL);
+
+        let timescale =
+            context_string.replace("http://clickhouse:8123", "postgres://timescale/flame");
+        fs::write(&tmp_file, timescale).map_err(|e| FlameError::Internal(e.to_string()))?;
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        assert_eq!(ctx.cluster.exporter.unwrap().kind, ExporterKind::Timescale);
+
+        for invalid in [
+            context_string.replace("http://", "https://"),
+            context_string.replace("batch_size: 500", "batch_size: 0"),
+            context_string.replace("batch_size: 500", "table: \"tasks; drop\""),
+        ] {
+            fs::write(&tmp_file, invalid).map_err(|e| FlameError::Internal(e.to_string()))?;
+            assert!(
+                FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))
+                    .is_err()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_join() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  join:
+    secret_file: /etc/flame/join.secret
+  admin:
+    token_file: /etc/flame/admin.token
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let join = ctx.cluster.join.unwrap();
+        assert_eq!(join.secret_file, "/etc/flame/join.secret");
+        assert_eq!(join.token_ttl, DEFAULT_JOIN_TOKEN_TTL);
+        assert_eq!(
+            ctx.cluster.admin.unwrap().token_file,
+            "/etc/flame/admin.token"
+        );
+
+        let no_secret = context_string.replace("/etc/flame/join.secret", "\"\"");
+        fs::write(&tmp_file, no_secret).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_memory() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  memory:
+    soft_limit: 2G
+    hard_limit: 3G
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let memory = ctx.cluster.memory.unwrap();
+        assert_eq!(memory.soft_limit, Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(memory.hard_limit, Some(3 * 1024 * 1024 * 1024));
+        assert_eq!(memory.interval, DEFAULT_MEMORY_CHECK_INTERVAL);
+
+        let inverted = context_string.replace("hard_limit: 3G", "hard_limit: 1G");
+        fs::write(&tmp_file, inverted).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_node_health() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  node_health:
+    not_ready_timeout: 20
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        assert_eq!(ctx.cluster.node_health.not_ready_timeout, 20);
+        assert_eq!(
+            ctx.cluster.node_health.lost_timeout,
+            DEFAULT_NODE_LOST_TIMEOUT
+        );
+
+        let inverted = context_string.replace(
+            "not_ready_timeout: 20",
+            "not_ready_timeout: 20\n    lost_timeout: 10",
+        );
+        fs::write(&tmp_file, inverted).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_scheduler_plugins() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  scheduler:
+    plugins: [fairshare, shim]
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        assert_eq!(ctx.cluster.scheduler.plugins, vec!["fairshare", "shim"]);
+        assert_eq!(
+            FlameCluster::default().scheduler.plugins,
+            DEFAULT_SCHEDULER_PLUGINS
+        );
+
+        for plugins in ["[]", "[fairshare, fairshare]"] {
+            let invalid = context_string.replace("[fairshare, shim]", plugins);
+            fs::write(&tmp_file, invalid).map_err(|e| FlameError::Internal(e.to_string()))?;
+            assert!(
+                FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))
+                    .is_err()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_logs() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  executors:
+    logs:
+      max_size: 10M
+      max_age: 86400
+      compress: false
+      max_disk: 1G
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let logs = ctx.cluster.executors.logs;
+        assert_eq!(logs.max_size, 10 * 1024 * 1024);
+        assert_eq!(logs.max_age, Some(86400));
+        assert_eq!(logs.max_files, DEFAULT_LOG_MAX_FILES);
+        assert!(!logs.compress);
+        assert_eq!(logs.max_disk, Some(1024 * 1024 * 1024));
+
+        let default = FlameClusterContext::default().cluster.executors.logs;
+        assert_eq!(default.max_size, 100 * 1024 * 1024);
+        assert!(default.compress);
+        assert_eq!(default.max_disk, None);
+
+        let capped = context_string.replace("max_disk: 1G", "max_disk: 1M");
+        fs::write(&tmp_file, capped).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_runtimes() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  runtimes:
+    frontend: 4
+    metrics_interval: 60
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        let runtimes = ctx.cluster.runtimes;
+        assert_eq!(runtimes.frontend, 4);
+        assert_eq!(runtimes.scheduler, DEFAULT_RUNTIME_THREADS);
+        assert_eq!(runtimes.metrics_interval, Some(60));
+        assert_eq!(runtimes.backend_threads(16), 10);
+        assert_eq!(runtimes.backend_threads(4), 1);
+
+        let zero = context_string.replace("frontend: 4", "frontend: 0");
+        fs::write(&tmp_file, zero).map_err(|e| FlameError::Internal(e.to_string()))?;
+        assert!(
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string())).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flame_context_with_node_configs() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  nodes:
+    - name: "*"
+      log_level: info
+      labels: [linux]
+    - name: "gpu-*"
+      slots: 4
+      labels: [gpu]
+      prefetch: ["llm-*"]
+      cache:
+        max_memory: 4G
+    - name: gpu-2
+      log_level: debug
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+
+        fs::write(&tmp_file, context_string).map_err(|e| FlameError::Internal(e.to_string()))?;
+
+        let ctx = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))?;
+        assert_eq!(ctx.cluster.nodes.len(), 3);
+
+        let config = ctx.cluster.node_config("gpu-2");
+        assert_eq!(config.slots, Some(4));
+        assert_eq!(config.labels, vec!["linux", "gpu"]);
+        assert_eq!(config.log_level.as_deref(), Some("debug"));
+        assert_eq!(config.cache_max_memory, Some(4 * 1024 * 1024 * 1024));
+        assert!(config.prefetches("llm-7b"));
+        assert!(!config.prefetches("etl"));
+
+        let config = ctx.cluster.node_config("cpu-1");
+        assert_eq!(config.slots, None);
+        assert_eq!(config.labels, vec!["linux"]);
+        assert_eq!(config.log_level.as_deref(), Some("info"));
+        assert!(!config.prefetches("llm-7b"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_name() {
+        assert!(matches_name("node-1", "node-1"));
+        assert!(!matches_name("node-1", "node-10"));
+        assert!(matches_name("*", "node-1"));
+        assert!(matches_name("node-*", "node-1"));
+        assert!(!matches_name("node-*", "gpu-1"));
+        assert!(matches_name("*-gpu-*", "rack1-gpu-2"));
+        assert!(matches_name("a*a", "aa"));
+        assert!(!matches_name("a*a", "a"));
+    }
+
+    #[test]
+    fn test_flame_context_with_tls_policy() -> Result<(), FlameError> {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "https://flame-session-manager:8080"
+  tls:
+    cert_file: /etc/flame/tls/server.crt
+    key_file: /etc/flame/tls/server.key
+    ca_file: /etc/flame/tls/ca.crt
+cache:
+  endpoint: "grpcs://127.0.0.1:9090"
+  tls:
+    cert_file: /etc/flame/tls/cache.crt
+    key_file: /etc/flame/tls/cache.key
+tls_policy:
+  min_version: "1.3"
+  cipher_suites:
+    - TLS13_AES_256_GCM_SHA384
+  fips: true
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+        let from_file = |contents: &str| {
+            fs::write(&tmp_file, contents).map_err(|e| FlameError::Internal(e.to_string()))?;
+            FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()))
+        };
+
+        let ctx = from_file(context_string)?;
+        let policy = ctx.cluster.tls.unwrap().policy.unwrap();
+        assert_eq!(policy.min_version, TlsVersion::V1_3);
+        assert_eq!(policy.cipher_suites, vec!["TLS13_AES_256_GCM_SHA384"]);
+        assert!(policy.fips);
+        assert!(ctx.cache.unwrap().tls.unwrap().policy.is_some());
+
+        let invalid_version =
+            context_string.replace(r#"min_version: "1.3""#, r#"min_version: "1.1""#);
+        assert!(from_file(&invalid_version).is_err());
+
+        let unknown_cipher =
+            context_string.replace("TLS13_AES_256_GCM_SHA384", "TLS_RSA_WITH_RC4_128_SHA");
+        assert!(from_file(&unknown_cipher).is_err());
+
+        let no_cipher =
+            context_string.replace("TLS13_AES_256_GCM_SHA384", "TLS13_CHACHA20_POLY1305_SHA256");
+        assert!(from_file(&no_cipher).is_err());
+
+        let no_tls = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+tls_policy:
+  min_version: "1.2"
+        "#;
+        assert!(from_file(no_tls).is_err());
 
         Ok(())
     }
@@ -623,6 +2595,7 @@ cache:
         assert_eq!(cache.eviction.policy, DEFAULT_EVICTION_POLICY);
         assert_eq!(cache.eviction.max_memory, 1024 * 1024 * 1024); // 1G in bytes
         assert_eq!(cache.eviction.max_objects, None);
+        assert_eq!(cache.orphan_grace_period, DEFAULT_ORPHAN_GRACE_PERIOD);
 
         Ok(())
     }
@@ -872,6 +2845,48 @@ cache:
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_flame_context_with_unknown_field() {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  max_task_attempt: 5
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+        fs::write(&tmp_file, context_string).unwrap();
+
+        let result = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()));
+        let Err(FlameError::InvalidConfig(msg)) = result else {
+            panic!("expected InvalidConfig error");
+        };
+        assert!(msg.contains("unknown field `max_task_attempt`"), "{msg}");
+        assert!(msg.contains("line 5"), "{msg}");
+    }
+
+    #[test]
+    fn test_flame_context_with_type_mismatch() {
+        let context_string = r#"---
+cluster:
+  name: flame
+  endpoint: "http://flame-session-manager:8080"
+  schedule_interval: fast
+        "#;
+
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_file = tmp_dir.path().join("flame-cluster.yaml");
+        fs::write(&tmp_file, context_string).unwrap();
+
+        let result = FlameClusterContext::from_file(Some(tmp_file.to_string_lossy().to_string()));
+        let Err(FlameError::InvalidConfig(msg)) = result else {
+            panic!("expected InvalidConfig error");
+        };
+        assert!(msg.contains("schedule_interval"), "{msg}");
+        assert!(msg.contains("line 5"), "{msg}");
+    }
+
     #[test]
     fn test_flame_context_with_invalid_max_memory_number() {
         // Test invalid number should fail
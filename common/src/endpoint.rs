@@ -0,0 +1,159 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The transport between the executors and their instances.
+//!
+//! The endpoint of an instance, i.e. `FLAME_INSTANCE_ENDPOINT`, is always a
+//! path in the socket directory. On Unix, the instance listens on a Unix
+//! domain socket at the path; on the other platforms, it listens on a TCP
+//! loopback port and writes the address, e.g. `127.0.0.1:50123`, to the path,
+//! so the executor connects to the instance the same way on all platforms.
+
+use std::net::SocketAddr;
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+
+#[cfg(unix)]
+use hyper_util::rt::TokioIo;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+#[cfg(unix)]
+use tonic::transport::Uri;
+use tonic::transport::{Channel, Endpoint};
+#[cfg(unix)]
+use tower::service_fn;
+
+use crate::FlameError;
+
+/// The address the instance listens on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InstanceAddr {
+    /// The Unix domain socket at the endpoint.
+    #[cfg(unix)]
+    Uds(PathBuf),
+    /// The TCP loopback address written to the endpoint.
+    Tcp(SocketAddr),
+}
+
+impl InstanceAddr {
+    /// Resolves the address of the instance by its endpoint, which is created
+    /// by the instance once it listens.
+    pub fn resolve(endpoint: &Path) -> Result<Self, FlameError> {
+        let unavailable = |e: std::io::Error| {
+            FlameError::Network(format!(
+                "instance endpoint <{}> is not available: {e}",
+                endpoint.display()
+            ))
+        };
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+
+            let metadata = std::fs::metadata(endpoint).map_err(unavailable)?;
+            if metadata.file_type().is_socket() {
+                return Ok(Self::Uds(endpoint.to_path_buf()));
+            }
+        }
+
+        let content = std::fs::read_to_string(endpoint).map_err(unavailable)?;
+        let addr: SocketAddr = content.trim().parse().map_err(|e| {
+            FlameError::InvalidConfig(format!(
+                "invalid instance address <{}> in <{}>: {e}",
+                content.trim(),
+                endpoint.display()
+            ))
+        })?;
+        if !addr.ip().is_loopback() {
+            return Err(FlameError::InvalidConfig(format!(
+                "instance address <{addr}> is not a loopback address"
+            )));
+        }
+
+        Ok(Self::Tcp(addr))
+    }
+}
+
+/// Connects to the gRPC services of the instance at the endpoint.
+pub async fn connect(endpoint: &Path) -> Result<Channel, FlameError> {
+    let channel = match InstanceAddr::resolve(endpoint)? {
+        #[cfg(unix)]
+        InstanceAddr::Uds(path) => {
+            // The URI is ignored by the connector of the socket.
+            Endpoint::try_from("http://[::]:50051")
+                .map_err(|e| FlameError::Internal(e.to_string()))?
+                .connect_with_connector(service_fn(move |_: Uri| {
+                    let path = path.clone();
+                    async move {
+                        UnixStream::connect(path)
+                            .await
+                            .map(TokioIo::new)
+                            .map_err(std::io::Error::other)
+                    }
+                }))
+                .await
+        }
+        InstanceAddr::Tcp(addr) => {
+            Endpoint::from_shared(format!("http://{addr}"))
+                .map_err(|e| FlameError::Internal(e.to_string()))?
+                .connect()
+                .await
+        }
+    };
+
+    channel.map_err(|e| {
+        FlameError::Network(format!(
+            "failed to connect to instance at <{}>: {e}",
+            endpoint.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_tcp() {
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = dir.path().join("exec-1.sock");
+
+        std::fs::write(&endpoint, "127.0.0.1:50123\n").unwrap();
+        assert_eq!(
+            InstanceAddr::resolve(&endpoint).unwrap(),
+            InstanceAddr::Tcp("127.0.0.1:50123".parse().unwrap())
+        );
+
+        std::fs::write(&endpoint, "10.0.0.1:50123").unwrap();
+        assert!(InstanceAddr::resolve(&endpoint).is_err());
+
+        std::fs::write(&endpoint, "").unwrap();
+        assert!(InstanceAddr::resolve(&endpoint).is_err());
+
+        assert!(InstanceAddr::resolve(&dir.path().join("exec-2.sock")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_uds() {
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = dir.path().join("exec-1.sock");
+        let _listener = std::os::unix::net::UnixListener::bind(&endpoint).unwrap();
+
+        assert_eq!(
+            InstanceAddr::resolve(&endpoint).unwrap(),
+            InstanceAddr::Uds(endpoint)
+        );
+    }
+}
@@ -12,15 +12,19 @@ limitations under the License.
 */
 
 pub mod apis;
+pub mod backup;
 pub mod ctx;
+pub mod endpoint;
 pub mod storage;
+pub mod tls;
 
 use std::string::FromUtf8Error;
 
 use prost::UnknownEnumValue;
 use serde_json::json;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
 use thiserror::Error;
 use time::macros::format_description;
 use tonic::Status;
@@ -29,6 +33,7 @@ use tracing_appender::rolling;
 use tracing_subscriber::filter::{FromEnvError, ParseError};
 use tracing_subscriber::fmt::time::LocalTime;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::EnvFilter;
 
 use crate::apis::{ApplicationAttributes, ApplicationSchema};
 
@@ -60,6 +65,18 @@ pub enum FlameError {
 
     #[error("{0}")]
     VersionMismatch(String),
+
+    /// The object was updated concurrently, i.e. its version is not the expected one.
+    #[error("{0}")]
+    Conflict(String),
+
+    /// The request is rejected to protect the service, e.g. under memory pressure.
+    #[error("{0}")]
+    ResourceExhausted(String),
+
+    /// The caller is not authenticated, e.g. a node without a valid credential.
+    #[error("{0}")]
+    Unauthenticated(String),
 }
 
 impl From<stdng::Error> for FlameError {
@@ -73,6 +90,9 @@ impl From<FlameError> for Status {
         match value {
             FlameError::NotFound(msg) => Status::not_found(msg),
             FlameError::AlreadyExist(msg) => Status::already_exists(msg),
+            FlameError::Conflict(msg) => Status::aborted(msg),
+            FlameError::ResourceExhausted(msg) => Status::resource_exhausted(msg),
+            FlameError::Unauthenticated(msg) => Status::unauthenticated(msg),
             FlameError::InvalidConfig(msg) | FlameError::InvalidState(msg) => {
                 Status::invalid_argument(msg)
             }
@@ -129,11 +149,27 @@ pub fn new_async_ptr<T>(t: T) -> AsyncMutexPtr<T> {
 
 pub const FLAME_HOME: &str = "FLAME_HOME";
 pub const FLAME_LOG: &str = "FLAME_LOG";
-pub const FLAME_WORKING_DIRECTORY: &str = "/tmp/flame";
 pub const FLAME_INSTANCE_ENDPOINT: &str = "FLAME_INSTANCE_ENDPOINT";
 pub const FLAME_CACHE_ENDPOINT: &str = "FLAME_CACHE_ENDPOINT";
 pub const FLAME_ENDPOINT: &str = "FLAME_ENDPOINT";
 pub const FLAME_CA_FILE: &str = "FLAME_CA_FILE";
+/// The NUMA node of the instance of a topology-aware application.
+pub const FLAME_NUMA_NODE: &str = "FLAME_NUMA_NODE";
+/// The gRPC metadata of the trace ID generated by the SDK for the request
+/// creating a session or a task.
+pub const FLAME_TRACE_ID: &str = "x-flame-trace-id";
+/// The gRPC metadata of the admin token presented by the clients of the
+/// administrative RPCs, e.g. `CreateJoinToken`.
+pub const FLAME_ADMIN_TOKEN: &str = "x-flame-admin-token";
+/// The gRPC metadata of the node name presented by the executor managers in
+/// the backend RPCs.
+pub const FLAME_NODE_NAME: &str = "x-flame-node-name";
+/// The gRPC metadata of the node token presented by the executor managers in
+/// the backend RPCs.
+pub const FLAME_NODE_TOKEN: &str = "x-flame-node-token";
+/// The gRPC metadata of the executor generation presented by the executor
+/// managers in the backend RPCs of the executors.
+pub const FLAME_EXECUTOR_GENERATION: &str = "x-flame-executor-generation";
 
 /// Returns the system temporary directory path.
 /// This is cross-platform: /tmp on Unix, %TEMP% on Windows.
@@ -141,6 +177,24 @@ pub fn temp_dir() -> std::path::PathBuf {
     std::env::temp_dir()
 }
 
+/// Returns the working directory of Flame without `FLAME_HOME`, i.e. `flame`
+/// in the system temporary directory.
+pub fn working_directory() -> std::path::PathBuf {
+    temp_dir().join("flame")
+}
+
+/// The default installation directory of Flame, i.e. the default prefix of
+/// `flmadm install`.
+pub const DEFAULT_FLAME_HOME: &str = "/usr/local/flame";
+
+/// Returns the installation directory of Flame, i.e. `FLAME_HOME` set by the
+/// installation, otherwise the default one.
+pub fn flame_home() -> std::path::PathBuf {
+    std::env::var(FLAME_HOME)
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::path::PathBuf::from(DEFAULT_FLAME_HOME))
+}
+
 /// Creates a SQLite URL pointing to a temporary database file.
 /// The path is cross-platform compatible.
 ///
@@ -171,12 +225,41 @@ pub fn temp_db_path(prefix: &str) -> String {
     temp_path.to_string_lossy().to_string()
 }
 
-pub fn init_logger(component: Option<&str>) -> Result<Option<WorkerGuard>, FlameError> {
-    let filter = tracing_subscriber::EnvFilter::from_default_env()
+type LogFilterReloader = Box<dyn Fn(EnvFilter) -> Result<(), FlameError> + Send + Sync>;
+
+/// Reloads the filter of the logger initialized by `init_logger`.
+static LOG_FILTER_RELOADER: OnceLock<LogFilterReloader> = OnceLock::new();
+
+/// The filter of the logs by the directives, or by `RUST_LOG` if None.
+fn log_filter(directives: Option<&str>) -> Result<EnvFilter, FlameError> {
+    let filter = match directives {
+        Some(directives) => EnvFilter::try_new(directives)?,
+        None => EnvFilter::from_default_env(),
+    };
+
+    Ok(filter
         .add_directive("h2=error".parse()?)
         .add_directive("hyper_util=error".parse()?)
         .add_directive("sqlx=warn".parse()?)
-        .add_directive("tower=error".parse()?);
+        .add_directive("tower=error".parse()?))
+}
+
+/// Changes the log level of the logger at runtime, e.g. "debug" or
+/// "flame=debug,info"; the one of `RUST_LOG` is restored if None.
+pub fn set_log_level(level: Option<&str>) -> Result<(), FlameError> {
+    let reload = LOG_FILTER_RELOADER
+        .get()
+        .ok_or(FlameError::Uninitialized("logger".to_string()))?;
+    reload(log_filter(level)?)
+}
+
+/// Initialize the logger; the logs of the component are also written into the
+/// log directory, which defaults to the one of `FlamePaths`.
+pub fn init_logger(
+    component: Option<&str>,
+    log_dir: Option<&Path>,
+) -> Result<Option<WorkerGuard>, FlameError> {
+    let filter = log_filter(None)?;
 
     let time_format = LocalTime::new(format_description!(
         "[hour repr:24]:[minute]:[second].[subsecond digits:3]"
@@ -193,23 +276,37 @@ pub fn init_logger(component: Option<&str>) -> Result<Option<WorkerGuard>, Flame
             let hostname = gethostname::gethostname().to_string_lossy().into_owned();
             let log_file = format!("{}-{}", name, hostname);
 
-            let log_dir = std::env::var(FLAME_HOME)
-                .map(|h| format!("{}/logs", h))
-                .unwrap_or_else(|_| format!("{}/logs", FLAME_WORKING_DIRECTORY));
+            let log_dir = log_dir
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| ctx::FlamePaths::default().logs);
 
             std::fs::create_dir_all(&log_dir)?;
 
             let file_appender = rolling::daily(&log_dir, log_file);
             let (non_blocking_file, guard) = tracing_appender::non_blocking(file_appender);
 
-            fmt_builder
+            let builder = fmt_builder
                 .with_writer(non_blocking_file.and(std::io::stdout))
-                .init();
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            let _ = LOG_FILTER_RELOADER.set(Box::new(move |filter| {
+                handle
+                    .reload(filter)
+                    .map_err(|e| FlameError::Internal(e.to_string()))
+            }));
+            builder.init();
 
             Ok(Some(guard))
         }
         None => {
-            fmt_builder.init();
+            let builder = fmt_builder.with_filter_reloading();
+            let handle = builder.reload_handle();
+            let _ = LOG_FILTER_RELOADER.set(Box::new(move |filter| {
+                handle
+                    .reload(filter)
+                    .map_err(|e| FlameError::Internal(e.to_string()))
+            }));
+            builder.init();
 
             Ok(None)
         }
@@ -347,5 +444,20 @@ mod tests {
         let status = Status::from(error);
         assert_eq!(status.code(), Code::InvalidArgument);
         assert_eq!(status.message(), "test");
+
+        let error = FlameError::Conflict("test".to_string());
+        let status = Status::from(error);
+        assert_eq!(status.code(), Code::Aborted);
+        assert_eq!(status.message(), "test");
+
+        let error = FlameError::ResourceExhausted("test".to_string());
+        let status = Status::from(error);
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(status.message(), "test");
+
+        let error = FlameError::Unauthenticated("test".to_string());
+        let status = Status::from(error);
+        assert_eq!(status.code(), Code::Unauthenticated);
+        assert_eq!(status.message(), "test");
     }
 }
@@ -0,0 +1,340 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! TLS listeners and clients restricted by the cluster TLS policy.
+//!
+//! The TLS support of tonic does not allow to restrict the protocol versions or
+//! cipher suites, so the services with a TLS policy build the rustls configs
+//! here and run the TLS handshake themselves.
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper_util::rt::TokioIo;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, NamedGroup, RootCertStore, ServerConfig, SupportedProtocolVersion};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tower::service_fn;
+
+use crate::ctx::{FlameTls, FlameTlsPolicy, TlsVersion};
+use crate::FlameError;
+
+const ALPN_H2: &[u8] = b"h2";
+static TLS13_ONLY: &[&SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+/// Returns the names of the cipher suites supported by Flame.
+pub fn supported_cipher_suites() -> Vec<&'static str> {
+    ring::default_provider()
+        .cipher_suites
+        .iter()
+        .filter_map(|suite| suite.suite().as_str())
+        .collect()
+}
+
+/// Builds the crypto provider with the cipher suites and key exchange groups
+/// permitted by the policy.
+pub fn crypto_provider(policy: &FlameTlsPolicy) -> Result<CryptoProvider, FlameError> {
+    let mut provider = ring::default_provider();
+
+    let supported = supported_cipher_suites();
+    for name in &policy.cipher_suites {
+        if !supported.contains(&name.as_str()) {
+            return Err(FlameError::InvalidConfig(format!(
+                "unsupported cipher suite <{name}>, supported: {}",
+                supported.join(", ")
+            )));
+        }
+    }
+
+    provider.cipher_suites.retain(|suite| {
+        let name = suite.suite().as_str().unwrap_or_default();
+        let version_permitted = match policy.min_version {
+            TlsVersion::V1_2 => true,
+            TlsVersion::V1_3 => suite.version() == &rustls::version::TLS13,
+        };
+        let cipher_permitted =
+            policy.cipher_suites.is_empty() || policy.cipher_suites.iter().any(|n| n == name);
+        // Only AES-GCM is FIPS-approved among the suites of the provider.
+        let fips_permitted = !policy.fips || name.contains("_AES_");
+
+        version_permitted && cipher_permitted && fips_permitted
+    });
+
+    if policy.fips {
+        provider
+            .kx_groups
+            .retain(|group| group.name() != NamedGroup::X25519);
+    }
+
+    if provider.cipher_suites.is_empty() {
+        return Err(FlameError::InvalidConfig(format!(
+            "no cipher suite is permitted by the TLS policy (min_version: {}, fips: {})",
+            policy.min_version, policy.fips
+        )));
+    }
+
+    Ok(provider)
+}
+
+fn protocol_versions(policy: &FlameTlsPolicy) -> &'static [&'static SupportedProtocolVersion] {
+    match policy.min_version {
+        TlsVersion::V1_2 => rustls::ALL_VERSIONS,
+        TlsVersion::V1_3 => TLS13_ONLY,
+    }
+}
+
+fn open_pem(path: &str, kind: &str) -> Result<BufReader<File>, FlameError> {
+    let file = File::open(path)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to read {kind} <{path}>: {e}")))?;
+    Ok(BufReader::new(file))
+}
+
+fn load_certs(path: &str, kind: &str) -> Result<Vec<CertificateDer<'static>>, FlameError> {
+    let certs = rustls_pemfile::certs(&mut open_pem(path, kind)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to parse {kind} <{path}>: {e}")))?;
+    if certs.is_empty() {
+        return Err(FlameError::InvalidConfig(format!(
+            "no certificate found in {kind} <{path}>"
+        )));
+    }
+
+    Ok(certs)
+}
+
+fn load_private_key(path: &str) -> Result<PrivateKeyDer<'static>, FlameError> {
+    rustls_pemfile::private_key(&mut open_pem(path, "key_file")?)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to parse key_file <{path}>: {e}")))?
+        .ok_or_else(|| FlameError::InvalidConfig(format!("no private key found in <{path}>")))
+}
+
+fn policy_of(tls: &FlameTls) -> Result<&FlameTlsPolicy, FlameError> {
+    tls.policy
+        .as_ref()
+        .ok_or_else(|| FlameError::InvalidConfig("TLS policy is not configured".to_string()))
+}
+
+/// Builds the rustls server config of the TLS settings and their policy.
+pub fn server_config(tls: &FlameTls) -> Result<ServerConfig, FlameError> {
+    let policy = policy_of(tls)?;
+
+    let mut config = ServerConfig::builder_with_provider(Arc::new(crypto_provider(policy)?))
+        .with_protocol_versions(protocol_versions(policy))
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid TLS policy: {e}")))?
+        .with_no_client_auth()
+        .with_single_cert(
+            load_certs(&tls.cert_file, "cert_file")?,
+            load_private_key(&tls.key_file)?,
+        )
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid TLS certificate: {e}")))?;
+    config.alpn_protocols = vec![ALPN_H2.to_vec()];
+
+    Ok(config)
+}
+
+/// Builds the rustls client config of the TLS settings and their policy; the
+/// server certificate is verified by the ca_file.
+pub fn client_config(tls: &FlameTls) -> Result<ClientConfig, FlameError> {
+    client_config_of(tls.ca_file.as_deref(), policy_of(tls)?)
+}
+
+/// Builds the rustls client config of the policy for the clients without the
+/// server settings, e.g. the SDK and flmctl; the server certificate is
+/// verified by the ca_file.
+pub fn client_config_of(
+    ca_file: Option<&str>,
+    policy: &FlameTlsPolicy,
+) -> Result<ClientConfig, FlameError> {
+    let ca_file = ca_file.ok_or_else(|| {
+        FlameError::InvalidConfig("tls.ca_file is required by the TLS policy".to_string())
+    })?;
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(ca_file, "ca_file")? {
+        roots.add(cert).map_err(|e| {
+            FlameError::InvalidConfig(format!("invalid certificate in ca_file <{ca_file}>: {e}"))
+        })?;
+    }
+
+    let mut config = ClientConfig::builder_with_provider(Arc::new(crypto_provider(policy)?))
+        .with_protocol_versions(protocol_versions(policy))
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid TLS policy: {e}")))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = vec![ALPN_H2.to_vec()];
+
+    Ok(config)
+}
+
+/// Listens on the address and returns the stream of the accepted TLS
+/// connections for `Router::serve_with_incoming`.
+pub async fn incoming(
+    tls: &FlameTls,
+    addr: SocketAddr,
+) -> Result<ReceiverStream<Result<TlsStream<TcpStream>, io::Error>>, FlameError> {
+    let acceptor = TlsAcceptor::from(Arc::new(server_config(tls)?));
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| FlameError::Network(format!("failed to listen on <{addr}>: {e}")))?;
+
+    let (tx, rx) = mpsc::channel(128);
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("Failed to accept connection on <{addr}>: {e}");
+                    continue;
+                }
+            };
+
+            let acceptor = acceptor.clone();
+            let sender = tx.clone();
+            // Handshake in its own task, so a slow client does not block the others.
+            tokio::spawn(async move {
+                match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        let _ = sender.send(Ok(stream)).await;
+                    }
+                    Err(e) => tracing::warn!("TLS handshake with <{peer}> failed: {e}"),
+                }
+            });
+
+            if tx.is_closed() {
+                break;
+            }
+        }
+    });
+
+    Ok(ReceiverStream::new(rx))
+}
+
+/// Connects to the https endpoint with the TLS settings and their policy.
+pub async fn connect(endpoint: &str, tls: &FlameTls) -> Result<Channel, FlameError> {
+    connect_with(endpoint, client_config(tls)?).await
+}
+
+/// Connects to the https endpoint with the rustls client config.
+pub async fn connect_with(endpoint: &str, config: ClientConfig) -> Result<Channel, FlameError> {
+    let uri: Uri = endpoint
+        .parse()
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid endpoint <{endpoint}>: {e}")))?;
+    let host = uri
+        .host()
+        .ok_or_else(|| FlameError::InvalidConfig(format!("no host in endpoint <{endpoint}>")))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let port = uri.port_u16().unwrap_or(443);
+    let server_name = ServerName::try_from(host.clone())
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid server name <{host}>: {e}")))?;
+
+    let connector = TlsConnector::from(Arc::new(config));
+
+    // The TLS is handled by the connector, so tonic only sees a plain http endpoint.
+    let authority = uri.authority().map(|a| a.as_str()).unwrap_or(&host);
+    Endpoint::from_shared(format!("http://{authority}"))
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid endpoint <{endpoint}>: {e}")))?
+        .connect_with_connector(service_fn(move |_: Uri| {
+            let connector = connector.clone();
+            let server_name = server_name.clone();
+            let host = host.clone();
+            async move {
+                let stream = TcpStream::connect((host.as_str(), port)).await?;
+                connector
+                    .connect(server_name, stream)
+                    .await
+                    .map(TokioIo::new)
+            }
+        }))
+        .await
+        .map_err(|e| FlameError::Network(format!("failed to connect to <{endpoint}>: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(min_version: TlsVersion, cipher_suites: &[&str], fips: bool) -> FlameTlsPolicy {
+        FlameTlsPolicy {
+            min_version,
+            cipher_suites: cipher_suites.iter().map(|s| s.to_string()).collect(),
+            fips,
+        }
+    }
+
+    fn suite_names(provider: &CryptoProvider) -> Vec<&'static str> {
+        provider
+            .cipher_suites
+            .iter()
+            .filter_map(|suite| suite.suite().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_crypto_provider() {
+        let provider = crypto_provider(&FlameTlsPolicy::default()).unwrap();
+        assert_eq!(suite_names(&provider), supported_cipher_suites());
+
+        let provider = crypto_provider(&policy(TlsVersion::V1_3, &[], false)).unwrap();
+        assert!(suite_names(&provider)
+            .iter()
+            .all(|name| name.starts_with("TLS13_")));
+
+        let provider = crypto_provider(&policy(TlsVersion::V1_2, &[], true)).unwrap();
+        assert!(suite_names(&provider)
+            .iter()
+            .all(|name| !name.contains("CHACHA20")));
+        assert!(provider
+            .kx_groups
+            .iter()
+            .all(|group| group.name() != NamedGroup::X25519));
+
+        let provider = crypto_provider(&policy(
+            TlsVersion::V1_2,
+            &[
+                "TLS13_AES_256_GCM_SHA384",
+                "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384",
+            ],
+            false,
+        ))
+        .unwrap();
+        assert_eq!(
+            suite_names(&provider),
+            vec![
+                "TLS13_AES_256_GCM_SHA384",
+                "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384"
+            ]
+        );
+
+        assert!(crypto_provider(&policy(TlsVersion::V1_2, &["TLS_RSA_WITH_RC4"], false)).is_err());
+        assert!(crypto_provider(&policy(
+            TlsVersion::V1_3,
+            &["TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384"],
+            false
+        ))
+        .is_err());
+        assert!(crypto_provider(&policy(
+            TlsVersion::V1_2,
+            &["TLS13_CHACHA20_POLY1305_SHA256"],
+            true
+        ))
+        .is_err());
+    }
+}
@@ -64,6 +64,11 @@ async fn test_run_pod() -> Result<(), FlameError> {
         environments: HashMap::new(),
         working_directory: None,
         url: None,
+        health_probe: None,
+        hooks: Default::default(),
+        python_env: None,
+        max_instances_per_node: None,
+        topology: Default::default(),
     };
 
     let pod = pm.run_pod(&app).await?;
@@ -94,6 +99,11 @@ async fn test_list_pods() -> Result<(), FlameError> {
         environments: HashMap::new(),
         working_directory: None,
         url: None,
+        health_probe: None,
+        hooks: Default::default(),
+        python_env: None,
+        max_instances_per_node: None,
+        topology: Default::default(),
     };
 
     let _ = pm.run_pod(&app).await?;
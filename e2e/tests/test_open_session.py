@@ -202,3 +202,28 @@ def test_open_session_closed_session():
 
     # Verify error indicates session is not open
     assert "not open" in str(exc_info.value).lower() or "closed" in str(exc_info.value).lower() or "invalid" in str(exc_info.value).lower()
+
+
+def test_open_session_reopen_closed_session():
+    """Test open_session reopens a closed session with a replay policy."""
+    session_id = f"test-open-reopen-{random_string(8)}"
+
+    # Create and close a session
+    flamepy.create_session(
+        application="flmtest-open-session",
+        session_id=session_id,
+    )
+    flamepy.close_session(session_id)
+
+    # Reopen the closed session, keeping the results of its tasks
+    session = flamepy.open_session(session_id, replay_policy=flamepy.ReplayPolicy.KEEP_RESULTS)
+    assert session.id == session_id
+    assert session.state == flamepy.SessionState.OPEN
+    assert session.completion_time is None
+
+    # Reopening an open session returns it as is
+    session = flamepy.open_session(session_id, replay_policy=flamepy.ReplayPolicy.RETRY_FAILED)
+    assert session.state == flamepy.SessionState.OPEN
+
+    # Clean up
+    flamepy.close_session(session_id)
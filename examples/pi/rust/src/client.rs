@@ -19,7 +19,7 @@ use clap::Parser;
 use futures::future::try_join_all;
 use stdng::{lock_ptr, new_ptr};
 
-use flame_rs::apis::{FlameContext, FlameError, TaskInput};
+use flame_rs::apis::{FlameContext, FlameError, ResultPolicy, TaskInput};
 use flame_rs::client::{SessionAttributes, Task, TaskInformer};
 use flame_rs::{self as flame};
 
@@ -64,13 +64,20 @@ async fn main() -> Result<(), Box<dyn Error>> {
     .await?;
     let ssn = conn
         .create_session(&SessionAttributes {
-            id: format!("{app}-{}", stdng::rand::short_name()),
+            id: format!("{app}-{}", stdng::rand::short_name()).into(),
             application: app,
             slots,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
         })
         .await?;
 
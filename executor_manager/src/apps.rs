@@ -0,0 +1,159 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The applications of the executors on this node.
+//!
+//! The executor manager caches the applications by their versions, which are
+//! filled by the binding of the executors; the shims get the latest definition
+//! of an application, e.g. its schema and environments, after the executor is
+//! bound by asking the session manager whether it's still of the cached version,
+//! so the definition is only sent again once the application was updated.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use common::apis::ApplicationContext;
+use common::FlameError;
+
+use crate::client::BackendClient;
+
+static APPLICATIONS: LazyLock<Mutex<ApplicationCache>> =
+    LazyLock::new(|| Mutex::new(ApplicationCache::default()));
+
+#[derive(Default)]
+struct ApplicationCache {
+    apps: HashMap<String, (u32, ApplicationContext)>,
+}
+
+impl ApplicationCache {
+    fn version(&self, name: &str) -> Option<u32> {
+        self.apps.get(name).map(|(version, _)| *version)
+    }
+
+    fn get(&self, name: &str) -> Option<ApplicationContext> {
+        self.apps.get(name).map(|(_, app)| app.clone())
+    }
+
+    /// Caches the application unless a newer version is cached.
+    fn put(&mut self, version: u32, app: ApplicationContext) {
+        match self.apps.get(&app.name) {
+            Some((cached, _)) if *cached > version => {}
+            _ => {
+                self.apps.insert(app.name.clone(), (version, app));
+            }
+        }
+    }
+
+    /// Caches the current application from the session manager, even if its
+    /// version is older, e.g. it was registered again.
+    fn update(&mut self, version: u32, app: ApplicationContext) {
+        self.apps.insert(app.name.clone(), (version, app));
+    }
+
+    fn remove(&mut self, name: &str) {
+        self.apps.remove(name);
+    }
+}
+
+fn lock() -> Result<std::sync::MutexGuard<'static, ApplicationCache>, FlameError> {
+    APPLICATIONS
+        .lock()
+        .map_err(|e| FlameError::Internal(format!("failed to lock applications: {e}")))
+}
+
+/// Caches the application of the version, e.g. the one the executor is bound to.
+pub fn put(version: u32, app: &ApplicationContext) -> Result<(), FlameError> {
+    lock()?.put(version, app.clone());
+    Ok(())
+}
+
+/// Gets the latest definition of the application; the cached one is used if
+/// it's still of the current version, or the session manager is unreachable.
+pub async fn get(client: &mut BackendClient, name: &str) -> Result<ApplicationContext, FlameError> {
+    let known_version = lock()?.version(name);
+
+    match client.fetch_application(name, known_version).await {
+        Ok((version, Some(app))) => {
+            tracing::debug!("Application <{name}> is updated to version <{version}>");
+            lock()?.update(version, app.clone());
+            Ok(app)
+        }
+        Ok((_, None)) => lock()?.get(name).ok_or(FlameError::NotFound(format!(
+            "application <{name}> is not cached"
+        ))),
+        Err(FlameError::NotFound(msg)) => {
+            lock()?.remove(name);
+            Err(FlameError::NotFound(msg))
+        }
+        Err(e) => {
+            let cached = lock()?.get(name);
+            match cached {
+                Some(app) => {
+                    tracing::warn!("Failed to get application <{name}>, use the cached one: {e}");
+                    Ok(app)
+                }
+                None => Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str, command: &str) -> ApplicationContext {
+        ApplicationContext {
+            name: name.to_string(),
+            shim: common::apis::Shim::Host,
+            image: None,
+            command: Some(command.to_string()),
+            arguments: vec![],
+            working_directory: None,
+            environments: HashMap::new(),
+            url: None,
+            health_probe: None,
+            hooks: Default::default(),
+            python_env: None,
+            max_instances_per_node: None,
+            topology: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_application_cache() {
+        let mut cache = ApplicationCache::default();
+        assert_eq!(cache.version("matrix"), None);
+
+        cache.put(2, app("matrix", "/opt/matrix-v2"));
+        assert_eq!(cache.version("matrix"), Some(2));
+
+        // The stale version, e.g. of a slow binding, does not replace the newer one.
+        cache.put(1, app("matrix", "/opt/matrix-v1"));
+        assert_eq!(cache.version("matrix"), Some(2));
+        assert_eq!(
+            cache.get("matrix").and_then(|app| app.command).as_deref(),
+            Some("/opt/matrix-v2")
+        );
+
+        cache.put(3, app("matrix", "/opt/matrix-v3"));
+        assert_eq!(cache.version("matrix"), Some(3));
+
+        // The application registered again restarts its versions.
+        cache.update(1, app("matrix", "/opt/matrix"));
+        assert_eq!(cache.version("matrix"), Some(1));
+
+        cache.remove("matrix");
+        assert!(cache.get("matrix").is_none());
+    }
+}
@@ -0,0 +1,208 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Prefetching of the artifacts of the applications on this node.
+//!
+//! The node-level configuration names the applications whose artifacts are
+//! prefetched when the node registers, i.e. the Wasm modules of their `image`
+//! on a Wasm node, and their Python environments on a host node, so the first
+//! instances of the applications on the node don't wait for them. The
+//! applications whose artifacts are ready are reported by the heartbeats of
+//! the node, and the scheduler prefers the nodes where the application of a
+//! session is ready. The packages of the `url` of the applications are
+//! installed by their instances, so they're not prefetched.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, OnceLock};
+
+use tokio::time::Instant;
+
+use common::apis::{ApplicationContext, Shim};
+use common::ctx::FlameClusterContext;
+use common::FlameError;
+
+use crate::apps;
+use crate::client::BackendClient;
+use crate::oci;
+use crate::python_env;
+use crate::shims::WASM_CACHE_DIR;
+
+/// The context of the executor manager; nothing is prefetched if not initialized.
+static CONTEXT: OnceLock<FlameClusterContext> = OnceLock::new();
+
+static ARTIFACTS: LazyLock<Mutex<Artifacts>> = LazyLock::new(|| Mutex::new(Artifacts::default()));
+
+/// The applications whose artifacts are ready, or being prefetched.
+#[derive(Default)]
+struct Artifacts {
+    ready: BTreeSet<String>,
+    pulling: HashSet<String>,
+}
+
+impl Artifacts {
+    /// Starts prefetching the applications, except the ones being prefetched;
+    /// the ready ones are prefetched again in case they were updated, and stay
+    /// ready meanwhile. The applications not listed any more are dropped.
+    fn start(&mut self, apps: &[String]) -> Vec<String> {
+        self.ready.retain(|app| apps.contains(app));
+        apps.iter()
+            .filter(|app| self.pulling.insert(app.to_string()))
+            .cloned()
+            .collect()
+    }
+
+    fn complete(&mut self, app: &str, ready: bool) {
+        self.pulling.remove(app);
+        if ready {
+            self.ready.insert(app.to_string());
+        } else {
+            self.ready.remove(app);
+        }
+    }
+}
+
+fn lock() -> Result<std::sync::MutexGuard<'static, Artifacts>, FlameError> {
+    ARTIFACTS
+        .lock()
+        .map_err(|e| FlameError::Internal(format!("failed to lock artifacts: {e}")))
+}
+
+pub fn init(ctx: &FlameClusterContext) {
+    if CONTEXT.set(ctx.clone()).is_err() {
+        tracing::warn!("The context of the artifacts was initialized already.");
+    }
+}
+
+/// The applications whose artifacts are prefetched on this node.
+pub fn prefetched() -> Vec<String> {
+    lock()
+        .map(|artifacts| artifacts.ready.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Prefetches the artifacts of the applications in background, one by one.
+pub fn prefetch(client: BackendClient, names: &[String]) {
+    let Some(ctx) = CONTEXT.get() else {
+        return;
+    };
+    let names = match lock() {
+        Ok(mut artifacts) => artifacts.start(names),
+        Err(e) => {
+            tracing::warn!("Failed to prefetch the artifacts: {e}");
+            return;
+        }
+    };
+    if names.is_empty() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut client = client;
+        for name in names {
+            let started = Instant::now();
+            let result = match apps::get(&mut client, &name).await {
+                Ok(app) => prefetch_app(ctx, &app).await,
+                Err(e) => Err(e),
+            };
+            match &result {
+                Ok(()) => tracing::info!(
+                    "Prefetched the artifacts of application <{name}> in {:.1}s",
+                    started.elapsed().as_secs_f64()
+                ),
+                Err(e) => {
+                    tracing::warn!("Failed to prefetch the artifacts of application <{name}>: {e}")
+                }
+            }
+
+            if let Ok(mut artifacts) = lock() {
+                artifacts.complete(&name, result.is_ok());
+            }
+        }
+    });
+}
+
+/// Prefetches the artifacts of the application by the shim of this node, as
+/// its instances get them at start.
+async fn prefetch_app(
+    ctx: &FlameClusterContext,
+    app: &ApplicationContext,
+) -> Result<(), FlameError> {
+    let shim = ctx.cluster.executors.shim;
+    if app.shim != shim {
+        return Err(FlameError::InvalidState(format!(
+            "application of shim <{:?}> is not run by the <{shim:?}> executors",
+            app.shim
+        )));
+    }
+
+    match shim {
+        Shim::Wasm => {
+            if let Some(image) = &app.image {
+                let cache_dir = ctx.paths.work.join(WASM_CACHE_DIR);
+                oci::pull(image, &ctx.cluster.executors.registry, &cache_dir).await?;
+            }
+        }
+        Shim::Host => {
+            if let Some(env) = &app.python_env {
+                // The instances without a working directory run in the ones of
+                // their executors, so only the absolute paths of their
+                // environments are resolved before.
+                let process_dir = match &app.working_directory {
+                    Some(wd) if !wd.is_empty() => PathBuf::from(wd),
+                    _ => ctx.paths.work.clone(),
+                };
+                python_env::prepare(app, env, &process_dir, &ctx.paths.cache).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_artifacts() {
+        let mut artifacts = Artifacts::default();
+
+        assert_eq!(
+            artifacts.start(&names(&["app-1", "app-2"])),
+            names(&["app-1", "app-2"])
+        );
+        artifacts.complete("app-1", true);
+        artifacts.complete("app-2", false);
+        assert_eq!(artifacts.ready, BTreeSet::from(["app-1".to_string()]));
+
+        // The ready ones stay ready while they're prefetched again; the ones
+        // being prefetched are not started twice.
+        assert_eq!(
+            artifacts.start(&names(&["app-1", "app-3"])),
+            names(&["app-1", "app-3"])
+        );
+        assert_eq!(artifacts.start(&names(&["app-1", "app-3"])), names(&[]));
+        assert_eq!(artifacts.ready, BTreeSet::from(["app-1".to_string()]));
+
+        // The applications not listed any more are dropped.
+        artifacts.complete("app-1", true);
+        artifacts.complete("app-3", true);
+        artifacts.start(&names(&["app-3"]));
+        assert_eq!(artifacts.ready, BTreeSet::from(["app-3".to_string()]));
+    }
+}
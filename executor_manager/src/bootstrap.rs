@@ -0,0 +1,128 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Bounded-parallel bootstrap of the shims.
+//!
+//! When a node is asked to create many executors at once, e.g. at startup,
+//! their shims are started in parallel up to the concurrency limit of the node,
+//! so the node reaches its capacity quickly without overloading the CPUs with
+//! the startup of all the instances. The startup time of the shims is tracked
+//! as the metrics of the node.
+
+use std::fmt;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+/// The permits to start a shim on this node; unlimited if not initialized.
+static PERMITS: OnceLock<Semaphore> = OnceLock::new();
+
+static STATS: LazyLock<Mutex<BootstrapStats>> =
+    LazyLock::new(|| Mutex::new(BootstrapStats::default()));
+
+pub fn init(concurrency: usize) {
+    if PERMITS.set(Semaphore::new(concurrency)).is_err() {
+        tracing::warn!("The bootstrap concurrency was initialized already.");
+    }
+}
+
+/// Waits for a permit to start a shim, released when it's dropped.
+pub async fn acquire() -> Option<SemaphorePermit<'static>> {
+    let permits = PERMITS.get()?;
+    let started = Instant::now();
+    let permit = permits.acquire().await.ok()?;
+    if let Ok(mut stats) = STATS.lock() {
+        stats.waited += started.elapsed();
+    }
+
+    Some(permit)
+}
+
+/// Observes the startup time of a shim, including the time waiting for permits.
+pub fn observe(executor_id: &str, elapsed: Duration, succeeded: bool) {
+    let Ok(mut stats) = STATS.lock() else {
+        return;
+    };
+    stats.observe(elapsed, succeeded);
+
+    tracing::info!(
+        "Shim of executor <{}> started in {}ms (succeeded={}), bootstrap: {}",
+        executor_id,
+        elapsed.as_millis(),
+        succeeded,
+        stats
+    );
+}
+
+/// The startup metrics of the shims on this node.
+#[derive(Clone, Debug, Default)]
+pub struct BootstrapStats {
+    pub count: u64,
+    pub failures: u64,
+    pub total: Duration,
+    pub max: Duration,
+    /// The time waiting for the permits, i.e. throttled by the concurrency limit.
+    pub waited: Duration,
+}
+
+impl BootstrapStats {
+    fn observe(&mut self, elapsed: Duration, succeeded: bool) {
+        self.count += 1;
+        if !succeeded {
+            self.failures += 1;
+        }
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+impl fmt::Display for BootstrapStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let avg = match self.count {
+            0 => Duration::ZERO,
+            n => self.total / n as u32,
+        };
+        write!(
+            f,
+            "count={}, failures={}, avg={}ms, max={}ms, waited={}ms",
+            self.count,
+            self.failures,
+            avg.as_millis(),
+            self.max.as_millis(),
+            self.waited.as_millis()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_stats() {
+        let mut stats = BootstrapStats::default();
+        assert_eq!(
+            stats.to_string(),
+            "count=0, failures=0, avg=0ms, max=0ms, waited=0ms"
+        );
+
+        stats.observe(Duration::from_millis(100), true);
+        stats.observe(Duration::from_millis(300), false);
+        assert_eq!(
+            stats.to_string(),
+            "count=2, failures=1, avg=200ms, max=300ms, waited=0ms"
+        );
+    }
+}
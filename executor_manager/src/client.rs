@@ -13,30 +13,60 @@ limitations under the License.
 use std::time::Duration;
 
 use stdng::{lock_ptr, MutexPtr};
-use tokio_stream::Stream;
+use tokio_stream::{Stream, StreamExt};
 use tonic::transport::Channel;
-use tonic::Streaming;
+use tonic::{Request, Streaming};
 
 use ::rpc::flame::v1 as rpc;
 use ::rpc::flame::v1::backend_client::BackendClient as FlameBackendClient;
 use ::rpc::flame::v1::{
-    BindExecutorCompletedRequest, BindExecutorRequest, CompleteTaskRequest, LaunchTaskRequest,
-    RegisterExecutorRequest, RegisterNodeRequest, ReleaseNodeRequest, SyncNodeRequest,
-    UnbindExecutorCompletedRequest, UnbindExecutorRequest, UnregisterExecutorRequest,
-    WatchNodeRequest, WatchNodeResponse,
+    BindExecutorCompletedRequest, BindExecutorFailedRequest, BindExecutorRequest,
+    CompleteTaskRequest, FetchApplicationRequest, LaunchTaskRequest, PushTaskOutputRequest,
+    RecordEventRequest, RegisterExecutorRequest, RegisterNodeRequest, ReleaseNodeRequest,
+    SyncNodeRequest, UnbindExecutorCompletedRequest, UnbindExecutorRequest,
+    UnregisterExecutorRequest, WatchNodeRequest, WatchNodeResponse,
 };
 
 use crate::executor::Executor;
+use crate::{apps, credentials};
 use common::apis::{
-    Application, Node, ResourceRequirement, Session, SessionContext, Shim, TaskContext, TaskResult,
+    Application, ApplicationContext, Event, Node, ResourceRequirement, Session, SessionContext,
+    Shim, TaskContext, TaskResult,
 };
 use common::ctx::FlameClusterContext;
-use common::FlameError;
+use common::{FlameError, FLAME_EXECUTOR_GENERATION, FLAME_NODE_NAME, FLAME_NODE_TOKEN};
 
 const DEFAULT_PORT: u16 = 8080;
 
 pub type FlameClient = FlameBackendClient<Channel>;
 
+/// The request carrying the credential of the node, if it joined the cluster.
+fn node_request<T>(message: T) -> Request<T> {
+    let mut req = Request::new(message);
+    if let Some((node_name, node_token)) = credentials::current() {
+        if let Ok(value) = node_name.parse() {
+            req.metadata_mut().insert(FLAME_NODE_NAME, value);
+        }
+        if let Ok(value) = node_token.parse() {
+            req.metadata_mut().insert(FLAME_NODE_TOKEN, value);
+        }
+    }
+    req
+}
+
+/// The request of the executor carrying the credential of the node, and the
+/// generation of the executor if known, so the session manager rejects the
+/// requests of a released executor whose id was reused.
+fn executor_request<T>(exe: &Executor, message: T) -> Request<T> {
+    let mut req = node_request(message);
+    if exe.generation != 0 {
+        if let Ok(value) = exe.generation.to_string().parse() {
+            req.metadata_mut().insert(FLAME_EXECUTOR_GENERATION, value);
+        }
+    }
+    req
+}
+
 #[derive(Clone, Debug)]
 pub struct BackendClient {
     client: FlameClient,
@@ -56,6 +86,18 @@ impl BackendClient {
         );
 
         tracing::info!("Connecting to flame backend at {}", endpoint);
+
+        // The handshake is done by rustls with a TLS policy.
+        if let Some(tls) = ctx.cluster.tls.as_ref().filter(|tls| tls.policy.is_some()) {
+            if endpoint.starts_with("https://") {
+                let channel = common::tls::connect(&endpoint, tls).await?;
+                tracing::info!("TLS enabled with TLS policy for backend client");
+                return Ok(Self {
+                    client: FlameBackendClient::new(channel),
+                });
+            }
+        }
+
         let mut channel_builder = Channel::from_shared(endpoint.clone()).map_err(|e| {
             FlameError::Network(format!("Failed to create channel for <{endpoint}>: {e}"))
         })?;
@@ -93,22 +135,29 @@ impl BackendClient {
         }
     }
 
+    /// Registers the node by its credential, or the join token at its first
+    /// registration; returns the credential issued to the node when it joined.
     pub async fn register_node(
         &mut self,
         node: &Node,
         executors: &[Executor],
-    ) -> Result<(), FlameError> {
+        join_token: Option<&str>,
+        node_token: Option<&str>,
+    ) -> Result<Option<String>, FlameError> {
         let req = RegisterNodeRequest {
             node: Some(node.clone().into()),
             executors: executors.iter().map(rpc::Executor::from).collect(),
+            join_token: join_token.map(str::to_string),
+            node_token: node_token.map(str::to_string),
         };
 
-        self.client
+        let resp = self
+            .client
             .register_node(req)
             .await
             .map_err(FlameError::from)?;
 
-        Ok(())
+        Ok(resp.into_inner().node_token)
     }
 
     /// # Deprecated
@@ -119,10 +168,12 @@ impl BackendClient {
         &mut self,
         node: &Node,
         executors: Vec<Executor>,
+        node_token: Option<&str>,
     ) -> Result<Vec<Executor>, FlameError> {
         let req = SyncNodeRequest {
             node: Some(node.clone().into()),
             executors: executors.into_iter().map(rpc::Executor::from).collect(),
+            node_token: node_token.map(str::to_string),
         };
 
         let resp = self.client.sync_node(req).await.map_err(FlameError::from)?;
@@ -166,9 +217,14 @@ impl BackendClient {
         Ok(resp.into_inner())
     }
 
-    pub async fn release_node(&mut self, node: &Node) -> Result<(), FlameError> {
+    pub async fn release_node(
+        &mut self,
+        node: &Node,
+        node_token: Option<&str>,
+    ) -> Result<(), FlameError> {
         let req = ReleaseNodeRequest {
             node_name: node.name.clone(),
+            node_token: node_token.map(str::to_string),
         };
 
         self.client
@@ -191,7 +247,7 @@ impl BackendClient {
         };
 
         self.client
-            .register_executor(req)
+            .register_executor(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
@@ -208,7 +264,7 @@ impl BackendClient {
 
         let resp = self
             .client
-            .bind_executor(req)
+            .bind_executor(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
@@ -217,18 +273,69 @@ impl BackendClient {
         let app = resp.clone().application;
 
         match (app, ssn) {
-            (Some(app), Some(ssn)) => Ok(Some(SessionContext::try_from((app, ssn))?)),
+            (Some(app), Some(ssn)) => {
+                let ctx = SessionContext::try_from((app, ssn))?;
+                if let Some(version) = resp.application_version {
+                    apps::put(version, &ctx.application)?;
+                }
+                Ok(Some(ctx))
+            }
             _ => Ok(None),
         }
     }
 
+    /// Gets the application of the name, which is not returned if it's still of
+    /// the known version; returns the current version of the application.
+    pub async fn fetch_application(
+        &mut self,
+        name: &str,
+        known_version: Option<u32>,
+    ) -> Result<(u32, Option<ApplicationContext>), FlameError> {
+        let req = FetchApplicationRequest {
+            name: name.to_string(),
+            known_version,
+        };
+
+        let resp = self
+            .client
+            .fetch_application(node_request(req))
+            .await
+            .map_err(FlameError::from)?
+            .into_inner();
+
+        let app = resp
+            .application
+            .map(ApplicationContext::try_from)
+            .transpose()?;
+
+        Ok((resp.version, app))
+    }
+
     pub async fn bind_executor_completed(&mut self, exe: &Executor) -> Result<(), FlameError> {
         let req = BindExecutorCompletedRequest {
             executor_id: exe.id.clone(),
         };
 
         self.client
-            .bind_executor_completed(req)
+            .bind_executor_completed(executor_request(exe, req))
+            .await
+            .map_err(FlameError::from)?;
+
+        Ok(())
+    }
+
+    pub async fn bind_executor_failed(
+        &mut self,
+        exe: &Executor,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        let req = BindExecutorFailedRequest {
+            executor_id: exe.id.clone(),
+            reason: reason.to_string(),
+        };
+
+        self.client
+            .bind_executor_failed(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
@@ -238,10 +345,11 @@ impl BackendClient {
     pub async fn unbind_executor(&mut self, exe: &Executor) -> Result<(), FlameError> {
         let req = UnbindExecutorRequest {
             executor_id: exe.id.clone(),
+            reason: exe.unhealthy.clone(),
         };
 
         self.client
-            .unbind_executor(req)
+            .unbind_executor(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
         Ok(())
@@ -253,29 +361,32 @@ impl BackendClient {
         };
 
         self.client
-            .unbind_executor_completed(req)
+            .unbind_executor_completed(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
         Ok(())
     }
 
-    pub async fn launch_task(&mut self, exe: &Executor) -> Result<Option<TaskContext>, FlameError> {
+    /// Launches the next tasks of the bound session; the tasks after the first one are
+    /// bundled by the application and have to be run and completed in order.
+    pub async fn launch_task(&mut self, exe: &Executor) -> Result<Vec<TaskContext>, FlameError> {
         let req = LaunchTaskRequest {
             executor_id: exe.id.clone(),
         };
 
         let resp = self
             .client
-            .launch_task(req)
+            .launch_task(executor_request(exe, req))
             .await
-            .map_err(FlameError::from)?;
-
-        if let Some(t) = resp.into_inner().task {
-            return Ok(Some(TaskContext::try_from(t)?));
-        }
-
-        Ok(None)
+            .map_err(FlameError::from)?
+            .into_inner();
+
+        resp.task
+            .into_iter()
+            .chain(resp.bundle)
+            .map(TaskContext::try_from)
+            .collect()
     }
 
     pub async fn complete_task(
@@ -286,10 +397,46 @@ impl BackendClient {
         let req = CompleteTaskRequest {
             executor_id: exe.id.clone(),
             task_result: Some(task_result.clone().try_into()?),
+            task_id: exe.task.as_ref().map(|task| task.task_id.to_string()),
+        };
+
+        self.client
+            .complete_task(executor_request(exe, req))
+            .await
+            .map_err(FlameError::from)?;
+
+        Ok(())
+    }
+
+    /// Relays the output chunks streamed by the tasks of the executor to the
+    /// session manager until the stream of the instance ends.
+    pub async fn push_task_output<S>(&mut self, exe: &Executor, chunks: S) -> Result<(), FlameError>
+    where
+        S: Stream<Item = rpc::TaskOutputChunk> + Send + 'static,
+    {
+        let executor_id = exe.id.clone();
+        let req = chunks.map(move |chunk| PushTaskOutputRequest {
+            executor_id: executor_id.clone(),
+            chunk: Some(chunk),
+        });
+
+        self.client
+            .push_task_output(executor_request(exe, req))
+            .await
+            .map_err(FlameError::from)?;
+
+        Ok(())
+    }
+
+    /// Records an event of the session the executor is bound to.
+    pub async fn record_event(&mut self, exe: &Executor, event: Event) -> Result<(), FlameError> {
+        let req = RecordEventRequest {
+            executor_id: exe.id.clone(),
+            event: Some(event.into()),
         };
 
         self.client
-            .complete_task(req)
+            .record_event(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
@@ -299,10 +446,31 @@ impl BackendClient {
     pub async fn unregister_executor(&mut self, exe: &Executor) -> Result<(), FlameError> {
         let req = UnregisterExecutorRequest {
             executor_id: exe.id.clone(),
+            reason: None,
+        };
+
+        self.client
+            .unregister_executor(executor_request(exe, req))
+            .await
+            .map_err(FlameError::from)?;
+
+        Ok(())
+    }
+
+    /// Rejects the new executor with the reason, e.g. the node reached its max
+    /// executors; the session manager removes the executor.
+    pub async fn reject_executor(
+        &mut self,
+        exe: &Executor,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        let req = UnregisterExecutorRequest {
+            executor_id: exe.id.clone(),
+            reason: Some(reason.to_string()),
         };
 
         self.client
-            .unregister_executor(req)
+            .unregister_executor(executor_request(exe, req))
             .await
             .map_err(FlameError::from)?;
 
@@ -0,0 +1,107 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The credentials of the node to register with the session manager.
+//!
+//! If the session manager requires the secure join of the nodes, the node
+//! registers with the one-time join token from `FLAME_JOIN_TOKEN` at first,
+//! and receives its credential, i.e. the node token; the node token is
+//! persisted in the work directory and presented at the later registrations,
+//! e.g. after a restart, so the join token is never needed again. Once the
+//! node registered, its credential is presented in every backend RPC.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use common::FlameError;
+
+const NODE_TOKEN_FILE: &str = "node-token";
+
+/// The environment variable of the one-time join token.
+pub const JOIN_TOKEN_ENV: &str = "FLAME_JOIN_TOKEN";
+
+/// The name and the token of the registered node.
+static CURRENT: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+fn token_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(NODE_TOKEN_FILE)
+}
+
+/// The join token of the node, if any.
+pub fn join_token() -> Option<String> {
+    std::env::var(JOIN_TOKEN_ENV)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// Sets the credential of the registered node, which is presented in the
+/// backend RPCs of the executors.
+pub fn set_current(node_name: &str, token: &str) {
+    if let Ok(mut current) = CURRENT.lock() {
+        *current = Some((node_name.to_string(), token.to_string()));
+    }
+}
+
+/// The credential of the registered node, if it joined the cluster.
+pub fn current() -> Option<(String, String)> {
+    CURRENT.lock().ok()?.clone()
+}
+
+/// Loads the persisted node token, if the node has joined the cluster.
+pub fn load(work_dir: &Path) -> Option<String> {
+    let token = fs::read_to_string(token_path(work_dir)).ok()?;
+    Some(token.trim().to_string()).filter(|token| !token.is_empty())
+}
+
+/// Persists the node token, which is only readable by the owner; it's written
+/// into a temporary file first, so a partial one is never loaded.
+pub fn save(work_dir: &Path, token: &str) -> Result<(), FlameError> {
+    let path = token_path(work_dir);
+    let tmp_path = path.with_extension("tmp");
+
+    fs::write(&tmp_path, token)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+    }
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load(dir.path()), None);
+
+        save(dir.path(), "0123abcd").unwrap();
+        assert_eq!(load(dir.path()), Some("0123abcd".to_string()));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(token_path(dir.path()))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -14,10 +14,14 @@ limitations under the License.
 use std::convert::TryFrom;
 use std::sync::{Arc, Mutex};
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
+use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 
 use crate::client::BackendClient;
+use crate::limits::InstanceSlotPtr;
+use crate::scratch::ScratchDirPtr;
 use crate::shims::ShimPtr;
+use crate::topology::PlacementPtr;
 use ::rpc::flame::v1::{self as rpc, ExecutorSpec, ExecutorStatus, Metadata};
 
 use crate::states;
@@ -33,6 +37,9 @@ pub struct Executor {
     /// Supported shim type from executor-manager config.
     /// This indicates what type of shim this executor supports (Host or Wasm).
     pub shim: Shim,
+    /// The generation assigned by the session manager, presented in the
+    /// backend RPCs of the executor; 0 if unknown.
+    pub generation: u64,
 
     pub session: Option<SessionContext>,
     pub task: Option<TaskContext>,
@@ -43,6 +50,27 @@ pub struct Executor {
     /// the executor binds to a session.
     pub shim_instance: Option<ShimPtr>,
 
+    /// The shared scratch directory of the bound session on this node.
+    pub scratch: Option<ScratchDirPtr>,
+
+    /// The instance of the bound application on this node, counted by its
+    /// `max_instances_per_node`.
+    pub instance: Option<InstanceSlotPtr>,
+
+    /// The NUMA node or the GPU group of this node taken by the instance of
+    /// the bound application, by its topology policy.
+    pub placement: Option<PlacementPtr>,
+
+    /// Why the instance is unhealthy, i.e. its health probe failed; the
+    /// executor unbinds from the session with it.
+    pub unhealthy: Option<String>,
+
+    /// Whether the session manager unbinds the executor for a preemption.
+    pub preempted: bool,
+    /// Notified when the executor is preempted, so its running task is
+    /// interrupted; it's shared by the clones of the executor.
+    pub preemption: Arc<Notify>,
+
     pub state: ExecutorState,
 }
 
@@ -81,10 +109,17 @@ impl TryFrom<&rpc::Executor> for Executor {
             node: spec.node.clone(),
             slots: spec.slots,
             shim: Shim::from(spec.shim()), // Get shim from spec
+            generation: status.generation,
             session: None,
             task: None,
             context: None,
             shim_instance: None,
+            scratch: None,
+            instance: None,
+            placement: None,
+            unhealthy: None,
+            preempted: status.preempted,
+            preemption: Arc::new(Notify::new()),
             state,
         })
     }
@@ -112,8 +147,10 @@ impl From<&Executor> for rpc::Executor {
 
         let status = Some(ExecutorStatus {
             state: rpc::ExecutorState::from(e.state).into(),
-            session_id: e.session.clone().map(|s| s.session_id),
+            session_id: e.session.clone().map(|s| s.session_id.into()),
             batch_index: None,
+            preempted: e.preempted,
+            generation: e.generation,
         });
 
         rpc::Executor {
@@ -133,9 +170,13 @@ impl Executor {
             next.state
         );
         self.state = next.state;
+        if next.generation != 0 {
+            self.generation = next.generation;
+        }
         self.shim_instance = next.shim_instance.clone();
         self.session = next.session.clone();
         self.task = next.task.clone();
+        self.unhealthy = next.unhealthy.clone();
     }
 }
 
@@ -0,0 +1,280 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Lifecycle hooks of the instances.
+//!
+//! The application may declare the commands run on the node around its
+//! instances, e.g. mounting datasets, warming caches or fetching licenses:
+//! `pre_bind` before the instance starts, `post_bind` after it entered the
+//! session, and `pre_release` before it leaves the session. The output of each
+//! hook is recorded as an event of the session; a failed `pre_bind` fails the
+//! binding of the executor, while the failures of the others are only recorded.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use chrono::Utc;
+use tokio::process::Command;
+
+use common::apis::{
+    Event, LifecycleHook, SessionContext, Shim, INSTANCE_HOOK_EVENT, INSTANCE_HOOK_FAILED_EVENT,
+};
+use common::{FlameError, FLAME_INSTANCE_ENDPOINT};
+
+use crate::client::BackendClient;
+use crate::executor::Executor;
+use crate::privileges;
+use crate::shims;
+
+const FLAME_SESSION_ID: &str = "FLAME_SESSION_ID";
+const FLAME_EXECUTOR_ID: &str = "FLAME_EXECUTOR_ID";
+/// The max bytes of the output of a hook kept in its event, i.e. its tail.
+const MAX_OUTPUT_LEN: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookPoint {
+    PreBind,
+    PostBind,
+    PreRelease,
+}
+
+impl fmt::Display for HookPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HookPoint::PreBind => "pre_bind",
+            HookPoint::PostBind => "post_bind",
+            HookPoint::PreRelease => "pre_release",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The runner of the lifecycle hooks of the instance of an executor.
+pub struct HookRunner {
+    executor_id: String,
+    session_id: String,
+    environments: Vec<(String, String)>,
+    /// The directory where the instance runs, i.e. the one of the hooks.
+    process_dir: PathBuf,
+    socket: PathBuf,
+}
+
+impl HookRunner {
+    /// The runner of the executor's instance in the session; only the hooks
+    /// of the host instances are run.
+    pub fn new(executor: &Executor, ssn: &SessionContext) -> Option<Self> {
+        if executor.shim != Shim::Host || ssn.application.hooks.is_empty() {
+            return None;
+        }
+        let paths = executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.paths.clone())
+            .unwrap_or_default();
+
+        Some(Self {
+            executor_id: executor.id.clone(),
+            session_id: ssn.session_id.to_string(),
+            environments: ssn
+                .application
+                .environments
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            process_dir: shims::process_dir(&ssn.application, &executor.id, &paths.work),
+            socket: shims::socket_path(&executor.id, &paths.sockets),
+        })
+    }
+
+    /// Runs the hook of the point, if any, and records its output as an event
+    /// of the session; the error of a failed hook is returned after recorded.
+    pub async fn run(
+        &self,
+        client: &mut BackendClient,
+        executor: &Executor,
+        ssn: &SessionContext,
+        point: HookPoint,
+    ) -> Result<(), FlameError> {
+        let hooks = &ssn.application.hooks;
+        let hook = match point {
+            HookPoint::PreBind => &hooks.pre_bind,
+            HookPoint::PostBind => &hooks.post_bind,
+            HookPoint::PreRelease => &hooks.pre_release,
+        };
+        let Some(hook) = hook else {
+            return Ok(());
+        };
+
+        let started = tokio::time::Instant::now();
+        let result = self.execute(hook).await;
+        let elapsed = started.elapsed().as_secs_f64();
+
+        let (code, message) = match &result {
+            Ok(output) => (
+                INSTANCE_HOOK_EVENT,
+                format!(
+                    "Hook <{point}> of executor <{}> completed in {elapsed:.1}s: {output}",
+                    self.executor_id
+                ),
+            ),
+            Err(e) => (
+                INSTANCE_HOOK_FAILED_EVENT,
+                format!(
+                    "Hook <{point}> of executor <{}> failed in {elapsed:.1}s: {e}",
+                    self.executor_id
+                ),
+            ),
+        };
+        match &result {
+            Ok(_) => tracing::debug!("{message}"),
+            Err(_) => tracing::warn!("{message}"),
+        }
+
+        let event = Event {
+            code,
+            message: Some(message),
+            creation_time: Utc::now(),
+        };
+        if let Err(e) = client.record_event(executor, event).await {
+            tracing::warn!(
+                "Failed to record the event of hook <{point}> of executor <{}>: {e}",
+                self.executor_id
+            );
+        }
+
+        result.map(|_| ())
+    }
+
+    /// Runs the command by `sh -c` in the directory of the instance, and returns
+    /// its output; the child is killed if the hook times out.
+    async fn execute(&self, hook: &LifecycleHook) -> Result<String, FlameError> {
+        tokio::fs::create_dir_all(&self.process_dir)
+            .await
+            .map_err(|e| {
+                FlameError::Internal(format!(
+                    "failed to create <{}>: {e}",
+                    self.process_dir.display()
+                ))
+            })?;
+
+        let timeout = hook.timeout();
+        let mut cmd = Command::new("sh");
+        privileges::apply(&mut cmd);
+        let child = cmd
+            .arg("-c")
+            .arg(&hook.command)
+            .envs(self.environments.iter().map(|(k, v)| (k, v)))
+            .env(FLAME_INSTANCE_ENDPOINT, &self.socket)
+            .env(FLAME_SESSION_ID, &self.session_id)
+            .env(FLAME_EXECUTOR_ID, &self.executor_id)
+            .current_dir(&self.process_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(timeout, child)
+            .await
+            .map_err(|_| FlameError::Internal(format!("timed out after {}s", timeout.as_secs())))?
+            .map_err(|e| FlameError::Internal(format!("failed to run <{}>: {e}", hook.command)))?;
+
+        let mut combined = output.stdout;
+        combined.extend_from_slice(&output.stderr);
+        let text = tail(&String::from_utf8_lossy(&combined));
+
+        if output.status.success() {
+            return Ok(text);
+        }
+
+        Err(FlameError::Internal(format!(
+            "exited with {}: {text}",
+            output.status
+        )))
+    }
+}
+
+/// The trimmed tail of the output, at most `MAX_OUTPUT_LEN` bytes.
+pub(crate) fn tail(output: &str) -> String {
+    let output = output.trim();
+    if output.len() <= MAX_OUTPUT_LEN {
+        return output.to_string();
+    }
+
+    let mut start = output.len() - MAX_OUTPUT_LEN;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("...{}", &output[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner(dir: &std::path::Path) -> HookRunner {
+        HookRunner {
+            executor_id: "exec-1".to_string(),
+            session_id: "ssn-1".to_string(),
+            environments: vec![("DATASET".to_string(), "mnist".to_string())],
+            process_dir: dir.join("exec-1"),
+            socket: PathBuf::from("/tmp/exec-1.sock"),
+        }
+    }
+
+    fn hook(command: &str) -> LifecycleHook {
+        LifecycleHook {
+            command: command.to_string(),
+            timeout_seconds: Some(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = runner(dir.path());
+
+        // The process directory is created for the hook before the instance starts.
+        let output = runner
+            .execute(&hook(
+                "echo $DATASET $FLAME_SESSION_ID $FLAME_EXECUTOR_ID; pwd",
+            ))
+            .await
+            .unwrap();
+        let expected = format!(
+            "mnist ssn-1 exec-1\n{}",
+            dir.path().join("exec-1").display()
+        );
+        assert_eq!(output, expected);
+
+        let err = runner
+            .execute(&hook("echo no license >&2; exit 3"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no license"));
+
+        // The wedged hook is failed by its timeout.
+        assert!(runner.execute(&hook("sleep 5")).await.is_err());
+    }
+
+    #[test]
+    fn test_tail() {
+        assert_eq!(tail("  done\n"), "done");
+
+        let output = "x".repeat(MAX_OUTPUT_LEN * 2);
+        let tailed = tail(&output);
+        assert!(tailed.starts_with("..."));
+        assert_eq!(tailed.len(), MAX_OUTPUT_LEN + 3);
+    }
+}
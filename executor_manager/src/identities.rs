@@ -0,0 +1,139 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The identities of the executors on this node across restarts.
+//!
+//! The session manager names the executors by their node and slot index, e.g.
+//! `node-1-0`. The executors on the node are persisted in the work directory on
+//! each heartbeat, and the idle ones are re-adopted at the next start: they're
+//! reported when the node registers, so the session manager keeps them instead
+//! of releasing them as orphans. The other executors are not re-adopted, as
+//! their instances and connections are gone with the executor manager; they're
+//! released, and the executors created on their slots get the same identities.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use prost::Message;
+
+use common::apis::ExecutorState;
+use common::FlameError;
+use rpc::flame::v1 as rpc;
+
+use crate::executor::Executor;
+
+const EXECUTORS_FILE: &str = "executors.pb";
+
+fn executors_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(EXECUTORS_FILE)
+}
+
+/// Loads the persisted executors which can be re-adopted, i.e. the idle ones.
+pub fn load(work_dir: &Path) -> Vec<Executor> {
+    let path = executors_path(work_dir);
+    let Ok(data) = fs::read(&path) else {
+        return vec![];
+    };
+
+    let list = match rpc::ExecutorList::decode(data.as_slice()) {
+        Ok(list) => list,
+        Err(e) => {
+            tracing::warn!("Ignore invalid executors <{}>: {e}", path.display());
+            return vec![];
+        }
+    };
+
+    list.executors
+        .iter()
+        .filter_map(|e| Executor::try_from(e).ok())
+        .filter(|e| {
+            let adopted = e.state == ExecutorState::Idle;
+            if !adopted {
+                tracing::info!(
+                    "Executor <{}> was <{}> in the last run, it can not be re-adopted.",
+                    e.id,
+                    e.state
+                );
+            }
+            adopted
+        })
+        .collect()
+}
+
+/// Persists the executors; it's written into a temporary file first, so a
+/// partial one is never loaded.
+pub fn save(work_dir: &Path, executors: &[Executor]) -> Result<(), FlameError> {
+    let path = executors_path(work_dir);
+    let tmp_path = path.with_extension("pb.tmp");
+
+    let list = rpc::ExecutorList {
+        executors: executors.iter().map(rpc::Executor::from).collect(),
+    };
+    fs::write(&tmp_path, list.encode_to_vec())?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use common::apis::{ResourceRequirement, Shim};
+    use tokio::sync::Notify;
+
+    fn executor(id: &str, state: ExecutorState) -> Executor {
+        Executor {
+            id: id.to_string(),
+            resreq: ResourceRequirement { cpu: 1, memory: 1 },
+            node: "node-1".to_string(),
+            slots: 1,
+            shim: Shim::Host,
+            generation: 0,
+            session: None,
+            task: None,
+            context: None,
+            shim_instance: None,
+            scratch: None,
+            instance: None,
+            placement: None,
+            unhealthy: None,
+            preempted: false,
+            preemption: Arc::new(Notify::new()),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path()).is_empty());
+
+        let executors = vec![
+            executor("node-1-0", ExecutorState::Idle),
+            executor("node-1-1", ExecutorState::Bound),
+        ];
+        save(dir.path(), &executors).unwrap();
+
+        // Only the idle executor is re-adopted.
+        let adopted = load(dir.path());
+        assert_eq!(adopted.len(), 1);
+        assert_eq!(adopted[0].id, "node-1-0");
+        assert_eq!(adopted[0].slots, 1);
+
+        fs::write(dir.path().join(EXECUTORS_FILE), "invalid").unwrap();
+        assert!(load(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+/*
+Copyright 2023 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The executor manager of Flame; it's run by the `flame-executor-manager`
+//! binary, or in-process by the local mode of the SDK.
+
+mod apps;
+mod artifacts;
+pub mod bootstrap;
+mod client;
+mod credentials;
+mod executor;
+mod hooks;
+mod identities;
+mod limits;
+mod load;
+mod logs;
+pub mod manager;
+pub mod node_config;
+mod oci;
+mod postmortem;
+mod prefetch;
+pub mod privileges;
+mod probe;
+mod python_env;
+pub mod resources;
+mod scratch;
+mod shims;
+mod states;
+mod stream_handler;
+mod topology;
+mod usage;
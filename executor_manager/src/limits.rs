@@ -0,0 +1,227 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Local enforcement of the executor limits of the node.
+//!
+//! The scheduler creates the executors of a node up to the max executors
+//! reported by its heartbeats, and places the instances of an application by
+//! its `max_instances_per_node`; the executor manager enforces both limits
+//! locally too, so a stale view of the scheduler, e.g. right after a failover,
+//! can't overload the node. The rejections are reported back to the session
+//! manager with their reasons, and tracked as the metrics of the node.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+
+use common::FlameError;
+
+/// The max executors of this node; unlimited if not initialized.
+static MAX_EXECUTORS: OnceLock<u32> = OnceLock::new();
+
+/// The instances of the applications bound on this node.
+static INSTANCES: LazyLock<Mutex<Instances>> = LazyLock::new(|| Mutex::new(Instances::default()));
+
+static STATS: LazyLock<Mutex<LimitStats>> = LazyLock::new(|| Mutex::new(LimitStats::default()));
+
+pub fn init(max_executors: u32) {
+    if MAX_EXECUTORS.set(max_executors).is_err() {
+        tracing::warn!("The max executors of the node were initialized already.");
+    }
+}
+
+/// The max executors of this node, if limited.
+pub fn max_executors() -> Option<u32> {
+    MAX_EXECUTORS.get().copied()
+}
+
+/// Checks whether the node accepts a new executor with its `current`
+/// executors; the rejection is observed with its reason.
+pub fn check_executors(executor_id: &str, current: usize) -> Result<(), FlameError> {
+    let Some(max) = max_executors() else {
+        return Ok(());
+    };
+    if let Ok(mut stats) = STATS.lock() {
+        stats.executors = current;
+        stats.max_executors = Some(max);
+    }
+
+    if current < max as usize {
+        return Ok(());
+    }
+
+    let reason = format!("node has reached max_executors: {current} >= {max}");
+    observe_rejection(executor_id, &reason, |stats| stats.rejected_executors += 1);
+    Err(FlameError::ResourceExhausted(reason))
+}
+
+/// Acquires an instance of the application on this node for the executor,
+/// released when it's dropped; the rejection is observed with its reason.
+pub fn acquire_instance(
+    executor_id: &str,
+    app: &str,
+    max_per_node: Option<u32>,
+) -> Result<InstanceSlotPtr, FlameError> {
+    let acquired = lock_instances()?.acquire(executor_id, app, max_per_node);
+    if let Err(e) = acquired {
+        observe_rejection(executor_id, &e.to_string(), |stats| {
+            stats.rejected_instances += 1
+        });
+        return Err(e);
+    }
+
+    Ok(Arc::new(InstanceSlot {
+        executor_id: executor_id.to_string(),
+    }))
+}
+
+fn lock_instances() -> Result<std::sync::MutexGuard<'static, Instances>, FlameError> {
+    INSTANCES
+        .lock()
+        .map_err(|e| FlameError::Internal(format!("failed to lock the instances: {e}")))
+}
+
+fn observe_rejection(executor_id: &str, reason: &str, count: impl FnOnce(&mut LimitStats)) {
+    let Ok(mut stats) = STATS.lock() else {
+        return;
+    };
+    count(&mut stats);
+
+    tracing::warn!(
+        "Rejected executor <{}>: {}, limits: {}",
+        executor_id,
+        reason,
+        stats
+    );
+}
+
+pub type InstanceSlotPtr = Arc<InstanceSlot>;
+
+/// An instance of an application on this node, held by its bound executor.
+#[derive(Debug)]
+pub struct InstanceSlot {
+    executor_id: String,
+}
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        match lock_instances() {
+            Ok(mut instances) => instances.release(&self.executor_id),
+            Err(e) => tracing::warn!(
+                "Failed to release the instance of executor <{}>: {e}",
+                self.executor_id
+            ),
+        }
+    }
+}
+
+/// The application of the instance of each executor.
+#[derive(Debug, Default)]
+struct Instances {
+    apps: HashMap<String, String>,
+}
+
+impl Instances {
+    fn acquire(
+        &mut self,
+        executor_id: &str,
+        app: &str,
+        max_per_node: Option<u32>,
+    ) -> Result<(), FlameError> {
+        if let Some(max) = max_per_node {
+            let count = self
+                .apps
+                .iter()
+                .filter(|(id, name)| id.as_str() != executor_id && name.as_str() == app)
+                .count();
+            if count >= max as usize {
+                return Err(FlameError::ResourceExhausted(format!(
+                    "application <{app}> has reached max_instances_per_node on the node: {count} >= {max}"
+                )));
+            }
+        }
+
+        self.apps.insert(executor_id.to_string(), app.to_string());
+        Ok(())
+    }
+
+    fn release(&mut self, executor_id: &str) {
+        self.apps.remove(executor_id);
+    }
+}
+
+/// The limit metrics of this node.
+#[derive(Clone, Debug, Default)]
+pub struct LimitStats {
+    /// The executors of the node by the last check.
+    pub executors: usize,
+    pub max_executors: Option<u32>,
+    pub rejected_executors: u64,
+    pub rejected_instances: u64,
+}
+
+impl fmt::Display for LimitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max = match self.max_executors {
+            Some(max) => max.to_string(),
+            None => "unlimited".to_string(),
+        };
+        write!(
+            f,
+            "executors={}/{}, rejected_executors={}, rejected_instances={}",
+            self.executors, max, self.rejected_executors, self.rejected_instances
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instances_per_node() {
+        let mut instances = Instances::default();
+        instances.acquire("exe-1", "app-1", Some(2)).unwrap();
+        instances.acquire("exe-2", "app-1", Some(2)).unwrap();
+
+        // The other applications and the unlimited ones are not affected.
+        instances.acquire("exe-3", "app-2", Some(1)).unwrap();
+        instances.acquire("exe-4", "app-3", None).unwrap();
+
+        let res = instances.acquire("exe-5", "app-1", Some(2));
+        assert!(matches!(res, Err(FlameError::ResourceExhausted(_))));
+
+        // The executor binding again keeps its own instance.
+        instances.acquire("exe-2", "app-1", Some(2)).unwrap();
+
+        instances.release("exe-1");
+        instances.acquire("exe-5", "app-1", Some(2)).unwrap();
+    }
+
+    #[test]
+    fn test_limit_stats() {
+        let mut stats = LimitStats::default();
+        assert_eq!(
+            stats.to_string(),
+            "executors=0/unlimited, rejected_executors=0, rejected_instances=0"
+        );
+
+        stats.executors = 4;
+        stats.max_executors = Some(4);
+        stats.rejected_executors = 1;
+        assert_eq!(
+            stats.to_string(),
+            "executors=4/4, rejected_executors=1, rejected_instances=0"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The load of the executors on this node.
+//!
+//! Each executor records the tasks waiting in its bundle and the time it runs
+//! the tasks; the loads are reported by the heartbeats of the node, so the
+//! scheduler prefers the executors of the less saturated nodes. The busy ratio
+//! of an executor is the fraction of the time since the last heartbeat it was
+//! running a task.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use rpc::flame::v1 as rpc;
+
+static LOADS: LazyLock<Mutex<Loads>> = LazyLock::new(|| Mutex::new(Loads::new(Instant::now())));
+
+#[derive(Default)]
+struct ExecutorLoad {
+    queue_depth: u32,
+    busy: Duration,
+    running_since: Option<Instant>,
+}
+
+struct Loads {
+    executors: HashMap<String, ExecutorLoad>,
+    last_report: Instant,
+}
+
+impl Loads {
+    fn new(now: Instant) -> Self {
+        Self {
+            executors: HashMap::new(),
+            last_report: now,
+        }
+    }
+
+    fn start_task(&mut self, id: &str, queue_depth: usize, now: Instant) {
+        let load = self.executors.entry(id.to_string()).or_default();
+        load.queue_depth = queue_depth as u32;
+        load.running_since = Some(now);
+    }
+
+    fn complete_task(&mut self, id: &str, now: Instant) {
+        if let Some(load) = self.executors.get_mut(id) {
+            if let Some(since) = load.running_since.take() {
+                load.busy += now.saturating_duration_since(since);
+            }
+        }
+    }
+
+    /// The loads of the executors since the last report; the executors which
+    /// are gone are forgotten.
+    fn report(&mut self, ids: &HashSet<String>, now: Instant) -> Vec<rpc::ExecutorLoad> {
+        let window = now.saturating_duration_since(self.last_report);
+        self.last_report = now;
+        self.executors.retain(|id, _| ids.contains(id));
+
+        let mut loads = vec![];
+        for id in ids {
+            let load = self.executors.entry(id.clone()).or_default();
+            let mut busy = std::mem::take(&mut load.busy);
+            if let Some(since) = load.running_since.as_mut() {
+                busy += now.saturating_duration_since(*since);
+                *since = now;
+            }
+
+            let busy_ratio = if window.is_zero() {
+                0.0
+            } else {
+                (busy.as_secs_f64() / window.as_secs_f64()).min(1.0)
+            };
+            loads.push(rpc::ExecutorLoad {
+                executor_id: id.clone(),
+                queue_depth: load.queue_depth,
+                busy_ratio,
+            });
+        }
+
+        loads
+    }
+}
+
+fn lock() -> MutexGuard<'static, Loads> {
+    LOADS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Records that the executor starts a task, with the tasks waiting for it.
+pub fn start_task(id: &str, queue_depth: usize) {
+    lock().start_task(id, queue_depth, Instant::now());
+}
+
+/// Records that the executor completed its running task.
+pub fn complete_task(id: &str) {
+    lock().complete_task(id, Instant::now());
+}
+
+/// The loads of the executors since the last heartbeat.
+pub fn report(ids: &HashSet<String>) -> Vec<rpc::ExecutorLoad> {
+    lock().report(ids, Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_executor_loads() {
+        let start = Instant::now();
+        let mut loads = Loads::new(start);
+        let ids: HashSet<String> = ["node-1-0", "node-1-1"].map(String::from).into();
+
+        // The executor runs the first of 3 bundled tasks for 5 of 10 seconds.
+        loads.start_task("node-1-0", 2, start);
+        loads.complete_task("node-1-0", start + Duration::from_secs(5));
+        loads.start_task("node-1-0", 1, start + Duration::from_secs(8));
+
+        let mut report = loads.report(&ids, start + Duration::from_secs(10));
+        report.sort_by(|a, b| a.executor_id.cmp(&b.executor_id));
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].queue_depth, 1);
+        assert_eq!(report[0].busy_ratio, 0.7);
+        assert_eq!(report[1].busy_ratio, 0.0);
+
+        // The running task is counted from the last report.
+        let report = loads.report(
+            &HashSet::from(["node-1-0".to_string()]),
+            start + Duration::from_secs(20),
+        );
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].busy_ratio, 1.0);
+    }
+}
@@ -0,0 +1,396 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Rotation of the stdout and stderr logs of the instances.
+//!
+//! The stdout and stderr of the instances of the host shim are piped to the
+//! executor manager, which writes them to the logs in the directory of the
+//! instance, e.g. `<executor-id>.err`. A log is rotated to `<log>.<timestamp>`
+//! when it's larger than `max_size` or older than `max_age`; the rotated logs
+//! are compressed by zstd, e.g. `<executor-id>.err.20261015120000000000.zst`, and
+//! only the latest `max_files` of each log are kept. The oldest rotated logs of
+//! all the instances on the node are removed while the total size of their
+//! logs is above `max_disk`.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+use chrono::Utc;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use common::ctx::FlameLogs;
+use common::FlameError;
+
+/// The suffix of the compressed rotated logs.
+const COMPRESSED_SUFFIX: &str = ".zst";
+/// The zstd level of the rotated logs, i.e. the default level of zstd.
+const COMPRESSION_LEVEL: i32 = 3;
+/// The size of the buffer to read the output of the instances.
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The live logs of the instances on this node, whose rotated logs are
+/// counted against the disk cap.
+static LOGS: LazyLock<Mutex<HashSet<PathBuf>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Writes the output of the instance to the log until the output is closed,
+/// i.e. the instance exited; the output is drained even if the log is not
+/// writable, so the instance is never blocked by its logs.
+pub fn spawn<R>(mut output: R, mut log: RotatingLog)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let (path, config) = (log.path.clone(), log.config.clone());
+
+    tokio::spawn(async move {
+        let mut buf = vec![0; READ_BUFFER_SIZE];
+        loop {
+            let n = match output.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) => {
+                    tracing::warn!("Failed to read the output of <{}>: {e}", path.display());
+                    break;
+                }
+            };
+
+            match log.write(&buf[..n]) {
+                Ok(Some(rotated)) => {
+                    let (path, config) = (path.clone(), config.clone());
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(e) = compact(&path, &rotated, &config) {
+                            tracing::warn!(
+                                "Failed to compact the logs of <{}>: {e}",
+                                path.display()
+                            );
+                        }
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to write log <{}>: {e}", path.display()),
+            }
+        }
+    });
+}
+
+/// The log of an instance, rotated by its size and age.
+pub struct RotatingLog {
+    path: PathBuf,
+    config: FlameLogs,
+    file: File,
+    size: u64,
+    opened: Instant,
+}
+
+impl RotatingLog {
+    /// Creates the log, truncating the one of the previous instance.
+    pub fn create(path: &Path, config: &FlameLogs) -> Result<Self, FlameError> {
+        let file = open(path)?;
+        if let Ok(mut logs) = LOGS.lock() {
+            logs.insert(path.to_path_buf());
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            config: config.clone(),
+            file,
+            size: 0,
+            opened: Instant::now(),
+        })
+    }
+
+    /// Writes the output to the log; returns the rotated log, if the log was
+    /// rotated before the output.
+    pub fn write(&mut self, buf: &[u8]) -> Result<Option<PathBuf>, FlameError> {
+        let rotated = match self.should_rotate(buf.len()) {
+            true => Some(self.rotate()?),
+            false => None,
+        };
+
+        self.file
+            .write_all(buf)
+            .map_err(|e| FlameError::Internal(format!("failed to write log: {e}")))?;
+        self.size += buf.len() as u64;
+
+        Ok(rotated)
+    }
+
+    fn should_rotate(&self, len: usize) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+
+        self.size + len as u64 > self.config.max_size
+            || self
+                .config
+                .max_age
+                .is_some_and(|age| self.opened.elapsed().as_secs() >= age)
+    }
+
+    /// Renames the log to `<log>.<timestamp>`, and continues with an empty one;
+    /// the timestamp is bumped if the log was rotated in the same microsecond.
+    fn rotate(&mut self) -> Result<PathBuf, FlameError> {
+        let mut timestamp: u128 = Utc::now()
+            .format("%Y%m%d%H%M%S%6f")
+            .to_string()
+            .parse()
+            .unwrap_or_default();
+        let rotated = loop {
+            let rotated = PathBuf::from(format!("{}.{timestamp}", self.path.display()));
+            let compressed = PathBuf::from(format!("{}{COMPRESSED_SUFFIX}", rotated.display()));
+            if !rotated.exists() && !compressed.exists() {
+                break rotated;
+            }
+            timestamp += 1;
+        };
+        fs::rename(&self.path, &rotated)
+            .map_err(|e| FlameError::Internal(format!("failed to rotate log: {e}")))?;
+
+        self.file = open(&self.path)?;
+        self.size = 0;
+        self.opened = Instant::now();
+
+        Ok(rotated)
+    }
+}
+
+impl Drop for RotatingLog {
+    fn drop(&mut self) {
+        if let Ok(mut logs) = LOGS.lock() {
+            logs.remove(&self.path);
+        }
+    }
+}
+
+fn open(path: &Path) -> Result<File, FlameError> {
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|e| FlameError::Internal(format!("failed to open log {}: {e}", path.display())))
+}
+
+/// Compresses the rotated log, and removes the rotated logs of the log beyond
+/// `max_files` and the oldest ones on the node above `max_disk`.
+pub fn compact(log: &Path, rotated: &Path, config: &FlameLogs) -> Result<(), FlameError> {
+    if config.compress {
+        compress(rotated)?;
+    }
+
+    let mut rotated_logs = rotated_logs(log);
+    while rotated_logs.len() > config.max_files {
+        remove(&rotated_logs.remove(0));
+    }
+
+    if let Some(max_disk) = config.max_disk {
+        enforce_disk_cap(max_disk);
+    }
+
+    Ok(())
+}
+
+/// Compresses the rotated log to `<rotated>.zst`; the compressed log is
+/// renamed at last, so a partially compressed log is never listed.
+fn compress(rotated: &Path) -> Result<(), FlameError> {
+    let compressed = PathBuf::from(format!("{}{COMPRESSED_SUFFIX}", rotated.display()));
+    let partial = PathBuf::from(format!("{}.tmp", compressed.display()));
+
+    let source = File::open(rotated)
+        .map_err(|e| FlameError::Internal(format!("failed to open rotated log: {e}")))?;
+    let target = File::create(&partial)
+        .map_err(|e| FlameError::Internal(format!("failed to create compressed log: {e}")))?;
+    zstd::stream::copy_encode(BufReader::new(source), target, COMPRESSION_LEVEL)
+        .map_err(|e| FlameError::Internal(format!("failed to compress rotated log: {e}")))?;
+
+    remove(rotated);
+    fs::rename(&partial, &compressed)
+        .map_err(|e| FlameError::Internal(format!("failed to rename compressed log: {e}")))?;
+
+    Ok(())
+}
+
+/// The rotated logs of the log, the oldest first.
+fn rotated_logs(log: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (log.parent(), log.file_name()) else {
+        return vec![];
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut rotated: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str()?.strip_prefix(&prefix))
+                .is_some_and(|suffix| timestamp_of(suffix).is_some())
+        })
+        .collect();
+    rotated.sort_by_key(|path| rotation_time(path));
+
+    rotated
+}
+
+/// The timestamp of the rotated log by the suffix after the name of its log,
+/// e.g. `20261015120000000000` or `20261015120000000000.zst`.
+fn timestamp_of(suffix: &str) -> Option<&str> {
+    let timestamp = suffix.strip_suffix(COMPRESSED_SUFFIX).unwrap_or(suffix);
+    (!timestamp.is_empty() && timestamp.bytes().all(|b| b.is_ascii_digit())).then_some(timestamp)
+}
+
+/// The rotation time of the rotated log, i.e. its last timestamp in the name.
+fn rotation_time(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = name.strip_suffix(COMPRESSED_SUFFIX).unwrap_or(&name);
+    name.rsplit('.').next().unwrap_or_default().to_string()
+}
+
+/// Removes the oldest rotated logs of the instances on the node while the
+/// total size of their logs is above the cap.
+fn enforce_disk_cap(max_disk: u64) {
+    let logs: Vec<PathBuf> = match LOGS.lock() {
+        Ok(logs) => logs.iter().cloned().collect(),
+        Err(_) => return,
+    };
+
+    let mut total = 0;
+    let mut rotated = vec![];
+    for log in &logs {
+        total += size_of(log);
+        for path in rotated_logs(log) {
+            total += size_of(&path);
+            rotated.push(path);
+        }
+    }
+    rotated.sort_by_key(|path| rotation_time(path));
+
+    for path in rotated {
+        if total <= max_disk {
+            return;
+        }
+        total = total.saturating_sub(size_of(&path));
+        remove(&path);
+    }
+
+    if total > max_disk {
+        tracing::warn!("The logs of the instances take {total} bytes above the cap {max_disk}.");
+    }
+}
+
+fn size_of(path: &Path) -> u64 {
+    fs::metadata(path)
+        .map(|meta| meta.len())
+        .unwrap_or_default()
+}
+
+/// Removes the rotated log; it may be removed by another log already.
+fn remove(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!("Failed to remove rotated log <{}>: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_size: u64, max_files: usize, compress: bool) -> FlameLogs {
+        FlameLogs {
+            max_size,
+            max_age: None,
+            max_files,
+            compress,
+            max_disk: None,
+        }
+    }
+
+    #[test]
+    fn test_rotate_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("executor-1.err");
+        let config = config(10, 2, false);
+        let mut log = RotatingLog::create(&path, &config).unwrap();
+
+        assert_eq!(log.write(b"0123456789").unwrap(), None);
+        let rotated = log.write(b"abc").unwrap().unwrap();
+        assert_eq!(fs::read(&rotated).unwrap(), b"0123456789");
+        assert_eq!(fs::read(&path).unwrap(), b"abc");
+
+        // The oldest rotated logs beyond max_files are removed.
+        compact(&path, &rotated, &config).unwrap();
+        for _ in 0..3 {
+            let rotated = log.write(&[b'x'; 10]).unwrap().unwrap();
+            compact(&path, &rotated, &config).unwrap();
+        }
+        assert_eq!(rotated_logs(&path).len(), 2);
+    }
+
+    #[test]
+    fn test_compress_rotated_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("executor-1.out");
+        let config = config(4, 5, true);
+        let mut log = RotatingLog::create(&path, &config).unwrap();
+
+        log.write(b"hello").unwrap();
+        let rotated = log.write(b"world").unwrap().unwrap();
+        compact(&path, &rotated, &config).unwrap();
+
+        let rotated_logs = rotated_logs(&path);
+        assert_eq!(rotated_logs.len(), 1);
+        assert!(rotated_logs[0]
+            .to_string_lossy()
+            .ends_with(COMPRESSED_SUFFIX));
+        assert!(!rotated.exists());
+        let data = zstd::decode_all(File::open(&rotated_logs[0]).unwrap()).unwrap();
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn test_rotated_logs_of_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("executor-1.out");
+        for name in [
+            "executor-1.out.20261015120000001.zst",
+            "executor-1.out.20261015120000000",
+            "executor-1.out.20261015120000002.zst.tmp",
+            "executor-1.outx.20261015120000000",
+            "executor-10.out.20261015120000000",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let names: Vec<String> = rotated_logs(&path)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "executor-1.out.20261015120000000".to_string(),
+                "executor-1.out.20261015120000001.zst".to_string()
+            ]
+        );
+    }
+}
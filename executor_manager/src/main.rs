@@ -12,18 +12,14 @@ limitations under the License.
 */
 
 use clap::Parser;
-use futures::future::select_all;
 use tokio::runtime::{Builder, Runtime};
+use tokio::task::JoinSet;
 
 use common::ctx::FlameClusterContext;
 use common::FlameError;
 
-mod client;
-mod executor;
-mod manager;
-mod shims;
-mod states;
-mod stream_handler;
+use flame_executor_manager::resources::SlotPolicy;
+use flame_executor_manager::{bootstrap, manager, node_config, privileges};
 
 #[derive(Parser)]
 #[command(name = "flame-executor-manager")]
@@ -33,8 +29,12 @@ mod stream_handler;
 struct Cli {
     #[arg(long)]
     config: Option<String>,
+    /// The slots of this node; auto-detected from the CPUs/memory if not set
     #[arg(long)]
-    slots: Option<i32>,
+    slots: Option<u32>,
+    /// The resources reserved for the system, e.g. cpu=2,memory=4Gi
+    #[arg(long)]
+    system_reserved: Option<String>,
 }
 
 fn build_runtime(name: &str, threads: usize) -> Result<Runtime, FlameError> {
@@ -48,14 +48,42 @@ fn build_runtime(name: &str, threads: usize) -> Result<Runtime, FlameError> {
 
 #[tokio::main]
 async fn main() -> Result<(), FlameError> {
-    let _log_guard = common::init_logger(Some("fem"))?;
-
     let cli = Cli::parse();
-    let ctx = FlameClusterContext::from_file(cli.config)?;
+    let mut ctx = FlameClusterContext::from_file(cli.config)?;
+    ctx.paths.create_dirs()?;
+
+    let _log_guard = common::init_logger(Some("fem"), Some(&ctx.paths.logs))?;
 
     tracing::info!("flame-executor-manager is starting ...");
 
-    let mut handlers = vec![];
+    // Fail early if the instances can not be started, e.g. the executor manager
+    // runs unprivileged but the instances are configured to run as another user.
+    if let Err(e) = privileges::init(&ctx) {
+        tracing::error!("Failed to check the privileges: {e}");
+        return Err(e);
+    }
+
+    // Apply the node-level configuration persisted from the last connection to
+    // the session manager, e.g. the cache settings which are only applied at start.
+    let node_config = node_config::load(&ctx.paths.work);
+    if let Some(cache) = ctx.cache.as_mut() {
+        node_config.apply_cache(cache);
+    }
+    if let Some(level) = &node_config.log_level {
+        if let Err(e) = common::set_log_level(Some(level)) {
+            tracing::warn!("Failed to set log level <{level}>: {e}");
+        }
+    }
+
+    let policy = SlotPolicy::new(
+        ctx.cluster.slot.clone(),
+        cli.system_reserved.as_deref(),
+        cli.slots,
+    );
+
+    // The components, each runs in its dedicated runtime; the executor manager
+    // exits when any of them exits.
+    let mut components = JoinSet::new();
 
     let num_cpus = std::thread::available_parallelism()
         .map(|p| p.get())
@@ -70,29 +98,30 @@ async fn main() -> Result<(), FlameError> {
         0
     };
     let manager_threads = ctx.cluster.limits.max_executors as usize + 1;
+    let bootstrap_concurrency = ctx
+        .cluster
+        .executors
+        .bootstrap_concurrency
+        .unwrap_or(num_cpus);
+    bootstrap::init(bootstrap_concurrency);
 
     tracing::info!(
-        "CPU allocation: total={}, cache={}, manager={}, max_executors={}",
+        "CPU allocation: total={}, cache={}, manager={}, max_executors={}, bootstrap_concurrency={}",
         num_cpus,
         cache_threads,
         manager_threads,
-        ctx.cluster.limits.max_executors
+        ctx.cluster.limits.max_executors,
+        bootstrap_concurrency
     );
 
-    // Keep dedicated runtimes alive for the lifetime of their join handles.
+    // Keep dedicated runtimes alive until the components are shut down.
     let cache_rt = if let Some(ref cache_config) = ctx.cache {
         let cache_rt = build_runtime("cache", cache_threads)?;
         let cache_config = cache_config.clone();
-        let handler = cache_rt.spawn(async move {
-            let result = flame_cache::run(&cache_config).await;
-            if let Err(e) = &result {
-                tracing::error!("Object cache exited with error: {e}");
-            } else {
-                tracing::info!("Object cache exited successfully.");
-            }
-            result
-        });
-        handlers.push(handler);
+        components.spawn_on(
+            async move { ("Object cache", flame_cache::run(&cache_config).await) },
+            cache_rt.handle(),
+        );
         tracing::info!("Object cache thread started.");
         Some(cache_rt)
     } else {
@@ -104,22 +133,39 @@ async fn main() -> Result<(), FlameError> {
     let manager_rt = build_runtime("manager", manager_threads)?;
     {
         let ctx = ctx.clone();
-        let handler = manager_rt.spawn(async move {
-            let result = manager::run(&ctx).await;
-            if let Err(e) = &result {
-                tracing::error!("Executor manager exited with error: {e}");
-            } else {
-                tracing::info!("Executor manager exited successfully.");
-            }
-            result
-        });
-        handlers.push(handler);
+        components.spawn_on(
+            async move { ("Executor manager", manager::run(&ctx, policy).await) },
+            manager_rt.handle(),
+        );
     }
 
     tracing::info!("flame-executor-manager started.");
 
-    let (res, idx, _) = select_all(handlers).await;
-    tracing::info!("Thread <{idx}> exited with result: {res:?}");
+    let result = match components.join_next().await {
+        Some(Ok((name, result))) => {
+            match &result {
+                Ok(_) => tracing::info!("{name} exited successfully."),
+                Err(e) => tracing::error!("{name} exited with error: {e}"),
+            }
+            result
+        }
+        Some(Err(e)) => {
+            tracing::error!("Component exited unexpectedly: {e}");
+            Err(FlameError::Internal(format!(
+                "component exited unexpectedly: {e}"
+            )))
+        }
+        None => Ok(()),
+    };
+
+    // Shut down the remaining components and their runtimes; the runtimes can
+    // not be dropped in the async context, so they are shut down in background.
+    tracing::info!("flame-executor-manager is shutting down ...");
+    components.shutdown().await;
+    if let Some(cache_rt) = cache_rt {
+        cache_rt.shutdown_background();
+    }
+    manager_rt.shutdown_background();
 
-    Ok(())
+    result
 }
@@ -20,8 +20,12 @@ use common::apis::ExecutorState;
 use common::{ctx::FlameClusterContext, FlameError};
 use stdng::{lock_ptr, MutexPtr};
 
+use crate::artifacts;
 use crate::client::BackendClient;
 use crate::executor::{self, Executor, ExecutorPtr};
+use crate::limits;
+use crate::resources::SlotPolicy;
+use crate::scratch;
 use crate::stream_handler::StreamHandler;
 
 /// Messages sent from StreamHandler to ExecutorManager
@@ -34,20 +38,32 @@ pub struct ExecutorManager {
     ctx: FlameClusterContext,
     executors: MutexPtr<HashMap<String, ExecutorPtr>>,
     client: BackendClient,
+    policy: SlotPolicy,
 }
 
 impl ExecutorManager {
-    pub async fn new(ctx: &FlameClusterContext) -> Result<Self, FlameError> {
+    pub async fn new(ctx: &FlameClusterContext, policy: SlotPolicy) -> Result<Self, FlameError> {
         // Create the Flame directory.
-        fs::create_dir_all("/tmp/flame/shim")
+        fs::create_dir_all(ctx.paths.work.join("shim"))
             .map_err(|e| FlameError::Internal(format!("failed to create shim directory: {e}")))?;
 
         let client = BackendClient::new(ctx).await?;
+        limits::init(ctx.cluster.limits.max_executors);
+        artifacts::init(ctx);
+
+        // No executor is bound yet, so the scratch directories are left by the
+        // last run, e.g. which crashed.
+        match scratch::sweep(&ctx.paths.scratch) {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Removed {n} scratch directories left by the last run."),
+            Err(e) => tracing::warn!("Failed to sweep the scratch directories: {e}"),
+        }
 
         Ok(Self {
             ctx: ctx.clone(),
             executors: Arc::new(Mutex::new(HashMap::new())),
             client,
+            policy,
         })
     }
 
@@ -66,11 +82,13 @@ impl ExecutorManager {
 
         // Share executors reference with StreamHandler for re-registration
         let executors_for_handler = self.executors.clone();
+        let policy = self.policy.clone();
+        let work_dir = self.ctx.paths.work.clone();
 
         // Spawn the stream handler (long-running, self-recovering task)
         // StreamHandler handles register_node + watch_node on each connection
         let stream_handle = tokio::spawn(async move {
-            let mut handler = StreamHandler::new(client, executors_for_handler);
+            let mut handler = StreamHandler::new(client, executors_for_handler, policy, work_dir);
             handler.run(executor_tx).await;
         });
 
@@ -98,7 +116,9 @@ impl ExecutorManager {
     ///
     /// Action derivation logic:
     /// - If state is Released -> Remove from map
-    /// - If ID is new -> Create and start executor
+    /// - If ID is new -> Create and start executor, or reject it if the node
+    ///   reached its max executors
+    /// - If preempted -> Interrupt the running task of the existing executor
     /// - Otherwise -> Log debug message (existing executor, no action needed)
     fn handle_executor_update(&mut self, mut executor: Executor) -> Result<(), FlameError> {
         let executor_id = executor.id.clone();
@@ -117,8 +137,19 @@ impl ExecutorManager {
             return Ok(());
         }
 
-        // 2. If ID is new (not in map), create and start executor
+        // 2. If ID is new (not in map), create and start executor; the
+        //    executors beyond the max of the node are rejected.
         if !executors.contains_key(&executor_id) {
+            if let Err(e) = limits::check_executors(&executor_id, executors.len()) {
+                let mut client = self.client.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = client.reject_executor(&executor, &e.to_string()).await {
+                        tracing::warn!("Failed to reject executor <{}>: {err}", executor.id);
+                    }
+                });
+                return Ok(());
+            }
+
             tracing::info!(
                 "Creating executor <{}> (state={:?}, shim={:?})",
                 executor_id,
@@ -135,7 +166,8 @@ impl ExecutorManager {
             return Ok(());
         }
 
-        // 3. Otherwise (existing ID, not Released), just log debug message
+        // 3. Otherwise (existing ID, not Released), interrupt the running task
+        //    if the executor is preempted.
         if let Some(existing) = executors.get(&executor_id) {
             let existing = lock_ptr!(existing)?;
             tracing::debug!(
@@ -144,14 +176,18 @@ impl ExecutorManager {
                 existing.state,
                 state
             );
+            if executor.preempted && state == ExecutorState::Unbinding {
+                tracing::info!("Executor <{}> is preempted.", executor_id);
+                existing.preemption.notify_waiters();
+            }
         }
 
         Ok(())
     }
 }
 
-pub async fn run(ctx: &FlameClusterContext) -> Result<(), FlameError> {
-    let mut manager = ExecutorManager::new(ctx).await?;
+pub async fn run(ctx: &FlameClusterContext, policy: SlotPolicy) -> Result<(), FlameError> {
+    let mut manager = ExecutorManager::new(ctx, policy).await?;
     manager.run().await?;
 
     Ok(())
@@ -0,0 +1,84 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The node-level configuration distributed by the session manager.
+//!
+//! The session manager sends the configuration of the node on each WatchNode
+//! connection; it's persisted in the work directory, so it's applied at the
+//! next start before connecting, e.g. the settings of the object cache which
+//! are only applied at start. The slots and the log level are applied at once.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::ctx::NodeConfig;
+use common::FlameError;
+
+const NODE_CONFIG_FILE: &str = "node-config.json";
+
+fn config_path(work_dir: &Path) -> PathBuf {
+    work_dir.join(NODE_CONFIG_FILE)
+}
+
+/// Loads the persisted configuration, or the empty one if it was never
+/// received or it's invalid.
+pub fn load(work_dir: &Path) -> NodeConfig {
+    let path = config_path(work_dir);
+    let Ok(data) = fs::read(&path) else {
+        return NodeConfig::default();
+    };
+
+    serde_json::from_slice(&data).unwrap_or_else(|e| {
+        tracing::warn!("Ignore invalid node config <{}>: {e}", path.display());
+        NodeConfig::default()
+    })
+}
+
+/// Persists the configuration; it's written into a temporary file first, so
+/// a partial one is never loaded.
+pub fn save(work_dir: &Path, config: &NodeConfig) -> Result<(), FlameError> {
+    let path = config_path(work_dir);
+    let tmp_path = path.with_extension("json.tmp");
+
+    let data = serde_json::to_vec_pretty(config)
+        .map_err(|e| FlameError::Internal(format!("failed to encode node config: {e}")))?;
+    fs::write(&tmp_path, data)?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load(dir.path()), NodeConfig::default());
+
+        let config = NodeConfig {
+            slots: Some(4),
+            labels: vec!["gpu".to_string()],
+            log_level: Some("debug".to_string()),
+            cache_max_memory: Some(1024),
+            cache_max_objects: None,
+            prefetch: vec!["llm-7b".to_string()],
+        };
+        save(dir.path(), &config).unwrap();
+        assert_eq!(load(dir.path()), config);
+
+        fs::write(dir.path().join(NODE_CONFIG_FILE), "{").unwrap();
+        assert_eq!(load(dir.path()), NodeConfig::default());
+    }
+}
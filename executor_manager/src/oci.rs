@@ -0,0 +1,810 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Pulling the Wasm modules of the applications from the OCI registries.
+//!
+//! The `image` of a Wasm application refers to the artifact of its module in
+//! an OCI registry, e.g. `ghcr.io/org/app:v1` or `ghcr.io/org/app@sha256:...`
+//! as pushed by `oras` or `wkg`. The executor manager resolves the manifest of
+//! the reference, pulls the Wasm layer of the artifact and verifies its digest.
+//! The modules are cached in the work directory by their digests, so a module
+//! is pulled once per node even if its tag is resolved again; the references
+//! pinned by digest are resolved locally once pulled.
+//!
+//! The registries are authenticated by the credentials of docker's config.json,
+//! i.e. by basic auth or the bearer token issued by the token service of the
+//! registry, as the container tooling does.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::Engine;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Empty};
+use hyper::header::{ACCEPT, AUTHORIZATION, HOST, LOCATION, USER_AGENT, WWW_AUTHENTICATE};
+use hyper::{HeaderMap, Request, StatusCode};
+use hyper_util::rt::TokioIo;
+use ring::digest;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, RootCertStore};
+use serde_derive::Deserialize;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use url::Url;
+
+use common::ctx::FlameRegistry;
+use common::FlameError;
+
+const DEFAULT_REGISTRY: &str = "docker.io";
+const DOCKER_HUB: &str = "registry-1.docker.io";
+const DEFAULT_TAG: &str = "latest";
+const MAX_REDIRECTS: usize = 5;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+const MANIFEST_TYPES: &[&str] = &[
+    "application/vnd.oci.image.manifest.v1+json",
+    "application/vnd.oci.image.index.v1+json",
+    "application/vnd.docker.distribution.manifest.v2+json",
+    "application/vnd.docker.distribution.manifest.list.v2+json",
+];
+
+/// The media types of the Wasm layers pushed by the common tools, e.g. `oras`,
+/// `wkg` and `wasm-to-oci`.
+const WASM_LAYER_TYPES: &[&str] = &[
+    "application/wasm",
+    "application/vnd.wasm.content.layer.v1+wasm",
+    "application/vnd.bytecodealliance.wasm.component.layer.v0+wasm",
+    "application/vnd.module.wasm.content.layer.v1+wasm",
+];
+
+/// The reference of an artifact, i.e. `[registry/]repository[:tag][@digest]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reference {
+    pub registry: String,
+    pub repository: String,
+    pub tag: String,
+    pub digest: Option<String>,
+}
+
+impl Reference {
+    pub fn parse(image: &str) -> Result<Self, FlameError> {
+        let invalid = || FlameError::InvalidConfig(format!("invalid image reference <{image}>"));
+
+        let image = image.strip_prefix("oci://").unwrap_or(image);
+        let (name, digest) = match image.split_once('@') {
+            Some((name, digest)) => {
+                validate_digest(digest)?;
+                (name, Some(digest.to_string()))
+            }
+            None => (image, None),
+        };
+
+        // The tag is after the last '/', so the port of the registry is not a tag.
+        let (name, tag) = match name.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, tag),
+            _ => (name, DEFAULT_TAG),
+        };
+
+        // The first segment is the registry if it looks like a host, as docker does.
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, path))
+                if host.contains('.') || host.contains(':') || host == "localhost" =>
+            {
+                (host.to_string(), path.to_string())
+            }
+            _ => (DEFAULT_REGISTRY.to_string(), name.to_string()),
+        };
+        let repository = match registry == DEFAULT_REGISTRY && !repository.contains('/') {
+            true => format!("library/{repository}"),
+            false => repository,
+        };
+
+        let valid_repository = !repository.is_empty()
+            && repository.split('/').all(|segment| {
+                !segment.is_empty()
+                    && segment != ".."
+                    && segment
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c))
+            });
+        if !valid_repository || tag.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Reference {
+            registry,
+            repository,
+            tag: tag.to_string(),
+            digest,
+        })
+    }
+
+    /// The host of the registry API, i.e. Docker Hub's for `docker.io`.
+    fn host(&self) -> &str {
+        match self.registry.as_str() {
+            DEFAULT_REGISTRY => DOCKER_HUB,
+            registry => registry,
+        }
+    }
+}
+
+/// Validates the digest, and returns its hex; only sha256 is supported.
+fn validate_digest(digest: &str) -> Result<&str, FlameError> {
+    digest
+        .strip_prefix("sha256:")
+        .filter(|hex| {
+            hex.len() == 64
+                && hex
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        })
+        .ok_or_else(|| {
+            FlameError::InvalidConfig(format!(
+                "unsupported digest <{digest}>: only sha256 is supported"
+            ))
+        })
+}
+
+fn sha256(data: &[u8]) -> String {
+    let hex: String = digest::digest(&digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    format!("sha256:{hex}")
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, DockerAuth>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DockerAuth {
+    auth: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+/// The host of a key of docker's config.json, e.g. `https://index.docker.io/v1/`.
+fn auth_host(key: &str) -> &str {
+    let key = key.split_once("://").map(|(_, rest)| rest).unwrap_or(key);
+    let host = key.split('/').next().unwrap_or(key);
+    match host {
+        "index.docker.io" | DOCKER_HUB => DEFAULT_REGISTRY,
+        host => host,
+    }
+}
+
+fn auth_file(config: &FlameRegistry) -> Option<PathBuf> {
+    if let Some(path) = &config.auth_file {
+        return Some(PathBuf::from(path));
+    }
+    if let Ok(dir) = std::env::var("DOCKER_CONFIG") {
+        return Some(Path::new(&dir).join("config.json"));
+    }
+    std::env::var("HOME")
+        .ok()
+        .map(|home| Path::new(&home).join(".docker").join("config.json"))
+}
+
+/// The basic credential of the registry, i.e. base64 of `username:password`.
+fn load_credential(config: &FlameRegistry, registry: &str) -> Result<Option<String>, FlameError> {
+    let Some(path) = auth_file(config) else {
+        return Ok(None);
+    };
+    // The default files are optional, while the configured one must be valid.
+    let content = match fs::read(&path) {
+        Ok(content) => content,
+        Err(_) if config.auth_file.is_none() => return Ok(None),
+        Err(e) => {
+            return Err(FlameError::InvalidConfig(format!(
+                "failed to read registry auth_file <{}>: {e}",
+                path.display()
+            )))
+        }
+    };
+    let docker: DockerConfig = serde_json::from_slice(&content).map_err(|e| {
+        FlameError::InvalidConfig(format!(
+            "invalid registry auth_file <{}>: {e}",
+            path.display()
+        ))
+    })?;
+
+    let credential = docker
+        .auths
+        .into_iter()
+        .find(|(key, _)| auth_host(key) == registry)
+        .and_then(
+            |(_, auth)| match (auth.auth, auth.username, auth.password) {
+                (Some(auth), _, _) if !auth.is_empty() => Some(auth),
+                (_, Some(username), Some(password)) => Some(
+                    base64::engine::general_purpose::STANDARD
+                        .encode(format!("{username}:{password}")),
+                ),
+                _ => None,
+            },
+        );
+
+    Ok(credential)
+}
+
+/// The TLS config of the registries, verified by the ca_file or the CA bundle
+/// of the system.
+fn tls_config(config: &FlameRegistry) -> Result<ClientConfig, FlameError> {
+    let ca_file = match &config.ca_file {
+        Some(ca_file) => PathBuf::from(ca_file),
+        None => openssl_probe::probe().cert_file.ok_or_else(|| {
+            FlameError::InvalidConfig(
+                "no CA bundle of the system to verify the registries, set registry.ca_file"
+                    .to_string(),
+            )
+        })?,
+    };
+
+    let file = File::open(&ca_file).map_err(|e| {
+        FlameError::InvalidConfig(format!("failed to read <{}>: {e}", ca_file.display()))
+    })?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to parse <{}>: {e}", ca_file.display()))
+        })?;
+    let mut roots = RootCertStore::empty();
+    roots.add_parsable_certificates(certs);
+
+    let mut tls =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| FlameError::InvalidConfig(format!("invalid TLS config: {e}")))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    tls.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(tls)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    /// The manifests of an index, i.e. of the platforms.
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    #[serde(default)]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+    platform: Option<Platform>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Platform {
+    #[serde(default)]
+    architecture: String,
+}
+
+/// The Wasm layer of the artifact, or its only layer.
+fn wasm_layer(manifest: &Manifest) -> Option<&Descriptor> {
+    manifest
+        .layers
+        .iter()
+        .find(|layer| WASM_LAYER_TYPES.contains(&layer.media_type.as_str()))
+        .or(match manifest.layers.as_slice() {
+            [layer] => Some(layer),
+            _ => None,
+        })
+}
+
+struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+/// The client of the registry API of a reference.
+struct RegistryClient {
+    reference: Reference,
+    scheme: &'static str,
+    tls: Option<TlsConnector>,
+    credential: Option<String>,
+    authorization: Option<String>,
+}
+
+impl RegistryClient {
+    fn new(reference: Reference, config: &FlameRegistry) -> Result<Self, FlameError> {
+        let insecure = config.insecure.contains(&reference.registry);
+        let (scheme, tls) = match insecure {
+            true => ("http", None),
+            false => (
+                "https",
+                Some(TlsConnector::from(Arc::new(tls_config(config)?))),
+            ),
+        };
+        let credential = load_credential(config, &reference.registry)?;
+
+        Ok(RegistryClient {
+            reference,
+            scheme,
+            tls,
+            credential,
+            authorization: None,
+        })
+    }
+
+    fn url(&self, path: &str) -> Result<Url, FlameError> {
+        let url = format!(
+            "{}://{}/v2/{}/{path}",
+            self.scheme,
+            self.reference.host(),
+            self.reference.repository
+        );
+        Url::parse(&url).map_err(|e| FlameError::InvalidConfig(format!("invalid url <{url}>: {e}")))
+    }
+
+    /// Gets the path of the repository, authenticated by the challenge of the
+    /// registry if required.
+    async fn get(&mut self, path: &str, accept: &[&str]) -> Result<Bytes, FlameError> {
+        let url = self.url(path)?;
+        let mut response = self
+            .send(url.clone(), accept, self.authorization.clone())
+            .await?;
+        if response.status == StatusCode::UNAUTHORIZED {
+            self.authenticate(&response).await?;
+            response = self
+                .send(url.clone(), accept, self.authorization.clone())
+                .await?;
+        }
+
+        match response.status {
+            status if status.is_success() => Ok(response.body),
+            StatusCode::NOT_FOUND => Err(FlameError::NotFound(format!("<{url}> not found"))),
+            status => Err(FlameError::Network(format!(
+                "failed to get <{url}>: {status} {}",
+                String::from_utf8_lossy(&response.body)
+            ))),
+        }
+    }
+
+    /// Authenticates by the challenge of the registry: the credential is sent by
+    /// basic auth, or exchanged for a bearer token of the repository.
+    async fn authenticate(&mut self, challenge: &Response) -> Result<(), FlameError> {
+        let header = challenge
+            .headers
+            .get(WWW_AUTHENTICATE)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default();
+        let registry = &self.reference.registry;
+
+        let Some(params) = header.strip_prefix("Bearer ") else {
+            let credential = self.credential.as_ref().ok_or_else(|| {
+                FlameError::Unauthenticated(format!("no credential of registry <{registry}>"))
+            })?;
+            self.authorization = Some(format!("Basic {credential}"));
+            return Ok(());
+        };
+
+        let params = parse_challenge(params);
+        let realm = params.get("realm").ok_or_else(|| {
+            FlameError::Unauthenticated(format!("no realm in the challenge of <{registry}>"))
+        })?;
+        let mut url = Url::parse(realm)
+            .map_err(|e| FlameError::Network(format!("invalid realm <{realm}>: {e}")))?;
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(service) = params.get("service") {
+                query.append_pair("service", service);
+            }
+            let scope = params
+                .get("scope")
+                .cloned()
+                .unwrap_or_else(|| format!("repository:{}:pull", self.reference.repository));
+            query.append_pair("scope", &scope);
+        }
+
+        let basic = self.credential.as_ref().map(|c| format!("Basic {c}"));
+        let response = self.send(url, &["application/json"], basic).await?;
+        if !response.status.is_success() {
+            return Err(FlameError::Unauthenticated(format!(
+                "failed to get the token of registry <{registry}>: {}",
+                response.status
+            )));
+        }
+
+        #[derive(Deserialize)]
+        struct Token {
+            token: Option<String>,
+            access_token: Option<String>,
+        }
+        let token: Token = serde_json::from_slice(&response.body)
+            .map_err(|e| FlameError::Network(format!("invalid token of <{registry}>: {e}")))?;
+        let token = token.token.or(token.access_token).ok_or_else(|| {
+            FlameError::Unauthenticated(format!("no token of registry <{registry}>"))
+        })?;
+        self.authorization = Some(format!("Bearer {token}"));
+
+        Ok(())
+    }
+
+    /// Sends the request, following the redirects, e.g. of the blobs to a CDN;
+    /// the authorization is not sent to the other hosts.
+    async fn send(
+        &self,
+        mut url: Url,
+        accept: &[&str],
+        mut authorization: Option<String>,
+    ) -> Result<Response, FlameError> {
+        for _ in 0..=MAX_REDIRECTS {
+            let response = tokio::time::timeout(
+                REQUEST_TIMEOUT,
+                self.request(&url, accept, authorization.as_deref()),
+            )
+            .await
+            .map_err(|_| FlameError::Network(format!("request to <{url}> timed out")))??;
+
+            if !response.status.is_redirection() {
+                return Ok(response);
+            }
+            let location = response
+                .headers
+                .get(LOCATION)
+                .and_then(|h| h.to_str().ok())
+                .ok_or_else(|| FlameError::Network(format!("no location of <{url}>")))?;
+            let next = url
+                .join(location)
+                .map_err(|e| FlameError::Network(format!("invalid location <{location}>: {e}")))?;
+            if next.host_str() != url.host_str() {
+                authorization = None;
+            }
+            url = next;
+        }
+
+        Err(FlameError::Network(format!(
+            "too many redirects of <{url}>"
+        )))
+    }
+
+    async fn request(
+        &self,
+        url: &Url,
+        accept: &[&str],
+        authorization: Option<&str>,
+    ) -> Result<Response, FlameError> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| FlameError::InvalidConfig(format!("no host in <{url}>")))?
+            .to_string();
+        let port = url.port_or_known_default().unwrap_or(443);
+        let failed =
+            |e: std::io::Error| FlameError::Network(format!("failed to connect <{url}>: {e}"));
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let mut request = Request::get(path)
+            .header(HOST, url.authority())
+            .header(USER_AGENT, "flame-executor-manager");
+        if !accept.is_empty() {
+            request = request.header(ACCEPT, accept.join(", "));
+        }
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        let request = request
+            .body(Empty::<Bytes>::new())
+            .map_err(|e| FlameError::Internal(format!("invalid request of <{url}>: {e}")))?;
+
+        let stream = TcpStream::connect((host.as_str(), port))
+            .await
+            .map_err(failed)?;
+        match (url.scheme(), &self.tls) {
+            ("https", Some(tls)) => {
+                let server_name = ServerName::try_from(host.clone()).map_err(|e| {
+                    FlameError::InvalidConfig(format!("invalid server name <{host}>: {e}"))
+                })?;
+                let stream = tls.connect(server_name, stream).await.map_err(failed)?;
+                send_request(stream, request).await
+            }
+            ("http", _) => send_request(stream, request).await,
+            (scheme, _) => Err(FlameError::Network(format!(
+                "unsupported scheme <{scheme}> of <{url}>"
+            ))),
+        }
+    }
+}
+
+/// The parameters of a challenge, e.g. `realm="...",service="...",scope="..."`.
+fn parse_challenge(params: &str) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    let mut rest = params.trim();
+    while let Some((key, value)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let value = value.trim_start();
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, next)) => (value, next),
+                None => (quoted, ""),
+            },
+            None => value.split_once(',').unwrap_or((value, "")),
+        };
+        result.insert(key, value.to_string());
+        rest = next;
+    }
+    result
+}
+
+async fn send_request<S>(io: S, request: Request<Empty<Bytes>>) -> Result<Response, FlameError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let failed = |e: hyper::Error| FlameError::Network(format!("request failed: {e}"));
+
+    let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(io))
+        .await
+        .map_err(failed)?;
+    tokio::spawn(async move {
+        if let Err(e) = conn.await {
+            tracing::debug!("Connection to the registry closed: {e}");
+        }
+    });
+
+    let (parts, body) = sender
+        .send_request(request)
+        .await
+        .map_err(failed)?
+        .into_parts();
+    let body = body.collect().await.map_err(failed)?.to_bytes();
+
+    Ok(Response {
+        status: parts.status,
+        headers: parts.headers,
+        body,
+    })
+}
+
+/// Pulls the Wasm module of the image into the cache directory, and returns
+/// the path of the module.
+pub async fn pull(
+    image: &str,
+    config: &FlameRegistry,
+    cache_dir: &Path,
+) -> Result<PathBuf, FlameError> {
+    let reference = Reference::parse(image)?;
+    let blobs = cache_dir.join("blobs").join("sha256");
+
+    // The manifest of a digest never changes, so its layer is resolved locally.
+    let pinned = match &reference.digest {
+        Some(digest) => Some(cache_dir.join("manifests").join(validate_digest(digest)?)),
+        None => None,
+    };
+    if let Some(layer) = pinned.as_ref().and_then(|p| fs::read_to_string(p).ok()) {
+        let path = blobs.join(layer.trim());
+        if path.exists() {
+            tracing::debug!("Wasm module of <{image}> is cached: {}", path.display());
+            return Ok(path);
+        }
+    }
+
+    let mut client = RegistryClient::new(reference.clone(), config)?;
+    let manifest = fetch_manifest(&mut client, image).await?;
+    let layer = wasm_layer(&manifest)
+        .ok_or_else(|| FlameError::InvalidConfig(format!("no Wasm layer in <{image}>")))?;
+    let hex = validate_digest(&layer.digest)?;
+    let path = blobs.join(hex);
+
+    if !path.exists() {
+        tracing::info!("Pulling Wasm module of <{image}>: {}", layer.digest);
+        let blob = client.get(&format!("blobs/{}", layer.digest), &[]).await?;
+        let actual = sha256(&blob);
+        if actual != layer.digest || (layer.size > 0 && blob.len() as u64 != layer.size) {
+            return Err(FlameError::InvalidState(format!(
+                "digest mismatch of the Wasm module of <{image}>: expected {}, pulled {actual}",
+                layer.digest
+            )));
+        }
+        write_atomic(&path, &blob)?;
+    }
+
+    if let Some(pinned) = pinned {
+        write_atomic(&pinned, hex.as_bytes())?;
+    }
+
+    Ok(path)
+}
+
+/// Fetches the manifest of the reference, i.e. the one of the Wasm platform if
+/// it's an index; the manifests of a digest are verified.
+async fn fetch_manifest(client: &mut RegistryClient, image: &str) -> Result<Manifest, FlameError> {
+    let reference = client.reference.clone();
+    let target = reference.digest.as_deref().unwrap_or(&reference.tag);
+    let mut body = client
+        .get(&format!("manifests/{target}"), MANIFEST_TYPES)
+        .await?;
+    verify_manifest(&body, reference.digest.as_deref(), image)?;
+
+    let mut manifest = parse_manifest(&body, image)?;
+    if manifest.layers.is_empty() && !manifest.manifests.is_empty() {
+        let platform = manifest
+            .manifests
+            .iter()
+            .find(|m| {
+                m.platform
+                    .as_ref()
+                    .is_some_and(|p| p.architecture == "wasm")
+            })
+            .unwrap_or(&manifest.manifests[0])
+            .clone();
+        validate_digest(&platform.digest)?;
+
+        body = client
+            .get(&format!("manifests/{}", platform.digest), MANIFEST_TYPES)
+            .await?;
+        verify_manifest(&body, Some(&platform.digest), image)?;
+        manifest = parse_manifest(&body, image)?;
+    }
+
+    Ok(manifest)
+}
+
+fn verify_manifest(body: &[u8], digest: Option<&str>, image: &str) -> Result<(), FlameError> {
+    let Some(digest) = digest else {
+        return Ok(());
+    };
+    let actual = sha256(body);
+    if actual != digest {
+        return Err(FlameError::InvalidState(format!(
+            "digest mismatch of the manifest of <{image}>: expected {digest}, pulled {actual}"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_manifest(body: &[u8], image: &str) -> Result<Manifest, FlameError> {
+    serde_json::from_slice(body)
+        .map_err(|e| FlameError::InvalidState(format!("invalid manifest of <{image}>: {e}")))
+}
+
+/// Writes the file by renaming a temporary one, so the concurrent pulls of the
+/// same module never see a partial file.
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), FlameError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_parse_reference() {
+        let reference = Reference::parse("ghcr.io/org/app:v1").unwrap();
+        assert_eq!(reference.registry, "ghcr.io");
+        assert_eq!(reference.repository, "org/app");
+        assert_eq!(reference.tag, "v1");
+        assert_eq!(reference.digest, None);
+
+        let reference = Reference::parse("localhost:5000/app").unwrap();
+        assert_eq!(reference.registry, "localhost:5000");
+        assert_eq!(reference.repository, "app");
+        assert_eq!(reference.tag, DEFAULT_TAG);
+
+        let reference = Reference::parse(&format!("oci://hello@{DIGEST}")).unwrap();
+        assert_eq!(reference.registry, DEFAULT_REGISTRY);
+        assert_eq!(reference.host(), DOCKER_HUB);
+        assert_eq!(reference.repository, "library/hello");
+        assert_eq!(reference.digest.as_deref(), Some(DIGEST));
+
+        assert!(Reference::parse("ghcr.io/org/App:v1").is_err());
+        assert!(Reference::parse("ghcr.io/../app").is_err());
+        assert!(Reference::parse("app@md5:1234").is_err());
+        assert!(Reference::parse("app:").is_err());
+    }
+
+    #[test]
+    fn test_load_credential() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"auths": {
+                "https://index.docker.io/v1/": {"auth": "dXNlcjpwYXNz"},
+                "ghcr.io": {"username": "user", "password": "pass"}
+            }}"#,
+        )
+        .unwrap();
+        let config = FlameRegistry {
+            auth_file: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        let credential = Some("dXNlcjpwYXNz".to_string());
+        assert_eq!(load_credential(&config, "docker.io").unwrap(), credential);
+        assert_eq!(load_credential(&config, "ghcr.io").unwrap(), credential);
+        assert_eq!(load_credential(&config, "quay.io").unwrap(), None);
+
+        // The configured auth_file must exist.
+        let config = FlameRegistry {
+            auth_file: Some(dir.path().join("none.json").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        assert!(load_credential(&config, "ghcr.io").is_err());
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        let params = parse_challenge(
+            r#"realm="https://ghcr.io/token",service="ghcr.io",scope="repository:org/app:pull""#,
+        );
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["service"], "ghcr.io");
+        assert_eq!(params["scope"], "repository:org/app:pull");
+    }
+
+    #[test]
+    fn test_wasm_layer() {
+        let manifest: Manifest = serde_json::from_str(&format!(
+            r#"{{"layers": [
+                {{"mediaType": "application/vnd.oci.image.config.v1+json", "digest": "{DIGEST}"}},
+                {{"mediaType": "application/vnd.wasm.content.layer.v1+wasm", "digest": "{DIGEST}", "size": 0}}
+            ]}}"#
+        ))
+        .unwrap();
+        let layer = wasm_layer(&manifest).unwrap();
+        assert_eq!(
+            layer.media_type,
+            "application/vnd.wasm.content.layer.v1+wasm"
+        );
+
+        assert!(wasm_layer(&Manifest::default()).is_none());
+        assert_eq!(sha256(b""), DIGEST);
+    }
+
+    #[tokio::test]
+    async fn test_pull_pinned_module_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let hex = validate_digest(DIGEST).unwrap();
+        write_atomic(&dir.path().join("manifests").join(hex), hex.as_bytes()).unwrap();
+        write_atomic(&dir.path().join("blobs").join("sha256").join(hex), b"").unwrap();
+
+        // The module pinned by digest is never pulled again, e.g. on an offline node.
+        let path = pull(
+            &format!("registry.invalid/org/app@{DIGEST}"),
+            &FlameRegistry::default(),
+            dir.path(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(path, dir.path().join("blobs").join("sha256").join(hex));
+    }
+}
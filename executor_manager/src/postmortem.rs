@@ -0,0 +1,137 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The postmortems of the crashed instances.
+//!
+//! When the instance exits while running a task, its exit status, the tail of
+//! its stderr, whether it dumped a core, and the context of the node, i.e. the
+//! load average and the available memory, are collected into a postmortem;
+//! it's attached to the failed task, so the crash is diagnosed by
+//! `flmctl debug postmortem` instead of a generic failure.
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitStatus;
+
+use chrono::Utc;
+
+use common::apis::TaskPostmortem;
+
+/// Collects the postmortem of the instance exited by the status; the core dump
+/// is the one reported by the status, or a `core` or `core.<pid>` file left in
+/// the directory of the instance.
+pub fn collect(
+    executor_id: &str,
+    node: &str,
+    status: Option<ExitStatus>,
+    stderr_tail: Option<String>,
+    process_dir: &Path,
+    pid: Option<u32>,
+) -> TaskPostmortem {
+    let (signal, core_dumped) = match status {
+        Some(status) => signal_of(&status),
+        None => (None, false),
+    };
+    let core_file = pid
+        .map(|pid| format!("core.{pid}"))
+        .into_iter()
+        .chain(["core".to_string()])
+        .any(|name| process_dir.join(name).is_file());
+
+    TaskPostmortem {
+        executor_id: executor_id.to_string(),
+        node: node.to_string(),
+        exit_code: status.and_then(|status| status.code()),
+        signal,
+        core_dumped: core_dumped || core_file,
+        stderr_tail,
+        load_average: fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|loadavg| parse_load_average(&loadavg)),
+        memory_available: fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| parse_memory_available(&meminfo)),
+        creation_time: Utc::now(),
+    }
+}
+
+/// The signal which terminated the process, and whether it dumped a core.
+#[cfg(unix)]
+fn signal_of(status: &ExitStatus) -> (Option<i32>, bool) {
+    use std::os::unix::process::ExitStatusExt;
+
+    (status.signal(), status.core_dumped())
+}
+
+#[cfg(not(unix))]
+fn signal_of(_status: &ExitStatus) -> (Option<i32>, bool) {
+    (None, false)
+}
+
+/// The load average of the last minute, i.e. the 1st field of `/proc/loadavg`.
+fn parse_load_average(loadavg: &str) -> Option<f64> {
+    loadavg.split_whitespace().next()?.parse().ok()
+}
+
+/// The available memory in bytes, i.e. `MemAvailable` of `/proc/meminfo`.
+fn parse_memory_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|l| l.starts_with("MemAvailable:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node_context() {
+        assert_eq!(
+            parse_load_average("0.52 0.58 0.59 2/1024 12345\n"),
+            Some(0.52)
+        );
+        assert_eq!(parse_load_average(""), None);
+
+        let meminfo = "MemTotal:       16384000 kB\nMemFree:         1024000 kB\nMemAvailable:    8192000 kB\n";
+        assert_eq!(parse_memory_available(meminfo), Some(8192000 * 1024));
+        assert_eq!(parse_memory_available("MemTotal: 1024 kB\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_postmortem() {
+        let status = std::process::Command::new("sh")
+            .args(["-c", "exit 3"])
+            .status()
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let postmortem = collect(
+            "executor-1",
+            "node-1",
+            Some(status),
+            Some("oops".to_string()),
+            dir.path(),
+            Some(42),
+        );
+        assert_eq!(postmortem.exit_code, Some(3));
+        assert_eq!(postmortem.signal, None);
+        assert!(!postmortem.core_dumped);
+        assert_eq!(postmortem.stderr_tail, Some("oops".to_string()));
+
+        // The core file left by the instance.
+        std::fs::write(dir.path().join("core.42"), "").unwrap();
+        let postmortem = collect("executor-1", "node-1", None, None, dir.path(), Some(42));
+        assert!(postmortem.core_dumped);
+    }
+}
@@ -0,0 +1,224 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Prefetching of the task inputs in the object cache.
+//!
+//! When the input of a task is a reference to an object in the object cache,
+//! i.e. its input reference or a reference encoded in its input by the SDKs,
+//! the executor asks the cache to load the objects of the next bundled tasks
+//! into memory while the current task is running, so the IO of the next tasks
+//! overlaps with the compute of the current one. A prefetch is a hit if it's
+//! done before its task is invoked.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use arrow_flight::flight_service_client::FlightServiceClient;
+use arrow_flight::Action;
+use serde_derive::Deserialize;
+use tokio::task::JoinHandle;
+
+use common::apis::{TaskContext, TaskID};
+use common::FlameError;
+
+const PREFETCH_ACTION: &str = "PREFETCH";
+
+/// The prefetches of all the executors on this node.
+static PREFETCHED: AtomicU64 = AtomicU64::new(0);
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// The reference to an object in the object cache, as encoded by the SDKs in BSON.
+#[derive(Debug, Deserialize)]
+struct ObjectRef {
+    endpoint: String,
+    key: String,
+}
+
+impl ObjectRef {
+    /// The object referred by the task input, if any.
+    fn from_input(input: &[u8]) -> Option<Self> {
+        bson::from_slice(input).ok()
+    }
+
+    /// The object of the task input, either its input reference or the
+    /// reference encoded in its inline input.
+    fn from_task(task: &TaskContext) -> Option<Self> {
+        match &task.input_ref {
+            Some(input_ref) => Some(ObjectRef {
+                endpoint: input_ref.endpoint.clone(),
+                key: input_ref.key.clone(),
+            }),
+            None => task
+                .input
+                .as_ref()
+                .and_then(|input| Self::from_input(input)),
+        }
+    }
+}
+
+/// The prefetch hit metrics of the executors on this node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PrefetchStats {
+    pub prefetched: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl fmt::Display for PrefetchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "prefetched={}, hits={}, misses={}",
+            self.prefetched, self.hits, self.misses
+        )
+    }
+}
+
+pub fn stats() -> PrefetchStats {
+    PrefetchStats {
+        prefetched: PREFETCHED.load(Ordering::Relaxed),
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+/// The bounded queue of the prefetches of an executor, at most `depth` tasks
+/// ahead of the running one.
+pub struct Prefetcher {
+    depth: usize,
+    pending: HashMap<TaskID, JoinHandle<Result<(), FlameError>>>,
+}
+
+impl Prefetcher {
+    pub fn new(depth: usize) -> Self {
+        Prefetcher {
+            depth,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Starts prefetching the inputs of the next tasks, up to the depth.
+    pub fn prefetch(&mut self, next: &[TaskContext]) {
+        for task in next.iter().take(self.depth) {
+            if self.pending.contains_key(&task.task_id) {
+                continue;
+            }
+            let Some(object) = ObjectRef::from_task(task) else {
+                continue;
+            };
+
+            PREFETCHED.fetch_add(1, Ordering::Relaxed);
+            self.pending
+                .insert(task.task_id, tokio::spawn(prefetch_object(object)));
+        }
+    }
+
+    /// Records whether the input of the task was prefetched before it's invoked;
+    /// an unfinished prefetch keeps loading the object in background.
+    pub fn on_task_invoke(&mut self, task: &TaskContext) {
+        let Some(handle) = self.pending.remove(&task.task_id) else {
+            return;
+        };
+
+        if handle.is_finished() {
+            HITS.fetch_add(1, Ordering::Relaxed);
+        } else {
+            MISSES.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+async fn prefetch_object(object: ObjectRef) -> Result<(), FlameError> {
+    // The executor manager has no client certificate of the cache, so only the
+    // plaintext endpoints are prefetched.
+    let Some(addr) = object.endpoint.strip_prefix("grpc://") else {
+        tracing::debug!(
+            "Skip prefetching object <{}> from <{}>",
+            object.key,
+            object.endpoint
+        );
+        return Ok(());
+    };
+
+    let result = async {
+        let mut client = FlightServiceClient::connect(format!("http://{addr}"))
+            .await
+            .map_err(|e| FlameError::Network(e.to_string()))?;
+        let mut results = client
+            .do_action(Action {
+                r#type: PREFETCH_ACTION.to_string(),
+                body: object.key.clone().into_bytes().into(),
+            })
+            .await
+            .map_err(|e| FlameError::Network(e.to_string()))?
+            .into_inner();
+        results
+            .message()
+            .await
+            .map_err(|e| FlameError::Network(e.to_string()))?;
+
+        Ok::<(), FlameError>(())
+    }
+    .await;
+
+    if let Err(e) = &result {
+        tracing::warn!("Failed to prefetch object <{}>: {e}", object.key);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_ref_from_input() {
+        let input = bson::to_vec(&bson::doc! {
+            "endpoint": "grpc://127.0.0.1:9090",
+            "key": "app/ssn-1/obj-1",
+            "version": 1i64,
+        })
+        .unwrap();
+        let object = ObjectRef::from_input(&input).unwrap();
+        assert_eq!(object.endpoint, "grpc://127.0.0.1:9090");
+        assert_eq!(object.key, "app/ssn-1/obj-1");
+
+        assert!(ObjectRef::from_input(b"plain input").is_none());
+    }
+
+    #[test]
+    fn test_object_ref_from_task() {
+        let mut task = TaskContext {
+            task_id: 1.into(),
+            session_id: "ssn-1".into(),
+            input: None,
+            input_ref: Some(common::apis::ObjectReference {
+                endpoint: "grpc://127.0.0.1:9090".to_string(),
+                key: "app/ssn-1/input-1".to_string(),
+                checksum: None,
+            }),
+            overrides: Default::default(),
+            checkpoint: None,
+            trace_id: None,
+        };
+        let object = ObjectRef::from_task(&task).unwrap();
+        assert_eq!(object.key, "app/ssn-1/input-1");
+
+        task.input_ref = None;
+        task.input = Some(bytes::Bytes::from("plain input"));
+        assert!(ObjectRef::from_task(&task).is_none());
+    }
+}
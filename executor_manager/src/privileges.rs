@@ -0,0 +1,253 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The privileges of the executor manager and the instances of its executors.
+//!
+//! The executor manager does not need root: it runs as a dedicated user, e.g.
+//! `flame` created by `flmadm install`, and the instances run as the same user.
+//! If `executors.user` or `executors.group` is set, the instances of the host
+//! shim, their hooks and probes run as that user and group instead, and the
+//! directories of the instances are owned by them; it requires the executor
+//! manager to run as root, or with CAP_SETUID, CAP_SETGID and CAP_CHOWN.
+//!
+//! The privileges and the directories are checked at startup, so the executor
+//! manager fails with a clear error instead of failing every instance later.
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use nix::unistd::{Gid, Group, Uid, User};
+
+use common::ctx::{FlameClusterContext, FlameExecutors};
+use common::FlameError;
+
+/// The capabilities of Linux, see capabilities(7).
+const CAP_CHOWN: u32 = 0;
+const CAP_SETGID: u32 = 6;
+const CAP_SETUID: u32 = 7;
+
+/// The Unix sockets are limited to ~100 bytes, leave room for the executor id.
+const MAX_SOCKET_DIR_LEN: usize = 64;
+
+/// The user and group of the instances; the ones of the executor manager if
+/// not initialized.
+static RUN_AS: OnceLock<Option<RunAs>> = OnceLock::new();
+
+/// The user and group the instances of the executors run as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RunAs {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl RunAs {
+    /// Resolves the user and group of the configuration; None if neither is set.
+    fn resolve(executors: &FlameExecutors) -> Result<Option<Self>, FlameError> {
+        if executors.user.is_none() && executors.group.is_none() {
+            return Ok(None);
+        }
+
+        let (uid, mut gid) = match &executors.user {
+            Some(name) => {
+                let user = User::from_name(name)
+                    .map_err(|e| {
+                        FlameError::InvalidConfig(format!("failed to look up user <{name}>: {e}"))
+                    })?
+                    .ok_or(FlameError::InvalidConfig(format!(
+                        "executors.user <{name}> does not exist"
+                    )))?;
+                (user.uid, user.gid)
+            }
+            None => (Uid::effective(), Gid::effective()),
+        };
+
+        if let Some(name) = &executors.group {
+            let group = Group::from_name(name)
+                .map_err(|e| {
+                    FlameError::InvalidConfig(format!("failed to look up group <{name}>: {e}"))
+                })?
+                .ok_or(FlameError::InvalidConfig(format!(
+                    "executors.group <{name}> does not exist"
+                )))?;
+            gid = group.gid;
+        }
+
+        Ok(Some(RunAs {
+            uid: uid.as_raw(),
+            gid: gid.as_raw(),
+        }))
+    }
+}
+
+/// Checks the privileges of the executor manager and prepares the directories
+/// of the instances; returns an error if the instances can not be started.
+pub fn init(ctx: &FlameClusterContext) -> Result<(), FlameError> {
+    let run_as = RunAs::resolve(&ctx.cluster.executors)?;
+
+    if let Some(run_as) = &run_as {
+        let caps = fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| effective_capabilities(&status));
+        let has = |cap: u32| match caps {
+            Some(caps) => caps & (1 << cap) != 0,
+            None => Uid::effective().is_root(),
+        };
+
+        if run_as.uid != Uid::effective().as_raw() && !(has(CAP_SETUID) && has(CAP_CHOWN)) {
+            return Err(FlameError::InvalidConfig(format!(
+                "the executor manager runs as uid <{}> without CAP_SETUID and CAP_CHOWN, \
+                 so the instances can not run as uid <{}>; run it as root, grant the \
+                 capabilities, or unset executors.user",
+                Uid::effective(),
+                run_as.uid
+            )));
+        }
+        if run_as.gid != Gid::effective().as_raw() && !(has(CAP_SETGID) && has(CAP_CHOWN)) {
+            return Err(FlameError::InvalidConfig(format!(
+                "the executor manager runs as gid <{}> without CAP_SETGID and CAP_CHOWN, \
+                 so the instances can not run as gid <{}>; run it as root, grant the \
+                 capabilities, or unset executors.group",
+                Gid::effective(),
+                run_as.gid
+            )));
+        }
+    }
+
+    for (name, path) in [
+        ("work", &ctx.paths.work),
+        ("cache", &ctx.paths.cache),
+        ("socket", &ctx.paths.sockets),
+        ("scratch", &ctx.paths.scratch),
+    ] {
+        check_writable(name, path)?;
+    }
+
+    if ctx.paths.sockets.as_os_str().len() > MAX_SOCKET_DIR_LEN {
+        tracing::warn!(
+            "The socket directory <{}> is longer than {MAX_SOCKET_DIR_LEN} bytes, the sockets \
+             of the instances may exceed the limit of Unix sockets.",
+            ctx.paths.sockets.display()
+        );
+    }
+
+    if RUN_AS.set(run_as).is_err() {
+        tracing::warn!("The privileges of the instances were initialized already.");
+    }
+
+    // The instances create their sockets and scratch directories in the roots.
+    chown(&ctx.paths.sockets)?;
+    chown(&ctx.paths.scratch)?;
+
+    match run_as {
+        Some(run_as) => tracing::info!(
+            "The instances run as uid <{}> and gid <{}>.",
+            run_as.uid,
+            run_as.gid
+        ),
+        None => tracing::info!(
+            "The instances run as the executor manager, i.e. uid <{}>.",
+            Uid::effective()
+        ),
+    }
+
+    Ok(())
+}
+
+/// The user and group of the instances, if they are different from the ones
+/// of the executor manager.
+pub fn run_as() -> Option<RunAs> {
+    RUN_AS.get().copied().flatten()
+}
+
+/// Runs the command as the user and group of the instances.
+pub fn apply(cmd: &mut tokio::process::Command) {
+    if let Some(run_as) = run_as() {
+        cmd.uid(run_as.uid).gid(run_as.gid);
+    }
+}
+
+/// Changes the owner of the directory of the instances to their user and group.
+pub fn chown(path: &Path) -> Result<(), FlameError> {
+    let Some(run_as) = run_as() else {
+        return Ok(());
+    };
+
+    std::os::unix::fs::chown(path, Some(run_as.uid), Some(run_as.gid)).map_err(|e| {
+        FlameError::Internal(format!(
+            "failed to change owner of <{}> to <{}:{}>: {e}",
+            path.display(),
+            run_as.uid,
+            run_as.gid
+        ))
+    })
+}
+
+fn check_writable(name: &str, path: &Path) -> Result<(), FlameError> {
+    let probe = path.join(format!(".flame-probe-{}", std::process::id()));
+    fs::create_dir_all(path)
+        .and_then(|_| fs::write(&probe, b""))
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| {
+            FlameError::InvalidConfig(format!(
+                "the {name} directory <{}> is not writable by uid <{}>: {e}",
+                path.display(),
+                Uid::effective()
+            ))
+        })
+}
+
+/// The effective capabilities in the status of a process, i.e. `CapEff`.
+fn effective_capabilities(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|caps| u64::from_str_radix(caps.trim(), 16).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_capabilities() {
+        let status = "Name:\tflame\nCapInh:\t0000000000000000\nCapPrm:\t00000000000000c1\n\
+                      CapEff:\t00000000000000c1\nCapBnd:\t000001ffffffffff\n";
+        let caps = effective_capabilities(status).unwrap();
+        assert!(caps & (1 << CAP_CHOWN) != 0);
+        assert!(caps & (1 << CAP_SETGID) != 0);
+        assert!(caps & (1 << CAP_SETUID) != 0);
+        assert!(caps & (1 << 1) == 0);
+
+        assert_eq!(effective_capabilities("Name:\tflame\n"), None);
+    }
+
+    #[test]
+    fn test_run_as() -> Result<(), FlameError> {
+        assert_eq!(RunAs::resolve(&FlameExecutors::default())?, None);
+
+        let executors = FlameExecutors {
+            user: Some("root".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(RunAs::resolve(&executors)?, Some(RunAs { uid: 0, gid: 0 }));
+
+        let executors = FlameExecutors {
+            user: Some("flame-no-such-user".to_string()),
+            ..Default::default()
+        };
+        assert!(RunAs::resolve(&executors).is_err());
+
+        Ok(())
+    }
+}
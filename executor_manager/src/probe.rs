@@ -0,0 +1,224 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Health probes of the bound instances.
+//!
+//! When the application defines a health probe, the executor probes its
+//! instance periodically while it runs the tasks; the instance is unhealthy if
+//! the probe fails `failure_threshold` times in a row, e.g. a wedged service
+//! process which never completes its task. The executor then fails its tasks
+//! to dispatch them again, and unbinds from the session with the reason, which
+//! is recorded as an event of the session.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use tokio::process::Command;
+use tokio::time::MissedTickBehavior;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+
+use common::apis::{HealthProbe, Shim};
+use common::{endpoint, FlameError, FLAME_INSTANCE_ENDPOINT};
+
+use crate::executor::Executor;
+use crate::privileges;
+use crate::shims;
+
+/// The method of the standard gRPC health check, i.e. grpc.health.v1.
+const GRPC_HEALTH_CHECK: &str = "/grpc.health.v1.Health/Check";
+/// The `SERVING` status of the gRPC health check.
+const SERVING: i32 = 1;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct HealthCheckRequest {
+    #[prost(string, tag = "1")]
+    service: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct HealthCheckResponse {
+    #[prost(int32, tag = "1")]
+    status: i32,
+}
+
+/// The health probe of the instance of an executor.
+pub struct Prober {
+    executor_id: String,
+    probe: HealthProbe,
+    /// The directory where the instance runs, i.e. the one of the command probe.
+    process_dir: PathBuf,
+    /// The endpoint of the instance, i.e. the one of the gRPC probe.
+    socket: PathBuf,
+}
+
+impl Prober {
+    /// The prober of the executor's instance, if the application of its session
+    /// has a health probe; only the host instances are probed.
+    pub fn new(executor: &Executor) -> Option<Self> {
+        if executor.shim != Shim::Host {
+            return None;
+        }
+        let app = &executor.session.as_ref()?.application;
+        let probe = app.health_probe.clone()?;
+        let paths = executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.paths.clone())
+            .unwrap_or_default();
+
+        Some(Self {
+            executor_id: executor.id.clone(),
+            probe,
+            process_dir: shims::process_dir(app, &executor.id, &paths.work),
+            socket: shims::socket_path(&executor.id, &paths.sockets),
+        })
+    }
+
+    /// Probes the instance periodically, and returns the reason once the probe
+    /// fails `failure_threshold` times in a row; it never returns while the
+    /// instance is healthy.
+    pub async fn unhealthy(&self) -> String {
+        let threshold = self.probe.failure_threshold();
+        let mut failures = 0;
+
+        let mut interval = tokio::time::interval(self.probe.period());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick is completed immediately, so the first probe is run
+        // after one period.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            match self.check().await {
+                Ok(()) => failures = 0,
+                Err(e) => {
+                    failures += 1;
+                    tracing::warn!(
+                        "Health probe of executor <{}> failed ({failures}/{threshold}): {e}",
+                        self.executor_id
+                    );
+                    if failures >= threshold {
+                        return format!("health probe failed {failures} times in a row: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    async fn check(&self) -> Result<(), FlameError> {
+        let timeout = self.probe.timeout();
+        let checked = match (&self.probe.command, &self.probe.grpc_service) {
+            (Some(command), _) => tokio::time::timeout(timeout, self.check_command(command)).await,
+            (None, Some(service)) => tokio::time::timeout(timeout, self.check_grpc(service)).await,
+            (None, None) => return Ok(()),
+        };
+
+        checked.map_err(|_| {
+            FlameError::Network(format!(
+                "health probe timed out after {}s",
+                timeout.as_secs()
+            ))
+        })?
+    }
+
+    /// Runs the command by `sh -c` in the directory of the instance; the child is
+    /// killed if the probe times out.
+    async fn check_command(&self, command: &str) -> Result<(), FlameError> {
+        let mut cmd = Command::new("sh");
+        privileges::apply(&mut cmd);
+        let output = cmd
+            .arg("-c")
+            .arg(command)
+            .env(FLAME_INSTANCE_ENDPOINT, &self.socket)
+            .current_dir(&self.process_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| FlameError::Internal(format!("failed to run <{command}>: {e}")))?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(FlameError::Internal(format!(
+            "<{command}> exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )))
+    }
+
+    /// Calls the standard gRPC health check of the instance at its endpoint.
+    async fn check_grpc(&self, service: &str) -> Result<(), FlameError> {
+        let channel = endpoint::connect(&self.socket).await?;
+
+        let mut client = tonic::client::Grpc::new(channel);
+        client
+            .ready()
+            .await
+            .map_err(|e| FlameError::Network(e.to_string()))?;
+        let resp: tonic::Response<HealthCheckResponse> = client
+            .unary(
+                tonic::Request::new(HealthCheckRequest {
+                    service: service.to_string(),
+                }),
+                PathAndQuery::from_static(GRPC_HEALTH_CHECK),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(|e| FlameError::Network(format!("health check failed: {e}")))?;
+
+        match resp.into_inner().status {
+            SERVING => Ok(()),
+            status => Err(FlameError::InvalidState(format!(
+                "instance is not serving (status {status})"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_prober(command: &str) -> Prober {
+        Prober {
+            executor_id: "exec-1".to_string(),
+            probe: HealthProbe {
+                command: Some(command.to_string()),
+                timeout_seconds: Some(1),
+                ..HealthProbe::default()
+            },
+            process_dir: std::env::temp_dir(),
+            socket: PathBuf::from("/tmp/exec-1.sock"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_command() {
+        assert!(command_prober("true").check().await.is_ok());
+        assert!(
+            command_prober("test \"$FLAME_INSTANCE_ENDPOINT\" = /tmp/exec-1.sock")
+                .check()
+                .await
+                .is_ok()
+        );
+        assert!(command_prober("exit 1").check().await.is_err());
+        // The wedged probe is failed by its timeout.
+        assert!(command_prober("sleep 5").check().await.is_err());
+    }
+}
@@ -0,0 +1,447 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The Python environments of the host applications.
+//!
+//! A host application may define its Python environment by its uv project or
+//! its requirements file; the executor manager builds the virtual environment
+//! by `uv` before the instance starts, so the dependencies of the applications
+//! on the same node are isolated from each other. The environments are cached
+//! in `<cache>/<app>/envs/<digest>`, where the digest is of the definition, i.e.
+//! the contents of the project (pyproject.toml and uv.lock) or the requirements
+//! and the Python version; the instances of the application share the
+//! environment, which is rebuilt only once its definition changed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use ring::digest;
+use tokio::process::Command;
+
+use common::apis::{ApplicationContext, PythonEnvironment};
+use common::FlameError;
+
+use crate::hooks;
+
+const UV: &str = "uv";
+const ENVS_DIR: &str = "envs";
+/// The file marking the environment was built completely.
+const READY_FILE: &str = ".flame-ready";
+const PYPROJECT_FILE: &str = "pyproject.toml";
+const UV_LOCK_FILE: &str = "uv.lock";
+/// The length of the digest in the directory name of the environment.
+const DIGEST_LEN: usize = 16;
+const BUILD_TIMEOUT: Duration = Duration::from_secs(1800);
+
+const VIRTUAL_ENV: &str = "VIRTUAL_ENV";
+const UV_PROJECT_ENVIRONMENT: &str = "UV_PROJECT_ENVIRONMENT";
+const UV_CACHE_DIR: &str = "UV_CACHE_DIR";
+const PATH: &str = "PATH";
+
+/// The locks of the environments being built; the instances of an application
+/// starting together wait for the one building the environment.
+static BUILD_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The definition of the environment on the node.
+#[derive(Debug, PartialEq)]
+enum Source {
+    /// The directory of the uv project.
+    Project(PathBuf),
+    /// The requirements file.
+    Requirements(PathBuf),
+}
+
+impl Source {
+    /// Resolves the definition; the relative paths are against the working
+    /// directory of the instance.
+    fn resolve(env: &PythonEnvironment, process_dir: &Path) -> Result<Self, FlameError> {
+        env.validate()?;
+        let resolve = |path: &str| process_dir.join(path.trim());
+
+        if let Some(project) = &env.project {
+            let path = resolve(project);
+            let dir = if path.is_file() {
+                path.parent().map(Path::to_path_buf).unwrap_or_default()
+            } else {
+                path
+            };
+            if !dir.join(PYPROJECT_FILE).is_file() {
+                return Err(FlameError::InvalidConfig(format!(
+                    "no {PYPROJECT_FILE} in the python project <{}>",
+                    dir.display()
+                )));
+            }
+            return Ok(Source::Project(dir));
+        }
+
+        let path = resolve(env.requirements.as_deref().unwrap_or_default());
+        if !path.is_file() {
+            return Err(FlameError::InvalidConfig(format!(
+                "python requirements <{}> not found",
+                path.display()
+            )));
+        }
+        Ok(Source::Requirements(path))
+    }
+
+    /// The files defining the environment.
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            Source::Project(dir) => [PYPROJECT_FILE, UV_LOCK_FILE]
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.is_file())
+                .collect(),
+            Source::Requirements(path) => vec![path.clone()],
+        }
+    }
+
+    /// The digest of the definition, i.e. the kind, the Python version and the
+    /// contents of the files.
+    fn digest(&self, python_version: Option<&str>) -> Result<String, FlameError> {
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        let kind = match self {
+            Source::Project(_) => "project",
+            Source::Requirements(_) => "requirements",
+        };
+        ctx.update(kind.as_bytes());
+        ctx.update(&[0]);
+        ctx.update(python_version.unwrap_or_default().trim().as_bytes());
+        for file in self.files() {
+            let data = std::fs::read(&file).map_err(|e| {
+                FlameError::Internal(format!("failed to read <{}>: {e}", file.display()))
+            })?;
+            ctx.update(&[0]);
+            ctx.update(file.file_name().unwrap_or_default().as_encoded_bytes());
+            ctx.update(&[0]);
+            ctx.update(&data);
+        }
+
+        let hex: String = ctx
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(hex[..DIGEST_LEN].to_string())
+    }
+}
+
+/// Prepares the Python environment of the application, and returns the
+/// environment variables activating it for the instance.
+pub async fn prepare(
+    app: &ApplicationContext,
+    env: &PythonEnvironment,
+    process_dir: &Path,
+    cache_dir: &Path,
+) -> Result<HashMap<String, String>, FlameError> {
+    prepare_with(UV, app, env, process_dir, cache_dir).await
+}
+
+async fn prepare_with(
+    uv: &str,
+    app: &ApplicationContext,
+    env: &PythonEnvironment,
+    process_dir: &Path,
+    cache_dir: &Path,
+) -> Result<HashMap<String, String>, FlameError> {
+    let source = Source::resolve(env, process_dir)?;
+    let python_version = env.python_version.as_deref().map(str::trim);
+    let app_cache = cache_dir.join(&app.name);
+    let env_dir = app_cache
+        .join(ENVS_DIR)
+        .join(source.digest(python_version)?);
+
+    let lock = {
+        let mut locks = BUILD_LOCKS
+            .lock()
+            .map_err(|e| FlameError::Internal(format!("failed to lock python envs: {e}")))?;
+        locks.entry(env_dir.clone()).or_default().clone()
+    };
+    let _guard = lock.lock().await;
+
+    if env_dir.join(READY_FILE).is_file() {
+        tracing::debug!(
+            "Reuse the python environment <{}> of application <{}>",
+            env_dir.display(),
+            app.name
+        );
+    } else {
+        let started = tokio::time::Instant::now();
+        let builder = Builder {
+            uv,
+            app,
+            env_dir: &env_dir,
+            uv_cache: app_cache.join("uv"),
+            python_version,
+        };
+        if let Err(e) = builder.build(&source).await {
+            // The half-built environment is rebuilt by the next instance.
+            let _ = tokio::fs::remove_dir_all(&env_dir).await;
+            return Err(e);
+        }
+        tracing::info!(
+            "Built the python environment <{}> of application <{}> in {:.1}s",
+            env_dir.display(),
+            app.name,
+            started.elapsed().as_secs_f64()
+        );
+    }
+
+    Ok(activation(app, &env_dir))
+}
+
+/// The environment variables activating the environment, as `activate` does.
+fn activation(app: &ApplicationContext, env_dir: &Path) -> HashMap<String, String> {
+    let bin_dir = env_dir.join("bin");
+    let path = app
+        .environments
+        .get(PATH)
+        .cloned()
+        .or_else(|| std::env::var(PATH).ok());
+    let path = match path {
+        Some(path) if !path.is_empty() => format!("{}:{path}", bin_dir.display()),
+        _ => bin_dir.display().to_string(),
+    };
+
+    HashMap::from([
+        (VIRTUAL_ENV.to_string(), env_dir.display().to_string()),
+        (
+            UV_PROJECT_ENVIRONMENT.to_string(),
+            env_dir.display().to_string(),
+        ),
+        (PATH.to_string(), path),
+    ])
+}
+
+struct Builder<'a> {
+    uv: &'a str,
+    app: &'a ApplicationContext,
+    env_dir: &'a Path,
+    uv_cache: PathBuf,
+    python_version: Option<&'a str>,
+}
+
+impl Builder<'_> {
+    async fn build(&self, source: &Source) -> Result<(), FlameError> {
+        if self.env_dir.exists() {
+            tokio::fs::remove_dir_all(self.env_dir).await.map_err(|e| {
+                FlameError::Internal(format!(
+                    "failed to remove <{}>: {e}",
+                    self.env_dir.display()
+                ))
+            })?;
+        }
+
+        match source {
+            Source::Project(dir) => {
+                let mut args = vec!["sync".to_string(), "--project".to_string()];
+                args.push(dir.display().to_string());
+                if dir.join(UV_LOCK_FILE).is_file() {
+                    args.push("--locked".to_string());
+                }
+                self.push_python(&mut args);
+                self.run(&args, dir).await?;
+            }
+            Source::Requirements(path) => {
+                let dir = path.parent().unwrap_or(Path::new("/"));
+                let mut args = vec!["venv".to_string()];
+                self.push_python(&mut args);
+                args.push(self.env_dir.display().to_string());
+                self.run(&args, dir).await?;
+
+                let python = self.env_dir.join("bin").join("python");
+                let args = [
+                    "pip".to_string(),
+                    "install".to_string(),
+                    "--python".to_string(),
+                    python.display().to_string(),
+                    "-r".to_string(),
+                    path.display().to_string(),
+                ];
+                self.run(&args, dir).await?;
+            }
+        }
+
+        let ready = self.env_dir.join(READY_FILE);
+        tokio::fs::write(&ready, b"").await.map_err(|e| {
+            FlameError::Internal(format!("failed to write <{}>: {e}", ready.display()))
+        })
+    }
+
+    fn push_python(&self, args: &mut Vec<String>) {
+        if let Some(version) = self.python_version {
+            args.push("--python".to_string());
+            args.push(version.to_string());
+        }
+    }
+
+    /// Runs uv with the environments of the application, e.g. its index.
+    async fn run(&self, args: &[String], dir: &Path) -> Result<(), FlameError> {
+        tracing::debug!(
+            "Run <{} {}> in <{}>",
+            self.uv,
+            args.join(" "),
+            dir.display()
+        );
+
+        let child = Command::new(self.uv)
+            .args(args)
+            .env(UV_CACHE_DIR, &self.uv_cache)
+            .envs(self.app.environments.iter())
+            .env(UV_PROJECT_ENVIRONMENT, self.env_dir)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(BUILD_TIMEOUT, child)
+            .await
+            .map_err(|_| {
+                FlameError::Internal(format!(
+                    "python environment of application <{}> timed out after {}s",
+                    self.app.name,
+                    BUILD_TIMEOUT.as_secs()
+                ))
+            })?
+            .map_err(|e| FlameError::Internal(format!("failed to run <{}>: {e}", self.uv)))?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        Err(FlameError::Internal(format!(
+            "failed to build python environment of application <{}> by <{} {}>, exited with {}: {}",
+            self.app.name,
+            self.uv,
+            args.first().map(String::as_str).unwrap_or_default(),
+            output.status,
+            hooks::tail(&String::from_utf8_lossy(&output.stderr))
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> ApplicationContext {
+        ApplicationContext {
+            name: "matrix".to_string(),
+            shim: common::apis::Shim::Host,
+            image: None,
+            command: Some("python".to_string()),
+            arguments: vec![],
+            working_directory: None,
+            environments: HashMap::new(),
+            url: None,
+            health_probe: None,
+            hooks: Default::default(),
+            python_env: None,
+            max_instances_per_node: None,
+            topology: Default::default(),
+        }
+    }
+
+    fn requirements(path: &str) -> PythonEnvironment {
+        PythonEnvironment {
+            requirements: Some(path.to_string()),
+            ..PythonEnvironment::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("requirements.txt"), "numpy\n").unwrap();
+        std::fs::create_dir(dir.path().join("app")).unwrap();
+        std::fs::write(dir.path().join("app").join(PYPROJECT_FILE), "").unwrap();
+
+        let source = Source::resolve(&requirements("requirements.txt"), dir.path()).unwrap();
+        assert_eq!(
+            source,
+            Source::Requirements(dir.path().join("requirements.txt"))
+        );
+
+        // The project is either the pyproject.toml or its directory.
+        for project in ["app", "app/pyproject.toml"] {
+            let env = PythonEnvironment {
+                project: Some(project.to_string()),
+                ..PythonEnvironment::default()
+            };
+            let source = Source::resolve(&env, dir.path()).unwrap();
+            assert_eq!(source, Source::Project(dir.path().join("app")));
+        }
+
+        assert!(Source::resolve(&requirements("missing.txt"), dir.path()).is_err());
+        let env = PythonEnvironment {
+            project: Some(".".to_string()),
+            ..PythonEnvironment::default()
+        };
+        assert!(Source::resolve(&env, dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_source_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("requirements.txt");
+        std::fs::write(&path, "numpy\n").unwrap();
+        let source = Source::Requirements(path.clone());
+
+        let digest = source.digest(None).unwrap();
+        assert_eq!(digest.len(), DIGEST_LEN);
+        assert_eq!(digest, source.digest(None).unwrap());
+        assert_ne!(digest, source.digest(Some("3.12")).unwrap());
+
+        std::fs::write(&path, "numpy\npandas\n").unwrap();
+        assert_ne!(digest, source.digest(None).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_python_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        std::fs::write(dir.path().join("requirements.txt"), "numpy\n").unwrap();
+        let env = requirements("requirements.txt");
+
+        let digest = Source::resolve(&env, dir.path())
+            .unwrap()
+            .digest(None)
+            .unwrap();
+        let env_dir = cache.join("matrix").join(ENVS_DIR).join(digest);
+
+        // The failed environment is removed to be rebuilt.
+        let err = prepare_with("false", &app(), &env, dir.path(), &cache)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("failed to build"));
+        assert!(!env_dir.exists());
+
+        // The built environment is reused without uv.
+        std::fs::create_dir_all(&env_dir).unwrap();
+        std::fs::write(env_dir.join(READY_FILE), "").unwrap();
+
+        let envs = prepare_with("/nonexistent/uv", &app(), &env, dir.path(), &cache)
+            .await
+            .unwrap();
+        assert_eq!(envs[VIRTUAL_ENV], env_dir.display().to_string());
+        assert_eq!(envs[UV_PROJECT_ENVIRONMENT], env_dir.display().to_string());
+        assert!(envs[PATH].starts_with(&format!("{}:", env_dir.join("bin").display())));
+    }
+}
@@ -0,0 +1,161 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Derivation of the node's allocatable resources and slots.
+//!
+//! The executor manager detects the CPUs/memory of the node, subtracts the
+//! resources reserved for the system, and reports the remaining resources as
+//! allocatable; the session manager converts them into slots by the cluster
+//! slot definition. The `--slots` flag overrides the auto-detection.
+
+use common::apis::{Node, ResourceRequirement};
+
+#[derive(Clone, Debug, Default)]
+pub struct SlotPolicy {
+    /// The resources of one slot, from the cluster configuration.
+    pub unit: ResourceRequirement,
+    /// The resources reserved for the system and Flame daemons.
+    pub reserved: ResourceRequirement,
+    /// The manual slots of the node; auto-detected if None.
+    pub slots: Option<u32>,
+}
+
+impl SlotPolicy {
+    pub fn new(unit: ResourceRequirement, reserved: Option<&str>, slots: Option<u32>) -> Self {
+        Self {
+            unit,
+            reserved: reserved.map(ResourceRequirement::from).unwrap_or_default(),
+            slots,
+        }
+    }
+
+    /// Returns the allocatable resources for the given capacity.
+    pub fn allocatable(&self, capacity: &ResourceRequirement) -> ResourceRequirement {
+        match self.slots {
+            Some(slots) => ResourceRequirement::new(slots, &self.unit),
+            None => capacity.saturating_sub(&self.reserved),
+        }
+    }
+
+    /// Returns the number of slots for the given capacity.
+    pub fn slots(&self, capacity: &ResourceRequirement) -> u32 {
+        self.allocatable(capacity).to_slots(&self.unit)
+    }
+
+    /// Updates the allocatable resources of the node according to its capacity.
+    pub fn apply(&self, node: &mut Node) {
+        node.allocatable = self.allocatable(&node.capacity);
+    }
+
+    /// Describes how the slots are derived from the capacity, e.g. for logging.
+    pub fn derivation(&self, capacity: &ResourceRequirement) -> String {
+        let allocatable = self.allocatable(capacity);
+        let slots = allocatable.to_slots(&self.unit);
+        match self.slots {
+            Some(manual) => format!(
+                "slots={slots} (manual={manual}), slot=<{}>, allocatable=<{}>, capacity=<{}>",
+                display(&self.unit),
+                display(&allocatable),
+                display(capacity)
+            ),
+            None => format!(
+                "slots={slots} (auto-detected), slot=<{}>, allocatable=<{}>, capacity=<{}>, reserved=<{}>",
+                display(&self.unit),
+                display(&allocatable),
+                display(capacity),
+                display(&self.reserved)
+            ),
+        }
+    }
+
+    /// Returns true if the manual slots exceed the detected capacity.
+    pub fn is_overcommitted(&self, capacity: &ResourceRequirement) -> bool {
+        let allocatable = self.allocatable(capacity);
+        allocatable.cpu > capacity.cpu || allocatable.memory > capacity.memory
+    }
+}
+
+fn display(resreq: &ResourceRequirement) -> String {
+    format!("cpu={},memory={}", resreq.cpu, resreq.memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GI: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_auto_detected_slots() {
+        let policy = SlotPolicy::new(ResourceRequirement::from("cpu=1,mem=2g"), None, None);
+        let capacity = ResourceRequirement {
+            cpu: 8,
+            memory: 32 * GI,
+        };
+
+        assert_eq!(policy.slots(&capacity), 8);
+        assert_eq!(policy.allocatable(&capacity), capacity);
+        assert!(!policy.is_overcommitted(&capacity));
+    }
+
+    #[test]
+    fn test_system_reserved_slots() {
+        let policy = SlotPolicy::new(
+            ResourceRequirement::from("cpu=1,mem=2g"),
+            Some("cpu=2,memory=4Gi"),
+            None,
+        );
+        let capacity = ResourceRequirement {
+            cpu: 8,
+            memory: 12 * GI,
+        };
+
+        let allocatable = policy.allocatable(&capacity);
+        assert_eq!(allocatable.cpu, 6);
+        assert_eq!(allocatable.memory, 8 * GI);
+        // Memory is the bottleneck: 8Gi / 2Gi = 4 slots.
+        assert_eq!(policy.slots(&capacity), 4);
+    }
+
+    #[test]
+    fn test_reserved_exceeds_capacity() {
+        let policy = SlotPolicy::new(
+            ResourceRequirement::from("cpu=1,mem=2g"),
+            Some("cpu=16,memory=4Gi"),
+            None,
+        );
+        let capacity = ResourceRequirement {
+            cpu: 8,
+            memory: 12 * GI,
+        };
+
+        assert_eq!(policy.slots(&capacity), 0);
+    }
+
+    #[test]
+    fn test_manual_slots() {
+        let policy = SlotPolicy::new(
+            ResourceRequirement::from("cpu=1,mem=2g"),
+            Some("cpu=2,memory=4Gi"),
+            Some(10),
+        );
+        let capacity = ResourceRequirement {
+            cpu: 8,
+            memory: 32 * GI,
+        };
+
+        assert_eq!(policy.slots(&capacity), 10);
+        assert!(policy.is_overcommitted(&capacity));
+        assert!(policy.derivation(&capacity).contains("manual=10"));
+    }
+}
@@ -0,0 +1,301 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Shared scratch directories of sessions.
+//!
+//! A session may request a scratch directory for the intermediate data shared
+//! by its instances on the same node, e.g. shuffle data. The executor manager
+//! creates one directory per session on the node when the first executor binds
+//! to the session, and removes it when the last executor of the session on the
+//! node is unbound, e.g. when the session is closed. The usage of the directory
+//! is checked against the session's size limit after each task. The directories
+//! left by a crashed executor manager are swept at its next startup.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+
+use common::apis::{SessionContext, SessionID};
+use common::FlameError;
+
+use crate::privileges;
+
+/// The scratch directories on this node, shared by the executors of the same session.
+static SCRATCH_DIRS: LazyLock<Mutex<HashMap<SessionID, Weak<ScratchDir>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub type ScratchDirPtr = Arc<ScratchDir>;
+
+#[derive(Debug)]
+pub struct ScratchDir {
+    session_id: SessionID,
+    path: PathBuf,
+    /// The size limit of the directory in bytes.
+    limit: u64,
+}
+
+impl ScratchDir {
+    /// Returns the scratch directory of the session under `root` on this node,
+    /// creating it for the first executor; None if the session did not request one.
+    pub fn acquire(ssn: &SessionContext, root: &Path) -> Result<Option<ScratchDirPtr>, FlameError> {
+        let Some(limit) = ssn.scratch_size else {
+            return Ok(None);
+        };
+
+        if ssn.session_id.is_empty()
+            || ssn.session_id.contains(['/', '\\'])
+            || ssn.session_id.starts_with('.')
+        {
+            return Err(FlameError::InvalidConfig(format!(
+                "invalid session id <{}> for scratch directory",
+                ssn.session_id
+            )));
+        }
+
+        let mut dirs = SCRATCH_DIRS
+            .lock()
+            .map_err(|e| FlameError::Internal(format!("failed to lock scratch dirs: {e}")))?;
+
+        if let Some(dir) = dirs.get(&ssn.session_id).and_then(Weak::upgrade) {
+            return Ok(Some(dir));
+        }
+
+        let path = root.join(ssn.session_id.as_str());
+        fs::create_dir_all(&path).map_err(|e| {
+            FlameError::Internal(format!(
+                "failed to create scratch directory {}: {e}",
+                path.display()
+            ))
+        })?;
+        // The instances write into the directory as their own user.
+        privileges::chown(&path)?;
+
+        tracing::debug!(
+            "Created scratch directory <{}> with limit <{limit}> for session <{}>",
+            path.display(),
+            ssn.session_id
+        );
+
+        let dir = Arc::new(ScratchDir {
+            session_id: ssn.session_id.clone(),
+            path,
+            limit,
+        });
+        dirs.insert(ssn.session_id.clone(), Arc::downgrade(&dir));
+
+        Ok(Some(dir))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the total size in bytes of the files in the directory.
+    pub fn usage(&self) -> Result<u64, FlameError> {
+        dir_size(&self.path).map_err(|e| {
+            FlameError::Internal(format!(
+                "failed to get usage of scratch directory {}: {e}",
+                self.path.display()
+            ))
+        })
+    }
+
+    /// Returns an error if the directory exceeds its size limit.
+    pub fn check(&self) -> Result<(), FlameError> {
+        let usage = self.usage()?;
+        if usage > self.limit {
+            return Err(FlameError::InvalidState(format!(
+                "scratch directory of session <{}> exceeds its size limit: {usage} > {}",
+                self.session_id, self.limit
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes the scratch directories under `root` which are not acquired by any
+/// executor, e.g. left by the last run of the executor manager which crashed
+/// before unbinding its executors; returns how many were removed.
+pub fn sweep(root: &Path) -> Result<usize, FlameError> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => {
+            return Err(FlameError::Internal(format!(
+                "failed to read scratch root {}: {e}",
+                root.display()
+            )))
+        }
+    };
+
+    let dirs = SCRATCH_DIRS
+        .lock()
+        .map_err(|e| FlameError::Internal(format!("failed to lock scratch dirs: {e}")))?;
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if dirs
+            .get(name.as_str())
+            .is_some_and(|dir| dir.strong_count() > 0)
+        {
+            continue;
+        }
+
+        match fs::remove_dir_all(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::warn!(
+                "Failed to remove scratch directory {}: {e}",
+                entry.path().display()
+            ),
+        }
+    }
+
+    Ok(removed)
+}
+
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+
+    Ok(size)
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let Ok(mut dirs) = SCRATCH_DIRS.lock() else {
+            tracing::warn!(
+                "Failed to lock scratch dirs, keep scratch directory {}",
+                self.path.display()
+            );
+            return;
+        };
+
+        // The directory was re-acquired by another executor of the session.
+        if let Some(dir) = dirs.get(&self.session_id) {
+            if dir.strong_count() > 0 {
+                return;
+            }
+        }
+        dirs.remove(&self.session_id);
+
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            tracing::warn!(
+                "Failed to remove scratch directory {}: {}",
+                self.path.display(),
+                e
+            );
+        } else {
+            tracing::debug!("Removed scratch directory: {}", self.path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::apis::{ApplicationContext, ResultPolicy, Shim};
+    use tempfile::tempdir;
+
+    fn create_test_session(id: &str, scratch_size: Option<u64>) -> SessionContext {
+        SessionContext {
+            session_id: id.to_string().into(),
+            application: ApplicationContext {
+                name: "test-app".to_string(),
+                shim: Shim::Host,
+                image: None,
+                command: None,
+                arguments: vec![],
+                working_directory: None,
+                environments: HashMap::new(),
+                url: None,
+                health_probe: None,
+                hooks: Default::default(),
+                python_env: None,
+                max_instances_per_node: None,
+                topology: Default::default(),
+            },
+            slots: 1,
+            common_data: None,
+            scratch_size,
+            scratch_dir: None,
+            result_policy: ResultPolicy::Inline,
+        }
+    }
+
+    #[test]
+    fn test_scratch_dir_lifecycle() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+
+        assert!(
+            ScratchDir::acquire(&create_test_session("ssn-none", None), root)
+                .unwrap()
+                .is_none()
+        );
+        assert!(ScratchDir::acquire(&create_test_session("../ssn", Some(1)), root).is_err());
+
+        let ssn = create_test_session("ssn-1", Some(8));
+        let dir_1 = ScratchDir::acquire(&ssn, root).unwrap().unwrap();
+        let dir_2 = ScratchDir::acquire(&ssn, root).unwrap().unwrap();
+        let path = dir_1.path().to_path_buf();
+        assert_eq!(path, temp.path().join("ssn-1"));
+        assert_eq!(dir_2.path(), path.as_path());
+
+        fs::write(path.join("data"), b"1234").unwrap();
+        assert_eq!(dir_1.usage().unwrap(), 4);
+        assert!(dir_1.check().is_ok());
+
+        fs::create_dir_all(path.join("shuffle")).unwrap();
+        fs::write(path.join("shuffle").join("part-0"), b"12345").unwrap();
+        assert_eq!(dir_1.usage().unwrap(), 9);
+        assert!(dir_1.check().is_err());
+
+        // The directory is kept until the last executor releases it.
+        drop(dir_1);
+        assert!(path.exists());
+        drop(dir_2);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_sweep() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        assert_eq!(sweep(&root.join("missing")).unwrap(), 0);
+
+        // The directory left by the last run, and the one acquired by an executor.
+        fs::create_dir_all(root.join("ssn-stale").join("shuffle")).unwrap();
+        fs::write(root.join("ssn-stale").join("shuffle").join("part-0"), b"1").unwrap();
+        let dir = ScratchDir::acquire(&create_test_session("ssn-sweep", Some(8)), root)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(sweep(root).unwrap(), 1);
+        assert!(!root.join("ssn-stale").exists());
+        assert!(dir.path().exists());
+    }
+}
@@ -13,25 +13,22 @@ limitations under the License.
 
 use std::fs;
 use std::future::Future;
+use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use async_trait::async_trait;
-use hyper_util::rt::TokioIo;
-#[cfg(unix)]
-use tokio::net::UnixStream;
 use tonic::transport::Channel;
-use tonic::transport::{Endpoint, Uri};
-use tonic::Request;
-use tower::service_fn;
+use tonic::{Code, Request};
 
 use ::rpc::flame::v1 as rpc;
 use rpc::instance_client::InstanceClient;
 use rpc::EmptyRequest;
 
-use crate::shims::{ExecutorWorkDir, Shim};
+use crate::shims::{ExecutorWorkDir, Shim, TaskOutputStream};
 use common::apis::{SessionContext, TaskContext, TaskResult, TaskState};
-use common::FlameError;
+use common::{endpoint, FlameError};
 use stdng::{logs::TraceFn, trace_fn};
 
 pub struct GrpcShim {
@@ -53,48 +50,18 @@ impl GrpcShim {
         self.endpoint.as_str()
     }
 
-    #[cfg(unix)]
     pub async fn connect(&mut self) -> Result<(), FlameError> {
         trace_fn!("GrpcShim::connect");
 
         WaitForSvcSocketFuture::new(self.endpoint.clone()).await?;
         tracing::debug!("Try to connect to service at <{}>", self.endpoint);
 
-        let channel = Endpoint::try_from("http://[::]:50051")
-            .unwrap()
-            .connect_with_connector({
-                let service_addr = self.endpoint.clone();
-
-                service_fn(move |_: Uri| {
-                    let service_addr = service_addr.clone();
-                    async move {
-                        UnixStream::connect(service_addr)
-                            .await
-                            .map(TokioIo::new)
-                            .map_err(std::io::Error::other)
-                    }
-                })
-            })
-            .await
-            .map_err(|e| {
-                FlameError::Network(format!(
-                    "failed to connect to service at <{}>: {e}",
-                    self.endpoint
-                ))
-            })?;
-
+        let channel = endpoint::connect(Path::new(&self.endpoint)).await?;
         self.client = Some(InstanceClient::new(channel));
 
         Ok(())
     }
 
-    #[cfg(not(unix))]
-    pub async fn connect(&mut self) -> Result<(), FlameError> {
-        Err(FlameError::Network(
-            "Unix domain sockets are not supported on this platform".to_string(),
-        ))
-    }
-
     pub fn close(&mut self) {
         if self.client.take().is_some() {
             tracing::debug!("Closed gRPC connection to service at <{}>", self.endpoint);
@@ -175,6 +142,54 @@ impl Shim for GrpcShim {
 
         Ok(())
     }
+
+    async fn on_preempt(
+        &mut self,
+        ctx: &TaskContext,
+        grace_period: Duration,
+    ) -> Result<(), FlameError> {
+        trace_fn!("GrpcShim::on_preempt");
+
+        let Some(ref mut client) = self.client else {
+            return Err(FlameError::Internal(format!(
+                "no connection to service at <{}>",
+                self.endpoint
+            )));
+        };
+
+        let req = Request::new(rpc::PreemptContext {
+            session_id: ctx.session_id.to_string(),
+            task_id: Some(ctx.task_id.to_string()),
+            grace_period: grace_period.as_secs(),
+        });
+        let output = client.on_preempt(req).await?.into_inner();
+        if output.return_code != 0 {
+            return Err(FlameError::Internal(output.message.unwrap_or_default()));
+        }
+
+        Ok(())
+    }
+
+    async fn watch_task_output(&mut self) -> Result<Option<TaskOutputStream>, FlameError> {
+        trace_fn!("GrpcShim::watch_task_output");
+
+        let Some(ref mut client) = self.client else {
+            return Err(FlameError::Internal(format!(
+                "no connection to service at <{}>",
+                self.endpoint
+            )));
+        };
+
+        match client
+            .watch_task_output(Request::new(EmptyRequest::default()))
+            .await
+        {
+            Ok(resp) => Ok(Some(resp.into_inner())),
+            // The SDK of the service doesn't stream the output of the tasks.
+            Err(status) if status.code() == Code::Unimplemented => Ok(None),
+            Err(status) => Err(FlameError::from(status)),
+        }
+    }
 }
 
 struct WaitForSvcSocketFuture {
@@ -203,7 +218,7 @@ impl Future for WaitForSvcSocketFuture {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common::apis::{ApplicationContext, Shim as ShimType};
+    use common::apis::{ApplicationContext, ResultPolicy, Shim as ShimType, TaskOverrides};
     use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Mutex;
@@ -214,7 +229,6 @@ mod tests {
     fn setup_test_env(temp: &tempfile::TempDir) -> PathBuf {
         let socket_dir = temp.path().join("sockets");
         std::fs::create_dir_all(&socket_dir).unwrap();
-        std::env::set_var("FLAME_SOCKET_DIR", &socket_dir);
         std::env::set_current_dir(temp.path()).unwrap();
         socket_dir
     }
@@ -229,9 +243,14 @@ mod tests {
             working_directory: None,
             environments: HashMap::new(),
             url: None,
+            health_probe: None,
+            hooks: Default::default(),
+            python_env: None,
+            max_instances_per_node: None,
+            topology: Default::default(),
         };
 
-        ExecutorWorkDir::new(&app, executor_id).unwrap()
+        ExecutorWorkDir::new(&app, executor_id, temp.path(), &temp.path().join("sockets")).unwrap()
     }
 
     #[test]
@@ -298,7 +317,7 @@ mod tests {
         let mut shim = GrpcShim::new(&work_dir).unwrap();
 
         let ctx = SessionContext {
-            session_id: "test-session".to_string(),
+            session_id: "test-session".into(),
             application: ApplicationContext {
                 name: "test-app".to_string(),
                 shim: ShimType::Host,
@@ -308,9 +327,17 @@ mod tests {
                 working_directory: None,
                 environments: HashMap::new(),
                 url: None,
+                health_probe: None,
+                hooks: Default::default(),
+                python_env: None,
+                max_instances_per_node: None,
+                topology: Default::default(),
             },
             slots: 1,
             common_data: None,
+            scratch_size: None,
+            scratch_dir: None,
+            result_policy: ResultPolicy::Inline,
         };
 
         let result = shim.on_session_enter(&ctx).await;
@@ -331,9 +358,13 @@ mod tests {
         let mut shim = GrpcShim::new(&work_dir).unwrap();
 
         let ctx = TaskContext {
-            task_id: "test-task".to_string(),
-            session_id: "test-session".to_string(),
+            task_id: 1.into(),
+            session_id: "test-session".into(),
             input: None,
+            input_ref: None,
+            overrides: TaskOverrides::default(),
+            checkpoint: None,
+            trace_id: None,
         };
 
         let result = shim.on_task_invoke(&ctx).await;
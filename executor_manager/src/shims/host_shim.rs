@@ -13,12 +13,14 @@ limitations under the License.
 
 use std::collections::HashMap;
 use std::env;
-use std::fs::{self, create_dir_all, OpenOptions};
+use std::fs::{self, create_dir_all, File};
+use std::io::{Read, Seek, SeekFrom};
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 #[cfg(unix)]
@@ -27,23 +29,57 @@ use nix::sys::signal::{killpg, Signal};
 use nix::unistd::Pid;
 use stdng::{logs::TraceFn, trace_fn};
 use tokio::sync::Mutex;
+use tokio::time::timeout;
 
 use crate::executor::Executor;
+use crate::logs::{self, RotatingLog};
+use crate::postmortem;
+use crate::privileges;
+use crate::python_env;
 use crate::shims::grpc_shim::GrpcShim;
-use crate::shims::{ExecutorWorkDir, Shim, ShimPtr};
-use common::apis::{ApplicationContext, SessionContext, TaskContext, TaskOutput, TaskResult};
+use crate::shims::{ExecutorWorkDir, Shim, ShimPtr, TaskOutputStream};
+use crate::usage::UsageSample;
+use common::apis::{
+    ApplicationContext, SessionContext, TaskContext, TaskOutput, TaskPostmortem, TaskResult,
+};
 use common::{
-    FlameError, FLAME_CACHE_ENDPOINT, FLAME_CA_FILE, FLAME_ENDPOINT, FLAME_HOME,
-    FLAME_INSTANCE_ENDPOINT, FLAME_LOG, FLAME_WORKING_DIRECTORY,
+    FlameError, FLAME_CACHE_ENDPOINT, FLAME_CA_FILE, FLAME_ENDPOINT, FLAME_INSTANCE_ENDPOINT,
+    FLAME_LOG, FLAME_NUMA_NODE,
 };
 
+/// The size of the stderr tail attached to the failures of the service.
+const STDERR_TAIL_SIZE: u64 = 4096;
+/// The size of the stderr tail in the postmortem of the crashed service.
+const POSTMORTEM_STDERR_TAIL_SIZE: u64 = 16 * 1024;
+/// The time to wait for the service to exit after the failed invocation,
+/// e.g. the connection is closed before the process is reaped.
+const POSTMORTEM_EXIT_TIMEOUT: Duration = Duration::from_secs(1);
+
 struct HostInstance {
     child: tokio::process::Child,
+    pid: Option<u32>,
+    stderr_log: PathBuf,
 }
 
 impl HostInstance {
-    fn new(child: tokio::process::Child) -> Self {
-        Self { child }
+    fn new(child: tokio::process::Child, stderr_log: PathBuf) -> Self {
+        Self {
+            pid: child.id(),
+            child,
+            stderr_log,
+        }
+    }
+
+    /// The tail of the stderr log of the service, if any.
+    fn stderr_tail(&self, size: u64) -> Option<String> {
+        let mut file = File::open(&self.stderr_log).ok()?;
+        let len = file.metadata().ok()?.len();
+        file.seek(SeekFrom::Start(len.saturating_sub(size))).ok()?;
+
+        let mut buf = vec![];
+        file.read_to_end(&mut buf).ok()?;
+        let tail = String::from_utf8_lossy(&buf).trim().to_string();
+        (!tail.is_empty()).then_some(tail)
     }
 
     /// Kill the child process
@@ -67,12 +103,16 @@ impl HostInstance {
 }
 
 pub struct HostShim {
+    executor_id: String,
+    node: String,
     instance: HostInstance,
     instance_client: GrpcShim,
     work_dir: ExecutorWorkDir,
 }
 
 const RUST_LOG: &str = "RUST_LOG";
+const CUDA_VISIBLE_DEVICES: &str = "CUDA_VISIBLE_DEVICES";
+const NUMACTL: &str = "numactl";
 const DEFAULT_SVC_LOG_LEVEL: &str = "info";
 
 impl HostShim {
@@ -83,15 +123,31 @@ impl HostShim {
         trace_fn!("HostShim::new_ptr");
 
         // Create work directory first - it provides socket path for GrpcShim
-        let work_dir = ExecutorWorkDir::new(app, &executor.id)?;
+        let paths = executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.paths.clone())
+            .unwrap_or_default();
+        let work_dir = ExecutorWorkDir::new(app, &executor.id, &paths.work, &paths.sockets)?;
 
         let mut instance_client = GrpcShim::new(&work_dir)?;
 
-        let instance = Self::launch_instance(app, executor, &work_dir)?;
+        // The Python environment of the application is built, or reused, before
+        // the instance starts; it may take a while for the first instance.
+        let python_envs = match &app.python_env {
+            Some(env) => {
+                python_env::prepare(app, env, work_dir.process_dir(), &paths.cache).await?
+            }
+            None => HashMap::new(),
+        };
+
+        let instance = Self::launch_instance(app, executor, &work_dir, &paths.cache, python_envs)?;
 
         instance_client.connect().await?;
 
         Ok(Arc::new(Mutex::new(Self {
+            executor_id: executor.id.clone(),
+            node: executor.node.clone(),
             instance,
             instance_client,
             work_dir,
@@ -105,7 +161,9 @@ impl HostShim {
                 name,
                 path.display()
             ))
-        })
+        })?;
+
+        privileges::chown(path)
     }
 
     /// Setup working directory and tmp directory for an application instance (per-instance).
@@ -136,14 +194,13 @@ impl HostShim {
 
     /// Setup per-application cache directories for uv and pip.
     /// These directories are shared across all instances of the same application.
-    /// Uses FLAME_HOME/data/cache if FLAME_HOME is set, otherwise falls back to FLAME_WORKING_DIRECTORY/cache.
-    fn setup_cache(app_name: &str) -> Result<HashMap<String, String>, FlameError> {
+    /// They are created under the configured cache directory of the cluster.
+    fn setup_cache(
+        cache_base: &Path,
+        app_name: &str,
+    ) -> Result<HashMap<String, String>, FlameError> {
         trace_fn!("HostShim::setup_cache");
 
-        let cache_base = match env::var(FLAME_HOME) {
-            Ok(flame_home) => Path::new(&flame_home).join("data").join("cache"),
-            Err(_) => Path::new(FLAME_WORKING_DIRECTORY).join("cache"),
-        };
         let app_cache_base = cache_base.join(app_name);
         let uv_cache_dir = app_cache_base.join("uv");
         let pip_cache_dir = app_cache_base.join("pip");
@@ -183,10 +240,19 @@ impl HostShim {
             .into_owned()
     }
 
+    /// Whether the program is found in the PATH of the executor manager.
+    fn find_in_path(program: &str) -> bool {
+        env::var_os("PATH")
+            .map(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+            .unwrap_or(false)
+    }
+
     fn launch_instance(
         app: &ApplicationContext,
         executor: &Executor,
         work_dir: &ExecutorWorkDir,
+        cache_dir: &Path,
+        python_envs: HashMap<String, String>,
     ) -> Result<HostInstance, FlameError> {
         trace_fn!("HostShim::launch_instance");
 
@@ -229,6 +295,31 @@ impl HostShim {
             }
         }
 
+        // The instance of a topology-aware application only sees the GPUs of its
+        // group, and is bound to its NUMA node by numactl if it's installed.
+        let mut numa_node = None;
+        if let Some(placement) = &executor.placement {
+            if let Some(devices) = placement.visible_devices() {
+                envs.insert(CUDA_VISIBLE_DEVICES.to_string(), devices);
+            }
+            if let Some(node) = placement.numa_node {
+                envs.insert(FLAME_NUMA_NODE.to_string(), node.to_string());
+                numa_node = Some(node);
+            }
+        }
+        let (command, args) = match numa_node {
+            Some(node) if Self::find_in_path(NUMACTL) => {
+                let mut numa_args = vec![
+                    format!("--cpunodebind={node}"),
+                    format!("--membind={node}"),
+                    command,
+                ];
+                numa_args.extend(args);
+                (NUMACTL.to_string(), numa_args)
+            }
+            _ => (command, args),
+        };
+
         // Propagate HOME environment variable to ensure Python finds user site-packages
         // This is needed when flamepy is installed with --user flag for the flame user
         if let Ok(home) = env::var("HOME") {
@@ -241,6 +332,8 @@ impl HostShim {
 
         // Spawn child process
         let mut cmd = tokio::process::Command::new(&command);
+        #[cfg(unix)]
+        privileges::apply(&mut cmd);
 
         // Use app_dir for temp files (per-instance isolation)
         let app_work_dir = work_dir.app_dir();
@@ -257,34 +350,36 @@ impl HostShim {
         // Use entry().or_insert() so application-specific envs take precedence over defaults
         // This allows applications like flmrun to specify UV_CACHE_DIR pointing to
         // the pre-cached directory instead of using a per-instance empty cache
-        let cache_envs = Self::setup_cache(&app.name)?;
+        let cache_envs = Self::setup_cache(cache_dir, &app.name)?;
         for (key, value) in cache_envs {
             envs.entry(key).or_insert(value);
         }
 
-        let log_out = OpenOptions::new()
-            .create(true)
-            .read(true)
-            .write(true)
-            .truncate(true)
-            .open(process_work_dir.join(format!("{}.out", executor.id)))
-            .map_err(|e| FlameError::Internal(format!("failed to open stdout log file: {e}")))?;
-
-        let log_err = OpenOptions::new()
-            .create(true)
-            .read(true)
-            .write(true)
-            .truncate(true)
-            .open(process_work_dir.join(format!("{}.err", executor.id)))
-            .map_err(|e| FlameError::Internal(format!("failed to open stderr log file: {e}")))?;
+        // The Python environment of the application is activated for the instance,
+        // so its command, e.g. `python`, is looked up in the environment first.
+        envs.extend(python_envs);
+
+        // The stdout and stderr of the instance are written to the logs in the
+        // process directory by the executor manager, which rotates them.
+        let log_config = executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.cluster.executors.logs.clone())
+            .unwrap_or_default();
+        let log_out = RotatingLog::create(
+            &process_work_dir.join(format!("{}.out", executor.id)),
+            &log_config,
+        )?;
+        let stderr_log = process_work_dir.join(format!("{}.err", executor.id));
+        let log_err = RotatingLog::create(&stderr_log, &log_config)?;
 
         #[cfg(unix)]
-        let child = cmd
+        let mut child = cmd
             .envs(envs)
             .args(args)
             .current_dir(process_work_dir)
-            .stdout(Stdio::from(log_out))
-            .stderr(Stdio::from(log_err))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .process_group(0)
             .spawn()
             .map_err(|e| {
@@ -294,12 +389,12 @@ impl HostShim {
             })?;
 
         #[cfg(not(unix))]
-        let child = cmd
+        let mut child = cmd
             .envs(envs)
             .args(args)
             .current_dir(process_work_dir)
-            .stdout(Stdio::from(log_out))
-            .stderr(Stdio::from(log_err))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| {
                 FlameError::InvalidConfig(format!(
@@ -307,7 +402,14 @@ impl HostShim {
                 ))
             })?;
 
-        Ok(HostInstance::new(child))
+        if let Some(stdout) = child.stdout.take() {
+            logs::spawn(stdout, log_out);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            logs::spawn(stderr, log_err);
+        }
+
+        Ok(HostInstance::new(child, stderr_log))
     }
 }
 
@@ -332,7 +434,21 @@ impl Shim for HostShim {
     async fn on_task_invoke(&mut self, ctx: &TaskContext) -> Result<TaskResult, FlameError> {
         trace_fn!("HostShim::on_task_invoke");
 
-        self.instance_client.on_task_invoke(ctx).await
+        // The resource usage of the service is measured per task.
+        let sample = self.instance.child.id().and_then(UsageSample::start);
+
+        // Attach the stderr of the service for diagnosing, e.g. it crashed.
+        let mut result = self
+            .instance_client
+            .on_task_invoke(ctx)
+            .await
+            .map_err(|e| match self.instance.stderr_tail(STDERR_TAIL_SIZE) {
+                Some(tail) => FlameError::Internal(format!("{e}; stderr of the service:\n{tail}")),
+                None => e,
+            })?;
+        result.usage = sample.and_then(|sample| sample.usage());
+
+        Ok(result)
     }
 
     async fn on_session_leave(&mut self) -> Result<(), FlameError> {
@@ -340,4 +456,40 @@ impl Shim for HostShim {
 
         self.instance_client.on_session_leave().await
     }
+
+    async fn on_preempt(
+        &mut self,
+        ctx: &TaskContext,
+        grace_period: Duration,
+    ) -> Result<(), FlameError> {
+        trace_fn!("HostShim::on_preempt");
+
+        self.instance_client.on_preempt(ctx, grace_period).await
+    }
+
+    async fn watch_task_output(&mut self) -> Result<Option<TaskOutputStream>, FlameError> {
+        trace_fn!("HostShim::watch_task_output");
+
+        self.instance_client.watch_task_output().await
+    }
+
+    async fn postmortem(&mut self) -> Option<TaskPostmortem> {
+        trace_fn!("HostShim::postmortem");
+
+        // The service is still running, e.g. the invocation failed by the
+        // connection only, so it didn't crash.
+        let status = timeout(POSTMORTEM_EXIT_TIMEOUT, self.instance.child.wait())
+            .await
+            .ok()?
+            .ok();
+
+        Some(postmortem::collect(
+            &self.executor_id,
+            &self.node,
+            status,
+            self.instance.stderr_tail(POSTMORTEM_STDERR_TAIL_SIZE),
+            self.work_dir.process_dir(),
+            self.instance.pid,
+        ))
+    }
 }
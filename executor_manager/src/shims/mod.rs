@@ -19,6 +19,7 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use tokio::sync::Mutex;
@@ -27,18 +28,25 @@ use self::host_shim::HostShim;
 use self::wasm_shim::WasmShim;
 
 use crate::executor::Executor;
+use crate::privileges;
+use ::rpc::flame::v1 as rpc;
 use common::apis::{
-    ApplicationContext, SessionContext, Shim as ShimType, TaskContext, TaskOutput, TaskResult,
+    ApplicationContext, SessionContext, Shim as ShimType, TaskContext, TaskOutput, TaskPostmortem,
+    TaskResult,
 };
-use common::{FlameError, FLAME_WORKING_DIRECTORY};
+use common::FlameError;
 
 pub type ShimPtr = Arc<Mutex<dyn Shim>>;
+pub type TaskOutputStream = tonic::Streaming<rpc::TaskOutputChunk>;
+
+/// The directory of the Wasm modules pulled from the OCI registries in the work directory.
+pub const WASM_CACHE_DIR: &str = "wasm";
 
 /// Represents the executor's working directory with cleanup management.
 /// Directory structure:
 ///   top_dir/                     - Process working directory, stdout/stderr logs
 ///   top_dir/work/<app_name>/     - App-specific directory for tmp, cache
-///   /var/flame/executors/<executor_id>.sock - Socket for gRPC communication
+///   socket_dir/<executor_id>.sock - Endpoint of the instance for gRPC communication
 /// Cleanup:
 ///   - top_dir: cleaned up only if auto-generated
 ///   - app_dir: always cleaned up
@@ -48,37 +56,44 @@ pub struct ExecutorWorkDir {
     top_dir: PathBuf,
     /// Application working directory: top_dir/work/<app-name> (for logs, tmp, cache).
     app_dir: PathBuf,
-    /// Socket path: /var/flame/executors/<executor_id>.sock
+    /// Socket path: socket_dir/<executor_id>.sock
     socket: PathBuf,
     /// If true, top_dir was auto-generated and should be cleaned up on release.
     auto_dir: bool,
 }
 
-const FLAME_SOCKET_DIR: &str = "/var/flame/executors";
+/// The directory where the instance of the executor runs: the working directory
+/// of the application, or a directory of the executor under `work_root`.
+pub fn process_dir(app: &ApplicationContext, executor_id: &str, work_root: &Path) -> PathBuf {
+    match &app.working_directory {
+        Some(wd) if !wd.is_empty() => Path::new(wd).to_path_buf(),
+        _ => work_root.join(executor_id),
+    }
+}
 
-fn get_socket_dir() -> PathBuf {
-    std::env::var("FLAME_SOCKET_DIR")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from(FLAME_SOCKET_DIR))
+/// The endpoint of the instance of the executor for gRPC communication, i.e.
+/// its Unix domain socket, or the file of its TCP loopback address on the
+/// platforms without Unix domain sockets; see `common::endpoint`.
+pub fn socket_path(executor_id: &str, socket_dir: &Path) -> PathBuf {
+    socket_dir.join(format!("{}.sock", executor_id))
 }
 
 impl ExecutorWorkDir {
-    /// Create an ExecutorWorkDir from application context and executor ID.
-    pub fn new(app: &ApplicationContext, executor_id: &str) -> Result<Self, FlameError> {
-        let (top_dir, auto_dir) = match &app.working_directory {
-            Some(wd) if !wd.is_empty() => (Path::new(wd).to_path_buf(), false),
-            _ => (
-                env::current_dir()
-                    .unwrap_or(Path::new(FLAME_WORKING_DIRECTORY).to_path_buf())
-                    .join(executor_id),
-                true,
-            ),
-        };
+    /// Create an ExecutorWorkDir from application context and executor ID; the
+    /// top_dir is generated under `work_root` if the application has no working directory,
+    /// and the socket is in `socket_dir`.
+    pub fn new(
+        app: &ApplicationContext,
+        executor_id: &str,
+        work_root: &Path,
+        socket_dir: &Path,
+    ) -> Result<Self, FlameError> {
+        let top_dir = process_dir(app, executor_id, work_root);
+        let auto_dir = !matches!(&app.working_directory, Some(wd) if !wd.is_empty());
 
         let work_dir = top_dir.join("work");
         let app_dir = work_dir.join(&app.name);
-        let socket_dir = get_socket_dir();
-        let socket = socket_dir.join(format!("{}.sock", executor_id));
+        let socket = socket_path(executor_id, socket_dir);
 
         // Create top_dir if auto-generated
         if auto_dir {
@@ -107,13 +122,21 @@ impl ExecutorWorkDir {
         })?;
 
         // Create socket directory
-        fs::create_dir_all(&socket_dir).map_err(|e| {
+        fs::create_dir_all(socket_dir).map_err(|e| {
             FlameError::Internal(format!(
                 "failed to create socket directory {}: {e}",
                 socket_dir.display()
             ))
         })?;
 
+        // The instance runs as its own user, if configured; the working
+        // directory of the application is left as is.
+        if auto_dir {
+            privileges::chown(&top_dir)?;
+        }
+        privileges::chown(&work_dir)?;
+        privileges::chown(&app_dir)?;
+
         Ok(Self {
             top_dir,
             app_dir,
@@ -211,6 +234,31 @@ pub trait Shim: Send + 'static {
     async fn on_session_enter(&mut self, ctx: &SessionContext) -> Result<(), FlameError>;
     async fn on_task_invoke(&mut self, ctx: &TaskContext) -> Result<TaskResult, FlameError>;
     async fn on_session_leave(&mut self) -> Result<(), FlameError>;
+
+    /// Asks the instance to checkpoint the state of the task interrupted by a
+    /// preemption within the grace period; the instances without the hook,
+    /// e.g. wasm, are stopped directly.
+    async fn on_preempt(
+        &mut self,
+        _ctx: &TaskContext,
+        _grace_period: Duration,
+    ) -> Result<(), FlameError> {
+        Ok(())
+    }
+
+    /// The postmortem of the instance if it crashed, i.e. exited, after the
+    /// failed invocation; the instances without a process, e.g. wasm, never
+    /// crash by themselves.
+    async fn postmortem(&mut self) -> Option<TaskPostmortem> {
+        None
+    }
+
+    /// Watches the output chunks streamed by the tasks of the instance; None
+    /// if the instance doesn't stream the output, e.g. wasm, or its SDK
+    /// doesn't support it.
+    async fn watch_task_output(&mut self) -> Result<Option<TaskOutputStream>, FlameError> {
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
@@ -233,13 +281,17 @@ mod tests {
             working_directory,
             environments: HashMap::new(),
             url: None,
+            health_probe: None,
+            hooks: Default::default(),
+            python_env: None,
+            max_instances_per_node: None,
+            topology: Default::default(),
         }
     }
 
     fn setup_test_env(temp: &tempfile::TempDir) -> PathBuf {
         let socket_dir = temp.path().join("sockets");
         std::fs::create_dir_all(&socket_dir).unwrap();
-        std::env::set_var("FLAME_SOCKET_DIR", &socket_dir);
         std::env::set_current_dir(temp.path()).unwrap();
         socket_dir
     }
@@ -253,7 +305,7 @@ mod tests {
         let app = create_test_app("test-app", None);
         let executor_id = "exec-123";
 
-        let work_dir = ExecutorWorkDir::new(&app, executor_id).unwrap();
+        let work_dir = ExecutorWorkDir::new(&app, executor_id, temp.path(), &socket_dir).unwrap();
 
         assert!(work_dir.process_dir().ends_with(executor_id));
         assert!(work_dir.app_dir().ends_with("test-app"));
@@ -276,7 +328,7 @@ mod tests {
         let app = create_test_app("test-app", Some(custom_dir.to_string_lossy().to_string()));
         let executor_id = "exec-456";
 
-        let work_dir = ExecutorWorkDir::new(&app, executor_id).unwrap();
+        let work_dir = ExecutorWorkDir::new(&app, executor_id, temp.path(), &socket_dir).unwrap();
 
         assert_eq!(work_dir.process_dir(), custom_dir.as_path());
         assert_eq!(work_dir.app_dir(), custom_dir.join("work").join("test-app"));
@@ -290,19 +342,19 @@ mod tests {
     fn test_executor_work_dir_socket_path_length() {
         let _guard = TEST_LOCK.lock().unwrap();
         let temp = tempdir().unwrap();
-        setup_test_env(&temp);
+        let socket_dir = setup_test_env(&temp);
 
         let app = create_test_app("test-app", None);
         let long_executor_id = "550e8400-e29b-41d4-a716-446655440000";
 
-        let work_dir = ExecutorWorkDir::new(&app, long_executor_id).unwrap();
+        let work_dir =
+            ExecutorWorkDir::new(&app, long_executor_id, temp.path(), &socket_dir).unwrap();
 
-        let socket_path = work_dir.socket();
-        let default_path = format!("{}/{}.sock", FLAME_SOCKET_DIR, long_executor_id);
+        let socket_path = work_dir.socket().to_string_lossy().to_string();
         assert!(
-            default_path.len() < 104,
-            "Default socket path should be under SUN_LEN limit: {} chars",
-            default_path.len()
+            socket_path.len() < 104,
+            "Socket path should be under SUN_LEN limit: {} chars",
+            socket_path.len()
         );
     }
 
@@ -310,7 +362,7 @@ mod tests {
     fn test_executor_work_dir_cleanup_on_drop() {
         let _guard = TEST_LOCK.lock().unwrap();
         let temp = tempdir().unwrap();
-        setup_test_env(&temp);
+        let socket_dir = setup_test_env(&temp);
 
         let app = create_test_app("test-app", None);
         let executor_id = "exec-drop-test";
@@ -320,7 +372,8 @@ mod tests {
         let socket_path: PathBuf;
 
         {
-            let work_dir = ExecutorWorkDir::new(&app, executor_id).unwrap();
+            let work_dir =
+                ExecutorWorkDir::new(&app, executor_id, temp.path(), &socket_dir).unwrap();
             top_dir = work_dir.process_dir().to_path_buf();
             app_dir = work_dir.app_dir().to_path_buf();
             socket_path = work_dir.socket().to_path_buf();
@@ -344,7 +397,7 @@ mod tests {
     fn test_executor_work_dir_no_cleanup_custom_dir_on_drop() {
         let _guard = TEST_LOCK.lock().unwrap();
         let temp = tempdir().unwrap();
-        setup_test_env(&temp);
+        let socket_dir = setup_test_env(&temp);
         let custom_dir = temp.path().join("persistent-workdir");
         std::fs::create_dir_all(&custom_dir).unwrap();
 
@@ -354,7 +407,8 @@ mod tests {
         let socket_path: PathBuf;
 
         {
-            let work_dir = ExecutorWorkDir::new(&app, executor_id).unwrap();
+            let work_dir =
+                ExecutorWorkDir::new(&app, executor_id, temp.path(), &socket_dir).unwrap();
             socket_path = work_dir.socket().to_path_buf();
 
             File::create(&socket_path).unwrap();
@@ -378,8 +432,8 @@ mod tests {
         std::fs::create_dir_all(&custom_work_dir).unwrap();
         let app2 = create_test_app("app2", Some(custom_work_dir.to_string_lossy().to_string()));
 
-        let work_dir1 = ExecutorWorkDir::new(&app1, "exec-1").unwrap();
-        let work_dir2 = ExecutorWorkDir::new(&app2, "exec-2").unwrap();
+        let work_dir1 = ExecutorWorkDir::new(&app1, "exec-1", temp.path(), &socket_dir).unwrap();
+        let work_dir2 = ExecutorWorkDir::new(&app2, "exec-2", temp.path(), &socket_dir).unwrap();
 
         assert_eq!(work_dir1.socket().parent().unwrap(), socket_dir.as_path());
         assert_eq!(work_dir2.socket().parent().unwrap(), socket_dir.as_path());
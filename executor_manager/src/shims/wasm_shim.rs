@@ -11,6 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -22,8 +23,9 @@ use wasmtime::{Config, Engine, Store};
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
 use crate::executor::Executor;
+use crate::oci;
 use crate::shims::wasm_shim::exports::component::flame::service;
-use crate::shims::{Shim, ShimPtr};
+use crate::shims::{Shim, ShimPtr, WASM_CACHE_DIR};
 use common::{self, apis, FlameError};
 
 wasmtime::component::bindgen!({
@@ -44,7 +46,7 @@ pub struct WasmShim {
 
 impl WasmShim {
     pub async fn new_ptr(
-        _: &Executor,
+        executor: &Executor,
         app: &apis::ApplicationContext,
     ) -> Result<ShimPtr, common::FlameError> {
         trace_fn!("WasmShim::new_ptr");
@@ -60,12 +62,29 @@ impl WasmShim {
         let wasi_view = ServerWasiView::new();
         let mut store = Store::new(&engine, wasi_view);
 
-        let cmd = app
-            .command
-            .clone()
-            .ok_or(FlameError::InvalidConfig("command is empty".to_string()))?;
+        // The module is pulled from the OCI registry by the image, or loaded from the command.
+        let module = match &app.image {
+            Some(image) => {
+                let (registry, work_dir) = executor
+                    .context
+                    .as_ref()
+                    .map(|ctx| {
+                        (
+                            ctx.cluster.executors.registry.clone(),
+                            ctx.paths.work.clone(),
+                        )
+                    })
+                    .unwrap_or_default();
+                oci::pull(image, &registry, &work_dir.join(WASM_CACHE_DIR)).await?
+            }
+            None => app
+                .command
+                .clone()
+                .map(PathBuf::from)
+                .ok_or(FlameError::InvalidConfig("command is empty".to_string()))?,
+        };
 
-        let component = Component::from_file(&engine, cmd).map_err(|e| {
+        let component = Component::from_file(&engine, module).map_err(|e| {
             common::FlameError::Internal(format!("Component file not found: {}", e))
         })?;
 
@@ -90,7 +109,7 @@ impl Shim for WasmShim {
         trace_fn!("WasmShim::on_session_enter");
 
         let ssn_ctx = service::SessionContext {
-            session_id: ctx.session_id.clone(),
+            session_id: ctx.session_id.to_string(),
             common_data: ctx.common_data.clone().map(apis::CommonData::into),
         };
 
@@ -112,9 +131,24 @@ impl Shim for WasmShim {
     ) -> Result<apis::TaskResult, common::FlameError> {
         trace_fn!("WasmShim::on_task_invoke");
 
+        if !ctx.overrides.is_empty() {
+            tracing::warn!(
+                "Task <{}/{}> overrides are not supported by wasm shim, ignored.",
+                ctx.session_id,
+                ctx.task_id
+            );
+        }
+
+        if let Some(input_ref) = &ctx.input_ref {
+            return Err(common::FlameError::InvalidConfig(format!(
+                "task <{}/{}> input in object cache <{}> is not supported by wasm shim",
+                ctx.session_id, ctx.task_id, input_ref.key
+            )));
+        }
+
         let task_ctx = service::TaskContext {
-            session_id: ctx.session_id.clone(),
-            task_id: ctx.task_id.clone(),
+            session_id: ctx.session_id.to_string(),
+            task_id: ctx.task_id.to_string(),
         };
 
         let result = self
@@ -131,14 +165,26 @@ impl Shim for WasmShim {
             Ok(output) => Ok(apis::TaskResult {
                 state: apis::TaskState::Succeed,
                 output: output.map(apis::TaskOutput::from),
+                output_ref: None,
                 message: None,
+                artifacts: vec![],
+                failure_reason: None,
+                usage: None,
+                postmortem: None,
+                codec: None,
             }),
             Err(e) => {
                 tracing::error!("Task failed: {}", e.message);
                 Ok(apis::TaskResult {
                     state: apis::TaskState::Failed,
                     output: None,
+                    output_ref: None,
                     message: Some(e.message),
+                    artifacts: vec![],
+                    failure_reason: Some(apis::FailureReason::ApplicationError),
+                    usage: None,
+                    postmortem: None,
+                    codec: None,
                 })
             }
         }
@@ -155,7 +201,7 @@ impl Shim for WasmShim {
             ))?;
 
         let ssn_ctx = service::SessionContext {
-            session_id: session_context.session_id.clone(),
+            session_id: session_context.session_id.to_string(),
             common_data: None,
         };
 
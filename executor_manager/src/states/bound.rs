@@ -11,13 +11,23 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 use stdng::{logs::TraceFn, trace_fn};
+use tokio::time::{timeout, Instant};
 
 use crate::client::BackendClient;
 use crate::executor::Executor;
+use crate::load;
+use crate::prefetch::{self, Prefetcher};
+use crate::probe::Prober;
+use crate::shims::Shim;
 use crate::states::State;
-use common::apis::ExecutorState;
+use common::apis::{
+    ExecutorState, FailureReason, ObjectReference, ResultPolicy, TaskArtifact, TaskContext,
+    TaskResult, TaskState,
+};
 use common::FlameError;
 
 #[derive(Clone)]
@@ -31,41 +41,303 @@ impl State for BoundState {
     async fn execute(&mut self) -> Result<Executor, FlameError> {
         trace_fn!("BoundState::execute");
 
-        let task = self.client.launch_task(&self.executor.clone()).await?;
-        self.executor.task = task.clone();
-
-        match task {
-            Some(task_ctx) => {
-                let shim_ptr =
-                    &mut self
-                        .executor
-                        .shim_instance
-                        .clone()
-                        .ok_or(FlameError::InvalidState(
-                            "no shim instance in bound state".to_string(),
-                        ))?;
-                let task_result = {
-                    let mut shim = shim_ptr.lock().await;
-                    shim.on_task_invoke(&task_ctx).await?
-                };
+        let tasks = self.client.launch_task(&self.executor.clone()).await?;
+        // The deadlines of the tasks are the remaining budgets since launched.
+        let launched = Instant::now();
+        if tasks.is_empty() {
+            self.executor.state = ExecutorState::Unbinding;
+        }
+
+        let depth = self
+            .executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.cluster.executors.prefetch)
+            .unwrap_or_default();
+        let mut prefetcher = Prefetcher::new(depth);
 
-                self.client
-                    .complete_task(&self.executor.clone(), &task_result)
-                    .await?;
+        // The instance is probed while it runs the tasks, if the application
+        // has a health probe.
+        let prober = Prober::new(&self.executor);
+        let unhealthy = async {
+            match &prober {
+                Some(prober) => prober.unhealthy().await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(unhealthy);
+
+        // The running task is interrupted if the executor is preempted, and the
+        // instance checkpoints it within the grace period before it's requeued;
+        // without the grace period, the running task completes instead.
+        let grace_period = self
+            .executor
+            .context
+            .as_ref()
+            .and_then(|ctx| ctx.cluster.executors.preempt_grace_period)
+            .map(Duration::from_secs);
+        let preemption = self.executor.preemption.clone();
+        let preempt = async {
+            match grace_period {
+                Some(_) => preemption.notified().await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(preempt);
+        let mut preempted = false;
+        // The instance running the timed out task may still be busy with it,
+        // so it's released instead of running the next tasks.
+        let mut timed_out = false;
+        // The crashed instance is gone, so the remaining tasks are failed and
+        // the executor is released.
+        let mut crashed = false;
 
-                let (ssn_id, task_id) = {
-                    let task = &self.executor.task.clone().unwrap();
-                    (task.session_id.clone(), task.task_id.clone())
+        // The bundled tasks are run one by one, and completed in order; the
+        // inputs of the next tasks are prefetched while the current one runs.
+        for (i, task_ctx) in tasks.iter().enumerate() {
+            self.executor.task = Some(task_ctx.clone());
+            load::start_task(&self.executor.id, tasks.len() - i - 1);
+            prefetcher.on_task_invoke(task_ctx);
+            prefetcher.prefetch(&tasks[i + 1..]);
+            // The trace ID of the request which created the task, to find it in the logs.
+            let trace_id = task_ctx.trace_id.as_deref().unwrap_or("-");
+            tracing::debug!(
+                "Invoke task <{}/{}> of request <{trace_id}>",
+                task_ctx.session_id,
+                task_ctx.task_id
+            );
+
+            let mut postmortem = None;
+            let deadline = task_ctx
+                .overrides
+                .deadline
+                .map(|ms| launched + Duration::from_millis(ms));
+            let mut deadline_exceeded = false;
+            let shim_ptr =
+                &mut self
+                    .executor
+                    .shim_instance
+                    .clone()
+                    .ok_or(FlameError::InvalidState(
+                        "no shim instance in bound state".to_string(),
+                    ))?;
+            // The tasks of the unhealthy instance are failed without running,
+            // so they're dispatched again to other executors.
+            let invoked = if let Some(reason) = self.executor.unhealthy.clone() {
+                Err(FlameError::InvalidState(format!(
+                    "instance is unhealthy: {reason}"
+                )))
+            } else if preempted {
+                Err(FlameError::InvalidState(
+                    "executor is preempted".to_string(),
+                ))
+            } else if timed_out {
+                Err(FlameError::InvalidState(
+                    "the previous task timed out".to_string(),
+                ))
+            } else if crashed {
+                Err(FlameError::InvalidState("the instance crashed".to_string()))
+            } else if deadline.is_some_and(|at| at <= Instant::now()) {
+                // The request is already timed out, so the task is failed
+                // without invoking the application.
+                deadline_exceeded = true;
+                Err(FlameError::InvalidState("deadline exceeded".to_string()))
+            } else {
+                let now = Instant::now();
+                let timeout_at = task_ctx
+                    .overrides
+                    .timeout
+                    .map(|secs| now + Duration::from_secs(secs));
+                let expire = async {
+                    match timeout_at.into_iter().chain(deadline).min() {
+                        Some(at) => tokio::time::sleep_until(at).await,
+                        None => std::future::pending().await,
+                    }
+                };
+                // The instance gets the remaining budget of the task.
+                let mut invoke_ctx = task_ctx.clone();
+                invoke_ctx.overrides.deadline =
+                    deadline.map(|at| at.saturating_duration_since(now).as_millis() as u64);
+                let mut shim = shim_ptr.lock().await;
+                let invoked = tokio::select! {
+                    invoked = shim.on_task_invoke(&invoke_ctx) => invoked,
+                    reason = &mut unhealthy => {
+                        tracing::error!(
+                            "Instance of executor <{}> is unhealthy: {reason}",
+                            self.executor.id
+                        );
+                        let e = FlameError::InvalidState(format!(
+                            "instance is unhealthy: {reason}"
+                        ));
+                        self.executor.unhealthy = Some(reason);
+                        Err(e)
+                    }
+                    _ = &mut preempt => {
+                        preempted = true;
+                        Err(FlameError::InvalidState("executor is preempted".to_string()))
+                    }
+                    _ = expire => {
+                        timed_out = true;
+                        if deadline.is_some_and(|at| at <= Instant::now()) {
+                            deadline_exceeded = true;
+                            Err(FlameError::InvalidState("deadline exceeded".to_string()))
+                        } else {
+                            Err(FlameError::InvalidState(format!(
+                                "task timed out after {}s",
+                                task_ctx.overrides.timeout.unwrap_or_default()
+                            )))
+                        }
+                    }
+                };
+                if let Some(grace_period) = grace_period.filter(|_| preempted) {
+                    checkpoint(&mut *shim, &invoke_ctx, grace_period).await;
+                }
+                // The failed invocation by the instance itself may be a crash.
+                if invoked.is_err() && !preempted && !timed_out && self.executor.unhealthy.is_none()
+                {
+                    postmortem = shim.postmortem().await;
+                    crashed = postmortem.is_some();
+                }
+                invoked
+            };
+            load::complete_task(&self.executor.id);
+            let mut task_result = match invoked {
+                Ok(task_result) => task_result,
+                Err(e) => {
+                    tracing::error!(
+                        "Shim failed to invoke task <{}> of request <{trace_id}>: {e}",
+                        task_ctx.task_id
+                    );
+                    TaskResult {
+                        state: TaskState::Failed,
+                        output: None,
+                        output_ref: None,
+                        message: Some(e.to_string()),
+                        artifacts: vec![],
+                        failure_reason: Some(if preempted {
+                            FailureReason::Preempted
+                        } else if deadline_exceeded {
+                            FailureReason::DeadlineExceeded
+                        } else if timed_out {
+                            FailureReason::Timeout
+                        } else {
+                            FailureReason::ShimCrash
+                        }),
+                        usage: None,
+                        postmortem,
+                        codec: None,
+                    }
+                }
+            };
+
+            let check =
+                TaskArtifact::validate_all(&task_result.artifacts).and_then(|_| {
+                    match &self.executor.scratch {
+                        Some(scratch) => scratch.check(),
+                        None => Ok(()),
+                    }
+                });
+            if let Err(e) = check {
+                tracing::warn!("Fail task <{}>: {e}", task_ctx.task_id);
+                task_result = TaskResult {
+                    state: TaskState::Failed,
+                    output: None,
+                    output_ref: None,
+                    message: Some(e.to_string()),
+                    artifacts: vec![],
+                    failure_reason: Some(FailureReason::ApplicationError),
+                    usage: task_result.usage,
+                    postmortem: None,
+                    codec: None,
                 };
-                tracing::debug!("Complete task <{ssn_id}/{task_id}>")
             }
-            None => {
-                self.executor.state = ExecutorState::Unbinding;
+
+            let result_policy = self
+                .executor
+                .session
+                .as_ref()
+                .map(|ssn| ssn.result_policy)
+                .unwrap_or_default();
+            if result_policy == ResultPolicy::ObjectCache {
+                offload_output(task_ctx, &mut task_result).await;
             }
+
+            self.client
+                .complete_task(&self.executor.clone(), &task_result)
+                .await?;
+
+            tracing::debug!(
+                "Complete task <{}/{}> of request <{trace_id}>",
+                task_ctx.session_id,
+                task_ctx.task_id
+            )
         }
 
         self.executor.task = None;
+        if self.executor.unhealthy.is_some() || preempted || timed_out || crashed {
+            self.executor.state = ExecutorState::Unbinding;
+        }
+
+        if depth > 0 && tasks.len() > 1 {
+            tracing::debug!("Prefetch of task inputs: {}", prefetch::stats());
+        }
 
         Ok(self.executor.clone())
     }
 }
+
+/// Asks the instance of the preempted executor to checkpoint the interrupted
+/// task; it's abandoned after the grace period.
+async fn checkpoint(shim: &mut dyn Shim, task_ctx: &TaskContext, grace_period: Duration) {
+    match timeout(grace_period, shim.on_preempt(task_ctx, grace_period)).await {
+        Ok(Ok(())) => tracing::debug!(
+            "Checkpointed task <{}/{}> of the preempted executor",
+            task_ctx.session_id,
+            task_ctx.task_id
+        ),
+        Ok(Err(e)) => tracing::warn!(
+            "Failed to checkpoint task <{}/{}>: {e}",
+            task_ctx.session_id,
+            task_ctx.task_id
+        ),
+        Err(_) => tracing::warn!(
+            "Checkpoint of task <{}/{}> exceeded the grace period <{}s>",
+            task_ctx.session_id,
+            task_ctx.task_id,
+            grace_period.as_secs()
+        ),
+    }
+}
+
+/// Puts the output of the task into the object cache of this node, and
+/// replaces it with its reference; the output is kept inline if the object
+/// cache is not running or failed to put it.
+async fn offload_output(task_ctx: &TaskContext, task_result: &mut TaskResult) {
+    let Some(output) = task_result.output.clone() else {
+        return;
+    };
+
+    let data = output.to_vec();
+    let checksum = format!("sha256:{}", flame_cache::upload::sha256(&data));
+    let object_id = format!("output-{}", task_ctx.task_id);
+    match flame_cache::put_local(task_ctx.session_id.clone(), object_id, data).await {
+        Ok(Some(meta)) => {
+            task_result.output = None;
+            task_result.output_ref = Some(ObjectReference {
+                endpoint: meta.endpoint,
+                key: meta.key,
+                checksum: Some(checksum),
+            });
+        }
+        Ok(None) => tracing::warn!(
+            "No object cache to offload the output of task <{}/{}>, keep it inline",
+            task_ctx.session_id,
+            task_ctx.task_id
+        ),
+        Err(e) => tracing::warn!(
+            "Failed to offload the output of task <{}/{}>, keep it inline: {e}",
+            task_ctx.session_id,
+            task_ctx.task_id
+        ),
+    }
+}
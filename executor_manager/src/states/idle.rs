@@ -13,11 +13,17 @@ limitations under the License.
 
 use async_trait::async_trait;
 use stdng::{logs::TraceFn, new_ptr, trace_fn, MutexPtr};
+use tokio_stream::StreamExt;
 
+use crate::bootstrap;
 use crate::client::BackendClient;
 use crate::executor::Executor;
+use crate::hooks::{HookPoint, HookRunner};
+use crate::limits;
+use crate::scratch::ScratchDir;
 use crate::shims;
 use crate::states::State;
+use crate::topology;
 use common::apis::{Event, EventOwner, ExecutorState, Shim};
 use common::{new_async_ptr, FlameError};
 
@@ -30,6 +36,26 @@ pub struct IdleState {
     pub executor: Executor,
 }
 
+impl IdleState {
+    /// Reports that the instance failed to start for the session, e.g. the binary
+    /// of the application is missing on this node, so that the executor is idle
+    /// again and the application is not scheduled to this node until it's retried.
+    async fn bind_failed(&mut self, e: FlameError) -> FlameError {
+        if let Err(err) = self
+            .client
+            .bind_executor_failed(&self.executor.clone(), &e.to_string())
+            .await
+        {
+            tracing::warn!(
+                "Failed to report the bind failure of executor <{}>: {err}",
+                self.executor.id
+            );
+        }
+
+        e
+    }
+}
+
 #[async_trait]
 impl State for IdleState {
     async fn execute(&mut self) -> Result<Executor, FlameError> {
@@ -37,7 +63,7 @@ impl State for IdleState {
 
         let ssn = self.client.bind_executor(&self.executor.clone()).await?;
 
-        let Some(ssn) = ssn else {
+        let Some(mut ssn) = ssn else {
             tracing::debug!(
                 "Executor <{}> is idle but no session is found, start to release.",
                 &self.executor.id.clone()
@@ -67,10 +93,11 @@ impl State for IdleState {
                 ssn.application.name,
                 app_shim
             );
-            return Err(FlameError::InvalidConfig(format!(
+            let e = FlameError::InvalidConfig(format!(
                 "Shim mismatch: executor supports {:?}, application requires {:?}",
                 executor_shim, app_shim
-            )));
+            ));
+            return Err(self.bind_failed(e).await);
         }
 
         tracing::debug!(
@@ -86,13 +113,75 @@ impl State for IdleState {
             &ssn.session_id.clone()
         );
 
-        let shim_ptr = shims::new(&self.executor.clone(), &ssn.application).await?;
+        // The instances of the application on this node are limited by its
+        // max_instances_per_node even if the view of the scheduler is stale;
+        // the instance is released if the executor fails to bind to the session.
+        let instance = match limits::acquire_instance(
+            &self.executor.id,
+            &ssn.application.name,
+            ssn.application.max_instances_per_node,
+        ) {
+            Ok(instance) => instance,
+            Err(e) => return Err(self.bind_failed(e).await),
+        };
+
+        // The instance of a topology-aware application takes a NUMA node or a
+        // GPU group of this node, which is released if the executor fails to bind.
+        let placement = match topology::acquire(&self.executor.id, ssn.application.topology) {
+            Ok(placement) => placement,
+            Err(e) => return Err(self.bind_failed(e).await),
+        };
+
+        // The scratch directory is released if the executor fails to bind to the session.
+        let scratch_root = self
+            .executor
+            .context
+            .as_ref()
+            .map(|ctx| ctx.paths.scratch.clone())
+            .unwrap_or_default();
+        let scratch = ScratchDir::acquire(&ssn, &scratch_root)?;
+        ssn.scratch_dir = scratch
+            .as_ref()
+            .map(|dir| dir.path().to_string_lossy().to_string());
+
+        // The executor fails to bind to the session if its pre-bind hook fails,
+        // e.g. the dataset of the application is not mounted.
+        let hooks = HookRunner::new(&self.executor, &ssn);
+        if let Some(hooks) = &hooks {
+            if let Err(e) = hooks
+                .run(&mut self.client, &self.executor, &ssn, HookPoint::PreBind)
+                .await
+            {
+                return Err(self.bind_failed(e).await);
+            }
+        }
+
+        // The shim is started with a bootstrap permit of the node, which is not
+        // held while waiting for the retries.
+        let started = tokio::time::Instant::now();
+        let shim_ptr = {
+            let mut executor = self.executor.clone();
+            executor.placement = placement.clone();
+            let _permit = bootstrap::acquire().await;
+            shims::new(&executor, &ssn.application).await
+        };
+        let shim_ptr = match shim_ptr {
+            Ok(shim_ptr) => shim_ptr,
+            Err(e) => {
+                bootstrap::observe(&self.executor.id, started.elapsed(), false);
+                return Err(self.bind_failed(e).await);
+            }
+        };
 
         // Retry on_session_enter with delay between attempts
         let mut last_error: Option<FlameError> = None;
         for attempt in 1..=ON_SESSION_ENTER_MAX_RETRIES {
-            let mut shim = shim_ptr.lock().await;
-            match shim.on_session_enter(&ssn).await {
+            let entered = {
+                let _permit = bootstrap::acquire().await;
+                let mut shim = shim_ptr.lock().await;
+                shim.on_session_enter(&ssn).await
+            };
+            match entered {
                 Ok(()) => {
                     tracing::debug!("Shim on_session_enter completed on attempt {}.", attempt);
                     last_error = None;
@@ -114,6 +203,7 @@ impl State for IdleState {
                 }
             }
         }
+        bootstrap::observe(&self.executor.id, started.elapsed(), last_error.is_none());
 
         if let Some(e) = last_error {
             tracing::error!(
@@ -121,18 +211,57 @@ impl State for IdleState {
                 ON_SESSION_ENTER_MAX_RETRIES,
                 e
             );
-            return Err(e);
+            return Err(self.bind_failed(e).await);
         }
 
         self.client
             .bind_executor_completed(&self.executor.clone())
             .await?;
 
+        // The output streamed by the tasks of the instance is relayed to the
+        // session manager until the instance exits; the failure to watch it
+        // only loses the streamed output, the results of the tasks are
+        // still completed.
+        let output = {
+            let mut shim = shim_ptr.lock().await;
+            shim.watch_task_output().await
+        };
+        match output {
+            Ok(Some(output)) => {
+                let mut client = self.client.clone();
+                let executor = self.executor.clone();
+                tokio::spawn(async move {
+                    let chunks = output.filter_map(|chunk| chunk.ok());
+                    if let Err(e) = client.push_task_output(&executor, chunks).await {
+                        tracing::warn!(
+                            "Failed to relay the task output of executor <{}>: {e}",
+                            executor.id
+                        );
+                    }
+                });
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to watch the task output of executor <{}>: {e}",
+                self.executor.id
+            ),
+        }
+
         // Own the shim instance.
         self.executor.shim_instance = Some(shim_ptr.clone());
+        self.executor.scratch = scratch;
+        self.executor.instance = Some(instance);
+        self.executor.placement = placement;
         self.executor.session = Some(ssn.clone());
         self.executor.state = ExecutorState::Bound;
 
+        // The failure of the post-bind hook is only recorded as an event.
+        if let Some(hooks) = &hooks {
+            let _ = hooks
+                .run(&mut self.client, &self.executor, &ssn, HookPoint::PostBind)
+                .await;
+        }
+
         tracing::debug!(
             "Executor <{}> was bound to <{}>.",
             &self.executor.id.clone(),
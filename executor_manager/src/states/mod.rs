@@ -78,10 +78,17 @@ mod tests {
             resreq: ResourceRequirement::default(),
             slots: 1,
             shim: Shim::Host,
+            generation: 0,
             session: None,
             task: None,
             context: None,
             shim_instance: None,
+            scratch: None,
+            instance: None,
+            placement: None,
+            unhealthy: None,
+            preempted: false,
+            preemption: Default::default(),
             state,
         }
     }
@@ -14,8 +14,10 @@ limitations under the License.
 use async_trait::async_trait;
 use stdng::{logs::TraceFn, trace_fn};
 
+use crate::apps;
 use crate::client::BackendClient;
 use crate::executor::Executor;
+use crate::hooks::{HookPoint, HookRunner};
 use crate::states::State;
 use common::apis::ExecutorState;
 use common::FlameError;
@@ -40,7 +42,28 @@ impl State for UnbindingState {
                 "no shim instance in unbinding state".to_string(),
             ))?;
 
-        {
+        // The pre-release hook is run even if the instance is unhealthy, e.g. to
+        // unmount its dataset; its failure is only recorded as an event. The hook
+        // of the latest application is run, e.g. a broken one was fixed meanwhile.
+        if let Some(mut ssn) = self.executor.session.clone() {
+            if let Ok(app) = apps::get(&mut self.client, &ssn.application.name).await {
+                ssn.application = app;
+            }
+            if let Some(hooks) = HookRunner::new(&self.executor, &ssn) {
+                let _ = hooks
+                    .run(
+                        &mut self.client,
+                        &self.executor,
+                        &ssn,
+                        HookPoint::PreRelease,
+                    )
+                    .await;
+            }
+        }
+
+        // The unhealthy instance is not asked to leave the session, as it may
+        // never respond; it's stopped when the shim is dropped.
+        if self.executor.unhealthy.is_none() {
             let mut shim = shim_ptr.lock().await;
             shim.on_session_leave().await?;
         }
@@ -52,6 +75,10 @@ impl State for UnbindingState {
         self.executor.task = None;
         self.executor.session = None;
         self.executor.shim_instance = None;
+        self.executor.scratch = None;
+        self.executor.instance = None;
+        self.executor.placement = None;
+        self.executor.unhealthy = None;
 
         // After unbound from session, the executor is idle now.
         self.executor.state = ExecutorState::Idle;
@@ -16,7 +16,8 @@ limitations under the License.
 //! This module provides the client-side implementation of the WatchNode
 //! streaming protocol, including reconnection logic and heartbeat management.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::time::Duration;
 
 use tokio::sync::mpsc;
@@ -24,14 +25,23 @@ use tokio::time::{interval, timeout};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::Streaming;
 
-use common::apis::Node;
+use common::apis::{Node, SessionID};
+use common::ctx::NodeConfig;
 use common::FlameError;
 use rpc::flame::v1 as proto;
 use stdng::{lock_ptr, MutexPtr};
 
+use crate::artifacts;
 use crate::client::BackendClient;
+use crate::credentials;
 use crate::executor::{Executor, ExecutorPtr};
+use crate::identities;
+use crate::limits;
+use crate::load;
 use crate::manager::ExecutorMessage;
+use crate::node_config;
+use crate::resources::SlotPolicy;
+use crate::topology;
 
 /// Default interval between heartbeats in seconds.
 const DEFAULT_HEARTBEAT_INTERVAL_SECS: u64 = 5;
@@ -54,11 +64,30 @@ const RESPONSE_TIMEOUT_SECS: u64 = 10;
 /// - Handling reconnection with exponential backoff
 /// - Processing executor state notifications from the server
 /// - Forwarding executor updates to the manager for action derivation
+/// - Applying the node-level configuration from the server
+/// - Prefetching the artifacts of the applications named by the configuration
+/// - Joining the cluster by the join token, and keeping the node credential
+/// - Persisting the executors, so the idle ones are re-adopted after a restart
 pub struct StreamHandler {
     client: BackendClient,
     node: MutexPtr<Node>,
     /// Reference to current executors (shared with manager) for re-registration
     executors: MutexPtr<HashMap<String, ExecutorPtr>>,
+    /// The idle executors of the last run, reported by the first registration
+    /// so the session manager keeps them; persisted in `work_dir`.
+    adopted: Vec<Executor>,
+    /// Derives the allocatable resources of the node from its capacity; its
+    /// slots are overridden by the node-level configuration.
+    policy: MutexPtr<SlotPolicy>,
+    /// The slots of the local configuration, e.g. `--slots`.
+    local_slots: Option<u32>,
+    /// The node-level configuration from the server, persisted in `work_dir`.
+    node_config: NodeConfig,
+    /// The one-time join token, dropped once the node joined the cluster.
+    join_token: Option<String>,
+    /// The credential of the node, persisted in `work_dir`.
+    node_token: Option<String>,
+    work_dir: PathBuf,
     reconnect_interval: Duration,
     heartbeat_interval: Duration,
 }
@@ -70,11 +99,48 @@ impl StreamHandler {
     ///
     /// * `client` - The backend client for gRPC communication
     /// * `executors` - Shared reference to current executors for re-registration on reconnect
-    pub fn new(client: BackendClient, executors: MutexPtr<HashMap<String, ExecutorPtr>>) -> Self {
+    /// * `policy` - The policy to derive the allocatable resources of the node
+    /// * `work_dir` - The directory of the persisted node-level configuration
+    pub fn new(
+        client: BackendClient,
+        executors: MutexPtr<HashMap<String, ExecutorPtr>>,
+        mut policy: SlotPolicy,
+        work_dir: PathBuf,
+    ) -> Self {
+        let local_slots = policy.slots;
+        let node_config = node_config::load(&work_dir);
+        let node_token = credentials::load(&work_dir);
+        let adopted = identities::load(&work_dir);
+        policy.slots = node_config.slots.or(local_slots);
+
+        let mut node = Node::new();
+        policy.apply(&mut node);
+
+        // The topology is detected once at start, and kept by the refreshes.
+        let (numa_nodes, gpu_groups) = topology::detect();
+        node.info.numa_nodes = numa_nodes.clone();
+        node.info.gpu_groups = gpu_groups.clone();
+        topology::init(numa_nodes, gpu_groups);
+
+        if policy.is_overcommitted(&node.capacity) {
+            tracing::warn!(
+                "Manual slots of node <{}> exceed its capacity: {}",
+                node.name,
+                policy.derivation(&node.capacity)
+            );
+        }
+
         StreamHandler {
             client,
-            node: stdng::new_ptr(Node::new()),
+            node: stdng::new_ptr(node),
             executors,
+            adopted,
+            policy: stdng::new_ptr(policy),
+            local_slots,
+            node_config,
+            join_token: credentials::join_token(),
+            node_token,
+            work_dir,
             reconnect_interval: Duration::from_secs(DEFAULT_RECONNECT_INTERVAL_SECS),
             heartbeat_interval: Duration::from_secs(DEFAULT_HEARTBEAT_INTERVAL_SECS),
         }
@@ -141,21 +207,52 @@ impl StreamHandler {
     ) -> Result<(), FlameError> {
         // Get current node state
         let node = lock_ptr!(self.node)?.clone();
+        let derivation = lock_ptr!(self.policy)?.derivation(&node.capacity);
 
-        // Get current executors for state alignment during registration
-        let current_executors: Vec<Executor> = lock_ptr!(self.executors)?
+        // Get current executors for state alignment during registration, and
+        // the ones of the last run to re-adopt
+        let mut current_executors: Vec<Executor> = lock_ptr!(self.executors)?
             .values()
             .filter_map(|ptr| lock_ptr!(ptr).ok().map(|e| (*e).clone()))
             .collect();
+        for executor in &self.adopted {
+            if !current_executors.iter().any(|e| e.id == executor.id) {
+                tracing::info!("Re-adopting executor <{}> of the last run", executor.id);
+                current_executors.push(executor.clone());
+            }
+        }
 
         // Register node with current executor list for state alignment
         // This is called on every reconnection to handle failover scenarios
         tracing::info!(
-            "Registering node <{}> with {} executors for state alignment",
+            "Registering node <{}> with {} executors for state alignment, {}",
             node.name,
-            current_executors.len()
+            current_executors.len(),
+            derivation
         );
-        self.client.register_node(&node, &current_executors).await?;
+        let node_token = self
+            .client
+            .register_node(
+                &node,
+                &current_executors,
+                self.join_token.as_deref(),
+                self.node_token.as_deref(),
+            )
+            .await?;
+        if let Some(token) = node_token {
+            tracing::info!("Node <{}> joined the cluster", node.name);
+            if let Err(e) = credentials::save(&self.work_dir, &token) {
+                tracing::warn!("Failed to persist the credential of the node: {e}");
+            }
+            self.node_token = Some(token);
+            self.join_token = None;
+        }
+        if let Some(token) = self.node_token.as_deref() {
+            credentials::set_current(&node.name, token);
+        }
+        // The session manager sends the re-adopted executors back, which are
+        // started as the other executors of the node.
+        self.adopted.clear();
 
         // Create channels for the bidirectional stream
         let (request_tx, request_rx) = mpsc::channel::<proto::WatchNodeRequest>(32);
@@ -168,7 +265,7 @@ impl StreamHandler {
 
         tracing::info!("WatchNode: Starting stream for node <{}>", node.name,);
 
-        // Send initial heartbeat immediately to identify the node
+        // Send initial heartbeat immediately to identify the node by its credential
         let initial_heartbeat = proto::WatchNodeRequest {
             heartbeat: Some(proto::NodeHeartbeat {
                 node_name: node.name.clone(),
@@ -179,7 +276,14 @@ impl StreamHandler {
                     info: Some(node.info.clone().into()),
                     addresses: vec![],
                     last_heartbeat_time: 0,
+                    saturation: None,
+                    events: vec![],
+                    executors: 0,
+                    max_executors: None,
                 }),
+                node_token: self.node_token.clone(),
+                executor_loads: vec![],
+                max_executors: limits::max_executors(),
             }),
         };
         request_tx
@@ -190,17 +294,56 @@ impl StreamHandler {
         // Spawn heartbeat task for periodic heartbeats
         let heartbeat_tx = request_tx.clone();
         let node_ptr = self.node.clone();
+        let policy = self.policy.clone();
         let heartbeat_interval = self.heartbeat_interval;
+        let executors = self.executors.clone();
+        let work_dir = self.work_dir.clone();
         let heartbeat_handle = tokio::spawn(async move {
             let mut ticker = interval(heartbeat_interval);
+            let mut persisted = vec![];
             loop {
                 ticker.tick().await;
 
+                let mut executor_loads = vec![];
+                // Persist the executors once they changed, so the idle ones are
+                // re-adopted after a restart.
+                if let Ok(executors) = lock_ptr!(executors) {
+                    executor_loads = load::report(&executors.keys().cloned().collect());
+                    let current: Vec<Executor> = executors
+                        .values()
+                        .filter_map(|ptr| lock_ptr!(ptr).ok().map(|e| (*e).clone()))
+                        .collect();
+                    let mut states: Vec<_> =
+                        current.iter().map(|e| (e.id.clone(), e.state)).collect();
+                    states.sort_by(|a, b| a.0.cmp(&b.0));
+                    if states != persisted {
+                        match identities::save(&work_dir, &current) {
+                            Ok(()) => persisted = states,
+                            Err(e) => tracing::warn!("Failed to persist executors: {e}"),
+                        }
+                    }
+                }
+
                 // Refresh and collect current node status
                 let (node_name, status) = match node_ptr.lock() {
                     Ok(mut node) => {
-                        // Refresh node to get current resource status
+                        // Refresh node to get current resource status, e.g. CPUs/memory
+                        // hot-plugged or container limits changed.
+                        let capacity = node.capacity.clone();
                         node.refresh();
+                        let Ok(policy) = lock_ptr!(policy) else {
+                            tracing::warn!("Failed to lock slot policy for heartbeat");
+                            continue;
+                        };
+                        policy.apply(&mut node);
+                        node.info.prefetched = artifacts::prefetched();
+                        if node.capacity != capacity {
+                            tracing::info!(
+                                "Resources of node <{}> changed: {}",
+                                node.name,
+                                policy.derivation(&node.capacity)
+                            );
+                        }
                         let status = proto::NodeStatus {
                             state: proto::NodeState::from(node.state) as i32,
                             capacity: Some(node.capacity.clone().into()),
@@ -208,6 +351,10 @@ impl StreamHandler {
                             info: Some(node.info.clone().into()),
                             addresses: vec![],
                             last_heartbeat_time: 0,
+                            saturation: None,
+                            events: vec![],
+                            executors: 0,
+                            max_executors: None,
                         };
                         (node.name.clone(), Some(status))
                     }
@@ -218,7 +365,13 @@ impl StreamHandler {
                 };
 
                 let heartbeat = proto::WatchNodeRequest {
-                    heartbeat: Some(proto::NodeHeartbeat { node_name, status }),
+                    heartbeat: Some(proto::NodeHeartbeat {
+                        node_name,
+                        status,
+                        node_token: None,
+                        executor_loads,
+                        max_executors: limits::max_executors(),
+                    }),
                 };
                 if heartbeat_tx.send(heartbeat).await.is_err() {
                     break;
@@ -237,7 +390,7 @@ impl StreamHandler {
 
     /// Processes responses from the server stream.
     async fn process_responses(
-        &self,
+        &mut self,
         mut stream: Streaming<proto::WatchNodeResponse>,
         executor_tx: &mpsc::Sender<ExecutorMessage>,
     ) -> Result<(), FlameError> {
@@ -274,7 +427,7 @@ impl StreamHandler {
     /// Returns an ExecutorMessage if the response contains an executor update,
     /// which the caller is responsible for forwarding to the manager.
     fn handle_response(
-        &self,
+        &mut self,
         response: proto::WatchNodeResponse,
     ) -> Result<Option<ExecutorMessage>, FlameError> {
         match response.response {
@@ -289,6 +442,17 @@ impl StreamHandler {
 
                 Ok(Some(ExecutorMessage::Update(executor)))
             }
+            Some(proto::watch_node_response::Response::Config(config)) => {
+                // The config is sent on each registration, when the artifacts
+                // of the applications are prefetched again.
+                artifacts::prefetch(self.client.clone(), &config.prefetch);
+                self.apply_node_config(NodeConfig::from(config))?;
+                Ok(None)
+            }
+            Some(proto::watch_node_response::Response::Sessions(sessions)) => {
+                self.reconcile_cache(sessions);
+                Ok(None)
+            }
             Some(proto::watch_node_response::Response::Ack(ack)) => {
                 tracing::trace!(
                     "WatchNode: Received acknowledgement with timestamp {}",
@@ -302,6 +466,69 @@ impl StreamHandler {
             }
         }
     }
+
+    /// Reconciles the object cache of the node with the live sessions from the
+    /// server in background, so the objects of the deleted sessions are removed.
+    fn reconcile_cache(&self, sessions: proto::LiveSessions) {
+        let live: HashSet<SessionID> = sessions
+            .session_ids
+            .into_iter()
+            .map(SessionID::from)
+            .collect();
+        tracing::debug!("WatchNode: Received <{}> live sessions", live.len());
+
+        tokio::task::spawn_blocking(move || match flame_cache::gc::reconcile(&live) {
+            Ok(deleted) if !deleted.is_empty() => {
+                tracing::info!(
+                    "Deleted the objects of <{}> orphaned sessions",
+                    deleted.len()
+                )
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to reconcile the object cache: {}", e),
+        });
+    }
+
+    /// Applies the node-level configuration from the server, and persists it
+    /// for the next start; the settings of the object cache are only applied
+    /// at start.
+    fn apply_node_config(&mut self, config: NodeConfig) -> Result<(), FlameError> {
+        if config == self.node_config {
+            return Ok(());
+        }
+        tracing::info!("WatchNode: Received node config {:?}", config);
+
+        if let Err(e) = node_config::save(&self.work_dir, &config) {
+            tracing::warn!("Failed to persist node config: {}", e);
+        }
+
+        if config.log_level != self.node_config.log_level {
+            if let Err(e) = common::set_log_level(config.log_level.as_deref()) {
+                tracing::warn!("Failed to set log level <{:?}>: {}", config.log_level, e);
+            }
+        }
+
+        if config.slots != self.node_config.slots {
+            let mut node = lock_ptr!(self.node)?;
+            let mut policy = lock_ptr!(self.policy)?;
+            policy.slots = config.slots.or(self.local_slots);
+            policy.apply(&mut node);
+            tracing::info!(
+                "Slots of node <{}> changed: {}",
+                node.name,
+                policy.derivation(&node.capacity)
+            );
+        }
+
+        if config.cache_max_memory != self.node_config.cache_max_memory
+            || config.cache_max_objects != self.node_config.cache_max_objects
+        {
+            tracing::info!("The object cache settings of the node are applied at the next start.");
+        }
+
+        self.node_config = config;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -327,6 +554,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -338,6 +566,10 @@ mod tests {
             info: Some(node.info.clone().into()),
             addresses: vec![],
             last_heartbeat_time: 0,
+            saturation: None,
+            events: vec![],
+            executors: 0,
+            max_executors: None,
         };
 
         assert_eq!(status.state, proto::NodeState::Ready as i32);
@@ -368,6 +600,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -388,6 +621,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_apply_node_config() {
+        use common::apis::ResourceRequirement;
+
+        let dir = tempfile::tempdir().unwrap();
+        let policy = SlotPolicy::new(ResourceRequirement::from("cpu=1,mem=1g"), None, Some(2));
+        let mut handler = StreamHandler::new(
+            BackendClient::default(),
+            stdng::new_ptr(HashMap::new()),
+            policy,
+            dir.path().to_path_buf(),
+        );
+        let allocatable = |handler: &StreamHandler| handler.node.lock().unwrap().allocatable.cpu;
+        assert_eq!(allocatable(&handler), 2);
+
+        let config = NodeConfig {
+            slots: Some(4),
+            ..NodeConfig::default()
+        };
+        handler.apply_node_config(config.clone()).unwrap();
+        assert_eq!(allocatable(&handler), 4);
+        assert_eq!(node_config::load(dir.path()), config);
+
+        // The persisted configuration is applied at the next start.
+        let restarted = StreamHandler::new(
+            BackendClient::default(),
+            stdng::new_ptr(HashMap::new()),
+            SlotPolicy::new(ResourceRequirement::from("cpu=1,mem=1g"), None, Some(2)),
+            dir.path().to_path_buf(),
+        );
+        assert_eq!(allocatable(&restarted), 4);
+
+        // The empty configuration restores the local slots.
+        handler.apply_node_config(NodeConfig::default()).unwrap();
+        assert_eq!(allocatable(&handler), 2);
+    }
+
     #[test]
     fn test_executor_conversion() {
         use common::apis::{ExecutorState, ResourceRequirement, Shim};
@@ -402,7 +672,14 @@ mod tests {
             task: None,
             context: None,
             shim: Shim::Host,
+            generation: 0,
             shim_instance: None,
+            scratch: None,
+            instance: None,
+            placement: None,
+            unhealthy: None,
+            preempted: false,
+            preemption: Default::default(),
             state: ExecutorState::Idle,
         };
 
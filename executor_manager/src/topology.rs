@@ -0,0 +1,425 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Detection of the GPU/NUMA topology of the node, and the placement of the
+//! instances of the topology-aware applications in it.
+//!
+//! The executor manager detects the NUMA nodes by sysfs and the GPU groups by
+//! `nvidia-smi topo -m` at start, and reports them in the info of the node;
+//! the topology plugin of the scheduler places the executors of a `NumaAligned`
+//! or `GpuAligned` application on the nodes with a free NUMA node or GPU group.
+//! When the executor binds, it takes a concrete one, which is passed to its
+//! instance by `CUDA_VISIBLE_DEVICES` and `numactl`; the binding is rejected
+//! if none is free, e.g. the view of the scheduler is stale.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use common::apis::{GpuGroup, GpuLink, NumaNode, TopologyPolicy};
+use common::FlameError;
+
+const SYSFS_NODE_DIR: &str = "/sys/devices/system/node";
+
+/// The placements of the executors on this node.
+static PLACEMENTS: LazyLock<Mutex<Placements>> =
+    LazyLock::new(|| Mutex::new(Placements::default()));
+
+/// Detects the NUMA nodes and the GPU groups of this node; they're empty if
+/// not detected, e.g. the node has no NVIDIA GPUs.
+pub fn detect() -> (Vec<NumaNode>, Vec<GpuGroup>) {
+    let numa_nodes = detect_numa_nodes(Path::new(SYSFS_NODE_DIR));
+
+    let gpu_groups = match Command::new("nvidia-smi").args(["topo", "-m"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_gpu_topology(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            tracing::debug!(
+                "Failed to detect the GPU topology by nvidia-smi: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            vec![]
+        }
+        Err(e) => {
+            tracing::debug!("No GPU topology is detected, nvidia-smi is not available: {e}");
+            vec![]
+        }
+    };
+
+    (numa_nodes, gpu_groups)
+}
+
+/// Initializes the topology of this node for the placements.
+pub fn init(numa_nodes: Vec<NumaNode>, gpu_groups: Vec<GpuGroup>) {
+    tracing::info!(
+        "The topology of the node: {} NUMA node(s), {} GPU group(s).",
+        numa_nodes.len(),
+        gpu_groups.len()
+    );
+
+    match lock_placements() {
+        Ok(mut placements) => {
+            placements.numa_nodes = numa_nodes;
+            placements.gpu_groups = gpu_groups;
+        }
+        Err(e) => tracing::warn!("Failed to initialize the topology of the node: {e}"),
+    }
+}
+
+/// Acquires a NUMA node or a GPU group of this node for the executor by the
+/// topology policy of its application, released when it's dropped; None for
+/// the topology-unaware applications.
+pub fn acquire(
+    executor_id: &str,
+    policy: TopologyPolicy,
+) -> Result<Option<PlacementPtr>, FlameError> {
+    if policy == TopologyPolicy::Ignore {
+        return Ok(None);
+    }
+
+    let placement = lock_placements()?.acquire(executor_id, policy)?;
+    tracing::debug!(
+        "Executor <{}> was placed in NUMA node <{:?}> with GPUs <{:?}>.",
+        executor_id,
+        placement.numa_node,
+        placement.gpus
+    );
+
+    Ok(Some(Arc::new(placement)))
+}
+
+fn lock_placements() -> Result<std::sync::MutexGuard<'static, Placements>, FlameError> {
+    PLACEMENTS
+        .lock()
+        .map_err(|e| FlameError::Internal(format!("failed to lock the placements: {e}")))
+}
+
+pub type PlacementPtr = Arc<Placement>;
+
+/// The NUMA node or the GPU group of this node taken by a bound executor.
+#[derive(Debug, Default)]
+pub struct Placement {
+    executor_id: String,
+    /// The NUMA node to bind the CPUs and the memory of the instance to.
+    pub numa_node: Option<u32>,
+    /// The GPUs visible to the instance; empty if not placed in a GPU group.
+    pub gpus: Vec<u32>,
+}
+
+impl Placement {
+    /// The `CUDA_VISIBLE_DEVICES` of the instance, if placed in a GPU group.
+    pub fn visible_devices(&self) -> Option<String> {
+        if self.gpus.is_empty() {
+            return None;
+        }
+
+        let gpus: Vec<String> = self.gpus.iter().map(|gpu| gpu.to_string()).collect();
+        Some(gpus.join(","))
+    }
+}
+
+impl Drop for Placement {
+    fn drop(&mut self) {
+        match lock_placements() {
+            Ok(mut placements) => placements.release(&self.executor_id),
+            Err(e) => tracing::warn!(
+                "Failed to release the placement of executor <{}>: {e}",
+                self.executor_id
+            ),
+        }
+    }
+}
+
+/// The topology of this node, and the NUMA node or the GPU group taken by
+/// each executor.
+#[derive(Debug, Default)]
+struct Placements {
+    numa_nodes: Vec<NumaNode>,
+    gpu_groups: Vec<GpuGroup>,
+    /// Map from executor ID to its NUMA node.
+    numa: HashMap<String, u32>,
+    /// Map from executor ID to the index of its GPU group.
+    gpu: HashMap<String, usize>,
+}
+
+impl Placements {
+    fn acquire(
+        &mut self,
+        executor_id: &str,
+        policy: TopologyPolicy,
+    ) -> Result<Placement, FlameError> {
+        // The executor binding again takes a placement by its new application.
+        self.release(executor_id);
+
+        match policy {
+            TopologyPolicy::Ignore => Ok(Placement::default()),
+            TopologyPolicy::NumaAligned => {
+                let numa_node = self
+                    .numa_nodes
+                    .iter()
+                    .map(|node| node.id)
+                    .find(|id| !self.numa.values().any(|taken| taken == id))
+                    .ok_or_else(|| {
+                        FlameError::ResourceExhausted(format!(
+                            "no free NUMA node on the node: {} taken",
+                            self.numa.len()
+                        ))
+                    })?;
+                self.numa.insert(executor_id.to_string(), numa_node);
+
+                Ok(Placement {
+                    executor_id: executor_id.to_string(),
+                    numa_node: Some(numa_node),
+                    gpus: vec![],
+                })
+            }
+            TopologyPolicy::GpuAligned => {
+                let index = (0..self.gpu_groups.len())
+                    .find(|i| !self.gpu.values().any(|taken| taken == i))
+                    .ok_or_else(|| {
+                        FlameError::ResourceExhausted(format!(
+                            "no free GPU group on the node: {} taken",
+                            self.gpu.len()
+                        ))
+                    })?;
+                self.gpu.insert(executor_id.to_string(), index);
+
+                let group = &self.gpu_groups[index];
+                Ok(Placement {
+                    executor_id: executor_id.to_string(),
+                    numa_node: group.numa_node,
+                    gpus: group.gpus.clone(),
+                })
+            }
+        }
+    }
+
+    fn release(&mut self, executor_id: &str) {
+        self.numa.remove(executor_id);
+        self.gpu.remove(executor_id);
+    }
+}
+
+fn detect_numa_nodes(dir: &Path) -> Vec<NumaNode> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut numa_nodes: Vec<NumaNode> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            let cpus = fs::read_to_string(entry.path().join("cpulist")).ok()?;
+            Some(NumaNode {
+                id,
+                cpus: cpus.trim().to_string(),
+            })
+        })
+        .collect();
+    numa_nodes.sort_by_key(|node| node.id);
+
+    numa_nodes
+}
+
+/// Parses the GPU groups from the matrix of `nvidia-smi topo -m`: the GPUs
+/// connected by NVLink are grouped together, and the other GPUs are grouped by
+/// their PCIe switches, i.e. `PIX` or `PXB`.
+fn parse_gpu_topology(output: &str) -> Vec<GpuGroup> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return vec![];
+    };
+    let header: Vec<&str> = header.split('\t').map(str::trim).collect();
+    let numa_col = header.iter().position(|col| *col == "NUMA Affinity");
+
+    let mut gpus: Vec<u32> = vec![];
+    let mut links: Vec<Vec<String>> = vec![];
+    let mut numa_nodes: Vec<Option<u32>> = vec![];
+    for line in lines {
+        let cols: Vec<&str> = line.split('\t').map(str::trim).collect();
+        let Some(gpu) = cols
+            .first()
+            .and_then(|col| col.strip_prefix("GPU"))
+            .and_then(|id| id.parse().ok())
+        else {
+            // The rows of the GPUs are followed by the NICs and the legend.
+            if !gpus.is_empty() {
+                break;
+            }
+            continue;
+        };
+
+        gpus.push(gpu);
+        links.push(
+            header
+                .iter()
+                .enumerate()
+                .filter(|(_, col)| col.starts_with("GPU"))
+                .map(|(i, _)| cols.get(i).unwrap_or(&"").to_string())
+                .collect(),
+        );
+        numa_nodes.push(
+            numa_col
+                .and_then(|i| cols.get(i))
+                .and_then(|col| col.parse().ok()),
+        );
+    }
+
+    // The group of each GPU, merged by their links.
+    let mut groups: Vec<usize> = (0..gpus.len()).collect();
+    let mut nvlinked = vec![false; gpus.len()];
+    for (i, row) in links.iter().enumerate() {
+        for (j, link) in row.iter().enumerate().take(gpus.len()) {
+            if i != j && link.starts_with("NV") {
+                nvlinked[i] = true;
+                nvlinked[j] = true;
+                merge(&mut groups, i, j);
+            }
+        }
+    }
+    for (i, row) in links.iter().enumerate() {
+        for (j, link) in row.iter().enumerate().take(gpus.len()) {
+            if i != j && !nvlinked[i] && !nvlinked[j] && (link == "PIX" || link == "PXB") {
+                merge(&mut groups, i, j);
+            }
+        }
+    }
+
+    let mut gpu_groups: Vec<GpuGroup> = vec![];
+    let mut indexes: HashMap<usize, usize> = HashMap::new();
+    for (i, group) in groups.iter().enumerate() {
+        let index = *indexes.entry(*group).or_insert_with(|| {
+            gpu_groups.push(GpuGroup {
+                gpus: vec![],
+                numa_node: numa_nodes[i],
+                link: if nvlinked[i] {
+                    GpuLink::NvLink
+                } else {
+                    GpuLink::Pcie
+                },
+            });
+            gpu_groups.len() - 1
+        });
+        gpu_groups[index].gpus.push(gpus[i]);
+    }
+
+    gpu_groups
+}
+
+fn merge(groups: &mut [usize], i: usize, j: usize) {
+    let (from, to) = (groups[j], groups[i]);
+    for group in groups.iter_mut() {
+        if *group == from {
+            *group = to;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpu_topology() {
+        let output =
+            "\tGPU0\tGPU1\tGPU2\tGPU3\tGPU4\tNIC0\tCPU Affinity\tNUMA Affinity\tGPU NUMA ID\n\
+            GPU0\t X \tNV12\tSYS\tSYS\tSYS\tPXB\t0-31\t0\t\tN/A\n\
+            GPU1\tNV12\t X \tSYS\tSYS\tSYS\tPXB\t0-31\t0\t\tN/A\n\
+            GPU2\tSYS\tSYS\t X \tPIX\tSYS\tSYS\t32-63\t1\t\tN/A\n\
+            GPU3\tSYS\tSYS\tPIX\t X \tSYS\tSYS\t32-63\t1\t\tN/A\n\
+            GPU4\tSYS\tSYS\tSYS\tSYS\t X \tSYS\t32-63\tN/A\t\tN/A\n\
+            NIC0\tPXB\tPXB\tSYS\tSYS\tSYS\t X \n\
+            \n\
+            Legend:\n\
+            \n\
+              X    = Self\n";
+
+        assert_eq!(
+            parse_gpu_topology(output),
+            vec![
+                GpuGroup {
+                    gpus: vec![0, 1],
+                    numa_node: Some(0),
+                    link: GpuLink::NvLink,
+                },
+                GpuGroup {
+                    gpus: vec![2, 3],
+                    numa_node: Some(1),
+                    link: GpuLink::Pcie,
+                },
+                GpuGroup {
+                    gpus: vec![4],
+                    numa_node: None,
+                    link: GpuLink::Pcie,
+                },
+            ]
+        );
+
+        assert!(parse_gpu_topology("").is_empty());
+    }
+
+    #[test]
+    fn test_placements() {
+        let mut placements = Placements {
+            numa_nodes: vec![
+                NumaNode {
+                    id: 0,
+                    cpus: "0-31".to_string(),
+                },
+                NumaNode {
+                    id: 1,
+                    cpus: "32-63".to_string(),
+                },
+            ],
+            gpu_groups: vec![GpuGroup {
+                gpus: vec![0, 1],
+                numa_node: Some(0),
+                link: GpuLink::NvLink,
+            }],
+            ..Default::default()
+        };
+
+        let p1 = placements
+            .acquire("exe-1", TopologyPolicy::NumaAligned)
+            .unwrap();
+        let p2 = placements
+            .acquire("exe-2", TopologyPolicy::NumaAligned)
+            .unwrap();
+        assert_eq!((p1.numa_node, p2.numa_node), (Some(0), Some(1)));
+        let res = placements.acquire("exe-3", TopologyPolicy::NumaAligned);
+        assert!(matches!(res, Err(FlameError::ResourceExhausted(_))));
+
+        // The GPU groups are taken independently of the NUMA nodes.
+        let p3 = placements
+            .acquire("exe-3", TopologyPolicy::GpuAligned)
+            .unwrap();
+        assert_eq!(p3.visible_devices(), Some("0,1".to_string()));
+        assert_eq!(p3.numa_node, Some(0));
+        let res = placements.acquire("exe-4", TopologyPolicy::GpuAligned);
+        assert!(matches!(res, Err(FlameError::ResourceExhausted(_))));
+
+        placements.release("exe-1");
+        let p4 = placements
+            .acquire("exe-4", TopologyPolicy::NumaAligned)
+            .unwrap();
+        assert_eq!(p4.numa_node, Some(0));
+    }
+}
@@ -0,0 +1,142 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The resource usage of the instances per task.
+//!
+//! The counters of the instance process are sampled from `/proc` before and
+//! after each task invocation, and their deltas are attached to the result of
+//! the task. The CPU time includes the children waited by the instance; the
+//! peak RSS is reset before the task, so it's the peak of the task only. The
+//! usage is not measured on the platforms without `/proc`.
+
+use std::fs;
+
+use common::apis::TaskUsage;
+
+/// The clock ticks per second of the CPU times in `/proc`, i.e. `USER_HZ`,
+/// which is 100 on all the supported platforms.
+const USER_HZ: u64 = 100;
+
+/// The counters of the instance process before a task.
+#[derive(Clone, Debug)]
+pub struct UsageSample {
+    pid: u32,
+    cpu_ticks: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+impl UsageSample {
+    /// Samples the process before the task, and resets its peak RSS; returns
+    /// None if the process can not be sampled.
+    pub fn start(pid: u32) -> Option<Self> {
+        let (read_bytes, write_bytes) = read_io(pid).unwrap_or_default();
+        let sample = Self {
+            pid,
+            cpu_ticks: read_cpu_ticks(pid)?,
+            read_bytes,
+            write_bytes,
+        };
+
+        // Reset the peak RSS, i.e. VmHWM, to the current RSS; the peak is the
+        // one of the process's lifetime if the kernel doesn't support it.
+        if let Err(e) = fs::write(format!("/proc/{pid}/clear_refs"), "5") {
+            tracing::debug!("Failed to reset the peak RSS of <{pid}>: {e}");
+        }
+
+        Some(sample)
+    }
+
+    /// The usage of the process since the sample, or None if it's gone, e.g.
+    /// crashed during the task.
+    pub fn usage(&self) -> Option<TaskUsage> {
+        let cpu_ticks = read_cpu_ticks(self.pid)?;
+        let (read_bytes, write_bytes) = read_io(self.pid).unwrap_or_default();
+
+        Some(TaskUsage {
+            cpu_time_ms: cpu_ticks.saturating_sub(self.cpu_ticks) * 1000 / USER_HZ,
+            max_rss_bytes: read_max_rss(self.pid).unwrap_or_default(),
+            read_bytes: read_bytes.saturating_sub(self.read_bytes),
+            write_bytes: write_bytes.saturating_sub(self.write_bytes),
+        })
+    }
+}
+
+fn read_cpu_ticks(pid: u32) -> Option<u64> {
+    parse_cpu_ticks(&fs::read_to_string(format!("/proc/{pid}/stat")).ok()?)
+}
+
+fn read_max_rss(pid: u32) -> Option<u64> {
+    parse_max_rss(&fs::read_to_string(format!("/proc/{pid}/status")).ok()?)
+}
+
+fn read_io(pid: u32) -> Option<(u64, u64)> {
+    parse_io(&fs::read_to_string(format!("/proc/{pid}/io")).ok()?)
+}
+
+/// The user and system ticks of the process and its waited children, i.e.
+/// `utime`, `stime`, `cutime` and `cstime` of `/proc/<pid>/stat`.
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    // The command may contain spaces and parentheses, so the fields are the
+    // ones after its last `)`, starting from the state, i.e. the 3rd field.
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    fields
+        .get(11..15)?
+        .iter()
+        .map(|f| f.parse::<u64>().ok())
+        .sum()
+}
+
+/// The peak RSS in bytes, i.e. `VmHWM` of `/proc/<pid>/status`.
+fn parse_max_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
+/// The bytes read from and written to the storage, i.e. `read_bytes` and
+/// `write_bytes` of `/proc/<pid>/io`.
+fn parse_io(io: &str) -> Option<(u64, u64)> {
+    let value = |name: &str| {
+        io.lines()
+            .find_map(|l| l.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    Some((value("read_bytes")?, value("write_bytes")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc() {
+        let stat = "1234 (flame (svc) x) S 1 1234 1234 0 -1 4194560 100 0 0 0 250 50 7 3 20 0 4 0";
+        assert_eq!(parse_cpu_ticks(stat), Some(310));
+        assert_eq!(parse_cpu_ticks("1234 (svc) S 1"), None);
+
+        let status = "Name:\tsvc\nVmPeak:\t  20000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+        assert_eq!(parse_max_rss(status), Some(2048 * 1024));
+
+        let io = "rchar: 100\nwchar: 200\nsyscr: 1\nsyscw: 2\nread_bytes: 4096\nwrite_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io(io), Some((4096, 8192)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sample_usage() {
+        let sample = UsageSample::start(std::process::id()).unwrap();
+        let usage = sample.usage().unwrap();
+        assert!(usage.max_rss_bytes > 0);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::managers::{
+    build::BuildManager, bundle::BundleManager, installation::InstallationManager,
+    source::SourceManager,
+};
+use crate::types::{BuildArtifacts, BundleConfig};
+use anyhow::{Context, Result};
+
+pub fn run(config: BundleConfig) -> Result<()> {
+    println!("📦 Flame Bundle");
+    println!();
+
+    // Phase 1: Validation
+    println!("═══ Phase 1: Validation ═══");
+    let uv = InstallationManager::new().find_uv_executable().context(
+        "uv not found in system (required to bundle the Python SDK). Please install uv first:\n\
+         1. curl -LsSf https://astral.sh/uv/install.sh | sh\n\
+         2. Or install via your package manager",
+    )?;
+    println!("✓ Found uv at: {}", uv.display());
+
+    // Phase 2: Preparation
+    println!("\n═══ Phase 2: Preparation ═══");
+    let mut source_manager = SourceManager::new();
+    let src_dir = source_manager.prepare_source(config.src_dir.clone())?;
+
+    // Phase 3: Build (skip if requested)
+    let artifacts = if !config.skip_build {
+        println!("\n═══ Phase 3: Build ═══");
+        let build_manager = BuildManager::new(config.verbose);
+        build_manager.check_prerequisites()?;
+        build_manager.build_all(&src_dir)?
+    } else {
+        println!("\n═══ Phase 3: Skipping Build (--skip-build) ═══");
+        BuildArtifacts::from_source_dir(&src_dir, "release")?
+    };
+
+    // Phase 4: Bundle
+    println!("\n═══ Phase 4: Bundle ═══");
+    let bundle_manager = BundleManager::new(config.verbose);
+    let output = bundle_manager.create(&artifacts, &src_dir, &uv, config.output.clone())?;
+
+    println!("\n✅ Created bundle: {}", output.display());
+    println!();
+    println!("To install it on a machine without internet access:");
+    println!(
+        "  sudo flmadm install --all --bundle {}",
+        output.file_name().unwrap_or_default().to_string_lossy()
+    );
+    println!();
+
+    Ok(())
+}
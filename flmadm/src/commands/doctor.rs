@@ -0,0 +1,114 @@
+use crate::types::{DoctorConfig, InstallationPaths};
+use anyhow::Result;
+use common::backup::StorageLocation;
+use common::ctx::{FlameClusterContext, FlameTls};
+use std::path::Path;
+
+pub fn run(config: DoctorConfig) -> Result<()> {
+    let paths = InstallationPaths::new(config.prefix.clone());
+    let config_file = config
+        .config
+        .clone()
+        .unwrap_or_else(|| paths.conf.join("flame-cluster.yaml"));
+
+    println!("🩺 Flame Doctor");
+    println!("   Configuration: {}", config_file.display());
+    println!();
+
+    if !config_file.is_file() {
+        anyhow::bail!(
+            "Flame configuration not found at: {}",
+            config_file.display()
+        );
+    }
+
+    // Unknown keys, mismatched types and invalid values are reported by loading
+    // the configuration, with their location in the file.
+    let ctx = match FlameClusterContext::from_file(Some(config_file.to_string_lossy().to_string()))
+    {
+        Ok(ctx) => {
+            println!("✓ Configuration is valid");
+            ctx
+        }
+        Err(e) => {
+            println!("✗ Configuration is invalid");
+            anyhow::bail!("{}", e);
+        }
+    };
+
+    let problems = diagnose(&ctx, &paths.prefix);
+    if problems.is_empty() {
+        println!("✓ All checks passed");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("✗ {}", problem);
+    }
+    anyhow::bail!("{} problems found in the configuration", problems.len())
+}
+
+/// Checks the files and directories referred by the configuration on this machine.
+fn diagnose(ctx: &FlameClusterContext, prefix: &Path) -> Vec<String> {
+    let mut problems = vec![];
+
+    if ctx.cluster.storage != "none" {
+        match StorageLocation::from_url(&ctx.cluster.storage, prefix) {
+            Ok(location) => match location.path.parent() {
+                Some(parent) if !parent.exists() => problems.push(format!(
+                    "cluster.storage: directory {} does not exist",
+                    parent.display()
+                )),
+                _ => println!(
+                    "✓ Found {} storage at: {}",
+                    location.engine,
+                    location.path.display()
+                ),
+            },
+            Err(e) => problems.push(format!("cluster.storage: {}", e)),
+        }
+    }
+
+    if let Some(tls) = &ctx.cluster.tls {
+        check_tls(&mut problems, "cluster.tls", tls);
+    }
+    if let Some(tls) = ctx.cache.as_ref().and_then(|c| c.tls.as_ref()) {
+        check_tls(&mut problems, "cache.tls", tls);
+    }
+
+    if let Some(encryption) = &ctx.cluster.encryption {
+        for key in &encryption.keys {
+            check_file(
+                &mut problems,
+                "cluster.encryption.keys.key_file",
+                &key.key_file,
+            );
+        }
+    }
+
+    match common::apis::load_applications(&ctx.paths.applications) {
+        Ok(apps) if !apps.is_empty() => println!(
+            "✓ Found {} applications in: {}",
+            apps.len(),
+            ctx.paths.applications.display()
+        ),
+        Ok(_) => {}
+        Err(e) => problems.push(format!("paths.applications_dir: {}", e)),
+    }
+
+    problems
+}
+
+fn check_tls(problems: &mut Vec<String>, section: &str, tls: &FlameTls) {
+    check_file(problems, &format!("{}.cert_file", section), &tls.cert_file);
+    check_file(problems, &format!("{}.key_file", section), &tls.key_file);
+    if let Some(ca_file) = &tls.ca_file {
+        check_file(problems, &format!("{}.ca_file", section), ca_file);
+    }
+}
+
+fn check_file(problems: &mut Vec<String>, field: &str, path: &str) {
+    if !Path::new(path).is_file() {
+        problems.push(format!("{}: {} is not a file", field, path));
+    }
+}
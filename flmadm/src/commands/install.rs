@@ -1,7 +1,7 @@
 use crate::managers::{
-    backup::BackupManager, build::BuildManager, config::ConfigGenerator,
-    installation::InstallationManager, source::SourceManager, systemd::SystemdManager,
-    user::UserManager,
+    backup::BackupManager, build::BuildManager, bundle::Bundle, bundle::BundleManager,
+    config::ConfigGenerator, installation::InstallationManager, security::SecurityFiles,
+    security::SecurityManager, source::SourceManager, systemd::SystemdManager, user::UserManager,
 };
 use crate::types::{InstallConfig, InstallationPaths};
 use anyhow::Result;
@@ -33,26 +33,39 @@ pub fn run(config: InstallConfig) -> Result<()> {
         handle_clean_install(&paths)?;
     }
 
-    let mut source_manager = SourceManager::new();
-    let src_dir = source_manager.prepare_source(config.src_dir.clone())?;
+    if let Some(bundle) = &config.bundle {
+        // Air-gapped installation: nothing is fetched or built
+        let bundle = BundleManager::new(config.verbose).extract(bundle)?;
+        println!("✓ Using Flame {} of the bundle", bundle.manifest.version);
 
-    // Phase 3: Build (skip if requested)
-    if !config.skip_build {
-        println!("\n═══ Phase 3: Build ═══");
-        let build_manager = BuildManager::new(config.verbose);
-        build_manager.check_prerequisites()?;
-        let artifacts = build_manager.build_all(&src_dir)?;
+        println!("\n═══ Phase 3: Skipping Build (--bundle) ═══");
 
         // Phase 4: Installation
         println!("\n═══ Phase 4: Installation ═══");
-        install_components(&artifacts, &src_dir, &paths, &config)?;
+        let artifacts = bundle.artifacts()?;
+        install_components(&artifacts, &bundle.root, Some(&bundle), &paths, &config)?;
     } else {
-        println!("\n═══ Phase 3: Skipping Build (--skip-build) ═══");
+        let mut source_manager = SourceManager::new();
+        let src_dir = source_manager.prepare_source(config.src_dir.clone())?;
+
+        // Phase 3: Build (skip if requested)
+        if !config.skip_build {
+            println!("\n═══ Phase 3: Build ═══");
+            let build_manager = BuildManager::new(config.verbose);
+            build_manager.check_prerequisites()?;
+            let artifacts = build_manager.build_all(&src_dir)?;
+
+            // Phase 4: Installation
+            println!("\n═══ Phase 4: Installation ═══");
+            install_components(&artifacts, &src_dir, None, &paths, &config)?;
+        } else {
+            println!("\n═══ Phase 3: Skipping Build (--skip-build) ═══");
 
-        // Phase 4: Installation
-        println!("\n═══ Phase 4: Installation ═══");
-        let artifacts = crate::types::BuildArtifacts::from_source_dir(&src_dir, "release")?;
-        install_components(&artifacts, &src_dir, &paths, &config)?;
+            // Phase 4: Installation
+            println!("\n═══ Phase 4: Installation ═══");
+            let artifacts = crate::types::BuildArtifacts::from_source_dir(&src_dir, "release")?;
+            install_components(&artifacts, &src_dir, None, &paths, &config)?;
+        }
     }
 
     // Phase 5: Systemd Setup (if requested and needed)
@@ -131,7 +144,9 @@ fn validate_config(config: &InstallConfig) -> Result<()> {
         || config
             .profiles
             .contains(&crate::types::InstallProfile::Client);
-    if needs_uv {
+    if needs_uv && config.bundle.is_some() {
+        println!("✓ Using uv of the bundle");
+    } else if needs_uv {
         match find_uv_executable() {
             Some(uv_path) => {
                 println!("✓ Found uv at: {}", uv_path.display());
@@ -147,6 +162,11 @@ fn validate_config(config: &InstallConfig) -> Result<()> {
         }
     }
 
+    // Check if openssl is available (required by the TLS certificates)
+    if !config.insecure {
+        SecurityManager::new().check_prerequisites()?;
+    }
+
     println!("✓ Configuration validated");
     Ok(())
 }
@@ -213,6 +233,7 @@ fn handle_clean_install(paths: &InstallationPaths) -> Result<()> {
 fn install_components(
     artifacts: &crate::types::BuildArtifacts,
     src_dir: &std::path::Path,
+    bundle: Option<&Bundle>,
     paths: &InstallationPaths,
     config: &InstallConfig,
 ) -> Result<()> {
@@ -229,11 +250,14 @@ fn install_components(
     )?;
 
     // Install uv (for worker and client profiles)
-    installation_manager.install_uv(paths, &config.profiles)?;
+    let uv = bundle.map(|b| b.uv());
+    installation_manager.install_uv(uv.as_deref(), paths, &config.profiles)?;
 
     // Install Python SDK
+    let wheels = bundle.map(|b| b.wheels());
     installation_manager.install_python_sdk(
         src_dir,
+        wheels.as_deref(),
         paths,
         &config.profiles,
         config.force_overwrite,
@@ -242,9 +266,30 @@ fn install_components(
     // Install database migrations
     installation_manager.install_migrations(src_dir, paths, &config.profiles)?;
 
+    // Generate the TLS certificates, the join secret and the admin token, unless
+    // insecure; the executor manager installed with the session manager is
+    // issued the credential of this node, so it joins without a join token.
+    let security = if config.insecure {
+        None
+    } else {
+        let security_manager = SecurityManager::new();
+        let files = security_manager.generate(paths)?;
+        let has_control_plane = config
+            .profiles
+            .contains(&crate::types::InstallProfile::ControlPlane);
+        let has_worker = config
+            .profiles
+            .contains(&crate::types::InstallProfile::Worker);
+        if has_control_plane && has_worker {
+            security_manager.issue_node_token(&files, &paths.work)?;
+        }
+        Some(files)
+    };
+
     // Generate configuration
     let config_generator = ConfigGenerator::new();
-    config_generator.generate_config(&paths.prefix)?;
+    config_generator.generate_config(&paths.prefix, security.as_ref())?;
+    config_generator.generate_client_config(&paths.prefix, security.as_ref())?;
 
     Ok(())
 }
@@ -252,8 +297,32 @@ fn install_components(
 fn setup_systemd(paths: &InstallationPaths, config: &InstallConfig) -> Result<()> {
     let systemd_manager = SystemdManager::new();
 
+    // The services run as a dedicated unprivileged user, which owns the
+    // directories they write; the binaries and configuration stay owned by root,
+    // except the server key, the join secret and the admin token read by the
+    // services.
+    let user_manager = UserManager::new();
+    user_manager.ensure_system_user(&config.user, &paths.prefix)?;
+    user_manager.chown_dirs(
+        &config.user,
+        &[
+            &paths.work,
+            &paths.events,
+            &paths.run,
+            &paths.logs,
+            &paths.data,
+        ],
+    )?;
+    if !config.insecure {
+        let files = SecurityFiles::new(&paths.conf);
+        user_manager.chown_files(
+            &config.user,
+            &[&files.key_file, &files.secret_file, &files.admin_token_file],
+        )?;
+    }
+
     // Install service files
-    systemd_manager.install_services(&paths.prefix, &config.profiles)?;
+    systemd_manager.install_services(&paths.prefix, &config.profiles, &config.user)?;
 
     // Enable and start services if requested
     if config.enable {
@@ -306,6 +375,24 @@ fn print_summary(paths: &InstallationPaths, config: &InstallConfig) {
         "  • Configuration: {}",
         paths.conf.join("flame-cluster.yaml").display()
     );
+    println!(
+        "  • Client configuration: {}",
+        paths.conf.join("flame.yaml").display()
+    );
+    if config.insecure {
+        println!("  • Security: disabled (--insecure), the services listen without TLS");
+    } else {
+        let files = SecurityFiles::new(&paths.conf);
+        println!(
+            "  • TLS certificates: {}",
+            files.cert_file.parent().unwrap().display()
+        );
+        println!("  • Join secret: {}", files.secret_file.display());
+        println!(
+            "  • Admin token: {} (required by flmctl join-token, enable, disable and debug)",
+            files.admin_token_file.display()
+        );
+    }
 
     // Only show SDK path if it was installed
     let has_flamepy = config
@@ -386,11 +473,15 @@ fn print_summary(paths: &InstallationPaths, config: &InstallConfig) {
         paths.prefix.display()
     );
     println!("  2. Add {}/bin to your PATH", paths.bin.display());
+    println!(
+        "  3. Configure the clients: mkdir -p ~/.flame && cp {} ~/.flame/flame.yaml",
+        paths.conf.join("flame.yaml").display()
+    );
 
     // Provide relevant test command based on what was installed
     if has_control_plane {
         println!(
-            "  3. Test the installation: {}/bin/flmctl --version",
+            "  4. Test the installation: {}/bin/flmctl --version",
             paths.bin.display()
         );
     } else if config
@@ -398,9 +489,21 @@ fn print_summary(paths: &InstallationPaths, config: &InstallConfig) {
         .contains(&crate::types::InstallProfile::Client)
     {
         println!(
-            "  3. Test the installation: {}/bin/flmping --version",
+            "  4. Test the installation: {}/bin/flmping --version",
             paths.bin.display()
         );
     }
+
+    // The worker of a remote control plane trusts its CA and joins by a join token
+    if !config.insecure && has_worker && !has_control_plane {
+        println!();
+        println!("To join the cluster of a remote control plane:");
+        println!(
+            "  • Copy the CA certificate of the control plane to {}",
+            SecurityFiles::new(&paths.conf).ca_file.display()
+        );
+        println!("  • Mint a join token on the control plane: flmctl join-token");
+        println!("  • Pass it to the executor manager by FLAME_JOIN_TOKEN");
+    }
     println!();
 }
@@ -1,2 +1,5 @@
+pub mod bundle;
+pub mod doctor;
 pub mod install;
+pub mod restore;
 pub mod uninstall;
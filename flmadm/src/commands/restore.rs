@@ -0,0 +1,196 @@
+use crate::managers::systemd::SystemdManager;
+use crate::types::{InstallationPaths, RestoreConfig};
+use anyhow::{Context, Result};
+use common::backup::{self, BackupEngine, BackupManifest, StorageLocation, BACKUP_DATA};
+use common::ctx::FlameClusterContext;
+use dialoguer::Confirm;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn run(config: RestoreConfig) -> Result<()> {
+    println!("♻️  Flame Restore");
+    println!("   Target: {}", config.prefix.display());
+    println!("   Backup: {}", config.backup.display());
+    println!();
+
+    // Phase 1: Validation
+    println!("═══ Phase 1: Validation ═══");
+    let (backup_dir, manifest, location) = validate_and_confirm(&config)?;
+
+    // Phase 2: Stop Services
+    println!("\n═══ Phase 2: Stop Services ═══");
+    stop_services()?;
+
+    // Phase 3: Replay Backup
+    println!("\n═══ Phase 3: Replay Backup ═══");
+    replay_backup(&backup_dir.join(BACKUP_DATA), &location)?;
+
+    // Phase 4: Summary
+    println!("\n═══ Restore Complete ═══");
+    print_summary(&backup_dir, &manifest, &location);
+
+    Ok(())
+}
+
+fn validate_and_confirm(
+    config: &RestoreConfig,
+) -> Result<(PathBuf, BackupManifest, StorageLocation)> {
+    if !config.prefix.is_absolute() {
+        anyhow::bail!("Installation prefix must be an absolute path");
+    }
+
+    let paths = InstallationPaths::new(config.prefix.clone());
+    let config_file = paths.conf.join("flame-cluster.yaml");
+    if !config_file.is_file() {
+        anyhow::bail!(
+            "Flame configuration not found at: {}\n  Install Flame with `flmadm install` before restoring.",
+            config_file.display()
+        );
+    }
+
+    let ctx = FlameClusterContext::from_file(Some(config_file.to_string_lossy().to_string()))
+        .context("Failed to load cluster configuration")?;
+    let location = StorageLocation::from_url(&ctx.cluster.storage, &paths.prefix)?;
+    println!(
+        "✓ Found {} storage at: {}",
+        location.engine,
+        location.path.display()
+    );
+
+    let (backup_dir, manifest) = backup::find_backup(&config.backup, config.before)?;
+    manifest.validate()?;
+    if manifest.engine != location.engine {
+        anyhow::bail!(
+            "Backup of {} storage can not be restored into {} storage",
+            manifest.engine,
+            location.engine
+        );
+    }
+    if !backup_dir.join(BACKUP_DATA).exists() {
+        anyhow::bail!("Backup data not found in: {}", backup_dir.display());
+    }
+    println!(
+        "✓ Found backup of version {} taken at {}: {}",
+        manifest.version,
+        manifest.creation_time,
+        backup_dir.display()
+    );
+
+    if has_data(&location)? {
+        if !config.force {
+            anyhow::bail!(
+                "Storage is not empty: {}\n  Restore into a fresh installation, or use --force to overwrite it.",
+                location.path.display()
+            );
+        }
+        println!("\n⚠️  WARNING: The existing storage will be overwritten (--force)");
+    }
+
+    // Confirm with user (unless --force)
+    if !config.force {
+        println!();
+        let confirmed = Confirm::new()
+            .with_prompt("Proceed with restore?")
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            println!("Restore cancelled.");
+            std::process::exit(0);
+        }
+    }
+
+    Ok((backup_dir, manifest, location))
+}
+
+/// Whether the storage has any data, e.g. it has been used before.
+fn has_data(location: &StorageLocation) -> Result<bool> {
+    match location.engine {
+        BackupEngine::Sqlite => Ok(location.path.exists()),
+        BackupEngine::Filesystem => {
+            for dir in ["applications", "sessions", "nodes"] {
+                let path = location.path.join(dir);
+                if path.is_dir() && fs::read_dir(&path)?.next().is_some() {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+    }
+}
+
+fn stop_services() -> Result<()> {
+    let fsm_service = Path::new("/etc/systemd/system/flame-session-manager.service");
+
+    if fsm_service.exists() {
+        println!("🛑 Stopping flame-session-manager...");
+        SystemdManager::new().stop_service("flame-session-manager")?;
+    } else {
+        println!("ℹ️  No systemd services found");
+    }
+
+    Ok(())
+}
+
+fn replay_backup(data: &Path, location: &StorageLocation) -> Result<()> {
+    match location.engine {
+        BackupEngine::Sqlite => {
+            // Remove the journals of the old database, which do not match the restored one.
+            for suffix in ["", "-wal", "-shm"] {
+                let path = PathBuf::from(format!("{}{}", location.path.display(), suffix));
+                if path.exists() {
+                    fs::remove_file(&path)
+                        .context(format!("Failed to remove {}", path.display()))?;
+                }
+            }
+            if let Some(parent) = location.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(data, &location.path).context(format!(
+                "Failed to copy {} to {}",
+                data.display(),
+                location.path.display()
+            ))?;
+        }
+        BackupEngine::Filesystem => {
+            for dir in ["applications", "sessions", "nodes"] {
+                let path = location.path.join(dir);
+                if path.exists() {
+                    fs::remove_dir_all(&path)
+                        .context(format!("Failed to remove {}", path.display()))?;
+                }
+            }
+            fs::create_dir_all(&location.path)?;
+
+            let mut options = fs_extra::dir::CopyOptions::new();
+            options.content_only = true;
+            options.overwrite = true;
+            fs_extra::dir::copy(data, &location.path, &options).context(format!(
+                "Failed to copy {} to {}",
+                data.display(),
+                location.path.display()
+            ))?;
+        }
+    }
+
+    println!("  ✓ Restored {} storage", location.engine);
+    Ok(())
+}
+
+fn print_summary(backup_dir: &Path, manifest: &BackupManifest, location: &StorageLocation) {
+    println!("\n✅ Flame storage has been restored!");
+    println!();
+    println!("Backup:");
+    println!("  {}", backup_dir.display());
+    println!(
+        "  Taken at {} by version {}",
+        manifest.creation_time, manifest.version
+    );
+    println!();
+    println!("Storage:");
+    println!("  {}", location.path.display());
+    println!();
+    println!("To start the session manager:");
+    println!("  sudo systemctl start flame-session-manager");
+    println!();
+}
@@ -40,6 +40,10 @@ enum Commands {
         #[arg(long, value_name = "PATH")]
         src_dir: Option<PathBuf>,
 
+        /// Install from an offline bundle created by `flmadm bundle`, without fetching or building
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["src_dir", "skip_build"])]
+        bundle: Option<PathBuf>,
+
         /// Target installation directory
         #[arg(long, default_value = "/usr/local/flame", value_name = "PATH")]
         prefix: PathBuf,
@@ -83,6 +87,33 @@ enum Commands {
         /// Show detailed build output
         #[arg(long)]
         verbose: bool,
+
+        /// System user the services run as; created if it does not exist
+        #[arg(long, default_value = types::DEFAULT_SERVICE_USER, value_name = "NAME")]
+        user: String,
+
+        /// Install without TLS and the secure join of the nodes, e.g. for local development
+        #[arg(long)]
+        insecure: bool,
+    },
+
+    /// Create an offline bundle of Flame for air-gapped installations
+    Bundle {
+        /// Source code directory for building Flame
+        #[arg(long, value_name = "PATH")]
+        src_dir: Option<PathBuf>,
+
+        /// Bundle to create (default: flame-<version>-<os>-<arch>.tar.gz)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Skip building from source (use pre-built binaries)
+        #[arg(long)]
+        skip_build: bool,
+
+        /// Show detailed build output
+        #[arg(long)]
+        verbose: bool,
     },
 
     /// Uninstall Flame from this machine
@@ -115,6 +146,34 @@ enum Commands {
         #[arg(long)]
         force: bool,
     },
+    /// Restore the storage of a fresh installation from a backup
+    Restore {
+        /// Backup to restore, or a directory of backups to restore the latest one
+        #[arg(long, value_name = "PATH")]
+        backup: PathBuf,
+
+        /// Installation directory to restore into
+        #[arg(long, default_value = "/usr/local/flame", value_name = "PATH")]
+        prefix: PathBuf,
+
+        /// Restore the latest backup taken no later than this time (RFC 3339)
+        #[arg(long, value_name = "TIME", value_parser = parse_time)]
+        before: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// Overwrite the existing storage and skip confirmation prompts
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check the configuration of the installation, e.g. unknown keys and missing files
+    Doctor {
+        /// Installation directory to check
+        #[arg(long, default_value = "/usr/local/flame", value_name = "PATH")]
+        prefix: PathBuf,
+
+        /// Configuration file to check instead of the installed one
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+    },
     /// Generate shell completion scripts
     Completion {
         /// Shell to generate completions for
@@ -123,9 +182,16 @@ enum Commands {
     },
 }
 
+#[cfg(unix)]
+fn parse_time(s: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .map_err(|e| format!("invalid time <{s}>: {e}"))
+}
+
 #[cfg(unix)]
 fn main() {
-    let _log_guard = match common::init_logger(None) {
+    let _log_guard = match common::init_logger(None, None) {
         Ok(guard) => guard,
         Err(e) => {
             eprintln!("Failed to initialize logger: {}", e);
@@ -138,6 +204,7 @@ fn main() {
     let result = match cli.command {
         Commands::Install {
             src_dir,
+            bundle,
             prefix,
             control_plane,
             worker,
@@ -149,6 +216,8 @@ fn main() {
             clean,
             force,
             verbose,
+            user,
+            insecure,
         } => {
             // Validate profile flags
             if all && (control_plane || worker || client) {
@@ -205,9 +274,23 @@ fn main() {
                 verbose,
                 profiles,
                 force_overwrite: force,
+                user,
+                bundle,
+                insecure,
             };
             commands::install::run(config)
         }
+        Commands::Bundle {
+            src_dir,
+            output,
+            skip_build,
+            verbose,
+        } => commands::bundle::run(types::BundleConfig {
+            src_dir,
+            output,
+            skip_build,
+            verbose,
+        }),
         Commands::Uninstall {
             prefix,
             preserve_data,
@@ -228,6 +311,23 @@ fn main() {
             };
             commands::uninstall::run(config)
         }
+        Commands::Restore {
+            backup,
+            prefix,
+            before,
+            force,
+        } => {
+            let config = types::RestoreConfig {
+                prefix,
+                backup,
+                before,
+                force,
+            };
+            commands::restore::run(config)
+        }
+        Commands::Doctor { prefix, config } => {
+            commands::doctor::run(types::DoctorConfig { prefix, config })
+        }
         Commands::Completion { shell } => {
             generate(shell, &mut Cli::command(), "flmadm", &mut io::stdout());
             Ok(())
@@ -0,0 +1,345 @@
+use crate::managers::installation::InstallationManager;
+use crate::types::BuildArtifacts;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use ring::digest;
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// The manifest of a bundle, at its root.
+pub const BUNDLE_MANIFEST: &str = "manifest.yaml";
+/// The checksums of the files of a bundle, in the format of `sha256sum`.
+pub const BUNDLE_CHECKSUMS: &str = "SHA256SUMS";
+
+/// The manifest of an offline bundle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// The version of Flame in the bundle.
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub creation_time: DateTime<Utc>,
+}
+
+impl BundleManifest {
+    /// The default file name of the bundle.
+    pub fn file_name(&self) -> String {
+        format!("flame-{}-{}-{}.tar.gz", self.version, self.os, self.arch)
+    }
+
+    /// Check whether the bundle can be installed on this machine.
+    pub fn validate(&self) -> Result<()> {
+        if self.os != std::env::consts::OS || self.arch != std::env::consts::ARCH {
+            anyhow::bail!(
+                "Bundle is built for {}/{}, but this machine is {}/{}",
+                self.os,
+                self.arch,
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// An extracted bundle; its layout mirrors the source tree for the Python SDK
+/// and the migrations, so it can be installed as a source directory.
+pub struct Bundle {
+    pub root: PathBuf,
+    pub manifest: BundleManifest,
+    _temp_dir: tempfile::TempDir,
+}
+
+impl Bundle {
+    pub fn artifacts(&self) -> Result<BuildArtifacts> {
+        BuildArtifacts::from_dir(&self.root.join("bin"))
+    }
+
+    pub fn uv(&self) -> PathBuf {
+        self.root.join("bin/uv")
+    }
+
+    pub fn wheels(&self) -> PathBuf {
+        self.root.join("wheels")
+    }
+}
+
+pub struct BundleManager {
+    verbose: bool,
+}
+
+impl BundleManager {
+    pub fn new(verbose: bool) -> Self {
+        Self { verbose }
+    }
+
+    /// Create a bundle of the built binaries, uv, the wheels of the Python SDK
+    /// and its dependencies, and the migrations
+    pub fn create(
+        &self,
+        artifacts: &BuildArtifacts,
+        src_dir: &Path,
+        uv: &Path,
+        output: Option<PathBuf>,
+    ) -> Result<PathBuf> {
+        which::which("tar").context("tar command not found. Please install tar")?;
+
+        let manifest = BundleManifest {
+            version: self.flame_version(&artifacts.flmctl)?,
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            creation_time: Utc::now(),
+        };
+        let output = output.unwrap_or_else(|| PathBuf::from(manifest.file_name()));
+
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        let name = format!("flame-{}", manifest.version);
+        let root = temp_dir.path().join(&name);
+
+        println!("📦 Staging binaries...");
+        let bin = root.join("bin");
+        fs::create_dir_all(&bin).context("Failed to create bin directory")?;
+        for (name, src) in artifacts.all().into_iter().chain([("uv", uv)]) {
+            let dst = bin.join(name);
+            fs::copy(src, &dst).context(format!("Failed to copy {} binary", name))?;
+            fs::set_permissions(&dst, fs::Permissions::from_mode(0o755))
+                .context(format!("Failed to set permissions on {}", name))?;
+            println!("  ✓ Staged {}", name);
+        }
+
+        println!("🐍 Staging Python SDK and wheels...");
+        let sdk = root.join("sdk/python");
+        InstallationManager::new()
+            .copy_sdk_excluding_artifacts(&src_dir.join("sdk/python"), &sdk)
+            .context("Failed to copy Python SDK")?;
+        self.download_wheels(uv, &sdk, &root.join("wheels"))?;
+
+        println!("🗄️  Staging database migrations...");
+        let migrations_src = src_dir.join("session_manager/migrations/sqlite");
+        let migrations = root.join("session_manager/migrations/sqlite");
+        fs::create_dir_all(&migrations).context("Failed to create migrations directory")?;
+        for entry in fs::read_dir(&migrations_src).context(format!(
+            "Failed to read migrations at: {:?}",
+            migrations_src
+        ))? {
+            let path = entry?.path();
+            if path.is_file() {
+                fs::copy(&path, migrations.join(path.file_name().unwrap()))
+                    .context(format!("Failed to copy migration: {:?}", path))?;
+            }
+        }
+
+        let contents = serde_yaml::to_string(&manifest)?;
+        fs::write(root.join(BUNDLE_MANIFEST), contents).context("Failed to write manifest")?;
+        self.write_checksums(&root)?;
+
+        println!("🗜️  Creating {}...", output.display());
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&output)
+            .arg("-C")
+            .arg(temp_dir.path())
+            .arg(&name)
+            .status()
+            .context("Failed to execute tar")?;
+        if !status.success() {
+            anyhow::bail!("Failed to create bundle: {}", output.display());
+        }
+
+        Ok(output)
+    }
+
+    /// Extract a bundle and verify its checksums
+    pub fn extract(&self, bundle: &Path) -> Result<Bundle> {
+        which::which("tar").context("tar command not found. Please install tar")?;
+
+        if !bundle.is_file() {
+            anyhow::bail!("Bundle does not exist: {:?}", bundle);
+        }
+
+        println!("📦 Extracting bundle: {}", bundle.display());
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        let output = Command::new("tar")
+            .arg("-xzf")
+            .arg(bundle)
+            .arg("-C")
+            .arg(temp_dir.path())
+            .output()
+            .context("Failed to execute tar")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to extract bundle: {}", stderr);
+        }
+
+        // The bundle has a single top-level directory
+        let mut entries = fs::read_dir(temp_dir.path())?.collect::<Result<Vec<_>, _>>()?;
+        let root = match entries.pop() {
+            Some(entry) if entries.is_empty() && entry.path().is_dir() => entry.path(),
+            _ => anyhow::bail!("Not a valid Flame bundle: {:?}", bundle),
+        };
+
+        let manifest_path = root.join(BUNDLE_MANIFEST);
+        let contents = fs::read_to_string(&manifest_path)
+            .context(format!("Failed to read manifest: {:?}", manifest_path))?;
+        let manifest: BundleManifest = serde_yaml::from_str(&contents)
+            .context(format!("Invalid manifest: {:?}", manifest_path))?;
+        manifest.validate()?;
+
+        let count = self.verify_checksums(&root)?;
+        println!("✓ Verified checksums of {} files", count);
+
+        Ok(Bundle {
+            root,
+            manifest,
+            _temp_dir: temp_dir,
+        })
+    }
+
+    /// Get the version of Flame from the built flmctl, e.g. `flmctl 0.5.0`
+    fn flame_version(&self, flmctl: &Path) -> Result<String> {
+        let output = Command::new(flmctl)
+            .arg("--version")
+            .output()
+            .context("Failed to execute flmctl --version")?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        match stdout.split_whitespace().last() {
+            Some(version) if output.status.success() => Ok(version.to_string()),
+            _ => anyhow::bail!("Failed to get the version of Flame from {:?}", flmctl),
+        }
+    }
+
+    /// Build the wheel of the Python SDK, and download the wheels of its
+    /// dependencies, so they can be installed without an index
+    fn download_wheels(&self, uv: &Path, sdk: &Path, wheels: &Path) -> Result<()> {
+        fs::create_dir_all(wheels).context("Failed to create wheels directory")?;
+
+        let output = Command::new(uv)
+            .arg("build")
+            .arg("--wheel")
+            .arg("--out-dir")
+            .arg(wheels)
+            .arg(sdk)
+            .output()
+            .context("Failed to execute uv build")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to build wheel: {}", stderr);
+        }
+        println!("  ✓ Built flamepy wheel");
+
+        // uv can't download wheels, so use pip in an ephemeral environment;
+        // pip is also bundled as it's used by flmrun for user packages
+        let output = Command::new(uv)
+            .arg("run")
+            .arg("--no-project")
+            .arg("--with")
+            .arg("pip")
+            .arg("python")
+            .arg("-m")
+            .arg("pip")
+            .arg("download")
+            .arg("--dest")
+            .arg(wheels)
+            .arg("--find-links")
+            .arg(wheels)
+            .arg("flamepy")
+            .arg("pip")
+            .output()
+            .context("Failed to execute pip download")?;
+        if self.verbose {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+        }
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to download wheels: {}", stderr);
+        }
+        println!("  ✓ Downloaded wheels of dependencies");
+
+        Ok(())
+    }
+
+    fn write_checksums(&self, root: &Path) -> Result<()> {
+        let mut checksums = String::new();
+        for entry in WalkDir::new(root).sort_by_file_name() {
+            let entry = entry.context("Failed to read directory entry")?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(root)?;
+            checksums.push_str(&format!(
+                "{}  {}\n",
+                sha256_file(entry.path())?,
+                relative.display()
+            ));
+        }
+
+        fs::write(root.join(BUNDLE_CHECKSUMS), checksums).context("Failed to write checksums")?;
+        Ok(())
+    }
+
+    /// Verify the files of the bundle against its checksums; every file
+    /// except the checksums themselves must be listed.
+    fn verify_checksums(&self, root: &Path) -> Result<usize> {
+        let path = root.join(BUNDLE_CHECKSUMS);
+        let contents =
+            fs::read_to_string(&path).context(format!("Failed to read checksums: {:?}", path))?;
+
+        let mut listed = Vec::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            let (expected, file) = line
+                .split_once("  ")
+                .context(format!("Invalid checksum line: {}", line))?;
+            let file = Path::new(file);
+            if !file.components().all(|c| matches!(c, Component::Normal(_))) {
+                anyhow::bail!("Invalid file in checksums: {:?}", file);
+            }
+
+            let actual = sha256_file(&root.join(file))
+                .context(format!("Failed to read bundled file: {:?}", file))?;
+            if actual != expected {
+                anyhow::bail!("Checksum mismatch of {:?}, the bundle is corrupted", file);
+            }
+            listed.push(file.to_path_buf());
+        }
+
+        for entry in WalkDir::new(root) {
+            let entry = entry.context("Failed to read directory entry")?;
+            let relative = entry.path().strip_prefix(root)?;
+            if entry.file_type().is_dir() || relative == Path::new(BUNDLE_CHECKSUMS) {
+                continue;
+            }
+            if !listed.iter().any(|f| f == relative) {
+                anyhow::bail!("File {:?} is not in the checksums of the bundle", relative);
+            }
+        }
+
+        Ok(listed.len())
+    }
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.update(&buf[..n]);
+    }
+
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
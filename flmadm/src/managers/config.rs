@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
 
+use crate::managers::security::SecurityFiles;
+
 pub struct ConfigGenerator;
 
 impl ConfigGenerator {
@@ -9,8 +11,9 @@ impl ConfigGenerator {
         Self
     }
 
-    /// Generate default flame-cluster.yaml configuration
-    pub fn generate_config(&self, prefix: &Path) -> Result<()> {
+    /// Generate default flame-cluster.yaml configuration; the services listen
+    /// by TLS and the nodes join by the join secret if the security files are set.
+    pub fn generate_config(&self, prefix: &Path, security: Option<&SecurityFiles>) -> Result<()> {
         let config_path = prefix.join("conf/flame-cluster.yaml");
 
         // Check if config already exists
@@ -25,7 +28,7 @@ impl ConfigGenerator {
         println!("📝 Generating configuration file...");
 
         let prefix_str = prefix.to_str().unwrap();
-        let config_content = self.get_config_template(prefix_str);
+        let config_content = self.get_config_template(prefix_str, security);
 
         fs::write(&config_path, config_content).context("Failed to write configuration file")?;
 
@@ -33,14 +36,79 @@ impl ConfigGenerator {
         Ok(())
     }
 
-    fn get_config_template(&self, prefix: &str) -> String {
+    /// Generate the flame.yaml of the clients, e.g. flmctl and flamepy, to
+    /// connect to this installation; it's copied to `~/.flame/flame.yaml` by the users.
+    pub fn generate_client_config(
+        &self,
+        prefix: &Path,
+        security: Option<&SecurityFiles>,
+    ) -> Result<()> {
+        let config_path = prefix.join("conf/flame.yaml");
+
+        if config_path.exists() {
+            println!(
+                "✓ Client configuration file already exists: {}",
+                config_path.display()
+            );
+            return Ok(());
+        }
+
+        let config_content = self.get_client_config_template(security);
+
+        fs::write(&config_path, config_content)
+            .context("Failed to write client configuration file")?;
+
+        println!(
+            "✓ Generated client configuration: {}",
+            config_path.display()
+        );
+        Ok(())
+    }
+
+    fn get_config_template(&self, prefix: &str, security: Option<&SecurityFiles>) -> String {
+        let (endpoint, cache_endpoint, cluster_security, cache_tls) = match security {
+            Some(files) => {
+                let tls = format!(
+                    r#"  tls:
+    cert_file: "{cert}"
+    key_file: "{key}"
+    ca_file: "{ca}"
+"#,
+                    cert = files.cert_file.display(),
+                    key = files.key_file.display(),
+                    ca = files.ca_file.display()
+                );
+                let join = format!(
+                    r#"  join:
+    secret_file: "{secret}"
+  admin:
+    token_file: "{admin_token}"
+"#,
+                    secret = files.secret_file.display(),
+                    admin_token = files.admin_token_file.display()
+                );
+                (
+                    "https://127.0.0.1:8080",
+                    "grpcs://127.0.0.1:9090",
+                    format!("{join}{tls}"),
+                    tls,
+                )
+            }
+            None => (
+                "http://127.0.0.1:8080",
+                "grpc://127.0.0.1:9090",
+                String::new(),
+                String::new(),
+            ),
+        };
+
         format!(
             r#"# Flame Cluster Configuration
 # Generated by flmadm install
 ---
 cluster:
   name: flame
-  endpoint: "http://127.0.0.1:8080"
+  endpoint: "{endpoint}"
   slot: "cpu=1,mem=2g"
   policy: proportion
   storage: "fs://{prefix}/data"
@@ -48,12 +116,60 @@ cluster:
     shim: host
   limits:
     max_executors: 128
-cache:
-  endpoint: "grpc://127.0.0.1:9090"
+{cluster_security}cache:
+  endpoint: "{cache_endpoint}"
   network_interface: "lo"
   storage: "{prefix}/data/cache"
+{cache_tls}paths:
+  work_dir: "{prefix}/work"
+  events_dir: "{prefix}/events"
+  cache_dir: "{prefix}/data/cache"
+  log_dir: "{prefix}/logs"
+  applications_dir: "{prefix}/conf/applications.d"
+  socket_dir: "{prefix}/run"
+  scratch_dir: "{prefix}/data/scratch"
 "#,
             prefix = prefix
         )
     }
+
+    fn get_client_config_template(&self, security: Option<&SecurityFiles>) -> String {
+        let (endpoint, cache_endpoint, tls, admin) = match security {
+            Some(files) => (
+                "https://127.0.0.1:8080",
+                "grpcs://127.0.0.1:9090",
+                format!(
+                    r#"      tls:
+        ca_file: "{ca}"
+"#,
+                    ca = files.ca_file.display()
+                ),
+                format!(
+                    r#"      admin_token_file: "{admin_token}"
+"#,
+                    admin_token = files.admin_token_file.display()
+                ),
+            ),
+            None => (
+                "http://127.0.0.1:8080",
+                "grpc://127.0.0.1:9090",
+                String::new(),
+                String::new(),
+            ),
+        };
+
+        format!(
+            r#"# Flame Client Configuration
+# Generated by flmadm install
+---
+current-context: flame
+contexts:
+  - name: flame
+    cluster:
+      endpoint: "{endpoint}"
+{tls}{admin}    cache:
+      endpoint: "{cache_endpoint}"
+{tls}"#
+        )
+    }
 }
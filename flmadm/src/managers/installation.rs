@@ -22,10 +22,13 @@ impl InstallationManager {
             ("work", &paths.work),
             ("work/sessions", &paths.work.join("sessions")),
             ("work/executors", &paths.work.join("executors")),
+            ("events", &paths.events),
+            ("run", &paths.run),
             ("logs", &paths.logs),
             ("conf", &paths.conf),
             ("data", &paths.data),
             ("data/cache", &paths.cache),
+            ("data/scratch", &paths.scratch),
             ("data/packages", &paths.data.join("packages")),
             ("migrations", &paths.migrations),
             ("migrations/sqlite", &paths.migrations.join("sqlite")),
@@ -147,10 +150,12 @@ impl InstallationManager {
         Ok(response == "y" || response == "yes")
     }
 
-    /// Install Python SDK
+    /// Install Python SDK; the wheels are built from the SDK unless prebuilt
+    /// ones, e.g. of a bundle, are provided.
     pub fn install_python_sdk(
         &self,
         src_dir: &Path,
+        wheels: Option<&Path>,
         paths: &InstallationPaths,
         profiles: &[InstallProfile],
         force_overwrite: bool,
@@ -200,11 +205,33 @@ impl InstallationManager {
 
         // Build wheel for faster runtime loading
         // uv always rebuilds local directory dependencies, but wheel files are cached
-        self.build_python_wheel(paths)?;
+        match wheels {
+            Some(wheels) => self.copy_python_wheels(wheels, paths)?,
+            None => self.build_python_wheel(paths)?,
+        }
 
         Ok(())
     }
 
+    /// Copy prebuilt wheels of flamepy and its dependencies, and pre-cache them
+    /// without an index
+    fn copy_python_wheels(&self, wheels: &Path, paths: &InstallationPaths) -> Result<()> {
+        println!("  📦 Copying Python wheels...");
+
+        fs::create_dir_all(&paths.wheels).context("Failed to create wheels directory")?;
+        for entry in fs::read_dir(wheels).context("Failed to read wheels directory")? {
+            let path = entry?.path();
+            if path.is_file() {
+                fs::copy(&path, paths.wheels.join(path.file_name().unwrap()))
+                    .context(format!("Failed to copy wheel: {:?}", path))?;
+            }
+        }
+
+        println!("  ✓ Copied wheels to: {}", paths.wheels.display());
+
+        self.cache_python_dependencies(paths, true)
+    }
+
     /// Build Python wheel from SDK source and pre-cache dependencies
     fn build_python_wheel(&self, paths: &InstallationPaths) -> Result<()> {
         println!("  📦 Building Python wheel...");
@@ -236,6 +263,19 @@ impl InstallationManager {
 
         println!("  ✓ Built wheel to: {}", paths.wheels.display());
 
+        self.cache_python_dependencies(paths, false)
+    }
+
+    /// Pre-cache flamepy and its dependencies; offline, they're only resolved
+    /// from the wheels directory.
+    fn cache_python_dependencies(&self, paths: &InstallationPaths, offline: bool) -> Result<()> {
+        let uv_path = paths.bin.join("uv");
+        let offline_args: &[&str] = if offline {
+            &["--offline", "--no-index"]
+        } else {
+            &[]
+        };
+
         // Pre-cache dependencies using uv's cache
         // Use FLAME_HOME/data/cache/uv as the cache directory so it's available at runtime
         println!("  📥 Caching dependencies...");
@@ -251,6 +291,7 @@ impl InstallationManager {
         let install_output = std::process::Command::new(&uv_path)
             .arg("pip")
             .arg("install")
+            .args(offline_args)
             .arg("--target")
             .arg(&cache_target)
             .arg("--find-links")
@@ -286,6 +327,7 @@ impl InstallationManager {
         // Create a simple Python script that just exits successfully
         let run_output = std::process::Command::new(&uv_path)
             .arg("run")
+            .args(offline_args)
             .arg("--find-links")
             .arg(&paths.wheels)
             .arg("--with")
@@ -313,7 +355,7 @@ impl InstallationManager {
     }
 
     /// Copy SDK directory while excluding development artifacts
-    fn copy_sdk_excluding_artifacts(&self, src: &Path, dst: &Path) -> Result<()> {
+    pub fn copy_sdk_excluding_artifacts(&self, src: &Path, dst: &Path) -> Result<()> {
         use walkdir::WalkDir;
 
         let exclude_patterns = [
@@ -408,8 +450,13 @@ impl InstallationManager {
         Ok(())
     }
 
-    /// Install uv tool
-    pub fn install_uv(&self, paths: &InstallationPaths, profiles: &[InstallProfile]) -> Result<()> {
+    /// Install uv tool, from the system unless a prebuilt one is provided
+    pub fn install_uv(
+        &self,
+        uv: Option<&Path>,
+        paths: &InstallationPaths,
+        profiles: &[InstallProfile],
+    ) -> Result<()> {
         // UV is only needed for worker and client profiles
         let needs_uv = profiles.contains(&InstallProfile::Worker)
             || profiles.contains(&InstallProfile::Client);
@@ -422,11 +469,14 @@ impl InstallationManager {
         println!("🔧 Installing uv...");
 
         // Find uv in the system
-        let uv_src = self.find_uv_executable().context(
-            "uv not found in system. Please install uv first:\n\
-             1. curl -LsSf https://astral.sh/uv/install.sh | sh\n\
-             2. Or install via your package manager",
-        )?;
+        let uv_src = match uv {
+            Some(uv) => uv.to_path_buf(),
+            None => self.find_uv_executable().context(
+                "uv not found in system. Please install uv first:\n\
+                 1. curl -LsSf https://astral.sh/uv/install.sh | sh\n\
+                 2. Or install via your package manager",
+            )?,
+        };
 
         let uv_dst = paths.bin.join("uv");
 
@@ -442,7 +492,7 @@ impl InstallationManager {
     }
 
     /// Find uv executable in the system
-    fn find_uv_executable(&self) -> Result<std::path::PathBuf> {
+    pub fn find_uv_executable(&self) -> Result<std::path::PathBuf> {
         use std::process::Command;
 
         // Try to find uv using 'which' command
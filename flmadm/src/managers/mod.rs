@@ -1,7 +1,9 @@
 pub mod backup;
 pub mod build;
+pub mod bundle;
 pub mod config;
 pub mod installation;
+pub mod security;
 pub mod source;
 pub mod systemd;
 pub mod user;
@@ -0,0 +1,297 @@
+use anyhow::{Context, Result};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::types::InstallationPaths;
+
+/// Days before the generated certificates expire
+const CERT_VALID_DAYS: &str = "365";
+/// Bytes of the generated join secret, i.e. 256 bits
+const JOIN_SECRET_LEN: usize = 32;
+/// Bytes of the generated admin token, i.e. 256 bits
+const ADMIN_TOKEN_LEN: usize = 32;
+/// The credential of the node persisted by the executor manager in its work directory
+const NODE_TOKEN_FILE: &str = "node-token";
+
+/// The security files of an installation, under `<prefix>/conf`
+#[derive(Debug, Clone)]
+pub struct SecurityFiles {
+    pub ca_file: PathBuf,
+    pub ca_key_file: PathBuf,
+    pub cert_file: PathBuf,
+    pub key_file: PathBuf,
+    pub secret_file: PathBuf,
+    pub admin_token_file: PathBuf,
+}
+
+impl SecurityFiles {
+    pub fn new(conf: &Path) -> Self {
+        let certs = conf.join("certs");
+        Self {
+            ca_file: certs.join("ca.crt"),
+            ca_key_file: certs.join("ca.key"),
+            cert_file: certs.join("server.crt"),
+            key_file: certs.join("server.key"),
+            secret_file: conf.join("join.secret"),
+            admin_token_file: conf.join("admin.token"),
+        }
+    }
+}
+
+pub struct SecurityManager;
+
+impl SecurityManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check that openssl is available to generate the certificates
+    pub fn check_prerequisites(&self) -> Result<()> {
+        match which::which("openssl") {
+            Ok(path) => {
+                println!("✓ Found openssl at: {}", path.display());
+                Ok(())
+            }
+            Err(_) => anyhow::bail!(
+                "openssl is not found in PATH (required to generate the TLS certificates)\n\
+                 Please install openssl, or use --insecure to install without TLS"
+            ),
+        }
+    }
+
+    /// Generate the CA, the server certificate, the join secret and the admin
+    /// token of the installation; the existing ones are kept, e.g. of a
+    /// reinstallation.
+    pub fn generate(&self, paths: &InstallationPaths) -> Result<SecurityFiles> {
+        let files = SecurityFiles::new(&paths.conf);
+
+        self.generate_certs(&files)?;
+        self.generate_join_secret(&files)?;
+        self.generate_admin_token(&files)?;
+
+        Ok(files)
+    }
+
+    fn generate_certs(&self, files: &SecurityFiles) -> Result<()> {
+        let certs_dir = files.cert_file.parent().unwrap();
+        if files.ca_file.exists() && files.cert_file.exists() && files.key_file.exists() {
+            println!("✓ TLS certificates already exist: {}", certs_dir.display());
+            return Ok(());
+        }
+
+        println!("🔐 Generating TLS certificates...");
+
+        fs::create_dir_all(certs_dir).context(format!(
+            "Failed to create directory: {}",
+            certs_dir.display()
+        ))?;
+
+        // The CA of the installation, which signs the server certificate
+        self.openssl(
+            Command::new("openssl")
+                .args(["genrsa", "-out"])
+                .arg(&files.ca_key_file)
+                .arg("4096"),
+            "generate the CA private key",
+        )?;
+        self.openssl(
+            Command::new("openssl")
+                .args(["req", "-new", "-x509", "-days", CERT_VALID_DAYS, "-key"])
+                .arg(&files.ca_key_file)
+                .arg("-out")
+                .arg(&files.ca_file)
+                .args(["-subj", "/CN=Flame CA/O=Flame"]),
+            "generate the CA certificate",
+        )?;
+
+        // The server certificate of the session manager and the object cache
+        let tmp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        let csr_file = tmp_dir.path().join("server.csr");
+        let ext_file = tmp_dir.path().join("server.ext");
+
+        self.openssl(
+            Command::new("openssl")
+                .args(["genrsa", "-out"])
+                .arg(&files.key_file)
+                .arg("4096"),
+            "generate the server private key",
+        )?;
+        self.openssl(
+            Command::new("openssl")
+                .args(["req", "-new", "-key"])
+                .arg(&files.key_file)
+                .arg("-out")
+                .arg(&csr_file)
+                .args(["-subj", "/CN=flame-server/O=Flame"]),
+            "generate the server CSR",
+        )?;
+
+        let sans = self.subject_alt_names();
+        fs::write(
+            &ext_file,
+            format!(
+                "authorityKeyIdentifier=keyid,issuer\n\
+                 basicConstraints=CA:FALSE\n\
+                 keyUsage = digitalSignature, keyEncipherment\n\
+                 extendedKeyUsage = serverAuth\n\
+                 subjectAltName = {}\n",
+                sans.join(",")
+            ),
+        )
+        .context("Failed to write the server certificate extensions")?;
+
+        self.openssl(
+            Command::new("openssl")
+                .args(["x509", "-req", "-in"])
+                .arg(&csr_file)
+                .arg("-CA")
+                .arg(&files.ca_file)
+                .arg("-CAkey")
+                .arg(&files.ca_key_file)
+                .arg("-CAcreateserial")
+                .arg("-CAserial")
+                .arg(tmp_dir.path().join("ca.srl"))
+                .arg("-out")
+                .arg(&files.cert_file)
+                .args(["-days", CERT_VALID_DAYS, "-extfile"])
+                .arg(&ext_file),
+            "sign the server certificate",
+        )?;
+
+        // The private keys are only readable by their owner
+        for key in [&files.ca_key_file, &files.key_file] {
+            fs::set_permissions(key, fs::Permissions::from_mode(0o600))
+                .context(format!("Failed to set permissions of {}", key.display()))?;
+        }
+
+        println!("✓ Generated TLS certificates: {}", certs_dir.display());
+        println!("   SANs: {}", sans.join(", "));
+        Ok(())
+    }
+
+    /// The names of this machine the server certificate is valid for
+    fn subject_alt_names(&self) -> Vec<String> {
+        let mut sans = vec!["DNS:localhost".to_string(), "IP:127.0.0.1".to_string()];
+
+        let hostname = common::apis::Node::new().name;
+        if !hostname.is_empty() && hostname != "localhost" {
+            sans.push(format!("DNS:{hostname}"));
+        }
+
+        sans
+    }
+
+    fn generate_join_secret(&self, files: &SecurityFiles) -> Result<()> {
+        if files.secret_file.exists() {
+            println!(
+                "✓ Join secret already exists: {}",
+                files.secret_file.display()
+            );
+            return Ok(());
+        }
+
+        let mut secret = [0u8; JOIN_SECRET_LEN];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .map_err(|_| anyhow::anyhow!("Failed to generate the join secret"))?;
+
+        write_private(&files.secret_file, &to_hex(&secret))?;
+
+        println!("✓ Generated join secret: {}", files.secret_file.display());
+        Ok(())
+    }
+
+    /// Generate the admin token, which is presented by flmctl in the
+    /// administrative requests, e.g. minting the join tokens.
+    fn generate_admin_token(&self, files: &SecurityFiles) -> Result<()> {
+        if files.admin_token_file.exists() {
+            println!(
+                "✓ Admin token already exists: {}",
+                files.admin_token_file.display()
+            );
+            return Ok(());
+        }
+
+        let mut token = [0u8; ADMIN_TOKEN_LEN];
+        SystemRandom::new()
+            .fill(&mut token)
+            .map_err(|_| anyhow::anyhow!("Failed to generate the admin token"))?;
+
+        write_private(&files.admin_token_file, &to_hex(&token))?;
+
+        println!(
+            "✓ Generated admin token: {}",
+            files.admin_token_file.display()
+        );
+        Ok(())
+    }
+
+    /// Issue the credential of this node by the join secret, so the executor
+    /// manager installed with the session manager joins without a join token.
+    pub fn issue_node_token(&self, files: &SecurityFiles, work_dir: &Path) -> Result<()> {
+        let token_file = work_dir.join(NODE_TOKEN_FILE);
+        if token_file.exists() {
+            println!("✓ Node credential already exists: {}", token_file.display());
+            return Ok(());
+        }
+
+        // The session manager signs the node name by the secret as is, i.e. the
+        // hex string in the file.
+        let secret = fs::read(&files.secret_file).context(format!(
+            "Failed to read join secret: {}",
+            files.secret_file.display()
+        ))?;
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.trim_ascii_end());
+
+        let node_name = common::apis::Node::new().name;
+        let tag = hmac::sign(&key, node_name.as_bytes());
+
+        write_private(&token_file, &to_hex(tag.as_ref()))?;
+
+        println!(
+            "✓ Issued credential of node <{}>: {}",
+            node_name,
+            token_file.display()
+        );
+        Ok(())
+    }
+
+    fn openssl(&self, command: &mut Command, action: &str) -> Result<()> {
+        let output = command.output().context("Failed to run openssl")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to {}: {}",
+                action,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Write the file which is only readable by its owner
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .context(format!("Failed to create {}", path.display()))?;
+
+    file.write_all(contents.as_bytes())
+        .context(format!("Failed to write {}", path.display()))?;
+
+    Ok(())
+}
@@ -12,7 +12,12 @@ impl SystemdManager {
     }
 
     /// Generate and install systemd service files
-    pub fn install_services(&self, prefix: &Path, profiles: &[InstallProfile]) -> Result<()> {
+    pub fn install_services(
+        &self,
+        prefix: &Path,
+        profiles: &[InstallProfile],
+        user: &str,
+    ) -> Result<()> {
         println!("⚙️  Installing systemd service files...");
 
         let prefix_str = prefix.to_str().unwrap();
@@ -22,7 +27,7 @@ impl SystemdManager {
 
         // Write to /etc/systemd/system/
         if has_control_plane {
-            let fsm_service = self.generate_session_manager_service(prefix_str);
+            let fsm_service = self.generate_session_manager_service(prefix_str, user);
             let fsm_path = PathBuf::from("/etc/systemd/system/flame-session-manager.service");
             fs::write(&fsm_path, fsm_service)
                 .context("Failed to write flame-session-manager.service")?;
@@ -32,7 +37,7 @@ impl SystemdManager {
         }
 
         if has_worker {
-            let fem_service = self.generate_executor_manager_service(prefix_str);
+            let fem_service = self.generate_executor_manager_service(prefix_str, user);
             let fem_path = PathBuf::from("/etc/systemd/system/flame-executor-manager.service");
             fs::write(&fem_path, fem_service)
                 .context("Failed to write flame-executor-manager.service")?;
@@ -159,7 +164,7 @@ impl SystemdManager {
         Ok(())
     }
 
-    fn stop_service(&self, service: &str) -> Result<()> {
+    pub fn stop_service(&self, service: &str) -> Result<()> {
         let output = Command::new("systemctl")
             .args(["stop", service])
             .output()
@@ -250,7 +255,7 @@ impl SystemdManager {
         Ok(())
     }
 
-    fn generate_session_manager_service(&self, prefix: &str) -> String {
+    fn generate_session_manager_service(&self, prefix: &str, user: &str) -> String {
         format!(
             r#"[Unit]
 Description=Flame Session Manager
@@ -260,6 +265,8 @@ Wants=network-online.target
 
 [Service]
 Type=simple
+User={user}
+Group={user}
 Environment="RUST_LOG=info"
 Environment="FLAME_HOME={prefix}"
 WorkingDirectory={prefix}
@@ -273,11 +280,12 @@ LimitNOFILE=65536
 [Install]
 WantedBy=multi-user.target
 "#,
-            prefix = prefix
+            prefix = prefix,
+            user = user
         )
     }
 
-    fn generate_executor_manager_service(&self, prefix: &str) -> String {
+    fn generate_executor_manager_service(&self, prefix: &str, user: &str) -> String {
         format!(
             r#"[Unit]
 Description=Flame Executor Manager
@@ -288,6 +296,8 @@ Requires=flame-session-manager.service
 
 [Service]
 Type=simple
+User={user}
+Group={user}
 Environment="RUST_LOG=info"
 Environment="FLAME_HOME={prefix}"
 WorkingDirectory={prefix}/work
@@ -301,7 +311,8 @@ LimitNOFILE=65536
 [Install]
 WantedBy=multi-user.target
 "#,
-            prefix = prefix
+            prefix = prefix,
+            user = user
         )
     }
 }
@@ -1,3 +1,7 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
 pub struct UserManager;
 
 impl UserManager {
@@ -9,4 +13,75 @@ impl UserManager {
     pub fn is_root(&self) -> bool {
         users::get_current_uid() == 0
     }
+
+    /// Create the system user of the services if it does not exist, so they
+    /// run unprivileged; the user has no login shell and no home directory.
+    pub fn ensure_system_user(&self, name: &str, home: &Path) -> Result<()> {
+        if users::get_user_by_name(name).is_some() {
+            println!("✓ System user already exists: {}", name);
+            return Ok(());
+        }
+
+        println!("👤 Creating system user: {}", name);
+
+        let status = Command::new("useradd")
+            .args(["--system", "--user-group", "--no-create-home"])
+            .arg("--home-dir")
+            .arg(home)
+            .args(["--shell", "/usr/sbin/nologin", name])
+            .status()
+            .context("Failed to run useradd")?;
+
+        if !status.success() {
+            anyhow::bail!("Failed to create system user {}: {}", name, status);
+        }
+
+        println!("✓ Created system user: {}", name);
+        Ok(())
+    }
+
+    /// Change the owner of the directories, recursively, to the user and its group
+    pub fn chown_dirs(&self, name: &str, dirs: &[&Path]) -> Result<()> {
+        for dir in dirs {
+            let status = Command::new("chown")
+                .arg("-R")
+                .arg(format!("{name}:{name}"))
+                .arg(dir)
+                .status()
+                .context(format!("Failed to run chown on {}", dir.display()))?;
+
+            if !status.success() {
+                anyhow::bail!(
+                    "Failed to change owner of {} to {}: {}",
+                    dir.display(),
+                    name,
+                    status
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Change the owner of the files to the user and its group
+    pub fn chown_files(&self, name: &str, files: &[&Path]) -> Result<()> {
+        for file in files {
+            let status = Command::new("chown")
+                .arg(format!("{name}:{name}"))
+                .arg(file)
+                .status()
+                .context(format!("Failed to run chown on {}", file.display()))?;
+
+            if !status.success() {
+                anyhow::bail!(
+                    "Failed to change owner of {} to {}: {}",
+                    file.display(),
+                    name,
+                    status
+                );
+            }
+        }
+
+        Ok(())
+    }
 }
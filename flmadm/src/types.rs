@@ -1,5 +1,8 @@
 use std::path::{Path, PathBuf};
 
+/// The dedicated system user of the services
+pub const DEFAULT_SERVICE_USER: &str = "flame";
+
 /// Installation profiles for different deployment scenarios
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstallProfile {
@@ -41,6 +44,12 @@ pub struct InstallConfig {
     pub verbose: bool,
     pub profiles: Vec<InstallProfile>,
     pub force_overwrite: bool,
+    /// The system user the services run as
+    pub user: String,
+    /// An offline bundle to install from instead of the source
+    pub bundle: Option<PathBuf>,
+    /// Skip generating the TLS certificates and the join secret
+    pub insecure: bool,
 }
 
 impl Default for InstallConfig {
@@ -59,6 +68,9 @@ impl Default for InstallConfig {
                 InstallProfile::Client,
             ],
             force_overwrite: false,
+            user: DEFAULT_SERVICE_USER.to_string(),
+            bundle: None,
+            insecure: false,
         }
     }
 }
@@ -89,6 +101,35 @@ impl Default for UninstallConfig {
     }
 }
 
+/// Configuration for the restore command
+#[derive(Debug, Clone)]
+pub struct RestoreConfig {
+    pub prefix: PathBuf,
+    /// A backup, or a directory of backups to pick the latest one from
+    pub backup: PathBuf,
+    /// Restore the latest backup taken no later than this time
+    pub before: Option<chrono::DateTime<chrono::Utc>>,
+    pub force: bool,
+}
+
+/// Configuration for the bundle command
+#[derive(Debug, Clone)]
+pub struct BundleConfig {
+    pub src_dir: Option<PathBuf>,
+    /// The bundle to create; `flame-<version>-<os>-<arch>.tar.gz` if not set
+    pub output: Option<PathBuf>,
+    pub skip_build: bool,
+    pub verbose: bool,
+}
+
+/// Configuration for the doctor command
+#[derive(Debug, Clone)]
+pub struct DoctorConfig {
+    pub prefix: PathBuf,
+    /// The configuration to check; `<prefix>/conf/flame-cluster.yaml` if not set
+    pub config: Option<PathBuf>,
+}
+
 /// Standard paths for a Flame installation
 #[derive(Debug, Clone)]
 pub struct InstallationPaths {
@@ -97,10 +138,13 @@ pub struct InstallationPaths {
     pub sdk_python: PathBuf,
     pub wheels: PathBuf,
     pub work: PathBuf,
+    pub events: PathBuf,
+    pub run: PathBuf,
     pub logs: PathBuf,
     pub conf: PathBuf,
     pub data: PathBuf,
     pub cache: PathBuf,
+    pub scratch: PathBuf,
     pub migrations: PathBuf,
 }
 
@@ -111,10 +155,13 @@ impl InstallationPaths {
             sdk_python: prefix.join("sdk/python"),
             wheels: prefix.join("wheels"),
             work: prefix.join("work"),
+            events: prefix.join("events"),
+            run: prefix.join("run"),
             logs: prefix.join("logs"),
             conf: prefix.join("conf"),
             data: prefix.join("data"),
             cache: prefix.join("data/cache"),
+            scratch: prefix.join("data/scratch"),
             migrations: prefix.join("migrations"),
             prefix,
         }
@@ -142,30 +189,24 @@ pub struct BuildArtifacts {
 impl BuildArtifacts {
     /// Find build artifacts in a source directory
     pub fn from_source_dir(src_dir: &Path, profile: &str) -> anyhow::Result<Self> {
-        let target_dir = src_dir.join("target").join(profile);
+        Self::from_dir(&src_dir.join("target").join(profile))
+    }
 
+    /// Find build artifacts in a directory of binaries, e.g. of a bundle
+    pub fn from_dir(dir: &Path) -> anyhow::Result<Self> {
         let artifacts = Self {
-            session_manager: target_dir.join("flame-session-manager"),
-            executor_manager: target_dir.join("flame-executor-manager"),
-            flmctl: target_dir.join("flmctl"),
-            flmadm: target_dir.join("flmadm"),
-            flmping: target_dir.join("flmping"),
-            flmping_service: target_dir.join("flmping-service"),
-            flmexec: target_dir.join("flmexec"),
-            flmexec_service: target_dir.join("flmexec-service"),
+            session_manager: dir.join("flame-session-manager"),
+            executor_manager: dir.join("flame-executor-manager"),
+            flmctl: dir.join("flmctl"),
+            flmadm: dir.join("flmadm"),
+            flmping: dir.join("flmping"),
+            flmping_service: dir.join("flmping-service"),
+            flmexec: dir.join("flmexec"),
+            flmexec_service: dir.join("flmexec-service"),
         };
 
         // Verify all artifacts exist
-        for (name, path) in [
-            ("flame-session-manager", &artifacts.session_manager),
-            ("flame-executor-manager", &artifacts.executor_manager),
-            ("flmctl", &artifacts.flmctl),
-            ("flmadm", &artifacts.flmadm),
-            ("flmping", &artifacts.flmping),
-            ("flmping-service", &artifacts.flmping_service),
-            ("flmexec", &artifacts.flmexec),
-            ("flmexec-service", &artifacts.flmexec_service),
-        ] {
+        for (name, path) in artifacts.all() {
             if !path.exists() {
                 anyhow::bail!("Build artifact not found: {} at {:?}", name, path);
             }
@@ -173,6 +214,20 @@ impl BuildArtifacts {
 
         Ok(artifacts)
     }
+
+    /// All artifacts with their names
+    pub fn all(&self) -> [(&'static str, &Path); 8] {
+        [
+            ("flame-session-manager", &self.session_manager),
+            ("flame-executor-manager", &self.executor_manager),
+            ("flmctl", &self.flmctl),
+            ("flmadm", &self.flmadm),
+            ("flmping", &self.flmping),
+            ("flmping-service", &self.flmping_service),
+            ("flmexec", &self.flmexec),
+            ("flmexec-service", &self.flmexec_service),
+        ]
+    }
 }
 
 /// Exit codes for flmadm commands
@@ -15,8 +15,11 @@ use std::collections::HashMap;
 
 use chrono::Duration;
 use flame_rs::{
-    apis::{FlameError, Shim},
-    client::{ApplicationAttributes, ApplicationSchema},
+    apis::{FairnessPolicy, FlameError, PayloadCodec, Shim, TopologyPolicy},
+    client::{
+        ApplicationAttributes, ApplicationSchema, HealthProbe, LifecycleHooks, PythonEnvironment,
+        SessionDefaults,
+    },
 };
 
 use serde_derive::{Deserialize, Serialize};
@@ -48,6 +51,18 @@ pub struct SpecYaml {
     pub delay_release: Option<i64>,
     pub schema: Option<SchemaYaml>,
     pub url: Option<String>,
+    pub task_environments: Option<Vec<String>>,
+    pub task_arguments: Option<Vec<String>>,
+    pub bundle_size: Option<u32>,
+    pub max_instances_per_node: Option<u32>,
+    pub session_defaults: Option<SessionDefaults>,
+    pub health_probe: Option<HealthProbe>,
+    pub fairness: Option<String>,
+    pub hooks: Option<LifecycleHooks>,
+    pub python_env: Option<PythonEnvironment>,
+    pub codec: Option<String>,
+    pub payload_type: Option<String>,
+    pub topology: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +86,46 @@ impl TryFrom<&ApplicationYaml> for ApplicationAttributes {
             }
         };
 
+        let fairness = match yaml.spec.fairness.as_deref() {
+            Some("RoundRobin") | Some("round-robin") => Some(FairnessPolicy::RoundRobin),
+            Some("Proportional") | Some("proportional") => Some(FairnessPolicy::Proportional),
+            None => None,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "Invalid fairness value '{}'. Must be 'RoundRobin' or 'Proportional'.",
+                    other
+                )))
+            }
+        };
+
+        let codec = match yaml.spec.codec.as_deref() {
+            Some("Raw") | Some("raw") => Some(PayloadCodec::Raw),
+            Some("Json") | Some("json") | Some("JSON") => Some(PayloadCodec::Json),
+            Some("Msgpack") | Some("msgpack") => Some(PayloadCodec::Msgpack),
+            Some("Arrow") | Some("arrow") => Some(PayloadCodec::Arrow),
+            Some("Protobuf") | Some("protobuf") => Some(PayloadCodec::Protobuf),
+            None => None,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "Invalid codec value '{}'. Must be 'Raw', 'Json', 'Msgpack', 'Arrow' or 'Protobuf'.",
+                    other
+                )))
+            }
+        };
+
+        let topology = match yaml.spec.topology.as_deref() {
+            Some("Ignore") | Some("ignore") => Some(TopologyPolicy::Ignore),
+            Some("NumaAligned") | Some("numa-aligned") => Some(TopologyPolicy::NumaAligned),
+            Some("GpuAligned") | Some("gpu-aligned") => Some(TopologyPolicy::GpuAligned),
+            None => None,
+            Some(other) => {
+                return Err(FlameError::InvalidConfig(format!(
+                    "Invalid topology value '{}'. Must be 'Ignore', 'NumaAligned' or 'GpuAligned'.",
+                    other
+                )))
+            }
+        };
+
         Ok(Self {
             shim,
             image: yaml.spec.image.clone(),
@@ -84,6 +139,18 @@ impl TryFrom<&ApplicationYaml> for ApplicationAttributes {
             delay_release: yaml.spec.delay_release.map(Duration::seconds),
             schema: yaml.spec.schema.clone().map(ApplicationSchema::from),
             url: yaml.spec.url.clone(),
+            task_environments: yaml.spec.task_environments.clone().unwrap_or_default(),
+            task_arguments: yaml.spec.task_arguments.clone().unwrap_or_default(),
+            bundle_size: yaml.spec.bundle_size,
+            max_instances_per_node: yaml.spec.max_instances_per_node,
+            session_defaults: yaml.spec.session_defaults.clone(),
+            health_probe: yaml.spec.health_probe.clone(),
+            fairness,
+            hooks: yaml.spec.hooks.clone(),
+            python_env: yaml.spec.python_env.clone(),
+            codec,
+            payload_type: yaml.spec.payload_type.clone(),
+            topology,
         })
     }
 }
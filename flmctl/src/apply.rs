@@ -0,0 +1,354 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+use flame_rs as flame;
+use flame_rs::{
+    apis::{FlameContext, FlameError, SessionState},
+    client::{Application, ApplicationAttributes},
+};
+
+use crate::apis::ApplicationYaml;
+
+/// The applications registered by Flame itself, which are never pruned.
+const BUILTIN_APPLICATIONS: [&str; 3] = ["flmexec", "flmping", "flmrun"];
+
+pub struct ApplyOptions {
+    pub dry_run: bool,
+    pub prune: bool,
+}
+
+/// The change of one field of an application.
+#[derive(Debug, PartialEq)]
+struct FieldDiff {
+    field: String,
+    current: Value,
+    desired: Value,
+}
+
+enum Change {
+    Create {
+        name: String,
+        attr: ApplicationAttributes,
+    },
+    Update {
+        name: String,
+        attr: ApplicationAttributes,
+        diffs: Vec<FieldDiff>,
+    },
+    Prune {
+        name: String,
+    },
+}
+
+impl Change {
+    fn name(&self) -> &str {
+        match self {
+            Change::Create { name, .. } | Change::Update { name, .. } | Change::Prune { name } => {
+                name
+            }
+        }
+    }
+}
+
+pub async fn run(ctx: &FlameContext, path: &str, opts: &ApplyOptions) -> Result<(), FlameError> {
+    let desired = load_applications(Path::new(path))?;
+
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
+
+    let current = conn.list_application().await?;
+    let mut open_sessions = HashMap::<String, usize>::new();
+    for ssn in conn.list_session().await? {
+        if ssn.state == SessionState::Open {
+            *open_sessions.entry(ssn.application.clone()).or_default() += 1;
+        }
+    }
+
+    let changes = plan(&desired, &current, opts.prune)?;
+    if changes.is_empty() {
+        println!("No changes, {} applications are up to date.", desired.len());
+        return Ok(());
+    }
+
+    // Updating or removing an application is rejected by Flame while it has open
+    // sessions, so such changes are reported and skipped instead of failing the others.
+    let mut blocked = vec![];
+    for change in &changes {
+        print_change(change);
+        if !matches!(change, Change::Create { .. }) {
+            if let Some(count) = open_sessions.get(change.name()) {
+                println!("    ! skipped: {count} open sessions in the application");
+                blocked.push(change.name().to_string());
+            }
+        }
+    }
+
+    if opts.dry_run {
+        return Ok(());
+    }
+
+    for change in changes {
+        if blocked.iter().any(|name| name == change.name()) {
+            continue;
+        }
+        match change {
+            Change::Create { name, attr } => conn.register_application(name, attr).await?,
+            Change::Update { name, attr, .. } => conn.update_application(name, attr).await?,
+            Change::Prune { name } => conn.unregister_application(name).await?,
+        }
+    }
+
+    if !blocked.is_empty() {
+        return Err(FlameError::Internal(format!(
+            "applications with open sessions were not reconciled: {}",
+            blocked.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Loads the applications from a YAML file, or from the `.yaml`/`.yml` files of a directory.
+fn load_applications(path: &Path) -> Result<BTreeMap<String, ApplicationAttributes>, FlameError> {
+    let files = if path.is_dir() {
+        let mut files = fs::read_dir(path)
+            .map_err(|e| FlameError::Internal(e.to_string()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.is_file()
+                    && matches!(
+                        p.extension().and_then(|ext| ext.to_str()),
+                        Some("yaml") | Some("yml")
+                    )
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    } else if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        return Err(FlameError::InvalidConfig(format!(
+            "<{}> is not a file or directory",
+            path.display()
+        )));
+    };
+
+    let mut apps = BTreeMap::new();
+    for file in files {
+        let contents =
+            fs::read_to_string(&file).map_err(|e| FlameError::Internal(e.to_string()))?;
+        for doc in contents
+            .split("\n---\n")
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            let app: ApplicationYaml = serde_yaml::from_str(doc)
+                .map_err(|e| FlameError::InvalidConfig(format!("<{}>: {e}", file.display())))?;
+            let attr = ApplicationAttributes::try_from(&app)?;
+            if apps.insert(app.metadata.name.clone(), attr).is_some() {
+                return Err(FlameError::InvalidConfig(format!(
+                    "application <{}> is defined more than once",
+                    app.metadata.name
+                )));
+            }
+        }
+    }
+
+    Ok(apps)
+}
+
+/// Computes the changes to reconcile the current applications with the desired ones.
+fn plan(
+    desired: &BTreeMap<String, ApplicationAttributes>,
+    current: &[Application],
+    prune: bool,
+) -> Result<Vec<Change>, FlameError> {
+    let current = current
+        .iter()
+        .map(|app| (app.name.clone(), app))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut changes = vec![];
+    for (name, attr) in desired {
+        match current.get(name) {
+            None => changes.push(Change::Create {
+                name: name.clone(),
+                attr: attr.clone(),
+            }),
+            Some(app) => {
+                let diffs = diff_attributes(&app.attributes, attr)?;
+                if !diffs.is_empty() {
+                    changes.push(Change::Update {
+                        name: name.clone(),
+                        attr: attr.clone(),
+                        diffs,
+                    });
+                }
+            }
+        }
+    }
+
+    if prune {
+        for name in current.keys() {
+            if !desired.contains_key(name) && !BUILTIN_APPLICATIONS.contains(&name.as_str()) {
+                changes.push(Change::Prune { name: name.clone() });
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Compares the fields set in the desired attributes with the current ones; the
+/// fields left unset are filled by Flame's defaults, so they are not compared.
+fn diff_attributes(
+    current: &ApplicationAttributes,
+    desired: &ApplicationAttributes,
+) -> Result<Vec<FieldDiff>, FlameError> {
+    let to_value = |attr: &ApplicationAttributes| {
+        serde_json::to_value(attr).map_err(|e| FlameError::Internal(e.to_string()))
+    };
+    let current = to_value(current)?;
+    let desired = to_value(desired)?;
+
+    let Value::Object(desired) = desired else {
+        return Ok(vec![]);
+    };
+
+    Ok(desired
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .filter_map(|(field, value)| {
+            let current = current.get(&field).cloned().unwrap_or(Value::Null);
+            (current != value).then_some(FieldDiff {
+                field,
+                current,
+                desired: value,
+            })
+        })
+        .collect())
+}
+
+fn print_change(change: &Change) {
+    match change {
+        Change::Create { name, .. } => println!("+ {name}"),
+        Change::Update { name, diffs, .. } => {
+            println!("~ {name}");
+            for diff in diffs {
+                println!("    {}: {} -> {}", diff.field, diff.current, diff.desired);
+            }
+        }
+        Change::Prune { name } => println!("- {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use flame_rs::apis::ApplicationState;
+
+    fn attributes(image: &str) -> ApplicationAttributes {
+        ApplicationAttributes {
+            shim: None,
+            image: Some(image.to_string()),
+            description: None,
+            labels: vec![],
+            command: None,
+            arguments: vec![],
+            environments: HashMap::new(),
+            working_directory: None,
+            max_instances: None,
+            delay_release: None,
+            schema: None,
+            url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: None,
+            max_instances_per_node: None,
+            session_defaults: None,
+            health_probe: None,
+            fairness: None,
+            hooks: None,
+            python_env: None,
+            codec: None,
+            payload_type: None,
+            topology: None,
+        }
+    }
+
+    fn application(name: &str, attributes: ApplicationAttributes) -> Application {
+        Application {
+            name: name.to_string(),
+            attributes,
+            state: ApplicationState::Enabled,
+            creation_time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_diff_attributes() {
+        let mut current = attributes("app:v1");
+        current.working_directory = Some("/tmp".to_string());
+
+        assert!(diff_attributes(&current, &attributes("app:v1"))
+            .unwrap()
+            .is_empty());
+
+        let diffs = diff_attributes(&current, &attributes("app:v2")).unwrap();
+        assert_eq!(
+            diffs,
+            vec![FieldDiff {
+                field: "image".to_string(),
+                current: Value::from("app:v1"),
+                desired: Value::from("app:v2"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_plan() {
+        let desired = BTreeMap::from([
+            ("new".to_string(), attributes("new:v1")),
+            ("changed".to_string(), attributes("changed:v2")),
+            ("same".to_string(), attributes("same:v1")),
+        ]);
+        let current = vec![
+            application("changed", attributes("changed:v1")),
+            application("same", attributes("same:v1")),
+            application("removed", attributes("removed:v1")),
+            application("flmexec", attributes("flmexec:v1")),
+        ];
+
+        let changes = plan(&desired, &current, false).unwrap();
+        let names = changes.iter().map(Change::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["changed", "new"]);
+        assert!(matches!(changes[0], Change::Update { .. }));
+        assert!(matches!(changes[1], Change::Create { .. }));
+
+        let changes = plan(&desired, &current, true).unwrap();
+        let names = changes.iter().map(Change::name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["changed", "new", "removed"]);
+        assert!(matches!(changes[2], Change::Prune { .. }));
+    }
+}
@@ -13,19 +13,42 @@ limitations under the License.
 
 use std::error::Error;
 
-use flame_rs as flame;
-use flame_rs::apis::FlameContext;
+use flame_rs::apis::{FlameContext, SessionID, SessionState};
+use flame_rs::client::federation::Federation;
 
-pub async fn run(ctx: &FlameContext, session_id: &str) -> Result<(), Box<dyn Error>> {
-    let current_ctx = ctx.get_current_context()?;
-    let conn = flame::client::connect_with_tls(
-        &current_ctx.cluster.endpoint,
-        current_ctx.cluster.tls.as_ref(),
-    )
-    .await?;
+/// Closes the session; it's closing until its running tasks are completed,
+/// which is waited for by `wait`, unless they're cancelled by `force`.
+pub async fn run(
+    ctx: &FlameContext,
+    session_id: &SessionID,
+    force: bool,
+    wait: bool,
+) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let ssn = federation.get_session(session_id).await?;
 
-    conn.close_session(session_id).await?;
+    let ssn = ssn.close_with_force(force).await?;
+    if ssn.state == SessionState::Closed {
+        println!("Session <{}> was closed.", session_id);
+        return Ok(());
+    }
+    if !wait {
+        println!(
+            "Session <{}> is closing, waiting for {} running task(s).",
+            session_id, ssn.running
+        );
+        return Ok(());
+    }
 
+    ssn.wait_closed(|ssn| {
+        if ssn.state == SessionState::Closing {
+            println!(
+                "Session <{}> is closing, {} running task(s) left.",
+                ssn.id, ssn.running
+            );
+        }
+    })
+    .await?;
     println!("Session <{}> was closed.", session_id);
 
     Ok(())
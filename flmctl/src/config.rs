@@ -0,0 +1,183 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use url::Url;
+
+use flame_rs::apis::{FlameClientTls, FlameContext, FlameError};
+
+/// Validates the configuration of flmctl, i.e. flame.yaml, and prints the problems found.
+pub fn validate(path: &Option<String>) -> Result<(), FlameError> {
+    // Unknown keys and mismatched types are rejected, with their location, when loading.
+    let ctx = FlameContext::from_file(path.clone())?;
+
+    let problems = diagnose(&ctx);
+    if problems.is_empty() {
+        println!(
+            "Configuration is valid: {} contexts, {} federations, current context <{}>.",
+            ctx.contexts.len(),
+            ctx.federations.len(),
+            ctx.current_context
+        );
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("  - {problem}");
+    }
+
+    Err(FlameError::InvalidConfig(format!(
+        "{} problems in the configuration",
+        problems.len()
+    )))
+}
+
+/// Checks the semantics of the configuration which can not be expressed by its schema.
+fn diagnose(ctx: &FlameContext) -> Vec<String> {
+    let mut problems = vec![];
+
+    let mut names = HashSet::new();
+    for name in ctx
+        .contexts
+        .iter()
+        .map(|c| &c.name)
+        .chain(ctx.federations.iter().map(|f| &f.name))
+    {
+        if !names.insert(name) {
+            problems.push(format!("context <{name}> is defined more than once"));
+        }
+    }
+
+    if let Err(e) = ctx.get_current_contexts() {
+        problems.push(e.to_string());
+    }
+
+    for entry in &ctx.contexts {
+        let name = &entry.name;
+        match Url::parse(&entry.cluster.endpoint) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            Ok(url) => problems.push(format!(
+                "context <{name}>: unsupported scheme <{}> of cluster.endpoint, expected http or https",
+                url.scheme()
+            )),
+            Err(e) => problems.push(format!(
+                "context <{name}>: invalid cluster.endpoint <{}>: {e}",
+                entry.cluster.endpoint
+            )),
+        }
+        check_ca_file(&mut problems, name, "cluster", &entry.cluster.tls);
+
+        if let Some(cache) = &entry.cache {
+            if let Some(endpoint) = &cache.endpoint {
+                if let Err(e) = Url::parse(endpoint) {
+                    problems.push(format!(
+                        "context <{name}>: invalid cache.endpoint <{endpoint}>: {e}"
+                    ));
+                }
+            }
+            check_ca_file(&mut problems, name, "cache", &cache.tls);
+        }
+    }
+
+    problems
+}
+
+fn check_ca_file(
+    problems: &mut Vec<String>,
+    name: &str,
+    section: &str,
+    tls: &Option<FlameClientTls>,
+) {
+    if let Some(ca_file) = tls.as_ref().and_then(|tls| tls.ca_file.as_ref()) {
+        if !Path::new(ca_file).is_file() {
+            problems.push(format!(
+                "context <{name}>: {section}.tls.ca_file <{ca_file}> is not a file"
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose() {
+        let ctx: FlameContext = serde_yaml::from_str(
+            r#"
+current-context: all
+contexts:
+  - name: east
+    cluster:
+      endpoint: "http://east:8080"
+  - name: west
+    cluster:
+      endpoint: "grpc://west:8080"
+      tls:
+        ca_file: "/nonexistent/ca.crt"
+federations:
+  - name: all
+    contexts: [east, north]
+"#,
+        )
+        .unwrap();
+
+        let problems = diagnose(&ctx);
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].contains("<north>"));
+        assert!(problems[1].contains("unsupported scheme <grpc>"));
+        assert!(problems[2].contains("ca_file"));
+    }
+
+    #[test]
+    fn test_current_context_of_federation() {
+        let ctx: FlameContext = serde_yaml::from_str(
+            r#"
+current-context: all
+contexts:
+  - name: east
+    cluster:
+      endpoint: "http://east:8080"
+  - name: west
+    cluster:
+      endpoint: "http://west:8080"
+federations:
+  - name: all
+    contexts: [west, east]
+"#,
+        )
+        .unwrap();
+
+        // The federation resolves to its preferred cluster.
+        assert_eq!(ctx.get_current_context().unwrap().name, "west");
+        assert_eq!(ctx.get_current_contexts().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let result = serde_yaml::from_str::<FlameContext>(
+            r#"
+current-context: flame
+contexts:
+  - name: flame
+    cluster:
+      endpoint: "http://127.0.0.1:8080"
+      ca_file: "/etc/flame/ca.crt"
+"#,
+        );
+        let err = result.err().unwrap().to_string();
+        assert!(err.contains("unknown field `ca_file`"), "{err}");
+    }
+}
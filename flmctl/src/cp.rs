@@ -0,0 +1,704 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Copying the files to and from the objects of the sessions in the object cache,
+//! e.g. to stage the data of a session without writing code.
+//!
+//! The objects are addressed by `flame://<session>/<key>`, and their content is
+//! verified by its SHA-256 checksum: an uploaded object is read back and
+//! compared with the local file. The deltas of a downloaded object, if any, are
+//! appended to its base in order.
+//!
+//! The files larger than a part are uploaded by a multi-part upload instead, so
+//! they're never read into memory at once: each part is verified by the cache
+//! and retried if failed, and the upload of a failed copy is resumed by its id,
+//! i.e. only the parts not received by the cache are uploaded again.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use std::time::Duration;
+
+use arrow::array::{Array, BinaryArray, LargeBinaryArray, RecordBatch, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow_flight::flight_service_client::FlightServiceClient;
+use arrow_flight::utils::{batches_to_flight_data, flight_data_to_arrow_batch};
+use arrow_flight::{Action, FlightData, FlightDescriptor, Ticket};
+use base64::Engine;
+use ring::digest;
+use serde_derive::{Deserialize, Serialize};
+use tonic::transport::{Channel, Endpoint};
+use url::Url;
+
+use flame_rs::apis::{FlameContext, FlameError};
+
+use crate::utils::format_memory;
+
+const FLAME_SCHEME: &str = "flame://";
+const DEFAULT_CACHE_PORT: u16 = 9090;
+/// The bytes of the local file read or written at a time, i.e. a progress step.
+const CHUNK_SIZE: usize = 1024 * 1024;
+/// The bytes of a part of the multi-part upload; the larger files are uploaded by parts.
+const PART_SIZE: usize = 16 * 1024 * 1024;
+/// The attempts to upload a part before the copy fails.
+const PART_ATTEMPTS: u32 = 5;
+
+/// The status of a multi-part upload, i.e. the parts received by the cache.
+#[derive(Debug, Deserialize)]
+struct UploadInfo {
+    upload_id: String,
+    key: String,
+    parts: Vec<UploadPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UploadPart {
+    part_number: u32,
+    checksum: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CompleteUpload {
+    upload_id: String,
+    parts: Vec<UploadPart>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResult {
+    size: u64,
+    checksum: String,
+}
+
+/// The source or destination of a copy.
+#[derive(Debug, PartialEq)]
+enum Location {
+    Local(PathBuf),
+    /// The object of the session; the key is the file name if not set.
+    Object {
+        session: String,
+        key: Option<String>,
+    },
+}
+
+impl Location {
+    fn parse(location: &str) -> Result<Self, FlameError> {
+        let Some(path) = location.strip_prefix(FLAME_SCHEME) else {
+            return Ok(Location::Local(PathBuf::from(location)));
+        };
+
+        let (session, key) = match path.split_once('/') {
+            Some((session, key)) => (session, Some(key).filter(|key| !key.is_empty())),
+            None => (path, None),
+        };
+        if session.is_empty() {
+            return Err(FlameError::InvalidConfig(format!(
+                "no session in <{location}>"
+            )));
+        }
+        if let Some(key) = key {
+            validate_key(key)?;
+        }
+
+        Ok(Location::Object {
+            session: session.to_string(),
+            key: key.map(str::to_string),
+        })
+    }
+}
+
+/// The key is the object id in the session, so it's a single path segment.
+fn validate_key(key: &str) -> Result<(), FlameError> {
+    if key.contains('/') || key.contains('\\') || key.contains("..") {
+        return Err(FlameError::InvalidConfig(format!(
+            "invalid object key <{key}>: it must not contain '/', '\\' or '..'"
+        )));
+    }
+    Ok(())
+}
+
+/// Copies the local file to the object of the session, or the reverse; the
+/// multi-part upload of the id is resumed if set.
+pub async fn run(
+    ctx: &FlameContext,
+    src: &str,
+    dst: &str,
+    upload_id: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    match (Location::parse(src)?, Location::parse(dst)?) {
+        (Location::Local(path), Location::Object { session, key }) => {
+            let key = match key {
+                Some(key) => key,
+                None => file_name(&path)?,
+            };
+            validate_key(&key)?;
+            let key = format!("{session}/{key}");
+
+            let size = std::fs::metadata(&path)
+                .map_err(|e| {
+                    FlameError::InvalidConfig(format!("failed to read <{}>: {e}", path.display()))
+                })?
+                .len();
+            match upload_id.is_some() || size > PART_SIZE as u64 {
+                true => upload_parts(ctx, &path, &key, size, upload_id).await,
+                false => upload(ctx, &path, &key).await,
+            }
+        }
+        (Location::Object { .. }, Location::Local(_)) if upload_id.is_some() => {
+            Err(FlameError::InvalidConfig("--upload-id is only for uploads".to_string()).into())
+        }
+        (Location::Object { session, key }, Location::Local(path)) => {
+            let key = key.ok_or_else(|| {
+                FlameError::InvalidConfig(format!("no object key in <{src}>"))
+            })?;
+            // The object is copied into the directory by its key, as `cp`.
+            let path = match path.is_dir() {
+                true => path.join(&key),
+                false => path,
+            };
+            download(ctx, &format!("{session}/{key}"), &path).await
+        }
+        _ => Err(FlameError::InvalidConfig(format!(
+            "one of <{src}> and <{dst}> must be a local file and the other {FLAME_SCHEME}<session>/<key>"
+        ))
+        .into()),
+    }
+}
+
+async fn upload(ctx: &FlameContext, path: &Path, key: &str) -> Result<(), Box<dyn Error>> {
+    let (data, checksum) = read_file(path)?;
+
+    let mut client = connect(ctx).await?;
+    eprintln!(
+        "Uploading {} to {FLAME_SCHEME}{key} ...",
+        format_memory(data.len() as u64)
+    );
+    let flight_data = encode_object(key, &data)?;
+    let mut results = client
+        .do_put(futures::stream::iter(flight_data))
+        .await?
+        .into_inner();
+    results
+        .message()
+        .await?
+        .ok_or_else(|| FlameError::Internal(format!("no result of the upload of <{key}>")))?;
+
+    // The object is read back to verify what the cache stored.
+    let stored = fetch(&mut client, key, "Verifying").await?;
+    let stored_checksum = sha256(&stored);
+    if stored_checksum != checksum {
+        return Err(FlameError::Internal(format!(
+            "checksum mismatch of <{key}>: {checksum} uploaded, {stored_checksum} stored"
+        ))
+        .into());
+    }
+
+    println!(
+        "Copied {} to {FLAME_SCHEME}{key} ({}, sha256 {checksum})",
+        path.display(),
+        format_memory(data.len() as u64)
+    );
+    Ok(())
+}
+
+/// Uploads the file by parts; the upload of the id is resumed if set, i.e. the
+/// parts received by the cache with the same checksum are skipped.
+async fn upload_parts(
+    ctx: &FlameContext,
+    path: &Path,
+    key: &str,
+    size: u64,
+    upload_id: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut client = connect(ctx).await?;
+    let info: UploadInfo = match upload_id {
+        Some(upload_id) => parse_result(&action(&mut client, "UPLOAD_STATUS", upload_id).await?)?,
+        None => parse_result(&action(&mut client, "UPLOAD_INIT", key).await?)?,
+    };
+    if info.key != key {
+        return Err(FlameError::InvalidConfig(format!(
+            "upload <{}> is of <{}> but not <{key}>",
+            info.upload_id, info.key
+        ))
+        .into());
+    }
+    let received: HashMap<u32, String> = info
+        .parts
+        .into_iter()
+        .map(|part| (part.part_number, part.checksum))
+        .collect();
+
+    eprintln!(
+        "Uploading {} to {FLAME_SCHEME}{key} by upload <{}> ...",
+        format_memory(size),
+        info.upload_id
+    );
+    let failed = |e: io::Error| {
+        FlameError::InvalidConfig(format!("failed to read <{}>: {e}", path.display()))
+    };
+    let mut file = File::open(path).map_err(failed)?;
+
+    let mut progress = Progress::new("Uploading", Some(size));
+    let mut ctx = digest::Context::new(&digest::SHA256);
+    let mut parts = vec![];
+    let mut part = vec![0u8; PART_SIZE];
+    for part_number in 1.. {
+        let n = read_part(&mut file, &mut part).map_err(failed)?;
+        if n == 0 {
+            break;
+        }
+        let data = &part[..n];
+        ctx.update(data);
+        let checksum = sha256(data);
+
+        if received.get(&part_number) != Some(&checksum) {
+            upload_part(&mut client, &info.upload_id, part_number, &checksum, data)
+                .await
+                .map_err(|e| {
+                    FlameError::Network(format!(
+                        "failed to upload part {part_number} of <{key}>: {e}; resume it by `--upload-id {}`",
+                        info.upload_id
+                    ))
+                })?;
+        }
+        parts.push(UploadPart {
+            part_number,
+            checksum,
+        });
+        progress.advance(n as u64);
+    }
+    progress.finish();
+    let checksum = to_hex(ctx.finish().as_ref());
+
+    let request = CompleteUpload {
+        upload_id: info.upload_id.clone(),
+        parts,
+    };
+    let body = serde_json::to_string(&request)
+        .map_err(|e| FlameError::Internal(format!("failed to encode the upload: {e}")))?;
+    let result: UploadResult = parse_result(&action(&mut client, "UPLOAD_COMPLETE", body).await?)?;
+    if result.checksum != checksum {
+        return Err(FlameError::Internal(format!(
+            "checksum mismatch of <{key}>: {checksum} uploaded, {} stored",
+            result.checksum
+        ))
+        .into());
+    }
+
+    println!(
+        "Copied {} to {FLAME_SCHEME}{key} ({}, sha256 {checksum})",
+        path.display(),
+        format_memory(result.size)
+    );
+    Ok(())
+}
+
+/// Uploads the part, retried with a backoff if failed, e.g. by a flaky link.
+async fn upload_part(
+    client: &mut FlightServiceClient<Channel>,
+    upload_id: &str,
+    part_number: u32,
+    checksum: &str,
+    data: &[u8],
+) -> Result<(), FlameError> {
+    let body = format!(
+        "{upload_id}:{part_number}:{checksum}:{}",
+        base64::engine::general_purpose::STANDARD.encode(data)
+    );
+
+    let mut attempt = 1;
+    loop {
+        match action(client, "UPLOAD_PART", body.clone()).await {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt >= PART_ATTEMPTS => return Err(e),
+            Err(e) => {
+                tracing::warn!("Failed to upload part {part_number} (attempt {attempt}): {e}");
+                tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Runs the action of the object cache, and returns the body of its result.
+async fn action(
+    client: &mut FlightServiceClient<Channel>,
+    r#type: &str,
+    body: impl Into<String>,
+) -> Result<Vec<u8>, FlameError> {
+    let mut results = client
+        .do_action(Action {
+            r#type: r#type.to_string(),
+            body: body.into().into_bytes().into(),
+        })
+        .await?
+        .into_inner();
+    let result = results
+        .message()
+        .await?
+        .ok_or_else(|| FlameError::Internal(format!("no result of action <{}>", r#type)))?;
+
+    Ok(result.body.to_vec())
+}
+
+fn parse_result<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, FlameError> {
+    serde_json::from_slice(body)
+        .map_err(|e| FlameError::Internal(format!("invalid result of the object cache: {e}")))
+}
+
+/// Reads a part of the file, i.e. until the buffer is full or the end of the file.
+fn read_part(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match file.read(&mut buf[n..])? {
+            0 => break,
+            read => n += read,
+        }
+    }
+    Ok(n)
+}
+
+async fn download(ctx: &FlameContext, key: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut client = connect(ctx).await?;
+    let data = fetch(&mut client, key, "Downloading").await?;
+    let checksum = sha256(&data);
+
+    write_file(path, &data)?;
+
+    // The written file is read again, so a short write is not reported as copied.
+    let (_, written_checksum) = read_file(path)?;
+    if written_checksum != checksum {
+        return Err(FlameError::Internal(format!(
+            "checksum mismatch of <{}>: {checksum} downloaded, {written_checksum} written",
+            path.display()
+        ))
+        .into());
+    }
+
+    println!(
+        "Copied {FLAME_SCHEME}{key} to {} ({}, sha256 {checksum})",
+        path.display(),
+        format_memory(data.len() as u64)
+    );
+    Ok(())
+}
+
+/// Connects to the object cache of the current context.
+async fn connect(ctx: &FlameContext) -> Result<FlightServiceClient<Channel>, FlameError> {
+    let cache = ctx.get_current_context()?.cache.clone().unwrap_or_default();
+    let endpoint = cache.endpoint.ok_or_else(|| {
+        FlameError::InvalidConfig("no cache endpoint in the current context".to_string())
+    })?;
+    let url = Url::parse(&endpoint).map_err(|e| {
+        FlameError::InvalidConfig(format!("invalid cache endpoint <{endpoint}>: {e}"))
+    })?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| FlameError::InvalidConfig(format!("no host in <{endpoint}>")))?;
+    let port = url.port().unwrap_or(DEFAULT_CACHE_PORT);
+
+    let mut channel = match url.scheme() {
+        "grpc" => Endpoint::from_shared(format!("http://{host}:{port}")),
+        "grpcs" => Endpoint::from_shared(format!("https://{host}:{port}")),
+        scheme => {
+            return Err(FlameError::InvalidConfig(format!(
+                "invalid scheme <{scheme}> of cache endpoint <{endpoint}>"
+            )))
+        }
+    }
+    .map_err(|e| FlameError::InvalidConfig(e.to_string()))?;
+    if url.scheme() == "grpcs" {
+        let tls = cache.tls.unwrap_or_default();
+        if let Some(channel) = tls
+            .connect_with_policy(&format!("https://{host}:{port}"))
+            .await?
+        {
+            return Ok(FlightServiceClient::new(channel).max_decoding_message_size(usize::MAX));
+        }
+
+        let tls = tls.client_tls_config(host)?;
+        channel = channel
+            .tls_config(tls)
+            .map_err(|e| FlameError::InvalidConfig(e.to_string()))?;
+    }
+
+    let channel = channel
+        .connect()
+        .await
+        .map_err(|e| FlameError::Network(format!("failed to connect to <{endpoint}>: {e}")))?;
+
+    Ok(FlightServiceClient::new(channel).max_decoding_message_size(usize::MAX))
+}
+
+/// Gets the content of the object, i.e. its base with the deltas appended.
+async fn fetch(
+    client: &mut FlightServiceClient<Channel>,
+    key: &str,
+    label: &'static str,
+) -> Result<Vec<u8>, FlameError> {
+    let mut stream = client
+        .do_get(Ticket {
+            ticket: key.as_bytes().to_vec().into(),
+        })
+        .await?
+        .into_inner();
+
+    let mut progress = Progress::new(label, None);
+    let mut messages = vec![];
+    while let Some(message) = stream.message().await? {
+        progress.advance(message.data_body.len() as u64);
+        messages.push(message);
+    }
+    progress.finish();
+
+    decode_object(messages)
+}
+
+/// The messages of the object, i.e. one row of its version and data, which is
+/// uploaded to the key by the descriptor of the first message.
+fn encode_object(key: &str, data: &[u8]) -> Result<Vec<FlightData>, FlameError> {
+    let schema = Schema::new(vec![
+        Field::new("version", DataType::UInt64, false),
+        Field::new("data", DataType::Binary, false),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(UInt64Array::from(vec![0u64])),
+            Arc::new(BinaryArray::from(vec![data])),
+        ],
+    )
+    .map_err(|e| FlameError::Internal(format!("failed to encode <{key}>: {e}")))?;
+
+    let mut flight_data = batches_to_flight_data(&schema, vec![batch])
+        .map_err(|e| FlameError::Internal(format!("failed to encode <{key}>: {e}")))?;
+    if let Some(first) = flight_data.first_mut() {
+        first.flight_descriptor = Some(FlightDescriptor::new_path(vec![key.to_string()]));
+    }
+
+    Ok(flight_data)
+}
+
+fn decode_object(messages: Vec<FlightData>) -> Result<Vec<u8>, FlameError> {
+    let mut messages = messages.into_iter();
+    let schema = messages
+        .next()
+        .ok_or_else(|| FlameError::Internal("no schema of the object".to_string()))?;
+    let schema = Arc::new(
+        Schema::try_from(&schema)
+            .map_err(|e| FlameError::Internal(format!("invalid schema of the object: {e}")))?,
+    );
+
+    let mut data = vec![];
+    for message in messages.filter(|m| !m.data_body.is_empty()) {
+        let batch = flight_data_to_arrow_batch(&message, schema.clone(), &HashMap::new())
+            .map_err(|e| FlameError::Internal(format!("invalid data of the object: {e}")))?;
+        let column = batch
+            .column_by_name("data")
+            .ok_or_else(|| FlameError::Internal("no data column of the object".to_string()))?
+            .as_any();
+        // The data over 2GB is sent as LargeBinary, e.g. of a multi-part upload.
+        if let Some(column) = column.downcast_ref::<BinaryArray>() {
+            column
+                .iter()
+                .flatten()
+                .for_each(|v| data.extend_from_slice(v));
+        } else if let Some(column) = column.downcast_ref::<LargeBinaryArray>() {
+            column
+                .iter()
+                .flatten()
+                .for_each(|v| data.extend_from_slice(v));
+        } else {
+            return Err(FlameError::Internal(
+                "invalid data column of the object".to_string(),
+            ));
+        }
+    }
+
+    Ok(data)
+}
+
+/// Reads the file with its checksum.
+fn read_file(path: &Path) -> Result<(Vec<u8>, String), FlameError> {
+    let failed = |e: io::Error| {
+        FlameError::InvalidConfig(format!("failed to read <{}>: {e}", path.display()))
+    };
+    let mut file = File::open(path).map_err(failed)?;
+    let size = file.metadata().map_err(failed)?.len();
+
+    let mut progress = Progress::new("Reading", Some(size));
+    let mut ctx = digest::Context::new(&digest::SHA256);
+    let mut data = Vec::with_capacity(size as usize);
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut chunk).map_err(failed)?;
+        if n == 0 {
+            break;
+        }
+        ctx.update(&chunk[..n]);
+        data.extend_from_slice(&chunk[..n]);
+        progress.advance(n as u64);
+    }
+    progress.finish();
+
+    Ok((data, to_hex(ctx.finish().as_ref())))
+}
+
+fn write_file(path: &Path, data: &[u8]) -> Result<(), FlameError> {
+    let failed = |e: io::Error| {
+        FlameError::InvalidConfig(format!("failed to write <{}>: {e}", path.display()))
+    };
+    let mut file = File::create(path).map_err(failed)?;
+
+    let mut progress = Progress::new("Writing", Some(data.len() as u64));
+    for chunk in data.chunks(CHUNK_SIZE) {
+        file.write_all(chunk).map_err(failed)?;
+        progress.advance(chunk.len() as u64);
+    }
+    file.sync_all().map_err(failed)?;
+    progress.finish();
+
+    Ok(())
+}
+
+fn file_name(path: &Path) -> Result<String, FlameError> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| FlameError::InvalidConfig(format!("no file name of <{}>", path.display())))
+}
+
+fn sha256(data: &[u8]) -> String {
+    to_hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The progress of a step of the copy, shown on stderr if it's a terminal.
+struct Progress {
+    label: &'static str,
+    total: Option<u64>,
+    done: u64,
+    enabled: bool,
+}
+
+impl Progress {
+    fn new(label: &'static str, total: Option<u64>) -> Self {
+        Progress {
+            label,
+            total,
+            done: 0,
+            enabled: io::stderr().is_terminal(),
+        }
+    }
+
+    fn advance(&mut self, n: u64) {
+        self.done += n;
+        if !self.enabled {
+            return;
+        }
+
+        match self.total.filter(|total| *total > 0) {
+            Some(total) => eprint!(
+                "\r{}: {} / {} ({}%)",
+                self.label,
+                format_memory(self.done),
+                format_memory(total),
+                self.done * 100 / total
+            ),
+            None => eprint!("\r{}: {}", self.label, format_memory(self.done)),
+        }
+    }
+
+    fn finish(&self) {
+        if self.enabled && self.done > 0 {
+            eprintln!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(
+            Location::parse("data.csv").unwrap(),
+            Location::Local(PathBuf::from("data.csv"))
+        );
+        assert_eq!(
+            Location::parse("flame://ssn-1/data.csv").unwrap(),
+            Location::Object {
+                session: "ssn-1".to_string(),
+                key: Some("data.csv".to_string()),
+            }
+        );
+        assert_eq!(
+            Location::parse("flame://ssn-1/").unwrap(),
+            Location::Object {
+                session: "ssn-1".to_string(),
+                key: None,
+            }
+        );
+        assert!(Location::parse("flame:///data.csv").is_err());
+        assert!(Location::parse("flame://ssn-1/dir/data.csv").is_err());
+        assert!(Location::parse("flame://ssn-1/..").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_object() {
+        let data = b"a,b\n1,2\n".to_vec();
+        let flight_data = encode_object("ssn-1/data.csv", &data).unwrap();
+
+        let descriptor = flight_data[0].flight_descriptor.as_ref().unwrap();
+        assert_eq!(descriptor.path, vec!["ssn-1/data.csv".to_string()]);
+        assert_eq!(decode_object(flight_data).unwrap(), data);
+    }
+
+    #[test]
+    fn test_file_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        let data = vec![7u8; CHUNK_SIZE + 10];
+
+        write_file(&path, &data).unwrap();
+        let (read, checksum) = read_file(&path).unwrap();
+        assert_eq!(read, data);
+        assert_eq!(checksum, sha256(&data));
+        assert_eq!(
+            sha256(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_read_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.bin");
+        std::fs::write(&path, vec![7u8; 10]).unwrap();
+
+        // The parts are full except the last one.
+        let mut file = File::open(&path).unwrap();
+        let mut part = vec![0u8; 4];
+        let sizes: Vec<usize> = std::iter::from_fn(|| {
+            Some(read_part(&mut file, &mut part).unwrap()).filter(|n| *n > 0)
+        })
+        .collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+    }
+}
@@ -13,32 +13,63 @@ limitations under the License.
 
 use std::error::Error;
 
-use flame_rs as flame;
-use flame_rs::{apis::FlameContext, client::SessionAttributes};
-
-pub async fn run(
-    ctx: &FlameContext,
-    app: &str,
-    slots: &u32,
-    batch_size: &u32,
-) -> Result<(), Box<dyn Error>> {
-    let current_ctx = ctx.get_current_context()?;
-    let conn = flame::client::connect_with_tls(
-        &current_ctx.cluster.endpoint,
-        current_ctx.cluster.tls.as_ref(),
-    )
-    .await?;
+use clap::ValueEnum;
+use flame_rs::apis::{FlameContext, ResultPolicy};
+use flame_rs::client::{federation::Federation, SessionAttributes};
+
+/// What happens to the outputs of the completed tasks of the session.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ResultStorage {
+    /// Keep the outputs in the storage of the session manager
+    #[default]
+    Inline,
+    /// Deliver the outputs to the watchers of the tasks without persisting them
+    Discard,
+    /// Put the outputs into the object cache, and keep their references instead
+    ObjectCache,
+}
+
+impl From<ResultStorage> for ResultPolicy {
+    fn from(storage: ResultStorage) -> Self {
+        match storage {
+            ResultStorage::Inline => ResultPolicy::Inline,
+            ResultStorage::Discard => ResultPolicy::Discard,
+            ResultStorage::ObjectCache => ResultPolicy::ObjectCache,
+        }
+    }
+}
+
+pub struct CreateOptions {
+    pub app: String,
+    pub slots: u32,
+    pub batch_size: u32,
+    /// The max executors of the session; only the limit of the application if None.
+    pub max_instances: Option<u32>,
+    pub priority: u32,
+    pub results: ResultStorage,
+    pub ttl: Option<u64>,
+}
+
+pub async fn run(ctx: &FlameContext, opts: &CreateOptions) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
     let attr = SessionAttributes {
-        id: format!("{app}-{}", stdng::rand::short_name()),
-        application: app.to_owned(),
-        slots: *slots,
+        id: format!("{}-{}", opts.app, stdng::rand::short_name()).into(),
+        application: opts.app.clone(),
+        slots: opts.slots,
         common_data: None,
         min_instances: 0,
-        max_instances: None,
-        batch_size: *batch_size,
+        max_instances: opts.max_instances,
+        batch_size: opts.batch_size,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: opts.priority,
+        result_policy: opts.results.into(),
+        ttl_after_completion: opts.ttl,
     };
 
-    let ssn = conn.create_session(&attr).await?;
+    let ssn = federation.create_session(&attr).await?;
 
     println!("Session <{}> was created.", ssn.id);
 
@@ -0,0 +1,324 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use chrono::Local;
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs as flame;
+use flame_rs::apis::{FlameContext, SessionID, TaskID};
+use flame_rs::client::{ClusterSnapshot, NodeState, SchedulerState, TaskPostmortem, TaskSettings};
+
+use crate::utils::{format_memory, parse_time};
+
+/// Prints what the cluster looked like at the time, i.e. the latest snapshot
+/// persisted by the scheduler at or before it; the session manager keeps the
+/// snapshots if `cluster.history` is configured.
+pub async fn history(
+    ctx: &FlameContext,
+    at: &Option<String>,
+    output_format: &Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let at = at.as_deref().map(parse_time).transpose()?;
+
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
+    let snapshot = conn.get_cluster_snapshot(at).await?;
+
+    match output_format.as_deref() {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+        _ => view_snapshot(&snapshot),
+    }
+
+    Ok(())
+}
+
+fn view_snapshot(snapshot: &ClusterSnapshot) {
+    println!(
+        "{:<15}{}",
+        "Snapshot:",
+        snapshot
+            .creation_time
+            .with_timezone(&Local)
+            .format("%F %T%.3f")
+    );
+
+    println!("{:<15}", "Nodes:");
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(vec!["NAME", "STATUS", "CPU", "MEMORY"]);
+    for node in &snapshot.nodes {
+        let status = match node.state {
+            NodeState::Ready => "Ready",
+            NodeState::NotReady => "NotReady",
+            NodeState::Lost => "Lost",
+            NodeState::Unknown => "Unknown",
+        };
+        table.add_row(vec![
+            node.name.to_string(),
+            status.to_string(),
+            node.allocatable_cpu.to_string(),
+            format_memory(node.allocatable_memory),
+        ]);
+    }
+    println!("{table}");
+
+    println!("{:<15}", "Sessions:");
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "ID", "State", "App", "Slots", "Pending", "Running", "Succeed", "Failed",
+    ]);
+    for ssn in &snapshot.sessions {
+        table.add_row(vec![
+            ssn.id.to_string(),
+            ssn.state.to_string(),
+            ssn.application.to_string(),
+            ssn.slots.to_string(),
+            ssn.pending.to_string(),
+            ssn.running.to_string(),
+            ssn.succeed.to_string(),
+            ssn.failed.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    println!("{:<15}", "Executors:");
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(vec!["ID", "State", "Session", "Slots", "Node"]);
+    for executor in &snapshot.executors {
+        table.add_row(vec![
+            executor.id.to_string(),
+            executor.state.to_string(),
+            executor.session_id.clone().unwrap_or("-".to_string()),
+            executor.slots.to_string(),
+            executor.node.to_string(),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// Prints the state of the scheduler plugins in the latest scheduling cycle:
+/// the order and the fairshare of the open sessions, and the verdicts of each
+/// plugin on them, e.g. to find the plugin which keeps a session pending.
+pub async fn scheduler(
+    ctx: &FlameContext,
+    session: Option<&SessionID>,
+    output_format: &Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
+    let mut state = conn.get_scheduler_state().await?;
+    if let Some(session) = session {
+        state.sessions.retain(|ssn| &ssn.session_id == session);
+    }
+
+    match output_format.as_deref() {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&state)?),
+        _ => view_scheduler_state(&state),
+    }
+
+    Ok(())
+}
+
+fn view_scheduler_state(state: &SchedulerState) {
+    let verdict = |v: Option<bool>| match v {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => "-".to_string(),
+    };
+    let share = |v: Option<f64>| v.map(|v| format!("{v:.1}")).unwrap_or("-".to_string());
+
+    println!(
+        "{:<15}{}",
+        "Cycle:",
+        state
+            .creation_time
+            .with_timezone(&Local)
+            .format("%F %T%.3f")
+    );
+    println!("{:<15}{}", "Plugins:", state.plugins.join(", "));
+
+    println!("{:<15}", "Sessions:");
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "RANK",
+        "ID",
+        "DESIRED",
+        "DESERVED",
+        "ALLOCATED",
+        "ELASTIC",
+        "UNDERUSED",
+        "PREEMPTIBLE",
+        "READY",
+    ]);
+    for ssn in &state.sessions {
+        let fairshare = ssn.share.as_ref();
+        table.add_row(vec![
+            ssn.rank.to_string(),
+            ssn.session_id.to_string(),
+            share(fairshare.map(|s| s.desired)),
+            share(fairshare.map(|s| s.deserved)),
+            share(fairshare.map(|s| s.allocated)),
+            share(fairshare.map(|s| s.elastic)),
+            verdict(Some(ssn.underused)),
+            verdict(Some(ssn.preemptible)),
+            verdict(Some(ssn.ready)),
+        ]);
+    }
+    println!("{table}");
+
+    println!("{:<15}", "Verdicts:");
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "SESSION",
+        "PLUGIN",
+        "UNDERUSED",
+        "PREEMPTIBLE",
+        "READY",
+    ]);
+    for ssn in &state.sessions {
+        for v in &ssn.verdicts {
+            table.add_row(vec![
+                ssn.session_id.to_string(),
+                v.plugin.clone(),
+                verdict(v.underused),
+                verdict(v.preemptible),
+                verdict(v.ready),
+            ]);
+        }
+    }
+    println!("{table}");
+}
+
+/// Prints the effective settings of the task, or of the tasks of the session
+/// without their own settings, with the level each one is resolved from, i.e.
+/// the cluster, the application, the session or the task.
+pub async fn settings(
+    ctx: &FlameContext,
+    session: &SessionID,
+    task: Option<&TaskID>,
+) -> Result<(), Box<dyn Error>> {
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?;
+    let settings = conn.get_task_settings(session, task).await?;
+
+    view_settings(&settings);
+
+    Ok(())
+}
+
+fn view_settings(settings: &TaskSettings) {
+    println!("{:<15}{}", "Session:", settings.session_id);
+    if let Some(task_id) = &settings.task_id {
+        println!("{:<15}{}", "Task:", task_id);
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(vec!["SETTING", "VALUE", "SOURCE"]);
+    table.add_row(vec![
+        "timeout".to_string(),
+        settings
+            .timeout
+            .map(|secs| format!("{secs}s"))
+            .unwrap_or("-".to_string()),
+        settings.timeout_source.clone(),
+    ]);
+    table.add_row(vec![
+        "max_attempts".to_string(),
+        settings.max_attempts.to_string(),
+        settings.max_attempts_source.clone(),
+    ]);
+    table.add_row(vec![
+        "priority".to_string(),
+        settings.priority.to_string(),
+        settings.priority_source.clone(),
+    ]);
+    println!("{table}");
+}
+
+/// Prints the postmortem of the latest crash of the instance which ran the
+/// task, collected by the executor manager when the instance exited.
+pub async fn postmortem(
+    ctx: &FlameContext,
+    session: &SessionID,
+    task: &TaskID,
+) -> Result<(), Box<dyn Error>> {
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?;
+    let ssn = conn.get_session(session).await?;
+    let task = ssn.get_task(task).await?;
+
+    match &task.postmortem {
+        Some(postmortem) => view_postmortem(postmortem),
+        None => println!("No postmortem of task <{session}/{}>.", task.id),
+    }
+
+    Ok(())
+}
+
+fn view_postmortem(postmortem: &TaskPostmortem) {
+    println!(
+        "{:<15}{}",
+        "Time:",
+        postmortem.creation_time.with_timezone(&Local)
+    );
+    println!("{:<15}{}", "Executor:", postmortem.executor_id);
+    println!("{:<15}{}", "Node:", postmortem.node);
+    let exit = match (postmortem.exit_code, postmortem.signal) {
+        (Some(code), _) => format!("code {code}"),
+        (None, Some(signal)) => format!("signal {signal}"),
+        (None, None) => "unknown".to_string(),
+    };
+    println!("{:<15}{}", "Exit:", exit);
+    println!(
+        "{:<15}{}",
+        "Core dumped:",
+        if postmortem.core_dumped { "yes" } else { "no" }
+    );
+    if let Some(load_average) = postmortem.load_average {
+        println!("{:<15}{:.2}", "Load average:", load_average);
+    }
+    if let Some(memory) = postmortem.memory_available {
+        println!("{:<15}{}", "Memory avail:", format_memory(memory));
+    }
+    if let Some(tail) = &postmortem.stderr_tail {
+        println!("{:<15}", "Stderr:");
+        for line in tail.lines() {
+            println!("  {line}");
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use chrono::Utc;
+use clap::ValueEnum;
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs as flame;
+use flame_rs::apis::{EventOwnerKind, FlameContext, SessionID, TaskID};
+use flame_rs::client::EventFilter;
+
+use crate::utils::parse_duration;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum EventOwner {
+    Session,
+    Task,
+}
+
+impl From<EventOwner> for EventOwnerKind {
+    fn from(owner: EventOwner) -> Self {
+        match owner {
+            EventOwner::Session => EventOwnerKind::Session,
+            EventOwner::Task => EventOwnerKind::Task,
+        }
+    }
+}
+
+pub struct EventsOptions {
+    pub session: SessionID,
+    pub task: Option<TaskID>,
+    pub codes: Vec<i32>,
+    pub since: Option<String>,
+    pub owner: Option<EventOwner>,
+    pub limit: Option<usize>,
+}
+
+/// Lists the events of the session and its tasks page by page, ordered by
+/// their creation time.
+pub async fn run(ctx: &FlameContext, opts: &EventsOptions) -> Result<(), Box<dyn Error>> {
+    let since = opts.since.as_deref().map(parse_duration).transpose()?;
+    let filter = EventFilter {
+        since: since.map(|d| Utc::now() - d),
+        until: None,
+        codes: opts.codes.clone(),
+        owner: opts.owner.map(EventOwnerKind::from),
+        task_id: opts.task.clone(),
+    };
+
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?;
+    let ssn = conn.get_session(&opts.session).await?;
+
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(vec!["Time", "Task", "Code", "Message"]);
+
+    let mut count = 0;
+    let mut page_token = None;
+    'pages: loop {
+        let page = ssn
+            .list_events(&filter, None, page_token.as_deref())
+            .await?;
+        for e in page.events {
+            if opts.limit.is_some_and(|limit| count >= limit) {
+                break 'pages;
+            }
+            table.add_row(vec![
+                e.event.creation_time.format("%F %T").to_string(),
+                e.task_id.map(String::from).unwrap_or("-".to_string()),
+                e.event.code.to_string(),
+                e.event.message.unwrap_or_default(),
+            ]);
+            count += 1;
+        }
+
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use flame_rs::apis::{FlameContext, FlameError, ResultPolicy, SessionID, TaskState};
+use flame_rs::client::{federation::Federation, SessionAttributes, Task};
+
+use crate::utils::{parse_duration, read_input};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The exit code of `flmctl exec` if the task is not completed in time, as the
+/// `timeout` command.
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+pub struct ExecOptions {
+    pub app: String,
+    pub input: Option<String>,
+    pub session: Option<SessionID>,
+    pub timeout: Option<String>,
+}
+
+/// Runs one task synchronously, prints its output to stdout, and returns the
+/// exit code of its state.
+pub async fn run(ctx: &FlameContext, opts: &ExecOptions) -> Result<i32, Box<dyn Error>> {
+    let input = opts.input.as_deref().map(read_input).transpose()?;
+    let timeout = opts.timeout.as_deref().map(parse_duration).transpose()?;
+
+    let federation = Federation::connect(ctx).await?;
+    let (ssn, created) = match &opts.session {
+        Some(id) => (federation.get_session(id).await?, false),
+        None => {
+            let attr = SessionAttributes {
+                id: format!("{}-{}", opts.app, stdng::rand::short_name()).into(),
+                application: opts.app.clone(),
+                slots: 0,
+                common_data: None,
+                min_instances: 0,
+                max_instances: None,
+                batch_size: 0,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: ResultPolicy::Inline,
+                ttl_after_completion: None,
+            };
+            (federation.create_session(&attr).await?, true)
+        }
+    };
+
+    let task = ssn.create_task(input).await?;
+    let completed = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, wait_task(&ssn, &task))
+            .await
+            .ok(),
+        None => Some(wait_task(&ssn, &task).await),
+    };
+
+    // The session is closed even if the task is not completed in time.
+    if created {
+        ssn.close().await?;
+    }
+
+    let Some(task) = completed.transpose()? else {
+        eprintln!(
+            "Task <{}/{}> was not completed in {}.",
+            task.ssn_id,
+            task.id,
+            opts.timeout.as_deref().unwrap_or_default()
+        );
+        return Ok(TIMEOUT_EXIT_CODE);
+    };
+
+    if let Some(output) = &task.output {
+        io::stdout().write_all(output)?;
+    }
+    if !task.is_succeed() {
+        eprintln!(
+            "Task <{}/{}> is <{}>: {}",
+            task.ssn_id,
+            task.id,
+            task.state,
+            task.events
+                .last()
+                .and_then(|e| e.message.clone())
+                .unwrap_or_default()
+        );
+    }
+
+    Ok(exit_code(task.state))
+}
+
+async fn wait_task(ssn: &flame_rs::client::Session, task: &Task) -> Result<Task, FlameError> {
+    loop {
+        let task = ssn.get_task(&task.id).await?;
+        if task.is_completed() {
+            return Ok(task);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn exit_code(state: TaskState) -> i32 {
+    match state {
+        TaskState::Succeed => 0,
+        TaskState::Failed => 1,
+        TaskState::Cancelled => 2,
+        TaskState::Quarantined => 3,
+        TaskState::Pending | TaskState::Running => TIMEOUT_EXIT_CODE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(TaskState::Succeed), 0);
+        assert_eq!(exit_code(TaskState::Failed), 1);
+        assert_eq!(exit_code(TaskState::Quarantined), 3);
+    }
+}
@@ -0,0 +1,190 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+use std::time::Duration;
+
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs::apis::{CompletionPolicy, FlameContext, FlameError, JobState, SessionID};
+use flame_rs::client::{federation::Federation, JobAttributes};
+
+use crate::submit::{collect_inputs, InputOptions};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct CreateOptions {
+    pub app: String,
+    pub slots: u32,
+    pub inputs: InputOptions,
+    pub count: Option<u32>,
+    pub parallelism: Option<u32>,
+    pub any: bool,
+    pub max_failed: u32,
+    pub wait: bool,
+}
+
+/// Creates the job, whose tasks are submitted and tracked by the session
+/// manager; it waits for the job by `wait`, and returns the exit code of its
+/// state, or 0 if not waiting.
+pub async fn create(ctx: &FlameContext, opts: &CreateOptions) -> Result<i32, Box<dyn Error>> {
+    let inputs: Vec<_> = collect_inputs(&opts.inputs)?
+        .into_iter()
+        .map(|(_, input)| input)
+        .collect();
+    if inputs.is_empty() && opts.count.is_none() {
+        return Err(FlameError::InvalidConfig("no input or count of the job".to_string()).into());
+    }
+
+    let federation = Federation::connect(ctx).await?;
+    let attrs = JobAttributes {
+        id: format!("{}-{}", opts.app, stdng::rand::short_name()).into(),
+        application: opts.app.clone(),
+        slots: opts.slots,
+        inputs,
+        count: opts.count,
+        parallelism: opts.parallelism,
+        completion_policy: match opts.any {
+            true => CompletionPolicy::AnyTask,
+            false => CompletionPolicy::AllTasks,
+        },
+        max_failed: opts.max_failed,
+        common_data: None,
+    };
+    let job = federation.create_job(&attrs).await?;
+    println!("Job <{}> was created with {} task(s).", job.id, job.total);
+
+    if !opts.wait {
+        return Ok(0);
+    }
+
+    let mut progress = (job.succeed, job.failed);
+    let job = loop {
+        let job = federation.get_job(&job.id).await?;
+        if job.state.is_terminal() {
+            break job;
+        }
+        if progress != (job.succeed, job.failed) {
+            progress = (job.succeed, job.failed);
+            println!(
+                "Job <{}>: {}/{} succeed, {} failed.",
+                job.id, job.succeed, job.total, job.failed
+            );
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    };
+
+    match &job.message {
+        Some(message) => println!("Job <{}> is <{}>: {message}", job.id, job.state),
+        None => println!("Job <{}> is <{}>.", job.id, job.state),
+    }
+
+    Ok(exit_code(job.state))
+}
+
+pub async fn view(ctx: &FlameContext, id: &SessionID) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let job = federation.get_job(id).await?;
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING);
+
+    table.add_row(vec!["Job:", job.id.as_str()]);
+    table.add_row(vec!["Application:", &job.application]);
+    table.add_row(vec!["Session:", job.session_id.as_str()]);
+    table.add_row(vec!["State:", &job.state.to_string()]);
+    if let Some(message) = &job.message {
+        table.add_row(vec!["Message:", message]);
+    }
+    table.add_row(vec!["Completion:", &job.completion_policy.to_string()]);
+    table.add_row(vec![
+        "Parallelism:",
+        &job.parallelism
+            .map(|p| p.to_string())
+            .unwrap_or("-".to_string()),
+    ]);
+    table.add_row(vec!["Max Failed:", &job.max_failed.to_string()]);
+    table.add_row(vec![
+        "Tasks:",
+        &format!(
+            "{} total, {} submitted, {} succeed, {} failed",
+            job.total, job.submitted, job.succeed, job.failed
+        ),
+    ]);
+    table.add_row(vec![
+        "Creation Time:",
+        &job.creation_time.format("%T").to_string(),
+    ]);
+    if let Some(completion_time) = job.completion_time {
+        table.add_row(vec![
+            "Completion Time:",
+            &completion_time.format("%T").to_string(),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+pub async fn list(ctx: &FlameContext) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let mut jobs = federation.list_job().await?;
+    jobs.sort_by_key(|job| job.creation_time);
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "ID",
+        "State",
+        "App",
+        "Total",
+        "Submitted",
+        "Succeed",
+        "Failed",
+        "Created",
+    ]);
+    for job in &jobs {
+        table.add_row(vec![
+            job.id.to_string(),
+            job.state.to_string(),
+            job.application.to_string(),
+            job.total.to_string(),
+            job.submitted.to_string(),
+            job.succeed.to_string(),
+            job.failed.to_string(),
+            job.creation_time.format("%T").to_string(),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Cancels the job, whose remaining tasks are cancelled by closing its session.
+pub async fn cancel(ctx: &FlameContext, id: &SessionID) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let job = federation.cancel_job(id).await?;
+    println!("Job <{}> is <{}>.", job.id, job.state);
+
+    Ok(())
+}
+
+fn exit_code(state: JobState) -> i32 {
+    match state {
+        JobState::Succeed => 0,
+        JobState::Failed => 1,
+        JobState::Cancelled => 2,
+        JobState::Running => 124,
+    }
+}
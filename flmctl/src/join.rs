@@ -0,0 +1,48 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use flame_rs as flame;
+use flame_rs::apis::FlameContext;
+
+use crate::utils::parse_duration;
+
+/// Mints a one-time join token for a node, which is passed to its executor
+/// manager by `FLAME_JOIN_TOKEN`.
+pub async fn run(
+    ctx: &FlameContext,
+    node: &Option<String>,
+    ttl: &Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let ttl = ttl.as_deref().map(parse_duration).transpose()?;
+
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
+
+    let token = conn.create_join_token(node.as_deref(), ttl).await?;
+
+    println!("{}", token.token);
+    eprintln!(
+        "The token joins node <{}> once, and expires at {}.",
+        token.node_name.as_deref().unwrap_or("*"),
+        token.expiration_time.format("%F %T")
+    );
+
+    Ok(())
+}
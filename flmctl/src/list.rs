@@ -18,9 +18,10 @@ use comfy_table::presets::NOTHING;
 use comfy_table::Table;
 use flame_rs as flame;
 use flame_rs::apis::{FlameContext, FlameError, SessionState};
+use flame_rs::client::federation::Federation;
 use flame_rs::client::{Connection, NodeState};
 
-use crate::utils::format_memory;
+use crate::utils::{format_executors, format_memory, format_saturation};
 
 pub async fn run(
     ctx: &FlameContext,
@@ -29,23 +30,26 @@ pub async fn run(
     executor: bool,
     node: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let current_ctx = ctx.get_current_context()?;
-    let conn = flame::client::connect_with_tls(
-        &current_ctx.cluster.endpoint,
-        current_ctx.cluster.tls.as_ref(),
-    )
-    .await?;
     match (application, session, executor, node) {
-        (true, _, _, _) => list_application(conn).await,
-        (_, true, _, _) => list_session(conn).await,
-        (_, _, true, _) => list_executor(conn).await,
-        (_, _, _, true) => list_node(conn).await,
+        (true, _, _, _) => list_application(connect(ctx).await?).await,
+        (_, true, _, _) => list_session(Federation::connect(ctx).await?).await,
+        (_, _, true, _) => list_executor(connect(ctx).await?).await,
+        (_, _, _, true) => list_node(connect(ctx).await?).await,
         _ => Err(Box::new(FlameError::InvalidConfig(
             "unsupported parameters".to_string(),
         ))),
     }
 }
 
+async fn connect(ctx: &FlameContext) -> Result<Connection, FlameError> {
+    let current_ctx = ctx.get_current_context()?;
+    flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await
+}
+
 async fn list_application(conn: Connection) -> Result<(), Box<dyn Error>> {
     let app_list = conn.list_application().await?;
 
@@ -73,8 +77,8 @@ async fn list_application(conn: Connection) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn list_session(conn: Connection) -> Result<(), Box<dyn Error>> {
-    let mut ssn_list = conn.list_session().await?;
+async fn list_session(federation: Federation) -> Result<(), Box<dyn Error>> {
+    let mut ssn_list = federation.list_session().await?;
     let mut table = Table::new();
     table.load_preset(NOTHING).set_header(vec![
         "ID", "State", "App", "Slots", "Pending", "Running", "Succeed", "Failed", "Created",
@@ -95,7 +99,10 @@ async fn list_session(conn: Connection) -> Result<(), Box<dyn Error>> {
     for ssn in &ssn_list {
         table.add_row(vec![
             ssn.id.to_string(),
-            ssn.state.to_string(),
+            match ssn.unschedulable {
+                Some(_) => format!("{} (Unschedulable)", ssn.state),
+                None => ssn.state.to_string(),
+            },
             ssn.application.to_string(),
             ssn.slots.to_string(),
             ssn.pending.to_string(),
@@ -137,13 +144,22 @@ async fn list_node(conn: Connection) -> Result<(), Box<dyn Error>> {
     let node_list = conn.list_node().await?;
     let mut table = Table::new();
     table.load_preset(NOTHING).set_header(vec![
-        "NAME", "HOSTNAME", "STATUS", "CPU", "MEMORY", "ARCH", "OS",
+        "NAME",
+        "HOSTNAME",
+        "STATUS",
+        "CPU",
+        "MEMORY",
+        "SATURATION",
+        "EXECUTORS",
+        "ARCH",
+        "OS",
     ]);
 
     for node in &node_list {
         let status = match node.state {
             NodeState::Ready => "Ready",
             NodeState::NotReady => "NotReady",
+            NodeState::Lost => "Lost",
             NodeState::Unknown => "Unknown",
         };
         table.add_row(vec![
@@ -152,6 +168,8 @@ async fn list_node(conn: Connection) -> Result<(), Box<dyn Error>> {
             status.to_string(),
             node.cpu.to_string(),
             format_memory(node.memory),
+            format_saturation(node.saturation),
+            format_executors(node.executors, node.max_executors),
             node.arch.to_string(),
             node.os.to_string(),
         ]);
@@ -16,17 +16,33 @@ use std::io;
 
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
-use flame_rs::apis::FlameContext;
+use flame_rs::apis::{FlameContext, SessionID, TaskID};
 
 mod apis;
+mod apply;
 mod close;
+mod config;
+mod cp;
 mod create;
+mod debug;
+mod disable;
+mod enable;
+mod events;
+mod exec;
 mod helper;
+mod job;
+mod join;
 mod list;
 mod migrate;
 mod register;
+mod release;
+mod resubmit;
+mod schema;
+mod submit;
+mod top;
 mod unregister;
 mod update;
+mod usage;
 mod utils;
 mod view;
 
@@ -54,11 +70,11 @@ enum Commands {
 
         /// The id of session
         #[arg(short, long)]
-        session: Option<String>,
+        session: Option<SessionID>,
 
         /// The id of task
         #[arg(short, long)]
-        task: Option<String>,
+        task: Option<TaskID>,
 
         /// The name of node
         #[arg(short, long)]
@@ -67,6 +83,14 @@ enum Commands {
         /// The output format of the view
         #[arg(short, long)]
         output_format: Option<String>,
+
+        /// Print the full JSON schemas of the application
+        #[arg(long, requires = "application")]
+        schema: bool,
+
+        /// Validate the JSON payload in the file against the application's input schema
+        #[arg(long, requires = "application")]
+        validate_file: Option<String>,
     },
     /// Update the object of Flame
     Update {
@@ -89,23 +113,155 @@ enum Commands {
         #[arg(short, long)]
         node: bool,
     },
-    /// Close the session in Flame
+    /// Close the session in Flame; it's closing until its running tasks are completed
     Close {
         /// The id of session
         #[arg(short, long)]
-        session: String,
+        session: SessionID,
+        /// Cancel the running tasks instead of waiting for them
+        #[arg(long)]
+        force: bool,
+        /// Wait until the running tasks are completed and the session is closed
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Release the quarantined task back to pending
+    Release {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// The id of task
+        #[arg(short, long)]
+        task: TaskID,
+    },
+    /// Create a session with the attributes of the session and resubmit its tasks
+    Resubmit {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// Only resubmit the failed tasks
+        #[arg(long)]
+        only_failed: bool,
+    },
+    /// Mint a one-time token for a node to join the cluster
+    JoinToken {
+        /// The name of the node which joins with the token; any node if not set
+        #[arg(short, long)]
+        node: Option<String>,
+        /// The time to live of the token, e.g. 10m or 1h; the default of the cluster if not set
+        #[arg(short, long)]
+        ttl: Option<String>,
     },
     /// Create a session in Flame
     Create {
         /// The name of Application
         #[arg(short, long)]
         app: String,
-        /// The slots requirements of each task
-        #[arg(short, long)]
+        /// The slots requirements of each task (0 means the application's default)
+        #[arg(short, long, default_value = "0")]
         slots: u32,
-        /// Number of executors per batch for gang scheduling
-        #[arg(short, long, default_value = "1")]
+        /// Number of executors per batch for gang scheduling (0 means the application's default)
+        #[arg(short, long, default_value = "0")]
         batch_size: u32,
+        /// The max executors of the session, i.e. its instances; only the application's limit if not set
+        #[arg(long, visible_alias = "max-executors")]
+        max_instances: Option<u32>,
+        /// The priority of the session in scheduling, the higher the earlier
+        #[arg(long, default_value = "0")]
+        priority: u32,
+        /// What happens to the outputs of the completed tasks
+        #[arg(long, value_enum, default_value_t)]
+        results: create::ResultStorage,
+        /// Seconds to keep the session after it's closed, then it's deleted; forever if not set
+        #[arg(long)]
+        ttl: Option<u64>,
+    },
+    /// Copy a file to or from an object of a session in the object cache
+    Cp {
+        /// The source, i.e. a local file or flame://<session>/<key>
+        src: String,
+        /// The destination, i.e. flame://<session>/[key] or a local file or directory
+        dst: String,
+        /// Resume the multi-part upload of a large file, i.e. only upload the parts not received
+        #[arg(long)]
+        upload_id: Option<String>,
+    },
+    /// Run a task synchronously, print its output and exit with its state
+    Exec {
+        /// The name of Application
+        #[arg(short, long)]
+        app: String,
+        /// The input of the task, or the file of the input with `@`, e.g. @input.json
+        #[arg(short, long)]
+        input: Option<String>,
+        /// The id of the session to reuse; a new session is created and closed if not set
+        #[arg(short, long)]
+        session: Option<SessionID>,
+        /// The time to wait for the task, e.g. 60s or 5m; wait forever if not set
+        #[arg(short, long)]
+        timeout: Option<String>,
+    },
+    /// Submit tasks to a session without waiting for them, one task per input
+    Submit {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// The input of a task, or the file of the input with `@`, e.g. @input.json; it can be repeated
+        #[arg(short, long)]
+        input: Vec<String>,
+        /// The directory of the inputs, one task per file
+        #[arg(long)]
+        inputs_dir: Option<String>,
+        /// The template of the inputs with placeholders, e.g. {{ name }}, rendered with each row of --values
+        #[arg(long, requires = "values")]
+        template: Option<String>,
+        /// The CSV file whose header names the placeholders of --template, one task per row
+        #[arg(long, requires = "template")]
+        values: Option<String>,
+    },
+    /// Run a set of tasks to completion in a session managed by the session manager
+    Job {
+        #[command(subcommand)]
+        command: JobCommands,
+    },
+    /// Show the resource usage of the tasks per application and session
+    Usage {
+        /// The name of application; all applications if not set
+        #[arg(short, long)]
+        application: Option<String>,
+        /// Recommend the slots of the sessions by the usage
+        #[arg(long)]
+        recommendations: bool,
+    },
+    /// Show the slots of the nodes, the executors and the sessions, refreshed periodically
+    Top {
+        /// The interval to refresh, e.g. 2s or 1m
+        #[arg(short, long, default_value = "2s")]
+        interval: String,
+        /// Show the utilization once instead of refreshing it
+        #[arg(long)]
+        once: bool,
+    },
+    /// List the events of the session and its tasks, e.g. for auditing
+    Events {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// The id of task; the events of all tasks if not set
+        #[arg(short, long)]
+        task: Option<TaskID>,
+        /// The code of the events, which can be repeated; all codes if not set
+        #[arg(short, long)]
+        code: Vec<i32>,
+        /// The events in the last duration, e.g. 10m or 1h; all events if not set
+        #[arg(long)]
+        since: Option<String>,
+        /// The owner of the events, i.e. the session itself or its tasks
+        #[arg(short, long, value_enum)]
+        owner: Option<events::EventOwner>,
+        /// The maximum number of events to list
+        #[arg(short, long)]
+        limit: Option<usize>,
     },
     /// Migrate Flame metadata
     Migrate {
@@ -115,6 +271,9 @@ enum Commands {
         /// The target SQL schema of Flame database
         #[arg(short, long)]
         sql: String,
+        /// Show the applied and pending migrations instead of running them
+        #[arg(long)]
+        status: bool,
     },
     /// Register an application
     Register {
@@ -122,12 +281,56 @@ enum Commands {
         #[arg(short, long)]
         file: String,
     },
+    /// Reconcile the applications of Flame with the yaml files
+    Apply {
+        /// The yaml file, or the directory of yaml files, of the applications
+        #[arg(short, long)]
+        file: String,
+        /// Print the changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+        /// Unregister the applications that are not in the yaml files
+        #[arg(long)]
+        prune: bool,
+    },
     /// Unregister the application from Flame
     Unregister {
         /// The name of the application
         #[arg(short, long)]
         application: String,
     },
+    /// Enable the application to accept new sessions again
+    Enable {
+        /// The name of the application
+        #[arg(short, long)]
+        application: String,
+    },
+    /// Disable the application to reject new sessions, e.g. for the incident
+    /// response; its existing sessions are running to completion
+    Disable {
+        /// The name of the application
+        #[arg(short, long)]
+        application: String,
+    },
+    /// Manage the configuration of flmctl
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Debug the cluster, e.g. for the post-incident analysis
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommands,
+    },
+    /// Print the JSON Schemas of the Flame objects, e.g. for the external tools
+    Schema {
+        /// The object to print the schema of; all the objects if not set
+        #[arg(short, long, value_enum)]
+        kind: Option<schema::SchemaKind>,
+        /// The format of the schema
+        #[arg(short, long, value_enum, default_value_t)]
+        format: schema::SchemaFormat,
+    },
     /// Generate shell completion scripts
     Completion {
         /// Shell to generate completions for
@@ -136,11 +339,124 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Validate the configuration file, e.g. unknown keys and unreachable contexts
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum JobCommands {
+    /// Create a job of the application, one task per input, or the tasks of --count
+    Create {
+        /// The name of Application
+        #[arg(short, long)]
+        app: String,
+        /// The slots requirements of each task (0 means the application's default)
+        #[arg(short, long, default_value = "0")]
+        slots: u32,
+        /// The input of a task, or the file of the input with `@`, e.g. @input.json; it can be repeated
+        #[arg(short, long)]
+        input: Vec<String>,
+        /// The directory of the inputs, one task per file
+        #[arg(long)]
+        inputs_dir: Option<String>,
+        /// The template of the inputs with placeholders, e.g. {{ name }}, rendered with each row of --values
+        #[arg(long, requires = "values")]
+        template: Option<String>,
+        /// The CSV file whose header names the placeholders of --template, one task per row
+        #[arg(long, requires = "template")]
+        values: Option<String>,
+        /// The number of the tasks whose inputs are their indexes, i.e. 0, 1..., instead of the inputs
+        #[arg(long, conflicts_with_all = ["input", "inputs_dir", "template"])]
+        count: Option<u32>,
+        /// The max tasks of the job pending or running at the same time; no limit if not set
+        #[arg(short, long)]
+        parallelism: Option<u32>,
+        /// The job succeeds once any task succeeded, instead of all tasks
+        #[arg(long)]
+        any: bool,
+        /// The failed tasks tolerated before the job fails
+        #[arg(long, default_value = "0")]
+        max_failed: u32,
+        /// Wait for the job to complete, and exit with its state
+        #[arg(short, long)]
+        wait: bool,
+    },
+    /// View the state and the progress of the job
+    View {
+        /// The id of job
+        id: SessionID,
+    },
+    /// List the jobs
+    List,
+    /// Cancel the job and its remaining tasks
+    Cancel {
+        /// The id of job
+        id: SessionID,
+    },
+}
+
+#[derive(Subcommand)]
+enum DebugCommands {
+    /// Show what the cluster looked like at a point in time from its history
+    History {
+        /// The time, e.g. 2026-10-15T08:00:00Z, 2026-10-15 08:00:00 (local) or 10m (ago); the latest if not set
+        #[arg(long)]
+        at: Option<String>,
+        /// The output format of the view
+        #[arg(short, long)]
+        output_format: Option<String>,
+    },
+    /// Show the shares of the sessions and the verdicts of each plugin in the latest scheduling cycle
+    Scheduler {
+        /// The id of session; all the open sessions if not set
+        #[arg(short, long)]
+        session: Option<SessionID>,
+        /// The output format of the view
+        #[arg(short, long)]
+        output_format: Option<String>,
+    },
+    /// Show the effective timeout, attempts and priority of the tasks and where they come from
+    Settings {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// The id of task; the settings of the tasks without their own ones if not set
+        #[arg(short, long)]
+        task: Option<TaskID>,
+    },
+    /// Show the postmortem of the crashed instance which ran the task, e.g. its exit code and stderr
+    Postmortem {
+        /// The id of session
+        #[arg(short, long)]
+        session: SessionID,
+        /// The id of task
+        #[arg(short, long)]
+        task: TaskID,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     flame_rs::apis::init_logger()?;
 
     let cli = Cli::parse();
+    // The configuration is validated before loading it for the other commands,
+    // so its problems are reported instead of failing on the first one.
+    if let Some(Commands::Config {
+        command: ConfigCommands::Validate,
+    }) = &cli.command
+    {
+        config::validate(&cli.config)?;
+        return Ok(());
+    }
+    // The schemas are derived from the types of the objects, not the cluster.
+    if let Some(Commands::Schema { kind, format }) = &cli.command {
+        schema::run(*kind, *format)?;
+        return Ok(());
+    }
+
     let ctx = FlameContext::from_file(cli.config)?;
 
     match &cli.command {
@@ -150,23 +466,221 @@ async fn main() -> Result<(), Box<dyn Error>> {
             executor,
             node,
         }) => list::run(&ctx, *application, *session, *executor, *node).await?,
-        Some(Commands::Close { session }) => close::run(&ctx, session).await?,
+        Some(Commands::Close {
+            session,
+            force,
+            wait,
+        }) => close::run(&ctx, session, *force, *wait).await?,
+        Some(Commands::Release { session, task }) => release::run(&ctx, session, task).await?,
+        Some(Commands::Resubmit {
+            session,
+            only_failed,
+        }) => resubmit::run(&ctx, session, *only_failed).await?,
+        Some(Commands::JoinToken { node, ttl }) => join::run(&ctx, node, ttl).await?,
         Some(Commands::Create {
             app,
             slots,
             batch_size,
-        }) => create::run(&ctx, app, slots, batch_size).await?,
+            max_instances,
+            priority,
+            results,
+            ttl,
+        }) => {
+            create::run(
+                &ctx,
+                &create::CreateOptions {
+                    app: app.clone(),
+                    slots: *slots,
+                    batch_size: *batch_size,
+                    max_instances: *max_instances,
+                    priority: *priority,
+                    results: *results,
+                    ttl: *ttl,
+                },
+            )
+            .await?
+        }
+        Some(Commands::Cp {
+            src,
+            dst,
+            upload_id,
+        }) => cp::run(&ctx, src, dst, upload_id.as_deref()).await?,
+        Some(Commands::Exec {
+            app,
+            input,
+            session,
+            timeout,
+        }) => {
+            let code = exec::run(
+                &ctx,
+                &exec::ExecOptions {
+                    app: app.clone(),
+                    input: input.clone(),
+                    session: session.clone(),
+                    timeout: timeout.clone(),
+                },
+            )
+            .await?;
+            std::process::exit(code);
+        }
         Some(Commands::View {
             application,
             session,
             task,
             node,
             output_format,
-        }) => view::run(&ctx, output_format, application, session, task, node).await?,
-        Some(Commands::Migrate { url, sql }) => migrate::run(&ctx, url, sql).await?,
+            schema,
+            validate_file,
+        }) => {
+            view::run(
+                &ctx,
+                output_format,
+                application,
+                session,
+                task,
+                node,
+                &view::SchemaOptions {
+                    schema: *schema,
+                    validate_file: validate_file.clone(),
+                },
+            )
+            .await?
+        }
+        Some(Commands::Submit {
+            session,
+            input,
+            inputs_dir,
+            template,
+            values,
+        }) => {
+            submit::run(
+                &ctx,
+                &submit::SubmitOptions {
+                    session: session.clone(),
+                    inputs: submit::InputOptions {
+                        inputs: input.clone(),
+                        inputs_dir: inputs_dir.clone(),
+                        template: template.clone(),
+                        values: values.clone(),
+                    },
+                },
+            )
+            .await?
+        }
+        Some(Commands::Job {
+            command:
+                JobCommands::Create {
+                    app,
+                    slots,
+                    input,
+                    inputs_dir,
+                    template,
+                    values,
+                    count,
+                    parallelism,
+                    any,
+                    max_failed,
+                    wait,
+                },
+        }) => {
+            let code = job::create(
+                &ctx,
+                &job::CreateOptions {
+                    app: app.clone(),
+                    slots: *slots,
+                    inputs: submit::InputOptions {
+                        inputs: input.clone(),
+                        inputs_dir: inputs_dir.clone(),
+                        template: template.clone(),
+                        values: values.clone(),
+                    },
+                    count: *count,
+                    parallelism: *parallelism,
+                    any: *any,
+                    max_failed: *max_failed,
+                    wait: *wait,
+                },
+            )
+            .await?;
+            std::process::exit(code);
+        }
+        Some(Commands::Job {
+            command: JobCommands::View { id },
+        }) => job::view(&ctx, id).await?,
+        Some(Commands::Job {
+            command: JobCommands::List,
+        }) => job::list(&ctx).await?,
+        Some(Commands::Job {
+            command: JobCommands::Cancel { id },
+        }) => job::cancel(&ctx, id).await?,
+        Some(Commands::Usage {
+            application,
+            recommendations,
+        }) => usage::run(&ctx, application, *recommendations).await?,
+        Some(Commands::Top { interval, once }) => top::run(&ctx, interval, *once).await?,
+        Some(Commands::Events {
+            session,
+            task,
+            code,
+            since,
+            owner,
+            limit,
+        }) => {
+            events::run(
+                &ctx,
+                &events::EventsOptions {
+                    session: session.clone(),
+                    task: task.clone(),
+                    codes: code.clone(),
+                    since: since.clone(),
+                    owner: *owner,
+                    limit: *limit,
+                },
+            )
+            .await?
+        }
+        Some(Commands::Migrate { url, sql, status }) => {
+            migrate::run(&ctx, url, sql, *status).await?
+        }
         Some(Commands::Register { file }) => register::run(&ctx, file).await?,
+        Some(Commands::Apply {
+            file,
+            dry_run,
+            prune,
+        }) => {
+            apply::run(
+                &ctx,
+                file,
+                &apply::ApplyOptions {
+                    dry_run: *dry_run,
+                    prune: *prune,
+                },
+            )
+            .await?
+        }
         Some(Commands::Unregister { application }) => unregister::run(&ctx, application).await?,
+        Some(Commands::Enable { application }) => enable::run(&ctx, application).await?,
+        Some(Commands::Disable { application }) => disable::run(&ctx, application).await?,
         Some(Commands::Update { application }) => update::run(&ctx, application).await?,
+        Some(Commands::Config { .. }) | Some(Commands::Schema { .. }) => {
+            unreachable!("handled before loading the configuration")
+        }
+        Some(Commands::Debug {
+            command: DebugCommands::History { at, output_format },
+        }) => debug::history(&ctx, at, output_format).await?,
+        Some(Commands::Debug {
+            command:
+                DebugCommands::Scheduler {
+                    session,
+                    output_format,
+                },
+        }) => debug::scheduler(&ctx, session.as_ref(), output_format).await?,
+        Some(Commands::Debug {
+            command: DebugCommands::Settings { session, task },
+        }) => debug::settings(&ctx, session, task.as_ref()).await?,
+        Some(Commands::Debug {
+            command: DebugCommands::Postmortem { session, task },
+        }) => debug::postmortem(&ctx, session, task).await?,
         Some(Commands::Completion { shell }) => {
             generate(*shell, &mut Cli::command(), "flmctl", &mut io::stdout());
         }
@@ -11,23 +11,82 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::BTreeMap;
 use std::error::Error;
 
-use flame_rs::apis::FlameContext;
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs::apis::{FlameContext, FlameError};
+use sqlx::migrate::Migrator;
 use sqlx::{migrate::MigrateDatabase, Sqlite, SqlitePool};
 use url::Url;
 
-pub async fn run(_: &FlameContext, url: &str, sql: &str) -> Result<(), Box<dyn Error>> {
+/// The state of a migration of the database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied by a newer version of Flame, i.e. not in the target schema.
+    Unknown,
+}
+
+impl std::fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationState::Applied => write!(f, "Applied"),
+            MigrationState::Pending => write!(f, "Pending"),
+            MigrationState::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+pub async fn run(
+    _: &FlameContext,
+    url: &str,
+    sql: &str,
+    status: bool,
+) -> Result<(), Box<dyn Error>> {
     let uri = Url::parse(url)?;
 
     match uri.scheme() {
         "sqlite" => {
-            Sqlite::database_exists(url).await?;
-            Sqlite::create_database(url).await?;
+            if !Sqlite::database_exists(url).await? {
+                if status {
+                    println!("Database <{url}> does not exist, all migrations are pending.");
+                    return Ok(());
+                }
+                Sqlite::create_database(url).await?;
+            }
 
             let db = SqlitePool::connect(url).await?;
             let migrations = std::path::Path::new(&sql);
             let migrator = sqlx::migrate::Migrator::new(migrations).await?;
+
+            let applied = applied_migrations(&db).await?;
+            let states = migration_states(&migrator, &applied);
+
+            if status {
+                print_status(&db, &states).await?;
+                return Ok(());
+            }
+
+            // Refuse to migrate the database of a newer schema, e.g. by an older
+            // flmctl or the schema of an older version.
+            let unknown: Vec<_> = states
+                .iter()
+                .filter(|(_, (_, state))| *state == MigrationState::Unknown)
+                .map(|(version, _)| version.to_string())
+                .collect();
+            if !unknown.is_empty() {
+                return Err(FlameError::InvalidConfig(format!(
+                    "the database has migrations <{}> which are not in <{sql}>, it is of a newer \
+                     schema; use the schema of the newer version, or restore a backup by \
+                     `flmadm restore`",
+                    unknown.join(", ")
+                ))
+                .into());
+            }
+
             migrator.run(&db).await?;
 
             Ok(())
@@ -36,3 +95,119 @@ pub async fn run(_: &FlameContext, url: &str, sql: &str) -> Result<(), Box<dyn E
         _ => Ok(()),
     }
 }
+
+/// The successful migrations applied to the database by their versions.
+async fn applied_migrations(db: &SqlitePool) -> Result<BTreeMap<i64, String>, Box<dyn Error>> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type='table' AND name='_sqlx_migrations')",
+    )
+    .fetch_one(db)
+    .await?;
+    if !exists {
+        return Ok(BTreeMap::new());
+    }
+
+    let applied: Vec<(i64, String)> =
+        sqlx::query_as("SELECT version, description FROM _sqlx_migrations WHERE success")
+            .fetch_all(db)
+            .await?;
+
+    Ok(applied.into_iter().collect())
+}
+
+/// The state of each migration either in the target schema or in the database.
+fn migration_states(
+    migrator: &Migrator,
+    applied: &BTreeMap<i64, String>,
+) -> BTreeMap<i64, (String, MigrationState)> {
+    let mut states = BTreeMap::new();
+    for migration in migrator.iter() {
+        let state = match applied.contains_key(&migration.version) {
+            true => MigrationState::Applied,
+            false => MigrationState::Pending,
+        };
+        states.insert(
+            migration.version,
+            (migration.description.to_string(), state),
+        );
+    }
+
+    for (version, description) in applied {
+        states
+            .entry(*version)
+            .or_insert_with(|| (description.clone(), MigrationState::Unknown));
+    }
+
+    states
+}
+
+async fn print_status(
+    db: &SqlitePool,
+    states: &BTreeMap<i64, (String, MigrationState)>,
+) -> Result<(), Box<dyn Error>> {
+    // The schema version is not recorded by the older versions.
+    let schema: Option<(i64, String)> =
+        sqlx::query_as("SELECT version, flame_version FROM schema_version WHERE id=1")
+            .fetch_optional(db)
+            .await
+            .unwrap_or_default();
+
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(vec!["Version", "Description", "State"]);
+    for (version, (description, state)) in states {
+        table.add_row(vec![
+            version.to_string(),
+            description.to_string(),
+            state.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    let count = |s: MigrationState| states.values().filter(|(_, state)| *state == s).count();
+    println!();
+    match schema {
+        Some((version, flame_version)) => {
+            println!("Schema version: {version} (written by Flame {flame_version})")
+        }
+        None => println!("Schema version: -"),
+    }
+    println!(
+        "Applied: {}, Pending: {}, Unknown: {}",
+        count(MigrationState::Applied),
+        count(MigrationState::Pending),
+        count(MigrationState::Unknown)
+    );
+    if count(MigrationState::Unknown) > 0 {
+        println!(
+            "The database is of a newer schema than the target one; it can not be used by this \
+             version of Flame."
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_migration_states() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("1_init.sql"), "SELECT 1;").unwrap();
+        std::fs::write(dir.path().join("2_add_column.sql"), "SELECT 1;").unwrap();
+        let migrator = Migrator::new(dir.path()).await.unwrap();
+
+        let applied = BTreeMap::from([(1, "init".to_string()), (3, "newer".to_string())]);
+        let states = migration_states(&migrator, &applied);
+
+        assert_eq!(states[&1].1, MigrationState::Applied);
+        assert_eq!(
+            states[&2],
+            ("add column".to_string(), MigrationState::Pending)
+        );
+        assert_eq!(states[&3], ("newer".to_string(), MigrationState::Unknown));
+    }
+}
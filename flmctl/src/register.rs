@@ -34,7 +34,8 @@ pub async fn run(ctx: &FlameContext, path: &String) -> Result<(), FlameError> {
         &current_ctx.cluster.endpoint,
         current_ctx.cluster.tls.as_ref(),
     )
-    .await?;
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
 
     let documents: Vec<&str> = contents
         .split("\n---\n")
@@ -0,0 +1,35 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use flame_rs::apis::{FlameContext, SessionID, TaskID};
+use flame_rs::client::federation::Federation;
+
+pub async fn run(
+    ctx: &FlameContext,
+    session_id: &SessionID,
+    task_id: &TaskID,
+) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let ssn = federation.get_session(session_id).await?;
+
+    let task = ssn.release_task(task_id).await?;
+
+    println!(
+        "Task <{}/{}> was released to <{}>.",
+        session_id, task.id, task.state
+    );
+
+    Ok(())
+}
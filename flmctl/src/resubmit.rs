@@ -0,0 +1,40 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use flame_rs::apis::{FlameContext, SessionID};
+use flame_rs::client::federation::Federation;
+
+/// Resubmits the tasks of the session to a new session with its attributes,
+/// e.g. after the bug failing them was fixed.
+pub async fn run(
+    ctx: &FlameContext,
+    session_id: &SessionID,
+    only_failed: bool,
+) -> Result<(), Box<dyn Error>> {
+    let federation = Federation::connect(ctx).await?;
+    let ssn = federation.get_session(session_id).await?;
+
+    let new_id: SessionID = format!("{}-{}", ssn.application, stdng::rand::short_name()).into();
+    let new_ssn = federation
+        .resubmit_session(session_id, &new_id, only_failed)
+        .await?;
+
+    println!(
+        "Session <{}> was created with {} tasks resubmitted from <{}>.",
+        new_ssn.id, new_ssn.pending, session_id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,665 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The JSON Schemas of the Flame objects, i.e. the JSON of the objects of the
+//! Rust SDK as printed by `flmctl view -o json`, so the external tools can
+//! validate them and generate their clients. The objects do not accept unknown
+//! properties, and the tests check the schemas against the serialized objects,
+//! so a new field of the objects fails the tests until it's in the schemas.
+
+use std::error::Error;
+
+use clap::ValueEnum;
+use serde_json::{json, Map, Value};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const OPENAPI_VERSION: &str = "3.1.0";
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum SchemaKind {
+    Application,
+    Session,
+    Task,
+    Node,
+    Event,
+}
+
+impl SchemaKind {
+    fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::Application => "Application",
+            SchemaKind::Session => "Session",
+            SchemaKind::Task => "Task",
+            SchemaKind::Node => "Node",
+            SchemaKind::Event => "Event",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum SchemaFormat {
+    /// A JSON Schema (2020-12) with the objects in `$defs`.
+    #[default]
+    JsonSchema,
+    /// An OpenAPI 3.1 document with the objects in `components.schemas`.
+    Openapi,
+}
+
+pub fn run(kind: Option<SchemaKind>, format: SchemaFormat) -> Result<(), Box<dyn Error>> {
+    let document = match format {
+        SchemaFormat::JsonSchema => json_schema(kind),
+        SchemaFormat::Openapi => openapi(kind),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+
+    Ok(())
+}
+
+/// The JSON Schema of the object, or of all the objects if no kind.
+fn json_schema(kind: Option<SchemaKind>) -> Value {
+    let mut schema = Map::new();
+    schema.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+    if let Some(kind) = kind {
+        schema.insert(
+            "$ref".to_string(),
+            json!(format!("#/$defs/{}", kind.name())),
+        );
+    }
+    schema.insert("$defs".to_string(), Value::Object(definitions("#/$defs/")));
+
+    Value::Object(schema)
+}
+
+/// The OpenAPI document of the object, or of all the objects if no kind; it
+/// has no paths, as the API of Flame is gRPC.
+fn openapi(kind: Option<SchemaKind>) -> Value {
+    let mut schemas = definitions("#/components/schemas/");
+    if let Some(kind) = kind {
+        let refs = referenced(&schemas, kind.name());
+        schemas.retain(|name, _| refs.contains(name));
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "Flame",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {},
+        "components": { "schemas": schemas },
+    })
+}
+
+/// The names of the definition and the ones it refers to, recursively.
+fn referenced(schemas: &Map<String, Value>, name: &str) -> Vec<String> {
+    fn collect(value: &Value, refs: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(r)) = map.get("$ref") {
+                    refs.push(r.rsplit('/').next().unwrap_or_default().to_string());
+                }
+                map.values().for_each(|v| collect(v, refs));
+            }
+            Value::Array(values) => values.iter().for_each(|v| collect(v, refs)),
+            _ => {}
+        }
+    }
+
+    let mut names = vec![name.to_string()];
+    let mut i = 0;
+    while i < names.len() {
+        let mut refs = vec![];
+        if let Some(schema) = schemas.get(&names[i]) {
+            collect(schema, &mut refs);
+        }
+        for r in refs {
+            if !names.contains(&r) {
+                names.push(r);
+            }
+        }
+        i += 1;
+    }
+
+    names
+}
+
+/// The definitions of the objects and the types they refer to, whose `$ref`s
+/// start with the prefix.
+fn definitions(prefix: &str) -> Map<String, Value> {
+    let reference = |name: &str| json!({ "$ref": format!("{prefix}{name}") });
+    let nullable_ref = |name: &str| nullable(reference(name));
+    let strings = || json!({ "type": "array", "items": string() });
+
+    let mut defs = Map::new();
+    let mut define = |name: &str, schema: Value| {
+        defs.insert(name.to_string(), schema);
+    };
+
+    define(
+        "Application",
+        object(
+            "An application registered in Flame.",
+            json!({
+                "name": string(),
+                "attributes": reference("ApplicationAttributes"),
+                "state": reference("ApplicationState"),
+                "creation_time": timestamp(),
+            }),
+        ),
+    );
+    define(
+        "ApplicationAttributes",
+        object(
+            "The attributes of an application.",
+            json!({
+                "shim": nullable_ref("Shim"),
+                "image": nullable(string()),
+                "description": nullable(string()),
+                "labels": strings(),
+                "command": nullable(string()),
+                "arguments": strings(),
+                "environments": { "type": "object", "additionalProperties": string() },
+                "working_directory": nullable(string()),
+                "max_instances": nullable(uint32()),
+                "delay_release": described(nullable(int64()), "The delay in seconds to release the idle instances."),
+                "schema": nullable_ref("ApplicationSchema"),
+                "url": nullable(string()),
+                "task_environments": strings(),
+                "task_arguments": strings(),
+                "bundle_size": nullable(uint32()),
+                "max_instances_per_node": nullable(uint32()),
+                "session_defaults": nullable_ref("SessionDefaults"),
+                "health_probe": nullable_ref("HealthProbe"),
+                "fairness": nullable_ref("FairnessPolicy"),
+                "hooks": nullable_ref("LifecycleHooks"),
+                "python_env": nullable_ref("PythonEnvironment"),
+                "codec": nullable_ref("PayloadCodec"),
+                "payload_type": described(nullable(string()), "The fully-qualified message type of the protobuf codec."),
+                "topology": nullable_ref("TopologyPolicy"),
+            }),
+        ),
+    );
+    define(
+        "ApplicationSchema",
+        object(
+            "The JSON Schemas of the input, output and common data of an application.",
+            json!({
+                "input": nullable(string()),
+                "output": nullable(string()),
+                "common_data": nullable(string()),
+            }),
+        ),
+    );
+    define(
+        "SessionDefaults",
+        object(
+            "The defaults of the sessions of an application.",
+            json!({
+                "slots": nullable(uint32()),
+                "min_instances": nullable(uint32()),
+                "max_instances": nullable(uint32()),
+                "batch_size": nullable(uint32()),
+                "scratch_size": nullable(uint64()),
+                "max_task_attempts": nullable(uint32()),
+                "task_timeout": nullable(uint64()),
+                "task_priority": nullable(uint32()),
+            }),
+        ),
+    );
+    define(
+        "HealthProbe",
+        object(
+            "The health probe of the instances of an application.",
+            json!({
+                "command": nullable(string()),
+                "grpc_service": nullable(string()),
+                "period_seconds": nullable(uint32()),
+                "timeout_seconds": nullable(uint32()),
+                "failure_threshold": nullable(uint32()),
+            }),
+        ),
+    );
+    define(
+        "LifecycleHooks",
+        object(
+            "The lifecycle hooks of the instances of an application.",
+            json!({
+                "pre_bind": nullable_ref("LifecycleHook"),
+                "post_bind": nullable_ref("LifecycleHook"),
+                "pre_release": nullable_ref("LifecycleHook"),
+            }),
+        ),
+    );
+    define(
+        "LifecycleHook",
+        object(
+            "A command run by `sh -c` in the working directory of the instance.",
+            json!({
+                "command": string(),
+                "timeout_seconds": nullable(uint32()),
+            }),
+        ),
+    );
+    define(
+        "PythonEnvironment",
+        object(
+            "The Python environment of the host instances of an application.",
+            json!({
+                "project": nullable(string()),
+                "requirements": nullable(string()),
+                "python_version": nullable(string()),
+            }),
+        ),
+    );
+    define(
+        "Session",
+        object(
+            "A session of an application, which runs its tasks.",
+            json!({
+                "id": string(),
+                "slots": uint32(),
+                "application": string(),
+                "priority": uint32(),
+                "creation_time": timestamp(),
+                "state": reference("SessionState"),
+                "pending": int32(),
+                "running": int32(),
+                "succeed": int32(),
+                "failed": int32(),
+                "events": { "type": "array", "items": reference("Event") },
+                "tasks": nullable(json!({ "type": "array", "items": reference("Task") })),
+                "task_durations": nullable_ref("TaskDurationStats"),
+                "unschedulable": nullable(string()),
+                "trace_id": nullable(string()),
+                "result_policy": reference("ResultPolicy"),
+                "ttl_after_completion": nullable(uint64()),
+            }),
+        ),
+    );
+    define(
+        "TaskDurationStats",
+        object(
+            "The statistics of the durations of the tasks, in milliseconds.",
+            json!({
+                "count": uint64(),
+                "mean": number(),
+                "min": uint64(),
+                "max": uint64(),
+                "recent": number(),
+            }),
+        ),
+    );
+    define(
+        "Task",
+        object(
+            "A task of a session.",
+            json!({
+                "id": string(),
+                "ssn_id": string(),
+                "state": reference("TaskState"),
+                "input": nullable(string()),
+                "input_ref": nullable_ref("ObjectReference"),
+                "output": nullable(string()),
+                "output_ref": nullable_ref("ObjectReference"),
+                "artifacts": { "type": "array", "items": reference("TaskArtifact") },
+                "failure_reason": nullable_ref("FailureReason"),
+                "attempts": uint32(),
+                "usage": nullable_ref("TaskUsage"),
+                "checkpoint": nullable(string()),
+                "postmortem": nullable_ref("TaskPostmortem"),
+                "trace_id": nullable(string()),
+                "events": { "type": "array", "items": reference("Event") },
+            }),
+        ),
+    );
+    define(
+        "ObjectReference",
+        object(
+            "A reference to an object in the object cache.",
+            json!({
+                "endpoint": string(),
+                "key": string(),
+                "checksum": nullable(string()),
+            }),
+        ),
+    );
+    define(
+        "TaskArtifact",
+        object(
+            "A named output of a task, inline or in the object cache.",
+            json!({
+                "name": string(),
+                "data": nullable(string()),
+                "object_ref": nullable(string()),
+            }),
+        ),
+    );
+    define(
+        "TaskUsage",
+        object(
+            "The resources used by the instance to run a task.",
+            json!({
+                "cpu_time_ms": uint64(),
+                "max_rss_bytes": uint64(),
+                "read_bytes": uint64(),
+                "write_bytes": uint64(),
+            }),
+        ),
+    );
+    define(
+        "TaskPostmortem",
+        object(
+            "The postmortem of the crashed instance which ran a task.",
+            json!({
+                "executor_id": string(),
+                "node": string(),
+                "exit_code": nullable(int32()),
+                "signal": nullable(int32()),
+                "core_dumped": boolean(),
+                "stderr_tail": nullable(string()),
+                "load_average": nullable(number()),
+                "memory_available": nullable(uint64()),
+                "creation_time": timestamp(),
+            }),
+        ),
+    );
+    define(
+        "Node",
+        object(
+            "A node of the cluster, which runs the executors.",
+            json!({
+                "name": string(),
+                "hostname": string(),
+                "state": reference("NodeState"),
+                "cpu": uint64(),
+                "memory": uint64(),
+                "allocatable_cpu": uint64(),
+                "allocatable_memory": uint64(),
+                "arch": string(),
+                "os": string(),
+                "saturation": nullable(number()),
+                "executors": uint32(),
+                "max_executors": nullable(uint32()),
+                "last_heartbeat": nullable(timestamp()),
+                "events": { "type": "array", "items": reference("Event") },
+                "numa_nodes": { "type": "array", "items": reference("NumaNode") },
+                "gpu_groups": { "type": "array", "items": reference("GpuGroup") },
+                "prefetched": { "type": "array", "items": string() },
+            }),
+        ),
+    );
+    define(
+        "NumaNode",
+        object(
+            "A NUMA node of a node.",
+            json!({
+                "id": uint32(),
+                "cpus": described(string(), "The CPUs as a cpulist, e.g. \"0-15,32-47\"."),
+            }),
+        ),
+    );
+    define(
+        "GpuGroup",
+        object(
+            "A group of GPUs of a node connected by NVLink or the same PCIe switch.",
+            json!({
+                "gpus": { "type": "array", "items": uint32() },
+                "numa_node": nullable(uint32()),
+                "link": enumeration(&["PCIe", "NVLink"]),
+            }),
+        ),
+    );
+    define(
+        "Event",
+        object(
+            "An event of a session, a task or a node.",
+            json!({
+                "code": int32(),
+                "message": nullable(string()),
+                "creation_time": timestamp(),
+            }),
+        ),
+    );
+
+    define("ApplicationState", enumeration(&["Enabled", "Disabled"]));
+    define("SessionState", enumeration(&["Open", "Closed", "Closing"]));
+    define(
+        "ResultPolicy",
+        enumeration(&["Inline", "Discard", "ObjectCache"]),
+    );
+    define(
+        "TaskState",
+        enumeration(&[
+            "Pending",
+            "Running",
+            "Succeed",
+            "Failed",
+            "Cancelled",
+            "Quarantined",
+        ]),
+    );
+    define(
+        "NodeState",
+        enumeration(&["Unknown", "Ready", "NotReady", "Lost"]),
+    );
+    define("Shim", enumeration(&["Host", "Wasm"]));
+    define(
+        "FairnessPolicy",
+        enumeration(&["RoundRobin", "Proportional"]),
+    );
+    define(
+        "PayloadCodec",
+        enumeration(&["Raw", "Json", "Msgpack", "Arrow", "Protobuf"]),
+    );
+    define(
+        "TopologyPolicy",
+        enumeration(&["Ignore", "NumaAligned", "GpuAligned"]),
+    );
+    define(
+        "FailureReason",
+        enumeration(&[
+            "Unknown",
+            "ApplicationError",
+            "ShimCrash",
+            "Timeout",
+            "Preempted",
+            "NodeLost",
+            "InputInvalid",
+            "DeadlineExceeded",
+        ]),
+    );
+
+    defs
+}
+
+/// An object whose properties are all present in its JSON, and no others.
+fn object(description: &str, properties: Value) -> Value {
+    let required: Vec<&String> = properties
+        .as_object()
+        .map(|properties| properties.keys().collect())
+        .unwrap_or_default();
+
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn enumeration(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn described(mut schema: Value, description: &str) -> Value {
+    schema["description"] = json!(description);
+    schema
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+fn int32() -> Value {
+    json!({ "type": "integer", "format": "int32" })
+}
+
+fn int64() -> Value {
+    json!({ "type": "integer", "format": "int64" })
+}
+
+fn uint32() -> Value {
+    json!({ "type": "integer", "format": "uint32", "minimum": 0 })
+}
+
+fn uint64() -> Value {
+    json!({ "type": "integer", "format": "uint64", "minimum": 0 })
+}
+
+fn timestamp() -> Value {
+    described(int64(), "The Unix timestamp in seconds.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use flame_rs::client::{Application, Event, Node, Session, Task};
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+
+    /// Round-trips the object through its Rust type, so all the fields of the
+    /// type are in the JSON, and validates it against the schema of its kind.
+    fn validate<T: Serialize + DeserializeOwned>(kind: SchemaKind, object: Value) {
+        let object: T = serde_json::from_value(object).unwrap();
+        let object = serde_json::to_value(&object).unwrap();
+
+        let schema = json_schema(Some(kind));
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&object)
+            .map(|e| format!("{}: {e}", e.instance_path))
+            .collect();
+        assert!(errors.is_empty(), "{}: {errors:?}", kind.name());
+    }
+
+    fn event() -> Value {
+        json!({ "code": 1, "message": "created", "creation_time": 1_792_051_200 })
+    }
+
+    #[test]
+    fn test_schemas_of_objects() {
+        validate::<Event>(SchemaKind::Event, event());
+
+        validate::<Node>(
+            SchemaKind::Node,
+            json!({
+                "name": "node-1", "hostname": "node-1", "state": "Ready",
+                "cpu": 8, "memory": 1024, "allocatable_cpu": 8, "allocatable_memory": 1024,
+                "arch": "x86_64", "os": "linux", "saturation": 0.5,
+                "executors": 3, "max_executors": 4, "last_heartbeat": 1_792_051_200, "events": [event()],
+                "numa_nodes": [{ "id": 0, "cpus": "0-7" }],
+                "gpu_groups": [{ "gpus": [0, 1], "numa_node": 0, "link": "NVLink" }],
+                "prefetched": ["app-1"],
+            }),
+        );
+
+        let task = json!({
+            "id": "1", "ssn_id": "ssn-1", "state": "Failed",
+            "input": "input", "output": "output",
+            "input_ref": { "endpoint": "grpc://127.0.0.1:9090", "key": "ssn-1/input", "checksum": null },
+            "output_ref": null,
+            "artifacts": [{ "name": "log", "data": "data", "object_ref": null }],
+            "failure_reason": "Timeout", "attempts": 2,
+            "usage": { "cpu_time_ms": 1, "max_rss_bytes": 2, "read_bytes": 3, "write_bytes": 4 },
+            "checkpoint": null,
+            "postmortem": {
+                "executor_id": "executor-1", "node": "node-1", "exit_code": null, "signal": 9,
+                "core_dumped": false, "stderr_tail": "killed", "load_average": 0.5,
+                "memory_available": 1024, "creation_time": 1_792_051_200,
+            },
+            "trace_id": "5f0c2d1e-8a4b-4c3d-9e2f-1a2b3c4d5e6f",
+            "events": [event()],
+        });
+        validate::<Task>(SchemaKind::Task, task.clone());
+
+        validate::<Session>(
+            SchemaKind::Session,
+            json!({
+                "id": "ssn-1", "slots": 1, "application": "flmping", "priority": 0,
+                "creation_time": 1_792_051_200, "state": "Open",
+                "pending": 1, "running": 0, "succeed": 0, "failed": 1,
+                "events": [event()], "tasks": [task],
+                "task_durations": { "count": 1, "mean": 1.0, "min": 1, "max": 1, "recent": 1.0 },
+                "unschedulable": null, "trace_id": null, "result_policy": "Inline",
+                "ttl_after_completion": null,
+            }),
+        );
+
+        validate::<Application>(
+            SchemaKind::Application,
+            json!({
+                "name": "flmping", "state": "Enabled", "creation_time": 1_792_051_200,
+                "attributes": {
+                    "shim": "Host", "image": null, "description": "ping", "labels": ["test"],
+                    "command": "/usr/bin/flmping-service", "arguments": [],
+                    "environments": { "RUST_LOG": "info" }, "working_directory": "/tmp",
+                    "max_instances": 10, "delay_release": 60,
+                    "schema": { "input": "{}", "output": null, "common_data": null },
+                    "url": null,
+                    "session_defaults": { "slots": 1 },
+                    "health_probe": { "command": "true", "period_seconds": 10 },
+                    "fairness": "Proportional",
+                    "hooks": { "pre_bind": { "command": "echo" } },
+                    "python_env": { "project": "/opt/app" },
+                    "codec": "Json",
+                    "topology": "GpuAligned",
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_schema_documents() {
+        let schema = json_schema(None);
+        assert!(jsonschema::validator_for(&schema).is_ok());
+
+        // The refs of the OpenAPI document resolve to its components.
+        let document = openapi(Some(SchemaKind::Task));
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let refs = referenced(schemas, "Task");
+        assert!(refs.iter().all(|name| schemas.contains_key(name)));
+        assert!(schemas.contains_key("TaskUsage"));
+        assert!(schemas.contains_key("TaskPostmortem"));
+        assert!(!schemas.contains_key("Node"));
+
+        let document = openapi(None);
+        assert_eq!(
+            document["components"]["schemas"].as_object().unwrap().len(),
+            26
+        );
+    }
+}
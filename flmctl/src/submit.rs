@@ -0,0 +1,231 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use bytes::Bytes;
+use flame_rs::apis::{FlameContext, FlameError, SessionID};
+use flame_rs::client::federation::Federation;
+
+use crate::utils::read_input;
+
+/// Where the inputs of the tasks come from, e.g. for `flmctl submit` and `flmctl job create`.
+pub struct InputOptions {
+    pub inputs: Vec<String>,
+    pub inputs_dir: Option<String>,
+    pub template: Option<String>,
+    pub values: Option<String>,
+}
+
+pub struct SubmitOptions {
+    pub session: SessionID,
+    pub inputs: InputOptions,
+}
+
+/// Submits the tasks to the session without waiting for them, one task per
+/// input, file in the directory or row of the values.
+pub async fn run(ctx: &FlameContext, opts: &SubmitOptions) -> Result<(), Box<dyn Error>> {
+    // All inputs are read before submitting, so a bad one submits nothing.
+    let inputs = collect_inputs(&opts.inputs)?;
+    if inputs.is_empty() {
+        return Err(FlameError::InvalidConfig("no input to submit".to_string()).into());
+    }
+
+    let federation = Federation::connect(ctx).await?;
+    let ssn = federation.get_session(&opts.session).await?;
+
+    for (source, input) in inputs {
+        let task = ssn.create_task(Some(input)).await?;
+        println!(
+            "Task <{}/{}> was submitted from <{source}>.",
+            ssn.id, task.id
+        );
+    }
+
+    Ok(())
+}
+
+/// The inputs of the tasks with where they came from, in the order to submit.
+pub fn collect_inputs(opts: &InputOptions) -> Result<Vec<(String, Bytes)>, Box<dyn Error>> {
+    let mut inputs = vec![];
+
+    for input in &opts.inputs {
+        let source = match input.strip_prefix('@') {
+            Some(path) => path.to_string(),
+            None => "--input".to_string(),
+        };
+        inputs.push((source, read_input(input)?));
+    }
+
+    if let Some(dir) = &opts.inputs_dir {
+        inputs.extend(read_inputs_dir(Path::new(dir))?);
+    }
+
+    match (&opts.template, &opts.values) {
+        (Some(template), Some(values)) => {
+            let template = fs::read_to_string(template).map_err(|e| {
+                FlameError::InvalidConfig(format!("failed to read template <{template}>: {e}"))
+            })?;
+            inputs.extend(render_values(&template, Path::new(values))?);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(FlameError::InvalidConfig(
+                "--template and --values must be set together".to_string(),
+            )
+            .into())
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// The files in the directory ordered by their names; the hidden files and the
+/// sub-directories are skipped.
+fn read_inputs_dir(dir: &Path) -> Result<Vec<(String, Bytes)>, Box<dyn Error>> {
+    let entries = fs::read_dir(dir).map_err(|e| {
+        FlameError::InvalidConfig(format!("failed to read directory <{}>: {e}", dir.display()))
+    })?;
+
+    let mut paths = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_none_or(|name| name.starts_with('.'));
+        if path.is_file() && !hidden {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut inputs = vec![];
+    for path in paths {
+        let input = fs::read(&path).map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to read input <{}>: {e}", path.display()))
+        })?;
+        inputs.push((path.display().to_string(), Bytes::from(input)));
+    }
+
+    Ok(inputs)
+}
+
+/// Renders the template with each row of the CSV file, whose header names the
+/// placeholders of the template.
+fn render_values(template: &str, values: &Path) -> Result<Vec<(String, Bytes)>, Box<dyn Error>> {
+    let mut reader = csv::Reader::from_path(values).map_err(|e| {
+        FlameError::InvalidConfig(format!("failed to read values <{}>: {e}", values.display()))
+    })?;
+    let headers: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+
+    let mut inputs = vec![];
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        let row: HashMap<&str, &str> = headers
+            .iter()
+            .map(String::as_str)
+            .zip(record.iter())
+            .collect();
+        // The header is the first line of the file.
+        let source = format!("{}:{}", values.display(), i + 2);
+        let input = render(template, &row)
+            .map_err(|e| FlameError::InvalidConfig(format!("{source}: {e}")))?;
+        inputs.push((source, Bytes::from(input)));
+    }
+
+    Ok(inputs)
+}
+
+/// Replaces the placeholders of the template, e.g. `{{ name }}`, by the values;
+/// it fails on the unknown or unclosed placeholders instead of leaving them.
+fn render(template: &str, values: &HashMap<&str, &str>) -> Result<String, FlameError> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            return Err(FlameError::InvalidConfig(
+                "unclosed placeholder in template".to_string(),
+            ));
+        };
+
+        let name = rest[start + 2..start + end].trim();
+        let value = values.get(name).ok_or(FlameError::InvalidConfig(format!(
+            "unknown placeholder <{name}> in template"
+        )))?;
+        output.push_str(value);
+
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let values = HashMap::from([("name", "pi"), ("n", "100")]);
+
+        assert_eq!(
+            render(r#"{"name": "{{ name }}", "n": {{n}}}"#, &values).unwrap(),
+            r#"{"name": "pi", "n": 100}"#
+        );
+        assert_eq!(render("no placeholder", &values).unwrap(), "no placeholder");
+        assert!(render("{{ unknown }}", &values).is_err());
+        assert!(render("{{ name", &values).is_err());
+    }
+
+    #[test]
+    fn test_collect_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let inputs_dir = dir.path().join("inputs");
+        fs::create_dir_all(inputs_dir.join("nested")).unwrap();
+        fs::write(inputs_dir.join("b.json"), "2").unwrap();
+        fs::write(inputs_dir.join("a.json"), "1").unwrap();
+        fs::write(inputs_dir.join(".hidden"), "0").unwrap();
+
+        let template = dir.path().join("template.json");
+        fs::write(&template, r#"{"n": {{ n }}}"#).unwrap();
+        let values = dir.path().join("values.csv");
+        fs::write(&values, "n,unused\n10,x\n20,y\n").unwrap();
+
+        let opts = InputOptions {
+            inputs: vec!["0".to_string()],
+            inputs_dir: Some(inputs_dir.display().to_string()),
+            template: Some(template.display().to_string()),
+            values: Some(values.display().to_string()),
+        };
+        let inputs: Vec<_> = collect_inputs(&opts)
+            .unwrap()
+            .into_iter()
+            .map(|(_, input)| String::from_utf8(input.to_vec()).unwrap())
+            .collect();
+        assert_eq!(inputs, vec!["0", "1", "2", r#"{"n": 10}"#, r#"{"n": 20}"#]);
+
+        let opts = InputOptions {
+            template: None,
+            ..opts
+        };
+        assert!(collect_inputs(&opts).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use chrono::Local;
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs as flame;
+use flame_rs::apis::FlameContext;
+use flame_rs::client::{ClusterUtilization, NodeState};
+
+use crate::utils::{format_saturation, parse_duration};
+
+/// Clears the terminal and moves the cursor to its top-left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Shows the utilization of the cluster, refreshed by the interval until
+/// interrupted, or once.
+pub async fn run(ctx: &FlameContext, interval: &str, once: bool) -> Result<(), Box<dyn Error>> {
+    let interval = parse_duration(interval)?;
+    if interval.is_zero() {
+        return Err("the interval must be greater than 0".into());
+    }
+
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?;
+
+    loop {
+        let utilization = conn.get_cluster_utilization().await?;
+        if once {
+            view_utilization(&utilization);
+            return Ok(());
+        }
+
+        print!("{CLEAR_SCREEN}");
+        view_utilization(&utilization);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn format_usage(used: u32, total: u32) -> String {
+    if total == 0 {
+        return format!("{used}/{total}");
+    }
+    format!(
+        "{used}/{total} ({:.0}%)",
+        used as f64 * 100.0 / total as f64
+    )
+}
+
+fn view_utilization(utilization: &ClusterUtilization) {
+    println!(
+        "{:<15}{}",
+        "Updated:",
+        utilization
+            .creation_time
+            .with_timezone(&Local)
+            .format("%F %T")
+    );
+    println!(
+        "{:<15}{}",
+        "Slots:",
+        format_usage(utilization.used_slots, utilization.total_slots)
+    );
+    println!(
+        "{:<15}{}",
+        "Executors:",
+        format_usage(
+            utilization
+                .executors
+                .iter()
+                .filter(|e| e.task_id.is_some())
+                .count() as u32,
+            utilization.executors.len() as u32
+        )
+    );
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "NODE",
+        "STATUS",
+        "SLOTS",
+        "EXECUTORS",
+        "RUNNING",
+        "SATURATION",
+    ]);
+    for node in &utilization.nodes {
+        let status = match node.state {
+            NodeState::Ready => "Ready",
+            NodeState::NotReady => "NotReady",
+            NodeState::Lost => "Lost",
+            NodeState::Unknown => "Unknown",
+        };
+        table.add_row(vec![
+            node.name.clone(),
+            status.to_string(),
+            format_usage(node.used_slots, node.total_slots),
+            node.executors.to_string(),
+            node.running_executors.to_string(),
+            format_saturation(node.saturation),
+        ]);
+    }
+    println!("{table}");
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "SESSION",
+        "APPLICATION",
+        "EXECUTORS",
+        "SLOTS",
+        "PENDING",
+        "RUNNING",
+        "SUCCEED",
+        "FAILED",
+    ]);
+    for ssn in &utilization.sessions {
+        table.add_row(vec![
+            ssn.session_id.to_string(),
+            ssn.application.clone(),
+            ssn.executors.to_string(),
+            ssn.used_slots.to_string(),
+            ssn.pending_tasks.to_string(),
+            ssn.running_tasks.to_string(),
+            ssn.succeed_tasks.to_string(),
+            ssn.failed_tasks.to_string(),
+        ]);
+    }
+    println!("{table}");
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(vec![
+        "EXECUTOR", "NODE", "STATE", "SLOTS", "SESSION", "TASK", "BUSY",
+    ]);
+    for exec in &utilization.executors {
+        table.add_row(vec![
+            exec.id.clone(),
+            exec.node.clone(),
+            exec.state.to_string(),
+            exec.slots.to_string(),
+            exec.session_id
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or("-".to_string()),
+            exec.task_id
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or("-".to_string()),
+            format_saturation(exec.busy_ratio),
+        ]);
+    }
+    println!("{table}");
+}
@@ -53,7 +53,8 @@ async fn update_application(ctx: &FlameContext, application: &str) -> Result<(),
         &current_ctx.cluster.endpoint,
         current_ctx.cluster.tls.as_ref(),
     )
-    .await?;
+    .await?
+    .with_admin_token(current_ctx.cluster.admin_token()?);
 
     conn.update_application(app.metadata.name, app_attr).await?;
 
@@ -0,0 +1,68 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::error::Error;
+
+use comfy_table::presets::NOTHING;
+use comfy_table::Table;
+use flame_rs as flame;
+use flame_rs::apis::FlameContext;
+
+use crate::utils::format_memory;
+
+pub async fn run(
+    ctx: &FlameContext,
+    application: &Option<String>,
+    recommendations: bool,
+) -> Result<(), Box<dyn Error>> {
+    let current_ctx = ctx.get_current_context()?;
+    let conn = flame::client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await?;
+
+    let usage = conn
+        .list_slot_recommendations(application.as_deref())
+        .await?;
+
+    let mut header = vec!["Application", "Session", "Tasks", "CPU", "Memory", "Slots"];
+    if recommendations {
+        header.extend(["Recommended", "Message"]);
+    }
+
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(header);
+
+    for r in &usage {
+        let mut row = vec![
+            r.application.clone(),
+            r.session_id
+                .clone()
+                .map(String::from)
+                .unwrap_or("-".to_string()),
+            r.tasks.to_string(),
+            format!("{:.1}", r.cpu),
+            format_memory(r.memory),
+            r.slots.to_string(),
+        ];
+        if recommendations {
+            row.extend([r.recommended_slots.to_string(), r.message.clone()]);
+        }
+        table.add_row(row);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
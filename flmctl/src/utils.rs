@@ -11,6 +11,14 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::error::Error;
+use std::fs;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use flame_rs::apis::FlameError;
+
 /// Formats a byte count into a human-readable string with appropriate unit suffix.
 /// Uses binary prefixes (Ki, Mi, Gi) following Kubernetes conventions.
 pub fn format_memory(bytes: u64) -> String {
@@ -24,3 +32,101 @@ pub fn format_memory(bytes: u64) -> String {
         format!("{}B", bytes)
     }
 }
+
+/// Formats the saturation of a node as a percentage, or `-` if it's not reported.
+pub fn format_saturation(saturation: Option<f64>) -> String {
+    match saturation {
+        Some(saturation) => format!("{:.0}%", saturation * 100.0),
+        None => "-".to_string(),
+    }
+}
+
+/// Formats the executors of a node with its max executors, e.g. `3/4`, or
+/// only the executors if it's unlimited.
+pub fn format_executors(executors: u32, max_executors: Option<u32>) -> String {
+    match max_executors {
+        Some(max) => format!("{executors}/{max}"),
+        None => executors.to_string(),
+    }
+}
+
+/// Parses the duration, e.g. `60s`, `5m`, `1h`, or seconds without the unit.
+pub fn parse_duration(duration: &str) -> Result<Duration, FlameError> {
+    let invalid = || FlameError::InvalidConfig(format!("invalid duration <{duration}>"));
+
+    let (value, unit) = match duration.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => duration.split_at(i),
+        None => (duration, "s"),
+    };
+    let value: u64 = value.parse().map_err(|_| invalid())?;
+    let secs = match unit {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 3600,
+        _ => return Err(invalid()),
+    };
+
+    Ok(Duration::from_secs(secs))
+}
+
+/// Parses the point in time, i.e. RFC 3339, e.g. `2026-10-15T08:00:00Z`, the
+/// local time, e.g. `2026-10-15 08:00:00`, or the duration ago, e.g. `10m`.
+pub fn parse_time(time: &str) -> Result<DateTime<Utc>, FlameError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(time, "%F %T") {
+        return Local
+            .from_local_datetime(&time)
+            .single()
+            .map(|time| time.with_timezone(&Utc))
+            .ok_or_else(|| FlameError::InvalidConfig(format!("ambiguous local time <{time}>")));
+    }
+
+    let ago = parse_duration(time)
+        .map_err(|_| FlameError::InvalidConfig(format!("invalid time <{time}>")))?;
+    let ago = chrono::Duration::from_std(ago)
+        .map_err(|_| FlameError::InvalidConfig(format!("invalid time <{time}>")))?;
+
+    Ok(Utc::now() - ago)
+}
+
+/// The input is the content of the file if it starts with `@`, e.g. `@input.json`.
+pub fn read_input(input: &str) -> Result<Bytes, Box<dyn Error>> {
+    match input.strip_prefix('@') {
+        Some(path) => Ok(Bytes::from(fs::read(path).map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to read input <{path}>: {e}"))
+        })?)),
+        None => Ok(Bytes::from(input.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("60").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("60s").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10d").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let time = parse_time("2026-10-15T08:00:00Z").unwrap();
+        assert_eq!(time.timestamp(), 1_792_051_200);
+        let time = parse_time("2026-10-15T10:00:00+02:00").unwrap();
+        assert_eq!(time.timestamp(), 1_792_051_200);
+        assert!(parse_time("2026-10-15 08:00:00").is_ok());
+
+        let time = parse_time("10m").unwrap();
+        assert!((600..660).contains(&(Utc::now() - time).num_seconds()));
+
+        assert!(parse_time("yesterday").is_err());
+    }
+}
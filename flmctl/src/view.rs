@@ -12,53 +12,125 @@ limitations under the License.
 */
 
 use std::error::Error;
+use std::fs;
 
 use comfy_table::presets::NOTHING;
 use comfy_table::Table;
 use serde_json::Value;
 
-use flame_rs::apis::{FlameContext, FlameError, TaskState};
+use flame_rs::apis::{
+    FairnessPolicy, FlameContext, FlameError, PayloadCodec, SessionID, TaskArtifact, TaskID,
+    TopologyPolicy,
+};
+use flame_rs::client::federation::Federation;
 use flame_rs::client::{self, NodeState};
 
-use crate::utils::format_memory;
+use crate::utils::{format_executors, format_memory, format_saturation};
+
+/// The codes of the session events about the binding of its executors, which are
+/// shown as a timeline of the executors instead of the other events.
+const EXECUTOR_EVENTS: std::ops::RangeInclusive<i32> = 110..=113;
+
+/// Options for rendering the schemas of an application.
+#[derive(Clone, Debug, Default)]
+pub struct SchemaOptions {
+    /// Print the full JSON schemas instead of the schema types.
+    pub schema: bool,
+    /// The JSON payload to validate against the input schema.
+    pub validate_file: Option<String>,
+}
 
 pub async fn run(
     ctx: &FlameContext,
     output_format: &Option<String>,
     application: &Option<String>,
-    session: &Option<String>,
-    task: &Option<String>,
+    session: &Option<SessionID>,
+    task: &Option<TaskID>,
     node: &Option<String>,
+    schema_opts: &SchemaOptions,
 ) -> Result<(), Box<dyn Error>> {
-    let current_ctx = ctx.get_current_context()?;
-    let conn = client::connect_with_tls(
-        &current_ctx.cluster.endpoint,
-        current_ctx.cluster.tls.as_ref(),
-    )
-    .await?;
     match (application, session, task, node) {
-        (Some(application), None, None, None) => view_application(conn, application).await,
-        (None, Some(session), None, None) => view_session(conn, output_format, session).await,
-        (None, Some(session), Some(task), None) => view_task(conn, session, task).await,
-        (None, None, None, Some(node)) => view_node(conn, node).await,
+        (Some(application), None, None, None) => {
+            view_application(connect(ctx).await?, application, schema_opts).await
+        }
+        (None, Some(session), None, None) => {
+            view_session(Federation::connect(ctx).await?, output_format, session).await
+        }
+        (None, Some(session), Some(task), None) => {
+            view_task(Federation::connect(ctx).await?, session, task).await
+        }
+        (None, None, None, Some(node)) => view_node(connect(ctx).await?, node).await,
         _ => Err(Box::new(FlameError::InvalidConfig(
             "unsupported parameters".to_string(),
         ))),
     }
 }
 
+async fn connect(ctx: &FlameContext) -> Result<client::Connection, FlameError> {
+    let current_ctx = ctx.get_current_context()?;
+    client::connect_with_tls(
+        &current_ctx.cluster.endpoint,
+        current_ctx.cluster.tls.as_ref(),
+    )
+    .await
+}
+
 async fn view_task(
-    conn: client::Connection,
-    ssn_id: &String,
-    task_id: &String,
+    federation: Federation,
+    ssn_id: &SessionID,
+    task_id: &TaskID,
 ) -> Result<(), Box<dyn Error>> {
-    let session = conn.get_session(ssn_id).await?;
+    let session = federation.get_session(ssn_id).await?;
     let task = session.get_task(task_id).await?;
 
     println!("{:<15}{}", "Task:", task.id);
     println!("{:<15}{}", "Session:", session.id);
     println!("{:<15}{}", "Application:", session.application);
     println!("{:<15}{}", "State:", task.state);
+    if let Some(trace_id) = &task.trace_id {
+        println!("{:<15}{}", "Trace ID:", trace_id);
+    }
+    if let Some(reason) = task.failure_reason {
+        println!("{:<15}{}", "Failure:", reason);
+    }
+    if task.attempts > 0 {
+        println!("{:<15}{}", "Attempts:", task.attempts);
+    }
+    if let Some(input_ref) = &task.input_ref {
+        println!("{:<15}{}/{}", "Input:", input_ref.endpoint, input_ref.key);
+    }
+    if let Some(output_ref) = &task.output_ref {
+        println!(
+            "{:<15}{}/{}",
+            "Output:", output_ref.endpoint, output_ref.key
+        );
+    }
+    if let Some(output) = &task.output {
+        let codec = application_codec(&federation, &session.application).await;
+        println!("{:<15}{}", "Output:", format_output(codec, output));
+    }
+    if let Some(checkpoint) = &task.checkpoint {
+        println!("{:<15}{}", "Checkpoint:", checkpoint);
+    }
+    if let Some(postmortem) = &task.postmortem {
+        println!(
+            "{:<15}executor <{}> on node <{}>, see `flmctl debug postmortem`",
+            "Crashed:", postmortem.executor_id, postmortem.node
+        );
+    }
+    if let Some(usage) = task.usage {
+        println!("{:<15}", "Usage:");
+        println!("  cpu_time: {}ms", usage.cpu_time_ms);
+        println!("  max_rss: {}", format_memory(usage.max_rss_bytes));
+        println!("  read: {}", format_memory(usage.read_bytes));
+        println!("  write: {}", format_memory(usage.write_bytes));
+    }
+    if !task.artifacts.is_empty() {
+        println!("{:<15}", "Artifacts:");
+        for artifact in &task.artifacts {
+            println!("  {}: {}", artifact.name, artifact_summary(artifact));
+        }
+    }
     println!("{:<15}", "Events:");
 
     for event in task.events {
@@ -73,53 +145,161 @@ async fn view_task(
     Ok(())
 }
 
+/// The codec of the application, from whichever cluster has it.
+async fn application_codec(federation: &Federation, application: &str) -> PayloadCodec {
+    for cluster in federation.clusters() {
+        if let Ok(app) = cluster.connection.get_application(application).await {
+            return app.attributes.codec.unwrap_or_default();
+        }
+    }
+
+    PayloadCodec::Raw
+}
+
+/// Formats the output by the codec of the application; the JSON outputs are
+/// pretty-printed, and the others are summarized by their sizes.
+fn format_output(codec: PayloadCodec, output: &[u8]) -> String {
+    match codec {
+        PayloadCodec::Json => match codec.decode::<Value>(output) {
+            Ok(value) => {
+                let pretty = serde_json::to_string_pretty(&value).unwrap_or_default();
+                format!("\n{pretty}")
+            }
+            Err(e) => format!("invalid JSON ({} bytes): {e}", output.len()),
+        },
+        PayloadCodec::Raw => match std::str::from_utf8(output) {
+            Ok(text) => text.to_string(),
+            Err(_) => format!("{} bytes", output.len()),
+        },
+        _ => format!("{codec} ({} bytes)", output.len()),
+    }
+}
+
+fn artifact_summary(artifact: &TaskArtifact) -> String {
+    match (&artifact.data, &artifact.object_ref) {
+        (_, Some(object_ref)) => format!("object <{object_ref}>"),
+        (Some(data), None) => format!("inline ({} bytes)", data.len()),
+        (None, None) => "empty".to_string(),
+    }
+}
+
 async fn view_session(
-    conn: client::Connection,
+    federation: Federation,
     output_format: &Option<String>,
-    ssn_id: &String,
+    ssn_id: &SessionID,
 ) -> Result<(), Box<dyn Error>> {
-    let mut session = conn.get_session(ssn_id).await?;
-    let tasks = session.list_tasks().await?;
-
-    session.tasks = Some(tasks);
+    let mut session = federation.get_session(ssn_id).await?;
 
-    match output_format {
-        Some(format) => match format.as_str() {
-            "json" => view_session_json(&session),
-            _ => view_session_table(&session),
-        },
-        None => view_session_table(&session),
+    match output_format.as_deref() {
+        Some("json") => {
+            let tasks = session.list_tasks().await?;
+            session.tasks = Some(tasks);
+            view_session_json(&session)
+        }
+        _ => {
+            let summary = session.summary(None).await?;
+            view_session_table(&session, &summary)
+        }
     }
 }
 
-fn view_session_table(session: &client::Session) -> Result<(), Box<dyn Error>> {
+fn view_session_table(
+    session: &client::Session,
+    summary: &client::SessionSummary,
+) -> Result<(), Box<dyn Error>> {
     let mut table = Table::new();
     table.load_preset(NOTHING);
 
-    table.add_row(vec!["Session:", &session.id.to_string()]);
+    table.add_row(vec!["Session:", session.id.as_str()]);
     table.add_row(vec!["Application:", &session.application.to_string()]);
     table.add_row(vec!["State:", &session.state.to_string()]);
+    table.add_row(vec!["Priority:", &session.priority.to_string()]);
+    table.add_row(vec!["Results:", &session.result_policy.to_string()]);
+    if let Some(ttl) = session.ttl_after_completion {
+        table.add_row(vec!["TTL:", &format!("{ttl}s")]);
+    }
+    if let Some(reason) = &session.unschedulable {
+        table.add_row(vec!["Unschedulable:", reason]);
+    }
+    if let Some(trace_id) = &session.trace_id {
+        table.add_row(vec!["Trace ID:", trace_id]);
+    }
     table.add_row(vec![
         "Creation Time:",
         &session.creation_time.format("%T").to_string(),
     ]);
 
-    let mut success = 0;
-    let mut failed = 0;
-    for task in session.tasks.as_ref().unwrap() {
-        if task.state == TaskState::Succeed {
-            success += 1;
-        } else {
-            failed += 1;
-        }
-    }
-
     table.add_row(vec![
         "Tasks:",
-        &format!("{success} succeed, {failed} failed"),
+        &format!(
+            "{} pending, {} running, {} succeed, {} failed, {} cancelled, {} quarantined",
+            summary.pending,
+            summary.running,
+            summary.succeed,
+            summary.failed,
+            summary.cancelled,
+            summary.quarantined
+        ),
+    ]);
+    table.add_row(vec![
+        "Throughput:",
+        &format!(
+            "{:.1} tasks/min in the last {}m",
+            summary.throughput, summary.window
+        ),
     ]);
+    if let Some(stats) = session.task_durations.filter(|stats| stats.count > 0) {
+        table.add_row(vec![
+            "Task Durations:",
+            &format!(
+                "mean {:.1}ms, recent {:.1}ms, min {}ms, max {}ms",
+                stats.mean, stats.recent, stats.min, stats.max
+            ),
+        ]);
+    }
 
     println!("{table}");
+
+    if !summary.failures.is_empty() {
+        println!("{:<15}", "Failures:");
+        for failure in &summary.failures {
+            match failure.reason.filter(|r| r.to_string() != failure.category) {
+                Some(reason) => {
+                    println!("  {} ({}): {}", failure.category, reason, failure.count)
+                }
+                None => println!("  {}: {}", failure.category, failure.count),
+            }
+        }
+    }
+
+    let (executor_events, events): (Vec<_>, Vec<_>) = session
+        .events
+        .iter()
+        .partition(|event| EXECUTOR_EVENTS.contains(&event.code));
+
+    if !executor_events.is_empty() {
+        println!("{:<15}", "Executors:");
+        for event in executor_events {
+            println!(
+                "  {}: {}",
+                event.creation_time.format("%H:%M:%S%.3f"),
+                event.message.as_deref().unwrap_or_default()
+            );
+        }
+    }
+
+    if !events.is_empty() {
+        println!("{:<15}", "Events:");
+        for event in events {
+            println!(
+                "  {}: {} ({})",
+                event.creation_time.format("%H:%M:%S%.3f"),
+                event.message.as_deref().unwrap_or_default(),
+                event.code
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -132,8 +312,22 @@ fn view_session_json(session: &client::Session) -> Result<(), Box<dyn Error>> {
 async fn view_application(
     conn: client::Connection,
     application: &str,
+    schema_opts: &SchemaOptions,
 ) -> Result<(), Box<dyn Error>> {
     let application = conn.get_application(application).await?;
+
+    if let Some(path) = &schema_opts.validate_file {
+        let input = application
+            .attributes
+            .schema
+            .as_ref()
+            .and_then(|s| s.input.clone());
+        return validate_file(&application.name, input, path);
+    }
+
+    if schema_opts.schema {
+        return view_application_schema(&application);
+    }
     println!("{:<15}{}", "Name:", application.name);
     println!(
         "{:<15}{}",
@@ -176,6 +370,13 @@ async fn view_application(
     for (key, value) in application.attributes.environments {
         println!("\t{key}: {value}");
     }
+    println!("{:<15}", "Task Overrides:");
+    for name in application.attributes.task_environments {
+        println!("\tenv: {name}");
+    }
+    for name in application.attributes.task_arguments {
+        println!("\targ: {name}");
+    }
     println!(
         "{:<15}{}",
         "WorkingDir:",
@@ -186,11 +387,117 @@ async fn view_application(
         "Max Instances:",
         application.attributes.max_instances.unwrap_or_default()
     );
+    println!(
+        "{:<15}{}",
+        "Max Per Node:",
+        application
+            .attributes
+            .max_instances_per_node
+            .map_or("unlimited".to_string(), |n| n.to_string())
+    );
+    println!(
+        "{:<15}{}",
+        "Fairness:",
+        application
+            .attributes
+            .fairness
+            .unwrap_or(FairnessPolicy::RoundRobin)
+    );
+    println!(
+        "{:<15}{}",
+        "Codec:",
+        application.attributes.codec.unwrap_or_default()
+    );
+    if let Some(payload_type) = &application.attributes.payload_type {
+        println!("{:<15}{}", "Payload Type:", payload_type);
+    }
+    println!(
+        "{:<15}{}",
+        "Topology:",
+        application
+            .attributes
+            .topology
+            .unwrap_or(TopologyPolicy::Ignore)
+    );
+    println!(
+        "{:<15}{}",
+        "Bundle Size:",
+        application.attributes.bundle_size.unwrap_or(1)
+    );
     println!(
         "{:<15}{}",
         "Delay Release:",
         application.attributes.delay_release.unwrap_or_default()
     );
+    println!("{:<15}", "Session Defaults:");
+    if let Some(defaults) = application.attributes.session_defaults {
+        let fields = [
+            ("slots", defaults.slots.map(u64::from)),
+            ("min_instances", defaults.min_instances.map(u64::from)),
+            ("max_instances", defaults.max_instances.map(u64::from)),
+            ("batch_size", defaults.batch_size.map(u64::from)),
+            ("scratch_size", defaults.scratch_size),
+            (
+                "max_task_attempts",
+                defaults.max_task_attempts.map(u64::from),
+            ),
+            ("task_timeout", defaults.task_timeout),
+            ("task_priority", defaults.task_priority.map(u64::from)),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                println!("	{name}: {value}");
+            }
+        }
+    }
+    println!("{:<15}", "Health Probe:");
+    if let Some(probe) = application.attributes.health_probe {
+        if let Some(command) = probe.command {
+            println!("	command: {command}");
+        }
+        if let Some(service) = probe.grpc_service {
+            println!("	grpc_service: {service}");
+        }
+        let fields = [
+            ("period_seconds", probe.period_seconds),
+            ("timeout_seconds", probe.timeout_seconds),
+            ("failure_threshold", probe.failure_threshold),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                println!("	{name}: {value}");
+            }
+        }
+    }
+    println!("{:<15}", "Hooks:");
+    if let Some(hooks) = application.attributes.hooks {
+        let fields = [
+            ("pre_bind", hooks.pre_bind),
+            ("post_bind", hooks.post_bind),
+            ("pre_release", hooks.pre_release),
+        ];
+        for (name, hook) in fields {
+            if let Some(hook) = hook {
+                match hook.timeout_seconds {
+                    Some(timeout) => println!("	{name}: {} (timeout: {timeout}s)", hook.command),
+                    None => println!("	{name}: {}", hook.command),
+                }
+            }
+        }
+    }
+    println!("{:<15}", "Python:");
+    if let Some(env) = application.attributes.python_env {
+        let fields = [
+            ("project", env.project),
+            ("requirements", env.requirements),
+            ("python_version", env.python_version),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                println!("	{name}: {value}");
+            }
+        }
+    }
 
     println!("{:<15}", "Schema:");
 
@@ -212,27 +519,158 @@ async fn view_node(conn: client::Connection, node_name: &str) -> Result<(), Box<
     let status = match node.state {
         NodeState::Ready => "Ready",
         NodeState::NotReady => "NotReady",
+        NodeState::Lost => "Lost",
         NodeState::Unknown => "Unknown",
     };
 
     println!("{:<15}{}", "Name:", node.name);
     println!("{:<15}{}", "Hostname:", node.hostname);
     println!("{:<15}{}", "Status:", status);
+    if let Some(last_heartbeat) = node.last_heartbeat {
+        println!("{:<15}{}", "Heartbeat:", last_heartbeat.format("%T"));
+    }
     println!("{:<15}", "Capacity:");
     println!("  {:<13}{}", "CPU:", node.cpu);
     println!("  {:<13}{}", "Memory:", format_memory(node.memory));
+    println!("{:<15}", "Allocatable:");
+    println!("  {:<13}{}", "CPU:", node.allocatable_cpu);
+    println!(
+        "  {:<13}{}",
+        "Memory:",
+        format_memory(node.allocatable_memory)
+    );
+    println!(
+        "{:<15}{}",
+        "Saturation:",
+        format_saturation(node.saturation)
+    );
+    println!(
+        "{:<15}{}",
+        "Executors:",
+        format_executors(node.executors, node.max_executors)
+    );
     println!("{:<15}", "Info:");
     println!("  {:<13}{}", "Arch:", node.arch);
     println!("  {:<13}{}", "OS:", node.os);
+    for numa_node in &node.numa_nodes {
+        println!(
+            "  {:<13}{}",
+            format!("NUMA {}:", numa_node.id),
+            numa_node.cpus
+        );
+    }
+    for (i, group) in node.gpu_groups.iter().enumerate() {
+        let gpus: Vec<String> = group.gpus.iter().map(|gpu| gpu.to_string()).collect();
+        let numa_node = group
+            .numa_node
+            .map_or("-".to_string(), |node| node.to_string());
+        println!(
+            "  {:<13}{} ({}, NUMA {})",
+            format!("GPU Group {i}:"),
+            gpus.join(","),
+            group.link,
+            numa_node
+        );
+    }
+    if !node.prefetched.is_empty() {
+        println!("  {:<13}{}", "Prefetched:", node.prefetched.join(", "));
+    }
+
+    if !node.events.is_empty() {
+        println!("{:<15}", "Events:");
+        for event in node.events {
+            println!(
+                "  {}: {} ({})",
+                event.creation_time.format("%H:%M:%S%.3f"),
+                event.message.as_deref().unwrap_or_default(),
+                event.code
+            );
+        }
+    }
 
     Ok(())
 }
 
+fn view_application_schema(application: &client::Application) -> Result<(), Box<dyn Error>> {
+    println!("{:<15}{}", "Name:", application.name);
+
+    let schema = application.attributes.schema.clone();
+    let schemas = [
+        ("Input", schema.as_ref().and_then(|s| s.input.clone())),
+        ("Output", schema.as_ref().and_then(|s| s.output.clone())),
+        (
+            "Common Data",
+            schema.as_ref().and_then(|s| s.common_data.clone()),
+        ),
+    ];
+
+    for (name, schema) in schemas {
+        println!("{:<15}", format!("{name}:"));
+        match schema {
+            Some(schema) => {
+                let value = parse_schema(&schema)?;
+                for line in serde_json::to_string_pretty(&value)?.lines() {
+                    println!("  {line}");
+                }
+            }
+            None => println!("  -"),
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_file(app: &str, schema: Option<String>, path: &str) -> Result<(), Box<dyn Error>> {
+    let schema = schema.ok_or(FlameError::InvalidConfig(format!(
+        "application <{app}> has no input schema"
+    )))?;
+    let schema = parse_schema(&schema)?;
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to read <{path}>: {e}")))?;
+    let payload = serde_json::from_str::<Value>(&contents)
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid JSON in <{path}>: {e}")))?;
+
+    let errors = schema_errors(&schema, &payload)?;
+    if errors.is_empty() {
+        println!("<{path}> is a valid input of application <{app}>");
+        return Ok(());
+    }
+
+    println!("<{path}> is not a valid input of application <{app}>:");
+    for error in &errors {
+        println!("  {error}");
+    }
+
+    Err(Box::new(FlameError::InvalidConfig(format!(
+        "{} validation error(s) in <{path}>",
+        errors.len()
+    ))))
+}
+
+/// Validates the payload against the schema, returning one message per violation.
+fn schema_errors(schema: &Value, payload: &Value) -> Result<Vec<String>, FlameError> {
+    let validator = jsonschema::validator_for(schema)
+        .map_err(|e| FlameError::InvalidConfig(format!("invalid schema: {e}")))?;
+
+    Ok(validator
+        .iter_errors(payload)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            let path = if path.is_empty() { "/" } else { path.as_str() };
+            format!("{path}: {e}")
+        })
+        .collect())
+}
+
+fn parse_schema(schema: &str) -> Result<Value, FlameError> {
+    serde_json::from_str::<Value>(schema).map_err(|e| FlameError::InvalidConfig(e.to_string()))
+}
+
 fn get_type(schema: Option<String>) -> Result<String, FlameError> {
     match schema {
         Some(schema) => {
-            let value = serde_json::from_str::<Value>(&schema)
-                .map_err(|e| FlameError::InvalidConfig(e.to_string()))?;
+            let value = parse_schema(&schema)?;
             let schema_type = value.get("type").ok_or(FlameError::InvalidConfig(
                 "schema type is missed".to_string(),
             ))?;
@@ -241,3 +679,62 @@ fn get_type(schema: Option<String>) -> Result<String, FlameError> {
         None => Ok("-".to_string()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_errors() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "language": { "type": "string" },
+                "code": { "type": "string" }
+            },
+            "required": ["language", "code"]
+        });
+
+        let valid = json!({"language": "python", "code": "print(1)"});
+        assert!(schema_errors(&schema, &valid).unwrap().is_empty());
+
+        let invalid = json!({"language": 1});
+        let errors = schema_errors(&schema, &invalid).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.starts_with("/language:")));
+        assert!(errors.iter().any(|e| e.starts_with("/:")));
+    }
+
+    #[test]
+    fn test_schema_errors_invalid_schema() {
+        let schema = json!({"type": "unknown"});
+        assert!(schema_errors(&schema, &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_artifact_summary() {
+        assert_eq!(
+            artifact_summary(&TaskArtifact::inline("summary", "1234")),
+            "inline (4 bytes)"
+        );
+        assert_eq!(
+            artifact_summary(&TaskArtifact::object("model", "grpc://cache/ssn/model")),
+            "object <grpc://cache/ssn/model>"
+        );
+    }
+
+    #[test]
+    fn test_format_output() {
+        assert_eq!(
+            format_output(PayloadCodec::Json, br#"{"sum":3}"#),
+            "\n{\n  \"sum\": 3\n}"
+        );
+        assert!(format_output(PayloadCodec::Json, b"{").starts_with("invalid JSON (1 bytes)"));
+        assert_eq!(format_output(PayloadCodec::Raw, b"pong"), "pong");
+        assert_eq!(
+            format_output(PayloadCodec::Msgpack, &[0x81, 0xa3]),
+            "Msgpack (2 bytes)"
+        );
+    }
+}
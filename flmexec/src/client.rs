@@ -21,7 +21,7 @@ use indicatif::HumanCount;
 use serde_derive::{Deserialize, Serialize};
 
 use flame_rs as flame;
-use flame_rs::apis::{FlameContext, FlameError};
+use flame_rs::apis::{FlameContext, FlameError, ResultPolicy};
 use flame_rs::client::{SessionAttributes, Task, TaskInformer};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,13 +85,20 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let ssn_creation_start_time = Local::now();
     let ssn_attr = SessionAttributes {
-        id: format!("{DEFAULT_APP}-{}", stdng::rand::short_name()),
+        id: format!("{DEFAULT_APP}-{}", stdng::rand::short_name()).into(),
         application: DEFAULT_APP.to_string(),
         slots,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn = conn.create_session(&ssn_attr).await?;
     let ssn_creation_end_time = Local::now();
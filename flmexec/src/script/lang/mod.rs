@@ -11,5 +11,16 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::path::PathBuf;
+
 pub mod python;
 pub mod shell;
+
+/// A new working directory of a script under the temporary directory of the
+/// platform, e.g. `/tmp/flame/script/<lang>-<random>` on Linux.
+fn script_work_dir(lang: &str, id: u32) -> PathBuf {
+    std::env::temp_dir()
+        .join("flame")
+        .join("script")
+        .join(format!("{lang}-{id}"))
+}
@@ -28,19 +28,21 @@ use stdng::trace_fn;
 use crate::api::{Script, ScriptRuntime};
 use crate::script::ScriptEngine;
 
+use super::script_work_dir;
+
 const DEFAULT_ENTRYPOINT: &str = "main.py";
 
-/// Get the uv command path from FLAME_HOME or fallback to system uv
+/// Get the uv command path from FLAME_HOME or fallback to the uv in PATH
 fn get_uv_cmd() -> String {
-    let flame_home = std::env::var("FLAME_HOME").unwrap_or_else(|_| "/usr/local/flame".to_string());
-    let uv_path = format!("{}/bin/uv", flame_home);
-
     // Check if uv exists in FLAME_HOME, otherwise fallback to system uv
-    if std::path::Path::new(&uv_path).exists() {
-        uv_path
-    } else {
-        "/usr/bin/uv".to_string()
+    if let Ok(flame_home) = std::env::var("FLAME_HOME") {
+        let uv_path = format!("{}/bin/uv", flame_home);
+        if std::path::Path::new(&uv_path).exists() {
+            return uv_path;
+        }
     }
+
+    "uv".to_string()
 }
 
 pub struct PythonScript {
@@ -52,11 +54,10 @@ impl PythonScript {
         trace_fn!("PythonScript::new");
 
         let mut rng = rand::rng();
-        let work_dir_path = format!("/tmp/flame/script/python-{}", rng.random::<u32>());
-        let work_dir = Path::new(&work_dir_path);
+        let work_dir = script_work_dir("python", rng.random::<u32>());
 
-        fs::create_dir_all(work_dir).map_err(|e| FlameError::Internal(e.to_string()))?;
-        tracing::debug!("Created work directory: {work_dir_path}");
+        fs::create_dir_all(&work_dir).map_err(|e| FlameError::Internal(e.to_string()))?;
+        tracing::debug!("Created work directory: {}", work_dir.display());
 
         let entrypoint = DEFAULT_ENTRYPOINT;
 
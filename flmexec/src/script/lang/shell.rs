@@ -28,6 +28,8 @@ use stdng::trace_fn;
 use crate::api::{Script, ScriptRuntime};
 use crate::script::ScriptEngine;
 
+use super::script_work_dir;
+
 const DEFAULT_ENTRYPOINT: &str = "main.sh";
 const SHELL_CMD: &str = "/bin/bash";
 
@@ -40,11 +42,10 @@ impl ShellScript {
         trace_fn!("ShellScript::new");
 
         let mut rng = rand::rng();
-        let work_dir_path = format!("/tmp/flame/script/shell-{}", rng.random::<u32>());
-        let work_dir = Path::new(&work_dir_path);
+        let work_dir = script_work_dir("shell", rng.random::<u32>());
 
-        fs::create_dir_all(work_dir).map_err(|e| FlameError::Internal(e.to_string()))?;
-        tracing::debug!("Created work directory: {work_dir_path}");
+        fs::create_dir_all(&work_dir).map_err(|e| FlameError::Internal(e.to_string()))?;
+        tracing::debug!("Created work directory: {}", work_dir.display());
 
         let entrypoint = DEFAULT_ENTRYPOINT;
 
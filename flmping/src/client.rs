@@ -20,7 +20,7 @@ use byte_unit::Byte;
 use clap::Parser;
 use comfy_table::presets::NOTHING;
 use comfy_table::Table;
-use flame_rs::apis::FlameError;
+use flame_rs::apis::{FlameError, ResultPolicy};
 use flame_rs::client::{SessionAttributes, Task, TaskInformer};
 use futures::future::try_join_all;
 use indicatif::HumanCount;
@@ -83,13 +83,20 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let ssn_creation_start_time = Instant::now();
     let ssn_attr = SessionAttributes {
-        id: format!("{DEFAULT_APP}-{}", stdng::rand::short_name()),
+        id: format!("{DEFAULT_APP}-{}", stdng::rand::short_name()).into(),
         application: DEFAULT_APP.to_string(),
         slots,
         common_data: cli.common_data.map(|s| s.into()),
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn = conn.create_session(&ssn_attr).await?;
     let ssn_creation_end_time = Instant::now();
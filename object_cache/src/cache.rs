@@ -11,13 +11,14 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use arrow::array::{BinaryArray, RecordBatch, UInt64Array};
+use arrow::array::{ArrayRef, BinaryArray, LargeBinaryArray, RecordBatch, UInt64Array};
 use arrow::compute::concat_batches;
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::ipc::{reader::FileReader, writer::FileWriter};
@@ -43,6 +44,8 @@ use common::ctx::FlameCache;
 use common::FlameError;
 
 use crate::eviction::{new_policy, EvictionConfig, EvictionPolicyPtr};
+use crate::gc::{self, OrphanTracker};
+use crate::upload::{CompleteUpload, UploadManager, UPLOADS_DIR};
 
 /// Maximum number of deltas allowed per object before requiring compaction.
 /// This prevents unbounded growth of delta files.
@@ -67,8 +70,13 @@ fn validate_key(key: &str) -> Result<(), FlameError> {
 
 /// Validate a session ID does not contain path traversal sequences.
 /// Security: Prevents directory traversal attacks via user-controlled session IDs.
+/// The IDs starting with '.' are reserved for the directories of the cache, e.g. the uploads.
 fn validate_session_id(session_id: &str) -> Result<(), FlameError> {
-    if session_id.contains("..") || session_id.contains('/') || session_id.contains('\\') {
+    if session_id.contains("..")
+        || session_id.contains('/')
+        || session_id.contains('\\')
+        || session_id.starts_with('.')
+    {
         return Err(FlameError::InvalidConfig(format!(
             "Invalid session_id '{}': contains path traversal sequences",
             session_id
@@ -131,6 +139,14 @@ pub struct ObjectMetadata {
     pub delta_count: u64,
 }
 
+/// The result of a completed multi-part upload, with the checksum of the object.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadResult {
+    #[serde(flatten)]
+    pub metadata: ObjectMetadata,
+    pub checksum: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct CacheEndpoint {
     pub scheme: String,
@@ -223,6 +239,10 @@ pub struct ObjectCache {
     metadata: MutexPtr<HashMap<String, ObjectMetadata>>,
     /// Eviction policy
     eviction_policy: EvictionPolicyPtr,
+    /// Multi-part uploads in progress
+    uploads: UploadManager,
+    /// The sessions of the cached objects which are unknown to the session manager
+    orphans: MutexPtr<OrphanTracker>,
 }
 
 impl ObjectCache {
@@ -230,8 +250,10 @@ impl ObjectCache {
         endpoint: CacheEndpoint,
         storage_path: Option<PathBuf>,
         eviction_config: Option<&EvictionConfig>,
+        orphan_grace_period: Duration,
     ) -> Result<Self, FlameError> {
         let eviction_policy = new_policy(eviction_config);
+        let uploads = UploadManager::new(storage_path.as_deref())?;
 
         let cache = Self {
             endpoint,
@@ -239,6 +261,8 @@ impl ObjectCache {
             objects: new_ptr(HashMap::new()),
             metadata: new_ptr(HashMap::new()),
             eviction_policy,
+            uploads,
+            orphans: new_ptr(OrphanTracker::new(orphan_grace_period)),
         };
 
         // Load existing objects from disk
@@ -364,7 +388,7 @@ impl ObjectCache {
             let session_entry = session_entry?;
             let session_path = session_entry.path();
 
-            if !session_path.is_dir() {
+            if !session_path.is_dir() || session_path.ends_with(UPLOADS_DIR) {
                 continue;
             }
 
@@ -444,7 +468,7 @@ impl ObjectCache {
         // Write to disk if storage is configured
         if let Some(storage_path) = &self.storage_path {
             // Create session directory
-            let session_dir = storage_path.join(&session_id);
+            let session_dir = storage_path.join(session_id.as_str());
             fs::create_dir_all(&session_dir)?;
 
             // Write object to Arrow IPC file
@@ -654,6 +678,19 @@ impl ObjectCache {
         Err(FlameError::NotFound(format!("object <{}> not found", key)))
     }
 
+    /// Load the object into memory ahead of its use, e.g. the input of the next
+    /// task; returns whether it was in memory already.
+    async fn prefetch(&self, key: String) -> Result<bool, FlameError> {
+        validate_key(&key)?;
+
+        let in_memory = lock_ptr!(self.objects)?.contains_key(&key);
+        if !in_memory {
+            self.get(key).await?;
+        }
+
+        Ok(in_memory)
+    }
+
     async fn update(&self, key: String, new_object: Object) -> Result<ObjectMetadata, FlameError> {
         validate_key(&key)?;
 
@@ -782,7 +819,11 @@ impl ObjectCache {
     }
 
     async fn delete(&self, session_id: SessionID) -> Result<(), FlameError> {
-        validate_session_id(&session_id)?;
+        self.delete_session(&session_id)
+    }
+
+    fn delete_session(&self, session_id: &SessionID) -> Result<(), FlameError> {
+        validate_session_id(session_id)?;
 
         let keys_to_remove: Vec<String> = {
             let metadata = lock_ptr!(self.metadata)?;
@@ -801,7 +842,7 @@ impl ObjectCache {
 
         // Delete session directory and all objects (including deltas)
         if let Some(storage_path) = &self.storage_path {
-            let session_dir = storage_path.join(&session_id);
+            let session_dir = storage_path.join(session_id.as_str());
             if session_dir.exists() {
                 fs::remove_dir_all(&session_dir)?;
                 tracing::debug!("Deleted session directory: {:?}", session_dir);
@@ -821,6 +862,58 @@ impl ObjectCache {
         Ok(())
     }
 
+    /// The sessions of the cached objects, including the ones only on disk.
+    fn session_ids(&self) -> Result<HashSet<SessionID>, FlameError> {
+        let mut ids: HashSet<SessionID> = {
+            let metadata = lock_ptr!(self.metadata)?;
+            metadata
+                .keys()
+                .filter_map(|key| key.split_once('/'))
+                .map(|(session_id, _)| SessionID::from(session_id))
+                .collect()
+        };
+
+        if let Some(storage_path) = &self.storage_path {
+            for entry in fs::read_dir(storage_path)? {
+                let path = entry?.path();
+                if !path.is_dir() || path.ends_with(UPLOADS_DIR) {
+                    continue;
+                }
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    ids.insert(SessionID::from(name));
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Deletes the objects of the sessions which were not live for the grace
+    /// period; returns the deleted sessions.
+    pub(crate) fn reconcile(
+        &self,
+        live: &HashSet<SessionID>,
+        now: Instant,
+    ) -> Result<Vec<SessionID>, FlameError> {
+        let cached = self.session_ids()?;
+        let expired = lock_ptr!(self.orphans)?.expired(&cached, live, now);
+
+        let mut deleted = vec![];
+        for session_id in expired {
+            match self.delete_session(&session_id) {
+                Ok(()) => {
+                    tracing::info!("Deleted the objects of orphaned session <{session_id}>");
+                    deleted.push(session_id);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to delete orphaned session <{session_id}>: {e}")
+                }
+            }
+        }
+
+        Ok(deleted)
+    }
+
     async fn list_all(&self) -> Result<Vec<ObjectMetadata>, FlameError> {
         let metadata = lock_ptr!(self.metadata)?;
         Ok(metadata.values().cloned().collect())
@@ -891,7 +984,7 @@ impl FlightCacheServer {
 
     async fn collect_batches_from_stream(
         mut stream: Streaming<FlightData>,
-    ) -> Result<(String, Option<String>, Vec<RecordBatch>), FlameError> {
+    ) -> Result<(SessionID, Option<String>, Vec<RecordBatch>), FlameError> {
         let mut batches = Vec::new();
         let mut session_id: Option<String> = None;
         let mut object_id: Option<String> = None;
@@ -928,7 +1021,7 @@ impl FlightCacheServer {
             )
         })?;
 
-        Ok((session_id, object_id, batches))
+        Ok((session_id.into(), object_id, batches))
     }
 
     fn combine_batches(batches: Vec<RecordBatch>) -> Result<RecordBatch, FlameError> {
@@ -963,7 +1056,7 @@ impl FlightCacheServer {
             .split_once(':')
             .ok_or_else(|| FlameError::InvalidState("Invalid PUT action format".to_string()))?;
 
-        let session_id = session_id_str.to_string();
+        let session_id = SessionID::from(session_id_str);
         let data = base64::engine::general_purpose::STANDARD
             .decode(data_b64)
             .map_err(|e| FlameError::InvalidState(format!("Invalid base64: {}", e)))?;
@@ -992,11 +1085,17 @@ impl FlightCacheServer {
             .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
     }
 
-    async fn handle_delete_action(&self, session_id: String) -> Result<String, FlameError> {
+    async fn handle_delete_action(&self, session_id: SessionID) -> Result<String, FlameError> {
         self.cache.delete(session_id).await?;
         Ok("OK".to_string())
     }
 
+    /// Handle PREFETCH action: load an object into memory, "HIT" if it was in memory already
+    async fn handle_prefetch_action(&self, key: String) -> Result<String, FlameError> {
+        let hit = self.cache.prefetch(key).await?;
+        Ok(if hit { "HIT" } else { "LOADED" }.to_string())
+    }
+
     /// Handle PATCH action: append delta to an existing object
     async fn handle_patch_action(&self, action_body: &str) -> Result<String, FlameError> {
         let (key_str, data_b64) = action_body
@@ -1014,6 +1113,96 @@ impl FlightCacheServer {
         serde_json::to_string(&metadata)
             .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
     }
+
+    /// Handle UPLOAD_INIT action: initiate a multi-part upload to the key,
+    /// i.e. "session_id/object_id", or "session_id" for a generated object_id
+    async fn handle_upload_init_action(&self, action_body: &str) -> Result<String, FlameError> {
+        let key = match action_body.split_once('/') {
+            Some((session_id, object_id)) => {
+                validate_session_id(session_id)?;
+                validate_object_id(object_id)?;
+                action_body.to_string()
+            }
+            None => {
+                validate_session_id(action_body)?;
+                format!("{}/{}", action_body, uuid::Uuid::new_v4())
+            }
+        };
+
+        let info = self.cache.uploads.init(key)?;
+        serde_json::to_string(&info)
+            .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
+    }
+
+    /// Handle UPLOAD_PART action: "upload_id:part_number:sha256:base64(data)"
+    async fn handle_upload_part_action(&self, action_body: &str) -> Result<String, FlameError> {
+        let invalid = || FlameError::InvalidState("Invalid UPLOAD_PART action format".to_string());
+        let mut fields = action_body.splitn(4, ':');
+        let (upload_id, part_number, checksum, data_b64) = (
+            fields.next().ok_or_else(invalid)?,
+            fields.next().ok_or_else(invalid)?,
+            fields.next().ok_or_else(invalid)?,
+            fields.next().ok_or_else(invalid)?,
+        );
+
+        let part_number = part_number
+            .parse::<u32>()
+            .map_err(|e| FlameError::InvalidState(format!("Invalid part_number: {}", e)))?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(data_b64)
+            .map_err(|e| FlameError::InvalidState(format!("Invalid base64: {}", e)))?;
+
+        let part = self
+            .cache
+            .uploads
+            .upload_part(upload_id, part_number, checksum, data)?;
+        serde_json::to_string(&part)
+            .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
+    }
+
+    /// Handle UPLOAD_STATUS action: list the parts received, to resume the upload
+    async fn handle_upload_status_action(&self, upload_id: &str) -> Result<String, FlameError> {
+        let info = self.cache.uploads.status(upload_id)?;
+        serde_json::to_string(&info)
+            .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
+    }
+
+    /// Handle UPLOAD_COMPLETE action: put the object joined from the listed parts
+    async fn handle_upload_complete_action(&self, action_body: &str) -> Result<String, FlameError> {
+        let request: CompleteUpload = serde_json::from_str(action_body)
+            .map_err(|e| FlameError::InvalidState(format!("Invalid UPLOAD_COMPLETE: {}", e)))?;
+
+        let (key, data, checksum) = self.cache.uploads.complete(&request)?;
+        let (session_id, object_id) = key
+            .split_once('/')
+            .ok_or_else(|| FlameError::Internal(format!("Invalid key of upload: {}", key)))?;
+
+        let metadata = self
+            .cache
+            .put_with_id(
+                SessionID::from(session_id),
+                Some(object_id.to_string()),
+                Object::new(0, data),
+            )
+            .await?;
+        self.cache.uploads.remove(&request.upload_id)?;
+
+        tracing::debug!(
+            "Upload <{}> completed: {} ({} bytes)",
+            request.upload_id,
+            metadata.key,
+            metadata.size
+        );
+
+        serde_json::to_string(&UploadResult { metadata, checksum })
+            .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))
+    }
+
+    /// Handle UPLOAD_ABORT action: drop the upload and its parts
+    async fn handle_upload_abort_action(&self, upload_id: &str) -> Result<String, FlameError> {
+        self.cache.uploads.remove(upload_id)?;
+        Ok("OK".to_string())
+    }
 }
 
 // Helper function to encode schema to IPC format for FlightInfo
@@ -1055,17 +1244,28 @@ fn write_batch_to_file(path: &Path, batch: &RecordBatch) -> Result<(), FlameErro
 
 // Helper function to create a RecordBatch from object data
 // Note: Only serializes version and data; deltas are stored separately
+// The offsets of Binary are i32, so the data over 2GB, e.g. of a multi-part upload,
+// is kept as LargeBinary.
 fn object_to_batch(object: &Object) -> Result<RecordBatch, FlameError> {
-    let schema = get_object_schema();
+    let (schema, data_array): (Schema, ArrayRef) = if object.data.len() > i32::MAX as usize {
+        (
+            Schema::new(vec![
+                Field::new("version", DataType::UInt64, false),
+                Field::new("data", DataType::LargeBinary, false),
+            ]),
+            Arc::new(LargeBinaryArray::from(vec![object.data.as_slice()])),
+        )
+    } else {
+        (
+            get_object_schema(),
+            Arc::new(BinaryArray::from(vec![object.data.as_slice()])),
+        )
+    };
 
     let version_array = UInt64Array::from(vec![object.version]);
-    let data_array = BinaryArray::from(vec![object.data.as_slice()]);
 
-    RecordBatch::try_new(
-        Arc::new(schema),
-        vec![Arc::new(version_array), Arc::new(data_array)],
-    )
-    .map_err(|e| FlameError::Internal(format!("Failed to create RecordBatch: {}", e)))
+    RecordBatch::try_new(Arc::new(schema), vec![Arc::new(version_array), data_array])
+        .map_err(|e| FlameError::Internal(format!("Failed to create RecordBatch: {}", e)))
 }
 
 // Helper function to extract data from RecordBatch
@@ -1082,14 +1282,17 @@ fn batch_to_object(batch: &RecordBatch) -> Result<Object, FlameError> {
         .as_any()
         .downcast_ref::<UInt64Array>()
         .ok_or_else(|| FlameError::Internal("Invalid version column".to_string()))?;
-    let data_col = batch
-        .column(1)
-        .as_any()
-        .downcast_ref::<BinaryArray>()
-        .ok_or_else(|| FlameError::Internal("Invalid data column".to_string()))?;
+    let data_col = batch.column(1).as_any();
+    let data = match data_col.downcast_ref::<BinaryArray>() {
+        Some(data_col) => data_col.value(0).to_vec(),
+        None => data_col
+            .downcast_ref::<LargeBinaryArray>()
+            .ok_or_else(|| FlameError::Internal("Invalid data column".to_string()))?
+            .value(0)
+            .to_vec(),
+    };
 
     let version = version_col.value(0);
-    let data = data_col.value(0).to_vec();
 
     Ok(Object::new(version, data))
 }
@@ -1266,8 +1469,14 @@ impl FlightService for FlightCacheServer {
         let result = match action_type.as_str() {
             "PUT" => self.handle_put_action(&action_body).await?,
             "UPDATE" => self.handle_update_action(&action_body).await?,
-            "DELETE" => self.handle_delete_action(action_body).await?,
+            "DELETE" => self.handle_delete_action(action_body.into()).await?,
             "PATCH" => self.handle_patch_action(&action_body).await?,
+            "PREFETCH" => self.handle_prefetch_action(action_body).await?,
+            "UPLOAD_INIT" => self.handle_upload_init_action(&action_body).await?,
+            "UPLOAD_PART" => self.handle_upload_part_action(&action_body).await?,
+            "UPLOAD_STATUS" => self.handle_upload_status_action(&action_body).await?,
+            "UPLOAD_COMPLETE" => self.handle_upload_complete_action(&action_body).await?,
+            "UPLOAD_ABORT" => self.handle_upload_abort_action(&action_body).await?,
             _ => {
                 return Err(Status::invalid_argument(format!(
                     "Unknown action type: {}",
@@ -1306,6 +1515,31 @@ impl FlightService for FlightCacheServer {
                 r#type: "PATCH".to_string(),
                 description: "Append delta data to an existing object".to_string(),
             },
+            ActionType {
+                r#type: "PREFETCH".to_string(),
+                description: "Load an object into memory ahead of its use".to_string(),
+            },
+            ActionType {
+                r#type: "UPLOAD_INIT".to_string(),
+                description: "Initiate a multi-part upload of a large object".to_string(),
+            },
+            ActionType {
+                r#type: "UPLOAD_PART".to_string(),
+                description: "Upload a part of a multi-part upload with its checksum".to_string(),
+            },
+            ActionType {
+                r#type: "UPLOAD_STATUS".to_string(),
+                description: "List the parts received of a multi-part upload".to_string(),
+            },
+            ActionType {
+                r#type: "UPLOAD_COMPLETE".to_string(),
+                description: "Put the object joined from the parts of a multi-part upload"
+                    .to_string(),
+            },
+            ActionType {
+                r#type: "UPLOAD_ABORT".to_string(),
+                description: "Abort a multi-part upload and drop its parts".to_string(),
+            },
         ];
 
         let stream = futures::stream::iter(actions.into_iter().map(Ok));
@@ -1393,6 +1627,23 @@ impl FlightService for FlightCacheServer {
     }
 }
 
+/// Puts the object into the object cache running in this process, e.g. the
+/// output of a task offloaded by the executor manager; returns None if the
+/// object cache is not running.
+pub async fn put_local(
+    session_id: SessionID,
+    object_id: String,
+    data: Vec<u8>,
+) -> Result<Option<ObjectMetadata>, FlameError> {
+    match gc::local() {
+        Some(cache) => cache
+            .put_with_id(session_id, Some(object_id), Object::new(0, data))
+            .await
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
 /// Run the object cache server.
 ///
 /// # Arguments
@@ -1435,8 +1686,14 @@ pub async fn run(cache_config: &FlameCache) -> Result<(), FlameError> {
         endpoint.clone(),
         storage_path,
         Some(&eviction_config),
+        Duration::from_secs(cache_config.orphan_grace_period),
     )?);
-    let server = FlightCacheServer::new(Arc::clone(&cache));
+    gc::register(Arc::clone(&cache));
+    // An object is sent in one message, e.g. a file copied by `flmctl cp`, so its
+    // size is only limited by the eviction of the cache.
+    let server = FlightServiceServer::new(FlightCacheServer::new(Arc::clone(&cache)))
+        .max_decoding_message_size(usize::MAX)
+        .max_encoding_message_size(usize::MAX);
 
     tracing::info!("Starting Arrow Flight cache server at {}", address_str);
 
@@ -1454,6 +1711,18 @@ pub async fn run(cache_config: &FlameCache) -> Result<(), FlameError> {
             )
         })?;
 
+        // The handshake is done by rustls with a TLS policy.
+        if tls_config.policy.is_some() {
+            let incoming = common::tls::incoming(tls_config, addr).await?;
+            tracing::info!("TLS enabled with TLS policy for object cache");
+            builder
+                .add_service(server)
+                .serve_with_incoming(incoming)
+                .await
+                .map_err(|e| FlameError::Internal(format!("Server error: {}", e)))?;
+            return Ok(());
+        }
+
         let tls = tls_config.server_tls_config()?;
         builder = builder
             .tls_config(tls)
@@ -1463,7 +1732,7 @@ pub async fn run(cache_config: &FlameCache) -> Result<(), FlameError> {
     }
 
     builder
-        .add_service(FlightServiceServer::new(server))
+        .add_service(server)
         .serve(addr)
         .await
         .map_err(|e| FlameError::Internal(format!("Server error: {}", e)))?;
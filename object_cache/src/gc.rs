@@ -0,0 +1,138 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The garbage collection of the objects of the sessions which no longer exist.
+//!
+//! The session manager sends the IDs of its sessions to the executor managers
+//! periodically, which reconcile the object cache of their node with them. The
+//! sessions of the cache which are not in the list are orphans; their objects
+//! are deleted once they were orphans for the grace period, so the objects of a
+//! session created after the list was taken are not deleted by mistake.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use common::apis::SessionID;
+use common::FlameError;
+
+use crate::cache::ObjectCache;
+
+/// The object cache running in this process, if any.
+static CACHE: OnceLock<Arc<ObjectCache>> = OnceLock::new();
+
+pub(crate) fn register(cache: Arc<ObjectCache>) {
+    if CACHE.set(cache).is_err() {
+        tracing::warn!("The object cache was registered already.");
+    }
+}
+
+/// The object cache running in this process, if any.
+pub(crate) fn local() -> Option<Arc<ObjectCache>> {
+    CACHE.get().cloned()
+}
+
+/// Reconciles the object cache in this process with the live sessions of the
+/// session manager; returns the sessions whose objects were deleted. It's a
+/// no-op if the object cache is not running.
+pub fn reconcile(live: &HashSet<SessionID>) -> Result<Vec<SessionID>, FlameError> {
+    match CACHE.get() {
+        Some(cache) => cache.reconcile(live, Instant::now()),
+        None => Ok(vec![]),
+    }
+}
+
+/// The orphaned sessions of the cache with the time they were found orphaned.
+#[derive(Debug)]
+pub(crate) struct OrphanTracker {
+    grace_period: Duration,
+    orphans: HashMap<SessionID, Instant>,
+}
+
+impl OrphanTracker {
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            orphans: HashMap::new(),
+        }
+    }
+
+    /// Tracks the sessions of the cache which are not live, and returns the
+    /// ones which were orphans for the grace period.
+    pub fn expired(
+        &mut self,
+        cached: &HashSet<SessionID>,
+        live: &HashSet<SessionID>,
+        now: Instant,
+    ) -> Vec<SessionID> {
+        // The sessions which are live again or gone from the cache are forgotten.
+        self.orphans
+            .retain(|id, _| cached.contains(id) && !live.contains(id));
+
+        let mut expired = vec![];
+        for id in cached.difference(live) {
+            let since = *self.orphans.entry(id.clone()).or_insert(now);
+            if now.duration_since(since) >= self.grace_period {
+                expired.push(id.clone());
+            }
+        }
+
+        for id in &expired {
+            self.orphans.remove(id);
+        }
+
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> HashSet<SessionID> {
+        ids.iter().map(|id| SessionID::from(*id)).collect()
+    }
+
+    #[test]
+    fn test_orphan_tracker() {
+        let mut tracker = OrphanTracker::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        let cached = ids(&["ssn-1", "ssn-2", "ssn-3"]);
+
+        // The orphans are not deleted until the grace period.
+        let live = ids(&["ssn-1"]);
+        assert!(tracker.expired(&cached, &live, start).is_empty());
+        assert!(tracker
+            .expired(&cached, &live, start + Duration::from_secs(30))
+            .is_empty());
+
+        // The session which is live again is not an orphan anymore.
+        let live = ids(&["ssn-1", "ssn-2"]);
+        assert_eq!(
+            tracker.expired(&cached, &live, start + Duration::from_secs(60)),
+            vec![SessionID::from("ssn-3")]
+        );
+
+        // The grace period restarts once the session is orphaned again.
+        let cached = ids(&["ssn-1", "ssn-2"]);
+        let live = ids(&["ssn-1"]);
+        assert!(tracker
+            .expired(&cached, &live, start + Duration::from_secs(90))
+            .is_empty());
+        assert_eq!(
+            tracker.expired(&cached, &live, start + Duration::from_secs(150)),
+            vec![SessionID::from("ssn-2")]
+        );
+    }
+}
@@ -13,9 +13,15 @@ limitations under the License.
 
 pub mod cache;
 pub mod eviction;
+pub mod gc;
+pub mod upload;
 
 // Re-export commonly used types
-pub use cache::{run, CacheEndpoint, FlightCacheServer, Object, ObjectCache, ObjectMetadata};
+pub use cache::{
+    put_local, run, CacheEndpoint, FlightCacheServer, Object, ObjectCache, ObjectMetadata,
+    UploadResult,
+};
 pub use eviction::{
     new_policy, EvictionConfig, EvictionPolicy, EvictionPolicyPtr, LRUPolicy, NoEvictionPolicy,
 };
+pub use upload::{CompleteUpload, CompletedPart, UploadInfo, UploadPart};
@@ -0,0 +1,519 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Multi-part upload of the large objects, e.g. the artifacts of GBs uploaded
+//! over flaky links.
+//!
+//! An upload is initiated for the key of the object, and its parts are uploaded
+//! in any order, each verified by its SHA-256 checksum. A part is replaced if
+//! uploaded again, and the parts received are listed by the status of the
+//! upload, so the client resumes the upload after a failure by the parts not
+//! received yet. The object is put when the upload is completed by the list of
+//! its parts, which are joined in the order of their numbers.
+//!
+//! The parts are kept in the `.uploads` directory of the storage if configured,
+//! so the uploads survive a restart of the cache; otherwise they're kept in
+//! memory. The uploads idle for `UPLOAD_TTL` are aborted.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use ring::digest;
+use serde_derive::{Deserialize, Serialize};
+use stdng::{lock_ptr, new_ptr, MutexPtr};
+
+use common::FlameError;
+
+/// The directory of the uploads in the storage; it's skipped when loading the
+/// objects, as the session ids never start with '.'.
+pub const UPLOADS_DIR: &str = ".uploads";
+/// The max number of parts of an upload.
+pub const MAX_PARTS: u32 = 10000;
+
+const MANIFEST_FILE: &str = "upload.json";
+const UPLOAD_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// A part received by the cache.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadPart {
+    pub part_number: u32,
+    pub size: u64,
+    pub checksum: String,
+}
+
+/// The status of an upload, i.e. the parts received.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadInfo {
+    pub upload_id: String,
+    pub key: String,
+    pub parts: Vec<UploadPart>,
+}
+
+/// A part listed by the client to complete the upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedPart {
+    pub part_number: u32,
+    pub checksum: String,
+}
+
+/// The request of `UPLOAD_COMPLETE`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompleteUpload {
+    pub upload_id: String,
+    pub parts: Vec<CompletedPart>,
+}
+
+/// The manifest of an upload kept in the storage.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    key: String,
+    parts: Vec<UploadPart>,
+}
+
+struct Upload {
+    key: String,
+    parts: BTreeMap<u32, UploadPart>,
+    /// The data of the parts if no storage is configured.
+    data: HashMap<u32, Vec<u8>>,
+    last_active: Instant,
+}
+
+impl Upload {
+    fn new(key: String) -> Self {
+        Self {
+            key,
+            parts: BTreeMap::new(),
+            data: HashMap::new(),
+            last_active: Instant::now(),
+        }
+    }
+
+    fn info(&self, upload_id: &str) -> UploadInfo {
+        UploadInfo {
+            upload_id: upload_id.to_string(),
+            key: self.key.clone(),
+            parts: self.parts.values().cloned().collect(),
+        }
+    }
+}
+
+pub fn sha256(data: &[u8]) -> String {
+    to_hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The upload id is a part of the path of its directory, so only UUIDs are accepted.
+fn validate_upload_id(upload_id: &str) -> Result<(), FlameError> {
+    uuid::Uuid::parse_str(upload_id)
+        .map(|_| ())
+        .map_err(|_| FlameError::InvalidConfig(format!("Invalid upload_id '{}'", upload_id)))
+}
+
+pub struct UploadManager {
+    root: Option<PathBuf>,
+    uploads: MutexPtr<HashMap<String, Upload>>,
+}
+
+impl UploadManager {
+    pub fn new(storage_path: Option<&Path>) -> Result<Self, FlameError> {
+        let manager = Self {
+            root: storage_path.map(|p| p.join(UPLOADS_DIR)),
+            uploads: new_ptr(HashMap::new()),
+        };
+        manager.load_from_disk()?;
+
+        Ok(manager)
+    }
+
+    /// Loads the uploads not completed before the restart of the cache.
+    fn load_from_disk(&self) -> Result<(), FlameError> {
+        let Some(root) = self.root.as_ref().filter(|root| root.exists()) else {
+            return Ok(());
+        };
+
+        let mut uploads = lock_ptr!(self.uploads)?;
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            let upload_id = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default()
+                .to_string();
+
+            let manifest = fs::read(path.join(MANIFEST_FILE))
+                .ok()
+                .and_then(|content| serde_json::from_slice::<Manifest>(&content).ok());
+            let Some(manifest) = manifest.filter(|_| validate_upload_id(&upload_id).is_ok()) else {
+                tracing::warn!("Removing invalid upload: {:?}", path);
+                fs::remove_dir_all(&path)?;
+                continue;
+            };
+
+            let mut upload = Upload::new(manifest.key);
+            for part in manifest.parts {
+                upload.parts.insert(part.part_number, part);
+            }
+            uploads.insert(upload_id, upload);
+        }
+
+        tracing::info!("Loaded {} uploads from disk", uploads.len());
+        Ok(())
+    }
+
+    fn upload_dir(&self, upload_id: &str) -> Option<PathBuf> {
+        self.root.as_ref().map(|root| root.join(upload_id))
+    }
+
+    fn write_manifest(&self, upload_id: &str, upload: &Upload) -> Result<(), FlameError> {
+        let Some(dir) = self.upload_dir(upload_id) else {
+            return Ok(());
+        };
+
+        let manifest = Manifest {
+            key: upload.key.clone(),
+            parts: upload.parts.values().cloned().collect(),
+        };
+        let content = serde_json::to_vec(&manifest)
+            .map_err(|e| FlameError::Internal(format!("Failed to serialize: {}", e)))?;
+
+        let tmp = dir.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, dir.join(MANIFEST_FILE))?;
+        Ok(())
+    }
+
+    /// Initiates the upload of the object of the key.
+    pub fn init(&self, key: String) -> Result<UploadInfo, FlameError> {
+        self.expire()?;
+
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let upload = Upload::new(key);
+
+        if let Some(dir) = self.upload_dir(&upload_id) {
+            fs::create_dir_all(&dir)?;
+        }
+        self.write_manifest(&upload_id, &upload)?;
+
+        let info = upload.info(&upload_id);
+        lock_ptr!(self.uploads)?.insert(upload_id.clone(), upload);
+
+        tracing::debug!("Upload <{}> initiated for: {}", upload_id, info.key);
+        Ok(info)
+    }
+
+    /// Aborts the uploads idle for `UPLOAD_TTL`.
+    fn expire(&self) -> Result<(), FlameError> {
+        let expired: Vec<String> = lock_ptr!(self.uploads)?
+            .iter()
+            .filter(|(_, upload)| upload.last_active.elapsed() > UPLOAD_TTL)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for upload_id in expired {
+            tracing::info!("Aborting the idle upload <{}>", upload_id);
+            if let Err(e) = self.remove(&upload_id) {
+                tracing::warn!("Failed to abort upload <{}>: {}", upload_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn status(&self, upload_id: &str) -> Result<UploadInfo, FlameError> {
+        let uploads = lock_ptr!(self.uploads)?;
+        let upload = uploads
+            .get(upload_id)
+            .ok_or_else(|| FlameError::NotFound(format!("Upload not found: {}", upload_id)))?;
+
+        Ok(upload.info(upload_id))
+    }
+
+    /// Receives the part of the upload after verifying its checksum; the part
+    /// uploaded before with the same number is replaced.
+    pub fn upload_part(
+        &self,
+        upload_id: &str,
+        part_number: u32,
+        checksum: &str,
+        data: Vec<u8>,
+    ) -> Result<UploadPart, FlameError> {
+        validate_upload_id(upload_id)?;
+        if part_number == 0 || part_number > MAX_PARTS {
+            return Err(FlameError::InvalidConfig(format!(
+                "Invalid part_number {}: it must be in [1, {}]",
+                part_number, MAX_PARTS
+            )));
+        }
+
+        let actual = sha256(&data);
+        if actual != checksum.to_lowercase() {
+            return Err(FlameError::InvalidState(format!(
+                "Checksum mismatch of part {} of upload <{}>: expected {}, received {}",
+                part_number, upload_id, checksum, actual
+            )));
+        }
+
+        let part = UploadPart {
+            part_number,
+            size: data.len() as u64,
+            checksum: actual,
+        };
+
+        // The part is written before being listed, so a listed part is always complete.
+        let path = self
+            .upload_dir(upload_id)
+            .map(|dir| dir.join(format!("{}.part", part_number)));
+        if let Some(path) = &path {
+            self.status(upload_id)?;
+            let tmp = path.with_extension("part.tmp");
+            fs::write(&tmp, &data)?;
+            fs::rename(&tmp, path)?;
+        }
+
+        let mut uploads = lock_ptr!(self.uploads)?;
+        let upload = uploads
+            .get_mut(upload_id)
+            .ok_or_else(|| FlameError::NotFound(format!("Upload not found: {}", upload_id)))?;
+
+        upload.parts.insert(part_number, part.clone());
+        upload.last_active = Instant::now();
+        if path.is_none() {
+            upload.data.insert(part_number, data);
+        }
+        self.write_manifest(upload_id, upload)?;
+
+        Ok(part)
+    }
+
+    /// Joins the parts listed by the client, and returns the key and data of the
+    /// object with its checksum; the upload is kept until it's removed, so the
+    /// client may retry the completion if the object failed to be put.
+    pub fn complete(
+        &self,
+        request: &CompleteUpload,
+    ) -> Result<(String, Vec<u8>, String), FlameError> {
+        let upload_id = &request.upload_id;
+        if request.parts.is_empty() {
+            return Err(FlameError::InvalidConfig(format!(
+                "No parts to complete upload <{}>",
+                upload_id
+            )));
+        }
+        if request
+            .parts
+            .windows(2)
+            .any(|w| w[0].part_number >= w[1].part_number)
+        {
+            return Err(FlameError::InvalidConfig(format!(
+                "The parts of upload <{}> must be listed in ascending order",
+                upload_id
+            )));
+        }
+
+        let (key, parts, mut data_parts) = {
+            let mut uploads = lock_ptr!(self.uploads)?;
+            let upload = uploads
+                .get_mut(upload_id)
+                .ok_or_else(|| FlameError::NotFound(format!("Upload not found: {}", upload_id)))?;
+            upload.last_active = Instant::now();
+
+            let mut parts = Vec::with_capacity(request.parts.len());
+            let mut data_parts = HashMap::new();
+            for listed in &request.parts {
+                let part = upload
+                    .parts
+                    .get(&listed.part_number)
+                    .filter(|part| part.checksum == listed.checksum.to_lowercase())
+                    .ok_or_else(|| {
+                        FlameError::InvalidState(format!(
+                            "Part {} of upload <{}> is missing or has a different checksum",
+                            listed.part_number, upload_id
+                        ))
+                    })?;
+                parts.push(part.clone());
+                if let Some(data) = upload.data.get(&listed.part_number) {
+                    data_parts.insert(listed.part_number, data.clone());
+                }
+            }
+
+            (upload.key.clone(), parts, data_parts)
+        };
+
+        let size = parts.iter().map(|part| part.size).sum::<u64>();
+        let mut data = Vec::with_capacity(size as usize);
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        for part in &parts {
+            let part_data = match (
+                data_parts.remove(&part.part_number),
+                self.upload_dir(upload_id),
+            ) {
+                (Some(part_data), _) => part_data,
+                (None, Some(dir)) => fs::read(dir.join(format!("{}.part", part.part_number)))?,
+                (None, None) => {
+                    return Err(FlameError::Internal(format!(
+                        "No data of part {} of upload <{}>",
+                        part.part_number, upload_id
+                    )))
+                }
+            };
+            ctx.update(&part_data);
+            data.extend_from_slice(&part_data);
+        }
+
+        Ok((key, data, to_hex(ctx.finish().as_ref())))
+    }
+
+    /// Removes the upload and its parts, i.e. after completed or aborted.
+    pub fn remove(&self, upload_id: &str) -> Result<(), FlameError> {
+        validate_upload_id(upload_id)?;
+
+        let removed = lock_ptr!(self.uploads)?.remove(upload_id);
+        if removed.is_none() {
+            return Err(FlameError::NotFound(format!(
+                "Upload not found: {}",
+                upload_id
+            )));
+        }
+
+        if let Some(dir) = self.upload_dir(upload_id) {
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+        }
+
+        tracing::debug!("Upload <{}> removed", upload_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed(parts: &[&[u8]]) -> Vec<CompletedPart> {
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, data)| CompletedPart {
+                part_number: i as u32 + 1,
+                checksum: sha256(data),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_upload_in_memory() {
+        let manager = UploadManager::new(None).unwrap();
+        let info = manager.init("ssn-1/model".to_string()).unwrap();
+        let id = info.upload_id.as_str();
+
+        // The parts are uploaded in any order, and a retried part is replaced.
+        manager
+            .upload_part(id, 2, &sha256(b"world"), b"world".to_vec())
+            .unwrap();
+        manager
+            .upload_part(id, 1, &sha256(b"hello "), b"hello ".to_vec())
+            .unwrap();
+        manager
+            .upload_part(id, 1, &sha256(b"hello "), b"hello ".to_vec())
+            .unwrap();
+
+        // A corrupted part is rejected, so the parts received are not changed.
+        assert!(manager
+            .upload_part(id, 3, &sha256(b"!"), b"?".to_vec())
+            .is_err());
+        assert!(manager.upload_part(id, 0, &sha256(b""), vec![]).is_err());
+        let status = manager.status(id).unwrap();
+        assert_eq!(
+            status
+                .parts
+                .iter()
+                .map(|p| p.part_number)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let request = CompleteUpload {
+            upload_id: id.to_string(),
+            parts: completed(&[b"hello ", b"world"]),
+        };
+        let (key, data, checksum) = manager.complete(&request).unwrap();
+        assert_eq!(key, "ssn-1/model");
+        assert_eq!(data, b"hello world");
+        assert_eq!(checksum, sha256(b"hello world"));
+
+        manager.remove(id).unwrap();
+        assert!(manager.status(id).is_err());
+    }
+
+    #[test]
+    fn test_complete_with_invalid_parts() {
+        let manager = UploadManager::new(None).unwrap();
+        let id = manager.init("ssn-1/model".to_string()).unwrap().upload_id;
+        manager
+            .upload_part(&id, 1, &sha256(b"a"), b"a".to_vec())
+            .unwrap();
+
+        let complete = |parts: Vec<CompletedPart>| {
+            manager.complete(&CompleteUpload {
+                upload_id: id.clone(),
+                parts,
+            })
+        };
+        // No parts, a missing part, a different checksum and a wrong order.
+        assert!(complete(vec![]).is_err());
+        assert!(complete(completed(&[b"a", b"b"])).is_err());
+        assert!(complete(completed(&[b"b"])).is_err());
+        let mut parts = completed(&[b"a", b"a"]);
+        parts.reverse();
+        assert!(complete(parts).is_err());
+
+        assert!(complete(completed(&[b"a"])).is_ok());
+    }
+
+    #[test]
+    fn test_resume_upload_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = {
+            let manager = UploadManager::new(Some(dir.path())).unwrap();
+            let id = manager.init("ssn-1/model".to_string()).unwrap().upload_id;
+            manager
+                .upload_part(&id, 1, &sha256(b"hello "), b"hello ".to_vec())
+                .unwrap();
+            id
+        };
+
+        // The parts received before the restart are listed, so only the rest is uploaded.
+        let manager = UploadManager::new(Some(dir.path())).unwrap();
+        let status = manager.status(&id).unwrap();
+        assert_eq!(status.parts.len(), 1);
+        manager
+            .upload_part(&id, 2, &sha256(b"world"), b"world".to_vec())
+            .unwrap();
+
+        let request = CompleteUpload {
+            upload_id: id.clone(),
+            parts: completed(&[b"hello ", b"world"]),
+        };
+        let (_, data, _) = manager.complete(&request).unwrap();
+        assert_eq!(data, b"hello world");
+
+        manager.remove(&id).unwrap();
+        assert!(!dir.path().join(UPLOADS_DIR).join(&id).exists());
+        assert!(manager.remove("../ssn-1").is_err());
+    }
+}
@@ -16,3 +16,16 @@ pub mod flame {
         tonic::include_proto!("flame.v1");
     }
 }
+
+pub mod grpc {
+    pub mod reflection {
+        pub mod v1alpha {
+            tonic::include_proto!("grpc.reflection.v1alpha");
+        }
+    }
+}
+
+pub mod reflection;
+
+/// The encoded descriptors of the protos above, served by the reflection service.
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("flame_descriptor");
@@ -0,0 +1,293 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The gRPC server reflection service, so tools like grpcurl or evans can
+//! introspect the services without the proto files; it's served by the session
+//! manager and the instances of the Rust SDK, each with its own descriptors.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use prost::Message;
+use prost_types::{DescriptorProto, EnumDescriptorProto, FileDescriptorProto, FileDescriptorSet};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::grpc::reflection::v1alpha::server_reflection_request::MessageRequest;
+use crate::grpc::reflection::v1alpha::server_reflection_response::MessageResponse;
+use crate::grpc::reflection::v1alpha::server_reflection_server::{
+    ServerReflection, ServerReflectionServer,
+};
+use crate::grpc::reflection::v1alpha::{
+    ErrorResponse, FileDescriptorResponse, ListServiceResponse, ServerReflectionRequest,
+    ServerReflectionResponse, ServiceResponse,
+};
+
+/// Returns the reflection server of the services in the encoded file descriptor set.
+pub fn server(
+    file_descriptor_set: &[u8],
+) -> Result<ServerReflectionServer<ReflectionService>, prost::DecodeError> {
+    Ok(ServerReflectionServer::new(ReflectionService::new(
+        file_descriptor_set,
+    )?))
+}
+
+#[derive(Debug, Default)]
+struct Descriptors {
+    /// The encoded file descriptors by file name.
+    files: HashMap<String, (FileDescriptorProto, Vec<u8>)>,
+    /// The file name by the fully-qualified symbol name.
+    symbols: HashMap<String, String>,
+    services: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReflectionService {
+    descriptors: Arc<Descriptors>,
+}
+
+impl ReflectionService {
+    pub fn new(file_descriptor_set: &[u8]) -> Result<Self, prost::DecodeError> {
+        let set = FileDescriptorSet::decode(file_descriptor_set)?;
+
+        let mut descriptors = Descriptors::default();
+        for file in set.file {
+            let name = file.name().to_string();
+            let prefix = match file.package() {
+                "" => String::new(),
+                package => format!("{package}."),
+            };
+
+            for service in &file.service {
+                let service_name = format!("{prefix}{}", service.name());
+                for method in &service.method {
+                    descriptors
+                        .symbols
+                        .insert(format!("{service_name}.{}", method.name()), name.clone());
+                }
+                descriptors.services.push(service_name.clone());
+                descriptors.symbols.insert(service_name, name.clone());
+            }
+            for message in &file.message_type {
+                add_message(&mut descriptors.symbols, &prefix, message, &name);
+            }
+            for enum_type in &file.enum_type {
+                add_enum(&mut descriptors.symbols, &prefix, enum_type, &name);
+            }
+
+            let encoded = file.encode_to_vec();
+            descriptors.files.insert(name, (file, encoded));
+        }
+
+        Ok(Self {
+            descriptors: Arc::new(descriptors),
+        })
+    }
+
+    fn handle(&self, request: &MessageRequest) -> MessageResponse {
+        let result = match request {
+            MessageRequest::FileByFilename(name) => self.file_with_dependencies(name),
+            MessageRequest::FileContainingSymbol(symbol) => {
+                match self.descriptors.symbols.get(symbol) {
+                    Some(name) => self.file_with_dependencies(name),
+                    None => Err(error_response(
+                        Code::NotFound,
+                        format!("symbol <{symbol}> not found"),
+                    )),
+                }
+            }
+            MessageRequest::ListServices(_) => {
+                return MessageResponse::ListServicesResponse(ListServiceResponse {
+                    service: self
+                        .descriptors
+                        .services
+                        .iter()
+                        .map(|name| ServiceResponse { name: name.clone() })
+                        .collect(),
+                })
+            }
+            MessageRequest::FileContainingExtension(_)
+            | MessageRequest::AllExtensionNumbersOfType(_) => Err(error_response(
+                Code::Unimplemented,
+                "extensions are not supported".to_string(),
+            )),
+        };
+
+        match result {
+            Ok(file_descriptor_proto) => {
+                MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+                    file_descriptor_proto,
+                })
+            }
+            Err(resp) => MessageResponse::ErrorResponse(resp),
+        }
+    }
+
+    /// Returns the encoded descriptors of the file and its transitive dependencies.
+    fn file_with_dependencies(&self, name: &str) -> Result<Vec<Vec<u8>>, ErrorResponse> {
+        let mut res = vec![];
+        let mut visited = HashSet::new();
+        let mut pending = VecDeque::from([name.to_string()]);
+
+        while let Some(name) = pending.pop_front() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            let (file, encoded) = self.descriptors.files.get(&name).ok_or_else(|| {
+                error_response(Code::NotFound, format!("file <{name}> not found"))
+            })?;
+            res.push(encoded.clone());
+            pending.extend(file.dependency.iter().cloned());
+        }
+
+        Ok(res)
+    }
+}
+
+fn error_response(code: Code, message: String) -> ErrorResponse {
+    ErrorResponse {
+        error_code: code as i32,
+        error_message: message,
+    }
+}
+
+fn add_message(
+    symbols: &mut HashMap<String, String>,
+    prefix: &str,
+    message: &DescriptorProto,
+    file: &str,
+) {
+    let name = format!("{prefix}{}", message.name());
+    let nested_prefix = format!("{name}.");
+    for nested in &message.nested_type {
+        add_message(symbols, &nested_prefix, nested, file);
+    }
+    for enum_type in &message.enum_type {
+        add_enum(symbols, &nested_prefix, enum_type, file);
+    }
+    symbols.insert(name, file.to_string());
+}
+
+fn add_enum(
+    symbols: &mut HashMap<String, String>,
+    prefix: &str,
+    enum_type: &EnumDescriptorProto,
+    file: &str,
+) {
+    symbols.insert(format!("{prefix}{}", enum_type.name()), file.to_string());
+}
+
+#[tonic::async_trait]
+impl ServerReflection for ReflectionService {
+    type ServerReflectionInfoStream =
+        Pin<Box<dyn Stream<Item = Result<ServerReflectionResponse, Status>> + Send + 'static>>;
+
+    async fn server_reflection_info(
+        &self,
+        req: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let mut stream = req.into_inner();
+        let service = self.clone();
+        let (tx, rx) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            loop {
+                let request = match stream.message().await {
+                    Ok(Some(request)) => request,
+                    Ok(None) => break,
+                    Err(status) => {
+                        let _ = tx.send(Err(status)).await;
+                        break;
+                    }
+                };
+
+                let message_response = match &request.message_request {
+                    Some(message_request) => service.handle(message_request),
+                    None => MessageResponse::ErrorResponse(error_response(
+                        Code::InvalidArgument,
+                        "empty message request".to_string(),
+                    )),
+                };
+                let response = ServerReflectionResponse {
+                    valid_host: request.host.clone(),
+                    original_request: Some(request),
+                    message_response: Some(message_response),
+                };
+
+                if tx.send(Ok(response)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_names(resp: MessageResponse) -> Vec<String> {
+        match resp {
+            MessageResponse::FileDescriptorResponse(resp) => resp
+                .file_descriptor_proto
+                .iter()
+                .map(|f| {
+                    FileDescriptorProto::decode(f.as_slice())
+                        .unwrap()
+                        .name()
+                        .to_string()
+                })
+                .collect(),
+            _ => panic!("unexpected response: {resp:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reflection_service() {
+        let service = ReflectionService::new(crate::FILE_DESCRIPTOR_SET).unwrap();
+
+        match service.handle(&MessageRequest::ListServices(String::new())) {
+            MessageResponse::ListServicesResponse(resp) => {
+                let names: Vec<_> = resp.service.into_iter().map(|s| s.name).collect();
+                assert!(names.contains(&"flame.v1.Frontend".to_string()));
+                assert!(names.contains(&"flame.v1.Backend".to_string()));
+                assert!(names.contains(&"grpc.reflection.v1alpha.ServerReflection".to_string()));
+            }
+            resp => panic!("unexpected response: {resp:?}"),
+        }
+
+        let names = file_names(service.handle(&MessageRequest::FileContainingSymbol(
+            "flame.v1.Frontend.CreateSession".to_string(),
+        )));
+        assert_eq!(names[0], "frontend.proto");
+        assert!(names.contains(&"types.proto".to_string()));
+
+        let names =
+            file_names(service.handle(&MessageRequest::FileByFilename("types.proto".to_string())));
+        assert_eq!(names, vec!["types.proto"]);
+
+        match service.handle(&MessageRequest::FileContainingSymbol(
+            "flame.v1.Unknown".to_string(),
+        )) {
+            MessageResponse::ErrorResponse(resp) => {
+                assert_eq!(resp.error_code, Code::NotFound as i32)
+            }
+            resp => panic!("unexpected response: {resp:?}"),
+        }
+    }
+}
@@ -11,8 +11,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
-# Import submodules for runner, agent, and util (only as submodules)
-from . import agent, runner, util
+# Import submodules for runner, agent, celery, and util (only as submodules)
+from . import agent, celery, runner, util
 
 # Export all core classes/types at top level
 from .core import (  # Type aliases; Constants; Enums; Exception classes; Data classes; Context and utility classes; Client functions; Client classes; Service constants; Service context classes; Service base classes; Service functions
@@ -33,37 +33,66 @@ from .core import (  # Type aliases; Constants; Enums; Exception classes; Data c
     FlameContextRunner,
     FlameError,
     FlameErrorCode,
+    Job,
+    JobState,
+    CompletionPolicy,
     FlamePackage,
     FlameService,
     Message,
     ObjectRef,
     Session,
     SessionAttributes,
+    SessionDefaults,
+    HealthProbe,
+    LifecycleHook,
+    LifecycleHooks,
+    PythonEnvironment,
     SessionContext,
     SessionID,
+    ReplayPolicy,
+    ResultPolicy,
     SessionState,
+    SessionSummary,
     Shim,
     Task,
+    TaskArtifact,
+    TaskPostmortem,
+    TaskUsage,
+    TaskDurationStats,
+    TaskFailureSummary,
     TaskContext,
+    PreemptContext,
     TaskID,
     TaskInformer,
     TaskInput,
     TaskOutput,
+    TaskResult,
     TaskState,
+    FailureReason,
+    FairnessPolicy,
+    PayloadCodec,
+    TopologyPolicy,
     TaskWatcher,
+    cancel_job,
     close_session,
     connect,
+    create_job,
     create_session,
+    disable_application,
+    enable_application,
     get_application,
+    get_job,
     get_object,
     get_session,
     list_applications,
+    list_jobs,
     list_sessions,
     open_session,
     put_object,
     register_application,
     run,
     unregister_application,
+    wait_job,
     update_object,
 )
 
@@ -84,7 +113,15 @@ __all__ = [
     "DEFAULT_FLAME_CACHE_ENDPOINT",
     # Enums
     "SessionState",
+    "ReplayPolicy",
+    "ResultPolicy",
     "TaskState",
+    "FailureReason",
+    "FairnessPolicy",
+    "PayloadCodec",
+    "TopologyPolicy",
+    "JobState",
+    "CompletionPolicy",
     "ApplicationState",
     "FlameErrorCode",
     "Shim",
@@ -93,9 +130,22 @@ __all__ = [
     # Data classes
     "Event",
     "SessionAttributes",
+    "SessionDefaults",
+    "HealthProbe",
+    "LifecycleHook",
+    "LifecycleHooks",
+    "PythonEnvironment",
     "ApplicationSchema",
     "ApplicationAttributes",
     "Task",
+    "TaskArtifact",
+    "TaskPostmortem",
+    "TaskUsage",
+    "TaskDurationStats",
+    "TaskFailureSummary",
+    "SessionSummary",
+    "Job",
+    "TaskResult",
     "Application",
     "FlamePackage",
     "FlameContextRunner",
@@ -108,11 +158,18 @@ __all__ = [
     "open_session",
     "register_application",
     "unregister_application",
+    "enable_application",
+    "disable_application",
     "list_applications",
     "get_application",
     "list_sessions",
     "get_session",
     "close_session",
+    "create_job",
+    "get_job",
+    "list_jobs",
+    "cancel_job",
+    "wait_job",
     # Client classes
     "Connection",
     "Session",
@@ -123,6 +180,7 @@ __all__ = [
     "ApplicationContext",
     "SessionContext",
     "TaskContext",
+    "PreemptContext",
     # Service base classes
     "FlameService",
     # Service functions
@@ -135,6 +193,7 @@ __all__ = [
     "update_object",
     # Submodules
     "agent",
+    "celery",
     "runner",
     "util",
 ]
@@ -0,0 +1,30 @@
+"""
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+from .app import Celery, Task
+from .canvas import Signature, chord, group, signature
+from .result import FAILURE, PENDING, SUCCESS, AsyncResult, GroupResult
+
+__all__ = [
+    "Celery",
+    "Task",
+    "Signature",
+    "signature",
+    "group",
+    "chord",
+    "AsyncResult",
+    "GroupResult",
+    "PENDING",
+    "SUCCESS",
+    "FAILURE",
+]
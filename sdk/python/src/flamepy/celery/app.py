@@ -0,0 +1,185 @@
+"""
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import functools
+import logging
+import threading
+from typing import Any, Callable, Dict, Optional, Tuple
+
+from flamepy.celery.canvas import Signature
+from flamepy.celery.result import AsyncResult
+from flamepy.core.types import FlameError, FlameErrorCode
+from flamepy.runner.runner import Runner, RunnerService
+
+logger = logging.getLogger(__name__)
+
+
+class Task:
+    """A function registered by `Celery.task`, like `celery.Task`.
+
+    Calling the task runs the function locally, as Celery does; `delay` and
+    `apply_async` run it remotely by the RunnerService of the task in Flame.
+
+    Attributes:
+        app: The Celery app of the task
+        name: The name of the task, `<module>.<qualname>` of the function by default
+        run: The function of the task
+    """
+
+    def __init__(self, app: "Celery", run: Callable, name: str):
+        self.app = app
+        self.name = name
+        self.run = run
+        functools.update_wrapper(self, run)
+
+    def __call__(self, *args, **kwargs) -> Any:
+        """Run the function locally."""
+        return self.run(*args, **kwargs)
+
+    def apply_async(self, args: Optional[Tuple[Any, ...]] = None, kwargs: Optional[Dict[str, Any]] = None, **options) -> AsyncResult:
+        """Run the task remotely in Flame.
+
+        Args:
+            args: The positional arguments of the task
+            kwargs: The keyword arguments of the task
+            **options: The options of Celery, e.g. `countdown` or `queue`, which
+                       are ignored with a warning, as the tasks are scheduled by Flame
+
+        Returns:
+            AsyncResult of the task
+        """
+        if options:
+            logger.warning(f"Ignore the unsupported options {sorted(options)} of task <{self.name}>")
+
+        service = self.app._service(self)
+        future = service(*(args or ()), **(kwargs or {}))
+        return AsyncResult(future, self.name)
+
+    def delay(self, *args, **kwargs) -> AsyncResult:
+        """Shortcut of `apply_async` with the arguments."""
+        return self.apply_async(args, kwargs)
+
+    def signature(self, args: Optional[Tuple[Any, ...]] = None, kwargs: Optional[Dict[str, Any]] = None, **options) -> Signature:
+        """Create the signature of the task with the arguments."""
+        return Signature(self, args, kwargs, options)
+
+    def s(self, *args, **kwargs) -> Signature:
+        """Shortcut of `signature` with the arguments."""
+        return Signature(self, args, kwargs)
+
+    def si(self, *args, **kwargs) -> Signature:
+        """Shortcut of the immutable signature, which ignores the partial arguments,
+        e.g. the results of the header of a chord."""
+        return Signature(self, args, kwargs, immutable=True)
+
+    def __repr__(self) -> str:
+        return f"<@task: {self.name}>"
+
+
+class Celery:
+    """The Celery-compatible app on top of the Runner of Flame, like `celery.Celery`.
+
+    The app is the Runner application of the current working directory, which
+    is packaged and registered by the first task applied; each task is run by
+    its own RunnerService, i.e. a session of the application. There's no broker
+    or result backend: the tasks are scheduled by Flame, and their results are
+    kept in the object cache.
+
+    Attributes:
+        main: The name of the app, i.e. the name of the Runner application
+    """
+
+    def __init__(self, main: str, fail_if_exists: bool = False, **options):
+        """Initialize a Celery app.
+
+        Args:
+            main: The name of the app, i.e. the name of the Runner application
+            fail_if_exists: If True, fail if the application already exists; see `Runner`
+            **options: The options of Celery, e.g. `broker` or `backend`, which are
+                       ignored, as they're not needed by Flame
+        """
+        if options:
+            logger.debug(f"Ignore the options {sorted(options)} of Celery app <{main}>")
+
+        self.main = main
+        self._fail_if_exists = fail_if_exists
+        self._runner: Optional[Runner] = None
+        self._services: Dict[str, RunnerService] = {}
+        self._tasks: Dict[str, Task] = {}
+        self._lock = threading.Lock()
+
+    @property
+    def tasks(self) -> Dict[str, Task]:
+        """The registered tasks by their names."""
+        return dict(self._tasks)
+
+    def task(self, *args, **options) -> Any:
+        """Register the function as a task, by `@app.task` or `@app.task(name=...)`.
+
+        Args:
+            name: The name of the task, `<module>.<qualname>` of the function by default
+            **options: The other options of Celery, e.g. `bind` or `retry_backoff`,
+                       which are ignored with a warning
+
+        Returns:
+            The Task of the function, or the decorator of the function
+        """
+        name = options.pop("name", None)
+        if options:
+            logger.warning(f"Ignore the unsupported task options {sorted(options)}")
+
+        def register(fn: Callable) -> Task:
+            task_name = name or f"{fn.__module__}.{fn.__qualname__}"
+            task = Task(self, fn, task_name)
+            self._tasks[task_name] = task
+            return task
+
+        if len(args) == 1 and callable(args[0]):
+            return register(args[0])
+        return register
+
+    def send_task(self, name: str, args: Optional[Tuple[Any, ...]] = None, kwargs: Optional[Dict[str, Any]] = None, **options) -> AsyncResult:
+        """Apply the registered task by its name.
+
+        Raises:
+            FlameError: If the task is not registered
+        """
+        task = self._tasks.get(name)
+        if task is None:
+            raise FlameError(FlameErrorCode.NOT_FOUND, f"Task <{name}> is not registered in app <{self.main}>")
+        return task.apply_async(args, kwargs, **options)
+
+    def _service(self, task: Task) -> RunnerService:
+        """Get the RunnerService of the task; the Runner is started by the first one."""
+        with self._lock:
+            service = self._services.get(task.name)
+            if service is None:
+                if self._runner is None:
+                    self._runner = Runner(self.main, fail_if_exists=self._fail_if_exists)
+                service = self._runner.service(task.run)
+                self._services[task.name] = service
+            return service
+
+    def close(self) -> None:
+        """Close the Runner of the app, i.e. the sessions of the tasks and the application."""
+        with self._lock:
+            if self._runner is not None:
+                self._runner.close()
+            self._runner = None
+            self._services.clear()
+
+    def __enter__(self) -> "Celery":
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb) -> None:
+        self.close()
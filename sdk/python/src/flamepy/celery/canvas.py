@@ -0,0 +1,160 @@
+"""
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import threading
+from concurrent.futures import Future
+from typing import TYPE_CHECKING, Any, Dict, Iterable, Optional, Tuple, Union
+
+from flamepy.celery.result import AsyncResult, GroupResult
+from flamepy.runner.runner import ObjectFuture
+
+if TYPE_CHECKING:
+    from flamepy.celery.app import Task
+
+
+class Signature:
+    """A task with its arguments to apply later, like `celery.Signature`.
+
+    The partial arguments given when the signature is applied are prepended
+    to its arguments, e.g. the results of the header of a chord; they're
+    ignored by an immutable signature.
+
+    Attributes:
+        task: The task to apply
+        args: The positional arguments of the task
+        kwargs: The keyword arguments of the task
+        options: The options of `apply_async`
+        immutable: Whether the partial arguments are ignored
+    """
+
+    def __init__(
+        self,
+        task: "Task",
+        args: Optional[Tuple[Any, ...]] = None,
+        kwargs: Optional[Dict[str, Any]] = None,
+        options: Optional[Dict[str, Any]] = None,
+        immutable: bool = False,
+    ):
+        self.task = task
+        self.args = tuple(args or ())
+        self.kwargs = dict(kwargs or {})
+        self.options = dict(options or {})
+        self.immutable = immutable
+
+    def _merge(self, args: Tuple[Any, ...], kwargs: Optional[Dict[str, Any]]) -> Tuple[Tuple[Any, ...], Dict[str, Any]]:
+        if self.immutable:
+            return self.args, self.kwargs
+        return tuple(args) + self.args, {**self.kwargs, **(kwargs or {})}
+
+    def clone(self, args: Tuple[Any, ...] = (), kwargs: Optional[Dict[str, Any]] = None, **options) -> "Signature":
+        """Return a copy of the signature with the partial arguments and options."""
+        args, kwargs = self._merge(args, kwargs)
+        return Signature(self.task, args, kwargs, {**self.options, **options}, self.immutable)
+
+    def apply_async(self, args: Tuple[Any, ...] = (), kwargs: Optional[Dict[str, Any]] = None, **options) -> AsyncResult:
+        """Apply the task with the partial arguments in Flame."""
+        args, kwargs = self._merge(args, kwargs)
+        return self.task.apply_async(args=args, kwargs=kwargs, **{**self.options, **options})
+
+    def delay(self, *args, **kwargs) -> AsyncResult:
+        """Shortcut of `apply_async` with the partial arguments."""
+        return self.apply_async(args, kwargs)
+
+    def __call__(self, *args, **kwargs) -> Any:
+        """Run the task locally with the partial arguments, as calling the task."""
+        args, kwargs = self._merge(args, kwargs)
+        return self.task(*args, **kwargs)
+
+    def __repr__(self) -> str:
+        args = [repr(arg) for arg in self.args] + [f"{key}={value!r}" for key, value in self.kwargs.items()]
+        return f"{self.task.name}({', '.join(args)})"
+
+
+def signature(task: "Task", args: Optional[Tuple[Any, ...]] = None, kwargs: Optional[Dict[str, Any]] = None, **options) -> Signature:
+    """Create the signature of the task, like `celery.signature`."""
+    return Signature(task, args, kwargs, options)
+
+
+class group:
+    """The tasks applied in parallel, like `celery.group`.
+
+    A group is created by the signatures, e.g. `group(add.s(1, 2), add.s(3, 4))`,
+    or an iterable of them, e.g. `group(add.s(i, i) for i in range(10))`.
+    """
+
+    def __init__(self, *tasks: Union[Signature, Iterable[Signature]]):
+        if len(tasks) == 1 and not isinstance(tasks[0], Signature):
+            tasks = tuple(tasks[0])
+        self.tasks = list(tasks)
+
+    def apply_async(self, args: Tuple[Any, ...] = (), kwargs: Optional[Dict[str, Any]] = None, **options) -> GroupResult:
+        """Apply all the tasks with the partial arguments in Flame."""
+        return GroupResult([task.apply_async(args, kwargs, **options) for task in self.tasks])
+
+    def delay(self, *args, **kwargs) -> GroupResult:
+        """Shortcut of `apply_async` with the partial arguments."""
+        return self.apply_async(args, kwargs)
+
+    def __call__(self, *args, **kwargs) -> GroupResult:
+        """Apply all the tasks in Flame, as Celery does for groups."""
+        return self.apply_async(args, kwargs)
+
+    def __len__(self) -> int:
+        return len(self.tasks)
+
+    def __repr__(self) -> str:
+        return f"group([{', '.join(repr(task) for task in self.tasks)}])"
+
+
+class chord:
+    """A group whose results are aggregated by a callback, like `celery.chord`.
+
+    The callback, i.e. the body, is applied with the list of the values of the
+    header as its first argument once all the tasks of the header succeeded,
+    e.g. `chord(add.s(i, i) for i in range(10))(tsum.s())`. The values are
+    collected by the client, in a background thread; the result of the chord
+    fails with the exception of the first failed task of the header.
+    """
+
+    def __init__(self, header: Union[group, Iterable[Signature]], body: Optional[Signature] = None):
+        self.header = header if isinstance(header, group) else group(header)
+        self.body = body
+
+    def apply_async(self, args: Tuple[Any, ...] = (), kwargs: Optional[Dict[str, Any]] = None, body: Optional[Signature] = None, **options) -> AsyncResult:
+        """Apply the header with the partial arguments in Flame, then the body with its values."""
+        body = body or self.body
+        if body is None:
+            raise ValueError("the body of the chord is not set")
+
+        header_result = self.header.apply_async(args, kwargs, **options)
+
+        future: Future = Future()
+
+        def apply_body():
+            try:
+                values = header_result.get()
+                body_result = body.apply_async((values,))
+                future.set_result(body_result._future.ref())
+            except BaseException as e:
+                future.set_exception(e)
+
+        threading.Thread(target=apply_body, name=f"chord-{body.task.name}", daemon=True).start()
+
+        return AsyncResult(ObjectFuture(future), body.task.name)
+
+    def __call__(self, body: Optional[Signature] = None, **options) -> AsyncResult:
+        """Apply the chord with the body, e.g. `chord(header)(callback.s())`."""
+        return self.apply_async(body=body, **options)
+
+    def __repr__(self) -> str:
+        return f"chord({self.header!r}, {self.body!r})"
@@ -0,0 +1,187 @@
+"""
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+"""
+
+import time
+import uuid
+from concurrent.futures import TimeoutError as FutureTimeoutError
+from typing import Any, Iterator, List, Optional
+
+from flamepy.runner.runner import ObjectFuture
+
+PENDING = "PENDING"
+SUCCESS = "SUCCESS"
+FAILURE = "FAILURE"
+
+
+class AsyncResult:
+    """The result of a task applied asynchronously, like `celery.result.AsyncResult`.
+
+    The result is backed by the ObjectFuture of the task in Flame, so there's
+    no result backend to configure; the `id` is generated by the client, as
+    the result is only tracked by the client which applied the task.
+
+    Attributes:
+        id: The unique id of the result
+        task_name: The name of the task
+    """
+
+    def __init__(self, future: ObjectFuture, task_name: str):
+        """Initialize an AsyncResult.
+
+        Args:
+            future: The ObjectFuture of the task
+            task_name: The name of the task
+        """
+        self.id = str(uuid.uuid4())
+        self.task_name = task_name
+        self._future = future
+
+    @property
+    def state(self) -> str:
+        """The state of the task, i.e. PENDING, SUCCESS or FAILURE."""
+        if not self._future.done():
+            return PENDING
+        try:
+            self._future.wait(timeout=0)
+        except Exception:
+            return FAILURE
+        return SUCCESS
+
+    @property
+    def status(self) -> str:
+        """Alias of `state`."""
+        return self.state
+
+    @property
+    def result(self) -> Any:
+        """The return value, or the exception of the failed task; None if it's not ready."""
+        if not self.ready():
+            return None
+        return self.get(propagate=False)
+
+    def ready(self) -> bool:
+        """Return True if the task is completed, i.e. succeeded or failed."""
+        return self._future.done()
+
+    def successful(self) -> bool:
+        """Return True if the task succeeded."""
+        return self.state == SUCCESS
+
+    def failed(self) -> bool:
+        """Return True if the task failed."""
+        return self.state == FAILURE
+
+    def get(self, timeout: Optional[float] = None, propagate: bool = True) -> Any:
+        """Wait for the task to complete and return its value.
+
+        Args:
+            timeout: Seconds to wait for the task; wait forever if None
+            propagate: If True, re-raise the exception of the failed task;
+                       otherwise, return the exception
+
+        Returns:
+            The return value of the task, or its exception if not propagated
+
+        Raises:
+            TimeoutError: If the task is not completed within the timeout
+        """
+        try:
+            return self._future.get(timeout=timeout)
+        except (TimeoutError, FutureTimeoutError):
+            raise
+        except Exception as e:
+            if propagate:
+                raise
+            return e
+
+    def wait(self, timeout: Optional[float] = None, propagate: bool = True) -> Any:
+        """Alias of `get`."""
+        return self.get(timeout=timeout, propagate=propagate)
+
+    def __repr__(self) -> str:
+        return f"<AsyncResult: {self.id} ({self.task_name})>"
+
+
+class GroupResult:
+    """The results of the tasks of a group, like `celery.result.GroupResult`.
+
+    Attributes:
+        id: The unique id of the group result
+        results: The results of the tasks, in the order of the group
+    """
+
+    def __init__(self, results: List[AsyncResult]):
+        """Initialize a GroupResult.
+
+        Args:
+            results: The results of the tasks of the group
+        """
+        self.id = str(uuid.uuid4())
+        self.results = results
+
+    def ready(self) -> bool:
+        """Return True if all the tasks are completed."""
+        return all(result.ready() for result in self.results)
+
+    def successful(self) -> bool:
+        """Return True if all the tasks succeeded."""
+        return all(result.successful() for result in self.results)
+
+    def failed(self) -> bool:
+        """Return True if any of the tasks failed."""
+        return any(result.failed() for result in self.results)
+
+    def waiting(self) -> bool:
+        """Return True if any of the tasks is not completed."""
+        return not self.ready()
+
+    def completed_count(self) -> int:
+        """Return the number of the tasks succeeded."""
+        return sum(1 for result in self.results if result.successful())
+
+    def get(self, timeout: Optional[float] = None, propagate: bool = True) -> List[Any]:
+        """Wait for all the tasks to complete and return their values in order.
+
+        Args:
+            timeout: Seconds to wait for all the tasks; wait forever if None
+            propagate: If True, re-raise the exception of the first failed task;
+                       otherwise, return the exceptions in place of the values
+
+        Returns:
+            The return values of the tasks, in the order of the group
+
+        Raises:
+            TimeoutError: If the tasks are not completed within the timeout
+        """
+        deadline = None if timeout is None else time.monotonic() + timeout
+        values = []
+        for result in self.results:
+            remaining = None if deadline is None else max(deadline - time.monotonic(), 0)
+            values.append(result.get(timeout=remaining, propagate=propagate))
+        return values
+
+    def join(self, timeout: Optional[float] = None, propagate: bool = True) -> List[Any]:
+        """Alias of `get`."""
+        return self.get(timeout=timeout, propagate=propagate)
+
+    def __iter__(self) -> Iterator[AsyncResult]:
+        return iter(self.results)
+
+    def __len__(self) -> int:
+        return len(self.results)
+
+    def __getitem__(self, index: int) -> AsyncResult:
+        return self.results[index]
+
+    def __repr__(self) -> str:
+        return f"<GroupResult: {self.id} [{', '.join(result.id for result in self.results)}]>"
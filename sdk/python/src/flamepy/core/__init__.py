@@ -15,6 +15,7 @@ limitations under the License.
 from .cache import (
     ObjectRef,
     get_object,
+    object_checksum,
     patch_object,
     put_object,
     update_object,
@@ -27,16 +28,23 @@ from .client import (
     ConnectionInstance,
     Session,
     TaskWatcher,
+    cancel_job,
     close_session,
     connect,
+    create_job,
     create_session,
+    disable_application,
+    enable_application,
     get_application,
+    get_job,
     get_session,
     list_applications,
+    list_jobs,
     list_sessions,
     open_session,
     register_application,
     unregister_application,
+    wait_job,
 )
 
 # Service functions
@@ -50,6 +58,7 @@ from .service import (
     FlameInstanceServer,
     FlameInstanceServicer,
     FlameService,
+    PreemptContext,
     SessionContext,
     TaskContext,
     run,
@@ -77,18 +86,39 @@ from .types import (
     FlameContextRunner,
     FlameError,
     FlameErrorCode,
+    Job,
+    JobState,
+    CompletionPolicy,
     FlamePackage,
     Message,
     SessionAttributes,
+    SessionDefaults,
+    HealthProbe,
+    LifecycleHook,
+    LifecycleHooks,
+    PythonEnvironment,
     SessionID,
+    ReplayPolicy,
+    ResultPolicy,
     SessionState,
+    SessionSummary,
     Shim,
     Task,
+    TaskArtifact,
+    TaskPostmortem,
+    TaskUsage,
+    TaskDurationStats,
+    TaskFailureSummary,
     TaskID,
     TaskInformer,
     TaskInput,
     TaskOutput,
+    TaskResult,
     TaskState,
+    FailureReason,
+    FairnessPolicy,
+    PayloadCodec,
+    TopologyPolicy,
     short_name,
 )
 
@@ -107,7 +137,15 @@ __all__ = [
     "DEFAULT_FLAME_CACHE_ENDPOINT",
     # Enums
     "SessionState",
+    "ReplayPolicy",
+    "ResultPolicy",
     "TaskState",
+    "FailureReason",
+    "FairnessPolicy",
+    "PayloadCodec",
+    "TopologyPolicy",
+    "JobState",
+    "CompletionPolicy",
     "ApplicationState",
     "FlameErrorCode",
     "Shim",
@@ -116,9 +154,22 @@ __all__ = [
     # Data classes
     "Event",
     "SessionAttributes",
+    "SessionDefaults",
+    "HealthProbe",
+    "LifecycleHook",
+    "LifecycleHooks",
+    "PythonEnvironment",
     "ApplicationSchema",
     "ApplicationAttributes",
     "Task",
+    "TaskArtifact",
+    "TaskPostmortem",
+    "TaskUsage",
+    "TaskDurationStats",
+    "TaskFailureSummary",
+    "SessionSummary",
+    "Job",
+    "TaskResult",
     "Application",
     "FlamePackage",
     "FlameContextRunner",
@@ -133,11 +184,18 @@ __all__ = [
     "open_session",
     "register_application",
     "unregister_application",
+    "enable_application",
+    "disable_application",
     "list_applications",
     "get_application",
     "list_sessions",
     "get_session",
     "close_session",
+    "create_job",
+    "get_job",
+    "list_jobs",
+    "cancel_job",
+    "wait_job",
     # Client classes
     "ConnectionInstance",
     "Connection",
@@ -149,6 +207,7 @@ __all__ = [
     "ApplicationContext",
     "SessionContext",
     "TaskContext",
+    "PreemptContext",
     # Service base classes
     "FlameService",
     # Service implementation classes
@@ -160,6 +219,7 @@ __all__ = [
     "ObjectRef",
     # Cache functions
     "get_object",
+    "object_checksum",
     "patch_object",
     "put_object",
     "update_object",
@@ -12,7 +12,9 @@ limitations under the License.
 """
 
 import base64
+import hashlib
 import json
+import time
 import uuid
 from dataclasses import asdict, dataclass
 from pathlib import Path
@@ -27,6 +29,14 @@ from flamepy.core.types import FlameClientCache, FlameClientTls, FlameContext
 
 Deserializer = Callable[[Any, List[Any]], Any]
 
+# The objects larger than this are uploaded by a multi-part upload, so a failure
+# of the link only retries a part instead of the whole object.
+MULTIPART_THRESHOLD = 64 * 1024 * 1024
+# The bytes of a part of the multi-part upload.
+PART_SIZE = 16 * 1024 * 1024
+# The attempts to upload a part before the upload fails.
+PART_ATTEMPTS = 5
+
 
 @dataclass
 class ObjectRef:
@@ -58,6 +68,11 @@ def _serialize_object(obj: Any) -> pa.RecordBatch:
     # Serialize the object using cloudpickle
     data_bytes = cloudpickle.dumps(obj, protocol=cloudpickle.DEFAULT_PROTOCOL)
 
+    return _to_batch(data_bytes)
+
+
+def _to_batch(data_bytes: bytes) -> pa.RecordBatch:
+    """Create an Arrow RecordBatch of the serialized object."""
     # Create Arrow schema
     schema = pa.schema(
         [
@@ -173,6 +188,70 @@ def _do_put_remote(client: flight.FlightClient, descriptor: flight.FlightDescrip
     raise ValueError("No result metadata received from cache server")
 
 
+def _do_action(client: flight.FlightClient, action_type: str, body: str) -> bytes:
+    """Run an action of the cache server and return the body of its result."""
+    action = flight.Action(action_type, body.encode("utf-8"))
+    results = list(client.do_action(action))
+    if not results:
+        raise ValueError(f"No result received from {action_type} action")
+
+    return results[0].body.to_pybytes()
+
+
+def _upload_multipart(client: flight.FlightClient, key: str, data: bytes, upload_id: Optional[str] = None) -> "ObjectRef":
+    """Upload the data to the key by a multi-part upload.
+
+    Each part is verified by its SHA-256 checksum at the cache server, and is
+    retried with a backoff if failed. If ``upload_id`` is set, the upload is
+    resumed, i.e. the parts already received by the server are skipped.
+
+    Args:
+        client: Arrow Flight client
+        key: "session_id/object_id", or "session_id" for a generated object_id
+        data: The serialized object
+        upload_id: The id of the upload to resume
+
+    Returns:
+        ObjectRef pointing to the uploaded object
+
+    Raises:
+        ValueError: If a part failed after all attempts, with the id of the upload to resume
+    """
+    if upload_id is None:
+        info = json.loads(_do_action(client, "UPLOAD_INIT", key))
+    else:
+        info = json.loads(_do_action(client, "UPLOAD_STATUS", upload_id))
+    upload_id = info["upload_id"]
+    received = {part["part_number"]: part["checksum"] for part in info["parts"]}
+
+    view = memoryview(data)
+    parts = []
+    for part_number, offset in enumerate(range(0, len(view), PART_SIZE), start=1):
+        part = view[offset : offset + PART_SIZE]
+        checksum = hashlib.sha256(part).hexdigest()
+        parts.append({"part_number": part_number, "checksum": checksum})
+        if received.get(part_number) == checksum:
+            continue
+
+        body = f"{upload_id}:{part_number}:{checksum}:{base64.b64encode(part).decode('utf-8')}"
+        for attempt in range(1, PART_ATTEMPTS + 1):
+            try:
+                _do_action(client, "UPLOAD_PART", body)
+                break
+            except Exception as e:
+                if attempt == PART_ATTEMPTS:
+                    raise ValueError(f"Failed to upload part {part_number} of upload <{upload_id}>: {e}")
+                time.sleep(2**attempt)
+
+    complete = json.dumps({"upload_id": upload_id, "parts": parts})
+    result = json.loads(_do_action(client, "UPLOAD_COMPLETE", complete))
+    checksum = hashlib.sha256(view).hexdigest()
+    if result["checksum"] != checksum:
+        raise ValueError(f"Checksum mismatch of <{result['key']}>: {checksum} uploaded, {result['checksum']} stored")
+
+    return ObjectRef(endpoint=result["endpoint"], key=result["key"], version=result["version"])
+
+
 def _get_cache_tls_config() -> Optional[FlameClientTls]:
     """Get TLS configuration for cache from FlameContext.
 
@@ -232,8 +311,14 @@ def put_object(session_id: str, obj: Any) -> "ObjectRef":
     if not cache_endpoint:
         raise ValueError("Cache endpoint not configured")
 
+    # The large objects are uploaded by parts to the remote cache.
+    data_bytes = cloudpickle.dumps(obj, protocol=cloudpickle.DEFAULT_PROTOCOL)
+    if len(data_bytes) > MULTIPART_THRESHOLD:
+        client = _get_flight_client(cache_endpoint, cache_tls)
+        return _upload_multipart(client, session_id, data_bytes)
+
     # Serialize object to Arrow RecordBatch
-    batch = _serialize_object(obj)
+    batch = _to_batch(data_bytes)
 
     # Check if local storage is configured and accessible
     if cache_storage:
@@ -286,6 +371,11 @@ def put_object(session_id: str, obj: Any) -> "ObjectRef":
         return _do_put_remote(client, upload_descriptor, batch)
 
 
+def object_checksum(data: bytes) -> str:
+    """The checksum of the data as "sha256:<hex>", e.g. of the input of a task in the cache."""
+    return f"sha256:{hashlib.sha256(data).hexdigest()}"
+
+
 def get_object(ref: ObjectRef, deserializer: Optional[Deserializer] = None) -> Any:
     """Get an object from the cache.
 
@@ -14,9 +14,10 @@ limitations under the License.
 import logging
 import threading
 import time
+import uuid
 from concurrent.futures import Future, ThreadPoolExecutor
 from datetime import datetime, timezone
-from typing import Any, Dict, List, Optional, Union
+from typing import TYPE_CHECKING, Any, Dict, List, Optional, Tuple, Union
 from urllib.parse import urlparse
 
 import grpc
@@ -27,41 +28,96 @@ from flamepy.core.types import (
     ApplicationSchema,
     ApplicationState,
     Event,
+    FailureReason,
+    FairnessPolicy,
+    PayloadCodec,
+    TopologyPolicy,
     FlameClientTls,
     FlameContext,
     FlameError,
     FlameErrorCode,
+    HealthProbe,
+    Job,
+    JobState,
+    CompletionPolicy,
+    LifecycleHook,
+    LifecycleHooks,
+    PythonEnvironment,
+    ReplayPolicy,
     SessionAttributes,
+    SessionDefaults,
     SessionID,
     SessionState,
+    SessionSummary,
+    TaskDurationStats,
+    TaskFailureSummary,
     Shim,
     Task,
+    TaskArtifact,
+    TaskPostmortem,
+    TaskUsage,
     TaskID,
     TaskInformer,
     TaskState,
     short_name,
 )
 from flamepy.proto.frontend_pb2 import (
+    CancelJobRequest,
+    CheckpointTaskRequest,
     CloseSessionRequest,
+    CreateJobRequest,
     CreateSessionRequest,
     CreateTaskRequest,
+    DisableApplicationRequest,
+    EnableApplicationRequest,
     GetApplicationRequest,
+    GetJobRequest,
     GetSessionRequest,
+    GetSessionSummaryRequest,
     GetTaskRequest,
     ListApplicationRequest,
+    ListJobRequest,
     ListSessionRequest,
     ListTaskRequest,
     OpenSessionRequest,
     RegisterApplicationRequest,
+    ReleaseTaskRequest,
+    RenewSessionLeaseRequest,
     UnregisterApplicationRequest,
     WatchTaskRequest,
 )
 from flamepy.proto.frontend_pb2_grpc import FrontendStub
 from flamepy.proto.types_pb2 import ApplicationSchema as ApplicationSchemaProto
-from flamepy.proto.types_pb2 import ApplicationSpec, Environment, SessionSpec, TaskSpec
+from flamepy.proto.types_pb2 import ApplicationSpec, Environment, JobSpec, SessionSpec, TaskSpec
+from flamepy.proto.types_pb2 import HealthProbe as HealthProbeProto
+from flamepy.proto.types_pb2 import LifecycleHook as LifecycleHookProto
+from flamepy.proto.types_pb2 import LifecycleHooks as LifecycleHooksProto
+from flamepy.proto.types_pb2 import ObjectReference as ObjectReferenceProto
+from flamepy.proto.types_pb2 import PythonEnvironment as PythonEnvironmentProto
+from flamepy.proto.types_pb2 import SessionDefaults as SessionDefaultsProto
+
+if TYPE_CHECKING:
+    from flamepy.core.cache import ObjectRef
 
 logger = logging.getLogger(__name__)
 
+# The interval in seconds to check whether the closing session is closed.
+_CLOSE_POLL_INTERVAL = 1.0
+
+# The interval in seconds to check whether the job is completed.
+_JOB_POLL_INTERVAL = 1.0
+
+# The gRPC metadata of the trace ID of the requests creating the sessions and tasks.
+FLAME_TRACE_ID = "x-flame-trace-id"
+
+
+def _trace_metadata() -> List[Tuple[str, str]]:
+    """The metadata with a new trace ID of the request; it's stored on the created
+    session or task, and logged by the session and executor managers."""
+    trace_id = str(uuid.uuid4())
+    logger.debug(f"Send the request <{trace_id}>")
+    return [(FLAME_TRACE_ID, trace_id)]
+
 
 def connect(addr: str, tls_config: Optional[FlameClientTls] = None) -> "Connection":
     """Connect to the Flame service.
@@ -73,7 +129,7 @@ def connect(addr: str, tls_config: Optional[FlameClientTls] = None) -> "Connecti
     return Connection.connect(addr, tls_config)
 
 
-def create_session(application: str, common_data: Optional[bytes] = None, session_id: Optional[str] = None, slots: int = 1, min_instances: int = 0, max_instances: Optional[int] = None, batch_size: int = 1) -> "Session":
+def create_session(application: str, common_data: Optional[bytes] = None, session_id: Optional[str] = None, slots: int = 1, min_instances: int = 0, max_instances: Optional[int] = None, batch_size: int = 1, scratch_size: Optional[int] = None, priority: int = 0) -> "Session":
     """Create a new session.
 
     Args:
@@ -84,12 +140,14 @@ def create_session(application: str, common_data: Optional[bytes] = None, sessio
         min_instances: Minimum number of instances (default: 0)
         max_instances: Maximum number of instances (None = unlimited)
         batch_size: Number of executors per batch for gang scheduling (default: 1)
+        scratch_size: Size limit in bytes of the shared scratch directory on each node (None = no scratch directory)
+        priority: Priority of the session in scheduling, the higher the earlier (default: 0)
     """
     conn = ConnectionInstance.instance()
-    return conn.create_session(SessionAttributes(id=session_id, application=application, common_data=common_data, slots=slots, min_instances=min_instances, max_instances=max_instances, batch_size=batch_size))
+    return conn.create_session(SessionAttributes(id=session_id, application=application, common_data=common_data, slots=slots, min_instances=min_instances, max_instances=max_instances, batch_size=batch_size, scratch_size=scratch_size, priority=priority))
 
 
-def open_session(session_id: SessionID, spec: Optional[SessionAttributes] = None) -> "Session":
+def open_session(session_id: SessionID, spec: Optional[SessionAttributes] = None, replay_policy: Optional[ReplayPolicy] = None) -> "Session":
     """Open an existing session or create a new one if spec is provided.
 
     Args:
@@ -97,17 +155,20 @@ def open_session(session_id: SessionID, spec: Optional[SessionAttributes] = None
         spec: Optional session specification. If provided and session doesn't
               exist, a new session will be created with this spec. If session
               exists, the spec will be validated against the existing session.
+        replay_policy: Optional policy to reopen the session if it's closed;
+              the failed tasks are run again by ReplayPolicy.RETRY_FAILED.
 
     Returns:
         The opened or newly created Session object.
 
     Raises:
         FlameError(NOT_FOUND): If session doesn't exist and no spec provided.
-        FlameError(INVALID_STATE): If session exists but is not in Open state.
+        FlameError(INVALID_STATE): If session exists but is not in Open state,
+            and no replay policy provided.
         FlameError(INVALID_ARGUMENT): If session exists but spec doesn't match.
     """
     conn = ConnectionInstance.instance()
-    return conn.open_session(session_id, spec)
+    return conn.open_session(session_id, spec, replay_policy)
 
 
 def register_application(name: str, app_attrs: Union[ApplicationAttributes, Dict[str, Any]]) -> None:
@@ -120,6 +181,16 @@ def unregister_application(name: str) -> None:
     conn.unregister_application(name)
 
 
+def enable_application(name: str) -> None:
+    conn = ConnectionInstance.instance()
+    conn.enable_application(name)
+
+
+def disable_application(name: str) -> None:
+    conn = ConnectionInstance.instance()
+    conn.disable_application(name)
+
+
 def list_applications() -> List[Application]:
     conn = ConnectionInstance.instance()
     return conn.list_applications()
@@ -140,9 +211,47 @@ def get_session(session_id: SessionID) -> "Session":
     return conn.get_session(session_id)
 
 
-def close_session(session_id: SessionID) -> "Session":
+def close_session(session_id: SessionID, force: bool = False) -> "Session":
+    conn = ConnectionInstance.instance()
+    return conn.close_session(session_id, force)
+
+
+def create_job(application: str, inputs: Optional[List[bytes]] = None, count: Optional[int] = None, job_id: Optional[str] = None, slots: int = 1, parallelism: Optional[int] = None, completion_policy: CompletionPolicy = CompletionPolicy.ALL_TASKS, max_failed: int = 0, common_data: Optional[bytes] = None) -> Job:
+    """Create a job running a set of tasks to completion in its own session.
+
+    Args:
+        application: Application name
+        inputs: The inputs of the tasks, one task per input
+        count: The number of the tasks whose inputs are their indexes, i.e. b"0", b"1"..., instead of the inputs
+        job_id: Optional job ID, which is also the ID of its session
+        slots: Number of slots of each task
+        parallelism: The max tasks pending or running at the same time (None = all tasks at once)
+        completion_policy: Whether the job succeeds once all tasks completed, or any task succeeded
+        max_failed: The failed tasks tolerated before the job fails (default: 0)
+        common_data: Common data of the session as bytes
+    """
+    conn = ConnectionInstance.instance()
+    return conn.create_job(application, inputs=inputs, count=count, job_id=job_id, slots=slots, parallelism=parallelism, completion_policy=completion_policy, max_failed=max_failed, common_data=common_data)
+
+
+def get_job(job_id: str) -> Job:
     conn = ConnectionInstance.instance()
-    return conn.close_session(session_id)
+    return conn.get_job(job_id)
+
+
+def list_jobs() -> List[Job]:
+    conn = ConnectionInstance.instance()
+    return conn.list_jobs()
+
+
+def cancel_job(job_id: str) -> Job:
+    conn = ConnectionInstance.instance()
+    return conn.cancel_job(job_id)
+
+
+def wait_job(job_id: str, timeout: Optional[float] = None) -> Job:
+    conn = ConnectionInstance.instance()
+    return conn.wait_job(job_id, timeout)
 
 
 class ConnectionInstance:
@@ -273,6 +382,18 @@ class Connection:
             delay_release=app_attrs.delay_release,
             schema=schema,
             url=app_attrs.url,
+            task_environments=app_attrs.task_environments or [],
+            task_arguments=app_attrs.task_arguments or [],
+            bundle_size=app_attrs.bundle_size,
+            max_instances_per_node=app_attrs.max_instances_per_node,
+            session_defaults=_session_defaults_to_proto(app_attrs.session_defaults),
+            health_probe=_health_probe_to_proto(app_attrs.health_probe),
+            fairness=app_attrs.fairness.value if app_attrs.fairness is not None else FairnessPolicy.ROUND_ROBIN.value,
+            hooks=_lifecycle_hooks_to_proto(app_attrs.hooks),
+            python_env=_python_env_to_proto(app_attrs.python_env),
+            codec=app_attrs.codec.value if app_attrs.codec is not None else PayloadCodec.RAW.value,
+            payload_type=app_attrs.payload_type,
+            topology=app_attrs.topology.value if app_attrs.topology is not None else TopologyPolicy.IGNORE.value,
         )
 
         request = RegisterApplicationRequest(name=name, application=app_spec)
@@ -297,6 +418,34 @@ class Connection:
                 f"failed to unregister application: {e.details()}",
             )
 
+    def enable_application(self, name: str) -> None:
+        """Enable an application to accept new sessions again."""
+        request = EnableApplicationRequest(name=name)
+
+        try:
+            response = self._frontend.EnableApplication(request)
+        except grpc.RpcError as e:
+            raise FlameError(
+                FlameErrorCode.INTERNAL,
+                f"failed to enable application: {e.details()}",
+            )
+        if response.return_code < 0:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to enable application: {response.message}")
+
+    def disable_application(self, name: str) -> None:
+        """Disable an application to reject new sessions; its existing sessions run to completion."""
+        request = DisableApplicationRequest(name=name)
+
+        try:
+            response = self._frontend.DisableApplication(request)
+        except grpc.RpcError as e:
+            raise FlameError(
+                FlameErrorCode.INTERNAL,
+                f"failed to disable application: {e.details()}",
+            )
+        if response.return_code < 0:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to disable application: {response.message}")
+
     def list_applications(self) -> List[Application]:
         """List all applications."""
         request = ListApplicationRequest()
@@ -334,6 +483,18 @@ class Connection:
                         delay_release=app.spec.delay_release,
                         schema=schema,
                         url=app.spec.url if app.spec.HasField("url") else None,
+                        task_environments=list(app.spec.task_environments),
+                        task_arguments=list(app.spec.task_arguments),
+                        bundle_size=app.spec.bundle_size if app.spec.HasField("bundle_size") else None,
+                        max_instances_per_node=app.spec.max_instances_per_node if app.spec.HasField("max_instances_per_node") else None,
+                        session_defaults=_session_defaults_from_proto(app.spec),
+                        health_probe=_health_probe_from_proto(app.spec),
+                        fairness=FairnessPolicy(app.spec.fairness),
+                        hooks=_lifecycle_hooks_from_proto(app.spec),
+                        python_env=_python_env_from_proto(app.spec),
+                        codec=PayloadCodec(app.spec.codec),
+                        payload_type=app.spec.payload_type if app.spec.HasField("payload_type") else None,
+                        topology=TopologyPolicy(app.spec.topology),
                     )
                 )
 
@@ -376,6 +537,18 @@ class Connection:
                 delay_release=response.spec.delay_release,
                 schema=schema,
                 url=response.spec.url if response.spec.HasField("url") else None,
+                task_environments=list(response.spec.task_environments),
+                task_arguments=list(response.spec.task_arguments),
+                bundle_size=response.spec.bundle_size if response.spec.HasField("bundle_size") else None,
+                max_instances_per_node=response.spec.max_instances_per_node if response.spec.HasField("max_instances_per_node") else None,
+                session_defaults=_session_defaults_from_proto(response.spec),
+                health_probe=_health_probe_from_proto(response.spec),
+                fairness=FairnessPolicy(response.spec.fairness),
+                hooks=_lifecycle_hooks_from_proto(response.spec),
+                python_env=_python_env_from_proto(response.spec),
+                codec=PayloadCodec(response.spec.codec),
+                payload_type=response.spec.payload_type if response.spec.HasField("payload_type") else None,
+                topology=TopologyPolicy(response.spec.topology),
             )
 
         except grpc.RpcError as e:
@@ -400,12 +573,19 @@ class Connection:
             min_instances=attrs.min_instances,
             max_instances=attrs.max_instances if attrs.max_instances is not None else None,
             batch_size=attrs.batch_size,
+            scratch_size=attrs.scratch_size,
+            max_task_attempts=attrs.max_task_attempts,
+            task_timeout=attrs.task_timeout,
+            task_priority=attrs.task_priority,
+            priority=attrs.priority,
+            result_policy=int(attrs.result_policy),
+            ttl_after_completion=attrs.ttl_after_completion,
         )
 
         request = CreateSessionRequest(session_id=session_id, session=session_spec)
 
         try:
-            response = self._frontend.CreateSession(request)
+            response = self._frontend.CreateSession(request, metadata=_trace_metadata())
             # Common data is bytes in core API
             common_data_bytes = response.spec.common_data if response.spec.HasField("common_data") and response.spec.common_data else None
 
@@ -414,6 +594,7 @@ class Connection:
                 id=response.metadata.id,
                 application=response.spec.application,
                 slots=response.spec.slots,
+                priority=response.spec.priority,
                 state=SessionState(response.status.state),
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 pending=response.status.pending,
@@ -422,6 +603,9 @@ class Connection:
                 failed=response.status.failed,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
                 common_data=common_data_bytes,
+                task_durations=_task_durations_from_proto(response.status),
+                unschedulable=response.status.unschedulable if response.status.HasField("unschedulable") else None,
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
             )
             return session
         except grpc.RpcError as e:
@@ -445,6 +629,7 @@ class Connection:
                         id=session.metadata.id,
                         application=session.spec.application,
                         slots=session.spec.slots,
+                        priority=session.spec.priority,
                         state=SessionState(session.status.state),
                         creation_time=datetime.fromtimestamp(session.status.creation_time / 1000, tz=timezone.utc),
                         pending=session.status.pending,
@@ -453,6 +638,9 @@ class Connection:
                         failed=session.status.failed,
                         completion_time=(datetime.fromtimestamp(session.status.completion_time / 1000, tz=timezone.utc) if session.status.HasField("completion_time") else None),
                         common_data=common_data_bytes,
+                        task_durations=_task_durations_from_proto(session.status),
+                        unschedulable=session.status.unschedulable if session.status.HasField("unschedulable") else None,
+                        trace_id=session.status.trace_id if session.status.HasField("trace_id") else None,
                     )
                 )
 
@@ -461,12 +649,13 @@ class Connection:
         except grpc.RpcError as e:
             raise FlameError(FlameErrorCode.INTERNAL, f"failed to list sessions: {e.details()}")
 
-    def open_session(self, session_id: SessionID, spec: Optional[SessionAttributes] = None) -> "Session":
+    def open_session(self, session_id: SessionID, spec: Optional[SessionAttributes] = None, replay_policy: Optional[ReplayPolicy] = None) -> "Session":
         """Open an existing session or create a new one if spec is provided.
 
         Args:
             session_id: The session ID to open or create.
             spec: Optional session specification for creation/validation.
+            replay_policy: Optional policy to reopen the session if it's closed.
 
         Returns:
             The opened or newly created Session object.
@@ -481,12 +670,23 @@ class Connection:
                 min_instances=spec.min_instances,
                 max_instances=spec.max_instances,
                 batch_size=spec.batch_size,
+                scratch_size=spec.scratch_size,
+                max_task_attempts=spec.max_task_attempts,
+                task_timeout=spec.task_timeout,
+                task_priority=spec.task_priority,
+                priority=spec.priority,
+                result_policy=int(spec.result_policy),
+                ttl_after_completion=spec.ttl_after_completion,
             )
 
-        request = OpenSessionRequest(session_id=session_id, session=session_spec)
+        request = OpenSessionRequest(
+            session_id=session_id,
+            session=session_spec,
+            replay_policy=int(replay_policy) if replay_policy is not None else None,
+        )
 
         try:
-            response = self._frontend.OpenSession(request)
+            response = self._frontend.OpenSession(request, metadata=_trace_metadata())
             # Common data is bytes in core API
             common_data_bytes = response.spec.common_data if response.spec.HasField("common_data") and response.spec.common_data else None
 
@@ -495,6 +695,7 @@ class Connection:
                 id=response.metadata.id,
                 application=response.spec.application,
                 slots=response.spec.slots,
+                priority=response.spec.priority,
                 state=SessionState(response.status.state),
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 pending=response.status.pending,
@@ -503,6 +704,9 @@ class Connection:
                 failed=response.status.failed,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
                 common_data=common_data_bytes,
+                task_durations=_task_durations_from_proto(response.status),
+                unschedulable=response.status.unschedulable if response.status.HasField("unschedulable") else None,
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
             )
 
         except grpc.RpcError as e:
@@ -523,6 +727,7 @@ class Connection:
                 id=response.metadata.id,
                 application=response.spec.application,
                 slots=response.spec.slots,
+                priority=response.spec.priority,
                 state=SessionState(response.status.state),
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 pending=response.status.pending,
@@ -531,14 +736,17 @@ class Connection:
                 failed=response.status.failed,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
                 common_data=common_data_bytes,
+                task_durations=_task_durations_from_proto(response.status),
+                unschedulable=response.status.unschedulable if response.status.HasField("unschedulable") else None,
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
             )
 
         except grpc.RpcError as e:
             raise FlameError(FlameErrorCode.INTERNAL, f"failed to get session: {e.details()}")
 
-    def close_session(self, session_id: SessionID) -> "Session":
-        """Close a session."""
-        request = CloseSessionRequest(session_id=session_id)
+    def close_session(self, session_id: SessionID, force: bool = False) -> "Session":
+        """Close a session; it's closing until its running tasks are completed, unless they're cancelled by `force`."""
+        request = CloseSessionRequest(session_id=session_id, force=force)
 
         try:
             response = self._frontend.CloseSession(request)
@@ -551,6 +759,7 @@ class Connection:
                 id=response.metadata.id,
                 application=response.spec.application,
                 slots=response.spec.slots,
+                priority=response.spec.priority,
                 state=SessionState(response.status.state),
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 pending=response.status.pending,
@@ -559,11 +768,122 @@ class Connection:
                 failed=response.status.failed,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
                 common_data=common_data_bytes,
+                task_durations=_task_durations_from_proto(response.status),
+                unschedulable=response.status.unschedulable if response.status.HasField("unschedulable") else None,
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
             )
 
         except grpc.RpcError as e:
             raise FlameError(FlameErrorCode.INTERNAL, f"failed to close session: {e.details()}")
 
+    def renew_session_lease(self, session_id: SessionID) -> int:
+        """Renew the client lease of a session, and return its grace period in seconds."""
+        request = RenewSessionLeaseRequest(session_id=session_id)
+
+        try:
+            response = self._frontend.RenewSessionLease(request)
+            return response.grace_period
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to renew session lease: {e.details()}")
+
+
+    def get_session_summary(self, session_id: SessionID, window: Optional[int] = None) -> SessionSummary:
+        """Get the aggregated statistics of the tasks in a session without listing them.
+
+        The throughput is over the last `window` minutes, 5 minutes by default.
+        """
+        request = GetSessionSummaryRequest(session_id=session_id, window=window)
+
+        try:
+            response = self._frontend.GetSessionSummary(request)
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to get session summary: {e.details()}")
+
+        return SessionSummary(
+            session_id=response.session_id,
+            pending=response.pending,
+            running=response.running,
+            succeed=response.succeed,
+            failed=response.failed,
+            cancelled=response.cancelled,
+            quarantined=response.quarantined,
+            window=response.window,
+            completed=response.completed,
+            throughput=response.throughput,
+            average_duration=response.average_duration if response.HasField("average_duration") else None,
+            failures=[
+                TaskFailureSummary(
+                    category=f.category,
+                    count=f.count,
+                    message=f.message if f.HasField("message") else None,
+                    reason=_failure_reason_from_proto(f, "reason"),
+                )
+                for f in response.failures
+            ],
+        )
+
+    def create_job(self, application: str, inputs: Optional[List[bytes]] = None, count: Optional[int] = None, job_id: Optional[str] = None, slots: int = 1, parallelism: Optional[int] = None, completion_policy: CompletionPolicy = CompletionPolicy.ALL_TASKS, max_failed: int = 0, common_data: Optional[bytes] = None) -> Job:
+        """Create a job, whose session and tasks are managed by the session manager until it's completed."""
+        if inputs and count is not None:
+            raise FlameError(FlameErrorCode.INVALID_ARGUMENT, "inputs and count of job are exclusive")
+
+        job_spec = JobSpec(
+            application=application,
+            slots=slots,
+            inputs=inputs or [],
+            count=count,
+            parallelism=parallelism,
+            completion_policy=completion_policy,
+            max_failed=max_failed,
+            common_data=common_data,
+        )
+        request = CreateJobRequest(job_id=short_name(application) if job_id is None else job_id, job=job_spec)
+
+        try:
+            response = self._frontend.CreateJob(request)
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to create job: {e.details()}")
+
+        return _job_from_proto(response)
+
+    def get_job(self, job_id: str) -> Job:
+        """Get the state and the progress of a job."""
+        try:
+            response = self._frontend.GetJob(GetJobRequest(job_id=job_id))
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to get job: {e.details()}")
+
+        return _job_from_proto(response)
+
+    def list_jobs(self) -> List[Job]:
+        """List all jobs."""
+        try:
+            response = self._frontend.ListJob(ListJobRequest())
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to list jobs: {e.details()}")
+
+        return [_job_from_proto(job) for job in response.jobs]
+
+    def cancel_job(self, job_id: str) -> Job:
+        """Cancel a job, and its remaining tasks by closing its session."""
+        try:
+            response = self._frontend.CancelJob(CancelJobRequest(job_id=job_id))
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to cancel job: {e.details()}")
+
+        return _job_from_proto(response)
+
+    def wait_job(self, job_id: str, timeout: Optional[float] = None) -> Job:
+        """Wait for the job to complete, and return it; it fails if the job is not completed in `timeout` seconds."""
+        deadline = None if timeout is None else time.monotonic() + timeout
+        while True:
+            job = self.get_job(job_id)
+            if job.state.is_terminal():
+                return job
+            if deadline is not None and time.monotonic() >= deadline:
+                raise FlameError(FlameErrorCode.INTERNAL, f"job <{job_id}> was not completed in {timeout}s")
+            time.sleep(_JOB_POLL_INTERVAL)
+
 
 class Session:
     connection: Connection
@@ -578,6 +898,13 @@ class Session:
     succeed: int = 0
     failed: int = 0
     completion_time: Optional[datetime] = None
+    task_durations: Optional[TaskDurationStats] = None
+    unschedulable: Optional[str] = None
+    """The reason why no ready node can run the application of the session, if any."""
+    trace_id: Optional[str] = None
+    """The trace ID of the request which created the session, if any."""
+    priority: int = 0
+    """The priority of the session in scheduling, the higher the earlier."""
     _common_data: Optional[bytes] = None
     """Client for session-specific operations."""
 
@@ -595,6 +922,10 @@ class Session:
         failed: int,
         completion_time: Optional[datetime],
         common_data: Optional[bytes] = None,
+        task_durations: Optional[TaskDurationStats] = None,
+        unschedulable: Optional[str] = None,
+        trace_id: Optional[str] = None,
+        priority: int = 0,
     ):
         self.connection = connection
         self.id = id
@@ -609,27 +940,106 @@ class Session:
         self.completion_time = completion_time
         self.mutex = threading.Lock()
         self._common_data = common_data
+        self.task_durations = task_durations
+        self.unschedulable = unschedulable
+        self.trace_id = trace_id
+        self.priority = priority
+        self._lease_stop: Optional[threading.Event] = None
 
     def common_data(self) -> Optional[bytes]:
         """Get the common data of Session as bytes."""
         return self._common_data
 
-    def create_task(self, input_data: bytes) -> Task:
+    def create_task(
+        self,
+        input_data: bytes,
+        environments: Optional[Dict[str, str]] = None,
+        arguments: Optional[List[str]] = None,
+        timeout: Optional[int] = None,
+        max_attempts: Optional[int] = None,
+        priority: Optional[int] = None,
+        deadline: Optional[int] = None,
+    ) -> Task:
         """Create a new task in the session.
 
         Args:
             input_data: Task input as bytes (core API works with bytes)
+            environments: Environment variables to override for this task; they must be
+                allowed by the application's task_environments
+            arguments: Arguments to pass to this task, e.g. "--level=3"; they must be
+                allowed by the application's task_arguments
+            timeout: Timeout in seconds of running this task, instead of the session's
+            max_attempts: Attempts of this task before it's quarantined, instead of the session's
+            priority: Priority of this task among the pending tasks of the session; the
+                higher ones are dispatched first
+            deadline: Deadline in milliseconds of this task since it's created; the task
+                is failed with DEADLINE_EXCEEDED once it's passed, without running it
         """
         # Input data should be bytes in core API
         if not isinstance(input_data, bytes):
             raise FlameError(FlameErrorCode.INVALID_ARGUMENT, "input_data must be bytes in core API")
 
-        task_spec = TaskSpec(session_id=self.id, input=input_data)
+        return self._create_task(input_data, None, environments, arguments, timeout, max_attempts, priority, deadline)
+
+    def create_typed_task(self, codec: PayloadCodec, value: Any) -> Task:
+        """Create a new task whose input is the value encoded by the codec.
+
+        The input is tagged by the codec, so the session manager rejects it if the
+        application has another codec; decode the output by codec.decode().
+        """
+        return self._create_task(codec.encode(value), None, None, None, codec=codec)
+
+    def create_task_from_ref(
+        self,
+        ref: "ObjectRef",
+        checksum: Optional[str] = None,
+        environments: Optional[Dict[str, str]] = None,
+        arguments: Optional[List[str]] = None,
+    ) -> Task:
+        """Create a new task whose input is an object in the cache.
+
+        The input is not sent to the session manager; the instance fetches it from
+        the cache when the service reads it by TaskContext.read_input().
+
+        Args:
+            ref: ObjectRef of the input, e.g. returned by put_object()
+            checksum: The checksum of the input as "sha256:<hex>", e.g. by object_checksum();
+                the instance verifies the fetched input against it
+            environments: Environment variables to override for this task
+            arguments: Arguments to pass to this task
+        """
+        input_ref = ObjectReferenceProto(endpoint=ref.endpoint, key=ref.key, checksum=checksum)
+        return self._create_task(None, input_ref, environments, arguments)
+
+    def _create_task(
+        self,
+        input_data: Optional[bytes],
+        input_ref: Optional[ObjectReferenceProto],
+        environments: Optional[Dict[str, str]],
+        arguments: Optional[List[str]],
+        timeout: Optional[int] = None,
+        max_attempts: Optional[int] = None,
+        priority: Optional[int] = None,
+        deadline: Optional[int] = None,
+        codec: Optional[PayloadCodec] = None,
+    ) -> Task:
+        task_spec = TaskSpec(
+            session_id=self.id,
+            input=input_data,
+            input_ref=input_ref,
+            environments=[Environment(name=k, value=v) for k, v in (environments or {}).items()],
+            arguments=arguments or [],
+            timeout=timeout,
+            max_attempts=max_attempts,
+            priority=priority,
+            deadline=deadline,
+            codec=codec.value if codec is not None else None,
+        )
 
         request = CreateTaskRequest(task=task_spec)
 
         try:
-            response = self.connection._frontend.CreateTask(request)
+            response = self.connection._frontend.CreateTask(request, metadata=_trace_metadata())
 
             return Task(
                 id=response.metadata.id,
@@ -638,6 +1048,7 @@ class Session:
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 input=input_data,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
                 events=[
                     Event(
                         code=event.code,
@@ -665,6 +1076,14 @@ class Session:
                 creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
                 input=response.spec.input if response.spec.HasField("input") and response.spec.input else None,
                 output=response.spec.output if response.spec.HasField("output") and response.spec.output else None,
+                output_ref=_output_ref_from_proto(response.spec),
+                artifacts=_artifacts_from_proto(response.spec.artifacts),
+                failure_reason=_failure_reason_from_proto(response.status),
+                attempts=response.status.attempts,
+                usage=_task_usage_from_proto(response.status),
+                postmortem=_task_postmortem_from_proto(response.status),
+                trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
+                checkpoint=response.status.checkpoint if response.status.HasField("checkpoint") else None,
                 completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
                 events=[
                     Event(
@@ -679,6 +1098,31 @@ class Session:
         except grpc.RpcError as e:
             raise FlameError(FlameErrorCode.INTERNAL, f"failed to get task: {e.details()}")
 
+    def release_task(self, task_id: TaskID) -> Task:
+        """Release a quarantined task back to pending, so it is dispatched again."""
+        request = ReleaseTaskRequest(task_id=task_id, session_id=self.id)
+
+        try:
+            response = self.connection._frontend.ReleaseTask(request)
+            return _task_from_proto(response, self.id)
+
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to release task: {e.details()}")
+
+    def checkpoint_task(self, task_id: TaskID, checkpoint: str) -> Task:
+        """Record the latest checkpoint of a running task, i.e. the reference of an object in the object cache.
+
+        The checkpoint is passed to the next invocation of the task after a retry, so it resumes from the checkpoint.
+        """
+        request = CheckpointTaskRequest(task_id=task_id, session_id=self.id, checkpoint=checkpoint)
+
+        try:
+            response = self.connection._frontend.CheckpointTask(request)
+            return _task_from_proto(response, self.id)
+
+        except grpc.RpcError as e:
+            raise FlameError(FlameErrorCode.INTERNAL, f"failed to checkpoint task: {e.details()}")
+
     def list_tasks(self) -> "TaskIterator":
         """List all tasks in the session.
 
@@ -785,9 +1229,252 @@ class Session:
             elif task.is_completed():
                 return task.output
 
-    def close(self) -> None:
-        """Close the session."""
-        self.connection.close_session(self.id)
+    def summary(self, window: Optional[int] = None) -> SessionSummary:
+        """Get the aggregated statistics of the tasks, e.g. the throughput over the last `window` minutes."""
+        return self.connection.get_session_summary(self.id, window)
+
+    def renew_lease(self) -> int:
+        """Renew the client lease of the session, and return its grace period in seconds.
+
+        Once renewed, the session is closed by the session manager if the lease
+        is not renewed within the grace period, e.g. the client crashed.
+        """
+        return self.connection.renew_session_lease(self.id)
+
+    def keep_alive(self) -> None:
+        """Heartbeat the session ownership in a background thread until the session is closed."""
+        if self._lease_stop is not None:
+            return
+
+        grace_period = self.renew_lease()
+        stop = threading.Event()
+        self._lease_stop = stop
+
+        def heartbeat():
+            # Renew the lease three times per grace period.
+            while not stop.wait(grace_period / 3):
+                try:
+                    self.renew_lease()
+                except FlameError as e:
+                    logger.warning(f"Failed to renew lease of session <{self.id}>: {e}")
+
+        threading.Thread(target=heartbeat, name=f"lease-{self.id}", daemon=True).start()
+
+    def close(self, force: bool = False) -> "Session":
+        """Close the session, and return it; it's closing until its running tasks are completed, unless they're cancelled by `force`."""
+        if self._lease_stop is not None:
+            self._lease_stop.set()
+            self._lease_stop = None
+        return self.connection.close_session(self.id, force)
+
+    def wait_closed(self, timeout: Optional[float] = None) -> "Session":
+        """Wait until the closing session is closed, i.e. its running tasks are completed."""
+        deadline = None if timeout is None else time.monotonic() + timeout
+        while True:
+            ssn = self.connection.get_session(self.id)
+            if ssn.state != SessionState.CLOSING:
+                return ssn
+            if deadline is not None and time.monotonic() >= deadline:
+                raise FlameError(FlameErrorCode.INTERNAL, f"session <{self.id}> is not closed in {timeout}s")
+            logger.debug(f"Session <{self.id}> is closing, {ssn.running} running task(s) left")
+            time.sleep(_CLOSE_POLL_INTERVAL)
+
+
+def _job_from_proto(response) -> Job:
+    spec = response.spec
+    status = response.status
+    return Job(
+        id=response.metadata.id,
+        application=spec.application,
+        state=JobState(status.state),
+        creation_time=datetime.fromtimestamp(status.creation_time, tz=timezone.utc),
+        session_id=status.session_id,
+        slots=spec.slots,
+        parallelism=spec.parallelism if spec.HasField("parallelism") else None,
+        completion_policy=CompletionPolicy(spec.completion_policy),
+        max_failed=spec.max_failed,
+        completion_time=(datetime.fromtimestamp(status.completion_time, tz=timezone.utc) if status.HasField("completion_time") else None),
+        total=status.total,
+        submitted=status.submitted,
+        succeed=status.succeed,
+        failed=status.failed,
+        message=status.message if status.HasField("message") else None,
+    )
+
+
+def _task_durations_from_proto(status) -> Optional[TaskDurationStats]:
+    """Convert the protobuf TaskDurationStats of a session status."""
+    if not status.HasField("task_durations"):
+        return None
+    stats = status.task_durations
+    return TaskDurationStats(count=stats.count, mean=stats.mean, min=stats.min, max=stats.max, recent=stats.recent)
+
+
+def _artifacts_from_proto(artifacts) -> List[TaskArtifact]:
+    """Convert the protobuf TaskArtifacts to TaskArtifact objects."""
+    return [
+        TaskArtifact(
+            name=artifact.name,
+            data=artifact.data if artifact.HasField("data") else None,
+            object_ref=artifact.object_ref if artifact.HasField("object_ref") else None,
+        )
+        for artifact in artifacts
+    ]
+
+
+def _output_ref_from_proto(spec) -> Optional["ObjectRef"]:
+    """Convert the output reference of a protobuf TaskSpec to an ObjectRef."""
+    if not spec.HasField("output_ref"):
+        return None
+
+    from flamepy.core.cache import ObjectRef
+
+    return ObjectRef(endpoint=spec.output_ref.endpoint, key=spec.output_ref.key)
+
+
+def _session_defaults_to_proto(defaults: Optional[Union[SessionDefaults, Dict[str, Any]]]) -> Optional[SessionDefaultsProto]:
+    """Convert the session defaults of an application to protobuf, if any."""
+    if defaults is None:
+        return None
+    if isinstance(defaults, dict):
+        defaults = SessionDefaults(**defaults)
+    return SessionDefaultsProto(
+        slots=defaults.slots,
+        min_instances=defaults.min_instances,
+        max_instances=defaults.max_instances,
+        batch_size=defaults.batch_size,
+        scratch_size=defaults.scratch_size,
+        max_task_attempts=defaults.max_task_attempts,
+        task_timeout=defaults.task_timeout,
+        task_priority=defaults.task_priority,
+    )
+
+
+def _session_defaults_from_proto(spec) -> Optional[SessionDefaults]:
+    """Convert the protobuf session defaults of an application spec, if any."""
+    if not spec.HasField("session_defaults"):
+        return None
+    defaults = spec.session_defaults
+    return SessionDefaults(
+        **{name: getattr(defaults, name) if defaults.HasField(name) else None for name in ("slots", "min_instances", "max_instances", "batch_size", "scratch_size", "max_task_attempts", "task_timeout", "task_priority")}
+    )
+
+
+def _health_probe_to_proto(probe: Optional[Union[HealthProbe, Dict[str, Any]]]) -> Optional[HealthProbeProto]:
+    """Convert the health probe of an application to protobuf, if any."""
+    if probe is None:
+        return None
+    if isinstance(probe, dict):
+        probe = HealthProbe(**probe)
+    return HealthProbeProto(
+        command=probe.command,
+        grpc_service=probe.grpc_service,
+        period_seconds=probe.period_seconds,
+        timeout_seconds=probe.timeout_seconds,
+        failure_threshold=probe.failure_threshold,
+    )
+
+
+def _health_probe_from_proto(spec) -> Optional[HealthProbe]:
+    """Convert the protobuf health probe of an application spec, if any."""
+    if not spec.HasField("health_probe"):
+        return None
+    probe = spec.health_probe
+    return HealthProbe(
+        **{name: getattr(probe, name) if probe.HasField(name) else None for name in ("command", "grpc_service", "period_seconds", "timeout_seconds", "failure_threshold")}
+    )
+
+
+def _lifecycle_hooks_to_proto(hooks: Optional[Union[LifecycleHooks, Dict[str, Any]]]) -> Optional[LifecycleHooksProto]:
+    """Convert the lifecycle hooks of an application to protobuf, if any."""
+    if hooks is None:
+        return None
+    if isinstance(hooks, dict):
+        hooks = LifecycleHooks(**{name: LifecycleHook(**hook) if isinstance(hook, dict) else hook for name, hook in hooks.items()})
+
+    def _hook(hook: Optional[LifecycleHook]) -> Optional[LifecycleHookProto]:
+        if hook is None:
+            return None
+        return LifecycleHookProto(command=hook.command, timeout_seconds=hook.timeout_seconds)
+
+    return LifecycleHooksProto(
+        pre_bind=_hook(hooks.pre_bind),
+        post_bind=_hook(hooks.post_bind),
+        pre_release=_hook(hooks.pre_release),
+    )
+
+
+def _lifecycle_hooks_from_proto(spec) -> Optional[LifecycleHooks]:
+    """Convert the protobuf lifecycle hooks of an application spec, if any."""
+    if not spec.HasField("hooks"):
+        return None
+    hooks = spec.hooks
+
+    def _hook(name: str) -> Optional[LifecycleHook]:
+        if not hooks.HasField(name):
+            return None
+        hook = getattr(hooks, name)
+        return LifecycleHook(command=hook.command, timeout_seconds=hook.timeout_seconds if hook.HasField("timeout_seconds") else None)
+
+    return LifecycleHooks(pre_bind=_hook("pre_bind"), post_bind=_hook("post_bind"), pre_release=_hook("pre_release"))
+
+
+def _python_env_to_proto(env: Optional[Union[PythonEnvironment, Dict[str, Any]]]) -> Optional[PythonEnvironmentProto]:
+    """Convert the Python environment of an application to protobuf, if any."""
+    if env is None:
+        return None
+    if isinstance(env, dict):
+        env = PythonEnvironment(**env)
+    return PythonEnvironmentProto(project=env.project, requirements=env.requirements, python_version=env.python_version)
+
+
+def _python_env_from_proto(spec) -> Optional[PythonEnvironment]:
+    """Convert the protobuf Python environment of an application spec, if any."""
+    if not spec.HasField("python_env"):
+        return None
+    env = spec.python_env
+    return PythonEnvironment(**{name: getattr(env, name) if env.HasField(name) else None for name in ("project", "requirements", "python_version")})
+
+
+def _failure_reason_from_proto(message, field_name: str = "failure_reason") -> Optional[FailureReason]:
+    """Convert the failure reason field of a protobuf message, if any."""
+    if not message.HasField(field_name):
+        return None
+    try:
+        return FailureReason(getattr(message, field_name))
+    except ValueError:
+        return FailureReason.UNKNOWN
+
+
+def _task_usage_from_proto(status) -> Optional[TaskUsage]:
+    """Convert the protobuf resource usage of a task status, if any."""
+    if not status.HasField("usage"):
+        return None
+    usage = status.usage
+    return TaskUsage(
+        cpu_time_ms=usage.cpu_time_ms,
+        max_rss_bytes=usage.max_rss_bytes,
+        read_bytes=usage.read_bytes,
+        write_bytes=usage.write_bytes,
+    )
+
+
+def _task_postmortem_from_proto(status) -> Optional[TaskPostmortem]:
+    """Convert the protobuf postmortem of a task status, if any."""
+    if not status.HasField("postmortem"):
+        return None
+    postmortem = status.postmortem
+    return TaskPostmortem(
+        executor_id=postmortem.executor_id,
+        node=postmortem.node,
+        creation_time=datetime.fromtimestamp(postmortem.creation_time, tz=timezone.utc),
+        exit_code=postmortem.exit_code if postmortem.HasField("exit_code") else None,
+        signal=postmortem.signal if postmortem.HasField("signal") else None,
+        core_dumped=postmortem.core_dumped,
+        stderr_tail=postmortem.stderr_tail if postmortem.HasField("stderr_tail") else None,
+        load_average=postmortem.load_average if postmortem.HasField("load_average") else None,
+        memory_available=postmortem.memory_available if postmortem.HasField("memory_available") else None,
+    )
 
 
 def _task_from_proto(response, session_id: str) -> Task:
@@ -799,6 +1486,14 @@ def _task_from_proto(response, session_id: str) -> Task:
         creation_time=datetime.fromtimestamp(response.status.creation_time / 1000, tz=timezone.utc),
         input=response.spec.input if response.spec.HasField("input") and response.spec.input else None,
         output=response.spec.output if response.spec.HasField("output") and response.spec.output else None,
+        output_ref=_output_ref_from_proto(response.spec),
+        artifacts=_artifacts_from_proto(response.spec.artifacts),
+        failure_reason=_failure_reason_from_proto(response.status),
+        attempts=response.status.attempts,
+        usage=_task_usage_from_proto(response.status),
+        postmortem=_task_postmortem_from_proto(response.status),
+        trace_id=response.status.trace_id if response.status.HasField("trace_id") else None,
+        checkpoint=response.status.checkpoint if response.status.HasField("checkpoint") else None,
         completion_time=(datetime.fromtimestamp(response.status.completion_time / 1000, tz=timezone.utc) if response.status.HasField("completion_time") else None),
         events=[
             Event(
@@ -16,8 +16,9 @@ import os
 import sys
 from abc import abstractmethod
 from concurrent import futures
-from dataclasses import dataclass
-from typing import Optional
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Dict, Iterator, List, Optional, Union
 
 # Handle typing.override compatibility for Python < 3.12
 if sys.version_info >= (3, 12):
@@ -33,16 +34,27 @@ else:
 
 import grpc
 
-from flamepy.core.types import FlameError, FlameErrorCode, TaskOutput
+from flamepy.core.types import FlameError, FlameErrorCode, TaskOutput, TaskResult
 from flamepy.proto.shim_pb2_grpc import InstanceServicer, add_InstanceServicer_to_server
 from flamepy.proto.types_pb2 import (
     Result,
 )
+from flamepy.proto.types_pb2 import FailureReason as FailureReasonProto
+from flamepy.proto.types_pb2 import TaskArtifact as TaskArtifactProto
 from flamepy.proto.types_pb2 import TaskResult as TaskResultProto
 
+if TYPE_CHECKING:
+    from flamepy.core.cache import ObjectRef
+
 logger = logging.getLogger(__name__)
 
+# The endpoint of the instance: a Unix domain socket on Unix, otherwise the
+# file of the TCP loopback address the instance listens on.
 FLAME_INSTANCE_ENDPOINT = "FLAME_INSTANCE_ENDPOINT"
+# Set to "true" to register the gRPC reflection service, e.g. for grpcurl.
+FLAME_GRPC_REFLECTION = "FLAME_GRPC_REFLECTION"
+# Unix domain sockets are not supported by gRPC on Windows.
+_USE_UDS = sys.platform != "win32"
 
 
 class TraceFn:
@@ -73,6 +85,8 @@ class SessionContext:
 
     session_id: str
     application: ApplicationContext
+    # The shared scratch directory of the session on this node, if requested.
+    scratch_dir: Optional[str] = None
 
     def common_data(self) -> Optional[bytes]:
         """Get the common data as bytes."""
@@ -86,6 +100,66 @@ class TaskContext:
     task_id: str
     session_id: str
     input: Optional[bytes]  # Task input as bytes in core API
+    # Per-task overrides; the environments are also set in os.environ during the invocation.
+    environments: Dict[str, str] = field(default_factory=dict)
+    arguments: List[str] = field(default_factory=list)
+    # The latest checkpoint recorded by the previous invocation to resume from, if any.
+    checkpoint: Optional[str] = None
+    # The input in the object cache instead of the inline input, and its checksum if any.
+    input_ref: Optional["ObjectRef"] = None
+    input_checksum: Optional[str] = None
+    # The remaining milliseconds before the deadline of the task, if any.
+    deadline: Optional[int] = None
+
+    def read_input(self) -> Optional[bytes]:
+        """Get the input of the task; the input in the object cache is fetched on the first call."""
+        if self.input is None and self.input_ref is not None:
+            from flamepy.core.cache import get_object, object_checksum
+
+            data = get_object(self.input_ref)
+            if not isinstance(data, bytes):
+                raise FlameError(FlameErrorCode.INVALID_ARGUMENT, f"input <{self.input_ref.key}> is not bytes")
+            if self.input_checksum is not None and object_checksum(data) != self.input_checksum:
+                raise FlameError(FlameErrorCode.INVALID_ARGUMENT, f"checksum of input <{self.input_ref.key}> mismatched")
+            self.input = data
+
+        return self.input
+
+
+@dataclass
+class PreemptContext:
+    """Context for a preemption of the instance."""
+
+    session_id: str
+    # The task interrupted by the preemption, which is requeued afterwards.
+    task_id: Optional[str]
+    # The seconds to checkpoint the state before the instance is stopped.
+    grace_period: int
+
+
+@contextmanager
+def _task_environments(environments: Dict[str, str]) -> Iterator[None]:
+    """Set the task's environment variables, and restore them after the invocation."""
+    previous = {name: os.environ.get(name) for name in environments}
+    os.environ.update(environments)
+    try:
+        yield
+    finally:
+        for name, value in previous.items():
+            if value is None:
+                os.environ.pop(name, None)
+            else:
+                os.environ[name] = value
+
+
+def _publish_addr(endpoint: str, addr: str) -> None:
+    """Write the TCP loopback address of the instance to its endpoint, where the
+    executor resolves it; the address is renamed into place, so the executor
+    never reads a partial one."""
+    tmp = f"{endpoint}.tmp"
+    with open(tmp, "w") as f:
+        f.write(addr)
+    os.replace(tmp, endpoint)
 
 
 class FlameService:
@@ -105,7 +179,7 @@ class FlameService:
         pass
 
     @abstractmethod
-    def on_task_invoke(self, context: TaskContext) -> Optional[TaskOutput]:
+    def on_task_invoke(self, context: TaskContext) -> Union[Optional[TaskOutput], TaskResult]:
         """
         Called when a task is invoked.
 
@@ -113,7 +187,8 @@ class FlameService:
             context: Task context information
 
         Returns:
-            Task output as bytes, or None if no output
+            Task output as bytes, or None if no output; or a TaskResult
+            with the output and the named artifacts
         """
         pass
 
@@ -127,6 +202,17 @@ class FlameService:
         """
         pass
 
+    def on_preempt(self, context: PreemptContext):
+        """
+        Called when the instance is preempted while a task is running, e.g.
+        to checkpoint its state into the object cache within the grace
+        period, so the requeued task resumes from it. Does nothing by default.
+
+        Args:
+            context: Preemption context information
+        """
+        pass
+
 
 class FlameInstanceServicer(InstanceServicer):
     """gRPC servicer implementation for GrpcShim service."""
@@ -160,6 +246,7 @@ class FlameInstanceServicer(InstanceServicer):
                 _common_data=common_data_bytes,
                 session_id=request.session_id,
                 application=app_context,
+                scratch_dir=request.scratch_dir if request.HasField("scratch_dir") else None,
             )
 
             logger.debug(f"session_context: {session_context}")
@@ -191,20 +278,40 @@ class FlameInstanceServicer(InstanceServicer):
                 task_id=request.task_id,
                 session_id=request.session_id,
                 input=input_bytes,
+                environments={env.name: env.value for env in request.environments},
+                arguments=list(request.arguments),
+                checkpoint=request.checkpoint if request.HasField("checkpoint") else None,
+                deadline=request.deadline if request.HasField("deadline") else None,
             )
+            if request.HasField("input_ref"):
+                from flamepy.core.cache import ObjectRef
+
+                task_context.input_ref = ObjectRef(endpoint=request.input_ref.endpoint, key=request.input_ref.key)
+                task_context.input_checksum = request.input_ref.checksum if request.input_ref.HasField("checksum") else None
 
             logger.debug(f"task_context: {task_context}")
 
             # Call the service implementation
-            output_data = self._service.on_task_invoke(task_context)
+            with _task_environments(task_context.environments):
+                output_data = self._service.on_task_invoke(task_context)
             logger.debug("on_task_invoke completed successfully")
 
-            # Return task output
-            return TaskResultProto(return_code=0, output=output_data, message=None)
+            # Return task output, and the artifacts if the service returns a TaskResult
+            artifacts = []
+            if isinstance(output_data, TaskResult):
+                artifacts = [TaskArtifactProto(name=a.name, data=a.data, object_ref=a.object_ref) for a in output_data.artifacts]
+                output_data = output_data.output
+
+            return TaskResultProto(return_code=0, output=output_data, message=None, artifacts=artifacts)
 
         except Exception as e:
             logger.error(f"Error in OnTaskInvoke: {e}")
-            return TaskResultProto(return_code=-1, output=None, message=f"{str(e)}")
+            # The services reject the invalid inputs by FlameError of INVALID_ARGUMENT.
+            if isinstance(e, FlameError) and e.code == FlameErrorCode.INVALID_ARGUMENT:
+                reason = FailureReasonProto.InputInvalid
+            else:
+                reason = FailureReasonProto.ApplicationError
+            return TaskResultProto(return_code=-1, output=None, message=f"{str(e)}", failure_reason=reason)
 
     @override
     def OnSessionLeave(self, request, context):  # noqa: N802
@@ -225,6 +332,32 @@ class FlameInstanceServicer(InstanceServicer):
             logger.error(f"Error in OnSessionLeave: {e}")
             return Result(return_code=-1, message=f"{str(e)}")
 
+    @override
+    def OnPreempt(self, request, context):  # noqa: N802
+        """Handle OnPreempt RPC call."""
+        _trace_fn = TraceFn("OnPreempt")
+
+        try:
+            preempt_context = PreemptContext(
+                session_id=request.session_id,
+                task_id=request.task_id if request.HasField("task_id") else None,
+                grace_period=request.grace_period,
+            )
+
+            logger.debug(f"preempt_context: {preempt_context}")
+
+            # Call the service implementation
+            self._service.on_preempt(preempt_context)
+            logger.debug("on_preempt completed successfully")
+
+            return Result(
+                return_code=0,
+            )
+
+        except Exception as e:
+            logger.error(f"Error in OnPreempt: {e}")
+            return Result(return_code=-1, message=f"{str(e)}")
+
 
 class FlameInstanceServer:
     """Server for gRPC shim services."""
@@ -243,16 +376,25 @@ class FlameInstanceServer:
             shim_servicer = FlameInstanceServicer(self._service)
             add_InstanceServicer_to_server(shim_servicer, self._server)
 
-            # Listen on Unix socket
+            if os.getenv(FLAME_GRPC_REFLECTION, "").lower() == "true":
+                self._enable_reflection()
+
             endpoint = os.getenv(FLAME_INSTANCE_ENDPOINT)
-            if endpoint is not None:
+            if endpoint is None:
+                raise FlameError(FlameErrorCode.INVALID_CONFIG, "FLAME_INSTANCE_ENDPOINT not found")
+
+            if _USE_UDS:
+                # Listen on Unix socket
                 self._server.add_insecure_port(f"unix://{endpoint}")
+                self._server.start()
                 logger.debug(f"Flame Python instance service started on Unix socket: {endpoint}")
             else:
-                raise FlameError(FlameErrorCode.INVALID_CONFIG, "FLAME_INSTANCE_ENDPOINT not found")
+                # Listen on a TCP loopback port, and publish its address once serving
+                port = self._server.add_insecure_port("127.0.0.1:0")
+                self._server.start()
+                _publish_addr(endpoint, f"127.0.0.1:{port}")
+                logger.debug(f"Flame Python instance service started on 127.0.0.1:{port}")
 
-            # Start server
-            self._server.start()
             # Keep server running
             self._server.wait_for_termination()
 
@@ -262,6 +404,23 @@ class FlameInstanceServer:
                 f"Failed to start gRPC instance server: {str(e)}",
             )
 
+    def _enable_reflection(self):
+        """Register the gRPC reflection service if grpcio-reflection is installed."""
+        try:
+            from grpc_reflection.v1alpha import reflection
+        except ImportError:
+            logger.warning("grpcio-reflection is not installed, gRPC reflection is disabled")
+            return
+
+        from flamepy.proto import shim_pb2
+
+        service_names = (
+            shim_pb2.DESCRIPTOR.services_by_name["Instance"].full_name,
+            reflection.SERVICE_NAME,
+        )
+        reflection.enable_server_reflection(service_names, self._server)
+        logger.debug("gRPC reflection enabled for Flame Python instance service")
+
     def stop(self):
         """Stop the gRPC server."""
         if self._server:
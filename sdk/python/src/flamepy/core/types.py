@@ -11,6 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 """
 
+import json
 import os
 import random
 import string
@@ -18,10 +19,13 @@ from dataclasses import dataclass, field
 from datetime import datetime
 from enum import IntEnum
 from pathlib import Path
-from typing import Any, Dict, List, Optional
+from typing import TYPE_CHECKING, Any, Dict, List, Optional
 
 import yaml
 
+if TYPE_CHECKING:
+    from flamepy.core.cache import ObjectRef
+
 # Type aliases
 TaskID = str
 SessionID = str
@@ -44,6 +48,23 @@ class SessionState(IntEnum):
 
     OPEN = 0
     CLOSED = 1
+    # No new tasks are accepted, and the running tasks are drained before the session is closed.
+    CLOSING = 2
+
+
+class ReplayPolicy(IntEnum):
+    """The policy of the tasks when a closed session is reopened."""
+
+    KEEP_RESULTS = 0  # Keep the results of the completed tasks, and only accept new tasks
+    RETRY_FAILED = 1  # Reset the failed tasks to pending to run them again
+
+
+class ResultPolicy(IntEnum):
+    """What happens to the outputs of the completed tasks of a session."""
+
+    INLINE = 0  # Keep the outputs in the storage of the session manager
+    DISCARD = 1  # Deliver the outputs to the watchers of the tasks without persisting them
+    OBJECT_CACHE = 2  # Put the outputs into the object cache, and keep their references instead
 
 
 class TaskState(IntEnum):
@@ -53,6 +74,45 @@ class TaskState(IntEnum):
     RUNNING = 1
     SUCCEED = 2
     FAILED = 3
+    CANCELLED = 4
+    # The task lost its executors too many times; it is not dispatched until it is released.
+    QUARANTINED = 5
+
+
+class FailureReason(IntEnum):
+    """The reason of a task failure."""
+
+    UNKNOWN = 0
+    APPLICATION_ERROR = 1
+    SHIM_CRASH = 2
+    TIMEOUT = 3
+    PREEMPTED = 4
+    NODE_LOST = 5
+    INPUT_INVALID = 6
+    DEADLINE_EXCEEDED = 7
+
+    def is_infrastructure(self) -> bool:
+        """Whether the failure is caused by the infrastructure instead of the application."""
+        return self in (FailureReason.SHIM_CRASH, FailureReason.TIMEOUT, FailureReason.PREEMPTED, FailureReason.NODE_LOST)
+
+
+class JobState(IntEnum):
+    """The state of a job, i.e. of the tasks it runs in its session."""
+
+    RUNNING = 0
+    SUCCEED = 1
+    FAILED = 2
+    CANCELLED = 3
+
+    def is_terminal(self) -> bool:
+        return self != JobState.RUNNING
+
+
+class CompletionPolicy(IntEnum):
+    """When a job succeeds: all its tasks completed, or any of them succeeded."""
+
+    ALL_TASKS = 0
+    ANY_TASK = 1
 
 
 class ApplicationState(IntEnum):
@@ -69,6 +129,58 @@ class Shim(IntEnum):
     WASM = 1
 
 
+class FairnessPolicy(IntEnum):
+    """How the instances of an application are shared among its sessions."""
+
+    ROUND_ROBIN = 0
+    PROPORTIONAL = 1
+
+
+class PayloadCodec(IntEnum):
+    """The codec of the task inputs and outputs of an application."""
+
+    RAW = 0
+    JSON = 1
+    MSGPACK = 2
+    ARROW = 3
+    PROTOBUF = 4
+
+    def encode(self, value: Any) -> bytes:
+        """Encode the value into a payload of this codec; JSON and MessagePack only."""
+        if self == PayloadCodec.JSON:
+            return json.dumps(value).encode("utf-8")
+        if self == PayloadCodec.MSGPACK:
+            return _msgpack().packb(value)
+        raise FlameError(FlameErrorCode.INVALID_ARGUMENT, f"can not encode payload of codec {self.name}")
+
+    def decode(self, data: bytes) -> Any:
+        """Decode the payload of this codec into a value; JSON and MessagePack only."""
+        try:
+            if self == PayloadCodec.JSON:
+                return json.loads(data)
+            if self == PayloadCodec.MSGPACK:
+                return _msgpack().unpackb(data)
+        except ValueError as e:
+            raise FlameError(FlameErrorCode.INVALID_ARGUMENT, f"failed to decode payload: {e}")
+        raise FlameError(FlameErrorCode.INVALID_ARGUMENT, f"can not decode payload of codec {self.name}")
+
+
+class TopologyPolicy(IntEnum):
+    """How the instances of an application are placed in the GPU/NUMA topology of the nodes."""
+
+    IGNORE = 0
+    NUMA_ALIGNED = 1
+    GPU_ALIGNED = 2
+
+
+def _msgpack():
+    try:
+        import msgpack
+    except ImportError:
+        raise FlameError(FlameErrorCode.INVALID_CONFIG, "the msgpack package is required by the MSGPACK codec")
+    return msgpack
+
+
 class FlameErrorCode(IntEnum):
     """Flame error code enumeration."""
 
@@ -109,6 +221,13 @@ class SessionAttributes:
     min_instances: int = 0
     max_instances: Optional[int] = None
     batch_size: int = 1
+    scratch_size: Optional[int] = None  # Size limit in bytes of the shared scratch directory per node
+    max_task_attempts: Optional[int] = None  # Attempts of a task before it's quarantined (None = the cluster's)
+    task_timeout: Optional[int] = None  # Timeout in seconds of running a task (None = the application's or the cluster's)
+    task_priority: Optional[int] = None  # Priority of the tasks among the pending tasks of the session
+    priority: int = 0  # Priority of the session in scheduling, the higher the earlier
+    result_policy: ResultPolicy = ResultPolicy.INLINE  # What happens to the outputs of the completed tasks
+    ttl_after_completion: Optional[int] = None  # Seconds to keep the session after it's closed, then it's deleted (None = forever)
 
 
 @dataclass
@@ -120,6 +239,57 @@ class ApplicationSchema:
     common_data: Optional[str] = None
 
 
+@dataclass
+class SessionDefaults:
+    """The defaults of the sessions of an application, applied to the attributes the session leaves unspecified."""
+
+    slots: Optional[int] = None
+    min_instances: Optional[int] = None
+    max_instances: Optional[int] = None
+    batch_size: Optional[int] = None
+    scratch_size: Optional[int] = None
+    max_task_attempts: Optional[int] = None
+    task_timeout: Optional[int] = None
+    task_priority: Optional[int] = None
+
+
+@dataclass
+class HealthProbe:
+    """The health probe of the instances of an application; exactly one of command and grpc_service is set."""
+
+    command: Optional[str] = None
+    grpc_service: Optional[str] = None
+    period_seconds: Optional[int] = None
+    timeout_seconds: Optional[int] = None
+    failure_threshold: Optional[int] = None
+
+
+@dataclass
+class LifecycleHook:
+    """A lifecycle hook of the instances of an application, i.e. a command run by `sh -c` in the directory of the instance."""
+
+    command: str
+    timeout_seconds: Optional[int] = None
+
+
+@dataclass
+class LifecycleHooks:
+    """The lifecycle hooks of the instances of an application, run by the executor manager on the node."""
+
+    pre_bind: Optional[LifecycleHook] = None
+    post_bind: Optional[LifecycleHook] = None
+    pre_release: Optional[LifecycleHook] = None
+
+
+@dataclass
+class PythonEnvironment:
+    """The Python environment of the host instances of an application, built by uv on the node from either its project or its requirements file."""
+
+    project: Optional[str] = None
+    requirements: Optional[str] = None
+    python_version: Optional[str] = None
+
+
 @dataclass
 class ApplicationAttributes:
     """Attributes for an application."""
@@ -136,6 +306,132 @@ class ApplicationAttributes:
     delay_release: Optional[int] = None
     schema: Optional[ApplicationSchema] = None
     url: Optional[str] = None
+    task_environments: Optional[List[str]] = None
+    task_arguments: Optional[List[str]] = None
+    bundle_size: Optional[int] = None
+    max_instances_per_node: Optional[int] = None
+    session_defaults: Optional[SessionDefaults] = None
+    health_probe: Optional[HealthProbe] = None
+    fairness: Optional[FairnessPolicy] = None
+    hooks: Optional[LifecycleHooks] = None
+    python_env: Optional[PythonEnvironment] = None
+    codec: Optional[PayloadCodec] = None
+    payload_type: Optional[str] = None
+    topology: Optional[TopologyPolicy] = None
+
+
+@dataclass
+class TaskDurationStats:
+    """The statistics of the durations of the completed tasks in a session, in milliseconds."""
+
+    count: int = 0
+    mean: float = 0.0
+    min: int = 0
+    max: int = 0
+    recent: float = 0.0
+
+
+@dataclass
+class TaskFailureSummary:
+    """The failed tasks of a category, e.g. the error type of their messages."""
+
+    category: str
+    count: int = 0
+    message: Optional[str] = None
+    reason: Optional[FailureReason] = None
+
+
+@dataclass
+class SessionSummary:
+    """The aggregated statistics of the tasks in a session, computed by the session manager."""
+
+    session_id: str
+    pending: int = 0
+    running: int = 0
+    succeed: int = 0
+    failed: int = 0
+    cancelled: int = 0
+    quarantined: int = 0
+    # The window in minutes of the throughput.
+    window: int = 0
+    # The tasks completed within the window.
+    completed: int = 0
+    # The completed tasks per minute within the window.
+    throughput: float = 0.0
+    # The average duration in milliseconds of the completed tasks.
+    average_duration: Optional[float] = None
+    # The failed tasks by category, the most frequent first.
+    failures: List[TaskFailureSummary] = field(default_factory=list)
+
+
+@dataclass
+class Job:
+    """A set of tasks run to completion in a session managed by the session manager."""
+
+    id: str
+    application: str
+    state: JobState
+    creation_time: datetime
+    # The session running the tasks of the job, which has the ID of the job.
+    session_id: str
+    slots: int = 0
+    parallelism: Optional[int] = None
+    completion_policy: CompletionPolicy = CompletionPolicy.ALL_TASKS
+    # The failed tasks tolerated before the job fails.
+    max_failed: int = 0
+    completion_time: Optional[datetime] = None
+    total: int = 0
+    submitted: int = 0
+    succeed: int = 0
+    failed: int = 0
+    # Why the job failed or was cancelled.
+    message: Optional[str] = None
+
+
+@dataclass
+class TaskArtifact:
+    """A named output artifact of a task: an inline payload or an object cache reference."""
+
+    name: str
+    data: Optional[bytes] = None
+    object_ref: Optional[str] = None
+
+
+@dataclass
+class TaskUsage:
+    """The resources used by the instance of an executor to run a task."""
+
+    cpu_time_ms: int = 0
+    max_rss_bytes: int = 0
+    read_bytes: int = 0
+    write_bytes: int = 0
+
+
+@dataclass
+class TaskPostmortem:
+    """The postmortem of the crashed instance which ran a task."""
+
+    executor_id: str
+    node: str
+    creation_time: datetime
+    # The exit code of the instance, if it exited by itself.
+    exit_code: Optional[int] = None
+    # The signal which terminated the instance, if any.
+    signal: Optional[int] = None
+    core_dumped: bool = False
+    # The tail of the stderr of the instance.
+    stderr_tail: Optional[str] = None
+    # The load average of the last minute and the available memory in bytes of the node.
+    load_average: Optional[float] = None
+    memory_available: Optional[int] = None
+
+
+@dataclass
+class TaskResult:
+    """The result of a task invocation: the output and the named artifacts."""
+
+    output: Optional[TaskOutput] = None
+    artifacts: List[TaskArtifact] = field(default_factory=list)
 
 
 @dataclass
@@ -148,8 +444,22 @@ class Task:
     creation_time: datetime
     input: Any = None
     output: Any = None
+    # The output in the object cache, if the session offloads the outputs of its tasks.
+    output_ref: Optional["ObjectRef"] = None
     completion_time: Optional[datetime] = None
     events: Optional[List[Event]] = None
+    artifacts: List["TaskArtifact"] = field(default_factory=list)
+    failure_reason: Optional[FailureReason] = None
+    # The times the task was dispatched again after its executor was lost.
+    attempts: int = 0
+    # The resources used to run the task, if measured.
+    usage: Optional[TaskUsage] = None
+    # The latest checkpoint of the task, i.e. the reference of an object in the object cache.
+    checkpoint: Optional[str] = None
+    # The postmortem of the latest crash of the instance running the task, if any.
+    postmortem: Optional[TaskPostmortem] = None
+    # The trace ID of the request which created the task, if any.
+    trace_id: Optional[str] = None
 
     def is_completed(self) -> bool:
         """Check if the task is completed."""
@@ -180,6 +490,18 @@ class Application:
     delay_release: Optional[int] = None
     schema: Optional[ApplicationSchema] = None
     url: Optional[str] = None
+    task_environments: Optional[List[str]] = None
+    task_arguments: Optional[List[str]] = None
+    bundle_size: Optional[int] = None
+    max_instances_per_node: Optional[int] = None
+    session_defaults: Optional[SessionDefaults] = None
+    health_probe: Optional[HealthProbe] = None
+    fairness: Optional[FairnessPolicy] = None
+    hooks: Optional[LifecycleHooks] = None
+    python_env: Optional[PythonEnvironment] = None
+    codec: Optional[PayloadCodec] = None
+    payload_type: Optional[str] = None
+    topology: Optional[TopologyPolicy] = None
 
 
 class TaskInformer:
@@ -324,6 +646,15 @@ class FlameContext:
                 if current_context is None:
                     raise FlameError(FlameErrorCode.INVALID_CONFIG, "current-context is not set")
 
+                # The federation resolves to its first context, i.e. the preferred cluster.
+                for federation in config.get("federations", []):
+                    if current_context == federation["name"]:
+                        contexts = federation.get("contexts", [])
+                        if not contexts:
+                            raise FlameError(FlameErrorCode.INVALID_CONFIG, f"federation <{current_context}> has no context")
+                        current_context = contexts[0]
+                        break
+
                 for ctx in config.get("contexts", []):
                     if current_context == ctx["name"]:
                         # Parse cluster configuration
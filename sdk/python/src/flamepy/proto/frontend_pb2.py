@@ -25,7 +25,7 @@ _sym_db = _symbol_database.Default()
 import flamepy.proto.types_pb2 as types__pb2
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x0e\x66rontend.proto\x12\x08\x66lame.v1\x1a\x0btypes.proto\"Z\n\x1aRegisterApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\x12.\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\",\n\x1cUnregisterApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"X\n\x18UpdateApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\x12.\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\"%\n\x15GetApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"\x18\n\x16ListApplicationRequest\"\x15\n\x13ListExecutorRequest\"\x12\n\x10ListNodesRequest\"\x1e\n\x0eGetNodeRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"/\n\x0fGetNodeResponse\x12\x1c\n\x04node\x18\x01 \x01(\x0b\x32\x0e.flame.v1.Node\"R\n\x14\x43reateSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12&\n\x07session\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpec\"*\n\x14\x44\x65leteSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"a\n\x12OpenSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12+\n\x07session\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpecH\x00\x88\x01\x01\x42\n\n\x08_session\")\n\x13\x43loseSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"\'\n\x11GetSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"\x14\n\x12ListSessionRequest\"5\n\x11\x43reateTaskRequest\x12 \n\x04task\x18\x01 \x01(\x0b\x32\x12.flame.v1.TaskSpec\"8\n\x11\x44\x65leteTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"5\n\x0eGetTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"7\n\x10WatchTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"%\n\x0fListTaskRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t2\xa6\n\n\x08\x46rontend\x12O\n\x13RegisterApplication\x12$.flame.v1.RegisterApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12S\n\x15UnregisterApplication\x12&.flame.v1.UnregisterApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12K\n\x11UpdateApplication\x12\".flame.v1.UpdateApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12J\n\x0eGetApplication\x12\x1f.flame.v1.GetApplicationRequest\x1a\x15.flame.v1.Application\"\x00\x12P\n\x0fListApplication\x12 .flame.v1.ListApplicationRequest\x1a\x19.flame.v1.ApplicationList\"\x00\x12G\n\x0cListExecutor\x12\x1d.flame.v1.ListExecutorRequest\x1a\x16.flame.v1.ExecutorList\"\x00\x12=\n\tListNodes\x12\x1a.flame.v1.ListNodesRequest\x1a\x12.flame.v1.NodeList\"\x00\x12@\n\x07GetNode\x12\x18.flame.v1.GetNodeRequest\x1a\x19.flame.v1.GetNodeResponse\"\x00\x12\x44\n\rCreateSession\x12\x1e.flame.v1.CreateSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x44\n\rDeleteSession\x12\x1e.flame.v1.DeleteSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12@\n\x0bOpenSession\x12\x1c.flame.v1.OpenSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x42\n\x0c\x43loseSession\x12\x1d.flame.v1.CloseSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12>\n\nGetSession\x12\x1b.flame.v1.GetSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x44\n\x0bListSession\x12\x1c.flame.v1.ListSessionRequest\x1a\x15.flame.v1.SessionList\"\x00\x12;\n\nCreateTask\x12\x1b.flame.v1.CreateTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12;\n\nDeleteTask\x12\x1b.flame.v1.DeleteTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12\x35\n\x07GetTask\x12\x18.flame.v1.GetTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12;\n\tWatchTask\x12\x1a.flame.v1.WatchTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x30\x01\x12\x39\n\x08ListTask\x12\x19.flame.v1.ListTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x30\x01\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x0e\x66rontend.proto\x12\x08\x66lame.v1\x1a\x0btypes.proto\"Z\n\x1aRegisterApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\x12.\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\",\n\x1cUnregisterApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"X\n\x18UpdateApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\x12.\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\"(\n\x18\x45nableApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\")\n\x19\x44isableApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"%\n\x15GetApplicationRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"\x18\n\x16ListApplicationRequest\"\x15\n\x13ListExecutorRequest\"\x12\n\x10ListNodesRequest\"\x1e\n\x0eGetNodeRequest\x12\x0c\n\x04name\x18\x01 \x01(\t\"/\n\x0fGetNodeResponse\x12\x1c\n\x04node\x18\x01 \x01(\x0b\x32\x0e.flame.v1.Node\"X\n\x16\x43reateJoinTokenRequest\x12\x16\n\tnode_name\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x10\n\x03ttl\x18\x02 \x01(\x04H\x01\x88\x01\x01\x42\x0c\n\n_node_nameB\x06\n\x04_ttl\"Y\n\tJoinToken\x12\r\n\x05token\x18\x01 \x01(\t\x12\x16\n\tnode_name\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x17\n\x0f\x65xpiration_time\x18\x03 \x01(\x03\x42\x0c\n\n_node_name\"3\n\x19GetClusterSnapshotRequest\x12\x0f\n\x02\x61t\x18\x01 \x01(\x03H\x00\x88\x01\x01\x42\x05\n\x03_at\"\x93\x01\n\x0f\x43lusterSnapshot\x12\x15\n\rcreation_time\x18\x01 \x01(\x03\x12\x1d\n\x05nodes\x18\x02 \x03(\x0b\x32\x0e.flame.v1.Node\x12#\n\x08sessions\x18\x03 \x03(\x0b\x32\x11.flame.v1.Session\x12%\n\texecutors\x18\x04 \x03(\x0b\x32\x12.flame.v1.Executor\"\x1a\n\x18GetSchedulerStateRequest\"U\n\x0cSessionShare\x12\x0f\n\x07\x64\x65sired\x18\x01 \x01(\x01\x12\x10\n\x08\x64\x65served\x18\x02 \x01(\x01\x12\x11\n\tallocated\x18\x03 \x01(\x01\x12\x0f\n\x07\x65lastic\x18\x04 \x01(\x01\"\x8d\x01\n\rPluginVerdict\x12\x0e\n\x06plugin\x18\x01 \x01(\t\x12\x16\n\tunderused\x18\x02 \x01(\x08H\x00\x88\x01\x01\x12\x18\n\x0bpreemptible\x18\x03 \x01(\x08H\x01\x88\x01\x01\x12\x12\n\x05ready\x18\x04 \x01(\x08H\x02\x88\x01\x01\x42\x0c\n\n_underusedB\x0e\n\x0c_preemptibleB\x08\n\x06_ready\"\xd2\x01\n\x16SessionSchedulingState\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x0c\n\x04rank\x18\x02 \x01(\r\x12*\n\x05share\x18\x03 \x01(\x0b\x32\x16.flame.v1.SessionShareH\x00\x88\x01\x01\x12)\n\x08verdicts\x18\x04 \x03(\x0b\x32\x17.flame.v1.PluginVerdict\x12\x11\n\tunderused\x18\x05 \x01(\x08\x12\x13\n\x0bpreemptible\x18\x06 \x01(\x08\x12\r\n\x05ready\x18\x07 \x01(\x08\x42\x08\n\x06_share\"l\n\x0eSchedulerState\x12\x15\n\rcreation_time\x18\x01 \x01(\x03\x12\x0f\n\x07plugins\x18\x02 \x03(\t\x12\x32\n\x08sessions\x18\x03 \x03(\x0b\x32 .flame.v1.SessionSchedulingState\"\x1e\n\x1cGetClusterUtilizationRequest\"\xc2\x01\n\x0fNodeUtilization\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\"\n\x05state\x18\x02 \x01(\x0e\x32\x13.flame.v1.NodeState\x12\x13\n\x0btotal_slots\x18\x03 \x01(\r\x12\x12\n\nused_slots\x18\x04 \x01(\r\x12\x11\n\texecutors\x18\x05 \x01(\r\x12\x19\n\x11running_executors\x18\x06 \x01(\r\x12\x17\n\nsaturation\x18\x07 \x01(\x01H\x00\x88\x01\x01\x42\r\n\x0b_saturation\"\xd8\x01\n\x13\x45xecutorUtilization\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04node\x18\x02 \x01(\t\x12&\n\x05state\x18\x03 \x01(\x0e\x32\x17.flame.v1.ExecutorState\x12\r\n\x05slots\x18\x04 \x01(\r\x12\x17\n\nsession_id\x18\x05 \x01(\tH\x00\x88\x01\x01\x12\x14\n\x07task_id\x18\x06 \x01(\tH\x01\x88\x01\x01\x12\x17\n\nbusy_ratio\x18\x07 \x01(\x01H\x02\x88\x01\x01\x42\r\n\x0b_session_idB\n\n\x08_task_idB\r\n\x0b_busy_ratio\"\xbf\x01\n\x12SessionUtilization\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x13\n\x0b\x61pplication\x18\x02 \x01(\t\x12\x11\n\texecutors\x18\x03 \x01(\r\x12\x12\n\nused_slots\x18\x04 \x01(\r\x12\x15\n\rpending_tasks\x18\x05 \x01(\r\x12\x15\n\rrunning_tasks\x18\x06 \x01(\r\x12\x15\n\rsucceed_tasks\x18\x07 \x01(\r\x12\x14\n\x0c\x66\x61iled_tasks\x18\x08 \x01(\r\"\xe0\x01\n\x12\x43lusterUtilization\x12\x15\n\rcreation_time\x18\x01 \x01(\x03\x12\x13\n\x0btotal_slots\x18\x02 \x01(\r\x12\x12\n\nused_slots\x18\x03 \x01(\r\x12(\n\x05nodes\x18\x04 \x03(\x0b\x32\x19.flame.v1.NodeUtilization\x12\x30\n\texecutors\x18\x05 \x03(\x0b\x32\x1d.flame.v1.ExecutorUtilization\x12.\n\x08sessions\x18\x06 \x03(\x0b\x32\x1c.flame.v1.SessionUtilization\"R\n\x14\x43reateSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12&\n\x07session\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpec\"*\n\x14\x44\x65leteSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"\xa7\x01\n\x12OpenSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12+\n\x07session\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpecH\x00\x88\x01\x01\x12\x32\n\rreplay_policy\x18\x03 \x01(\x0e\x32\x16.flame.v1.ReplayPolicyH\x01\x88\x01\x01\x42\n\n\x08_sessionB\x10\n\x0e_replay_policy\"G\n\x13\x43loseSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x12\n\x05\x66orce\x18\x02 \x01(\x08H\x00\x88\x01\x01\x42\x08\n\x06_force\"Y\n\x16ResubmitSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x16\n\x0enew_session_id\x18\x02 \x01(\t\x12\x13\n\x0bonly_failed\x18\x03 \x01(\x08\"\'\n\x11GetSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\")\n\x13WatchSessionRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"\x14\n\x12ListSessionRequest\".\n\x18RenewSessionLeaseRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"J\n\x19RenewSessionLeaseResponse\x12\x17\n\x0f\x65xpiration_time\x18\x01 \x01(\x03\x12\x14\n\x0cgrace_period\x18\x02 \x01(\x04\"N\n\x18GetSessionSummaryRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x13\n\x06window\x18\x02 \x01(\rH\x00\x88\x01\x01\x42\t\n\x07_window\"\x90\x01\n\x12TaskFailureSummary\x12\x10\n\x08\x63\x61tegory\x18\x01 \x01(\t\x12\r\n\x05\x63ount\x18\x02 \x01(\x04\x12\x14\n\x07message\x18\x03 \x01(\tH\x00\x88\x01\x01\x12,\n\x06reason\x18\x04 \x01(\x0e\x32\x17.flame.v1.FailureReasonH\x01\x88\x01\x01\x42\n\n\x08_messageB\t\n\x07_reason\"\xaa\x02\n\x0eSessionSummary\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x0f\n\x07pending\x18\x02 \x01(\x05\x12\x0f\n\x07running\x18\x03 \x01(\x05\x12\x0f\n\x07succeed\x18\x04 \x01(\x05\x12\x0e\n\x06\x66\x61iled\x18\x05 \x01(\x05\x12\x11\n\tcancelled\x18\x06 \x01(\x05\x12\x0e\n\x06window\x18\x07 \x01(\r\x12\x11\n\tcompleted\x18\x08 \x01(\x04\x12\x12\n\nthroughput\x18\t \x01(\x01\x12\x1d\n\x10\x61verage_duration\x18\n \x01(\x01H\x00\x88\x01\x01\x12.\n\x08\x66\x61ilures\x18\x0b \x03(\x0b\x32\x1c.flame.v1.TaskFailureSummary\x12\x13\n\x0bquarantined\x18\x0c \x01(\x05\x42\x13\n\x11_average_duration\"J\n\x1eListSlotRecommendationsRequest\x12\x18\n\x0b\x61pplication\x18\x01 \x01(\tH\x00\x88\x01\x01\x42\x0e\n\x0c_application\"\xb8\x01\n\x12SlotRecommendation\x12\x13\n\x0b\x61pplication\x18\x01 \x01(\t\x12\x17\n\nsession_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\r\n\x05tasks\x18\x03 \x01(\x04\x12\x0b\n\x03\x63pu\x18\x04 \x01(\x01\x12\x0e\n\x06memory\x18\x05 \x01(\x04\x12\r\n\x05slots\x18\x06 \x01(\r\x12\x19\n\x11recommended_slots\x18\x07 \x01(\r\x12\x0f\n\x07message\x18\x08 \x01(\tB\r\n\x0b_session_id\"O\n\x16SlotRecommendationList\x12\x35\n\x0frecommendations\x18\x01 \x03(\x0b\x32\x1c.flame.v1.SlotRecommendation\"N\n\x16GetTaskSettingsRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x14\n\x07task_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\n\n\x08_task_id\"\xdc\x01\n\x0cTaskSettings\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x14\n\x07task_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x14\n\x07timeout\x18\x03 \x01(\x04H\x01\x88\x01\x01\x12\x16\n\x0etimeout_source\x18\x04 \x01(\t\x12\x14\n\x0cmax_attempts\x18\x05 \x01(\r\x12\x1b\n\x13max_attempts_source\x18\x06 \x01(\t\x12\x10\n\x08priority\x18\x07 \x01(\r\x12\x17\n\x0fpriority_source\x18\x08 \x01(\tB\n\n\x08_task_idB\n\n\x08_timeout\"\x9a\x02\n\x11ListEventsRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x12\n\x05since\x18\x02 \x01(\x03H\x00\x88\x01\x01\x12\x12\n\x05until\x18\x03 \x01(\x03H\x01\x88\x01\x01\x12\r\n\x05\x63odes\x18\x04 \x03(\x05\x12,\n\x05owner\x18\x05 \x01(\x0e\x32\x18.flame.v1.EventOwnerKindH\x02\x88\x01\x01\x12\x14\n\x07task_id\x18\x06 \x01(\tH\x03\x88\x01\x01\x12\x16\n\tpage_size\x18\x07 \x01(\rH\x04\x88\x01\x01\x12\x17\n\npage_token\x18\x08 \x01(\tH\x05\x88\x01\x01\x42\x08\n\x06_sinceB\x08\n\x06_untilB\x08\n\x06_ownerB\n\n\x08_task_idB\x0c\n\n_page_sizeB\r\n\x0b_page_token\"P\n\x0cSessionEvent\x12\x14\n\x07task_id\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x1e\n\x05\x65vent\x18\x02 \x01(\x0b\x32\x0f.flame.v1.EventB\n\n\x08_task_id\"e\n\tEventList\x12&\n\x06\x65vents\x18\x01 \x03(\x0b\x32\x16.flame.v1.SessionEvent\x12\x1c\n\x0fnext_page_token\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\x12\n\x10_next_page_token\"5\n\x11\x43reateTaskRequest\x12 \n\x04task\x18\x01 \x01(\x0b\x32\x12.flame.v1.TaskSpec\"8\n\x11\x44\x65leteTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"9\n\x12ReleaseTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"P\n\x15\x43heckpointTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\x12\x12\n\ncheckpoint\x18\x03 \x01(\t\"5\n\x0eGetTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"7\n\x10WatchTaskRequest\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\"=\n\x16WatchTaskOutputRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x0f\n\x07task_id\x18\x02 \x01(\t\"%\n\x0fListTaskRequest\x12\x12\n\nsession_id\x18\x01 \x01(\t\"B\n\x10\x43reateJobRequest\x12\x0e\n\x06job_id\x18\x01 \x01(\t\x12\x1e\n\x03job\x18\x02 \x01(\x0b\x32\x11.flame.v1.JobSpec\"\x1f\n\rGetJobRequest\x12\x0e\n\x06job_id\x18\x01 \x01(\t\"\x10\n\x0eListJobRequest\"&\n\x07JobList\x12\x1b\n\x04jobs\x18\x01 \x03(\x0b\x32\r.flame.v1.Job\"\"\n\x10\x43\x61ncelJobRequest\x12\x0e\n\x06job_id\x18\x01 \x01(\t2\x92\x16\n\x08\x46rontend\x12O\n\x13RegisterApplication\x12$.flame.v1.RegisterApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12S\n\x15UnregisterApplication\x12&.flame.v1.UnregisterApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12K\n\x11UpdateApplication\x12\".flame.v1.UpdateApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12J\n\x0eGetApplication\x12\x1f.flame.v1.GetApplicationRequest\x1a\x15.flame.v1.Application\"\x00\x12P\n\x0fListApplication\x12 .flame.v1.ListApplicationRequest\x1a\x19.flame.v1.ApplicationList\"\x00\x12G\n\x0cListExecutor\x12\x1d.flame.v1.ListExecutorRequest\x1a\x16.flame.v1.ExecutorList\"\x00\x12=\n\tListNodes\x12\x1a.flame.v1.ListNodesRequest\x1a\x12.flame.v1.NodeList\"\x00\x12@\n\x07GetNode\x12\x18.flame.v1.GetNodeRequest\x1a\x19.flame.v1.GetNodeResponse\"\x00\x12_\n\x15GetClusterUtilization\x12&.flame.v1.GetClusterUtilizationRequest\x1a\x1c.flame.v1.ClusterUtilization\"\x00\x12\x44\n\rCreateSession\x12\x1e.flame.v1.CreateSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x44\n\rDeleteSession\x12\x1e.flame.v1.DeleteSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12@\n\x0bOpenSession\x12\x1c.flame.v1.OpenSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x42\n\x0c\x43loseSession\x12\x1d.flame.v1.CloseSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12>\n\nGetSession\x12\x1b.flame.v1.GetSessionRequest\x1a\x11.flame.v1.Session\"\x00\x12\x44\n\x0bListSession\x12\x1c.flame.v1.ListSessionRequest\x1a\x15.flame.v1.SessionList\"\x00\x12\x41\n\x0cWatchSession\x12\x1d.flame.v1.WatchSessionRequest\x1a\x0e.flame.v1.Task\"\x00\x30\x01\x12^\n\x11RenewSessionLease\x12\".flame.v1.RenewSessionLeaseRequest\x1a#.flame.v1.RenewSessionLeaseResponse\"\x00\x12S\n\x11GetSessionSummary\x12\".flame.v1.GetSessionSummaryRequest\x1a\x18.flame.v1.SessionSummary\"\x00\x12;\n\nCreateTask\x12\x1b.flame.v1.CreateTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12;\n\nDeleteTask\x12\x1b.flame.v1.DeleteTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12=\n\x0bReleaseTask\x12\x1c.flame.v1.ReleaseTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12\x35\n\x07GetTask\x12\x18.flame.v1.GetTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12;\n\tWatchTask\x12\x1a.flame.v1.WatchTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x30\x01\x12R\n\x0fWatchTaskOutput\x12 .flame.v1.WatchTaskOutputRequest\x1a\x19.flame.v1.TaskOutputChunk\"\x00\x30\x01\x12\x39\n\x08ListTask\x12\x19.flame.v1.ListTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x30\x01\x12g\n\x17ListSlotRecommendations\x12(.flame.v1.ListSlotRecommendationsRequest\x1a .flame.v1.SlotRecommendationList\"\x00\x12@\n\nListEvents\x12\x1b.flame.v1.ListEventsRequest\x1a\x13.flame.v1.EventList\"\x00\x12\x43\n\x0e\x43heckpointTask\x12\x1f.flame.v1.CheckpointTaskRequest\x1a\x0e.flame.v1.Task\"\x00\x12J\n\x0f\x43reateJoinToken\x12 .flame.v1.CreateJoinTokenRequest\x1a\x13.flame.v1.JoinToken\"\x00\x12V\n\x12GetClusterSnapshot\x12#.flame.v1.GetClusterSnapshotRequest\x1a\x19.flame.v1.ClusterSnapshot\"\x00\x12S\n\x11GetSchedulerState\x12\".flame.v1.GetSchedulerStateRequest\x1a\x18.flame.v1.SchedulerState\"\x00\x12M\n\x0fGetTaskSettings\x12 .flame.v1.GetTaskSettingsRequest\x1a\x16.flame.v1.TaskSettings\"\x00\x12K\n\x11\x45nableApplication\x12\".flame.v1.EnableApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12M\n\x12\x44isableApplication\x12#.flame.v1.DisableApplicationRequest\x1a\x10.flame.v1.Result\"\x00\x12\x38\n\tCreateJob\x12\x1a.flame.v1.CreateJobRequest\x1a\r.flame.v1.Job\"\x00\x12\x32\n\x06GetJob\x12\x17.flame.v1.GetJobRequest\x1a\r.flame.v1.Job\"\x00\x12\x38\n\x07ListJob\x12\x18.flame.v1.ListJobRequest\x1a\x11.flame.v1.JobList\"\x00\x12\x38\n\tCancelJob\x12\x1a.flame.v1.CancelJobRequest\x1a\r.flame.v1.Job\"\x00\x12H\n\x0fResubmitSession\x12 .flame.v1.ResubmitSessionRequest\x1a\x11.flame.v1.Session\"\x00\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
@@ -39,40 +39,118 @@ if not _descriptor._USE_C_DESCRIPTORS:
   _globals['_UNREGISTERAPPLICATIONREQUEST']._serialized_end=177
   _globals['_UPDATEAPPLICATIONREQUEST']._serialized_start=179
   _globals['_UPDATEAPPLICATIONREQUEST']._serialized_end=267
-  _globals['_GETAPPLICATIONREQUEST']._serialized_start=269
-  _globals['_GETAPPLICATIONREQUEST']._serialized_end=306
-  _globals['_LISTAPPLICATIONREQUEST']._serialized_start=308
-  _globals['_LISTAPPLICATIONREQUEST']._serialized_end=332
-  _globals['_LISTEXECUTORREQUEST']._serialized_start=334
-  _globals['_LISTEXECUTORREQUEST']._serialized_end=355
-  _globals['_LISTNODESREQUEST']._serialized_start=357
-  _globals['_LISTNODESREQUEST']._serialized_end=375
-  _globals['_GETNODEREQUEST']._serialized_start=377
-  _globals['_GETNODEREQUEST']._serialized_end=407
-  _globals['_GETNODERESPONSE']._serialized_start=409
-  _globals['_GETNODERESPONSE']._serialized_end=456
-  _globals['_CREATESESSIONREQUEST']._serialized_start=458
-  _globals['_CREATESESSIONREQUEST']._serialized_end=540
-  _globals['_DELETESESSIONREQUEST']._serialized_start=542
-  _globals['_DELETESESSIONREQUEST']._serialized_end=584
-  _globals['_OPENSESSIONREQUEST']._serialized_start=586
-  _globals['_OPENSESSIONREQUEST']._serialized_end=683
-  _globals['_CLOSESESSIONREQUEST']._serialized_start=685
-  _globals['_CLOSESESSIONREQUEST']._serialized_end=726
-  _globals['_GETSESSIONREQUEST']._serialized_start=728
-  _globals['_GETSESSIONREQUEST']._serialized_end=767
-  _globals['_LISTSESSIONREQUEST']._serialized_start=769
-  _globals['_LISTSESSIONREQUEST']._serialized_end=789
-  _globals['_CREATETASKREQUEST']._serialized_start=791
-  _globals['_CREATETASKREQUEST']._serialized_end=844
-  _globals['_DELETETASKREQUEST']._serialized_start=846
-  _globals['_DELETETASKREQUEST']._serialized_end=902
-  _globals['_GETTASKREQUEST']._serialized_start=904
-  _globals['_GETTASKREQUEST']._serialized_end=957
-  _globals['_WATCHTASKREQUEST']._serialized_start=959
-  _globals['_WATCHTASKREQUEST']._serialized_end=1014
-  _globals['_LISTTASKREQUEST']._serialized_start=1016
-  _globals['_LISTTASKREQUEST']._serialized_end=1053
-  _globals['_FRONTEND']._serialized_start=1056
-  _globals['_FRONTEND']._serialized_end=2374
+  _globals['_ENABLEAPPLICATIONREQUEST']._serialized_start=269
+  _globals['_ENABLEAPPLICATIONREQUEST']._serialized_end=309
+  _globals['_DISABLEAPPLICATIONREQUEST']._serialized_start=311
+  _globals['_DISABLEAPPLICATIONREQUEST']._serialized_end=352
+  _globals['_GETAPPLICATIONREQUEST']._serialized_start=354
+  _globals['_GETAPPLICATIONREQUEST']._serialized_end=391
+  _globals['_LISTAPPLICATIONREQUEST']._serialized_start=393
+  _globals['_LISTAPPLICATIONREQUEST']._serialized_end=417
+  _globals['_LISTEXECUTORREQUEST']._serialized_start=419
+  _globals['_LISTEXECUTORREQUEST']._serialized_end=440
+  _globals['_LISTNODESREQUEST']._serialized_start=442
+  _globals['_LISTNODESREQUEST']._serialized_end=460
+  _globals['_GETNODEREQUEST']._serialized_start=462
+  _globals['_GETNODEREQUEST']._serialized_end=492
+  _globals['_GETNODERESPONSE']._serialized_start=494
+  _globals['_GETNODERESPONSE']._serialized_end=541
+  _globals['_CREATEJOINTOKENREQUEST']._serialized_start=543
+  _globals['_CREATEJOINTOKENREQUEST']._serialized_end=631
+  _globals['_JOINTOKEN']._serialized_start=633
+  _globals['_JOINTOKEN']._serialized_end=722
+  _globals['_GETCLUSTERSNAPSHOTREQUEST']._serialized_start=724
+  _globals['_GETCLUSTERSNAPSHOTREQUEST']._serialized_end=775
+  _globals['_CLUSTERSNAPSHOT']._serialized_start=778
+  _globals['_CLUSTERSNAPSHOT']._serialized_end=925
+  _globals['_GETSCHEDULERSTATEREQUEST']._serialized_start=927
+  _globals['_GETSCHEDULERSTATEREQUEST']._serialized_end=953
+  _globals['_SESSIONSHARE']._serialized_start=955
+  _globals['_SESSIONSHARE']._serialized_end=1040
+  _globals['_PLUGINVERDICT']._serialized_start=1043
+  _globals['_PLUGINVERDICT']._serialized_end=1184
+  _globals['_SESSIONSCHEDULINGSTATE']._serialized_start=1187
+  _globals['_SESSIONSCHEDULINGSTATE']._serialized_end=1397
+  _globals['_SCHEDULERSTATE']._serialized_start=1399
+  _globals['_SCHEDULERSTATE']._serialized_end=1507
+  _globals['_GETCLUSTERUTILIZATIONREQUEST']._serialized_start=1509
+  _globals['_GETCLUSTERUTILIZATIONREQUEST']._serialized_end=1539
+  _globals['_NODEUTILIZATION']._serialized_start=1542
+  _globals['_NODEUTILIZATION']._serialized_end=1736
+  _globals['_EXECUTORUTILIZATION']._serialized_start=1739
+  _globals['_EXECUTORUTILIZATION']._serialized_end=1955
+  _globals['_SESSIONUTILIZATION']._serialized_start=1958
+  _globals['_SESSIONUTILIZATION']._serialized_end=2149
+  _globals['_CLUSTERUTILIZATION']._serialized_start=2152
+  _globals['_CLUSTERUTILIZATION']._serialized_end=2376
+  _globals['_CREATESESSIONREQUEST']._serialized_start=2378
+  _globals['_CREATESESSIONREQUEST']._serialized_end=2460
+  _globals['_DELETESESSIONREQUEST']._serialized_start=2462
+  _globals['_DELETESESSIONREQUEST']._serialized_end=2504
+  _globals['_OPENSESSIONREQUEST']._serialized_start=2507
+  _globals['_OPENSESSIONREQUEST']._serialized_end=2674
+  _globals['_CLOSESESSIONREQUEST']._serialized_start=2676
+  _globals['_CLOSESESSIONREQUEST']._serialized_end=2747
+  _globals['_RESUBMITSESSIONREQUEST']._serialized_start=2749
+  _globals['_RESUBMITSESSIONREQUEST']._serialized_end=2838
+  _globals['_GETSESSIONREQUEST']._serialized_start=2840
+  _globals['_GETSESSIONREQUEST']._serialized_end=2879
+  _globals['_WATCHSESSIONREQUEST']._serialized_start=2881
+  _globals['_WATCHSESSIONREQUEST']._serialized_end=2922
+  _globals['_LISTSESSIONREQUEST']._serialized_start=2924
+  _globals['_LISTSESSIONREQUEST']._serialized_end=2944
+  _globals['_RENEWSESSIONLEASEREQUEST']._serialized_start=2946
+  _globals['_RENEWSESSIONLEASEREQUEST']._serialized_end=2992
+  _globals['_RENEWSESSIONLEASERESPONSE']._serialized_start=2994
+  _globals['_RENEWSESSIONLEASERESPONSE']._serialized_end=3068
+  _globals['_GETSESSIONSUMMARYREQUEST']._serialized_start=3070
+  _globals['_GETSESSIONSUMMARYREQUEST']._serialized_end=3148
+  _globals['_TASKFAILURESUMMARY']._serialized_start=3151
+  _globals['_TASKFAILURESUMMARY']._serialized_end=3295
+  _globals['_SESSIONSUMMARY']._serialized_start=3298
+  _globals['_SESSIONSUMMARY']._serialized_end=3596
+  _globals['_LISTSLOTRECOMMENDATIONSREQUEST']._serialized_start=3598
+  _globals['_LISTSLOTRECOMMENDATIONSREQUEST']._serialized_end=3672
+  _globals['_SLOTRECOMMENDATION']._serialized_start=3675
+  _globals['_SLOTRECOMMENDATION']._serialized_end=3859
+  _globals['_SLOTRECOMMENDATIONLIST']._serialized_start=3861
+  _globals['_SLOTRECOMMENDATIONLIST']._serialized_end=3940
+  _globals['_GETTASKSETTINGSREQUEST']._serialized_start=3942
+  _globals['_GETTASKSETTINGSREQUEST']._serialized_end=4020
+  _globals['_TASKSETTINGS']._serialized_start=4023
+  _globals['_TASKSETTINGS']._serialized_end=4243
+  _globals['_LISTEVENTSREQUEST']._serialized_start=4246
+  _globals['_LISTEVENTSREQUEST']._serialized_end=4528
+  _globals['_SESSIONEVENT']._serialized_start=4530
+  _globals['_SESSIONEVENT']._serialized_end=4610
+  _globals['_EVENTLIST']._serialized_start=4612
+  _globals['_EVENTLIST']._serialized_end=4713
+  _globals['_CREATETASKREQUEST']._serialized_start=4715
+  _globals['_CREATETASKREQUEST']._serialized_end=4768
+  _globals['_DELETETASKREQUEST']._serialized_start=4770
+  _globals['_DELETETASKREQUEST']._serialized_end=4826
+  _globals['_RELEASETASKREQUEST']._serialized_start=4828
+  _globals['_RELEASETASKREQUEST']._serialized_end=4885
+  _globals['_CHECKPOINTTASKREQUEST']._serialized_start=4887
+  _globals['_CHECKPOINTTASKREQUEST']._serialized_end=4967
+  _globals['_GETTASKREQUEST']._serialized_start=4969
+  _globals['_GETTASKREQUEST']._serialized_end=5022
+  _globals['_WATCHTASKREQUEST']._serialized_start=5024
+  _globals['_WATCHTASKREQUEST']._serialized_end=5079
+  _globals['_WATCHTASKOUTPUTREQUEST']._serialized_start=5081
+  _globals['_WATCHTASKOUTPUTREQUEST']._serialized_end=5142
+  _globals['_LISTTASKREQUEST']._serialized_start=5144
+  _globals['_LISTTASKREQUEST']._serialized_end=5181
+  _globals['_CREATEJOBREQUEST']._serialized_start=5183
+  _globals['_CREATEJOBREQUEST']._serialized_end=5249
+  _globals['_GETJOBREQUEST']._serialized_start=5251
+  _globals['_GETJOBREQUEST']._serialized_end=5282
+  _globals['_LISTJOBREQUEST']._serialized_start=5284
+  _globals['_LISTJOBREQUEST']._serialized_end=5300
+  _globals['_JOBLIST']._serialized_start=5302
+  _globals['_JOBLIST']._serialized_end=5340
+  _globals['_CANCELJOBREQUEST']._serialized_start=5342
+  _globals['_CANCELJOBREQUEST']._serialized_end=5376
+  _globals['_FRONTEND']._serialized_start=5379
+  _globals['_FRONTEND']._serialized_end=8213
 # @@protoc_insertion_point(module_scope)
@@ -53,6 +53,16 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.UpdateApplicationRequest.SerializeToString,
                 response_deserializer=types__pb2.Result.FromString,
                 _registered_method=True)
+        self.EnableApplication = channel.unary_unary(
+                '/flame.v1.Frontend/EnableApplication',
+                request_serializer=frontend__pb2.EnableApplicationRequest.SerializeToString,
+                response_deserializer=types__pb2.Result.FromString,
+                _registered_method=True)
+        self.DisableApplication = channel.unary_unary(
+                '/flame.v1.Frontend/DisableApplication',
+                request_serializer=frontend__pb2.DisableApplicationRequest.SerializeToString,
+                response_deserializer=types__pb2.Result.FromString,
+                _registered_method=True)
         self.GetApplication = channel.unary_unary(
                 '/flame.v1.Frontend/GetApplication',
                 request_serializer=frontend__pb2.GetApplicationRequest.SerializeToString,
@@ -78,6 +88,26 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.GetNodeRequest.SerializeToString,
                 response_deserializer=frontend__pb2.GetNodeResponse.FromString,
                 _registered_method=True)
+        self.CreateJoinToken = channel.unary_unary(
+                '/flame.v1.Frontend/CreateJoinToken',
+                request_serializer=frontend__pb2.CreateJoinTokenRequest.SerializeToString,
+                response_deserializer=frontend__pb2.JoinToken.FromString,
+                _registered_method=True)
+        self.GetClusterSnapshot = channel.unary_unary(
+                '/flame.v1.Frontend/GetClusterSnapshot',
+                request_serializer=frontend__pb2.GetClusterSnapshotRequest.SerializeToString,
+                response_deserializer=frontend__pb2.ClusterSnapshot.FromString,
+                _registered_method=True)
+        self.GetSchedulerState = channel.unary_unary(
+                '/flame.v1.Frontend/GetSchedulerState',
+                request_serializer=frontend__pb2.GetSchedulerStateRequest.SerializeToString,
+                response_deserializer=frontend__pb2.SchedulerState.FromString,
+                _registered_method=True)
+        self.GetClusterUtilization = channel.unary_unary(
+                '/flame.v1.Frontend/GetClusterUtilization',
+                request_serializer=frontend__pb2.GetClusterUtilizationRequest.SerializeToString,
+                response_deserializer=frontend__pb2.ClusterUtilization.FromString,
+                _registered_method=True)
         self.CreateSession = channel.unary_unary(
                 '/flame.v1.Frontend/CreateSession',
                 request_serializer=frontend__pb2.CreateSessionRequest.SerializeToString,
@@ -98,6 +128,11 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.CloseSessionRequest.SerializeToString,
                 response_deserializer=types__pb2.Session.FromString,
                 _registered_method=True)
+        self.ResubmitSession = channel.unary_unary(
+                '/flame.v1.Frontend/ResubmitSession',
+                request_serializer=frontend__pb2.ResubmitSessionRequest.SerializeToString,
+                response_deserializer=types__pb2.Session.FromString,
+                _registered_method=True)
         self.GetSession = channel.unary_unary(
                 '/flame.v1.Frontend/GetSession',
                 request_serializer=frontend__pb2.GetSessionRequest.SerializeToString,
@@ -108,6 +143,36 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.ListSessionRequest.SerializeToString,
                 response_deserializer=types__pb2.SessionList.FromString,
                 _registered_method=True)
+        self.WatchSession = channel.unary_stream(
+                '/flame.v1.Frontend/WatchSession',
+                request_serializer=frontend__pb2.WatchSessionRequest.SerializeToString,
+                response_deserializer=types__pb2.Task.FromString,
+                _registered_method=True)
+        self.RenewSessionLease = channel.unary_unary(
+                '/flame.v1.Frontend/RenewSessionLease',
+                request_serializer=frontend__pb2.RenewSessionLeaseRequest.SerializeToString,
+                response_deserializer=frontend__pb2.RenewSessionLeaseResponse.FromString,
+                _registered_method=True)
+        self.GetSessionSummary = channel.unary_unary(
+                '/flame.v1.Frontend/GetSessionSummary',
+                request_serializer=frontend__pb2.GetSessionSummaryRequest.SerializeToString,
+                response_deserializer=frontend__pb2.SessionSummary.FromString,
+                _registered_method=True)
+        self.ListSlotRecommendations = channel.unary_unary(
+                '/flame.v1.Frontend/ListSlotRecommendations',
+                request_serializer=frontend__pb2.ListSlotRecommendationsRequest.SerializeToString,
+                response_deserializer=frontend__pb2.SlotRecommendationList.FromString,
+                _registered_method=True)
+        self.ListEvents = channel.unary_unary(
+                '/flame.v1.Frontend/ListEvents',
+                request_serializer=frontend__pb2.ListEventsRequest.SerializeToString,
+                response_deserializer=frontend__pb2.EventList.FromString,
+                _registered_method=True)
+        self.GetTaskSettings = channel.unary_unary(
+                '/flame.v1.Frontend/GetTaskSettings',
+                request_serializer=frontend__pb2.GetTaskSettingsRequest.SerializeToString,
+                response_deserializer=frontend__pb2.TaskSettings.FromString,
+                _registered_method=True)
         self.CreateTask = channel.unary_unary(
                 '/flame.v1.Frontend/CreateTask',
                 request_serializer=frontend__pb2.CreateTaskRequest.SerializeToString,
@@ -118,6 +183,16 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.DeleteTaskRequest.SerializeToString,
                 response_deserializer=types__pb2.Task.FromString,
                 _registered_method=True)
+        self.ReleaseTask = channel.unary_unary(
+                '/flame.v1.Frontend/ReleaseTask',
+                request_serializer=frontend__pb2.ReleaseTaskRequest.SerializeToString,
+                response_deserializer=types__pb2.Task.FromString,
+                _registered_method=True)
+        self.CheckpointTask = channel.unary_unary(
+                '/flame.v1.Frontend/CheckpointTask',
+                request_serializer=frontend__pb2.CheckpointTaskRequest.SerializeToString,
+                response_deserializer=types__pb2.Task.FromString,
+                _registered_method=True)
         self.GetTask = channel.unary_unary(
                 '/flame.v1.Frontend/GetTask',
                 request_serializer=frontend__pb2.GetTaskRequest.SerializeToString,
@@ -128,11 +203,36 @@ class FrontendStub(object):
                 request_serializer=frontend__pb2.WatchTaskRequest.SerializeToString,
                 response_deserializer=types__pb2.Task.FromString,
                 _registered_method=True)
+        self.WatchTaskOutput = channel.unary_stream(
+                '/flame.v1.Frontend/WatchTaskOutput',
+                request_serializer=frontend__pb2.WatchTaskOutputRequest.SerializeToString,
+                response_deserializer=types__pb2.TaskOutputChunk.FromString,
+                _registered_method=True)
         self.ListTask = channel.unary_stream(
                 '/flame.v1.Frontend/ListTask',
                 request_serializer=frontend__pb2.ListTaskRequest.SerializeToString,
                 response_deserializer=types__pb2.Task.FromString,
                 _registered_method=True)
+        self.CreateJob = channel.unary_unary(
+                '/flame.v1.Frontend/CreateJob',
+                request_serializer=frontend__pb2.CreateJobRequest.SerializeToString,
+                response_deserializer=types__pb2.Job.FromString,
+                _registered_method=True)
+        self.GetJob = channel.unary_unary(
+                '/flame.v1.Frontend/GetJob',
+                request_serializer=frontend__pb2.GetJobRequest.SerializeToString,
+                response_deserializer=types__pb2.Job.FromString,
+                _registered_method=True)
+        self.ListJob = channel.unary_unary(
+                '/flame.v1.Frontend/ListJob',
+                request_serializer=frontend__pb2.ListJobRequest.SerializeToString,
+                response_deserializer=frontend__pb2.JobList.FromString,
+                _registered_method=True)
+        self.CancelJob = channel.unary_unary(
+                '/flame.v1.Frontend/CancelJob',
+                request_serializer=frontend__pb2.CancelJobRequest.SerializeToString,
+                response_deserializer=types__pb2.Job.FromString,
+                _registered_method=True)
 
 
 class FrontendServicer(object):
@@ -159,6 +259,20 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def EnableApplication(self, request, context):
+        """Disable the application to reject its new sessions, while the existing
+        sessions are running to completion; enable it to accept them again.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def DisableApplication(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def GetApplication(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
@@ -190,6 +304,36 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def CreateJoinToken(self, request, context):
+        """Mint a one-time token for a node to join the cluster.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetClusterSnapshot(self, request, context):
+        """Get the state of the cluster which the scheduler persisted at a point in time.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetSchedulerState(self, request, context):
+        """Get the state of the scheduler plugins in the latest scheduling cycle, e.g.
+        to diagnose why a session is not scheduled.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetClusterUtilization(self, request, context):
+        """Get the slots of the nodes, the executors and the open sessions taken at
+        present, e.g. for `flmctl top`.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def CreateSession(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
@@ -214,6 +358,14 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def ResubmitSession(self, request, context):
+        """Create a session with the attributes of the session, and resubmit the
+        inputs of its tasks, e.g. after the bug failing them was fixed.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def GetSession(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
@@ -226,6 +378,43 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def WatchSession(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def RenewSessionLease(self, request, context):
+        """Client heartbeats of the session ownership
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetSessionSummary(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ListSlotRecommendations(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ListEvents(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetTaskSettings(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def CreateTask(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
@@ -238,6 +427,20 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def ReleaseTask(self, request, context):
+        """Release a quarantined task back to pending.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def CheckpointTask(self, request, context):
+        """Record the checkpoint of a running task, resumed by its next invocation.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def GetTask(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
@@ -250,12 +453,45 @@ class FrontendServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def WatchTaskOutput(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
     def ListTask(self, request, context):
         """Missing associated documentation comment in .proto file."""
         context.set_code(grpc.StatusCode.UNIMPLEMENTED)
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def CreateJob(self, request, context):
+        """Create a job, i.e. a session whose tasks are submitted and tracked by the
+        session manager until the job is completed.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def GetJob(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def ListJob(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def CancelJob(self, request, context):
+        """Cancel the job and its running tasks.
+        """
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
 
 def add_FrontendServicer_to_server(servicer, server):
     rpc_method_handlers = {
@@ -274,6 +510,16 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.UpdateApplicationRequest.FromString,
                     response_serializer=types__pb2.Result.SerializeToString,
             ),
+            'EnableApplication': grpc.unary_unary_rpc_method_handler(
+                    servicer.EnableApplication,
+                    request_deserializer=frontend__pb2.EnableApplicationRequest.FromString,
+                    response_serializer=types__pb2.Result.SerializeToString,
+            ),
+            'DisableApplication': grpc.unary_unary_rpc_method_handler(
+                    servicer.DisableApplication,
+                    request_deserializer=frontend__pb2.DisableApplicationRequest.FromString,
+                    response_serializer=types__pb2.Result.SerializeToString,
+            ),
             'GetApplication': grpc.unary_unary_rpc_method_handler(
                     servicer.GetApplication,
                     request_deserializer=frontend__pb2.GetApplicationRequest.FromString,
@@ -299,6 +545,26 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.GetNodeRequest.FromString,
                     response_serializer=frontend__pb2.GetNodeResponse.SerializeToString,
             ),
+            'CreateJoinToken': grpc.unary_unary_rpc_method_handler(
+                    servicer.CreateJoinToken,
+                    request_deserializer=frontend__pb2.CreateJoinTokenRequest.FromString,
+                    response_serializer=frontend__pb2.JoinToken.SerializeToString,
+            ),
+            'GetClusterSnapshot': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetClusterSnapshot,
+                    request_deserializer=frontend__pb2.GetClusterSnapshotRequest.FromString,
+                    response_serializer=frontend__pb2.ClusterSnapshot.SerializeToString,
+            ),
+            'GetSchedulerState': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetSchedulerState,
+                    request_deserializer=frontend__pb2.GetSchedulerStateRequest.FromString,
+                    response_serializer=frontend__pb2.SchedulerState.SerializeToString,
+            ),
+            'GetClusterUtilization': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetClusterUtilization,
+                    request_deserializer=frontend__pb2.GetClusterUtilizationRequest.FromString,
+                    response_serializer=frontend__pb2.ClusterUtilization.SerializeToString,
+            ),
             'CreateSession': grpc.unary_unary_rpc_method_handler(
                     servicer.CreateSession,
                     request_deserializer=frontend__pb2.CreateSessionRequest.FromString,
@@ -319,6 +585,11 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.CloseSessionRequest.FromString,
                     response_serializer=types__pb2.Session.SerializeToString,
             ),
+            'ResubmitSession': grpc.unary_unary_rpc_method_handler(
+                    servicer.ResubmitSession,
+                    request_deserializer=frontend__pb2.ResubmitSessionRequest.FromString,
+                    response_serializer=types__pb2.Session.SerializeToString,
+            ),
             'GetSession': grpc.unary_unary_rpc_method_handler(
                     servicer.GetSession,
                     request_deserializer=frontend__pb2.GetSessionRequest.FromString,
@@ -329,6 +600,36 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.ListSessionRequest.FromString,
                     response_serializer=types__pb2.SessionList.SerializeToString,
             ),
+            'WatchSession': grpc.unary_stream_rpc_method_handler(
+                    servicer.WatchSession,
+                    request_deserializer=frontend__pb2.WatchSessionRequest.FromString,
+                    response_serializer=types__pb2.Task.SerializeToString,
+            ),
+            'RenewSessionLease': grpc.unary_unary_rpc_method_handler(
+                    servicer.RenewSessionLease,
+                    request_deserializer=frontend__pb2.RenewSessionLeaseRequest.FromString,
+                    response_serializer=frontend__pb2.RenewSessionLeaseResponse.SerializeToString,
+            ),
+            'GetSessionSummary': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetSessionSummary,
+                    request_deserializer=frontend__pb2.GetSessionSummaryRequest.FromString,
+                    response_serializer=frontend__pb2.SessionSummary.SerializeToString,
+            ),
+            'ListSlotRecommendations': grpc.unary_unary_rpc_method_handler(
+                    servicer.ListSlotRecommendations,
+                    request_deserializer=frontend__pb2.ListSlotRecommendationsRequest.FromString,
+                    response_serializer=frontend__pb2.SlotRecommendationList.SerializeToString,
+            ),
+            'ListEvents': grpc.unary_unary_rpc_method_handler(
+                    servicer.ListEvents,
+                    request_deserializer=frontend__pb2.ListEventsRequest.FromString,
+                    response_serializer=frontend__pb2.EventList.SerializeToString,
+            ),
+            'GetTaskSettings': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetTaskSettings,
+                    request_deserializer=frontend__pb2.GetTaskSettingsRequest.FromString,
+                    response_serializer=frontend__pb2.TaskSettings.SerializeToString,
+            ),
             'CreateTask': grpc.unary_unary_rpc_method_handler(
                     servicer.CreateTask,
                     request_deserializer=frontend__pb2.CreateTaskRequest.FromString,
@@ -339,6 +640,16 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.DeleteTaskRequest.FromString,
                     response_serializer=types__pb2.Task.SerializeToString,
             ),
+            'ReleaseTask': grpc.unary_unary_rpc_method_handler(
+                    servicer.ReleaseTask,
+                    request_deserializer=frontend__pb2.ReleaseTaskRequest.FromString,
+                    response_serializer=types__pb2.Task.SerializeToString,
+            ),
+            'CheckpointTask': grpc.unary_unary_rpc_method_handler(
+                    servicer.CheckpointTask,
+                    request_deserializer=frontend__pb2.CheckpointTaskRequest.FromString,
+                    response_serializer=types__pb2.Task.SerializeToString,
+            ),
             'GetTask': grpc.unary_unary_rpc_method_handler(
                     servicer.GetTask,
                     request_deserializer=frontend__pb2.GetTaskRequest.FromString,
@@ -349,11 +660,36 @@ def add_FrontendServicer_to_server(servicer, server):
                     request_deserializer=frontend__pb2.WatchTaskRequest.FromString,
                     response_serializer=types__pb2.Task.SerializeToString,
             ),
+            'WatchTaskOutput': grpc.unary_stream_rpc_method_handler(
+                    servicer.WatchTaskOutput,
+                    request_deserializer=frontend__pb2.WatchTaskOutputRequest.FromString,
+                    response_serializer=types__pb2.TaskOutputChunk.SerializeToString,
+            ),
             'ListTask': grpc.unary_stream_rpc_method_handler(
                     servicer.ListTask,
                     request_deserializer=frontend__pb2.ListTaskRequest.FromString,
                     response_serializer=types__pb2.Task.SerializeToString,
             ),
+            'CreateJob': grpc.unary_unary_rpc_method_handler(
+                    servicer.CreateJob,
+                    request_deserializer=frontend__pb2.CreateJobRequest.FromString,
+                    response_serializer=types__pb2.Job.SerializeToString,
+            ),
+            'GetJob': grpc.unary_unary_rpc_method_handler(
+                    servicer.GetJob,
+                    request_deserializer=frontend__pb2.GetJobRequest.FromString,
+                    response_serializer=types__pb2.Job.SerializeToString,
+            ),
+            'ListJob': grpc.unary_unary_rpc_method_handler(
+                    servicer.ListJob,
+                    request_deserializer=frontend__pb2.ListJobRequest.FromString,
+                    response_serializer=frontend__pb2.JobList.SerializeToString,
+            ),
+            'CancelJob': grpc.unary_unary_rpc_method_handler(
+                    servicer.CancelJob,
+                    request_deserializer=frontend__pb2.CancelJobRequest.FromString,
+                    response_serializer=types__pb2.Job.SerializeToString,
+            ),
     }
     generic_handler = grpc.method_handlers_generic_handler(
             'flame.v1.Frontend', rpc_method_handlers)
@@ -450,7 +786,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def GetApplication(request,
+    def EnableApplication(request,
             target,
             options=(),
             channel_credentials=None,
@@ -463,9 +799,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/GetApplication',
-            frontend__pb2.GetApplicationRequest.SerializeToString,
-            types__pb2.Application.FromString,
+            '/flame.v1.Frontend/EnableApplication',
+            frontend__pb2.EnableApplicationRequest.SerializeToString,
+            types__pb2.Result.FromString,
             options,
             channel_credentials,
             insecure,
@@ -477,7 +813,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def ListApplication(request,
+    def DisableApplication(request,
             target,
             options=(),
             channel_credentials=None,
@@ -490,9 +826,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/ListApplication',
-            frontend__pb2.ListApplicationRequest.SerializeToString,
-            types__pb2.ApplicationList.FromString,
+            '/flame.v1.Frontend/DisableApplication',
+            frontend__pb2.DisableApplicationRequest.SerializeToString,
+            types__pb2.Result.FromString,
             options,
             channel_credentials,
             insecure,
@@ -504,7 +840,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def ListExecutor(request,
+    def GetApplication(request,
             target,
             options=(),
             channel_credentials=None,
@@ -517,9 +853,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/ListExecutor',
-            frontend__pb2.ListExecutorRequest.SerializeToString,
-            types__pb2.ExecutorList.FromString,
+            '/flame.v1.Frontend/GetApplication',
+            frontend__pb2.GetApplicationRequest.SerializeToString,
+            types__pb2.Application.FromString,
             options,
             channel_credentials,
             insecure,
@@ -531,7 +867,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def ListNodes(request,
+    def ListApplication(request,
             target,
             options=(),
             channel_credentials=None,
@@ -544,9 +880,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/ListNodes',
-            frontend__pb2.ListNodesRequest.SerializeToString,
-            types__pb2.NodeList.FromString,
+            '/flame.v1.Frontend/ListApplication',
+            frontend__pb2.ListApplicationRequest.SerializeToString,
+            types__pb2.ApplicationList.FromString,
             options,
             channel_credentials,
             insecure,
@@ -558,7 +894,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def GetNode(request,
+    def ListExecutor(request,
             target,
             options=(),
             channel_credentials=None,
@@ -571,9 +907,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/GetNode',
-            frontend__pb2.GetNodeRequest.SerializeToString,
-            frontend__pb2.GetNodeResponse.FromString,
+            '/flame.v1.Frontend/ListExecutor',
+            frontend__pb2.ListExecutorRequest.SerializeToString,
+            types__pb2.ExecutorList.FromString,
             options,
             channel_credentials,
             insecure,
@@ -585,7 +921,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def CreateSession(request,
+    def ListNodes(request,
             target,
             options=(),
             channel_credentials=None,
@@ -598,9 +934,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/CreateSession',
-            frontend__pb2.CreateSessionRequest.SerializeToString,
-            types__pb2.Session.FromString,
+            '/flame.v1.Frontend/ListNodes',
+            frontend__pb2.ListNodesRequest.SerializeToString,
+            types__pb2.NodeList.FromString,
             options,
             channel_credentials,
             insecure,
@@ -612,7 +948,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def DeleteSession(request,
+    def GetNode(request,
             target,
             options=(),
             channel_credentials=None,
@@ -625,9 +961,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/DeleteSession',
-            frontend__pb2.DeleteSessionRequest.SerializeToString,
-            types__pb2.Session.FromString,
+            '/flame.v1.Frontend/GetNode',
+            frontend__pb2.GetNodeRequest.SerializeToString,
+            frontend__pb2.GetNodeResponse.FromString,
             options,
             channel_credentials,
             insecure,
@@ -639,7 +975,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def OpenSession(request,
+    def CreateJoinToken(request,
             target,
             options=(),
             channel_credentials=None,
@@ -652,9 +988,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/OpenSession',
-            frontend__pb2.OpenSessionRequest.SerializeToString,
-            types__pb2.Session.FromString,
+            '/flame.v1.Frontend/CreateJoinToken',
+            frontend__pb2.CreateJoinTokenRequest.SerializeToString,
+            frontend__pb2.JoinToken.FromString,
             options,
             channel_credentials,
             insecure,
@@ -666,7 +1002,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def CloseSession(request,
+    def GetClusterSnapshot(request,
             target,
             options=(),
             channel_credentials=None,
@@ -679,9 +1015,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/CloseSession',
-            frontend__pb2.CloseSessionRequest.SerializeToString,
-            types__pb2.Session.FromString,
+            '/flame.v1.Frontend/GetClusterSnapshot',
+            frontend__pb2.GetClusterSnapshotRequest.SerializeToString,
+            frontend__pb2.ClusterSnapshot.FromString,
             options,
             channel_credentials,
             insecure,
@@ -693,7 +1029,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def GetSession(request,
+    def GetSchedulerState(request,
             target,
             options=(),
             channel_credentials=None,
@@ -706,9 +1042,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/GetSession',
-            frontend__pb2.GetSessionRequest.SerializeToString,
-            types__pb2.Session.FromString,
+            '/flame.v1.Frontend/GetSchedulerState',
+            frontend__pb2.GetSchedulerStateRequest.SerializeToString,
+            frontend__pb2.SchedulerState.FromString,
             options,
             channel_credentials,
             insecure,
@@ -720,7 +1056,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def ListSession(request,
+    def GetClusterUtilization(request,
             target,
             options=(),
             channel_credentials=None,
@@ -733,9 +1069,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/ListSession',
-            frontend__pb2.ListSessionRequest.SerializeToString,
-            types__pb2.SessionList.FromString,
+            '/flame.v1.Frontend/GetClusterUtilization',
+            frontend__pb2.GetClusterUtilizationRequest.SerializeToString,
+            frontend__pb2.ClusterUtilization.FromString,
             options,
             channel_credentials,
             insecure,
@@ -747,7 +1083,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def CreateTask(request,
+    def CreateSession(request,
             target,
             options=(),
             channel_credentials=None,
@@ -760,9 +1096,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/CreateTask',
-            frontend__pb2.CreateTaskRequest.SerializeToString,
-            types__pb2.Task.FromString,
+            '/flame.v1.Frontend/CreateSession',
+            frontend__pb2.CreateSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
             options,
             channel_credentials,
             insecure,
@@ -774,7 +1110,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def DeleteTask(request,
+    def DeleteSession(request,
             target,
             options=(),
             channel_credentials=None,
@@ -787,9 +1123,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/DeleteTask',
-            frontend__pb2.DeleteTaskRequest.SerializeToString,
-            types__pb2.Task.FromString,
+            '/flame.v1.Frontend/DeleteSession',
+            frontend__pb2.DeleteSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
             options,
             channel_credentials,
             insecure,
@@ -801,7 +1137,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def GetTask(request,
+    def OpenSession(request,
             target,
             options=(),
             channel_credentials=None,
@@ -814,9 +1150,9 @@ class Frontend(object):
         return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/GetTask',
-            frontend__pb2.GetTaskRequest.SerializeToString,
-            types__pb2.Task.FromString,
+            '/flame.v1.Frontend/OpenSession',
+            frontend__pb2.OpenSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
             options,
             channel_credentials,
             insecure,
@@ -828,7 +1164,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def WatchTask(request,
+    def CloseSession(request,
             target,
             options=(),
             channel_credentials=None,
@@ -838,12 +1174,12 @@ class Frontend(object):
             wait_for_ready=None,
             timeout=None,
             metadata=None):
-        return grpc.experimental.unary_stream(
+        return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/WatchTask',
-            frontend__pb2.WatchTaskRequest.SerializeToString,
-            types__pb2.Task.FromString,
+            '/flame.v1.Frontend/CloseSession',
+            frontend__pb2.CloseSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
             options,
             channel_credentials,
             insecure,
@@ -855,7 +1191,7 @@ class Frontend(object):
             _registered_method=True)
 
     @staticmethod
-    def ListTask(request,
+    def ResubmitSession(request,
             target,
             options=(),
             channel_credentials=None,
@@ -865,10 +1201,442 @@ class Frontend(object):
             wait_for_ready=None,
             timeout=None,
             metadata=None):
-        return grpc.experimental.unary_stream(
+        return grpc.experimental.unary_unary(
             request,
             target,
-            '/flame.v1.Frontend/ListTask',
+            '/flame.v1.Frontend/ResubmitSession',
+            frontend__pb2.ResubmitSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def GetSession(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/GetSession',
+            frontend__pb2.GetSessionRequest.SerializeToString,
+            types__pb2.Session.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ListSession(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/ListSession',
+            frontend__pb2.ListSessionRequest.SerializeToString,
+            types__pb2.SessionList.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def WatchSession(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(
+            request,
+            target,
+            '/flame.v1.Frontend/WatchSession',
+            frontend__pb2.WatchSessionRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def RenewSessionLease(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/RenewSessionLease',
+            frontend__pb2.RenewSessionLeaseRequest.SerializeToString,
+            frontend__pb2.RenewSessionLeaseResponse.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def GetSessionSummary(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/GetSessionSummary',
+            frontend__pb2.GetSessionSummaryRequest.SerializeToString,
+            frontend__pb2.SessionSummary.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ListSlotRecommendations(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/ListSlotRecommendations',
+            frontend__pb2.ListSlotRecommendationsRequest.SerializeToString,
+            frontend__pb2.SlotRecommendationList.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ListEvents(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/ListEvents',
+            frontend__pb2.ListEventsRequest.SerializeToString,
+            frontend__pb2.EventList.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def GetTaskSettings(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/GetTaskSettings',
+            frontend__pb2.GetTaskSettingsRequest.SerializeToString,
+            frontend__pb2.TaskSettings.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def CreateTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/CreateTask',
+            frontend__pb2.CreateTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def DeleteTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/DeleteTask',
+            frontend__pb2.DeleteTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ReleaseTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/ReleaseTask',
+            frontend__pb2.ReleaseTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def CheckpointTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/CheckpointTask',
+            frontend__pb2.CheckpointTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def GetTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/GetTask',
+            frontend__pb2.GetTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def WatchTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(
+            request,
+            target,
+            '/flame.v1.Frontend/WatchTask',
+            frontend__pb2.WatchTaskRequest.SerializeToString,
+            types__pb2.Task.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def WatchTaskOutput(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(
+            request,
+            target,
+            '/flame.v1.Frontend/WatchTaskOutput',
+            frontend__pb2.WatchTaskOutputRequest.SerializeToString,
+            types__pb2.TaskOutputChunk.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ListTask(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(
+            request,
+            target,
+            '/flame.v1.Frontend/ListTask',
             frontend__pb2.ListTaskRequest.SerializeToString,
             types__pb2.Task.FromString,
             options,
@@ -880,3 +1648,111 @@ class Frontend(object):
             timeout,
             metadata,
             _registered_method=True)
+
+    @staticmethod
+    def CreateJob(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/CreateJob',
+            frontend__pb2.CreateJobRequest.SerializeToString,
+            types__pb2.Job.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def GetJob(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/GetJob',
+            frontend__pb2.GetJobRequest.SerializeToString,
+            types__pb2.Job.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def ListJob(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/ListJob',
+            frontend__pb2.ListJobRequest.SerializeToString,
+            frontend__pb2.JobList.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def CancelJob(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Frontend/CancelJob',
+            frontend__pb2.CancelJobRequest.SerializeToString,
+            types__pb2.Job.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
@@ -25,7 +25,7 @@ _sym_db = _symbol_database.Default()
 import flamepy.proto.types_pb2 as types__pb2
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\nshim.proto\x12\x08\x66lame.v1\x1a\x0btypes.proto\"\xd0\x01\n\x12\x41pplicationContext\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\x1c\n\x04shim\x18\x02 \x01(\x0e\x32\x0e.flame.v1.Shim\x12\x12\n\x05image\x18\x03 \x01(\tH\x00\x88\x01\x01\x12\x14\n\x07\x63ommand\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x1e\n\x11working_directory\x18\x05 \x01(\tH\x02\x88\x01\x01\x12\x10\n\x03url\x18\x06 \x01(\tH\x03\x88\x01\x01\x42\x08\n\x06_imageB\n\n\x08_commandB\x14\n\x12_working_directoryB\x06\n\x04_url\"\x81\x01\n\x0eSessionContext\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x31\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x1c.flame.v1.ApplicationContext\x12\x18\n\x0b\x63ommon_data\x18\x03 \x01(\x0cH\x00\x88\x01\x01\x42\x0e\n\x0c_common_data\"P\n\x0bTaskContext\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\x12\x12\n\x05input\x18\x04 \x01(\x0cH\x00\x88\x01\x01\x42\x08\n\x06_input2\xc7\x01\n\x08Instance\x12>\n\x0eOnSessionEnter\x12\x18.flame.v1.SessionContext\x1a\x10.flame.v1.Result\"\x00\x12=\n\x0cOnTaskInvoke\x12\x15.flame.v1.TaskContext\x1a\x14.flame.v1.TaskResult\"\x00\x12<\n\x0eOnSessionLeave\x12\x16.flame.v1.EmptyRequest\x1a\x10.flame.v1.Result\"\x00\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\nshim.proto\x12\x08\x66lame.v1\x1a\x0btypes.proto\"\xd0\x01\n\x12\x41pplicationContext\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\x1c\n\x04shim\x18\x02 \x01(\x0e\x32\x0e.flame.v1.Shim\x12\x12\n\x05image\x18\x03 \x01(\tH\x00\x88\x01\x01\x12\x14\n\x07\x63ommand\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x1e\n\x11working_directory\x18\x05 \x01(\tH\x02\x88\x01\x01\x12\x10\n\x03url\x18\x06 \x01(\tH\x03\x88\x01\x01\x42\x08\n\x06_imageB\n\n\x08_commandB\x14\n\x12_working_directoryB\x06\n\x04_url\"\xd7\x01\n\x0eSessionContext\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x31\n\x0b\x61pplication\x18\x02 \x01(\x0b\x32\x1c.flame.v1.ApplicationContext\x12\x18\n\x0b\x63ommon_data\x18\x03 \x01(\x0cH\x00\x88\x01\x01\x12\x18\n\x0bscratch_dir\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x19\n\x0cscratch_size\x18\x05 \x01(\x04H\x02\x88\x01\x01\x42\x0e\n\x0c_common_dataB\x0e\n\x0c_scratch_dirB\x0f\n\r_scratch_size\"\x9d\x02\n\x0bTaskContext\x12\x0f\n\x07task_id\x18\x01 \x01(\t\x12\x12\n\nsession_id\x18\x02 \x01(\t\x12\x12\n\x05input\x18\x04 \x01(\x0cH\x00\x88\x01\x01\x12+\n\x0c\x65nvironments\x18\x05 \x03(\x0b\x32\x15.flame.v1.Environment\x12\x11\n\targuments\x18\x06 \x03(\t\x12\x17\n\ncheckpoint\x18\x07 \x01(\tH\x01\x88\x01\x01\x12\x31\n\tinput_ref\x18\x08 \x01(\x0b\x32\x19.flame.v1.ObjectReferenceH\x02\x88\x01\x01\x12\x15\n\x08\x64\x65\x61\x64line\x18\t \x01(\x04H\x03\x88\x01\x01\x42\x08\n\x06_inputB\r\n\x0b_checkpointB\x0c\n\n_input_refB\x0b\n\t_deadline\"\\\n\x0ePreemptContext\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x14\n\x07task_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x14\n\x0cgrace_period\x18\x03 \x01(\x04\x42\n\n\x08_task_id2\xcc\x02\n\x08Instance\x12>\n\x0eOnSessionEnter\x12\x18.flame.v1.SessionContext\x1a\x10.flame.v1.Result\"\x00\x12=\n\x0cOnTaskInvoke\x12\x15.flame.v1.TaskContext\x1a\x14.flame.v1.TaskResult\"\x00\x12<\n\x0eOnSessionLeave\x12\x16.flame.v1.EmptyRequest\x1a\x10.flame.v1.Result\"\x00\x12\x39\n\tOnPreempt\x12\x18.flame.v1.PreemptContext\x1a\x10.flame.v1.Result\"\x00\x12H\n\x0fWatchTaskOutput\x12\x16.flame.v1.EmptyRequest\x1a\x19.flame.v1.TaskOutputChunk\"\x00\x30\x01\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
@@ -36,9 +36,11 @@ if not _descriptor._USE_C_DESCRIPTORS:
   _globals['_APPLICATIONCONTEXT']._serialized_start=38
   _globals['_APPLICATIONCONTEXT']._serialized_end=246
   _globals['_SESSIONCONTEXT']._serialized_start=249
-  _globals['_SESSIONCONTEXT']._serialized_end=378
-  _globals['_TASKCONTEXT']._serialized_start=380
-  _globals['_TASKCONTEXT']._serialized_end=460
-  _globals['_INSTANCE']._serialized_start=463
-  _globals['_INSTANCE']._serialized_end=662
+  _globals['_SESSIONCONTEXT']._serialized_end=464
+  _globals['_TASKCONTEXT']._serialized_start=467
+  _globals['_TASKCONTEXT']._serialized_end=752
+  _globals['_PREEMPTCONTEXT']._serialized_start=754
+  _globals['_PREEMPTCONTEXT']._serialized_end=846
+  _globals['_INSTANCE']._serialized_start=849
+  _globals['_INSTANCE']._serialized_end=1181
 # @@protoc_insertion_point(module_scope)
@@ -50,6 +50,16 @@ class InstanceStub(object):
                 request_serializer=types__pb2.EmptyRequest.SerializeToString,
                 response_deserializer=types__pb2.Result.FromString,
                 _registered_method=True)
+        self.OnPreempt = channel.unary_unary(
+                '/flame.v1.Instance/OnPreempt',
+                request_serializer=shim__pb2.PreemptContext.SerializeToString,
+                response_deserializer=types__pb2.Result.FromString,
+                _registered_method=True)
+        self.WatchTaskOutput = channel.unary_stream(
+                '/flame.v1.Instance/WatchTaskOutput',
+                request_serializer=types__pb2.EmptyRequest.SerializeToString,
+                response_deserializer=types__pb2.TaskOutputChunk.FromString,
+                _registered_method=True)
 
 
 class InstanceServicer(object):
@@ -73,6 +83,18 @@ class InstanceServicer(object):
         context.set_details('Method not implemented!')
         raise NotImplementedError('Method not implemented!')
 
+    def OnPreempt(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
+    def WatchTaskOutput(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details('Method not implemented!')
+        raise NotImplementedError('Method not implemented!')
+
 
 def add_InstanceServicer_to_server(servicer, server):
     rpc_method_handlers = {
@@ -91,6 +113,16 @@ def add_InstanceServicer_to_server(servicer, server):
                     request_deserializer=types__pb2.EmptyRequest.FromString,
                     response_serializer=types__pb2.Result.SerializeToString,
             ),
+            'OnPreempt': grpc.unary_unary_rpc_method_handler(
+                    servicer.OnPreempt,
+                    request_deserializer=shim__pb2.PreemptContext.FromString,
+                    response_serializer=types__pb2.Result.SerializeToString,
+            ),
+            'WatchTaskOutput': grpc.unary_stream_rpc_method_handler(
+                    servicer.WatchTaskOutput,
+                    request_deserializer=types__pb2.EmptyRequest.FromString,
+                    response_serializer=types__pb2.TaskOutputChunk.SerializeToString,
+            ),
     }
     generic_handler = grpc.method_handlers_generic_handler(
             'flame.v1.Instance', rpc_method_handlers)
@@ -182,3 +214,57 @@ class Instance(object):
             timeout,
             metadata,
             _registered_method=True)
+
+    @staticmethod
+    def OnPreempt(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            '/flame.v1.Instance/OnPreempt',
+            shim__pb2.PreemptContext.SerializeToString,
+            types__pb2.Result.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
+
+    @staticmethod
+    def WatchTaskOutput(request,
+            target,
+            options=(),
+            channel_credentials=None,
+            call_credentials=None,
+            insecure=False,
+            compression=None,
+            wait_for_ready=None,
+            timeout=None,
+            metadata=None):
+        return grpc.experimental.unary_stream(
+            request,
+            target,
+            '/flame.v1.Instance/WatchTaskOutput',
+            types__pb2.EmptyRequest.SerializeToString,
+            types__pb2.TaskOutputChunk.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+            _registered_method=True)
@@ -24,7 +24,7 @@ _sym_db = _symbol_database.Default()
 
 
 
-DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x0btypes.proto\x12\x08\x66lame.v1\"$\n\x08Metadata\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\"\xf6\x01\n\rSessionStatus\x12%\n\x05state\x18\x01 \x01(\x0e\x32\x16.flame.v1.SessionState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\x12\x1c\n\x0f\x63ompletion_time\x18\x03 \x01(\x03H\x00\x88\x01\x01\x12\x0f\n\x07pending\x18\x04 \x01(\x05\x12\x0f\n\x07running\x18\x05 \x01(\x05\x12\x0f\n\x07succeed\x18\x06 \x01(\x05\x12\x0e\n\x06\x66\x61iled\x18\x07 \x01(\x05\x12\x11\n\tcancelled\x18\t \x01(\x05\x12\x1f\n\x06\x65vents\x18\x08 \x03(\x0b\x32\x0f.flame.v1.EventB\x12\n\x10_completion_time\"\xb4\x01\n\x0bSessionSpec\x12\x13\n\x0b\x61pplication\x18\x02 \x01(\t\x12\r\n\x05slots\x18\x03 \x01(\r\x12\x18\n\x0b\x63ommon_data\x18\x04 \x01(\x0cH\x00\x88\x01\x01\x12\x15\n\rmin_instances\x18\x05 \x01(\r\x12\x1a\n\rmax_instances\x18\x06 \x01(\rH\x01\x88\x01\x01\x12\x12\n\nbatch_size\x18\x07 \x01(\rB\x0e\n\x0c_common_dataB\x10\n\x0e_max_instances\"}\n\x07Session\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12#\n\x04spec\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpec\x12\'\n\x06status\x18\x03 \x01(\x0b\x32\x17.flame.v1.SessionStatus\"\x9a\x01\n\nTaskStatus\x12\"\n\x05state\x18\x01 \x01(\x0e\x32\x13.flame.v1.TaskState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\x12\x1c\n\x0f\x63ompletion_time\x18\x03 \x01(\x03H\x00\x88\x01\x01\x12\x1f\n\x06\x65vents\x18\x04 \x03(\x0b\x32\x0f.flame.v1.EventB\x12\n\x10_completion_time\"\\\n\x08TaskSpec\x12\x12\n\nsession_id\x18\x02 \x01(\t\x12\x12\n\x05input\x18\x03 \x01(\x0cH\x00\x88\x01\x01\x12\x13\n\x06output\x18\x04 \x01(\x0cH\x01\x88\x01\x01\x42\x08\n\x06_inputB\t\n\x07_output\"t\n\x04Task\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12 \n\x04spec\x18\x02 \x01(\x0b\x32\x12.flame.v1.TaskSpec\x12$\n\x06status\x18\x03 \x01(\x0b\x32\x14.flame.v1.TaskStatus\"U\n\x11\x41pplicationStatus\x12)\n\x05state\x18\x01 \x01(\x0e\x32\x1a.flame.v1.ApplicationState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\"*\n\x0b\x45nvironment\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\r\n\x05value\x18\x02 \x01(\t\"{\n\x11\x41pplicationSchema\x12\x12\n\x05input\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x13\n\x06output\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x18\n\x0b\x63ommon_data\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\x08\n\x06_inputB\t\n\x07_outputB\x0e\n\x0c_common_data\"\xd2\x03\n\x0f\x41pplicationSpec\x12\x1c\n\x04shim\x18\x01 \x01(\x0e\x32\x0e.flame.v1.Shim\x12\x18\n\x0b\x64\x65scription\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x0e\n\x06labels\x18\x03 \x03(\t\x12\x12\n\x05image\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x14\n\x07\x63ommand\x18\x05 \x01(\tH\x02\x88\x01\x01\x12\x11\n\targuments\x18\x06 \x03(\t\x12+\n\x0c\x65nvironments\x18\x07 \x03(\x0b\x32\x15.flame.v1.Environment\x12\x1e\n\x11working_directory\x18\x08 \x01(\tH\x03\x88\x01\x01\x12\x1a\n\rmax_instances\x18\t \x01(\rH\x04\x88\x01\x01\x12\x1a\n\rdelay_release\x18\n \x01(\x03H\x05\x88\x01\x01\x12\x30\n\x06schema\x18\x0b \x01(\x0b\x32\x1b.flame.v1.ApplicationSchemaH\x06\x88\x01\x01\x12\x10\n\x03url\x18\x0c \x01(\tH\x07\x88\x01\x01\x42\x0e\n\x0c_descriptionB\x08\n\x06_imageB\n\n\x08_commandB\x14\n\x12_working_directoryB\x10\n\x0e_max_instancesB\x10\n\x0e_delay_releaseB\t\n\x07_schemaB\x06\n\x04_url\"\x89\x01\n\x0b\x41pplication\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12\'\n\x04spec\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\x12+\n\x06status\x18\x03 \x01(\x0b\x32\x1b.flame.v1.ApplicationStatus\"x\n\x0c\x45xecutorSpec\x12\x0c\n\x04node\x18\x01 \x01(\t\x12-\n\x06resreq\x18\x02 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12\r\n\x05slots\x18\x03 \x01(\r\x12\x1c\n\x04shim\x18\x04 \x01(\x0e\x32\x0e.flame.v1.Shim\"`\n\x0e\x45xecutorStatus\x12&\n\x05state\x18\x01 \x01(\x0e\x32\x17.flame.v1.ExecutorState\x12\x17\n\nsession_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\r\n\x0b_session_id\"\x80\x01\n\x08\x45xecutor\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12$\n\x04spec\x18\x02 \x01(\x0b\x32\x16.flame.v1.ExecutorSpec\x12(\n\x06status\x18\x03 \x01(\x0b\x32\x18.flame.v1.ExecutorStatus\"5\n\x0c\x45xecutorList\x12%\n\texecutors\x18\x01 \x03(\x0b\x32\x12.flame.v1.Executor\"2\n\x0bSessionList\x12#\n\x08sessions\x18\x01 \x03(\x0b\x32\x11.flame.v1.Session\">\n\x0f\x41pplicationList\x12+\n\x0c\x61pplications\x18\x01 \x03(\x0b\x32\x15.flame.v1.Application\"?\n\x13ResourceRequirement\x12\x0b\n\x03\x63pu\x18\x01 \x01(\x04\x12\x0e\n\x06memory\x18\x02 \x01(\x04\x12\x0b\n\x03gpu\x18\x03 \x01(\x05\"\x1c\n\x08NodeSpec\x12\x10\n\x08hostname\x18\x01 \x01(\t\"$\n\x08NodeInfo\x12\x0c\n\x04\x61rch\x18\x01 \x01(\t\x12\n\n\x02os\x18\x02 \x01(\t\",\n\x0bNodeAddress\x12\x0c\n\x04type\x18\x01 \x01(\t\x12\x0f\n\x07\x61\x64\x64ress\x18\x02 \x01(\t\"\xfe\x01\n\nNodeStatus\x12\"\n\x05state\x18\x01 \x01(\x0e\x32\x13.flame.v1.NodeState\x12/\n\x08\x63\x61pacity\x18\x02 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12\x32\n\x0b\x61llocatable\x18\x03 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12 \n\x04info\x18\x04 \x01(\x0b\x32\x12.flame.v1.NodeInfo\x12(\n\taddresses\x18\x05 \x03(\x0b\x32\x15.flame.v1.NodeAddress\x12\x1b\n\x13last_heartbeat_time\x18\x06 \x01(\x03\"t\n\x04Node\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12 \n\x04spec\x18\x02 \x01(\x0b\x32\x12.flame.v1.NodeSpec\x12$\n\x06status\x18\x03 \x01(\x0b\x32\x14.flame.v1.NodeStatus\")\n\x08NodeList\x12\x1d\n\x05nodes\x18\x01 \x03(\x0b\x32\x0e.flame.v1.Node\"?\n\x06Result\x12\x13\n\x0breturn_code\x18\x01 \x01(\x05\x12\x14\n\x07message\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\n\n\x08_message\"c\n\nTaskResult\x12\x13\n\x0breturn_code\x18\x01 \x01(\x05\x12\x13\n\x06output\x18\x02 \x01(\x0cH\x00\x88\x01\x01\x12\x14\n\x07message\x18\x03 \x01(\tH\x01\x88\x01\x01\x42\t\n\x07_outputB\n\n\x08_message\"\x0e\n\x0c\x45mptyRequest\"N\n\x05\x45vent\x12\x0c\n\x04\x63ode\x18\x01 \x01(\x05\x12\x14\n\x07message\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x15\n\rcreation_time\x18\x03 \x01(\x03\x42\n\n\x08_message*$\n\x0cSessionState\x12\x08\n\x04Open\x10\x00\x12\n\n\x06\x43losed\x10\x01*M\n\tTaskState\x12\x0b\n\x07Pending\x10\x00\x12\x0b\n\x07Running\x10\x01\x12\x0b\n\x07Succeed\x10\x02\x12\n\n\x06\x46\x61iled\x10\x03\x12\r\n\tCancelled\x10\x04*\x1a\n\x04Shim\x12\x08\n\x04Host\x10\x00\x12\x08\n\x04Wasm\x10\x01*-\n\x10\x41pplicationState\x12\x0b\n\x07\x45nabled\x10\x00\x12\x0c\n\x08\x44isabled\x10\x01*\xb4\x01\n\rExecutorState\x12\x13\n\x0f\x45xecutorUnknown\x10\x00\x12\x10\n\x0c\x45xecutorVoid\x10\x01\x12\x10\n\x0c\x45xecutorIdle\x10\x02\x12\x13\n\x0f\x45xecutorBinding\x10\x03\x12\x11\n\rExecutorBound\x10\x04\x12\x15\n\x11\x45xecutorUnbinding\x10\x05\x12\x15\n\x11\x45xecutorReleasing\x10\x06\x12\x14\n\x10\x45xecutorReleased\x10\x07*1\n\tNodeState\x12\x0b\n\x07Unknown\x10\x00\x12\t\n\x05Ready\x10\x01\x12\x0c\n\x08NotReady\x10\x02\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
+DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(b'\n\x0btypes.proto\x12\x08\x66lame.v1\"$\n\x08Metadata\x12\n\n\x02id\x18\x01 \x01(\t\x12\x0c\n\x04name\x18\x02 \x01(\t\"\xaa\x03\n\rSessionStatus\x12%\n\x05state\x18\x01 \x01(\x0e\x32\x16.flame.v1.SessionState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\x12\x1c\n\x0f\x63ompletion_time\x18\x03 \x01(\x03H\x00\x88\x01\x01\x12\x0f\n\x07pending\x18\x04 \x01(\x05\x12\x0f\n\x07running\x18\x05 \x01(\x05\x12\x0f\n\x07succeed\x18\x06 \x01(\x05\x12\x0e\n\x06\x66\x61iled\x18\x07 \x01(\x05\x12\x11\n\tcancelled\x18\t \x01(\x05\x12\x13\n\x0bquarantined\x18\x0b \x01(\x05\x12\x1f\n\x06\x65vents\x18\x08 \x03(\x0b\x32\x0f.flame.v1.Event\x12\x38\n\x0etask_durations\x18\n \x01(\x0b\x32\x1b.flame.v1.TaskDurationStatsH\x01\x88\x01\x01\x12\x1a\n\runschedulable\x18\x0c \x01(\tH\x02\x88\x01\x01\x12\x15\n\x08trace_id\x18\r \x01(\tH\x03\x88\x01\x01\x42\x12\n\x10_completion_timeB\x11\n\x0f_task_durationsB\x10\n\x0e_unschedulableB\x0b\n\t_trace_id\"Z\n\x11TaskDurationStats\x12\r\n\x05\x63ount\x18\x01 \x01(\x04\x12\x0c\n\x04mean\x18\x02 \x01(\x01\x12\x0b\n\x03min\x18\x03 \x01(\x04\x12\x0b\n\x03max\x18\x04 \x01(\x04\x12\x0e\n\x06recent\x18\x05 \x01(\x01\"\xed\x03\n\x0bSessionSpec\x12\x13\n\x0b\x61pplication\x18\x02 \x01(\t\x12\r\n\x05slots\x18\x03 \x01(\r\x12\x18\n\x0b\x63ommon_data\x18\x04 \x01(\x0cH\x00\x88\x01\x01\x12\x15\n\rmin_instances\x18\x05 \x01(\r\x12\x1a\n\rmax_instances\x18\x06 \x01(\rH\x01\x88\x01\x01\x12\x12\n\nbatch_size\x18\x07 \x01(\r\x12\x19\n\x0cscratch_size\x18\x08 \x01(\x04H\x02\x88\x01\x01\x12\x1e\n\x11max_task_attempts\x18\t \x01(\rH\x03\x88\x01\x01\x12\x19\n\x0ctask_timeout\x18\n \x01(\x04H\x04\x88\x01\x01\x12\x1a\n\rtask_priority\x18\x0b \x01(\rH\x05\x88\x01\x01\x12\x10\n\x08priority\x18\x0c \x01(\r\x12-\n\rresult_policy\x18\r \x01(\x0e\x32\x16.flame.v1.ResultPolicy\x12!\n\x14ttl_after_completion\x18\x0e \x01(\x04H\x06\x88\x01\x01\x42\x0e\n\x0c_common_dataB\x10\n\x0e_max_instancesB\x0f\n\r_scratch_sizeB\x14\n\x12_max_task_attemptsB\x0f\n\r_task_timeoutB\x10\n\x0e_task_priorityB\x17\n\x15_ttl_after_completion\"}\n\x07Session\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12#\n\x04spec\x18\x02 \x01(\x0b\x32\x15.flame.v1.SessionSpec\x12\'\n\x06status\x18\x03 \x01(\x0b\x32\x17.flame.v1.SessionStatus\"\xb6\x03\n\nTaskStatus\x12\"\n\x05state\x18\x01 \x01(\x0e\x32\x13.flame.v1.TaskState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\x12\x1c\n\x0f\x63ompletion_time\x18\x03 \x01(\x03H\x00\x88\x01\x01\x12\x1f\n\x06\x65vents\x18\x04 \x03(\x0b\x32\x0f.flame.v1.Event\x12\x34\n\x0e\x66\x61ilure_reason\x18\x05 \x01(\x0e\x32\x17.flame.v1.FailureReasonH\x01\x88\x01\x01\x12\x10\n\x08\x61ttempts\x18\x06 \x01(\r\x12\'\n\x05usage\x18\x07 \x01(\x0b\x32\x13.flame.v1.TaskUsageH\x02\x88\x01\x01\x12\x17\n\ncheckpoint\x18\x08 \x01(\tH\x03\x88\x01\x01\x12\x31\n\npostmortem\x18\t \x01(\x0b\x32\x18.flame.v1.TaskPostmortemH\x04\x88\x01\x01\x12\x15\n\x08trace_id\x18\n \x01(\tH\x05\x88\x01\x01\x42\x12\n\x10_completion_timeB\x11\n\x0f_failure_reasonB\x08\n\x06_usageB\r\n\x0b_checkpointB\r\n\x0b_postmortemB\x0b\n\t_trace_id\"\x97\x04\n\x08TaskSpec\x12\x12\n\nsession_id\x18\x02 \x01(\t\x12\x12\n\x05input\x18\x03 \x01(\x0cH\x00\x88\x01\x01\x12\x13\n\x06output\x18\x04 \x01(\x0cH\x01\x88\x01\x01\x12+\n\x0c\x65nvironments\x18\x05 \x03(\x0b\x32\x15.flame.v1.Environment\x12\x11\n\targuments\x18\x06 \x03(\t\x12)\n\tartifacts\x18\x07 \x03(\x0b\x32\x16.flame.v1.TaskArtifact\x12\x31\n\tinput_ref\x18\x08 \x01(\x0b\x32\x19.flame.v1.ObjectReferenceH\x02\x88\x01\x01\x12\x14\n\x07timeout\x18\t \x01(\x04H\x03\x88\x01\x01\x12\x19\n\x0cmax_attempts\x18\n \x01(\rH\x04\x88\x01\x01\x12\x15\n\x08priority\x18\x0b \x01(\rH\x05\x88\x01\x01\x12\x15\n\x08\x64\x65\x61\x64line\x18\x0c \x01(\x04H\x06\x88\x01\x01\x12\x32\n\noutput_ref\x18\r \x01(\x0b\x32\x19.flame.v1.ObjectReferenceH\x07\x88\x01\x01\x12*\n\x05\x63odec\x18\x0e \x01(\x0e\x32\x16.flame.v1.PayloadCodecH\x08\x88\x01\x01\x42\x08\n\x06_inputB\t\n\x07_outputB\x0c\n\n_input_refB\n\n\x08_timeoutB\x0f\n\r_max_attemptsB\x0b\n\t_priorityB\x0b\n\t_deadlineB\r\n\x0b_output_refB\x08\n\x06_codec\"`\n\x0cTaskArtifact\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\x11\n\x04\x64\x61ta\x18\x02 \x01(\x0cH\x00\x88\x01\x01\x12\x17\n\nobject_ref\x18\x03 \x01(\tH\x01\x88\x01\x01\x42\x07\n\x05_dataB\r\n\x0b_object_ref\"T\n\x0fObjectReference\x12\x10\n\x08\x65ndpoint\x18\x01 \x01(\t\x12\x0b\n\x03key\x18\x02 \x01(\t\x12\x15\n\x08\x63hecksum\x18\x03 \x01(\tH\x00\x88\x01\x01\x42\x0b\n\t_checksum\"t\n\x04Task\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12 \n\x04spec\x18\x02 \x01(\x0b\x32\x12.flame.v1.TaskSpec\x12$\n\x06status\x18\x03 \x01(\x0b\x32\x14.flame.v1.TaskStatus\"\xfa\x01\n\x07JobSpec\x12\x13\n\x0b\x61pplication\x18\x01 \x01(\t\x12\r\n\x05slots\x18\x02 \x01(\r\x12\x0e\n\x06inputs\x18\x03 \x03(\x0c\x12\x12\n\x05\x63ount\x18\x04 \x01(\rH\x00\x88\x01\x01\x12\x18\n\x0bparallelism\x18\x05 \x01(\rH\x01\x88\x01\x01\x12\x35\n\x11\x63ompletion_policy\x18\x06 \x01(\x0e\x32\x1a.flame.v1.CompletionPolicy\x12\x12\n\nmax_failed\x18\x07 \x01(\r\x12\x18\n\x0b\x63ommon_data\x18\x08 \x01(\x0cH\x02\x88\x01\x01\x42\x08\n\x06_countB\x0e\n\x0c_parallelismB\x0e\n\x0c_common_data\"\xf0\x01\n\tJobStatus\x12!\n\x05state\x18\x01 \x01(\x0e\x32\x12.flame.v1.JobState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\x12\x1c\n\x0f\x63ompletion_time\x18\x03 \x01(\x03H\x00\x88\x01\x01\x12\x12\n\nsession_id\x18\x04 \x01(\t\x12\r\n\x05total\x18\x05 \x01(\r\x12\x11\n\tsubmitted\x18\x06 \x01(\r\x12\x0f\n\x07succeed\x18\x07 \x01(\r\x12\x0e\n\x06\x66\x61iled\x18\x08 \x01(\r\x12\x14\n\x07message\x18\t \x01(\tH\x01\x88\x01\x01\x42\x12\n\x10_completion_timeB\n\n\x08_message\"q\n\x03Job\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12\x1f\n\x04spec\x18\x02 \x01(\x0b\x32\x11.flame.v1.JobSpec\x12#\n\x06status\x18\x03 \x01(\x0b\x32\x13.flame.v1.JobStatus\"U\n\x11\x41pplicationStatus\x12)\n\x05state\x18\x01 \x01(\x0e\x32\x1a.flame.v1.ApplicationState\x12\x15\n\rcreation_time\x18\x02 \x01(\x03\"*\n\x0b\x45nvironment\x12\x0c\n\x04name\x18\x01 \x01(\t\x12\r\n\x05value\x18\x02 \x01(\t\"{\n\x11\x41pplicationSchema\x12\x12\n\x05input\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x13\n\x06output\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x18\n\x0b\x63ommon_data\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\x08\n\x06_inputB\t\n\x07_outputB\x0e\n\x0c_common_data\"\xa9\x08\n\x0f\x41pplicationSpec\x12\x1c\n\x04shim\x18\x01 \x01(\x0e\x32\x0e.flame.v1.Shim\x12\x18\n\x0b\x64\x65scription\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x0e\n\x06labels\x18\x03 \x03(\t\x12\x12\n\x05image\x18\x04 \x01(\tH\x01\x88\x01\x01\x12\x14\n\x07\x63ommand\x18\x05 \x01(\tH\x02\x88\x01\x01\x12\x11\n\targuments\x18\x06 \x03(\t\x12+\n\x0c\x65nvironments\x18\x07 \x03(\x0b\x32\x15.flame.v1.Environment\x12\x1e\n\x11working_directory\x18\x08 \x01(\tH\x03\x88\x01\x01\x12\x1a\n\rmax_instances\x18\t \x01(\rH\x04\x88\x01\x01\x12\x1a\n\rdelay_release\x18\n \x01(\x03H\x05\x88\x01\x01\x12\x30\n\x06schema\x18\x0b \x01(\x0b\x32\x1b.flame.v1.ApplicationSchemaH\x06\x88\x01\x01\x12\x10\n\x03url\x18\x0c \x01(\tH\x07\x88\x01\x01\x12\x19\n\x11task_environments\x18\r \x03(\t\x12\x16\n\x0etask_arguments\x18\x0e \x03(\t\x12\x18\n\x0b\x62undle_size\x18\x0f \x01(\rH\x08\x88\x01\x01\x12#\n\x16max_instances_per_node\x18\x10 \x01(\rH\t\x88\x01\x01\x12\x38\n\x10session_defaults\x18\x11 \x01(\x0b\x32\x19.flame.v1.SessionDefaultsH\n\x88\x01\x01\x12\x30\n\x0chealth_probe\x18\x12 \x01(\x0b\x32\x15.flame.v1.HealthProbeH\x0b\x88\x01\x01\x12*\n\x08\x66\x61irness\x18\x13 \x01(\x0e\x32\x18.flame.v1.FairnessPolicy\x12,\n\x05hooks\x18\x14 \x01(\x0b\x32\x18.flame.v1.LifecycleHooksH\x0c\x88\x01\x01\x12\x34\n\npython_env\x18\x15 \x01(\x0b\x32\x1b.flame.v1.PythonEnvironmentH\r\x88\x01\x01\x12%\n\x05\x63odec\x18\x16 \x01(\x0e\x32\x16.flame.v1.PayloadCodec\x12\x19\n\x0cpayload_type\x18\x17 \x01(\tH\x0e\x88\x01\x01\x12*\n\x08topology\x18\x18 \x01(\x0e\x32\x18.flame.v1.TopologyPolicyB\x0e\n\x0c_descriptionB\x08\n\x06_imageB\n\n\x08_commandB\x14\n\x12_working_directoryB\x10\n\x0e_max_instancesB\x10\n\x0e_delay_releaseB\t\n\x07_schemaB\x06\n\x04_urlB\x0e\n\x0c_bundle_sizeB\x19\n\x17_max_instances_per_nodeB\x13\n\x11_session_defaultsB\x0f\n\r_health_probeB\x08\n\x06_hooksB\r\n\x0b_python_envB\x0f\n\r_payload_type\"\xef\x02\n\x0fSessionDefaults\x12\x12\n\x05slots\x18\x01 \x01(\rH\x00\x88\x01\x01\x12\x1a\n\rmin_instances\x18\x02 \x01(\rH\x01\x88\x01\x01\x12\x1a\n\rmax_instances\x18\x03 \x01(\rH\x02\x88\x01\x01\x12\x17\n\nbatch_size\x18\x04 \x01(\rH\x03\x88\x01\x01\x12\x19\n\x0cscratch_size\x18\x05 \x01(\x04H\x04\x88\x01\x01\x12\x1e\n\x11max_task_attempts\x18\x06 \x01(\rH\x05\x88\x01\x01\x12\x19\n\x0ctask_timeout\x18\x07 \x01(\x04H\x06\x88\x01\x01\x12\x1a\n\rtask_priority\x18\x08 \x01(\rH\x07\x88\x01\x01\x42\x08\n\x06_slotsB\x10\n\x0e_min_instancesB\x10\n\x0e_max_instancesB\r\n\x0b_batch_sizeB\x0f\n\r_scratch_sizeB\x14\n\x12_max_task_attemptsB\x0f\n\r_task_timeoutB\x10\n\x0e_task_priority\"\xf3\x01\n\x0bHealthProbe\x12\x14\n\x07\x63ommand\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x19\n\x0cgrpc_service\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x1b\n\x0eperiod_seconds\x18\x03 \x01(\rH\x02\x88\x01\x01\x12\x1c\n\x0ftimeout_seconds\x18\x04 \x01(\rH\x03\x88\x01\x01\x12\x1e\n\x11\x66\x61ilure_threshold\x18\x05 \x01(\rH\x04\x88\x01\x01\x42\n\n\x08_commandB\x0f\n\r_grpc_serviceB\x11\n\x0f_period_secondsB\x12\n\x10_timeout_secondsB\x14\n\x12_failure_threshold\"\xcf\x01\n\x0eLifecycleHooks\x12.\n\x08pre_bind\x18\x01 \x01(\x0b\x32\x17.flame.v1.LifecycleHookH\x00\x88\x01\x01\x12/\n\tpost_bind\x18\x02 \x01(\x0b\x32\x17.flame.v1.LifecycleHookH\x01\x88\x01\x01\x12\x31\n\x0bpre_release\x18\x03 \x01(\x0b\x32\x17.flame.v1.LifecycleHookH\x02\x88\x01\x01\x42\x0b\n\t_pre_bindB\x0c\n\n_post_bindB\x0e\n\x0c_pre_release\"R\n\rLifecycleHook\x12\x0f\n\x07\x63ommand\x18\x01 \x01(\t\x12\x1c\n\x0ftimeout_seconds\x18\x02 \x01(\rH\x00\x88\x01\x01\x42\x12\n\x10_timeout_seconds\"\x91\x01\n\x11PythonEnvironment\x12\x14\n\x07project\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x19\n\x0crequirements\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x1b\n\x0epython_version\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\n\n\x08_projectB\x0f\n\r_requirementsB\x11\n\x0f_python_version\"\x89\x01\n\x0b\x41pplication\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12\'\n\x04spec\x18\x02 \x01(\x0b\x32\x19.flame.v1.ApplicationSpec\x12+\n\x06status\x18\x03 \x01(\x0b\x32\x1b.flame.v1.ApplicationStatus\"x\n\x0c\x45xecutorSpec\x12\x0c\n\x04node\x18\x01 \x01(\t\x12-\n\x06resreq\x18\x02 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12\r\n\x05slots\x18\x03 \x01(\r\x12\x1c\n\x04shim\x18\x04 \x01(\x0e\x32\x0e.flame.v1.Shim\"\xb1\x01\n\x0e\x45xecutorStatus\x12&\n\x05state\x18\x01 \x01(\x0e\x32\x17.flame.v1.ExecutorState\x12\x17\n\nsession_id\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x18\n\x0b\x62\x61tch_index\x18\x03 \x01(\rH\x01\x88\x01\x01\x12\x11\n\tpreempted\x18\x04 \x01(\x08\x12\x12\n\ngeneration\x18\x05 \x01(\x04\x42\r\n\x0b_session_idB\x0e\n\x0c_batch_index\"\x80\x01\n\x08\x45xecutor\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12$\n\x04spec\x18\x02 \x01(\x0b\x32\x16.flame.v1.ExecutorSpec\x12(\n\x06status\x18\x03 \x01(\x0b\x32\x18.flame.v1.ExecutorStatus\"5\n\x0c\x45xecutorList\x12%\n\texecutors\x18\x01 \x03(\x0b\x32\x12.flame.v1.Executor\"2\n\x0bSessionList\x12#\n\x08sessions\x18\x01 \x03(\x0b\x32\x11.flame.v1.Session\">\n\x0f\x41pplicationList\x12+\n\x0c\x61pplications\x18\x01 \x03(\x0b\x32\x15.flame.v1.Application\"?\n\x13ResourceRequirement\x12\x0b\n\x03\x63pu\x18\x01 \x01(\x04\x12\x0e\n\x06memory\x18\x02 \x01(\x04\x12\x0b\n\x03gpu\x18\x03 \x01(\x05\"\x1c\n\x08NodeSpec\x12\x10\n\x08hostname\x18\x01 \x01(\t\"$\n\x08NumaNode\x12\n\n\x02id\x18\x01 \x01(\r\x12\x0c\n\x04\x63pus\x18\x02 \x01(\t\"_\n\x08GpuGroup\x12\x0c\n\x04gpus\x18\x01 \x03(\r\x12\x16\n\tnuma_node\x18\x02 \x01(\rH\x00\x88\x01\x01\x12\x1f\n\x04link\x18\x03 \x01(\x0e\x32\x11.flame.v1.GpuLinkB\x0c\n\n_numa_node\"\x88\x01\n\x08NodeInfo\x12\x0c\n\x04\x61rch\x18\x01 \x01(\t\x12\n\n\x02os\x18\x02 \x01(\t\x12&\n\nnuma_nodes\x18\x03 \x03(\x0b\x32\x12.flame.v1.NumaNode\x12&\n\ngpu_groups\x18\x04 \x03(\x0b\x32\x12.flame.v1.GpuGroup\x12\x12\n\nprefetched\x18\x05 \x03(\t\",\n\x0bNodeAddress\x12\x0c\n\x04type\x18\x01 \x01(\t\x12\x0f\n\x07\x61\x64\x64ress\x18\x02 \x01(\t\"\x88\x03\n\nNodeStatus\x12\"\n\x05state\x18\x01 \x01(\x0e\x32\x13.flame.v1.NodeState\x12/\n\x08\x63\x61pacity\x18\x02 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12\x32\n\x0b\x61llocatable\x18\x03 \x01(\x0b\x32\x1d.flame.v1.ResourceRequirement\x12 \n\x04info\x18\x04 \x01(\x0b\x32\x12.flame.v1.NodeInfo\x12(\n\taddresses\x18\x05 \x03(\x0b\x32\x15.flame.v1.NodeAddress\x12\x1b\n\x13last_heartbeat_time\x18\x06 \x01(\x03\x12\x17\n\nsaturation\x18\x07 \x01(\x01H\x00\x88\x01\x01\x12\x1f\n\x06\x65vents\x18\x08 \x03(\x0b\x32\x0f.flame.v1.Event\x12\x11\n\texecutors\x18\t \x01(\r\x12\x1a\n\rmax_executors\x18\n \x01(\rH\x01\x88\x01\x01\x42\r\n\x0b_saturationB\x10\n\x0e_max_executors\"t\n\x04Node\x12$\n\x08metadata\x18\x01 \x01(\x0b\x32\x12.flame.v1.Metadata\x12 \n\x04spec\x18\x02 \x01(\x0b\x32\x12.flame.v1.NodeSpec\x12$\n\x06status\x18\x03 \x01(\x0b\x32\x14.flame.v1.NodeStatus\")\n\x08NodeList\x12\x1d\n\x05nodes\x18\x01 \x03(\x0b\x32\x0e.flame.v1.Node\"?\n\x06Result\x12\x13\n\x0breturn_code\x18\x01 \x01(\x05\x12\x14\n\x07message\x18\x02 \x01(\tH\x00\x88\x01\x01\x42\n\n\x08_message\"\xc5\x03\n\nTaskResult\x12\x13\n\x0breturn_code\x18\x01 \x01(\x05\x12\x13\n\x06output\x18\x02 \x01(\x0cH\x00\x88\x01\x01\x12\x14\n\x07message\x18\x03 \x01(\tH\x01\x88\x01\x01\x12)\n\tartifacts\x18\x04 \x03(\x0b\x32\x16.flame.v1.TaskArtifact\x12\x34\n\x0e\x66\x61ilure_reason\x18\x05 \x01(\x0e\x32\x17.flame.v1.FailureReasonH\x02\x88\x01\x01\x12\'\n\x05usage\x18\x06 \x01(\x0b\x32\x13.flame.v1.TaskUsageH\x03\x88\x01\x01\x12\x31\n\npostmortem\x18\x07 \x01(\x0b\x32\x18.flame.v1.TaskPostmortemH\x04\x88\x01\x01\x12\x32\n\noutput_ref\x18\x08 \x01(\x0b\x32\x19.flame.v1.ObjectReferenceH\x05\x88\x01\x01\x12*\n\x05\x63odec\x18\t \x01(\x0e\x32\x16.flame.v1.PayloadCodecH\x06\x88\x01\x01\x42\t\n\x07_outputB\n\n\x08_messageB\x11\n\x0f_failure_reasonB\x08\n\x06_usageB\r\n\x0b_postmortemB\r\n\x0b_output_refB\x08\n\x06_codec\"V\n\x0fTaskOutputChunk\x12\x12\n\nsession_id\x18\x01 \x01(\t\x12\x0f\n\x07task_id\x18\x02 \x01(\t\x12\x10\n\x08sequence\x18\x03 \x01(\x04\x12\x0c\n\x04\x64\x61ta\x18\x04 \x01(\x0c\"`\n\tTaskUsage\x12\x13\n\x0b\x63pu_time_ms\x18\x01 \x01(\x04\x12\x15\n\rmax_rss_bytes\x18\x02 \x01(\x04\x12\x12\n\nread_bytes\x18\x03 \x01(\x04\x12\x13\n\x0bwrite_bytes\x18\x04 \x01(\x04\"\xaf\x02\n\x0eTaskPostmortem\x12\x13\n\x0b\x65xecutor_id\x18\x01 \x01(\t\x12\x0c\n\x04node\x18\x02 \x01(\t\x12\x16\n\texit_code\x18\x03 \x01(\x05H\x00\x88\x01\x01\x12\x13\n\x06signal\x18\x04 \x01(\x05H\x01\x88\x01\x01\x12\x13\n\x0b\x63ore_dumped\x18\x05 \x01(\x08\x12\x18\n\x0bstderr_tail\x18\x06 \x01(\tH\x02\x88\x01\x01\x12\x19\n\x0cload_average\x18\x07 \x01(\x01H\x03\x88\x01\x01\x12\x1d\n\x10memory_available\x18\x08 \x01(\x04H\x04\x88\x01\x01\x12\x15\n\rcreation_time\x18\t \x01(\x03\x42\x0c\n\n_exit_codeB\t\n\x07_signalB\x0e\n\x0c_stderr_tailB\x0f\n\r_load_averageB\x13\n\x11_memory_available\"\x0e\n\x0c\x45mptyRequest\"N\n\x05\x45vent\x12\x0c\n\x04\x63ode\x18\x01 \x01(\x05\x12\x14\n\x07message\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x15\n\rcreation_time\x18\x03 \x01(\x03\x42\n\n\x08_message*1\n\x0cSessionState\x12\x08\n\x04Open\x10\x00\x12\n\n\x06\x43losed\x10\x01\x12\x0b\n\x07\x43losing\x10\x02*0\n\x0cReplayPolicy\x12\x0f\n\x0bKeepResults\x10\x00\x12\x0f\n\x0bRetryFailed\x10\x01*8\n\x0cResultPolicy\x12\n\n\x06Inline\x10\x00\x12\x0b\n\x07\x44iscard\x10\x01\x12\x0f\n\x0bObjectCache\x10\x02*^\n\tTaskState\x12\x0b\n\x07Pending\x10\x00\x12\x0b\n\x07Running\x10\x01\x12\x0b\n\x07Succeed\x10\x02\x12\n\n\x06\x46\x61iled\x10\x03\x12\r\n\tCancelled\x10\x04\x12\x0f\n\x0bQuarantined\x10\x05*\x99\x01\n\rFailureReason\x12\x11\n\rUnknownReason\x10\x00\x12\x14\n\x10\x41pplicationError\x10\x01\x12\r\n\tShimCrash\x10\x02\x12\x0b\n\x07Timeout\x10\x03\x12\r\n\tPreempted\x10\x04\x12\x0c\n\x08NodeLost\x10\x05\x12\x10\n\x0cInputInvalid\x10\x06\x12\x14\n\x10\x44\x65\x61\x64lineExceeded\x10\x07*K\n\x08JobState\x12\x0e\n\nJobRunning\x10\x00\x12\x0e\n\nJobSucceed\x10\x01\x12\r\n\tJobFailed\x10\x02\x12\x10\n\x0cJobCancelled\x10\x03*-\n\x10\x43ompletionPolicy\x12\x0c\n\x08\x41llTasks\x10\x00\x12\x0b\n\x07\x41nyTask\x10\x01*\x1a\n\x04Shim\x12\x08\n\x04Host\x10\x00\x12\x08\n\x04Wasm\x10\x01*2\n\x0e\x46\x61irnessPolicy\x12\x0e\n\nRoundRobin\x10\x00\x12\x10\n\x0cProportional\x10\x01*G\n\x0cPayloadCodec\x12\x07\n\x03Raw\x10\x00\x12\x08\n\x04Json\x10\x01\x12\x0b\n\x07Msgpack\x10\x02\x12\t\n\x05\x41rrow\x10\x03\x12\x0c\n\x08Protobuf\x10\x04*=\n\x0eTopologyPolicy\x12\n\n\x06Ignore\x10\x00\x12\x0f\n\x0bNumaAligned\x10\x01\x12\x0e\n\nGpuAligned\x10\x02*-\n\x10\x41pplicationState\x12\x0b\n\x07\x45nabled\x10\x00\x12\x0c\n\x08\x44isabled\x10\x01*\xb4\x01\n\rExecutorState\x12\x13\n\x0f\x45xecutorUnknown\x10\x00\x12\x10\n\x0c\x45xecutorVoid\x10\x01\x12\x10\n\x0c\x45xecutorIdle\x10\x02\x12\x13\n\x0f\x45xecutorBinding\x10\x03\x12\x11\n\rExecutorBound\x10\x04\x12\x15\n\x11\x45xecutorUnbinding\x10\x05\x12\x15\n\x11\x45xecutorReleasing\x10\x06\x12\x14\n\x10\x45xecutorReleased\x10\x07*;\n\tNodeState\x12\x0b\n\x07Unknown\x10\x00\x12\t\n\x05Ready\x10\x01\x12\x0c\n\x08NotReady\x10\x02\x12\x08\n\x04Lost\x10\x03*;\n\x0e\x45ventOwnerKind\x12\x15\n\x11\x45ventOwnerSession\x10\x00\x12\x12\n\x0e\x45ventOwnerTask\x10\x01*\x1f\n\x07GpuLink\x12\x08\n\x04Pcie\x10\x00\x12\n\n\x06NvLink\x10\x01\x42)Z\'github.com/flame-sh/flame/sdk/go/rpc/v1b\x06proto3')
 
 _globals = globals()
 _builder.BuildMessageAndEnumDescriptors(DESCRIPTOR, _globals)
@@ -32,74 +32,126 @@ _builder.BuildTopDescriptorsAndMessages(DESCRIPTOR, 'types_pb2', _globals)
 if not _descriptor._USE_C_DESCRIPTORS:
   _globals['DESCRIPTOR']._loaded_options = None
   _globals['DESCRIPTOR']._serialized_options = b'Z\'github.com/flame-sh/flame/sdk/go/rpc/v1'
-  _globals['_SESSIONSTATE']._serialized_start=3237
-  _globals['_SESSIONSTATE']._serialized_end=3273
-  _globals['_TASKSTATE']._serialized_start=3275
-  _globals['_TASKSTATE']._serialized_end=3352
-  _globals['_SHIM']._serialized_start=3354
-  _globals['_SHIM']._serialized_end=3380
-  _globals['_APPLICATIONSTATE']._serialized_start=3382
-  _globals['_APPLICATIONSTATE']._serialized_end=3427
-  _globals['_EXECUTORSTATE']._serialized_start=3430
-  _globals['_EXECUTORSTATE']._serialized_end=3610
-  _globals['_NODESTATE']._serialized_start=3612
-  _globals['_NODESTATE']._serialized_end=3661
+  _globals['_SESSIONSTATE']._serialized_start=8305
+  _globals['_SESSIONSTATE']._serialized_end=8354
+  _globals['_REPLAYPOLICY']._serialized_start=8356
+  _globals['_REPLAYPOLICY']._serialized_end=8404
+  _globals['_RESULTPOLICY']._serialized_start=8406
+  _globals['_RESULTPOLICY']._serialized_end=8462
+  _globals['_TASKSTATE']._serialized_start=8464
+  _globals['_TASKSTATE']._serialized_end=8558
+  _globals['_FAILUREREASON']._serialized_start=8561
+  _globals['_FAILUREREASON']._serialized_end=8714
+  _globals['_JOBSTATE']._serialized_start=8716
+  _globals['_JOBSTATE']._serialized_end=8791
+  _globals['_COMPLETIONPOLICY']._serialized_start=8793
+  _globals['_COMPLETIONPOLICY']._serialized_end=8838
+  _globals['_SHIM']._serialized_start=8840
+  _globals['_SHIM']._serialized_end=8866
+  _globals['_FAIRNESSPOLICY']._serialized_start=8868
+  _globals['_FAIRNESSPOLICY']._serialized_end=8918
+  _globals['_PAYLOADCODEC']._serialized_start=8920
+  _globals['_PAYLOADCODEC']._serialized_end=8991
+  _globals['_TOPOLOGYPOLICY']._serialized_start=8993
+  _globals['_TOPOLOGYPOLICY']._serialized_end=9054
+  _globals['_APPLICATIONSTATE']._serialized_start=9056
+  _globals['_APPLICATIONSTATE']._serialized_end=9101
+  _globals['_EXECUTORSTATE']._serialized_start=9104
+  _globals['_EXECUTORSTATE']._serialized_end=9284
+  _globals['_NODESTATE']._serialized_start=9286
+  _globals['_NODESTATE']._serialized_end=9345
+  _globals['_EVENTOWNERKIND']._serialized_start=9347
+  _globals['_EVENTOWNERKIND']._serialized_end=9406
+  _globals['_GPULINK']._serialized_start=9408
+  _globals['_GPULINK']._serialized_end=9439
   _globals['_METADATA']._serialized_start=25
   _globals['_METADATA']._serialized_end=61
   _globals['_SESSIONSTATUS']._serialized_start=64
-  _globals['_SESSIONSTATUS']._serialized_end=310
-  _globals['_SESSIONSPEC']._serialized_start=313
-  _globals['_SESSIONSPEC']._serialized_end=493
-  _globals['_SESSION']._serialized_start=495
-  _globals['_SESSION']._serialized_end=620
-  _globals['_TASKSTATUS']._serialized_start=623
-  _globals['_TASKSTATUS']._serialized_end=777
-  _globals['_TASKSPEC']._serialized_start=779
-  _globals['_TASKSPEC']._serialized_end=871
-  _globals['_TASK']._serialized_start=873
-  _globals['_TASK']._serialized_end=989
-  _globals['_APPLICATIONSTATUS']._serialized_start=991
-  _globals['_APPLICATIONSTATUS']._serialized_end=1076
-  _globals['_ENVIRONMENT']._serialized_start=1078
-  _globals['_ENVIRONMENT']._serialized_end=1120
-  _globals['_APPLICATIONSCHEMA']._serialized_start=1122
-  _globals['_APPLICATIONSCHEMA']._serialized_end=1245
-  _globals['_APPLICATIONSPEC']._serialized_start=1248
-  _globals['_APPLICATIONSPEC']._serialized_end=1714
-  _globals['_APPLICATION']._serialized_start=1717
-  _globals['_APPLICATION']._serialized_end=1854
-  _globals['_EXECUTORSPEC']._serialized_start=1856
-  _globals['_EXECUTORSPEC']._serialized_end=1976
-  _globals['_EXECUTORSTATUS']._serialized_start=1978
-  _globals['_EXECUTORSTATUS']._serialized_end=2074
-  _globals['_EXECUTOR']._serialized_start=2077
-  _globals['_EXECUTOR']._serialized_end=2205
-  _globals['_EXECUTORLIST']._serialized_start=2207
-  _globals['_EXECUTORLIST']._serialized_end=2260
-  _globals['_SESSIONLIST']._serialized_start=2262
-  _globals['_SESSIONLIST']._serialized_end=2312
-  _globals['_APPLICATIONLIST']._serialized_start=2314
-  _globals['_APPLICATIONLIST']._serialized_end=2376
-  _globals['_RESOURCEREQUIREMENT']._serialized_start=2378
-  _globals['_RESOURCEREQUIREMENT']._serialized_end=2441
-  _globals['_NODESPEC']._serialized_start=2443
-  _globals['_NODESPEC']._serialized_end=2471
-  _globals['_NODEINFO']._serialized_start=2473
-  _globals['_NODEINFO']._serialized_end=2509
-  _globals['_NODEADDRESS']._serialized_start=2511
-  _globals['_NODEADDRESS']._serialized_end=2555
-  _globals['_NODESTATUS']._serialized_start=2558
-  _globals['_NODESTATUS']._serialized_end=2812
-  _globals['_NODE']._serialized_start=2814
-  _globals['_NODE']._serialized_end=2930
-  _globals['_NODELIST']._serialized_start=2932
-  _globals['_NODELIST']._serialized_end=2973
-  _globals['_RESULT']._serialized_start=2975
-  _globals['_RESULT']._serialized_end=3038
-  _globals['_TASKRESULT']._serialized_start=3040
-  _globals['_TASKRESULT']._serialized_end=3139
-  _globals['_EMPTYREQUEST']._serialized_start=3141
-  _globals['_EMPTYREQUEST']._serialized_end=3155
-  _globals['_EVENT']._serialized_start=3157
-  _globals['_EVENT']._serialized_end=3235
+  _globals['_SESSIONSTATUS']._serialized_end=490
+  _globals['_TASKDURATIONSTATS']._serialized_start=492
+  _globals['_TASKDURATIONSTATS']._serialized_end=582
+  _globals['_SESSIONSPEC']._serialized_start=585
+  _globals['_SESSIONSPEC']._serialized_end=1078
+  _globals['_SESSION']._serialized_start=1080
+  _globals['_SESSION']._serialized_end=1205
+  _globals['_TASKSTATUS']._serialized_start=1208
+  _globals['_TASKSTATUS']._serialized_end=1646
+  _globals['_TASKSPEC']._serialized_start=1649
+  _globals['_TASKSPEC']._serialized_end=2184
+  _globals['_TASKARTIFACT']._serialized_start=2186
+  _globals['_TASKARTIFACT']._serialized_end=2282
+  _globals['_OBJECTREFERENCE']._serialized_start=2284
+  _globals['_OBJECTREFERENCE']._serialized_end=2368
+  _globals['_TASK']._serialized_start=2370
+  _globals['_TASK']._serialized_end=2486
+  _globals['_JOBSPEC']._serialized_start=2489
+  _globals['_JOBSPEC']._serialized_end=2739
+  _globals['_JOBSTATUS']._serialized_start=2742
+  _globals['_JOBSTATUS']._serialized_end=2982
+  _globals['_JOB']._serialized_start=2984
+  _globals['_JOB']._serialized_end=3097
+  _globals['_APPLICATIONSTATUS']._serialized_start=3099
+  _globals['_APPLICATIONSTATUS']._serialized_end=3184
+  _globals['_ENVIRONMENT']._serialized_start=3186
+  _globals['_ENVIRONMENT']._serialized_end=3228
+  _globals['_APPLICATIONSCHEMA']._serialized_start=3230
+  _globals['_APPLICATIONSCHEMA']._serialized_end=3353
+  _globals['_APPLICATIONSPEC']._serialized_start=3356
+  _globals['_APPLICATIONSPEC']._serialized_end=4421
+  _globals['_SESSIONDEFAULTS']._serialized_start=4424
+  _globals['_SESSIONDEFAULTS']._serialized_end=4791
+  _globals['_HEALTHPROBE']._serialized_start=4794
+  _globals['_HEALTHPROBE']._serialized_end=5037
+  _globals['_LIFECYCLEHOOKS']._serialized_start=5040
+  _globals['_LIFECYCLEHOOKS']._serialized_end=5247
+  _globals['_LIFECYCLEHOOK']._serialized_start=5249
+  _globals['_LIFECYCLEHOOK']._serialized_end=5331
+  _globals['_PYTHONENVIRONMENT']._serialized_start=5334
+  _globals['_PYTHONENVIRONMENT']._serialized_end=5479
+  _globals['_APPLICATION']._serialized_start=5482
+  _globals['_APPLICATION']._serialized_end=5619
+  _globals['_EXECUTORSPEC']._serialized_start=5621
+  _globals['_EXECUTORSPEC']._serialized_end=5741
+  _globals['_EXECUTORSTATUS']._serialized_start=5744
+  _globals['_EXECUTORSTATUS']._serialized_end=5921
+  _globals['_EXECUTOR']._serialized_start=5924
+  _globals['_EXECUTOR']._serialized_end=6052
+  _globals['_EXECUTORLIST']._serialized_start=6054
+  _globals['_EXECUTORLIST']._serialized_end=6107
+  _globals['_SESSIONLIST']._serialized_start=6109
+  _globals['_SESSIONLIST']._serialized_end=6159
+  _globals['_APPLICATIONLIST']._serialized_start=6161
+  _globals['_APPLICATIONLIST']._serialized_end=6223
+  _globals['_RESOURCEREQUIREMENT']._serialized_start=6225
+  _globals['_RESOURCEREQUIREMENT']._serialized_end=6288
+  _globals['_NODESPEC']._serialized_start=6290
+  _globals['_NODESPEC']._serialized_end=6318
+  _globals['_NUMANODE']._serialized_start=6320
+  _globals['_NUMANODE']._serialized_end=6356
+  _globals['_GPUGROUP']._serialized_start=6358
+  _globals['_GPUGROUP']._serialized_end=6453
+  _globals['_NODEINFO']._serialized_start=6456
+  _globals['_NODEINFO']._serialized_end=6592
+  _globals['_NODEADDRESS']._serialized_start=6594
+  _globals['_NODEADDRESS']._serialized_end=6638
+  _globals['_NODESTATUS']._serialized_start=6641
+  _globals['_NODESTATUS']._serialized_end=7033
+  _globals['_NODE']._serialized_start=7035
+  _globals['_NODE']._serialized_end=7151
+  _globals['_NODELIST']._serialized_start=7153
+  _globals['_NODELIST']._serialized_end=7194
+  _globals['_RESULT']._serialized_start=7196
+  _globals['_RESULT']._serialized_end=7259
+  _globals['_TASKRESULT']._serialized_start=7262
+  _globals['_TASKRESULT']._serialized_end=7715
+  _globals['_TASKOUTPUTCHUNK']._serialized_start=7717
+  _globals['_TASKOUTPUTCHUNK']._serialized_end=7803
+  _globals['_TASKUSAGE']._serialized_start=7805
+  _globals['_TASKUSAGE']._serialized_end=7901
+  _globals['_TASKPOSTMORTEM']._serialized_start=7904
+  _globals['_TASKPOSTMORTEM']._serialized_end=8207
+  _globals['_EMPTYREQUEST']._serialized_start=8209
+  _globals['_EMPTYREQUEST']._serialized_end=8223
+  _globals['_EVENT']._serialized_start=8225
+  _globals['_EVENT']._serialized_end=8303
 # @@protoc_insertion_point(module_scope)
@@ -58,16 +58,22 @@ class ObjectFuture:
         """
         self._future = future
 
-    def ref(self) -> ObjectRef:
+    def ref(self, timeout: Optional[float] = None) -> ObjectRef:
         """Get the ObjectRef by waiting for the future to complete.
 
         This method is primarily intended for internal use within the Flame SDK,
         providing direct access to the encapsulated object reference.
 
+        Args:
+            timeout: Seconds to wait for the future; wait forever if None
+
         Returns:
             The ObjectRef from the completed future
+
+        Raises:
+            TimeoutError: If the future is not completed within the timeout
         """
-        result = self._future.result()
+        result = self._future.result(timeout)
         # The future returns bytes (ObjectRef encoded), decode it to ObjectRef
         if isinstance(result, bytes):
             return ObjectRef.decode(result)
@@ -77,16 +83,22 @@ class ObjectFuture:
         # Otherwise, assume it's bytes and try to decode
         return ObjectRef.decode(result)
 
-    def get(self) -> Any:
+    def get(self, timeout: Optional[float] = None) -> Any:
         """Retrieve the concrete object that this ObjectFuture represents.
 
         This method fetches the ObjectRef via the future, then uses cache.get_object
         to retrieve the actual underlying object.
 
+        Args:
+            timeout: Seconds to wait for the future; wait forever if None
+
         Returns:
             The deserialized object from the cache
+
+        Raises:
+            TimeoutError: If the future is not completed within the timeout
         """
-        result = self._future.result()
+        result = self._future.result(timeout)
         # The future returns bytes (ObjectRef encoded), decode it to ObjectRef
         if isinstance(result, bytes):
             object_ref = ObjectRef.decode(result)
@@ -97,9 +109,20 @@ class ObjectFuture:
             object_ref = ObjectRef.decode(result)
         return get_object(object_ref)
 
-    def wait(self) -> None:
-        """Wait for the future to complete without fetching the result."""
-        self._future.result()
+    def wait(self, timeout: Optional[float] = None) -> None:
+        """Wait for the future to complete without fetching the result.
+
+        Args:
+            timeout: Seconds to wait for the future; wait forever if None
+
+        Raises:
+            TimeoutError: If the future is not completed within the timeout
+        """
+        self._future.result(timeout)
+
+    def done(self) -> bool:
+        """Return True if the future is completed, i.e. succeeded or failed."""
+        return self._future.done()
 
 
 class ObjectFutureIterator:
@@ -1,3 +1,4 @@
+import base64
 import json
 from datetime import datetime, timezone
 
@@ -44,3 +45,53 @@ def test_get_object_with_fake_flight_client(monkeypatch):
     ref = ObjectRef(endpoint="grpc://host:9090", key="sess-1/obj1", version=0)
     result = get_object(ref)
     assert result == base
+
+
+def test_upload_multipart_with_fake_flight_client(monkeypatch):
+    import hashlib
+
+    import flamepy.core.cache as cache
+
+    monkeypatch.setattr(cache, "PART_SIZE", 4)
+    monkeypatch.setattr(cache.time, "sleep", lambda _: None)
+
+    data = b"hello world"
+    received = {}
+    failures = {"part": 1}
+
+    class DummyResult:
+        def __init__(self, body):
+            self.body = pa.py_buffer(json.dumps(body).encode("utf-8"))
+
+    class DummyFlightClient:
+        def do_action(self, action):
+            body = action.body.to_pybytes().decode("utf-8")
+            if action.type == "UPLOAD_INIT":
+                return [DummyResult({"upload_id": "u-1", "key": f"{body}/obj1", "parts": []})]
+            if action.type == "UPLOAD_PART":
+                # The first attempt of a part fails, e.g. by a flaky link.
+                if failures["part"] > 0:
+                    failures["part"] -= 1
+                    raise ConnectionError("link down")
+                _, number, checksum, part = body.split(":", 3)
+                received[int(number)] = base64.b64decode(part)
+                return [DummyResult({"part_number": int(number), "checksum": checksum})]
+            assert action.type == "UPLOAD_COMPLETE"
+            parts = json.loads(body)["parts"]
+            assert [p["part_number"] for p in parts] == [1, 2, 3]
+            joined = b"".join(received[p["part_number"]] for p in parts)
+            return [
+                DummyResult(
+                    {
+                        "endpoint": "grpc://host:9090",
+                        "key": "sess-1/obj1",
+                        "version": 0,
+                        "size": len(joined),
+                        "checksum": hashlib.sha256(joined).hexdigest(),
+                    }
+                )
+            ]
+
+    ref = cache._upload_multipart(DummyFlightClient(), "sess-1", data)
+    assert ref.key == "sess-1/obj1"
+    assert b"".join(received[n] for n in sorted(received)) == data
@@ -0,0 +1,134 @@
+"""Tests for flamepy.celery - the Celery-style API on top of the Runner."""
+
+from concurrent.futures import Future
+from unittest.mock import patch
+
+import cloudpickle
+import pytest
+
+
+class DummyObjectRef:
+    """Mock ObjectRef holding the pickled value."""
+
+    def __init__(self, data: bytes):
+        self._data = data
+
+    @classmethod
+    def decode(cls, data: bytes) -> "DummyObjectRef":
+        return cls(data)
+
+
+class FakeRunner:
+    """Mock Runner whose services run the functions locally."""
+
+    instances = []
+
+    def __init__(self, name, fail_if_exists=False):
+        self.name = name
+        self.services = []
+        self.closed = False
+        FakeRunner.instances.append(self)
+
+    def service(self, fn):
+        from flamepy.runner.runner import ObjectFuture
+
+        def call(*args, **kwargs):
+            future = Future()
+            try:
+                future.set_result(DummyObjectRef(cloudpickle.dumps(fn(*args, **kwargs))))
+            except Exception as e:
+                future.set_exception(e)
+            return ObjectFuture(future)
+
+        self.services.append(fn)
+        return call
+
+    def close(self):
+        self.closed = True
+
+
+@pytest.fixture
+def app():
+    from flamepy.celery import Celery
+
+    FakeRunner.instances = []
+    with patch("flamepy.celery.app.Runner", FakeRunner), patch("flamepy.runner.runner.ObjectRef", DummyObjectRef), patch("flamepy.runner.runner.get_object", lambda ref: cloudpickle.loads(ref._data)):
+        yield Celery("celery-test", broker="redis://localhost")
+
+
+def test_task_delay_and_apply_async(app):
+    @app.task
+    def add(x, y):
+        return x + y
+
+    # Calling the task runs it locally, without starting the runner.
+    assert add(1, 2) == 3
+    assert FakeRunner.instances == []
+    assert add.name.endswith("add")
+    assert app.tasks == {add.name: add}
+
+    result = add.delay(1, 2)
+    assert result.get(timeout=1) == 3
+    assert result.ready() and result.successful()
+    assert result.state == "SUCCESS"
+
+    assert add.apply_async(args=(3,), kwargs={"y": 4}, countdown=10).get() == 7
+    assert app.send_task(add.name, args=(5, 6)).get() == 11
+
+    # The runner is started once, with one service per task.
+    assert len(FakeRunner.instances) == 1
+    assert len(FakeRunner.instances[0].services) == 1
+
+    app.close()
+    assert FakeRunner.instances[0].closed
+
+
+def test_task_failure(app):
+    @app.task(name="tasks.fail")
+    def fail():
+        raise ValueError("boom")
+
+    result = fail.delay()
+    assert result.failed()
+    assert result.state == "FAILURE"
+    assert isinstance(result.result, ValueError)
+    with pytest.raises(ValueError):
+        result.get()
+
+
+def test_group_and_chord(app):
+    from flamepy.celery import chord, group
+
+    @app.task
+    def add(x, y):
+        return x + y
+
+    @app.task
+    def tsum(values, offset=0):
+        return sum(values) + offset
+
+    result = group(add.s(i, i) for i in range(5))()
+    assert len(result) == 5
+    assert result.get(timeout=1) == [0, 2, 4, 6, 8]
+    assert result.completed_count() == 5
+
+    # The partial arguments are prepended to the ones of the signatures.
+    assert group(add.s(1), add.s(2)).delay(10).get() == [11, 12]
+
+    assert chord(add.s(i, i) for i in range(5))(tsum.s(offset=1)).get(timeout=1) == 21
+    assert chord([add.s(1, 1)], tsum.si([10])).apply_async().get(timeout=1) == 10
+
+
+def test_chord_with_failed_header(app):
+    from flamepy.celery import chord
+
+    @app.task
+    def fail(x):
+        raise ValueError(f"boom {x}")
+
+    @app.task
+    def tsum(values):
+        return sum(values)
+
+    with pytest.raises(ValueError):
+        chord([fail.s(1)])(tsum.s()).get(timeout=1)
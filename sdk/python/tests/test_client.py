@@ -100,3 +100,35 @@ def test_session_create_task_with_mocked_frontend(monkeypatch):
     t = s.create_task(b"input")
     assert t.session_id == s.id
     assert t.id is not None
+
+
+def test_connection_create_job_with_mocked_frontend():
+    from flamepy.core.types import CompletionPolicy, JobState
+    from flamepy.proto.types_pb2 import Job, JobStatus, Metadata
+
+    requests = []
+
+    class DummyFrontend:
+        def CreateJob(self, req):
+            requests.append(req)
+            return Job(
+                metadata=Metadata(id=req.job_id, name=req.job_id),
+                spec=req.job,
+                status=JobStatus(state=0, creation_time=int(time.time()), session_id=req.job_id, total=req.job.count, submitted=2),
+            )
+
+    conn = client.Connection("http://localhost:8080", DummyChannel("localhost:8080"), DummyFrontend())
+    job = conn.create_job("app", count=4, job_id="job-1", parallelism=2, completion_policy=CompletionPolicy.ANY_TASK)
+
+    assert requests[0].job.count == 4
+    assert job.id == "job-1"
+    assert job.session_id == "job-1"
+    assert job.state == JobState.RUNNING
+    assert job.completion_policy == CompletionPolicy.ANY_TASK
+    assert job.parallelism == 2
+    assert (job.total, job.submitted) == (4, 2)
+    assert job.completion_time is None
+    assert job.message is None
+
+    with pytest.raises(client.FlameError):
+        conn.create_job("app", inputs=[b"0"], count=1)
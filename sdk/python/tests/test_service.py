@@ -5,6 +5,7 @@ import os
 import pytest
 
 import flamepy.core.service as service
+from flamepy.core.types import FlameError, TaskArtifact, TaskResult
 from flamepy.proto.types_pb2 import Result as ResultProto
 from flamepy.proto.types_pb2 import TaskResult as TaskResultProto
 
@@ -172,6 +173,8 @@ def test_on_task_invoke_exception_path():  # noqa: N802
             self.task_id = "tid"
             self.session_id = "sess"
             self.input = b"in"
+            self.environments = []
+            self.arguments = []
 
         def HasField(self, field):  # noqa: N802
             return field == "input" and self.input is not None
@@ -182,6 +185,114 @@ def test_on_task_invoke_exception_path():  # noqa: N802
     assert getattr(resp, "output", None) is None
 
 
+def test_on_task_invoke_with_overrides(monkeypatch):  # noqa: N802
+    monkeypatch.setenv("FLAME_TEST_EXISTING", "old")
+    monkeypatch.delenv("FLAME_TEST_NEW", raising=False)
+    seen = {}
+
+    class EnvService(service.FlameService):
+        def on_session_enter(self, context: service.SessionContext):
+            return True
+
+        def on_task_invoke(self, context: service.TaskContext):
+            seen["existing"] = os.environ.get("FLAME_TEST_EXISTING")
+            seen["new"] = os.environ.get("FLAME_TEST_NEW")
+            seen["arguments"] = context.arguments
+            return None
+
+        def on_session_leave(self):
+            return True
+
+    servicer = service.FlameInstanceServicer(EnvService())
+
+    class MockEnvironment:
+        def __init__(self, name, value):
+            self.name = name
+            self.value = value
+
+    class MockTaskRequest:
+        def __init__(self):
+            self.task_id = "tid"
+            self.session_id = "sess"
+            self.input = None
+            self.environments = [
+                MockEnvironment("FLAME_TEST_EXISTING", "new"),
+                MockEnvironment("FLAME_TEST_NEW", "1"),
+            ]
+            self.arguments = ["--epochs=3"]
+
+        def HasField(self, field):  # noqa: N802
+            return False
+
+    resp = servicer.OnTaskInvoke(MockTaskRequest(), DummyContext())
+    assert resp.return_code == 0
+    assert seen == {"existing": "new", "new": "1", "arguments": ["--epochs=3"]}
+    # The environments are restored after the invocation.
+    assert os.environ.get("FLAME_TEST_EXISTING") == "old"
+    assert "FLAME_TEST_NEW" not in os.environ
+
+
+def test_on_task_invoke_with_artifacts():  # noqa: N802
+    class ArtifactService(service.FlameService):
+        def on_session_enter(self, context: service.SessionContext):
+            return True
+
+        def on_task_invoke(self, context: service.TaskContext):
+            return TaskResult(
+                output=b"out",
+                artifacts=[
+                    TaskArtifact(name="summary", data=b"ok"),
+                    TaskArtifact(name="model", object_ref="grpc://cache/ssn/model"),
+                ],
+            )
+
+        def on_session_leave(self):
+            return True
+
+    servicer = service.FlameInstanceServicer(ArtifactService())
+
+    class MockTaskRequest:
+        def __init__(self):
+            self.task_id = "tid"
+            self.session_id = "sess"
+            self.input = None
+            self.environments = []
+            self.arguments = []
+
+        def HasField(self, field):  # noqa: N802
+            return False
+
+    resp = servicer.OnTaskInvoke(MockTaskRequest(), DummyContext())
+    assert resp.return_code == 0
+    assert resp.output == b"out"
+    assert [a.name for a in resp.artifacts] == ["summary", "model"]
+    assert resp.artifacts[0].data == b"ok"
+    assert resp.artifacts[1].object_ref == "grpc://cache/ssn/model"
+
+
+def test_task_context_read_input(monkeypatch):
+    import flamepy.core.cache as cache
+
+    fetched = []
+
+    def get_object(ref):
+        fetched.append(ref.key)
+        return b"input"
+
+    monkeypatch.setattr(cache, "get_object", get_object)
+
+    ref = cache.ObjectRef(endpoint="grpc://cache", key="sess/input")
+    context = service.TaskContext(task_id="tid", session_id="sess", input=None, input_ref=ref, input_checksum=cache.object_checksum(b"input"))
+    assert context.read_input() == b"input"
+    # The input is fetched only once.
+    assert context.read_input() == b"input"
+    assert fetched == ["sess/input"]
+
+    context = service.TaskContext(task_id="tid", session_id="sess", input=None, input_ref=ref, input_checksum=cache.object_checksum(b"other"))
+    with pytest.raises(FlameError):
+        context.read_input()
+
+
 def test_flame_instance_server_start_and_stop(monkeypatch, tmp_path):
     # Fake grpc server and helper to intercept calls
     started = {"start": False, "stop": False}
@@ -236,6 +347,47 @@ def test_flame_instance_server_start_and_stop(monkeypatch, tmp_path):
     assert started["stop"] is True
 
 
+def test_flame_instance_server_start_on_tcp(monkeypatch, tmp_path):
+    class FakeServer:
+        def __init__(self, *args, **kwargs):
+            self.started = False
+
+        def add_insecure_port(self, addr):
+            assert addr == "127.0.0.1:0"
+            return 50123
+
+        def start(self):
+            self.started = True
+
+        def wait_for_termination(self):
+            return None
+
+    fake_grpc = type("fake_grpc", (), {})()
+    fake_grpc.server = lambda executor=None: FakeServer()
+    monkeypatch.setattr(service, "grpc", fake_grpc)
+    monkeypatch.setattr(service, "add_InstanceServicer_to_server", lambda servicer, srv: None)
+    # The platforms without Unix domain sockets listen on a TCP loopback port.
+    monkeypatch.setattr(service, "_USE_UDS", False)
+
+    endpoint = tmp_path / "exec-1.sock"
+    monkeypatch.setenv(service.FLAME_INSTANCE_ENDPOINT, str(endpoint))
+
+    class DummyService(service.FlameService):
+        def on_session_enter(self, context):
+            return True
+
+        def on_task_invoke(self, context):
+            return b"OUT"
+
+        def on_session_leave(self):
+            return True
+
+    service.FlameInstanceServer(DummyService()).start()
+    # The address is published to the endpoint for the executor.
+    assert endpoint.read_text() == "127.0.0.1:50123"
+    assert not (tmp_path / "exec-1.sock.tmp").exists()
+
+
 def test_flame_instance_server_start_without_endpoint_raises():
     # Ensure the environment does not provide the endpoint
     if service.FLAME_INSTANCE_ENDPOINT in os.environ:
@@ -9,8 +9,10 @@ import pytest
 from flamepy.core.types import (
     SessionState,
     TaskState,
+    FailureReason,
     ApplicationState,
     Shim,
+    PayloadCodec,
     FlameErrorCode,
     FlameError,
     Event,
@@ -33,9 +35,14 @@ def test_enums_and_flame_error():
     # Enums should be int-like and have expected values
     assert int(SessionState.OPEN) == 0
     assert int(TaskState.PENDING) == 0
+    assert int(TaskState.QUARANTINED) == 5
     assert int(ApplicationState.ENABLED) == 0
     assert int(Shim.HOST) == 0
     assert int(FlameErrorCode.INVALID_ARGUMENT) == 2
+    assert int(FailureReason.INPUT_INVALID) == 6
+    assert not FailureReason.DEADLINE_EXCEEDED.is_infrastructure()
+    assert FailureReason.NODE_LOST.is_infrastructure()
+    assert not FailureReason.APPLICATION_ERROR.is_infrastructure()
 
     # FlameError
     err = FlameError(FlameErrorCode.INVALID_ARGUMENT, "bad arg")
@@ -43,6 +50,15 @@ def test_enums_and_flame_error():
     assert "bad arg" in str(err)
 
 
+def test_payload_codec():
+    assert PayloadCodec.JSON.decode(PayloadCodec.JSON.encode({"sum": 3})) == {"sum": 3}
+
+    with pytest.raises(FlameError):
+        PayloadCodec.JSON.decode(b"{")
+    with pytest.raises(FlameError):
+        PayloadCodec.ARROW.encode([1, 2])
+
+
 def test_dataclass_defaults_and_instantiation():
     t = Event(code=1)
     sa = SessionAttributes(application="app", slots=2)
@@ -96,3 +112,26 @@ def test_flame_context_env_overrides(tmp_path, monkeypatch):
     monkeypatch.setenv("FLAME_ENDPOINT", "http://override:1234")
     ctx2 = FlameContext()
     assert ctx2.endpoint == "http://override:1234"
+
+
+def test_flame_context_federation(tmp_path, monkeypatch):
+    fake_home = tmp_path / ".home"
+    fake_home.mkdir()
+    monkeypatch.setenv("HOME", str(fake_home))
+    monkeypatch.delenv("FLAME_ENDPOINT", raising=False)
+
+    flame_yaml = {
+        "current-context": "all",
+        "contexts": [
+            {"name": "east", "cluster": {"endpoint": "http://east:8080"}},
+            {"name": "west", "cluster": {"endpoint": "http://west:8080"}},
+        ],
+        "federations": [{"name": "all", "contexts": ["west", "east"]}],
+    }
+    conf_dir = fake_home / ".flame"
+    conf_dir.mkdir()
+    (conf_dir / "flame.yaml").write_text(json.dumps(flame_yaml))
+
+    # The federation resolves to its preferred cluster.
+    ctx = FlameContext()
+    assert ctx.endpoint == "http://west:8080"
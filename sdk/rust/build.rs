@@ -12,9 +12,13 @@ limitations under the License.
 */
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+
     tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("flame_descriptor.bin"))
         .type_attribute("flame.v1.TaskState", "#[allow(clippy::enum_variant_names)]")
         .type_attribute("flame.v1.Shim", "#[allow(clippy::enum_variant_names)]")
+        .type_attribute("flame.v1.JobState", "#[allow(clippy::enum_variant_names)]")
         .type_attribute(
             "flame.v1.ExecutorState",
             "#[allow(clippy::enum_variant_names)]",
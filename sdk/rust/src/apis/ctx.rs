@@ -16,7 +16,10 @@ use std::env;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::Path;
-use tonic::transport::{Certificate, ClientTlsConfig};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig};
+
+use common::ctx::{FlameTlsPolicy, FlameTlsPolicyYaml};
+use common::tls;
 
 use crate::apis::FlameError;
 
@@ -30,10 +33,15 @@ const FLAME_CA_FILE: &str = "FLAME_CA_FILE";
 /// Note: To disable TLS for development, use http:// instead of https://
 /// in the endpoint URL.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlameClientTls {
     /// Path to CA certificate for server verification
     #[serde(default)]
     pub ca_file: Option<String>,
+    /// TLS policy of the connections, the same as the `tls_policy` of the
+    /// cluster; the ca_file is required if it is set.
+    #[serde(default)]
+    pub policy: Option<FlameTlsPolicyYaml>,
 }
 
 impl FlameClientTls {
@@ -53,16 +61,35 @@ impl FlameClientTls {
 
         Ok(config)
     }
+
+    /// Connect to the https endpoint with the TLS policy, if any; `None` if no
+    /// policy is configured, so the endpoint is connected by `client_tls_config`.
+    pub async fn connect_with_policy(&self, endpoint: &str) -> Result<Option<Channel>, FlameError> {
+        let Some(policy) = self.policy.clone() else {
+            return Ok(None);
+        };
+
+        let policy = FlameTlsPolicy::try_from(policy)?;
+        let config = tls::client_config_of(self.ca_file.as_deref(), &policy)?;
+        let channel = tls::connect_with(endpoint, config).await?;
+
+        Ok(Some(channel))
+    }
 }
 
 /// Cluster configuration within a context.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlameClusterConfig {
     /// Cluster endpoint URL (e.g., "https://flame-session-manager:8080")
     pub endpoint: String,
     /// TLS configuration for cluster connection (optional)
     #[serde(default)]
     pub tls: Option<FlameClientTls>,
+    /// Path to the admin token of the administrative commands, e.g. minting
+    /// the join tokens (optional)
+    #[serde(default)]
+    pub admin_token_file: Option<String>,
 }
 
 impl FlameClusterConfig {
@@ -70,10 +97,26 @@ impl FlameClusterConfig {
     pub fn requires_tls(&self) -> bool {
         self.endpoint.starts_with("https://")
     }
+
+    /// Read the admin token of the cluster, if configured.
+    pub fn admin_token(&self) -> Result<Option<String>, FlameError> {
+        let Some(ref token_file) = self.admin_token_file else {
+            return Ok(None);
+        };
+
+        let token = fs::read_to_string(token_file).map_err(|e| {
+            FlameError::InvalidConfig(format!(
+                "failed to read admin_token_file <{}>: {}",
+                token_file, e
+            ))
+        })?;
+        Ok(Some(token.trim().to_string()))
+    }
 }
 
 /// Cache configuration within a context.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlameClientCache {
     /// Cache endpoint URL (e.g., "grpcs://flame-object-cache:9090")
     #[serde(default)]
@@ -88,6 +131,7 @@ pub struct FlameClientCache {
 
 /// Package configuration for application deployment.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlamePackage {
     /// Storage URL for the package (e.g., "file:///var/lib/flame/packages")
     #[serde(default)]
@@ -99,6 +143,7 @@ pub struct FlamePackage {
 
 /// Runner configuration for application execution.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlameRunner {
     /// Runner template name
     #[serde(default)]
@@ -107,6 +152,7 @@ pub struct FlameRunner {
 
 /// A named context containing cluster, cache, and package configurations.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FlameContextEntry {
     /// Name of this context
     pub name: String,
@@ -123,6 +169,17 @@ pub struct FlameContextEntry {
     pub runner: Option<FlameRunner>,
 }
 
+/// A named group of contexts; a session is submitted to any of its clusters
+/// which has the application and capacity.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FlameFederation {
+    /// Name of this federation, which can be used as the current context.
+    pub name: String,
+    /// Names of the member contexts, in the order of preference.
+    pub contexts: Vec<String>,
+}
+
 /// Root configuration structure for flame.yaml
 ///
 /// Example configuration:
@@ -139,25 +196,83 @@ pub struct FlameContextEntry {
 ///       tls:
 ///         ca_file: "/etc/flame/certs/cache-ca.crt"
 /// ```
+///
+/// With several clusters, the current context can be a federation of them:
+/// ```yaml
+/// current-context: all
+/// federations:
+///   - name: all
+///     contexts: [east, west]
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct FlameContext {
     #[serde(rename = "current-context")]
     pub current_context: String,
     pub contexts: Vec<FlameContextEntry>,
+    #[serde(default)]
+    pub federations: Vec<FlameFederation>,
 }
 
 impl FlameContext {
-    /// Get the current context entry.
+    /// Get the current context entry; the current federation resolves to its
+    /// first context, i.e. the preferred cluster.
     pub fn get_current_context(&self) -> Result<&FlameContextEntry, FlameError> {
+        let name = match self.get_current_federation() {
+            Some(federation) => federation.contexts.first().ok_or_else(|| {
+                FlameError::InvalidConfig(format!(
+                    "Federation <{}> has no context",
+                    federation.name
+                ))
+            })?,
+            None => &self.current_context,
+        };
+
         self.contexts
             .iter()
-            .find(|c| c.name == self.current_context)
+            .find(|c| &c.name == name)
             .ok_or(FlameError::InvalidConfig(format!(
                 "Context <{}> not found",
-                self.current_context
+                name
             )))
     }
 
+    /// Get the current federation, if the current context is one.
+    pub fn get_current_federation(&self) -> Option<&FlameFederation> {
+        self.federations
+            .iter()
+            .find(|f| f.name == self.current_context)
+    }
+
+    /// Get the context entries of the current context: the members of the
+    /// current federation, or the current context entry itself.
+    pub fn get_current_contexts(&self) -> Result<Vec<&FlameContextEntry>, FlameError> {
+        let Some(federation) = self.get_current_federation() else {
+            return Ok(vec![self.get_current_context()?]);
+        };
+
+        if federation.contexts.is_empty() {
+            return Err(FlameError::InvalidConfig(format!(
+                "Federation <{}> has no context",
+                federation.name
+            )));
+        }
+
+        federation
+            .contexts
+            .iter()
+            .map(|name| {
+                self.contexts
+                    .iter()
+                    .find(|c| &c.name == name)
+                    .ok_or(FlameError::InvalidConfig(format!(
+                        "Context <{}> of federation <{}> not found",
+                        name, federation.name
+                    )))
+            })
+            .collect()
+    }
+
     /// Create a FlameContext from environment variables.
     ///
     /// This is useful for instances running inside executors where the
@@ -173,7 +288,10 @@ impl FlameContext {
         })?;
 
         let ca_file = env::var(FLAME_CA_FILE).ok();
-        let tls = ca_file.map(|f| FlameClientTls { ca_file: Some(f) });
+        let tls = ca_file.map(|f| FlameClientTls {
+            ca_file: Some(f),
+            ..Default::default()
+        });
 
         let cache_endpoint = env::var(FLAME_CACHE_ENDPOINT).ok();
         let cache = cache_endpoint.map(|ep| FlameClientCache {
@@ -184,7 +302,11 @@ impl FlameContext {
 
         let ctx = FlameContextEntry {
             name: "env".to_string(),
-            cluster: FlameClusterConfig { endpoint, tls },
+            cluster: FlameClusterConfig {
+                endpoint,
+                tls,
+                admin_token_file: None,
+            },
             cache,
             package: None,
             runner: None,
@@ -193,6 +315,7 @@ impl FlameContext {
         Ok(FlameContext {
             current_context: "env".to_string(),
             contexts: vec![ctx],
+            federations: vec![],
         })
     }
 
@@ -222,6 +345,7 @@ impl FlameContext {
                 if current.cluster.tls.is_none() {
                     current.cluster.tls = Some(FlameClientTls {
                         ca_file: Some(ca_file.clone()),
+                        ..Default::default()
                     });
                 } else if let Some(ref mut tls) = current.cluster.tls {
                     if tls.ca_file.is_none() {
@@ -234,6 +358,7 @@ impl FlameContext {
                     if cache.tls.is_none() {
                         cache.tls = Some(FlameClientTls {
                             ca_file: Some(ca_file.clone()),
+                            ..Default::default()
                         });
                     } else if let Some(ref mut tls) = cache.tls {
                         if tls.ca_file.is_none() {
@@ -260,7 +385,7 @@ impl FlameContext {
 
     /// Get mutable reference to the current context entry.
     fn get_current_context_mut(&mut self) -> Result<&mut FlameContextEntry, FlameError> {
-        let current = self.current_context.clone();
+        let current = self.get_current_context()?.name.clone();
         self.contexts
             .iter_mut()
             .find(|c| c.name == current)
@@ -284,8 +409,8 @@ impl FlameContext {
 
         let contents =
             fs::read_to_string(fp.clone()).map_err(|e| FlameError::Internal(e.to_string()))?;
-        let ctx: FlameContext =
-            serde_yaml::from_str(&contents).map_err(|e| FlameError::Internal(e.to_string()))?;
+        let ctx: FlameContext = serde_yaml::from_str(&contents)
+            .map_err(|e| FlameError::InvalidConfig(format!("invalid configuration <{fp}>: {e}")))?;
 
         tracing::debug!("Load FlameContext from <{fp}>: {ctx}");
 
@@ -297,9 +422,10 @@ impl Display for FlameContext {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "current_context: {}, contexts: {}",
+            "current_context: {}, contexts: {}, federations: {}",
             self.current_context,
-            self.contexts.len()
+            self.contexts.len(),
+            self.federations.len()
         )
     }
 }
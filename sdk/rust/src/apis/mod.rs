@@ -16,8 +16,14 @@ pub(crate) mod flame {
         tonic::include_proto!("flame.v1");
     }
 }
+
+/// The encoded descriptors of the protos above, served by the reflection service.
+pub(crate) const FILE_DESCRIPTOR_SET: &[u8] =
+    tonic::include_file_descriptor_set!("flame_descriptor");
 use flame::v1 as rpc;
 
+use std::collections::HashMap;
+
 use bincode::{config, Decode, Encode};
 use bytes::Bytes;
 use prost::Enumeration;
@@ -29,16 +35,17 @@ use tracing_subscriber::filter::{FromEnvError, ParseError};
 use tracing_subscriber::fmt::time::LocalTime;
 
 mod ctx;
+mod ids;
 pub use ctx::FlameClientCache;
 pub use ctx::FlameClientTls;
 pub use ctx::FlameClusterConfig;
 pub use ctx::FlameContext;
 pub use ctx::FlameContextEntry;
+pub use ctx::FlameFederation;
 pub use ctx::FlamePackage;
 pub use ctx::FlameRunner;
+pub use ids::{SessionID, TaskID};
 
-pub type TaskID = String;
-pub type SessionID = String;
 pub type ApplicationID = String;
 
 type Message = Bytes;
@@ -46,6 +53,164 @@ pub type TaskInput = Message;
 pub type TaskOutput = Message;
 pub type CommonData = Message;
 
+/// The environment variables and arguments of a task, which are passed to
+/// the service by the shim for that invocation only, see
+/// `TaskContext::overrides`; they must be allowed by the application.
+/// The timeout, attempts and priority override the ones of its session; the
+/// deadline in milliseconds since the task is created fails the task with
+/// `DeadlineExceeded` once passed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskOverrides {
+    pub environments: HashMap<String, String>,
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub priority: Option<u32>,
+    #[serde(default)]
+    pub deadline: Option<u64>,
+}
+
+/// A named output artifact of a task: either a small inline payload or a
+/// reference to an object in the object cache.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskArtifact {
+    pub name: String,
+    #[serde(with = "crate::client::serde_message")]
+    pub data: Option<Bytes>,
+    pub object_ref: Option<String>,
+}
+
+impl TaskArtifact {
+    pub fn inline(name: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        Self {
+            name: name.into(),
+            data: Some(data.into()),
+            object_ref: None,
+        }
+    }
+
+    pub fn object(name: impl Into<String>, object_ref: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            data: None,
+            object_ref: Some(object_ref.into()),
+        }
+    }
+}
+
+impl From<rpc::TaskArtifact> for TaskArtifact {
+    fn from(artifact: rpc::TaskArtifact) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(Bytes::from),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+impl From<TaskArtifact> for rpc::TaskArtifact {
+    fn from(artifact: TaskArtifact) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(|data| data.to_vec()),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+/// A chunk of the output streamed by a running task, e.g. its progress, before
+/// its result; the chunks of a task are ordered by their sequence, and a gap
+/// in the sequence means the watcher lagged behind and missed some chunks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaskOutputChunk {
+    pub task_id: TaskID,
+    pub sequence: u64,
+    pub data: Bytes,
+}
+
+impl From<rpc::TaskOutputChunk> for TaskOutputChunk {
+    fn from(chunk: rpc::TaskOutputChunk) -> Self {
+        Self {
+            task_id: TaskID::new(chunk.task_id),
+            sequence: chunk.sequence,
+            data: Bytes::from(chunk.data),
+        }
+    }
+}
+
+/// A reference to an object in the object cache, e.g. the input of a task which
+/// is fetched by the instance instead of being sent through the session manager.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectReference {
+    pub endpoint: String,
+    pub key: String,
+    /// The checksum of the object, e.g. `sha256:<hex>`, verified after fetching it.
+    pub checksum: Option<String>,
+}
+
+impl From<rpc::ObjectReference> for ObjectReference {
+    fn from(object: rpc::ObjectReference) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
+        }
+    }
+}
+
+impl From<ObjectReference> for rpc::ObjectReference {
+    fn from(object: ObjectReference) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
+        }
+    }
+}
+
+/// The resources used by the instance of an executor to run a task.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskUsage {
+    /// The CPU time, i.e. user and system, in milliseconds.
+    pub cpu_time_ms: u64,
+    /// The peak resident memory in bytes.
+    pub max_rss_bytes: u64,
+    /// The bytes read from the storage.
+    pub read_bytes: u64,
+    /// The bytes written to the storage.
+    pub write_bytes: u64,
+}
+
+impl From<rpc::TaskUsage> for TaskUsage {
+    fn from(usage: rpc::TaskUsage) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+/// The result of a task invocation: the output and the named artifacts.
+#[derive(Clone, Debug, Default)]
+pub struct TaskResult {
+    pub output: Option<TaskOutput>,
+    pub artifacts: Vec<TaskArtifact>,
+}
+
+impl From<Option<TaskOutput>> for TaskResult {
+    fn from(output: Option<TaskOutput>) -> Self {
+        Self {
+            output,
+            artifacts: vec![],
+        }
+    }
+}
+
 #[derive(Encode, Decode, PartialEq, Eq)]
 pub enum DataSource {
     Local,
@@ -108,6 +273,17 @@ impl From<stdng::Error> for FlameError {
     }
 }
 
+impl From<common::FlameError> for FlameError {
+    fn from(value: common::FlameError) -> Self {
+        match value {
+            common::FlameError::NotFound(msg) => FlameError::NotFound(msg),
+            common::FlameError::Network(msg) => FlameError::Network(msg),
+            common::FlameError::InvalidConfig(msg) => FlameError::InvalidConfig(msg),
+            e => FlameError::Internal(e.to_string()),
+        }
+    }
+}
+
 impl From<ParseError> for FlameError {
     fn from(value: ParseError) -> Self {
         FlameError::InvalidConfig(value.to_string())
@@ -126,6 +302,43 @@ impl From<FromEnvError> for FlameError {
 pub enum SessionState {
     Open = 0,
     Closed = 1,
+    /// No new tasks are accepted, and the running tasks are drained before
+    /// the session is closed.
+    Closing = 2,
+}
+
+/// The policy of the tasks when a closed session is reopened.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum ReplayPolicy {
+    /// Keep the results of the completed tasks, and only accept new tasks.
+    KeepResults = 0,
+    /// Reset the failed tasks to pending to run them again.
+    RetryFailed = 1,
+}
+
+/// What happens to the outputs of the completed tasks of a session.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum ResultPolicy {
+    /// Keep the outputs in the storage of the session manager.
+    Inline = 0,
+    /// Deliver the outputs to the watchers of the tasks without persisting them.
+    Discard = 1,
+    /// Put the outputs into the object cache, and keep their references instead;
+    /// the outputs are fetched from the object cache by `Task::output_ref`.
+    ObjectCache = 2,
+}
+
+/// The owner of the events, i.e. the session itself or its tasks.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum EventOwnerKind {
+    Session = 0,
+    Task = 1,
 }
 
 #[derive(
@@ -137,6 +350,9 @@ pub enum TaskState {
     Succeed = 2,
     Failed = 3,
     Cancelled = 4,
+    /// The task lost its executors too many times; it is not dispatched
+    /// until it is released.
+    Quarantined = 5,
 }
 
 impl TaskState {
@@ -145,6 +361,58 @@ impl TaskState {
     }
 }
 
+/// The state of a job, i.e. of the tasks it runs in its session.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum JobState {
+    Running = 0,
+    Succeed = 1,
+    Failed = 2,
+    Cancelled = 3,
+}
+
+impl JobState {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Running)
+    }
+}
+
+/// When a job succeeds: all its tasks completed, or any of them succeeded.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum CompletionPolicy {
+    AllTasks = 0,
+    AnyTask = 1,
+}
+
+/// The reason of a task failure.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum FailureReason {
+    Unknown = 0,
+    ApplicationError = 1,
+    ShimCrash = 2,
+    Timeout = 3,
+    Preempted = 4,
+    NodeLost = 5,
+    InputInvalid = 6,
+    DeadlineExceeded = 7,
+}
+
+impl FailureReason {
+    /// Whether the failure is caused by the infrastructure instead of the
+    /// application, i.e. the task may succeed if it runs again.
+    pub fn is_infrastructure(&self) -> bool {
+        matches!(
+            self,
+            Self::ShimCrash | Self::Timeout | Self::Preempted | Self::NodeLost
+        )
+    }
+}
+
 #[derive(
     Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
 )]
@@ -161,6 +429,95 @@ pub enum Shim {
     Wasm = 1,
 }
 
+/// How the instances of an application are shared among its sessions when
+/// they compete for the `max_instances` of the application.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
+)]
+pub enum FairnessPolicy {
+    /// Each session gets an equal share.
+    RoundRobin = 0,
+    /// Each session gets a share proportional to its pending tasks.
+    Proportional = 1,
+}
+
+/// The codec of the task inputs and outputs of an application; the session
+/// manager checks the content type tags of the payloads against it.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Enumeration,
+    strum_macros::Display,
+    Serialize,
+    Deserialize,
+)]
+pub enum PayloadCodec {
+    /// The opaque bytes, i.e. no codec.
+    #[default]
+    Raw = 0,
+    Json = 1,
+    Msgpack = 2,
+    Arrow = 3,
+    /// The protobuf message of the `payload_type` of the application.
+    Protobuf = 4,
+}
+
+/// How the instances of an application are placed in the topology of the
+/// nodes, so the performance-sensitive instances avoid the contention across
+/// the sockets and the PCIe switches.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Enumeration,
+    strum_macros::Display,
+    Serialize,
+    Deserialize,
+)]
+pub enum TopologyPolicy {
+    /// The topology of the nodes is ignored.
+    #[default]
+    Ignore = 0,
+    /// Each instance takes a NUMA node of its node.
+    NumaAligned = 1,
+    /// Each instance takes a GPU group, i.e. the GPUs connected by NVLink or
+    /// the same PCIe switch, of its node.
+    GpuAligned = 2,
+}
+
+impl PayloadCodec {
+    /// Encodes the value into a payload of this codec; only JSON is supported
+    /// by the SDK, the payloads of other codecs are encoded by the caller.
+    pub fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Bytes, FlameError> {
+        match self {
+            PayloadCodec::Json => serde_json::to_vec(value)
+                .map(Bytes::from)
+                .map_err(|e| FlameError::InvalidConfig(format!("failed to encode payload: {e}"))),
+            _ => Err(FlameError::InvalidConfig(format!(
+                "can not encode payload of codec <{self}>"
+            ))),
+        }
+    }
+
+    /// Decodes the payload of this codec into a value.
+    pub fn decode<T: serde::de::DeserializeOwned>(&self, data: &[u8]) -> Result<T, FlameError> {
+        match self {
+            PayloadCodec::Json => serde_json::from_slice(data)
+                .map_err(|e| FlameError::InvalidConfig(format!("failed to decode payload: {e}"))),
+            _ => Err(FlameError::InvalidConfig(format!(
+                "can not decode payload of codec <{self}>"
+            ))),
+        }
+    }
+}
+
 #[derive(
     Clone, Copy, Debug, PartialEq, Eq, Enumeration, strum_macros::Display, Serialize, Deserialize,
 )]
@@ -200,6 +557,37 @@ impl From<rpc::Shim> for Shim {
     }
 }
 
+impl From<rpc::FairnessPolicy> for FairnessPolicy {
+    fn from(policy: rpc::FairnessPolicy) -> Self {
+        match policy {
+            rpc::FairnessPolicy::RoundRobin => FairnessPolicy::RoundRobin,
+            rpc::FairnessPolicy::Proportional => FairnessPolicy::Proportional,
+        }
+    }
+}
+
+impl From<rpc::PayloadCodec> for PayloadCodec {
+    fn from(codec: rpc::PayloadCodec) -> Self {
+        match codec {
+            rpc::PayloadCodec::Raw => PayloadCodec::Raw,
+            rpc::PayloadCodec::Json => PayloadCodec::Json,
+            rpc::PayloadCodec::Msgpack => PayloadCodec::Msgpack,
+            rpc::PayloadCodec::Arrow => PayloadCodec::Arrow,
+            rpc::PayloadCodec::Protobuf => PayloadCodec::Protobuf,
+        }
+    }
+}
+
+impl From<rpc::TopologyPolicy> for TopologyPolicy {
+    fn from(policy: rpc::TopologyPolicy) -> Self {
+        match policy {
+            rpc::TopologyPolicy::Ignore => TopologyPolicy::Ignore,
+            rpc::TopologyPolicy::NumaAligned => TopologyPolicy::NumaAligned,
+            rpc::TopologyPolicy::GpuAligned => TopologyPolicy::GpuAligned,
+        }
+    }
+}
+
 impl From<rpc::ApplicationState> for ApplicationState {
     fn from(s: rpc::ApplicationState) -> Self {
         match s {
@@ -0,0 +1,334 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Submits sessions to any cluster of a federation: the session goes to the
+//! least loaded cluster which has the application enabled, and the returned
+//! session keeps the connection of that cluster, so its tasks and results are
+//! retrieved from there transparently.
+
+use stdng::trace_fn;
+
+use crate::apis::{ApplicationState, FlameContext, FlameError, SessionID, SessionState};
+use crate::client::{
+    connect_with_tls, Connection, Job, JobAttributes, Node, NodeState, Session, SessionAttributes,
+};
+
+/// A connected cluster of the federation.
+#[derive(Clone)]
+pub struct Cluster {
+    /// The name of the context of this cluster.
+    pub name: String,
+    pub connection: Connection,
+}
+
+/// The connections to the clusters of the current context, which is either a
+/// federation or a single cluster.
+#[derive(Clone)]
+pub struct Federation {
+    clusters: Vec<Cluster>,
+}
+
+impl Federation {
+    /// Connect to the clusters of the current context; the unreachable ones
+    /// are skipped, and it fails only if none is reachable.
+    pub async fn connect(ctx: &FlameContext) -> Result<Self, FlameError> {
+        trace_fn!("Federation::connect");
+
+        let mut clusters = vec![];
+        for entry in ctx.get_current_contexts()? {
+            match connect_with_tls(&entry.cluster.endpoint, entry.cluster.tls.as_ref()).await {
+                Ok(connection) => clusters.push(Cluster {
+                    name: entry.name.clone(),
+                    connection: connection.with_admin_token(entry.cluster.admin_token()?),
+                }),
+                Err(e) => tracing::warn!("Skip cluster <{}>: {}", entry.name, e),
+            }
+        }
+
+        if clusters.is_empty() {
+            return Err(FlameError::Network(format!(
+                "no cluster of <{}> is available",
+                ctx.current_context
+            )));
+        }
+
+        Ok(Self { clusters })
+    }
+
+    /// The connected clusters, in the order of preference.
+    pub fn clusters(&self) -> &[Cluster] {
+        &self.clusters
+    }
+
+    /// Select the least loaded cluster which has the application enabled and
+    /// ready nodes; the ties are broken by the order of the federation.
+    pub async fn select(&self, application: &str) -> Result<&Cluster, FlameError> {
+        trace_fn!("Federation::select");
+
+        let mut loads = vec![];
+        for cluster in &self.clusters {
+            let load = match cluster_load(&cluster.connection, application).await {
+                Ok(load) => load,
+                Err(e) => {
+                    tracing::warn!("Failed to get load of cluster <{}>: {}", cluster.name, e);
+                    None
+                }
+            };
+            tracing::debug!("Load of cluster <{}>: {:?}", cluster.name, load);
+            loads.push(load);
+        }
+
+        least_loaded(&loads)
+            .map(|i| &self.clusters[i])
+            .ok_or(FlameError::NotFound(format!(
+                "no cluster available for application <{application}>"
+            )))
+    }
+
+    /// Create the session in the selected cluster of its application.
+    pub async fn create_session(&self, attrs: &SessionAttributes) -> Result<Session, FlameError> {
+        let cluster = self.select(&attrs.application).await?;
+        tracing::debug!(
+            "Create session <{}> in cluster <{}>",
+            attrs.id,
+            cluster.name
+        );
+
+        cluster.connection.create_session(attrs).await
+    }
+
+    /// Get the session from whichever cluster it was created in.
+    pub async fn get_session(&self, id: &SessionID) -> Result<Session, FlameError> {
+        for cluster in &self.clusters {
+            match cluster.connection.get_session(id).await {
+                Ok(ssn) => return Ok(ssn),
+                Err(e) => tracing::debug!(
+                    "Session <{}> not found in cluster <{}>: {}",
+                    id,
+                    cluster.name,
+                    e
+                ),
+            }
+        }
+
+        Err(FlameError::NotFound(format!("session <{id}>")))
+    }
+
+    /// Resubmit the tasks of the session to a new session in the cluster of
+    /// the session, or only its failed tasks.
+    pub async fn resubmit_session(
+        &self,
+        id: &SessionID,
+        new_id: &SessionID,
+        only_failed: bool,
+    ) -> Result<Session, FlameError> {
+        for cluster in &self.clusters {
+            if cluster.connection.get_session(id).await.is_err() {
+                continue;
+            }
+            tracing::debug!(
+                "Resubmit session <{}> to <{}> in cluster <{}>",
+                id,
+                new_id,
+                cluster.name
+            );
+
+            return cluster
+                .connection
+                .resubmit_session(id, new_id, only_failed)
+                .await;
+        }
+
+        Err(FlameError::NotFound(format!("session <{id}>")))
+    }
+
+    /// List the sessions of all clusters; the clusters which fail are skipped,
+    /// and it fails only if all of them fail.
+    pub async fn list_session(&self) -> Result<Vec<Session>, FlameError> {
+        let mut ssns = vec![];
+        let mut failures = vec![];
+        for cluster in &self.clusters {
+            match cluster.connection.list_session().await {
+                Ok(list) => ssns.extend(list),
+                Err(e) => {
+                    tracing::warn!("Skip the sessions of cluster <{}>: {}", cluster.name, e);
+                    failures.push(e);
+                }
+            }
+        }
+
+        if failures.len() == self.clusters.len() {
+            if let Some(e) = failures.pop() {
+                return Err(e);
+            }
+        }
+
+        Ok(ssns)
+    }
+
+    /// Create the job in the selected cluster of its application.
+    pub async fn create_job(&self, attrs: &JobAttributes) -> Result<Job, FlameError> {
+        let cluster = self.select(&attrs.application).await?;
+        tracing::debug!("Create job <{}> in cluster <{}>", attrs.id, cluster.name);
+
+        cluster.connection.create_job(attrs).await
+    }
+
+    /// Get the job from whichever cluster it was created in.
+    pub async fn get_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        Ok(self.find_job(id).await?.1)
+    }
+
+    /// List the jobs of all clusters; the clusters which fail are skipped, and
+    /// it fails only if all of them fail.
+    pub async fn list_job(&self) -> Result<Vec<Job>, FlameError> {
+        let mut jobs = vec![];
+        let mut failures = vec![];
+        for cluster in &self.clusters {
+            match cluster.connection.list_job().await {
+                Ok(list) => jobs.extend(list),
+                Err(e) => {
+                    tracing::warn!("Skip the jobs of cluster <{}>: {}", cluster.name, e);
+                    failures.push(e);
+                }
+            }
+        }
+
+        if failures.len() == self.clusters.len() {
+            if let Some(e) = failures.pop() {
+                return Err(e);
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Cancel the job in whichever cluster it was created in.
+    pub async fn cancel_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        let (cluster, _) = self.find_job(id).await?;
+        cluster.connection.cancel_job(id).await
+    }
+
+    async fn find_job(&self, id: &SessionID) -> Result<(&Cluster, Job), FlameError> {
+        for cluster in &self.clusters {
+            match cluster.connection.get_job(id).await {
+                Ok(job) => return Ok((cluster, job)),
+                Err(e) => tracing::debug!(
+                    "Job <{}> not found in cluster <{}>: {}",
+                    id,
+                    cluster.name,
+                    e
+                ),
+            }
+        }
+
+        Err(FlameError::NotFound(format!("job <{id}>")))
+    }
+}
+
+/// The load of the cluster for the application, or `None` if the application
+/// is not enabled in it.
+async fn cluster_load(conn: &Connection, application: &str) -> Result<Option<f64>, FlameError> {
+    match conn.get_application(application).await {
+        Ok(app) if app.state == ApplicationState::Enabled => {}
+        _ => return Ok(None),
+    }
+
+    let nodes = conn.list_node().await?;
+    let tasks = conn
+        .list_session()
+        .await?
+        .iter()
+        .filter(|ssn| ssn.state == SessionState::Open)
+        .map(|ssn| (ssn.pending + ssn.running).max(0) as u64)
+        .sum();
+
+    Ok(load(tasks, &nodes))
+}
+
+/// The unfinished tasks per allocatable cpu of the ready nodes, or `None` if
+/// there is no ready node.
+fn load(tasks: u64, nodes: &[Node]) -> Option<f64> {
+    let cpu: u64 = nodes
+        .iter()
+        .filter(|n| n.state == NodeState::Ready)
+        .map(|n| n.allocatable_cpu)
+        .sum();
+
+    (cpu > 0).then(|| tasks as f64 / cpu as f64)
+}
+
+/// The index of the least loaded cluster; the first one wins the ties.
+fn least_loaded(loads: &[Option<f64>]) -> Option<usize> {
+    loads
+        .iter()
+        .enumerate()
+        .filter_map(|(i, load)| load.map(|l| (i, l)))
+        .fold(None, |min: Option<(usize, f64)>, (i, l)| match min {
+            Some((_, m)) if m <= l => min,
+            _ => Some((i, l)),
+        })
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(state: NodeState, allocatable_cpu: u64) -> Node {
+        Node {
+            name: "node".to_string(),
+            hostname: "node".to_string(),
+            state,
+            cpu: allocatable_cpu,
+            memory: 0,
+            allocatable_cpu,
+            allocatable_memory: 0,
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            saturation: None,
+            executors: 0,
+            max_executors: None,
+            last_heartbeat: None,
+            events: vec![],
+            numa_nodes: vec![],
+            gpu_groups: vec![],
+            prefetched: vec![],
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        assert_eq!(load(8, &[]), None);
+        assert_eq!(load(8, &[node(NodeState::NotReady, 4)]), None);
+        assert_eq!(
+            load(
+                8,
+                &[node(NodeState::Ready, 2), node(NodeState::NotReady, 4)]
+            ),
+            Some(4.0)
+        );
+        assert_eq!(
+            load(8, &[node(NodeState::Ready, 2), node(NodeState::Ready, 6)]),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_least_loaded() {
+        assert_eq!(least_loaded(&[]), None);
+        assert_eq!(least_loaded(&[None, None]), None);
+        assert_eq!(least_loaded(&[None, Some(2.0), Some(1.0)]), Some(2));
+        assert_eq!(least_loaded(&[Some(1.0), None, Some(1.0)]), Some(0));
+    }
+}
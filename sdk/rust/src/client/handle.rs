@@ -0,0 +1,189 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Persists the handle of a session on disk, so a batch driver resumes the
+//! session after it restarts: the tasks it submitted are reconciled with the
+//! session manager, i.e. the ones completed while the driver was offline are
+//! reported, and the remaining ones are watched again before it continues to
+//! submit new tasks.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use futures::future::try_join_all;
+use serde_derive::{Deserialize, Serialize};
+use stdng::trace_fn;
+
+use crate::apis::{FlameError, SessionID, SessionState, TaskID, TaskInput};
+use crate::client::{Connection, Session, Task, TaskInformerPtr};
+
+/// The handle of a session and the tasks submitted to it, which were not
+/// completed yet when the handle was saved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionHandle {
+    pub session_id: SessionID,
+    pub pending: BTreeSet<TaskID>,
+}
+
+impl SessionHandle {
+    pub fn new(session_id: SessionID) -> Self {
+        Self {
+            session_id,
+            pending: BTreeSet::new(),
+        }
+    }
+
+    /// Loads the handle saved at the path, or `None` if there is none, e.g.
+    /// the first run of the driver.
+    pub fn load(path: &Path) -> Result<Option<Self>, FlameError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read_to_string(path).map_err(|e| {
+            FlameError::Internal(format!("failed to read <{}>: {e}", path.display()))
+        })?;
+        let handle = serde_json::from_str(&data).map_err(|e| {
+            FlameError::InvalidConfig(format!("invalid session handle <{}>: {e}", path.display()))
+        })?;
+
+        Ok(Some(handle))
+    }
+
+    /// Saves the handle to the path; it's written to a temporary file first, so
+    /// the saved handle is never partially written if the driver crashes.
+    pub fn save(&self, path: &Path) -> Result<(), FlameError> {
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| FlameError::Internal(format!("failed to encode session handle: {e}")))?;
+
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data).map_err(|e| {
+            FlameError::Internal(format!("failed to write <{}>: {e}", tmp.display()))
+        })?;
+        fs::rename(&tmp, path).map_err(|e| {
+            FlameError::Internal(format!("failed to write <{}>: {e}", path.display()))
+        })?;
+
+        Ok(())
+    }
+
+    /// Records the task as pending until it's completed.
+    pub fn track(&mut self, task: &Task) {
+        if task.is_completed() {
+            self.pending.remove(&task.id);
+        } else {
+            self.pending.insert(task.id.clone());
+        }
+    }
+}
+
+/// The session resumed from its handle.
+pub struct ResumedSession {
+    pub session: Session,
+    /// The pending tasks of the handle which completed while the driver was offline.
+    pub completed: Vec<Task>,
+    /// The pending tasks of the handle which are still running or pending.
+    pub pending: Vec<Task>,
+}
+
+impl ResumedSession {
+    /// Creates a task, and tracks it in the handle as pending.
+    pub async fn create_task(
+        &self,
+        handle: &mut SessionHandle,
+        input: Option<TaskInput>,
+    ) -> Result<Task, FlameError> {
+        let task = self.session.create_task(input).await?;
+        handle.track(&task);
+
+        Ok(task)
+    }
+
+    /// Re-attaches the watches of the pending tasks, until all of them are completed.
+    pub async fn watch_pending(&self, informer_ptr: TaskInformerPtr) -> Result<(), FlameError> {
+        try_join_all(self.pending.iter().map(|task| {
+            self.session
+                .watch_task(task.ssn_id.clone(), task.id.clone(), informer_ptr.clone())
+        }))
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl Connection {
+    /// Resumes the session of the handle, and reconciles its pending tasks with
+    /// the session manager; the tasks completed while the driver was offline are
+    /// removed from the handle.
+    pub async fn resume_session(
+        &self,
+        handle: &mut SessionHandle,
+    ) -> Result<ResumedSession, FlameError> {
+        trace_fn!("Connection::resume_session");
+
+        let session = self.get_session(&handle.session_id).await?;
+        if session.state != SessionState::Open {
+            return Err(FlameError::InvalidConfig(format!(
+                "session <{}> is not open",
+                handle.session_id
+            )));
+        }
+
+        let mut completed = vec![];
+        let mut pending = vec![];
+        for id in handle.pending.clone() {
+            let task = session.get_task(&id).await?;
+            if task.is_completed() {
+                completed.push(task);
+            } else {
+                pending.push(task);
+            }
+        }
+
+        handle.pending = pending.iter().map(|task| task.id.clone()).collect();
+
+        Ok(ResumedSession {
+            session,
+            completed,
+            pending,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_handle_save_load() {
+        let dir = std::env::temp_dir().join(format!("flame-handle-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ssn-1.json");
+
+        assert_eq!(SessionHandle::load(&path).unwrap(), None);
+
+        let mut handle = SessionHandle::new("ssn-1".into());
+        handle.pending.insert("1".into());
+        handle.pending.insert("2".into());
+        handle.save(&path).unwrap();
+
+        assert_eq!(SessionHandle::load(&path).unwrap(), Some(handle));
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::write(&path, "not a handle").unwrap();
+        assert!(SessionHandle::load(&path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -11,6 +11,9 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod federation;
+pub mod handle;
+
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
@@ -19,7 +22,7 @@ use futures::TryFutureExt;
 // use serde::{Deserialize, Serialize};
 use serde_derive::{Deserialize, Serialize};
 use stdng::{lock_ptr, trace_fn};
-use tokio_stream::StreamExt;
+use tokio_stream::{Stream, StreamExt};
 use tonic::transport::Channel;
 use tonic::transport::Endpoint;
 use tonic::Request;
@@ -27,21 +30,51 @@ use url::Url;
 
 use self::rpc::frontend_client::FrontendClient as FlameFrontendClient;
 use self::rpc::{
-    ApplicationSpec, CloseSessionRequest, CreateSessionRequest, CreateTaskRequest, Environment,
-    GetApplicationRequest, GetNodeRequest, GetSessionRequest, GetTaskRequest,
-    ListApplicationRequest, ListExecutorRequest, ListNodesRequest, ListSessionRequest,
-    ListTaskRequest, OpenSessionRequest, RegisterApplicationRequest, SessionSpec, TaskSpec,
-    UnregisterApplicationRequest, UpdateApplicationRequest, WatchTaskRequest,
+    ApplicationSpec, CancelJobRequest, CheckpointTaskRequest, CloseSessionRequest,
+    CreateJobRequest, CreateJoinTokenRequest, CreateSessionRequest, CreateTaskRequest,
+    DisableApplicationRequest, EnableApplicationRequest, Environment, GetApplicationRequest,
+    GetClusterSnapshotRequest, GetClusterUtilizationRequest, GetJobRequest, GetNodeRequest,
+    GetSchedulerStateRequest, GetSessionRequest, GetSessionSummaryRequest, GetTaskRequest,
+    GetTaskSettingsRequest, JobSpec, ListApplicationRequest, ListEventsRequest,
+    ListExecutorRequest, ListJobRequest, ListNodesRequest, ListSessionRequest,
+    ListSlotRecommendationsRequest, ListTaskRequest, OpenSessionRequest,
+    RegisterApplicationRequest, ReleaseTaskRequest, RenewSessionLeaseRequest,
+    ResubmitSessionRequest, SessionSpec, TaskSpec, UnregisterApplicationRequest,
+    UpdateApplicationRequest, WatchSessionRequest, WatchTaskOutputRequest, WatchTaskRequest,
 };
 use crate::apis::flame::v1 as rpc;
 use crate::apis::FlameClientTls;
 use crate::apis::{
-    ApplicationID, ApplicationState, CommonData, ExecutorState, FlameError, SessionID,
-    SessionState, Shim, TaskID, TaskInput, TaskOutput, TaskState,
+    ApplicationID, ApplicationState, CommonData, CompletionPolicy, EventOwnerKind, ExecutorState,
+    FailureReason, FairnessPolicy, FlameError, JobState, ObjectReference, PayloadCodec,
+    ReplayPolicy, ResultPolicy, SessionID, SessionState, Shim, TaskArtifact, TaskID, TaskInput,
+    TaskOutput, TaskOutputChunk, TaskOverrides, TaskState, TaskUsage, TopologyPolicy,
 };
 
 type FlameClient = FlameFrontendClient<Channel>;
 
+/// The interval to check whether the closing session is closed.
+const CLOSE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The gRPC metadata of the trace ID of the requests creating the sessions and tasks.
+const FLAME_TRACE_ID: &str = "x-flame-trace-id";
+/// The gRPC metadata of the admin token of the administrative requests.
+const FLAME_ADMIN_TOKEN: &str = "x-flame-admin-token";
+
+/// The request with a new trace ID; it's stored on the created session or task,
+/// and logged by the session and executor managers, so the request is traced by
+/// a single ID across the client and the cluster.
+fn traced_request<T>(message: T) -> Request<T> {
+    let trace_id = uuid::Uuid::new_v4().to_string();
+    tracing::debug!("Send the request <{trace_id}>");
+
+    let mut req = Request::new(message);
+    if let Ok(value) = trace_id.parse() {
+        req.metadata_mut().insert(FLAME_TRACE_ID, value);
+    }
+    req
+}
+
 /// Connect to a Flame service without TLS (plaintext).
 ///
 /// Use `connect_with_tls` for TLS-enabled connections.
@@ -56,7 +89,8 @@ pub async fn connect(addr: &str) -> Result<Connection, FlameError> {
 /// * `tls_config` - Optional TLS configuration for secure connections
 ///
 /// # TLS Behavior
-/// - If `addr` starts with `https://` and `tls_config` is `Some`, use provided TLS config
+/// - If `addr` starts with `https://` and `tls_config` is `Some`, use provided TLS config,
+///   and its `policy` if set
 /// - If `addr` starts with `https://` and `tls_config` is `None`, use default TLS config (system CA)
 /// - If `addr` starts with `http://`, TLS is not used regardless of `tls_config`
 pub async fn connect_with_tls(
@@ -68,6 +102,17 @@ pub async fn connect_with_tls(
 
     // Apply TLS if endpoint uses https://
     if addr.starts_with("https://") {
+        // The TLS policy is applied by the same client config as the executor manager.
+        if let Some(tls) = tls_config {
+            if let Some(channel) = tls.connect_with_policy(addr).await? {
+                tracing::debug!("TLS policy enabled for connection to {}", addr);
+                return Ok(Connection {
+                    channel,
+                    admin_token: None,
+                });
+            }
+        }
+
         // Extract domain name from URL for TLS verification
         let url = Url::parse(addr)
             .map_err(|e| FlameError::InvalidConfig(format!("invalid URL <{}>: {}", addr, e)))?;
@@ -92,7 +137,10 @@ pub async fn connect_with_tls(
         FlameError::InvalidConfig(format!("failed to connect to <{}>: {}", addr, e))
     })?;
 
-    Ok(Connection { channel })
+    Ok(Connection {
+        channel,
+        admin_token: None,
+    })
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -103,9 +151,64 @@ pub struct Event {
     pub creation_time: DateTime<Utc>,
 }
 
+/// The filter of the events of a session; all its events match the default one.
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    /// The events created at or after the time.
+    pub since: Option<DateTime<Utc>>,
+    /// The events created before the time.
+    pub until: Option<DateTime<Utc>>,
+    /// The codes of the events; any code if empty.
+    pub codes: Vec<i32>,
+    pub owner: Option<EventOwnerKind>,
+    pub task_id: Option<TaskID>,
+}
+
+/// An event of a session, or of one of its tasks.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionEvent {
+    /// The task of the event, or None for the session itself.
+    pub task_id: Option<TaskID>,
+    pub event: Event,
+}
+
+/// A page of the events of a session, ordered by their creation time.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventPage {
+    pub events: Vec<SessionEvent>,
+    /// The token of the next page, or None if it's the last one.
+    pub next_page_token: Option<String>,
+}
+
+impl TryFrom<rpc::EventList> for EventPage {
+    type Error = FlameError;
+    fn try_from(list: rpc::EventList) -> Result<Self, FlameError> {
+        let events = list
+            .events
+            .into_iter()
+            .map(|e| {
+                let event = e
+                    .event
+                    .ok_or(FlameError::Internal("no event".to_string()))?;
+                Ok(SessionEvent {
+                    task_id: e.task_id.map(TaskID::from),
+                    event: Event::try_from(event)?,
+                })
+            })
+            .collect::<Result<Vec<SessionEvent>, FlameError>>()?;
+
+        Ok(Self {
+            events,
+            next_page_token: list.next_page_token,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct Connection {
     pub(crate) channel: Channel,
+    /// The admin token presented in the administrative requests.
+    admin_token: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -119,6 +222,28 @@ pub struct SessionAttributes {
     pub max_instances: Option<u32>,
     #[serde(default = "default_batch_size")]
     pub batch_size: u32,
+    /// The size limit in bytes of the shared scratch directory on each node.
+    #[serde(default)]
+    pub scratch_size: Option<u64>,
+    /// The attempts of a task before it's quarantined.
+    #[serde(default)]
+    pub max_task_attempts: Option<u32>,
+    /// The timeout in seconds of running a task.
+    #[serde(default)]
+    pub task_timeout: Option<u64>,
+    /// The priority of the tasks among the pending tasks of the session.
+    #[serde(default)]
+    pub task_priority: Option<u32>,
+    /// The priority of the session in scheduling, the higher the earlier.
+    #[serde(default)]
+    pub priority: u32,
+    /// What happens to the outputs of the completed tasks.
+    #[serde(default)]
+    pub result_policy: ResultPolicy,
+    /// Seconds to keep the session after it's closed; it's deleted with its
+    /// tasks afterwards. None keeps it forever.
+    #[serde(default)]
+    pub ttl_after_completion: Option<u64>,
 }
 
 fn default_batch_size() -> u32 {
@@ -147,6 +272,111 @@ pub struct ApplicationAttributes {
     pub delay_release: Option<Duration>,
     pub schema: Option<ApplicationSchema>,
     pub url: Option<String>,
+    #[serde(default)]
+    pub task_environments: Vec<String>,
+    #[serde(default)]
+    pub task_arguments: Vec<String>,
+    /// The max number of tasks handed to an executor in one launch.
+    #[serde(default)]
+    pub bundle_size: Option<u32>,
+    /// The max number of instances on each node; unlimited if None.
+    #[serde(default)]
+    pub max_instances_per_node: Option<u32>,
+    /// The defaults of the sessions of the application.
+    #[serde(default)]
+    pub session_defaults: Option<SessionDefaults>,
+    /// The health probe of the instances of the application.
+    #[serde(default)]
+    pub health_probe: Option<HealthProbe>,
+    /// How the instances are shared among the sessions of the application.
+    #[serde(default)]
+    pub fairness: Option<FairnessPolicy>,
+    /// The lifecycle hooks of the instances of the application.
+    #[serde(default)]
+    pub hooks: Option<LifecycleHooks>,
+    /// The Python environment of the host instances of the application.
+    #[serde(default)]
+    pub python_env: Option<PythonEnvironment>,
+    /// The codec of the task inputs and outputs; raw bytes if None.
+    #[serde(default)]
+    pub codec: Option<PayloadCodec>,
+    /// The fully-qualified message type of the protobuf codec.
+    #[serde(default)]
+    pub payload_type: Option<String>,
+    /// How the instances are placed in the GPU/NUMA topology of the nodes.
+    #[serde(default)]
+    pub topology: Option<TopologyPolicy>,
+}
+
+/// The defaults of the sessions of an application, applied by Flame to the
+/// attributes that the session leaves unspecified, i.e. zero or None.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionDefaults {
+    #[serde(default)]
+    pub slots: Option<u32>,
+    #[serde(default)]
+    pub min_instances: Option<u32>,
+    #[serde(default)]
+    pub max_instances: Option<u32>,
+    #[serde(default)]
+    pub batch_size: Option<u32>,
+    #[serde(default)]
+    pub scratch_size: Option<u64>,
+    #[serde(default)]
+    pub max_task_attempts: Option<u32>,
+    #[serde(default)]
+    pub task_timeout: Option<u64>,
+    #[serde(default)]
+    pub task_priority: Option<u32>,
+}
+
+/// The health probe of the instances of an application; exactly one of
+/// `command` and `grpc_service` is set.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HealthProbe {
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub grpc_service: Option<String>,
+    #[serde(default)]
+    pub period_seconds: Option<u32>,
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+    #[serde(default)]
+    pub failure_threshold: Option<u32>,
+}
+
+/// The lifecycle hooks of the instances of an application, run by the executor
+/// manager on the node around the startup and the stop of the instance.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LifecycleHooks {
+    #[serde(default)]
+    pub pre_bind: Option<LifecycleHook>,
+    #[serde(default)]
+    pub post_bind: Option<LifecycleHook>,
+    #[serde(default)]
+    pub pre_release: Option<LifecycleHook>,
+}
+
+/// A lifecycle hook, i.e. a command run by `sh -c` in the working directory of
+/// the instance.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LifecycleHook {
+    pub command: String,
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+}
+
+/// The Python environment of the host instances of an application, built by uv
+/// on the node from either its project or its requirements file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PythonEnvironment {
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub requirements: Option<String>,
+    #[serde(default)]
+    pub python_version: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -176,8 +406,207 @@ pub struct Node {
     pub state: NodeState,
     pub cpu: u64,
     pub memory: u64,
+    pub allocatable_cpu: u64,
+    pub allocatable_memory: u64,
     pub arch: String,
     pub os: String,
+    /// The mean busy ratio of the executors on the node since its last heartbeat.
+    pub saturation: Option<f64>,
+    /// The executors on the node, and the max executors of the node if limited.
+    #[serde(default)]
+    pub executors: u32,
+    #[serde(default)]
+    pub max_executors: Option<u32>,
+    /// The time of the last heartbeat of the node, if any.
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    /// The recent transitions of the node's state, e.g. it was lost.
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// The NUMA nodes and the GPU groups detected on the node.
+    #[serde(default)]
+    pub numa_nodes: Vec<NumaNode>,
+    #[serde(default)]
+    pub gpu_groups: Vec<GpuGroup>,
+    /// The applications whose artifacts are prefetched on the node.
+    #[serde(default)]
+    pub prefetched: Vec<String>,
+}
+
+/// A NUMA node of a node, with its CPUs as a cpulist, e.g. "0-15,32-47".
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NumaNode {
+    pub id: u32,
+    pub cpus: String,
+}
+
+/// A group of GPUs of a node connected by NVLink or the same PCIe switch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GpuGroup {
+    pub gpus: Vec<u32>,
+    pub numa_node: Option<u32>,
+    /// How the GPUs are connected, i.e. "NVLink" or "PCIe".
+    pub link: String,
+}
+
+/// A one-time token for a node to join the cluster.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JoinToken {
+    pub token: String,
+    /// The node which joins with the token; any node if not set.
+    pub node_name: Option<String>,
+    #[serde(with = "serde_utc")]
+    pub expiration_time: DateTime<Utc>,
+}
+
+/// The state of the cluster which the scheduler persisted at a point in time.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ClusterSnapshot {
+    #[serde(with = "serde_utc")]
+    pub creation_time: DateTime<Utc>,
+    pub nodes: Vec<Node>,
+    pub sessions: Vec<Session>,
+    pub executors: Vec<Executor>,
+}
+
+/// The job to run a set of tasks to completion in its own session.
+#[derive(Clone, Debug, Default)]
+pub struct JobAttributes {
+    /// The ID of the job, which is also the ID of its session.
+    pub id: SessionID,
+    pub application: String,
+    pub slots: u32,
+    /// The inputs of the tasks, one task per input.
+    pub inputs: Vec<TaskInput>,
+    /// Generate the tasks whose inputs are their indexes, i.e. "0", "1"...,
+    /// instead of the inputs.
+    pub count: Option<u32>,
+    /// The max tasks of the job pending or running at the same time; all the
+    /// tasks are submitted at once if not set.
+    pub parallelism: Option<u32>,
+    pub completion_policy: CompletionPolicy,
+    /// The failed tasks tolerated before the job fails.
+    pub max_failed: u32,
+    pub common_data: Option<CommonData>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub id: SessionID,
+    pub application: String,
+    pub slots: u32,
+    pub parallelism: Option<u32>,
+    pub completion_policy: CompletionPolicy,
+    pub max_failed: u32,
+    pub state: JobState,
+    pub creation_time: DateTime<Utc>,
+    pub completion_time: Option<DateTime<Utc>>,
+    /// The session running the tasks of the job.
+    pub session_id: SessionID,
+    pub total: u32,
+    pub submitted: u32,
+    pub succeed: u32,
+    pub failed: u32,
+    /// Why the job failed or was cancelled.
+    pub message: Option<String>,
+}
+
+/// The share of a session by the fairshare plugin, in slots.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionShare {
+    pub desired: f64,
+    pub deserved: f64,
+    pub allocated: f64,
+    /// The slots backfilled beyond the deserved share.
+    pub elastic: f64,
+}
+
+/// The verdicts of a scheduler plugin on a session; None if the plugin has no opinion.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PluginVerdict {
+    pub plugin: String,
+    pub underused: Option<bool>,
+    pub preemptible: Option<bool>,
+    pub ready: Option<bool>,
+}
+
+/// The state of an open session in the scheduler.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionSchedulingState {
+    pub session_id: SessionID,
+    /// The order of the session in the cycle, from 0 which is scheduled first.
+    pub rank: u32,
+    pub share: Option<SessionShare>,
+    pub verdicts: Vec<PluginVerdict>,
+    /// The verdicts combined over the plugins, which the actions act on.
+    pub underused: bool,
+    pub preemptible: bool,
+    pub ready: bool,
+}
+
+/// The state of the scheduler plugins at the end of the latest scheduling cycle.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SchedulerState {
+    #[serde(with = "serde_utc")]
+    pub creation_time: DateTime<Utc>,
+    /// The enabled plugins in order.
+    pub plugins: Vec<String>,
+    /// The open sessions in the order of the cycle.
+    pub sessions: Vec<SessionSchedulingState>,
+}
+
+/// The slots of a node taken by its executors.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeUtilization {
+    pub name: String,
+    pub state: NodeState,
+    /// The slots of the allocatable resources of the node.
+    pub total_slots: u32,
+    pub used_slots: u32,
+    pub executors: u32,
+    /// The executors running a task.
+    pub running_executors: u32,
+    /// The mean busy ratio of the executors by the last heartbeat of the node.
+    pub saturation: Option<f64>,
+}
+
+/// The slots of an executor and the task it's running.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExecutorUtilization {
+    pub id: String,
+    pub node: String,
+    pub state: ExecutorState,
+    pub slots: u32,
+    pub session_id: Option<SessionID>,
+    pub task_id: Option<TaskID>,
+    /// The fraction of the time the executor was running tasks.
+    pub busy_ratio: Option<f64>,
+}
+
+/// The slots taken by an open session and its tasks.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionUtilization {
+    pub session_id: SessionID,
+    pub application: String,
+    pub executors: u32,
+    pub used_slots: u32,
+    pub pending_tasks: u32,
+    pub running_tasks: u32,
+    pub succeed_tasks: u32,
+    pub failed_tasks: u32,
+}
+
+/// The utilization of the cluster at a point in time.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ClusterUtilization {
+    #[serde(with = "serde_utc")]
+    pub creation_time: DateTime<Utc>,
+    /// The slots of the ready nodes, and the ones taken by the executors.
+    pub total_slots: u32,
+    pub used_slots: u32,
+    pub nodes: Vec<NodeUtilization>,
+    pub executors: Vec<ExecutorUtilization>,
+    pub sessions: Vec<SessionUtilization>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -186,16 +615,29 @@ pub enum NodeState {
     Unknown = 0,
     Ready = 1,
     NotReady = 2,
+    Lost = 3,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Session {
     #[serde(skip)]
     pub(crate) client: Option<FlameClient>,
+    /// The admin token of the connection which got the session, if any.
+    #[serde(skip)]
+    pub(crate) admin_token: Option<String>,
 
     pub id: SessionID,
     pub slots: u32,
     pub application: String,
+    /// The priority of the session in scheduling.
+    #[serde(default)]
+    pub priority: u32,
+    /// What happens to the outputs of the completed tasks.
+    #[serde(default)]
+    pub result_policy: ResultPolicy,
+    /// Seconds to keep the session after it's closed.
+    #[serde(default)]
+    pub ttl_after_completion: Option<u64>,
     #[serde(with = "serde_utc")]
     pub creation_time: DateTime<Utc>,
 
@@ -207,6 +649,200 @@ pub struct Session {
 
     pub events: Vec<Event>,
     pub tasks: Option<Vec<Task>>,
+    /// The durations of the completed tasks, collected by the session manager.
+    #[serde(default)]
+    pub task_durations: Option<TaskDurationStats>,
+    /// The reason why no ready node can run the application of the session;
+    /// `None` if the session is schedulable.
+    #[serde(default)]
+    pub unschedulable: Option<String>,
+    /// The trace ID of the request which created the session, if any.
+    #[serde(default)]
+    pub trace_id: Option<String>,
+}
+
+/// The statistics of task durations, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct TaskDurationStats {
+    pub count: u64,
+    pub mean: f64,
+    pub min: u64,
+    pub max: u64,
+    pub recent: f64,
+}
+
+impl From<rpc::TaskDurationStats> for TaskDurationStats {
+    fn from(stats: rpc::TaskDurationStats) -> Self {
+        Self {
+            count: stats.count,
+            mean: stats.mean,
+            min: stats.min,
+            max: stats.max,
+            recent: stats.recent,
+        }
+    }
+}
+
+/// The aggregated statistics of the tasks in a session, computed by the session manager.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: SessionID,
+    pub pending: i32,
+    pub running: i32,
+    pub succeed: i32,
+    pub failed: i32,
+    pub cancelled: i32,
+    #[serde(default)]
+    pub quarantined: i32,
+    /// The window in minutes of the throughput.
+    pub window: u32,
+    /// The tasks completed within the window.
+    pub completed: u64,
+    /// The completed tasks per minute within the window.
+    pub throughput: f64,
+    /// The average duration in milliseconds of the completed tasks.
+    pub average_duration: Option<f64>,
+    /// The failed tasks by category, the most frequent first.
+    pub failures: Vec<TaskFailureSummary>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskFailureSummary {
+    pub category: String,
+    pub count: u64,
+    pub message: Option<String>,
+    pub reason: Option<FailureReason>,
+}
+
+/// The recommended slots of a session, or of an application if no session,
+/// by the resource usage of its tasks.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SlotRecommendation {
+    pub application: String,
+    pub session_id: Option<SessionID>,
+    /// The tasks with the measured usage.
+    pub tasks: u64,
+    /// The CPUs used by 95% of the tasks.
+    pub cpu: f64,
+    /// The memory in bytes used by 95% of the tasks.
+    pub memory: u64,
+    pub slots: u32,
+    pub recommended_slots: u32,
+    pub message: String,
+}
+
+impl From<rpc::SlotRecommendation> for SlotRecommendation {
+    fn from(r: rpc::SlotRecommendation) -> Self {
+        Self {
+            application: r.application,
+            session_id: r.session_id.map(SessionID::from),
+            tasks: r.tasks,
+            cpu: r.cpu,
+            memory: r.memory,
+            slots: r.slots,
+            recommended_slots: r.recommended_slots,
+            message: r.message,
+        }
+    }
+}
+
+/// The effective settings of a task, or of the tasks of a session without
+/// their own settings; the sources are one of `cluster`, `application`,
+/// `session` and `task`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskSettings {
+    pub session_id: SessionID,
+    pub task_id: Option<TaskID>,
+    /// The timeout in seconds of running the task; no timeout if None.
+    pub timeout: Option<u64>,
+    pub timeout_source: String,
+    pub max_attempts: u32,
+    pub max_attempts_source: String,
+    pub priority: u32,
+    pub priority_source: String,
+}
+
+impl From<rpc::TaskSettings> for TaskSettings {
+    fn from(s: rpc::TaskSettings) -> Self {
+        Self {
+            session_id: SessionID::from(s.session_id),
+            task_id: s.task_id.and_then(|id| id.parse().ok()),
+            timeout: s.timeout,
+            timeout_source: s.timeout_source,
+            max_attempts: s.max_attempts,
+            max_attempts_source: s.max_attempts_source,
+            priority: s.priority,
+            priority_source: s.priority_source,
+        }
+    }
+}
+
+impl From<rpc::SessionSummary> for SessionSummary {
+    fn from(summary: rpc::SessionSummary) -> Self {
+        Self {
+            session_id: SessionID::from(summary.session_id),
+            pending: summary.pending,
+            running: summary.running,
+            succeed: summary.succeed,
+            failed: summary.failed,
+            cancelled: summary.cancelled,
+            quarantined: summary.quarantined,
+            window: summary.window,
+            completed: summary.completed,
+            throughput: summary.throughput,
+            average_duration: summary.average_duration,
+            failures: summary
+                .failures
+                .into_iter()
+                .map(|f| TaskFailureSummary {
+                    category: f.category,
+                    count: f.count,
+                    message: f.message,
+                    reason: f
+                        .reason
+                        .map(|r| FailureReason::try_from(r).unwrap_or(FailureReason::Unknown)),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The postmortem of the crashed instance which ran a task: how it exited,
+/// the tail of its stderr, and the context of its node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TaskPostmortem {
+    pub executor_id: String,
+    pub node: String,
+    /// The exit code of the instance, if it exited by itself.
+    pub exit_code: Option<i32>,
+    /// The signal which terminated the instance, if any.
+    pub signal: Option<i32>,
+    pub core_dumped: bool,
+    /// The tail of the stderr of the instance.
+    pub stderr_tail: Option<String>,
+    /// The load average of the last minute of the node.
+    pub load_average: Option<f64>,
+    /// The available memory of the node in bytes.
+    pub memory_available: Option<u64>,
+    #[serde(with = "serde_utc")]
+    pub creation_time: DateTime<Utc>,
+}
+
+impl From<rpc::TaskPostmortem> for TaskPostmortem {
+    fn from(postmortem: rpc::TaskPostmortem) -> Self {
+        Self {
+            executor_id: postmortem.executor_id,
+            node: postmortem.node,
+            exit_code: postmortem.exit_code,
+            signal: postmortem.signal,
+            core_dumped: postmortem.core_dumped,
+            stderr_tail: postmortem.stderr_tail,
+            load_average: postmortem.load_average,
+            memory_available: postmortem.memory_available,
+            creation_time: DateTime::from_timestamp(postmortem.creation_time, 0)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -218,8 +854,37 @@ pub struct Task {
 
     #[serde(with = "serde_message")]
     pub input: Option<TaskInput>,
+    /// The input in the object cache instead of the inline input, if any.
+    #[serde(default)]
+    pub input_ref: Option<ObjectReference>,
     #[serde(with = "serde_message")]
     pub output: Option<TaskOutput>,
+    /// The output in the object cache instead of the inline output, by the
+    /// result policy of the session.
+    #[serde(default)]
+    pub output_ref: Option<ObjectReference>,
+    /// The named output artifacts of the task, besides the output.
+    #[serde(default)]
+    pub artifacts: Vec<TaskArtifact>,
+    /// The reason of the failure, only for the failed tasks.
+    #[serde(default)]
+    pub failure_reason: Option<FailureReason>,
+    /// The times the task was dispatched again after its executor was lost.
+    #[serde(default)]
+    pub attempts: u32,
+    /// The resources used to run the task, if measured.
+    #[serde(default)]
+    pub usage: Option<TaskUsage>,
+    /// The latest checkpoint of the task, i.e. the reference of an object in
+    /// the object cache.
+    #[serde(default)]
+    pub checkpoint: Option<String>,
+    /// The postmortem of the latest crash of the instance running the task, if any.
+    #[serde(default)]
+    pub postmortem: Option<TaskPostmortem>,
+    /// The trace ID of the request which created the task, if any.
+    #[serde(default)]
+    pub trace_id: Option<String>,
 
     pub events: Vec<Event>,
 }
@@ -247,29 +912,72 @@ impl Task {
     pub fn is_cancelled(&self) -> bool {
         self.state == TaskState::Cancelled
     }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.state == TaskState::Quarantined
+    }
+
+    /// Decodes the inline output of the task by the codec of the application;
+    /// None if the task has no inline output.
+    pub fn decode_output<T: serde::de::DeserializeOwned>(
+        &self,
+        codec: PayloadCodec,
+    ) -> Result<Option<T>, FlameError> {
+        self.output
+            .as_ref()
+            .map(|output| codec.decode(output))
+            .transpose()
+    }
 }
 
 impl Connection {
+    /// Presents the admin token in the administrative requests, e.g. minting
+    /// the join tokens or disabling an application.
+    pub fn with_admin_token(mut self, token: Option<String>) -> Self {
+        self.admin_token = token;
+        self
+    }
+
+    /// The request carrying the admin token of the connection, if any.
+    fn admin_request<T>(&self, message: T) -> Request<T> {
+        let mut req = Request::new(message);
+        if let Some(value) = self.admin_token.as_ref().and_then(|t| t.parse().ok()) {
+            req.metadata_mut().insert(FLAME_ADMIN_TOKEN, value);
+        }
+        req
+    }
+
     pub async fn create_session(&self, attrs: &SessionAttributes) -> Result<Session, FlameError> {
         trace_fn!("Connection::create_session");
 
         let create_ssn_req = CreateSessionRequest {
-            session_id: attrs.id.clone(),
+            session_id: attrs.id.to_string(),
             session: Some(SessionSpec {
                 application: attrs.application.clone(),
                 slots: attrs.slots,
                 common_data: attrs.common_data.clone().map(CommonData::into),
                 min_instances: attrs.min_instances,
                 max_instances: attrs.max_instances,
-                batch_size: attrs.batch_size.max(1),
+                batch_size: attrs.batch_size,
+                scratch_size: attrs.scratch_size,
+                max_task_attempts: attrs.max_task_attempts,
+                task_timeout: attrs.task_timeout,
+                task_priority: attrs.task_priority,
+                priority: attrs.priority,
+                result_policy: attrs.result_policy.into(),
+                ttl_after_completion: attrs.ttl_after_completion,
+                ttl_after_completion: attrs.ttl_after_completion,
             }),
         };
 
         let mut client = FlameClient::new(self.channel.clone());
-        let ssn = client.create_session(create_ssn_req).await?;
+        let ssn = client
+            .create_session(traced_request(create_ssn_req))
+            .await?;
         let inner_ssn = ssn.into_inner();
         let mut ssn = Session::try_from(&inner_ssn)?;
         ssn.client = Some(client);
+        ssn.admin_token = self.admin_token.clone();
         Ok(ssn)
     }
 
@@ -285,6 +993,45 @@ impl Connection {
             .collect::<Result<Vec<Session>, FlameError>>()
     }
 
+    /// Lists the recommended slots of the sessions of the application, or of all
+    /// applications, by the resource usage of their tasks.
+    pub async fn list_slot_recommendations(
+        &self,
+        application: Option<&str>,
+    ) -> Result<Vec<SlotRecommendation>, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let list = client
+            .list_slot_recommendations(ListSlotRecommendationsRequest {
+                application: application.map(str::to_string),
+            })
+            .await?;
+
+        Ok(list
+            .into_inner()
+            .recommendations
+            .into_iter()
+            .map(SlotRecommendation::from)
+            .collect())
+    }
+
+    /// Gets the effective settings of the task, or of the tasks of the session
+    /// without their own settings if no task, with where each one comes from.
+    pub async fn get_task_settings(
+        &self,
+        id: &SessionID,
+        task_id: Option<&TaskID>,
+    ) -> Result<TaskSettings, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let settings = client
+            .get_task_settings(GetTaskSettingsRequest {
+                session_id: id.to_string(),
+                task_id: task_id.map(TaskID::to_string),
+            })
+            .await?;
+
+        Ok(TaskSettings::from(settings.into_inner()))
+    }
+
     pub async fn get_session(&self, id: &SessionID) -> Result<Session, FlameError> {
         let mut client = FlameClient::new(self.channel.clone());
         let ssn = client
@@ -296,6 +1043,7 @@ impl Connection {
         let inner_ssn = ssn.into_inner();
         let mut ssn = Session::try_from(&inner_ssn)?;
         ssn.client = Some(client);
+        ssn.admin_token = self.admin_token.clone();
         Ok(ssn)
     }
 
@@ -303,6 +1051,49 @@ impl Connection {
         &self,
         id: &SessionID,
         spec: Option<&SessionAttributes>,
+    ) -> Result<Session, FlameError> {
+        self.open_session_with_replay(id, spec, None).await
+    }
+
+    /// Create the session `new_id` with the attributes of the session, and
+    /// resubmit the inputs of its tasks, or only of its failed tasks.
+    pub async fn resubmit_session(
+        &self,
+        id: &SessionID,
+        new_id: &SessionID,
+        only_failed: bool,
+    ) -> Result<Session, FlameError> {
+        trace_fn!("Connection::resubmit_session");
+        let mut client = FlameClient::new(self.channel.clone());
+        let ssn = client
+            .resubmit_session(traced_request(ResubmitSessionRequest {
+                session_id: id.to_string(),
+                new_session_id: new_id.to_string(),
+                only_failed,
+            }))
+            .await?;
+
+        let inner_ssn = ssn.into_inner();
+        let mut ssn = Session::try_from(&inner_ssn)?;
+        ssn.client = Some(client);
+        ssn.admin_token = self.admin_token.clone();
+        Ok(ssn)
+    }
+
+    /// Reopen the closed session, and replay its tasks by the policy.
+    pub async fn reopen_session(
+        &self,
+        id: &SessionID,
+        policy: ReplayPolicy,
+    ) -> Result<Session, FlameError> {
+        self.open_session_with_replay(id, None, Some(policy)).await
+    }
+
+    async fn open_session_with_replay(
+        &self,
+        id: &SessionID,
+        spec: Option<&SessionAttributes>,
+        replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         let session_spec = spec.map(|attrs| SessionSpec {
             application: attrs.application.clone(),
@@ -310,19 +1101,28 @@ impl Connection {
             common_data: attrs.common_data.clone().map(CommonData::into),
             min_instances: attrs.min_instances,
             max_instances: attrs.max_instances,
-            batch_size: attrs.batch_size.max(1),
+            batch_size: attrs.batch_size,
+            scratch_size: attrs.scratch_size,
+            max_task_attempts: attrs.max_task_attempts,
+            task_timeout: attrs.task_timeout,
+            task_priority: attrs.task_priority,
+            priority: attrs.priority,
+            result_policy: attrs.result_policy.into(),
+            ttl_after_completion: attrs.ttl_after_completion,
         });
 
         let open_ssn_req = OpenSessionRequest {
-            session_id: id.clone(),
+            session_id: id.to_string(),
             session: session_spec,
+            replay_policy: replay.map(|policy| policy as i32),
         };
 
         let mut client = FlameClient::new(self.channel.clone());
-        let ssn = client.open_session(open_ssn_req).await?;
+        let ssn = client.open_session(traced_request(open_ssn_req)).await?;
         let inner_ssn = ssn.into_inner();
         let mut ssn = Session::try_from(&inner_ssn)?;
         ssn.client = Some(client);
+        ssn.admin_token = self.admin_token.clone();
         Ok(ssn)
     }
 
@@ -331,6 +1131,7 @@ impl Connection {
         client
             .close_session(CloseSessionRequest {
                 session_id: id.to_string(),
+                force: None,
             })
             .await?;
 
@@ -350,7 +1151,7 @@ impl Connection {
         };
 
         let res = client
-            .register_application(Request::new(req))
+            .register_application(self.admin_request(req))
             .await?
             .into_inner();
 
@@ -374,7 +1175,44 @@ impl Connection {
         };
 
         let res = client
-            .update_application(Request::new(req))
+            .update_application(self.admin_request(req))
+            .await?
+            .into_inner();
+
+        if res.return_code < 0 {
+            Err(FlameError::Network(res.message.unwrap_or_default()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Enables the application to accept new sessions again.
+    pub async fn enable_application(&self, name: String) -> Result<(), FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+
+        let req = EnableApplicationRequest { name };
+
+        let res = client
+            .enable_application(self.admin_request(req))
+            .await?
+            .into_inner();
+
+        if res.return_code < 0 {
+            Err(FlameError::Network(res.message.unwrap_or_default()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Disables the application to reject new sessions; its existing sessions
+    /// are running to completion.
+    pub async fn disable_application(&self, name: String) -> Result<(), FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+
+        let req = DisableApplicationRequest { name };
+
+        let res = client
+            .disable_application(self.admin_request(req))
             .await?
             .into_inner();
 
@@ -391,7 +1229,7 @@ impl Connection {
         let req = UnregisterApplicationRequest { name };
 
         let res = client
-            .unregister_application(Request::new(req))
+            .unregister_application(self.admin_request(req))
             .await?
             .into_inner();
 
@@ -459,25 +1297,188 @@ impl Connection {
             .ok_or(FlameError::NotFound(format!("node <{}> not found", name)))?;
         Ok(Node::from(&node))
     }
-}
 
-impl Session {
-    pub async fn create_task(&self, input: Option<TaskInput>) -> Result<Task, FlameError> {
-        trace_fn!("Session::create_task");
-        let mut client = self
-            .client
-            .clone()
-            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+    /// Mints a one-time token for a node to join the cluster, which is bound
+    /// to the node if its name is set; the token expires after the ttl, or
+    /// the default one of the cluster.
+    pub async fn create_join_token(
+        &self,
+        node_name: Option<&str>,
+        ttl: Option<std::time::Duration>,
+    ) -> Result<JoinToken, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let token = client
+            .create_join_token(self.admin_request(CreateJoinTokenRequest {
+                node_name: node_name.map(str::to_string),
+                ttl: ttl.map(|ttl| ttl.as_secs().max(1)),
+            }))
+            .await?;
+        JoinToken::try_from(&token.into_inner())
+    }
 
-        let create_task_req = CreateTaskRequest {
-            task: Some(TaskSpec {
-                session_id: self.id.clone(),
-                input: input.map(|input| input.to_vec()),
-                output: None,
-            }),
+    /// Gets the state of the cluster from its history, i.e. the latest snapshot
+    /// taken at or before the time, or the latest one if no time.
+    pub async fn get_cluster_snapshot(
+        &self,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<ClusterSnapshot, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let snapshot = client
+            .get_cluster_snapshot(self.admin_request(GetClusterSnapshotRequest {
+                at: at.map(|at| at.timestamp_millis()),
+            }))
+            .await?;
+        ClusterSnapshot::try_from(&snapshot.into_inner())
+    }
+
+    /// Gets the state of the scheduler plugins in the latest scheduling cycle,
+    /// e.g. to diagnose why a session is not scheduled.
+    pub async fn get_scheduler_state(&self) -> Result<SchedulerState, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let state = client
+            .get_scheduler_state(self.admin_request(GetSchedulerStateRequest {}))
+            .await?;
+        SchedulerState::try_from(state.into_inner())
+    }
+
+    /// Gets the slots of the nodes, the executors and the open sessions taken
+    /// at present, e.g. for `flmctl top`.
+    pub async fn get_cluster_utilization(&self) -> Result<ClusterUtilization, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let utilization = client
+            .get_cluster_utilization(GetClusterUtilizationRequest {})
+            .await?;
+        ClusterUtilization::try_from(utilization.into_inner())
+    }
+
+    /// Creates the job, whose session and tasks are managed by the session
+    /// manager until the job is completed.
+    pub async fn create_job(&self, attrs: &JobAttributes) -> Result<Job, FlameError> {
+        trace_fn!("Connection::create_job");
+        let mut client = FlameClient::new(self.channel.clone());
+        let job = client
+            .create_job(CreateJobRequest {
+                job_id: attrs.id.to_string(),
+                job: Some(JobSpec {
+                    application: attrs.application.clone(),
+                    slots: attrs.slots,
+                    inputs: attrs.inputs.iter().map(|input| input.to_vec()).collect(),
+                    count: attrs.count,
+                    parallelism: attrs.parallelism,
+                    completion_policy: attrs.completion_policy as i32,
+                    max_failed: attrs.max_failed,
+                    common_data: attrs.common_data.clone().map(CommonData::into),
+                }),
+            })
+            .await?;
+        Job::try_from(&job.into_inner())
+    }
+
+    pub async fn get_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let job = client
+            .get_job(GetJobRequest {
+                job_id: id.to_string(),
+            })
+            .await?;
+        Job::try_from(&job.into_inner())
+    }
+
+    pub async fn list_job(&self) -> Result<Vec<Job>, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let jobs = client.list_job(ListJobRequest {}).await?;
+        jobs.into_inner().jobs.iter().map(Job::try_from).collect()
+    }
+
+    /// Cancels the job, and its remaining tasks by closing its session.
+    pub async fn cancel_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        let mut client = FlameClient::new(self.channel.clone());
+        let job = client
+            .cancel_job(CancelJobRequest {
+                job_id: id.to_string(),
+            })
+            .await?;
+        Job::try_from(&job.into_inner())
+    }
+}
+
+impl Session {
+    pub async fn create_task(&self, input: Option<TaskInput>) -> Result<Task, FlameError> {
+        self.create_task_with_overrides(input, TaskOverrides::default())
+            .await
+    }
+
+    /// Creates a task with the environment variables and arguments to inject
+    /// for its invocation; they must be allowed by the application.
+    pub async fn create_task_with_overrides(
+        &self,
+        input: Option<TaskInput>,
+        overrides: TaskOverrides,
+    ) -> Result<Task, FlameError> {
+        self._create_task(input, None, overrides, None).await
+    }
+
+    /// Creates a task whose input is the value encoded by the codec of the
+    /// application; the input is tagged by the codec, so the session manager
+    /// rejects it if the application has another codec.
+    pub async fn create_typed_task<T: serde::Serialize>(
+        &self,
+        codec: PayloadCodec,
+        input: &T,
+    ) -> Result<Task, FlameError> {
+        let input = codec.encode(input)?;
+        self._create_task(Some(input), None, TaskOverrides::default(), Some(codec))
+            .await
+    }
+
+    /// Creates a task whose input is the object in the object cache; the
+    /// instance fetches it from the cache, so the large inputs are not sent
+    /// through the session manager.
+    pub async fn create_task_with_input_ref(
+        &self,
+        input_ref: ObjectReference,
+        overrides: TaskOverrides,
+    ) -> Result<Task, FlameError> {
+        self._create_task(None, Some(input_ref), overrides, None)
+            .await
+    }
+
+    async fn _create_task(
+        &self,
+        input: Option<TaskInput>,
+        input_ref: Option<ObjectReference>,
+        overrides: TaskOverrides,
+        codec: Option<PayloadCodec>,
+    ) -> Result<Task, FlameError> {
+        trace_fn!("Session::create_task");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let create_task_req = CreateTaskRequest {
+            task: Some(TaskSpec {
+                session_id: self.id.to_string(),
+                input: input.map(|input| input.to_vec()),
+                output: None,
+                environments: overrides
+                    .environments
+                    .into_iter()
+                    .map(|(name, value)| Environment { name, value })
+                    .collect(),
+                arguments: overrides.arguments,
+                artifacts: vec![],
+                input_ref: input_ref.map(rpc::ObjectReference::from),
+                output_ref: None,
+                timeout: overrides.timeout,
+                max_attempts: overrides.max_attempts,
+                priority: overrides.priority,
+                deadline: overrides.deadline,
+                codec: codec.map(|c| c as i32),
+            }),
         };
 
-        let task = client.create_task(create_task_req).await?;
+        let task = client.create_task(traced_request(create_task_req)).await?;
 
         let inner = task.into_inner();
         Task::try_from(&inner)
@@ -491,8 +1492,8 @@ impl Session {
             .ok_or(FlameError::Internal("no flame client".to_string()))?;
 
         let get_task_req = GetTaskRequest {
-            session_id: self.id.clone(),
-            task_id: id.clone(),
+            session_id: self.id.to_string(),
+            task_id: id.to_string(),
         };
         let task = client.get_task(get_task_req).await?;
 
@@ -500,6 +1501,51 @@ impl Session {
         Task::try_from(&inner)
     }
 
+    /// Release the quarantined task back to pending, so it is dispatched again;
+    /// it's an administrative request, which carries the admin token of the
+    /// connection of the session.
+    pub async fn release_task(&self, id: &TaskID) -> Result<Task, FlameError> {
+        trace_fn!("Session::release_task");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let release_task_req = ReleaseTaskRequest {
+            session_id: self.id.to_string(),
+            task_id: id.to_string(),
+        };
+        let mut req = Request::new(release_task_req);
+        if let Some(value) = self.admin_token.as_ref().and_then(|t| t.parse().ok()) {
+            req.metadata_mut().insert(FLAME_ADMIN_TOKEN, value);
+        }
+        let task = client.release_task(req).await?;
+
+        let inner = task.into_inner();
+        Task::try_from(&inner)
+    }
+
+    /// Record the latest checkpoint of the running task, i.e. the reference of
+    /// an object in the object cache; it's passed to the next invocation of the
+    /// task after a retry, so it resumes from the checkpoint.
+    pub async fn checkpoint_task(&self, id: &TaskID, checkpoint: &str) -> Result<Task, FlameError> {
+        trace_fn!("Session::checkpoint_task");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let checkpoint_task_req = CheckpointTaskRequest {
+            session_id: self.id.to_string(),
+            task_id: id.to_string(),
+            checkpoint: checkpoint.to_string(),
+        };
+        let task = client.checkpoint_task(checkpoint_task_req).await?;
+
+        let inner = task.into_inner();
+        Task::try_from(&inner)
+    }
+
     pub async fn list_tasks(&self) -> Result<Vec<Task>, FlameError> {
         // TODO (k82cn): Add top n tasks to avoid memory overflow.
         trace_fn!("Session::list_task");
@@ -549,8 +1595,8 @@ impl Session {
             .ok_or(FlameError::Internal("no flame client".to_string()))?;
 
         let watch_task_req = WatchTaskRequest {
-            session_id,
-            task_id,
+            session_id: session_id.to_string(),
+            task_id: task_id.to_string(),
         };
         let mut task_stream = client.watch_task(watch_task_req).await?.into_inner();
         while let Some(task) = task_stream.next().await {
@@ -571,7 +1617,61 @@ impl Session {
         Ok(())
     }
 
+    /// Watches the state transitions of the tasks of the session, including the
+    /// tasks created afterwards, instead of polling them by `get_task`; the
+    /// current states of its tasks are the first updates, and the stream ends
+    /// once the session is closed and its tasks are completed.
+    pub async fn watch(&self) -> Result<impl Stream<Item = Result<Task, FlameError>>, FlameError> {
+        trace_fn!("Session::watch");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let req = WatchSessionRequest {
+            session_id: self.id.to_string(),
+        };
+        let stream = client.watch_session(req).await?.into_inner();
+
+        Ok(stream.map(|task| {
+            task.map_err(FlameError::from)
+                .and_then(|task| Task::try_from(&task))
+        }))
+    }
+
+    /// Watches the output chunks streamed by the running task, e.g. its
+    /// progress; the stream ends when the task is completed, and its result is
+    /// got by `get_task` or `watch_task`. The chunks streamed before the watch
+    /// are replayed if they're still buffered by the session manager.
+    pub async fn watch_task_output(
+        &self,
+        task_id: TaskID,
+    ) -> Result<impl Stream<Item = Result<TaskOutputChunk, FlameError>>, FlameError> {
+        trace_fn!("Session::watch_task_output");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let req = WatchTaskOutputRequest {
+            session_id: self.id.to_string(),
+            task_id: task_id.to_string(),
+        };
+        let stream = client.watch_task_output(req).await?.into_inner();
+
+        Ok(stream.map(|chunk| chunk.map(TaskOutputChunk::from).map_err(FlameError::from)))
+    }
+
+    /// Closes the session without waiting for its running tasks; it's closed
+    /// once they're completed, see `wait_closed`.
     pub async fn close(&self) -> Result<(), FlameError> {
+        self.close_with_force(false).await?;
+        Ok(())
+    }
+
+    /// Closes the session, and returns it; it's `Closing` until its running
+    /// tasks are completed, unless they're cancelled by `force`.
+    pub async fn close_with_force(&self, force: bool) -> Result<Session, FlameError> {
         trace_fn!("Session::close");
         let mut client = self
             .client
@@ -579,12 +1679,151 @@ impl Session {
             .ok_or(FlameError::Internal("no flame client".to_string()))?;
 
         let close_ssn_req = CloseSessionRequest {
-            session_id: self.id.clone(),
+            session_id: self.id.to_string(),
+            force: Some(force),
+        };
+
+        let ssn = client.close_session(close_ssn_req).await?;
+        let mut ssn = Session::try_from(&ssn.into_inner())?;
+        ssn.client = Some(client);
+        ssn.admin_token = self.admin_token.clone();
+        Ok(ssn)
+    }
+
+    /// Waits until the closing session is closed, i.e. its running tasks are
+    /// completed; `on_progress` is called with the session whenever its
+    /// running tasks change.
+    pub async fn wait_closed(
+        &self,
+        mut on_progress: impl FnMut(&Session),
+    ) -> Result<Session, FlameError> {
+        trace_fn!("Session::wait_closed");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let mut running = None;
+        loop {
+            let ssn = client
+                .get_session(GetSessionRequest {
+                    session_id: self.id.to_string(),
+                })
+                .await?;
+            let mut ssn = Session::try_from(&ssn.into_inner())?;
+            if running != Some(ssn.running) {
+                running = Some(ssn.running);
+                on_progress(&ssn);
+            }
+            if ssn.state != SessionState::Closing {
+                ssn.client = Some(client);
+                ssn.admin_token = self.admin_token.clone();
+                return Ok(ssn);
+            }
+
+            tokio::time::sleep(CLOSE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Gets the aggregated statistics of the tasks without listing them; the
+    /// throughput is over the last `window` minutes, 5 minutes by default.
+    pub async fn summary(&self, window: Option<u32>) -> Result<SessionSummary, FlameError> {
+        trace_fn!("Session::summary");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let summary_req = GetSessionSummaryRequest {
+            session_id: self.id.to_string(),
+            window,
         };
+        let summary = client.get_session_summary(summary_req).await?;
 
-        client.close_session(close_ssn_req).await?;
+        Ok(SessionSummary::from(summary.into_inner()))
+    }
 
-        Ok(())
+    /// Queries a page of the events of the session and its tasks without
+    /// listing the tasks; the next page is queried by the `next_page_token`
+    /// of the page, and the page size is 100 by default.
+    pub async fn list_events(
+        &self,
+        filter: &EventFilter,
+        page_size: Option<u32>,
+        page_token: Option<&str>,
+    ) -> Result<EventPage, FlameError> {
+        trace_fn!("Session::list_events");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let list_events_req = ListEventsRequest {
+            session_id: self.id.to_string(),
+            since: filter.since.map(|t| t.timestamp()),
+            until: filter.until.map(|t| t.timestamp()),
+            codes: filter.codes.clone(),
+            owner: filter.owner.map(|owner| owner as i32),
+            task_id: filter.task_id.as_ref().map(|id| id.to_string()),
+            page_size,
+            page_token: page_token.map(str::to_string),
+        };
+        let list = client.list_events(list_events_req).await?;
+
+        EventPage::try_from(list.into_inner())
+    }
+
+    /// Renews the client lease of the session, and returns its grace period;
+    /// once renewed, the session is closed by the session manager if the lease
+    /// is not renewed within the grace period, e.g. the client crashed.
+    pub async fn renew_lease(&self) -> Result<std::time::Duration, FlameError> {
+        trace_fn!("Session::renew_lease");
+        let mut client = self
+            .client
+            .clone()
+            .ok_or(FlameError::Internal("no flame client".to_string()))?;
+
+        let renew_lease_req = RenewSessionLeaseRequest {
+            session_id: self.id.to_string(),
+        };
+        let resp = client.renew_session_lease(renew_lease_req).await?;
+
+        Ok(std::time::Duration::from_secs(
+            resp.into_inner().grace_period,
+        ))
+    }
+
+    /// Heartbeats the session ownership in the background until the returned
+    /// lease is dropped; the lease is renewed three times per grace period.
+    pub async fn keep_alive(&self) -> Result<SessionLease, FlameError> {
+        let grace_period = self.renew_lease().await?;
+
+        let ssn = self.clone();
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(grace_period / 3);
+            // The first tick completes immediately, and the lease was just renewed.
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = ssn.renew_lease().await {
+                    tracing::warn!("Failed to renew lease of session <{}>: {e}", ssn.id);
+                }
+            }
+        });
+
+        Ok(SessionLease { handle })
+    }
+}
+
+/// The heartbeats of the session ownership, which stop when it's dropped.
+pub struct SessionLease {
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for SessionLease {
+    fn drop(&mut self) {
+        self.handle.abort();
     }
 }
 
@@ -612,11 +1851,22 @@ impl TryFrom<&rpc::Task> for Task {
             .collect::<Result<Vec<Event>, FlameError>>()?;
 
         Ok(Task {
-            id: metadata.id,
-            ssn_id: spec.session_id.clone(),
+            id: TaskID::from(metadata.id),
+            ssn_id: SessionID::from(spec.session_id.as_str()),
             input: spec.input.map(TaskInput::from),
+            input_ref: spec.input_ref.map(ObjectReference::from),
             output: spec.output.map(TaskOutput::from),
+            output_ref: spec.output_ref.map(ObjectReference::from),
+            artifacts: spec.artifacts.into_iter().map(TaskArtifact::from).collect(),
             state: TaskState::try_from(status.state).unwrap_or(TaskState::default()),
+            failure_reason: status
+                .failure_reason
+                .map(|r| FailureReason::try_from(r).unwrap_or(FailureReason::Unknown)),
+            attempts: status.attempts,
+            usage: status.usage.map(TaskUsage::from),
+            checkpoint: status.checkpoint,
+            postmortem: status.postmortem.map(TaskPostmortem::from),
+            trace_id: status.trace_id,
             events,
         })
     }
@@ -651,9 +1901,13 @@ impl TryFrom<&rpc::Session> for Session {
 
         Ok(Session {
             client: None,
-            id: metadata.id,
+            admin_token: None,
+            id: SessionID::from(metadata.id),
             slots: spec.slots,
             application: spec.application,
+            priority: spec.priority,
+            result_policy: ResultPolicy::try_from(spec.result_policy).unwrap_or_default(),
+            ttl_after_completion: spec.ttl_after_completion,
             creation_time,
             state: SessionState::try_from(status.state).unwrap_or(SessionState::default()),
             pending: status.pending,
@@ -662,6 +1916,9 @@ impl TryFrom<&rpc::Session> for Session {
             failed: status.failed,
             events,
             tasks: None,
+            task_durations: status.task_durations.map(TaskDurationStats::from),
+            unschedulable: status.unschedulable,
+            trace_id: status.trace_id,
         })
     }
 }
@@ -736,6 +1993,18 @@ impl From<ApplicationAttributes> for ApplicationSpec {
             delay_release: app.delay_release.map(|s| s.num_seconds()),
             schema: app.schema.clone().map(rpc::ApplicationSchema::from),
             url: app.url.clone(),
+            task_environments: app.task_environments.clone(),
+            task_arguments: app.task_arguments.clone(),
+            bundle_size: app.bundle_size,
+            max_instances_per_node: app.max_instances_per_node,
+            session_defaults: app.session_defaults.map(rpc::SessionDefaults::from),
+            health_probe: app.health_probe.map(rpc::HealthProbe::from),
+            fairness: app.fairness.map(|p| p as i32).unwrap_or(0),
+            hooks: app.hooks.map(rpc::LifecycleHooks::from),
+            python_env: app.python_env.map(rpc::PythonEnvironment::from),
+            codec: app.codec.map(|c| c as i32).unwrap_or(0),
+            payload_type: app.payload_type.clone(),
+            topology: app.topology.map(|p| p as i32).unwrap_or(0),
         }
     }
 }
@@ -762,6 +2031,137 @@ impl From<ApplicationSpec> for ApplicationAttributes {
             delay_release: app.delay_release.map(Duration::seconds),
             schema: app.schema.clone().map(ApplicationSchema::from),
             url: app.url.clone(),
+            task_environments: app.task_environments.clone(),
+            task_arguments: app.task_arguments.clone(),
+            bundle_size: app.bundle_size,
+            max_instances_per_node: app.max_instances_per_node,
+            session_defaults: app.session_defaults.map(SessionDefaults::from),
+            health_probe: app.health_probe.map(HealthProbe::from),
+            fairness: Some(FairnessPolicy::from(
+                rpc::FairnessPolicy::try_from(app.fairness)
+                    .unwrap_or(rpc::FairnessPolicy::RoundRobin),
+            )),
+            hooks: app.hooks.map(LifecycleHooks::from),
+            python_env: app.python_env.map(PythonEnvironment::from),
+            codec: Some(PayloadCodec::from(
+                rpc::PayloadCodec::try_from(app.codec).unwrap_or(rpc::PayloadCodec::Raw),
+            )),
+            payload_type: app.payload_type.clone(),
+            topology: Some(TopologyPolicy::from(
+                rpc::TopologyPolicy::try_from(app.topology).unwrap_or(rpc::TopologyPolicy::Ignore),
+            )),
+        }
+    }
+}
+
+impl From<SessionDefaults> for rpc::SessionDefaults {
+    fn from(defaults: SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<rpc::SessionDefaults> for SessionDefaults {
+    fn from(defaults: rpc::SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<HealthProbe> for rpc::HealthProbe {
+    fn from(probe: HealthProbe) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<rpc::HealthProbe> for HealthProbe {
+    fn from(probe: rpc::HealthProbe) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<LifecycleHooks> for rpc::LifecycleHooks {
+    fn from(hooks: LifecycleHooks) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.map(rpc::LifecycleHook::from),
+            post_bind: hooks.post_bind.map(rpc::LifecycleHook::from),
+            pre_release: hooks.pre_release.map(rpc::LifecycleHook::from),
+        }
+    }
+}
+
+impl From<rpc::LifecycleHooks> for LifecycleHooks {
+    fn from(hooks: rpc::LifecycleHooks) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.map(LifecycleHook::from),
+            post_bind: hooks.post_bind.map(LifecycleHook::from),
+            pre_release: hooks.pre_release.map(LifecycleHook::from),
+        }
+    }
+}
+
+impl From<LifecycleHook> for rpc::LifecycleHook {
+    fn from(hook: LifecycleHook) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<rpc::LifecycleHook> for LifecycleHook {
+    fn from(hook: rpc::LifecycleHook) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<PythonEnvironment> for rpc::PythonEnvironment {
+    fn from(env: PythonEnvironment) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
+        }
+    }
+}
+
+impl From<rpc::PythonEnvironment> for PythonEnvironment {
+    fn from(env: rpc::PythonEnvironment) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
         }
     }
 }
@@ -822,13 +2222,10 @@ impl From<&rpc::Node> for Node {
         let spec = n.spec.clone().unwrap_or_default();
         let status = n.status.clone().unwrap_or_default();
 
-        let state = match rpc::NodeState::try_from(status.state) {
-            Ok(rpc::NodeState::Ready) => NodeState::Ready,
-            Ok(rpc::NodeState::NotReady) => NodeState::NotReady,
-            _ => NodeState::Unknown,
-        };
+        let state = NodeState::from(status.state);
 
         let capacity = status.capacity.unwrap_or_default();
+        let allocatable = status.allocatable.unwrap_or_default();
         let info = status.info.unwrap_or_default();
 
         Node {
@@ -837,8 +2234,244 @@ impl From<&rpc::Node> for Node {
             state,
             cpu: capacity.cpu,
             memory: capacity.memory,
+            allocatable_cpu: allocatable.cpu,
+            allocatable_memory: allocatable.memory,
             arch: info.arch,
             os: info.os,
+            saturation: status.saturation,
+            executors: status.executors,
+            max_executors: status.max_executors,
+            last_heartbeat: (status.last_heartbeat_time > 0)
+                .then(|| DateTime::<Utc>::from_timestamp(status.last_heartbeat_time, 0))
+                .flatten(),
+            events: status
+                .events
+                .iter()
+                .filter_map(|event| Event::try_from(event).ok())
+                .collect(),
+            numa_nodes: info
+                .numa_nodes
+                .iter()
+                .map(|node| NumaNode {
+                    id: node.id,
+                    cpus: node.cpus.clone(),
+                })
+                .collect(),
+            gpu_groups: info
+                .gpu_groups
+                .iter()
+                .map(|group| GpuGroup {
+                    gpus: group.gpus.clone(),
+                    numa_node: group.numa_node,
+                    link: match group.link() {
+                        rpc::GpuLink::Pcie => "PCIe".to_string(),
+                        rpc::GpuLink::NvLink => "NVLink".to_string(),
+                    },
+                })
+                .collect(),
+            prefetched: info.prefetched,
+        }
+    }
+}
+
+impl TryFrom<&rpc::JoinToken> for JoinToken {
+    type Error = FlameError;
+
+    fn try_from(token: &rpc::JoinToken) -> Result<Self, Self::Error> {
+        let expiration_time = DateTime::<Utc>::from_timestamp(token.expiration_time, 0)
+            .ok_or(FlameError::Internal("invalid expiration time".to_string()))?;
+
+        Ok(JoinToken {
+            token: token.token.clone(),
+            node_name: token.node_name.clone(),
+            expiration_time,
+        })
+    }
+}
+
+impl TryFrom<&rpc::ClusterSnapshot> for ClusterSnapshot {
+    type Error = FlameError;
+
+    fn try_from(snapshot: &rpc::ClusterSnapshot) -> Result<Self, Self::Error> {
+        let creation_time = DateTime::<Utc>::from_timestamp_millis(snapshot.creation_time)
+            .ok_or(FlameError::Internal("invalid creation time".to_string()))?;
+
+        Ok(ClusterSnapshot {
+            creation_time,
+            nodes: snapshot.nodes.iter().map(Node::from).collect(),
+            sessions: snapshot
+                .sessions
+                .iter()
+                .map(Session::try_from)
+                .collect::<Result<Vec<Session>, FlameError>>()?,
+            executors: snapshot
+                .executors
+                .iter()
+                .map(Executor::try_from)
+                .collect::<Result<Vec<Executor>, FlameError>>()?,
+        })
+    }
+}
+
+impl TryFrom<&rpc::Job> for Job {
+    type Error = FlameError;
+
+    fn try_from(job: &rpc::Job) -> Result<Self, Self::Error> {
+        let metadata = job
+            .metadata
+            .clone()
+            .ok_or_else(|| FlameError::Internal("missing metadata in response".to_string()))?;
+        let spec = job
+            .spec
+            .clone()
+            .ok_or_else(|| FlameError::Internal("missing spec in response".to_string()))?;
+        let status = job
+            .status
+            .clone()
+            .ok_or_else(|| FlameError::Internal("missing status in response".to_string()))?;
+
+        let creation_time = DateTime::<Utc>::from_timestamp(status.creation_time, 0)
+            .ok_or_else(|| FlameError::Internal("invalid timestamp".to_string()))?;
+        let completion_time = status
+            .completion_time
+            .map(|t| {
+                DateTime::<Utc>::from_timestamp(t, 0)
+                    .ok_or_else(|| FlameError::Internal("invalid timestamp".to_string()))
+            })
+            .transpose()?;
+
+        Ok(Job {
+            id: SessionID::from(metadata.id),
+            application: spec.application,
+            slots: spec.slots,
+            parallelism: spec.parallelism,
+            completion_policy: CompletionPolicy::try_from(spec.completion_policy)
+                .unwrap_or_default(),
+            max_failed: spec.max_failed,
+            state: JobState::try_from(status.state).unwrap_or_default(),
+            creation_time,
+            completion_time,
+            session_id: SessionID::from(status.session_id),
+            total: status.total,
+            submitted: status.submitted,
+            succeed: status.succeed,
+            failed: status.failed,
+            message: status.message,
+        })
+    }
+}
+
+impl TryFrom<rpc::SchedulerState> for SchedulerState {
+    type Error = FlameError;
+
+    fn try_from(state: rpc::SchedulerState) -> Result<Self, Self::Error> {
+        let creation_time = DateTime::<Utc>::from_timestamp_millis(state.creation_time)
+            .ok_or(FlameError::Internal("invalid creation time".to_string()))?;
+
+        Ok(SchedulerState {
+            creation_time,
+            plugins: state.plugins,
+            sessions: state
+                .sessions
+                .into_iter()
+                .map(|ssn| SessionSchedulingState {
+                    session_id: SessionID::from(ssn.session_id),
+                    rank: ssn.rank,
+                    share: ssn.share.map(|share| SessionShare {
+                        desired: share.desired,
+                        deserved: share.deserved,
+                        allocated: share.allocated,
+                        elastic: share.elastic,
+                    }),
+                    verdicts: ssn
+                        .verdicts
+                        .into_iter()
+                        .map(|v| PluginVerdict {
+                            plugin: v.plugin,
+                            underused: v.underused,
+                            preemptible: v.preemptible,
+                            ready: v.ready,
+                        })
+                        .collect(),
+                    underused: ssn.underused,
+                    preemptible: ssn.preemptible,
+                    ready: ssn.ready,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<rpc::ClusterUtilization> for ClusterUtilization {
+    type Error = FlameError;
+
+    fn try_from(utilization: rpc::ClusterUtilization) -> Result<Self, Self::Error> {
+        let creation_time = DateTime::<Utc>::from_timestamp_millis(utilization.creation_time)
+            .ok_or(FlameError::Internal("invalid creation time".to_string()))?;
+
+        let executors = utilization
+            .executors
+            .into_iter()
+            .map(|e| {
+                let state = rpc::ExecutorState::try_from(e.state)
+                    .map_err(|_| FlameError::Internal("invalid executor state".to_string()))?
+                    .into();
+                Ok(ExecutorUtilization {
+                    id: e.id,
+                    node: e.node,
+                    state,
+                    slots: e.slots,
+                    session_id: e.session_id.map(SessionID::from),
+                    task_id: e.task_id.map(TaskID::from),
+                    busy_ratio: e.busy_ratio,
+                })
+            })
+            .collect::<Result<_, FlameError>>()?;
+
+        Ok(ClusterUtilization {
+            creation_time,
+            total_slots: utilization.total_slots,
+            used_slots: utilization.used_slots,
+            nodes: utilization
+                .nodes
+                .into_iter()
+                .map(|n| NodeUtilization {
+                    name: n.name,
+                    state: NodeState::from(n.state),
+                    total_slots: n.total_slots,
+                    used_slots: n.used_slots,
+                    executors: n.executors,
+                    running_executors: n.running_executors,
+                    saturation: n.saturation,
+                })
+                .collect(),
+            executors,
+            sessions: utilization
+                .sessions
+                .into_iter()
+                .map(|s| SessionUtilization {
+                    session_id: SessionID::from(s.session_id),
+                    application: s.application,
+                    executors: s.executors,
+                    used_slots: s.used_slots,
+                    pending_tasks: s.pending_tasks,
+                    running_tasks: s.running_tasks,
+                    succeed_tasks: s.succeed_tasks,
+                    failed_tasks: s.failed_tasks,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// The state of the node by its value in the protocol; unknown if invalid.
+impl From<i32> for NodeState {
+    fn from(state: i32) -> Self {
+        match rpc::NodeState::try_from(state) {
+            Ok(rpc::NodeState::Ready) => NodeState::Ready,
+            Ok(rpc::NodeState::NotReady) => NodeState::NotReady,
+            Ok(rpc::NodeState::Lost) => NodeState::Lost,
+            _ => NodeState::Unknown,
         }
     }
 }
@@ -893,7 +2526,7 @@ mod serde_utc {
     }
 }
 
-mod serde_message {
+pub(crate) mod serde_message {
     use bytes::Bytes;
     use prost::Message;
     use serde::{Deserialize, Deserializer, Serializer};
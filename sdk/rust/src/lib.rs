@@ -13,4 +13,6 @@ limitations under the License.
 
 pub mod apis;
 pub mod client;
+#[cfg(feature = "local")]
+pub mod local;
 pub mod service;
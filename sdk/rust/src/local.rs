@@ -0,0 +1,223 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The local mode, which runs a mini cluster in-process: a session manager
+//! with the memory storage and an executor manager with the host shim, so the
+//! applications and tests run Flame workloads end-to-end without any deployed
+//! services. It's enabled by the `local` feature, e.g.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), flame_rs::apis::FlameError> {
+//! let cluster = flame_rs::local::LocalCluster::start(4).await?;
+//! let conn = cluster.connect().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The cluster is stopped when it's dropped.
+
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use stdng::trace_fn;
+use tokio::runtime::{Builder, Runtime};
+use tokio_util::sync::CancellationToken;
+
+use common::ctx::FlameClusterContext;
+use flame_executor_manager::manager;
+use flame_executor_manager::resources::SlotPolicy;
+use flame_session_manager::Runtimes;
+
+use crate::apis::FlameError;
+use crate::client::{self, Connection, NodeState};
+
+/// The time to wait for the node of the local cluster to be ready.
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+const READY_INTERVAL: Duration = Duration::from_millis(100);
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// An in-process cluster with one node of the given slots.
+pub struct LocalCluster {
+    endpoint: String,
+    work_dir: PathBuf,
+    runtime: Option<Runtime>,
+    shutdown: CancellationToken,
+}
+
+impl LocalCluster {
+    /// Starts the cluster, and waits until its node is ready.
+    pub async fn start(slots: u32) -> Result<Self, FlameError> {
+        trace_fn!("LocalCluster::start");
+
+        let port = free_port()?;
+        let endpoint = format!("http://127.0.0.1:{port}");
+        let work_dir =
+            std::env::temp_dir().join(format!("flame-local-{}-{port}", std::process::id()));
+        let ctx = local_context(&endpoint, &work_dir)?;
+
+        // The components run in a dedicated runtime, so all their tasks, e.g.
+        // the executors, are stopped with the cluster.
+        let runtime = Builder::new_multi_thread()
+            .thread_name("flame-local")
+            .enable_all()
+            .build()
+            .map_err(|e| FlameError::Internal(format!("failed to build runtime: {e}")))?;
+        let shutdown = CancellationToken::new();
+
+        {
+            let ctx = ctx.clone();
+            let shutdown = shutdown.clone();
+            runtime.spawn(async move {
+                let res = flame_session_manager::run(ctx, Runtimes::current(), shutdown).await;
+                tracing::info!("Local session manager exited: {res:?}");
+            });
+        }
+
+        // The executor manager is restarted until the session manager is ready.
+        {
+            let policy = SlotPolicy::new(ctx.cluster.slot.clone(), None, Some(slots));
+            runtime.spawn(async move {
+                loop {
+                    match manager::run(&ctx, policy.clone()).await {
+                        Ok(()) => break,
+                        Err(e) => {
+                            tracing::debug!("Restart local executor manager: {e}");
+                            tokio::time::sleep(RESTART_DELAY).await;
+                        }
+                    }
+                }
+            });
+        }
+
+        let cluster = LocalCluster {
+            endpoint,
+            work_dir,
+            runtime: Some(runtime),
+            shutdown,
+        };
+        cluster.wait_ready().await?;
+
+        tracing::info!("Local cluster started at <{}>", cluster.endpoint);
+
+        Ok(cluster)
+    }
+
+    /// The endpoint of the session manager, e.g. for the clients in other processes.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    pub async fn connect(&self) -> Result<Connection, FlameError> {
+        client::connect(&self.endpoint).await
+    }
+
+    async fn wait_ready(&self) -> Result<(), FlameError> {
+        let ready = async {
+            loop {
+                if let Ok(conn) = self.connect().await {
+                    if let Ok(nodes) = conn.list_node().await {
+                        if nodes.iter().any(|n| n.state == NodeState::Ready) {
+                            return;
+                        }
+                    }
+                }
+                tokio::time::sleep(READY_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(READY_TIMEOUT, ready)
+            .await
+            .map_err(|_| {
+                FlameError::Network(format!(
+                    "local cluster at <{}> is not ready in {}s",
+                    self.endpoint,
+                    READY_TIMEOUT.as_secs()
+                ))
+            })
+    }
+}
+
+impl Drop for LocalCluster {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+        // The runtime can not be dropped in the async context.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+        let _ = fs::remove_dir_all(&self.work_dir);
+    }
+}
+
+/// The configuration of the local cluster: the memory storage, the host shim,
+/// and all the directories in the work directory.
+fn local_context(endpoint: &str, work_dir: &Path) -> Result<FlameClusterContext, FlameError> {
+    fs::create_dir_all(work_dir).map_err(|e| {
+        FlameError::Internal(format!("failed to create <{}>: {e}", work_dir.display()))
+    })?;
+
+    let dir = |name: &str| work_dir.join(name).to_string_lossy().to_string();
+    let config = format!(
+        r#"cluster:
+  name: local
+  endpoint: "{endpoint}"
+  storage: mem
+  executors:
+    shim: host
+paths:
+  work_dir: "{}"
+  events_dir: "{}"
+  cache_dir: "{}"
+  log_dir: "{}"
+  applications_dir: "{}"
+"#,
+        dir("work"),
+        dir("events"),
+        dir("cache"),
+        dir("logs"),
+        dir("applications"),
+    );
+
+    let file = work_dir.join("flame-cluster.yaml");
+    fs::write(&file, config)
+        .map_err(|e| FlameError::Internal(format!("failed to write <{}>: {e}", file.display())))?;
+
+    let ctx = FlameClusterContext::from_file(Some(file.to_string_lossy().to_string()))
+        .map_err(|e| FlameError::InvalidConfig(e.to_string()))?;
+    ctx.paths
+        .create_dirs()
+        .map_err(|e| FlameError::Internal(e.to_string()))?;
+
+    Ok(ctx)
+}
+
+/// A free port for the frontend of the session manager, whose next port is
+/// free too for its backend.
+fn free_port() -> Result<u16, FlameError> {
+    for _ in 0..10 {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| FlameError::Network(format!("failed to find a free port: {e}")))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| FlameError::Network(format!("failed to find a free port: {e}")))?
+            .port();
+        if port < u16::MAX && TcpListener::bind(("127.0.0.1", port + 1)).is_ok() {
+            return Ok(port);
+        }
+    }
+
+    Err(FlameError::Network(
+        "failed to find a free port".to_string(),
+    ))
+}
@@ -11,25 +11,44 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+#[cfg(not(unix))]
+use std::net::{Ipv4Addr, SocketAddr};
+#[cfg(not(unix))]
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+#[cfg(not(unix))]
+use tokio::net::TcpListener;
 #[cfg(unix)]
 use tokio::net::UnixListener;
+use tokio::sync::broadcast;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+#[cfg(not(unix))]
+use tokio_stream::wrappers::TcpListenerStream;
 #[cfg(unix)]
 use tokio_stream::wrappers::UnixListenerStream;
-#[cfg(unix)]
+use tokio_stream::Stream;
 use tonic::transport::Server;
-#[cfg(unix)]
 use tonic::{Request, Response, Status};
 
-#[cfg(unix)]
 use self::rpc::instance_server::{Instance, InstanceServer};
 use crate::apis::flame::v1 as rpc;
 
-use crate::apis::{CommonData, FlameError, TaskInput, TaskOutput};
+use crate::apis::{
+    CommonData, FlameError, ObjectReference, TaskInput, TaskOutput, TaskOverrides, TaskResult,
+};
 
-#[cfg(unix)]
+/// The endpoint of the instance: a Unix domain socket on Unix, otherwise the
+/// file of the TCP loopback address the instance listens on.
 const FLAME_INSTANCE_ENDPOINT: &str = "FLAME_INSTANCE_ENDPOINT";
+/// Set to "true" to register the gRPC reflection service, e.g. for grpcurl.
+const FLAME_GRPC_REFLECTION: &str = "FLAME_GRPC_REFLECTION";
+/// The output chunks buffered for the executor manager; the oldest ones are
+/// dropped if it's lagging behind.
+const TASK_OUTPUT_CAPACITY: usize = 1024;
 
 pub struct ApplicationContext {
     pub name: String,
@@ -41,12 +60,73 @@ pub struct SessionContext {
     pub session_id: String,
     pub application: ApplicationContext,
     pub common_data: Option<CommonData>,
+    /// The shared scratch directory of the session on this node, if requested.
+    pub scratch_dir: Option<String>,
 }
 
 pub struct TaskContext {
     pub task_id: String,
     pub session_id: String,
     pub input: Option<TaskInput>,
+    /// The input in the object cache instead of the inline input, if any; the
+    /// service fetches it from the cache when it's needed.
+    pub input_ref: Option<ObjectReference>,
+    /// The environment variables and arguments of this invocation. The
+    /// environment variables are not set in the process of the service, which
+    /// is shared by its invocations; the service reads them from here, or
+    /// passes them to the processes it spawns, e.g. by `Command::envs`.
+    pub overrides: TaskOverrides,
+    /// The latest checkpoint recorded by the previous invocation of the task,
+    /// i.e. the reference of an object in the object cache to resume from.
+    pub checkpoint: Option<String>,
+    /// The writer of the output chunks streamed by the task before its result.
+    pub output: TaskOutputWriter,
+}
+
+/// The writer of the output chunks of a running task, e.g. its progress, which
+/// are streamed to the watchers of the task by `Session::watch_task_output`
+/// before its result; the chunks are dropped if nobody watches them.
+#[derive(Clone, Default)]
+pub struct TaskOutputWriter {
+    session_id: String,
+    task_id: String,
+    sequence: Arc<AtomicU64>,
+    sender: Option<broadcast::Sender<rpc::TaskOutputChunk>>,
+}
+
+impl TaskOutputWriter {
+    fn new(ctx: &TaskContext, sender: broadcast::Sender<rpc::TaskOutputChunk>) -> Self {
+        Self {
+            session_id: ctx.session_id.clone(),
+            task_id: ctx.task_id.clone(),
+            sequence: Arc::new(AtomicU64::new(0)),
+            sender: Some(sender),
+        }
+    }
+
+    /// Writes a chunk of the output of the task.
+    pub fn write(&self, data: impl AsRef<[u8]>) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+
+        let chunk = rpc::TaskOutputChunk {
+            session_id: self.session_id.clone(),
+            task_id: self.task_id.clone(),
+            sequence: self.sequence.fetch_add(1, Ordering::Relaxed),
+            data: data.as_ref().to_vec(),
+        };
+        // The chunk is dropped if the executor manager doesn't watch the output.
+        let _ = sender.send(chunk);
+    }
+}
+
+pub struct PreemptContext {
+    pub session_id: String,
+    /// The task interrupted by the preemption, which is requeued afterwards.
+    pub task_id: Option<String>,
+    /// The seconds to checkpoint the state before the instance is stopped.
+    pub grace_period: u64,
 }
 
 #[tonic::async_trait]
@@ -54,18 +134,37 @@ pub trait FlameService: Send + Sync + 'static {
     async fn on_session_enter(&self, _: SessionContext) -> Result<(), FlameError>;
     async fn on_task_invoke(&self, _: TaskContext) -> Result<Option<TaskOutput>, FlameError>;
     async fn on_session_leave(&self) -> Result<(), FlameError>;
+
+    /// Invokes the task and returns its output with the named artifacts;
+    /// override it to emit artifacts, it returns the output of
+    /// `on_task_invoke` without artifacts by default.
+    async fn on_task_invoke_with_artifacts(
+        &self,
+        ctx: TaskContext,
+    ) -> Result<TaskResult, FlameError> {
+        self.on_task_invoke(ctx).await.map(TaskResult::from)
+    }
+
+    /// Called when the instance is preempted while a task is running; override
+    /// it to checkpoint the state, e.g. into the object cache, within the grace
+    /// period, so the requeued task resumes from it. It does nothing by default.
+    async fn on_preempt(&self, _: PreemptContext) -> Result<(), FlameError> {
+        Ok(())
+    }
 }
 
 pub type FlameServicePtr = Arc<dyn FlameService>;
 
-#[cfg(unix)]
 struct ShimService {
     service: FlameServicePtr,
+    output: broadcast::Sender<rpc::TaskOutputChunk>,
 }
 
-#[cfg(unix)]
 #[tonic::async_trait]
 impl Instance for ShimService {
+    type WatchTaskOutputStream =
+        Pin<Box<dyn Stream<Item = Result<rpc::TaskOutputChunk, Status>> + Send>>;
+
     async fn on_session_enter(
         &self,
         req: Request<rpc::SessionContext>,
@@ -95,19 +194,36 @@ impl Instance for ShimService {
         req: Request<rpc::TaskContext>,
     ) -> Result<Response<rpc::TaskResult>, Status> {
         tracing::debug!("ShimService::on_task_invoke");
-        let req = req.into_inner();
-        let resp = self.service.on_task_invoke(TaskContext::from(req)).await;
+        let mut ctx = TaskContext::from(req.into_inner());
+        ctx.output = TaskOutputWriter::new(&ctx, self.output.clone());
+        let resp = self.service.on_task_invoke_with_artifacts(ctx).await;
 
         match resp {
-            Ok(data) => Ok(Response::new(rpc::TaskResult {
+            Ok(result) => Ok(Response::new(rpc::TaskResult {
                 return_code: 0,
-                output: data.map(|d| d.into()),
+                output: result.output.map(|d| d.into()),
                 message: None,
+                artifacts: result
+                    .artifacts
+                    .into_iter()
+                    .map(rpc::TaskArtifact::from)
+                    .collect(),
+                failure_reason: None,
+                usage: None,
+                postmortem: None,
+                output_ref: None,
+                codec: None,
             })),
             Err(e) => Ok(Response::new(rpc::TaskResult {
                 return_code: -1,
                 output: None,
                 message: Some(e.to_string()),
+                artifacts: vec![],
+                failure_reason: Some(rpc::FailureReason::ApplicationError as i32),
+                usage: None,
+                postmortem: None,
+                output_ref: None,
+                codec: None,
             })),
         }
     }
@@ -130,33 +246,104 @@ impl Instance for ShimService {
             })),
         }
     }
+
+    async fn on_preempt(
+        &self,
+        req: Request<rpc::PreemptContext>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        tracing::debug!("ShimService::on_preempt");
+        let req = req.into_inner();
+        let resp = self.service.on_preempt(PreemptContext::from(req)).await;
+
+        match resp {
+            Ok(_) => Ok(Response::new(rpc::Result {
+                return_code: 0,
+                message: None,
+            })),
+            Err(e) => Ok(Response::new(rpc::Result {
+                return_code: -1,
+                message: Some(e.to_string()),
+            })),
+        }
+    }
+
+    async fn watch_task_output(
+        &self,
+        _: Request<rpc::EmptyRequest>,
+    ) -> Result<Response<Self::WatchTaskOutputStream>, Status> {
+        tracing::debug!("ShimService::watch_task_output");
+        let mut output = self.output.subscribe();
+
+        let (tx, rx) = mpsc::channel(128);
+        tokio::spawn(async move {
+            loop {
+                match output.recv().await {
+                    Ok(chunk) => {
+                        if tx.send(Ok(chunk)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        tracing::warn!("Dropped <{n}> output chunks of the lagging watcher.");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as Self::WatchTaskOutputStream
+        ))
+    }
 }
 
-#[cfg(unix)]
 pub async fn run(service: impl FlameService) -> Result<(), Box<dyn std::error::Error>> {
+    let (output, _) = broadcast::channel(TASK_OUTPUT_CAPACITY);
     let shim_service = ShimService {
         service: Arc::new(service),
+        output,
     };
 
     let endpoint = std::env::var(FLAME_INSTANCE_ENDPOINT)
         .map_err(|_| FlameError::InvalidConfig("FLAME_INSTANCE_ENDPOINT not found".to_string()))?;
 
-    let uds_stream = UnixListenerStream::new(UnixListener::bind(endpoint)?);
+    let reflection = match std::env::var(FLAME_GRPC_REFLECTION) {
+        Ok(v) if v.eq_ignore_ascii_case("true") => {
+            Some(::rpc::reflection::server(crate::apis::FILE_DESCRIPTOR_SET)?)
+        }
+        _ => None,
+    };
 
-    Server::builder()
+    let router = Server::builder()
         .add_service(InstanceServer::new(shim_service))
-        .serve_with_incoming(uds_stream)
-        .await?;
+        .add_optional_service(reflection);
+
+    #[cfg(unix)]
+    {
+        let uds_stream = UnixListenerStream::new(UnixListener::bind(endpoint)?);
+        router.serve_with_incoming(uds_stream).await?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        publish_addr(Path::new(&endpoint), listener.local_addr()?)?;
+        router
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await?;
+    }
 
     Ok(())
 }
 
+/// Writes the TCP loopback address of the instance to its endpoint, where the
+/// executor resolves it; the address is renamed into place, so the executor
+/// never reads a partial one.
 #[cfg(not(unix))]
-pub async fn run(_service: impl FlameService) -> Result<(), Box<dyn std::error::Error>> {
-    Err(FlameError::InvalidConfig(
-        "Unix domain sockets are not supported on this platform".to_string(),
-    )
-    .into())
+fn publish_addr(endpoint: &Path, addr: SocketAddr) -> std::io::Result<()> {
+    let tmp = endpoint.with_extension("tmp");
+    std::fs::write(&tmp, addr.to_string())?;
+    std::fs::rename(tmp, endpoint)
 }
 
 impl From<rpc::ApplicationContext> for ApplicationContext {
@@ -175,6 +362,7 @@ impl From<rpc::SessionContext> for SessionContext {
             session_id: ctx.session_id.clone(),
             application: ctx.application.map(ApplicationContext::from).unwrap(),
             common_data: ctx.common_data.map(|data| data.into()),
+            scratch_dir: ctx.scratch_dir.clone(),
         }
     }
 }
@@ -185,6 +373,30 @@ impl From<rpc::TaskContext> for TaskContext {
             task_id: ctx.task_id.clone(),
             session_id: ctx.session_id.clone(),
             input: ctx.input.map(|data| data.into()),
+            input_ref: ctx.input_ref.map(ObjectReference::from),
+            overrides: TaskOverrides {
+                environments: ctx
+                    .environments
+                    .into_iter()
+                    .map(|e| (e.name, e.value))
+                    .collect(),
+                arguments: ctx.arguments,
+                // The remaining budget of the task when it's invoked.
+                deadline: ctx.deadline,
+                ..TaskOverrides::default()
+            },
+            checkpoint: ctx.checkpoint,
+            output: TaskOutputWriter::default(),
+        }
+    }
+}
+
+impl From<rpc::PreemptContext> for PreemptContext {
+    fn from(ctx: rpc::PreemptContext) -> Self {
+        PreemptContext {
+            session_id: ctx.session_id,
+            task_id: ctx.task_id,
+            grace_period: ctx.grace_period,
         }
     }
 }
@@ -24,7 +24,7 @@ use futures::future::try_join_all;
 use stdng::new_ptr;
 
 use flame::{
-    apis::{FlameClientTls, FlameError, SessionState, TaskState},
+    apis::{FlameClientTls, FlameError, ResultPolicy, SessionState, TaskState},
     client::{SessionAttributes, Task, TaskInformer},
 };
 use flame_rs as flame;
@@ -97,13 +97,20 @@ async fn run_session(
     metrics: Arc<BenchmarkMetrics>,
 ) -> Result<(), FlameError> {
     let ssn_attr = SessionAttributes {
-        id: format!("benchmark-ssn-{}", session_id),
+        id: format!("benchmark-ssn-{}", session_id).into(),
         application: FLAME_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
 
     let ssn = conn.create_session(&ssn_attr).await?;
@@ -138,6 +145,7 @@ async fn benchmark_multi_session_throughput() -> Result<(), FlameError> {
     let metrics = Arc::new(BenchmarkMetrics::new());
     let tls_config = FlameClientTls {
         ca_file: Some(get_ca_cert_path()),
+        ..Default::default()
     };
     let conn = flame::client::connect_with_tls(FLAME_ADDR, Some(&tls_config)).await?;
 
@@ -24,7 +24,7 @@ use stdng::{lock_ptr, new_ptr};
 use flame_rs as flame;
 
 use flame::{
-    apis::{FlameClientTls, FlameError, SessionState, TaskState},
+    apis::{FlameClientTls, FlameError, ResultPolicy, SessionState, TaskID, TaskState},
     client::{ApplicationAttributes, ApplicationSchema, SessionAttributes, Task, TaskInformer},
 };
 
@@ -45,6 +45,7 @@ fn get_ca_cert_path() -> String {
 async fn get_connection() -> Result<flame::client::Connection, FlameError> {
     let tls_config = FlameClientTls {
         ca_file: Some(get_ca_cert_path()),
+        ..Default::default()
     };
     flame::client::connect_with_tls(FLAME_DEFAULT_ADDR, Some(&tls_config)).await
 }
@@ -76,13 +77,20 @@ async fn test_create_session() -> Result<(), FlameError> {
     let conn = get_connection().await?;
 
     let ssn_attr = SessionAttributes {
-        id: String::from("ssn-1-test-create-session"),
+        id: "ssn-1-test-create-session".into(),
         application: FLAME_DEFAULT_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn = conn.create_session(&ssn_attr).await?;
 
@@ -101,13 +109,20 @@ async fn test_create_multiple_sessions() -> Result<(), FlameError> {
 
     for i in 0..ssn_num {
         let ssn_attr = SessionAttributes {
-            id: format!("ssn-1-test-create-multiple-sessions-{}", i),
+            id: format!("ssn-1-test-create-multiple-sessions-{}", i).into(),
             application: FLAME_DEFAULT_APP.to_string(),
             slots: 1,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
         };
         let ssn = conn.create_session(&ssn_attr).await?;
 
@@ -124,13 +139,20 @@ async fn test_create_session_with_tasks() -> Result<(), FlameError> {
     let conn = get_connection().await?;
 
     let ssn_attr = SessionAttributes {
-        id: String::from("ssn-1-test-create-session-with-tasks"),
+        id: "ssn-1-test-create-session-with-tasks".into(),
         application: FLAME_DEFAULT_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn = conn.create_session(&ssn_attr).await?;
 
@@ -157,7 +179,7 @@ async fn test_create_session_with_tasks() -> Result<(), FlameError> {
     }
 
     // Also check the events of the task.
-    let task = ssn.get_task(&String::from("1")).await?;
+    let task = ssn.get_task(&TaskID::from("1")).await?;
     assert_eq!(task.state, TaskState::Succeed);
     assert_ne!(task.events.len(), 0);
     for event in task.events {
@@ -185,25 +207,39 @@ async fn test_create_multiple_sessions_with_tasks() -> Result<(), FlameError> {
     let conn = get_connection().await?;
 
     let ssn_1_attr = SessionAttributes {
-        id: String::from("ssn-1-test-create-multiple-sessions-with-tasks"),
+        id: "ssn-1-test-create-multiple-sessions-with-tasks".into(),
         application: FLAME_DEFAULT_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn_1 = conn.create_session(&ssn_1_attr).await?;
     assert_eq!(ssn_1.state, SessionState::Open);
 
     let ssn_2_attr = SessionAttributes {
-        id: String::from("ssn-2-test-create-multiple-sessions-with-tasks"),
+        id: "ssn-2-test-create-multiple-sessions-with-tasks".into(),
         application: FLAME_DEFAULT_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 0,
         max_instances: None,
         batch_size: 1,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn_2 = conn.create_session(&ssn_2_attr).await?;
     assert_eq!(ssn_2.state, SessionState::Open);
@@ -270,6 +306,18 @@ async fn test_application_lifecycle() -> Result<(), FlameError> {
                     common_data: None,
                 }),
                 url: None,
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: None,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: None,
+                hooks: None,
+                python_env: None,
+                codec: None,
+                payload_type: None,
+                topology: None,
             },
         ),
         (
@@ -287,6 +335,18 @@ async fn test_application_lifecycle() -> Result<(), FlameError> {
                 delay_release: None,
                 schema: None,
                 url: None,
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: None,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: None,
+                hooks: None,
+                python_env: None,
+                codec: None,
+                payload_type: None,
+                topology: None,
             },
         ),
     ];
@@ -307,13 +367,20 @@ async fn test_batch_session() -> Result<(), FlameError> {
     let conn = get_connection().await?;
 
     let ssn_attr = SessionAttributes {
-        id: String::from("ssn-batch-test"),
+        id: "ssn-batch-test".into(),
         application: FLAME_DEFAULT_APP.to_string(),
         slots: 1,
         common_data: None,
         min_instances: 2, // Ensure batch_size executors are allocated
         max_instances: None,
         batch_size: 2,
+        scratch_size: None,
+        max_task_attempts: None,
+        task_timeout: None,
+        task_priority: None,
+        priority: 0,
+        result_policy: ResultPolicy::Inline,
+        ttl_after_completion: None,
     };
     let ssn = conn.create_session(&ssn_attr).await?;
 
@@ -0,0 +1,9 @@
+-- Add per-task environments/arguments overrides and the application allowlists
+-- task_environments/task_arguments: the names that tasks are allowed to override
+-- overrides: the environments/arguments of a task, in JSON
+
+ALTER TABLE applications ADD COLUMN task_environments TEXT;
+
+ALTER TABLE applications ADD COLUMN task_arguments TEXT;
+
+ALTER TABLE tasks ADD COLUMN overrides TEXT;
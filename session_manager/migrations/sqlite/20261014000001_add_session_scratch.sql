@@ -0,0 +1,3 @@
+-- Add the size limit of the session's shared scratch directory
+-- NULL means the session does not request a scratch directory
+ALTER TABLE sessions ADD COLUMN scratch_size INTEGER;
@@ -0,0 +1,4 @@
+-- Add the named output artifacts of tasks
+-- artifacts: the inline payloads or object references of a task, in JSON
+
+ALTER TABLE tasks ADD COLUMN artifacts TEXT;
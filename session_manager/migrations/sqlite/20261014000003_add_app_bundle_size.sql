@@ -0,0 +1,4 @@
+-- Add the bundle size of applications
+-- bundle_size: the max number of tasks handed to an executor in one launch
+
+ALTER TABLE applications ADD COLUMN bundle_size INTEGER NOT NULL DEFAULT 1;
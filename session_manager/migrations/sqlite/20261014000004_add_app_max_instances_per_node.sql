@@ -0,0 +1,4 @@
+-- Add the node-level concurrency limit of applications
+-- max_instances_per_node: the max number of instances on each node (NULL means unlimited)
+
+ALTER TABLE applications ADD COLUMN max_instances_per_node INTEGER;
@@ -0,0 +1,4 @@
+-- Add the failure reason of tasks
+-- failure_reason: the FailureReason of a failed task, NULL otherwise
+
+ALTER TABLE tasks ADD COLUMN failure_reason INTEGER;
@@ -0,0 +1,4 @@
+-- Add the attempts of tasks
+-- attempts: the times the task was dispatched again after losing its executor
+
+ALTER TABLE tasks ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Add the session defaults of applications and the task attempts limit of sessions
+-- session_defaults: the defaults of the sessions of the application as JSON (NULL means no defaults)
+-- max_task_attempts: the attempts of a task before it's quarantined (NULL means the cluster's max_task_attempts)
+
+ALTER TABLE applications ADD COLUMN session_defaults TEXT;
+ALTER TABLE sessions ADD COLUMN max_task_attempts INTEGER;
@@ -0,0 +1,4 @@
+-- Add the health probe of applications
+-- health_probe: the health probe of the instances of the application as JSON (NULL means no probe)
+
+ALTER TABLE applications ADD COLUMN health_probe TEXT;
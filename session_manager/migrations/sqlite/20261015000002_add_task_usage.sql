@@ -0,0 +1,4 @@
+-- Add the resource usage of tasks
+-- usage: the JSON of the TaskUsage measured by the executor manager, NULL if not measured
+
+ALTER TABLE tasks ADD COLUMN usage TEXT;
@@ -0,0 +1,4 @@
+-- Add the fairness policy of applications
+-- fairness: how the instances are shared among the sessions of the application (0: RoundRobin, 1: Proportional)
+
+ALTER TABLE applications ADD COLUMN fairness INTEGER NOT NULL DEFAULT 0;
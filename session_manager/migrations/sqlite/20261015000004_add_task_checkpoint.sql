@@ -0,0 +1,4 @@
+-- Add the checkpoint of tasks
+-- checkpoint: the object cache reference of the latest checkpoint, NULL if none; it's kept on retry
+
+ALTER TABLE tasks ADD COLUMN checkpoint TEXT;
@@ -0,0 +1,4 @@
+-- Add the lifecycle hooks of applications
+-- hooks: the lifecycle hooks of the instances of the application as JSON (NULL means no hooks)
+
+ALTER TABLE applications ADD COLUMN hooks TEXT;
@@ -0,0 +1,4 @@
+-- Add the Python environment of applications
+-- python_env: the Python environment of the host instances of the application as JSON (NULL means none)
+
+ALTER TABLE applications ADD COLUMN python_env TEXT;
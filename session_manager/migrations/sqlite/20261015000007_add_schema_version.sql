@@ -0,0 +1,11 @@
+-- Add the schema version of the database
+-- version: the latest migration applied by the session manager
+-- flame_version: the version of the session manager which applied the migration
+-- The session manager refuses to start against a database of a newer schema.
+
+CREATE TABLE IF NOT EXISTS schema_version (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version INTEGER NOT NULL,
+    flame_version TEXT NOT NULL,
+    update_time INTEGER NOT NULL
+);
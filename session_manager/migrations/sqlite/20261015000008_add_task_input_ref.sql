@@ -0,0 +1,4 @@
+-- Add the input reference of tasks
+-- input_ref: the input of the task in the object cache as JSON, instead of the inline input (NULL means inline)
+
+ALTER TABLE tasks ADD COLUMN input_ref TEXT;
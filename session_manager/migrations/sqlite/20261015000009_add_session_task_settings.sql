@@ -0,0 +1,6 @@
+-- Add the settings of the tasks of sessions
+-- task_timeout: the timeout in seconds of running a task (NULL means the application's, then the cluster's)
+-- task_priority: the priority of the pending tasks (NULL means the application's, then the cluster's)
+
+ALTER TABLE sessions ADD COLUMN task_timeout INTEGER;
+ALTER TABLE sessions ADD COLUMN task_priority INTEGER;
@@ -0,0 +1,4 @@
+-- Add the postmortem of the crashed instance running the task
+-- postmortem: the JSON of the TaskPostmortem collected by the executor manager, NULL if the instance didn't crash
+
+ALTER TABLE tasks ADD COLUMN postmortem TEXT;
@@ -0,0 +1,5 @@
+-- Add the trace IDs of sessions and tasks
+-- trace_id: the trace ID generated by the SDK for the request which created the session or the task, NULL if not traced
+
+ALTER TABLE sessions ADD COLUMN trace_id TEXT;
+ALTER TABLE tasks ADD COLUMN trace_id TEXT;
@@ -0,0 +1,4 @@
+-- Add the priority of sessions
+-- priority: the priority of the session in scheduling, the higher the earlier
+
+ALTER TABLE sessions ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
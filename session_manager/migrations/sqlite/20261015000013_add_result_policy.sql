@@ -0,0 +1,6 @@
+-- Add the result policy of sessions and the output reference of tasks
+-- result_policy: what happens to the task outputs, 0 inline, 1 discard, 2 object cache
+-- output_ref: the output of the task in the object cache as JSON, instead of the inline output (NULL means inline)
+
+ALTER TABLE sessions ADD COLUMN result_policy INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tasks ADD COLUMN output_ref TEXT;
@@ -0,0 +1,6 @@
+-- Add the payload codec of applications
+-- codec: the codec of the task inputs and outputs, 0 raw, 1 json, 2 msgpack, 3 arrow, 4 protobuf
+-- payload_type: the fully-qualified message type of the protobuf codec (NULL for other codecs)
+
+ALTER TABLE applications ADD COLUMN codec INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE applications ADD COLUMN payload_type TEXT;
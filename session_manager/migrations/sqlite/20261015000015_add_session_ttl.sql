@@ -0,0 +1,4 @@
+-- Add the TTL of sessions after their completion
+-- ttl_after_completion: seconds to keep the closed session before it's deleted (NULL means forever)
+
+ALTER TABLE sessions ADD COLUMN ttl_after_completion INTEGER;
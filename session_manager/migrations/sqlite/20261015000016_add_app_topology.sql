@@ -0,0 +1,4 @@
+-- Add the topology policy of applications
+-- topology: how the instances are placed within the GPU and NUMA topology of the nodes (0: Ignore, 1: NumaAligned, 2: GpuAligned)
+
+ALTER TABLE applications ADD COLUMN topology INTEGER NOT NULL DEFAULT 0;
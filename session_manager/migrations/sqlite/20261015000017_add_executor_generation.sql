@@ -0,0 +1,4 @@
+-- Add the generation of executors
+-- generation: differs from the one of the released executor of the same id, so the requests of the released one are rejected (0: unknown)
+
+ALTER TABLE executors ADD COLUMN generation INTEGER NOT NULL DEFAULT 0;
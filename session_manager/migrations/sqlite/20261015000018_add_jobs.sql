@@ -0,0 +1,8 @@
+-- Add the records of the running jobs
+-- The jobs are kept in memory by the session manager, so their sessions are
+-- closed on recovery by these records after a restart.
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id                  TEXT PRIMARY KEY,
+    creation_time       INTEGER NOT NULL
+);
@@ -21,23 +21,54 @@ use tonic::{Request, Response, Status, Streaming};
 
 use self::rpc::backend_server::Backend;
 use self::rpc::{
-    BindExecutorCompletedRequest, BindExecutorRequest, BindExecutorResponse, CompleteTaskRequest,
-    LaunchTaskRequest, LaunchTaskResponse, RegisterExecutorRequest, RegisterNodeRequest,
-    ReleaseNodeRequest, SyncNodeRequest, SyncNodeResponse, UnbindExecutorCompletedRequest,
-    UnbindExecutorRequest, UnregisterExecutorRequest, WatchNodeRequest, WatchNodeResponse,
+    BindExecutorCompletedRequest, BindExecutorFailedRequest, BindExecutorRequest,
+    BindExecutorResponse, CompleteTaskRequest, FetchApplicationRequest, FetchApplicationResponse,
+    LaunchTaskRequest, LaunchTaskResponse, PushTaskOutputRequest, RecordEventRequest,
+    RegisterExecutorRequest, RegisterNodeRequest, RegisterNodeResponse, ReleaseNodeRequest,
+    SyncNodeRequest, SyncNodeResponse, UnbindExecutorCompletedRequest, UnbindExecutorRequest,
+    UnregisterExecutorRequest, WatchNodeRequest, WatchNodeResponse,
 };
 use ::rpc::flame::v1 as rpc;
 
 use crate::apiserver::Flame;
 use crate::controller::ControllerPtr;
-use crate::model::Executor;
-use common::apis::{ExecutorState, Node, Shim, TaskResult};
-use common::FlameError;
+use crate::model::{Executor, NodeLoad};
+use common::apis::{
+    Event, EventOwner, ExecutorState, Node, Shim, TaskID, TaskResult, INSTANCE_UNHEALTHY_EVENT,
+};
+use common::{FlameError, FLAME_EXECUTOR_GENERATION, FLAME_NODE_NAME, FLAME_NODE_TOKEN};
 
 /// Timeout for heartbeat in seconds. If no heartbeat is received within this
 /// duration, the stream is considered stale and will be closed.
 const HEARTBEAT_TIMEOUT_SECS: u64 = 15;
 
+/// Interval in seconds of sending the live sessions to the nodes, so their
+/// object caches delete the objects of the sessions which no longer exist.
+const LIVE_SESSIONS_INTERVAL_SECS: u64 = 300;
+
+/// The credential of the node presented in the metadata of the backend RPC.
+fn node_credential<T>(req: &Request<T>) -> (String, Option<String>) {
+    let value = |key: &str| {
+        req.metadata()
+            .get(key)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    (
+        value(FLAME_NODE_NAME).unwrap_or_default(),
+        value(FLAME_NODE_TOKEN),
+    )
+}
+
+/// The generation of the executor presented in the metadata of its backend
+/// RPCs, if any.
+fn executor_generation<T>(req: &Request<T>) -> Option<u64> {
+    req.metadata()
+        .get(FLAME_EXECUTOR_GENERATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
 // ============================================================================
 // Helper functions for watch_node stream handling
 // ============================================================================
@@ -54,6 +85,28 @@ async fn send_ack(tx: &mpsc::Sender<Result<WatchNodeResponse, Status>>) -> bool
     tx.send(Ok(ack)).await.is_ok()
 }
 
+/// Sends the IDs of all the sessions to the client.
+async fn send_live_sessions(
+    controller: &ControllerPtr,
+    tx: &mpsc::Sender<Result<WatchNodeResponse, Status>>,
+) -> bool {
+    let session_ids = match controller.list_session_ids().await {
+        Ok(ids) => ids.into_iter().map(String::from).collect(),
+        Err(e) => {
+            // Skip this round; the caches keep their objects until the next one.
+            tracing::warn!("Failed to list the live sessions: {}", e);
+            return true;
+        }
+    };
+
+    let response = WatchNodeResponse {
+        response: Some(rpc::watch_node_response::Response::Sessions(
+            rpc::LiveSessions { session_ids },
+        )),
+    };
+    tx.send(Ok(response)).await.is_ok()
+}
+
 /// Handles a heartbeat request from the client.
 /// Updates node status and sends acknowledgement.
 async fn handle_heartbeat(
@@ -64,6 +117,12 @@ async fn handle_heartbeat(
 ) -> bool {
     tracing::debug!("Received heartbeat from node <{}>", hb.node_name);
 
+    let mut load = NodeLoad::from(hb.executor_loads.as_slice());
+    load.max_executors = hb.max_executors;
+    if let Err(e) = controller.update_node_load(node_name, load) {
+        tracing::warn!("Failed to update the load of node <{}>: {}", node_name, e);
+    }
+
     // Update node status if provided
     if let Some(status) = hb.status {
         let node = build_node_from_heartbeat(controller, node_name, status);
@@ -128,7 +187,7 @@ impl Backend for Flame {
     async fn register_node(
         &self,
         req: Request<RegisterNodeRequest>,
-    ) -> Result<Response<rpc::Result>, Status> {
+    ) -> Result<Response<RegisterNodeResponse>, Status> {
         trace_fn!("Backend::register_node");
         let req = req.into_inner();
         let node = Node::from(
@@ -136,6 +195,13 @@ impl Backend for Flame {
                 .ok_or(FlameError::InvalidConfig("node is required".to_string()))?,
         );
 
+        // Admit the node by its credential, or the join token at its first registration.
+        let node_token = self.controller.admit_node(
+            &node.name,
+            req.join_token.as_deref(),
+            req.node_token.as_deref(),
+        )?;
+
         // Convert reported executors from proto
         let reported_executors: Vec<Executor> =
             req.executors.into_iter().map(Executor::from).collect();
@@ -151,7 +217,10 @@ impl Backend for Flame {
             .register_node(&node, &reported_executors)
             .await?;
 
-        Ok(Response::new(rpc::Result::default()))
+        Ok(Response::new(RegisterNodeResponse {
+            node_token,
+            ..Default::default()
+        }))
     }
 
     /// Deprecated: Use `watch_node` streaming RPC instead for better efficiency.
@@ -167,6 +236,8 @@ impl Backend for Flame {
             req.node
                 .ok_or(FlameError::InvalidConfig("node is required".to_string()))?,
         );
+        self.controller
+            .verify_node(&node.name, req.node_token.as_deref())?;
         let executors: Vec<Executor> = req.executors.into_iter().map(rpc::Executor::into).collect();
 
         let executors = self.controller.sync_node(&node, &executors).await?;
@@ -204,6 +275,7 @@ impl Backend for Flame {
         // Spawn a task to handle the incoming stream
         tokio::spawn(async move {
             let mut node_name: Option<String> = None;
+            let mut live_sessions_time: Option<std::time::Instant> = None;
 
             loop {
                 let request = match timeout(
@@ -239,11 +311,41 @@ impl Backend for Flame {
                 if node_name.is_none() {
                     tracing::info!("Node <{}> starting watch stream", name);
 
+                    if let Err(e) = controller.verify_node(&name, hb.node_token.as_deref()) {
+                        tracing::warn!("Reject watch stream of node <{}>: {}", name, e);
+                        let _ = tx_for_queue.send(Err(Status::from(e))).await;
+                        break;
+                    }
+
                     // Get the channel (already created by register_node)
                     match controller.get_node_channel(&name) {
                         Ok(Some((_sender, receiver))) => {
                             node_name = Some(name.clone());
 
+                            // Send the node-level configuration before the executors; the
+                            // empty one resets the node to its local configuration.
+                            let config = match controller.get_node_config(&name) {
+                                Ok(config) => config,
+                                Err(e) => {
+                                    tracing::warn!(
+                                        "Failed to get the config of node <{}>: {}",
+                                        name,
+                                        e
+                                    );
+                                    let _ = tx_for_queue.send(Err(Status::from(e))).await;
+                                    break;
+                                }
+                            };
+                            let response = WatchNodeResponse {
+                                response: Some(rpc::watch_node_response::Response::Config(
+                                    rpc::NodeConfig::from(&config),
+                                )),
+                            };
+                            if tx_for_queue.send(Ok(response)).await.is_err() {
+                                tracing::warn!("Client disconnected before node config");
+                                break;
+                            }
+
                             // Spawn a task to receive from connection and send to client
                             let tx_clone = tx_for_queue.clone();
                             let name_clone = name.clone();
@@ -286,6 +388,16 @@ impl Backend for Flame {
                         tracing::warn!("Client disconnected during heartbeat");
                         break;
                     }
+
+                    let due = live_sessions_time
+                        .is_none_or(|time| time.elapsed().as_secs() >= LIVE_SESSIONS_INTERVAL_SECS);
+                    if due {
+                        live_sessions_time = Some(std::time::Instant::now());
+                        if !send_live_sessions(&controller, &tx).await {
+                            tracing::warn!("Client disconnected before live sessions");
+                            break;
+                        }
+                    }
                 }
             }
 
@@ -308,6 +420,8 @@ impl Backend for Flame {
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::release_node");
         let req = req.into_inner();
+        self.controller
+            .verify_node(&req.node_name, req.node_token.as_deref())?;
         self.controller.release_node(&req.node_name).await?;
         Ok(Response::new(rpc::Result::default()))
     }
@@ -317,10 +431,18 @@ impl Backend for Flame {
         req: Request<RegisterExecutorRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::register_executor");
+        let (_, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
         let spec = req
             .executor_spec
             .ok_or(FlameError::InvalidConfig("no executor spec".to_string()))?;
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &spec.node,
+            node_token.as_deref(),
+        )?;
 
         let shim = Shim::from(spec.shim());
         let e = Executor {
@@ -330,8 +452,12 @@ impl Backend for Flame {
             slots: spec.slots,
             shim,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: generation.unwrap_or_default(),
             creation_time: Utc::now(),
             state: ExecutorState::Idle,
         };
@@ -349,9 +475,19 @@ impl Backend for Flame {
         req: Request<UnregisterExecutorRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::unregister_executor");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
 
-        self.controller.unregister_executor(req.executor_id).await?;
+        self.controller
+            .unregister_executor(req.executor_id, req.reason)
+            .await?;
 
         Ok(Response::new(rpc::Result::default()))
     }
@@ -361,7 +497,15 @@ impl Backend for Flame {
         req: Request<BindExecutorRequest>,
     ) -> Result<Response<BindExecutorResponse>, Status> {
         trace_fn!("Backend::bind_executor");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
         let executor_id = req.executor_id.to_string();
 
         let ssn = self
@@ -375,6 +519,7 @@ impl Backend for Flame {
                 application: None,
                 session: None,
                 batch_index: None,
+                application_version: None,
             }));
         };
 
@@ -403,6 +548,7 @@ impl Backend for Flame {
             application,
             session,
             batch_index,
+            application_version: Some(app.version),
         }))
     }
 
@@ -411,7 +557,15 @@ impl Backend for Flame {
         req: Request<BindExecutorCompletedRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::bind_executor_completed");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
 
         self.controller
             .bind_session_completed(req.executor_id)
@@ -420,12 +574,66 @@ impl Backend for Flame {
         Ok(Response::new(rpc::Result::default()))
     }
 
+    async fn bind_executor_failed(
+        &self,
+        req: Request<BindExecutorFailedRequest>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        trace_fn!("Backend::bind_executor_failed");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
+        let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
+
+        self.controller
+            .bind_session_failed(req.executor_id, req.reason)
+            .await?;
+
+        Ok(Response::new(rpc::Result::default()))
+    }
+
     async fn unbind_executor(
         &self,
         req: Request<UnbindExecutorRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::unbind_executor");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
+
+        // The executor unbinds itself if its instance is unhealthy, which is
+        // recorded as an event of its session before the executor is unbound.
+        if let Some(reason) = req.reason {
+            let executor = self.controller.get_executor(req.executor_id.clone())?;
+            if let Some(ssn_id) = executor.ssn_id {
+                let message = format!(
+                    "Instance of executor <{}> on node <{}> is unhealthy: {reason}",
+                    executor.id, executor.node
+                );
+                tracing::warn!("Session <{ssn_id}>: {message}");
+                self.controller
+                    .record_event(
+                        EventOwner::session(ssn_id),
+                        Event {
+                            code: INSTANCE_UNHEALTHY_EVENT,
+                            message: Some(message),
+                            creation_time: Utc::now(),
+                        },
+                    )
+                    .await?;
+            }
+        }
+
         self.controller.unbind_executor(req.executor_id).await?;
 
         Ok(Response::new(rpc::Result::default()))
@@ -436,7 +644,15 @@ impl Backend for Flame {
         req: Request<UnbindExecutorCompletedRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::unbind_executor_completed");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
         self.controller
             .unbind_executor_completed(req.executor_id)
             .await?;
@@ -449,7 +665,15 @@ impl Backend for Flame {
         req: Request<LaunchTaskRequest>,
     ) -> Result<Response<LaunchTaskResponse>, Status> {
         trace_fn!("Backend::launch_task");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
         let executor_id = req.executor_id.clone();
 
         let batch_index = self
@@ -458,17 +682,17 @@ impl Backend for Flame {
             .ok()
             .and_then(|e| e.batch_index);
 
-        let task = self.controller.launch_task(executor_id).await?;
-        if let Some(task) = task {
-            return Ok(Response::new(LaunchTaskResponse {
-                task: Some(rpc::Task::from(&task)),
-                batch_index,
-            }));
-        }
+        let mut tasks = self
+            .controller
+            .launch_task(executor_id)
+            .await?
+            .into_iter()
+            .map(|task| rpc::Task::from(&task));
 
         Ok(Response::new(LaunchTaskResponse {
-            task: None,
+            task: tasks.next(),
             batch_index,
+            bundle: tasks.collect(),
         }))
     }
 
@@ -477,17 +701,136 @@ impl Backend for Flame {
         req: Request<CompleteTaskRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Backend::complete_task");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
         let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
 
         let task_result = req.task_result.ok_or(FlameError::InvalidState(format!(
             "no task result when completing task in {}",
             req.executor_id.clone()
         )))?;
 
+        let task_id = req
+            .task_id
+            .map(|id| id.parse::<TaskID>())
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid task id"))?;
+
         self.controller
-            .complete_task(req.executor_id.clone(), TaskResult::from(task_result))
+            .complete_task(
+                req.executor_id.clone(),
+                task_id,
+                TaskResult::from(task_result),
+            )
             .await?;
 
         Ok(Response::new(rpc::Result::default()))
     }
+
+    async fn push_task_output(
+        &self,
+        req: Request<Streaming<PushTaskOutputRequest>>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        trace_fn!("Backend::push_task_output");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
+        let mut in_stream = req.into_inner();
+
+        // The stream is pushed by a single executor, which is verified once.
+        let mut verified = None;
+        while let Some(req) = in_stream.message().await? {
+            if verified.as_ref() != Some(&req.executor_id) {
+                self.controller.verify_executor(
+                    &req.executor_id,
+                    generation,
+                    &node_name,
+                    node_token.as_deref(),
+                )?;
+                verified = Some(req.executor_id.clone());
+            }
+            let Some(chunk) = req.chunk else {
+                continue;
+            };
+            // The chunks pushed after their task was completed are dropped,
+            // and the stream is kept for the next tasks of the executor.
+            if let Err(e) = self
+                .controller
+                .push_task_output(req.executor_id.clone(), chunk)
+            {
+                tracing::debug!(
+                    "Dropped the task output of executor <{}>: {e}",
+                    req.executor_id
+                );
+            }
+        }
+
+        Ok(Response::new(rpc::Result::default()))
+    }
+
+    async fn record_event(
+        &self,
+        req: Request<RecordEventRequest>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        trace_fn!("Backend::record_event");
+        let (node_name, node_token) = node_credential(&req);
+        let generation = executor_generation(&req);
+        let req = req.into_inner();
+        self.controller.verify_executor(
+            &req.executor_id,
+            generation,
+            &node_name,
+            node_token.as_deref(),
+        )?;
+
+        let event = req.event.ok_or(FlameError::InvalidConfig(format!(
+            "no event to record of executor <{}>",
+            req.executor_id
+        )))?;
+
+        // The event belongs to the session the executor is bound to; it's
+        // dropped if the executor was unbound meanwhile.
+        let executor = self.controller.get_executor(req.executor_id.clone())?;
+        let Some(ssn_id) = executor.ssn_id else {
+            tracing::debug!(
+                "Executor <{}> is not bound to any session, drop its event <{}>",
+                executor.id,
+                event.code
+            );
+            return Ok(Response::new(rpc::Result::default()));
+        };
+
+        self.controller
+            .record_event(EventOwner::session(ssn_id), Event::from(event))
+            .await?;
+
+        Ok(Response::new(rpc::Result::default()))
+    }
+
+    async fn fetch_application(
+        &self,
+        req: Request<FetchApplicationRequest>,
+    ) -> Result<Response<FetchApplicationResponse>, Status> {
+        trace_fn!("Backend::fetch_application");
+        let (node_name, node_token) = node_credential(&req);
+        self.controller
+            .verify_node(&node_name, node_token.as_deref())?;
+        let req = req.into_inner();
+
+        let app = self.controller.get_application(req.name).await?;
+
+        // The executor manager keeps its cached application if unchanged.
+        let application =
+            (req.known_version != Some(app.version)).then(|| rpc::Application::from(&app));
+
+        Ok(Response::new(FetchApplicationResponse {
+            application,
+            version: app.version,
+        }))
+    }
 }
@@ -10,31 +10,41 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
+use std::collections::HashMap;
 use std::path::Path;
 use std::pin::Pin;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
 use common::apis::{ApplicationAttributes, SessionAttributes};
 use futures::Stream;
 use serde_json::Value;
 use stdng::trace_fn;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
 use self::rpc::frontend_server::Frontend;
 use self::rpc::{
-    ApplicationList, CloseSessionRequest, CreateSessionRequest, CreateTaskRequest,
-    DeleteSessionRequest, DeleteTaskRequest, ExecutorList, GetApplicationRequest, GetNodeRequest,
-    GetNodeResponse, GetSessionRequest, GetTaskRequest, ListApplicationRequest,
-    ListExecutorRequest, ListNodesRequest, ListSessionRequest, ListTaskRequest, NodeList,
-    OpenSessionRequest, RegisterApplicationRequest, Session, SessionList, Task,
-    UnregisterApplicationRequest, UpdateApplicationRequest, WatchTaskRequest,
+    ApplicationList, CancelJobRequest, CheckpointTaskRequest, CloseSessionRequest, ClusterSnapshot,
+    ClusterUtilization, CreateJobRequest, CreateJoinTokenRequest, CreateSessionRequest,
+    CreateTaskRequest, DeleteSessionRequest, DeleteTaskRequest, DisableApplicationRequest,
+    EnableApplicationRequest, EventList, ExecutorList, GetApplicationRequest,
+    GetClusterSnapshotRequest, GetClusterUtilizationRequest, GetJobRequest, GetNodeRequest,
+    GetNodeResponse, GetSchedulerStateRequest, GetSessionRequest, GetSessionSummaryRequest,
+    GetTaskRequest, GetTaskSettingsRequest, Job, JobList, JoinToken, ListApplicationRequest,
+    ListEventsRequest, ListExecutorRequest, ListJobRequest, ListNodesRequest, ListSessionRequest,
+    ListSlotRecommendationsRequest, ListTaskRequest, NodeList, OpenSessionRequest,
+    RegisterApplicationRequest, ReleaseTaskRequest, RenewSessionLeaseRequest,
+    RenewSessionLeaseResponse, ResubmitSessionRequest, SchedulerState, Session, SessionList,
+    SessionSummary, SlotRecommendation, SlotRecommendationList, Task, TaskOutputChunk,
+    TaskSettings, UnregisterApplicationRequest, UpdateApplicationRequest, WatchSessionRequest,
+    WatchTaskOutputRequest, WatchTaskRequest,
 };
 
 use rpc::flame::v1 as rpc;
 
-use common::{apis, FlameError};
+use common::{apis, FlameError, FLAME_ADMIN_TOKEN, FLAME_TRACE_ID};
 
 use crate::apiserver::Flame;
 
@@ -49,9 +59,65 @@ fn validate_working_directory(working_dir: &Option<String>) -> Result<(), FlameE
     Ok(())
 }
 
+/// The interval to check whether the task, whose output is watched, is
+/// completed without any output pushed.
+const TASK_OUTPUT_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The max length of the trace IDs of the requests.
+const MAX_TRACE_ID_LEN: usize = 128;
+
+/// The trace ID generated by the SDK for the request, if any; the invalid ones,
+/// e.g. too long, are ignored.
+fn trace_id<T>(req: &Request<T>) -> Option<String> {
+    let trace_id = req.metadata().get(FLAME_TRACE_ID)?.to_str().ok()?;
+    let valid = !trace_id.is_empty()
+        && trace_id.len() <= MAX_TRACE_ID_LEN
+        && trace_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        tracing::warn!("Ignore the invalid trace ID <{trace_id}> of the request");
+        return None;
+    }
+
+    Some(trace_id.to_string())
+}
+
+/// The admin token presented by the client of an administrative RPC, if any.
+fn admin_token<T>(req: &Request<T>) -> Option<String> {
+    let token = req.metadata().get(FLAME_ADMIN_TOKEN)?.to_str().ok()?;
+    Some(token.to_string())
+}
+
+/// Sets the saturation and the executors of the node by the loads of its
+/// executors, and its last heartbeat and recent events.
+fn node_with_status(flame: &Flame, mut node: rpc::Node) -> Result<rpc::Node, FlameError> {
+    let name = node
+        .metadata
+        .as_ref()
+        .map(|m| m.name.clone())
+        .unwrap_or_default();
+    if let Some(status) = node.status.as_mut() {
+        status.saturation = flame.controller.get_node_saturation(&name)?;
+        (status.executors, status.max_executors) = flame.controller.get_node_executors(&name)?;
+        status.last_heartbeat_time = flame.controller.get_node_heartbeat(&name)?.timestamp();
+        status.events = flame
+            .controller
+            .get_node_events(&name)?
+            .into_iter()
+            .map(rpc::Event::from)
+            .collect();
+    }
+
+    Ok(node)
+}
+
 #[async_trait]
 impl Frontend for Flame {
+    type WatchSessionStream = Pin<Box<dyn Stream<Item = Result<Task, Status>> + Send>>;
     type WatchTaskStream = Pin<Box<dyn Stream<Item = Result<Task, Status>> + Send>>;
+    type WatchTaskOutputStream =
+        Pin<Box<dyn Stream<Item = Result<TaskOutputChunk, Status>> + Send>>;
     type ListTaskStream = Pin<Box<dyn Stream<Item = Result<Task, Status>> + Send>>;
 
     async fn list_task(
@@ -91,6 +157,7 @@ impl Frontend for Flame {
         req: Request<RegisterApplicationRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Frontend::register_application");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
 
         let req = req.into_inner();
         let spec = req.application.ok_or(FlameError::InvalidConfig(
@@ -145,6 +212,7 @@ impl Frontend for Flame {
         req: Request<UnregisterApplicationRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Frontend::unregister_application");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
         let req = req.into_inner();
         let res = self.controller.unregister_application(req.name).await;
 
@@ -165,6 +233,7 @@ impl Frontend for Flame {
         req: Request<UpdateApplicationRequest>,
     ) -> Result<Response<rpc::Result>, Status> {
         trace_fn!("Frontend::update_application");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
         let req = req.into_inner();
         let spec = req.application.ok_or(FlameError::InvalidConfig(
             "applilcation spec is missed".to_string(),
@@ -214,6 +283,52 @@ impl Frontend for Flame {
         }
     }
 
+    async fn enable_application(
+        &self,
+        req: Request<EnableApplicationRequest>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        trace_fn!("Frontend::enable_application");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
+        let res = self
+            .controller
+            .enable_application(req.into_inner().name)
+            .await;
+
+        match res {
+            Ok(..) => Ok(Response::new(rpc::Result {
+                return_code: 0,
+                message: None,
+            })),
+            Err(e) => Ok(Response::new(rpc::Result {
+                return_code: -1,
+                message: Some(e.to_string()),
+            })),
+        }
+    }
+
+    async fn disable_application(
+        &self,
+        req: Request<DisableApplicationRequest>,
+    ) -> Result<Response<rpc::Result>, Status> {
+        trace_fn!("Frontend::disable_application");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
+        let res = self
+            .controller
+            .disable_application(req.into_inner().name)
+            .await;
+
+        match res {
+            Ok(..) => Ok(Response::new(rpc::Result {
+                return_code: 0,
+                message: None,
+            })),
+            Err(e) => Ok(Response::new(rpc::Result {
+                return_code: -1,
+                message: Some(e.to_string()),
+            })),
+        }
+    }
+
     async fn get_application(
         &self,
         req: tonic::Request<GetApplicationRequest>,
@@ -260,7 +375,10 @@ impl Frontend for Flame {
     ) -> Result<Response<NodeList>, Status> {
         trace_fn!("Frontend::list_nodes");
         let node_list = self.controller.list_node().map_err(Status::from)?;
-        let nodes = node_list.iter().map(rpc::Node::from).collect();
+        let mut nodes = vec![];
+        for node in &node_list {
+            nodes.push(node_with_status(self, rpc::Node::from(node)).map_err(Status::from)?);
+        }
         Ok(Response::new(NodeList { nodes }))
     }
 
@@ -276,15 +394,90 @@ impl Frontend for Flame {
             .map_err(Status::from)?
             .ok_or_else(|| Status::not_found(format!("node <{}> not found", name)))?;
         Ok(Response::new(GetNodeResponse {
-            node: Some(rpc::Node::from(node)),
+            node: Some(node_with_status(self, rpc::Node::from(node)).map_err(Status::from)?),
         }))
     }
 
+    async fn create_join_token(
+        &self,
+        req: Request<CreateJoinTokenRequest>,
+    ) -> Result<Response<JoinToken>, Status> {
+        trace_fn!("Frontend::create_join_token");
+        let admin_token = admin_token(&req);
+        let req = req.into_inner();
+        let token = self
+            .controller
+            .create_join_token(
+                admin_token.as_deref(),
+                req.node_name.filter(|name| !name.is_empty()),
+                req.ttl,
+            )
+            .map_err(Status::from)?;
+
+        tracing::info!(
+            "Created join token for node <{}>, expires at {}",
+            token.node_name.as_deref().unwrap_or("*"),
+            token.expiration_time
+        );
+
+        Ok(Response::new(JoinToken::from(&token)))
+    }
+
+    async fn get_cluster_snapshot(
+        &self,
+        req: Request<GetClusterSnapshotRequest>,
+    ) -> Result<Response<ClusterSnapshot>, Status> {
+        trace_fn!("Frontend::get_cluster_snapshot");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
+        let at = match req.into_inner().at {
+            Some(millis) => Some(
+                DateTime::<Utc>::from_timestamp_millis(millis)
+                    .ok_or_else(|| Status::invalid_argument("invalid time"))?,
+            ),
+            None => None,
+        };
+
+        let snapshot = self
+            .controller
+            .get_cluster_snapshot(at)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(snapshot))
+    }
+
+    async fn get_scheduler_state(
+        &self,
+        req: Request<GetSchedulerStateRequest>,
+    ) -> Result<Response<SchedulerState>, Status> {
+        trace_fn!("Frontend::get_scheduler_state");
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
+        let state = self
+            .controller
+            .get_scheduler_state()
+            .map_err(Status::from)?;
+
+        Ok(Response::new(state))
+    }
+
+    async fn get_cluster_utilization(
+        &self,
+        _: Request<GetClusterUtilizationRequest>,
+    ) -> Result<Response<ClusterUtilization>, Status> {
+        trace_fn!("Frontend::get_cluster_utilization");
+        let utilization = self
+            .controller
+            .get_cluster_utilization()
+            .map_err(Status::from)?;
+
+        Ok(Response::new(utilization))
+    }
+
     async fn create_session(
         &self,
         req: Request<CreateSessionRequest>,
     ) -> Result<Response<Session>, Status> {
         trace_fn!("Frontend::create_session");
+        let trace_id = trace_id(&req);
         let req = req.into_inner();
         let ssn_spec = req
             .session
@@ -301,16 +494,26 @@ impl Frontend for Flame {
             common_data: ssn_spec.common_data.map(apis::CommonData::from),
             min_instances: ssn_spec.min_instances,
             max_instances: ssn_spec.max_instances,
-            batch_size: ssn_spec.batch_size.max(1),
+            batch_size: ssn_spec.batch_size,
+            scratch_size: ssn_spec.scratch_size.filter(|size| *size > 0),
+            max_task_attempts: ssn_spec.max_task_attempts,
+            task_timeout: ssn_spec.task_timeout,
+            task_priority: ssn_spec.task_priority,
+            priority: ssn_spec.priority,
+            result_policy: apis::ResultPolicy::try_from(ssn_spec.result_policy)?,
+            ttl_after_completion: ssn_spec.ttl_after_completion,
+            trace_id,
         };
 
         tracing::debug!(
-            "Creating session with attributes: id={}, application={}, slots={}, min_instances={}, max_instances={:?}",
+            "Creating session with attributes: id={}, application={}, slots={}, min_instances={}, max_instances={:?}, priority={}, trace_id={:?}",
             attr.id,
             attr.application,
             attr.slots,
             attr.min_instances,
-            attr.max_instances
+            attr.max_instances,
+            attr.priority,
+            attr.trace_id
         );
 
         let ssn = self
@@ -347,6 +550,7 @@ impl Frontend for Flame {
         req: Request<OpenSessionRequest>,
     ) -> Result<Response<rpc::Session>, Status> {
         trace_fn!("Frontend::open_session");
+        let trace_id = trace_id(&req);
         let req = req.into_inner();
         let ssn_id = req
             .session_id
@@ -354,19 +558,38 @@ impl Frontend for Flame {
             .map_err(|_| Status::invalid_argument("invalid session id"))?;
 
         // Convert optional SessionSpec to SessionAttributes
-        let spec = req.session.map(|ssn_spec| SessionAttributes {
-            id: ssn_id.clone(),
-            application: ssn_spec.application,
-            slots: ssn_spec.slots,
-            common_data: ssn_spec.common_data.map(apis::CommonData::from),
-            min_instances: ssn_spec.min_instances,
-            max_instances: ssn_spec.max_instances,
-            batch_size: ssn_spec.batch_size.max(1),
-        });
+        let spec = req
+            .session
+            .map(|ssn_spec| -> Result<SessionAttributes, FlameError> {
+                Ok(SessionAttributes {
+                    id: ssn_id.clone(),
+                    application: ssn_spec.application,
+                    slots: ssn_spec.slots,
+                    common_data: ssn_spec.common_data.map(apis::CommonData::from),
+                    min_instances: ssn_spec.min_instances,
+                    max_instances: ssn_spec.max_instances,
+                    batch_size: ssn_spec.batch_size,
+                    scratch_size: ssn_spec.scratch_size.filter(|size| *size > 0),
+                    max_task_attempts: ssn_spec.max_task_attempts,
+                    task_timeout: ssn_spec.task_timeout,
+                    task_priority: ssn_spec.task_priority,
+                    priority: ssn_spec.priority,
+                    result_policy: apis::ResultPolicy::try_from(ssn_spec.result_policy)?,
+                    ttl_after_completion: ssn_spec.ttl_after_completion,
+                    trace_id,
+                })
+            })
+            .transpose()?;
+
+        let replay = req
+            .replay_policy
+            .map(apis::ReplayPolicy::try_from)
+            .transpose()
+            .map_err(Status::from)?;
 
         let ssn = self
             .controller
-            .open_session(ssn_id, spec)
+            .open_session(ssn_id, spec, replay)
             .await
             .map(Session::from)
             .map_err(Status::from)?;
@@ -379,15 +602,41 @@ impl Frontend for Flame {
         req: Request<CloseSessionRequest>,
     ) -> Result<Response<rpc::Session>, Status> {
         trace_fn!("Frontend::close_session");
+        let req = req.into_inner();
         let ssn_id = req
-            .into_inner()
             .session_id
             .parse::<apis::SessionID>()
             .map_err(|_| Status::invalid_argument("invalid session id"))?;
 
         let ssn = self
             .controller
-            .close_session(ssn_id)
+            .close_session(ssn_id, req.force.unwrap_or_default())
+            .await
+            .map(rpc::Session::from)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(ssn))
+    }
+
+    async fn resubmit_session(
+        &self,
+        req: Request<ResubmitSessionRequest>,
+    ) -> Result<Response<rpc::Session>, Status> {
+        trace_fn!("Frontend::resubmit_session");
+        let trace_id = trace_id(&req);
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+        let new_ssn_id = req
+            .new_session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid new session id"))?;
+
+        let ssn = self
+            .controller
+            .resubmit_session(ssn_id, new_ssn_id, req.only_failed, trace_id)
             .await
             .map(rpc::Session::from)
             .map_err(Status::from)?;
@@ -426,8 +675,145 @@ impl Frontend for Flame {
         Ok(Response::new(SessionList { sessions }))
     }
 
+    async fn renew_session_lease(
+        &self,
+        req: Request<RenewSessionLeaseRequest>,
+    ) -> Result<Response<RenewSessionLeaseResponse>, Status> {
+        trace_fn!("Frontend::renew_session_lease");
+        let ssn_id = req
+            .into_inner()
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+
+        let expiration_time = self
+            .controller
+            .renew_session_lease(&ssn_id)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(RenewSessionLeaseResponse {
+            expiration_time: expiration_time.timestamp_millis(),
+            grace_period: self.controller.lease_grace_period(),
+        }))
+    }
+
+    async fn get_session_summary(
+        &self,
+        req: Request<GetSessionSummaryRequest>,
+    ) -> Result<Response<SessionSummary>, Status> {
+        trace_fn!("Frontend::get_session_summary");
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+        let window = req.window.unwrap_or(apis::DEFAULT_SUMMARY_WINDOW);
+        if window == 0 {
+            return Err(Status::invalid_argument("window must be positive"));
+        }
+
+        let summary = self
+            .controller
+            .get_session_summary(ssn_id, Duration::minutes(window as i64))
+            .map_err(Status::from)?;
+
+        Ok(Response::new(SessionSummary::from(&summary)))
+    }
+
+    async fn list_slot_recommendations(
+        &self,
+        req: Request<ListSlotRecommendationsRequest>,
+    ) -> Result<Response<SlotRecommendationList>, Status> {
+        trace_fn!("Frontend::list_slot_recommendations");
+        let recommendations = self
+            .controller
+            .get_slot_recommendations(req.into_inner().application)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(SlotRecommendationList {
+            recommendations: recommendations
+                .iter()
+                .map(SlotRecommendation::from)
+                .collect(),
+        }))
+    }
+
+    async fn list_events(
+        &self,
+        req: Request<ListEventsRequest>,
+    ) -> Result<Response<EventList>, Status> {
+        trace_fn!("Frontend::list_events");
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+        let since = req
+            .since
+            .map(|secs| DateTime::<Utc>::from_timestamp(secs, 0));
+        let until = req
+            .until
+            .map(|secs| DateTime::<Utc>::from_timestamp(secs, 0));
+        if matches!(since, Some(None)) || matches!(until, Some(None)) {
+            return Err(Status::invalid_argument("invalid time range"));
+        }
+
+        let filter = apis::EventFilter {
+            since: since.flatten(),
+            until: until.flatten(),
+            codes: req.codes,
+            owner: req
+                .owner
+                .map(apis::EventOwnerKind::try_from)
+                .transpose()
+                .map_err(Status::from)?,
+            task_id: req
+                .task_id
+                .map(|id| id.parse::<apis::TaskID>())
+                .transpose()
+                .map_err(|_| Status::invalid_argument("invalid task id"))?,
+        };
+
+        let page = self
+            .controller
+            .query_events(
+                ssn_id,
+                &filter,
+                req.page_size.unwrap_or_default(),
+                req.page_token.as_deref(),
+            )
+            .map_err(Status::from)?;
+
+        Ok(Response::new(EventList::from(&page)))
+    }
+
+    async fn get_task_settings(
+        &self,
+        req: Request<GetTaskSettingsRequest>,
+    ) -> Result<Response<TaskSettings>, Status> {
+        trace_fn!("Frontend::get_task_settings");
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+        let task_id = req
+            .task_id
+            .map(|id| id.parse::<apis::TaskID>())
+            .transpose()
+            .map_err(|_| Status::invalid_argument("invalid task id"))?;
+
+        let settings = self
+            .controller
+            .get_task_settings(ssn_id, task_id)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(TaskSettings::from(&settings)))
+    }
+
     async fn create_task(&self, req: Request<CreateTaskRequest>) -> Result<Response<Task>, Status> {
         trace_fn!("Frontend::create_task");
+        let trace_id = trace_id(&req);
         let task_spec = req
             .into_inner()
             .task
@@ -437,9 +823,27 @@ impl Frontend for Flame {
             .parse::<apis::SessionID>()
             .map_err(|_| Status::invalid_argument("invalid session id"))?;
 
+        let codec = task_spec
+            .codec
+            .map(apis::PayloadCodec::try_from)
+            .transpose()
+            .map_err(Status::from)?;
+        self.controller
+            .validate_task_payload(ssn_id, codec, task_spec.input.as_deref())
+            .map_err(Status::from)?;
+
+        let overrides = apis::TaskOverrides::from(&task_spec);
         let task = self
             .controller
-            .create_task(ssn_id, task_spec.input.map(apis::TaskInput::from))
+            .create_task(
+                ssn_id,
+                apis::TaskAttributes {
+                    input: task_spec.input.map(apis::TaskInput::from),
+                    input_ref: task_spec.input_ref.map(apis::ObjectReference::from),
+                    overrides,
+                    trace_id,
+                },
+            )
             .await
             .map(Task::from)
             .map_err(Status::from)?;
@@ -453,6 +857,54 @@ impl Frontend for Flame {
         todo!()
     }
 
+    async fn watch_session(
+        &self,
+        req: Request<WatchSessionRequest>,
+    ) -> Result<Response<Self::WatchSessionStream>, Status> {
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+        // The session is not found before watching it.
+        self.controller.get_session(ssn_id.clone())?;
+
+        let (tx, rx) = mpsc::channel(128);
+
+        let controller = self.controller.clone();
+        tokio::spawn(async move {
+            let mut states = HashMap::new();
+            loop {
+                match controller.watch_session(ssn_id.clone(), &mut states).await {
+                    Ok(Some(tasks)) => {
+                        for task in &tasks {
+                            if let Err(e) = tx.send(Ok(Task::from(task))).await {
+                                tracing::debug!(
+                                    "Failed to send Task <{}> of Session <{ssn_id}>: {e}",
+                                    task.id
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    Ok(None) => {
+                        tracing::debug!("Session <{ssn_id}> is closed, exit.");
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::debug!("Failed to watch Session <{ssn_id}>: {e}");
+                        break;
+                    }
+                }
+            }
+        });
+
+        let output_stream = ReceiverStream::new(rx);
+        Ok(Response::new(
+            Box::pin(output_stream) as Self::WatchSessionStream
+        ))
+    }
+
     async fn watch_task(
         &self,
         req: Request<WatchTaskRequest>,
@@ -501,6 +953,75 @@ impl Frontend for Flame {
         ))
     }
 
+    async fn watch_task_output(
+        &self,
+        req: Request<WatchTaskOutputRequest>,
+    ) -> Result<Response<Self::WatchTaskOutputStream>, Status> {
+        let req = req.into_inner();
+        let gid = apis::TaskGID {
+            ssn_id: req
+                .session_id
+                .parse::<apis::SessionID>()
+                .map_err(|_| Status::invalid_argument("invalid session id"))?,
+
+            task_id: req
+                .task_id
+                .parse::<apis::TaskID>()
+                .map_err(|_| Status::invalid_argument("invalid task id"))?,
+        };
+
+        let (tx, rx) = mpsc::channel(128);
+
+        // The output of a completed task is its result, so nothing is streamed.
+        let Some(watcher) = self.controller.watch_task_output(&gid)? else {
+            return Ok(Response::new(
+                Box::pin(ReceiverStream::new(rx)) as Self::WatchTaskOutputStream
+            ));
+        };
+
+        let controller = self.controller.clone();
+        tokio::spawn(async move {
+            let mut receiver = watcher.receiver;
+            for chunk in watcher.buffered {
+                if tx.send(Ok(chunk)).await.is_err() {
+                    return;
+                }
+            }
+
+            loop {
+                match tokio::time::timeout(TASK_OUTPUT_CHECK_INTERVAL, receiver.recv()).await {
+                    Ok(Ok(chunk)) => {
+                        if let Err(e) = tx.send(Ok(chunk)).await {
+                            tracing::debug!("Failed to send the output of Task <{gid}>: {e}");
+                            break;
+                        }
+                    }
+                    Ok(Err(broadcast::error::RecvError::Lagged(n))) => {
+                        tracing::warn!("The watcher of Task <{gid}> lagged {n} output chunks.");
+                    }
+                    Ok(Err(broadcast::error::RecvError::Closed)) => {
+                        tracing::debug!("Task <{gid}> is completed, exit.");
+                        break;
+                    }
+                    // The task may be completed before it was watched.
+                    Err(_) => match controller.sweep_task_output(&gid) {
+                        Ok(false) => {}
+                        Ok(true) => break,
+                        Err(e) => {
+                            tracing::debug!("Failed to watch the output of Task <{gid}>: {e}");
+                            break;
+                        }
+                    },
+                }
+            }
+        });
+
+        let output_stream = ReceiverStream::new(rx);
+        Ok(Response::new(
+            Box::pin(output_stream) as Self::WatchTaskOutputStream
+        ))
+    }
+
     async fn get_task(&self, req: Request<GetTaskRequest>) -> Result<Response<Task>, Status> {
         let req = req.into_inner();
         let ssn_id = req
@@ -521,4 +1042,198 @@ impl Frontend for Flame {
 
         Ok(Response::new(task))
     }
+
+    async fn release_task(
+        &self,
+        req: Request<ReleaseTaskRequest>,
+    ) -> Result<Response<Task>, Status> {
+        self.controller.verify_admin(admin_token(&req).as_deref())?;
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+
+        let task_id = req
+            .task_id
+            .parse::<apis::TaskID>()
+            .map_err(|_| Status::invalid_argument("invalid task id"))?;
+
+        let task = self
+            .controller
+            .release_task(ssn_id, task_id)
+            .await
+            .map(Task::from)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(task))
+    }
+
+    async fn checkpoint_task(
+        &self,
+        req: Request<CheckpointTaskRequest>,
+    ) -> Result<Response<Task>, Status> {
+        let req = req.into_inner();
+        let ssn_id = req
+            .session_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid session id"))?;
+
+        let task_id = req
+            .task_id
+            .parse::<apis::TaskID>()
+            .map_err(|_| Status::invalid_argument("invalid task id"))?;
+
+        if req.checkpoint.is_empty() {
+            return Err(Status::invalid_argument("checkpoint is empty"));
+        }
+
+        let task = self
+            .controller
+            .checkpoint_task(ssn_id, task_id, req.checkpoint)
+            .await
+            .map(Task::from)
+            .map_err(Status::from)?;
+
+        Ok(Response::new(task))
+    }
+
+    async fn create_job(&self, req: Request<CreateJobRequest>) -> Result<Response<Job>, Status> {
+        trace_fn!("Frontend::create_job");
+        let attr = apis::JobAttributes::try_from(req.into_inner()).map_err(Status::from)?;
+
+        tracing::debug!(
+            "Creating job <{}> of application <{}> with {} tasks, parallelism {:?}",
+            attr.id,
+            attr.application,
+            attr.inputs.len(),
+            attr.parallelism
+        );
+
+        let job = self
+            .controller
+            .create_job(attr)
+            .await
+            .map(|job| Job::from(&job))
+            .map_err(Status::from)?;
+
+        Ok(Response::new(job))
+    }
+
+    async fn get_job(&self, req: Request<GetJobRequest>) -> Result<Response<Job>, Status> {
+        trace_fn!("Frontend::get_job");
+        let job_id = req
+            .into_inner()
+            .job_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid job id"))?;
+
+        let job = self
+            .controller
+            .get_job(&job_id)
+            .map(|job| Job::from(&job))
+            .map_err(Status::from)?;
+
+        Ok(Response::new(job))
+    }
+
+    async fn list_job(&self, _: Request<ListJobRequest>) -> Result<Response<JobList>, Status> {
+        trace_fn!("Frontend::list_job");
+        let jobs = self
+            .controller
+            .list_job()
+            .map_err(Status::from)?
+            .iter()
+            .map(|job| {
+                let mut job = Job::from(job);
+                // The inputs may be large, so they're only returned by GetJob.
+                if let Some(spec) = job.spec.as_mut() {
+                    spec.inputs.clear();
+                }
+                job
+            })
+            .collect();
+
+        Ok(Response::new(JobList { jobs }))
+    }
+
+    async fn cancel_job(&self, req: Request<CancelJobRequest>) -> Result<Response<Job>, Status> {
+        trace_fn!("Frontend::cancel_job");
+        let job_id = req
+            .into_inner()
+            .job_id
+            .parse::<apis::SessionID>()
+            .map_err(|_| Status::invalid_argument("invalid job id"))?;
+
+        let job = self
+            .controller
+            .cancel_job(&job_id)
+            .await
+            .map(|job| Job::from(&job))
+            .map_err(Status::from)?;
+
+        Ok(Response::new(job))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{self, AdminToken};
+    use crate::storage;
+    use common::ctx::{FlameAdmin, FlameCluster, FlameClusterContext};
+
+    fn register_request(token: Option<&str>) -> Request<RegisterApplicationRequest> {
+        let mut req = Request::new(RegisterApplicationRequest {
+            name: "app-1".to_string(),
+            application: Some(rpc::ApplicationSpec::default()),
+        });
+        if let Some(token) = token {
+            req.metadata_mut()
+                .insert(FLAME_ADMIN_TOKEN, token.parse().unwrap());
+        }
+        req
+    }
+
+    #[tokio::test]
+    async fn test_register_application_requires_admin() {
+        let dir = tempfile::tempdir().unwrap();
+        let token = "a".repeat(64);
+        let token_file = dir.path().join("admin-token");
+        std::fs::write(&token_file, &token).unwrap();
+        let admin = AdminToken::new(&FlameAdmin {
+            token_file: token_file.display().to_string(),
+        })
+        .unwrap();
+
+        let ctx = FlameClusterContext {
+            cluster: FlameCluster {
+                storage: "none".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let storage = storage::new_ptr(&ctx).await.unwrap();
+        let flame = Flame {
+            controller: controller::new_ptr(storage, None, Some(admin)),
+        };
+
+        let status = flame
+            .register_application(register_request(None))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let status = flame
+            .register_application(register_request(Some(&"b".repeat(64))))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let resp = flame
+            .register_application(register_request(Some(&token)))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_inner().return_code, 0);
+    }
 }
@@ -0,0 +1,202 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The plain HTTP endpoint of the session manager for the load balancers and
+//! the uptime monitors without the support of the gRPC health protocol:
+//!
+//! - `/healthz`: the session manager is alive;
+//! - `/readyz`: the session manager accepts new sessions, i.e. it's not
+//!   shedding load above the soft memory limit;
+//! - `/buildinfo`: the version, git sha and schema version of the build.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::header::CONTENT_TYPE;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_derive::Serialize;
+use tokio::net::TcpListener;
+
+use common::ctx::FlameClusterContext;
+
+use crate::controller::ControllerPtr;
+use crate::storage::SCHEMA_VERSION;
+use crate::{FlameError, FlameThread};
+
+use super::ALL_HOST_ADDRESS;
+
+/// The git sha of the build, see build.rs.
+const GIT_SHA: &str = env!("FLAME_GIT_SHA");
+
+#[derive(Serialize)]
+struct BuildInfo {
+    version: &'static str,
+    git_sha: &'static str,
+    schema_version: i64,
+}
+
+pub fn new_http(controller: ControllerPtr) -> Arc<dyn FlameThread> {
+    Arc::new(HttpRunner { controller })
+}
+
+struct HttpRunner {
+    controller: ControllerPtr,
+}
+
+#[async_trait::async_trait]
+impl FlameThread for HttpRunner {
+    async fn run(&self, ctx: FlameClusterContext) -> Result<(), FlameError> {
+        let port = ctx.cluster.http_port.ok_or_else(|| {
+            FlameError::InvalidConfig("http_port of the cluster is not set".to_string())
+        })?;
+
+        let address = format!("{ALL_HOST_ADDRESS}:{port}");
+        let listener = TcpListener::bind(&address)
+            .await
+            .map_err(|e| FlameError::Network(format!("failed to bind <{address}>: {e}")))?;
+        tracing::info!("Listening http endpoint at {}", address);
+
+        loop {
+            let (stream, peer) = listener
+                .accept()
+                .await
+                .map_err(|e| FlameError::Network(e.to_string()))?;
+
+            let controller = self.controller.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let controller = controller.clone();
+                    async move { Ok::<_, Infallible>(handle(&controller, &req)) }
+                });
+                if let Err(e) = hyper::server::conn::http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    tracing::debug!("Http connection from <{peer}> closed: {e}");
+                }
+            });
+        }
+    }
+}
+
+fn handle<B>(controller: &ControllerPtr, req: &Request<B>) -> Response<Full<Bytes>> {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            "text/plain",
+            "method not allowed",
+        );
+    }
+
+    match req.uri().path() {
+        "/healthz" => response(StatusCode::OK, "text/plain", "ok"),
+        "/readyz" if controller.is_shedding() => response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "text/plain",
+            "not ready: under memory pressure",
+        ),
+        "/readyz" => response(StatusCode::OK, "text/plain", "ok"),
+        "/buildinfo" => {
+            let info = BuildInfo {
+                version: env!("CARGO_PKG_VERSION"),
+                git_sha: GIT_SHA,
+                schema_version: SCHEMA_VERSION,
+            };
+            match serde_json::to_string(&info) {
+                Ok(body) => response(StatusCode::OK, "application/json", body),
+                Err(e) => response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "text/plain",
+                    e.to_string(),
+                ),
+            }
+        }
+        _ => response(StatusCode::NOT_FOUND, "text/plain", "not found"),
+    }
+}
+
+fn response(
+    status: StatusCode,
+    content_type: &'static str,
+    body: impl Into<Bytes>,
+) -> Response<Full<Bytes>> {
+    let mut resp = Response::new(Full::new(body.into()));
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(
+        CONTENT_TYPE,
+        content_type.parse().expect("valid content type"),
+    );
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller;
+    use crate::storage;
+    use common::ctx::FlameCluster;
+    use http_body_util::BodyExt;
+
+    async fn get(controller: &ControllerPtr, path: &str) -> (StatusCode, String) {
+        let req = Request::get(path).body(()).unwrap();
+        let resp = handle(controller, &req);
+        let status = resp.status();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_http_endpoints() {
+        let ctx = FlameClusterContext {
+            cluster: FlameCluster {
+                storage: "none".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let storage = storage::new_ptr(&ctx).await.unwrap();
+        let controller = controller::new_ptr(storage, None, None);
+
+        assert_eq!(
+            get(&controller, "/healthz").await,
+            (StatusCode::OK, "ok".to_string())
+        );
+        assert_eq!(
+            get(&controller, "/readyz").await,
+            (StatusCode::OK, "ok".to_string())
+        );
+
+        // The session manager is alive but not ready while shedding load.
+        controller.set_shedding(true);
+        assert_eq!(get(&controller, "/healthz").await.0, StatusCode::OK);
+        assert_eq!(
+            get(&controller, "/readyz").await.0,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let (status, body) = get(&controller, "/buildinfo").await;
+        assert_eq!(status, StatusCode::OK);
+        let info: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(info["git_sha"], GIT_SHA);
+        assert_eq!(info["schema_version"], SCHEMA_VERSION);
+
+        assert_eq!(get(&controller, "/metrics").await.0, StatusCode::NOT_FOUND);
+    }
+}
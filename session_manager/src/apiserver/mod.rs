@@ -18,12 +18,17 @@ use tonic::transport::Server;
 use common::ctx::FlameClusterContext;
 use rpc::flame::v1::backend_server::BackendServer;
 use rpc::flame::v1::frontend_server::FrontendServer;
+use rpc::grpc::reflection::v1alpha::server_reflection_server::ServerReflectionServer;
+use rpc::reflection::ReflectionService;
 
 use crate::controller::ControllerPtr;
 use crate::{FlameError, FlameThread};
 
 mod backend;
 mod frontend;
+mod http;
+
+pub use http::new_http;
 
 const DEFAULT_PORT: u16 = 8080;
 const ALL_HOST_ADDRESS: &str = "0.0.0.0";
@@ -32,6 +37,21 @@ pub struct Flame {
     controller: ControllerPtr,
 }
 
+/// Returns the gRPC reflection service if enabled, e.g. for grpcurl in dev environments.
+fn reflection_service(
+    ctx: &FlameClusterContext,
+) -> Result<Option<ServerReflectionServer<ReflectionService>>, FlameError> {
+    if !ctx.cluster.reflection {
+        return Ok(None);
+    }
+
+    let service = rpc::reflection::server(rpc::FILE_DESCRIPTOR_SET)
+        .map_err(|e| FlameError::Internal(format!("failed to load file descriptors: {e}")))?;
+    tracing::info!("gRPC reflection enabled for apiserver");
+
+    Ok(Some(service))
+}
+
 pub fn new_frontend(controller: ControllerPtr) -> Arc<dyn FlameThread> {
     Arc::new(FrontendRunner { controller })
 }
@@ -66,20 +86,32 @@ impl FlameThread for FrontendRunner {
 
         let mut builder = Server::builder().tcp_keepalive(Some(Duration::from_secs(1)));
 
-        // Apply TLS if configured
-        if let Some(ref tls_config) = ctx.cluster.tls {
-            let tls = tls_config.server_tls_config()?;
-            builder = builder
-                .tls_config(tls)
-                .map_err(|e| FlameError::InvalidConfig(format!("TLS config error: {}", e)))?;
-            tracing::info!("TLS enabled for frontend apiserver");
-        }
+        // Apply TLS if configured; the handshake is done by rustls with a TLS policy.
+        let incoming = match ctx.cluster.tls {
+            Some(ref tls_config) if tls_config.policy.is_some() => {
+                tracing::info!("TLS enabled with TLS policy for frontend apiserver");
+                Some(common::tls::incoming(tls_config, address).await?)
+            }
+            Some(ref tls_config) => {
+                let tls = tls_config.server_tls_config()?;
+                builder = builder
+                    .tls_config(tls)
+                    .map_err(|e| FlameError::InvalidConfig(format!("TLS config error: {}", e)))?;
+                tracing::info!("TLS enabled for frontend apiserver");
+                None
+            }
+            None => None,
+        };
 
-        builder
+        let router = builder
             .add_service(FrontendServer::new(frontend_service))
-            .serve(address)
-            .await
-            .map_err(|e| FlameError::Network(e.to_string()))?;
+            .add_optional_service(reflection_service(&ctx)?);
+
+        match incoming {
+            Some(incoming) => router.serve_with_incoming(incoming).await,
+            None => router.serve(address).await,
+        }
+        .map_err(|e| FlameError::Network(e.to_string()))?;
 
         Ok(())
     }
@@ -110,20 +142,32 @@ impl FlameThread for BackendRunner {
 
         let mut builder = Server::builder().tcp_keepalive(Some(Duration::from_secs(1)));
 
-        // Apply TLS if configured
-        if let Some(ref tls_config) = ctx.cluster.tls {
-            let tls = tls_config.server_tls_config()?;
-            builder = builder
-                .tls_config(tls)
-                .map_err(|e| FlameError::InvalidConfig(format!("TLS config error: {}", e)))?;
-            tracing::info!("TLS enabled for backend apiserver");
-        }
+        // Apply TLS if configured; the handshake is done by rustls with a TLS policy.
+        let incoming = match ctx.cluster.tls {
+            Some(ref tls_config) if tls_config.policy.is_some() => {
+                tracing::info!("TLS enabled with TLS policy for backend apiserver");
+                Some(common::tls::incoming(tls_config, address).await?)
+            }
+            Some(ref tls_config) => {
+                let tls = tls_config.server_tls_config()?;
+                builder = builder
+                    .tls_config(tls)
+                    .map_err(|e| FlameError::InvalidConfig(format!("TLS config error: {}", e)))?;
+                tracing::info!("TLS enabled for backend apiserver");
+                None
+            }
+            None => None,
+        };
 
-        builder
+        let router = builder
             .add_service(BackendServer::new(backend_service))
-            .serve(address)
-            .await
-            .map_err(|e| FlameError::Network(e.to_string()))?;
+            .add_optional_service(reflection_service(&ctx)?);
+
+        match incoming {
+            Some(incoming) => router.serve_with_incoming(incoming).await,
+            None => router.serve(address).await,
+        }
+        .map_err(|e| FlameError::Network(e.to_string()))?;
 
         Ok(())
     }
@@ -0,0 +1,71 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The credential of the administrators.
+//!
+//! The administrative RPCs, e.g. `CreateJoinToken`, are only served to the
+//! clients presenting the admin token of the cluster in their metadata. The
+//! token is compared by its HMAC, so the comparison takes constant time.
+
+use ring::hmac;
+use ring::rand::SystemRandom;
+
+use common::ctx::FlameAdmin;
+use common::FlameError;
+
+use super::join::read_secret_file;
+
+/// Verifies the admin token presented by the clients.
+pub struct AdminToken {
+    key: hmac::Key,
+    tag: hmac::Tag,
+}
+
+impl AdminToken {
+    pub fn new(config: &FlameAdmin) -> Result<Self, FlameError> {
+        let token = read_secret_file("token_file", &config.token_file)?;
+        Self::with_token(&token)
+    }
+
+    fn with_token(token: &[u8]) -> Result<Self, FlameError> {
+        let key = hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new())
+            .map_err(|_| FlameError::Internal("failed to generate admin key".to_string()))?;
+        let tag = hmac::sign(&key, token);
+        Ok(AdminToken { key, tag })
+    }
+
+    /// Verifies the admin token presented by the client.
+    pub fn verify(&self, token: Option<&str>) -> Result<(), FlameError> {
+        let token = token.ok_or_else(|| {
+            FlameError::Unauthenticated("the admin token is required".to_string())
+        })?;
+
+        hmac::verify(&self.key, token.trim().as_bytes(), self.tag.as_ref())
+            .map_err(|_| FlameError::Unauthenticated("invalid admin token".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_admin_token() {
+        let admin = AdminToken::with_token(&[b'a'; 64]).unwrap();
+
+        admin.verify(Some(&"a".repeat(64))).unwrap();
+        assert!(admin.verify(Some(&"b".repeat(64))).is_err());
+        assert!(admin.verify(Some("")).is_err());
+        assert!(admin.verify(None).is_err());
+    }
+}
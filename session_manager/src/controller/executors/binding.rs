@@ -11,6 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use chrono::Utc;
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
 use crate::controller::executors::States;
@@ -64,6 +65,18 @@ impl States for BindingState {
 
         let mut e = lock_ptr!(self.executor)?;
         e.state = ExecutorState::Bound;
+        e.task_start = Some(Utc::now());
+
+        Ok(())
+    }
+
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("BindingState::bind_session_failed");
+
+        let mut e = lock_ptr!(self.executor)?;
+        e.state = ExecutorState::Idle;
+        e.ssn_id = None;
+        e.batch_index = None;
 
         Ok(())
     }
@@ -80,7 +93,7 @@ impl States for BindingState {
         Err(FlameError::InvalidState("Executor is binding".to_string()))
     }
 
-    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("BindingState::launch_task");
 
         Err(FlameError::InvalidState("Executor is binding".to_string()))
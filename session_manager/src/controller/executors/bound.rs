@@ -20,7 +20,8 @@ use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
 use crate::model::ExecutorPtr;
 use common::apis::{
-    ExecutorState, SessionPtr, SessionState, Task, TaskOutput, TaskPtr, TaskResult, TaskState,
+    ExecutorState, SessionPtr, SessionState, Task, TaskDurationStats, TaskOutput, TaskPtr,
+    TaskResult, TaskState,
 };
 use common::FlameError;
 
@@ -64,6 +65,12 @@ impl States for BoundState {
         Err(FlameError::InvalidState("Executor is bound".to_string()))
     }
 
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("BoundState::bind_session_failed");
+
+        Err(FlameError::InvalidState("Executor is bound".to_string()))
+    }
+
     async fn unbind_executor(&self) -> Result<(), FlameError> {
         trace_fn!("BoundState::unbind_session");
 
@@ -79,15 +86,15 @@ impl States for BoundState {
         Err(FlameError::InvalidState("Executor is bound".to_string()))
     }
 
-    async fn launch_task(&self, ssn_ptr: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, ssn_ptr: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("BoundState::launch_task");
 
         tracing::debug!("Launching task for session");
 
-        let app_name = {
+        let (ssn_id, app_name) = {
             let ssn = lock_ptr!(ssn_ptr)?;
             tracing::debug!("Got session <{}>", ssn.id);
-            ssn.application.clone()
+            (ssn.id.clone(), ssn.application.clone())
         };
 
         tracing::debug!("Getting application <{}>", app_name);
@@ -95,22 +102,51 @@ impl States for BoundState {
         let app_ptr = self.storage.get_application(app_name.clone()).await?;
 
         tracing::debug!(
-            "Got application <{}>, delay release: {:?}",
+            "Got application <{}>, delay release: {:?}, bundle size: {}",
             app_name,
-            app_ptr.delay_release
+            app_ptr.delay_release,
+            app_ptr.bundle_size
         );
 
+        // The settings of the tasks without their own ones.
+        let settings = self.storage.get_task_settings(ssn_id, None)?;
+        let priority = settings.priority.value;
+
         let (batch_index, batch_size) = {
             let executor = lock_ptr!(self.executor)?;
             let ssn = lock_ptr!(ssn_ptr)?;
             (executor.batch_index, ssn.batch_size.max(1))
         };
 
-        let task_ptr =
-            WaitForTaskFuture::new(&ssn_ptr, app_ptr.delay_release, batch_index, batch_size)
-                .await?;
+        let task_ptr = WaitForTaskFuture::new(
+            &ssn_ptr,
+            app_ptr.delay_release,
+            batch_index,
+            batch_size,
+            priority,
+        )
+        .await?;
+
+        // No pending task, return.
+        let Some(task_ptr) = task_ptr else {
+            tracing::debug!("No pending task, return.");
+            return Ok(vec![]);
+        };
         tracing::debug!("Got task!");
 
+        // Bundle the other pending tasks without waiting for them.
+        let mut task_ptrs = vec![task_ptr];
+        {
+            let mut ssn = lock_ptr!(ssn_ptr)?;
+            let bundle_size = bundle_size(app_ptr.bundle_size, &ssn.task_durations);
+            while task_ptrs.len() < bundle_size as usize {
+                match ssn.pop_pending_task(batch_index.unwrap_or(0), batch_size, priority) {
+                    Some(task_ptr) => task_ptrs.push(task_ptr),
+                    None => break,
+                }
+            }
+        }
+
         let (exec_id, host) = {
             let e = lock_ptr!(self.executor)?;
             (e.id.clone(), e.node.clone())
@@ -118,54 +154,48 @@ impl States for BoundState {
 
         tracing::debug!("Got executor <{}>, host <{}>", exec_id, host);
 
-        let task_ptr = {
-            match task_ptr {
-                Some(task_ptr) => {
-                    let msg = format!("Running task on host <{}>.", host.clone());
-                    self.storage
-                        .update_task_state(
-                            ssn_ptr.clone(),
-                            task_ptr.clone(),
-                            TaskState::Running,
-                            Some(msg),
-                        )
-                        .await?;
-                    Some(task_ptr)
-                }
-                None => None,
-            }
-        };
-
-        // No pending task, return.
-        if task_ptr.is_none() {
-            tracing::debug!("No pending task, return.");
-            return Ok(None);
-        }
+        let mut tasks = vec![];
+        for task_ptr in task_ptrs {
+            let msg = format!("Running task on host <{}>.", host.clone());
+            self.storage
+                .update_task_state(
+                    ssn_ptr.clone(),
+                    task_ptr.clone(),
+                    TaskState::Running,
+                    Some(msg),
+                )
+                .await?;
 
-        // let task_ptr = task_ptr.unwrap();
-        let (ssn_id, task_id) = {
-            let task_ptr = task_ptr.clone().unwrap();
             let task = lock_ptr!(task_ptr)?;
-            (task.ssn_id.clone(), task.id)
-        };
-
-        tracing::debug!(
-            "Launching task <{}/{}> on host <{}> by executor {}",
-            ssn_id.clone(),
-            task_id.clone(),
-            host.clone(),
-            exec_id
-        );
+            tracing::debug!(
+                "Launching task <{}/{}> on host <{}> by executor {}",
+                task.ssn_id.clone(),
+                task.id,
+                host.clone(),
+                exec_id
+            );
+            // The executor enforces the effective timeout of the task.
+            let mut task = (*task).clone();
+            task.overrides.timeout = task.overrides.timeout.or(settings.timeout.value);
+            // The time in the queue is taken from the deadline of the task, so
+            // the executor only enforces the remaining budget.
+            let queued = (Utc::now() - task.creation_time).num_milliseconds().max(0) as u64;
+            task.overrides.deadline = task
+                .overrides
+                .deadline
+                .map(|deadline| deadline.saturating_sub(queued));
+            tasks.push(task);
+        }
 
         {
             let mut e = lock_ptr!(self.executor)?;
-            e.task_id = Some(task_id);
-            e.ssn_id = Some(ssn_id);
+            e.task_id = tasks.first().map(|task| task.id);
+            e.bundle = tasks.iter().skip(1).map(|task| task.id).collect();
+            e.ssn_id = tasks.first().map(|task| task.ssn_id.clone());
+            e.task_start = Some(Utc::now());
         };
 
-        let task_ptr = task_ptr.unwrap();
-        let task = lock_ptr!(task_ptr)?;
-        Ok(Some((*task).clone()))
+        Ok(tasks)
     }
 
     async fn complete_task(
@@ -176,25 +206,49 @@ impl States for BoundState {
     ) -> Result<(), FlameError> {
         trace_fn!("BoundState::complete_task");
 
+        let usage = task_result.usage;
         self.storage
-            .update_task_result(ssn_ptr, task_ptr, task_result)
+            .update_task_result(ssn_ptr.clone(), task_ptr, task_result)
             .await?;
 
         {
             let mut e = lock_ptr!(self.executor)?;
-            e.task_id = None;
+            // The next bundled task starts when the current one completes.
+            let now = Utc::now();
+            if let Some(start) = e.task_start.replace(now) {
+                let mut ssn = lock_ptr!(ssn_ptr)?;
+                ssn.task_durations.observe(now - start);
+                if let Some(usage) = &usage {
+                    ssn.task_usage.observe(usage, now - start);
+                }
+            }
+            e.task_id = (!e.bundle.is_empty()).then(|| e.bundle.remove(0));
         };
 
         Ok(())
     }
 }
 
+/// The target run time of a bundle in milliseconds; the longer the tasks, the fewer
+/// of them are bundled so they can be spread over more executors.
+const BUNDLE_DURATION_MS: f64 = 1000.0;
+
+/// Returns the number of tasks to launch together, up to the bundle size of the application.
+fn bundle_size(max: u32, stats: &TaskDurationStats) -> u32 {
+    let max = max.max(1);
+    match stats.expected() {
+        Some(expected) => ((BUNDLE_DURATION_MS / expected.max(1.0)) as u32).clamp(1, max),
+        None => max,
+    }
+}
+
 struct WaitForTaskFuture {
     ssn: SessionPtr,
     delay_release: Duration,
     start_time: DateTime<Utc>,
     batch_index: u32,
     batch_size: u32,
+    priority: u32,
 }
 
 impl WaitForTaskFuture {
@@ -203,6 +257,7 @@ impl WaitForTaskFuture {
         delay_release: Duration,
         batch_index: Option<u32>,
         batch_size: u32,
+        priority: u32,
     ) -> Self {
         Self {
             ssn: ssn.clone(),
@@ -210,6 +265,7 @@ impl WaitForTaskFuture {
             start_time: Utc::now(),
             batch_index: batch_index.unwrap_or(0),
             batch_size: batch_size.max(1),
+            priority,
         }
     }
 }
@@ -220,12 +276,12 @@ impl Future for WaitForTaskFuture {
     fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
         let mut ssn = lock_ptr!(self.ssn)?;
 
-        match ssn.pop_pending_task(self.batch_index, self.batch_size) {
+        match ssn.pop_pending_task(self.batch_index, self.batch_size, self.priority) {
             None => {
                 let now = Utc::now();
                 let duration = now.signed_duration_since(self.start_time);
                 if duration.num_seconds() > self.delay_release.num_seconds()
-                    || ssn.status.state == SessionState::Closed
+                    || ssn.status.state != SessionState::Open
                 {
                     Poll::Ready(Ok(None))
                 } else {
@@ -237,3 +293,27 @@ impl Future for WaitForTaskFuture {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::apis::MIN_TASK_DURATION_SAMPLES;
+
+    #[test]
+    fn test_bundle_size() {
+        let mut stats = TaskDurationStats::default();
+        assert_eq!(bundle_size(16, &stats), 16);
+        assert_eq!(bundle_size(0, &stats), 1);
+
+        for _ in 0..MIN_TASK_DURATION_SAMPLES {
+            stats.observe(Duration::milliseconds(100));
+        }
+        assert_eq!(bundle_size(16, &stats), 10);
+        assert_eq!(bundle_size(4, &stats), 4);
+
+        for _ in 0..32 {
+            stats.observe(Duration::seconds(5));
+        }
+        assert_eq!(bundle_size(16, &stats), 1);
+    }
+}
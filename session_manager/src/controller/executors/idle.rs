@@ -69,6 +69,12 @@ impl States for IdleState {
         Err(FlameError::InvalidState("Executor is idle".to_string()))
     }
 
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("IdleState::bind_session_failed");
+
+        Err(FlameError::InvalidState("Executor is idle".to_string()))
+    }
+
     async fn unbind_executor(&self) -> Result<(), FlameError> {
         trace_fn!("IdleState::unbind_executor");
 
@@ -81,7 +87,7 @@ impl States for IdleState {
         Err(FlameError::InvalidState("Executor is idle".to_string()))
     }
 
-    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("IdleState::launch_task");
 
         Err(FlameError::InvalidState("Executor is idle".to_string()))
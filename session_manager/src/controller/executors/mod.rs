@@ -72,11 +72,14 @@ pub trait States: Send + Sync + 'static {
 
     async fn bind_session(&self, ssn: SessionPtr) -> Result<(), FlameError>;
     async fn bind_session_completed(&self) -> Result<(), FlameError>;
+    /// The instance of the executor failed to start for the session, so the
+    /// executor is idle again.
+    async fn bind_session_failed(&self) -> Result<(), FlameError>;
 
     async fn unbind_executor(&self) -> Result<(), FlameError>;
     async fn unbind_executor_completed(&self) -> Result<(), FlameError>;
 
-    async fn launch_task(&self, ssn: SessionPtr) -> Result<Option<Task>, FlameError>;
+    async fn launch_task(&self, ssn: SessionPtr) -> Result<Vec<Task>, FlameError>;
     async fn complete_task(
         &self,
         ssn: SessionPtr,
@@ -90,7 +93,7 @@ mod tests {
     use super::*;
     use crate::model::Executor;
     use chrono::Utc;
-    use common::apis::{ResourceRequirement, Shim};
+    use common::apis::{ResourceRequirement, Shim, TaskAttributes};
     use common::ctx::{FlameCluster, FlameClusterContext, FlameExecutors, FlameLimits};
 
     fn create_test_executor(id: &str, state: ExecutorState) -> ExecutorPtr {
@@ -101,8 +104,12 @@ mod tests {
             slots: 1,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state,
         })
@@ -134,14 +141,42 @@ mod tests {
                 schedule_interval: 1000,
                 executors: FlameExecutors {
                     shim: Shim::default(),
+                    idle_timeout: None,
+                    prefetch: 0,
+                    bootstrap_concurrency: None,
+                    preempt_grace_period: None,
+                    registry: Default::default(),
+                    user: None,
+                    group: None,
+                    logs: Default::default(),
                 },
                 tls: None,
                 limits: FlameLimits {
                     max_sessions: None,
                     max_executors: 10,
                 },
+                encryption: None,
+                reflection: false,
+                http_port: None,
+                backup: None,
+                history: None,
+                exporter: None,
+                lease_grace_period: 60,
+                max_task_attempts: 3,
+                task_timeout: None,
+                task_priority: 0,
+                shuffle: Default::default(),
+                slo: Default::default(),
+                memory: None,
+                nodes: vec![],
+                runtimes: Default::default(),
+                join: None,
+                admin: None,
+                node_health: Default::default(),
+                scheduler: Default::default(),
             },
             cache: None,
+            paths: Default::default(),
         };
 
         crate::storage::new_ptr(&ctx).await.unwrap()
@@ -182,7 +217,7 @@ mod tests {
         }
 
         #[tokio::test]
-        async fn test_unregister_executor_fails() {
+        async fn test_unregister_rejected_executor() {
             let exe_ptr = create_test_executor("exe-1", ExecutorState::Void);
             let state = VoidState {
                 storage: create_mock_storage().await,
@@ -191,8 +226,8 @@ mod tests {
 
             let result = state.unregister_executor().await;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+            assert!(result.is_ok());
+            assert_eq!(get_state(&exe_ptr).unwrap(), ExecutorState::Released);
         }
 
         #[tokio::test]
@@ -297,7 +332,7 @@ mod tests {
             };
 
             let ssn = common::apis::Session {
-                id: "ssn-1".to_string(),
+                id: "ssn-1".into(),
                 ..Default::default()
             };
             let ssn_ptr = new_ptr(ssn);
@@ -308,7 +343,7 @@ mod tests {
             assert_eq!(get_state(&exe_ptr).unwrap(), ExecutorState::Binding);
 
             let exe = lock_ptr!(exe_ptr).unwrap();
-            assert_eq!(exe.ssn_id, Some("ssn-1".to_string()));
+            assert_eq!(exe.ssn_id, Some("ssn-1".into()));
         }
 
         #[tokio::test]
@@ -355,6 +390,19 @@ mod tests {
             assert!(matches!(result, Err(FlameError::InvalidState(_))));
         }
 
+        #[tokio::test]
+        async fn test_bind_session_failed_fails() {
+            let exe_ptr = create_test_executor("exe-1", ExecutorState::Idle);
+            let state = IdleState {
+                storage: create_mock_storage().await,
+                executor: exe_ptr.clone(),
+            };
+
+            let result = state.bind_session_failed().await;
+
+            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+        }
+
         #[tokio::test]
         async fn test_unbind_executor_fails() {
             let exe_ptr = create_test_executor("exe-1", ExecutorState::Idle);
@@ -404,6 +452,22 @@ mod tests {
             assert_eq!(get_state(&exe_ptr).unwrap(), ExecutorState::Bound);
         }
 
+        #[tokio::test]
+        async fn test_bind_session_failed_transitions_to_idle() {
+            let exe_ptr = create_test_executor("exe-1", ExecutorState::Binding);
+            lock_ptr!(exe_ptr).unwrap().ssn_id = Some("ssn-1".into());
+            let state = BindingState {
+                storage: create_mock_storage().await,
+                executor: exe_ptr.clone(),
+            };
+
+            let result = state.bind_session_failed().await;
+
+            assert!(result.is_ok());
+            assert_eq!(get_state(&exe_ptr).unwrap(), ExecutorState::Idle);
+            assert!(lock_ptr!(exe_ptr).unwrap().ssn_id.is_none());
+        }
+
         #[tokio::test]
         async fn test_bind_session_is_idempotent() {
             let exe_ptr = create_test_executor("exe-1", ExecutorState::Binding);
@@ -415,7 +479,7 @@ mod tests {
             };
 
             let ssn = common::apis::Session {
-                id: "ssn-2".to_string(),
+                id: "ssn-2".into(),
                 ..Default::default()
             };
             let ssn_ptr = new_ptr(ssn);
@@ -601,6 +665,75 @@ mod tests {
             assert!(result.is_err());
             assert!(matches!(result, Err(FlameError::InvalidState(_))));
         }
+
+        #[tokio::test]
+        async fn test_launch_task_with_bundle() {
+            let exe_ptr = create_test_executor("exe-1", ExecutorState::Bound);
+            let storage = create_mock_storage().await;
+
+            storage
+                .register_application(
+                    "flmbundle".to_string(),
+                    common::apis::ApplicationAttributes {
+                        bundle_size: 2,
+                        ..common::apis::ApplicationAttributes::default()
+                    },
+                )
+                .await
+                .unwrap();
+            let ssn = storage
+                .create_session(common::apis::SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "flmbundle".to_string(),
+                    ..common::apis::SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+            for _ in 0..3 {
+                storage
+                    .create_task(ssn.id.clone(), TaskAttributes::default())
+                    .await
+                    .unwrap();
+            }
+
+            let state = BoundState {
+                storage: storage.clone(),
+                executor: exe_ptr.clone(),
+            };
+            let ssn_ptr = storage.get_session_ptr(ssn.id.clone()).unwrap();
+
+            let tasks = state.launch_task(ssn_ptr.clone()).await.unwrap();
+            let ids: Vec<_> = tasks.iter().map(|t| t.id).collect();
+            assert_eq!(ids.len(), 2);
+            {
+                let exe = lock_ptr!(exe_ptr).unwrap();
+                assert_eq!(exe.task_id, Some(ids[0]));
+                assert_eq!(exe.bundle, vec![ids[1]]);
+            }
+
+            for (task_id, next) in [(ids[0], Some(ids[1])), (ids[1], None)] {
+                let task_ptr = storage
+                    .get_task_ptr(common::apis::TaskGID {
+                        ssn_id: ssn.id.clone(),
+                        task_id,
+                    })
+                    .unwrap();
+                let task_result = TaskResult {
+                    state: common::apis::TaskState::Succeed,
+                    ..TaskResult::default()
+                };
+                state
+                    .complete_task(ssn_ptr.clone(), task_ptr, task_result)
+                    .await
+                    .unwrap();
+                assert_eq!(lock_ptr!(exe_ptr).unwrap().task_id, next);
+            }
+            assert_eq!(lock_ptr!(ssn_ptr).unwrap().task_durations.count, 2);
+
+            let tasks = state.launch_task(ssn_ptr).await.unwrap();
+            assert_eq!(tasks.len(), 1);
+            assert!(!ids.contains(&tasks[0].id));
+        }
     }
 
     mod unbinding_state_tests {
@@ -613,8 +746,9 @@ mod tests {
 
             {
                 let mut exe = lock_ptr!(exe_ptr).unwrap();
-                exe.ssn_id = Some("ssn-1".to_string());
-                exe.task_id = Some(1);
+                exe.ssn_id = Some("ssn-1".into());
+                exe.task_id = Some(1.into());
+                exe.preempted = true;
             }
 
             let state = UnbindingState {
@@ -630,6 +764,7 @@ mod tests {
             let exe = lock_ptr!(exe_ptr).unwrap();
             assert!(exe.ssn_id.is_none());
             assert!(exe.task_id.is_none());
+            assert!(!exe.preempted);
         }
 
         #[tokio::test]
@@ -731,7 +866,74 @@ mod tests {
             let result = state.launch_task(ssn_ptr).await;
 
             assert!(result.is_ok());
-            assert!(result.unwrap().is_none());
+            assert!(result.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_complete_task_redispatches_bundle() {
+            let exe_ptr = create_test_executor("exe-1", ExecutorState::Bound);
+            let storage = create_mock_storage().await;
+
+            storage
+                .register_application(
+                    "flmbundle".to_string(),
+                    common::apis::ApplicationAttributes {
+                        bundle_size: 3,
+                        ..common::apis::ApplicationAttributes::default()
+                    },
+                )
+                .await
+                .unwrap();
+            let ssn = storage
+                .create_session(common::apis::SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "flmbundle".to_string(),
+                    ..common::apis::SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+            for _ in 0..3 {
+                storage
+                    .create_task(ssn.id.clone(), TaskAttributes::default())
+                    .await
+                    .unwrap();
+            }
+            let ssn_ptr = storage.get_session_ptr(ssn.id.clone()).unwrap();
+            let tasks = BoundState {
+                storage: storage.clone(),
+                executor: exe_ptr.clone(),
+            }
+            .launch_task(ssn_ptr.clone())
+            .await
+            .unwrap();
+            assert_eq!(tasks.len(), 3);
+
+            // The executor is unbound after its current task, and the bundled
+            // tasks it didn't run are dispatched again.
+            lock_ptr!(exe_ptr).unwrap().state = ExecutorState::Unbinding;
+            let state = UnbindingState {
+                storage: storage.clone(),
+                executor: exe_ptr.clone(),
+            };
+            let task_ptr = storage.get_task_ptr(tasks[0].gid()).unwrap();
+            let task_result = TaskResult {
+                state: common::apis::TaskState::Succeed,
+                ..TaskResult::default()
+            };
+            state
+                .complete_task(ssn_ptr, task_ptr, task_result)
+                .await
+                .unwrap();
+
+            {
+                let exe = lock_ptr!(exe_ptr).unwrap();
+                assert_eq!(exe.task_id, None);
+                assert!(exe.bundle.is_empty());
+            }
+            for task in &tasks[1..] {
+                let task = storage.get_task(task.ssn_id.clone(), task.id).unwrap();
+                assert_eq!(task.state, common::apis::TaskState::Pending);
+            }
         }
     }
 
@@ -979,7 +1181,7 @@ mod tests {
                 executor: exe_ptr.clone(),
             };
             let ssn = common::apis::Session {
-                id: "ssn-1".to_string(),
+                id: "ssn-1".into(),
                 ..Default::default()
             };
             let ssn_ptr = new_ptr(ssn);
@@ -68,6 +68,14 @@ impl States for ReleasingState {
         ))
     }
 
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("ReleasingState::bind_session_failed");
+
+        Err(FlameError::InvalidState(
+            "Executor is releasing".to_string(),
+        ))
+    }
+
     async fn unbind_executor(&self) -> Result<(), FlameError> {
         trace_fn!("ReleasingState::unbind_executor");
 
@@ -84,7 +92,7 @@ impl States for ReleasingState {
         ))
     }
 
-    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("ReleasingState::launch_task");
 
         Err(FlameError::InvalidState(
@@ -15,8 +15,10 @@ use crate::controller::executors::States;
 use crate::storage::StoragePtr;
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
-use crate::model::ExecutorPtr;
-use common::apis::{ExecutorState, SessionPtr, Task, TaskOutput, TaskPtr, TaskResult, TaskState};
+use crate::model::{Executor, ExecutorPtr};
+use common::apis::{
+    ExecutorState, FailureReason, SessionPtr, Task, TaskOutput, TaskPtr, TaskResult, TaskState,
+};
 use common::FlameError;
 
 pub struct UnbindingState {
@@ -24,6 +26,19 @@ pub struct UnbindingState {
     pub executor: ExecutorPtr,
 }
 
+impl UnbindingState {
+    /// Dispatch the running tasks left by the unbound executor again, e.g. the
+    /// bundled ones it didn't run.
+    async fn redispatch_leftover(&self, leftover: &Executor) -> Result<(), FlameError> {
+        let message = format!("executor <{}> was unbound", leftover.id);
+        self.storage
+            .redispatch_executor_tasks(leftover, FailureReason::Preempted, &message)
+            .await?;
+
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 impl States for UnbindingState {
     async fn register_executor(&self) -> Result<(), FlameError> {
@@ -66,6 +81,14 @@ impl States for UnbindingState {
         ))
     }
 
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("UnbindingState::bind_session_failed");
+
+        Err(FlameError::InvalidState(
+            "Executor is unbinding".to_string(),
+        ))
+    }
+
     async fn unbind_executor(&self) -> Result<(), FlameError> {
         trace_fn!("UnbindingState::unbind_session");
 
@@ -78,18 +101,25 @@ impl States for UnbindingState {
     async fn unbind_executor_completed(&self) -> Result<(), FlameError> {
         trace_fn!("UnbindingState::unbind_session_completed");
 
-        let mut e = lock_ptr!(self.executor)?;
-        e.state = ExecutorState::Idle;
-        e.ssn_id = None;
-        e.task_id = None;
+        let leftover = {
+            let mut e = lock_ptr!(self.executor)?;
+            let leftover = e.clone();
+            e.state = ExecutorState::Idle;
+            e.ssn_id = None;
+            e.task_id = None;
+            e.bundle.clear();
+            e.preempted = false;
+            leftover
+        };
+        self.redispatch_leftover(&leftover).await?;
 
         Ok(())
     }
 
-    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("UnbindingState::launch_task");
 
-        Ok(None)
+        Ok(vec![])
     }
 
     async fn complete_task(
@@ -104,11 +134,21 @@ impl States for UnbindingState {
             .update_task_result(ssn_ptr, task_ptr, task_result)
             .await?;
 
-        {
+        // The executor is unbound after the current task, so the bundled tasks
+        // after it are left.
+        let leftover = {
             let mut e = lock_ptr!(self.executor)?;
+            let mut bundle = std::mem::take(&mut e.bundle);
+            let leftover = Executor {
+                task_id: (!bundle.is_empty()).then(|| bundle.remove(0)),
+                bundle,
+                ..e.clone()
+            };
             e.task_id = None;
             e.ssn_id = None;
+            leftover
         };
+        self.redispatch_leftover(&leftover).await?;
 
         Ok(())
     }
@@ -40,10 +40,14 @@ impl States for VoidState {
         Err(FlameError::InvalidState("Executor is void".to_string()))
     }
 
+    /// The new executor is rejected by its node, e.g. the node reached its max
+    /// executors, so it's released without being started.
     async fn unregister_executor(&self) -> Result<(), FlameError> {
         trace_fn!("VoidState::unregister_executor");
+        let mut e = lock_ptr!(self.executor)?;
+        e.state = ExecutorState::Released;
 
-        Err(FlameError::InvalidState("Executor is void".to_string()))
+        Ok(())
     }
 
     async fn bind_session(&self, _ssn_ptr: SessionPtr) -> Result<(), FlameError> {
@@ -58,6 +62,12 @@ impl States for VoidState {
         Err(FlameError::InvalidState("Executor is void".to_string()))
     }
 
+    async fn bind_session_failed(&self) -> Result<(), FlameError> {
+        trace_fn!("VoidState::bind_session_failed");
+
+        Err(FlameError::InvalidState("Executor is void".to_string()))
+    }
+
     async fn unbind_executor(&self) -> Result<(), FlameError> {
         trace_fn!("VoidState::unbind_executor");
 
@@ -70,7 +80,7 @@ impl States for VoidState {
         Err(FlameError::InvalidState("Executor is void".to_string()))
     }
 
-    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Option<Task>, FlameError> {
+    async fn launch_task(&self, _ssn: SessionPtr) -> Result<Vec<Task>, FlameError> {
         trace_fn!("VoidState::launch_task");
 
         Err(FlameError::InvalidState("Executor is void".to_string()))
@@ -0,0 +1,398 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The jobs, i.e. a set of tasks run to completion in a session.
+//!
+//! The controller creates the session of the job, whose ID is the ID of the
+//! job, and submits its tasks up to the parallelism of the job; the job is
+//! synced by the tasks of its session periodically, which submits the next
+//! tasks and completes the job by its completion policy. The session is
+//! closed when the job is completed, and its remaining tasks are cancelled.
+//!
+//! The jobs are kept in memory, and the completed jobs are evicted after
+//! `JOB_RETENTION`. The running jobs are recorded by the storage, so the
+//! sessions of the jobs lost by a restart of the session manager are closed
+//! by force on recovery, instead of being left open forever.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use common::apis::{CompletionPolicy, Job, JobState, SessionID};
+use common::FlameError;
+use stdng::{lock_ptr, MutexPtr};
+use tokio_util::sync::CancellationToken;
+
+use crate::controller::ControllerPtr;
+
+/// The interval to sync the jobs by the tasks of their sessions.
+const JOB_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+/// How long the completed jobs are kept before they're evicted.
+pub const JOB_RETENTION: Duration = Duration::from_secs(3600);
+
+/// The tasks of the session of a job by their states.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TaskCounts {
+    /// The tasks not completed yet, i.e. pending, running or quarantined.
+    pub inflight: u32,
+    pub succeed: u32,
+    /// The failed and the cancelled tasks.
+    pub failed: u32,
+}
+
+impl TaskCounts {
+    /// The last counts of the job, e.g. after its session was deleted with
+    /// its tasks.
+    pub fn of(job: &Job) -> Self {
+        Self {
+            inflight: 0,
+            succeed: job.succeed,
+            failed: job.failed,
+        }
+    }
+}
+
+/// What the sync of a job does next.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobAction {
+    /// Submits the next tasks.
+    Submit(u32),
+    /// Completes the job, and closes its session.
+    Complete(JobState, Option<String>),
+    /// Waits for the submitted tasks.
+    Wait,
+}
+
+/// The next action of the running job by the tasks of its session; the
+/// session is closed if not `open`.
+pub fn next_action(job: &Job, counts: &TaskCounts, open: bool) -> JobAction {
+    let attr = &job.attributes;
+
+    if counts.failed > attr.max_failed {
+        return JobAction::Complete(
+            JobState::Failed,
+            Some(format!(
+                "{} tasks failed, more than {} tolerated",
+                counts.failed, attr.max_failed
+            )),
+        );
+    }
+
+    if attr.completion_policy == CompletionPolicy::AnyTask && counts.succeed > 0 {
+        return JobAction::Complete(JobState::Succeed, None);
+    }
+
+    let remaining = job.total().saturating_sub(job.submitted);
+    if remaining == 0 && counts.inflight == 0 {
+        return match attr.completion_policy {
+            CompletionPolicy::AllTasks => JobAction::Complete(JobState::Succeed, None),
+            CompletionPolicy::AnyTask => {
+                JobAction::Complete(JobState::Failed, Some("no task succeeded".to_string()))
+            }
+        };
+    }
+
+    if !open {
+        return JobAction::Complete(
+            JobState::Failed,
+            Some("the session of the job was closed".to_string()),
+        );
+    }
+
+    let slots = match attr.parallelism {
+        Some(parallelism) => parallelism.saturating_sub(counts.inflight),
+        None => remaining,
+    };
+    match remaining.min(slots) {
+        0 => JobAction::Wait,
+        n => JobAction::Submit(n),
+    }
+}
+
+/// The jobs managed by the controller.
+pub struct JobManager {
+    jobs: MutexPtr<HashMap<SessionID, Job>>,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        Self {
+            jobs: stdng::new_ptr(HashMap::new()),
+        }
+    }
+
+    pub fn add(&self, job: Job) -> Result<(), FlameError> {
+        let mut jobs = lock_ptr!(self.jobs)?;
+        if jobs.contains_key(job.id()) {
+            return Err(FlameError::AlreadyExist(format!(
+                "job <{}> already exists",
+                job.id()
+            )));
+        }
+        jobs.insert(job.id().clone(), job);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &SessionID) -> Result<Job, FlameError> {
+        let jobs = lock_ptr!(self.jobs)?;
+        jobs.get(id)
+            .cloned()
+            .ok_or(FlameError::NotFound(format!("job <{id}> not found")))
+    }
+
+    pub fn list(&self) -> Result<Vec<Job>, FlameError> {
+        let jobs = lock_ptr!(self.jobs)?;
+        let mut jobs: Vec<_> = jobs.values().cloned().collect();
+        jobs.sort_by_key(|job| job.creation_time);
+        Ok(jobs)
+    }
+
+    /// The IDs of the running jobs.
+    pub fn running(&self) -> Result<Vec<SessionID>, FlameError> {
+        let jobs = lock_ptr!(self.jobs)?;
+        Ok(jobs
+            .values()
+            .filter(|job| !job.state.is_terminal())
+            .map(|job| job.id().clone())
+            .collect())
+    }
+
+    /// Updates the counts of the running job, and completes it by the state
+    /// if any; the completed jobs are not updated anymore.
+    pub fn update(
+        &self,
+        id: &SessionID,
+        submitted: u32,
+        counts: &TaskCounts,
+        state: Option<(JobState, Option<String>)>,
+    ) -> Result<Job, FlameError> {
+        let mut jobs = lock_ptr!(self.jobs)?;
+        let job = jobs
+            .get_mut(id)
+            .ok_or(FlameError::NotFound(format!("job <{id}> not found")))?;
+
+        if !job.state.is_terminal() {
+            job.submitted += submitted;
+            job.succeed = counts.succeed;
+            job.failed = counts.failed;
+            if let Some((state, message)) = state {
+                job.state = state;
+                job.message = message;
+                job.completion_time = Some(Utc::now());
+            }
+        }
+
+        Ok(job.clone())
+    }
+
+    /// Evicts the jobs completed before the time, and returns how many.
+    pub fn evict(&self, before: DateTime<Utc>) -> Result<usize, FlameError> {
+        let mut jobs = lock_ptr!(self.jobs)?;
+        let count = jobs.len();
+        jobs.retain(|_, job| job.completion_time.is_none_or(|t| t >= before));
+        Ok(count - jobs.len())
+    }
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sync the running jobs by the tasks of their sessions periodically, and
+/// evict the completed ones after the retention, until it's shut down.
+pub async fn run(controller: ControllerPtr, shutdown: CancellationToken) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(JOB_SYNC_INTERVAL);
+    let retention = chrono::Duration::seconds(JOB_RETENTION.as_secs() as i64);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => return Ok(()),
+            _ = interval.tick() => {}
+        }
+
+        if let Err(e) = controller.sync_jobs().await {
+            tracing::error!("Failed to sync jobs: {e}");
+        }
+        match controller.evict_jobs(Utc::now() - retention) {
+            Ok(0) => {}
+            Ok(n) => tracing::debug!("Evicted {n} completed jobs."),
+            Err(e) => tracing::error!("Failed to evict jobs: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::apis::{JobAttributes, JobInputs};
+
+    fn job(count: u32, parallelism: Option<u32>, policy: CompletionPolicy) -> Job {
+        Job::new(JobAttributes {
+            id: SessionID::from("job-1"),
+            application: "app".to_string(),
+            slots: 1,
+            inputs: JobInputs::Count(count),
+            parallelism,
+            completion_policy: policy,
+            max_failed: 1,
+            common_data: None,
+        })
+    }
+
+    fn counts(inflight: u32, succeed: u32, failed: u32) -> TaskCounts {
+        TaskCounts {
+            inflight,
+            succeed,
+            failed,
+        }
+    }
+
+    #[test]
+    fn test_submit_by_parallelism() {
+        let mut job = job(10, Some(4), CompletionPolicy::AllTasks);
+        assert_eq!(
+            next_action(&job, &counts(0, 0, 0), true),
+            JobAction::Submit(4)
+        );
+
+        job.submitted = 4;
+        assert_eq!(next_action(&job, &counts(4, 0, 0), true), JobAction::Wait);
+        assert_eq!(
+            next_action(&job, &counts(1, 3, 0), true),
+            JobAction::Submit(3)
+        );
+
+        // The last tasks are submitted.
+        job.submitted = 8;
+        assert_eq!(
+            next_action(&job, &counts(0, 8, 0), true),
+            JobAction::Submit(2)
+        );
+
+        // All tasks are submitted at once without parallelism.
+        let job = super::tests::job(10, None, CompletionPolicy::AllTasks);
+        assert_eq!(
+            next_action(&job, &counts(0, 0, 0), true),
+            JobAction::Submit(10)
+        );
+    }
+
+    #[test]
+    fn test_complete_all_tasks() {
+        let mut job = job(4, None, CompletionPolicy::AllTasks);
+        job.submitted = 4;
+
+        assert_eq!(next_action(&job, &counts(1, 2, 1), true), JobAction::Wait);
+        // The failed tasks within max_failed are tolerated.
+        assert_eq!(
+            next_action(&job, &counts(0, 3, 1), true),
+            JobAction::Complete(JobState::Succeed, None)
+        );
+        assert!(matches!(
+            next_action(&job, &counts(1, 1, 2), true),
+            JobAction::Complete(JobState::Failed, Some(_))
+        ));
+    }
+
+    #[test]
+    fn test_complete_any_task() {
+        let mut job = job(4, Some(2), CompletionPolicy::AnyTask);
+        job.submitted = 2;
+
+        assert_eq!(
+            next_action(&job, &counts(1, 1, 0), true),
+            JobAction::Complete(JobState::Succeed, None)
+        );
+
+        job.submitted = 4;
+        assert_eq!(
+            next_action(&job, &counts(0, 0, 1), true),
+            JobAction::Complete(JobState::Failed, Some("no task succeeded".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_session_closed() {
+        let mut job = job(4, None, CompletionPolicy::AllTasks);
+        job.submitted = 4;
+
+        assert!(matches!(
+            next_action(&job, &counts(2, 2, 0), false),
+            JobAction::Complete(JobState::Failed, Some(_))
+        ));
+        // The job is completed by its tasks before the session was closed.
+        assert_eq!(
+            next_action(&job, &counts(0, 4, 0), false),
+            JobAction::Complete(JobState::Succeed, None)
+        );
+    }
+
+    #[test]
+    fn test_update_completed_job() {
+        let manager = JobManager::new();
+        manager
+            .add(job(4, None, CompletionPolicy::AllTasks))
+            .unwrap();
+        assert!(manager
+            .add(job(4, None, CompletionPolicy::AllTasks))
+            .is_err());
+
+        let id = SessionID::from("job-1");
+        let job = manager.update(&id, 4, &counts(4, 0, 0), None).unwrap();
+        assert_eq!(job.submitted, 4);
+        assert_eq!(manager.running().unwrap(), vec![id.clone()]);
+
+        let job = manager
+            .update(&id, 0, &counts(0, 4, 0), Some((JobState::Succeed, None)))
+            .unwrap();
+        assert_eq!(job.state, JobState::Succeed);
+        assert!(job.completion_time.is_some());
+        assert!(manager.running().unwrap().is_empty());
+
+        // The completed job is not updated anymore.
+        let job = manager
+            .update(&id, 1, &counts(0, 0, 4), Some((JobState::Failed, None)))
+            .unwrap();
+        assert_eq!(job.state, JobState::Succeed);
+        assert_eq!(job.submitted, 4);
+    }
+
+    #[test]
+    fn test_evict_completed_jobs() {
+        let manager = JobManager::new();
+        let mut completed = job(4, None, CompletionPolicy::AllTasks);
+        completed.attributes.id = SessionID::from("job-completed");
+        manager.add(completed).unwrap();
+        manager
+            .add(job(4, None, CompletionPolicy::AllTasks))
+            .unwrap();
+
+        let id = SessionID::from("job-completed");
+        let job = manager
+            .update(&id, 4, &counts(0, 4, 0), Some((JobState::Succeed, None)))
+            .unwrap();
+        let completion_time = job.completion_time.unwrap();
+
+        // The job completed within the retention is kept.
+        assert_eq!(manager.evict(completion_time).unwrap(), 0);
+
+        // Only the completed job is evicted, not the running one.
+        let before = completion_time + chrono::Duration::seconds(1);
+        assert_eq!(manager.evict(before).unwrap(), 1);
+        assert!(manager.get(&id).is_err());
+        assert_eq!(manager.running().unwrap(), vec![SessionID::from("job-1")]);
+    }
+}
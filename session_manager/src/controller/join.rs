@@ -0,0 +1,255 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The secure join of the nodes to the cluster.
+//!
+//! The administrator mints a one-time join token, e.g. by `flmctl join-token`,
+//! for the executor manager of the new node; the node presents it at its first
+//! `RegisterNode` and receives its credential, i.e. the node token, which is
+//! presented at the later registrations and heartbeats instead.
+//!
+//! The node token is the HMAC-SHA256 of the node name by the secret of the
+//! cluster, so it's verified without storing it, and all node tokens are
+//! revoked by rotating the secret. The join tokens are kept in memory, so
+//! the tokens not used yet are invalidated by a restart of the session manager.
+
+use std::collections::HashMap;
+use std::fs;
+
+use chrono::{Duration, Utc};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use common::apis::JoinToken;
+use common::ctx::FlameJoin;
+use common::FlameError;
+use stdng::{lock_ptr, MutexPtr};
+
+const MIN_SECRET_LEN: usize = 32;
+const JOIN_TOKEN_LEN: usize = 32;
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect()
+}
+
+/// Reads the secret of the `key` option from the file, e.g. `secret_file`,
+/// which must have at least 256 bits.
+pub(crate) fn read_secret_file(key: &str, path: &str) -> Result<Vec<u8>, FlameError> {
+    let content = fs::read(path)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to read {key} <{path}>: {e}")))?;
+
+    let secret = content.trim_ascii_end().to_vec();
+    if secret.len() < MIN_SECRET_LEN {
+        return Err(FlameError::InvalidConfig(format!(
+            "{key} <{path}> is shorter than {MIN_SECRET_LEN} bytes"
+        )));
+    }
+
+    Ok(secret)
+}
+
+/// Mints the join tokens and issues/verifies the credentials of the nodes.
+pub struct JoinManager {
+    key: hmac::Key,
+    token_ttl: u64,
+    rng: SystemRandom,
+    tokens: MutexPtr<HashMap<String, JoinToken>>,
+}
+
+impl JoinManager {
+    pub fn new(config: &FlameJoin) -> Result<Self, FlameError> {
+        let secret = read_secret_file("secret_file", &config.secret_file)?;
+        Ok(Self::with_secret(&secret, config.token_ttl))
+    }
+
+    fn with_secret(secret: &[u8], token_ttl: u64) -> Self {
+        JoinManager {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+            token_ttl,
+            rng: SystemRandom::new(),
+            tokens: stdng::new_ptr(HashMap::new()),
+        }
+    }
+
+    /// Mints a one-time join token, which is bound to the node if its name is
+    /// set; the token expires after the ttl in seconds, or the default one.
+    pub fn create_token(
+        &self,
+        node_name: Option<String>,
+        ttl: Option<u64>,
+    ) -> Result<JoinToken, FlameError> {
+        let mut bytes = [0u8; JOIN_TOKEN_LEN];
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| FlameError::Internal("failed to generate join token".to_string()))?;
+
+        let ttl = ttl.filter(|ttl| *ttl > 0).unwrap_or(self.token_ttl);
+        let token = JoinToken {
+            token: to_hex(&bytes),
+            node_name,
+            expiration_time: Utc::now() + Duration::seconds(ttl as i64),
+        };
+
+        let mut tokens = lock_ptr!(self.tokens)?;
+        let now = Utc::now();
+        tokens.retain(|_, t| t.expiration_time > now);
+        tokens.insert(token.token.clone(), token.clone());
+
+        Ok(token)
+    }
+
+    /// Admits the registration of a node: a valid node token is accepted as
+    /// is, otherwise the join token is consumed and the credential of the node
+    /// is returned.
+    pub fn admit(
+        &self,
+        node_name: &str,
+        join_token: Option<&str>,
+        node_token: Option<&str>,
+    ) -> Result<Option<String>, FlameError> {
+        if let Some(node_token) = node_token {
+            if self.verify(node_name, Some(node_token)).is_ok() {
+                return Ok(None);
+            }
+        }
+
+        let Some(join_token) = join_token else {
+            return Err(FlameError::Unauthenticated(format!(
+                "node <{node_name}> has no valid credential or join token"
+            )));
+        };
+
+        let mut tokens = lock_ptr!(self.tokens)?;
+        let valid = tokens.get(join_token).is_some_and(|t| {
+            t.expiration_time > Utc::now()
+                && t.node_name.as_deref().is_none_or(|name| name == node_name)
+        });
+        if !valid {
+            return Err(FlameError::Unauthenticated(format!(
+                "invalid or expired join token of node <{node_name}>"
+            )));
+        }
+        tokens.remove(join_token);
+
+        tracing::info!("Node <{node_name}> joined the cluster by a join token");
+
+        let tag = hmac::sign(&self.key, node_name.as_bytes());
+        Ok(Some(to_hex(tag.as_ref())))
+    }
+
+    /// Verifies the credential of the node.
+    pub fn verify(&self, node_name: &str, node_token: Option<&str>) -> Result<(), FlameError> {
+        let tag = node_token.and_then(from_hex).ok_or_else(|| {
+            FlameError::Unauthenticated(format!("node <{node_name}> has no valid credential"))
+        })?;
+
+        hmac::verify(&self.key, node_name.as_bytes(), &tag).map_err(|_| {
+            FlameError::Unauthenticated(format!("invalid credential of node <{node_name}>"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_manager() -> JoinManager {
+        JoinManager::with_secret(&[7u8; MIN_SECRET_LEN], 60)
+    }
+
+    #[test]
+    fn test_join_with_token() {
+        let manager = new_manager();
+        assert!(manager.admit("node-1", None, None).is_err());
+        assert!(manager.admit("node-1", Some("unknown"), None).is_err());
+
+        let token = manager.create_token(None, None).unwrap();
+        let node_token = manager
+            .admit("node-1", Some(&token.token), None)
+            .unwrap()
+            .unwrap();
+
+        // The join token is used only once.
+        assert!(manager.admit("node-2", Some(&token.token), None).is_err());
+
+        manager.verify("node-1", Some(&node_token)).unwrap();
+        assert!(manager.verify("node-2", Some(&node_token)).is_err());
+        assert!(manager.verify("node-1", Some("invalid")).is_err());
+        assert!(manager.verify("node-1", None).is_err());
+
+        // The registrations with the credential are admitted.
+        assert_eq!(
+            manager.admit("node-1", None, Some(&node_token)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_join_token_of_node() {
+        let manager = new_manager();
+        let token = manager
+            .create_token(Some("node-1".to_string()), Some(10))
+            .unwrap();
+        assert!(token.expiration_time <= Utc::now() + Duration::seconds(10));
+
+        assert!(manager.admit("node-2", Some(&token.token), None).is_err());
+        assert!(manager
+            .admit("node-1", Some(&token.token), None)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_expired_join_token() {
+        let manager = new_manager();
+        let token = manager.create_token(None, None).unwrap();
+        lock_ptr!(manager.tokens)
+            .unwrap()
+            .get_mut(&token.token)
+            .unwrap()
+            .expiration_time = Utc::now() - Duration::seconds(1);
+
+        assert!(manager.admit("node-1", Some(&token.token), None).is_err());
+    }
+
+    #[test]
+    fn test_read_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("join.secret");
+
+        fs::write(&path, "too short\n").unwrap();
+        assert!(read_secret_file("secret_file", &path.to_string_lossy()).is_err());
+
+        fs::write(&path, format!("{}\n", to_hex(&[1u8; 32]))).unwrap();
+        assert_eq!(
+            read_secret_file("secret_file", &path.to_string_lossy())
+                .unwrap()
+                .len(),
+            64
+        );
+    }
+}
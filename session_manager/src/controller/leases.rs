@@ -0,0 +1,39 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::time::Duration;
+
+use common::FlameError;
+
+use crate::controller::ControllerPtr;
+
+/// The interval to check the client leases of the sessions.
+const LEASE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Close the sessions whose client lease expired periodically; a session
+/// without lease, i.e. its client never renewed it, is never closed.
+pub async fn run(controller: ControllerPtr) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(LEASE_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match controller.expire_session_leases().await {
+            Ok(closed) if !closed.is_empty() => {
+                tracing::info!("Closed <{}> sessions of expired lease.", closed.len())
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to expire session leases: {e}"),
+        }
+    }
+}
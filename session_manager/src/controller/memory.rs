@@ -0,0 +1,142 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Memory guardrails of the session manager.
+//!
+//! The RSS of the process is checked periodically against the limits: above
+//! the soft limit, the new sessions are rejected and the closed sessions are
+//! evicted from the cache; above the hard limit, the storage is checkpointed
+//! and the guardrails exit with an error, which stops the session manager to be
+//! restarted by its supervisor, e.g. systemd or Kubernetes, with its state
+//! reloaded from the storage instead of being lost by an OOM-kill.
+
+use std::fs;
+use std::time::Duration;
+
+use common::ctx::FlameMemory;
+use common::FlameError;
+
+use crate::controller::ControllerPtr;
+
+/// The memory pressure of the session manager against the limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pressure {
+    Normal,
+    Soft,
+    Hard,
+}
+
+fn pressure(rss: u64, config: &FlameMemory) -> Pressure {
+    if config.hard_limit.is_some_and(|limit| rss >= limit) {
+        Pressure::Hard
+    } else if config.soft_limit.is_some_and(|limit| rss >= limit) {
+        Pressure::Soft
+    } else {
+        Pressure::Normal
+    }
+}
+
+/// Check the memory usage periodically; it only returns above the hard limit.
+pub async fn run(controller: ControllerPtr, config: FlameMemory) -> Result<(), FlameError> {
+    if read_rss().is_none() {
+        tracing::warn!("The memory usage is not available on this platform, skip the guardrails.");
+        return std::future::pending().await;
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval));
+
+    loop {
+        interval.tick().await;
+
+        let Some(rss) = read_rss() else {
+            continue;
+        };
+
+        match pressure(rss, &config) {
+            Pressure::Normal => {
+                if controller.is_shedding() {
+                    tracing::info!(
+                        "Memory usage <{rss}> is below the soft limit, accept new sessions."
+                    );
+                    controller.set_shedding(false);
+                }
+            }
+            Pressure::Soft => {
+                if !controller.is_shedding() {
+                    tracing::warn!(
+                        "Memory usage <{rss}> is above the soft limit, reject new sessions."
+                    );
+                    controller.set_shedding(true);
+                }
+                match controller.shrink_caches() {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!("Evicted <{n}> closed sessions from the cache."),
+                    Err(e) => tracing::error!("Failed to shrink the caches: {e}"),
+                }
+            }
+            Pressure::Hard => {
+                tracing::error!(
+                    "Memory usage <{rss}> is above the hard limit, checkpoint and restart."
+                );
+                controller.set_shedding(true);
+                if let Err(e) = controller.checkpoint().await {
+                    tracing::error!("Failed to checkpoint the storage: {e}");
+                }
+                return Err(FlameError::ResourceExhausted(format!(
+                    "memory usage <{rss}> is above the hard limit"
+                )));
+            }
+        }
+    }
+}
+
+/// The RSS in bytes of the session manager, i.e. `VmRSS` of `/proc/self/status`.
+fn read_rss() -> Option<u64> {
+    parse_rss(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+fn parse_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let status = "Name:\tflame\nVmHWM:\t    4096 kB\nVmRSS:\t    2048 kB\n";
+        assert_eq!(parse_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_rss("Name:\tflame\n"), None);
+    }
+
+    #[test]
+    fn test_pressure() {
+        let config = FlameMemory {
+            soft_limit: Some(100),
+            hard_limit: Some(200),
+            interval: 1,
+        };
+        assert_eq!(pressure(99, &config), Pressure::Normal);
+        assert_eq!(pressure(100, &config), Pressure::Soft);
+        assert_eq!(pressure(200, &config), Pressure::Hard);
+
+        let soft_only = FlameMemory {
+            hard_limit: None,
+            ..config
+        };
+        assert_eq!(pressure(1000, &soft_only), Pressure::Soft);
+    }
+}
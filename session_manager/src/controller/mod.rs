@@ -11,32 +11,57 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use chrono::{DateTime, Duration, Utc};
 use common::apis::{
-    Application, ApplicationAttributes, ApplicationID, CommonData, Event, EventOwner, ExecutorID,
-    ExecutorState, Node, NodeState, Session, SessionAttributes, SessionID, SessionPtr,
-    SessionState, Task, TaskGID, TaskID, TaskInput, TaskOutput, TaskPtr, TaskResult, TaskState,
+    Application, ApplicationAttributes, ApplicationID, ApplicationState, CommonData, Event,
+    EventFilter, EventOwner, EventPage, ExecutorID, ExecutorState, Job, JobAttributes, JobState,
+    JoinToken, Node, NodeState, PayloadCodec, ReplayPolicy, Session, SessionAttributes, SessionID,
+    SessionPtr, SessionState, SessionSummary, SlotRecommendation, Task, TaskAttributes, TaskGID,
+    TaskID, TaskOutput, TaskPtr, TaskResult, TaskSettings, TaskState, EXECUTOR_BINDING_EVENT,
+    EXECUTOR_BIND_FAILED_EVENT, EXECUTOR_BOUND_EVENT, EXECUTOR_UNBOUND_EVENT,
+    SESSION_JOB_LOST_EVENT, SESSION_RESUBMITTED_EVENT,
 };
 
+use common::ctx::{FlameNodeHealth, NodeConfig};
 use common::FlameError;
+use rpc::flame::v1 as rpc;
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
 use crate::model::{
     ConnectionCallbacks, ConnectionState, Executor, ExecutorFilter, ExecutorPtr, NodeConnectionPtr,
-    NodeConnectionReceiver, NodeConnectionSender, NodeInfoPtr, SessionInfoPtr, SnapShotPtr,
+    NodeConnectionReceiver, NodeConnectionSender, NodeInfoPtr, NodeLoad, SessionInfoPtr, SnapShot,
+    SnapShotPtr,
 };
 use crate::storage::StoragePtr;
 
+pub mod admin;
 mod connections;
 mod executors;
+pub mod jobs;
+pub mod join;
+pub mod leases;
+pub mod memory;
+pub mod node_health;
 mod nodes;
-
+pub mod session_gc;
+pub mod settings;
+pub mod task_output;
+pub mod task_watchdog;
+mod usage;
+mod utilization;
+
+pub use admin::AdminToken;
 pub use connections::ConnectionManager;
+pub use jobs::JobManager;
+pub use join::JoinManager;
+pub use task_output::{TaskOutputManager, TaskOutputWatcher};
 
 /// Callbacks for node connection lifecycle events.
 /// Implements the state machine transitions for node states.
@@ -89,20 +114,71 @@ impl ConnectionCallbacks for NodeCallbacks {
     }
 }
 
+/// The attempts of an update conflicting with the concurrent ones.
+const MAX_CONFLICT_ATTEMPTS: u32 = 3;
+
+/// The interval to check the state transitions of the tasks of the watched sessions.
+const WATCH_SESSION_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Run the optimistic update, which reads the expected version of the object,
+/// again while it conflicts with the concurrent updates; the last conflict is
+/// returned after `MAX_CONFLICT_ATTEMPTS` attempts.
+async fn retry_on_conflict<T, F, Fut>(mut update: F) -> Result<T, FlameError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FlameError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match update().await {
+            Err(FlameError::Conflict(e)) if attempt < MAX_CONFLICT_ATTEMPTS => {
+                tracing::debug!(
+                    "Retry the conflicting update ({attempt}/{MAX_CONFLICT_ATTEMPTS}): {e}"
+                );
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
 pub struct Controller {
     storage: StoragePtr,
     connection_manager: ConnectionManager<NodeCallbacks>,
+    /// Whether the new sessions are rejected, e.g. above the soft memory limit.
+    shedding: AtomicBool,
+    /// The join tokens and credentials of the nodes; any node can register if not set.
+    join: Option<JoinManager>,
+    /// The admin token of the administrative RPCs; the join tokens are not
+    /// minted if not set.
+    admin: Option<AdminToken>,
+    /// The state of the scheduler plugins in the latest scheduling cycle.
+    scheduler_state: MutexPtr<Option<rpc::SchedulerState>>,
+    /// The jobs running their tasks in the sessions.
+    jobs: JobManager,
+    /// The output streamed by the running tasks.
+    task_outputs: TaskOutputManager,
 }
 
 pub type ControllerPtr = Arc<Controller>;
 
-pub fn new_ptr(storage: StoragePtr) -> ControllerPtr {
+pub fn new_ptr(
+    storage: StoragePtr,
+    join: Option<JoinManager>,
+    admin: Option<AdminToken>,
+) -> ControllerPtr {
     let callbacks = NodeCallbacks {
         storage: storage.clone(),
     };
     Arc::new(Controller {
         storage,
         connection_manager: ConnectionManager::new(callbacks),
+        shedding: AtomicBool::new(false),
+        join,
+        admin,
+        scheduler_state: stdng::new_ptr(None),
+        jobs: JobManager::new(),
+        task_outputs: TaskOutputManager::new(),
     })
 }
 
@@ -116,6 +192,131 @@ impl Controller {
         &self.storage
     }
 
+    // ========================================================================
+    // Load Shedding
+    // ========================================================================
+
+    /// Starts or stops rejecting the new sessions, e.g. by the memory guardrails.
+    pub fn set_shedding(&self, shedding: bool) {
+        self.shedding.store(shedding, Ordering::SeqCst);
+    }
+
+    pub fn is_shedding(&self) -> bool {
+        self.shedding.load(Ordering::SeqCst)
+    }
+
+    fn check_shedding(&self) -> Result<(), FlameError> {
+        if self.is_shedding() {
+            return Err(FlameError::ResourceExhausted(
+                "session manager is under memory pressure, retry later".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Evicts the closed sessions from the cache of the storage.
+    pub fn shrink_caches(&self) -> Result<usize, FlameError> {
+        trace_fn!("Controller::shrink_caches");
+        self.storage.shrink_caches()
+    }
+
+    /// Flushes the storage, e.g. before exiting on the hard memory limit.
+    pub async fn checkpoint(&self) -> Result<(), FlameError> {
+        trace_fn!("Controller::checkpoint");
+        self.storage.checkpoint().await
+    }
+
+    // ========================================================================
+    // Node Join
+    // ========================================================================
+
+    /// Mints a one-time token for a node to join the cluster; the tokens are
+    /// only minted for the administrators, so the admin token is required.
+    pub fn create_join_token(
+        &self,
+        admin_token: Option<&str>,
+        node_name: Option<String>,
+        ttl: Option<u64>,
+    ) -> Result<JoinToken, FlameError> {
+        trace_fn!("Controller::create_join_token");
+        let join = self.join.as_ref().ok_or_else(|| {
+            FlameError::InvalidState("the join of the nodes is not configured".to_string())
+        })?;
+        let admin = self.admin.as_ref().ok_or_else(|| {
+            FlameError::InvalidState(
+                "the admin token is not configured to mint the join tokens".to_string(),
+            )
+        })?;
+        admin.verify(admin_token)?;
+        join.create_token(node_name, ttl)
+    }
+
+    /// Verifies the admin token presented in the administrative requests, e.g.
+    /// disabling an application; any client is an admin if it's not configured.
+    pub fn verify_admin(&self, admin_token: Option<&str>) -> Result<(), FlameError> {
+        match &self.admin {
+            Some(admin) => admin.verify(admin_token),
+            None => Ok(()),
+        }
+    }
+
+    /// Admits the registration of the node by its credential or a join token;
+    /// returns the credential issued to the node when it joins by the token.
+    pub fn admit_node(
+        &self,
+        node_name: &str,
+        join_token: Option<&str>,
+        node_token: Option<&str>,
+    ) -> Result<Option<String>, FlameError> {
+        match &self.join {
+            Some(join) => join.admit(node_name, join_token, node_token),
+            None => Ok(None),
+        }
+    }
+
+    /// Verifies the credential of the node, if the join is configured.
+    pub fn verify_node(&self, node_name: &str, node_token: Option<&str>) -> Result<(), FlameError> {
+        match &self.join {
+            Some(join) => join.verify(node_name, node_token),
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies the credential of the node presented in the backend RPCs of
+    /// an executor, which is rejected if the executor is on the other node.
+    /// The RPCs of a released executor are rejected too, if the executor of
+    /// its id was created again, i.e. its generation is stale.
+    pub fn verify_executor(
+        &self,
+        executor_id: &str,
+        generation: Option<u64>,
+        node_name: &str,
+        node_token: Option<&str>,
+    ) -> Result<(), FlameError> {
+        if let Some(join) = &self.join {
+            join.verify(node_name, node_token)?;
+        }
+
+        // The RPCs of an unknown executor fail by themselves.
+        let Ok(exe_ptr) = self.storage.get_executor_ptr(executor_id.to_string()) else {
+            return Ok(());
+        };
+        let exe = lock_ptr!(exe_ptr)?;
+        if self.join.is_some() && exe.node != node_name {
+            return Err(FlameError::Unauthenticated(format!(
+                "executor <{executor_id}> is not on node <{node_name}>"
+            )));
+        }
+        if let Some(generation) = generation.filter(|g| *g != exe.generation) {
+            return Err(FlameError::InvalidState(format!(
+                "executor <{executor_id}> of generation <{generation}> is stale, the current one is <{}>",
+                exe.generation
+            )));
+        }
+
+        Ok(())
+    }
+
     // ========================================================================
     // Node Management
     // ========================================================================
@@ -154,9 +355,12 @@ impl Controller {
         // Connect node and get sender (creates connection if not exists, calls on_connected)
         let (sender, _receiver) = self.connection_manager.connect(&node.name).await?;
 
-        // Build sets for comparison
-        let reported_ids: HashSet<String> =
-            reported_executors.iter().map(|e| e.id.clone()).collect();
+        // Build sets for comparison; a reported executor of a stale generation
+        // is not the one in DB, which reused its id.
+        let reported_generations: HashMap<String, u64> = reported_executors
+            .iter()
+            .map(|e| (e.id.clone(), e.generation))
+            .collect();
 
         // Get executors from DB for this node
         let db_executors = self
@@ -169,7 +373,10 @@ impl Controller {
 
         // 1. DB executors not reported by node - orphaned in DB, release them
         for db_exec in &db_executors {
-            if !reported_ids.contains(&db_exec.id) {
+            let reported = reported_generations
+                .get(&db_exec.id)
+                .is_some_and(|g| *g == 0 || *g == db_exec.generation);
+            if !reported {
                 tracing::info!(
                     "Executor <{}> in DB but not reported by node <{}>. Releasing orphaned executor.",
                     db_exec.id,
@@ -226,6 +433,12 @@ impl Controller {
         self.connection_manager.get_channel(node_name)
     }
 
+    /// Gets the node-level configuration of the node, which is sent to its
+    /// executor manager on each connection.
+    pub fn get_node_config(&self, node_name: &str) -> Result<NodeConfig, FlameError> {
+        self.storage.node_config(node_name)
+    }
+
     /// Drains a node when its watch stream disconnects.
     ///
     /// This handles the full drain lifecycle:
@@ -256,12 +469,14 @@ impl Controller {
     /// Used for periodic heartbeat updates from connected nodes.
     ///
     /// If the node doesn't exist or is not in Ready state, the update is
-    /// silently ignored (heartbeats are best-effort).
+    /// silently ignored (heartbeats are best-effort); the NotReady node is
+    /// Ready again.
     pub async fn update_node(&self, node: &Node) -> Result<(), FlameError> {
         trace_fn!("Controller::update_node");
 
         // Only update if node exists
         if let Ok(node_ptr) = self.storage.get_node_ptr(&node.name) {
+            self.storage.touch_node(&node.name)?;
             let state = nodes::from(self.storage.clone(), node_ptr)?;
             // Ignore errors from state machine (e.g., node not in Ready state)
             // Heartbeats are best-effort and shouldn't fail the connection
@@ -273,6 +488,75 @@ impl Controller {
         Ok(())
     }
 
+    /// Transitions the nodes without heartbeat to NotReady after the not-ready
+    /// timeout, and to Lost after the lost timeout, which requeues the running
+    /// tasks of their executors; returns the nodes transitioned with their
+    /// new state.
+    pub async fn check_node_health(
+        &self,
+        health: &FlameNodeHealth,
+    ) -> Result<Vec<(String, NodeState)>, FlameError> {
+        trace_fn!("Controller::check_node_health");
+
+        let now = Utc::now();
+        let mut transitioned = vec![];
+        for node in self.storage.list_node()? {
+            let last_heartbeat = self.storage.last_node_heartbeat(&node.name)?;
+            let silence = (now - last_heartbeat).num_seconds().max(0) as u64;
+            let node_ptr = self.storage.get_node_ptr(&node.name)?;
+
+            if silence >= health.not_ready_timeout && node.state == NodeState::Ready {
+                let state = nodes::from(self.storage.clone(), node_ptr.clone())?;
+                state.mark_not_ready().await?;
+                transitioned.push((node.name.clone(), NodeState::NotReady));
+            }
+
+            let current = lock_ptr!(node_ptr)?.state;
+            if silence >= health.lost_timeout
+                && matches!(current, NodeState::Unknown | NodeState::NotReady)
+            {
+                let state = nodes::from(self.storage.clone(), node_ptr)?;
+                state.shutdown().await?;
+                transitioned.push((node.name.clone(), NodeState::Lost));
+            }
+        }
+
+        Ok(transitioned)
+    }
+
+    /// The recent events of the node, i.e. the transitions of its state.
+    pub fn get_node_events(&self, name: &str) -> Result<Vec<Event>, FlameError> {
+        self.storage.node_events(name)
+    }
+
+    /// The time of the last heartbeat of the node.
+    pub fn get_node_heartbeat(&self, name: &str) -> Result<DateTime<Utc>, FlameError> {
+        self.storage.last_node_heartbeat(name)
+    }
+
+    /// Records the loads of the executors on the node by its heartbeat; the
+    /// scheduler prefers the executors of the less saturated nodes.
+    pub fn update_node_load(&self, name: &str, load: NodeLoad) -> Result<(), FlameError> {
+        self.storage.update_node_load(name, load)
+    }
+
+    /// The executors of the node and their max by its last heartbeat.
+    pub fn get_node_executors(&self, name: &str) -> Result<(u32, Option<u32>), FlameError> {
+        Ok(self
+            .storage
+            .get_node_load(name)?
+            .map(|load| (load.executors.len() as u32, load.max_executors))
+            .unwrap_or_default())
+    }
+
+    /// The saturation of the node by its last heartbeat, if known.
+    pub fn get_node_saturation(&self, name: &str) -> Result<Option<f64>, FlameError> {
+        Ok(self
+            .storage
+            .get_node_load(name)?
+            .and_then(|load| load.saturation()))
+    }
+
     /// Syncs node state and returns executors for the node.
     ///
     /// # Deprecated
@@ -311,6 +595,7 @@ impl Controller {
 
     pub async fn create_session(&self, attr: SessionAttributes) -> Result<Session, FlameError> {
         trace_fn!("Controller::create_session");
+        self.check_shedding()?;
         self.storage.create_session(attr).await
     }
 
@@ -318,14 +603,134 @@ impl Controller {
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         trace_fn!("Controller::open_session");
-        self.storage.open_session(id, spec).await
+        // The existing sessions are still opened, e.g. by the clients reconnecting.
+        if self.storage.get_session_ptr(id.clone()).is_err() {
+            self.check_shedding()?;
+        }
+        self.storage.open_session(id, spec, replay).await
     }
 
-    pub async fn close_session(&self, id: SessionID) -> Result<Session, FlameError> {
+    /// Closes the session; it's `Closing` until its running tasks are
+    /// completed, unless they're cancelled by `force`, which interrupts them.
+    pub async fn close_session(&self, id: SessionID, force: bool) -> Result<Session, FlameError> {
         trace_fn!("Controller::close_session");
-        self.storage.close_session(id).await
+        let ssn = retry_on_conflict(|| async {
+            let ssn_ptr = self.storage.get_session_ptr(id.clone())?;
+            let version = lock_ptr!(ssn_ptr)?.version;
+            self.storage
+                .close_session(id.clone(), Some(version), force)
+                .await
+        })
+        .await?;
+
+        if let Err(e) = self.task_outputs.close_session(&id) {
+            tracing::warn!("Failed to drop the task output of the closed session <{id}>: {e}");
+        }
+
+        if force {
+            let executors = self
+                .storage
+                .list_executor(Some(&ExecutorFilter::by_state(ExecutorState::Bound)))?;
+            for exe in executors {
+                if exe.ssn_id.as_ref() != Some(&id) || exe.task_id.is_none() {
+                    continue;
+                }
+                if let Err(e) = self.preempt_executor(exe.id.clone()).await {
+                    tracing::warn!(
+                        "Failed to interrupt the task of executor <{}> of the closed session <{id}>: {e}",
+                        exe.id
+                    );
+                }
+            }
+        }
+
+        Ok(ssn)
+    }
+
+    /// Creates a session with the attributes of the session, and resubmits the
+    /// inputs of its tasks in their order, or only of the failed ones; both
+    /// sessions record the resubmission as their events.
+    pub async fn resubmit_session(
+        &self,
+        id: SessionID,
+        new_id: SessionID,
+        only_failed: bool,
+        trace_id: Option<String>,
+    ) -> Result<Session, FlameError> {
+        trace_fn!("Controller::resubmit_session");
+        let ssn = self.storage.get_session(id.clone())?;
+
+        let mut tasks = vec![];
+        for task_ptr in ssn.tasks.values() {
+            let task = lock_ptr!(task_ptr)?;
+            if !only_failed || task.state == TaskState::Failed {
+                tasks.push((
+                    task.id,
+                    task.input.clone(),
+                    task.input_ref.clone(),
+                    task.overrides.clone(),
+                ));
+            }
+        }
+        tasks.sort_by_key(|(task_id, ..)| *task_id);
+
+        let attr = SessionAttributes {
+            id: new_id.clone(),
+            application: ssn.application.clone(),
+            slots: ssn.slots,
+            common_data: ssn.common_data.clone(),
+            min_instances: ssn.min_instances,
+            max_instances: ssn.max_instances,
+            batch_size: ssn.batch_size,
+            scratch_size: ssn.scratch_size,
+            max_task_attempts: ssn.max_task_attempts,
+            task_timeout: ssn.task_timeout,
+            task_priority: ssn.task_priority,
+            priority: ssn.priority,
+            result_policy: ssn.result_policy,
+            ttl_after_completion: ssn.ttl_after_completion,
+            trace_id,
+        };
+        self.create_session(attr).await?;
+
+        let count = tasks.len();
+        for (_, input, input_ref, overrides) in tasks {
+            self.create_task(
+                new_id.clone(),
+                TaskAttributes {
+                    input,
+                    input_ref,
+                    overrides,
+                    ..TaskAttributes::default()
+                },
+            )
+            .await?;
+        }
+
+        let records = [
+            (
+                new_id.clone(),
+                format!("Resubmitted {count} tasks of session <{id}>"),
+            ),
+            (
+                id.clone(),
+                format!("Resubmitted {count} tasks to session <{new_id}>"),
+            ),
+        ];
+        for (owner, message) in records {
+            let event = Event {
+                code: SESSION_RESUBMITTED_EVENT,
+                message: Some(message),
+                creation_time: Utc::now(),
+            };
+            self.record_event(EventOwner::session(owner), event).await?;
+        }
+        tracing::info!("Resubmitted {count} tasks of session <{id}> to session <{new_id}>.");
+
+        self.storage.get_session(new_id)
     }
 
     pub fn get_session(&self, id: SessionID) -> Result<Session, FlameError> {
@@ -340,12 +745,348 @@ impl Controller {
         self.storage.list_session()
     }
 
+    pub async fn list_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        self.storage.list_session_ids().await
+    }
+
+    /// Summarize the tasks of the session within the window; the failed tasks
+    /// are categorized by their failure reasons and the messages of their failure events.
+    pub fn get_session_summary(
+        &self,
+        id: SessionID,
+        window: Duration,
+    ) -> Result<SessionSummary, FlameError> {
+        trace_fn!("Controller::get_session_summary");
+        let ssn_ptr = self.storage.get_session_ptr(id.clone())?;
+        let (mut summary, failed) = {
+            let ssn = lock_ptr!(ssn_ptr)?;
+            let failed: Vec<TaskID> = ssn
+                .tasks_index
+                .get(&TaskState::Failed)
+                .map(|tasks| tasks.keys().copied().collect())
+                .unwrap_or_default();
+            (ssn.summary(window, Utc::now())?, failed)
+        };
+
+        for task_id in failed {
+            let task = self.storage.get_task(id.clone(), task_id)?;
+            let message = task
+                .events
+                .iter()
+                .rev()
+                .find(|e| e.code == i32::from(TaskState::Failed))
+                .and_then(|e| e.message.as_deref());
+            summary.add_failure(task.failure_reason, message);
+        }
+
+        Ok(summary)
+    }
+
+    /// Recommend the slots of the sessions, and of their applications, by the
+    /// resource usage of their tasks; all applications if none is given.
+    pub fn get_slot_recommendations(
+        &self,
+        application: Option<ApplicationID>,
+    ) -> Result<Vec<SlotRecommendation>, FlameError> {
+        trace_fn!("Controller::get_slot_recommendations");
+        let sessions = self.storage.list_session()?;
+        Ok(usage::recommend(
+            &sessions,
+            &self.storage.slot(),
+            application.as_ref(),
+        ))
+    }
+
+    /// The effective settings of the task, or of the tasks of the session
+    /// without their own settings if no task.
+    pub fn get_task_settings(
+        &self,
+        ssn_id: SessionID,
+        task_id: Option<TaskID>,
+    ) -> Result<TaskSettings, FlameError> {
+        trace_fn!("Controller::get_task_settings");
+        self.storage.get_task_settings(ssn_id, task_id)
+    }
+
+    /// Query the events of the session and its tasks by pages, without loading its tasks.
+    pub fn query_events(
+        &self,
+        id: SessionID,
+        filter: &EventFilter,
+        page_size: u32,
+        page_token: Option<&str>,
+    ) -> Result<EventPage, FlameError> {
+        trace_fn!("Controller::query_events");
+        self.storage.query_events(id, filter, page_size, page_token)
+    }
+
+    pub fn renew_session_lease(&self, id: &SessionID) -> Result<DateTime<Utc>, FlameError> {
+        trace_fn!("Controller::renew_session_lease");
+        self.storage.renew_session_lease(id)
+    }
+
+    pub fn lease_grace_period(&self) -> u64 {
+        self.storage.lease_grace_period()
+    }
+
+    /// Close the orphaned sessions, whose client lease expired.
+    pub async fn expire_session_leases(&self) -> Result<Vec<SessionID>, FlameError> {
+        trace_fn!("Controller::expire_session_leases");
+        self.storage.expire_session_leases().await
+    }
+
+    /// Delete the closed sessions whose TTL after completion expired.
+    pub async fn expire_sessions(&self) -> Result<Vec<SessionID>, FlameError> {
+        trace_fn!("Controller::expire_sessions");
+        self.storage.expire_sessions().await
+    }
+
+    /// Dispatch the running tasks of the hung executors again, which exceeded
+    /// their timeout by the grace period.
+    pub async fn expire_running_tasks(
+        &self,
+        grace_period: u64,
+    ) -> Result<Vec<TaskGID>, FlameError> {
+        trace_fn!("Controller::expire_running_tasks");
+        self.storage.expire_running_tasks(grace_period).await
+    }
+
+    // ========================================================================
+    // Jobs
+    // ========================================================================
+
+    /// Creates the session of the job, and submits its first tasks.
+    pub async fn create_job(&self, attr: JobAttributes) -> Result<Job, FlameError> {
+        trace_fn!("Controller::create_job");
+        attr.validate()?;
+
+        let ssn_attr = SessionAttributes {
+            id: attr.id.clone(),
+            application: attr.application.clone(),
+            slots: attr.slots,
+            common_data: attr.common_data.clone(),
+            ..SessionAttributes::default()
+        };
+        let id = attr.id.clone();
+        if self.jobs.get(&id).is_ok() {
+            return Err(FlameError::AlreadyExist(format!(
+                "job <{id}> already exists"
+            )));
+        }
+        // The job is recorded before its session, so the session is closed
+        // on recovery if the job is lost by a restart.
+        self.storage.create_job(&id).await?;
+        if let Err(e) = self.create_session(ssn_attr).await {
+            if let Err(e) = self.storage.delete_job(&id).await {
+                tracing::warn!("Failed to delete the record of job <{id}>: {e}");
+            }
+            return Err(e);
+        }
+        self.jobs.add(Job::new(attr))?;
+
+        self.sync_job(&id).await
+    }
+
+    pub fn get_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        self.jobs.get(id)
+    }
+
+    pub fn list_job(&self) -> Result<Vec<Job>, FlameError> {
+        self.jobs.list()
+    }
+
+    /// Cancels the running job, and its tasks by closing its session.
+    pub async fn cancel_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        trace_fn!("Controller::cancel_job");
+        let job = self.jobs.get(id)?;
+        if job.state.is_terminal() {
+            return Ok(job);
+        }
+
+        if let Err(e) = self.close_session(id.clone(), true).await {
+            tracing::warn!("Failed to close the session of the cancelled job <{id}>: {e}");
+        }
+        let counts = match self.job_tasks(id)? {
+            Some((counts, _)) => counts,
+            None => jobs::TaskCounts::of(&job),
+        };
+        self.complete_job(
+            id,
+            &counts,
+            JobState::Cancelled,
+            Some("cancelled".to_string()),
+        )
+        .await
+    }
+
+    /// Syncs the running jobs by the tasks of their sessions.
+    pub async fn sync_jobs(&self) -> Result<(), FlameError> {
+        for id in self.jobs.running()? {
+            if let Err(e) = self.sync_job(&id).await {
+                tracing::warn!("Failed to sync job <{id}>: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Submits the next tasks of the job, or completes it by its tasks.
+    async fn sync_job(&self, id: &SessionID) -> Result<Job, FlameError> {
+        trace_fn!("Controller::sync_job");
+        let job = self.jobs.get(id)?;
+        if job.state.is_terminal() {
+            return Ok(job);
+        }
+
+        let Some((counts, open)) = self.job_tasks(id)? else {
+            tracing::warn!("The session of job <{id}> was deleted, fail the job.");
+            return self
+                .complete_job(
+                    id,
+                    &jobs::TaskCounts::of(&job),
+                    JobState::Failed,
+                    Some("session deleted".to_string()),
+                )
+                .await;
+        };
+
+        match jobs::next_action(&job, &counts, open) {
+            jobs::JobAction::Submit(n) => {
+                let mut submitted = 0;
+                for index in job.submitted..job.submitted + n {
+                    let input = job.attributes.inputs.get(index);
+                    let res = self
+                        .create_task(
+                            id.clone(),
+                            TaskAttributes {
+                                input,
+                                ..TaskAttributes::default()
+                            },
+                        )
+                        .await;
+                    if let Err(e) = res {
+                        // The submitted tasks are still counted, and the rest
+                        // are submitted by the next sync.
+                        self.jobs.update(id, submitted, &counts, None)?;
+                        return Err(e);
+                    }
+                    submitted += 1;
+                }
+                self.jobs.update(id, submitted, &counts, None)
+            }
+            jobs::JobAction::Complete(state, message) => {
+                if open {
+                    self.close_session(id.clone(), counts.inflight > 0).await?;
+                }
+                tracing::info!(
+                    "Job <{id}> is {state}: succeed {}, failed {}.",
+                    counts.succeed,
+                    counts.failed
+                );
+                self.complete_job(id, &counts, state, message).await
+            }
+            jobs::JobAction::Wait => self.jobs.update(id, 0, &counts, None),
+        }
+    }
+
+    /// Completes the job by the state, and deletes its record, so its closed
+    /// session is not closed again on recovery.
+    async fn complete_job(
+        &self,
+        id: &SessionID,
+        counts: &jobs::TaskCounts,
+        state: JobState,
+        message: Option<String>,
+    ) -> Result<Job, FlameError> {
+        let job = self.jobs.update(id, 0, counts, Some((state, message)))?;
+        if let Err(e) = self.storage.delete_job(id).await {
+            tracing::warn!("Failed to delete the record of job <{id}>: {e}");
+        }
+        Ok(job)
+    }
+
+    /// Evicts the jobs completed before the time, and returns how many.
+    pub fn evict_jobs(&self, before: DateTime<Utc>) -> Result<usize, FlameError> {
+        self.jobs.evict(before)
+    }
+
+    /// Closes the sessions of the jobs lost by a restart, i.e. the recorded
+    /// jobs not in memory, by force, so their tasks are not left pending and
+    /// their sessions open forever.
+    pub async fn recover_jobs(&self) -> Result<(), FlameError> {
+        trace_fn!("Controller::recover_jobs");
+        for id in self.storage.find_job_ids().await? {
+            if self.jobs.get(&id).is_ok() {
+                continue;
+            }
+
+            let closed = match self.storage.get_session_ptr(id.clone()) {
+                Ok(ssn_ptr) => lock_ptr!(ssn_ptr)?.status.state == SessionState::Closed,
+                Err(FlameError::NotFound(_)) => true,
+                Err(e) => return Err(e),
+            };
+            if !closed {
+                tracing::warn!("Job <{id}> was lost by a restart, close its session.");
+                let event = Event {
+                    code: SESSION_JOB_LOST_EVENT,
+                    message: Some("The job of the session was lost by a restart".to_string()),
+                    creation_time: Utc::now(),
+                };
+                self.record_event(EventOwner::session(id.clone()), event)
+                    .await?;
+                if let Err(e) = self.close_session(id.clone(), true).await {
+                    tracing::error!("Failed to close the session of the lost job <{id}>: {e}");
+                    continue;
+                }
+            }
+
+            self.storage.delete_job(&id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The tasks of the session of the job by their states, and whether the
+    /// session is open; None if the session was deleted.
+    fn job_tasks(&self, id: &SessionID) -> Result<Option<(jobs::TaskCounts, bool)>, FlameError> {
+        let ssn_ptr = match self.storage.get_session_ptr(id.clone()) {
+            Ok(ssn_ptr) => ssn_ptr,
+            Err(FlameError::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let ssn = lock_ptr!(ssn_ptr)?;
+        let count = |state: TaskState| {
+            ssn.tasks_index
+                .get(&state)
+                .map(|tasks| tasks.len() as u32)
+                .unwrap_or_default()
+        };
+
+        let counts = jobs::TaskCounts {
+            inflight: count(TaskState::Pending)
+                + count(TaskState::Running)
+                + count(TaskState::Quarantined),
+            succeed: count(TaskState::Succeed),
+            failed: count(TaskState::Failed) + count(TaskState::Cancelled),
+        };
+
+        Ok(Some((counts, ssn.status.state == SessionState::Open)))
+    }
+
     pub async fn create_task(
         &self,
         ssn_id: SessionID,
-        task_input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError> {
-        self.storage.create_task(ssn_id, task_input).await
+        self.storage.create_task(ssn_id, attr).await
+    }
+
+    pub fn validate_task_payload(
+        &self,
+        ssn_id: SessionID,
+        codec: Option<PayloadCodec>,
+        payload: Option<&[u8]>,
+    ) -> Result<(), FlameError> {
+        self.storage.validate_task_payload(ssn_id, codec, payload)
     }
 
     pub fn get_task(&self, ssn_id: SessionID, id: TaskID) -> Result<Task, FlameError> {
@@ -356,6 +1097,34 @@ impl Controller {
         self.storage.list_task(ssn_id)
     }
 
+    pub async fn release_task(&self, ssn_id: SessionID, id: TaskID) -> Result<Task, FlameError> {
+        trace_fn!("Controller::release_task");
+        self.storage
+            .release_task(TaskGID {
+                ssn_id,
+                task_id: id,
+            })
+            .await
+    }
+
+    pub async fn checkpoint_task(
+        &self,
+        ssn_id: SessionID,
+        id: TaskID,
+        checkpoint: String,
+    ) -> Result<Task, FlameError> {
+        trace_fn!("Controller::checkpoint_task");
+        self.storage
+            .checkpoint_task(
+                TaskGID {
+                    ssn_id,
+                    task_id: id,
+                },
+                checkpoint,
+            )
+            .await
+    }
+
     pub async fn update_task_result(
         &self,
         ssn: SessionPtr,
@@ -444,6 +1213,45 @@ impl Controller {
         self.storage.snapshot()
     }
 
+    /// Persists the snapshot of the scheduler into the history of the cluster.
+    pub fn record_history(&self, snapshot: &SnapShot) -> Result<(), FlameError> {
+        trace_fn!("Controller::record_history");
+        self.storage.record_history(snapshot)?;
+        Ok(())
+    }
+
+    /// Records the state of the scheduler plugins at the end of a scheduling cycle.
+    pub fn record_scheduler_state(&self, state: rpc::SchedulerState) -> Result<(), FlameError> {
+        let mut scheduler_state = lock_ptr!(self.scheduler_state)?;
+        *scheduler_state = Some(state);
+        Ok(())
+    }
+
+    /// Gets the state of the scheduler plugins in the latest scheduling cycle.
+    pub fn get_scheduler_state(&self) -> Result<rpc::SchedulerState, FlameError> {
+        trace_fn!("Controller::get_scheduler_state");
+        let scheduler_state = lock_ptr!(self.scheduler_state)?;
+        scheduler_state
+            .clone()
+            .ok_or_else(|| FlameError::NotFound("no scheduling cycle completed yet".to_string()))
+    }
+
+    /// Gets the utilization of the nodes, the executors and the open sessions at present.
+    pub fn get_cluster_utilization(&self) -> Result<rpc::ClusterUtilization, FlameError> {
+        trace_fn!("Controller::get_cluster_utilization");
+        let snapshot = self.snapshot()?;
+        utilization::cluster_utilization(&snapshot, Utc::now())
+    }
+
+    /// Gets the state of the cluster at the time from its history, or the latest one.
+    pub fn get_cluster_snapshot(
+        &self,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<rpc::ClusterSnapshot, FlameError> {
+        trace_fn!("Controller::get_cluster_snapshot");
+        self.storage.get_history(at)
+    }
+
     pub async fn get_application(&self, id: ApplicationID) -> Result<Application, FlameError> {
         self.storage.get_application(id).await
     }
@@ -468,7 +1276,29 @@ impl Controller {
         attr: ApplicationAttributes,
     ) -> Result<(), FlameError> {
         trace_fn!("Controller::update_application");
-        self.storage.update_application(name, attr).await
+        retry_on_conflict(|| async {
+            let version = self.storage.get_application(name.clone()).await?.version;
+            self.storage
+                .update_application(name.clone(), attr.clone(), Some(version))
+                .await
+        })
+        .await
+    }
+
+    pub async fn enable_application(&self, name: String) -> Result<(), FlameError> {
+        trace_fn!("Controller::enable_application");
+        self.storage
+            .update_application_state(name, ApplicationState::Enabled)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn disable_application(&self, name: String) -> Result<(), FlameError> {
+        trace_fn!("Controller::disable_application");
+        self.storage
+            .update_application_state(name, ApplicationState::Disabled)
+            .await?;
+        Ok(())
     }
 
     pub async fn list_application(&self) -> Result<Vec<Application>, FlameError> {
@@ -481,8 +1311,59 @@ impl Controller {
         let task_ptr = self.storage.get_task_ptr(gid)?;
         WatchTaskFuture::new(self.storage.clone(), &task_ptr)?.await?;
 
-        let task = lock_ptr!(task_ptr)?;
-        Ok((*task).clone())
+        let task = lock_ptr!(task_ptr)?.clone();
+        if task.is_completed() && task.output.is_some() {
+            self.storage.discard_task_output(task.gid())?;
+        }
+
+        Ok(task)
+    }
+
+    /// Waits for the state transitions of the tasks of the session since the
+    /// `states` seen by the watcher, which are updated by the transitions; None
+    /// if the session is closed, and all its tasks were completed and seen.
+    pub async fn watch_session(
+        &self,
+        id: SessionID,
+        states: &mut HashMap<TaskID, TaskState>,
+    ) -> Result<Option<Vec<Task>>, FlameError> {
+        trace_fn!("Controller::watch_session");
+        loop {
+            let ssn_ptr = self.storage.get_session_ptr(id.clone())?;
+            let (closed, task_ptrs) = {
+                let ssn = lock_ptr!(ssn_ptr)?;
+                (
+                    ssn.status.state == SessionState::Closed,
+                    ssn.tasks.values().cloned().collect::<Vec<_>>(),
+                )
+            };
+
+            let mut tasks = vec![];
+            for task_ptr in task_ptrs {
+                let task = lock_ptr!(task_ptr)?;
+                if states.get(&task.id) != Some(&task.state) {
+                    states.insert(task.id, task.state);
+                    tasks.push(task.clone());
+                }
+            }
+
+            if !tasks.is_empty() {
+                tasks.sort_by_key(|task| task.id);
+                // The outputs are delivered to the watcher as by `watch_task`.
+                for task in &tasks {
+                    if task.is_completed() && task.output.is_some() {
+                        self.storage.discard_task_output(task.gid())?;
+                    }
+                }
+                return Ok(Some(tasks));
+            }
+
+            if closed && states.values().all(|state| state.is_terminal()) {
+                return Ok(None);
+            }
+
+            tokio::time::sleep(WATCH_SESSION_INTERVAL).await;
+        }
     }
 
     pub async fn wait_for_session(&self, id: ExecutorID) -> Result<Option<Session>, FlameError> {
@@ -511,7 +1392,7 @@ impl Controller {
         let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
         let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
 
-        let ssn_ptr = self.storage.get_session_ptr(ssn_id)?;
+        let ssn_ptr = self.storage.get_session_ptr(ssn_id.clone())?;
         state.bind_session(ssn_ptr).await?;
 
         let executor = {
@@ -520,6 +1401,8 @@ impl Controller {
             (*exe).clone()
         };
         self.storage.update_executor(&executor).await?;
+        self.record_executor_event(&ssn_id, &executor, EXECUTOR_BINDING_EVENT, "is binding")
+            .await;
 
         if let Err(e) = self
             .connection_manager
@@ -549,6 +1432,11 @@ impl Controller {
             (*exe).clone()
         };
         self.storage.update_executor(&executor).await?;
+        if let Some(ssn_id) = &executor.ssn_id {
+            self.storage.observe_bind_latency(ssn_id)?;
+            self.record_executor_event(ssn_id, &executor, EXECUTOR_BOUND_EVENT, "was bound")
+                .await;
+        }
 
         if let Err(e) = self
             .connection_manager
@@ -565,31 +1453,91 @@ impl Controller {
         Ok(())
     }
 
-    pub async fn launch_task(&self, id: ExecutorID) -> Result<Option<Task>, FlameError> {
+    /// The instance of the executor failed to start for its session, e.g. the binary
+    /// of the application is missing on the node; the executor is idle again, and the
+    /// application is not scheduled to the node until it's retried.
+    pub async fn bind_session_failed(
+        &self,
+        id: ExecutorID,
+        reason: String,
+    ) -> Result<(), FlameError> {
+        trace_fn!("Controller::bind_session_failed");
+
+        let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
+        let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
+
+        let ssn_id = lock_ptr!(exe_ptr)?.ssn_id.clone();
+        state.bind_session_failed().await?;
+
+        let executor = {
+            let exe = lock_ptr!(exe_ptr)?;
+            (*exe).clone()
+        };
+        self.storage.update_executor(&executor).await?;
+
+        if let Some(ssn_id) = &ssn_id {
+            let action = format!("failed to bind: {reason}");
+            self.record_executor_event(ssn_id, &executor, EXECUTOR_BIND_FAILED_EVENT, &action)
+                .await;
+        }
+
+        if let Some(ssn_ptr) = ssn_id.and_then(|ssn_id| self.storage.get_session_ptr(ssn_id).ok()) {
+            let app = lock_ptr!(ssn_ptr)?.application.clone();
+            self.storage
+                .mark_app_unavailable(&executor.node, &app, &reason)?;
+        }
+
+        if let Err(e) = self
+            .connection_manager
+            .notify_executor(&executor.node, &executor)
+            .await
+        {
+            tracing::debug!(
+                "Failed to notify node <{}> about bind failure: {}",
+                executor.node,
+                e
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Launches the tasks of the executor's session; the first one is the current task of
+    /// the executor and the others are bundled after it, see `Application::bundle_size`.
+    pub async fn launch_task(&self, id: ExecutorID) -> Result<Vec<Task>, FlameError> {
         trace_fn!("Controller::launch_task");
         let exe_ptr = self.storage.get_executor_ptr(id)?;
         let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
-        let (ssn_id, task_id) = {
+        let (ssn_id, task_id, bundle) = {
             let exec = lock_ptr!(exe_ptr)?;
-            (exec.ssn_id.clone(), exec.task_id)
+            (exec.ssn_id.clone(), exec.task_id, exec.bundle.clone())
         };
 
         tracing::debug!("Try to launch task for session <{:?}>", ssn_id);
         let Some(ssn_id) = ssn_id else {
             tracing::debug!("No session to launch task for, return.");
-            return Ok(None);
+            return Ok(vec![]);
         };
 
         if let Some(task_id) = task_id {
             tracing::warn!(
-                "Re-launch the task <{}/{}>",
+                "Re-launch the task <{}/{}> with {} bundled tasks",
                 ssn_id.clone(),
-                task_id.clone()
+                task_id.clone(),
+                bundle.len()
             );
-            let task_ptr = self.storage.get_task_ptr(TaskGID { ssn_id, task_id })?;
 
-            let task = lock_ptr!(task_ptr)?;
-            return Ok(Some((*task).clone()));
+            let mut tasks = vec![];
+            for task_id in std::iter::once(task_id).chain(bundle) {
+                let task_ptr = self.storage.get_task_ptr(TaskGID {
+                    ssn_id: ssn_id.clone(),
+                    task_id,
+                })?;
+                let task = lock_ptr!(task_ptr)?;
+                tasks.push((*task).clone());
+            }
+            lock_ptr!(exe_ptr)?.task_start = Some(Utc::now());
+            return Ok(tasks);
         }
 
         tracing::debug!("Launching task for session <{:?}>", ssn_id);
@@ -603,7 +1551,7 @@ impl Controller {
                     ssn_id,
                     msg
                 );
-                Ok(None)
+                Ok(vec![])
             }
             Err(e) => {
                 tracing::error!(
@@ -615,20 +1563,25 @@ impl Controller {
             }
         };
 
-        if result.is_ok() {
+        if let Ok(tasks) = &result {
             let executor = {
                 let exe = lock_ptr!(exe_ptr)?;
                 (*exe).clone()
             };
             self.storage.update_executor(&executor).await?;
+            self.storage.observe_dispatch_latency(tasks)?;
+            self.storage.export_dispatched(&executor.node, tasks)?;
         }
 
         result
     }
 
+    /// Completes the current task of the executor; the executor has to complete the bundled
+    /// tasks in order, so `expected` is checked against the current task if set.
     pub async fn complete_task(
         &self,
         id: ExecutorID,
+        expected: Option<TaskID>,
         task_result: TaskResult,
     ) -> Result<(), FlameError> {
         trace_fn!("Controller::complete_task");
@@ -645,6 +1598,12 @@ impl Controller {
             )
         };
 
+        if let Some(expected) = expected.filter(|expected| *expected != task_id) {
+            return Err(FlameError::InvalidState(format!(
+                "task <{ssn_id}/{expected}> is not the current task <{task_id}> of executor <{id}>"
+            )));
+        }
+
         let task_ptr = self.storage.get_task_ptr(TaskGID {
             ssn_id: ssn_id.clone(),
             task_id,
@@ -674,6 +1633,10 @@ impl Controller {
         let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
         state.complete_task(ssn_ptr, task_ptr, task_result).await?;
 
+        if let Err(e) = self.task_outputs.close(&ssn_id, task_id) {
+            tracing::warn!("Failed to drop the output of task <{ssn_id}/{task_id}>: {e}");
+        }
+
         let executor = {
             let exe = lock_ptr!(exe_ptr)?;
             (*exe).clone()
@@ -695,14 +1658,84 @@ impl Controller {
         Ok(())
     }
 
+    /// Pushes the output chunk of the current task of the executor to the
+    /// watchers of the task; the chunks of the other tasks, e.g. pushed after
+    /// the task was completed, are rejected.
+    pub fn push_task_output(
+        &self,
+        id: ExecutorID,
+        chunk: rpc::TaskOutputChunk,
+    ) -> Result<(), FlameError> {
+        let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
+        let (ssn_id, task_id) = {
+            let exe = lock_ptr!(exe_ptr)?;
+            (exe.ssn_id.clone(), exe.task_id)
+        };
+
+        let (Some(ssn_id), Some(task_id)) = (ssn_id, task_id) else {
+            return Err(FlameError::InvalidState(format!(
+                "no task in executor <{id}>"
+            )));
+        };
+        if ssn_id != chunk.session_id || task_id.to_string() != chunk.task_id {
+            return Err(FlameError::InvalidState(format!(
+                "task <{}/{}> is not the current task <{ssn_id}/{task_id}> of executor <{id}>",
+                chunk.session_id, chunk.task_id
+            )));
+        }
+
+        self.task_outputs.push(ssn_id, task_id, chunk)
+    }
+
+    /// Watches the output of the task, which is ended when the task is
+    /// completed; None if the task is completed already.
+    pub fn watch_task_output(
+        &self,
+        gid: &TaskGID,
+    ) -> Result<Option<TaskOutputWatcher>, FlameError> {
+        let task = self.storage.get_task(gid.ssn_id.clone(), gid.task_id)?;
+        if task.is_completed() {
+            return Ok(None);
+        }
+
+        self.task_outputs
+            .watch(gid.ssn_id.clone(), gid.task_id)
+            .map(Some)
+    }
+
+    /// Drops the output of the task if it's completed, e.g. the output of a
+    /// task watched after it was completed.
+    pub fn sweep_task_output(&self, gid: &TaskGID) -> Result<bool, FlameError> {
+        let task = self.storage.get_task(gid.ssn_id.clone(), gid.task_id)?;
+        if !task.is_completed() {
+            return Ok(false);
+        }
+
+        self.task_outputs.close(&gid.ssn_id, gid.task_id)?;
+        Ok(true)
+    }
+
     pub async fn unbind_executor(&self, id: ExecutorID) -> Result<(), FlameError> {
         trace_fn!("Controller::unbind_executor");
+        self.unbind(id, false).await
+    }
+
+    /// Unbinds the executor for a preemption: its running task is interrupted,
+    /// and the instance may checkpoint it within the grace period of the
+    /// executors before the task is requeued.
+    pub async fn preempt_executor(&self, id: ExecutorID) -> Result<(), FlameError> {
+        trace_fn!("Controller::preempt_executor");
+        self.unbind(id, true).await
+    }
+
+    async fn unbind(&self, id: ExecutorID, preempted: bool) -> Result<(), FlameError> {
         let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
         let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
         state.unbind_executor().await?;
 
         let executor = {
-            let exe = lock_ptr!(exe_ptr)?;
+            let mut exe = lock_ptr!(exe_ptr)?;
+            exe.preempted = preempted;
             (*exe).clone()
         };
         self.storage.update_executor(&executor).await?;
@@ -727,6 +1760,10 @@ impl Controller {
         let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
         let state = executors::from(self.storage.clone(), exe_ptr.clone())?;
 
+        let (ssn_id, preempted) = {
+            let exe = lock_ptr!(exe_ptr)?;
+            (exe.ssn_id.clone(), exe.preempted)
+        };
         state.unbind_executor_completed().await?;
 
         let executor = {
@@ -735,6 +1772,16 @@ impl Controller {
         };
         self.storage.update_executor(&executor).await?;
 
+        if let Some(ssn_id) = &ssn_id {
+            let action = if preempted {
+                "was unbound (preempted)"
+            } else {
+                "was unbound"
+            };
+            self.record_executor_event(ssn_id, &executor, EXECUTOR_UNBOUND_EVENT, action)
+                .await;
+        }
+
         if let Err(e) = self
             .connection_manager
             .notify_executor(&executor.node, &executor)
@@ -777,7 +1824,13 @@ impl Controller {
         Ok(())
     }
 
-    pub async fn unregister_executor(&self, id: ExecutorID) -> Result<(), FlameError> {
+    /// Unregisters the released executor, or the new one rejected by its node
+    /// with the reason, e.g. the node reached its max executors.
+    pub async fn unregister_executor(
+        &self,
+        id: ExecutorID,
+        reason: Option<String>,
+    ) -> Result<(), FlameError> {
         trace_fn!("Controller::unregister_executor");
         let exe_ptr = self.storage.get_executor_ptr(id.clone())?;
 
@@ -787,6 +1840,14 @@ impl Controller {
             (*exe).clone()
         };
 
+        if let Some(reason) = &reason {
+            tracing::warn!(
+                "Node <{}> rejected executor <{}>: {reason}",
+                executor.node,
+                executor.id
+            );
+        }
+
         let state = executors::from(self.storage.clone(), exe_ptr)?;
         state.unregister_executor().await?;
 
@@ -808,10 +1869,97 @@ impl Controller {
         Ok(())
     }
 
+    /// Records the binding of the executor as an event of the session, so the users
+    /// see where its instances run; the failure is only logged as it's not critical.
+    async fn record_executor_event(
+        &self,
+        ssn_id: &SessionID,
+        executor: &Executor,
+        code: i32,
+        action: &str,
+    ) {
+        let event = Event {
+            code,
+            message: Some(format!(
+                "Executor <{}> on node <{}> {}",
+                executor.id, executor.node, action
+            )),
+            creation_time: Utc::now(),
+        };
+
+        if let Err(e) = self
+            .storage
+            .record_event(EventOwner::session(ssn_id.clone()), event)
+            .await
+        {
+            tracing::warn!(
+                "Failed to record event of executor <{}> for session <{}>: {}",
+                executor.id,
+                ssn_id,
+                e
+            );
+        }
+    }
+
     pub async fn record_event(&self, owner: EventOwner, event: Event) -> Result<(), FlameError> {
         trace_fn!("Controller::record_event");
         self.storage.record_event(owner, event).await
     }
+
+    pub fn throttle_session(&self, id: &SessionID, reason: &str) -> Result<(), FlameError> {
+        trace_fn!("Controller::throttle_session");
+        self.storage.throttle_session(id, reason)
+    }
+
+    pub fn resume_session(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Controller::resume_session");
+        self.storage.resume_session(id)
+    }
+
+    pub fn mark_unschedulable(&self, id: &SessionID, reason: &str) -> Result<(), FlameError> {
+        trace_fn!("Controller::mark_unschedulable");
+        self.storage.mark_unschedulable(id, reason)
+    }
+
+    pub fn mark_schedulable(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Controller::mark_schedulable");
+        self.storage.mark_schedulable(id)
+    }
+
+    pub fn latency_report(&self) -> Result<String, FlameError> {
+        self.storage.latency_report()
+    }
+
+    /// The summary of the states of the nodes and their transitions, e.g. for the logs.
+    pub fn node_report(&self) -> Result<String, FlameError> {
+        self.storage.node_report()
+    }
+
+    /// The summary of the applications whose slots may be right-sized, e.g. for the logs.
+    pub fn usage_report(&self) -> Result<String, FlameError> {
+        let resized: Vec<String> = self
+            .get_slot_recommendations(None)?
+            .into_iter()
+            .filter(|r| r.session_id.is_none() && r.slots != r.recommended_slots)
+            .map(|r| format!("{} {}->{}", r.application, r.slots, r.recommended_slots))
+            .collect();
+        if resized.is_empty() {
+            return Ok("slot recommendations: none".to_string());
+        }
+
+        Ok(format!("slot recommendations: {}", resized.join(", ")))
+    }
+
+    pub fn record_shuffle(
+        &self,
+        exec_id: &ExecutorID,
+        from: &SessionID,
+        to: &SessionID,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        trace_fn!("Controller::record_shuffle");
+        self.storage.record_shuffle(exec_id, from, to, reason)
+    }
 }
 
 struct WatchTaskFuture {
@@ -889,7 +2037,7 @@ impl Future for WaitForSsnFuture {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common::apis::{Node, NodeInfo, NodeState, ResourceRequirement, Shim};
+    use common::apis::{Node, NodeInfo, NodeState, ResourceRequirement, Shim, TaskInput};
     use common::ctx::{FlameCluster, FlameClusterContext, FlameExecutors, FlameLimits};
     use tokio::sync::mpsc;
 
@@ -915,14 +2063,42 @@ mod tests {
                 schedule_interval: 1000,
                 executors: FlameExecutors {
                     shim: Shim::default(),
+                    idle_timeout: None,
+                    prefetch: 0,
+                    bootstrap_concurrency: None,
+                    preempt_grace_period: None,
+                    registry: Default::default(),
+                    user: None,
+                    group: None,
+                    logs: Default::default(),
                 },
                 tls: None,
                 limits: FlameLimits {
                     max_sessions: None,
                     max_executors: 10,
                 },
+                encryption: None,
+                reflection: false,
+                http_port: None,
+                backup: None,
+                history: None,
+                exporter: None,
+                lease_grace_period: 60,
+                max_task_attempts: 3,
+                task_timeout: None,
+                task_priority: 0,
+                shuffle: Default::default(),
+                slo: Default::default(),
+                memory: None,
+                nodes: vec![],
+                runtimes: Default::default(),
+                join: None,
+                admin: None,
+                node_health: Default::default(),
+                scheduler: Default::default(),
             },
             cache: None,
+            paths: Default::default(),
         };
 
         crate::storage::new_ptr(&ctx).await.unwrap()
@@ -944,6 +2120,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         }
     }
@@ -960,7 +2137,7 @@ mod tests {
         async fn test_update_node_does_not_transition_state() {
             // This tests that update_node (used for heartbeat) does NOT transition state
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("heartbeat-node");
 
@@ -983,7 +2160,7 @@ mod tests {
         #[tokio::test]
         async fn test_register_node_with_stream_transitions_to_ready() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("stream-node");
 
@@ -1001,7 +2178,7 @@ mod tests {
         #[tokio::test]
         async fn test_register_node_fresh_connection_succeeds() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("fresh-node");
 
@@ -1014,7 +2191,7 @@ mod tests {
         #[tokio::test]
         async fn test_register_node_replaces_existing_connection() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("replace-node");
 
@@ -1033,7 +2210,7 @@ mod tests {
         #[tokio::test]
         async fn test_register_node_reconnect_during_drain() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("drain-reconnect-node");
 
@@ -1059,7 +2236,7 @@ mod tests {
         #[tokio::test]
         async fn test_register_node_updates_node_info() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let mut node = create_test_node("update-node");
 
@@ -1095,7 +2272,7 @@ mod tests {
         #[tokio::test]
         async fn test_drain_node_transitions_to_unknown() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage.clone());
+            let controller = new_ptr(storage.clone(), None, None);
 
             let node = create_test_node("drain-test-node");
 
@@ -1115,7 +2292,7 @@ mod tests {
         #[tokio::test]
         async fn test_drain_nonexistent_node_succeeds() {
             let storage = create_test_storage().await;
-            let controller = new_ptr(storage);
+            let controller = new_ptr(storage, None, None);
 
             // Draining a node that doesn't exist should not error
             let result = controller.drain_node("nonexistent-node").await;
@@ -1123,4 +2300,535 @@ mod tests {
             assert!(result.is_ok());
         }
     }
+
+    // ========================================================================
+    // Controller::check_node_health Tests
+    // ========================================================================
+
+    mod node_health_tests {
+        use super::*;
+        use common::apis::{NODE_LOST_EVENT, NODE_NOT_READY_EVENT, NODE_READY_EVENT};
+        use common::ctx::FlameNodeHealth;
+
+        fn health(not_ready_timeout: u64, lost_timeout: u64) -> FlameNodeHealth {
+            FlameNodeHealth {
+                not_ready_timeout,
+                lost_timeout,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_node_without_heartbeat_is_not_ready_then_lost() {
+            let ctx = FlameClusterContext {
+                cluster: FlameCluster {
+                    storage: "none".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let storage = crate::storage::new_ptr(&ctx).await.unwrap();
+            let controller = new_ptr(storage.clone(), None, None);
+
+            let node = create_test_node("health-node");
+            controller.register_node(&node, &[]).await.unwrap();
+
+            // The running task on the executor of the node.
+            storage
+                .create_session(SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "test-app".to_string(),
+                    ..SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+            let task = storage
+                .create_task("ssn-1".into(), TaskAttributes::default())
+                .await
+                .unwrap();
+            storage
+                .update_task_state(
+                    storage.get_session_ptr("ssn-1".into()).unwrap(),
+                    storage.get_task_ptr(task.gid()).unwrap(),
+                    TaskState::Running,
+                    None,
+                )
+                .await
+                .unwrap();
+            let mut executor = storage
+                .create_executor("health-node".to_string(), "ssn-1".into(), None)
+                .await
+                .unwrap();
+            executor.ssn_id = Some("ssn-1".into());
+            executor.task_id = Some(task.id);
+            storage.update_executor(&executor).await.unwrap();
+
+            // The heartbeat is within the timeouts.
+            let transitioned = controller
+                .check_node_health(&health(60, 300))
+                .await
+                .unwrap();
+            assert!(transitioned.is_empty());
+
+            // NotReady without heartbeat, its executors are kept.
+            let transitioned = controller.check_node_health(&health(0, 300)).await.unwrap();
+            assert_eq!(
+                transitioned,
+                vec![("health-node".to_string(), NodeState::NotReady)]
+            );
+            let task = storage.get_task(task.ssn_id.clone(), task.id).unwrap();
+            assert_eq!(task.state, TaskState::Running);
+
+            // Ready again once its heartbeats resume.
+            controller.update_node(&node).await.unwrap();
+            let stored = storage.get_node("health-node").unwrap().unwrap();
+            assert_eq!(stored.state, NodeState::Ready);
+
+            // Lost without heartbeat, its running task is requeued.
+            let transitioned = controller.check_node_health(&health(0, 0)).await.unwrap();
+            assert_eq!(
+                transitioned,
+                vec![
+                    ("health-node".to_string(), NodeState::NotReady),
+                    ("health-node".to_string(), NodeState::Lost)
+                ]
+            );
+            let task = storage.get_task(task.ssn_id.clone(), task.id).unwrap();
+            assert_eq!(task.state, TaskState::Pending);
+            assert!(storage.list_executor(None).unwrap().is_empty());
+
+            let codes: Vec<i32> = controller
+                .get_node_events("health-node")
+                .unwrap()
+                .iter()
+                .map(|event| event.code)
+                .collect();
+            assert_eq!(
+                codes,
+                vec![
+                    NODE_READY_EVENT,
+                    NODE_NOT_READY_EVENT,
+                    NODE_READY_EVENT,
+                    NODE_NOT_READY_EVENT,
+                    NODE_LOST_EVENT
+                ]
+            );
+            assert!(controller
+                .node_report()
+                .unwrap()
+                .contains("node transitions: 2 Ready, 2 NotReady, 1 Lost"));
+        }
+    }
+
+    // ========================================================================
+    // Optimistic Concurrency Tests
+    // ========================================================================
+
+    mod conflict_tests {
+        use super::*;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        #[tokio::test]
+        async fn test_retry_on_conflict() {
+            let attempts = AtomicU32::new(0);
+            let res = retry_on_conflict(|| async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err(FlameError::Conflict("conflict".to_string())),
+                    n => Ok(n),
+                }
+            })
+            .await;
+            assert_eq!(res.unwrap(), 2);
+
+            // The last conflict is returned after all the attempts.
+            attempts.store(0, Ordering::SeqCst);
+            let res: Result<(), FlameError> = retry_on_conflict(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(FlameError::Conflict("conflict".to_string()))
+            })
+            .await;
+            assert!(matches!(res, Err(FlameError::Conflict(_))));
+            assert_eq!(attempts.load(Ordering::SeqCst), MAX_CONFLICT_ATTEMPTS);
+
+            // The other errors are not retried.
+            attempts.store(0, Ordering::SeqCst);
+            let res: Result<(), FlameError> = retry_on_conflict(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(FlameError::NotFound("ssn-1".to_string()))
+            })
+            .await;
+            assert!(matches!(res, Err(FlameError::NotFound(_))));
+            assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        }
+
+        #[tokio::test]
+        async fn test_concurrent_close_session() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            let ssn = storage
+                .create_session(SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "app-1".to_string(),
+                    slots: 1,
+                    ..SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+
+            // The close of the stale version is a conflict, and keeps the session open.
+            let res = storage
+                .close_session(ssn.id.clone(), Some(ssn.version + 1), false)
+                .await;
+            assert!(matches!(res, Err(FlameError::Conflict(_))));
+            assert_eq!(
+                storage.get_session(ssn.id.clone()).unwrap().status.state,
+                SessionState::Open
+            );
+
+            let (first, second) = tokio::join!(
+                controller.close_session(ssn.id.clone(), false),
+                controller.close_session(ssn.id.clone(), false)
+            );
+            assert!(first.is_ok());
+            assert!(second.is_ok());
+
+            let closed = storage.get_session(ssn.id.clone()).unwrap();
+            assert_eq!(closed.status.state, SessionState::Closed);
+            assert_eq!(closed.version, ssn.version + 2);
+        }
+
+        #[tokio::test]
+        async fn test_concurrent_update_application() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            let app = storage.get_application("app-1".to_string()).await.unwrap();
+
+            let update = |command: &str| ApplicationAttributes {
+                command: Some(command.to_string()),
+                ..ApplicationAttributes::default()
+            };
+            let (first, second) = tokio::join!(
+                controller.update_application("app-1".to_string(), update("/usr/bin/v2")),
+                controller.update_application("app-1".to_string(), update("/usr/bin/v3"))
+            );
+            assert!(first.is_ok());
+            assert!(second.is_ok());
+
+            // Both updates are applied, neither one is lost by the conflict.
+            let updated = storage.get_application("app-1".to_string()).await.unwrap();
+            assert_eq!(updated.version, app.version + 2);
+        }
+    }
+
+    // ========================================================================
+    // Controller::resubmit_session Tests
+    // ========================================================================
+
+    mod resubmit_session_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_resubmit_session() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            controller
+                .create_session(SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "app-1".to_string(),
+                    slots: 2,
+                    priority: 5,
+                    ..SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+
+            for (input, state) in [("1", TaskState::Succeed), ("2", TaskState::Failed)] {
+                let task = controller
+                    .create_task(
+                        "ssn-1".into(),
+                        TaskAttributes {
+                            input: Some(TaskInput::from(input)),
+                            ..TaskAttributes::default()
+                        },
+                    )
+                    .await
+                    .unwrap();
+                let ssn_ptr = storage.get_session_ptr("ssn-1".into()).unwrap();
+                let task_ptr = storage.get_task_ptr(task.gid()).unwrap();
+                storage
+                    .update_task_state(ssn_ptr, task_ptr, state, None)
+                    .await
+                    .unwrap();
+            }
+            controller
+                .close_session("ssn-1".into(), false)
+                .await
+                .unwrap();
+
+            let inputs = |id: &str| {
+                let mut tasks = storage.list_task(id.into()).unwrap();
+                tasks.sort_by_key(|t| t.id);
+                tasks
+                    .into_iter()
+                    .map(|t| t.input.unwrap_or_default())
+                    .collect::<Vec<_>>()
+            };
+
+            let ssn = controller
+                .resubmit_session("ssn-1".into(), "ssn-2".into(), false, None)
+                .await
+                .unwrap();
+            assert_eq!(ssn.status.state, SessionState::Open);
+            assert_eq!((ssn.slots, ssn.priority), (2, 5));
+            assert_eq!(inputs("ssn-2"), vec!["1", "2"]);
+
+            controller
+                .resubmit_session("ssn-1".into(), "ssn-3".into(), true, None)
+                .await
+                .unwrap();
+            assert_eq!(inputs("ssn-3"), vec!["2"]);
+
+            // The sessions are linked by their events.
+            let resubmitted = |id: &str| {
+                storage
+                    .get_session(id.into())
+                    .unwrap()
+                    .events
+                    .into_iter()
+                    .filter(|e| e.code == SESSION_RESUBMITTED_EVENT)
+                    .count()
+            };
+            assert_eq!(resubmitted("ssn-1"), 2);
+            assert_eq!(resubmitted("ssn-3"), 1);
+
+            let res = controller
+                .resubmit_session("ssn-0".into(), "ssn-4".into(), false, None)
+                .await;
+            assert!(matches!(res, Err(FlameError::NotFound(_))));
+        }
+    }
+
+    // ========================================================================
+    // Load Shedding Tests
+    // ========================================================================
+
+    mod watch_session_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_watch_session() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            controller
+                .create_session(SessionAttributes {
+                    id: "ssn-1".into(),
+                    application: "app-1".to_string(),
+                    slots: 1,
+                    ..SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+
+            let mut tasks = vec![];
+            for _ in 0..2 {
+                let task = controller
+                    .create_task("ssn-1".into(), TaskAttributes::default())
+                    .await
+                    .unwrap();
+                tasks.push(task);
+            }
+
+            // The current states of the tasks are the first transitions.
+            let mut states = HashMap::new();
+            let updated = controller
+                .watch_session("ssn-1".into(), &mut states)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                updated.iter().map(|t| t.id).collect::<Vec<_>>(),
+                vec![tasks[0].id, tasks[1].id]
+            );
+
+            // Only the tasks transitioned since are watched.
+            for task in &tasks {
+                let ssn_ptr = storage.get_session_ptr("ssn-1".into()).unwrap();
+                let task_ptr = storage.get_task_ptr(task.gid()).unwrap();
+                storage
+                    .update_task_state(ssn_ptr, task_ptr, TaskState::Succeed, None)
+                    .await
+                    .unwrap();
+                let updated = controller
+                    .watch_session("ssn-1".into(), &mut states)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(updated.len(), 1);
+                assert_eq!(
+                    (updated[0].id, updated[0].state),
+                    (task.id, TaskState::Succeed)
+                );
+            }
+
+            // The watch is ended once the session is closed.
+            controller
+                .close_session("ssn-1".into(), false)
+                .await
+                .unwrap();
+            assert!(controller
+                .watch_session("ssn-1".into(), &mut states)
+                .await
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    mod shedding_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_shedding_rejects_new_sessions() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            let attr = |id: &str| SessionAttributes {
+                id: id.to_string().into(),
+                application: "app-1".to_string(),
+                slots: 1,
+                ..SessionAttributes::default()
+            };
+            controller.create_session(attr("ssn-1")).await.unwrap();
+            controller
+                .close_session("ssn-1".into(), false)
+                .await
+                .unwrap();
+            controller.create_session(attr("ssn-2")).await.unwrap();
+
+            controller.set_shedding(true);
+            let res = controller.create_session(attr("ssn-3")).await;
+            assert!(matches!(res, Err(FlameError::ResourceExhausted(_))));
+            let res = controller
+                .open_session("ssn-3".into(), Some(attr("ssn-3")), None)
+                .await;
+            assert!(matches!(res, Err(FlameError::ResourceExhausted(_))));
+            // The existing sessions are still opened.
+            assert!(controller
+                .open_session("ssn-2".into(), None, None)
+                .await
+                .is_ok());
+
+            // Only the closed sessions are evicted from the cache.
+            assert_eq!(controller.shrink_caches().unwrap(), 1);
+            assert!(storage.get_session_ptr("ssn-1".into()).is_err());
+            assert!(storage.get_session_ptr("ssn-2".into()).is_ok());
+
+            controller.set_shedding(false);
+            assert!(controller.create_session(attr("ssn-3")).await.is_ok());
+        }
+    }
+
+    mod job_tests {
+        use super::*;
+        use common::apis::{CompletionPolicy, JobInputs};
+
+        #[tokio::test]
+        async fn test_fail_job_of_deleted_session() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            let job = controller
+                .create_job(JobAttributes {
+                    id: "job-1".into(),
+                    application: "app-1".to_string(),
+                    slots: 1,
+                    inputs: JobInputs::Count(4),
+                    parallelism: Some(2),
+                    completion_policy: CompletionPolicy::AllTasks,
+                    max_failed: 0,
+                    common_data: None,
+                })
+                .await
+                .unwrap();
+            assert_eq!(job.submitted, 2);
+
+            controller.delete_session("job-1".into()).await.unwrap();
+
+            let job = controller.sync_job(&"job-1".into()).await.unwrap();
+            assert_eq!(job.state, JobState::Failed);
+            assert_eq!(job.message.as_deref(), Some("session deleted"));
+            assert!(controller.jobs.running().unwrap().is_empty());
+            assert!(storage.find_job_ids().await.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_recover_lost_jobs() {
+            let storage = create_test_storage().await;
+            let controller = new_ptr(storage.clone(), None, None);
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            controller
+                .create_job(JobAttributes {
+                    id: "job-1".into(),
+                    application: "app-1".to_string(),
+                    slots: 1,
+                    inputs: JobInputs::Count(4),
+                    parallelism: None,
+                    completion_policy: CompletionPolicy::AllTasks,
+                    max_failed: 0,
+                    common_data: None,
+                })
+                .await
+                .unwrap();
+            assert_eq!(
+                storage.find_job_ids().await.unwrap(),
+                vec![SessionID::from("job-1")]
+            );
+
+            // The running job is not closed by the recovery of its own controller.
+            controller.recover_jobs().await.unwrap();
+            let ssn = storage.get_session("job-1".into()).unwrap();
+            assert_eq!(ssn.status.state, SessionState::Open);
+
+            // The job is lost by a restart, i.e. a controller without it.
+            let controller = new_ptr(storage.clone(), None, None);
+            controller.recover_jobs().await.unwrap();
+
+            let ssn = storage.get_session("job-1".into()).unwrap();
+            assert_eq!(ssn.status.state, SessionState::Closed);
+            let tasks = storage.list_task("job-1".into()).unwrap();
+            assert!(tasks.iter().all(|t| t.state == TaskState::Cancelled));
+            assert!(storage.find_job_ids().await.unwrap().is_empty());
+            assert!(controller.get_job(&"job-1".into()).is_err());
+        }
+    }
 }
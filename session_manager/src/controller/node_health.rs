@@ -0,0 +1,51 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The health of the nodes by their heartbeats.
+//!
+//! The node without heartbeat is NotReady after the not-ready timeout, so the
+//! scheduler stops allocating executors to it, and Lost after the lost timeout,
+//! so the running tasks of its executors are requeued to the other nodes.
+
+use std::time::Duration;
+
+use common::ctx::FlameNodeHealth;
+use common::FlameError;
+
+use crate::controller::ControllerPtr;
+
+/// The interval to check the heartbeats of the nodes.
+const NODE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Transition the nodes without heartbeat periodically.
+pub async fn run(controller: ControllerPtr, health: FlameNodeHealth) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(NODE_HEALTH_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match controller.check_node_health(&health).await {
+            Ok(transitioned) if !transitioned.is_empty() => {
+                for (node, state) in transitioned {
+                    tracing::warn!("Node <{node}> is {state} without heartbeat.");
+                }
+                match controller.node_report() {
+                    Ok(report) => tracing::info!("Node health: {report}."),
+                    Err(e) => tracing::error!("Failed to report node health: {e}"),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to check node health: {e}"),
+        }
+    }
+}
@@ -16,20 +16,25 @@ limitations under the License.
 //! State transitions:
 //! - Unknown -> Ready (on register/reconnect)
 //! - Ready -> Unknown (on drain - cleanup timer starts)
-//! - Unknown -> NotReady (on shutdown after cleanup timeout)
-//! - NotReady -> Ready (on reconnect after shutdown)
+//! - Ready -> NotReady (on no heartbeat within the not-ready timeout)
+//! - NotReady -> Ready (on heartbeat or reconnect)
+//! - Unknown/NotReady -> Lost (on shutdown after cleanup timeout, or no
+//!   heartbeat within the lost timeout)
+//! - Lost -> Ready (on reconnect after shutdown)
 
 use std::sync::Arc;
 
 use crate::controller::nodes::{
-    not_ready::NotReadyState, ready::ReadyState, unknown::UnknownState,
+    lost::LostState, not_ready::NotReadyState, ready::ReadyState, unknown::UnknownState,
 };
+use crate::model::ExecutorFilter;
 use crate::storage::StoragePtr;
 
 use common::apis::{Node, NodePtr, NodeState};
 use common::FlameError;
 use stdng::{lock_ptr, MutexPtr};
 
+mod lost;
 mod not_ready;
 mod ready;
 mod unknown;
@@ -52,9 +57,29 @@ pub fn from(storage: StoragePtr, node_ptr: NodePtr) -> Result<Arc<dyn NodeStates
             storage,
             node: node_ptr.clone(),
         })),
+        NodeState::Lost => Ok(Arc::new(LostState {
+            storage,
+            node: node_ptr.clone(),
+        })),
     }
 }
 
+/// Cleans up all executors on the lost node; their running tasks are dispatched again.
+async fn release_executors(storage: &StoragePtr, node_name: &str) -> Result<(), FlameError> {
+    let executors = storage.list_executor(Some(&ExecutorFilter::by_node(node_name)))?;
+
+    if !executors.is_empty() {
+        tracing::info!(
+            "Cleaning up {} executors for node <{}>",
+            executors.len(),
+            node_name
+        );
+        storage.delete_executors(&executors).await?;
+    }
+
+    Ok(())
+}
+
 /// Trait defining the operations available for each node state.
 ///
 /// Each state implements this trait, returning errors for invalid operations
@@ -62,19 +87,25 @@ pub fn from(storage: StoragePtr, node_ptr: NodePtr) -> Result<Arc<dyn NodeStates
 #[async_trait::async_trait]
 pub trait NodeStates: Send + Sync + 'static {
     /// Register a node (transition to Ready state).
-    /// Valid from: Unknown, NotReady
+    /// Valid from: Unknown, NotReady, Lost
     async fn register_node(&self) -> Result<(), FlameError>;
 
     /// Mark node as draining (transition to Unknown state, starts cleanup timer).
     /// Valid from: Ready
     async fn drain(&self) -> Result<(), FlameError>;
 
-    /// Shutdown node after cleanup timeout (transition to NotReady state).
-    /// Valid from: Unknown
+    /// Mark node as not ready after the heartbeat timeout (transition to NotReady
+    /// state); no executor is allocated to the node until it's Ready again.
+    /// Valid from: Ready
+    async fn mark_not_ready(&self) -> Result<(), FlameError>;
+
+    /// Shutdown node after cleanup timeout (transition to Lost state), and
+    /// release its executors.
+    /// Valid from: Unknown, NotReady
     async fn shutdown(&self) -> Result<(), FlameError>;
 
     /// Update node information (heartbeat, resource update).
-    /// Valid from: Ready
+    /// Valid from: Ready, NotReady
     async fn update_node(&self, node: &Node) -> Result<(), FlameError>;
 
     /// Release/unregister a node completely.
@@ -127,14 +158,42 @@ mod tests {
                 schedule_interval: 1000,
                 executors: FlameExecutors {
                     shim: Shim::default(),
+                    idle_timeout: None,
+                    prefetch: 0,
+                    bootstrap_concurrency: None,
+                    preempt_grace_period: None,
+                    registry: Default::default(),
+                    user: None,
+                    group: None,
+                    logs: Default::default(),
                 },
                 tls: None,
                 limits: FlameLimits {
                     max_sessions: None,
                     max_executors: 10,
                 },
+                encryption: None,
+                reflection: false,
+                http_port: None,
+                backup: None,
+                history: None,
+                exporter: None,
+                lease_grace_period: 60,
+                max_task_attempts: 3,
+                task_timeout: None,
+                task_priority: 0,
+                shuffle: Default::default(),
+                slo: Default::default(),
+                memory: None,
+                nodes: vec![],
+                runtimes: Default::default(),
+                join: None,
+                admin: None,
+                node_health: Default::default(),
+                scheduler: Default::default(),
             },
             cache: None,
+            paths: Default::default(),
         };
 
         crate::storage::new_ptr(&ctx).await.unwrap()
@@ -183,7 +242,21 @@ mod tests {
         }
 
         #[tokio::test]
-        async fn test_shutdown_transitions_to_not_ready() {
+        async fn test_mark_not_ready_is_ignored() {
+            let node_ptr = create_test_node("test-node", NodeState::Unknown);
+            let state = UnknownState {
+                storage: create_mock_storage().await,
+                node: node_ptr.clone(),
+            };
+
+            let result = state.mark_not_ready().await;
+
+            assert!(result.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Unknown);
+        }
+
+        #[tokio::test]
+        async fn test_shutdown_transitions_to_lost() {
             let node_ptr = create_test_node("test-node", NodeState::Unknown);
             let storage = create_mock_storage().await;
 
@@ -198,7 +271,7 @@ mod tests {
             let result = state.shutdown().await;
 
             assert!(result.is_ok());
-            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::NotReady);
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Lost);
         }
 
         #[tokio::test]
@@ -282,6 +355,28 @@ mod tests {
             assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Unknown);
         }
 
+        #[tokio::test]
+        async fn test_mark_not_ready_transitions_to_not_ready() {
+            let node_ptr = create_test_node("test-node", NodeState::Ready);
+            let storage = create_mock_storage().await;
+
+            let node = lock_ptr!(node_ptr).unwrap().clone();
+            storage.register_node(&node).await.unwrap();
+
+            let state = ReadyState {
+                storage: storage.clone(),
+                node: node_ptr.clone(),
+            };
+
+            let result = state.mark_not_ready().await;
+
+            assert!(result.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::NotReady);
+            let events = storage.node_events("test-node").unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].code, common::apis::NODE_NOT_READY_EVENT);
+        }
+
         #[tokio::test]
         async fn test_shutdown_fails_must_drain_first() {
             let node_ptr = create_test_node("test-node", NodeState::Ready);
@@ -319,6 +414,7 @@ mod tests {
                 info: NodeInfo {
                     arch: "x86_64".to_string(),
                     os: "linux".to_string(),
+                    ..Default::default()
                 },
             };
 
@@ -371,7 +467,7 @@ mod tests {
         }
 
         #[tokio::test]
-        async fn test_drain_fails_already_not_ready() {
+        async fn test_drain_is_ignored() {
             let node_ptr = create_test_node("test-node", NodeState::NotReady);
             let state = NotReadyState {
                 storage: create_mock_storage().await,
@@ -380,44 +476,58 @@ mod tests {
 
             let result = state.drain().await;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+            assert!(result.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::NotReady);
         }
 
         #[tokio::test]
-        async fn test_shutdown_is_idempotent() {
+        async fn test_shutdown_transitions_to_lost() {
             let node_ptr = create_test_node("test-node", NodeState::NotReady);
+            let storage = create_mock_storage().await;
+
+            let node = lock_ptr!(node_ptr).unwrap().clone();
+            storage.register_node(&node).await.unwrap();
+
             let state = NotReadyState {
-                storage: create_mock_storage().await,
+                storage,
                 node: node_ptr.clone(),
             };
 
             let result = state.shutdown().await;
 
             assert!(result.is_ok());
-            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::NotReady);
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Lost);
         }
 
         #[tokio::test]
-        async fn test_update_node_fails_must_reconnect() {
+        async fn test_update_node_transitions_to_ready() {
             let node_ptr = create_test_node("test-node", NodeState::NotReady);
+            let storage = create_mock_storage().await;
+
+            let node = lock_ptr!(node_ptr).unwrap().clone();
+            storage.register_node(&node).await.unwrap();
+
             let state = NotReadyState {
-                storage: create_mock_storage().await,
+                storage,
                 node: node_ptr.clone(),
             };
 
             let updated_node = Node {
                 name: "test-node".to_string(),
                 state: NodeState::Ready,
-                capacity: ResourceRequirement::default(),
+                capacity: ResourceRequirement {
+                    cpu: 4,
+                    memory: 8192,
+                },
                 allocatable: ResourceRequirement::default(),
                 info: NodeInfo::default(),
             };
 
             let result = state.update_node(&updated_node).await;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+            assert!(result.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Ready);
+            assert_eq!(lock_ptr!(node_ptr).unwrap().capacity.cpu, 4);
         }
 
         #[tokio::test]
@@ -433,6 +543,82 @@ mod tests {
         }
     }
 
+    // ========================================================================
+    // LostState Tests
+    // ========================================================================
+
+    mod lost_state_tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_register_node_transitions_to_ready() {
+            let node_ptr = create_test_node("test-node", NodeState::Lost);
+            let storage = create_mock_storage().await;
+
+            let node = lock_ptr!(node_ptr).unwrap().clone();
+            storage.register_node(&node).await.unwrap();
+
+            let state = LostState {
+                storage,
+                node: node_ptr.clone(),
+            };
+
+            let result = state.register_node().await;
+
+            assert!(result.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Ready);
+        }
+
+        #[tokio::test]
+        async fn test_drain_fails_already_lost() {
+            let node_ptr = create_test_node("test-node", NodeState::Lost);
+            let state = LostState {
+                storage: create_mock_storage().await,
+                node: node_ptr.clone(),
+            };
+
+            let result = state.drain().await;
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+        }
+
+        #[tokio::test]
+        async fn test_shutdown_is_idempotent() {
+            let node_ptr = create_test_node("test-node", NodeState::Lost);
+            let state = LostState {
+                storage: create_mock_storage().await,
+                node: node_ptr.clone(),
+            };
+
+            assert!(state.mark_not_ready().await.is_ok());
+            assert!(state.shutdown().await.is_ok());
+            assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Lost);
+        }
+
+        #[tokio::test]
+        async fn test_update_node_fails_must_reconnect() {
+            let node_ptr = create_test_node("test-node", NodeState::Lost);
+            let state = LostState {
+                storage: create_mock_storage().await,
+                node: node_ptr.clone(),
+            };
+
+            let updated_node = Node {
+                name: "test-node".to_string(),
+                state: NodeState::Ready,
+                capacity: ResourceRequirement::default(),
+                allocatable: ResourceRequirement::default(),
+                info: NodeInfo::default(),
+            };
+
+            let result = state.update_node(&updated_node).await;
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(FlameError::InvalidState(_))));
+        }
+    }
+
     // ========================================================================
     // State Factory Tests
     // ========================================================================
@@ -469,6 +655,16 @@ mod tests {
 
             assert!(state.is_ok());
         }
+
+        #[tokio::test]
+        async fn test_from_creates_lost_state() {
+            let node_ptr = create_test_node("test-node", NodeState::Lost);
+            let storage = create_mock_storage().await;
+
+            let state = from(storage, node_ptr);
+
+            assert!(state.is_ok());
+        }
     }
 
     // ========================================================================
@@ -479,7 +675,7 @@ mod tests {
         use super::*;
 
         #[tokio::test]
-        async fn test_full_lifecycle_unknown_to_ready_to_unknown_to_not_ready() {
+        async fn test_full_lifecycle_unknown_to_ready_to_unknown_to_lost() {
             let storage = create_mock_storage().await;
             let node_ptr = create_test_node("lifecycle-node", NodeState::Unknown);
 
@@ -501,11 +697,11 @@ mod tests {
                 assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Unknown);
             }
 
-            // Unknown -> NotReady (shutdown after timeout)
+            // Unknown -> Lost (shutdown after timeout)
             {
                 let state = from(storage.clone(), node_ptr.clone()).unwrap();
                 state.shutdown().await.unwrap();
-                assert_eq!(get_state(&node_ptr).unwrap(), NodeState::NotReady);
+                assert_eq!(get_state(&node_ptr).unwrap(), NodeState::Lost);
             }
         }
 
@@ -535,7 +731,7 @@ mod tests {
         #[tokio::test]
         async fn test_reconnection_after_shutdown() {
             let storage = create_mock_storage().await;
-            let node_ptr = create_test_node("shutdown-reconnect-node", NodeState::NotReady);
+            let node_ptr = create_test_node("shutdown-reconnect-node", NodeState::Lost);
 
             let node = lock_ptr!(node_ptr).unwrap().clone();
             storage.register_node(&node).await.unwrap();
@@ -11,7 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::controller::nodes::NodeStates;
+use crate::controller::nodes::{release_executors, NodeStates};
 use crate::storage::StoragePtr;
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
@@ -20,9 +20,10 @@ use common::FlameError;
 
 /// State handler for nodes in NotReady state.
 ///
-/// NotReady state means the node was shutdown (cleanup completed after drain timeout).
-/// It can transition to:
-/// - Ready: if the node reconnects (register_node)
+/// NotReady state means the node is connected but missed its heartbeats; no
+/// executor is allocated to it, but its executors are kept. It can transition to:
+/// - Ready: if the node reconnects (register_node) or its heartbeats resume (update_node)
+/// - Lost: if the cleanup timer or the lost timeout expires (shutdown)
 pub struct NotReadyState {
     pub storage: StoragePtr,
     pub node: NodePtr,
@@ -37,7 +38,7 @@ impl NodeStates for NotReadyState {
             let mut node = lock_ptr!(self.node)?;
             node.state = NodeState::Ready;
             tracing::info!(
-                "Node <{}> reconnected after shutdown, transitioning from NotReady to Ready",
+                "Node <{}> reconnected, transitioning from NotReady to Ready",
                 node.name
             );
             node.name.clone()
@@ -52,25 +53,60 @@ impl NodeStates for NotReadyState {
     async fn drain(&self) -> Result<(), FlameError> {
         trace_fn!("NotReadyState::drain");
 
-        Err(FlameError::InvalidState(
-            "Node is already in NotReady state (shutdown)".to_string(),
-        ))
+        // Node is not allocatable already, the cleanup timer shuts it down
+        tracing::debug!("Node is already in NotReady state, ignoring drain");
+        Ok(())
+    }
+
+    async fn mark_not_ready(&self) -> Result<(), FlameError> {
+        trace_fn!("NotReadyState::mark_not_ready");
+
+        tracing::debug!("Node is already in NotReady state, ignoring mark_not_ready");
+        Ok(())
     }
 
     async fn shutdown(&self) -> Result<(), FlameError> {
         trace_fn!("NotReadyState::shutdown");
 
-        // Already shutdown, this is a no-op
-        tracing::debug!("Node is already in NotReady state, ignoring shutdown");
-        Ok(())
+        let node_name = {
+            let mut node = lock_ptr!(self.node)?;
+            node.state = NodeState::Lost;
+            tracing::info!(
+                "Node <{}> shutdown, transitioning from NotReady to Lost",
+                node.name
+            );
+            node.name.clone()
+        };
+
+        // Clean up all executors on this node
+        release_executors(&self.storage, &node_name).await?;
+
+        // Persist the state change
+        self.storage
+            .update_node_state(&node_name, NodeState::Lost)
+            .await
     }
 
-    async fn update_node(&self, _node: &Node) -> Result<(), FlameError> {
+    async fn update_node(&self, updated: &Node) -> Result<(), FlameError> {
         trace_fn!("NotReadyState::update_node");
 
-        Err(FlameError::InvalidState(
-            "Cannot update node in NotReady state, must reconnect first".to_string(),
-        ))
+        let node_name = {
+            let mut node = lock_ptr!(self.node)?;
+            node.capacity = updated.capacity.clone();
+            node.allocatable = updated.allocatable.clone();
+            node.info = updated.info.clone();
+            node.state = NodeState::Ready;
+            tracing::info!(
+                "Node <{}> heartbeats resumed, transitioning from NotReady to Ready",
+                node.name
+            );
+            node.name.clone()
+        };
+
+        // Persist the state change
+        self.storage
+            .update_node_state(&node_name, NodeState::Ready)
+            .await
     }
 
     async fn release_node(&self) -> Result<(), FlameError> {
@@ -22,6 +22,7 @@ use common::FlameError;
 ///
 /// Ready state means the node is connected and operational. It can transition to:
 /// - Unknown: if the node starts draining (drain)
+/// - NotReady: if there's no heartbeat within the timeout (mark_not_ready)
 pub struct ReadyState {
     pub storage: StoragePtr,
     pub node: NodePtr,
@@ -56,6 +57,25 @@ impl NodeStates for ReadyState {
             .await
     }
 
+    async fn mark_not_ready(&self) -> Result<(), FlameError> {
+        trace_fn!("ReadyState::mark_not_ready");
+
+        let node_name = {
+            let mut node = lock_ptr!(self.node)?;
+            node.state = NodeState::NotReady;
+            tracing::warn!(
+                "Node <{}> missed heartbeats, transitioning from Ready to NotReady",
+                node.name
+            );
+            node.name.clone()
+        };
+
+        // Persist the state change
+        self.storage
+            .update_node_state(&node_name, NodeState::NotReady)
+            .await
+    }
+
     async fn shutdown(&self) -> Result<(), FlameError> {
         trace_fn!("ReadyState::shutdown");
 
@@ -11,8 +11,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::controller::nodes::NodeStates;
-use crate::model::ExecutorFilter;
+use crate::controller::nodes::{release_executors, NodeStates};
 use crate::storage::StoragePtr;
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
@@ -24,7 +23,7 @@ use common::FlameError;
 /// Unknown state means the node was previously connected but has disconnected,
 /// and the cleanup timer is running. It can transition to:
 /// - Ready: if the node reconnects (register_node)
-/// - NotReady: if the cleanup timer expires (shutdown)
+/// - Lost: if the cleanup timer expires (shutdown)
 pub struct UnknownState {
     pub storage: StoragePtr,
     pub node: NodePtr,
@@ -59,36 +58,33 @@ impl NodeStates for UnknownState {
         ))
     }
 
+    async fn mark_not_ready(&self) -> Result<(), FlameError> {
+        trace_fn!("UnknownState::mark_not_ready");
+
+        // Draining node is not allocatable either, it's lost after the cleanup timer
+        tracing::debug!("Node is already in Unknown state, ignoring mark_not_ready");
+        Ok(())
+    }
+
     async fn shutdown(&self) -> Result<(), FlameError> {
         trace_fn!("UnknownState::shutdown");
 
         let node_name = {
             let mut node = lock_ptr!(self.node)?;
-            node.state = NodeState::NotReady;
+            node.state = NodeState::Lost;
             tracing::info!(
-                "Node <{}> shutdown, transitioning from Unknown to NotReady",
+                "Node <{}> shutdown, transitioning from Unknown to Lost",
                 node.name
             );
             node.name.clone()
         };
 
         // Clean up all executors on this node
-        let executors = self
-            .storage
-            .list_executor(Some(&ExecutorFilter::by_node(&node_name)))?;
-
-        if !executors.is_empty() {
-            tracing::info!(
-                "Cleaning up {} executors for node <{}>",
-                executors.len(),
-                node_name
-            );
-            self.storage.delete_executors(&executors).await?;
-        }
+        release_executors(&self.storage, &node_name).await?;
 
         // Persist the state change
         self.storage
-            .update_node_state(&node_name, NodeState::NotReady)
+            .update_node_state(&node_name, NodeState::Lost)
             .await
     }
 
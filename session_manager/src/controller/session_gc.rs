@@ -0,0 +1,39 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::time::Duration;
+
+use common::FlameError;
+
+use crate::controller::ControllerPtr;
+
+/// The interval to check the TTL of the closed sessions.
+pub const SESSION_GC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Delete the closed sessions whose TTL after completion expired periodically;
+/// a session without TTL is kept until it's deleted by the user.
+pub async fn run(controller: ControllerPtr) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(SESSION_GC_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match controller.expire_sessions().await {
+            Ok(deleted) if !deleted.is_empty() => {
+                tracing::info!("Deleted <{}> sessions of expired TTL.", deleted.len())
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to expire sessions: {e}"),
+        }
+    }
+}
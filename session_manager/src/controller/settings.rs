@@ -0,0 +1,168 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The settings of the tasks, i.e. their timeout, attempts and priority, are
+//! resolved by the hierarchy: the settings of the cluster, then the session
+//! defaults of the application, the session and the task, each overriding the
+//! previous ones. They're resolved when they're used instead of at creation,
+//! so the updated settings of the cluster and the applications apply to the
+//! existing sessions.
+
+use common::apis::{Session, SessionDefaults, Setting, SettingSource, Task, TaskSettings};
+use common::ctx::FlameCluster;
+
+/// Resolves the effective settings of the task by its overrides, or of the
+/// tasks of the session without their own settings if no task.
+pub fn resolve(
+    cluster: &FlameCluster,
+    defaults: &SessionDefaults,
+    ssn: &Session,
+    task: Option<&Task>,
+) -> TaskSettings {
+    let overrides = task.map(|task| &task.overrides);
+
+    TaskSettings {
+        session_id: ssn.id.clone(),
+        task_id: task.map(|task| task.id),
+        timeout: match resolve_setting(
+            None,
+            defaults.task_timeout,
+            ssn.task_timeout,
+            overrides.and_then(|o| o.timeout),
+        ) {
+            Some(setting) => Setting {
+                value: Some(setting.value),
+                source: setting.source,
+            },
+            None => Setting {
+                value: cluster.task_timeout,
+                source: SettingSource::Cluster,
+            },
+        },
+        max_attempts: resolve_setting(
+            Some(cluster.max_task_attempts),
+            defaults.max_task_attempts,
+            ssn.max_task_attempts,
+            overrides.and_then(|o| o.max_attempts),
+        )
+        .unwrap_or_default(),
+        priority: resolve_setting(
+            Some(cluster.task_priority),
+            defaults.task_priority,
+            ssn.task_priority,
+            overrides.and_then(|o| o.priority),
+        )
+        .unwrap_or_default(),
+    }
+}
+
+/// The setting of the lowest level of the hierarchy which has it.
+fn resolve_setting<T: Copy>(
+    cluster: Option<T>,
+    application: Option<T>,
+    session: Option<T>,
+    task: Option<T>,
+) -> Option<Setting<T>> {
+    [
+        (task, SettingSource::Task),
+        (session, SettingSource::Session),
+        (application, SettingSource::Application),
+        (cluster, SettingSource::Cluster),
+    ]
+    .into_iter()
+    .find_map(|(value, source)| value.map(|value| Setting { value, source }))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::apis::{SessionID, TaskID, TaskOverrides};
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_settings() {
+        let cluster = FlameCluster {
+            max_task_attempts: 3,
+            task_timeout: Some(600),
+            task_priority: 1,
+            ..FlameCluster::default()
+        };
+        let mut defaults = SessionDefaults::default();
+        let mut ssn = Session {
+            id: SessionID::new("ssn-1"),
+            ..Session::default()
+        };
+
+        // The settings of the cluster without the others.
+        let settings = resolve(&cluster, &defaults, &ssn, None);
+        assert_eq!(settings.task_id, None);
+        assert_eq!(
+            settings.timeout,
+            Setting {
+                value: Some(600),
+                source: SettingSource::Cluster
+            }
+        );
+        assert_eq!(settings.max_attempts.value, 3);
+        assert_eq!(settings.priority.source, SettingSource::Cluster);
+
+        // The application overrides the cluster, and the session overrides the application.
+        defaults.task_timeout = Some(60);
+        defaults.max_task_attempts = Some(5);
+        ssn.max_task_attempts = Some(2);
+        let settings = resolve(&cluster, &defaults, &ssn, None);
+        assert_eq!(
+            settings.timeout,
+            Setting {
+                value: Some(60),
+                source: SettingSource::Application
+            }
+        );
+        assert_eq!(
+            settings.max_attempts,
+            Setting {
+                value: 2,
+                source: SettingSource::Session
+            }
+        );
+
+        // The task overrides the session.
+        let task = Task {
+            id: TaskID::from(7),
+            overrides: TaskOverrides {
+                timeout: Some(10),
+                priority: Some(9),
+                ..TaskOverrides::default()
+            },
+            ..Task::default()
+        };
+        let settings = resolve(&cluster, &defaults, &ssn, Some(&task));
+        assert_eq!(settings.task_id, Some(task.id));
+        assert_eq!(settings.timeout.value, Some(10));
+        assert_eq!(settings.timeout.source, SettingSource::Task);
+        assert_eq!(settings.max_attempts.source, SettingSource::Session);
+        assert_eq!(
+            settings.priority,
+            Setting {
+                value: 9,
+                source: SettingSource::Task
+            }
+        );
+
+        // No timeout at all.
+        let cluster = FlameCluster::default();
+        let settings = resolve(&cluster, &SessionDefaults::default(), &ssn, None);
+        assert_eq!(settings.timeout.value, None);
+        assert_eq!(settings.timeout.source, SettingSource::Cluster);
+    }
+}
@@ -0,0 +1,204 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The output streamed by the running tasks, e.g. their progress.
+//!
+//! The executor managers relay the output chunks of the tasks of their
+//! instances, which are broadcast to the clients watching the tasks; the
+//! latest chunks of a task are buffered for the clients starting to watch it
+//! after the chunks were pushed. The output of a task is dropped when it's
+//! completed, whose result is the output of the task.
+//!
+//! The output is kept in memory only, so it's not recovered by a restart of
+//! the session manager.
+
+use std::collections::{HashMap, VecDeque};
+
+use tokio::sync::broadcast;
+
+use common::apis::{SessionID, TaskID};
+use common::FlameError;
+use rpc::flame::v1 as rpc;
+use stdng::{lock_ptr, MutexPtr};
+
+/// The bytes of the latest output chunks of a task buffered for the new
+/// watchers; the latest chunk is buffered even if it's larger.
+const MAX_BUFFERED_BYTES: usize = 1024 * 1024;
+
+/// The chunks not received yet by a watcher, beyond which the watcher lags
+/// and misses the oldest ones.
+const MAX_PENDING_CHUNKS: usize = 256;
+
+/// The output chunks of a running task.
+struct TaskOutput {
+    chunks: VecDeque<rpc::TaskOutputChunk>,
+    /// The bytes of the data of the buffered chunks.
+    buffered_bytes: usize,
+    sender: broadcast::Sender<rpc::TaskOutputChunk>,
+}
+
+impl TaskOutput {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(MAX_PENDING_CHUNKS);
+        Self {
+            chunks: VecDeque::new(),
+            buffered_bytes: 0,
+            sender,
+        }
+    }
+
+    /// Buffers the chunk, and drops the oldest chunks beyond the max bytes.
+    fn buffer(&mut self, chunk: rpc::TaskOutputChunk) {
+        self.buffered_bytes += chunk.data.len();
+        self.chunks.push_back(chunk);
+
+        while self.buffered_bytes > MAX_BUFFERED_BYTES && self.chunks.len() > 1 {
+            if let Some(oldest) = self.chunks.pop_front() {
+                self.buffered_bytes -= oldest.data.len();
+            }
+        }
+    }
+}
+
+/// The watcher of the output of a task: the buffered chunks, then the ones
+/// pushed after them until the task is completed.
+pub struct TaskOutputWatcher {
+    pub buffered: Vec<rpc::TaskOutputChunk>,
+    pub receiver: broadcast::Receiver<rpc::TaskOutputChunk>,
+}
+
+pub struct TaskOutputManager {
+    outputs: MutexPtr<HashMap<(SessionID, TaskID), TaskOutput>>,
+}
+
+impl TaskOutputManager {
+    pub fn new() -> Self {
+        Self {
+            outputs: stdng::new_ptr(HashMap::new()),
+        }
+    }
+
+    /// Buffers the chunk of the task and broadcasts it to the watchers of the task.
+    pub fn push(
+        &self,
+        ssn_id: SessionID,
+        task_id: TaskID,
+        chunk: rpc::TaskOutputChunk,
+    ) -> Result<(), FlameError> {
+        let mut outputs = lock_ptr!(self.outputs)?;
+        let output = outputs
+            .entry((ssn_id, task_id))
+            .or_insert_with(TaskOutput::new);
+
+        output.buffer(chunk.clone());
+        // No one is watching the task yet.
+        let _ = output.sender.send(chunk);
+
+        Ok(())
+    }
+
+    pub fn watch(
+        &self,
+        ssn_id: SessionID,
+        task_id: TaskID,
+    ) -> Result<TaskOutputWatcher, FlameError> {
+        let mut outputs = lock_ptr!(self.outputs)?;
+        let output = outputs
+            .entry((ssn_id, task_id))
+            .or_insert_with(TaskOutput::new);
+
+        Ok(TaskOutputWatcher {
+            buffered: output.chunks.iter().cloned().collect(),
+            receiver: output.sender.subscribe(),
+        })
+    }
+
+    /// Drops the output of the completed task, which ends its watchers.
+    pub fn close(&self, ssn_id: &SessionID, task_id: TaskID) -> Result<(), FlameError> {
+        let mut outputs = lock_ptr!(self.outputs)?;
+        outputs.remove(&(ssn_id.clone(), task_id));
+        Ok(())
+    }
+
+    /// Drops the output of all the tasks of the closed session.
+    pub fn close_session(&self, ssn_id: &SessionID) -> Result<(), FlameError> {
+        let mut outputs = lock_ptr!(self.outputs)?;
+        outputs.retain(|(id, _), _| id != ssn_id);
+        Ok(())
+    }
+}
+
+impl Default for TaskOutputManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(sequence: u64, size: usize) -> rpc::TaskOutputChunk {
+        rpc::TaskOutputChunk {
+            session_id: "ssn-1".to_string(),
+            task_id: "1".to_string(),
+            sequence,
+            data: vec![sequence as u8; size],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_output() {
+        let manager = TaskOutputManager::new();
+        let ssn_id = SessionID::from("ssn-1");
+        let task_id = TaskID::new(1);
+
+        let size = MAX_BUFFERED_BYTES / 4;
+        for sequence in 0..6 {
+            manager
+                .push(ssn_id.clone(), task_id, chunk(sequence, size))
+                .unwrap();
+        }
+
+        // Only the latest chunks within the max bytes are buffered for the new watchers.
+        let mut watcher = manager.watch(ssn_id.clone(), task_id).unwrap();
+        assert_eq!(watcher.buffered.len(), 4);
+        assert_eq!(watcher.buffered[0].sequence, 2);
+
+        manager
+            .push(ssn_id.clone(), task_id, chunk(300, 1))
+            .unwrap();
+        assert_eq!(watcher.receiver.recv().await.unwrap().sequence, 300);
+
+        // The watchers are ended when the task is completed.
+        manager.close(&ssn_id, task_id).unwrap();
+        assert!(matches!(
+            watcher.receiver.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+
+        // The latest chunk is buffered even if it's beyond the max bytes.
+        manager
+            .push(ssn_id.clone(), task_id, chunk(301, 1))
+            .unwrap();
+        manager
+            .push(ssn_id.clone(), task_id, chunk(302, MAX_BUFFERED_BYTES + 1))
+            .unwrap();
+        let watcher = manager.watch(ssn_id.clone(), task_id).unwrap();
+        assert_eq!(watcher.buffered.len(), 1);
+        assert_eq!(watcher.buffered[0].sequence, 302);
+
+        manager.close_session(&ssn_id).unwrap();
+        assert!(manager.watch(ssn_id, task_id).unwrap().buffered.is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The watchdog of the running tasks.
+//!
+//! The executor enforces the timeout of its task; but a hung executor leaves
+//! the task running forever, so the task is dispatched again once it exceeds
+//! its timeout by the grace period, and quarantined after its max attempts.
+
+use std::time::Duration;
+
+use common::FlameError;
+
+use crate::controller::ControllerPtr;
+
+/// The interval to check the running tasks.
+const TASK_WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The seconds for the executor to report the task failed by its own timeout.
+pub const TASK_TIMEOUT_GRACE_PERIOD: u64 = 30;
+
+/// Dispatch the running tasks of the hung executors again periodically.
+pub async fn run(controller: ControllerPtr) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(TASK_WATCHDOG_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match controller
+            .expire_running_tasks(TASK_TIMEOUT_GRACE_PERIOD)
+            .await
+        {
+            Ok(expired) if !expired.is_empty() => {
+                tracing::info!("Dispatched <{}> timed out tasks again.", expired.len())
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to expire running tasks: {e}"),
+        }
+    }
+}
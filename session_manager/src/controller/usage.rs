@@ -0,0 +1,140 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+
+use common::apis::{
+    ApplicationID, ResourceRequirement, Session, SlotRecommendation, TaskUsageStats,
+};
+
+/// Recommends the slots of the sessions, and of their applications by the usage
+/// of all their sessions, e.g. "95% of tasks used <=2 CPUs; consider slots=2";
+/// the ones without enough measured tasks are skipped. The recommendations are
+/// ordered by application, the one of the application first.
+pub fn recommend(
+    sessions: &[Session],
+    unit: &ResourceRequirement,
+    application: Option<&ApplicationID>,
+) -> Vec<SlotRecommendation> {
+    let mut apps: BTreeMap<&ApplicationID, Vec<&Session>> = BTreeMap::new();
+    for ssn in sessions {
+        if application.is_none_or(|app| *app == ssn.application) {
+            apps.entry(&ssn.application).or_default().push(ssn);
+        }
+    }
+
+    let mut recommendations = vec![];
+    for (app, mut ssns) in apps {
+        ssns.sort_by_key(|ssn| ssn.creation_time);
+
+        // The application is recommended against the slots of its latest session.
+        let mut stats = TaskUsageStats::default();
+        for ssn in &ssns {
+            stats.merge(&ssn.task_usage);
+        }
+        let slots = ssns.last().map(|ssn| ssn.slots).unwrap_or_default();
+        recommendations.extend(SlotRecommendation::new(
+            app.clone(),
+            None,
+            slots,
+            &stats,
+            unit,
+        ));
+
+        recommendations.extend(ssns.iter().filter_map(|ssn| {
+            SlotRecommendation::new(
+                app.clone(),
+                Some(ssn.id.clone()),
+                ssn.slots,
+                &ssn.task_usage,
+                unit,
+            )
+        }));
+    }
+
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use common::apis::{TaskUsage, MIN_USAGE_SAMPLES};
+
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn session(id: &str, app: &str, slots: u32, cpu_time_ms: u64, tasks: u64) -> Session {
+        let mut ssn = Session {
+            id: id.to_string().into(),
+            application: app.to_string(),
+            slots,
+            creation_time: Utc::now(),
+            ..Session::default()
+        };
+        for _ in 0..tasks {
+            ssn.task_usage.observe(
+                &TaskUsage {
+                    cpu_time_ms,
+                    max_rss_bytes: GIB / 2,
+                    ..TaskUsage::default()
+                },
+                Duration::seconds(1),
+            );
+        }
+        ssn
+    }
+
+    #[test]
+    fn test_recommend_slots() {
+        let unit = ResourceRequirement::from("cpu=1,mem=1g");
+        let sessions = vec![
+            // 1.5 CPUs per task, over-provisioned by 4 slots.
+            session("ssn-1", "pi", 4, 1500, MIN_USAGE_SAMPLES),
+            // Not enough measured tasks.
+            session("ssn-2", "pi", 4, 1500, MIN_USAGE_SAMPLES - 1),
+            // 0.5 CPU per task, which fits 1 slot.
+            session("ssn-3", "agent", 1, 500, MIN_USAGE_SAMPLES),
+        ];
+
+        let recommendations = recommend(&sessions, &unit, None);
+        let slots: Vec<_> = recommendations
+            .iter()
+            .map(|r| {
+                (
+                    r.application.as_str(),
+                    r.session_id.as_deref(),
+                    r.slots,
+                    r.recommended_slots,
+                )
+            })
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                ("agent", None, 1, 1),
+                ("agent", Some("ssn-3"), 1, 1),
+                ("pi", None, 4, 2),
+                ("pi", Some("ssn-1"), 4, 2),
+            ]
+        );
+        assert_eq!(
+            recommendations[2].message(),
+            "95% of tasks used <=1.5 CPUs and <=512MiB memory; consider slots=2"
+        );
+        assert_eq!(recommendations[2].tasks, 2 * MIN_USAGE_SAMPLES - 1);
+
+        let pi = "pi".to_string();
+        assert_eq!(recommend(&sessions, &unit, Some(&pi)).len(), 2);
+    }
+}
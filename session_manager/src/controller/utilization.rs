@@ -0,0 +1,218 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The utilization of the cluster, i.e. the slots of the nodes taken by the
+//! executors, and the executors and the tasks of the open sessions; it's
+//! aggregated from a snapshot of the cluster for `flmctl top`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rpc::flame::v1 as rpc;
+
+use crate::model::{SnapShot, ALL_EXECUTOR, ALL_NODE, OPEN_SESSION};
+use common::apis::{NodeState, SessionID, TaskState};
+use common::FlameError;
+
+pub fn cluster_utilization(
+    ss: &SnapShot,
+    now: DateTime<Utc>,
+) -> Result<rpc::ClusterUtilization, FlameError> {
+    let mut execs: Vec<_> = ss.find_executors(ALL_EXECUTOR)?.into_values().collect();
+    execs.sort_by(|e1, e2| e1.id.cmp(&e2.id));
+
+    let mut node_execs: HashMap<&str, (u32, u32, u32)> = HashMap::new();
+    let mut ssn_execs: HashMap<&SessionID, (u32, u32)> = HashMap::new();
+    let mut executors = Vec::with_capacity(execs.len());
+    for exec in &execs {
+        let (count, used, running) = node_execs.entry(exec.node.as_str()).or_default();
+        *count += 1;
+        *used += exec.slots;
+        if exec.task_id.is_some() {
+            *running += 1;
+        }
+
+        if let Some(ssn_id) = &exec.ssn_id {
+            let (count, used) = ssn_execs.entry(ssn_id).or_default();
+            *count += 1;
+            *used += exec.slots;
+        }
+
+        executors.push(rpc::ExecutorUtilization {
+            id: exec.id.clone(),
+            node: exec.node.clone(),
+            state: rpc::ExecutorState::from(exec.state) as i32,
+            slots: exec.slots,
+            session_id: exec.ssn_id.as_ref().map(|id| id.to_string()),
+            task_id: exec.task_id.map(|id| id.to_string()),
+            busy_ratio: ss
+                .node_loads
+                .get(&exec.node)
+                .and_then(|load| load.executors.get(&exec.id))
+                .map(|load| load.busy_ratio),
+        });
+    }
+
+    let mut nodes: Vec<_> = ss.find_nodes(ALL_NODE)?.into_values().collect();
+    nodes.sort_by(|n1, n2| n1.name.cmp(&n2.name));
+
+    let (mut total_slots, mut used_slots) = (0, 0);
+    let nodes = nodes
+        .iter()
+        .map(|node| {
+            let (count, used, running) = node_execs
+                .get(node.name.as_str())
+                .copied()
+                .unwrap_or_default();
+            let total = node.allocatable.to_slots(&ss.unit);
+            // Only the ready nodes are allocatable for the new executors.
+            if node.state == NodeState::Ready {
+                total_slots += total;
+                used_slots += used;
+            }
+
+            rpc::NodeUtilization {
+                name: node.name.clone(),
+                state: rpc::NodeState::from(node.state) as i32,
+                total_slots: total,
+                used_slots: used,
+                executors: count,
+                running_executors: running,
+                saturation: ss.node_loads.get(&node.name).and_then(|l| l.saturation()),
+            }
+        })
+        .collect();
+
+    let mut ssns: Vec<_> = ss.find_sessions(OPEN_SESSION)?.into_values().collect();
+    ssns.sort_by(|s1, s2| s1.id.cmp(&s2.id));
+
+    let sessions = ssns
+        .iter()
+        .map(|ssn| {
+            let (count, used) = ssn_execs.get(&ssn.id).copied().unwrap_or_default();
+            let tasks =
+                |state: TaskState| ssn.tasks_status.get(&state).copied().unwrap_or(0).max(0) as u32;
+
+            rpc::SessionUtilization {
+                session_id: ssn.id.to_string(),
+                application: ssn.application.clone(),
+                executors: count,
+                used_slots: used,
+                pending_tasks: tasks(TaskState::Pending),
+                running_tasks: tasks(TaskState::Running),
+                succeed_tasks: tasks(TaskState::Succeed),
+                failed_tasks: tasks(TaskState::Failed),
+            }
+        })
+        .collect();
+
+    Ok(rpc::ClusterUtilization {
+        creation_time: now.timestamp_millis(),
+        total_slots,
+        used_slots,
+        nodes,
+        executors,
+        sessions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::model::{ExecutorInfo, ExecutorLoad, NodeInfo, NodeLoad, SessionInfo};
+    use common::apis::{ExecutorState, ResourceRequirement, SessionState, TaskID};
+
+    fn executor(id: &str, node: &str, ssn_id: &str, task_id: Option<i64>) -> Arc<ExecutorInfo> {
+        Arc::new(ExecutorInfo {
+            id: id.to_string(),
+            node: node.to_string(),
+            slots: 2,
+            ssn_id: Some(ssn_id.to_string().into()),
+            task_id: task_id.map(TaskID::new),
+            state: ExecutorState::Bound,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_cluster_utilization() {
+        let mut ss = SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g"));
+        ss.node_loads.insert(
+            "node-1".to_string(),
+            NodeLoad {
+                executors: HashMap::from([(
+                    "exec-1".to_string(),
+                    ExecutorLoad {
+                        queue_depth: 0,
+                        busy_ratio: 0.5,
+                    },
+                )]),
+                max_executors: None,
+            },
+        );
+        for (name, state) in [("node-1", NodeState::Ready), ("node-2", NodeState::Lost)] {
+            ss.add_node(Arc::new(NodeInfo {
+                name: name.to_string(),
+                allocatable: ResourceRequirement::from("cpu=4,mem=4g"),
+                state,
+                ..Default::default()
+            }))
+            .unwrap();
+        }
+        ss.add_session(Arc::new(SessionInfo {
+            id: "ssn-1".to_string().into(),
+            application: "app".to_string(),
+            slots: 2,
+            tasks_status: HashMap::from([(TaskState::Pending, 3), (TaskState::Running, 1)]),
+            state: SessionState::Open,
+            ..Default::default()
+        }))
+        .unwrap();
+        ss.add_executor(executor("exec-1", "node-1", "ssn-1", Some(1)))
+            .unwrap();
+        ss.add_executor(executor("exec-2", "node-2", "ssn-1", None))
+            .unwrap();
+
+        let now = Utc::now();
+        let utilization = cluster_utilization(&ss, now).unwrap();
+        assert_eq!(utilization.creation_time, now.timestamp_millis());
+
+        // Only the slots of the ready nodes are counted in the cluster.
+        assert_eq!(utilization.total_slots, 4);
+        assert_eq!(utilization.used_slots, 2);
+
+        let node = &utilization.nodes[0];
+        assert_eq!(node.name, "node-1");
+        assert_eq!(node.total_slots, 4);
+        assert_eq!(node.used_slots, 2);
+        assert_eq!(node.executors, 1);
+        assert_eq!(node.running_executors, 1);
+        assert_eq!(node.saturation, Some(0.5));
+        assert_eq!(utilization.nodes[1].running_executors, 0);
+
+        assert_eq!(utilization.executors.len(), 2);
+        assert_eq!(utilization.executors[0].task_id.as_deref(), Some("1"));
+        assert_eq!(utilization.executors[0].busy_ratio, Some(0.5));
+        assert_eq!(utilization.executors[1].busy_ratio, None);
+
+        let ssn = &utilization.sessions[0];
+        assert_eq!(ssn.session_id, "ssn-1");
+        assert_eq!(ssn.executors, 2);
+        assert_eq!(ssn.used_slots, 4);
+        assert_eq!(ssn.pending_tasks, 3);
+        assert_eq!(ssn.running_tasks, 1);
+        assert_eq!(ssn.succeed_tasks, 0);
+    }
+}
@@ -18,7 +18,7 @@ use bincode::{Decode, Encode};
 use chrono::{DateTime, Utc};
 use stdng::{lock_ptr, new_ptr, MutexPtr};
 
-use common::apis::{Event, EventOwner, SessionID, TaskID};
+use common::apis::{Event, EventFilter, EventOwner, SessionEvent, SessionID, TaskID};
 use common::storage::{DataStorage, Index, Object, ObjectId, ObjectStorage};
 use common::FlameError;
 
@@ -32,7 +32,7 @@ struct EventStorage {
 #[derive(Clone, Debug, Encode, Decode)]
 struct EventDao {
     id: Option<u64>,
-    owner: TaskID,
+    owner: i64,
     code: i32,
     message: Index,
     creation_time: i64,
@@ -97,7 +97,7 @@ impl FsEventManager {
                 events
                     .entry(session_id.clone())
                     .or_default()
-                    .entry(event_dao.owner as TaskID)
+                    .entry(TaskID::from(event_dao.owner))
                     .or_default()
                     .push(event_dao);
             }
@@ -110,7 +110,7 @@ impl FsEventManager {
         let entries = fs::read_dir(&self.storage_path)?;
         for entry in entries {
             let file_name = entry?.file_name();
-            let session_id = file_name.to_string_lossy().to_string();
+            let session_id = SessionID::from(file_name.to_string_lossy().as_ref());
             sessions.push(session_id);
         }
         Ok(sessions)
@@ -146,7 +146,7 @@ impl EventManager for FsEventManager {
 
         let event_dao = EventDao {
             id: None,
-            owner: owner.task_id,
+            owner: owner.task_id.into(),
             code: event.code,
             message: msg_index,
             creation_time: event.creation_time.timestamp(),
@@ -167,9 +167,10 @@ impl EventManager for FsEventManager {
 
     fn find_events(&self, owner: EventOwner) -> Result<Vec<Event>, FlameError> {
         let mut event_storage = lock_ptr!(self.event_storage)?;
-        let storage = event_storage
-            .get_mut(&owner.session_id)
-            .ok_or(FlameError::Internal("Event storage not found".to_string()))?;
+        // No event was recorded for the session yet.
+        let Some(storage) = event_storage.get_mut(&owner.session_id) else {
+            return Ok(vec![]);
+        };
 
         let events = lock_ptr!(self.events)?;
         let event_daos = events
@@ -194,10 +195,50 @@ impl EventManager for FsEventManager {
         Ok(event_list)
     }
 
+    fn query_events(
+        &self,
+        session_id: SessionID,
+        filter: &EventFilter,
+    ) -> Result<Vec<SessionEvent>, FlameError> {
+        let mut event_storage = lock_ptr!(self.event_storage)?;
+        let Some(storage) = event_storage.get_mut(&session_id) else {
+            return Ok(vec![]);
+        };
+
+        let events = lock_ptr!(self.events)?;
+        let Some(owners) = events.get(&session_id) else {
+            return Ok(vec![]);
+        };
+
+        // Only the messages of the matched events are loaded.
+        let mut event_list = vec![];
+        for (task_id, event_daos) in owners {
+            for event_dao in event_daos {
+                let creation_time = DateTime::<Utc>::from_timestamp(event_dao.creation_time, 0)
+                    .ok_or(FlameError::Internal("Invalid creation time".to_string()))?;
+                if !filter.matches(*task_id, event_dao.code, creation_time) {
+                    continue;
+                }
+                let message = storage.data_storage.load(&event_dao.message)?;
+                event_list.push(SessionEvent::new(
+                    *task_id,
+                    Event {
+                        code: event_dao.code,
+                        message: Some(String::from_utf8(message)?),
+                        creation_time,
+                    },
+                ));
+            }
+        }
+
+        Ok(event_list)
+    }
+
     fn remove_events(&self, session_id: SessionID) -> Result<(), FlameError> {
         {
+            // The storage is set up again by the next event of the session.
             let mut event_storage = lock_ptr!(self.event_storage)?;
-            if let Some(storage) = event_storage.get_mut(&session_id) {
+            if let Some(mut storage) = event_storage.remove(&session_id) {
                 storage.object_storage.clear()?;
                 storage.data_storage.clear()?;
             }
@@ -220,7 +261,7 @@ impl EventManager for FsEventManager {
 
     fn clear(&self) -> Result<(), FlameError> {
         let mut event_storage = lock_ptr!(self.event_storage)?;
-        for storage in event_storage.values_mut() {
+        for (_, mut storage) in event_storage.drain() {
             storage.object_storage.clear()?;
             storage.data_storage.clear()?;
         }
@@ -16,7 +16,7 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use stdng::{lock_ptr, new_ptr, MutexPtr};
 
-use common::apis::{Event, EventOwner, SessionID, TaskID};
+use common::apis::{Event, EventFilter, EventOwner, SessionEvent, SessionID, TaskID};
 use common::FlameError;
 
 use super::EventManager;
@@ -82,6 +82,38 @@ impl EventManager for MemoryEventManager {
         Ok(event_list)
     }
 
+    fn query_events(
+        &self,
+        session_id: SessionID,
+        filter: &EventFilter,
+    ) -> Result<Vec<SessionEvent>, FlameError> {
+        let events = lock_ptr!(self.events)?;
+        let Some(owners) = events.get(&session_id) else {
+            return Ok(vec![]);
+        };
+
+        let mut event_list = vec![];
+        for (task_id, events) in owners {
+            for e in events {
+                let creation_time =
+                    DateTime::<Utc>::from_timestamp(e.creation_time, 0).unwrap_or_else(Utc::now);
+                if !filter.matches(*task_id, e.code, creation_time) {
+                    continue;
+                }
+                event_list.push(SessionEvent::new(
+                    *task_id,
+                    Event {
+                        code: e.code,
+                        message: Some(e.message.clone()),
+                        creation_time,
+                    },
+                ));
+            }
+        }
+
+        Ok(event_list)
+    }
+
     fn remove_events(&self, session_id: SessionID) -> Result<(), FlameError> {
         let mut events = lock_ptr!(self.events)?;
         events.remove(&session_id);
@@ -13,7 +13,7 @@ limitations under the License.
 
 use std::sync::Arc;
 
-use common::apis::{Event, EventOwner, SessionID};
+use common::apis::{Event, EventFilter, EventOwner, SessionEvent, SessionID};
 use common::FlameError;
 
 mod fs;
@@ -25,6 +25,13 @@ pub use memory::MemoryEventManager;
 pub trait EventManager: Send + Sync {
     fn record_event(&self, owner: EventOwner, event: Event) -> Result<(), FlameError>;
     fn find_events(&self, owner: EventOwner) -> Result<Vec<Event>, FlameError>;
+    /// The events of the session and its tasks matching the filter, without
+    /// loading its tasks; they're in the recorded order of each owner.
+    fn query_events(
+        &self,
+        session_id: SessionID,
+        filter: &EventFilter,
+    ) -> Result<Vec<SessionEvent>, FlameError>;
     fn remove_events(&self, session_id: SessionID) -> Result<(), FlameError>;
     fn clear(&self) -> Result<(), FlameError>;
 }
@@ -35,13 +42,14 @@ pub type EventManagerPtr = Arc<dyn EventManager>;
 mod tests {
     use super::*;
     use chrono::Utc;
+    use common::apis::EventOwnerKind;
 
     fn test_event_manager_impl(manager: &dyn EventManager) {
         manager
             .record_event(
                 EventOwner {
-                    session_id: String::from("1"),
-                    task_id: 1,
+                    session_id: "1".into(),
+                    task_id: 1.into(),
                 },
                 Event {
                     code: 1,
@@ -53,8 +61,8 @@ mod tests {
 
         let events = manager
             .find_events(EventOwner {
-                session_id: String::from("1"),
-                task_id: 1,
+                session_id: "1".into(),
+                task_id: 1.into(),
             })
             .unwrap();
 
@@ -65,10 +73,79 @@ mod tests {
         manager.clear().unwrap();
     }
 
+    fn test_query_events_impl(manager: &dyn EventManager) {
+        // The events are recorded in seconds.
+        let now = chrono::DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+        for (task_id, code, secs) in [(0, 100, 0), (1, 1, 0), (1, 2, 10), (2, 1, 20)] {
+            manager
+                .record_event(
+                    EventOwner {
+                        session_id: "1".into(),
+                        task_id: task_id.into(),
+                    },
+                    Event {
+                        code,
+                        message: Some(format!("event-{code}")),
+                        creation_time: now + chrono::Duration::seconds(secs),
+                    },
+                )
+                .unwrap();
+        }
+
+        let query = |filter: EventFilter| {
+            let mut events: Vec<_> = manager
+                .query_events("1".into(), &filter)
+                .unwrap()
+                .into_iter()
+                .map(|e| (e.task_id.map(i64::from), e.event.code))
+                .collect();
+            events.sort();
+            events
+        };
+
+        assert_eq!(query(EventFilter::default()).len(), 4);
+        assert_eq!(
+            query(EventFilter {
+                owner: Some(EventOwnerKind::Session),
+                ..Default::default()
+            }),
+            vec![(None, 100)]
+        );
+        assert_eq!(
+            query(EventFilter {
+                codes: vec![1],
+                ..Default::default()
+            }),
+            vec![(Some(1), 1), (Some(2), 1)]
+        );
+        assert_eq!(
+            query(EventFilter {
+                since: Some(now + chrono::Duration::seconds(5)),
+                until: Some(now + chrono::Duration::seconds(20)),
+                ..Default::default()
+            }),
+            vec![(Some(1), 2)]
+        );
+        assert_eq!(
+            query(EventFilter {
+                task_id: Some(2.into()),
+                ..Default::default()
+            }),
+            vec![(Some(2), 1)]
+        );
+        assert!(manager
+            .query_events("2".into(), &EventFilter::default())
+            .unwrap()
+            .is_empty());
+
+        manager.clear().unwrap();
+    }
+
     #[test]
     fn test_memory_event_manager() {
         let manager = MemoryEventManager::new();
         test_event_manager_impl(&manager);
+        test_query_events_impl(&manager);
     }
 
     #[test]
@@ -76,6 +153,7 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let manager = FsEventManager::new(temp_dir.path().to_str().unwrap()).unwrap();
         test_event_manager_impl(&manager);
+        test_query_events_impl(&manager);
     }
 
     #[test]
@@ -84,8 +162,8 @@ mod tests {
         let manager = FsEventManager::new(temp_dir.path().to_str().unwrap()).unwrap();
 
         let owner = EventOwner {
-            session_id: "session-1".to_string(),
-            task_id: 1,
+            session_id: "session-1".into(),
+            task_id: 1.into(),
         };
 
         for i in 0..3 {
@@ -119,8 +197,8 @@ mod tests {
             manager
                 .record_event(
                     EventOwner {
-                        session_id: session_id.clone(),
-                        task_id,
+                        session_id: session_id.clone().into(),
+                        task_id: task_id.into(),
                     },
                     Event {
                         code: task_id as i32,
@@ -134,8 +212,8 @@ mod tests {
         for task_id in 1..=3 {
             let events = manager
                 .find_events(EventOwner {
-                    session_id: session_id.clone(),
-                    task_id,
+                    session_id: session_id.clone().into(),
+                    task_id: task_id.into(),
                 })
                 .unwrap();
             assert_eq!(events.len(), 1);
@@ -152,8 +230,8 @@ mod tests {
             manager
                 .record_event(
                     EventOwner {
-                        session_id: format!("session-{}", i),
-                        task_id: 1,
+                        session_id: format!("session-{}", i).into(),
+                        task_id: 1.into(),
                     },
                     Event {
                         code: i,
@@ -167,8 +245,8 @@ mod tests {
         for i in 1..=3 {
             let events = manager
                 .find_events(EventOwner {
-                    session_id: format!("session-{}", i),
-                    task_id: 1,
+                    session_id: format!("session-{}", i).into(),
+                    task_id: 1.into(),
                 })
                 .unwrap();
             assert_eq!(events.len(), 1);
@@ -183,8 +261,8 @@ mod tests {
 
         let session_id = "session-to-remove".to_string();
         let owner = EventOwner {
-            session_id: session_id.clone(),
-            task_id: 1,
+            session_id: session_id.clone().into(),
+            task_id: 1.into(),
         };
 
         manager
@@ -201,7 +279,7 @@ mod tests {
         let events = manager.find_events(owner.clone()).unwrap();
         assert_eq!(events.len(), 1);
 
-        manager.remove_events(session_id.clone()).unwrap();
+        manager.remove_events(session_id.clone().into()).unwrap();
 
         let result = manager.find_events(owner);
         assert!(result.is_err() || result.unwrap().is_empty());
@@ -215,8 +293,8 @@ mod tests {
         manager
             .record_event(
                 EventOwner {
-                    session_id: "session-1".to_string(),
-                    task_id: 1,
+                    session_id: "session-1".into(),
+                    task_id: 1.into(),
                 },
                 Event {
                     code: 1,
@@ -228,8 +306,8 @@ mod tests {
 
         let events = manager
             .find_events(EventOwner {
-                session_id: "session-1".to_string(),
-                task_id: 999,
+                session_id: "session-1".into(),
+                task_id: 999.into(),
             })
             .unwrap();
         assert!(events.is_empty());
@@ -241,8 +319,8 @@ mod tests {
         let path = temp_dir.path().to_str().unwrap().to_string();
 
         let owner = EventOwner {
-            session_id: "persistent-session".to_string(),
-            task_id: 1,
+            session_id: "persistent-session".into(),
+            task_id: 1.into(),
         };
 
         {
@@ -279,8 +357,8 @@ mod tests {
         let manager = FsEventManager::new(&events_path).unwrap();
 
         let owner = EventOwner {
-            session_id: "test-session".to_string(),
-            task_id: 1,
+            session_id: "test-session".into(),
+            task_id: 1.into(),
         };
 
         manager
@@ -306,8 +384,8 @@ mod tests {
         let manager = FsEventManager::new(temp_dir.path().to_str().unwrap()).unwrap();
 
         let owner = EventOwner {
-            session_id: "session-1".to_string(),
-            task_id: 1,
+            session_id: "session-1".into(),
+            task_id: 1.into(),
         };
 
         manager
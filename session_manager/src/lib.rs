@@ -0,0 +1,284 @@
+/*
+Copyright 2023 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The session manager of Flame; it's run by the `flame-session-manager`
+//! binary, or in-process by the local mode of the SDK.
+
+use futures::future::select_all;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use common::ctx::FlameClusterContext;
+use common::FlameError;
+
+mod apiserver;
+mod controller;
+mod events;
+mod metrics;
+mod model;
+mod provider;
+pub mod scheduler;
+mod storage;
+
+/// The runtimes of the components of the session manager.
+#[derive(Clone)]
+pub struct Runtimes {
+    pub frontend: Handle,
+    pub backend: Handle,
+    pub scheduler: Handle,
+    pub provider: Handle,
+}
+
+impl Runtimes {
+    /// Runs all the components in the current runtime, e.g. in-process.
+    pub fn current() -> Self {
+        let handle = Handle::current();
+        Runtimes {
+            frontend: handle.clone(),
+            backend: handle.clone(),
+            scheduler: handle.clone(),
+            provider: handle,
+        }
+    }
+}
+
+/// Runs the session manager until any of its components exits, or it's shut down.
+pub async fn run(
+    ctx: FlameClusterContext,
+    runtimes: Runtimes,
+    shutdown: CancellationToken,
+) -> Result<(), FlameError> {
+    let mut handlers = vec![];
+
+    scheduler::validate(&ctx)?;
+
+    let storage = storage::new_ptr(&ctx).await?;
+
+    // Load data from engine, e.g. sqlite.
+    storage.load_data().await?;
+
+    let join = ctx
+        .cluster
+        .join
+        .as_ref()
+        .map(controller::JoinManager::new)
+        .transpose()?;
+    let admin = ctx
+        .cluster
+        .admin
+        .as_ref()
+        .map(controller::AdminToken::new)
+        .transpose()?;
+    let controller = controller::new_ptr(storage.clone(), join, admin);
+
+    // Close the sessions of the jobs lost by the restart, before new jobs are
+    // created by the frontend.
+    controller.recover_jobs().await?;
+
+    // Start provider thread.
+    #[allow(clippy::let_underscore_future)]
+    {
+        let controller = controller.clone();
+        let ctx = ctx.clone();
+        let _ = runtimes.provider.spawn(async move {
+            let provider = provider::new("none", controller)?;
+            provider.run(ctx).await
+        });
+        // handlers.push(handler);
+    }
+
+    // Start apiserver frontend thread.
+    {
+        let controller = controller.clone();
+        let ctx = ctx.clone();
+        let handler = runtimes.frontend.spawn(async move {
+            let apiserver = apiserver::new_frontend(controller);
+            apiserver.run(ctx).await
+        });
+        handlers.push(handler);
+    }
+
+    // Start apiserver backend thread.
+    {
+        let controller = controller.clone();
+        let ctx = ctx.clone();
+        let handler = runtimes.backend.spawn(async move {
+            let apiserver = apiserver::new_backend(controller);
+            apiserver.run(ctx).await
+        });
+        handlers.push(handler);
+    }
+
+    // Start apiserver http thread, e.g. for the health checks of the load balancers.
+    if ctx.cluster.http_port.is_some() {
+        let controller = controller.clone();
+        let ctx = ctx.clone();
+        let handler = runtimes.frontend.spawn(async move {
+            let apiserver = apiserver::new_http(controller);
+            apiserver.run(ctx).await
+        });
+        handlers.push(handler);
+    }
+
+    // Start scheduler thread.
+    {
+        let controller = controller.clone();
+        let ctx = ctx.clone();
+        let shutdown = shutdown.clone();
+        let handler = runtimes.scheduler.spawn(async move {
+            let scheduler = scheduler::new(controller, shutdown);
+            scheduler.run(ctx).await
+        });
+        handlers.push(handler);
+    }
+
+    // Start backup thread.
+    if let Some(backup) = ctx.cluster.backup.clone() {
+        tracing::info!(
+            "Back up storage to <{}> every {}s, keep {} backups.",
+            backup.path,
+            backup.interval,
+            backup.retention
+        );
+        let handler = tokio::spawn(storage::backup::run(storage.clone(), backup));
+        handlers.push(handler);
+    }
+
+    // Start exporter thread.
+    if let Some(exporter) = ctx.cluster.exporter.clone() {
+        tracing::info!(
+            "Export task records to <{}> every {}s, {} records per batch.",
+            exporter.table,
+            exporter.flush_interval,
+            exporter.batch_size
+        );
+        let handler = tokio::spawn(storage::exporter::run(storage.clone(), exporter));
+        handlers.push(handler);
+    }
+
+    // Start lease thread.
+    {
+        tracing::info!(
+            "Close the sessions of expired client lease after {}s.",
+            ctx.cluster.lease_grace_period
+        );
+        let handler = tokio::spawn(controller::leases::run(controller.clone()));
+        handlers.push(handler);
+    }
+
+    // Start job thread.
+    {
+        tracing::info!("Sync the jobs by the tasks of their sessions every 1s.");
+        let handler = tokio::spawn(controller::jobs::run(controller.clone(), shutdown.clone()));
+        handlers.push(handler);
+    }
+
+    // Start session GC thread.
+    {
+        tracing::info!(
+            "Delete the closed sessions after their TTL every {}s.",
+            controller::session_gc::SESSION_GC_INTERVAL.as_secs()
+        );
+        let handler = tokio::spawn(controller::session_gc::run(controller.clone()));
+        handlers.push(handler);
+    }
+
+    // Start task watchdog thread.
+    {
+        tracing::info!(
+            "Dispatch the running tasks again after their timeout and {}s of grace period.",
+            controller::task_watchdog::TASK_TIMEOUT_GRACE_PERIOD
+        );
+        let handler = tokio::spawn(controller::task_watchdog::run(controller.clone()));
+        handlers.push(handler);
+    }
+
+    // Start node health thread.
+    {
+        let health = ctx.cluster.node_health.clone();
+        tracing::info!(
+            "Mark the nodes without heartbeat NotReady after {}s, Lost after {}s.",
+            health.not_ready_timeout,
+            health.lost_timeout
+        );
+        let handler = tokio::spawn(controller::node_health::run(controller.clone(), health));
+        handlers.push(handler);
+    }
+
+    // Start memory guardrails thread.
+    if let Some(memory) = ctx.cluster.memory.clone() {
+        tracing::info!(
+            "Check memory usage every {}s, soft limit: {:?}, hard limit: {:?}.",
+            memory.interval,
+            memory.soft_limit,
+            memory.hard_limit
+        );
+        let handler = tokio::spawn(controller::memory::run(controller.clone(), memory));
+        handlers.push(handler);
+    }
+
+    // Start runtime metrics thread.
+    if let Some(interval) = ctx.cluster.runtimes.metrics_interval {
+        tracing::info!("Report the metrics of the runtimes every {interval}s.");
+        let handler = tokio::spawn(metrics::run(runtimes.clone(), interval));
+        handlers.push(handler);
+    }
+
+    tracing::info!("flame-session-manager started.");
+
+    // Register default applications, then the applications of the site, e.g. baked
+    // into the installation image; the ones registered before are updated.
+    let applications = common::apis::load_applications(&ctx.paths.applications)?;
+    tracing::info!(
+        "Load {} applications from <{}>.",
+        applications.len(),
+        ctx.paths.applications.display()
+    );
+    #[allow(clippy::let_underscore_future)]
+    let _: JoinHandle<Result<(), FlameError>> = tokio::spawn(async move {
+        for (name, attr) in common::default_applications() {
+            if let Err(e) = controller.register_application(name.clone(), attr).await {
+                tracing::debug!("Skip default application <{name}>: {e}");
+            }
+        }
+
+        for (name, attr) in applications {
+            let res = match controller.get_application(name.clone()).await {
+                Ok(_) => controller.update_application(name.clone(), attr).await,
+                Err(_) => controller.register_application(name.clone(), attr).await,
+            };
+            if let Err(e) = res {
+                tracing::warn!("Failed to register application <{name}>: {e}");
+            }
+        }
+
+        Ok(())
+    });
+
+    let (res, idx, _) = select_all(handlers).await;
+    tracing::info!("Thread <{idx}> exited with result: {res:?}");
+    shutdown.cancel();
+
+    // Exit with the error above the hard memory limit, so the session manager
+    // is restarted by its supervisor.
+    match res {
+        Ok(Err(e @ FlameError::ResourceExhausted(_))) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[async_trait::async_trait]
+pub trait FlameThread: Send + Sync + 'static {
+    async fn run(&self, ctx: FlameClusterContext) -> Result<(), FlameError>;
+}
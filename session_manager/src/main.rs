@@ -12,20 +12,12 @@ limitations under the License.
 */
 
 use clap::Parser;
-use futures::future::select_all;
 use tokio::runtime::{Builder, Runtime};
-use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use common::ctx::FlameClusterContext;
 use common::FlameError;
-
-mod apiserver;
-mod controller;
-mod events;
-mod model;
-mod provider;
-pub mod scheduler;
-mod storage;
+use flame_session_manager::Runtimes;
 
 #[derive(Parser)]
 #[command(name = "flame-session-manager")]
@@ -39,21 +31,14 @@ struct Cli {
 
 #[tokio::main]
 async fn main() -> Result<(), FlameError> {
-    let _log_guard = common::init_logger(Some("fsm"))?;
-
     let cli = Cli::parse();
     let ctx = FlameClusterContext::from_file(cli.config)?;
+    ctx.paths.create_dirs()?;
 
-    tracing::info!("flame-session-manager is starting ...");
-
-    let mut handlers = vec![];
+    let _log_guard = common::init_logger(Some("fsm"), Some(&ctx.paths.logs))?;
 
-    let storage = storage::new_ptr(&ctx).await?;
-
-    // Load data from engine, e.g. sqlite.
-    storage.load_data().await?;
+    tracing::info!("flame-session-manager is starting ...");
 
-    let controller = controller::new_ptr(storage.clone());
     let build_runtime = |name: &str, threads: usize| -> Result<Runtime, FlameError> {
         Builder::new_multi_thread()
             .worker_threads(threads)
@@ -66,15 +51,10 @@ async fn main() -> Result<(), FlameError> {
     let num_cpus = std::thread::available_parallelism()
         .map(|p| p.get())
         .unwrap_or(4);
-    let frontend_threads = 1;
-    let scheduler_threads = 1;
-    let provider_threads = 1;
-    let reserved_threads = frontend_threads + scheduler_threads + provider_threads;
-    let backend_threads = if num_cpus > reserved_threads {
-        num_cpus - reserved_threads
-    } else {
-        1
-    };
+    let frontend_threads = ctx.cluster.runtimes.frontend;
+    let scheduler_threads = ctx.cluster.runtimes.scheduler;
+    let provider_threads = ctx.cluster.runtimes.provider;
+    let backend_threads = ctx.cluster.runtimes.backend_threads(num_cpus);
 
     tracing::info!(
         "CPU allocation: total={}, frontend={}, scheduler={}, provider={}, backend={}",
@@ -90,70 +70,24 @@ async fn main() -> Result<(), FlameError> {
     let scheduler_rt = build_runtime("scheduler", scheduler_threads)?;
     let provider_rt = build_runtime("provider", provider_threads)?;
 
-    // Start provider thread.
-    #[allow(clippy::let_underscore_future)]
+    // Stop the scheduler before exiting, e.g. on Ctrl-C.
+    let shutdown = CancellationToken::new();
     {
-        let controller = controller.clone();
-        let ctx = ctx.clone();
-        let _ = provider_rt.spawn(async move {
-            let provider = provider::new("none", controller)?;
-            provider.run(ctx).await
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                tracing::info!("Received Ctrl-C, flame-session-manager is shutting down ...");
+                shutdown.cancel();
+            }
         });
-        // handlers.push(handler);
     }
 
-    // Start apiserver frontend thread.
-    {
-        let controller = controller.clone();
-        let ctx = ctx.clone();
-        let handler = frontend_rt.spawn(async move {
-            let apiserver = apiserver::new_frontend(controller);
-            apiserver.run(ctx).await
-        });
-        handlers.push(handler);
-    }
-
-    // Start apiserver backend thread.
-    {
-        let controller = controller.clone();
-        let ctx = ctx.clone();
-        let handler = backend_rt.spawn(async move {
-            let apiserver = apiserver::new_backend(controller);
-            apiserver.run(ctx).await
-        });
-        handlers.push(handler);
-    }
-
-    // Start scheduler thread.
-    {
-        let controller = controller.clone();
-        let ctx = ctx.clone();
-        let handler = scheduler_rt.spawn(async move {
-            let scheduler = scheduler::new(controller);
-            scheduler.run(ctx).await
-        });
-        handlers.push(handler);
-    }
-
-    tracing::info!("flame-session-manager started.");
-
-    // Register default applications.
-    #[allow(clippy::let_underscore_future)]
-    let _: JoinHandle<Result<(), FlameError>> = tokio::spawn(async move {
-        for (name, attr) in common::default_applications() {
-            controller.register_application(name, attr).await?;
-        }
-
-        Ok(())
-    });
-
-    let (res, idx, _) = select_all(handlers).await;
-    tracing::info!("Thread <{idx}> exited with result: {res:?}");
-
-    Ok(())
-}
+    let runtimes = Runtimes {
+        frontend: frontend_rt.handle().clone(),
+        backend: backend_rt.handle().clone(),
+        scheduler: scheduler_rt.handle().clone(),
+        provider: provider_rt.handle().clone(),
+    };
 
-#[async_trait::async_trait]
-pub trait FlameThread: Send + Sync + 'static {
-    async fn run(&self, ctx: FlameClusterContext) -> Result<(), FlameError>;
+    flame_session_manager::run(ctx, runtimes, shutdown).await
 }
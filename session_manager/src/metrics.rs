@@ -0,0 +1,101 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The metrics of the runtimes of the session manager.
+//!
+//! The metrics of each runtime are reported periodically to size its worker
+//! threads by `cluster.runtimes`: the workers busy most of the time, or the
+//! tasks piling up in the global queue, mean the runtime needs more threads,
+//! e.g. the frontend runtime of high-throughput clients.
+
+use std::time::Duration;
+
+use tokio::runtime::{Handle, RuntimeMetrics};
+
+use common::FlameError;
+
+use crate::Runtimes;
+
+/// The total time the workers of the runtime were busy since it started.
+fn total_busy(metrics: &RuntimeMetrics) -> Duration {
+    (0..metrics.num_workers())
+        .map(|worker| metrics.worker_total_busy_duration(worker))
+        .sum()
+}
+
+/// The percentage of the time the workers were busy in the interval.
+fn busy_percent(busy: Duration, workers: usize, interval: Duration) -> f64 {
+    let capacity = interval.as_secs_f64() * workers as f64;
+    if capacity == 0.0 {
+        return 0.0;
+    }
+
+    (busy.as_secs_f64() / capacity * 100.0).min(100.0)
+}
+
+/// Report the metrics of the runtimes every interval in seconds; it never
+/// returns.
+pub async fn run(runtimes: Runtimes, interval: u64) -> Result<(), FlameError> {
+    let runtimes: [(&str, Handle); 4] = [
+        ("frontend", runtimes.frontend),
+        ("backend", runtimes.backend),
+        ("scheduler", runtimes.scheduler),
+        ("provider", runtimes.provider),
+    ];
+    let period = Duration::from_secs(interval);
+
+    let mut busy: Vec<Duration> = runtimes
+        .iter()
+        .map(|(_, handle)| total_busy(&handle.metrics()))
+        .collect();
+
+    let mut ticker = tokio::time::interval(period);
+    // The first tick completes immediately.
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+
+        for ((name, handle), last_busy) in runtimes.iter().zip(busy.iter_mut()) {
+            let metrics = handle.metrics();
+            let current_busy = total_busy(&metrics);
+            let percent = busy_percent(
+                current_busy.saturating_sub(*last_busy),
+                metrics.num_workers(),
+                period,
+            );
+            *last_busy = current_busy;
+
+            tracing::info!(
+                "Runtime <{name}>: workers={}, alive_tasks={}, global_queue_depth={}, busy={percent:.1}%",
+                metrics.num_workers(),
+                metrics.num_alive_tasks(),
+                metrics.global_queue_depth(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_busy_percent() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(busy_percent(Duration::from_secs(5), 1, interval), 50.0);
+        assert_eq!(busy_percent(Duration::from_secs(5), 2, interval), 25.0);
+        assert_eq!(busy_percent(Duration::from_secs(30), 2, interval), 100.0);
+        assert_eq!(busy_percent(Duration::from_secs(5), 0, interval), 0.0);
+    }
+}
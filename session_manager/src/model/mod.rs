@@ -18,16 +18,18 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use chrono::{DateTime, Duration, Utc};
 use stdng::{lock_ptr, MutexPtr};
 
 use common::apis::{
-    Application, ExecutorID, ExecutorState, Node, NodeState, ResourceRequirement, Session,
-    SessionID, SessionState, Shim, Task, TaskID, TaskState,
+    Application, ExecutorID, ExecutorState, FairnessPolicy, Node, NodeState, ResourceRequirement,
+    Session, SessionID, SessionState, Shim, Task, TaskDurationStats, TaskID, TaskState,
+    TopologyPolicy,
 };
+use common::ctx::FlameShuffle;
 use common::FlameError;
 use rpc::flame::v1 as rpc;
 
@@ -48,6 +50,30 @@ pub struct SnapShot {
     pub exec_index: MutexPtr<HashMap<ExecutorState, HashMap<ExecutorID, ExecutorInfoPtr>>>,
 
     pub nodes: MutexPtr<HashMap<String, NodeInfoPtr>>,
+
+    /// The number of instances, i.e. the executors binding or bound, of each
+    /// session and application; used to enforce the max_instances limits.
+    pub ssn_instances: MutexPtr<HashMap<SessionID, u32>>,
+    pub app_instances: MutexPtr<HashMap<String, u32>>,
+
+    /// The idle timeout of the bound executors without tasks, if enabled.
+    pub idle_timeout: Option<Duration>,
+
+    /// The safety limits of the shuffle action, and the last time each session
+    /// gave or received an executor by a shuffle.
+    pub shuffle: FlameShuffle,
+    pub last_shuffles: HashMap<SessionID, DateTime<Utc>>,
+
+    /// The executors bound with a previous definition of their application,
+    /// which are recycled after the application was updated.
+    pub stale_executors: HashSet<ExecutorID>,
+
+    /// The applications whose instances failed to start on each node, with the
+    /// reason of the last failure; they're not scheduled to the node.
+    pub unavailable_apps: HashMap<String, HashMap<String, String>>,
+
+    /// The loads of the executors on each node, by the heartbeats of the nodes.
+    pub node_loads: HashMap<String, NodeLoad>,
 }
 
 pub type SnapShotPtr = Arc<SnapShot>;
@@ -62,6 +88,14 @@ impl SnapShot {
             executors: Arc::new(Mutex::new(HashMap::new())),
             exec_index: Arc::new(Mutex::new(HashMap::new())),
             nodes: Arc::new(Mutex::new(HashMap::new())),
+            ssn_instances: Arc::new(Mutex::new(HashMap::new())),
+            app_instances: Arc::new(Mutex::new(HashMap::new())),
+            idle_timeout: None,
+            shuffle: FlameShuffle::default(),
+            last_shuffles: HashMap::new(),
+            stale_executors: HashSet::new(),
+            unavailable_apps: HashMap::new(),
+            node_loads: HashMap::new(),
         }
     }
 
@@ -94,6 +128,25 @@ impl SnapShot {
         Ok(())
     }
 
+    /// The reason why the application can not run on the node, if its instances
+    /// failed to start there.
+    pub fn unavailable_reason(&self, node: &str, app: &str) -> Option<&String> {
+        self.unavailable_apps.get(node)?.get(app)
+    }
+
+    /// The max executors of the node by its last heartbeat, if limited.
+    pub fn node_max_executors(&self, node: &str) -> Option<u32> {
+        self.node_loads.get(node)?.max_executors
+    }
+
+    /// The saturation of the node by its last heartbeat; 0 if it's unknown.
+    pub fn node_saturation(&self, node: &str) -> f64 {
+        self.node_loads
+            .get(node)
+            .and_then(NodeLoad::saturation)
+            .unwrap_or_default()
+    }
+
     /// Get the application info for a session by looking up the application name.
     pub fn get_application(&self, app_name: &str) -> Result<Option<AppInfoPtr>, FlameError> {
         let apps = lock_ptr!(self.applications)?;
@@ -127,6 +180,37 @@ pub struct SessionInfo {
     pub min_instances: u32,
     pub max_instances: Option<u32>,
     pub batch_size: u32,
+    /// The priority of the session in scheduling, the higher the earlier.
+    pub priority: u32,
+    /// The durations of the tasks completed since the session manager started;
+    /// they're not persisted, so they're observed again after a restart.
+    pub task_durations: TaskDurationStats,
+}
+
+/// The time in milliseconds that the executors of a session are expected to take for its
+/// tasks; sessions of short tasks ask for fewer executors, as starting one is not free.
+const EXECUTOR_BACKLOG_MS: f64 = 10_000.0;
+
+impl SessionInfo {
+    /// Returns the number of instances to run the pending and running tasks of the session.
+    pub fn desired_instances(&self) -> f64 {
+        let mut tasks = 0.0;
+        for state in [TaskState::Pending, TaskState::Running] {
+            if let Some(d) = self.tasks_status.get(&state) {
+                tasks += *d as f64;
+            }
+        }
+
+        // Gang scheduling needs all the instances of a batch.
+        if self.batch_size > 1 || tasks == 0.0 {
+            return tasks;
+        }
+
+        match self.task_durations.expected() {
+            Some(expected) => tasks.min((tasks * expected / EXECUTOR_BACKLOG_MS).ceil().max(1.0)),
+            None => tasks,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -139,6 +223,9 @@ pub struct ExecutorInfo {
     pub task_id: Option<TaskID>,
     pub ssn_id: Option<SessionID>,
     pub batch_index: Option<u32>,
+    /// When the executor became idle, i.e. it was bound or completed its last
+    /// task; `None` if it is running a task.
+    pub idle_since: Option<DateTime<Utc>>,
 
     pub creation_time: DateTime<Utc>,
     pub state: ExecutorState,
@@ -149,6 +236,70 @@ pub struct NodeInfo {
     pub name: String,
     pub allocatable: ResourceRequirement,
     pub state: NodeState,
+    /// The NUMA nodes and the GPU groups of the node, for the placement of
+    /// the topology-aware applications.
+    pub numa_nodes: u32,
+    pub gpu_groups: u32,
+    /// The applications whose artifacts are prefetched on the node.
+    pub prefetched: HashSet<String>,
+}
+
+impl NodeInfo {
+    /// Whether the artifacts of the application are prefetched on the node,
+    /// so its instances start without pulling them.
+    pub fn is_prefetched(&self, app: &str) -> bool {
+        self.prefetched.contains(app)
+    }
+}
+
+/// The load of an executor, reported by the heartbeats of its node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutorLoad {
+    /// The launched tasks waiting for the running one, e.g. the rest of its bundle.
+    pub queue_depth: u32,
+    /// The fraction of the time the executor was running tasks.
+    pub busy_ratio: f64,
+}
+
+/// The loads of the executors on a node, by the last heartbeat of the node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeLoad {
+    pub executors: HashMap<ExecutorID, ExecutorLoad>,
+    /// The max executors of the node, enforced by its executor manager.
+    pub max_executors: Option<u32>,
+}
+
+impl NodeLoad {
+    /// The saturation of the node, i.e. the mean busy ratio of its executors;
+    /// None if it has no executors.
+    pub fn saturation(&self) -> Option<f64> {
+        if self.executors.is_empty() {
+            return None;
+        }
+
+        let busy: f64 = self.executors.values().map(|e| e.busy_ratio).sum();
+        Some(busy / self.executors.len() as f64)
+    }
+}
+
+impl From<&[rpc::ExecutorLoad]> for NodeLoad {
+    fn from(loads: &[rpc::ExecutorLoad]) -> Self {
+        NodeLoad {
+            executors: loads
+                .iter()
+                .map(|load| {
+                    (
+                        load.executor_id.clone(),
+                        ExecutorLoad {
+                            queue_depth: load.queue_depth,
+                            busy_ratio: load.busy_ratio.clamp(0.0, 1.0),
+                        },
+                    )
+                })
+                .collect(),
+            max_executors: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -156,7 +307,10 @@ pub struct AppInfo {
     pub name: String,
     pub shim: Shim, // Required shim type for the application
     pub max_instances: u32,
+    pub max_instances_per_node: Option<u32>,
     pub delay_release: Duration,
+    pub fairness: FairnessPolicy,
+    pub topology: TopologyPolicy,
 }
 
 impl From<Application> for AppInfo {
@@ -171,6 +325,9 @@ impl From<&Node> for NodeInfo {
             name: node.name.clone(),
             allocatable: node.allocatable.clone(),
             state: node.state,
+            numa_nodes: node.info.numa_nodes.len() as u32,
+            gpu_groups: node.info.gpu_groups.len() as u32,
+            prefetched: node.info.prefetched.iter().cloned().collect(),
         }
     }
 }
@@ -181,7 +338,20 @@ impl From<&Application> for AppInfo {
             name: app.name.to_string(),
             shim: app.shim, // Get shim from application
             max_instances: app.max_instances,
+            max_instances_per_node: app.max_instances_per_node,
             delay_release: app.delay_release,
+            fairness: app.fairness,
+            topology: app.topology,
+        }
+    }
+}
+
+impl ExecutorInfo {
+    /// The session of which this executor is an instance, i.e. it is binding or bound to.
+    pub fn instance_of(&self) -> Option<&SessionID> {
+        match self.state {
+            ExecutorState::Binding | ExecutorState::Bound => self.ssn_id.as_ref(),
+            _ => None,
         }
     }
 }
@@ -197,6 +367,7 @@ impl From<&Executor> for ExecutorInfo {
             task_id: exec.task_id,
             ssn_id: exec.ssn_id.clone(),
             batch_index: exec.batch_index,
+            idle_since: exec.task_start.filter(|_| exec.task_id.is_none()),
             creation_time: exec.creation_time,
             state: exec.state,
         }
@@ -233,6 +404,8 @@ impl From<&Session> for SessionInfo {
             min_instances: ssn.min_instances,
             max_instances: ssn.max_instances,
             batch_size: ssn.batch_size.max(1),
+            priority: ssn.priority,
+            task_durations: ssn.task_durations,
         }
     }
 }
@@ -383,6 +556,7 @@ impl Default for NodeFilter {
 }
 
 pub const ALL_NODE: Option<NodeFilter> = None;
+pub const READY_NODE: Option<NodeFilter> = Some(NodeFilter::by_state(NodeState::Ready));
 
 pub const IDLE_EXECUTOR: Option<ExecutorFilter> =
     Some(ExecutorFilter::by_state(ExecutorState::Idle));
@@ -722,6 +896,10 @@ impl SnapShot {
             executors.insert(exec.id.clone(), exec.clone());
         }
 
+        if let Some(ssn_id) = exec.instance_of() {
+            self.update_instances(ssn_id, true)?;
+        }
+
         {
             let mut exec_index = lock_ptr!(self.exec_index)?;
             exec_index.entry(exec.state).or_default();
@@ -735,9 +913,14 @@ impl SnapShot {
     }
 
     pub fn delete_executor(&self, exec: ExecutorInfoPtr) -> Result<(), FlameError> {
-        {
+        // The given executor may have a new state, e.g. by `update_executor_state`;
+        // so count the instance by the one in the snapshot.
+        let removed = {
             let mut executors = lock_ptr!(self.executors)?;
-            executors.remove(&exec.id);
+            executors.remove(&exec.id)
+        };
+        if let Some(ssn_id) = removed.as_ref().and_then(|e| e.instance_of()) {
+            self.update_instances(ssn_id, false)?;
         }
         {
             let mut exec_index = lock_ptr!(self.exec_index)?;
@@ -763,6 +946,7 @@ impl SnapShot {
             shim: exec.shim,
             ssn_id: exec.ssn_id.clone(),
             batch_index: exec.batch_index,
+            idle_since: None,
             creation_time: exec.creation_time,
             state,
         });
@@ -806,6 +990,67 @@ impl SnapShot {
             .cloned()
             .collect())
     }
+
+    /// Count an instance allocated or bound to the session in this scheduling cycle,
+    /// whose executor in the snapshot is not bound to the session yet.
+    pub fn add_instance(&self, ssn: &SessionInfoPtr) -> Result<(), FlameError> {
+        self.update_instances(&ssn.id, true)
+    }
+
+    /// Get the number of instances of the session.
+    pub fn session_instances(&self, ssn_id: &SessionID) -> Result<u32, FlameError> {
+        let instances = lock_ptr!(self.ssn_instances)?;
+        Ok(instances.get(ssn_id).copied().unwrap_or(0))
+    }
+
+    /// Get the number of instances of all the sessions of the application.
+    pub fn app_instances(&self, app_name: &str) -> Result<u32, FlameError> {
+        let instances = lock_ptr!(self.app_instances)?;
+        Ok(instances.get(app_name).copied().unwrap_or(0))
+    }
+
+    fn update_instances(&self, ssn_id: &SessionID, add: bool) -> Result<(), FlameError> {
+        let app_name = {
+            let sessions = lock_ptr!(self.sessions)?;
+            sessions.get(ssn_id).map(|ssn| ssn.application.clone())
+        };
+
+        let update = |count: &mut u32| {
+            *count = if add {
+                *count + 1
+            } else {
+                count.saturating_sub(1)
+            };
+        };
+
+        update(
+            lock_ptr!(self.ssn_instances)?
+                .entry(ssn_id.clone())
+                .or_default(),
+        );
+        if let Some(app_name) = app_name {
+            update(lock_ptr!(self.app_instances)?.entry(app_name).or_default());
+        }
+
+        Ok(())
+    }
+
+    /// Get the number of executors on the node which are bound to the sessions of the application.
+    pub fn app_executors_on_node(
+        &self,
+        app_name: &str,
+        node_name: &str,
+    ) -> Result<usize, FlameError> {
+        let ssn_list = self.find_sessions(None)?;
+        let executors = self.find_executors(Some(ExecutorFilter::by_node(node_name)))?;
+
+        Ok(executors
+            .values()
+            .filter_map(|exec| exec.ssn_id.as_ref())
+            .filter_map(|id| ssn_list.get(id))
+            .filter(|ssn| ssn.application == app_name)
+            .count())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -816,8 +1061,21 @@ pub struct Executor {
     pub slots: u32,
     pub shim: Shim,
     pub task_id: Option<TaskID>,
+    /// The tasks launched together with `task_id`, in the order the executor runs them.
+    pub bundle: Vec<TaskID>,
+    /// When the current task started, i.e. when it was launched or the previous one completed;
+    /// it is also set when the executor is bound.
+    pub task_start: Option<DateTime<Utc>>,
     pub ssn_id: Option<SessionID>,
     pub batch_index: Option<u32>,
+    /// Whether the executor is unbinding for a preemption, so the running task
+    /// is interrupted; it's not persisted.
+    pub preempted: bool,
+    /// The generation of the executor, which differs from the one of the
+    /// released executor of the same id, so the requests of the released one
+    /// are rejected; 0 if unknown, e.g. the executors persisted by the older
+    /// versions.
+    pub generation: u64,
 
     pub creation_time: DateTime<Utc>,
     pub state: ExecutorState,
@@ -832,8 +1090,12 @@ impl Default for Executor {
             slots: 0,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::default(),
         }
@@ -863,8 +1125,12 @@ impl From<&rpc::Executor> for Executor {
             slots: spec.slots,
             shim: Shim::from(spec.shim()),
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: status.batch_index,
+            preempted: status.preempted,
+            generation: status.generation,
             creation_time: Utc::now(),
             state,
         }
@@ -893,8 +1159,10 @@ impl From<&Executor> for rpc::Executor {
 
         let status = Some(rpc::ExecutorStatus {
             state: rpc::ExecutorState::from(e.state).into(),
-            session_id: e.ssn_id.clone(),
+            session_id: e.ssn_id.as_ref().map(|id| id.to_string()),
             batch_index: e.batch_index,
+            preempted: e.preempted,
+            generation: e.generation,
         });
 
         rpc::Executor {
@@ -905,10 +1173,87 @@ impl From<&Executor> for rpc::Executor {
     }
 }
 
+impl From<&ExecutorInfo> for rpc::Executor {
+    fn from(e: &ExecutorInfo) -> Self {
+        rpc::Executor {
+            metadata: Some(rpc::Metadata {
+                id: e.id.clone(),
+                name: e.id.clone(),
+            }),
+            spec: Some(rpc::ExecutorSpec {
+                resreq: Some(e.resreq.clone().into()),
+                node: e.node.clone(),
+                slots: e.slots,
+                shim: rpc::Shim::from(e.shim).into(),
+            }),
+            status: Some(rpc::ExecutorStatus {
+                state: rpc::ExecutorState::from(e.state).into(),
+                session_id: e.ssn_id.as_ref().map(|id| id.to_string()),
+                batch_index: e.batch_index,
+                preempted: false,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl From<&SessionInfo> for rpc::Session {
+    fn from(ssn: &SessionInfo) -> Self {
+        let count = |state: TaskState| ssn.tasks_status.get(&state).copied().unwrap_or(0);
+
+        rpc::Session {
+            metadata: Some(rpc::Metadata {
+                id: ssn.id.to_string(),
+                name: ssn.id.to_string(),
+            }),
+            spec: Some(rpc::SessionSpec {
+                application: ssn.application.clone(),
+                slots: ssn.slots,
+                min_instances: ssn.min_instances,
+                max_instances: ssn.max_instances,
+                batch_size: ssn.batch_size,
+                priority: ssn.priority,
+                ..Default::default()
+            }),
+            status: Some(rpc::SessionStatus {
+                state: rpc::SessionState::from(ssn.state).into(),
+                creation_time: ssn.creation_time.timestamp(),
+                completion_time: ssn.completion_time.map(|t| t.timestamp()),
+                pending: count(TaskState::Pending),
+                running: count(TaskState::Running),
+                succeed: count(TaskState::Succeed),
+                failed: count(TaskState::Failed),
+                cancelled: count(TaskState::Cancelled),
+                quarantined: count(TaskState::Quarantined),
+                task_durations: Some(ssn.task_durations.into()),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl From<&NodeInfo> for rpc::Node {
+    fn from(node: &NodeInfo) -> Self {
+        rpc::Node {
+            metadata: Some(rpc::Metadata {
+                id: node.name.clone(),
+                name: node.name.clone(),
+            }),
+            spec: None,
+            status: Some(rpc::NodeStatus {
+                state: rpc::NodeState::from(node.state).into(),
+                allocatable: Some(node.allocatable.clone().into()),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use common::apis::MIN_TASK_DURATION_SAMPLES;
 
     /// Helper to create a test executor with given parameters.
     fn create_test_executor(id: &str, slots: u32, state: ExecutorState) -> ExecutorInfoPtr {
@@ -924,6 +1269,7 @@ mod tests {
             task_id: None,
             ssn_id: None,
             batch_index: None,
+            idle_since: None,
             creation_time: Utc::now(),
             state,
         })
@@ -932,7 +1278,7 @@ mod tests {
     /// Helper to create a test session with given parameters.
     fn create_test_session(id: &str, slots: u32, state: SessionState) -> SessionInfoPtr {
         Arc::new(SessionInfo {
-            id: id.to_string(),
+            id: id.to_string().into(),
             application: "test-app".to_string(),
             slots,
             tasks_status: HashMap::from([(TaskState::Pending, 1)]),
@@ -942,9 +1288,55 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            priority: 0,
+            task_durations: TaskDurationStats::default(),
         })
     }
 
+    fn session_info(pending: i32, expected: Option<Duration>) -> SessionInfo {
+        let mut task_durations = TaskDurationStats::default();
+        if let Some(expected) = expected {
+            for _ in 0..MIN_TASK_DURATION_SAMPLES {
+                task_durations.observe(expected);
+            }
+        }
+
+        SessionInfo {
+            id: "ssn-1".into(),
+            application: "test-app".to_string(),
+            slots: 1,
+            tasks_status: HashMap::from([(TaskState::Pending, pending)]),
+            creation_time: Utc::now(),
+            completion_time: None,
+            state: SessionState::Open,
+            min_instances: 0,
+            max_instances: None,
+            batch_size: 1,
+            priority: 0,
+            task_durations,
+        }
+    }
+
+    #[test]
+    fn test_desired_instances() {
+        assert_eq!(session_info(100, None).desired_instances(), 100.0);
+        assert_eq!(session_info(0, None).desired_instances(), 0.0);
+
+        // 100 tasks of 1ms fit into one executor.
+        let ssn = session_info(100, Some(Duration::milliseconds(1)));
+        assert_eq!(ssn.desired_instances(), 1.0);
+        let ssn = session_info(1000, Some(Duration::milliseconds(100)));
+        assert_eq!(ssn.desired_instances(), 10.0);
+        let ssn = session_info(100, Some(Duration::seconds(60)));
+        assert_eq!(ssn.desired_instances(), 100.0);
+
+        let ssn = SessionInfo {
+            batch_size: 2,
+            ..session_info(100, Some(Duration::milliseconds(1)))
+        };
+        assert_eq!(ssn.desired_instances(), 100.0);
+    }
+
     /// Test that SnapShot correctly filters executors by state.
     #[test]
     fn test_snapshot_find_executors_by_state() {
@@ -1139,4 +1531,35 @@ mod tests {
         let idle_execs = ss.find_executors(IDLE_EXECUTOR).unwrap();
         assert_eq!(idle_execs.len(), 0);
     }
+
+    /// Test that the saturation of a node is the mean busy ratio of its executors.
+    #[test]
+    fn test_snapshot_node_saturation() {
+        let mut ss = SnapShot::new(ResourceRequirement {
+            cpu: 1,
+            memory: 1024,
+        });
+
+        let loads = vec![
+            rpc::ExecutorLoad {
+                executor_id: "exec-1".to_string(),
+                queue_depth: 2,
+                busy_ratio: 1.5,
+            },
+            rpc::ExecutorLoad {
+                executor_id: "exec-2".to_string(),
+                queue_depth: 0,
+                busy_ratio: 0.5,
+            },
+        ];
+        ss.node_loads
+            .insert("test-node".to_string(), NodeLoad::from(loads.as_slice()));
+        ss.node_loads
+            .insert("idle-node".to_string(), NodeLoad::default());
+
+        assert_eq!(ss.node_saturation("test-node"), 0.75);
+        assert_eq!(ss.node_saturation("idle-node"), 0.0);
+        assert_eq!(ss.node_saturation("unknown-node"), 0.0);
+        assert_eq!(NodeLoad::default().saturation(), None);
+    }
 }
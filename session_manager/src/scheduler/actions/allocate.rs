@@ -16,13 +16,16 @@ use std::sync::Arc;
 use stdng::collections::{BinaryHeap, Cmp};
 use stdng::{logs::TraceFn, trace_fn};
 
-use crate::model::{ALL_NODE, OPEN_SESSION};
+use crate::model::{
+    ExecutorFilter, NodeInfoPtr, SessionInfoPtr, SnapShotPtr, OPEN_SESSION, READY_NODE,
+};
 use crate::scheduler::actions::{Action, ActionPtr};
 use crate::scheduler::plugins::node_order_fn;
 use crate::scheduler::plugins::ssn_order_fn;
 use crate::scheduler::statement::Statement;
 use crate::scheduler::Context;
 
+use common::apis::ExecutorState;
 use common::FlameError;
 
 pub struct AllocateAction {}
@@ -31,10 +34,47 @@ impl AllocateAction {
     pub fn new_ptr() -> ActionPtr {
         Arc::new(AllocateAction {})
     }
+
+    /// The number of instances of the session's application on the node; the
+    /// void executors are not bound to any session yet, so they're counted as
+    /// instances of the application to avoid over-packing the node.
+    pub(super) fn node_instances(
+        ss: &SnapShotPtr,
+        ssn: &SessionInfoPtr,
+        node: &NodeInfoPtr,
+    ) -> Result<usize, FlameError> {
+        let bound = ss.app_executors_on_node(&ssn.application, &node.name)?;
+        let void = ss
+            .find_executors(Some(ExecutorFilter {
+                state: Some(ExecutorState::Void),
+                ..ExecutorFilter::by_node(node.name.clone())
+            }))?
+            .values()
+            .filter(|exec| exec.ssn_id.is_none())
+            .count();
+
+        Ok(bound + void)
+    }
+
+    /// The nodes to place the executors of the session on: the ones where the
+    /// artifacts of its application are prefetched first, so its instances
+    /// start without pulling them; the order of the plugins is kept otherwise.
+    pub(super) fn prefer_prefetched(
+        nodes: &[NodeInfoPtr],
+        ssn: &SessionInfoPtr,
+    ) -> Vec<NodeInfoPtr> {
+        let mut nodes = nodes.to_vec();
+        nodes.sort_by_key(|node| !node.is_prefetched(&ssn.application));
+        nodes
+    }
 }
 
 #[async_trait::async_trait]
 impl Action for AllocateAction {
+    fn name(&self) -> &'static str {
+        "allocate"
+    }
+
     async fn execute(&self, ctx: &mut Context) -> Result<(), FlameError> {
         trace_fn!("AllocateAction::execute");
         let ss = ctx.snapshot.clone();
@@ -48,7 +88,7 @@ impl Action for AllocateAction {
         }
 
         let mut nodes = vec![];
-        let node_list = ss.find_nodes(ALL_NODE)?;
+        let node_list = ss.find_nodes(READY_NODE)?;
         for node in node_list.values() {
             nodes.push(node.clone());
         }
@@ -70,6 +110,14 @@ impl Action for AllocateAction {
                 .pop()
                 .expect("failed to pop open session: loop guard ensures non-empty");
 
+            if ctx.is_unschedulable(&ssn)? {
+                continue;
+            }
+
+            if ctx.is_throttled(&ssn)? {
+                continue;
+            }
+
             let is_underused = ctx.is_underused(&ssn)?;
             if !is_underused {
                 tracing::debug!(
@@ -88,23 +136,6 @@ impl Action for AllocateAction {
                 ssn.tasks_status.get(&common::apis::TaskState::Running)
             );
 
-            if let Some(max_instances) = ssn.max_instances {
-                let all_executors = ss.find_executors(None)?;
-                let current_count = all_executors
-                    .values()
-                    .filter(|e| e.ssn_id.as_ref() == Some(&ssn.id))
-                    .count();
-                if current_count >= max_instances as usize {
-                    tracing::debug!(
-                        "Session <{}> has reached max_instances limit: {} >= {}",
-                        ssn.id,
-                        current_count,
-                        max_instances
-                    );
-                    continue;
-                }
-            }
-
             let pipelined_executors = ss.pipelined_executors(ssn.clone())?;
             if !pipelined_executors.is_empty() {
                 tracing::debug!(
@@ -115,11 +146,38 @@ impl Action for AllocateAction {
                 continue;
             }
 
+            let max_instances_per_node = ss
+                .get_application(&ssn.application)?
+                .and_then(|app| app.max_instances_per_node);
+
             let mut stmt = Statement::new(ss.clone(), ctx.plugins.clone(), ctx.controller.clone());
 
-            for node in nodes.iter() {
+            for node in Self::prefer_prefetched(&nodes, &ssn).iter() {
+                let mut node_instances = match max_instances_per_node {
+                    Some(_) => Self::node_instances(&ss, &ssn, node)?,
+                    None => 0,
+                };
+
                 while ctx.is_allocatable(node, &ssn)? {
+                    if ctx.check_instances(&ssn, stmt.len() as u32)?.is_some() {
+                        break;
+                    }
+
+                    if let Some(max) = max_instances_per_node {
+                        if node_instances >= max as usize {
+                            tracing::debug!(
+                                "Application <{}> has reached max_instances_per_node limit on node <{}>: {} >= {}",
+                                ssn.application,
+                                node.name,
+                                node_instances,
+                                max
+                            );
+                            break;
+                        }
+                    }
+
                     stmt.pipeline(node, &ssn)?;
+                    node_instances += 1;
 
                     if stmt.is_ready(&ssn)? {
                         break;
@@ -138,6 +196,7 @@ impl Action for AllocateAction {
                     ssn.id
                 );
                 stmt.commit().await?;
+                ctx.resume_session(&ssn)?;
                 nodes.sort_by(|a, b| node_order_fn.cmp(a, b));
                 open_ssns.push(ssn.clone());
             } else if !stmt.is_empty() {
@@ -152,3 +211,38 @@ impl Action for AllocateAction {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::{NodeInfo, SessionInfo};
+
+    fn node(name: &str, prefetched: &[&str]) -> NodeInfoPtr {
+        Arc::new(NodeInfo {
+            name: name.to_string(),
+            prefetched: prefetched.iter().map(|app| app.to_string()).collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_prefer_prefetched() {
+        let nodes = vec![
+            node("node-1", &[]),
+            node("node-2", &["app-2"]),
+            node("node-3", &["app-1"]),
+            node("node-4", &["app-1", "app-2"]),
+        ];
+        let ssn = Arc::new(SessionInfo {
+            application: "app-1".to_string(),
+            ..Default::default()
+        });
+
+        let names: Vec<_> = AllocateAction::prefer_prefetched(&nodes, &ssn)
+            .iter()
+            .map(|node| node.name.clone())
+            .collect();
+        assert_eq!(names, vec!["node-3", "node-4", "node-1", "node-2"]);
+    }
+}
@@ -0,0 +1,177 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use stdng::collections::{BinaryHeap, Cmp};
+use stdng::{logs::TraceFn, trace_fn};
+
+use crate::model::{SessionInfo, SessionInfoPtr, OPEN_SESSION, READY_NODE};
+use crate::scheduler::actions::{Action, ActionPtr, AllocateAction};
+use crate::scheduler::plugins::node_order_fn;
+use crate::scheduler::plugins::ssn_order_fn;
+use crate::scheduler::statement::Statement;
+use crate::scheduler::Context;
+
+use common::FlameError;
+
+/// Backfills the capacity left by the allocate action, e.g. single slots scattered
+/// over many nodes, to the elastic sessions which still have tasks waiting for
+/// executors. The backfilled executors are tracked as elastic allocations by the
+/// plugins, so the shuffle action reclaims them first.
+pub struct BackfillAction {}
+
+impl BackfillAction {
+    pub fn new_ptr() -> ActionPtr {
+        Arc::new(BackfillAction {})
+    }
+}
+
+/// The number of executors the session can still use, i.e. its desired instances
+/// without an executor; the sessions of short tasks are not backfilled beyond the
+/// executors their observed task durations ask for.
+fn backfill_room(ssn: &SessionInfo, instances: u32) -> u32 {
+    (ssn.desired_instances().max(0.0) as u32).saturating_sub(instances)
+}
+
+#[async_trait::async_trait]
+impl Action for BackfillAction {
+    fn name(&self) -> &'static str {
+        "backfill"
+    }
+
+    async fn execute(&self, ctx: &mut Context) -> Result<(), FlameError> {
+        trace_fn!("BackfillAction::execute");
+        let ss = ctx.snapshot.clone();
+
+        let mut elastic_ssns = BinaryHeap::new(ssn_order_fn(ctx));
+        let ssn_list = ss.find_sessions(OPEN_SESSION)?;
+        for ssn in ssn_list.values() {
+            if ctx.is_elastic(ssn) {
+                elastic_ssns.push(ssn.clone());
+            }
+        }
+
+        let node_order_fn = node_order_fn(ctx);
+        let mut nodes = ss
+            .find_nodes(READY_NODE)?
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| node_order_fn.cmp(a, b));
+
+        while let Some(ssn) = elastic_ssns.pop() {
+            if ctx.is_throttled(&ssn)? {
+                continue;
+            }
+
+            // The executors in pipeline will pick the waiting tasks up soon.
+            if !ss.pipelined_executors(ssn.clone())?.is_empty() {
+                continue;
+            }
+
+            let room = backfill_room(&ssn, ss.session_instances(&ssn.id)?);
+            if room == 0 {
+                continue;
+            }
+
+            let max_instances_per_node = ss
+                .get_application(&ssn.application)?
+                .and_then(|app| app.max_instances_per_node);
+
+            // Spread the executors over the nodes, one per node, to use the
+            // fragmented capacity in one round.
+            let mut stmt = Statement::new(ss.clone(), ctx.plugins.clone(), ctx.controller.clone());
+            for node in AllocateAction::prefer_prefetched(&nodes, &ssn).iter() {
+                if stmt.len() as u32 >= room {
+                    break;
+                }
+                if ctx.check_instances(&ssn, stmt.len() as u32)?.is_some() {
+                    break;
+                }
+                if !ctx.is_allocatable(node, &ssn)? {
+                    continue;
+                }
+                if let Some(max) = max_instances_per_node {
+                    if AllocateAction::node_instances(&ss, &ssn, node)? >= max as usize {
+                        continue;
+                    }
+                }
+
+                stmt.pipeline(node, &ssn)?;
+                ctx.plugins
+                    .on_backfill_executor(node.clone(), ssn.clone())?;
+            }
+
+            if stmt.is_empty() {
+                continue;
+            }
+
+            tracing::info!(
+                "Backfilling {} executor(s) for elastic session <{}>",
+                stmt.len(),
+                ssn.id
+            );
+            stmt.commit().await?;
+            ctx.resume_session(&ssn)?;
+            nodes.sort_by(|a, b| node_order_fn.cmp(a, b));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use chrono::{Duration, Utc};
+    use common::apis::{SessionState, TaskDurationStats, TaskState, MIN_TASK_DURATION_SAMPLES};
+
+    fn session_info(pending: i32, running: i32) -> SessionInfo {
+        SessionInfo {
+            id: "ssn-1".into(),
+            application: "test-app".to_string(),
+            slots: 1,
+            tasks_status: HashMap::from([
+                (TaskState::Pending, pending),
+                (TaskState::Running, running),
+            ]),
+            creation_time: Utc::now(),
+            completion_time: None,
+            state: SessionState::Open,
+            min_instances: 0,
+            max_instances: None,
+            batch_size: 1,
+            priority: 0,
+            task_durations: TaskDurationStats::default(),
+        }
+    }
+
+    #[test]
+    fn test_backfill_room() {
+        assert_eq!(backfill_room(&session_info(10, 2), 4), 8);
+        assert_eq!(backfill_room(&session_info(0, 2), 4), 0);
+        assert_eq!(backfill_room(&session_info(0, 0), 0), 0);
+
+        // 1000 tasks of 100ms are expected to take 10 executors.
+        let mut ssn = session_info(1000, 0);
+        for _ in 0..MIN_TASK_DURATION_SAMPLES {
+            ssn.task_durations.observe(Duration::milliseconds(100));
+        }
+        assert_eq!(backfill_room(&ssn, 4), 6);
+        assert_eq!(backfill_room(&ssn, 10), 0);
+    }
+}
@@ -17,6 +17,8 @@ use std::sync::Arc;
 use stdng::collections::{BinaryHeap, Cmp};
 use stdng::{logs::TraceFn, trace_fn};
 
+use common::apis::SessionID;
+
 use crate::model::{
     ExecutorInfoPtr, SessionInfoPtr, ALL_EXECUTOR, IDLE_EXECUTOR, OPEN_SESSION, UNBINDING_EXECUTOR,
     VOID_EXECUTOR,
@@ -46,6 +48,10 @@ impl DispatchAction {
 
 #[async_trait::async_trait]
 impl Action for DispatchAction {
+    fn name(&self) -> &'static str {
+        "dispatch"
+    }
+
     async fn execute(&self, ctx: &mut Context) -> Result<(), FlameError> {
         trace_fn!("DispatchAction::execute");
         let ss = ctx.snapshot.clone();
@@ -63,7 +69,10 @@ impl Action for DispatchAction {
         let mut unbinding_executors = ss.find_executors(UNBINDING_EXECUTOR)?;
 
         let all_executors = ss.find_executors(ALL_EXECUTOR)?;
-        let mut bound_counts: HashMap<String, u32> = HashMap::new();
+        let mut bound_counts: HashMap<SessionID, u32> = HashMap::new();
+        // The instances of the applications per node, counted on demand for
+        // the applications with max_instances_per_node.
+        let mut node_instances: HashMap<(String, String), usize> = HashMap::new();
         for exec in all_executors.values() {
             if let Some(ssn_id) = &exec.ssn_id {
                 *bound_counts.entry(ssn_id.clone()).or_insert(0) += 1;
@@ -84,6 +93,10 @@ impl Action for DispatchAction {
                 .pop()
                 .expect("failed to pop open session: loop guard ensures non-empty");
 
+            if ctx.is_throttled(&ssn)? {
+                continue;
+            }
+
             if !ctx.is_underused(&ssn)? {
                 tracing::debug!("Session <{}> is not underused, skip it.", ssn.id);
                 continue;
@@ -94,13 +107,46 @@ impl Action for DispatchAction {
                 &ssn.id
             );
 
-            // Allocate idle executors to underused sessions.
+            let max_instances_per_node = ss
+                .get_application(&ssn.application)?
+                .and_then(|app| app.max_instances_per_node);
+
+            // Allocate idle executors to underused sessions; the executors of the less
+            // saturated nodes are preferred, so the tasks don't pile onto the busy nodes.
+            let mut candidates: Vec<&ExecutorInfoPtr> = idle_executors.values().collect();
+            candidates.sort_by(|a, b| {
+                ss.node_saturation(&a.node)
+                    .total_cmp(&ss.node_saturation(&b.node))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+
             let mut exec: Option<ExecutorInfoPtr> = None;
-            for (_, e) in idle_executors.iter_mut() {
-                if ctx.is_available(e, &ssn)? {
-                    exec = Some(e.clone());
-                    break;
+            for e in candidates {
+                if !ctx.is_available(e, &ssn)? {
+                    continue;
+                }
+
+                if let Some(max) = max_instances_per_node {
+                    let key = (ssn.application.clone(), e.node.clone());
+                    let count = match node_instances.get(&key) {
+                        Some(count) => *count,
+                        None => ss.app_executors_on_node(&ssn.application, &e.node)?,
+                    };
+                    node_instances.insert(key, count);
+                    if count >= max as usize {
+                        tracing::debug!(
+                            "Skip executor <{}> for session <{}>: application <{}> has reached max_instances_per_node limit on node <{}>.",
+                            e.id,
+                            ssn.id,
+                            ssn.application,
+                            e.node
+                        );
+                        continue;
+                    }
                 }
+
+                exec = Some(e.clone());
+                break;
             }
 
             if let Some(exec) = exec {
@@ -116,6 +162,11 @@ impl Action for DispatchAction {
                 ctx.bind_session(&exec, &ssn, batch_index).await?;
                 idle_executors.remove(&exec.id);
                 *bound_count += 1;
+                if let Some(count) =
+                    node_instances.get_mut(&(ssn.application.clone(), exec.node.clone()))
+                {
+                    *count += 1;
+                }
 
                 open_ssns.push(ssn);
                 continue;
@@ -19,10 +19,12 @@ use crate::scheduler::Context;
 use crate::FlameError;
 
 pub use allocate::AllocateAction;
+pub use backfill::BackfillAction;
 pub use dispatch::DispatchAction;
 pub use shuffle::ShuffleAction;
 
 mod allocate;
+mod backfill;
 mod dispatch;
 mod shuffle;
 
@@ -30,5 +32,8 @@ pub type ActionPtr = Arc<dyn Action>;
 
 #[async_trait]
 pub trait Action: Send + Sync + 'static {
+    /// The name of the action, e.g. in the logs and metrics.
+    fn name(&self) -> &'static str;
+
     async fn execute(&self, ctx: &mut Context) -> Result<(), FlameError>;
 }
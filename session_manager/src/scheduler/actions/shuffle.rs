@@ -11,16 +11,22 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
 use stdng::collections::{BinaryHeap, Cmp};
 use stdng::{logs::TraceFn, trace_fn};
 
-use crate::model::{BOUND_EXECUTOR, IDLE_EXECUTOR, OPEN_SESSION};
+use crate::model::{
+    ExecutorInfoPtr, NodeInfoPtr, SnapShotPtr, ALL_EXECUTOR, ALL_NODE, BOUND_EXECUTOR,
+    IDLE_EXECUTOR, OPEN_SESSION,
+};
 use crate::scheduler::actions::{Action, ActionPtr};
 use crate::scheduler::ctx::Context;
 use crate::scheduler::plugins::ssn_order_fn;
 
+use common::apis::{ExecutorID, ExecutorState, ResourceRequirement, SessionID, TaskState};
 use common::FlameError;
 
 pub struct ShuffleAction {}
@@ -33,6 +39,10 @@ impl ShuffleAction {
 
 #[async_trait::async_trait]
 impl Action for ShuffleAction {
+    fn name(&self) -> &'static str {
+        "shuffle"
+    }
+
     async fn execute(&self, ctx: &mut Context) -> Result<(), FlameError> {
         trace_fn!("ShuffleAction::execute");
         let ss = ctx.snapshot.clone();
@@ -47,55 +57,174 @@ impl Action for ShuffleAction {
 
         let mut bound_execs = ss.find_executors(BOUND_EXECUTOR)?;
 
-        // Unbind overused sessions for underused sessions.
+        // Unbind the executors on the nodes whose allocatable resources shrank,
+        // e.g. the container limits were lowered; the executors finish their
+        // current task before unbinding.
+        let all_execs = ss.find_executors(ALL_EXECUTOR)?;
+        for node in ss.find_nodes(ALL_NODE)?.values() {
+            for exec in overcommitted_executors(node, &all_execs, &ss.unit) {
+                let Some(ssn_id) = exec.ssn_id.clone() else {
+                    continue;
+                };
+                tracing::info!(
+                    "Node <{}> is overcommitted, unbind executor <{}> from session <{}>.",
+                    node.name,
+                    exec.id,
+                    ssn_id
+                );
+                // The session may be deleted since the snapshot; the other
+                // executors are still unbound.
+                let ssn = match ss.get_session(&ssn_id) {
+                    Ok(ssn) => ssn,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Skip unbinding executor <{}> of the overcommitted node <{}>: {e}",
+                            exec.id,
+                            node.name
+                        );
+                        continue;
+                    }
+                };
+                ctx.unbind_session(&exec, &ssn).await?;
+                bound_execs.remove(&exec.id);
+            }
+        }
+
+        // Unbind the executors which are idle for longer than the idle timeout,
+        // e.g. of the abandoned interactive sessions, so the node resources are
+        // released.
+        if let Some(timeout) = ss.idle_timeout {
+            for exec in idle_executors(&ss, &bound_execs, timeout, Utc::now())? {
+                let Some(ssn_id) = exec.ssn_id.clone() else {
+                    continue;
+                };
+                tracing::info!(
+                    "Executor <{}> is idle for longer than {}s, unbind it from session <{}>.",
+                    exec.id,
+                    timeout.num_seconds(),
+                    ssn_id
+                );
+                let ssn = ss.get_session(&ssn_id)?;
+                ctx.unbind_session(&exec, &ssn).await?;
+                bound_execs.remove(&exec.id);
+            }
+        }
+
+        // Recycle the executors bound with a previous definition of their
+        // application, e.g. its command or image was updated; they finish their
+        // current task before unbinding, and are released as idle executors.
+        let stale_execs = bound_execs
+            .values()
+            .filter(|exec| ss.stale_executors.contains(&exec.id))
+            .cloned()
+            .collect::<Vec<_>>();
+        for exec in stale_execs {
+            let Some(ssn_id) = exec.ssn_id.clone() else {
+                continue;
+            };
+            tracing::info!(
+                "Executor <{}> was bound with a previous definition of its application, unbind it from session <{}>.",
+                exec.id,
+                ssn_id
+            );
+            let ssn = ss.get_session(&ssn_id)?;
+            ctx.unbind_session(&exec, &ssn).await?;
+            bound_execs.remove(&exec.id);
+        }
+
+        // Unbind overused sessions for underused sessions, within the safety
+        // limits of the shuffle configuration to avoid churn.
+        let now = Utc::now();
+        let mut shuffled = HashSet::new();
+        let mut moves = 0;
         loop {
             if underused.is_empty() {
                 break;
             }
 
+            if let Some(max) = ss.shuffle.max_executors {
+                if moves >= max {
+                    tracing::debug!(
+                        "Shuffled {moves} executors in this cycle, skip the other {} underused sessions.",
+                        underused.len()
+                    );
+                    break;
+                }
+            }
+
             let ssn = underused
                 .pop()
                 .expect("failed to pop underused session: loop guard ensures non-empty");
             if !ctx.is_underused(&ssn)? {
                 continue;
             }
+            if in_cooldown(&ss, &shuffled, &ssn.id, now) {
+                tracing::debug!("Session <{}> is in shuffle cooldown, skip it.", ssn.id);
+                continue;
+            }
+
+            // Reclaim the elastic allocations first, i.e. the executors backfilled
+            // beyond the deserved share of their sessions.
+            let mut candidates = vec![];
+            for e in bound_execs.values() {
+                if let Some(ssn_id) = e.ssn_id.clone() {
+                    let target_ssn = ss.get_session(&ssn_id)?;
+                    candidates.push((ctx.elastic_allocated(&target_ssn)?, e.clone(), target_ssn));
+                }
+            }
+            candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
 
             let mut exec = None;
-            for (_, e) in bound_execs.iter_mut() {
+            for (elastic, e, target_ssn) in candidates {
                 tracing::debug!(
                     "Try to unbound Executor <{}> for session <{}>",
                     e.id,
                     ssn.id.clone()
                 );
 
-                let target_ssn = match e.ssn_id.clone() {
-                    Some(ssn_id) => Some(ss.get_session(&ssn_id)?),
-                    None => None,
-                };
-
-                if let Some(target_ssn) = target_ssn {
-                    if !ctx.is_preemptible(&target_ssn)? {
-                        continue;
-                    }
+                if !ctx.is_preemptible_for(&target_ssn, &ssn)? {
+                    continue;
+                }
+                if in_cooldown(&ss, &shuffled, &target_ssn.id, now) {
+                    continue;
+                }
 
-                    // Unbind the overused session, so the executor will
-                    // become idle and be allocated to the underused session.
-                    ctx.unbind_session(e, &target_ssn).await?;
-                    exec = Some(e.clone());
+                let reason = format!(
+                    "session <{}> is underused, and session <{}> is preemptible with {} elastic slots",
+                    ssn.id, target_ssn.id, elastic
+                );
+                ctx.record_shuffle(&e, &target_ssn, &ssn, &reason)?;
+                if ss.shuffle.cooldown > 0 {
+                    shuffled.insert(target_ssn.id.clone());
+                    shuffled.insert(ssn.id.clone());
+                }
 
-                    break;
+                // Preempt the executor of the overused session, so it will
+                // become idle and be allocated to the underused session.
+                if !ss.shuffle.dry_run {
+                    ctx.preempt_session(&e, &target_ssn).await?;
                 }
+                exec = Some(e);
+
+                break;
             }
 
             if let Some(exec) = exec {
+                moves += 1;
+                bound_execs.remove(&exec.id);
+
+                // The executor is still bound in the dry-run mode, so the session
+                // stays underused; it's not shuffled again in this cycle.
+                if ss.shuffle.dry_run {
+                    continue;
+                }
+
                 tracing::debug!(
                     "Executor <{}> was pipelined to session <{}>, remove it from bound list.",
                     exec.id,
                     ssn.id.clone()
                 );
 
-                bound_execs.remove(&exec.id);
-
                 // Pipeline the executor to the underused session to avoid over allocation.
                 ctx.pipeline_session(&exec, &ssn).await?;
                 underused.push(ssn.clone());
@@ -111,3 +240,242 @@ impl Action for ShuffleAction {
         Ok(())
     }
 }
+
+/// Whether the session gave or received an executor by a shuffle within the
+/// cooldown, in this cycle or the previous ones.
+fn in_cooldown(
+    ss: &SnapShotPtr,
+    shuffled: &HashSet<SessionID>,
+    ssn_id: &SessionID,
+    now: DateTime<Utc>,
+) -> bool {
+    if ss.shuffle.cooldown == 0 {
+        return false;
+    }
+
+    shuffled.contains(ssn_id)
+        || ss
+            .last_shuffles
+            .get(ssn_id)
+            .is_some_and(|t| now - *t < Duration::seconds(ss.shuffle.cooldown as i64))
+}
+
+/// Returns the bound executors without tasks which are idle for longer than the
+/// timeout, or than the `delay_release` of their application if it is longer.
+/// The sessions with pending tasks are skipped, and the `min_instances` of each
+/// session are kept as its warm pool; the longest idle executors go first.
+fn idle_executors(
+    ss: &SnapShotPtr,
+    execs: &HashMap<ExecutorID, ExecutorInfoPtr>,
+    timeout: Duration,
+    now: DateTime<Utc>,
+) -> Result<Vec<ExecutorInfoPtr>, FlameError> {
+    let mut ssn_execs: HashMap<SessionID, Vec<ExecutorInfoPtr>> = HashMap::new();
+    for exec in execs.values() {
+        if let (Some(ssn_id), Some(_)) = (exec.ssn_id.clone(), exec.idle_since) {
+            ssn_execs.entry(ssn_id).or_default().push(exec.clone());
+        }
+    }
+
+    let mut res = vec![];
+    for (ssn_id, mut execs) in ssn_execs {
+        let ssn = ss.get_session(&ssn_id)?;
+        if ssn
+            .tasks_status
+            .get(&TaskState::Pending)
+            .copied()
+            .unwrap_or(0)
+            > 0
+        {
+            continue;
+        }
+
+        let delay_release = ss
+            .get_application(&ssn.application)?
+            .map(|app| app.delay_release)
+            .unwrap_or_default();
+        let timeout = timeout.max(delay_release);
+
+        let mut instances = ss.session_instances(&ssn_id)?;
+        execs.sort_by_key(|e| e.idle_since);
+        for exec in execs {
+            if instances <= ssn.min_instances {
+                break;
+            }
+            if exec.idle_since.is_some_and(|t| now - t > timeout) {
+                instances -= 1;
+                res.push(exec);
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+/// Returns the bound executors to unbind so that the executors on the node fit
+/// into its allocatable slots again. Idle executors are not counted, as they are
+/// released anyway; the newest executors are unbound first.
+fn overcommitted_executors(
+    node: &NodeInfoPtr,
+    execs: &HashMap<ExecutorID, ExecutorInfoPtr>,
+    unit: &ResourceRequirement,
+) -> Vec<ExecutorInfoPtr> {
+    let allocatable = node.allocatable.to_slots(unit);
+
+    let mut allocated = 0;
+    let mut bound = vec![];
+    for exec in execs.values().filter(|e| e.node == node.name) {
+        match exec.state {
+            ExecutorState::Void | ExecutorState::Binding => allocated += exec.slots,
+            ExecutorState::Bound => {
+                allocated += exec.slots;
+                bound.push(exec.clone());
+            }
+            _ => {}
+        }
+    }
+
+    bound.sort_by_key(|e| std::cmp::Reverse(e.creation_time));
+
+    let mut res = vec![];
+    for exec in bound {
+        if allocated <= allocatable {
+            break;
+        }
+        allocated -= exec.slots;
+        res.push(exec);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::apis::{NodeState, Shim};
+
+    use crate::model::{AppInfo, ExecutorInfo, NodeInfo, SessionInfo, SnapShot};
+
+    fn new_executor(id: &str, node: &str, state: ExecutorState, age: i64) -> ExecutorInfoPtr {
+        Arc::new(ExecutorInfo {
+            id: id.to_string(),
+            node: node.to_string(),
+            resreq: ResourceRequirement::from("cpu=1,mem=1g"),
+            slots: 1,
+            shim: Shim::Host,
+            task_id: None,
+            ssn_id: Some("ssn-1".into()),
+            batch_index: None,
+            idle_since: None,
+            creation_time: Utc::now() - Duration::seconds(age),
+            state,
+        })
+    }
+
+    #[test]
+    fn test_overcommitted_executors() {
+        let unit = ResourceRequirement::from("cpu=1,mem=1g");
+        let node = Arc::new(NodeInfo {
+            name: "node-1".to_string(),
+            allocatable: ResourceRequirement::from("cpu=2,mem=2g"),
+            state: NodeState::Ready,
+            ..Default::default()
+        });
+
+        let mut execs = HashMap::new();
+        for exec in [
+            new_executor("e1", "node-1", ExecutorState::Bound, 40),
+            new_executor("e2", "node-1", ExecutorState::Bound, 30),
+            new_executor("e3", "node-1", ExecutorState::Bound, 20),
+            new_executor("e4", "node-1", ExecutorState::Binding, 10),
+            new_executor("e5", "node-1", ExecutorState::Idle, 5),
+            new_executor("e6", "node-2", ExecutorState::Bound, 0),
+        ] {
+            execs.insert(exec.id.clone(), exec);
+        }
+
+        let res = overcommitted_executors(&node, &execs, &unit);
+        let ids: Vec<_> = res.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["e3", "e2"]);
+
+        execs.remove("e4");
+        execs.remove("e3");
+        assert!(overcommitted_executors(&node, &execs, &unit).is_empty());
+    }
+
+    #[test]
+    fn test_in_cooldown() {
+        let now = Utc::now();
+        let mut ss = SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g"));
+        ss.last_shuffles
+            .insert("ssn-1".into(), now - Duration::seconds(10));
+        ss.last_shuffles
+            .insert("ssn-2".into(), now - Duration::seconds(90));
+        let mut shuffled = HashSet::from([SessionID::from("ssn-3")]);
+
+        // The cooldown is disabled by default.
+        let ss_ptr = Arc::new(ss.clone());
+        assert!(!in_cooldown(&ss_ptr, &shuffled, &"ssn-1".into(), now));
+
+        ss.shuffle.cooldown = 60;
+        let ss = Arc::new(ss);
+        assert!(in_cooldown(&ss, &shuffled, &"ssn-1".into(), now));
+        assert!(!in_cooldown(&ss, &shuffled, &"ssn-2".into(), now));
+        assert!(in_cooldown(&ss, &shuffled, &"ssn-3".into(), now));
+
+        shuffled.clear();
+        assert!(!in_cooldown(&ss, &shuffled, &"ssn-3".into(), now));
+    }
+
+    #[test]
+    fn test_idle_executors() {
+        let now = Utc::now();
+        let ss = Arc::new(SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g")));
+        ss.add_application(Arc::new(AppInfo {
+            name: "app".to_string(),
+            delay_release: Duration::seconds(60),
+            ..Default::default()
+        }))
+        .unwrap();
+        ss.add_session(Arc::new(SessionInfo {
+            id: "ssn-1".into(),
+            application: "app".to_string(),
+            min_instances: 3,
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let idle_executor = |id: &str, idle: Option<i64>| {
+            let exec = new_executor(id, "node-1", ExecutorState::Bound, 100);
+            Arc::new(ExecutorInfo {
+                idle_since: idle.map(|secs| now - Duration::seconds(secs)),
+                ..(*exec).clone()
+            })
+        };
+
+        let mut execs = HashMap::new();
+        for exec in [
+            idle_executor("e1", Some(90)),
+            idle_executor("e2", Some(120)),
+            idle_executor("e3", Some(80)),
+            idle_executor("e4", Some(30)),
+            idle_executor("e5", None),
+        ] {
+            ss.add_executor(exec.clone()).unwrap();
+            execs.insert(exec.id.clone(), exec);
+        }
+
+        // The delay release of the application is longer than the timeout, and
+        // three instances are kept as the warm pool.
+        let timeout = Duration::seconds(10);
+        let res = idle_executors(&ss, &execs, timeout, now).unwrap();
+        let ids: Vec<_> = res.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["e2", "e1"]);
+
+        let timeout = Duration::seconds(100);
+        let res = idle_executors(&ss, &execs, timeout, now).unwrap();
+        let ids: Vec<_> = res.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["e2"]);
+    }
+}
@@ -17,10 +17,14 @@ use std::sync::Arc;
 use stdng::collections;
 
 use crate::controller::ControllerPtr;
-use crate::model::{ExecutorInfo, ExecutorInfoPtr, NodeInfoPtr, SessionInfoPtr, SnapShotPtr};
-use crate::scheduler::actions::{ActionPtr, AllocateAction, DispatchAction, ShuffleAction};
+use crate::model::{
+    ExecutorInfo, ExecutorInfoPtr, NodeInfoPtr, SessionInfoPtr, SnapShotPtr, READY_NODE,
+};
+use crate::scheduler::actions::{
+    ActionPtr, AllocateAction, BackfillAction, DispatchAction, ShuffleAction,
+};
 use crate::scheduler::plugins::{PluginManager, PluginManagerPtr};
-use common::apis::ExecutorState;
+use common::apis::{ExecutorState, TaskState};
 use common::FlameError;
 
 pub struct Context {
@@ -31,9 +35,9 @@ pub struct Context {
 }
 
 impl Context {
-    pub fn new(controller: ControllerPtr) -> Result<Self, FlameError> {
+    pub fn new(controller: ControllerPtr, plugins: &[String]) -> Result<Self, FlameError> {
         let snapshot = controller.snapshot()?;
-        let plugins = PluginManager::setup(&snapshot.clone())?;
+        let plugins = PluginManager::setup(&snapshot.clone(), plugins)?;
 
         Ok(Context {
             snapshot,
@@ -42,6 +46,7 @@ impl Context {
             actions: vec![
                 DispatchAction::new_ptr(),
                 AllocateAction::new_ptr(),
+                BackfillAction::new_ptr(),
                 ShuffleAction::new_ptr(),
             ],
         })
@@ -55,6 +60,24 @@ impl Context {
         self.plugins.is_preemptible(ssn)
     }
 
+    pub fn is_preemptible_for(
+        &self,
+        ssn: &SessionInfoPtr,
+        target: &SessionInfoPtr,
+    ) -> Result<bool, FlameError> {
+        self.plugins.is_preemptible_for(ssn, target)
+    }
+
+    /// Whether the session can grow and shrink by single executors, i.e. it's not
+    /// gang scheduled and its max_instances is above its min_instances.
+    pub fn is_elastic(&self, ssn: &SessionInfoPtr) -> bool {
+        ssn.batch_size <= 1 && !matches!(ssn.max_instances, Some(max) if max <= ssn.min_instances)
+    }
+
+    pub fn elastic_allocated(&self, ssn: &SessionInfoPtr) -> Result<f64, FlameError> {
+        self.plugins.elastic_allocated(ssn)
+    }
+
     pub fn is_allocatable(
         &self,
         node: &NodeInfoPtr,
@@ -71,6 +94,108 @@ impl Context {
         self.plugins.is_available(exec, ssn)
     }
 
+    /// Check the instance limits of the session and its application, where
+    /// `pipelined` is the number of instances not counted in the snapshot yet;
+    /// returns the reason if any limit is reached.
+    pub fn check_instances(
+        &self,
+        ssn: &SessionInfoPtr,
+        pipelined: u32,
+    ) -> Result<Option<String>, FlameError> {
+        if let Some(max_instances) = ssn.max_instances {
+            let instances = self.snapshot.session_instances(&ssn.id)? + pipelined;
+            if instances >= max_instances {
+                return Ok(Some(format!(
+                    "session <{}> has reached max_instances limit: {} >= {}",
+                    ssn.id, instances, max_instances
+                )));
+            }
+        }
+
+        if let Some(app) = self.snapshot.get_application(&ssn.application)? {
+            let instances = self.snapshot.app_instances(&app.name)? + pipelined;
+            if instances >= app.max_instances {
+                return Ok(Some(format!(
+                    "application <{}> has reached max_instances limit: {} >= {}",
+                    app.name, instances, app.max_instances
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Whether the session can not get more instances because of the instance
+    /// limits; the session is throttled, and it's recorded as session events,
+    /// if it has more pending tasks than its instances.
+    pub fn is_throttled(&self, ssn: &SessionInfoPtr) -> Result<bool, FlameError> {
+        let Some(reason) = self.check_instances(ssn, 0)? else {
+            return Ok(false);
+        };
+
+        let pending = ssn
+            .tasks_status
+            .get(&TaskState::Pending)
+            .copied()
+            .unwrap_or(0)
+            .max(0) as u32;
+        if pending > self.snapshot.session_instances(&ssn.id)? {
+            tracing::debug!("Session <{}> is throttled: {}", ssn.id, reason);
+            self.controller.throttle_session(&ssn.id, &reason)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Whether no ready node can run the application of the session, e.g. its
+    /// instances failed to start on all of them; the session is marked
+    /// unschedulable, and it's recorded as session events, until a node can run it.
+    pub fn is_unschedulable(&self, ssn: &SessionInfoPtr) -> Result<bool, FlameError> {
+        let nodes = self.snapshot.find_nodes(READY_NODE)?;
+        let mut failures: Vec<(&String, &String)> = vec![];
+        for node in nodes.values() {
+            match self
+                .snapshot
+                .unavailable_reason(&node.name, &ssn.application)
+            {
+                Some(reason) => failures.push((&node.name, reason)),
+                None => {
+                    self.controller.mark_schedulable(&ssn.id)?;
+                    return Ok(false);
+                }
+            }
+        }
+
+        let reason = match failures.iter().min() {
+            Some((node, reason)) => format!(
+                "Application <{}> failed to start on all {} ready nodes, e.g. <{node}>: {reason}",
+                ssn.application,
+                failures.len()
+            ),
+            None => "No ready node in the cluster".to_string(),
+        };
+        tracing::debug!("Session <{}> is unschedulable: {}", ssn.id, reason);
+        self.controller.mark_unschedulable(&ssn.id, &reason)?;
+
+        Ok(true)
+    }
+
+    /// Resume the session, which got a new instance.
+    pub fn resume_session(&self, ssn: &SessionInfoPtr) -> Result<(), FlameError> {
+        self.controller.resume_session(&ssn.id)
+    }
+
+    pub fn record_shuffle(
+        &self,
+        exec: &ExecutorInfoPtr,
+        from: &SessionInfoPtr,
+        to: &SessionInfoPtr,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        self.controller
+            .record_shuffle(&exec.id, &from.id, &to.id, reason)
+    }
+
     pub async fn bind_session(
         &self,
         exec: &ExecutorInfoPtr,
@@ -81,8 +206,15 @@ impl Context {
             .bind_session(exec.id.clone(), ssn.id.clone(), batch_index)
             .await?;
         self.plugins.on_session_bind(ssn.clone())?;
+
+        // The executor is an instance of the session since binding.
+        let exec = Arc::new(ExecutorInfo {
+            ssn_id: Some(ssn.id.clone()),
+            ..(**exec).clone()
+        });
         self.snapshot
-            .update_executor_state(exec.clone(), ExecutorState::Binding)?;
+            .update_executor_state(exec, ExecutorState::Binding)?;
+        self.resume_session(ssn)?;
 
         Ok(())
     }
@@ -113,6 +245,21 @@ impl Context {
         Ok(())
     }
 
+    /// Unbinds the executor from the session for the preemption by another
+    /// session, which interrupts its running task.
+    pub async fn preempt_session(
+        &self,
+        exec: &ExecutorInfoPtr,
+        ssn: &SessionInfoPtr,
+    ) -> Result<(), FlameError> {
+        self.controller.preempt_executor(exec.id.clone()).await?;
+        self.plugins.on_session_unbind(ssn.clone())?;
+        self.snapshot
+            .update_executor_state(exec.clone(), ExecutorState::Unbinding)?;
+
+        Ok(())
+    }
+
     pub async fn release_executor(&self, exec: &ExecutorInfoPtr) -> Result<(), FlameError> {
         self.controller.release_executor(exec.id.clone()).await?;
 
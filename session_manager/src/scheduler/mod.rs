@@ -12,8 +12,13 @@ limitations under the License.
 */
 
 use async_trait::async_trait;
-use std::sync::Arc;
-use std::{thread, time};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use stdng::lock_ptr;
+use tokio_util::sync::CancellationToken;
 
 use crate::controller::ControllerPtr;
 use crate::scheduler::ctx::Context;
@@ -25,14 +30,104 @@ use common::FlameError;
 mod actions;
 mod ctx;
 mod plugins;
+#[cfg(feature = "simulation")]
+pub mod simulation;
+mod state;
 pub mod statement;
 
-pub fn new(controller: ControllerPtr) -> Arc<dyn FlameThread> {
-    Arc::new(ScheduleRunner { controller })
+/// Check the configuration of the scheduler, e.g. its plugins are registered.
+pub fn validate(ctx: &FlameClusterContext) -> Result<(), FlameError> {
+    plugins::validate(&ctx.cluster.scheduler.plugins)
+}
+
+/// Create the scheduler, which stops when the shutdown token is cancelled.
+pub fn new(controller: ControllerPtr, shutdown: CancellationToken) -> Arc<dyn FlameThread> {
+    Arc::new(ScheduleRunner {
+        controller,
+        shutdown,
+        metrics: ScheduleMetrics::default(),
+    })
+}
+
+/// The counters of the scheduling cycles and their failures.
+#[derive(Default)]
+struct ScheduleMetrics {
+    cycles: AtomicU64,
+    /// The failures of each action, and of building the context as `context`.
+    failures: Mutex<HashMap<&'static str, u64>>,
+}
+
+impl ScheduleMetrics {
+    /// Record a failure of the action, returns its failures so far.
+    fn record_failure(&self, action: &'static str) -> Result<u64, FlameError> {
+        let mut failures = lock_ptr!(self.failures)?;
+        let count = failures.entry(action).or_default();
+        *count += 1;
+
+        Ok(*count)
+    }
+
+    fn failures(&self) -> Result<u64, FlameError> {
+        let failures = lock_ptr!(self.failures)?;
+        Ok(failures.values().sum())
+    }
 }
 
 struct ScheduleRunner {
     controller: ControllerPtr,
+    shutdown: CancellationToken,
+    metrics: ScheduleMetrics,
+}
+
+impl ScheduleRunner {
+    /// Run one scheduling cycle; the failed actions are logged, and the others
+    /// still run in the cycle.
+    async fn schedule(&self, ctx: &mut Context) -> Result<(), FlameError> {
+        self.metrics.cycles.fetch_add(1, Ordering::Relaxed);
+
+        for action in ctx.actions.clone() {
+            if let Err(e) = action.execute(ctx).await {
+                let failures = self.metrics.record_failure(action.name())?;
+                tracing::error!(
+                    "Failed to run action <{}> ({} failures): {e}",
+                    action.name(),
+                    failures
+                );
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Persist the snapshot of the cycle into the history of the cluster every
+    /// `history.rounds` cycles, if the history is enabled.
+    fn record_history(&self, flame_ctx: &FlameClusterContext, ctx: &Context) {
+        let Some(history) = &flame_ctx.cluster.history else {
+            return;
+        };
+        if !self
+            .metrics
+            .cycles
+            .load(Ordering::Relaxed)
+            .is_multiple_of(history.rounds)
+        {
+            return;
+        }
+
+        if let Err(e) = self.controller.record_history(&ctx.snapshot) {
+            tracing::warn!("Failed to record the history of the cluster: {e}");
+        }
+    }
+
+    /// Record the state of the plugins at the end of the cycle, e.g. for
+    /// `flmctl debug scheduler`.
+    fn record_state(&self, ctx: &Context) {
+        let recorded = state::scheduler_state(&ctx.snapshot, &ctx.plugins, Utc::now())
+            .and_then(|state| self.controller.record_scheduler_state(state));
+        if let Err(e) = recorded {
+            tracing::warn!("Failed to record the state of the scheduler: {e}");
+        }
+    }
 }
 
 #[async_trait]
@@ -41,18 +136,40 @@ impl FlameThread for ScheduleRunner {
         let schedule_interval = flame_ctx.cluster.schedule_interval;
         tracing::info!("Scheduler started with interval: {}ms", schedule_interval);
 
-        loop {
-            let mut ctx = Context::new(self.controller.clone())?;
-
-            for action in ctx.actions.clone() {
-                if let Err(e) = action.execute(&mut ctx).await {
-                    tracing::error!("Failed to run scheduling: {e}");
-                    break;
-                };
+        while !self.shutdown.is_cancelled() {
+            match Context::new(
+                self.controller.clone(),
+                &flame_ctx.cluster.scheduler.plugins,
+            ) {
+                Ok(mut ctx) => {
+                    self.schedule(&mut ctx).await?;
+                    self.record_state(&ctx);
+                    self.record_history(&flame_ctx, &ctx);
+                }
+                Err(e) => {
+                    let failures = self.metrics.record_failure("context")?;
+                    tracing::error!(
+                        "Failed to build scheduling context ({failures} failures): {e}"
+                    );
+                }
             }
 
-            tokio::time::sleep(tokio::time::Duration::from_millis(schedule_interval)).await;
+            tokio::select! {
+                _ = self.shutdown.cancelled() => {}
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(schedule_interval)) => {}
+            }
         }
+
+        tracing::info!(
+            "Scheduler stopped after {} cycles with {} failures; {}; {}; {}.",
+            self.metrics.cycles.load(Ordering::Relaxed),
+            self.metrics.failures()?,
+            self.controller.latency_report()?,
+            self.controller.usage_report()?,
+            self.controller.node_report()?
+        );
+
+        Ok(())
     }
 }
 
@@ -62,21 +179,28 @@ mod tests {
 
     use crate::controller;
     use crate::model::{ALL_NODE, OPEN_SESSION};
-    use crate::scheduler::actions::{AllocateAction, DispatchAction};
+    use crate::scheduler::actions::{Action, AllocateAction, DispatchAction};
     use crate::scheduler::ctx::Context;
     use crate::scheduler::plugins::PluginManager;
-    use crate::scheduler::ControllerPtr;
+    use crate::scheduler::{ControllerPtr, ScheduleMetrics, ScheduleRunner};
     use crate::storage;
+    use crate::FlameThread;
     use chrono::Duration;
     use chrono::Utc;
     use common::apis::{
-        Application, ApplicationAttributes, Node, NodeInfo, NodeState, ResourceRequirement, Shim,
+        Application, ApplicationAttributes, Node, NodeInfo, NodeState, ResourceRequirement,
+        SessionID, Shim, TaskAttributes, EXECUTOR_BINDING_EVENT, EXECUTOR_BOUND_EVENT,
+        EXECUTOR_UNBOUND_EVENT, SESSION_SCHEDULABLE_EVENT, SESSION_THROTTLED_EVENT,
+        SESSION_UNSCHEDULABLE_EVENT,
     };
     use common::ctx::FlameCluster;
     use common::ctx::FlameClusterContext;
+    use common::ctx::FlameScheduler;
     use common::FlameError;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
     use uuid::Uuid;
     // use tracing_test::traced_test;
 
@@ -94,6 +218,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: Vec::new(),
+            task_arguments: Vec::new(),
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         }
     }
 
@@ -111,6 +247,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
             state: NodeState::Ready,
         }
@@ -129,12 +266,13 @@ mod tests {
                 .add_directive("sqlx=error".parse()?)
                 .add_directive("tower=error".parse()?);
 
-            tracing_subscriber::fmt()
+            // The subscriber is global, so only the first test env installs it.
+            let _ = tracing_subscriber::fmt()
                 .with_env_filter(filter)
                 .with_test_writer()
                 .with_target(true)
                 .with_ansi(false)
-                .init();
+                .try_init();
 
             let url = common::temp_db_path("flame_test_env");
             let config = FlameClusterContext {
@@ -146,7 +284,7 @@ mod tests {
             };
 
             let storage = tokio_test::block_on(storage::new_ptr(&config))?;
-            let controller = controller::new_ptr(storage.clone());
+            let controller = controller::new_ptr(storage.clone(), None, None);
 
             Ok(Self { url, controller })
         }
@@ -177,7 +315,7 @@ mod tests {
                 .storage()
                 .register_node(&new_test_node("node_1".to_string())),
         )?;
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 =
             tokio_test::block_on(controller.create_session(common::apis::SessionAttributes {
                 id: ssn_1_id.clone(),
@@ -187,15 +325,26 @@ mod tests {
                 min_instances: 0,
                 max_instances: None,
                 batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: common::apis::ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
             }))?;
 
         for _ in 0..task_num {
-            tokio_test::block_on(controller.create_task(ssn_1.id.clone(), None))?;
+            tokio_test::block_on(
+                controller.create_task(ssn_1.id.clone(), TaskAttributes::default()),
+            )?;
         }
 
         for i in 0..10 {
             let snapshot = controller.snapshot()?;
-            let plugins = PluginManager::setup(&snapshot.clone())?;
+            let plugins =
+                PluginManager::setup(&snapshot.clone(), &FlameScheduler::default().plugins)?;
 
             let mut ctx = Context {
                 snapshot: snapshot.clone(),
@@ -224,4 +373,387 @@ mod tests {
 
         Ok(())
     }
+
+    /// Test the max_instances_per_node limit is shared by the sessions of the application.
+    #[test]
+    fn test_allocate_max_instances_per_node() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let controller = env.controller.clone();
+
+        tokio_test::block_on(controller.register_application(
+            "flmtest".to_string(),
+            ApplicationAttributes {
+                max_instances_per_node: Some(1),
+                ..new_test_application()
+            },
+        ))?;
+        tokio_test::block_on(
+            controller
+                .storage()
+                .register_node(&new_test_node("node_1".to_string())),
+        )?;
+        // The sessions have different slots, so the executor of one session is
+        // not taken as pipelined for the other.
+        for slots in 1..=2 {
+            let ssn =
+                tokio_test::block_on(controller.create_session(common::apis::SessionAttributes {
+                    id: format!("ssn-{slots}-{}", Utc::now().timestamp()).into(),
+                    application: "flmtest".to_string(),
+                    slots,
+                    common_data: None,
+                    min_instances: 0,
+                    max_instances: None,
+                    batch_size: 1,
+                    scratch_size: None,
+                    max_task_attempts: None,
+                    task_timeout: None,
+                    task_priority: None,
+                    priority: 0,
+                    result_policy: common::apis::ResultPolicy::Inline,
+                    ttl_after_completion: None,
+                    trace_id: None,
+                }))?;
+            tokio_test::block_on(
+                controller.create_task(ssn.id.clone(), TaskAttributes::default()),
+            )?;
+        }
+
+        for _ in 0..3 {
+            let snapshot = controller.snapshot()?;
+            let plugins =
+                PluginManager::setup(&snapshot.clone(), &FlameScheduler::default().plugins)?;
+
+            let mut ctx = Context {
+                snapshot: snapshot.clone(),
+                controller: controller.clone(),
+                plugins,
+                actions: vec![],
+            };
+
+            let alloc = AllocateAction::new_ptr();
+            tokio_test::block_on(alloc.execute(&mut ctx))?;
+
+            let exec_list = controller.list_executor()?;
+            assert_eq!(exec_list.len(), 1);
+            assert_eq!(exec_list[0].node, "node_1");
+        }
+
+        Ok(())
+    }
+
+    /// Test the sessions are throttled by the max_instances limit of the application.
+    #[test]
+    fn test_allocate_throttled_by_app_max_instances() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let controller = env.controller.clone();
+
+        tokio_test::block_on(controller.register_application(
+            "flmtest".to_string(),
+            ApplicationAttributes {
+                max_instances: 1,
+                ..new_test_application()
+            },
+        ))?;
+        tokio_test::block_on(
+            controller
+                .storage()
+                .register_node(&new_test_node("node_1".to_string())),
+        )?;
+        let mut ssn_ids = vec![];
+        for slots in 1..=2 {
+            let ssn =
+                tokio_test::block_on(controller.create_session(common::apis::SessionAttributes {
+                    id: format!("ssn-{slots}-{}", Utc::now().timestamp()).into(),
+                    application: "flmtest".to_string(),
+                    slots,
+                    common_data: None,
+                    min_instances: 0,
+                    max_instances: None,
+                    batch_size: 1,
+                    scratch_size: None,
+                    max_task_attempts: None,
+                    task_timeout: None,
+                    task_priority: None,
+                    priority: 0,
+                    result_policy: common::apis::ResultPolicy::Inline,
+                    ttl_after_completion: None,
+                    trace_id: None,
+                }))?;
+            tokio_test::block_on(
+                controller.create_task(ssn.id.clone(), TaskAttributes::default()),
+            )?;
+            ssn_ids.push(ssn.id);
+        }
+
+        let snapshot = controller.snapshot()?;
+        let plugins = PluginManager::setup(&snapshot.clone(), &FlameScheduler::default().plugins)?;
+        let mut ctx = Context {
+            snapshot: snapshot.clone(),
+            controller: controller.clone(),
+            plugins,
+            actions: vec![],
+        };
+
+        let alloc = AllocateAction::new_ptr();
+        tokio_test::block_on(alloc.execute(&mut ctx))?;
+
+        assert_eq!(controller.list_executor()?.len(), 1);
+        assert_eq!(snapshot.app_instances("flmtest")?, 1);
+
+        // Only the session without instance is throttled, and only once.
+        let mut throttled = vec![];
+        for id in &ssn_ids {
+            let ssn = controller.get_session(id.clone())?;
+            let events: Vec<_> = ssn
+                .events
+                .iter()
+                .filter(|e| e.code == SESSION_THROTTLED_EVENT)
+                .collect();
+            assert!(events.len() <= 1);
+            if !events.is_empty() {
+                throttled.push(id.clone());
+            }
+        }
+        assert_eq!(throttled.len(), 1);
+        assert_eq!(snapshot.session_instances(&throttled[0])?, 0);
+
+        Ok(())
+    }
+
+    /// Test the session is unschedulable while its application failed to start
+    /// on all ready nodes, and schedulable again once a node can run it.
+    #[test]
+    fn test_allocate_unschedulable_without_runnable_node() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let controller = env.controller.clone();
+
+        tokio_test::block_on(
+            controller.register_application("flmtest".to_string(), new_test_application()),
+        )?;
+        let node = new_test_node("node_1".to_string());
+        tokio_test::block_on(controller.storage().register_node(&node))?;
+        let ssn =
+            tokio_test::block_on(controller.create_session(common::apis::SessionAttributes {
+                id: format!("ssn-{}", Utc::now().timestamp()).into(),
+                application: "flmtest".to_string(),
+                slots: 1,
+                common_data: None,
+                min_instances: 0,
+                max_instances: None,
+                batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: common::apis::ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
+            }))?;
+        tokio_test::block_on(controller.create_task(ssn.id.clone(), TaskAttributes::default()))?;
+
+        controller
+            .storage()
+            .mark_app_unavailable("node_1", "flmtest", "no such file")?;
+
+        let allocate = || -> Result<(), FlameError> {
+            let snapshot = controller.snapshot()?;
+            let plugins =
+                PluginManager::setup(&snapshot.clone(), &FlameScheduler::default().plugins)?;
+            let mut ctx = Context {
+                snapshot,
+                controller: controller.clone(),
+                plugins,
+                actions: vec![],
+            };
+            tokio_test::block_on(AllocateAction::new_ptr().execute(&mut ctx))
+        };
+        let count_events = |code: i32| -> Result<usize, FlameError> {
+            let ssn = controller.get_session(ssn.id.clone())?;
+            Ok(ssn.events.iter().filter(|e| e.code == code).count())
+        };
+
+        // The condition is recorded once, and no executor is created for it.
+        allocate()?;
+        allocate()?;
+        assert!(controller.list_executor()?.is_empty());
+        let reason = controller.get_session(ssn.id.clone())?.status.unschedulable;
+        assert!(reason.is_some_and(|r| r.contains("no such file")));
+        assert_eq!(count_events(SESSION_UNSCHEDULABLE_EVENT)?, 1);
+
+        // The node is retried after it registers again.
+        tokio_test::block_on(controller.storage().register_node(&node))?;
+        allocate()?;
+        assert_eq!(controller.list_executor()?.len(), 1);
+        assert!(controller
+            .get_session(ssn.id.clone())?
+            .status
+            .unschedulable
+            .is_none());
+        assert_eq!(count_events(SESSION_SCHEDULABLE_EVENT)?, 1);
+
+        Ok(())
+    }
+
+    /// Test the binding and unbinding of the executors are recorded as the
+    /// events of the session.
+    #[test]
+    fn test_executor_binding_events() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let controller = env.controller.clone();
+
+        tokio_test::block_on(
+            controller.register_application("flmtest".to_string(), new_test_application()),
+        )?;
+        tokio_test::block_on(
+            controller
+                .storage()
+                .register_node(&new_test_node("node_1".to_string())),
+        )?;
+        let ssn =
+            tokio_test::block_on(controller.create_session(common::apis::SessionAttributes {
+                id: format!("ssn-binding-{}", Utc::now().timestamp()).into(),
+                application: "flmtest".to_string(),
+                slots: 1,
+                common_data: None,
+                min_instances: 0,
+                max_instances: None,
+                batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: common::apis::ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
+            }))?;
+
+        let exec = tokio_test::block_on(controller.create_executor(
+            "node_1".to_string(),
+            ssn.id.clone(),
+            None,
+        ))?;
+        tokio_test::block_on(controller.register_executor(&exec))?;
+        tokio_test::block_on(controller.bind_session(exec.id.clone(), ssn.id.clone(), None))?;
+        tokio_test::block_on(controller.bind_session_completed(exec.id.clone()))?;
+        tokio_test::block_on(controller.unbind_executor(exec.id.clone()))?;
+        tokio_test::block_on(controller.unbind_executor_completed(exec.id.clone()))?;
+
+        let events: Vec<_> = controller
+            .get_session(ssn.id.clone())?
+            .events
+            .into_iter()
+            .filter(|e| (EXECUTOR_BINDING_EVENT..=EXECUTOR_UNBOUND_EVENT).contains(&e.code))
+            .collect();
+        let codes: Vec<i32> = events.iter().map(|e| e.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                EXECUTOR_BINDING_EVENT,
+                EXECUTOR_BOUND_EVENT,
+                EXECUTOR_UNBOUND_EVENT
+            ]
+        );
+        for event in &events {
+            let message = event.message.clone().unwrap_or_default();
+            assert!(message.contains(&exec.id));
+            assert!(message.contains("node_1"));
+        }
+
+        Ok(())
+    }
+
+    /// An action which counts its runs, and fails if `fail` is set.
+    struct TestAction {
+        fail: bool,
+        runs: Arc<AtomicU64>,
+    }
+
+    #[async_trait::async_trait]
+    impl Action for TestAction {
+        fn name(&self) -> &'static str {
+            if self.fail {
+                "failing"
+            } else {
+                "counting"
+            }
+        }
+
+        async fn execute(&self, _: &mut Context) -> Result<(), FlameError> {
+            self.runs.fetch_add(1, Ordering::Relaxed);
+            if self.fail {
+                return Err(FlameError::Internal("test failure".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    /// Test the failed action does not stop the other actions of the cycle.
+    #[test]
+    fn test_schedule_continues_after_action_failure() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let controller = env.controller.clone();
+        let runner = ScheduleRunner {
+            controller: controller.clone(),
+            shutdown: CancellationToken::new(),
+            metrics: ScheduleMetrics::default(),
+        };
+
+        let runs = Arc::new(AtomicU64::new(0));
+        let snapshot = controller.snapshot()?;
+        let mut ctx = Context {
+            plugins: PluginManager::setup(&snapshot, &FlameScheduler::default().plugins)?,
+            snapshot,
+            controller,
+            actions: vec![
+                Arc::new(TestAction {
+                    fail: true,
+                    runs: runs.clone(),
+                }),
+                Arc::new(TestAction {
+                    fail: false,
+                    runs: runs.clone(),
+                }),
+            ],
+        };
+
+        for _ in 0..2 {
+            tokio_test::block_on(runner.schedule(&mut ctx))?;
+        }
+
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+        assert_eq!(runner.metrics.cycles.load(Ordering::Relaxed), 2);
+        assert_eq!(runner.metrics.failures()?, 2);
+
+        Ok(())
+    }
+
+    /// Test the scheduler stops when the shutdown token is cancelled.
+    #[test]
+    fn test_scheduler_shutdown() -> Result<(), FlameError> {
+        let env = TestEnv::new()?;
+        let shutdown = CancellationToken::new();
+        let scheduler = crate::scheduler::new(env.controller.clone(), shutdown.clone());
+
+        let config = FlameClusterContext {
+            cluster: FlameCluster {
+                schedule_interval: 60_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        tokio_test::block_on(async move {
+            let handle = tokio::spawn(async move { scheduler.run(config).await });
+
+            shutdown.cancel();
+            let res = tokio::time::timeout(std::time::Duration::from_secs(5), handle)
+                .await
+                .map_err(|_| FlameError::Internal("scheduler did not stop".to_string()))?;
+            assert!(matches!(res, Ok(Ok(()))));
+
+            Ok(())
+        })
+    }
 }
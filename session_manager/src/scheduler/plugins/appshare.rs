@@ -0,0 +1,423 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Fairness among the sessions of the same application.
+//!
+//! The fairshare plugin shares the cluster among the sessions, but the sessions
+//! of an application also compete for the `max_instances` of the application,
+//! where a giant session may take all the instances and starve the interactive
+//! ones. When the sessions of an application desire more instances than its
+//! `max_instances`, the instances are shared among them by the fairness policy
+//! of the application: a session above its share gets no more instances while
+//! another session of the application is below its own, and its instances are
+//! preempted for such a session by the shuffle action.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use common::apis::{ApplicationID, FairnessPolicy, SessionID, TaskState};
+use common::FlameError;
+
+use crate::model::{
+    ExecutorInfoPtr, NodeInfoPtr, SessionInfo, SessionInfoPtr, SnapShot, ALL_APPLICATION,
+    ALL_EXECUTOR, OPEN_SESSION,
+};
+use crate::scheduler::plugins::{Plugin, PluginPtr};
+
+#[derive(Clone, Debug, Default)]
+struct SsnShare {
+    application: ApplicationID,
+    /// The instances to run the pending and running tasks of the session.
+    desired: f64,
+    /// The weight of the session in its application by the fairness policy.
+    weight: f64,
+    /// The share of the session in the `max_instances` of its application.
+    deserved: f64,
+    allocated: f64,
+}
+
+impl SsnShare {
+    /// Whether the session is below its share by at least one instance.
+    fn is_below(&self) -> bool {
+        self.allocated + 1.0 <= self.deserved
+    }
+
+    /// Whether the session is above its share by at least one instance.
+    fn is_above(&self) -> bool {
+        self.allocated - 1.0 >= self.deserved
+    }
+}
+
+/// Returns the number of instances to run the pending and running tasks of the
+/// session, within its instance limits.
+pub(super) fn desired_instances(ssn: &SessionInfo) -> f64 {
+    let tasks: i32 = [TaskState::Pending, TaskState::Running]
+        .iter()
+        .filter_map(|state| ssn.tasks_status.get(state))
+        .sum();
+
+    let mut desired = tasks.max(0) as u32;
+    if let Some(max_instances) = ssn.max_instances {
+        desired = desired.min(max_instances);
+    }
+
+    desired.max(ssn.min_instances) as f64
+}
+
+fn weight(policy: FairnessPolicy, ssn: &SessionInfo) -> f64 {
+    match policy {
+        FairnessPolicy::RoundRobin => 1.0,
+        FairnessPolicy::Proportional => ssn
+            .tasks_status
+            .get(&TaskState::Pending)
+            .copied()
+            .unwrap_or_default()
+            .max(0) as f64,
+    }
+}
+
+/// Shares the instances among the sessions by their weights, i.e. `(desired, weight)`;
+/// a session never deserves more than it desires, and the rest is shared by the others.
+fn distribute(instances: f64, demands: &[(f64, f64)]) -> Vec<f64> {
+    let mut shares = vec![0.0; demands.len()];
+    let mut remaining = instances;
+    let mut open: Vec<usize> = (0..demands.len()).filter(|&i| demands[i].0 > 0.0).collect();
+
+    loop {
+        let weights: f64 = open.iter().map(|&i| demands[i].1).sum();
+        if open.is_empty() || weights <= 0.0 || remaining < 0.001 {
+            break;
+        }
+
+        let quota = |i: usize| remaining * demands[i].1 / weights;
+        let (full, partial): (Vec<usize>, Vec<usize>) =
+            open.iter().partition(|&&i| demands[i].0 <= quota(i));
+        if full.is_empty() {
+            for &i in &partial {
+                shares[i] = quota(i);
+            }
+            break;
+        }
+
+        for &i in &full {
+            shares[i] = demands[i].0;
+            remaining -= demands[i].0;
+        }
+        open = partial;
+    }
+
+    shares
+}
+
+pub struct AppSharePlugin {
+    ssn_map: HashMap<SessionID, SsnShare>,
+    /// The applications whose sessions desire more than its `max_instances`.
+    contended: HashSet<ApplicationID>,
+}
+
+impl AppSharePlugin {
+    pub fn new_ptr() -> PluginPtr {
+        Box::new(AppSharePlugin {
+            ssn_map: HashMap::new(),
+            contended: HashSet::new(),
+        })
+    }
+
+    /// The share of the session if its application is contended.
+    fn contended_share(&self, ssn_id: &SessionID) -> Option<&SsnShare> {
+        self.ssn_map
+            .get(ssn_id)
+            .filter(|ssn| self.contended.contains(&ssn.application))
+    }
+
+    /// Whether another session of the application is below its share.
+    fn has_sibling_below(&self, ssn_id: &SessionID, application: &ApplicationID) -> bool {
+        self.ssn_map
+            .iter()
+            .any(|(id, ssn)| id != ssn_id && &ssn.application == application && ssn.is_below())
+    }
+
+    /// A session above its share gets no more instances while another session
+    /// of its application is below its own.
+    fn can_grow(&self, ssn_id: &SessionID) -> Option<bool> {
+        let share = self.contended_share(ssn_id)?;
+        Some(share.is_below() || !self.has_sibling_below(ssn_id, &share.application))
+    }
+}
+
+impl Plugin for AppSharePlugin {
+    fn setup(&mut self, ss: &SnapShot) -> Result<(), FlameError> {
+        self.ssn_map.clear();
+        self.contended.clear();
+
+        let apps = ss.find_applications(ALL_APPLICATION)?;
+        let open_ssns = ss.find_sessions(OPEN_SESSION)?;
+
+        let mut app_ssns: HashMap<ApplicationID, Vec<SessionID>> = HashMap::new();
+        for ssn in open_ssns.values() {
+            let Some(app) = apps.get(&ssn.application) else {
+                continue;
+            };
+            self.ssn_map.insert(
+                ssn.id.clone(),
+                SsnShare {
+                    application: ssn.application.clone(),
+                    desired: desired_instances(ssn),
+                    weight: weight(app.fairness, ssn),
+                    ..SsnShare::default()
+                },
+            );
+            app_ssns
+                .entry(ssn.application.clone())
+                .or_default()
+                .push(ssn.id.clone());
+        }
+
+        let executors = ss.find_executors(ALL_EXECUTOR)?;
+        for exec in executors.values() {
+            if let Some(ssn) = exec.ssn_id.as_ref().and_then(|id| self.ssn_map.get_mut(id)) {
+                ssn.allocated += 1.0;
+            }
+        }
+
+        for (app_name, ssn_ids) in app_ssns {
+            let max_instances = apps[&app_name].max_instances as f64;
+            let demands: Vec<(f64, f64)> = ssn_ids
+                .iter()
+                .map(|id| (self.ssn_map[id].desired, self.ssn_map[id].weight))
+                .collect();
+            if demands.iter().map(|(desired, _)| desired).sum::<f64>() <= max_instances {
+                continue;
+            }
+
+            let shares = distribute(max_instances, &demands);
+            for (id, share) in ssn_ids.iter().zip(shares) {
+                if let Some(ssn) = self.ssn_map.get_mut(id) {
+                    ssn.deserved = share;
+                }
+            }
+
+            if tracing::enabled!(tracing::Level::DEBUG) {
+                for id in &ssn_ids {
+                    let ssn = &self.ssn_map[id];
+                    tracing::debug!(
+                        "Session <{}> of contended application <{}>: desired <{}>, deserved <{}>, allocated <{}>.",
+                        id,
+                        app_name,
+                        ssn.desired,
+                        ssn.deserved,
+                        ssn.allocated
+                    );
+                }
+            }
+            self.contended.insert(app_name);
+        }
+
+        Ok(())
+    }
+
+    fn ssn_order_fn(&self, s1: &SessionInfo, s2: &SessionInfo) -> Option<Ordering> {
+        let ss1 = self.contended_share(&s1.id)?;
+        let ss2 = self.contended_share(&s2.id)?;
+        if ss1.application != ss2.application {
+            return None;
+        }
+
+        // The session with the lower ratio of its share goes first.
+        let left = ss1.allocated * ss2.deserved;
+        let right = ss2.allocated * ss1.deserved;
+        right.partial_cmp(&left)
+    }
+
+    fn is_allocatable(&self, _node: &NodeInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        self.can_grow(&ssn.id)
+    }
+
+    fn is_available(&self, _exec: &ExecutorInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        self.can_grow(&ssn.id)
+    }
+
+    fn is_preemptible_for(&self, ssn: &SessionInfoPtr, target: &SessionInfoPtr) -> Option<bool> {
+        let share = self.contended_share(&ssn.id)?;
+        let target = self.contended_share(&target.id)?;
+        Some(share.application == target.application && share.is_above() && target.is_below())
+    }
+
+    fn on_pipeline_executor(&mut self, _node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
+            ss.allocated += 1.0;
+        }
+    }
+
+    fn on_discard_executor(&mut self, _node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
+            ss.allocated -= 1.0;
+        }
+    }
+
+    fn on_session_bind(&mut self, ssn: SessionInfoPtr) {
+        if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
+            ss.allocated += 1.0;
+        }
+    }
+
+    fn on_session_unbind(&mut self, ssn: SessionInfoPtr) {
+        if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
+            ss.allocated -= 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use common::apis::{ExecutorState, ResourceRequirement, SessionState};
+
+    use super::*;
+    use crate::model::{AppInfo, ExecutorInfo, NodeInfo};
+
+    fn session(id: &str, pending: i32) -> SessionInfoPtr {
+        Arc::new(SessionInfo {
+            id: id.to_string().into(),
+            application: "app".to_string(),
+            slots: 1,
+            tasks_status: HashMap::from([(TaskState::Pending, pending)]),
+            creation_time: Utc::now(),
+            state: SessionState::Open,
+            batch_size: 1,
+            ..SessionInfo::default()
+        })
+    }
+
+    fn snapshot(fairness: FairnessPolicy, sessions: &[(&str, i32, usize)]) -> SnapShot {
+        let ss = SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g"));
+        ss.add_application(Arc::new(AppInfo {
+            name: "app".to_string(),
+            max_instances: 10,
+            fairness,
+            ..AppInfo::default()
+        }))
+        .unwrap();
+
+        for (id, pending, bound) in sessions {
+            ss.add_session(session(id, *pending)).unwrap();
+            for i in 0..*bound {
+                ss.add_executor(Arc::new(ExecutorInfo {
+                    id: format!("{id}-exec-{i}"),
+                    node: "node-1".to_string(),
+                    slots: 1,
+                    ssn_id: Some(id.to_string().into()),
+                    creation_time: Utc::now(),
+                    state: ExecutorState::Bound,
+                    ..ExecutorInfo::default()
+                }))
+                .unwrap();
+            }
+        }
+
+        ss
+    }
+
+    fn setup(ss: &SnapShot) -> AppSharePlugin {
+        let mut plugin = AppSharePlugin {
+            ssn_map: HashMap::new(),
+            contended: HashSet::new(),
+        };
+        plugin.setup(ss).unwrap();
+        plugin
+    }
+
+    #[test]
+    fn test_distribute() {
+        // The small demand is satisfied, the rest is shared equally.
+        assert_eq!(
+            distribute(10.0, &[(100.0, 1.0), (2.0, 1.0), (100.0, 1.0)]),
+            vec![4.0, 2.0, 4.0]
+        );
+        // Proportional to the weights.
+        assert_eq!(
+            distribute(10.0, &[(100.0, 80.0), (100.0, 20.0)]),
+            vec![8.0, 2.0]
+        );
+        // Nothing for the sessions without weight or demand.
+        assert_eq!(
+            distribute(10.0, &[(100.0, 0.0), (0.0, 1.0), (100.0, 1.0)]),
+            vec![0.0, 0.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn test_round_robin() {
+        // The giant session holds all the instances of the application.
+        let ss = snapshot(
+            FairnessPolicy::RoundRobin,
+            &[("giant", 1000, 10), ("interactive", 2, 0)],
+        );
+        let mut plugin = setup(&ss);
+
+        let giant = session("giant", 1000);
+        let interactive = session("interactive", 2);
+        let node = Arc::new(NodeInfo::default());
+
+        assert_eq!(plugin.is_allocatable(&node, &giant), Some(false));
+        assert_eq!(plugin.is_allocatable(&node, &interactive), Some(true));
+        assert_eq!(plugin.is_preemptible_for(&giant, &interactive), Some(true));
+        assert_eq!(plugin.is_preemptible_for(&interactive, &giant), Some(false));
+        assert_eq!(
+            plugin.ssn_order_fn(&interactive, &giant),
+            Some(Ordering::Greater)
+        );
+
+        // The interactive session reaches its share.
+        plugin.on_session_unbind(giant.clone());
+        plugin.on_session_bind(interactive.clone());
+        plugin.on_session_unbind(giant.clone());
+        plugin.on_session_bind(interactive.clone());
+        assert_eq!(plugin.is_allocatable(&node, &giant), Some(true));
+        assert_eq!(plugin.is_preemptible_for(&giant, &interactive), Some(false));
+    }
+
+    #[test]
+    fn test_proportional() {
+        let ss = snapshot(
+            FairnessPolicy::Proportional,
+            &[("ssn-1", 60, 9), ("ssn-2", 40, 1)],
+        );
+        let plugin = setup(&ss);
+
+        assert_eq!(plugin.ssn_map["ssn-1"].deserved, 6.0);
+        assert_eq!(plugin.ssn_map["ssn-2"].deserved, 4.0);
+        assert_eq!(
+            plugin.is_preemptible_for(&session("ssn-1", 60), &session("ssn-2", 40)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_uncontended() {
+        let ss = snapshot(
+            FairnessPolicy::RoundRobin,
+            &[("ssn-1", 5, 5), ("ssn-2", 2, 0)],
+        );
+        let plugin = setup(&ss);
+
+        let node = Arc::new(NodeInfo::default());
+        assert_eq!(plugin.is_allocatable(&node, &session("ssn-1", 5)), None);
+        assert_eq!(
+            plugin.is_preemptible_for(&session("ssn-1", 5), &session("ssn-2", 2)),
+            None
+        );
+    }
+}
@@ -0,0 +1,208 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Availability plugin for filtering the nodes by the applications they can run.
+//!
+//! The executor manager reports the instances failed to start on its node, e.g.
+//! the binary of the application is missing or built for another architecture;
+//! the application is not scheduled to the node until it's retried. The
+//! executors are also not created beyond the max executors of the node by its
+//! heartbeats, which its executor manager would reject.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::{ExecutorInfoPtr, NodeInfoPtr, SessionInfoPtr, SnapShot, ALL_EXECUTOR};
+use crate::scheduler::plugins::{Plugin, PluginPtr};
+use common::FlameError;
+
+/// Availability plugin that filters the nodes where the application failed to start.
+pub struct AvailabilityPlugin {
+    /// Map from node name to the applications unavailable on it
+    unavailable_apps: HashMap<String, HashSet<String>>,
+    /// Map from node name to its max executors, if limited
+    max_executors: HashMap<String, u32>,
+    /// Map from node name to its executors, including the pipelined ones
+    executors: HashMap<String, u32>,
+}
+
+impl AvailabilityPlugin {
+    pub fn new_ptr() -> PluginPtr {
+        Box::new(AvailabilityPlugin {
+            unavailable_apps: HashMap::new(),
+            max_executors: HashMap::new(),
+            executors: HashMap::new(),
+        })
+    }
+
+    fn has_capacity(&self, node: &str) -> bool {
+        self.max_executors
+            .get(node)
+            .is_none_or(|max| self.executors.get(node).copied().unwrap_or_default() < *max)
+    }
+
+    fn is_runnable(&self, node: &str, app: &str) -> bool {
+        self.unavailable_apps
+            .get(node)
+            .is_none_or(|apps| !apps.contains(app))
+    }
+}
+
+impl Plugin for AvailabilityPlugin {
+    fn setup(&mut self, ss: &SnapShot) -> Result<(), FlameError> {
+        self.unavailable_apps = ss
+            .unavailable_apps
+            .iter()
+            .map(|(node, apps)| (node.clone(), apps.keys().cloned().collect()))
+            .collect();
+
+        self.max_executors = ss
+            .node_loads
+            .keys()
+            .filter_map(|node| Some((node.clone(), ss.node_max_executors(node)?)))
+            .collect();
+
+        self.executors = HashMap::new();
+        for exec in ss.find_executors(ALL_EXECUTOR)?.values() {
+            *self.executors.entry(exec.node.clone()).or_default() += 1;
+        }
+
+        Ok(())
+    }
+
+    /// The idle executors are not bound to the sessions whose application failed
+    /// to start on the executor's node.
+    fn is_available(&self, exec: &ExecutorInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        Some(self.is_runnable(&exec.node, &ssn.application))
+    }
+
+    /// The executors are not created for the session on the nodes where its
+    /// application failed to start, or which reached their max executors.
+    fn is_allocatable(&self, node: &NodeInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        Some(self.is_runnable(&node.name, &ssn.application) && self.has_capacity(&node.name))
+    }
+
+    fn on_pipeline_executor(&mut self, node: NodeInfoPtr, _: SessionInfoPtr) {
+        *self.executors.entry(node.name.clone()).or_default() += 1;
+    }
+
+    fn on_discard_executor(&mut self, node: NodeInfoPtr, _: SessionInfoPtr) {
+        if let Some(count) = self.executors.get_mut(&node.name) {
+            *count = count.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::model::{ExecutorInfo, NodeInfo, NodeLoad, SessionInfo};
+    use common::apis::ResourceRequirement;
+
+    fn create_session_info(id: &str, app: &str) -> SessionInfoPtr {
+        Arc::new(SessionInfo {
+            id: id.to_string().into(),
+            application: app.to_string(),
+            slots: 1,
+            ..Default::default()
+        })
+    }
+
+    fn create_node_info(name: &str) -> NodeInfoPtr {
+        Arc::new(NodeInfo {
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_availability_plugin() {
+        let mut ss = SnapShot::new(ResourceRequirement {
+            cpu: 1,
+            memory: 1024,
+        });
+        ss.unavailable_apps.insert(
+            "node1".to_string(),
+            HashMap::from([("app-1".to_string(), "no such file".to_string())]),
+        );
+
+        let mut plugin = AvailabilityPlugin::new_ptr();
+        plugin.setup(&ss).unwrap();
+
+        let ssn1 = create_session_info("ssn-1", "app-1");
+        let ssn2 = create_session_info("ssn-2", "app-2");
+
+        // The application is only unavailable on the node where it failed to start.
+        assert_eq!(
+            plugin.is_allocatable(&create_node_info("node1"), &ssn1),
+            Some(false)
+        );
+        assert_eq!(
+            plugin.is_allocatable(&create_node_info("node2"), &ssn1),
+            Some(true)
+        );
+        assert_eq!(
+            plugin.is_allocatable(&create_node_info("node1"), &ssn2),
+            Some(true)
+        );
+
+        let exec = Arc::new(ExecutorInfo {
+            id: "exec-1".to_string(),
+            node: "node1".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(plugin.is_available(&exec, &ssn1), Some(false));
+        assert_eq!(plugin.is_available(&exec, &ssn2), Some(true));
+    }
+
+    #[test]
+    fn test_max_executors_of_node() {
+        let mut ss = SnapShot::new(ResourceRequirement {
+            cpu: 1,
+            memory: 1024,
+        });
+        ss.node_loads.insert(
+            "node1".to_string(),
+            NodeLoad {
+                max_executors: Some(2),
+                ..Default::default()
+            },
+        );
+        ss.add_executor(Arc::new(ExecutorInfo {
+            id: "exec-1".to_string(),
+            node: "node1".to_string(),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let mut plugin = AvailabilityPlugin::new_ptr();
+        plugin.setup(&ss).unwrap();
+
+        let ssn = create_session_info("ssn-1", "app-1");
+        let node1 = create_node_info("node1");
+        let node2 = create_node_info("node2");
+        assert_eq!(plugin.is_allocatable(&node1, &ssn), Some(true));
+
+        // The pipelined executor takes the last executor of the node.
+        plugin.on_pipeline_executor(node1.clone(), ssn.clone());
+        assert_eq!(plugin.is_allocatable(&node1, &ssn), Some(false));
+
+        // The nodes without max executors are unlimited.
+        plugin.on_pipeline_executor(node2.clone(), ssn.clone());
+        assert_eq!(plugin.is_allocatable(&node2, &ssn), Some(true));
+
+        plugin.on_discard_executor(node1.clone(), ssn.clone());
+        assert_eq!(plugin.is_allocatable(&node1, &ssn), Some(true));
+    }
+}
@@ -19,8 +19,8 @@ use crate::model::{
     ExecutorInfo, ExecutorInfoPtr, NodeInfo, NodeInfoPtr, SessionInfo, SessionInfoPtr, SnapShot,
     ALL_APPLICATION, ALL_EXECUTOR, ALL_NODE, OPEN_SESSION,
 };
-use crate::scheduler::plugins::{Plugin, PluginPtr};
-use common::apis::{ExecutorState, ResourceRequirement, SessionID, TaskState};
+use crate::scheduler::plugins::{Plugin, PluginPtr, Share};
+use common::apis::{ExecutorState, ResourceRequirement, SessionID};
 use common::FlameError;
 
 #[derive(Default, Clone)]
@@ -32,6 +32,8 @@ struct SSNInfo {
     pub allocated: f64,
     pub min_instances: u32,         // Minimum number of instances
     pub max_instances: Option<u32>, // Maximum number of instances (None means unlimited)
+    /// The slots backfilled beyond the deserved share, which are reclaimed first.
+    pub elastic: f64,
 }
 
 impl Eq for SSNInfo {}
@@ -127,15 +129,10 @@ impl Plugin for FairShare {
         );
 
         for ssn in open_ssns.values() {
-            let mut desired = 0.0;
-            for state in [TaskState::Pending, TaskState::Running] {
-                if let Some(d) = ssn.tasks_status.get(&state) {
-                    desired += *d as f64 * ssn.slots as f64;
-                }
-            }
+            let mut desired = ssn.desired_instances() * ssn.slots as f64;
 
             if let Some(app) = apps.get(&ssn.application) {
-                // Cap desired by session's max_instances (already includes app limit from session creation)
+                // Cap desired by session's max_instances; the app limit is checked by the actions
                 if let Some(max_instances) = ssn.max_instances {
                     desired = desired.min((max_instances * ssn.slots) as f64);
                 }
@@ -230,6 +227,12 @@ impl Plugin for FairShare {
             }
         }
 
+        // The allocations beyond the deserved share were backfilled in the previous
+        // cycles, e.g. with the leftover capacity of the cluster.
+        for ssn in self.ssn_map.values_mut() {
+            ssn.elastic = (ssn.allocated - ssn.deserved).max(0.0);
+        }
+
         if tracing::enabled!(tracing::Level::DEBUG) {
             for ssn in self.ssn_map.values() {
                 tracing::debug!(
@@ -374,6 +377,26 @@ impl Plugin for FairShare {
     fn on_session_unbind(&mut self, ssn: SessionInfoPtr) {
         if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
             ss.allocated -= ssn.slots as f64;
+            ss.elastic = (ss.elastic - ssn.slots as f64).max(0.0);
+        }
+    }
+
+    fn elastic_allocated(&self, ssn: &SessionInfoPtr) -> Option<f64> {
+        self.ssn_map.get(&ssn.id).map(|ssn| ssn.elastic)
+    }
+
+    fn share(&self, ssn: &SessionInfoPtr) -> Option<Share> {
+        self.ssn_map.get(&ssn.id).map(|ssn| Share {
+            desired: ssn.desired,
+            deserved: ssn.deserved,
+            allocated: ssn.allocated,
+            elastic: ssn.elastic,
+        })
+    }
+
+    fn on_backfill_executor(&mut self, _node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(ss) = self.ssn_map.get_mut(&ssn.id) {
+            ss.elastic += ssn.slots as f64;
         }
     }
 }
@@ -19,20 +19,76 @@ use stdng::collections;
 use stdng::{lock_ptr, new_ptr, MutexPtr};
 
 use crate::model::{ExecutorInfoPtr, NodeInfo, NodeInfoPtr, SessionInfo, SessionInfoPtr, SnapShot};
+use crate::scheduler::plugins::appshare::AppSharePlugin;
+use crate::scheduler::plugins::availability::AvailabilityPlugin;
 use crate::scheduler::plugins::fairshare::FairShare;
 use crate::scheduler::plugins::gang::GangPlugin;
+use crate::scheduler::plugins::priority::PriorityPlugin;
 use crate::scheduler::plugins::shim::ShimPlugin;
+use crate::scheduler::plugins::topology::TopologyPlugin;
 use crate::scheduler::Context;
 
 use common::FlameError;
 
+mod appshare;
+mod availability;
 mod fairshare;
 mod gang;
+mod priority;
 mod shim;
+mod topology;
 
 pub type PluginPtr = Box<dyn Plugin>;
 pub type PluginManagerPtr = Arc<PluginManager>;
 
+/// The share of a session in slots, e.g. by the fairshare plugin.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Share {
+    pub desired: f64,
+    pub deserved: f64,
+    pub allocated: f64,
+    /// The slots backfilled beyond the deserved share.
+    pub elastic: f64,
+}
+
+/// Create a plugin for a scheduling cycle.
+type PluginFactory = fn() -> PluginPtr;
+
+/// The registry of the plugins, by their names in `scheduler.plugins` of the
+/// cluster; a new plugin is registered here to be enabled by the configuration.
+const REGISTRY: [(&str, PluginFactory); 7] = [
+    ("fairshare", FairShare::new_ptr),
+    ("shim", ShimPlugin::new_ptr),
+    ("gang", GangPlugin::new_ptr),
+    ("appshare", AppSharePlugin::new_ptr),
+    ("availability", AvailabilityPlugin::new_ptr),
+    ("priority", PriorityPlugin::new_ptr),
+    ("topology", TopologyPlugin::new_ptr),
+];
+
+fn new_plugin(name: &str) -> Result<PluginPtr, FlameError> {
+    REGISTRY
+        .iter()
+        .find(|(registered, _)| *registered == name)
+        .map(|(_, factory)| factory())
+        .ok_or_else(|| {
+            let registered: Vec<_> = REGISTRY.iter().map(|(name, _)| *name).collect();
+            FlameError::InvalidConfig(format!(
+                "unknown scheduler plugin <{name}>, expected one of: {}",
+                registered.join(", ")
+            ))
+        })
+}
+
+/// Check all the plugins are registered, e.g. before starting the scheduler.
+pub fn validate(names: &[String]) -> Result<(), FlameError> {
+    for name in names {
+        new_plugin(name)?;
+    }
+
+    Ok(())
+}
+
 /// Plugin trait for scheduler plugins.
 ///
 /// # Stale Data Limitation
@@ -73,6 +129,12 @@ pub trait Plugin: Send + Sync + 'static {
         None
     }
 
+    /// Whether the executors of the session can be preempted for the target
+    /// session, even if the session is not preemptible.
+    fn is_preemptible_for(&self, ssn: &SessionInfoPtr, target: &SessionInfoPtr) -> Option<bool> {
+        None
+    }
+
     fn is_available(&self, exec: &ExecutorInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
         None
     }
@@ -89,6 +151,16 @@ pub trait Plugin: Send + Sync + 'static {
         None
     }
 
+    /// The slots allocated to the session by backfilling beyond its deserved share.
+    fn elastic_allocated(&self, ssn: &SessionInfoPtr) -> Option<f64> {
+        None
+    }
+
+    /// The share of the session by the plugin, e.g. to debug the scheduling.
+    fn share(&self, ssn: &SessionInfoPtr) -> Option<Share> {
+        None
+    }
+
     // Events callbacks
     fn on_create_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {}
 
@@ -99,22 +171,23 @@ pub trait Plugin: Send + Sync + 'static {
     fn on_pipeline_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {}
 
     fn on_discard_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {}
+
+    fn on_backfill_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {}
 }
 
 pub struct PluginManager {
-    pub plugins: MutexPtr<HashMap<String, PluginPtr>>,
+    /// The enabled plugins with their names, in the configured order.
+    pub plugins: MutexPtr<Vec<(String, PluginPtr)>>,
 }
 
 impl PluginManager {
-    pub fn setup(ss: &SnapShot) -> Result<PluginManagerPtr, FlameError> {
-        let mut plugins = HashMap::from([
-            ("fairshare".to_string(), FairShare::new_ptr()),
-            ("shim".to_string(), ShimPlugin::new_ptr()),
-            ("gang".to_string(), GangPlugin::new_ptr()),
-        ]);
-
-        for plugin in plugins.values_mut() {
+    /// Set up the plugins enabled by their names for the snapshot.
+    pub fn setup(ss: &SnapShot, names: &[String]) -> Result<PluginManagerPtr, FlameError> {
+        let mut plugins = Vec::with_capacity(names.len());
+        for name in names {
+            let mut plugin = new_plugin(name)?;
             plugin.setup(ss)?;
+            plugins.push((name.clone(), plugin));
         }
 
         Ok(Arc::new(PluginManager {
@@ -126,7 +199,8 @@ impl PluginManager {
         let plugins = lock_ptr!(self.plugins)?;
 
         Ok(plugins
-            .values()
+            .iter()
+            .map(|(_, plugin)| plugin)
             .any(|plugin| plugin.is_underused(ssn).unwrap_or(false)))
     }
 
@@ -134,10 +208,33 @@ impl PluginManager {
         let plugins = lock_ptr!(self.plugins)?;
 
         Ok(plugins
-            .values()
+            .iter()
+            .map(|(_, plugin)| plugin)
             .all(|plugin| plugin.is_preemptible(ssn).unwrap_or(false)))
     }
 
+    /// Check if the executors of the session can be preempted for the target
+    /// session: either any plugin allows it for the target, e.g. the sessions
+    /// of the same application, or the session is preemptible.
+    pub fn is_preemptible_for(
+        &self,
+        ssn: &SessionInfoPtr,
+        target: &SessionInfoPtr,
+    ) -> Result<bool, FlameError> {
+        {
+            let plugins = lock_ptr!(self.plugins)?;
+            if plugins
+                .iter()
+                .map(|(_, plugin)| plugin)
+                .any(|plugin| plugin.is_preemptible_for(ssn, target).unwrap_or(false))
+            {
+                return Ok(true);
+            }
+        }
+
+        self.is_preemptible(ssn)
+    }
+
     /// Check if an executor is available for a session.
     ///
     /// Returns true if ALL plugins agree the executor is available.
@@ -191,7 +288,8 @@ impl PluginManager {
         let plugins = lock_ptr!(self.plugins)?;
 
         Ok(plugins
-            .values()
+            .iter()
+            .map(|(_, plugin)| plugin)
             .all(|plugin| plugin.is_allocatable(node, ssn).unwrap_or(true)))
     }
 
@@ -199,7 +297,8 @@ impl PluginManager {
         let plugins = lock_ptr!(self.plugins)?;
 
         Ok(plugins
-            .values()
+            .iter()
+            .map(|(_, plugin)| plugin)
             .all(|plugin| plugin.is_reclaimable(exec).unwrap_or(true)))
     }
 
@@ -210,7 +309,7 @@ impl PluginManager {
     ) -> Result<(), FlameError> {
         let mut plugins = lock_ptr!(self.plugins)?;
 
-        for plugin in plugins.values_mut() {
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
             plugin.on_create_executor(node.clone(), ssn.clone());
         }
 
@@ -220,7 +319,7 @@ impl PluginManager {
     pub fn on_session_bind(&self, ssn: SessionInfoPtr) -> Result<(), FlameError> {
         let mut plugins = lock_ptr!(self.plugins)?;
 
-        for plugin in plugins.values_mut() {
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
             plugin.on_session_bind(ssn.clone());
         }
 
@@ -230,7 +329,7 @@ impl PluginManager {
     pub fn on_session_unbind(&self, ssn: SessionInfoPtr) -> Result<(), FlameError> {
         let mut plugins = lock_ptr!(self.plugins)?;
 
-        for plugin in plugins.values_mut() {
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
             plugin.on_session_unbind(ssn.clone());
         }
         Ok(())
@@ -240,10 +339,35 @@ impl PluginManager {
         let plugins = lock_ptr!(self.plugins)?;
 
         Ok(plugins
-            .values()
+            .iter()
+            .map(|(_, plugin)| plugin)
             .all(|plugin| plugin.is_ready(ssn).unwrap_or(true)))
     }
 
+    pub fn elastic_allocated(&self, ssn: &SessionInfoPtr) -> Result<f64, FlameError> {
+        let plugins = lock_ptr!(self.plugins)?;
+
+        Ok(plugins
+            .iter()
+            .map(|(_, plugin)| plugin)
+            .filter_map(|plugin| plugin.elastic_allocated(ssn))
+            .sum())
+    }
+
+    pub fn on_backfill_executor(
+        &self,
+        node: NodeInfoPtr,
+        ssn: SessionInfoPtr,
+    ) -> Result<(), FlameError> {
+        let mut plugins = lock_ptr!(self.plugins)?;
+
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
+            plugin.on_backfill_executor(node.clone(), ssn.clone());
+        }
+
+        Ok(())
+    }
+
     pub fn on_pipeline_executor(
         &self,
         node: NodeInfoPtr,
@@ -251,7 +375,7 @@ impl PluginManager {
     ) -> Result<(), FlameError> {
         let mut plugins = lock_ptr!(self.plugins)?;
 
-        for plugin in plugins.values_mut() {
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
             plugin.on_pipeline_executor(node.clone(), ssn.clone());
         }
 
@@ -265,7 +389,7 @@ impl PluginManager {
     ) -> Result<(), FlameError> {
         let mut plugins = lock_ptr!(self.plugins)?;
 
-        for plugin in plugins.values_mut() {
+        for plugin in plugins.iter_mut().map(|(_, plugin)| plugin) {
             plugin.on_discard_executor(node.clone(), ssn.clone());
         }
 
@@ -274,7 +398,7 @@ impl PluginManager {
 
     pub fn ssn_order_fn(&self, t1: &SessionInfoPtr, t2: &SessionInfoPtr) -> Ordering {
         if let Ok(plugins) = lock_ptr!(self.plugins) {
-            for plugin in plugins.values() {
+            for plugin in plugins.iter().map(|(_, plugin)| plugin) {
                 if let Some(order) = plugin.ssn_order_fn(t1, t2) {
                     if order != Ordering::Equal {
                         return order;
@@ -288,7 +412,7 @@ impl PluginManager {
 
     pub fn node_order_fn(&self, t1: &NodeInfoPtr, t2: &NodeInfoPtr) -> Ordering {
         if let Ok(plugins) = lock_ptr!(self.plugins) {
-            for plugin in plugins.values() {
+            for plugin in plugins.iter().map(|(_, plugin)| plugin) {
                 if let Some(order) = plugin.node_order_fn(t1, t2) {
                     if order != Ordering::Equal {
                         return order;
@@ -387,13 +511,16 @@ mod tests {
     use super::*;
     use crate::model::{ExecutorInfo, SessionInfo};
     use chrono::Utc;
-    use common::apis::{ExecutorState, ResourceRequirement, SessionState, Shim, TaskState};
+    use common::apis::{
+        ExecutorState, ResourceRequirement, SessionState, Shim, TaskDurationStats, TaskState,
+    };
+    use common::ctx::FlameScheduler;
     use std::collections::HashMap;
 
     /// Create a test session with the given parameters.
     fn create_test_session(id: &str, slots: u32) -> SessionInfoPtr {
         Arc::new(SessionInfo {
-            id: id.to_string(),
+            id: id.to_string().into(),
             application: "test-app".to_string(),
             slots,
             tasks_status: HashMap::from([(TaskState::Pending, 1)]),
@@ -403,6 +530,8 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            priority: 0,
+            task_durations: TaskDurationStats::default(),
         })
     }
 
@@ -420,6 +549,7 @@ mod tests {
             task_id: None,
             ssn_id: None,
             batch_index: None,
+            idle_since: None,
             creation_time: Utc::now(),
             state: ExecutorState::Idle,
         })
@@ -432,7 +562,7 @@ mod tests {
             cpu: 1,
             memory: 1024,
         });
-        let pm = PluginManager::setup(&ss).unwrap();
+        let pm = PluginManager::setup(&ss, &FlameScheduler::default().plugins).unwrap();
 
         let ssn = create_test_session("ssn-1", 2);
         let exec = create_test_executor("exec-1", 2);
@@ -450,7 +580,7 @@ mod tests {
             cpu: 1,
             memory: 1024,
         });
-        let pm = PluginManager::setup(&ss).unwrap();
+        let pm = PluginManager::setup(&ss, &FlameScheduler::default().plugins).unwrap();
 
         let ssn = create_test_session("ssn-1", 2);
         let exec = create_test_executor("exec-1", 4);
@@ -461,6 +591,35 @@ mod tests {
         );
     }
 
+    /// Test the plugins are enabled and ordered by their names.
+    #[test]
+    fn test_setup_enabled_plugins() {
+        let ss = SnapShot::new(ResourceRequirement {
+            cpu: 1,
+            memory: 1024,
+        });
+        let names = vec!["gang".to_string(), "availability".to_string()];
+        let pm = PluginManager::setup(&ss, &names).unwrap();
+
+        let plugins = lock_ptr!(pm.plugins).unwrap();
+        let enabled: Vec<_> = plugins.iter().map(|(name, _)| name.clone()).collect();
+        assert_eq!(enabled, names);
+        drop(plugins);
+
+        // The slots are not checked without the shim and fairshare plugins.
+        let ssn = create_test_session("ssn-1", 2);
+        let exec = create_test_executor("exec-1", 4);
+        assert!(pm.is_available(&exec, &ssn).unwrap());
+
+        let unknown = vec!["fairshare".to_string(), "binpack".to_string()];
+        assert!(matches!(
+            PluginManager::setup(&ss, &unknown),
+            Err(FlameError::InvalidConfig(_))
+        ));
+        assert!(validate(&unknown).is_err());
+        assert!(validate(&FlameScheduler::default().plugins).is_ok());
+    }
+
     /// Test find_available_executors filters correctly based on slots.
     #[test]
     fn test_find_available_executors_filters_by_slots() {
@@ -468,7 +627,7 @@ mod tests {
             cpu: 1,
             memory: 1024,
         });
-        let pm = PluginManager::setup(&ss).unwrap();
+        let pm = PluginManager::setup(&ss, &FlameScheduler::default().plugins).unwrap();
 
         let ssn = create_test_session("ssn-1", 2);
 
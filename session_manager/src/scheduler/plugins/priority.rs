@@ -0,0 +1,246 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Priority among the sessions.
+//!
+//! The sessions with a higher `priority` are allocated executors before the
+//! ones with a lower priority. When a session desires more instances than it's
+//! allocated, it's underused as long as a session of a lower priority holds
+//! executors, and those executors are preempted for it by the shuffle action.
+//! The sessions of the same priority are left to the other plugins, e.g.
+//! fairshare.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use common::apis::SessionID;
+use common::FlameError;
+
+use crate::model::{
+    NodeInfoPtr, SessionInfo, SessionInfoPtr, SnapShot, ALL_EXECUTOR, OPEN_SESSION,
+};
+use crate::scheduler::plugins::appshare::desired_instances;
+use crate::scheduler::plugins::{Plugin, PluginPtr};
+
+#[derive(Clone, Debug, Default)]
+struct SsnState {
+    priority: u32,
+    /// The instances to run the pending and running tasks of the session.
+    desired: f64,
+    allocated: f64,
+}
+
+pub struct PriorityPlugin {
+    ssn_map: HashMap<SessionID, SsnState>,
+}
+
+impl PriorityPlugin {
+    pub fn new_ptr() -> PluginPtr {
+        Box::new(PriorityPlugin {
+            ssn_map: HashMap::new(),
+        })
+    }
+
+    /// Whether a session of a lower priority than `priority` holds executors.
+    fn has_lower_allocated(&self, priority: u32) -> bool {
+        self.ssn_map
+            .values()
+            .any(|ssn| ssn.priority < priority && ssn.allocated > 0.0)
+    }
+}
+
+impl Plugin for PriorityPlugin {
+    fn setup(&mut self, ss: &SnapShot) -> Result<(), FlameError> {
+        self.ssn_map.clear();
+
+        let open_ssns = ss.find_sessions(OPEN_SESSION)?;
+        for ssn in open_ssns.values() {
+            self.ssn_map.insert(
+                ssn.id.clone(),
+                SsnState {
+                    priority: ssn.priority,
+                    desired: desired_instances(ssn),
+                    allocated: 0.0,
+                },
+            );
+        }
+
+        let executors = ss.find_executors(ALL_EXECUTOR)?;
+        for exec in executors.values() {
+            if let Some(ssn) = exec.ssn_id.as_ref().and_then(|id| self.ssn_map.get_mut(id)) {
+                ssn.allocated += 1.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn ssn_order_fn(&self, s1: &SessionInfo, s2: &SessionInfo) -> Option<Ordering> {
+        // The session with the higher priority goes first.
+        Some(s1.priority.cmp(&s2.priority))
+    }
+
+    fn is_underused(&self, ssn: &SessionInfoPtr) -> Option<bool> {
+        let state = self.ssn_map.get(&ssn.id)?;
+        if state.allocated < state.desired && self.has_lower_allocated(state.priority) {
+            return Some(true);
+        }
+
+        None
+    }
+
+    fn is_preemptible_for(&self, ssn: &SessionInfoPtr, target: &SessionInfoPtr) -> Option<bool> {
+        // The executors are reused by the target session, so only the ones of
+        // the same slots are preempted.
+        Some(ssn.priority < target.priority && ssn.slots == target.slots)
+    }
+
+    fn on_pipeline_executor(&mut self, _node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(state) = self.ssn_map.get_mut(&ssn.id) {
+            state.allocated += 1.0;
+        }
+    }
+
+    fn on_discard_executor(&mut self, _node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(state) = self.ssn_map.get_mut(&ssn.id) {
+            state.allocated -= 1.0;
+        }
+    }
+
+    fn on_session_bind(&mut self, ssn: SessionInfoPtr) {
+        if let Some(state) = self.ssn_map.get_mut(&ssn.id) {
+            state.allocated += 1.0;
+        }
+    }
+
+    fn on_session_unbind(&mut self, ssn: SessionInfoPtr) {
+        if let Some(state) = self.ssn_map.get_mut(&ssn.id) {
+            state.allocated -= 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use common::apis::{ExecutorState, ResourceRequirement, SessionState, TaskState};
+
+    use super::*;
+    use crate::model::ExecutorInfo;
+
+    fn session(id: &str, priority: u32, pending: i32) -> SessionInfoPtr {
+        Arc::new(SessionInfo {
+            id: id.to_string().into(),
+            application: "app".to_string(),
+            slots: 1,
+            tasks_status: HashMap::from([(TaskState::Pending, pending)]),
+            creation_time: Utc::now(),
+            state: SessionState::Open,
+            batch_size: 1,
+            priority,
+            ..SessionInfo::default()
+        })
+    }
+
+    fn snapshot(sessions: &[(&str, u32, i32, usize)]) -> SnapShot {
+        let ss = SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g"));
+        for (id, priority, pending, bound) in sessions {
+            ss.add_session(session(id, *priority, *pending)).unwrap();
+            for i in 0..*bound {
+                ss.add_executor(Arc::new(ExecutorInfo {
+                    id: format!("{id}-exec-{i}"),
+                    node: "node-1".to_string(),
+                    slots: 1,
+                    ssn_id: Some(id.to_string().into()),
+                    creation_time: Utc::now(),
+                    state: ExecutorState::Bound,
+                    ..ExecutorInfo::default()
+                }))
+                .unwrap();
+            }
+        }
+
+        ss
+    }
+
+    fn setup(ss: &SnapShot) -> PriorityPlugin {
+        let mut plugin = PriorityPlugin {
+            ssn_map: HashMap::new(),
+        };
+        plugin.setup(ss).unwrap();
+        plugin
+    }
+
+    #[test]
+    fn test_ssn_order() {
+        let plugin = setup(&snapshot(&[]));
+
+        let high = session("high", 10, 1);
+        let low = session("low", 1, 1);
+        assert_eq!(plugin.ssn_order_fn(&high, &low), Some(Ordering::Greater));
+        assert_eq!(plugin.ssn_order_fn(&low, &high), Some(Ordering::Less));
+        assert_eq!(
+            plugin.ssn_order_fn(&low, &session("other", 1, 1)),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_reclaim_from_lower_priority() {
+        // The low priority session holds all the executors.
+        let ss = snapshot(&[("high", 10, 2, 0), ("low", 1, 100, 4)]);
+        let mut plugin = setup(&ss);
+
+        let high = session("high", 10, 2);
+        let low = session("low", 1, 100);
+
+        assert_eq!(plugin.is_underused(&high), Some(true));
+        assert_eq!(plugin.is_underused(&low), None);
+        assert_eq!(plugin.is_preemptible_for(&low, &high), Some(true));
+        assert_eq!(plugin.is_preemptible_for(&high, &low), Some(false));
+
+        // The high priority session has the instances it desires.
+        plugin.on_session_unbind(low.clone());
+        plugin.on_session_bind(high.clone());
+        plugin.on_session_unbind(low.clone());
+        plugin.on_session_bind(high.clone());
+        assert_eq!(plugin.is_underused(&high), None);
+    }
+
+    #[test]
+    fn test_same_priority() {
+        let ss = snapshot(&[("ssn-1", 1, 2, 0), ("ssn-2", 1, 100, 4)]);
+        let plugin = setup(&ss);
+
+        let ssn_1 = session("ssn-1", 1, 2);
+        let ssn_2 = session("ssn-2", 1, 100);
+        assert_eq!(plugin.is_underused(&ssn_1), None);
+        assert_eq!(plugin.is_preemptible_for(&ssn_2, &ssn_1), Some(false));
+    }
+
+    #[test]
+    fn test_slots_mismatch() {
+        let plugin = setup(&snapshot(&[]));
+
+        let high = Arc::new(SessionInfo {
+            slots: 2,
+            ..(*session("high", 10, 1)).clone()
+        });
+        assert_eq!(
+            plugin.is_preemptible_for(&session("low", 1, 1), &high),
+            Some(false)
+        );
+    }
+}
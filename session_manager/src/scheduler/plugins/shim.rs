@@ -123,13 +123,16 @@ mod tests {
             name: name.to_string(),
             shim,
             max_instances: 100,
+            max_instances_per_node: None,
             delay_release: Duration::seconds(60),
+            fairness: Default::default(),
+            topology: Default::default(),
         })
     }
 
     fn create_session_info(id: &str, app: &str) -> SessionInfoPtr {
         Arc::new(SessionInfo {
-            id: id.to_string(),
+            id: id.to_string().into(),
             application: app.to_string(),
             slots: 1,
             tasks_status: [(TaskState::Pending, 1)].into_iter().collect(),
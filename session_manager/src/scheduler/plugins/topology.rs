@@ -0,0 +1,265 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Topology plugin for placing the instances of the topology-aware applications.
+//!
+//! The instance of a `NumaAligned` application takes a NUMA node of its node,
+//! and the one of a `GpuAligned` application takes a GPU group, so the
+//! performance-sensitive instances don't contend across the sockets and the
+//! PCIe switches. The executors of these applications are only placed on the
+//! nodes with a free NUMA node or GPU group by their topology; the executor
+//! manager assigns the concrete one when the executor binds, and rejects the
+//! binding if none is free, e.g. the view of the scheduler is stale.
+
+use std::collections::HashMap;
+
+use crate::model::{
+    ExecutorInfoPtr, NodeInfoPtr, SessionInfoPtr, SnapShot, ALL_APPLICATION, ALL_EXECUTOR, ALL_NODE,
+};
+use crate::scheduler::plugins::{Plugin, PluginPtr};
+use common::apis::{SessionID, TopologyPolicy};
+use common::FlameError;
+
+/// The NUMA nodes and the GPU groups of a node.
+#[derive(Clone, Copy, Debug, Default)]
+struct Domains {
+    numa_nodes: u32,
+    gpu_groups: u32,
+}
+
+impl Domains {
+    fn get_mut(&mut self, policy: TopologyPolicy) -> Option<&mut u32> {
+        match policy {
+            TopologyPolicy::Ignore => None,
+            TopologyPolicy::NumaAligned => Some(&mut self.numa_nodes),
+            TopologyPolicy::GpuAligned => Some(&mut self.gpu_groups),
+        }
+    }
+
+    fn get(mut self, policy: TopologyPolicy) -> u32 {
+        self.get_mut(policy).copied().unwrap_or_default()
+    }
+}
+
+/// Topology plugin that filters the nodes without a free NUMA node or GPU group.
+pub struct TopologyPlugin {
+    /// Map from application name to its topology policy, if topology-aware
+    policies: HashMap<String, TopologyPolicy>,
+    /// Map from session ID to its application
+    ssn_apps: HashMap<SessionID, String>,
+    /// Map from node name to its NUMA nodes and GPU groups
+    domains: HashMap<String, Domains>,
+    /// Map from node name to its NUMA nodes and GPU groups taken by the
+    /// executors, including the pipelined ones
+    taken: HashMap<String, Domains>,
+}
+
+impl TopologyPlugin {
+    pub fn new_ptr() -> PluginPtr {
+        Box::new(TopologyPlugin {
+            policies: HashMap::new(),
+            ssn_apps: HashMap::new(),
+            domains: HashMap::new(),
+            taken: HashMap::new(),
+        })
+    }
+
+    fn policy(&self, app: &str) -> Option<TopologyPolicy> {
+        self.policies.get(app).copied()
+    }
+
+    fn has_free(&self, node: &str, policy: TopologyPolicy) -> bool {
+        let total = self.domains.get(node).copied().unwrap_or_default();
+        let taken = self.taken.get(node).copied().unwrap_or_default();
+        taken.get(policy) < total.get(policy)
+    }
+
+    fn take(&mut self, node: &str, policy: TopologyPolicy) {
+        if let Some(count) = self
+            .taken
+            .entry(node.to_string())
+            .or_default()
+            .get_mut(policy)
+        {
+            *count += 1;
+        }
+    }
+}
+
+impl Plugin for TopologyPlugin {
+    fn setup(&mut self, ss: &SnapShot) -> Result<(), FlameError> {
+        self.policies = ss
+            .find_applications(ALL_APPLICATION)?
+            .values()
+            .filter(|app| app.topology != TopologyPolicy::Ignore)
+            .map(|app| (app.name.clone(), app.topology))
+            .collect();
+
+        self.ssn_apps = ss
+            .find_sessions(None)?
+            .values()
+            .map(|ssn| (ssn.id.clone(), ssn.application.clone()))
+            .collect();
+
+        self.domains = ss
+            .find_nodes(ALL_NODE)?
+            .values()
+            .map(|node| {
+                (
+                    node.name.clone(),
+                    Domains {
+                        numa_nodes: node.numa_nodes,
+                        gpu_groups: node.gpu_groups,
+                    },
+                )
+            })
+            .collect();
+
+        self.taken = HashMap::new();
+        for exec in ss.find_executors(ALL_EXECUTOR)?.values() {
+            let policy = exec
+                .ssn_id
+                .as_ref()
+                .and_then(|id| self.ssn_apps.get(id))
+                .and_then(|app| self.policy(app));
+            if let Some(policy) = policy {
+                self.take(&exec.node, policy);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The idle executors are not bound to the topology-aware sessions if their
+    /// node has no free NUMA node or GPU group.
+    fn is_available(&self, exec: &ExecutorInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        let policy = self.policy(&ssn.application)?;
+        Some(self.has_free(&exec.node, policy))
+    }
+
+    /// The executors of the topology-aware sessions are only created on the
+    /// nodes with a free NUMA node or GPU group.
+    fn is_allocatable(&self, node: &NodeInfoPtr, ssn: &SessionInfoPtr) -> Option<bool> {
+        let policy = self.policy(&ssn.application)?;
+        Some(self.has_free(&node.name, policy))
+    }
+
+    fn on_pipeline_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        if let Some(policy) = self.policy(&ssn.application) {
+            self.take(&node.name, policy);
+        }
+    }
+
+    fn on_discard_executor(&mut self, node: NodeInfoPtr, ssn: SessionInfoPtr) {
+        let Some(policy) = self.policy(&ssn.application) else {
+            return;
+        };
+        if let Some(count) = self
+            .taken
+            .get_mut(&node.name)
+            .and_then(|taken| taken.get_mut(policy))
+        {
+            *count = count.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::model::{AppInfo, ExecutorInfo, NodeInfo, SessionInfo};
+    use common::apis::ResourceRequirement;
+
+    fn create_session_info(id: &str, app: &str) -> SessionInfoPtr {
+        Arc::new(SessionInfo {
+            id: id.to_string().into(),
+            application: app.to_string(),
+            slots: 1,
+            ..Default::default()
+        })
+    }
+
+    fn create_node_info(name: &str, numa_nodes: u32, gpu_groups: u32) -> NodeInfoPtr {
+        Arc::new(NodeInfo {
+            name: name.to_string(),
+            numa_nodes,
+            gpu_groups,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_topology_plugin() {
+        let ss = SnapShot::new(ResourceRequirement {
+            cpu: 1,
+            memory: 1024,
+        });
+        for (name, topology) in [
+            ("gpu-app", TopologyPolicy::GpuAligned),
+            ("numa-app", TopologyPolicy::NumaAligned),
+            ("app", TopologyPolicy::Ignore),
+        ] {
+            ss.add_application(Arc::new(AppInfo {
+                name: name.to_string(),
+                topology,
+                ..Default::default()
+            }))
+            .unwrap();
+        }
+        let node1 = create_node_info("node1", 2, 1);
+        let node2 = create_node_info("node2", 1, 0);
+        ss.add_node(node1.clone()).unwrap();
+        ss.add_node(node2.clone()).unwrap();
+
+        let gpu_ssn = create_session_info("ssn-1", "gpu-app");
+        let numa_ssn = create_session_info("ssn-2", "numa-app");
+        let ssn = create_session_info("ssn-3", "app");
+        for s in [&gpu_ssn, &numa_ssn, &ssn] {
+            ss.add_session(s.clone()).unwrap();
+        }
+
+        // The GPU group of node1 is taken by the bound executor.
+        ss.add_executor(Arc::new(ExecutorInfo {
+            id: "exec-1".to_string(),
+            node: "node1".to_string(),
+            ssn_id: Some(gpu_ssn.id.clone()),
+            ..Default::default()
+        }))
+        .unwrap();
+
+        let mut plugin = TopologyPlugin::new_ptr();
+        plugin.setup(&ss).unwrap();
+
+        assert_eq!(plugin.is_allocatable(&node1, &gpu_ssn), Some(false));
+        assert_eq!(plugin.is_allocatable(&node2, &gpu_ssn), Some(false));
+        assert_eq!(plugin.is_allocatable(&node1, &numa_ssn), Some(true));
+        // The topology-unaware sessions are not filtered.
+        assert_eq!(plugin.is_allocatable(&node2, &ssn), None);
+
+        // The pipelined executors take the NUMA nodes until discarded.
+        plugin.on_pipeline_executor(node2.clone(), numa_ssn.clone());
+        assert_eq!(plugin.is_allocatable(&node2, &numa_ssn), Some(false));
+        plugin.on_discard_executor(node2.clone(), numa_ssn.clone());
+        assert_eq!(plugin.is_allocatable(&node2, &numa_ssn), Some(true));
+
+        let exec = Arc::new(ExecutorInfo {
+            id: "exec-2".to_string(),
+            node: "node1".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(plugin.is_available(&exec, &gpu_ssn), Some(false));
+        assert_eq!(plugin.is_available(&exec, &numa_ssn), Some(true));
+    }
+}
@@ -0,0 +1,541 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The simulation of the scheduler with virtual time and synthetic workloads.
+//!
+//! The simulator runs the scheduling cycles of the session manager against the
+//! in-memory storage, and emulates the executor managers of the nodes: the
+//! executors are registered, bound and unbound in the cycle after the scheduler
+//! asks for it, and the tasks "run" for the virtual durations of the workload.
+//! Each cycle advances the virtual clock by `cluster.schedule_interval`, and the
+//! idle periods between the arrivals are skipped, so hours of a workload are
+//! simulated in seconds without any node.
+//!
+//! The policies of the scheduler which depend on the wall clock, e.g. the idle
+//! timeout of the executors, do not follow the virtual time; the delay release
+//! of the applications is emulated by the simulator instead.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use common::apis::{
+    ApplicationAttributes, ExecutorID, ExecutorState, Node, NodeInfo, NodeState,
+    ResourceRequirement, ResultPolicy, SessionAttributes, SessionID, TaskAttributes, TaskID,
+    TaskResult, TaskState,
+};
+use common::ctx::FlameClusterContext;
+use common::FlameError;
+
+use crate::controller::{self, ControllerPtr};
+use crate::model::Executor;
+use crate::scheduler::ctx::Context;
+use crate::scheduler::{ScheduleMetrics, ScheduleRunner};
+use crate::storage;
+
+mod report;
+mod workload;
+
+pub use report::{Latency, Report};
+pub use workload::{Distribution, NodeGroup, SessionGroup, Workload};
+
+use report::jain_index;
+use workload::Arrival;
+
+/// Simulates the workload with the scheduler of the cluster, e.g. its slot,
+/// policy and scheduling interval; the storage of the cluster is not used.
+pub async fn run(mut ctx: FlameClusterContext, workload: &Workload) -> Result<Report, FlameError> {
+    workload.validate()?;
+    ctx.cluster.storage = "none".to_string();
+
+    Simulator::new(ctx, workload).await?.run().await
+}
+
+struct SimSession {
+    slots: u32,
+    arrival: Duration,
+    delay_release: Duration,
+    tasks: usize,
+    completed: usize,
+    completion: Option<Duration>,
+}
+
+/// A task "running" on an emulated executor.
+struct SimTask {
+    id: TaskID,
+    start: Duration,
+    end: Duration,
+}
+
+#[derive(Default)]
+struct SimExecutor {
+    slots: u32,
+    running: VecDeque<SimTask>,
+    /// Since when the bound executor is waiting for the tasks of its session.
+    waiting_since: Option<Duration>,
+}
+
+struct Simulator<'a> {
+    workload: &'a Workload,
+    controller: ControllerPtr,
+    runner: ScheduleRunner,
+    /// The enabled plugins of the scheduler.
+    plugins: Vec<String>,
+    tick: Duration,
+    now: Duration,
+    cluster_slots: u32,
+    arrivals: VecDeque<Arrival>,
+    sessions: HashMap<SessionID, SimSession>,
+    /// The creation time and the virtual duration of the tasks not launched yet.
+    tasks: HashMap<(SessionID, TaskID), (Duration, Duration)>,
+    executors: HashMap<ExecutorID, SimExecutor>,
+    queue_latencies: Vec<Duration>,
+    /// The slot-seconds running tasks.
+    busy: f64,
+    fairness: Vec<f64>,
+}
+
+impl<'a> Simulator<'a> {
+    async fn new(ctx: FlameClusterContext, workload: &'a Workload) -> Result<Self, FlameError> {
+        super::validate(&ctx)?;
+        let storage = storage::new_ptr(&ctx).await?;
+        let controller = controller::new_ptr(storage, None, None);
+
+        let slot = ctx.cluster.slot.clone();
+        for i in 0..workload.nodes.count {
+            let resreq = ResourceRequirement::new(workload.nodes.slots, &slot);
+            let node = Node {
+                name: format!("sim-node-{i}"),
+                capacity: resreq.clone(),
+                allocatable: resreq,
+                info: NodeInfo {
+                    arch: std::env::consts::ARCH.to_string(),
+                    os: std::env::consts::OS.to_string(),
+                    ..Default::default()
+                },
+                state: NodeState::Ready,
+            };
+            controller.storage().register_node(&node).await?;
+        }
+
+        for group in &workload.sessions {
+            let attr = ApplicationAttributes {
+                // The tasks are launched one by one, and the delay release is
+                // emulated with the virtual time.
+                bundle_size: 1,
+                delay_release: chrono::Duration::zero(),
+                schema: None,
+                ..Default::default()
+            };
+            controller
+                .register_application(group.name.clone(), attr)
+                .await?;
+        }
+
+        Ok(Simulator {
+            workload,
+            runner: ScheduleRunner {
+                controller: controller.clone(),
+                shutdown: CancellationToken::new(),
+                metrics: ScheduleMetrics::default(),
+            },
+            controller,
+            plugins: ctx.cluster.scheduler.plugins.clone(),
+            tick: Duration::from_millis(ctx.cluster.schedule_interval.max(1)),
+            now: Duration::ZERO,
+            cluster_slots: workload.nodes.count * workload.nodes.slots,
+            arrivals: workload.arrivals().into(),
+            sessions: HashMap::new(),
+            tasks: HashMap::new(),
+            executors: HashMap::new(),
+            queue_latencies: vec![],
+            busy: 0.0,
+            fairness: vec![],
+        })
+    }
+
+    async fn run(mut self) -> Result<Report, FlameError> {
+        let end = Duration::from_secs(self.workload.duration);
+
+        while self.now < end {
+            self.arrive().await?;
+
+            let unfinished = self.sessions.values().any(|ssn| ssn.completion.is_none());
+            if !unfinished {
+                // Skip the idle period to the next arrival, or stop after the last one.
+                match self.arrivals.front() {
+                    Some(next) if next.time > self.now => {
+                        self.now = next.time.min(end);
+                        continue;
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+
+            match Context::new(self.controller.clone(), &self.plugins) {
+                Ok(mut ctx) => self.runner.schedule(&mut ctx).await?,
+                Err(e) => {
+                    self.runner.metrics.record_failure("context")?;
+                    tracing::warn!("Failed to build scheduling context: {e}");
+                }
+            }
+
+            self.now += self.tick;
+            self.step().await?;
+        }
+
+        self.report()
+    }
+
+    /// Creates the sessions arrived by now with their tasks.
+    async fn arrive(&mut self) -> Result<(), FlameError> {
+        while self
+            .arrivals
+            .front()
+            .is_some_and(|arrival| arrival.time <= self.now)
+        {
+            let Some(arrival) = self.arrivals.pop_front() else {
+                break;
+            };
+            let group = &self.workload.sessions[arrival.group];
+
+            let id = SessionID::from(format!("{}-{}", group.name, self.sessions.len()));
+            self.controller
+                .create_session(SessionAttributes {
+                    id: id.clone(),
+                    application: group.name.clone(),
+                    slots: group.slots,
+                    common_data: None,
+                    min_instances: group.min_instances,
+                    max_instances: group.max_instances,
+                    batch_size: 1,
+                    scratch_size: None,
+                    max_task_attempts: None,
+                    task_timeout: None,
+                    task_priority: None,
+                    priority: group.priority,
+                    result_policy: ResultPolicy::Inline,
+                    ttl_after_completion: None,
+                    trace_id: None,
+                })
+                .await?;
+
+            for duration in &arrival.tasks {
+                let task = self
+                    .controller
+                    .create_task(id.clone(), TaskAttributes::default())
+                    .await?;
+                self.tasks
+                    .insert((id.clone(), task.id), (arrival.time, *duration));
+            }
+
+            self.sessions.insert(
+                id,
+                SimSession {
+                    slots: group.slots,
+                    arrival: arrival.time,
+                    delay_release: Duration::from_secs(group.delay_release),
+                    tasks: arrival.tasks.len(),
+                    completed: 0,
+                    completion: None,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Emulates the executor managers for the executors changed by the scheduler.
+    async fn step(&mut self) -> Result<(), FlameError> {
+        let executors = self.controller.list_executor()?;
+        self.sample_fairness(&executors);
+
+        for e in executors {
+            match e.state {
+                ExecutorState::Void => {
+                    self.controller.register_executor(&e).await?;
+                    self.executors.insert(
+                        e.id.clone(),
+                        SimExecutor {
+                            slots: e.slots,
+                            ..Default::default()
+                        },
+                    );
+                }
+                ExecutorState::Binding => {
+                    self.controller.bind_session_completed(e.id.clone()).await?;
+                    self.run_tasks(&e.id).await?;
+                }
+                ExecutorState::Bound => self.run_tasks(&e.id).await?,
+                ExecutorState::Unbinding => {
+                    // The running task completes before the executor is unbound.
+                    self.complete_tasks(&e.id).await?;
+                    if self.is_running(&e.id) {
+                        continue;
+                    }
+                    self.controller
+                        .unbind_executor_completed(e.id.clone())
+                        .await?;
+                    if let Some(exec) = self.executors.get_mut(&e.id) {
+                        exec.waiting_since = None;
+                    }
+                }
+                ExecutorState::Releasing => {
+                    self.controller
+                        .unregister_executor(e.id.clone(), None)
+                        .await?;
+                    self.executors.remove(&e.id);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_running(&self, id: &ExecutorID) -> bool {
+        self.executors
+            .get(id)
+            .is_some_and(|exec| !exec.running.is_empty())
+    }
+
+    /// Completes the due tasks of the executor, and launches the next ones of
+    /// its session; it's unbound once its session has no task for it.
+    async fn run_tasks(&mut self, id: &ExecutorID) -> Result<(), FlameError> {
+        let mut start = self.complete_tasks(id).await?.unwrap_or(self.now);
+        if self.is_running(id) {
+            return Ok(());
+        }
+
+        let Some(ssn_id) = self.controller.get_executor(id.clone())?.ssn_id else {
+            return Ok(());
+        };
+        let ssn = self.controller.get_session(ssn_id.clone())?;
+        let pending = ssn
+            .tasks_index
+            .get(&TaskState::Pending)
+            .map(|tasks| tasks.len())
+            .unwrap_or(0);
+
+        let exec = self.executors.entry(id.clone()).or_default();
+        if pending == 0 {
+            // Wait for the next task of the session within its delay release.
+            let waiting_since = *exec.waiting_since.get_or_insert(self.now);
+            let delay_release = self
+                .sessions
+                .get(&ssn_id)
+                .map(|ssn| ssn.delay_release)
+                .unwrap_or_default();
+            if self.now.saturating_sub(waiting_since) >= delay_release {
+                exec.waiting_since = None;
+                self.controller.unbind_executor(id.clone()).await?;
+            }
+            return Ok(());
+        }
+        exec.waiting_since = None;
+
+        for task in self.controller.launch_task(id.clone()).await? {
+            let Some((creation, duration)) = self.tasks.remove(&(ssn_id.clone(), task.id)) else {
+                continue;
+            };
+            self.queue_latencies.push(start.saturating_sub(creation));
+
+            let end = start + duration;
+            exec.running.push_back(SimTask {
+                id: task.id,
+                start,
+                end,
+            });
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// Completes the tasks of the executor which ended by now; returns when the
+    /// last one ended, if any.
+    async fn complete_tasks(&mut self, id: &ExecutorID) -> Result<Option<Duration>, FlameError> {
+        let Some(exec) = self.executors.get_mut(id) else {
+            return Ok(None);
+        };
+        let Some(ssn_id) = self.controller.get_executor(id.clone())?.ssn_id else {
+            return Ok(None);
+        };
+
+        let mut last_end = None;
+        while exec
+            .running
+            .front()
+            .is_some_and(|task| task.end <= self.now)
+        {
+            let Some(task) = exec.running.pop_front() else {
+                break;
+            };
+            self.controller
+                .complete_task(
+                    id.clone(),
+                    Some(task.id),
+                    TaskResult {
+                        state: TaskState::Succeed,
+                        output: None,
+                        output_ref: None,
+                        message: None,
+                        artifacts: vec![],
+                        failure_reason: None,
+                        usage: None,
+                        postmortem: None,
+                        codec: None,
+                    },
+                )
+                .await?;
+            self.busy += exec.slots as f64 * (task.end - task.start).as_secs_f64();
+            last_end = Some(task.end);
+
+            let Some(ssn) = self.sessions.get_mut(&ssn_id) else {
+                continue;
+            };
+            ssn.completed += 1;
+            if ssn.completed == ssn.tasks {
+                ssn.completion = Some(task.end);
+                self.controller.close_session(ssn_id.clone(), false).await?;
+            }
+        }
+
+        Ok(last_end)
+    }
+
+    /// Samples the fairness of the slots of the backlogged sessions.
+    fn sample_fairness(&mut self, executors: &[Executor]) {
+        let mut allocated: HashMap<&SessionID, u32> = HashMap::new();
+        for e in executors {
+            if let (Some(ssn_id), ExecutorState::Binding | ExecutorState::Bound) =
+                (&e.ssn_id, e.state)
+            {
+                *allocated.entry(ssn_id).or_default() += 1;
+            }
+        }
+
+        let backlogged: Vec<f64> = self
+            .sessions
+            .iter()
+            .filter(|(_, ssn)| ssn.completion.is_none())
+            .filter_map(|(id, ssn)| {
+                let executors = allocated.get(id).copied().unwrap_or(0);
+                let unfinished = (ssn.tasks - ssn.completed) as u32;
+                (unfinished > executors).then_some((executors * ssn.slots) as f64)
+            })
+            .collect();
+
+        if let Some(index) = jain_index(&backlogged) {
+            self.fairness.push(index);
+        }
+    }
+
+    fn report(&self) -> Result<Report, FlameError> {
+        let elapsed = self.now.as_secs_f64();
+
+        // The tasks still running are counted until now.
+        let running: f64 = self
+            .executors
+            .values()
+            .flat_map(|exec| {
+                exec.running.iter().map(|task| {
+                    exec.slots as f64 * self.now.saturating_sub(task.start).as_secs_f64()
+                })
+            })
+            .sum();
+        let capacity = self.cluster_slots as f64 * elapsed;
+
+        let makespans: Vec<Duration> = self
+            .sessions
+            .values()
+            .filter_map(|ssn| ssn.completion.map(|end| end.saturating_sub(ssn.arrival)))
+            .collect();
+
+        Ok(Report {
+            elapsed,
+            cycles: self.runner.metrics.cycles.load(Ordering::Relaxed),
+            failures: self.runner.metrics.failures()?,
+            sessions: self.sessions.len(),
+            completed_sessions: makespans.len(),
+            tasks: self.sessions.values().map(|ssn| ssn.tasks).sum(),
+            completed_tasks: self.sessions.values().map(|ssn| ssn.completed).sum(),
+            utilization: if capacity > 0.0 {
+                ((self.busy + running) / capacity).min(1.0)
+            } else {
+                0.0
+            },
+            fairness: (!self.fairness.is_empty())
+                .then(|| self.fairness.iter().sum::<f64>() / self.fairness.len() as f64),
+            queue_latency: Latency::new(&self.queue_latencies),
+            makespan: Latency::new(&makespans),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::ctx::FlameCluster;
+
+    #[test]
+    fn test_simulation() -> Result<(), FlameError> {
+        let workload: Workload = serde_yaml::from_str(
+            r#"
+seed: 1
+duration: 600
+nodes:
+  count: 2
+  slots: 2
+sessions:
+  - name: sim-short
+    count: 4
+    arrival: { type: exponential, mean: 5 }
+    tasks: { type: constant, value: 4 }
+    duration: { type: uniform, min: 1, max: 3 }
+  - name: sim-long
+    count: 1
+    arrival: { type: constant, value: 0 }
+    tasks: { type: constant, value: 2 }
+    duration: { type: constant, value: 20 }
+"#,
+        )
+        .map_err(|e| FlameError::InvalidConfig(e.to_string()))?;
+
+        let ctx = FlameClusterContext {
+            cluster: FlameCluster {
+                schedule_interval: 500,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let report = tokio_test::block_on(run(ctx, &workload))?;
+
+        assert_eq!(report.sessions, 5);
+        assert_eq!(report.completed_sessions, 5);
+        assert_eq!(report.tasks, 18);
+        assert_eq!(report.completed_tasks, 18);
+        assert!(report.cycles > 0);
+        assert!(report.utilization > 0.0 && report.utilization <= 1.0);
+        assert_eq!(report.queue_latency.count, 18);
+        // The long tasks run for 20s at least.
+        assert!(report.makespan.max >= 20.0);
+        assert!(report.elapsed < 600.0);
+
+        Ok(())
+    }
+}
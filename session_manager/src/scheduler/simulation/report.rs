@@ -0,0 +1,148 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// The report of a simulation; the times are virtual seconds.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+    pub elapsed: f64,
+    pub cycles: u64,
+    /// The failed actions of the scheduling cycles.
+    pub failures: u64,
+    pub sessions: usize,
+    pub completed_sessions: usize,
+    pub tasks: usize,
+    pub completed_tasks: usize,
+    /// The fraction of the slots of the cluster running tasks.
+    pub utilization: f64,
+    /// The mean Jain's index of the slots of the backlogged sessions, i.e. the
+    /// ones with more unfinished tasks than executors; 1 is perfectly fair.
+    pub fairness: Option<f64>,
+    /// The time from the creation of the tasks to their launch.
+    pub queue_latency: Latency,
+    /// The time from the arrival of the sessions to their last completed task.
+    pub makespan: Latency,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Latency {
+    pub count: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Latency {
+    pub fn new(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Latency::default();
+        }
+
+        let mut samples: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        samples.sort_by(f64::total_cmp);
+
+        // The nearest-rank percentile.
+        let percentile = |p: f64| {
+            let rank = (p * samples.len() as f64).ceil() as usize;
+            samples[rank.clamp(1, samples.len()) - 1]
+        };
+
+        Latency {
+            count: samples.len(),
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Latency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "count={}, mean={:.2}s, p50={:.2}s, p95={:.2}s, max={:.2}s",
+            self.count, self.mean, self.p50, self.p95, self.max
+        )
+    }
+}
+
+/// Jain's fairness index of the allocations, i.e. (Σx)² / (n·Σx²); None if
+/// there are less than 2 allocations to compare.
+pub fn jain_index(allocations: &[f64]) -> Option<f64> {
+    if allocations.len() < 2 {
+        return None;
+    }
+
+    let sum: f64 = allocations.iter().sum();
+    let squares: f64 = allocations.iter().map(|x| x * x).sum();
+    if squares == 0.0 {
+        return Some(1.0);
+    }
+
+    Some(sum * sum / (allocations.len() as f64 * squares))
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16}{:.1}s", "Elapsed:", self.elapsed)?;
+        writeln!(
+            f,
+            "{:<16}{} ({} failures)",
+            "Cycles:", self.cycles, self.failures
+        )?;
+        writeln!(
+            f,
+            "{:<16}{}/{} completed",
+            "Sessions:", self.completed_sessions, self.sessions
+        )?;
+        writeln!(
+            f,
+            "{:<16}{}/{} completed",
+            "Tasks:", self.completed_tasks, self.tasks
+        )?;
+        writeln!(f, "{:<16}{:.1}%", "Utilization:", self.utilization * 100.0)?;
+        match self.fairness {
+            Some(fairness) => writeln!(f, "{:<16}{:.3}", "Fairness:", fairness)?,
+            None => writeln!(f, "{:<16}-", "Fairness:")?,
+        }
+        writeln!(f, "{:<16}{}", "Queue latency:", self.queue_latency)?;
+        write!(f, "{:<16}{}", "Makespan:", self.makespan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_and_fairness() {
+        let samples: Vec<Duration> = (1..=20).map(Duration::from_secs).collect();
+        let latency = Latency::new(&samples);
+        assert_eq!(latency.count, 20);
+        assert_eq!(latency.mean, 10.5);
+        assert_eq!(latency.p50, 10.0);
+        assert_eq!(latency.p95, 19.0);
+        assert_eq!(latency.max, 20.0);
+        assert_eq!(Latency::new(&[]), Latency::default());
+
+        assert_eq!(jain_index(&[4.0]), None);
+        assert_eq!(jain_index(&[4.0, 4.0, 4.0]), Some(1.0));
+        assert_eq!(jain_index(&[4.0, 0.0]), Some(0.5));
+    }
+}
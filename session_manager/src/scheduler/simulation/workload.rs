@@ -0,0 +1,253 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::path::Path;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use common::FlameError;
+
+const DEFAULT_DURATION: u64 = 3600;
+
+/// The synthetic workload of a simulation, e.g.
+///
+/// ```yaml
+/// seed: 42
+/// duration: 3600
+/// nodes:
+///   count: 4
+///   slots: 8
+/// sessions:
+///   - name: short
+///     count: 20
+///     arrival: { type: exponential, mean: 10 }
+///     tasks: { type: uniform, min: 10, max: 50 }
+///     duration: { type: exponential, mean: 2 }
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+pub struct Workload {
+    /// The seed of the random samples, so the simulations are reproducible.
+    #[serde(default)]
+    pub seed: u64,
+    /// The virtual seconds to simulate at most.
+    #[serde(default = "default_duration")]
+    pub duration: u64,
+    pub nodes: NodeGroup,
+    pub sessions: Vec<SessionGroup>,
+}
+
+/// The nodes of the simulated cluster, all of the same size.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NodeGroup {
+    pub count: u32,
+    /// The allocatable slots of each node, see `cluster.slot`.
+    pub slots: u32,
+}
+
+/// The sessions of an application, which arrive by the arrival distribution.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SessionGroup {
+    /// The name of the application of the sessions.
+    pub name: String,
+    pub count: u32,
+    #[serde(default = "default_slots")]
+    pub slots: u32,
+    #[serde(default)]
+    pub min_instances: u32,
+    pub max_instances: Option<u32>,
+    /// The priority of the sessions in scheduling, see `SessionAttributes::priority`.
+    #[serde(default)]
+    pub priority: u32,
+    /// The seconds between the arrivals of the sessions.
+    pub arrival: Distribution,
+    /// The number of the tasks of each session.
+    pub tasks: Distribution,
+    /// The seconds each task runs.
+    pub duration: Distribution,
+    /// The seconds an executor waits for the next task of its session before
+    /// it's unbound, see `Application::delay_release`.
+    #[serde(default)]
+    pub delay_release: u64,
+}
+
+/// The distribution of the samples of a workload, in seconds or counts.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Distribution {
+    Constant { value: f64 },
+    Uniform { min: f64, max: f64 },
+    Exponential { mean: f64 },
+}
+
+impl Distribution {
+    pub fn sample(&self, rng: &mut impl Rng) -> f64 {
+        match self {
+            Distribution::Constant { value } => *value,
+            Distribution::Uniform { min, max } if min < max => rng.random_range(*min..*max),
+            Distribution::Uniform { min, .. } => *min,
+            // The inverse transform of the uniform sample.
+            Distribution::Exponential { mean } => -mean * (1.0 - rng.random::<f64>()).ln(),
+        }
+        .max(0.0)
+    }
+
+    fn validate(&self, name: &str) -> Result<(), FlameError> {
+        let valid = match self {
+            Distribution::Constant { value } => value.is_finite() && *value >= 0.0,
+            Distribution::Uniform { min, max } => min.is_finite() && *min >= 0.0 && max >= min,
+            Distribution::Exponential { mean } => mean.is_finite() && *mean > 0.0,
+        };
+        if !valid {
+            return Err(FlameError::InvalidConfig(format!(
+                "invalid distribution <{name}>: {self:?}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// A session of the workload with the virtual time it arrives.
+#[derive(Clone, Debug)]
+pub struct Arrival {
+    pub time: Duration,
+    pub group: usize,
+    /// The virtual durations of its tasks.
+    pub tasks: Vec<Duration>,
+}
+
+impl Workload {
+    pub fn from_file(path: &Path) -> Result<Self, FlameError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            FlameError::InvalidConfig(format!("failed to read <{}>: {e}", path.display()))
+        })?;
+        let workload: Workload = serde_yaml::from_str(&contents).map_err(|e| {
+            FlameError::InvalidConfig(format!("invalid workload <{}>: {e}", path.display()))
+        })?;
+        workload.validate()?;
+
+        Ok(workload)
+    }
+
+    pub fn validate(&self) -> Result<(), FlameError> {
+        if self.nodes.count == 0 || self.nodes.slots == 0 {
+            return Err(FlameError::InvalidConfig(
+                "the workload has no node slots".to_string(),
+            ));
+        }
+
+        for group in &self.sessions {
+            if group.slots == 0 {
+                return Err(FlameError::InvalidConfig(format!(
+                    "the slots of sessions <{}> must be positive",
+                    group.name
+                )));
+            }
+            group.arrival.validate(&format!("{}.arrival", group.name))?;
+            group.tasks.validate(&format!("{}.tasks", group.name))?;
+            group
+                .duration
+                .validate(&format!("{}.duration", group.name))?;
+        }
+
+        Ok(())
+    }
+
+    /// Samples the sessions of the workload by their arrival time.
+    pub fn arrivals(&self) -> Vec<Arrival> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let mut arrivals = vec![];
+        for (i, group) in self.sessions.iter().enumerate() {
+            let mut time = 0.0;
+            for _ in 0..group.count {
+                time += group.arrival.sample(&mut rng);
+                let tasks = (group.tasks.sample(&mut rng).round() as usize).max(1);
+                arrivals.push(Arrival {
+                    time: Duration::from_secs_f64(time),
+                    group: i,
+                    tasks: (0..tasks)
+                        .map(|_| Duration::from_secs_f64(group.duration.sample(&mut rng)))
+                        .collect(),
+                });
+            }
+        }
+        arrivals.sort_by_key(|arrival| arrival.time);
+
+        arrivals
+    }
+}
+
+fn default_duration() -> u64 {
+    DEFAULT_DURATION
+}
+
+fn default_slots() -> u32 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKLOAD: &str = r#"
+seed: 7
+nodes:
+  count: 2
+  slots: 4
+sessions:
+  - name: short
+    count: 3
+    arrival: { type: constant, value: 5 }
+    tasks: { type: uniform, min: 2, max: 4 }
+    duration: { type: exponential, mean: 1 }
+  - name: long
+    count: 1
+    slots: 2
+    arrival: { type: constant, value: 1 }
+    tasks: { type: constant, value: 3 }
+    duration: { type: constant, value: 10 }
+"#;
+
+    #[test]
+    fn test_workload_arrivals() {
+        let workload: Workload = serde_yaml::from_str(WORKLOAD).unwrap();
+        workload.validate().unwrap();
+        assert_eq!(workload.duration, DEFAULT_DURATION);
+        assert_eq!(workload.sessions[1].slots, 2);
+
+        let arrivals = workload.arrivals();
+        let times: Vec<u64> = arrivals.iter().map(|a| a.time.as_secs()).collect();
+        assert_eq!(times, vec![1, 5, 10, 15]);
+        assert_eq!(arrivals[0].tasks, vec![Duration::from_secs(10); 3]);
+        assert!(arrivals[1..]
+            .iter()
+            .all(|a| a.group == 0 && (2..=4).contains(&a.tasks.len())));
+
+        // The same seed samples the same workload.
+        let again = workload.arrivals();
+        assert_eq!(arrivals[1].tasks, again[1].tasks);
+
+        let invalid = Workload {
+            nodes: NodeGroup { count: 0, slots: 4 },
+            ..workload.clone()
+        };
+        assert!(invalid.validate().is_err());
+        let mut invalid = workload;
+        invalid.sessions[0].duration = Distribution::Exponential { mean: 0.0 };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,158 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The state of the scheduler plugins at the end of a scheduling cycle, i.e.
+//! the order of the open sessions, their shares by fairshare and the verdicts
+//! of each plugin on them; it's recorded by the controller for
+//! `flmctl debug scheduler`.
+
+use chrono::{DateTime, Utc};
+use rpc::flame::v1 as rpc;
+use stdng::lock_ptr;
+
+use crate::model::{SnapShot, OPEN_SESSION};
+use crate::scheduler::plugins::PluginManager;
+use common::FlameError;
+
+pub fn scheduler_state(
+    ss: &SnapShot,
+    plugins: &PluginManager,
+    now: DateTime<Utc>,
+) -> Result<rpc::SchedulerState, FlameError> {
+    let mut ssns: Vec<_> = ss.find_sessions(OPEN_SESSION)?.into_values().collect();
+    // The sessions of the same order are listed by their IDs.
+    ssns.sort_by(|s1, s2| s1.id.cmp(&s2.id));
+    ssns.sort_by(|s1, s2| plugins.ssn_order_fn(s2, s1));
+
+    let names = {
+        let enabled = lock_ptr!(plugins.plugins)?;
+        enabled.iter().map(|(name, _)| name.clone()).collect()
+    };
+
+    let mut sessions = Vec::with_capacity(ssns.len());
+    for (rank, ssn) in ssns.iter().enumerate() {
+        let (verdicts, share) = {
+            let enabled = lock_ptr!(plugins.plugins)?;
+            let verdicts = enabled
+                .iter()
+                .map(|(name, plugin)| rpc::PluginVerdict {
+                    plugin: name.clone(),
+                    underused: plugin.is_underused(ssn),
+                    preemptible: plugin.is_preemptible(ssn),
+                    ready: plugin.is_ready(ssn),
+                })
+                .collect();
+            let share = enabled.iter().find_map(|(_, plugin)| plugin.share(ssn));
+            (verdicts, share)
+        };
+
+        sessions.push(rpc::SessionSchedulingState {
+            session_id: ssn.id.to_string(),
+            rank: rank as u32,
+            share: share.map(|share| rpc::SessionShare {
+                desired: share.desired,
+                deserved: share.deserved,
+                allocated: share.allocated,
+                elastic: share.elastic,
+            }),
+            verdicts,
+            underused: plugins.is_underused(ssn)?,
+            preemptible: plugins.is_preemptible(ssn)?,
+            ready: plugins.is_ready(ssn)?,
+        });
+    }
+
+    Ok(rpc::SchedulerState {
+        creation_time: now.timestamp_millis(),
+        plugins: names,
+        sessions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::model::{AppInfo, NodeInfo, SessionInfo};
+    use common::apis::{NodeState, ResourceRequirement, SessionState, TaskState};
+    use common::ctx::FlameScheduler;
+
+    fn session(id: &str, priority: u32, pending: i32) -> Arc<SessionInfo> {
+        Arc::new(SessionInfo {
+            id: id.to_string().into(),
+            application: "app".to_string(),
+            slots: 1,
+            tasks_status: HashMap::from([(TaskState::Pending, pending)]),
+            creation_time: Utc::now(),
+            state: SessionState::Open,
+            batch_size: 1,
+            priority,
+            ..SessionInfo::default()
+        })
+    }
+
+    #[test]
+    fn test_scheduler_state() {
+        let ss = SnapShot::new(ResourceRequirement::from("cpu=1,mem=1g"));
+        ss.add_application(Arc::new(AppInfo {
+            name: "app".to_string(),
+            max_instances: 10,
+            ..Default::default()
+        }))
+        .unwrap();
+        ss.add_node(Arc::new(NodeInfo {
+            name: "node-1".to_string(),
+            allocatable: ResourceRequirement::from("cpu=4,mem=4g"),
+            state: NodeState::Ready,
+            ..Default::default()
+        }))
+        .unwrap();
+        ss.add_session(session("ssn-1", 0, 2)).unwrap();
+        ss.add_session(session("ssn-2", 10, 2)).unwrap();
+
+        let names = FlameScheduler::default().plugins;
+        let plugins = PluginManager::setup(&ss, &names).unwrap();
+        let now = Utc::now();
+        let state = scheduler_state(&ss, &plugins, now).unwrap();
+
+        assert_eq!(state.creation_time, now.timestamp_millis());
+        assert_eq!(state.plugins, names);
+
+        // The session of the higher priority is scheduled first.
+        let ids: Vec<_> = state
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["ssn-2", "ssn-1"]);
+        assert_eq!(state.sessions[0].rank, 0);
+        assert_eq!(state.sessions[1].rank, 1);
+
+        // Both sessions deserve the slots they desire on the node.
+        let ssn = &state.sessions[0];
+        let share = ssn.share.as_ref().unwrap();
+        assert_eq!(share.desired, 2.0);
+        assert_eq!(share.deserved, 2.0);
+        assert_eq!(share.allocated, 0.0);
+        assert!(ssn.underused);
+        assert_eq!(ssn.verdicts.len(), names.len());
+        let fairshare = ssn
+            .verdicts
+            .iter()
+            .find(|v| v.plugin == "fairshare")
+            .unwrap();
+        assert_eq!(fairshare.underused, Some(true));
+    }
+}
@@ -73,6 +73,8 @@ impl Statement {
 
             let exec_info = ExecutorInfo::from(&executor);
             self.snapshot.add_executor(Arc::new(exec_info))?;
+            // The new executor is not bound yet, count it as an instance of the session.
+            self.snapshot.add_instance(&op.ssn)?;
         }
         Ok(())
     }
@@ -0,0 +1,65 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::path::Path;
+
+use clap::Parser;
+
+use common::ctx::FlameClusterContext;
+use common::FlameError;
+use flame_session_manager::scheduler::simulation::{self, Workload};
+
+#[derive(Parser)]
+#[command(name = "flame-simulator")]
+#[command(author = "Klaus Ma <klaus@xflops.cn>")]
+#[command(version = "0.5.0")]
+#[command(about = "Simulate the Flame scheduler with synthetic workloads", long_about = None)]
+struct Cli {
+    /// The configuration of the cluster, e.g. its slot and scheduling interval;
+    /// the defaults if not set.
+    #[arg(long)]
+    config: Option<String>,
+    /// The synthetic workload to simulate.
+    #[arg(long)]
+    workload: String,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), FlameError> {
+    let cli = Cli::parse();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    let ctx = match cli.config {
+        Some(config) => FlameClusterContext::from_file(Some(config))?,
+        None => FlameClusterContext::default(),
+    };
+    let workload = Workload::from_file(Path::new(&cli.workload))?;
+
+    let report = simulation::run(ctx, &workload).await?;
+    if cli.json {
+        let report = serde_json::to_string_pretty(&report)
+            .map_err(|e| FlameError::Internal(format!("failed to encode report: {e}")))?;
+        println!("{report}");
+    } else {
+        println!("{report}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::model::Executor;
+    use crate::storage;
+    use common::apis::{
+        ApplicationAttributes, ApplicationState, ExecutorState, SessionAttributes, SessionState,
+    };
+    use common::ctx::{FlameCluster, FlameClusterContext};
+    use common::FlameError;
+
+    async fn new_bound_executor(storage: &storage::StoragePtr, ssn_id: &str) -> Executor {
+        let exec = storage
+            .create_executor("node-1".to_string(), ssn_id.to_string().into(), None)
+            .await
+            .unwrap();
+        let exec = Executor {
+            ssn_id: Some(ssn_id.to_string().into()),
+            state: ExecutorState::Bound,
+            ..exec
+        };
+        storage.update_executor(&exec).await.unwrap();
+
+        exec
+    }
+
+    #[test]
+    fn test_update_application_recycles_executors() {
+        tokio_test::block_on(async {
+            let ctx = FlameClusterContext {
+                cluster: FlameCluster {
+                    storage: "none".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let storage = storage::new_ptr(&ctx).await.unwrap();
+
+            for app in ["app-1", "app-2"] {
+                storage
+                    .register_application(app.to_string(), ApplicationAttributes::default())
+                    .await
+                    .unwrap();
+                storage
+                    .create_session(SessionAttributes {
+                        id: format!("ssn-{app}").into(),
+                        application: app.to_string(),
+                        slots: 1,
+                        ..SessionAttributes::default()
+                    })
+                    .await
+                    .unwrap();
+            }
+
+            let stale = new_bound_executor(&storage, "ssn-app-1").await;
+            let other = new_bound_executor(&storage, "ssn-app-2").await;
+
+            storage
+                .update_application(
+                    "app-1".to_string(),
+                    ApplicationAttributes {
+                        command: Some("/usr/bin/app-v2".to_string()),
+                        ..ApplicationAttributes::default()
+                    },
+                    None,
+                )
+                .await
+                .unwrap();
+
+            let ss = storage.snapshot().unwrap();
+            assert!(ss.stale_executors.contains(&stale.id));
+            assert!(!ss.stale_executors.contains(&other.id));
+
+            // The executor is not stale anymore once it's unbound.
+            storage
+                .update_executor(&Executor {
+                    ssn_id: None,
+                    state: ExecutorState::Idle,
+                    ..stale
+                })
+                .await
+                .unwrap();
+            assert!(storage.snapshot().unwrap().stale_executors.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_disable_application_rejects_new_sessions() {
+        tokio_test::block_on(async {
+            let ctx = FlameClusterContext {
+                cluster: FlameCluster {
+                    storage: "none".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let storage = storage::new_ptr(&ctx).await.unwrap();
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            let attr = |id: &str| SessionAttributes {
+                id: id.into(),
+                application: "app-1".to_string(),
+                slots: 1,
+                ..SessionAttributes::default()
+            };
+            storage.create_session(attr("ssn-1")).await.unwrap();
+
+            let app = storage
+                .update_application_state("app-1".to_string(), ApplicationState::Disabled)
+                .await
+                .unwrap();
+            assert_eq!(app.state, ApplicationState::Disabled);
+
+            // The new session is rejected, while the existing one is still open.
+            let res = storage.create_session(attr("ssn-2")).await;
+            assert!(matches!(res, Err(FlameError::InvalidState(_))));
+            let ssn = storage.get_session("ssn-1".into()).unwrap();
+            assert_eq!(ssn.status.state, SessionState::Open);
+
+            storage
+                .update_application_state("app-1".to_string(), ApplicationState::Enabled)
+                .await
+                .unwrap();
+            storage.create_session(attr("ssn-2")).await.unwrap();
+        });
+    }
+}
@@ -0,0 +1,85 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::fs;
+
+use common::backup::{self, BackupManifest, StorageLocation, BACKUP_DATA};
+use common::ctx::FlameBackup;
+use common::{flame_home, FlameError};
+
+use crate::storage::{Storage, StoragePtr};
+
+impl Storage {
+    /// Take a backup of the engine into the backup directory, and remove the
+    /// backups beyond the retention.
+    pub async fn backup(&self, config: &FlameBackup) -> Result<PathBuf, FlameError> {
+        // The relative paths are resolved against the installation directory as
+        // the engine does.
+        let location = StorageLocation::from_url(&self.context.cluster.storage, &flame_home())?;
+
+        let root = Path::new(&config.path);
+        let manifest = BackupManifest::new(location.engine, Utc::now());
+        let path = root.join(manifest.name());
+        if path.exists() {
+            return Err(FlameError::AlreadyExist(format!(
+                "backup <{}>",
+                path.display()
+            )));
+        }
+
+        // Write into a hidden directory first, so a partial backup is never listed.
+        let tmp_path = root.join(format!(".{}", manifest.name()));
+        if tmp_path.exists() {
+            fs::remove_dir_all(&tmp_path).await?;
+        }
+        fs::create_dir_all(&tmp_path).await?;
+
+        if let Err(e) = self.engine.backup(&tmp_path.join(BACKUP_DATA)).await {
+            let _ = fs::remove_dir_all(&tmp_path).await;
+            return Err(e);
+        }
+        manifest.save(&tmp_path)?;
+        fs::rename(&tmp_path, &path).await?;
+
+        // The expired backups may be large, which are removed in a blocking thread.
+        let (root, retention) = (root.to_path_buf(), config.retention);
+        let removed = tokio::task::spawn_blocking(move || backup::prune_backups(&root, retention))
+            .await
+            .map_err(|e| FlameError::Internal(format!("Failed to prune backups: {e}")))??;
+        for removed in removed {
+            tracing::info!("Removed expired backup <{}>", removed.display());
+        }
+
+        Ok(path)
+    }
+}
+
+/// Take the backups of the storage periodically.
+pub async fn run(storage: StoragePtr, config: FlameBackup) -> Result<(), FlameError> {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval));
+    // The first tick completes immediately; skip it to not back up at startup.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        match storage.backup(&config).await {
+            Ok(path) => tracing::info!("Backup <{}> was created.", path.display()),
+            Err(e) => tracing::error!("Failed to back up storage: {e}"),
+        }
+    }
+}
@@ -14,6 +14,7 @@ limitations under the License.
 #[cfg(test)]
 mod tests {
     use super::super::derive_events_path;
+    use std::path::Path;
     use tempfile::TempDir;
 
     #[test]
@@ -23,7 +24,7 @@ mod tests {
         let expected = format!("{}/events", temp_path);
 
         std::env::set_var("FLAME_TEST_DIR", &temp_path);
-        assert_eq!(derive_events_path("any"), expected);
+        assert_eq!(derive_events_path(Path::new("any")), expected);
         std::env::remove_var("FLAME_TEST_DIR");
     }
 
@@ -32,6 +33,10 @@ mod tests {
         if std::env::var("FLAME_TEST_DIR").is_ok() {
             return;
         }
-        assert_eq!(derive_events_path("any"), "events");
+        assert_eq!(derive_events_path(Path::new("events")), "events");
+        assert_eq!(
+            derive_events_path(Path::new("/var/lib/flame/events")),
+            "/var/lib/flame/events"
+        );
     }
 }
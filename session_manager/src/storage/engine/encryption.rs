@@ -0,0 +1,766 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Encryption at rest of the payloads in the storage engine.
+//!
+//! The `EncryptedEngine` wraps another engine and encrypts task input/output,
+//! the stderr of the task postmortems and session common data before they are
+//! stored, so neither the database, the filesystem blobs nor their backups
+//! contain plaintext payloads.
+//!
+//! Each payload is encrypted by a random data key with AES-256-GCM, and the
+//! data key is wrapped by the active key of the cluster (envelope encryption).
+//! The id of the wrapping key is stored in the envelope, so the payloads
+//! encrypted before a key rotation are still decrypted as long as the retired
+//! key is kept in the configuration. Payloads without an envelope, e.g. stored
+//! before encryption was enabled, are returned as is.
+//!
+//! Envelope layout:
+//! `magic(4) | key_id_len(1) | key_id | key_nonce(12) | wrapped_key(48) | nonce(12) | ciphertext`
+//!
+//! The envelopes of the text fields, e.g. the stderr, are stored in base64.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::model::Executor;
+use crate::FlameError;
+use common::apis::{
+    Application, ApplicationAttributes, ApplicationID, ApplicationState, ExecutorID, ExecutorState,
+    Node, ReplayPolicy, Session, SessionAttributes, SessionID, Task, TaskAttributes, TaskGID,
+    TaskPostmortem, TaskResult, TaskState,
+};
+use common::ctx::FlameEncryption;
+
+use super::{Engine, EnginePtr};
+
+const ENVELOPE_MAGIC: &[u8; 4] = b"FLE1";
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+const WRAPPED_KEY_LEN: usize = KEY_LEN + TAG_LEN;
+
+/// The keys of the cluster to wrap/unwrap the data keys.
+pub struct KeyRing {
+    active_key: String,
+    keys: HashMap<String, LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl KeyRing {
+    pub fn load(config: &FlameEncryption) -> Result<Self, FlameError> {
+        let mut keys = HashMap::new();
+        for key in &config.keys {
+            // The length of the key id is a single byte of the envelopes.
+            if key.id.is_empty() || key.id.len() > u8::MAX as usize {
+                return Err(FlameError::InvalidConfig(format!(
+                    "the id of encryption key <{}> must be 1 to {} bytes",
+                    key.id,
+                    u8::MAX
+                )));
+            }
+            let bytes = read_key_file(&key.key_file)?;
+            keys.insert(key.id.clone(), new_key(&bytes)?);
+        }
+
+        if !keys.contains_key(&config.active_key) {
+            return Err(FlameError::InvalidConfig(format!(
+                "active encryption key <{}> not found",
+                config.active_key
+            )));
+        }
+
+        Ok(Self {
+            active_key: config.active_key.clone(),
+            keys,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Result<Bytes, FlameError> {
+        let key = &self.keys[&self.active_key];
+
+        let mut data_key = [0u8; KEY_LEN];
+        self.fill(&mut data_key)?;
+        let key_nonce = self.nonce()?;
+        let nonce = self.nonce()?;
+
+        let mut wrapped_key = data_key.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(key_nonce),
+            Aad::from(self.active_key.as_bytes()),
+            &mut wrapped_key,
+        )
+        .map_err(|_| FlameError::Internal("failed to wrap data key".to_string()))?;
+
+        let mut ciphertext = data.to_vec();
+        new_key(&data_key)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| FlameError::Internal("failed to encrypt payload".to_string()))?;
+
+        let mut envelope = Vec::with_capacity(
+            ENVELOPE_MAGIC.len()
+                + 1
+                + self.active_key.len()
+                + NONCE_LEN
+                + WRAPPED_KEY_LEN
+                + NONCE_LEN
+                + ciphertext.len(),
+        );
+        envelope.extend_from_slice(ENVELOPE_MAGIC);
+        envelope.push(self.active_key.len() as u8);
+        envelope.extend_from_slice(self.active_key.as_bytes());
+        envelope.extend_from_slice(&key_nonce);
+        envelope.extend_from_slice(&wrapped_key);
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&ciphertext);
+
+        Ok(Bytes::from(envelope))
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Bytes, FlameError> {
+        let Some(data) = data.strip_prefix(ENVELOPE_MAGIC) else {
+            return Ok(Bytes::copy_from_slice(data));
+        };
+
+        let invalid = || FlameError::Storage("invalid encrypted payload".to_string());
+
+        let (&key_id_len, data) = data.split_first().ok_or_else(invalid)?;
+        let key_id_len = key_id_len as usize;
+        if data.len() < key_id_len + NONCE_LEN + WRAPPED_KEY_LEN + NONCE_LEN + TAG_LEN {
+            return Err(invalid());
+        }
+        let (key_id, data) = data.split_at(key_id_len);
+        let (key_nonce, data) = data.split_at(NONCE_LEN);
+        let (wrapped_key, data) = data.split_at(WRAPPED_KEY_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let key_id = std::str::from_utf8(key_id).map_err(|_| invalid())?;
+        let key = self.keys.get(key_id).ok_or_else(|| {
+            FlameError::Storage(format!("encryption key <{key_id}> of payload not found"))
+        })?;
+
+        let mut data_key = wrapped_key.to_vec();
+        let data_key = key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(key_nonce).map_err(|_| invalid())?,
+                Aad::from(key_id.as_bytes()),
+                &mut data_key,
+            )
+            .map_err(|_| FlameError::Storage("failed to unwrap data key".to_string()))?;
+
+        let mut plaintext = ciphertext.to_vec();
+        let len = new_key(data_key)?
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid())?,
+                Aad::empty(),
+                &mut plaintext,
+            )
+            .map_err(|_| FlameError::Storage("failed to decrypt payload".to_string()))?
+            .len();
+        plaintext.truncate(len);
+
+        Ok(Bytes::from(plaintext))
+    }
+
+    fn nonce(&self) -> Result<[u8; NONCE_LEN], FlameError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.fill(&mut nonce)?;
+        Ok(nonce)
+    }
+
+    fn fill(&self, buf: &mut [u8]) -> Result<(), FlameError> {
+        self.rng
+            .fill(buf)
+            .map_err(|_| FlameError::Internal("failed to generate random bytes".to_string()))
+    }
+}
+
+fn new_key(bytes: &[u8]) -> Result<LessSafeKey, FlameError> {
+    let key = UnboundKey::new(&AES_256_GCM, bytes)
+        .map_err(|_| FlameError::InvalidConfig("invalid encryption key".to_string()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Reads a 256-bit key from the file, in raw bytes or hex.
+fn read_key_file(path: &str) -> Result<Vec<u8>, FlameError> {
+    let content = fs::read(path)
+        .map_err(|e| FlameError::InvalidConfig(format!("failed to read key_file <{path}>: {e}")))?;
+
+    if content.len() == KEY_LEN {
+        return Ok(content);
+    }
+
+    let hex = String::from_utf8_lossy(&content);
+    let hex = hex.trim();
+    if hex.len() == KEY_LEN * 2 {
+        if let Some(key) = (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+        {
+            return Ok(key);
+        }
+    }
+
+    Err(FlameError::InvalidConfig(format!(
+        "key_file <{path}> is not a 256-bit key in raw bytes or hex"
+    )))
+}
+
+/// An engine that encrypts the payloads before storing them in the inner engine.
+pub struct EncryptedEngine {
+    engine: EnginePtr,
+    keyring: KeyRing,
+}
+
+impl EncryptedEngine {
+    pub fn new_ptr(engine: EnginePtr, config: &FlameEncryption) -> Result<EnginePtr, FlameError> {
+        tracing::info!(
+            "Encrypting payloads in storage with key <{}>",
+            config.active_key
+        );
+
+        Ok(std::sync::Arc::new(Self {
+            engine,
+            keyring: KeyRing::load(config)?,
+        }))
+    }
+
+    fn encrypt(&self, data: Option<Bytes>) -> Result<Option<Bytes>, FlameError> {
+        data.map(|d| self.keyring.encrypt(&d)).transpose()
+    }
+
+    fn decrypt(&self, data: Option<Bytes>) -> Result<Option<Bytes>, FlameError> {
+        data.map(|d| self.keyring.decrypt(&d)).transpose()
+    }
+
+    fn encrypt_text(&self, text: Option<String>) -> Result<Option<String>, FlameError> {
+        text.map(|t| Ok(STANDARD.encode(self.keyring.encrypt(t.as_bytes())?)))
+            .transpose()
+    }
+
+    /// Decrypts the text encrypted by `encrypt_text`; the text without an
+    /// envelope, e.g. stored before encryption was enabled, is returned as is.
+    fn decrypt_text(&self, text: Option<String>) -> Result<Option<String>, FlameError> {
+        let Some(text) = text else {
+            return Ok(None);
+        };
+        let Some(envelope) = STANDARD
+            .decode(&text)
+            .ok()
+            .filter(|data| data.starts_with(ENVELOPE_MAGIC))
+        else {
+            return Ok(Some(text));
+        };
+
+        let data = self.keyring.decrypt(&envelope)?;
+        String::from_utf8(data.to_vec())
+            .map(Some)
+            .map_err(|_| FlameError::Storage("invalid encrypted text".to_string()))
+    }
+
+    fn encrypt_postmortem(
+        &self,
+        mut postmortem: TaskPostmortem,
+    ) -> Result<TaskPostmortem, FlameError> {
+        postmortem.stderr_tail = self.encrypt_text(postmortem.stderr_tail)?;
+        Ok(postmortem)
+    }
+
+    fn encrypt_session(
+        &self,
+        mut attr: SessionAttributes,
+    ) -> Result<SessionAttributes, FlameError> {
+        attr.common_data = self.encrypt(attr.common_data)?;
+        Ok(attr)
+    }
+
+    fn decrypt_session(&self, mut ssn: Session) -> Result<Session, FlameError> {
+        ssn.common_data = self.decrypt(ssn.common_data)?;
+        Ok(ssn)
+    }
+
+    fn decrypt_task(&self, mut task: Task) -> Result<Task, FlameError> {
+        task.input = self.decrypt(task.input)?;
+        task.output = self.decrypt(task.output)?;
+        for artifact in task.artifacts.iter_mut() {
+            artifact.data = self.decrypt(artifact.data.take())?;
+        }
+        if let Some(postmortem) = task.postmortem.as_mut() {
+            postmortem.stderr_tail = self.decrypt_text(postmortem.stderr_tail.take())?;
+        }
+        Ok(task)
+    }
+}
+
+#[async_trait]
+impl Engine for EncryptedEngine {
+    async fn register_application(
+        &self,
+        name: String,
+        attr: ApplicationAttributes,
+    ) -> Result<Application, FlameError> {
+        self.engine.register_application(name, attr).await
+    }
+
+    async fn unregister_application(&self, id: String) -> Result<(), FlameError> {
+        self.engine.unregister_application(id).await
+    }
+
+    async fn update_application(
+        &self,
+        id: String,
+        attr: ApplicationAttributes,
+        expected_version: Option<u32>,
+    ) -> Result<Application, FlameError> {
+        self.engine
+            .update_application(id, attr, expected_version)
+            .await
+    }
+
+    async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
+    ) -> Result<Application, FlameError> {
+        self.engine.update_application_state(name, state).await
+    }
+
+    async fn get_application(&self, id: ApplicationID) -> Result<Application, FlameError> {
+        self.engine.get_application(id).await
+    }
+
+    async fn find_application(&self) -> Result<Vec<Application>, FlameError> {
+        self.engine.find_application().await
+    }
+
+    async fn create_session(&self, attr: SessionAttributes) -> Result<Session, FlameError> {
+        let ssn = self
+            .engine
+            .create_session(self.encrypt_session(attr)?)
+            .await?;
+        self.decrypt_session(ssn)
+    }
+
+    async fn get_session(&self, id: SessionID) -> Result<Session, FlameError> {
+        let ssn = self.engine.get_session(id).await?;
+        self.decrypt_session(ssn)
+    }
+
+    async fn open_session(
+        &self,
+        id: SessionID,
+        spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
+    ) -> Result<Session, FlameError> {
+        let spec = spec.map(|attr| self.encrypt_session(attr)).transpose()?;
+        let ssn = self.engine.open_session(id, spec, replay).await?;
+        self.decrypt_session(ssn)
+    }
+
+    async fn close_session(
+        &self,
+        id: SessionID,
+        expected_version: Option<u32>,
+        force: bool,
+    ) -> Result<Session, FlameError> {
+        let ssn = self
+            .engine
+            .close_session(id, expected_version, force)
+            .await?;
+        self.decrypt_session(ssn)
+    }
+
+    async fn delete_session(&self, id: SessionID) -> Result<Session, FlameError> {
+        let ssn = self.engine.delete_session(id).await?;
+        self.decrypt_session(ssn)
+    }
+
+    async fn find_session(&self) -> Result<Vec<Session>, FlameError> {
+        let ssns = self.engine.find_session().await?;
+        ssns.into_iter()
+            .map(|ssn| self.decrypt_session(ssn))
+            .collect()
+    }
+
+    async fn find_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        self.engine.find_session_ids().await
+    }
+
+    async fn find_expired_session_ids(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SessionID>, FlameError> {
+        self.engine.find_expired_session_ids(now).await
+    }
+
+    async fn create_task(
+        &self,
+        ssn_id: SessionID,
+        attr: TaskAttributes,
+    ) -> Result<Task, FlameError> {
+        let input = self.encrypt(attr.input)?;
+        let task = self
+            .engine
+            .create_task(ssn_id, TaskAttributes { input, ..attr })
+            .await?;
+        self.decrypt_task(task)
+    }
+
+    async fn get_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        let task = self.engine.get_task(gid).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn retry_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        let task = self.engine.retry_task(gid).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        let task = self.engine.release_task(gid).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn checkpoint_task(&self, gid: TaskGID, checkpoint: String) -> Result<Task, FlameError> {
+        let task = self.engine.checkpoint_task(gid, checkpoint).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn update_task_postmortem(
+        &self,
+        gid: TaskGID,
+        postmortem: TaskPostmortem,
+    ) -> Result<Task, FlameError> {
+        let postmortem = self.encrypt_postmortem(postmortem)?;
+        let task = self.engine.update_task_postmortem(gid, postmortem).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn delete_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        let task = self.engine.delete_task(gid).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn update_task_state(
+        &self,
+        gid: TaskGID,
+        task_state: TaskState,
+        message: Option<String>,
+    ) -> Result<Task, FlameError> {
+        let task = self
+            .engine
+            .update_task_state(gid, task_state, message)
+            .await?;
+        self.decrypt_task(task)
+    }
+
+    async fn update_task_result(
+        &self,
+        gid: TaskGID,
+        mut task_result: TaskResult,
+    ) -> Result<Task, FlameError> {
+        task_result.output = self.encrypt(task_result.output)?;
+        for artifact in task_result.artifacts.iter_mut() {
+            artifact.data = self.encrypt(artifact.data.take())?;
+        }
+        task_result.postmortem = task_result
+            .postmortem
+            .map(|postmortem| self.encrypt_postmortem(postmortem))
+            .transpose()?;
+        let task = self.engine.update_task_result(gid, task_result).await?;
+        self.decrypt_task(task)
+    }
+
+    async fn find_tasks(&self, ssn_id: SessionID) -> Result<Vec<Task>, FlameError> {
+        let tasks = self.engine.find_tasks(ssn_id).await?;
+        tasks
+            .into_iter()
+            .map(|task| self.decrypt_task(task))
+            .collect()
+    }
+
+    async fn create_node(&self, node: &Node) -> Result<Node, FlameError> {
+        self.engine.create_node(node).await
+    }
+
+    async fn get_node(&self, name: &str) -> Result<Option<Node>, FlameError> {
+        self.engine.get_node(name).await
+    }
+
+    async fn update_node(&self, node: &Node) -> Result<Node, FlameError> {
+        self.engine.update_node(node).await
+    }
+
+    async fn delete_node(&self, name: &str) -> Result<(), FlameError> {
+        self.engine.delete_node(name).await
+    }
+
+    async fn find_nodes(&self) -> Result<Vec<Node>, FlameError> {
+        self.engine.find_nodes().await
+    }
+
+    async fn create_executor(&self, executor: &Executor) -> Result<Executor, FlameError> {
+        self.engine.create_executor(executor).await
+    }
+
+    async fn get_executor(&self, id: &ExecutorID) -> Result<Option<Executor>, FlameError> {
+        self.engine.get_executor(id).await
+    }
+
+    async fn update_executor(&self, executor: &Executor) -> Result<Executor, FlameError> {
+        self.engine.update_executor(executor).await
+    }
+
+    async fn update_executor_state(
+        &self,
+        id: &ExecutorID,
+        state: ExecutorState,
+    ) -> Result<Executor, FlameError> {
+        self.engine.update_executor_state(id, state).await
+    }
+
+    async fn delete_executor(&self, id: &ExecutorID) -> Result<(), FlameError> {
+        self.engine.delete_executor(id).await
+    }
+
+    async fn find_executors(&self, node: Option<&str>) -> Result<Vec<Executor>, FlameError> {
+        self.engine.find_executors(node).await
+    }
+
+    async fn create_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        self.engine.create_job(id).await
+    }
+
+    async fn delete_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        self.engine.delete_job(id).await
+    }
+
+    async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        self.engine.find_job_ids().await
+    }
+
+    /// The payloads stay encrypted in the backups.
+    async fn backup(&self, path: &Path) -> Result<(), FlameError> {
+        self.engine.backup(path).await
+    }
+
+    async fn checkpoint(&self) -> Result<(), FlameError> {
+        self.engine.checkpoint().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use common::apis::TaskArtifact;
+    use common::ctx::FlameEncryptionKey;
+    use tempfile::TempDir;
+
+    use crate::storage::engine::SqliteEngine;
+
+    fn test_encryption(dir: &TempDir, active_key: &str, ids: &[&str]) -> FlameEncryption {
+        let keys = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let key_file = dir.path().join(id);
+                fs::write(&key_file, format!("{:064x}\n", i + 1)).unwrap();
+                FlameEncryptionKey {
+                    id: id.to_string(),
+                    key_file: key_file.to_string_lossy().to_string(),
+                }
+            })
+            .collect();
+
+        FlameEncryption {
+            active_key: active_key.to_string(),
+            keys,
+        }
+    }
+
+    #[test]
+    fn test_key_ring_encrypt_decrypt() -> Result<(), FlameError> {
+        let dir = TempDir::new().unwrap();
+        let keyring = KeyRing::load(&test_encryption(&dir, "key-1", &["key-1"]))?;
+
+        let envelope = keyring.encrypt(b"hello flame")?;
+        assert!(envelope.starts_with(ENVELOPE_MAGIC));
+        assert!(!envelope
+            .windows(b"hello flame".len())
+            .any(|w| w == b"hello flame"));
+        assert_eq!(keyring.decrypt(&envelope)?, Bytes::from("hello flame"));
+
+        // The payloads stored before encryption was enabled are returned as is.
+        assert_eq!(keyring.decrypt(b"plaintext")?, Bytes::from("plaintext"));
+
+        // The tampered payload is rejected.
+        let mut tampered = envelope.to_vec();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert!(keyring.decrypt(&tampered).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_ring_rotation() -> Result<(), FlameError> {
+        let dir = TempDir::new().unwrap();
+        let old_keyring = KeyRing::load(&test_encryption(&dir, "key-1", &["key-1"]))?;
+        let envelope = old_keyring.encrypt(b"before rotation")?;
+
+        let keyring = KeyRing::load(&test_encryption(&dir, "key-2", &["key-1", "key-2"]))?;
+        assert_eq!(keyring.decrypt(&envelope)?, Bytes::from("before rotation"));
+
+        let envelope = keyring.encrypt(b"after rotation")?;
+        assert!(old_keyring.decrypt(&envelope).is_err());
+        assert_eq!(keyring.decrypt(&envelope)?, Bytes::from("after rotation"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_key_file() {
+        let dir = TempDir::new().unwrap();
+        let key_file = dir.path().join("short-key");
+        fs::write(&key_file, "1234").unwrap();
+
+        let config = FlameEncryption {
+            active_key: "short-key".to_string(),
+            keys: vec![FlameEncryptionKey {
+                id: "short-key".to_string(),
+                key_file: key_file.to_string_lossy().to_string(),
+            }],
+        };
+        assert!(KeyRing::load(&config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_id() {
+        let dir = TempDir::new().unwrap();
+        let key_file = dir.path().join("key");
+        fs::write(&key_file, format!("{:064x}\n", 1)).unwrap();
+
+        let config = |id: String| FlameEncryption {
+            active_key: id.clone(),
+            keys: vec![FlameEncryptionKey {
+                id,
+                key_file: key_file.to_string_lossy().to_string(),
+            }],
+        };
+        for id in [String::new(), "k".repeat(u8::MAX as usize + 1)] {
+            assert!(matches!(
+                KeyRing::load(&config(id)),
+                Err(FlameError::InvalidConfig(_))
+            ));
+        }
+        assert!(KeyRing::load(&config("k".repeat(u8::MAX as usize))).is_ok());
+    }
+
+    #[test]
+    fn test_encrypted_engine() -> Result<(), FlameError> {
+        let dir = TempDir::new().unwrap();
+        let url = common::temp_sqlite_url("flame_test_encrypted_engine");
+        let inner = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        let storage =
+            EncryptedEngine::new_ptr(inner.clone(), &test_encryption(&dir, "key-1", &["key-1"]))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            common_data: Some(Bytes::from("common data")),
+            ..SessionAttributes::default()
+        }))?;
+        assert_eq!(ssn.common_data, Some(Bytes::from("common data")));
+
+        let task = tokio_test::block_on(storage.create_task(
+            ssn.id.clone(),
+            TaskAttributes {
+                input: Some(Bytes::from("task input")),
+                ..TaskAttributes::default()
+            },
+        ))?;
+        assert_eq!(task.input, Some(Bytes::from("task input")));
+
+        let task = tokio_test::block_on(storage.update_task_result(
+            task.gid(),
+            TaskResult {
+                state: TaskState::Succeed,
+                output: Some(Bytes::from("task output")),
+                output_ref: None,
+                message: None,
+                artifacts: vec![TaskArtifact {
+                    name: "summary".to_string(),
+                    data: Some(Bytes::from("task artifact")),
+                    object_ref: None,
+                }],
+                failure_reason: None,
+                usage: None,
+                postmortem: None,
+                codec: None,
+            },
+        ))?;
+        assert_eq!(task.output, Some(Bytes::from("task output")));
+        assert_eq!(task.artifacts[0].data, Some(Bytes::from("task artifact")));
+
+        // No plaintext payloads in the inner engine.
+        let stored_ssn = tokio_test::block_on(inner.get_session(ssn.id.clone()))?;
+        assert_ne!(stored_ssn.common_data, Some(Bytes::from("common data")));
+        let stored_task = tokio_test::block_on(inner.get_task(task.gid()))?;
+        assert_ne!(stored_task.input, Some(Bytes::from("task input")));
+        assert_ne!(stored_task.output, Some(Bytes::from("task output")));
+        assert_ne!(
+            stored_task.artifacts[0].data,
+            Some(Bytes::from("task artifact"))
+        );
+
+        let tasks = tokio_test::block_on(storage.find_tasks(ssn.id.clone()))?;
+        assert_eq!(tasks[0].input, Some(Bytes::from("task input")));
+        assert_eq!(tasks[0].output, Some(Bytes::from("task output")));
+
+        let task = tokio_test::block_on(storage.update_task_postmortem(
+            task.gid(),
+            TaskPostmortem {
+                exit_code: Some(1),
+                stderr_tail: Some("task stderr".to_string()),
+                ..TaskPostmortem::default()
+            },
+        ))?;
+        let stderr_tail = |task: &Task| task.postmortem.as_ref().unwrap().stderr_tail.clone();
+        assert_eq!(stderr_tail(&task), Some("task stderr".to_string()));
+        let stored_task = tokio_test::block_on(inner.get_task(task.gid()))?;
+        assert_ne!(stderr_tail(&stored_task), Some("task stderr".to_string()));
+
+        Ok(())
+    }
+}
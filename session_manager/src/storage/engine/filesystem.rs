@@ -26,8 +26,9 @@ limitations under the License.
 //! │   ├── tasks.bin         # TaskMetadata records (fixed-size, indexed by Task ID)
 //! │   ├── inputs.bin        # Concatenated input data (append-only)
 //! │   └── outputs.bin       # Concatenated output data (append-only)
-//! └── applications/<app_name>/
-//!     └── metadata          # Application metadata (JSON)
+//! ├── applications/<app_name>/
+//! │   └── metadata          # Application metadata (JSON)
+//! └── jobs/<job_id>         # Empty record of the running job
 //! ```
 //!
 //! # Design Decisions
@@ -40,7 +41,7 @@ limitations under the License.
 use std::collections::HashMap;
 use std::fs;
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, RwLock};
 
 use async_trait::async_trait;
@@ -51,14 +52,17 @@ use serde::{Deserialize, Serialize};
 
 use common::apis::{
     Application, ApplicationAttributes, ApplicationID, ApplicationSchema, ApplicationState,
-    ExecutorID, ExecutorState, Node, NodeInfo, NodeState, ResourceRequirement, Session,
-    SessionAttributes, SessionID, SessionState, SessionStatus, Shim, Task, TaskGID, TaskID,
-    TaskInput, TaskOutput, TaskResult, TaskState,
+    ExecutorID, ExecutorState, FailureReason, FairnessPolicy, HealthProbe, LifecycleHook,
+    LifecycleHooks, Node, NodeInfo, NodeState, ObjectReference, PayloadCodec, PythonEnvironment,
+    ReplayPolicy, ResourceRequirement, ResultPolicy, Session, SessionAttributes, SessionDefaults,
+    SessionID, SessionState, SessionStatus, Shim, Task, TaskArtifact, TaskAttributes,
+    TaskDurationStats, TaskGID, TaskID, TaskOutput, TaskOverrides, TaskPostmortem, TaskResult,
+    TaskState, TaskUsage, TaskUsageStats, TopologyPolicy, DEFAULT_BUNDLE_SIZE,
 };
-use common::{FlameError, FLAME_HOME};
+use common::{flame_home, FlameError};
 
 use crate::model::Executor;
-use crate::storage::engine::{Engine, EnginePtr};
+use crate::storage::engine::{check_version, Engine, EnginePtr};
 
 /// Task metadata stored in tasks.bin with fixed-size records.
 ///
@@ -102,6 +106,22 @@ struct SessionMetadata {
     pub max_instances: Option<u32>,
     #[serde(default = "default_batch_size")]
     pub batch_size: u32,
+    #[serde(default)]
+    pub scratch_size: Option<u64>,
+    #[serde(default)]
+    pub max_task_attempts: Option<u32>,
+    #[serde(default)]
+    pub task_timeout: Option<u64>,
+    #[serde(default)]
+    pub task_priority: Option<u32>,
+    #[serde(default)]
+    pub priority: u32,
+    #[serde(default)]
+    pub result_policy: i32,
+    #[serde(default)]
+    pub ttl_after_completion: Option<u64>,
+    #[serde(default)]
+    pub trace_id: Option<String>,
     pub common_data_len: u64,
 }
 
@@ -129,6 +149,156 @@ struct ApplicationMetadata {
     pub delay_release_seconds: i64,
     pub schema: Option<ApplicationSchemaMetadata>,
     pub url: Option<String>,
+    #[serde(default)]
+    pub task_environments: Vec<String>,
+    #[serde(default)]
+    pub task_arguments: Vec<String>,
+    #[serde(default = "default_bundle_size")]
+    pub bundle_size: u32,
+    #[serde(default)]
+    pub max_instances_per_node: Option<u32>,
+    #[serde(default)]
+    pub session_defaults: SessionDefaultsMetadata,
+    #[serde(default)]
+    pub health_probe: Option<HealthProbeMetadata>,
+    #[serde(default)]
+    pub fairness: i32, // 0 = RoundRobin (default), 1 = Proportional
+    #[serde(default)]
+    pub hooks: LifecycleHooksMetadata,
+    #[serde(default)]
+    pub python_env: Option<PythonEnvironmentMetadata>,
+    #[serde(default)]
+    pub codec: i32, // 0 = Raw (default), 1 = Json, 2 = Msgpack, 3 = Arrow, 4 = Protobuf
+    #[serde(default)]
+    pub payload_type: Option<String>,
+    #[serde(default)]
+    pub topology: i32, // 0 = Ignore (default), 1 = NumaAligned, 2 = GpuAligned
+}
+
+fn default_bundle_size() -> u32 {
+    DEFAULT_BUNDLE_SIZE
+}
+
+/// Task overrides stored in `overrides/<task id>` of the session, only for the
+/// tasks with overrides; it's kept out of tasks.bin to keep fixed-size records.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct TaskOverridesMetadata {
+    pub environments: HashMap<String, String>,
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub priority: Option<u32>,
+    #[serde(default)]
+    pub deadline: Option<u64>,
+}
+
+/// Task input and output references stored in `input_refs/<task id>` and
+/// `output_refs/<task id>` of the session, only for the tasks whose input or
+/// output is in the object cache.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct ObjectReferenceMetadata {
+    pub endpoint: String,
+    pub key: String,
+    pub checksum: Option<String>,
+}
+
+/// Task artifacts stored in `artifacts/<task id>` of the session, only for the
+/// tasks with artifacts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct TaskArtifactMetadata {
+    pub name: String,
+    pub data: Option<Vec<u8>>,
+    pub object_ref: Option<String>,
+}
+
+/// Task failure stored in `failures/<task id>` of the session, only for the
+/// failed tasks with a failure reason.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct TaskFailureMetadata {
+    pub reason: i32,
+}
+
+/// Task usage stored in `usages/<task id>` of the session, only for the tasks
+/// with measured resource usage.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct TaskUsageMetadata {
+    pub cpu_time_ms: u64,
+    pub max_rss_bytes: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+impl From<TaskUsage> for TaskUsageMetadata {
+    fn from(usage: TaskUsage) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+impl From<TaskUsageMetadata> for TaskUsage {
+    fn from(usage: TaskUsageMetadata) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+/// Task postmortem stored in `postmortems/<task id>` of the session, only for
+/// the tasks whose instance crashed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct TaskPostmortemMetadata {
+    pub executor_id: String,
+    pub node: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub core_dumped: bool,
+    pub stderr_tail: Option<String>,
+    pub load_average: Option<f64>,
+    pub memory_available: Option<u64>,
+    pub creation_time: i64,
+}
+
+impl From<TaskPostmortem> for TaskPostmortemMetadata {
+    fn from(postmortem: TaskPostmortem) -> Self {
+        Self {
+            executor_id: postmortem.executor_id,
+            node: postmortem.node,
+            exit_code: postmortem.exit_code,
+            signal: postmortem.signal,
+            core_dumped: postmortem.core_dumped,
+            stderr_tail: postmortem.stderr_tail,
+            load_average: postmortem.load_average,
+            memory_available: postmortem.memory_available,
+            creation_time: postmortem.creation_time.timestamp(),
+        }
+    }
+}
+
+impl From<TaskPostmortemMetadata> for TaskPostmortem {
+    fn from(postmortem: TaskPostmortemMetadata) -> Self {
+        Self {
+            executor_id: postmortem.executor_id,
+            node: postmortem.node,
+            exit_code: postmortem.exit_code,
+            signal: postmortem.signal,
+            core_dumped: postmortem.core_dumped,
+            stderr_tail: postmortem.stderr_tail,
+            load_average: postmortem.load_average,
+            memory_available: postmortem.memory_available,
+            creation_time: DateTime::from_timestamp(postmortem.creation_time, 0)
+                .unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -138,6 +308,151 @@ struct ApplicationSchemaMetadata {
     pub common_data: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct SessionDefaultsMetadata {
+    pub slots: Option<u32>,
+    pub min_instances: Option<u32>,
+    pub max_instances: Option<u32>,
+    pub batch_size: Option<u32>,
+    pub scratch_size: Option<u64>,
+    pub max_task_attempts: Option<u32>,
+    #[serde(default)]
+    pub task_timeout: Option<u64>,
+    #[serde(default)]
+    pub task_priority: Option<u32>,
+}
+
+impl From<SessionDefaults> for SessionDefaultsMetadata {
+    fn from(defaults: SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<&SessionDefaultsMetadata> for SessionDefaults {
+    fn from(meta: &SessionDefaultsMetadata) -> Self {
+        Self {
+            slots: meta.slots,
+            min_instances: meta.min_instances,
+            max_instances: meta.max_instances,
+            batch_size: meta.batch_size,
+            scratch_size: meta.scratch_size,
+            max_task_attempts: meta.max_task_attempts,
+            task_timeout: meta.task_timeout,
+            task_priority: meta.task_priority,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct HealthProbeMetadata {
+    pub command: Option<String>,
+    pub grpc_service: Option<String>,
+    pub period_seconds: Option<u32>,
+    pub timeout_seconds: Option<u32>,
+    pub failure_threshold: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct LifecycleHooksMetadata {
+    pub pre_bind: Option<LifecycleHookMetadata>,
+    pub post_bind: Option<LifecycleHookMetadata>,
+    pub pre_release: Option<LifecycleHookMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LifecycleHookMetadata {
+    pub command: String,
+    pub timeout_seconds: Option<u32>,
+}
+
+impl From<LifecycleHooks> for LifecycleHooksMetadata {
+    fn from(hooks: LifecycleHooks) -> Self {
+        let to_meta = |hook: LifecycleHook| LifecycleHookMetadata {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        };
+        Self {
+            pre_bind: hooks.pre_bind.map(to_meta),
+            post_bind: hooks.post_bind.map(to_meta),
+            pre_release: hooks.pre_release.map(to_meta),
+        }
+    }
+}
+
+impl From<&LifecycleHooksMetadata> for LifecycleHooks {
+    fn from(meta: &LifecycleHooksMetadata) -> Self {
+        let from_meta = |hook: &LifecycleHookMetadata| LifecycleHook {
+            command: hook.command.clone(),
+            timeout_seconds: hook.timeout_seconds,
+        };
+        Self {
+            pre_bind: meta.pre_bind.as_ref().map(from_meta),
+            post_bind: meta.post_bind.as_ref().map(from_meta),
+            pre_release: meta.pre_release.as_ref().map(from_meta),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct PythonEnvironmentMetadata {
+    pub project: Option<String>,
+    pub requirements: Option<String>,
+    pub python_version: Option<String>,
+}
+
+impl From<PythonEnvironment> for PythonEnvironmentMetadata {
+    fn from(env: PythonEnvironment) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
+        }
+    }
+}
+
+impl From<&PythonEnvironmentMetadata> for PythonEnvironment {
+    fn from(meta: &PythonEnvironmentMetadata) -> Self {
+        Self {
+            project: meta.project.clone(),
+            requirements: meta.requirements.clone(),
+            python_version: meta.python_version.clone(),
+        }
+    }
+}
+
+impl From<HealthProbe> for HealthProbeMetadata {
+    fn from(probe: HealthProbe) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<&HealthProbeMetadata> for HealthProbe {
+    fn from(meta: &HealthProbeMetadata) -> Self {
+        Self {
+            command: meta.command.clone(),
+            grpc_service: meta.grpc_service.clone(),
+            period_seconds: meta.period_seconds,
+            timeout_seconds: meta.timeout_seconds,
+            failure_threshold: meta.failure_threshold,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct NodeMetadata {
     pub name: String,
@@ -164,6 +479,8 @@ struct ExecutorMetadata {
     pub ssn_id: Option<String>,
     #[serde(default)]
     pub batch_index: Option<u32>,
+    #[serde(default)]
+    pub generation: u64,
     pub creation_time: i64,
     pub state: i32,
 }
@@ -202,7 +519,8 @@ fn calculate_checksum(meta: &TaskMetadata) -> u32 {
 pub struct FilesystemEngine {
     base_path: PathBuf,
     record_size: usize,
-    ssn_locks: RwLock<HashMap<String, Arc<Mutex<()>>>>,
+    /// Shared with the backups, which copy the data in a blocking thread.
+    ssn_locks: Arc<RwLock<HashMap<SessionID, Arc<Mutex<()>>>>>,
     node_locks: RwLock<HashMap<String, Arc<Mutex<()>>>>,
     executor_locks: RwLock<HashMap<String, Arc<Mutex<()>>>>,
 }
@@ -279,6 +597,7 @@ impl FilesystemEngine {
         let sessions_path = path.join("sessions");
         let applications_path = path.join("applications");
         let nodes_path = path.join("nodes");
+        let jobs_path = path.join("jobs");
 
         fs::create_dir_all(&sessions_path).map_err(|e| {
             FlameError::Storage(format!("Failed to create sessions directory: {e}"))
@@ -288,6 +607,8 @@ impl FilesystemEngine {
         })?;
         fs::create_dir_all(&nodes_path)
             .map_err(|e| FlameError::Storage(format!("Failed to create nodes directory: {e}")))?;
+        fs::create_dir_all(&jobs_path)
+            .map_err(|e| FlameError::Storage(format!("Failed to create jobs directory: {e}")))?;
 
         let record_size = task_record_size();
         tracing::info!(
@@ -299,7 +620,7 @@ impl FilesystemEngine {
         Ok(Arc::new(FilesystemEngine {
             base_path: path,
             record_size,
-            ssn_locks: RwLock::new(HashMap::new()),
+            ssn_locks: Arc::new(RwLock::new(HashMap::new())),
             node_locks: RwLock::new(HashMap::new()),
             executor_locks: RwLock::new(HashMap::new()),
         }))
@@ -322,9 +643,7 @@ impl FilesystemEngine {
         if path.starts_with('/') {
             Ok(PathBuf::from(path))
         } else {
-            let flame_home =
-                std::env::var(FLAME_HOME).unwrap_or_else(|_| "/usr/local/flame".to_string());
-            Ok(PathBuf::from(flame_home).join(path))
+            Ok(flame_home().join(path))
         }
     }
 
@@ -340,6 +659,10 @@ impl FilesystemEngine {
         self.base_path.join("nodes").join(node_name)
     }
 
+    fn job_path(&self, job_id: &str) -> PathBuf {
+        self.base_path.join("jobs").join(job_id)
+    }
+
     fn executor_path(&self, node_name: &str, executor_id: &str) -> PathBuf {
         self.node_path(node_name)
             .join("executors")
@@ -535,7 +858,7 @@ impl FilesystemEngine {
         session_id: &str,
         task_id: TaskID,
     ) -> Result<TaskMetadata, FlameError> {
-        if task_id < 1 {
+        if task_id.value() < 1 {
             return Err(FlameError::NotFound(format!(
                 "Invalid task ID: {task_id} (must be >= 1)"
             )));
@@ -548,7 +871,7 @@ impl FilesystemEngine {
             .open(&path)
             .map_err(|e| FlameError::NotFound(format!("Tasks file not found: {e}")))?;
 
-        let offset = (task_id as u64 - 1) * self.record_size as u64;
+        let offset = (task_id.value() as u64 - 1) * self.record_size as u64;
         file.seek(SeekFrom::Start(offset))
             .map_err(|e| FlameError::Storage(format!("Failed to seek to task {task_id}: {e}")))?;
 
@@ -689,6 +1012,453 @@ impl FilesystemEngine {
         Ok(())
     }
 
+    /// Read the overrides of a task; empty if the task has no overrides.
+    fn read_task_overrides(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<TaskOverrides, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("overrides")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(TaskOverrides::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task overrides: {e}")))?;
+        let meta: TaskOverridesMetadata = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task overrides: {e}")))?;
+
+        Ok(TaskOverrides {
+            environments: meta.environments,
+            arguments: meta.arguments,
+            timeout: meta.timeout,
+            max_attempts: meta.max_attempts,
+            priority: meta.priority,
+            deadline: meta.deadline,
+        })
+    }
+
+    /// Write the overrides of a task.
+    fn write_task_overrides(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        overrides: &TaskOverrides,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("overrides");
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create overrides directory: {e}"))
+        })?;
+
+        let meta = TaskOverridesMetadata {
+            environments: overrides.environments.clone(),
+            arguments: overrides.arguments.clone(),
+            timeout: overrides.timeout,
+            max_attempts: overrides.max_attempts,
+            priority: overrides.priority,
+            deadline: overrides.deadline,
+        };
+        let content = serde_json::to_string(&meta)
+            .map_err(|e| FlameError::Storage(format!("Failed to serialize task overrides: {e}")))?;
+        fs::write(dir.join(task_id.to_string()), content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task overrides: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the input or output reference of a task by `kind`, if it's in the
+    /// object cache.
+    fn read_task_object_ref(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        kind: &str,
+    ) -> Result<Option<ObjectReference>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join(format!("{kind}_refs"))
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task {kind} ref: {e}")))?;
+        let meta: ObjectReferenceMetadata = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task {kind} ref: {e}")))?;
+
+        Ok(Some(ObjectReference {
+            endpoint: meta.endpoint,
+            key: meta.key,
+            checksum: meta.checksum,
+        }))
+    }
+
+    /// Write the input or output reference of a task by `kind`.
+    fn write_task_object_ref(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        kind: &str,
+        object_ref: &ObjectReference,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join(format!("{kind}_refs"));
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create {kind} refs directory: {e}"))
+        })?;
+
+        let meta = ObjectReferenceMetadata {
+            endpoint: object_ref.endpoint.clone(),
+            key: object_ref.key.clone(),
+            checksum: object_ref.checksum.clone(),
+        };
+        let content = serde_json::to_string(&meta).map_err(|e| {
+            FlameError::Storage(format!("Failed to serialize task {kind} ref: {e}"))
+        })?;
+        fs::write(dir.join(task_id.to_string()), content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task {kind} ref: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the artifacts of a task, empty if the task has no artifacts.
+    fn read_task_artifacts(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Vec<TaskArtifact>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("artifacts")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task artifacts: {e}")))?;
+        let artifacts: Vec<TaskArtifactMetadata> = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task artifacts: {e}")))?;
+
+        Ok(artifacts
+            .into_iter()
+            .map(|a| TaskArtifact {
+                name: a.name,
+                data: a.data.map(Bytes::from),
+                object_ref: a.object_ref,
+            })
+            .collect())
+    }
+
+    /// Write the artifacts of a task, replacing the ones of the previous run.
+    fn write_task_artifacts(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        artifacts: &[TaskArtifact],
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("artifacts");
+        let path = dir.join(task_id.to_string());
+        if artifacts.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| {
+                    FlameError::Storage(format!("Failed to remove task artifacts: {e}"))
+                })?;
+            }
+            return Ok(());
+        }
+
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create artifacts directory: {e}"))
+        })?;
+
+        let artifacts: Vec<TaskArtifactMetadata> = artifacts
+            .iter()
+            .map(|a| TaskArtifactMetadata {
+                name: a.name.clone(),
+                data: a.data.as_ref().map(|d| d.to_vec()),
+                object_ref: a.object_ref.clone(),
+            })
+            .collect();
+        let content = serde_json::to_string(&artifacts)
+            .map_err(|e| FlameError::Storage(format!("Failed to serialize task artifacts: {e}")))?;
+        fs::write(path, content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task artifacts: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the failure reason of a task, if any.
+    fn read_task_failure(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Option<FailureReason>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("failures")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task failure: {e}")))?;
+        let failure: TaskFailureMetadata = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task failure: {e}")))?;
+
+        Ok(Some(FailureReason::try_from(failure.reason)?))
+    }
+
+    /// Write the failure reason of a task, or remove it if there is none.
+    fn write_task_failure(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        reason: Option<FailureReason>,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("failures");
+        let path = dir.join(task_id.to_string());
+        let Some(reason) = reason else {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| {
+                    FlameError::Storage(format!("Failed to remove task failure: {e}"))
+                })?;
+            }
+            return Ok(());
+        };
+
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create failures directory: {e}"))
+        })?;
+
+        let content = serde_json::to_string(&TaskFailureMetadata {
+            reason: reason.into(),
+        })
+        .map_err(|e| FlameError::Storage(format!("Failed to serialize task failure: {e}")))?;
+        fs::write(path, content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task failure: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the resource usage of a task, if any.
+    fn read_task_usage(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Option<TaskUsage>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("usages")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task usage: {e}")))?;
+        let usage: TaskUsageMetadata = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task usage: {e}")))?;
+
+        Ok(Some(usage.into()))
+    }
+
+    /// Write the resource usage of a task, or remove it if there is none.
+    fn write_task_usage(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        usage: Option<TaskUsage>,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("usages");
+        let path = dir.join(task_id.to_string());
+        let Some(usage) = usage else {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| {
+                    FlameError::Storage(format!("Failed to remove task usage: {e}"))
+                })?;
+            }
+            return Ok(());
+        };
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| FlameError::Storage(format!("Failed to create usages directory: {e}")))?;
+
+        let content = serde_json::to_string(&TaskUsageMetadata::from(usage))
+            .map_err(|e| FlameError::Storage(format!("Failed to serialize task usage: {e}")))?;
+        fs::write(path, content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task usage: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the attempts of a task, 0 if it was never dispatched again.
+    fn read_task_attempts(&self, session_id: &str, task_id: u64) -> Result<u32, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("attempts")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task attempts: {e}")))?;
+        content
+            .trim()
+            .parse()
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task attempts: {e}")))
+    }
+
+    /// Write the attempts of a task, or remove them if there is none.
+    fn write_task_attempts(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        attempts: u32,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("attempts");
+        let path = dir.join(task_id.to_string());
+        if attempts == 0 {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| {
+                    FlameError::Storage(format!("Failed to remove task attempts: {e}"))
+                })?;
+            }
+            return Ok(());
+        }
+
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create attempts directory: {e}"))
+        })?;
+        fs::write(path, attempts.to_string())
+            .map_err(|e| FlameError::Storage(format!("Failed to write task attempts: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the latest checkpoint of a task, if any.
+    fn read_task_checkpoint(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Option<String>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("checkpoints")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task checkpoint: {e}")))?;
+
+        Ok(Some(content))
+    }
+
+    /// Write the latest checkpoint of a task.
+    fn write_task_checkpoint(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        checkpoint: &str,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("checkpoints");
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create checkpoints directory: {e}"))
+        })?;
+        fs::write(dir.join(task_id.to_string()), checkpoint)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task checkpoint: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the trace ID of a task, if any.
+    fn read_task_trace_id(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Option<String>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("trace_ids")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task trace ID: {e}")))?;
+
+        Ok(Some(content))
+    }
+
+    /// Write the trace ID of a task.
+    fn write_task_trace_id(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        trace_id: &str,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("trace_ids");
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create trace IDs directory: {e}"))
+        })?;
+        fs::write(dir.join(task_id.to_string()), trace_id)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task trace ID: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Read the postmortem of a task, if any.
+    fn read_task_postmortem(
+        &self,
+        session_id: &str,
+        task_id: u64,
+    ) -> Result<Option<TaskPostmortem>, FlameError> {
+        let path = self
+            .session_path(session_id)
+            .join("postmortems")
+            .join(task_id.to_string());
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| FlameError::Storage(format!("Failed to read task postmortem: {e}")))?;
+        let postmortem: TaskPostmortemMetadata = serde_json::from_str(&content)
+            .map_err(|e| FlameError::Storage(format!("Failed to parse task postmortem: {e}")))?;
+
+        Ok(Some(postmortem.into()))
+    }
+
+    /// Write the postmortem of a task.
+    fn write_task_postmortem(
+        &self,
+        session_id: &str,
+        task_id: u64,
+        postmortem: TaskPostmortem,
+    ) -> Result<(), FlameError> {
+        let dir = self.session_path(session_id).join("postmortems");
+        fs::create_dir_all(&dir).map_err(|e| {
+            FlameError::Storage(format!("Failed to create postmortems directory: {e}"))
+        })?;
+
+        let content =
+            serde_json::to_string(&TaskPostmortemMetadata::from(postmortem)).map_err(|e| {
+                FlameError::Storage(format!("Failed to serialize task postmortem: {e}"))
+            })?;
+        fs::write(dir.join(task_id.to_string()), content)
+            .map_err(|e| FlameError::Storage(format!("Failed to write task postmortem: {e}")))?;
+
+        Ok(())
+    }
+
     /// Convert TaskMetadata to Task.
     fn task_from_metadata(
         &self,
@@ -723,16 +1493,26 @@ impl FilesystemEngine {
         };
 
         Ok(Task {
-            id: meta.id as TaskID,
-            ssn_id: session_id.to_string(),
+            id: TaskID::from(meta.id as i64),
+            ssn_id: SessionID::from(session_id),
             version: meta.version,
             input,
+            input_ref: self.read_task_object_ref(session_id, meta.id, "input")?,
             output,
+            output_ref: self.read_task_object_ref(session_id, meta.id, "output")?,
+            artifacts: self.read_task_artifacts(session_id, meta.id)?,
+            overrides: self.read_task_overrides(session_id, meta.id)?,
             creation_time: DateTime::from_timestamp(meta.creation_time, 0)
                 .ok_or_else(|| FlameError::Storage("Invalid creation time".to_string()))?,
             completion_time,
             events: Vec::new(), // Events are handled by EventManager
             state,
+            failure_reason: self.read_task_failure(session_id, meta.id)?,
+            attempts: self.read_task_attempts(session_id, meta.id)?,
+            usage: self.read_task_usage(session_id, meta.id)?,
+            checkpoint: self.read_task_checkpoint(session_id, meta.id)?,
+            postmortem: self.read_task_postmortem(session_id, meta.id)?,
+            trace_id: self.read_task_trace_id(session_id, meta.id)?,
         })
     }
 
@@ -745,7 +1525,7 @@ impl FilesystemEngine {
             .and_then(|t| DateTime::from_timestamp(t, 0));
 
         Ok(Session {
-            id: meta.id.clone(),
+            id: SessionID::from(meta.id.as_str()),
             application: meta.application.clone(),
             slots: meta.slots,
             version: meta.version,
@@ -756,10 +1536,24 @@ impl FilesystemEngine {
                 .ok_or_else(|| FlameError::Storage("Invalid creation time".to_string()))?,
             completion_time,
             events: Vec::new(),
-            status: SessionStatus { state },
+            status: SessionStatus {
+                state,
+                ..Default::default()
+            },
             min_instances: meta.min_instances,
             max_instances: meta.max_instances,
             batch_size: meta.batch_size.max(1),
+            task_durations: TaskDurationStats::default(),
+            task_usage: TaskUsageStats::default(),
+            scratch_size: meta.scratch_size,
+            max_task_attempts: meta.max_task_attempts,
+            task_timeout: meta.task_timeout,
+            task_priority: meta.task_priority,
+            task_priorities: std::collections::HashMap::new(),
+            priority: meta.priority,
+            result_policy: ResultPolicy::try_from(meta.result_policy)?,
+            ttl_after_completion: meta.ttl_after_completion,
+            trace_id: meta.trace_id.clone(),
         })
     }
 
@@ -790,6 +1584,18 @@ impl FilesystemEngine {
             delay_release: Duration::seconds(meta.delay_release_seconds),
             schema,
             url: meta.url.clone(),
+            task_environments: meta.task_environments.clone(),
+            task_arguments: meta.task_arguments.clone(),
+            bundle_size: meta.bundle_size,
+            max_instances_per_node: meta.max_instances_per_node,
+            session_defaults: SessionDefaults::from(&meta.session_defaults),
+            health_probe: meta.health_probe.as_ref().map(HealthProbe::from),
+            fairness: FairnessPolicy::try_from(meta.fairness).unwrap_or_default(),
+            hooks: LifecycleHooks::from(&meta.hooks),
+            python_env: meta.python_env.as_ref().map(PythonEnvironment::from),
+            codec: PayloadCodec::try_from(meta.codec).unwrap_or_default(),
+            payload_type: meta.payload_type.clone(),
+            topology: TopologyPolicy::try_from(meta.topology).unwrap_or_default(),
         })
     }
 
@@ -798,12 +1604,45 @@ impl FilesystemEngine {
         let meta = self.read_application_metadata(app_name)?;
         if meta.state != ApplicationState::Enabled as i32 {
             return Err(FlameError::InvalidState(format!(
-                "Application {app_name} is not enabled"
+                "Application {app_name} is disabled"
             )));
         }
         Ok(())
     }
 
+    fn reopen_session(&self, id: &SessionID, policy: ReplayPolicy) -> Result<Session, FlameError> {
+        lock_ssn!(self, id);
+
+        if policy == ReplayPolicy::RetryFailed {
+            let task_count = self.get_task_count(id)?;
+            for task_id in 1..=task_count {
+                let Ok(mut task_meta) = self.read_task_metadata(id, TaskID::from(task_id as i64))
+                else {
+                    continue;
+                };
+                if task_meta.state != TaskState::Failed as u8 {
+                    continue;
+                }
+
+                task_meta.state = TaskState::Pending as u8;
+                task_meta.completion_time = 0;
+                task_meta.version += 1;
+                task_meta.checksum = calculate_checksum(&task_meta);
+                self.write_task_failure(id, task_meta.id, None)?;
+                self.write_task_usage(id, task_meta.id, None)?;
+                self.write_task_metadata(id, &task_meta)?;
+            }
+        }
+
+        let mut meta = self.read_session_metadata(id)?;
+        meta.state = SessionState::Open as i32;
+        meta.completion_time = None;
+        meta.version += 1;
+
+        self.write_session_metadata(id, &meta)?;
+        self.session_from_metadata(&meta)
+    }
+
     fn _update_task_state(
         &self,
         ssn_id: &SessionID,
@@ -863,12 +1702,39 @@ impl Engine for FilesystemEngine {
             delay_release_seconds: attr.delay_release.num_seconds(),
             schema,
             url: attr.url,
+            task_environments: attr.task_environments,
+            task_arguments: attr.task_arguments,
+            bundle_size: attr.bundle_size,
+            max_instances_per_node: attr.max_instances_per_node,
+            session_defaults: attr.session_defaults.into(),
+            health_probe: attr.health_probe.map(HealthProbeMetadata::from),
+            fairness: attr.fairness as i32,
+            hooks: attr.hooks.into(),
+            python_env: attr.python_env.map(PythonEnvironmentMetadata::from),
+            codec: attr.codec as i32,
+            payload_type: attr.payload_type,
+            topology: attr.topology as i32,
         };
 
         self.write_application_metadata(&name, &meta)?;
         Self::application_from_metadata(&meta)
     }
 
+    async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
+    ) -> Result<Application, FlameError> {
+        let _guard = lock_app!(self)?;
+
+        let mut meta = self.read_application_metadata(&name)?;
+        meta.version += 1;
+        meta.state = state as i32;
+
+        self.write_application_metadata(&name, &meta)?;
+        Self::application_from_metadata(&meta)
+    }
+
     async fn unregister_application(&self, name: String) -> Result<(), FlameError> {
         let _guard = lock_app!(self)?;
 
@@ -899,10 +1765,12 @@ impl Engine for FilesystemEngine {
         &self,
         name: String,
         attr: ApplicationAttributes,
+        expected_version: Option<u32>,
     ) -> Result<Application, FlameError> {
         let _guard = lock_app!(self)?;
 
         let mut meta = self.read_application_metadata(&name)?;
+        check_version("application", &name, expected_version, meta.version)?;
 
         let sessions_dir = self.base_path.join("sessions");
         if let Ok(entries) = fs::read_dir(&sessions_dir) {
@@ -937,6 +1805,18 @@ impl Engine for FilesystemEngine {
         meta.delay_release_seconds = attr.delay_release.num_seconds();
         meta.schema = schema;
         meta.url = attr.url;
+        meta.task_environments = attr.task_environments;
+        meta.task_arguments = attr.task_arguments;
+        meta.bundle_size = attr.bundle_size;
+        meta.max_instances_per_node = attr.max_instances_per_node;
+        meta.session_defaults = attr.session_defaults.into();
+        meta.health_probe = attr.health_probe.map(HealthProbeMetadata::from);
+        meta.fairness = attr.fairness as i32;
+        meta.hooks = attr.hooks.into();
+        meta.python_env = attr.python_env.map(PythonEnvironmentMetadata::from);
+        meta.codec = attr.codec as i32;
+        meta.payload_type = attr.payload_type;
+        meta.topology = attr.topology as i32;
 
         self.write_application_metadata(&name, &meta)?;
         Self::application_from_metadata(&meta)
@@ -992,7 +1872,7 @@ impl Engine for FilesystemEngine {
         };
 
         let meta = SessionMetadata {
-            id: attr.id.clone(),
+            id: attr.id.to_string(),
             application: attr.application.clone(),
             slots: attr.slots,
             version: 1,
@@ -1002,6 +1882,14 @@ impl Engine for FilesystemEngine {
             min_instances: attr.min_instances,
             max_instances: attr.max_instances,
             batch_size: attr.batch_size.max(1),
+            scratch_size: attr.scratch_size,
+            max_task_attempts: attr.max_task_attempts,
+            task_timeout: attr.task_timeout,
+            task_priority: attr.task_priority,
+            priority: attr.priority,
+            result_policy: attr.result_policy.into(),
+            ttl_after_completion: attr.ttl_after_completion,
+            trace_id: attr.trace_id.clone(),
             common_data_len,
         };
 
@@ -1030,12 +1918,13 @@ impl Engine for FilesystemEngine {
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         // Try to get existing session
         match self.read_session_metadata(&id) {
             Ok(meta) => {
-                // Session exists - validate state
-                if meta.state != SessionState::Open as i32 {
+                // Session exists - validate state, or reopen it by the replay policy
+                if meta.state != SessionState::Open as i32 && replay.is_none() {
                     return Err(FlameError::InvalidState(format!(
                         "Session {id} is not open"
                     )));
@@ -1055,7 +1944,12 @@ impl Engine for FilesystemEngine {
                     }
                 }
 
-                self.session_from_metadata(&meta)
+                match replay {
+                    Some(policy) if meta.state != SessionState::Open as i32 => {
+                        self.reopen_session(&id, policy)
+                    }
+                    _ => self.session_from_metadata(&meta),
+                }
             }
             Err(_) => {
                 // Session doesn't exist
@@ -1067,17 +1961,24 @@ impl Engine for FilesystemEngine {
         }
     }
 
-    async fn close_session(&self, id: SessionID) -> Result<Session, FlameError> {
+    async fn close_session(
+        &self,
+        id: SessionID,
+        expected_version: Option<u32>,
+        force: bool,
+    ) -> Result<Session, FlameError> {
         lock_ssn!(self, &id);
 
         let mut meta = self.read_session_metadata(&id)?;
+        check_version("session", &id, expected_version, meta.version)?;
 
         let task_count = self.get_task_count(&id)?;
         let mut pending_tasks = Vec::new();
+        let mut running = false;
 
         // First pass: check for running tasks and collect pending tasks
         for task_id in 1..=task_count {
-            if let Ok(task_meta) = self.read_task_metadata(&id, task_id as TaskID) {
+            if let Ok(task_meta) = self.read_task_metadata(&id, TaskID::from(task_id as i64)) {
                 let state = match TaskState::try_from(task_meta.state as i32) {
                     Ok(s) => s,
                     Err(e) => {
@@ -1093,13 +1994,15 @@ impl Engine for FilesystemEngine {
                         ));
                     }
                 };
-                if state == TaskState::Running {
-                    return Err(FlameError::Storage(
-                        "Cannot close session with running tasks".to_string(),
-                    ));
-                }
-                if state == TaskState::Pending {
-                    pending_tasks.push(task_id as TaskID);
+                // The quarantined tasks are cancelled with the pending ones,
+                // and the running ones too by force.
+                match state {
+                    TaskState::Pending | TaskState::Quarantined => {
+                        pending_tasks.push(TaskID::from(task_id as i64))
+                    }
+                    TaskState::Running if force => pending_tasks.push(TaskID::from(task_id as i64)),
+                    TaskState::Running => running = true,
+                    _ => {}
                 }
             }
         }
@@ -1109,8 +2012,12 @@ impl Engine for FilesystemEngine {
             self._update_task_state(&id, &task_id, TaskState::Cancelled)?;
         }
 
-        meta.state = SessionState::Closed as i32;
-        meta.completion_time = Some(Utc::now().timestamp());
+        if running {
+            meta.state = SessionState::Closing as i32;
+        } else {
+            meta.state = SessionState::Closed as i32;
+            meta.completion_time = Some(Utc::now().timestamp());
+        }
         meta.version += 1;
 
         self.write_session_metadata(&id, &meta)?;
@@ -1128,7 +2035,7 @@ impl Engine for FilesystemEngine {
 
         let task_count = self.get_task_count(&id)?;
         for task_id in 1..=task_count {
-            if let Ok(task_meta) = self.read_task_metadata(&id, task_id as TaskID) {
+            if let Ok(task_meta) = self.read_task_metadata(&id, TaskID::from(task_id as i64)) {
                 let state = match TaskState::try_from(task_meta.state as i32) {
                     Ok(s) => s,
                     Err(e) => {
@@ -1177,7 +2084,10 @@ impl Engine for FilesystemEngine {
                     if let Ok(session) = self.session_from_metadata(&meta) {
                         {
                             let mut locks = lock_app!(self)?;
-                            locks.insert(session_id.clone(), Arc::new(Mutex::new(())));
+                            locks.insert(
+                                SessionID::from(session_id.as_str()),
+                                Arc::new(Mutex::new(())),
+                            );
                         }
                         sessions.push(session);
                     }
@@ -1188,11 +2098,54 @@ impl Engine for FilesystemEngine {
         Ok(sessions)
     }
 
+    async fn find_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        let mut ids = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.base_path.join("sessions")) {
+            for entry in entries.flatten() {
+                ids.push(SessionID::from(
+                    entry.file_name().to_string_lossy().to_string(),
+                ));
+            }
+        }
+
+        Ok(ids)
+    }
+
+    async fn find_expired_session_ids(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SessionID>, FlameError> {
+        let mut ids = Vec::new();
+        for id in self.find_session_ids().await? {
+            let Ok(meta) = self.read_session_metadata(&id) else {
+                continue;
+            };
+            if meta.state != SessionState::Closed as i32 {
+                continue;
+            }
+            if let (Some(completion_time), Some(ttl)) =
+                (meta.completion_time, meta.ttl_after_completion)
+            {
+                if completion_time.saturating_add(ttl as i64) <= now.timestamp() {
+                    ids.push(id);
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
     async fn create_task(
         &self,
         ssn_id: SessionID,
-        input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError> {
+        let TaskAttributes {
+            input,
+            input_ref,
+            overrides,
+            trace_id,
+        } = attr;
         let ssn_meta = self.read_session_metadata(&ssn_id)?;
         if ssn_meta.state != SessionState::Open as i32 {
             return Err(FlameError::InvalidState(
@@ -1227,6 +2180,15 @@ impl Engine for FilesystemEngine {
 
         meta.checksum = calculate_checksum(&meta);
 
+        if let Some(input_ref) = &input_ref {
+            self.write_task_object_ref(&ssn_id, task_id, "input", input_ref)?;
+        }
+        if !overrides.is_empty() {
+            self.write_task_overrides(&ssn_id, task_id, &overrides)?;
+        }
+        if let Some(trace_id) = &trace_id {
+            self.write_task_trace_id(&ssn_id, task_id, trace_id)?;
+        }
         self.write_task_metadata(&ssn_id, &meta)?;
 
         self.task_from_metadata(&ssn_id, &meta)
@@ -1247,6 +2209,64 @@ impl Engine for FilesystemEngine {
         meta.version += 1;
         meta.checksum = calculate_checksum(&meta);
 
+        let attempts = self.read_task_attempts(&gid.ssn_id, meta.id)?;
+        self.write_task_attempts(&gid.ssn_id, meta.id, attempts + 1)?;
+        self.write_task_failure(&gid.ssn_id, meta.id, None)?;
+        self.write_task_usage(&gid.ssn_id, meta.id, None)?;
+        self.write_task_metadata(&gid.ssn_id, &meta)?;
+        self.task_from_metadata(&gid.ssn_id, &meta)
+    }
+
+    async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        lock_ssn!(self, &gid.ssn_id);
+
+        let mut meta = self.read_task_metadata(&gid.ssn_id, gid.task_id)?;
+        if meta.state != TaskState::Quarantined as u8 {
+            return Err(FlameError::InvalidState(format!(
+                "task <{gid}> is not quarantined"
+            )));
+        }
+
+        meta.state = TaskState::Pending as u8;
+        meta.completion_time = 0;
+        meta.version += 1;
+        meta.checksum = calculate_checksum(&meta);
+
+        self.write_task_attempts(&gid.ssn_id, meta.id, 0)?;
+        self.write_task_metadata(&gid.ssn_id, &meta)?;
+        self.task_from_metadata(&gid.ssn_id, &meta)
+    }
+
+    async fn checkpoint_task(&self, gid: TaskGID, checkpoint: String) -> Result<Task, FlameError> {
+        lock_ssn!(self, &gid.ssn_id);
+
+        let mut meta = self.read_task_metadata(&gid.ssn_id, gid.task_id)?;
+        if meta.state != TaskState::Running as u8 {
+            return Err(FlameError::InvalidState(format!(
+                "task <{gid}> is not running"
+            )));
+        }
+
+        meta.version += 1;
+        meta.checksum = calculate_checksum(&meta);
+
+        self.write_task_checkpoint(&gid.ssn_id, meta.id, &checkpoint)?;
+        self.write_task_metadata(&gid.ssn_id, &meta)?;
+        self.task_from_metadata(&gid.ssn_id, &meta)
+    }
+
+    async fn update_task_postmortem(
+        &self,
+        gid: TaskGID,
+        postmortem: TaskPostmortem,
+    ) -> Result<Task, FlameError> {
+        lock_ssn!(self, &gid.ssn_id);
+
+        let mut meta = self.read_task_metadata(&gid.ssn_id, gid.task_id)?;
+        meta.version += 1;
+        meta.checksum = calculate_checksum(&meta);
+
+        self.write_task_postmortem(&gid.ssn_id, meta.id, postmortem)?;
         self.write_task_metadata(&gid.ssn_id, &meta)?;
         self.task_from_metadata(&gid.ssn_id, &meta)
     }
@@ -1287,6 +2307,12 @@ impl Engine for FilesystemEngine {
             meta.output_offset = offset;
             meta.output_len = output.len() as u64;
         }
+        if let Some(ref output_ref) = task_result.output_ref {
+            self.write_task_object_ref(&gid.ssn_id, meta.id, "output", output_ref)?;
+        }
+        self.write_task_artifacts(&gid.ssn_id, meta.id, &task_result.artifacts)?;
+        self.write_task_failure(&gid.ssn_id, meta.id, task_result.failure_reason)?;
+        self.write_task_usage(&gid.ssn_id, meta.id, task_result.usage)?;
 
         meta.state = task_result.state as u8;
         meta.version += 1;
@@ -1308,7 +2334,7 @@ impl Engine for FilesystemEngine {
         let task_count = self.get_task_count(&ssn_id)?;
 
         for task_id in 1..=task_count {
-            if let Ok(meta) = self.read_task_metadata(&ssn_id, task_id as TaskID) {
+            if let Ok(meta) = self.read_task_metadata(&ssn_id, TaskID::from(task_id as i64)) {
                 if let Ok(task) = self.task_from_metadata(&ssn_id, &meta) {
                     tasks.push(task);
                 }
@@ -1357,6 +2383,7 @@ impl Engine for FilesystemEngine {
                 info: NodeInfo {
                     arch: meta.info_arch,
                     os: meta.info_os,
+                    ..Default::default()
                 },
             })),
             Err(FlameError::NotFound(_)) => Ok(None),
@@ -1432,6 +2459,7 @@ impl Engine for FilesystemEngine {
                         info: NodeInfo {
                             arch: meta.info_arch,
                             os: meta.info_os,
+                            ..Default::default()
                         },
                     });
                 }
@@ -1451,9 +2479,10 @@ impl Engine for FilesystemEngine {
             resreq_memory: executor.resreq.memory,
             slots: executor.slots,
             shim: i32::from(executor.shim),
-            task_id: executor.task_id,
-            ssn_id: executor.ssn_id.clone(),
+            task_id: executor.task_id.map(i64::from),
+            ssn_id: executor.ssn_id.as_ref().map(|id| id.to_string()),
             batch_index: executor.batch_index,
+            generation: executor.generation,
             creation_time: executor.creation_time.timestamp(),
             state: i32::from(executor.state),
         };
@@ -1481,9 +2510,13 @@ impl Engine for FilesystemEngine {
                 },
                 slots: meta.slots,
                 shim: Shim::try_from(meta.shim).unwrap_or_default(),
-                task_id: meta.task_id.map(|t| t as TaskID),
-                ssn_id: meta.ssn_id,
+                task_id: meta.task_id.map(TaskID::from),
+                bundle: vec![],
+                task_start: None,
+                ssn_id: meta.ssn_id.map(SessionID::from),
                 batch_index: meta.batch_index,
+                preempted: false,
+                generation: meta.generation,
                 creation_time: DateTime::from_timestamp(meta.creation_time, 0).unwrap_or_default(),
                 state: ExecutorState::from(meta.state),
             })),
@@ -1502,9 +2535,10 @@ impl Engine for FilesystemEngine {
             resreq_memory: executor.resreq.memory,
             slots: executor.slots,
             shim: i32::from(executor.shim),
-            task_id: executor.task_id,
-            ssn_id: executor.ssn_id.clone(),
+            task_id: executor.task_id.map(i64::from),
+            ssn_id: executor.ssn_id.as_ref().map(|id| id.to_string()),
             batch_index: executor.batch_index,
+            generation: executor.generation,
             creation_time: executor.creation_time.timestamp(),
             state: i32::from(executor.state),
         };
@@ -1534,9 +2568,13 @@ impl Engine for FilesystemEngine {
             },
             slots: meta.slots,
             shim: Shim::try_from(meta.shim).unwrap_or_default(),
-            task_id: meta.task_id.map(|t| t as TaskID),
-            ssn_id: meta.ssn_id,
+            task_id: meta.task_id.map(TaskID::from),
+            bundle: vec![],
+            task_start: None,
+            ssn_id: meta.ssn_id.map(SessionID::from),
             batch_index: meta.batch_index,
+            preempted: false,
+            generation: meta.generation,
             creation_time: DateTime::from_timestamp(meta.creation_time, 0).unwrap_or_default(),
             state,
         })
@@ -1600,9 +2638,13 @@ impl Engine for FilesystemEngine {
                             },
                             slots: meta.slots,
                             shim: Shim::try_from(meta.shim).unwrap_or_default(),
-                            task_id: meta.task_id.map(|t| t as TaskID),
-                            ssn_id: meta.ssn_id,
+                            task_id: meta.task_id.map(TaskID::from),
+                            bundle: vec![],
+                            task_start: None,
+                            ssn_id: meta.ssn_id.map(SessionID::from),
                             batch_index: meta.batch_index,
+                            preempted: false,
+                            generation: meta.generation,
                             creation_time: DateTime::from_timestamp(meta.creation_time, 0)
                                 .unwrap_or_default(),
                             state: ExecutorState::from(meta.state),
@@ -1614,6 +2656,82 @@ impl Engine for FilesystemEngine {
 
         Ok(executors)
     }
+
+    /// The job is recorded by an empty file named by its ID.
+    async fn create_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        fs::write(self.job_path(id), b"")
+            .map_err(|e| FlameError::Storage(format!("Failed to create job {id}: {e}")))
+    }
+
+    async fn delete_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        match fs::remove_file(self.job_path(id)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(FlameError::Storage(format!(
+                "Failed to delete job {id}: {e}"
+            ))),
+        }
+    }
+
+    async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        let mut ids = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.base_path.join("jobs")) {
+            for entry in entries.flatten() {
+                ids.push(SessionID::from(
+                    entry.file_name().to_string_lossy().to_string(),
+                ));
+            }
+        }
+
+        Ok(ids)
+    }
+
+    async fn backup(&self, path: &Path) -> Result<(), FlameError> {
+        let ssn_locks = self.ssn_locks.clone();
+        let (src, dst) = (self.base_path.clone(), path.to_path_buf());
+
+        // The data is copied in a blocking thread, so the runtime is not blocked
+        // by the copy of a large storage.
+        tokio::task::spawn_blocking(move || {
+            // Block the sessions from being created, deleted or updated while copying.
+            let locks = ssn_locks
+                .write()
+                .map_err(|e| FlameError::Storage(format!("App lock poisoned: {}", e)))?;
+            let _guards = locks
+                .values()
+                .map(|lock| {
+                    lock.lock()
+                        .map_err(|e| FlameError::Storage(format!("Session lock poisoned: {}", e)))
+                })
+                .collect::<Result<Vec<_>, FlameError>>()?;
+
+            copy_dir(&src, &dst)
+                .map_err(|e| FlameError::Storage(format!("Failed to back up storage: {e}")))
+        })
+        .await
+        .map_err(|e| FlameError::Internal(format!("Failed to back up storage: {e}")))?
+    }
+
+    /// The data is written to the files directly, so there's nothing to flush.
+    async fn checkpoint(&self) -> Result<(), FlameError> {
+        Ok(())
+    }
+}
+
+/// Copy the directory recursively.
+fn copy_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1629,7 +2747,7 @@ mod tests {
         let engine = FilesystemEngine {
             base_path: temp_dir.path().to_path_buf(),
             record_size: task_record_size(),
-            ssn_locks: RwLock::new(HashMap::new()),
+            ssn_locks: Arc::new(RwLock::new(HashMap::new())),
             node_locks: RwLock::new(HashMap::new()),
             executor_locks: RwLock::new(HashMap::new()),
         };
@@ -1737,6 +2855,18 @@ mod tests {
             delay_release: Duration::seconds(60),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
 
         let app = engine
@@ -1763,12 +2893,18 @@ mod tests {
             ..attr
         };
         let app3 = engine
-            .update_application("test-app".to_string(), updated_attr)
+            .update_application("test-app".to_string(), updated_attr.clone(), Some(1))
             .await
             .unwrap();
         assert_eq!(app3.description, Some("Updated description".to_string()));
         assert_eq!(app3.version, 2);
 
+        // The update of the stale version is a conflict.
+        let result = engine
+            .update_application("test-app".to_string(), updated_attr, Some(1))
+            .await;
+        assert!(matches!(result, Err(FlameError::Conflict(_))));
+
         // Unregister application
         engine
             .unregister_application("test-app".to_string())
@@ -1798,6 +2934,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
         engine
             .register_application("test-app".to_string(), app_attr)
@@ -1806,13 +2954,21 @@ mod tests {
 
         // Create session
         let ssn_attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: Some(Bytes::from("test data")),
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
 
         let session = engine.create_session(ssn_attr).await.unwrap();
@@ -1820,28 +2976,29 @@ mod tests {
         assert_eq!(session.status.state, SessionState::Open);
 
         // Get session
-        let session2 = engine
-            .get_session("test-session".to_string())
-            .await
-            .unwrap();
+        let session2 = engine.get_session("test-session".into()).await.unwrap();
         assert_eq!(session2.id, "test-session");
 
         // Find sessions
         let sessions = engine.find_session().await.unwrap();
         assert_eq!(sessions.len(), 1);
 
+        // The close of another version is a conflict.
+        let result = engine
+            .close_session("test-session".into(), Some(session.version + 1), false)
+            .await;
+        assert!(matches!(result, Err(FlameError::Conflict(_))));
+
         // Close session (should work since no tasks)
         let closed = engine
-            .close_session("test-session".to_string())
+            .close_session("test-session".into(), Some(session.version), false)
             .await
             .unwrap();
         assert_eq!(closed.status.state, SessionState::Closed);
+        assert_eq!(closed.version, session.version + 1);
 
         // Delete session
-        let deleted = engine
-            .delete_session("test-session".to_string())
-            .await
-            .unwrap();
+        let deleted = engine.delete_session("test-session".into()).await.unwrap();
         assert_eq!(deleted.id, "test-session");
     }
 
@@ -1863,6 +3020,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
         engine
             .register_application("test-app".to_string(), app_attr)
@@ -1870,33 +3039,53 @@ mod tests {
             .unwrap();
 
         let ssn_attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(ssn_attr).await.unwrap();
 
         // Create task with input
         let input = Bytes::from("test input data");
         let task = engine
-            .create_task("test-session".to_string(), Some(input.clone()))
+            .create_task(
+                "test-session".into(),
+                TaskAttributes {
+                    input: Some(input.clone()),
+                    ..TaskAttributes::default()
+                },
+            )
             .await
             .unwrap();
-        assert_eq!(task.id, 1);
+        assert_eq!(task.id, 1.into());
         assert_eq!(task.state, TaskState::Pending);
         assert_eq!(task.input, Some(input));
 
         // Get task
         let gid = TaskGID {
-            ssn_id: "test-session".to_string(),
-            task_id: 1,
+            ssn_id: "test-session".into(),
+            task_id: 1.into(),
         };
         let task2 = engine.get_task(gid.clone()).await.unwrap();
-        assert_eq!(task2.id, 1);
+        assert_eq!(task2.id, 1.into());
+        assert!(matches!(
+            engine
+                .checkpoint_task(gid.clone(), "ckpt".to_string())
+                .await,
+            Err(FlameError::InvalidState(_))
+        ));
 
         // Update task state
         let task3 = engine
@@ -1905,12 +3094,66 @@ mod tests {
             .unwrap();
         assert_eq!(task3.state, TaskState::Running);
 
+        // Checkpoint task, only when it's running
+        let checkpoint = "grpc://127.0.0.1:9090/test-session/ckpt-1".to_string();
+        let task3 = engine
+            .checkpoint_task(gid.clone(), checkpoint.clone())
+            .await
+            .unwrap();
+        assert_eq!(task3.checkpoint, Some(checkpoint.clone()));
+        assert_eq!(
+            engine.get_task(gid.clone()).await.unwrap().checkpoint,
+            Some(checkpoint)
+        );
+
+        // Record the postmortem of the crashed instance
+        let postmortem = TaskPostmortem {
+            executor_id: "executor-1".to_string(),
+            node: "node-1".to_string(),
+            exit_code: Some(134),
+            stderr_tail: Some("panicked at src/main.rs".to_string()),
+            creation_time: DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap(),
+            ..TaskPostmortem::default()
+        };
+        engine
+            .update_task_postmortem(gid.clone(), postmortem.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            engine.get_task(gid.clone()).await.unwrap().postmortem,
+            Some(postmortem)
+        );
+
         // Update task result
         let output = Bytes::from("test output data");
+        let artifacts = vec![
+            TaskArtifact {
+                name: "summary".to_string(),
+                data: Some(Bytes::from("test artifact")),
+                object_ref: None,
+            },
+            TaskArtifact {
+                name: "model".to_string(),
+                data: None,
+                object_ref: Some("grpc://127.0.0.1:9090/test-session/model".to_string()),
+            },
+        ];
+        let usage = TaskUsage {
+            cpu_time_ms: 250,
+            max_rss_bytes: 32 * 1024 * 1024,
+            read_bytes: 1024,
+            write_bytes: 2048,
+        };
         let result = TaskResult {
             state: TaskState::Succeed,
             output: Some(output.clone()),
+            output_ref: None,
             message: None,
+            artifacts: artifacts.clone(),
+            failure_reason: None,
+            usage: Some(usage),
+            postmortem: None,
+            codec: None,
         };
         let task4 = engine
             .update_task_result(gid.clone(), result)
@@ -1918,23 +3161,60 @@ mod tests {
             .unwrap();
         assert_eq!(task4.state, TaskState::Succeed);
         assert_eq!(task4.output, Some(output));
+        assert_eq!(task4.artifacts, artifacts);
+        assert_eq!(task4.usage, Some(usage));
+        let task4 = engine.get_task(gid.clone()).await.unwrap();
+        assert_eq!(task4.artifacts, artifacts);
+        assert_eq!(task4.usage, Some(usage));
 
         // Find tasks
-        let tasks = engine.find_tasks("test-session".to_string()).await.unwrap();
+        let tasks = engine.find_tasks("test-session".into()).await.unwrap();
         assert_eq!(tasks.len(), 1);
 
         // Create another task
         let task5 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task5.id, 2);
+        assert_eq!(task5.id, 2.into());
 
-        // Complete second task
+        // Fail second task, and retry it
         let gid2 = TaskGID {
-            ssn_id: "test-session".to_string(),
-            task_id: 2,
+            ssn_id: "test-session".into(),
+            task_id: 2.into(),
+        };
+        let result = TaskResult {
+            state: TaskState::Failed,
+            failure_reason: Some(FailureReason::ShimCrash),
+            ..TaskResult::default()
         };
+        let failed = engine
+            .update_task_result(gid2.clone(), result)
+            .await
+            .unwrap();
+        assert_eq!(failed.failure_reason, Some(FailureReason::ShimCrash));
+        assert_eq!(
+            engine.get_task(gid2.clone()).await.unwrap().failure_reason,
+            Some(FailureReason::ShimCrash)
+        );
+        let retried = engine.retry_task(gid2.clone()).await.unwrap();
+        assert_eq!(retried.failure_reason, None);
+        assert_eq!(retried.attempts, 1);
+
+        // Quarantine and release it
+        engine
+            .update_task_state(gid2.clone(), TaskState::Quarantined, None)
+            .await
+            .unwrap();
+        let released = engine.release_task(gid2.clone()).await.unwrap();
+        assert_eq!(released.state, TaskState::Pending);
+        assert_eq!(released.attempts, 0);
+        assert!(matches!(
+            engine.release_task(gid2.clone()).await,
+            Err(FlameError::InvalidState(_))
+        ));
+
+        // Complete second task
         engine
             .update_task_state(gid2, TaskState::Succeed, None)
             .await
@@ -1942,7 +3222,7 @@ mod tests {
 
         // Now we can close the session
         let closed = engine
-            .close_session("test-session".to_string())
+            .close_session("test-session".into(), None, false)
             .await
             .unwrap();
         assert_eq!(closed.status.state, SessionState::Closed);
@@ -1965,6 +3245,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
 
         engine
@@ -1998,6 +3290,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
         engine
             .register_application("test-app".to_string(), app_attr)
@@ -2005,13 +3309,21 @@ mod tests {
             .unwrap();
 
         let ssn_attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
 
         engine.create_session(ssn_attr.clone()).await.unwrap();
@@ -2023,6 +3335,86 @@ mod tests {
         assert!(matches!(err, FlameError::AlreadyExist(_)));
     }
 
+    #[tokio::test]
+    async fn test_task_overrides() {
+        let (engine, _temp_dir) = create_test_engine().await;
+
+        let app_attr = ApplicationAttributes {
+            task_environments: vec!["LOG_LEVEL".to_string()],
+            task_arguments: vec!["--level".to_string()],
+            ..ApplicationAttributes::default()
+        };
+        engine
+            .register_application("test-app".to_string(), app_attr)
+            .await
+            .unwrap();
+        let app = engine
+            .get_application("test-app".to_string())
+            .await
+            .unwrap();
+        assert_eq!(app.task_environments, vec!["LOG_LEVEL".to_string()]);
+        assert_eq!(app.task_arguments, vec!["--level".to_string()]);
+
+        let ssn_attr = SessionAttributes {
+            id: "test-session".into(),
+            application: "test-app".to_string(),
+            ..SessionAttributes::default()
+        };
+        engine.create_session(ssn_attr).await.unwrap();
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            arguments: vec!["--level=3".to_string()],
+            timeout: Some(60),
+            max_attempts: Some(2),
+            priority: Some(5),
+            deadline: Some(30_000),
+        };
+        let task = engine
+            .create_task(
+                "test-session".into(),
+                TaskAttributes {
+                    overrides: overrides.clone(),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(task.overrides, overrides);
+
+        let task = engine
+            .create_task("test-session".into(), TaskAttributes::default())
+            .await
+            .unwrap();
+        assert!(task.overrides.is_empty());
+
+        let gid = TaskGID {
+            ssn_id: "test-session".into(),
+            task_id: 1.into(),
+        };
+        let task = engine.get_task(gid).await.unwrap();
+        assert_eq!(task.overrides, overrides);
+        assert!(task.input_ref.is_none());
+
+        let input_ref = ObjectReference {
+            endpoint: "grpc://127.0.0.1:9090".to_string(),
+            key: "test-app/test-session/input-3".to_string(),
+            checksum: None,
+        };
+        let task = engine
+            .create_task(
+                "test-session".into(),
+                TaskAttributes {
+                    input_ref: Some(input_ref.clone()),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await
+            .unwrap();
+        let task = engine.get_task(task.gid()).await.unwrap();
+        assert_eq!(task.input_ref, Some(input_ref));
+    }
+
     #[tokio::test]
     async fn test_close_session_with_pending_tasks() {
         let (engine, _temp_dir) = create_test_engine().await;
@@ -2040,6 +3432,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
         engine
             .register_application("test-app".to_string(), app_attr)
@@ -2047,30 +3451,38 @@ mod tests {
             .unwrap();
 
         let ssn_attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(ssn_attr).await.unwrap();
 
         let task1 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
         assert_eq!(task1.state, TaskState::Pending);
 
         let task2 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
         assert_eq!(task2.state, TaskState::Pending);
 
         let closed = engine
-            .close_session("test-session".to_string())
+            .close_session("test-session".into(), None, false)
             .await
             .unwrap();
         assert_eq!(closed.status.state, SessionState::Closed);
@@ -2099,6 +3511,18 @@ mod tests {
             delay_release: Duration::seconds(0),
             schema: None,
             url: None,
+            task_environments: vec![],
+            task_arguments: vec![],
+            bundle_size: 1,
+            max_instances_per_node: None,
+            session_defaults: Default::default(),
+            health_probe: None,
+            fairness: Default::default(),
+            hooks: Default::default(),
+            python_env: None,
+            codec: Default::default(),
+            payload_type: None,
+            topology: Default::default(),
         };
         engine
             .register_application("test-app".to_string(), app_attr)
@@ -2106,18 +3530,26 @@ mod tests {
             .unwrap();
 
         let ssn_attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(ssn_attr).await.unwrap();
 
         let task = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
 
@@ -2126,8 +3558,22 @@ mod tests {
             .await
             .unwrap();
 
-        let result = engine.close_session("test-session".to_string()).await;
-        assert!(result.is_err());
+        // The session is closing until its running task is completed.
+        let session = engine
+            .close_session("test-session".into(), None, false)
+            .await
+            .unwrap();
+        assert_eq!(session.status.state, SessionState::Closing);
+        assert!(session.completion_time.is_none());
+
+        // The running task is cancelled by force.
+        let session = engine
+            .close_session("test-session".into(), None, true)
+            .await
+            .unwrap();
+        assert_eq!(session.status.state, SessionState::Closed);
+        let task = engine.get_task(task.gid()).await.unwrap();
+        assert_eq!(task.state, TaskState::Cancelled);
     }
 
     #[tokio::test]
@@ -2148,6 +3594,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -2173,6 +3620,24 @@ mod tests {
         assert!(deleted.is_none());
     }
 
+    #[tokio::test]
+    async fn test_job_ids() {
+        let (engine, _temp_dir) = create_test_engine().await;
+
+        engine.create_job(&SessionID::from("job-1")).await.unwrap();
+        engine.create_job(&SessionID::from("job-2")).await.unwrap();
+        // The job is recorded again, e.g. a job of the same ID after a failed deletion.
+        engine.create_job(&SessionID::from("job-1")).await.unwrap();
+
+        engine.delete_job(&SessionID::from("job-1")).await.unwrap();
+        // The deleted job is deleted again, e.g. by a retry.
+        engine.delete_job(&SessionID::from("job-1")).await.unwrap();
+        assert_eq!(
+            engine.find_job_ids().await.unwrap(),
+            vec![SessionID::from("job-2")]
+        );
+    }
+
     #[tokio::test]
     async fn test_executor_crud() {
         let (engine, _temp_dir) = create_test_engine().await;
@@ -2191,6 +3656,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
         engine.create_node(&node).await.unwrap();
@@ -2205,8 +3671,12 @@ mod tests {
             slots: 1,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::Void,
         };
@@ -2255,6 +3725,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
         engine.create_node(&node).await.unwrap();
@@ -2270,8 +3741,12 @@ mod tests {
                 slots: 1,
                 shim: Shim::Host,
                 task_id: None,
+                bundle: vec![],
+                task_start: None,
                 ssn_id: None,
                 batch_index: None,
+                preempted: false,
+                generation: 0,
                 creation_time: Utc::now(),
                 state: ExecutorState::Void,
             };
@@ -2286,4 +3761,26 @@ mod tests {
         let executors = engine.find_executors(None).await.unwrap();
         assert_eq!(executors.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_backup() {
+        let (engine, _temp_dir) = create_test_engine().await;
+        let app_attr = ApplicationAttributes::default();
+        engine
+            .register_application("test-app".to_string(), app_attr)
+            .await
+            .unwrap();
+
+        let backup_dir = TempDir::new().unwrap();
+        let backup_path = backup_dir.path().join("data");
+        engine.backup(&backup_path).await.unwrap();
+
+        let url = format!("filesystem://{}", backup_path.display());
+        let restored = FilesystemEngine::new_ptr(&url).await.unwrap();
+        let app = restored
+            .get_application("test-app".to_string())
+            .await
+            .unwrap();
+        assert_eq!(app.name, "test-app");
+    }
 }
@@ -11,25 +11,30 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::model::Executor;
 use crate::FlameError;
 use common::apis::{
-    Application, ApplicationAttributes, ApplicationID, CommonData, Event, ExecutorID,
-    ExecutorState, Node, Session, SessionAttributes, SessionID, Task, TaskGID, TaskInput,
-    TaskOutput, TaskResult, TaskState,
+    Application, ApplicationAttributes, ApplicationID, ApplicationState, CommonData, Event,
+    ExecutorID, ExecutorState, Node, ReplayPolicy, Session, SessionAttributes, SessionID, Task,
+    TaskAttributes, TaskGID, TaskOutput, TaskPostmortem, TaskResult, TaskState,
 };
 
+mod encryption;
 mod filesystem;
 mod none;
 mod sqlite;
 pub mod types;
 
+pub use encryption::EncryptedEngine;
 #[cfg(test)]
 pub use sqlite::SqliteEngine;
+pub use sqlite::SCHEMA_VERSION;
 
 pub type EnginePtr = Arc<dyn Engine>;
 
@@ -42,10 +47,20 @@ pub trait Engine: Send + Sync + 'static {
         attr: ApplicationAttributes,
     ) -> Result<Application, FlameError>;
     async fn unregister_application(&self, id: String) -> Result<(), FlameError>;
+    /// Update the application; fails with `FlameError::Conflict` if its version
+    /// is not the expected one, which is not checked if None.
     async fn update_application(
         &self,
         id: String,
         attr: ApplicationAttributes,
+        expected_version: Option<u32>,
+    ) -> Result<Application, FlameError>;
+    /// Enable or disable the application; the disabled application rejects
+    /// new sessions, while its existing sessions are not changed.
+    async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
     ) -> Result<Application, FlameError>;
     async fn get_application(&self, id: ApplicationID) -> Result<Application, FlameError>;
     async fn find_application(&self) -> Result<Vec<Application>, FlameError>;
@@ -53,26 +68,59 @@ pub trait Engine: Send + Sync + 'static {
     // Session operations
     async fn create_session(&self, attr: SessionAttributes) -> Result<Session, FlameError>;
     async fn get_session(&self, id: SessionID) -> Result<Session, FlameError>;
+    /// Get or create the session; the closed session is reopened only with a replay policy.
     async fn open_session(
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
+    ) -> Result<Session, FlameError>;
+    /// Close the session, and cancel its pending tasks; it's `Closing` until its
+    /// running tasks are completed, unless they're cancelled by `force`. Fails
+    /// with `FlameError::Conflict` if its version is not the expected one,
+    /// which is not checked if None.
+    async fn close_session(
+        &self,
+        id: SessionID,
+        expected_version: Option<u32>,
+        force: bool,
     ) -> Result<Session, FlameError>;
-    async fn close_session(&self, id: SessionID) -> Result<Session, FlameError>;
     async fn delete_session(&self, id: SessionID) -> Result<Session, FlameError>;
     async fn find_session(&self) -> Result<Vec<Session>, FlameError>;
+    /// The IDs of all the sessions, without loading them.
+    async fn find_session_ids(&self) -> Result<Vec<SessionID>, FlameError>;
+    /// The IDs of the closed sessions whose TTL after completion expired by `now`.
+    async fn find_expired_session_ids(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SessionID>, FlameError>;
 
     // Task operations
     async fn create_task(
         &self,
         ssn_id: SessionID,
-        task_input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError>;
 
     async fn get_task(&self, gid: TaskGID) -> Result<Task, FlameError>;
 
+    /// Reset the task to pending to dispatch it again, counting the attempt.
     async fn retry_task(&self, gid: TaskGID) -> Result<Task, FlameError>;
 
+    /// Release the quarantined task to pending, resetting its attempts.
+    async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError>;
+
+    /// Record the latest checkpoint of the running task, kept on retry.
+    async fn checkpoint_task(&self, gid: TaskGID, checkpoint: String) -> Result<Task, FlameError>;
+
+    /// Record the postmortem of the crashed instance running the task, kept on
+    /// retry until the next crash.
+    async fn update_task_postmortem(
+        &self,
+        gid: TaskGID,
+        postmortem: TaskPostmortem,
+    ) -> Result<Task, FlameError>;
+
     async fn delete_task(&self, gid: TaskGID) -> Result<Task, FlameError>;
 
     async fn update_task_state(
@@ -108,6 +156,37 @@ pub trait Engine: Send + Sync + 'static {
     ) -> Result<Executor, FlameError>;
     async fn delete_executor(&self, id: &ExecutorID) -> Result<(), FlameError>;
     async fn find_executors(&self, node: Option<&str>) -> Result<Vec<Executor>, FlameError>;
+
+    // Job operations
+    /// Record the running job, whose session is closed on recovery if the
+    /// job was lost by a restart; the jobs themselves are kept in memory.
+    /// Recording the job again is not an error.
+    async fn create_job(&self, id: &SessionID) -> Result<(), FlameError>;
+    /// Remove the record of the completed job.
+    async fn delete_job(&self, id: &SessionID) -> Result<(), FlameError>;
+    /// The IDs of the recorded jobs.
+    async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError>;
+
+    // Backup operations
+    /// Write a consistent copy of the engine data to `path`, which does not exist yet.
+    async fn backup(&self, path: &Path) -> Result<(), FlameError>;
+    /// Flush the written data to the durable storage, e.g. before exiting.
+    async fn checkpoint(&self) -> Result<(), FlameError>;
+}
+
+/// Check the version of the object for the optimistic concurrency of its update.
+pub fn check_version(
+    kind: &str,
+    id: &str,
+    expected: Option<u32>,
+    actual: u32,
+) -> Result<(), FlameError> {
+    match expected {
+        Some(expected) if expected != actual => Err(FlameError::Conflict(format!(
+            "{kind} <{id}> was updated concurrently: version {actual}, expected {expected}"
+        ))),
+        _ => Ok(()),
+    }
 }
 
 /// Connect to a storage engine based on the URL scheme.
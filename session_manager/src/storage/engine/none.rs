@@ -26,23 +26,25 @@ limitations under the License.
 //! - Evicted sessions are permanently lost (no persistence to fall back to)
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
 use stdng::{lock_ptr, MutexPtr};
 
 use crate::model::Executor;
 use crate::FlameError;
 use common::apis::{
-    Application, ApplicationAttributes, ApplicationID, ExecutorID, ExecutorState, Node, Session,
-    SessionAttributes, SessionID, SessionState, SessionStatus, Task, TaskGID, TaskID, TaskInput,
-    TaskOutput, TaskResult, TaskState,
+    Application, ApplicationAttributes, ApplicationID, ApplicationState, ExecutorID, ExecutorState,
+    Node, ReplayPolicy, Session, SessionAttributes, SessionID, SessionState, SessionStatus, Task,
+    TaskAttributes, TaskDurationStats, TaskGID, TaskID, TaskOutput, TaskPostmortem, TaskResult,
+    TaskState, TaskUsageStats,
 };
 
-use super::{Engine, EnginePtr};
+use super::{check_version, Engine, EnginePtr};
 
 /// None Storage Engine - stores nothing, only allocates task IDs.
 ///
@@ -72,7 +74,7 @@ impl NoneEngine {
         let counter = counters
             .entry(ssn_id.clone())
             .or_insert_with(|| Arc::new(AtomicI64::new(0)));
-        Ok(counter.fetch_add(1, Ordering::SeqCst) + 1)
+        Ok(TaskID::from(counter.fetch_add(1, Ordering::SeqCst) + 1))
     }
 
     /// Initialize task counter for a new session.
@@ -116,6 +118,18 @@ impl Engine for NoneEngine {
             delay_release: attr.delay_release,
             schema: attr.schema,
             url: attr.url,
+            task_environments: attr.task_environments,
+            task_arguments: attr.task_arguments,
+            bundle_size: attr.bundle_size,
+            max_instances_per_node: attr.max_instances_per_node,
+            session_defaults: attr.session_defaults,
+            health_probe: attr.health_probe,
+            fairness: attr.fairness,
+            hooks: attr.hooks,
+            python_env: attr.python_env,
+            codec: attr.codec,
+            payload_type: attr.payload_type,
+            topology: attr.topology,
         };
 
         let mut apps = lock_ptr!(self.applications)?;
@@ -134,11 +148,13 @@ impl Engine for NoneEngine {
         &self,
         id: String,
         attr: ApplicationAttributes,
+        expected_version: Option<u32>,
     ) -> Result<Application, FlameError> {
         let mut apps = lock_ptr!(self.applications)?;
         let app = apps
             .get(&id)
             .ok_or_else(|| FlameError::NotFound(format!("application <{}>", id)))?;
+        check_version("application", &id, expected_version, app.version)?;
 
         let updated = Application {
             name: id.clone(),
@@ -157,12 +173,39 @@ impl Engine for NoneEngine {
             delay_release: attr.delay_release,
             schema: attr.schema,
             url: attr.url,
+            task_environments: attr.task_environments,
+            task_arguments: attr.task_arguments,
+            bundle_size: attr.bundle_size,
+            max_instances_per_node: attr.max_instances_per_node,
+            session_defaults: attr.session_defaults,
+            health_probe: attr.health_probe,
+            fairness: attr.fairness,
+            hooks: attr.hooks,
+            python_env: attr.python_env,
+            codec: attr.codec,
+            payload_type: attr.payload_type,
+            topology: attr.topology,
         };
 
         apps.insert(id, updated.clone());
         Ok(updated)
     }
 
+    async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
+    ) -> Result<Application, FlameError> {
+        let mut apps = lock_ptr!(self.applications)?;
+        let app = apps
+            .get_mut(&name)
+            .ok_or_else(|| FlameError::NotFound(format!("application <{}>", name)))?;
+        app.state = state;
+        app.version += 1;
+
+        Ok(app.clone())
+    }
+
     async fn get_application(&self, id: ApplicationID) -> Result<Application, FlameError> {
         let apps = lock_ptr!(self.applications)?;
         apps.get(&id)
@@ -188,8 +231,20 @@ impl Engine for NoneEngine {
             min_instances: attr.min_instances,
             max_instances: attr.max_instances,
             batch_size: attr.batch_size.max(1),
+            task_durations: TaskDurationStats::default(),
+            task_usage: TaskUsageStats::default(),
+            scratch_size: attr.scratch_size,
+            max_task_attempts: attr.max_task_attempts,
+            task_timeout: attr.task_timeout,
+            task_priority: attr.task_priority,
+            task_priorities: HashMap::new(),
+            priority: attr.priority,
+            result_policy: attr.result_policy,
+            ttl_after_completion: attr.ttl_after_completion,
+            trace_id: attr.trace_id,
             status: SessionStatus {
                 state: SessionState::Open,
+                ..Default::default()
             },
             creation_time: Utc::now(),
             completion_time: None,
@@ -208,6 +263,7 @@ impl Engine for NoneEngine {
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        _replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         match spec {
             Some(attr) => self.create_session(attr).await,
@@ -215,7 +271,12 @@ impl Engine for NoneEngine {
         }
     }
 
-    async fn close_session(&self, id: SessionID) -> Result<Session, FlameError> {
+    async fn close_session(
+        &self,
+        id: SessionID,
+        _expected_version: Option<u32>,
+        _force: bool,
+    ) -> Result<Session, FlameError> {
         Err(FlameError::NotFound(format!("session <{}>", id)))
     }
 
@@ -228,13 +289,30 @@ impl Engine for NoneEngine {
         Ok(vec![])
     }
 
+    async fn find_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        Ok(vec![])
+    }
+
+    async fn find_expired_session_ids(
+        &self,
+        _now: DateTime<Utc>,
+    ) -> Result<Vec<SessionID>, FlameError> {
+        Ok(vec![])
+    }
+
     // ========== Task operations ==========
 
     async fn create_task(
         &self,
         ssn_id: SessionID,
-        task_input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError> {
+        let TaskAttributes {
+            input: task_input,
+            input_ref,
+            overrides,
+            trace_id,
+        } = attr;
         let task_id = self.next_task_id(&ssn_id)?;
 
         Ok(Task {
@@ -245,8 +323,18 @@ impl Engine for NoneEngine {
             creation_time: Utc::now(),
             completion_time: None,
             input: task_input,
+            input_ref,
             output: None,
+            output_ref: None,
+            artifacts: vec![],
+            overrides,
             events: vec![],
+            failure_reason: None,
+            attempts: 0,
+            usage: None,
+            checkpoint: None,
+            postmortem: None,
+            trace_id,
         })
     }
 
@@ -258,6 +346,22 @@ impl Engine for NoneEngine {
         Err(FlameError::NotFound(format!("task <{}>", gid)))
     }
 
+    async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        Err(FlameError::NotFound(format!("task <{}>", gid)))
+    }
+
+    async fn checkpoint_task(&self, gid: TaskGID, _checkpoint: String) -> Result<Task, FlameError> {
+        Err(FlameError::NotFound(format!("task <{}>", gid)))
+    }
+
+    async fn update_task_postmortem(
+        &self,
+        gid: TaskGID,
+        _postmortem: TaskPostmortem,
+    ) -> Result<Task, FlameError> {
+        Err(FlameError::NotFound(format!("task <{}>", gid)))
+    }
+
     async fn delete_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
         Err(FlameError::NotFound(format!("task <{}>", gid)))
     }
@@ -334,24 +438,57 @@ impl Engine for NoneEngine {
     async fn find_executors(&self, _node: Option<&str>) -> Result<Vec<Executor>, FlameError> {
         Ok(vec![])
     }
+
+    // ========== Job operations ==========
+
+    async fn create_job(&self, _id: &SessionID) -> Result<(), FlameError> {
+        Ok(())
+    }
+
+    async fn delete_job(&self, _id: &SessionID) -> Result<(), FlameError> {
+        Ok(())
+    }
+
+    async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        Ok(vec![])
+    }
+
+    async fn backup(&self, _path: &Path) -> Result<(), FlameError> {
+        Err(FlameError::InvalidConfig(
+            "the none storage engine has no data to back up".to_string(),
+        ))
+    }
+
+    async fn checkpoint(&self) -> Result<(), FlameError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::apis::ResultPolicy;
 
     #[tokio::test]
     async fn test_none_engine_create_session() {
         let engine = NoneEngine::new_ptr("none").await.unwrap();
 
         let attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 1,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
 
         let session = engine.create_session(attr).await.unwrap();
@@ -364,7 +501,7 @@ mod tests {
     async fn test_none_engine_get_session_returns_not_found() {
         let engine = NoneEngine::new_ptr("none").await.unwrap();
 
-        let result = engine.get_session("test-session".to_string()).await;
+        let result = engine.get_session("test-session".into()).await;
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), FlameError::NotFound(_)));
     }
@@ -382,33 +519,41 @@ mod tests {
         let engine = NoneEngine::new_ptr("none").await.unwrap();
 
         let attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 1,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(attr).await.unwrap();
 
         let task1 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task1.id, 1);
+        assert_eq!(task1.id, 1.into());
 
         let task2 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task2.id, 2);
+        assert_eq!(task2.id, 2.into());
 
         let task3 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task3.id, 3);
+        assert_eq!(task3.id, 3.into());
     }
 
     #[tokio::test]
@@ -416,44 +561,60 @@ mod tests {
         let engine = NoneEngine::new_ptr("none").await.unwrap();
 
         let attr1 = SessionAttributes {
-            id: "session-1".to_string(),
+            id: "session-1".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 1,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(attr1).await.unwrap();
 
         let attr2 = SessionAttributes {
-            id: "session-2".to_string(),
+            id: "session-2".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 1,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(attr2).await.unwrap();
 
         let task1_s1 = engine
-            .create_task("session-1".to_string(), None)
+            .create_task("session-1".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task1_s1.id, 1);
+        assert_eq!(task1_s1.id, 1.into());
 
         let task1_s2 = engine
-            .create_task("session-2".to_string(), None)
+            .create_task("session-2".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task1_s2.id, 1);
+        assert_eq!(task1_s2.id, 1.into());
 
         let task2_s1 = engine
-            .create_task("session-1".to_string(), None)
+            .create_task("session-1".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task2_s1.id, 2);
+        assert_eq!(task2_s1.id, 2.into());
     }
 
     #[tokio::test]
@@ -461,31 +622,39 @@ mod tests {
         let engine = NoneEngine::new_ptr("none").await.unwrap();
 
         let attr = SessionAttributes {
-            id: "test-session".to_string(),
+            id: "test-session".into(),
             application: "test-app".to_string(),
             slots: 1,
             common_data: None,
             min_instances: 1,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         };
         engine.create_session(attr.clone()).await.unwrap();
 
         let task1 = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task1.id, 1);
+        assert_eq!(task1.id, 1.into());
 
-        let result = engine.delete_session("test-session".to_string()).await;
+        let result = engine.delete_session("test-session".into()).await;
         assert!(result.is_err());
 
         engine.create_session(attr).await.unwrap();
 
         let task_new = engine
-            .create_task("test-session".to_string(), None)
+            .create_task("test-session".into(), TaskAttributes::default())
             .await
             .unwrap();
-        assert_eq!(task_new.id, 1);
+        assert_eq!(task_new.id, 1.into());
     }
 }
@@ -12,6 +12,7 @@ limitations under the License.
 */
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time;
@@ -32,22 +33,29 @@ use stdng::{logs::TraceFn, trace_fn};
 use common::{
     apis::{
         Application, ApplicationAttributes, ApplicationID, ApplicationSchema, ApplicationState,
-        CommonData, Event, ExecutorID, ExecutorState, Node, Session, SessionAttributes, SessionID,
-        SessionState, SessionStatus, Shim, Task, TaskGID, TaskID, TaskInput, TaskOutput,
-        TaskResult, TaskState, DEFAULT_DELAY_RELEASE, DEFAULT_MAX_INSTANCES,
+        CommonData, Event, ExecutorID, ExecutorState, Node, ReplayPolicy, Session,
+        SessionAttributes, SessionID, SessionState, SessionStatus, Shim, Task, TaskAttributes,
+        TaskGID, TaskID, TaskOutput, TaskPostmortem, TaskResult, TaskState, DEFAULT_DELAY_RELEASE,
+        DEFAULT_MAX_INSTANCES,
     },
     FlameError,
 };
 
 use crate::model::Executor;
 use crate::storage::engine::types::{
-    AppSchemaDao, ApplicationDao, EventDao, ExecutorDao, NodeDao, SessionDao, TaskDao,
+    AppSchemaDao, ApplicationDao, EventDao, ExecutorDao, HealthProbeDao, LifecycleHooksDao,
+    NodeDao, ObjectReferenceDao, PythonEnvironmentDao, SessionDao, SessionDefaultsDao,
+    TaskArtifactDao, TaskDao, TaskOverridesDao, TaskPostmortemDao, TaskUsageDao,
 };
 
-use crate::storage::engine::{Engine, EnginePtr};
+use crate::storage::engine::{check_version, Engine, EnginePtr};
 
 const SQLITE_SQL: &str = "migrations/sqlite";
 
+/// The schema version of the database this session manager works with, i.e.
+/// the version of its latest migration; it must be bumped with each migration.
+pub const SCHEMA_VERSION: i64 = 20261015000018;
+
 pub struct SqliteEngine {
     pool: SqlitePool,
 }
@@ -79,14 +87,82 @@ impl SqliteEngine {
         let migrator = sqlx::migrate::Migrator::new(migrations)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        // Refuse to touch the database of a newer schema, e.g. after a downgrade,
+        // before the migrations run; otherwise it may be corrupted by this version.
+        let (applied, written_by) = Self::schema_version(&db).await?;
+        check_schema_version(applied, written_by.as_deref())?;
+        if let Some(latest) = migrator.iter().map(|m| m.version).max() {
+            if latest > SCHEMA_VERSION {
+                return Err(FlameError::VersionMismatch(format!(
+                    "the migrations in <{SQLITE_SQL}> are up to schema <{latest}>, but this \
+                     session manager supports schema <{SCHEMA_VERSION}>; install the \
+                     migrations of this version, or upgrade the session manager"
+                )));
+            }
+        }
+
         migrator
             .run(&db)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
+        let (applied, _) = Self::schema_version(&db).await?;
+        if applied.unwrap_or_default() < SCHEMA_VERSION {
+            return Err(FlameError::VersionMismatch(format!(
+                "the database is of schema <{}>, but this session manager requires schema \
+                 <{SCHEMA_VERSION}>; the migrations in <{SQLITE_SQL}> are incomplete, check \
+                 them by `flmctl migrate --status`",
+                applied.unwrap_or_default()
+            )));
+        }
+
+        sqlx::query(
+            "INSERT INTO schema_version (id, version, flame_version, update_time) \
+             VALUES (1, ?, ?, ?) \
+             ON CONFLICT(id) DO UPDATE SET version=excluded.version, \
+             flame_version=excluded.flame_version, update_time=excluded.update_time",
+        )
+        .bind(SCHEMA_VERSION)
+        .bind(env!("CARGO_PKG_VERSION"))
+        .bind(Utc::now().timestamp())
+        .execute(&db)
+        .await
+        .map_err(|e| FlameError::Storage(format!("failed to record schema version: {e}")))?;
+
         Ok(Arc::new(SqliteEngine { pool: db }))
     }
 
+    /// The latest migration applied to the database and the version of Flame
+    /// which applied it; None for a new database.
+    async fn schema_version(db: &SqlitePool) -> Result<(Option<i64>, Option<String>), FlameError> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type='table' \
+             AND name IN ('_sqlx_migrations', 'schema_version')",
+        )
+        .fetch_all(db)
+        .await
+        .map_err(|e| FlameError::Storage(format!("failed to get schema version: {e}")))?;
+
+        let mut applied = None;
+        if tables.iter().any(|t| t == "_sqlx_migrations") {
+            applied = sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+                .fetch_one(db)
+                .await
+                .map_err(|e| FlameError::Storage(format!("failed to get schema version: {e}")))?;
+        }
+
+        let mut written_by = None;
+        if tables.iter().any(|t| t == "schema_version") {
+            written_by = sqlx::query_scalar("SELECT flame_version FROM schema_version WHERE id=1")
+                .fetch_optional(db)
+                .await
+                .map_err(|e| FlameError::Storage(format!("failed to get schema version: {e}")))?;
+        }
+
+        Ok((applied, written_by))
+    }
+
     async fn _count_open_tasks(
         &self,
         tx: &mut SqliteConnection,
@@ -143,6 +219,31 @@ impl SqliteEngine {
         Ok(count)
     }
 
+    /// The error of the update conditioned on the version which matched no row,
+    /// i.e. the object is not found or was updated concurrently.
+    async fn _version_error(
+        &self,
+        tx: &mut SqliteConnection,
+        kind: &str,
+        sql: &str,
+        id: &str,
+        expected_version: Option<u32>,
+    ) -> FlameError {
+        let version: Result<Option<u32>, _> = sqlx::query_scalar(sql)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await;
+        match version {
+            Ok(Some(version)) => check_version(kind, id, expected_version, version)
+                .err()
+                .unwrap_or(FlameError::Storage(format!(
+                    "failed to update {kind} <{id}>"
+                ))),
+            Ok(None) => FlameError::NotFound(format!("{kind} <{id}>")),
+            Err(e) => FlameError::Storage(format!("failed to get {kind} <{id}>: {e}")),
+        }
+    }
+
     async fn _list_session_ids(
         &self,
         tx: &mut SqliteConnection,
@@ -195,9 +296,22 @@ impl SqliteEngine {
         tx: &mut SqliteConnection,
         attr: SessionAttributes,
     ) -> Result<Session, FlameError> {
+        let state: Option<i32> = sqlx::query_scalar("SELECT state FROM applications WHERE name=?")
+            .bind(attr.application.clone())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        if state == Some(ApplicationState::Disabled as i32) {
+            return Err(FlameError::InvalidState(format!(
+                "application <{}> is disabled",
+                attr.application
+            )));
+        }
+
         let common_data: Option<Vec<u8>> = attr.common_data.map(Bytes::into);
-        let sql = r#"INSERT INTO sessions (id, application, slots, common_data, creation_time, state, min_instances, max_instances)
+        let sql = r#"INSERT INTO sessions (id, application, slots, common_data, creation_time, state, min_instances, max_instances, scratch_size, max_task_attempts, task_timeout, task_priority, priority, result_policy, ttl_after_completion, trace_id)
             VALUES (
+                ?,
                 ?,
                 (SELECT name FROM applications WHERE name=? AND state=?),
                 ?,
@@ -205,6 +319,13 @@ impl SqliteEngine {
                 ?,
                 ?,
                 ?,
+                ?,
+                ?,
+                ?,
+                ?,
+                ?,
+                ?,
+                ?,
                 ?
             )
             RETURNING *"#;
@@ -218,6 +339,45 @@ impl SqliteEngine {
             .bind(SessionState::Open as i32)
             .bind(attr.min_instances as i64)
             .bind(attr.max_instances.map(|v| v as i64))
+            .bind(attr.scratch_size.map(|v| v as i64))
+            .bind(attr.max_task_attempts.map(|v| v as i64))
+            .bind(attr.task_timeout.map(|v| v as i64))
+            .bind(attr.task_priority.map(|v| v as i64))
+            .bind(attr.priority as i64)
+            .bind(i32::from(attr.result_policy))
+            .bind(attr.ttl_after_completion.map(|v| v as i64))
+            .bind(attr.trace_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        ssn.try_into()
+    }
+
+    /// Internal helper to reopen the closed session within an existing transaction.
+    async fn _reopen_session(
+        tx: &mut SqliteConnection,
+        id: SessionID,
+        policy: ReplayPolicy,
+    ) -> Result<Session, FlameError> {
+        if policy == ReplayPolicy::RetryFailed {
+            let retry_failed_sql = "UPDATE tasks SET state=?, completion_time=NULL, failure_reason=NULL, usage=NULL, version=version+1 WHERE ssn_id=? AND state=?";
+            sqlx::query(retry_failed_sql)
+                .bind(TaskState::Pending as i32)
+                .bind(id.clone())
+                .bind(TaskState::Failed as i32)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| FlameError::Storage(e.to_string()))?;
+        }
+
+        let reopen_session_sql = r#"UPDATE sessions
+            SET state=?, completion_time=NULL, version=version+1
+            WHERE id=?
+            RETURNING *"#;
+        let ssn: SessionDao = sqlx::query_as(reopen_session_sql)
+            .bind(SessionState::Open as i32)
+            .bind(id)
             .fetch_one(&mut *tx)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
@@ -243,6 +403,21 @@ impl Engine for SqliteEngine {
 
         let schema: Option<Json<AppSchemaDao>> =
             attr.schema.clone().map(AppSchemaDao::from).map(Json);
+        let session_defaults: Option<Json<SessionDefaultsDao>> =
+            (!attr.session_defaults.is_empty())
+                .then(|| Json(SessionDefaultsDao::from(attr.session_defaults.clone())));
+        let health_probe: Option<Json<HealthProbeDao>> = attr
+            .health_probe
+            .clone()
+            .map(HealthProbeDao::from)
+            .map(Json);
+        let hooks: Option<Json<LifecycleHooksDao>> =
+            (!attr.hooks.is_empty()).then(|| Json(LifecycleHooksDao::from(attr.hooks.clone())));
+        let python_env: Option<Json<PythonEnvironmentDao>> = attr
+            .python_env
+            .clone()
+            .map(PythonEnvironmentDao::from)
+            .map(Json);
 
         let sql = r#"INSERT INTO applications
             (
@@ -257,9 +432,21 @@ impl Engine for SqliteEngine {
                 delay_release, 
                 schema, 
                 url,
+                task_environments,
+                task_arguments,
+                bundle_size,
+                max_instances_per_node,
+                session_defaults,
+                health_probe,
+                fairness,
+                hooks,
+                python_env,
+                codec,
+                payload_type,
+                topology,
                 creation_time, 
                 state)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *"#;
         let app: ApplicationDao = sqlx::query_as(sql)
             .bind(name)
@@ -273,6 +460,18 @@ impl Engine for SqliteEngine {
             .bind(attr.delay_release.num_seconds())
             .bind(schema)
             .bind(attr.url)
+            .bind(Json(attr.task_environments))
+            .bind(Json(attr.task_arguments))
+            .bind(attr.bundle_size)
+            .bind(attr.max_instances_per_node)
+            .bind(session_defaults)
+            .bind(health_probe)
+            .bind(attr.fairness as i32)
+            .bind(hooks)
+            .bind(python_env)
+            .bind(attr.codec as i32)
+            .bind(attr.payload_type)
+            .bind(attr.topology as i32)
             .bind(Utc::now().timestamp())
             .bind(ApplicationState::Enabled as i32)
             .fetch_one(&mut *tx)
@@ -290,12 +489,15 @@ impl Engine for SqliteEngine {
         &self,
         name: String,
         attr: ApplicationAttributes,
+        expected_version: Option<u32>,
     ) -> Result<Application, FlameError> {
         trace_fn!("Sqlite::update_application");
 
+        // Take the write lock at first, so the concurrent updates wait for each
+        // other instead of failing to upgrade the read of the version.
         let mut tx = self
             .pool
-            .begin()
+            .begin_with("BEGIN IMMEDIATE")
             .await
             .map_err(|e| FlameError::Storage(format!("failed to begin TX: {e}")))?;
 
@@ -308,6 +510,21 @@ impl Engine for SqliteEngine {
 
         let schema: Option<Json<AppSchemaDao>> =
             attr.schema.clone().map(AppSchemaDao::from).map(Json);
+        let session_defaults: Option<Json<SessionDefaultsDao>> =
+            (!attr.session_defaults.is_empty())
+                .then(|| Json(SessionDefaultsDao::from(attr.session_defaults.clone())));
+        let health_probe: Option<Json<HealthProbeDao>> = attr
+            .health_probe
+            .clone()
+            .map(HealthProbeDao::from)
+            .map(Json);
+        let hooks: Option<Json<LifecycleHooksDao>> =
+            (!attr.hooks.is_empty()).then(|| Json(LifecycleHooksDao::from(attr.hooks.clone())));
+        let python_env: Option<Json<PythonEnvironmentDao>> = attr
+            .python_env
+            .clone()
+            .map(PythonEnvironmentDao::from)
+            .map(Json);
 
         let sql = r#"UPDATE applications
                     SET schema=?,
@@ -320,11 +537,23 @@ impl Engine for SqliteEngine {
                         max_instances=?,
                         delay_release=?,
                         url=?,
+                        task_environments=?,
+                        task_arguments=?,
+                        bundle_size=?,
+                        max_instances_per_node=?,
+                        session_defaults=?,
+                        health_probe=?,
+                        fairness=?,
+                        hooks=?,
+                        python_env=?,
+                        codec=?,
+                        payload_type=?,
+                        topology=?,
                         version=version+1
-                    WHERE name=?
+                    WHERE name=? AND (? IS NULL OR version=?)
                     RETURNING *"#;
 
-        let app: ApplicationDao = sqlx::query_as(sql)
+        let app: Option<ApplicationDao> = sqlx::query_as(sql)
             .bind(schema)
             .bind(attr.description)
             .bind(Json(attr.labels))
@@ -335,10 +564,30 @@ impl Engine for SqliteEngine {
             .bind(attr.max_instances)
             .bind(attr.delay_release.num_seconds())
             .bind(attr.url)
-            .bind(name)
-            .fetch_one(&mut *tx)
+            .bind(Json(attr.task_environments))
+            .bind(Json(attr.task_arguments))
+            .bind(attr.bundle_size)
+            .bind(attr.max_instances_per_node)
+            .bind(session_defaults)
+            .bind(health_probe)
+            .bind(attr.fairness as i32)
+            .bind(hooks)
+            .bind(python_env)
+            .bind(attr.codec as i32)
+            .bind(attr.payload_type)
+            .bind(attr.topology as i32)
+            .bind(name.clone())
+            .bind(expected_version)
+            .bind(expected_version)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(|e| FlameError::Storage(format!("failed to update application: {e}")))?;
+        let Some(app) = app else {
+            let sql = "SELECT version FROM applications WHERE name=?";
+            return Err(self
+                ._version_error(&mut tx, "application", sql, &name, expected_version)
+                .await);
+        };
 
         tx.commit()
             .await
@@ -347,6 +596,29 @@ impl Engine for SqliteEngine {
         Ok(app.try_into()?)
     }
 
+    async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
+    ) -> Result<Application, FlameError> {
+        trace_fn!("Sqlite::update_application_state");
+
+        let sql = "UPDATE applications SET state=?, version=version+1 WHERE name=? RETURNING *";
+        let app: Option<ApplicationDao> = sqlx::query_as(sql)
+            .bind(state as i32)
+            .bind(name.clone())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(format!("failed to update application: {e}")))?;
+
+        match app {
+            Some(app) => Ok(app.try_into()?),
+            None => Err(FlameError::NotFound(format!(
+                "application <{name}> not found"
+            ))),
+        }
+    }
+
     async fn unregister_application(&self, name: String) -> Result<(), FlameError> {
         trace_fn!("Sqlite::unregister_application");
 
@@ -465,6 +737,7 @@ impl Engine for SqliteEngine {
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         let mut tx = self
             .pool
@@ -474,17 +747,20 @@ impl Engine for SqliteEngine {
 
         let ssn = match Self::_get_session(&mut tx, id.clone()).await? {
             Some(session) => {
-                // Session exists - validate state
-                if session.status.state != SessionState::Open {
-                    return Err(FlameError::InvalidState(format!(
-                        "session <{id}> is not open"
-                    )));
-                }
                 // If spec provided, validate it matches
                 if let Some(ref attr) = spec {
                     session.validate_spec(attr)?;
                 }
-                session
+                // Session exists - validate state, or reopen it by the replay policy
+                match (session.status.state, replay) {
+                    (SessionState::Open, _) => session,
+                    (_, Some(policy)) => Self::_reopen_session(&mut tx, id.clone(), policy).await?,
+                    (_, None) => {
+                        return Err(FlameError::InvalidState(format!(
+                            "session <{id}> is not open"
+                        )));
+                    }
+                }
             }
             None => {
                 // Session doesn't exist
@@ -525,49 +801,66 @@ impl Engine for SqliteEngine {
         Ok(ssn)
     }
 
-    async fn close_session(&self, id: SessionID) -> Result<Session, FlameError> {
+    async fn close_session(
+        &self,
+        id: SessionID,
+        expected_version: Option<u32>,
+        force: bool,
+    ) -> Result<Session, FlameError> {
         let mut tx = self
             .pool
-            .begin()
+            .begin_with("BEGIN IMMEDIATE")
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
-        let check_running_sql = "SELECT COUNT(*) as cnt FROM tasks WHERE ssn_id=? AND state=?";
-        let running_count: (i32,) = sqlx::query_as(check_running_sql)
-            .bind(id.clone())
-            .bind(TaskState::Running as i32)
-            .fetch_one(&mut *tx)
-            .await
-            .map_err(|e| FlameError::Storage(e.to_string()))?;
-
-        if running_count.0 > 0 {
-            return Err(FlameError::Storage(
-                "Cannot close session with running tasks".to_string(),
-            ));
-        }
-
-        let cancel_pending_sql =
-            "UPDATE tasks SET state=?, completion_time=? WHERE ssn_id=? AND state=?";
+        // The quarantined tasks are cancelled with the pending ones, and the
+        // running ones too by force.
+        let cancel_pending_sql = r#"UPDATE tasks SET state=?, completion_time=?
+            WHERE ssn_id=? AND (state IN (?, ?) OR (? AND state=?))"#;
         sqlx::query(cancel_pending_sql)
             .bind(TaskState::Cancelled as i32)
             .bind(Utc::now().timestamp())
             .bind(id.clone())
             .bind(TaskState::Pending as i32)
+            .bind(TaskState::Quarantined as i32)
+            .bind(force)
+            .bind(TaskState::Running as i32)
             .execute(&mut *tx)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
+        let check_running_sql = "SELECT COUNT(*) as cnt FROM tasks WHERE ssn_id=? AND state=?";
+        let running_count: (i32,) = sqlx::query_as(check_running_sql)
+            .bind(id.clone())
+            .bind(TaskState::Running as i32)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        let (state, completion_time) = match running_count.0 {
+            0 => (SessionState::Closed, Some(Utc::now().timestamp())),
+            _ => (SessionState::Closing, None),
+        };
+
         let close_session_sql = r#"UPDATE sessions 
             SET state=?, completion_time=?, version=version+1
-            WHERE id=?
+            WHERE id=? AND (? IS NULL OR version=?)
             RETURNING *"#;
-        let ssn: SessionDao = sqlx::query_as(close_session_sql)
-            .bind(SessionState::Closed as i32)
-            .bind(Utc::now().timestamp())
-            .bind(id)
-            .fetch_one(&mut *tx)
+        let ssn: Option<SessionDao> = sqlx::query_as(close_session_sql)
+            .bind(state as i32)
+            .bind(completion_time)
+            .bind(id.clone())
+            .bind(expected_version)
+            .bind(expected_version)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
+        // The cancelled tasks are rolled back with the transaction.
+        let Some(ssn) = ssn else {
+            let sql = "SELECT version FROM sessions WHERE id=?";
+            return Err(self
+                ._version_error(&mut tx, "session", sql, &id, expected_version)
+                .await);
+        };
 
         tx.commit()
             .await
@@ -600,11 +893,43 @@ impl Engine for SqliteEngine {
             .collect())
     }
 
+    async fn find_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM sessions")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(ids.into_iter().map(SessionID::from).collect())
+    }
+
+    async fn find_expired_session_ids(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SessionID>, FlameError> {
+        let sql = r#"SELECT id FROM sessions
+            WHERE state=? AND ttl_after_completion IS NOT NULL
+                AND completion_time + ttl_after_completion <= ?"#;
+        let ids: Vec<String> = sqlx::query_scalar(sql)
+            .bind(SessionState::Closed as i32)
+            .bind(now.timestamp())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(ids.into_iter().map(SessionID::from).collect())
+    }
+
     async fn create_task(
         &self,
         ssn_id: SessionID,
-        input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError> {
+        let TaskAttributes {
+            input,
+            input_ref,
+            overrides,
+            trace_id,
+        } = attr;
         let mut tx = self
             .pool
             .begin()
@@ -612,12 +937,19 @@ impl Engine for SqliteEngine {
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
         let input: Option<Vec<u8>> = input.map(Bytes::into);
-        let sql = r#"INSERT INTO tasks (id, ssn_id, input, creation_time, state)
+        let input_ref: Option<Json<ObjectReferenceDao>> =
+            input_ref.map(|object| Json(ObjectReferenceDao::from(object)));
+        let overrides: Option<Json<TaskOverridesDao>> =
+            (!overrides.is_empty()).then(|| Json(TaskOverridesDao::from(overrides)));
+        let sql = r#"INSERT INTO tasks (id, ssn_id, input, input_ref, overrides, creation_time, state, trace_id)
             VALUES (
                 COALESCE((SELECT MAX(id)+1 FROM tasks WHERE ssn_id=?), 1),
                 (SELECT id FROM sessions WHERE id=? AND state=?),
                 ?,
                 ?,
+                ?,
+                ?,
+                ?,
                 ?)
             RETURNING *"#;
         let task: TaskDao = sqlx::query_as(sql)
@@ -625,8 +957,11 @@ impl Engine for SqliteEngine {
             .bind(ssn_id)
             .bind(SessionState::Open as i32)
             .bind(input)
+            .bind(input_ref)
+            .bind(overrides)
             .bind(Utc::now().timestamp())
             .bind(TaskState::Pending as i32)
+            .bind(trace_id)
             .fetch_one(&mut *tx)
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
@@ -689,8 +1024,7 @@ impl Engine for SqliteEngine {
             .await
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
-        let sql =
-            r#"UPDATE tasks SET state=?, version=version+1 WHERE id=? AND ssn_id=? RETURNING *"#;
+        let sql = r#"UPDATE tasks SET state=?, failure_reason=NULL, usage=NULL, attempts=attempts+1, version=version+1 WHERE id=? AND ssn_id=? RETURNING *"#;
         let task: TaskDao = sqlx::query_as(sql)
             .bind(TaskState::Pending as i32)
             .bind(gid.task_id)
@@ -706,6 +1040,88 @@ impl Engine for SqliteEngine {
         task.try_into()
     }
 
+    async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        let sql = r#"UPDATE tasks SET state=?, completion_time=NULL, attempts=0, version=version+1 WHERE id=? AND ssn_id=? AND state=? RETURNING *"#;
+        let task: Option<TaskDao> = sqlx::query_as(sql)
+            .bind(TaskState::Pending as i32)
+            .bind(gid.task_id)
+            .bind(gid.ssn_id.clone())
+            .bind(TaskState::Quarantined as i32)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        let task = task.ok_or(FlameError::InvalidState(format!(
+            "task <{gid}> is not quarantined"
+        )))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        task.try_into()
+    }
+
+    async fn checkpoint_task(&self, gid: TaskGID, checkpoint: String) -> Result<Task, FlameError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        let sql = r#"UPDATE tasks SET checkpoint=?, version=version+1 WHERE id=? AND ssn_id=? AND state=? RETURNING *"#;
+        let task: Option<TaskDao> = sqlx::query_as(sql)
+            .bind(checkpoint)
+            .bind(gid.task_id)
+            .bind(gid.ssn_id.clone())
+            .bind(TaskState::Running as i32)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        let task = task.ok_or(FlameError::InvalidState(format!(
+            "task <{gid}> is not running"
+        )))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        task.try_into()
+    }
+
+    async fn update_task_postmortem(
+        &self,
+        gid: TaskGID,
+        postmortem: TaskPostmortem,
+    ) -> Result<Task, FlameError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        let sql = r#"UPDATE tasks SET postmortem=?, version=version+1 WHERE id=? AND ssn_id=? RETURNING *"#;
+        let task: Option<TaskDao> = sqlx::query_as(sql)
+            .bind(Json(TaskPostmortemDao::from(postmortem)))
+            .bind(gid.task_id)
+            .bind(gid.ssn_id.clone())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        let task = task.ok_or(FlameError::NotFound(format!("task <{gid}>")))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        task.try_into()
+    }
+
     async fn update_task_state(
         &self,
         gid: TaskGID,
@@ -765,12 +1181,35 @@ impl Engine for SqliteEngine {
             }
         };
 
-        let sql = r#"UPDATE tasks SET state=?, completion_time=?, output=?, version=version+1 WHERE id=? AND ssn_id=? RETURNING *"#;
+        let artifacts: Option<Json<Vec<TaskArtifactDao>>> = (!task_result.artifacts.is_empty())
+            .then(|| {
+                Json(
+                    task_result
+                        .artifacts
+                        .into_iter()
+                        .map(TaskArtifactDao::from)
+                        .collect(),
+                )
+            });
+
+        let output_ref: Option<Json<ObjectReferenceDao>> = task_result
+            .output_ref
+            .map(|object| Json(ObjectReferenceDao::from(object)));
+
+        let sql = r#"UPDATE tasks SET state=?, completion_time=?, output=?, output_ref=?, artifacts=?, failure_reason=?, usage=?, version=version+1 WHERE id=? AND ssn_id=? RETURNING *"#;
 
         let task: TaskDao = sqlx::query_as(sql)
             .bind::<i32>(task_result.state.into())
             .bind(completion_time)
             .bind::<Option<Vec<u8>>>(task_result.output.map(Bytes::into))
+            .bind(output_ref)
+            .bind(artifacts)
+            .bind(task_result.failure_reason.map(i32::from))
+            .bind(
+                task_result
+                    .usage
+                    .map(|usage| Json(TaskUsageDao::from(usage))),
+            )
             .bind(gid.task_id)
             .bind(gid.ssn_id)
             .fetch_one(&mut *tx)
@@ -972,8 +1411,8 @@ impl Engine for SqliteEngine {
             .map_err(|e| FlameError::Storage(e.to_string()))?;
 
         let sql = r#"INSERT INTO executors 
-            (id, node, resreq_cpu, resreq_memory, slots, shim, task_id, ssn_id, creation_time, state)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            (id, node, resreq_cpu, resreq_memory, slots, shim, task_id, ssn_id, generation, creation_time, state)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *"#;
 
         let dao: ExecutorDao = sqlx::query_as(sql)
@@ -985,6 +1424,7 @@ impl Engine for SqliteEngine {
             .bind(i32::from(executor.shim))
             .bind(executor.task_id)
             .bind(&executor.ssn_id)
+            .bind(executor.generation as i64)
             .bind(executor.creation_time.timestamp())
             .bind(i32::from(executor.state))
             .fetch_one(&mut *tx)
@@ -1145,11 +1585,92 @@ impl Engine for SqliteEngine {
             .filter_map(Result::ok)
             .collect())
     }
+
+    // Job operations
+
+    async fn create_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Sqlite::create_job");
+
+        sqlx::query("INSERT OR REPLACE INTO jobs (id, creation_time) VALUES (?, ?)")
+            .bind(id.as_str())
+            .bind(Utc::now().timestamp())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(format!("failed to create job: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn delete_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Sqlite::delete_job");
+
+        sqlx::query("DELETE FROM jobs WHERE id=?")
+            .bind(id.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(format!("failed to delete job: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM jobs")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(ids.into_iter().map(SessionID::from).collect())
+    }
+
+    async fn backup(&self, path: &Path) -> Result<(), FlameError> {
+        trace_fn!("Sqlite::backup");
+
+        // VACUUM INTO writes a transactionally consistent copy of the database.
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn checkpoint(&self) -> Result<(), FlameError> {
+        trace_fn!("Sqlite::checkpoint");
+
+        // Move the WAL into the database, so it's not replayed at startup.
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Checks the schema of the database is not newer than the one of this session
+/// manager; the older one is upgraded by the migrations.
+fn check_schema_version(applied: Option<i64>, written_by: Option<&str>) -> Result<(), FlameError> {
+    match applied {
+        Some(applied) if applied > SCHEMA_VERSION => Err(FlameError::VersionMismatch(format!(
+            "the database is of schema <{applied}>{}, which is newer than schema \
+             <{SCHEMA_VERSION}> of this session manager <{}>; upgrade the session manager, \
+             or restore a backup of this version by `flmadm restore`",
+            written_by
+                .map(|v| format!(" written by Flame <{v}>"))
+                .unwrap_or_default(),
+            env!("CARGO_PKG_VERSION")
+        ))),
+        _ => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use common::apis::ApplicationState;
+    use common::apis::{
+        ApplicationState, FailureReason, FairnessPolicy, LifecycleHook, LifecycleHooks,
+        ObjectReference, PythonEnvironment, ResultPolicy, TaskOverrides, TaskUsage,
+    };
 
     use super::*;
 
@@ -1161,7 +1682,7 @@ mod tests {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
 
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1170,16 +1691,25 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
         let tasks = tokio_test::block_on(storage.find_tasks(ssn_1.id.clone()))?;
         assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].id, 1);
+        assert_eq!(tasks[0].id, 1.into());
         assert_eq!(tasks[0].ssn_id, ssn_1.id.clone());
         assert_eq!(tasks[0].state, TaskState::Pending);
         assert_eq!(tasks[0].input, None);
@@ -1194,7 +1724,7 @@ mod tests {
         let tasks = tokio_test::block_on(storage.find_tasks(ssn_1.id.clone()))?;
 
         assert_eq!(tasks.len(), 1);
-        assert_eq!(tasks[0].id, 1);
+        assert_eq!(tasks[0].id, 1.into());
         assert_eq!(tasks[0].ssn_id, ssn_1.id.clone());
         assert_eq!(tasks[0].state, TaskState::Succeed);
 
@@ -1229,7 +1759,29 @@ mod tests {
                 delay_release: Duration::seconds(0),
                 schema: None,
                 url: None,
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: 8,
+                max_instances_per_node: Some(2),
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: FairnessPolicy::Proportional,
+                hooks: LifecycleHooks {
+                    pre_bind: Some(LifecycleHook {
+                        command: "fetch-license".to_string(),
+                        timeout_seconds: Some(30),
+                    }),
+                    ..LifecycleHooks::default()
+                },
+                python_env: Some(PythonEnvironment {
+                    project: Some("/opt/matrix".to_string()),
+                    ..PythonEnvironment::default()
+                }),
+                codec: PayloadCodec::Json,
+                payload_type: None,
+                topology: Default::default(),
             },
+            None,
         ))?;
         assert_eq!(app_2.name, "flmexec");
         assert_eq!(
@@ -1250,6 +1802,61 @@ mod tests {
         assert_eq!(app_2.max_instances, 10);
         assert_eq!(app_2.delay_release, Duration::seconds(0));
         assert!(app_2.schema.is_none());
+        assert_eq!(app_2.bundle_size, 8);
+        assert_eq!(app_2.max_instances_per_node, Some(2));
+        assert_eq!(app_2.fairness, FairnessPolicy::Proportional);
+        assert_eq!(
+            app_2.hooks.pre_bind.as_ref().map(|h| h.command.as_str()),
+            Some("fetch-license")
+        );
+        assert!(app_2.hooks.pre_release.is_none());
+        assert_eq!(
+            app_2.python_env.and_then(|env| env.project),
+            Some("/opt/matrix".to_string())
+        );
+        assert_eq!(app_2.codec, PayloadCodec::Json);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_application_state() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_update_application_state");
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+        let attr = |id: &str| SessionAttributes {
+            id: id.into(),
+            application: "flmexec".to_string(),
+            slots: 1,
+            ..SessionAttributes::default()
+        };
+        tokio_test::block_on(storage.create_session(attr("ssn-1")))?;
+
+        let app = tokio_test::block_on(
+            storage.update_application_state("flmexec".to_string(), ApplicationState::Disabled),
+        )?;
+        assert_eq!(app.state, ApplicationState::Disabled);
+        assert_eq!(app.version, 2);
+
+        // The disabled application rejects the new sessions, but keeps the existing ones.
+        let res = tokio_test::block_on(storage.create_session(attr("ssn-2")));
+        assert!(matches!(res, Err(FlameError::InvalidState(_))));
+        let ssn = tokio_test::block_on(storage.get_session("ssn-1".into()))?;
+        assert_eq!(ssn.status.state, SessionState::Open);
+
+        let app = tokio_test::block_on(
+            storage.update_application_state("flmexec".to_string(), ApplicationState::Enabled),
+        )?;
+        assert_eq!(app.state, ApplicationState::Enabled);
+        tokio_test::block_on(storage.create_session(attr("ssn-2")))?;
+
+        let res = tokio_test::block_on(
+            storage.update_application_state("unknown".to_string(), ApplicationState::Disabled),
+        );
+        assert!(matches!(res, Err(FlameError::NotFound(_))));
 
         Ok(())
     }
@@ -1263,7 +1870,7 @@ mod tests {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
 
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
 
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
@@ -1273,13 +1880,22 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id, None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id, TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
         let res = tokio_test::block_on(storage.unregister_application("flmexec".to_string()));
         assert!(res.is_err());
 
@@ -1296,7 +1912,7 @@ mod tests {
         let res = tokio_test::block_on(storage.unregister_application("flmexec".to_string()));
         assert!(res.is_err());
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
 
         let res = tokio_test::block_on(storage.unregister_application("flmexec".to_string()));
@@ -1342,6 +1958,18 @@ mod tests {
                         common_data: None,
                     }),
                     url: None,
+                    task_environments: vec![],
+                    task_arguments: vec![],
+                    bundle_size: 1,
+                    max_instances_per_node: None,
+                    session_defaults: Default::default(),
+                    health_probe: None,
+                    fairness: Default::default(),
+                    hooks: Default::default(),
+                    python_env: None,
+                    codec: Default::default(),
+                    payload_type: None,
+                    topology: Default::default(),
                 },
             ),
             (
@@ -1359,6 +1987,18 @@ mod tests {
                     delay_release: Duration::seconds(0),
                     schema: None,
                     url: None,
+                    task_environments: vec![],
+                    task_arguments: vec![],
+                    bundle_size: 1,
+                    max_instances_per_node: None,
+                    session_defaults: Default::default(),
+                    health_probe: None,
+                    fairness: Default::default(),
+                    hooks: Default::default(),
+                    python_env: None,
+                    codec: Default::default(),
+                    payload_type: None,
+                    topology: Default::default(),
                 },
             ),
         ];
@@ -1422,6 +2062,18 @@ mod tests {
                 delay_release: Duration::seconds(10),
                 schema: None,
                 url: Some(test_url.clone()),
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: 1,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: Default::default(),
+                hooks: Default::default(),
+                python_env: None,
+                codec: Default::default(),
+                payload_type: None,
+                topology: Default::default(),
             },
         ))?;
 
@@ -1469,6 +2121,18 @@ mod tests {
                 delay_release: Duration::seconds(10),
                 schema: None,
                 url: None,
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: 1,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: Default::default(),
+                hooks: Default::default(),
+                python_env: None,
+                codec: Default::default(),
+                payload_type: None,
+                topology: Default::default(),
             },
         ))?;
 
@@ -1512,6 +2176,18 @@ mod tests {
                 delay_release: Duration::seconds(10),
                 schema: None,
                 url: None,
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: 1,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: Default::default(),
+                hooks: Default::default(),
+                python_env: None,
+                codec: Default::default(),
+                payload_type: None,
+                topology: Default::default(),
             },
         ))?;
 
@@ -1536,7 +2212,20 @@ mod tests {
                 delay_release: Duration::seconds(20),
                 schema: None,
                 url: Some(test_url.clone()),
+                task_environments: vec![],
+                task_arguments: vec![],
+                bundle_size: 1,
+                max_instances_per_node: None,
+                session_defaults: Default::default(),
+                health_probe: None,
+                fairness: Default::default(),
+                hooks: Default::default(),
+                python_env: None,
+                codec: Default::default(),
+                payload_type: None,
+                topology: Default::default(),
             },
+            None,
         ))?;
 
         // Verify update including URL
@@ -1570,7 +2259,7 @@ mod tests {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
 
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1579,17 +2268,27 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
 
-        let task_1_2 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_2.id, 2);
+        let task_1_2 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_2.id, 2.into());
 
         let task_list = tokio_test::block_on(storage.find_tasks(ssn_1.id))?;
         assert_eq!(task_list.len(), 2);
@@ -1608,7 +2307,7 @@ mod tests {
         ))?;
         assert_eq!(task_1_2.state, TaskState::Succeed);
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
 
         Ok(())
@@ -1622,7 +2321,7 @@ mod tests {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
 
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1631,17 +2330,27 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
 
-        let task_1_2 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_2.id, 2);
+        let task_1_2 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_2.id, 2.into());
 
         let task_1_1 = tokio_test::block_on(storage.update_task_state(
             task_1_1.gid(),
@@ -1657,7 +2366,7 @@ mod tests {
         ))?;
         assert_eq!(task_1_2.state, TaskState::Succeed);
 
-        let ssn_2_id = format!("ssn-2-{}", Utc::now().timestamp());
+        let ssn_2_id = SessionID::from(format!("ssn-2-{}", Utc::now().timestamp()));
         let ssn_2 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_2_id.clone(),
             application: "flmping".to_string(),
@@ -1666,17 +2375,27 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_2.id, ssn_2_id);
         assert_eq!(ssn_2.application, "flmping");
         assert_eq!(ssn_2.status.state, SessionState::Open);
 
-        let task_2_1 = tokio_test::block_on(storage.create_task(ssn_2.id.clone(), None))?;
-        assert_eq!(task_2_1.id, 1);
+        let task_2_1 =
+            tokio_test::block_on(storage.create_task(ssn_2.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_2_1.id, 1.into());
 
-        let task_2_2 = tokio_test::block_on(storage.create_task(ssn_2.id.clone(), None))?;
-        assert_eq!(task_2_2.id, 2);
+        let task_2_2 =
+            tokio_test::block_on(storage.create_task(ssn_2.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_2_2.id, 2.into());
 
         let task_2_1 = tokio_test::block_on(storage.update_task_state(
             task_2_1.gid(),
@@ -1695,9 +2414,9 @@ mod tests {
         let ssn_list = tokio_test::block_on(storage.find_session())?;
         assert_eq!(ssn_list.len(), 2);
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
-        let ssn_2 = tokio_test::block_on(storage.close_session(ssn_2_id.clone()))?;
+        let ssn_2 = tokio_test::block_on(storage.close_session(ssn_2_id.clone(), None, false))?;
         assert_eq!(ssn_2.status.state, SessionState::Closed);
 
         Ok(())
@@ -1710,7 +2429,7 @@ mod tests {
         for (name, attr) in common::default_applications() {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1719,19 +2438,29 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
 
-        let task_1_2 = tokio_test::block_on(storage.create_task(ssn_1.id, None))?;
-        assert_eq!(task_1_2.id, 2);
+        let task_1_2 =
+            tokio_test::block_on(storage.create_task(ssn_1.id, TaskAttributes::default()))?;
+        assert_eq!(task_1_2.id, 2.into());
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
 
         let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
@@ -1750,7 +2479,7 @@ mod tests {
         for (name, attr) in common::default_applications() {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1759,17 +2488,38 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
         assert_eq!(task_1_1.state, TaskState::Pending);
 
         tokio_test::block_on(storage.update_task_state(task_1_1.gid(), TaskState::Running, None))?;
 
-        let res = tokio_test::block_on(storage.close_session(ssn_1_id.clone()));
-        assert!(res.is_err());
+        // The session is closing until its running task is completed.
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
+        assert_eq!(ssn_1.status.state, SessionState::Closing);
+        assert!(ssn_1.completion_time.is_none());
+
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()));
+        assert!(task_1_1.is_err());
+
+        // The running task is cancelled by force.
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, true))?;
+        assert_eq!(ssn_1.status.state, SessionState::Closed);
+        let tasks = tokio_test::block_on(storage.find_tasks(ssn_1_id.clone()))?;
+        assert!(tasks.iter().all(|task| task.state == TaskState::Cancelled));
 
         Ok(())
     }
@@ -1782,7 +2532,7 @@ mod tests {
         for (name, attr) in common::default_applications() {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1791,14 +2541,23 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id, None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id, TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
 
         let task_1_1 = tokio_test::block_on(storage.update_task_state(
             task_1_1.gid(),
@@ -1807,15 +2566,453 @@ mod tests {
         ))?;
         assert_eq!(task_1_1.state, TaskState::Succeed);
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
 
-        let res = tokio_test::block_on(storage.create_task(ssn_1.id, None));
+        let res = tokio_test::block_on(storage.create_task(ssn_1.id, TaskAttributes::default()));
         assert!(res.is_err());
 
         Ok(())
     }
 
+    #[test]
+    fn test_version_conflict() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_version_conflict");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        // The update of the stale version is a conflict, and the one of the
+        // unknown application is not found.
+        let app = tokio_test::block_on(storage.get_application("flmexec".to_string()))?;
+        let attr = ApplicationAttributes {
+            description: Some("updated".to_string()),
+            ..ApplicationAttributes::default()
+        };
+        let updated = tokio_test::block_on(storage.update_application(
+            "flmexec".to_string(),
+            attr.clone(),
+            Some(app.version),
+        ))?;
+        assert_eq!(updated.version, app.version + 1);
+        let res = tokio_test::block_on(storage.update_application(
+            "flmexec".to_string(),
+            attr.clone(),
+            Some(app.version),
+        ));
+        assert!(matches!(res, Err(FlameError::Conflict(_))));
+        let res =
+            tokio_test::block_on(storage.update_application("unknown".to_string(), attr, Some(1)));
+        assert!(matches!(res, Err(FlameError::NotFound(_))));
+
+        let ssn = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: "ssn-conflict".into(),
+            application: "flmexec".to_string(),
+            slots: 1,
+            ..SessionAttributes::default()
+        }))?;
+        let task =
+            tokio_test::block_on(storage.create_task(ssn.id.clone(), TaskAttributes::default()))?;
+
+        // The conflicting close doesn't cancel the pending tasks.
+        let res = tokio_test::block_on(storage.close_session(
+            ssn.id.clone(),
+            Some(ssn.version + 1),
+            false,
+        ));
+        assert!(matches!(res, Err(FlameError::Conflict(_))));
+        let task = tokio_test::block_on(storage.get_task(task.gid()))?;
+        assert_eq!(task.state, TaskState::Pending);
+
+        let closed =
+            tokio_test::block_on(storage.close_session(ssn.id.clone(), Some(ssn.version), false))?;
+        assert_eq!(closed.status.state, SessionState::Closed);
+        assert_eq!(closed.version, ssn.version + 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_task_with_overrides() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_create_task_with_overrides");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        tokio_test::block_on(storage.register_application(
+            "flmtest".to_string(),
+            ApplicationAttributes {
+                task_environments: vec!["LOG_LEVEL".to_string()],
+                task_arguments: vec!["--level".to_string()],
+                ..ApplicationAttributes::default()
+            },
+        ))?;
+        let app = tokio_test::block_on(storage.get_application("flmtest".to_string()))?;
+        assert_eq!(app.task_environments, vec!["LOG_LEVEL".to_string()]);
+        assert_eq!(app.task_arguments, vec!["--level".to_string()]);
+
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: ssn_1_id.clone(),
+            application: "flmtest".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            arguments: vec!["--level=3".to_string()],
+            timeout: Some(60),
+            max_attempts: Some(2),
+            priority: Some(5),
+            deadline: Some(30_000),
+        };
+        let task_1_1 = tokio_test::block_on(storage.create_task(
+            ssn_1.id.clone(),
+            TaskAttributes {
+                overrides: overrides.clone(),
+                ..TaskAttributes::default()
+            },
+        ))?;
+        assert_eq!(task_1_1.overrides, overrides);
+
+        let task_1_2 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert!(task_1_2.overrides.is_empty());
+
+        let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.overrides, overrides);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_task_with_input_ref() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_create_task_with_input_ref");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: SessionID::from(format!("ssn-1-{}", Utc::now().timestamp())),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+
+        let input_ref = ObjectReference {
+            endpoint: "grpc://127.0.0.1:9090".to_string(),
+            key: "flmexec/ssn-1/input-1".to_string(),
+            checksum: Some("sha256:9f86d0".to_string()),
+        };
+        let task_1_1 = tokio_test::block_on(storage.create_task(
+            ssn_1.id.clone(),
+            TaskAttributes {
+                input_ref: Some(input_ref.clone()),
+                ..TaskAttributes::default()
+            },
+        ))?;
+        assert!(task_1_1.input.is_none());
+        assert_eq!(task_1_1.input_ref, Some(input_ref.clone()));
+
+        let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.input_ref, Some(input_ref));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_failure_reason() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_task_failure_reason");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.failure_reason, None);
+
+        let task_1_1 = tokio_test::block_on(storage.update_task_result(
+            task_1_1.gid(),
+            TaskResult {
+                state: TaskState::Failed,
+                message: Some("node lost".to_string()),
+                failure_reason: Some(FailureReason::NodeLost),
+                ..TaskResult::default()
+            },
+        ))?;
+        assert_eq!(task_1_1.failure_reason, Some(FailureReason::NodeLost));
+        let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.failure_reason, Some(FailureReason::NodeLost));
+
+        let task_1_1 = tokio_test::block_on(storage.retry_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.state, TaskState::Pending);
+        assert_eq!(task_1_1.failure_reason, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_usage() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_task_usage");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.usage, None);
+
+        let usage = TaskUsage {
+            cpu_time_ms: 1200,
+            max_rss_bytes: 64 * 1024 * 1024,
+            read_bytes: 4096,
+            write_bytes: 8192,
+        };
+        let task_1_1 = tokio_test::block_on(storage.update_task_result(
+            task_1_1.gid(),
+            TaskResult {
+                state: TaskState::Succeed,
+                usage: Some(usage),
+                ..TaskResult::default()
+            },
+        ))?;
+        assert_eq!(task_1_1.usage, Some(usage));
+        let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.usage, Some(usage));
+
+        let task_1_1 = tokio_test::block_on(storage.retry_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.usage, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_checkpoint() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_task_checkpoint");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.checkpoint, None);
+
+        // Only the running task is checkpointed.
+        let res = tokio_test::block_on(storage.checkpoint_task(task_1_1.gid(), "ckpt".to_string()));
+        assert!(matches!(res, Err(FlameError::InvalidState(_))));
+
+        tokio_test::block_on(storage.update_task_state(task_1_1.gid(), TaskState::Running, None))?;
+        let checkpoint = format!("grpc://127.0.0.1:9090/{}/ckpt-1", ssn_1.id);
+        let task_1_1 =
+            tokio_test::block_on(storage.checkpoint_task(task_1_1.gid(), checkpoint.clone()))?;
+        assert_eq!(task_1_1.checkpoint, Some(checkpoint.clone()));
+
+        // The checkpoint is kept on retry, so the next invocation resumes from it.
+        let task_1_1 = tokio_test::block_on(storage.retry_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.state, TaskState::Pending);
+        assert_eq!(task_1_1.checkpoint, Some(checkpoint));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_postmortem() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_task_postmortem");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.postmortem, None);
+
+        let postmortem = TaskPostmortem {
+            executor_id: "executor-1".to_string(),
+            node: "node-1".to_string(),
+            signal: Some(11),
+            core_dumped: true,
+            stderr_tail: Some("Segmentation fault".to_string()),
+            load_average: Some(1.5),
+            memory_available: Some(1024 * 1024 * 1024),
+            creation_time: DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap(),
+            ..TaskPostmortem::default()
+        };
+        let task_1_1 = tokio_test::block_on(
+            storage.update_task_postmortem(task_1_1.gid(), postmortem.clone()),
+        )?;
+        assert_eq!(task_1_1.postmortem, Some(postmortem.clone()));
+
+        // The postmortem is kept on retry, so it's still there after the task is dispatched again.
+        let task_1_1 = tokio_test::block_on(storage.retry_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.postmortem, Some(postmortem));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_id() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_trace_id");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            trace_id: Some("trace-ssn-1".to_string()),
+            ..SessionAttributes::default()
+        }))?;
+        assert_eq!(ssn_1.trace_id, Some("trace-ssn-1".to_string()));
+
+        let task_1_1 = tokio_test::block_on(storage.create_task(
+            ssn_1.id.clone(),
+            TaskAttributes {
+                trace_id: Some("trace-task-1".to_string()),
+                ..TaskAttributes::default()
+            },
+        ))?;
+        let task_1_2 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.trace_id, Some("trace-task-1".to_string()));
+        assert_eq!(task_1_2.trace_id, None);
+
+        // The trace IDs are stored with the session and tasks.
+        let ssn_1 = tokio_test::block_on(storage.get_session(ssn_1.id.clone()))?;
+        assert_eq!(ssn_1.trace_id, Some("trace-ssn-1".to_string()));
+        let task_1_1 = tokio_test::block_on(storage.get_task(task_1_1.gid()))?;
+        assert_eq!(task_1_1.trace_id, Some("trace-task-1".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_priority() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_session_priority");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            priority: 10,
+            ..SessionAttributes::default()
+        }))?;
+        let ssn_2 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-2-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        assert_eq!(ssn_1.priority, 10);
+        assert_eq!(ssn_2.priority, 0);
+
+        let ssn_1 = tokio_test::block_on(storage.get_session(ssn_1.id.clone()))?;
+        assert_eq!(ssn_1.priority, 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_task_attempts_and_release() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_task_attempts_and_release");
+
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            ..SessionAttributes::default()
+        }))?;
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.attempts, 0);
+
+        let gid = task_1_1.gid();
+        assert!(matches!(
+            tokio_test::block_on(storage.release_task(gid.clone())),
+            Err(FlameError::InvalidState(_))
+        ));
+
+        tokio_test::block_on(storage.retry_task(gid.clone()))?;
+        let task_1_1 = tokio_test::block_on(storage.retry_task(gid.clone()))?;
+        assert_eq!(task_1_1.attempts, 2);
+
+        let task_1_1 = tokio_test::block_on(storage.update_task_state(
+            gid.clone(),
+            TaskState::Quarantined,
+            None,
+        ))?;
+        assert_eq!(task_1_1.state, TaskState::Quarantined);
+        assert_eq!(task_1_1.attempts, 2);
+
+        let task_1_1 = tokio_test::block_on(storage.release_task(gid.clone()))?;
+        assert_eq!(task_1_1.state, TaskState::Pending);
+        assert_eq!(task_1_1.attempts, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_scratch_size() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_session_scratch_size");
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
+            id: format!("ssn-1-{}", Utc::now().timestamp()).into(),
+            application: "flmexec".to_string(),
+            scratch_size: Some(1024 * 1024),
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            ..SessionAttributes::default()
+        }))?;
+        assert_eq!(ssn_1.scratch_size, Some(1024 * 1024));
+
+        let ssn_1 = tokio_test::block_on(storage.get_session(ssn_1.id))?;
+        assert_eq!(ssn_1.scratch_size, Some(1024 * 1024));
+
+        Ok(())
+    }
+
     #[test]
     fn test_delete_session_with_open_tasks() -> Result<(), FlameError> {
         let url = common::temp_sqlite_url("flame_test_delete_session_with_open_tasks");
@@ -1823,7 +3020,7 @@ mod tests {
         for (name, attr) in common::default_applications() {
             tokio_test::block_on(storage.register_application(name.clone(), attr))?;
         }
-        let ssn_1_id = format!("ssn-1-{}", Utc::now().timestamp());
+        let ssn_1_id = SessionID::from(format!("ssn-1-{}", Utc::now().timestamp()));
         let ssn_1 = tokio_test::block_on(storage.create_session(SessionAttributes {
             id: ssn_1_id.clone(),
             application: "flmexec".to_string(),
@@ -1832,14 +3029,23 @@ mod tests {
             min_instances: 0,
             max_instances: None,
             batch_size: 1,
+            scratch_size: None,
+            max_task_attempts: None,
+            task_timeout: None,
+            task_priority: None,
+            priority: 0,
+            result_policy: ResultPolicy::Inline,
+            ttl_after_completion: None,
+            trace_id: None,
         }))?;
 
         assert_eq!(ssn_1.id, ssn_1_id);
         assert_eq!(ssn_1.application, "flmexec");
         assert_eq!(ssn_1.status.state, SessionState::Open);
 
-        let task_1_1 = tokio_test::block_on(storage.create_task(ssn_1.id.clone(), None))?;
-        assert_eq!(task_1_1.id, 1);
+        let task_1_1 =
+            tokio_test::block_on(storage.create_task(ssn_1.id.clone(), TaskAttributes::default()))?;
+        assert_eq!(task_1_1.id, 1.into());
 
         // It should be failed because the session is open and there are open tasks
         let res = tokio_test::block_on(storage.delete_session(ssn_1_id.clone()));
@@ -1859,7 +3065,7 @@ mod tests {
         let res = tokio_test::block_on(storage.delete_session(ssn_1_id.clone()));
         assert!(res.is_err());
 
-        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone()))?;
+        let ssn_1 = tokio_test::block_on(storage.close_session(ssn_1_id.clone(), None, false))?;
         assert_eq!(ssn_1.status.state, SessionState::Closed);
 
         let ssn_1 = tokio_test::block_on(storage.delete_session(ssn_1_id.clone()))?;
@@ -1867,4 +3073,94 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_backup() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_backup");
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+
+        for (name, attr) in common::default_applications() {
+            tokio_test::block_on(storage.register_application(name.clone(), attr))?;
+        }
+
+        let backup_path = std::env::temp_dir().join(format!(
+            "flame_test_backup_{}.db",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        tokio_test::block_on(storage.backup(&backup_path))?;
+
+        let restored = tokio_test::block_on(SqliteEngine::new_ptr(&format!(
+            "sqlite://{}",
+            backup_path.display()
+        )))?;
+        let app = tokio_test::block_on(restored.get_application("flmexec".to_string()))?;
+        assert_eq!(app.name, "flmexec");
+
+        let _ = std::fs::remove_file(&backup_path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_job_ids() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_job_ids");
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+
+        tokio_test::block_on(storage.create_job(&SessionID::from("job-1")))?;
+        tokio_test::block_on(storage.create_job(&SessionID::from("job-2")))?;
+        // The job is recorded again, e.g. a job of the same ID after a failed deletion.
+        tokio_test::block_on(storage.create_job(&SessionID::from("job-1")))?;
+
+        tokio_test::block_on(storage.delete_job(&SessionID::from("job-1")))?;
+        let ids = tokio_test::block_on(storage.find_job_ids())?;
+        assert_eq!(ids, vec![SessionID::from("job-2")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_version_is_latest_migration() {
+        let latest = std::fs::read_dir(SQLITE_SQL)
+            .unwrap()
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().to_string_lossy().to_string();
+                name.split('_').next()?.parse::<i64>().ok()
+            })
+            .max();
+        assert_eq!(latest, Some(SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn test_schema_version_gate() -> Result<(), FlameError> {
+        let url = common::temp_sqlite_url("flame_test_schema_version_gate");
+        let storage = tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+        drop(storage);
+
+        // The same schema is opened again.
+        tokio_test::block_on(SqliteEngine::new_ptr(&url))?;
+
+        // A migration of a newer session manager is applied, e.g. before a downgrade.
+        let db = tokio_test::block_on(SqlitePool::connect(&url))
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        tokio_test::block_on(
+            sqlx::query(
+                "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+                 VALUES (?, 'newer', 1, x'00', 0)",
+            )
+            .bind(SCHEMA_VERSION + 1)
+            .execute(&db),
+        )
+        .map_err(|e| FlameError::Storage(e.to_string()))?;
+        tokio_test::block_on(db.close());
+
+        let res = tokio_test::block_on(SqliteEngine::new_ptr(&url));
+        assert!(matches!(res, Err(FlameError::VersionMismatch(_))));
+
+        assert!(check_schema_version(None, None).is_ok());
+        assert!(check_schema_version(Some(SCHEMA_VERSION - 1), None).is_ok());
+        let err = check_schema_version(Some(SCHEMA_VERSION + 1), Some("0.6.0")).unwrap_err();
+        assert!(err.to_string().contains("written by Flame <0.6.0>"));
+
+        Ok(())
+    }
 }
@@ -19,8 +19,11 @@ use std::collections::HashMap;
 use crate::FlameError;
 use bytes::Bytes;
 use common::apis::{
-    Application, ApplicationSchema, ApplicationState, ExecutorState, Node, NodeInfo, NodeState,
-    ResourceRequirement, Session, SessionStatus, Shim, Task,
+    Application, ApplicationSchema, ApplicationState, ExecutorState, FailureReason, FairnessPolicy,
+    HealthProbe, LifecycleHook, LifecycleHooks, Node, NodeInfo, NodeState, ObjectReference,
+    PayloadCodec, PythonEnvironment, ResourceRequirement, Session, SessionDefaults, SessionStatus,
+    Shim, Task, TaskArtifact, TaskDurationStats, TaskOverrides, TaskPostmortem, TaskUsage,
+    TaskUsageStats, TopologyPolicy,
 };
 use common::apis::{ApplicationID, Event, ExecutorID, SessionID, TaskID};
 
@@ -42,6 +45,96 @@ pub struct AppSchemaDao {
     pub common_data: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionDefaultsDao {
+    pub slots: Option<u32>,
+    pub min_instances: Option<u32>,
+    pub max_instances: Option<u32>,
+    pub batch_size: Option<u32>,
+    pub scratch_size: Option<u64>,
+    pub max_task_attempts: Option<u32>,
+    pub task_timeout: Option<u64>,
+    pub task_priority: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HealthProbeDao {
+    pub command: Option<String>,
+    pub grpc_service: Option<String>,
+    pub period_seconds: Option<u32>,
+    pub timeout_seconds: Option<u32>,
+    pub failure_threshold: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LifecycleHooksDao {
+    pub pre_bind: Option<LifecycleHookDao>,
+    pub post_bind: Option<LifecycleHookDao>,
+    pub pre_release: Option<LifecycleHookDao>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LifecycleHookDao {
+    pub command: String,
+    pub timeout_seconds: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PythonEnvironmentDao {
+    pub project: Option<String>,
+    pub requirements: Option<String>,
+    pub python_version: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskOverridesDao {
+    pub environments: HashMap<String, String>,
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+    #[serde(default)]
+    pub priority: Option<u32>,
+    #[serde(default)]
+    pub deadline: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskArtifactDao {
+    pub name: String,
+    pub data: Option<Vec<u8>>,
+    pub object_ref: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ObjectReferenceDao {
+    pub endpoint: String,
+    pub key: String,
+    pub checksum: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskUsageDao {
+    pub cpu_time_ms: u64,
+    pub max_rss_bytes: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TaskPostmortemDao {
+    pub executor_id: ExecutorID,
+    pub node: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub core_dumped: bool,
+    pub stderr_tail: Option<String>,
+    pub load_average: Option<f64>,
+    pub memory_available: Option<u64>,
+    pub creation_time: i64,
+}
+
 #[derive(Clone, FromRow, Debug)]
 pub struct ApplicationDao {
     pub name: ApplicationID,
@@ -58,6 +151,18 @@ pub struct ApplicationDao {
     pub delay_release: i64,
     pub schema: Option<Json<AppSchemaDao>>,
     pub url: Option<String>,
+    pub task_environments: Option<Json<Vec<String>>>,
+    pub task_arguments: Option<Json<Vec<String>>>,
+    pub bundle_size: i64,
+    pub max_instances_per_node: Option<i64>,
+    pub session_defaults: Option<Json<SessionDefaultsDao>>,
+    pub health_probe: Option<Json<HealthProbeDao>>,
+    pub fairness: i32,
+    pub hooks: Option<Json<LifecycleHooksDao>>,
+    pub python_env: Option<Json<PythonEnvironmentDao>>,
+    pub codec: i32,
+    pub payload_type: Option<String>,
+    pub topology: i32,
     pub creation_time: i64,
     pub state: i32,
 }
@@ -77,6 +182,14 @@ pub struct SessionDao {
     pub min_instances: i64,
     pub max_instances: Option<i64>,
     pub batch_size: i64,
+    pub scratch_size: Option<i64>,
+    pub max_task_attempts: Option<i64>,
+    pub task_timeout: Option<i64>,
+    pub task_priority: Option<i64>,
+    pub priority: i64,
+    pub result_policy: i32,
+    pub ttl_after_completion: Option<i64>,
+    pub trace_id: Option<String>,
 }
 
 #[derive(Clone, FromRow, Debug)]
@@ -85,12 +198,22 @@ pub struct TaskDao {
     pub ssn_id: SessionID,
     pub version: u32,
     pub input: Option<Vec<u8>>,
+    pub input_ref: Option<Json<ObjectReferenceDao>>,
     pub output: Option<Vec<u8>>,
+    pub output_ref: Option<Json<ObjectReferenceDao>>,
+    pub overrides: Option<Json<TaskOverridesDao>>,
+    pub artifacts: Option<Json<Vec<TaskArtifactDao>>>,
 
     pub creation_time: i64,
     pub completion_time: Option<i64>,
 
     pub state: i32,
+    pub failure_reason: Option<i32>,
+    pub attempts: u32,
+    pub usage: Option<Json<TaskUsageDao>>,
+    pub checkpoint: Option<String>,
+    pub postmortem: Option<Json<TaskPostmortemDao>>,
+    pub trace_id: Option<String>,
 }
 
 #[derive(Clone, FromRow, Debug)]
@@ -128,6 +251,7 @@ pub struct ExecutorDao {
     pub task_id: Option<TaskID>,
     pub ssn_id: Option<SessionID>,
     pub batch_index: Option<i64>,
+    pub generation: i64,
 
     pub creation_time: i64,
     pub state: i32,
@@ -156,11 +280,23 @@ impl TryFrom<&SessionDao> for Session {
             tasks_index: HashMap::new(),
             status: SessionStatus {
                 state: ssn.state.try_into()?,
+                ..Default::default()
             },
             events: vec![],
             min_instances: ssn.min_instances as u32,
             max_instances: ssn.max_instances.map(|v| v as u32),
             batch_size: ssn.batch_size.max(1) as u32,
+            task_durations: TaskDurationStats::default(),
+            task_usage: TaskUsageStats::default(),
+            scratch_size: ssn.scratch_size.map(|v| v as u64),
+            max_task_attempts: ssn.max_task_attempts.map(|v| v as u32),
+            task_timeout: ssn.task_timeout.map(|v| v as u64),
+            task_priority: ssn.task_priority.map(|v| v as u32),
+            task_priorities: HashMap::new(),
+            priority: ssn.priority as u32,
+            result_policy: ssn.result_policy.try_into()?,
+            ttl_after_completion: ssn.ttl_after_completion.map(|v| v as u64),
+            trace_id: ssn.trace_id.clone(),
         })
     }
 }
@@ -182,7 +318,19 @@ impl TryFrom<&TaskDao> for Task {
             ssn_id: task.ssn_id.clone(),
             version: task.version,
             input: task.input.clone().map(Bytes::from),
+            input_ref: task.input_ref.clone().map(|object| object.0.into()),
             output: task.output.clone().map(Bytes::from),
+            output_ref: task.output_ref.clone().map(|object| object.0.into()),
+            artifacts: task
+                .artifacts
+                .clone()
+                .map(|artifacts| artifacts.0.into_iter().map(TaskArtifact::from).collect())
+                .unwrap_or_default(),
+            overrides: task
+                .overrides
+                .clone()
+                .map(|overrides| overrides.0.into())
+                .unwrap_or_default(),
 
             creation_time: DateTime::<Utc>::from_timestamp(task.creation_time, 0)
                 .ok_or(FlameError::Storage("invalid creation time".to_string()))?,
@@ -195,6 +343,19 @@ impl TryFrom<&TaskDao> for Task {
                 .transpose()?,
 
             state: task.state.try_into()?,
+            failure_reason: task
+                .failure_reason
+                .map(FailureReason::try_from)
+                .transpose()?,
+            attempts: task.attempts,
+            usage: task.usage.clone().map(|usage| usage.0.into()),
+            checkpoint: task.checkpoint.clone(),
+            postmortem: task
+                .postmortem
+                .clone()
+                .map(|postmortem| postmortem.0.try_into())
+                .transpose()?,
+            trace_id: task.trace_id.clone(),
             events: vec![],
         })
     }
@@ -238,6 +399,34 @@ impl TryFrom<&ApplicationDao> for Application {
             delay_release: Duration::seconds(app.delay_release),
             schema: app.schema.clone().map(|arg| arg.0.into()),
             url: app.url.clone(),
+            task_environments: app
+                .task_environments
+                .clone()
+                .map(|envs| envs.0)
+                .unwrap_or_default(),
+            task_arguments: app
+                .task_arguments
+                .clone()
+                .map(|args| args.0)
+                .unwrap_or_default(),
+            bundle_size: app.bundle_size.max(1) as u32,
+            max_instances_per_node: app.max_instances_per_node.map(|v| v as u32),
+            session_defaults: app
+                .session_defaults
+                .clone()
+                .map(|defaults| defaults.0.into())
+                .unwrap_or_default(),
+            health_probe: app.health_probe.clone().map(|probe| probe.0.into()),
+            fairness: FairnessPolicy::try_from(app.fairness).unwrap_or_default(),
+            hooks: app
+                .hooks
+                .clone()
+                .map(|hooks| hooks.0.into())
+                .unwrap_or_default(),
+            python_env: app.python_env.clone().map(|env| env.0.into()),
+            codec: PayloadCodec::try_from(app.codec).unwrap_or_default(),
+            payload_type: app.payload_type.clone(),
+            topology: TopologyPolicy::try_from(app.topology).unwrap_or_default(),
         })
     }
 }
@@ -250,6 +439,241 @@ impl TryFrom<ApplicationDao> for Application {
     }
 }
 
+impl From<TaskOverrides> for TaskOverridesDao {
+    fn from(overrides: TaskOverrides) -> Self {
+        Self {
+            environments: overrides.environments,
+            arguments: overrides.arguments,
+            timeout: overrides.timeout,
+            max_attempts: overrides.max_attempts,
+            priority: overrides.priority,
+            deadline: overrides.deadline,
+        }
+    }
+}
+
+impl From<TaskOverridesDao> for TaskOverrides {
+    fn from(overrides: TaskOverridesDao) -> Self {
+        Self {
+            environments: overrides.environments,
+            arguments: overrides.arguments,
+            timeout: overrides.timeout,
+            max_attempts: overrides.max_attempts,
+            priority: overrides.priority,
+            deadline: overrides.deadline,
+        }
+    }
+}
+
+impl From<TaskArtifact> for TaskArtifactDao {
+    fn from(artifact: TaskArtifact) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(Bytes::into),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+impl From<TaskArtifactDao> for TaskArtifact {
+    fn from(artifact: TaskArtifactDao) -> Self {
+        Self {
+            name: artifact.name,
+            data: artifact.data.map(Bytes::from),
+            object_ref: artifact.object_ref,
+        }
+    }
+}
+
+impl From<ObjectReference> for ObjectReferenceDao {
+    fn from(object: ObjectReference) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
+        }
+    }
+}
+
+impl From<ObjectReferenceDao> for ObjectReference {
+    fn from(object: ObjectReferenceDao) -> Self {
+        Self {
+            endpoint: object.endpoint,
+            key: object.key,
+            checksum: object.checksum,
+        }
+    }
+}
+
+impl From<TaskUsage> for TaskUsageDao {
+    fn from(usage: TaskUsage) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+impl From<TaskUsageDao> for TaskUsage {
+    fn from(usage: TaskUsageDao) -> Self {
+        Self {
+            cpu_time_ms: usage.cpu_time_ms,
+            max_rss_bytes: usage.max_rss_bytes,
+            read_bytes: usage.read_bytes,
+            write_bytes: usage.write_bytes,
+        }
+    }
+}
+
+impl From<TaskPostmortem> for TaskPostmortemDao {
+    fn from(postmortem: TaskPostmortem) -> Self {
+        Self {
+            executor_id: postmortem.executor_id,
+            node: postmortem.node,
+            exit_code: postmortem.exit_code,
+            signal: postmortem.signal,
+            core_dumped: postmortem.core_dumped,
+            stderr_tail: postmortem.stderr_tail,
+            load_average: postmortem.load_average,
+            memory_available: postmortem.memory_available,
+            creation_time: postmortem.creation_time.timestamp(),
+        }
+    }
+}
+
+impl TryFrom<TaskPostmortemDao> for TaskPostmortem {
+    type Error = FlameError;
+
+    fn try_from(postmortem: TaskPostmortemDao) -> Result<Self, Self::Error> {
+        Ok(Self {
+            executor_id: postmortem.executor_id,
+            node: postmortem.node,
+            exit_code: postmortem.exit_code,
+            signal: postmortem.signal,
+            core_dumped: postmortem.core_dumped,
+            stderr_tail: postmortem.stderr_tail,
+            load_average: postmortem.load_average,
+            memory_available: postmortem.memory_available,
+            creation_time: DateTime::<Utc>::from_timestamp(postmortem.creation_time, 0)
+                .ok_or(FlameError::Storage("invalid postmortem time".to_string()))?,
+        })
+    }
+}
+
+impl From<SessionDefaults> for SessionDefaultsDao {
+    fn from(defaults: SessionDefaults) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<SessionDefaultsDao> for SessionDefaults {
+    fn from(defaults: SessionDefaultsDao) -> Self {
+        Self {
+            slots: defaults.slots,
+            min_instances: defaults.min_instances,
+            max_instances: defaults.max_instances,
+            batch_size: defaults.batch_size,
+            scratch_size: defaults.scratch_size,
+            max_task_attempts: defaults.max_task_attempts,
+            task_timeout: defaults.task_timeout,
+            task_priority: defaults.task_priority,
+        }
+    }
+}
+
+impl From<LifecycleHooks> for LifecycleHooksDao {
+    fn from(hooks: LifecycleHooks) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.map(LifecycleHookDao::from),
+            post_bind: hooks.post_bind.map(LifecycleHookDao::from),
+            pre_release: hooks.pre_release.map(LifecycleHookDao::from),
+        }
+    }
+}
+
+impl From<LifecycleHooksDao> for LifecycleHooks {
+    fn from(hooks: LifecycleHooksDao) -> Self {
+        Self {
+            pre_bind: hooks.pre_bind.map(LifecycleHook::from),
+            post_bind: hooks.post_bind.map(LifecycleHook::from),
+            pre_release: hooks.pre_release.map(LifecycleHook::from),
+        }
+    }
+}
+
+impl From<LifecycleHook> for LifecycleHookDao {
+    fn from(hook: LifecycleHook) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<LifecycleHookDao> for LifecycleHook {
+    fn from(hook: LifecycleHookDao) -> Self {
+        Self {
+            command: hook.command,
+            timeout_seconds: hook.timeout_seconds,
+        }
+    }
+}
+
+impl From<PythonEnvironment> for PythonEnvironmentDao {
+    fn from(env: PythonEnvironment) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
+        }
+    }
+}
+
+impl From<PythonEnvironmentDao> for PythonEnvironment {
+    fn from(env: PythonEnvironmentDao) -> Self {
+        Self {
+            project: env.project,
+            requirements: env.requirements,
+            python_version: env.python_version,
+        }
+    }
+}
+
+impl From<HealthProbe> for HealthProbeDao {
+    fn from(probe: HealthProbe) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
+impl From<HealthProbeDao> for HealthProbe {
+    fn from(probe: HealthProbeDao) -> Self {
+        Self {
+            command: probe.command,
+            grpc_service: probe.grpc_service,
+            period_seconds: probe.period_seconds,
+            timeout_seconds: probe.timeout_seconds,
+            failure_threshold: probe.failure_threshold,
+        }
+    }
+}
+
 impl From<ApplicationSchema> for AppSchemaDao {
     fn from(schema: ApplicationSchema) -> Self {
         Self {
@@ -300,9 +724,12 @@ impl TryFrom<&NodeDao> for Node {
                 cpu: dao.allocatable_cpu as u64,
                 memory: dao.allocatable_memory as u64,
             },
+            // The topology is not persisted, which the executor manager
+            // reports again when the node registers.
             info: NodeInfo {
                 arch: dao.info_arch.clone(),
                 os: dao.info_os.clone(),
+                ..Default::default()
             },
         })
     }
@@ -349,8 +776,12 @@ impl TryFrom<&ExecutorDao> for Executor {
             slots: dao.slots as u32,
             shim: Shim::try_from(dao.shim).unwrap_or_default(),
             task_id: dao.task_id,
+            bundle: vec![],
+            task_start: None,
             ssn_id: dao.ssn_id.clone(),
             batch_index: dao.batch_index.map(|v| v as u32),
+            preempted: false,
+            generation: dao.generation as u64,
             creation_time: DateTime::<Utc>::from_timestamp(dao.creation_time, 0)
                 .ok_or(FlameError::Storage("invalid creation time".to_string()))?,
             state: ExecutorState::from(dao.state),
@@ -378,6 +809,7 @@ impl From<&Executor> for ExecutorDao {
             task_id: exec.task_id,
             ssn_id: exec.ssn_id.clone(),
             batch_index: exec.batch_index.map(|v| v as i64),
+            generation: exec.generation as i64,
             creation_time: exec.creation_time.timestamp(),
             state: i32::from(exec.state),
         }
@@ -0,0 +1,83 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::storage;
+    use common::apis::{ApplicationAttributes, SessionAttributes};
+    use common::ctx::{FlameCluster, FlameClusterContext};
+
+    #[test]
+    fn test_executor_id_by_node_and_slot() {
+        tokio_test::block_on(async {
+            let ctx = FlameClusterContext {
+                cluster: FlameCluster {
+                    storage: "none".to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let storage = storage::new_ptr(&ctx).await.unwrap();
+
+            storage
+                .register_application("app-1".to_string(), ApplicationAttributes::default())
+                .await
+                .unwrap();
+            storage
+                .create_session(SessionAttributes {
+                    id: "ssn-1".to_string().into(),
+                    application: "app-1".to_string(),
+                    slots: 1,
+                    ..SessionAttributes::default()
+                })
+                .await
+                .unwrap();
+
+            let mut ids = vec![];
+            for node in ["node-1", "node-1", "node-2"] {
+                let exec = storage
+                    .create_executor(node.to_string(), "ssn-1".to_string().into(), None)
+                    .await
+                    .unwrap();
+                ids.push(exec.id);
+            }
+            assert_eq!(ids, vec!["node-1-0", "node-1-1", "node-2-0"]);
+
+            // The slot of the deleted executor is reused by the next one on the node,
+            // which is of another generation.
+            let released = storage
+                .get_executor_ptr("node-1-0".to_string())
+                .unwrap()
+                .lock()
+                .unwrap()
+                .generation;
+            storage
+                .delete_executor("node-1-0".to_string())
+                .await
+                .unwrap();
+            let exec = storage
+                .create_executor("node-1".to_string(), "ssn-1".to_string().into(), None)
+                .await
+                .unwrap();
+            assert_eq!(exec.id, "node-1-0");
+            assert_ne!(exec.generation, 0);
+            assert_ne!(exec.generation, released);
+
+            let exec = storage
+                .create_executor("node-1".to_string(), "ssn-1".to_string().into(), None)
+                .await
+                .unwrap();
+            assert_eq!(exec.id, "node-1-2");
+        });
+    }
+}
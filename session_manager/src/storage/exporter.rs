@@ -0,0 +1,595 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The continuous export of the task records to an analytical database, e.g.
+//! ClickHouse or TimescaleDB, for the long-term analysis of the workloads.
+//!
+//! A record is taken when a task is completed, i.e. succeed, failed or
+//! cancelled, with the node and the time of its last dispatch; the records are
+//! buffered in memory, and written in batches every flush interval. A batch
+//! failed after its retries is kept for the next flush, and the oldest records
+//! are dropped once the buffer is full, so the database being unavailable
+//! never blocks the scheduling. The records are exported at least once, i.e.
+//! a batch may be written again if its response was lost.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http_body_util::{BodyExt, Full};
+use hyper::header::{CONTENT_TYPE, HOST, USER_AGENT};
+use hyper::Request;
+use hyper_util::rt::TokioIo;
+use serde::Serialize;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::{Postgres, QueryBuilder};
+use tokio::net::TcpStream;
+use url::Url;
+
+use common::apis::{SessionID, Task, TaskID};
+use common::ctx::{ExporterKind, FlameExporter};
+use common::FlameError;
+use stdng::{lock_ptr, MutexPtr};
+
+use crate::storage::{Storage, StoragePtr};
+
+/// The delay before the first retry of a failed batch, doubled by each retry.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The rows inserted by one statement of PostgreSQL, within its limit of the
+/// bind parameters.
+const MAX_INSERT_ROWS: usize = 2000;
+
+/// The record of a completed task.
+#[derive(Clone, Debug, Serialize)]
+pub struct TaskRecord {
+    pub session_id: String,
+    pub task_id: i64,
+    pub application: String,
+    /// The node of the last dispatch, if the task was dispatched.
+    pub node: Option<String>,
+    pub state: String,
+    pub failure_reason: Option<String>,
+    pub attempts: u32,
+    pub creation_time: DateTime<Utc>,
+    pub dispatch_time: Option<DateTime<Utc>>,
+    pub completion_time: DateTime<Utc>,
+    /// The milliseconds from the creation to the last dispatch.
+    pub queue_ms: Option<i64>,
+    /// The milliseconds from the last dispatch to the completion.
+    pub run_ms: Option<i64>,
+    pub cpu_time_ms: Option<u64>,
+    pub max_rss_bytes: Option<u64>,
+}
+
+#[derive(Default)]
+struct RecordBuffer {
+    records: VecDeque<TaskRecord>,
+    /// The records dropped since the last flush, as the buffer was full.
+    dropped: u64,
+}
+
+/// The records to export, and the last dispatches of the running tasks.
+#[derive(Clone)]
+pub struct TaskExporter {
+    buffer_size: usize,
+    buffer: MutexPtr<RecordBuffer>,
+    dispatches: MutexPtr<HashMap<(SessionID, TaskID), (String, DateTime<Utc>)>>,
+}
+
+impl TaskExporter {
+    pub fn new(config: &FlameExporter) -> Self {
+        Self {
+            buffer_size: config.buffer_size,
+            buffer: stdng::new_ptr(RecordBuffer::default()),
+            dispatches: stdng::new_ptr(HashMap::new()),
+        }
+    }
+
+    fn dispatched(&self, node: &str, tasks: &[Task], now: DateTime<Utc>) -> Result<(), FlameError> {
+        let mut dispatches = lock_ptr!(self.dispatches)?;
+        for task in tasks {
+            dispatches.insert((task.ssn_id.clone(), task.id), (node.to_string(), now));
+        }
+        Ok(())
+    }
+
+    fn completed(&self, application: &str, task: &Task) -> Result<(), FlameError> {
+        let dispatch = lock_ptr!(self.dispatches)?.remove(&(task.ssn_id.clone(), task.id));
+        let completion_time = task.completion_time.unwrap_or_else(Utc::now);
+        let dispatch_time = dispatch.as_ref().map(|(_, time)| *time);
+
+        let record = TaskRecord {
+            session_id: task.ssn_id.to_string(),
+            task_id: task.id.into(),
+            application: application.to_string(),
+            node: dispatch.map(|(node, _)| node),
+            state: task.state.to_string(),
+            failure_reason: task.failure_reason.map(|reason| reason.to_string()),
+            attempts: task.attempts,
+            creation_time: task.creation_time,
+            dispatch_time,
+            completion_time,
+            queue_ms: dispatch_time.map(|time| (time - task.creation_time).num_milliseconds()),
+            run_ms: dispatch_time.map(|time| (completion_time - time).num_milliseconds()),
+            cpu_time_ms: task.usage.map(|usage| usage.cpu_time_ms),
+            max_rss_bytes: task.usage.map(|usage| usage.max_rss_bytes),
+        };
+
+        let mut buffer = lock_ptr!(self.buffer)?;
+        buffer.records.push_back(record);
+        self.trim(&mut buffer);
+        Ok(())
+    }
+
+    /// Takes the oldest records up to `max` to write.
+    fn take(&self, max: usize) -> Result<Vec<TaskRecord>, FlameError> {
+        let mut buffer = lock_ptr!(self.buffer)?;
+        let len = buffer.records.len().min(max);
+        Ok(buffer.records.drain(..len).collect())
+    }
+
+    /// Puts the failed batch back before the newer records, for the next flush.
+    fn requeue(&self, batch: Vec<TaskRecord>) -> Result<(), FlameError> {
+        let mut buffer = lock_ptr!(self.buffer)?;
+        for record in batch.into_iter().rev() {
+            buffer.records.push_front(record);
+        }
+        self.trim(&mut buffer);
+        Ok(())
+    }
+
+    /// Drops the oldest records beyond the buffer size.
+    fn trim(&self, buffer: &mut RecordBuffer) {
+        while buffer.records.len() > self.buffer_size {
+            buffer.records.pop_front();
+            buffer.dropped += 1;
+        }
+    }
+
+    fn take_dropped(&self) -> Result<u64, FlameError> {
+        let mut buffer = lock_ptr!(self.buffer)?;
+        Ok(std::mem::take(&mut buffer.dropped))
+    }
+}
+
+impl Storage {
+    /// Records the node and the time of the dispatch of the tasks, for their
+    /// records when they're completed; it's a no-op if the exporter is disabled.
+    pub fn export_dispatched(&self, node: &str, tasks: &[Task]) -> Result<(), FlameError> {
+        match &self.exporter {
+            Some(exporter) => exporter.dispatched(node, tasks, Utc::now()),
+            None => Ok(()),
+        }
+    }
+
+    /// Buffers the record of the completed task of the application to export.
+    pub(crate) fn export_completed(
+        &self,
+        application: &str,
+        task: &Task,
+    ) -> Result<(), FlameError> {
+        match &self.exporter {
+            Some(exporter) if task.state.is_terminal() => exporter.completed(application, task),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The analytical database the task records are written to.
+#[async_trait]
+trait TaskSink: Send + Sync {
+    async fn write(&self, records: &[TaskRecord]) -> Result<(), FlameError>;
+}
+
+fn new_sink(config: &FlameExporter) -> Result<Box<dyn TaskSink>, FlameError> {
+    match config.kind {
+        ExporterKind::ClickHouse => Ok(Box::new(ClickHouseSink::new(config)?)),
+        ExporterKind::Timescale => Ok(Box::new(TimescaleSink::new(config)?)),
+    }
+}
+
+/// Writes the task records in batches every flush interval.
+pub async fn run(storage: StoragePtr, config: FlameExporter) -> Result<(), FlameError> {
+    let Some(exporter) = storage.exporter.clone() else {
+        return Ok(());
+    };
+    let sink = new_sink(&config)?;
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.flush_interval));
+    loop {
+        interval.tick().await;
+
+        let dropped = exporter.take_dropped()?;
+        if dropped > 0 {
+            tracing::warn!("Dropped {dropped} task records, as the export buffer was full.");
+        }
+
+        // Flush the buffered records, until a batch fails or the buffer is drained.
+        loop {
+            let batch = exporter.take(config.batch_size)?;
+            if batch.is_empty() {
+                break;
+            }
+
+            if let Err(e) = write_batch(sink.as_ref(), &batch, config.max_retries).await {
+                tracing::error!("Failed to export {} task records: {e}", batch.len());
+                exporter.requeue(batch)?;
+                break;
+            }
+            tracing::debug!("Exported {} task records.", batch.len());
+
+            if batch.len() < config.batch_size {
+                break;
+            }
+        }
+    }
+}
+
+/// Writes the batch, retried with an exponential backoff.
+async fn write_batch(
+    sink: &dyn TaskSink,
+    batch: &[TaskRecord],
+    max_retries: u32,
+) -> Result<(), FlameError> {
+    let mut backoff = RETRY_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        match sink.write(batch).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < max_retries => {
+                attempt += 1;
+                tracing::debug!("Retry exporting task records ({attempt}/{max_retries}): {e}");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// ClickHouse by its HTTP interface; the records are inserted as `JSONEachRow`.
+struct ClickHouseSink {
+    url: Url,
+    table: String,
+    /// Whether the table was created, i.e. by the first write.
+    created: AtomicBool,
+}
+
+impl ClickHouseSink {
+    fn new(config: &FlameExporter) -> Result<Self, FlameError> {
+        let url = Url::parse(&config.endpoint).map_err(|e| {
+            FlameError::InvalidConfig(format!(
+                "invalid exporter.endpoint <{}>: {e}",
+                config.endpoint
+            ))
+        })?;
+
+        Ok(Self {
+            url,
+            table: config.table.clone(),
+            created: AtomicBool::new(false),
+        })
+    }
+
+    fn create_table_sql(&self) -> String {
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                session_id String,
+                task_id Int64,
+                application LowCardinality(String),
+                node Nullable(String),
+                state LowCardinality(String),
+                failure_reason Nullable(String),
+                attempts UInt32,
+                creation_time DateTime64(3, 'UTC'),
+                dispatch_time Nullable(DateTime64(3, 'UTC')),
+                completion_time DateTime64(3, 'UTC'),
+                queue_ms Nullable(Int64),
+                run_ms Nullable(Int64),
+                cpu_time_ms Nullable(UInt64),
+                max_rss_bytes Nullable(UInt64)
+            ) ENGINE = MergeTree ORDER BY (application, completion_time)",
+            self.table
+        )
+    }
+
+    /// Runs the query with the data in the body, if any.
+    async fn query(&self, query: &str, body: String) -> Result<(), FlameError> {
+        let host = self
+            .url
+            .host_str()
+            .ok_or_else(|| FlameError::InvalidConfig(format!("no host in <{}>", self.url)))?
+            .to_string();
+        let port = self.url.port_or_known_default().unwrap_or(8123);
+        let failed = |e: std::io::Error| {
+            FlameError::Network(format!("failed to connect <{host}:{port}>: {e}"))
+        };
+
+        let mut url = self.url.clone();
+        url.query_pairs_mut()
+            .append_pair("query", query)
+            .append_pair("date_time_input_format", "best_effort");
+        let path = format!("{}?{}", url.path(), url.query().unwrap_or_default());
+
+        let mut request = Request::post(path)
+            .header(HOST, self.url.authority())
+            .header(USER_AGENT, "flame-session-manager")
+            .header(CONTENT_TYPE, "application/x-ndjson");
+        if !self.url.username().is_empty() {
+            request = request.header("X-ClickHouse-User", self.url.username());
+        }
+        if let Some(password) = self.url.password() {
+            request = request.header("X-ClickHouse-Key", password);
+        }
+        let request = request
+            .body(Full::new(Bytes::from(body)))
+            .map_err(|e| FlameError::Internal(format!("invalid request of ClickHouse: {e}")))?;
+
+        let stream = TcpStream::connect((host.as_str(), port))
+            .await
+            .map_err(failed)?;
+
+        let failed = |e: hyper::Error| FlameError::Network(format!("request failed: {e}"));
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(failed)?;
+        tokio::spawn(async move {
+            if let Err(e) = conn.await {
+                tracing::debug!("Connection to ClickHouse closed: {e}");
+            }
+        });
+
+        let (parts, body) = sender
+            .send_request(request)
+            .await
+            .map_err(failed)?
+            .into_parts();
+        let body = body.collect().await.map_err(failed)?.to_bytes();
+        if !parts.status.is_success() {
+            return Err(FlameError::Network(format!(
+                "ClickHouse responded {}: {}",
+                parts.status,
+                String::from_utf8_lossy(&body).trim()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskSink for ClickHouseSink {
+    async fn write(&self, records: &[TaskRecord]) -> Result<(), FlameError> {
+        if !self.created.load(Ordering::SeqCst) {
+            self.query(&self.create_table_sql(), String::new()).await?;
+            self.created.store(true, Ordering::SeqCst);
+        }
+
+        self.query(
+            &format!("INSERT INTO {} FORMAT JSONEachRow", self.table),
+            to_json_rows(records)?,
+        )
+        .await
+    }
+}
+
+/// The records in `JSONEachRow`, i.e. one JSON object per line.
+fn to_json_rows(records: &[TaskRecord]) -> Result<String, FlameError> {
+    let mut rows = String::new();
+    for record in records {
+        let row = serde_json::to_string(record)
+            .map_err(|e| FlameError::Internal(format!("failed to encode task record: {e}")))?;
+        rows.push_str(&row);
+        rows.push('\n');
+    }
+    Ok(rows)
+}
+
+/// TimescaleDB, or PostgreSQL; the table is turned into a hypertable by the
+/// completion time if the extension of TimescaleDB is installed.
+struct TimescaleSink {
+    pool: PgPool,
+    table: String,
+    /// Whether the table was created, i.e. by the first write.
+    created: AtomicBool,
+}
+
+impl TimescaleSink {
+    fn new(config: &FlameExporter) -> Result<Self, FlameError> {
+        // The database is connected by the first write, so it's not required
+        // to be available when the session manager starts.
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .connect_lazy(&config.endpoint)
+            .map_err(|e| {
+                FlameError::InvalidConfig(format!(
+                    "invalid exporter.endpoint <{}>: {e}",
+                    config.endpoint
+                ))
+            })?;
+
+        Ok(Self {
+            pool,
+            table: config.table.clone(),
+            created: AtomicBool::new(false),
+        })
+    }
+
+    async fn create_table(&self) -> Result<(), FlameError> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                session_id TEXT NOT NULL,
+                task_id BIGINT NOT NULL,
+                application TEXT NOT NULL,
+                node TEXT,
+                state TEXT NOT NULL,
+                failure_reason TEXT,
+                attempts INTEGER NOT NULL,
+                creation_time TIMESTAMPTZ NOT NULL,
+                dispatch_time TIMESTAMPTZ,
+                completion_time TIMESTAMPTZ NOT NULL,
+                queue_ms BIGINT,
+                run_ms BIGINT,
+                cpu_time_ms BIGINT,
+                max_rss_bytes BIGINT
+            )",
+            self.table
+        );
+        sqlx::query(&sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| FlameError::Storage(format!("failed to create table: {e}")))?;
+
+        let sql = format!(
+            "SELECT create_hypertable('{}', 'completion_time', if_not_exists => TRUE)",
+            self.table
+        );
+        if let Err(e) = sqlx::query(&sql).execute(&self.pool).await {
+            tracing::info!(
+                "Export task records to the plain table <{}>, not a hypertable: {e}",
+                self.table
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskSink for TimescaleSink {
+    async fn write(&self, records: &[TaskRecord]) -> Result<(), FlameError> {
+        if !self.created.load(Ordering::SeqCst) {
+            self.create_table().await?;
+            self.created.store(true, Ordering::SeqCst);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+        for chunk in records.chunks(MAX_INSERT_ROWS) {
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(format!(
+                "INSERT INTO {} (session_id, task_id, application, node, state, failure_reason, \
+                 attempts, creation_time, dispatch_time, completion_time, queue_ms, run_ms, \
+                 cpu_time_ms, max_rss_bytes) ",
+                self.table
+            ));
+            // The times are bound as RFC 3339, as the driver is built without chrono.
+            query.push_values(chunk, |mut row, record| {
+                row.push_bind(record.session_id.clone())
+                    .push_bind(record.task_id)
+                    .push_bind(record.application.clone())
+                    .push_bind(record.node.clone())
+                    .push_bind(record.state.clone())
+                    .push_bind(record.failure_reason.clone())
+                    .push_bind(record.attempts as i32)
+                    .push_bind(record.creation_time.to_rfc3339())
+                    .push_unseparated("::timestamptz")
+                    .push_bind(record.dispatch_time.map(|time| time.to_rfc3339()))
+                    .push_unseparated("::timestamptz")
+                    .push_bind(record.completion_time.to_rfc3339())
+                    .push_unseparated("::timestamptz")
+                    .push_bind(record.queue_ms)
+                    .push_bind(record.run_ms)
+                    .push_bind(record.cpu_time_ms.map(|ms| ms as i64))
+                    .push_bind(record.max_rss_bytes.map(|bytes| bytes as i64));
+            });
+            query
+                .build()
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| FlameError::Storage(format!("failed to insert task records: {e}")))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| FlameError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::apis::TaskState;
+
+    fn config(buffer_size: usize) -> FlameExporter {
+        FlameExporter {
+            kind: ExporterKind::ClickHouse,
+            endpoint: "http://localhost:8123".to_string(),
+            table: "flame_tasks".to_string(),
+            batch_size: 2,
+            flush_interval: 1,
+            max_retries: 0,
+            buffer_size,
+        }
+    }
+
+    fn task(id: i64, state: TaskState) -> Task {
+        Task {
+            id: TaskID::new(id),
+            ssn_id: "ssn-1".into(),
+            state,
+            creation_time: Utc::now() - chrono::Duration::seconds(10),
+            completion_time: Some(Utc::now()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_task_exporter() {
+        let exporter = TaskExporter::new(&config(2));
+
+        let dispatched = task(1, TaskState::Running);
+        let dispatch_time = dispatched.creation_time + chrono::Duration::seconds(4);
+        exporter
+            .dispatched("node-1", std::slice::from_ref(&dispatched), dispatch_time)
+            .unwrap();
+        exporter
+            .completed("app", &task(1, TaskState::Succeed))
+            .unwrap();
+        exporter
+            .completed("app", &task(2, TaskState::Cancelled))
+            .unwrap();
+
+        let records = exporter.take(10).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].node.as_deref(), Some("node-1"));
+        assert_eq!(records[0].state, "Succeed");
+        assert_eq!(records[0].queue_ms, Some(4000));
+        assert!(records[0].run_ms.unwrap() >= 6000);
+        // The task cancelled before its dispatch has no node and durations.
+        assert_eq!(records[1].node, None);
+        assert_eq!(records[1].run_ms, None);
+
+        // The failed batch is flushed again before the newer records, and the
+        // oldest records are dropped beyond the buffer size.
+        exporter
+            .completed("app", &task(3, TaskState::Failed))
+            .unwrap();
+        exporter.requeue(records).unwrap();
+        assert_eq!(exporter.take_dropped().unwrap(), 1);
+        let records = exporter.take(10).unwrap();
+        let ids: Vec<_> = records.iter().map(|r| r.task_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let rows = to_json_rows(&records).unwrap();
+        assert_eq!(rows.lines().count(), 2);
+        assert!(rows.starts_with("{\"session_id\":\"ssn-1\",\"task_id\":2,"));
+    }
+}
@@ -0,0 +1,59 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The fixtures shared by the tests of the storage.
+
+use common::apis::{SessionAttributes, TaskAttributes, TaskGID};
+use common::ctx::{FlameCluster, FlameClusterContext};
+
+use crate::storage::{self, StoragePtr};
+
+/// The application of the sessions created by `create_session`.
+pub const TEST_APP: &str = "test-app";
+
+/// The in-memory storage of the cluster settings, i.e. its `storage` is
+/// always "none".
+pub async fn new_storage(cluster: FlameCluster) -> StoragePtr {
+    let ctx = FlameClusterContext {
+        cluster: FlameCluster {
+            storage: "none".to_string(),
+            ..cluster
+        },
+        ..Default::default()
+    };
+
+    storage::new_ptr(&ctx).await.unwrap()
+}
+
+/// The attributes of the session of `TEST_APP`.
+pub fn session_attributes(id: &str) -> SessionAttributes {
+    SessionAttributes {
+        id: id.into(),
+        application: TEST_APP.to_string(),
+        ..SessionAttributes::default()
+    }
+}
+
+/// Creates the session of the attributes.
+pub async fn create_session(storage: &StoragePtr, attr: SessionAttributes) {
+    storage.create_session(attr).await.unwrap();
+}
+
+/// Creates a pending task without input in the session.
+pub async fn create_task(storage: &StoragePtr, ssn_id: &str) -> TaskGID {
+    storage
+        .create_task(ssn_id.into(), TaskAttributes::default())
+        .await
+        .unwrap()
+        .gid()
+}
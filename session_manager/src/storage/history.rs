@@ -0,0 +1,222 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The history of the cluster state: the scheduler persists the snapshot of the
+//! nodes, sessions and executors every few rounds, so the cluster at a point in
+//! time can be reconstructed after an incident, e.g. `flmctl debug history`.
+//!
+//! Each snapshot is a zstd-compressed `ClusterSnapshot` message in a file named
+//! by its creation time in milliseconds.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use prost::Message;
+
+use common::ctx::FlameHistory;
+use common::FlameError;
+use rpc::flame::v1 as rpc;
+
+use crate::model::{SnapShot, ALL_EXECUTOR, ALL_NODE};
+use crate::storage::Storage;
+
+const SNAPSHOT_SUFFIX: &str = ".pb.zst";
+const COMPRESSION_LEVEL: i32 = 3;
+
+impl Storage {
+    /// Persist the snapshot of the scheduler into the history, and remove the
+    /// snapshots beyond the retention; it's a no-op if the history is disabled.
+    pub fn record_history(&self, snapshot: &SnapShot) -> Result<Option<PathBuf>, FlameError> {
+        let Some(config) = &self.context.cluster.history else {
+            return Ok(None);
+        };
+
+        let cluster = cluster_snapshot(snapshot, Utc::now())?;
+        let path = save(config, &cluster)?;
+        for removed in prune(Path::new(&config.path), config.retention)? {
+            tracing::debug!("Removed expired cluster snapshot <{}>", removed.display());
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Get the latest snapshot of the cluster taken at or before the time, or
+    /// the latest one if no time.
+    pub fn get_history(
+        &self,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<rpc::ClusterSnapshot, FlameError> {
+        let Some(config) = &self.context.cluster.history else {
+            return Err(FlameError::InvalidConfig(
+                "the history of the cluster is not enabled".to_string(),
+            ));
+        };
+
+        load(Path::new(&config.path), at)
+    }
+}
+
+/// Build the snapshot of the cluster from the snapshot of the scheduler.
+fn cluster_snapshot(
+    snapshot: &SnapShot,
+    now: DateTime<Utc>,
+) -> Result<rpc::ClusterSnapshot, FlameError> {
+    let mut nodes: Vec<_> = snapshot.find_nodes(ALL_NODE)?.into_values().collect();
+    let mut sessions: Vec<_> = snapshot.find_sessions(None)?.into_values().collect();
+    let mut executors: Vec<_> = snapshot
+        .find_executors(ALL_EXECUTOR)?
+        .into_values()
+        .collect();
+
+    // Keep the order stable, so the snapshots are easy to compare.
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    sessions.sort_by(|a, b| a.id.cmp(&b.id));
+    executors.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(rpc::ClusterSnapshot {
+        creation_time: now.timestamp_millis(),
+        nodes: nodes.iter().map(|node| node.as_ref().into()).collect(),
+        sessions: sessions.iter().map(|ssn| ssn.as_ref().into()).collect(),
+        executors: executors.iter().map(|exec| exec.as_ref().into()).collect(),
+    })
+}
+
+fn save(config: &FlameHistory, cluster: &rpc::ClusterSnapshot) -> Result<PathBuf, FlameError> {
+    let root = Path::new(&config.path);
+    fs::create_dir_all(root)?;
+
+    let name = format!("{}{SNAPSHOT_SUFFIX}", cluster.creation_time);
+    let data = zstd::encode_all(cluster.encode_to_vec().as_slice(), COMPRESSION_LEVEL)?;
+
+    // Write into a hidden file first, so a partial snapshot is never loaded.
+    let tmp_path = root.join(format!(".{name}"));
+    let path = root.join(name);
+    fs::write(&tmp_path, data)?;
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(path)
+}
+
+/// The snapshots in the directory with their creation time in milliseconds,
+/// ordered from the oldest to the latest.
+fn list(root: &Path) -> Result<Vec<(i64, PathBuf)>, FlameError> {
+    if !root.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut snapshots = vec![];
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let time = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(SNAPSHOT_SUFFIX))
+            .and_then(|time| time.parse::<i64>().ok());
+        if let Some(time) = time {
+            snapshots.push((time, path));
+        }
+    }
+    snapshots.sort();
+
+    Ok(snapshots)
+}
+
+/// Remove the oldest snapshots beyond the retention, returns the removed ones.
+fn prune(root: &Path, retention: usize) -> Result<Vec<PathBuf>, FlameError> {
+    let snapshots = list(root)?;
+    let expired = snapshots.len().saturating_sub(retention);
+
+    let mut removed = vec![];
+    for (_, path) in snapshots.into_iter().take(expired) {
+        fs::remove_file(&path)?;
+        removed.push(path);
+    }
+
+    Ok(removed)
+}
+
+fn load(root: &Path, at: Option<DateTime<Utc>>) -> Result<rpc::ClusterSnapshot, FlameError> {
+    let at = at.map(|at| at.timestamp_millis());
+    let Some((_, path)) = list(root)?
+        .into_iter()
+        .rev()
+        .find(|(time, _)| at.is_none_or(|at| *time <= at))
+    else {
+        return Err(FlameError::NotFound(
+            "no snapshot of the cluster at the time".to_string(),
+        ));
+    };
+
+    let data = zstd::decode_all(fs::read(&path)?.as_slice())?;
+    rpc::ClusterSnapshot::decode(data.as_slice()).map_err(|e| {
+        FlameError::Internal(format!(
+            "failed to decode cluster snapshot <{}>: {e}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+    use common::apis::{NodeState, ResourceRequirement};
+
+    use crate::model::NodeInfo;
+
+    #[test]
+    fn test_cluster_history() -> Result<(), FlameError> {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let config = FlameHistory {
+            path: tmp_dir.path().to_string_lossy().to_string(),
+            rounds: 1,
+            retention: 2,
+        };
+
+        let snapshot = SnapShot::new(ResourceRequirement::default());
+        snapshot.add_node(std::sync::Arc::new(NodeInfo {
+            name: "node_1".to_string(),
+            allocatable: ResourceRequirement::default(),
+            state: NodeState::Ready,
+            ..Default::default()
+        }))?;
+
+        let start = Utc::now();
+        for i in 0..3 {
+            let cluster = cluster_snapshot(&snapshot, start + Duration::seconds(i))?;
+            save(&config, &cluster)?;
+            prune(tmp_dir.path(), config.retention)?;
+        }
+
+        // The oldest snapshot is removed by the retention.
+        assert_eq!(list(tmp_dir.path())?.len(), 2);
+        assert!(load(tmp_dir.path(), Some(start)).is_err());
+
+        let cluster = load(tmp_dir.path(), Some(start + Duration::milliseconds(1500)))?;
+        assert_eq!(
+            cluster.creation_time,
+            (start + Duration::seconds(1)).timestamp_millis()
+        );
+        assert_eq!(cluster.nodes.len(), 1);
+
+        let latest = load(tmp_dir.path(), None)?;
+        assert_eq!(
+            latest.creation_time,
+            (start + Duration::seconds(2)).timestamp_millis()
+        );
+
+        Ok(())
+    }
+}
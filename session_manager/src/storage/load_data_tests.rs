@@ -33,14 +33,42 @@ mod tests {
                 schedule_interval: 1000,
                 executors: FlameExecutors {
                     shim: Shim::default(),
+                    idle_timeout: None,
+                    prefetch: 0,
+                    bootstrap_concurrency: None,
+                    preempt_grace_period: None,
+                    registry: Default::default(),
+                    user: None,
+                    group: None,
+                    logs: Default::default(),
                 },
                 tls: None,
                 limits: FlameLimits {
                     max_sessions: None,
                     max_executors: 10,
                 },
+                encryption: None,
+                reflection: false,
+                http_port: None,
+                backup: None,
+                history: None,
+                exporter: None,
+                lease_grace_period: 60,
+                max_task_attempts: 3,
+                task_timeout: None,
+                task_priority: 0,
+                shuffle: Default::default(),
+                slo: Default::default(),
+                memory: None,
+                nodes: vec![],
+                runtimes: Default::default(),
+                join: None,
+                admin: None,
+                node_health: Default::default(),
+                scheduler: Default::default(),
             },
             cache: None,
+            paths: Default::default(),
         }
     }
 
@@ -75,8 +103,12 @@ mod tests {
             slots: 2,
             shim: Shim::Host,
             task_id: None,
-            ssn_id: Some("incomplete-session".to_string()),
+            bundle: vec![],
+            task_start: None,
+            ssn_id: Some("incomplete-session".into()),
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::Binding,
         };
@@ -92,8 +124,12 @@ mod tests {
             slots: 2,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::Idle,
         };
@@ -162,8 +198,12 @@ mod tests {
                 slots: 1,
                 shim: Shim::Host,
                 task_id: None,
+                bundle: vec![],
+                task_start: None,
                 ssn_id: None,
                 batch_index: None,
+                preempted: false,
+                generation: 0,
                 creation_time: Utc::now(),
                 state: *state,
             };
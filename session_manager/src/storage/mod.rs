@@ -11,35 +11,59 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use chrono::Utc;
-use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::ops::Deref;
 use std::sync::Arc;
-use uuid::Uuid;
 
 use stdng::{lock_ptr, logs::TraceFn, trace_fn, MutexPtr};
 
 use common::apis::{
-    Application, ApplicationAttributes, ApplicationID, ApplicationPtr, CommonData, Event,
-    EventOwner, ExecutorID, ExecutorState, Node, NodePtr, ResourceRequirement, Session,
-    SessionAttributes, SessionID, SessionPtr, SessionState, Shim, Task, TaskGID, TaskID, TaskInput,
-    TaskOutput, TaskPtr, TaskResult, TaskState,
+    Application, ApplicationAttributes, ApplicationID, ApplicationPtr, ApplicationState,
+    CommonData, Event, EventFilter, EventOwner, EventPage, ExecutorID, ExecutorState,
+    FailureReason, Node, NodePtr, NodeState, PayloadCodec, ReplayPolicy, ResourceRequirement,
+    ResultPolicy, Session, SessionAttributes, SessionDefaults, SessionID, SessionPtr, SessionState,
+    Shim, Task, TaskAttributes, TaskGID, TaskID, TaskOutput, TaskOverrides, TaskPostmortem,
+    TaskPtr, TaskResult, TaskSettings, TaskState, NODE_LOST_EVENT, NODE_NOT_READY_EVENT,
+    NODE_READY_EVENT, SESSION_CLOSED_EVENT, SESSION_CLOSING_EVENT, SESSION_CREATED_EVENT,
+    SESSION_LEASE_EXPIRED_EVENT, SESSION_RESUMED_EVENT, SESSION_SCHEDULABLE_EVENT,
+    SESSION_SHUFFLED_EVENT, SESSION_THROTTLED_EVENT, SESSION_UNSCHEDULABLE_EVENT,
+    SLO_BREACHED_EVENT,
 };
-use common::ctx::FlameClusterContext;
+use common::ctx::{FlameClusterContext, NodeConfig};
 use common::FlameError;
 
 use crate::model::{
-    AppInfo, Executor, ExecutorFilter, ExecutorInfo, ExecutorPtr, NodeInfo, NodeInfoPtr,
+    AppInfo, Executor, ExecutorFilter, ExecutorInfo, ExecutorPtr, NodeInfo, NodeInfoPtr, NodeLoad,
     SessionInfo, SessionInfoPtr, SnapShot, SnapShotPtr,
 };
 
+use crate::controller::settings;
 use crate::events::{EventManagerPtr, FsEventManager, MemoryEventManager};
 use crate::storage::engine::EnginePtr;
+use crate::storage::exporter::TaskExporter;
 
+pub use crate::storage::engine::SCHEMA_VERSION;
+use crate::storage::slo::SloTracker;
+
+pub mod backup;
 mod engine;
+pub mod exporter;
+mod history;
+mod slo;
 
 pub type StoragePtr = Arc<Storage>;
 
+/// The seconds before the application is retried on the node where its
+/// instance failed to start, unless the node registers again before that.
+const UNAVAILABLE_APP_TTL_SECS: i64 = 600;
+
+/// The reason and time of the last failure of each application on a node.
+type AppFailures = HashMap<String, (String, DateTime<Utc>)>;
+
+/// The recent events kept for each node, i.e. the transitions of its state.
+const MAX_NODE_EVENTS: usize = 20;
+
 #[derive(Clone)]
 pub struct Storage {
     context: FlameClusterContext,
@@ -50,29 +74,76 @@ pub struct Storage {
     applications: MutexPtr<HashMap<String, ApplicationPtr>>,
     event_manager: EventManagerPtr,
     max_sessions: Option<usize>,
+    /// The sessions throttled by the instance limits in the scheduler.
+    throttled_sessions: MutexPtr<HashSet<SessionID>>,
+    /// The last renewal time of the client leases of the sessions.
+    leases: MutexPtr<HashMap<SessionID, DateTime<Utc>>>,
+    /// The last time each session gave or received an executor by a shuffle.
+    shuffles: MutexPtr<HashMap<SessionID, DateTime<Utc>>>,
+    /// The shuffles intended in the dry-run mode of the shuffle action.
+    intended_shuffles: MutexPtr<u64>,
+    /// The generation of the last created executor.
+    last_generation: MutexPtr<u64>,
+    /// The latencies of the scheduling, checked against the SLO thresholds.
+    slo: MutexPtr<SloTracker>,
+    /// The executors bound with a previous definition of their application,
+    /// which are recycled by the scheduler.
+    stale_executors: MutexPtr<HashSet<ExecutorID>>,
+    /// The applications whose instances failed to start on each node, with the
+    /// reason and time of the last failure.
+    unavailable_apps: MutexPtr<HashMap<String, AppFailures>>,
+    /// The loads of the executors on each node, by the last heartbeat of the node.
+    node_loads: MutexPtr<HashMap<String, NodeLoad>>,
+    /// The time of the last heartbeat of each node.
+    node_heartbeats: MutexPtr<HashMap<String, DateTime<Utc>>>,
+    /// The recent events of each node, the oldest first.
+    node_events: MutexPtr<HashMap<String, VecDeque<Event>>>,
+    /// The transitions of the nodes to each state since the start.
+    node_transitions: MutexPtr<HashMap<NodeState, u64>>,
+    /// The records of the completed tasks to export, if the exporter is enabled.
+    exporter: Option<TaskExporter>,
 }
 
 pub async fn new_ptr(config: &FlameClusterContext) -> Result<StoragePtr, FlameError> {
     let event_manager: EventManagerPtr = if config.cluster.storage == "none" {
         Arc::new(MemoryEventManager::new())
     } else {
-        let events_path = derive_events_path(&config.cluster.storage);
+        let events_path = derive_events_path(&config.paths.events);
         Arc::new(FsEventManager::new(&events_path)?)
     };
 
+    let mut engine = engine::connect(&config.cluster.storage).await?;
+    if let Some(encryption) = &config.cluster.encryption {
+        engine = engine::EncryptedEngine::new_ptr(engine, encryption)?;
+    }
+
     Ok(Arc::new(Storage {
         context: config.clone(),
-        engine: engine::connect(&config.cluster.storage).await?,
+        engine,
         sessions: stdng::new_ptr(HashMap::new()),
         executors: stdng::new_ptr(HashMap::new()),
         nodes: stdng::new_ptr(HashMap::new()),
         applications: stdng::new_ptr(HashMap::new()),
         event_manager,
         max_sessions: config.cluster.limits.max_sessions,
+        throttled_sessions: stdng::new_ptr(HashSet::new()),
+        leases: stdng::new_ptr(HashMap::new()),
+        shuffles: stdng::new_ptr(HashMap::new()),
+        intended_shuffles: stdng::new_ptr(0),
+        last_generation: stdng::new_ptr(0),
+        slo: stdng::new_ptr(SloTracker::default()),
+        stale_executors: stdng::new_ptr(HashSet::new()),
+        unavailable_apps: stdng::new_ptr(HashMap::new()),
+        node_loads: stdng::new_ptr(HashMap::new()),
+        node_heartbeats: stdng::new_ptr(HashMap::new()),
+        node_events: stdng::new_ptr(HashMap::new()),
+        node_transitions: stdng::new_ptr(HashMap::new()),
+        exporter: config.cluster.exporter.as_ref().map(TaskExporter::new),
     }))
 }
 
-fn derive_events_path(storage_url: &str) -> String {
+/// The directory of the events; `FLAME_TEST_DIR` overrides the configured one.
+fn derive_events_path(events_dir: &std::path::Path) -> String {
     if let Ok(test_dir) = std::env::var("FLAME_TEST_DIR") {
         return std::path::Path::new(&test_dir)
             .join("events")
@@ -80,12 +151,41 @@ fn derive_events_path(storage_url: &str) -> String {
             .to_string();
     }
 
-    "events".to_string()
+    events_dir.to_string_lossy().to_string()
 }
 
 impl Storage {
     pub fn snapshot(&self) -> Result<SnapShotPtr, FlameError> {
-        let res = SnapShot::new(self.context.cluster.slot.clone());
+        let mut res = SnapShot::new(self.context.cluster.slot.clone());
+        res.idle_timeout = self
+            .context
+            .cluster
+            .executors
+            .idle_timeout
+            .map(|timeout| Duration::seconds(timeout as i64));
+        res.shuffle = self.context.cluster.shuffle.clone();
+        res.last_shuffles = lock_ptr!(self.shuffles)?.clone();
+        res.stale_executors = lock_ptr!(self.stale_executors)?.clone();
+        {
+            let mut unavailable_apps = lock_ptr!(self.unavailable_apps)?;
+            let expired = Utc::now() - Duration::seconds(UNAVAILABLE_APP_TTL_SECS);
+            unavailable_apps.retain(|_, apps| {
+                apps.retain(|_, (_, since)| *since > expired);
+                !apps.is_empty()
+            });
+            res.unavailable_apps = unavailable_apps
+                .iter()
+                .map(|(node, apps)| {
+                    let apps = apps
+                        .iter()
+                        .map(|(app, (reason, _))| (app.clone(), reason.clone()))
+                        .collect();
+                    (node.clone(), apps)
+                })
+                .collect();
+        }
+
+        res.node_loads = lock_ptr!(self.node_loads)?.clone();
 
         {
             let node_map = lock_ptr!(self.nodes)?;
@@ -138,11 +238,28 @@ impl Storage {
 
     pub async fn load_data(&self) -> Result<(), FlameError> {
         let ssn_list = self.engine.find_session().await?;
+        let mut closing = vec![];
         for ssn in ssn_list {
             let task_list = self.engine.find_tasks(ssn.id.clone()).await?;
             let mut ssn = ssn.clone();
+            // The running tasks of the closing session are failed instead of
+            // dispatched again, and the session is closed after loading.
+            if ssn.status.state == SessionState::Closing {
+                closing.push(ssn.id.clone());
+            }
             for task in task_list {
                 let task = match task.state {
+                    TaskState::Running if ssn.status.state == SessionState::Closing => {
+                        let task_result = TaskResult {
+                            state: TaskState::Failed,
+                            message: Some("the session manager was restarted".to_string()),
+                            failure_reason: Some(FailureReason::NodeLost),
+                            ..TaskResult::default()
+                        };
+                        self.engine
+                            .update_task_result(task.gid(), task_result)
+                            .await?
+                    }
                     TaskState::Running => self.engine.retry_task(task.gid()).await?,
                     _ => task,
                 };
@@ -190,6 +307,10 @@ impl Storage {
             exe_map.insert(executor.id.clone(), ExecutorPtr::new(executor.into()));
         }
 
+        for id in closing {
+            self.close_session(id, None, false).await?;
+        }
+
         Ok(())
     }
 
@@ -245,6 +366,41 @@ impl Storage {
         Ok(())
     }
 
+    /// Evict all the closed sessions from the cache, e.g. under memory pressure;
+    /// they are kept in the engine. Returns the number of the evicted sessions.
+    pub fn shrink_caches(&self) -> Result<usize, FlameError> {
+        let mut ssn_map = lock_ptr!(self.sessions)?;
+        let total = ssn_map.len();
+        ssn_map.retain(|_, ssn_ptr| {
+            lock_ptr!(ssn_ptr)
+                .map(|ssn| ssn.status.state != SessionState::Closed)
+                .unwrap_or(true)
+        });
+
+        Ok(total - ssn_map.len())
+    }
+
+    /// Flush the written data of the engine to the durable storage.
+    pub async fn checkpoint(&self) -> Result<(), FlameError> {
+        trace_fn!("Storage::checkpoint");
+        self.engine.checkpoint().await
+    }
+
+    /// Record the running job, see `Engine::create_job`.
+    pub async fn create_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Storage::create_job");
+        self.engine.create_job(id).await
+    }
+
+    pub async fn delete_job(&self, id: &SessionID) -> Result<(), FlameError> {
+        trace_fn!("Storage::delete_job");
+        self.engine.delete_job(id).await
+    }
+
+    pub async fn find_job_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        self.engine.find_job_ids().await
+    }
+
     pub async fn register_node(&self, node: &Node) -> Result<(), FlameError> {
         trace_fn!("Storage::register_node");
 
@@ -259,11 +415,94 @@ impl Storage {
             self.engine.create_node(node).await?;
         }
 
-        let mut node_map = lock_ptr!(self.nodes)?;
-        node_map.insert(node.name.clone(), stdng::new_ptr(node.clone()));
+        {
+            let mut node_map = lock_ptr!(self.nodes)?;
+            node_map.insert(node.name.clone(), stdng::new_ptr(node.clone()));
+        }
+
+        // The node may be fixed before it registers again, e.g. the missing
+        // binaries were installed; its applications are retried.
+        lock_ptr!(self.unavailable_apps)?.remove(&node.name);
+        self.touch_node(&node.name)?;
+
+        Ok(())
+    }
+
+    /// Records the heartbeat of the node.
+    pub fn touch_node(&self, name: &str) -> Result<(), FlameError> {
+        lock_ptr!(self.node_heartbeats)?.insert(name.to_string(), Utc::now());
+        Ok(())
+    }
+
+    /// The time of the last heartbeat of the node; the heartbeats of the nodes
+    /// are not persisted, so the node without heartbeat, e.g. loaded after a
+    /// restart, is given the whole timeout from now.
+    pub fn last_node_heartbeat(&self, name: &str) -> Result<DateTime<Utc>, FlameError> {
+        let mut heartbeats = lock_ptr!(self.node_heartbeats)?;
+        Ok(*heartbeats.entry(name.to_string()).or_insert_with(Utc::now))
+    }
+
+    /// The recent events of the node, the oldest first.
+    pub fn node_events(&self, name: &str) -> Result<Vec<Event>, FlameError> {
+        Ok(lock_ptr!(self.node_events)?
+            .get(name)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// The summary of the states of the nodes and their transitions, e.g. for the logs.
+    pub fn node_report(&self) -> Result<String, FlameError> {
+        let mut counts: HashMap<NodeState, usize> = HashMap::new();
+        for node in self.list_node()? {
+            *counts.entry(node.state).or_default() += 1;
+        }
+        let transitions = lock_ptr!(self.node_transitions)?;
+        let states = [NodeState::Ready, NodeState::NotReady, NodeState::Lost];
+
+        Ok(format!(
+            "nodes: {}; node transitions: {}",
+            states
+                .iter()
+                .map(|s| format!("{} {s}", counts.get(s).copied().unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            states
+                .iter()
+                .map(|s| format!("{} {s}", transitions.get(s).copied().unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+
+    /// Records the loads of the executors on the node by its heartbeat.
+    pub fn update_node_load(&self, name: &str, load: NodeLoad) -> Result<(), FlameError> {
+        lock_ptr!(self.node_loads)?.insert(name.to_string(), load);
         Ok(())
     }
 
+    /// The loads of the executors on the node by its last heartbeat, if any.
+    pub fn get_node_load(&self, name: &str) -> Result<Option<NodeLoad>, FlameError> {
+        Ok(lock_ptr!(self.node_loads)?.get(name).cloned())
+    }
+
+    /// The node-level configuration of the node in the cluster configuration;
+    /// its prefetch patterns are resolved to the registered applications.
+    pub fn node_config(&self, name: &str) -> Result<NodeConfig, FlameError> {
+        let mut config = self.context.cluster.node_config(name);
+        if !config.prefetch.is_empty() {
+            let app_map = lock_ptr!(self.applications)?;
+            let mut apps: Vec<String> = app_map
+                .keys()
+                .filter(|app| config.prefetches(app))
+                .cloned()
+                .collect();
+            apps.sort();
+            config.prefetch = apps;
+        }
+
+        Ok(config)
+    }
+
     /// Gets a node by name. Returns None if the node doesn't exist.
     pub fn get_node(&self, name: &str) -> Result<Option<Node>, FlameError> {
         let node_map = lock_ptr!(self.nodes)?;
@@ -311,13 +550,47 @@ impl Storage {
             self.engine.update_node(&node).await?;
 
             // Only update in-memory state after successful persistence
-            let node_map = lock_ptr!(self.nodes)?;
-            if let Some(node_ptr) = node_map.get(name) {
-                let mut node = lock_ptr!(node_ptr)?;
-                node.state = state;
+            {
+                let node_map = lock_ptr!(self.nodes)?;
+                if let Some(node_ptr) = node_map.get(name) {
+                    let mut node = lock_ptr!(node_ptr)?;
+                    node.state = state;
+                }
             }
             tracing::info!("Updated node {} state to {:?}", name, state);
+
+            self.record_node_transition(name, state)?;
+        }
+        Ok(())
+    }
+
+    /// Records the event and the metrics of the transition of the node to
+    /// Ready, NotReady or Lost; the transitions to Unknown, i.e. draining,
+    /// are not recorded.
+    fn record_node_transition(&self, name: &str, state: NodeState) -> Result<(), FlameError> {
+        let (code, message) = match state {
+            NodeState::Ready => (NODE_READY_EVENT, "Node is ready"),
+            NodeState::NotReady => (NODE_NOT_READY_EVENT, "Node is not ready, no heartbeat"),
+            NodeState::Lost => (NODE_LOST_EVENT, "Node is lost, its tasks were requeued"),
+            NodeState::Unknown => return Ok(()),
+        };
+
+        let mut node_events = lock_ptr!(self.node_events)?;
+        let events = node_events.entry(name.to_string()).or_default();
+        // The state handlers may persist the same state again, e.g. on reconnection.
+        if events.back().map(|e| e.code) == Some(code) {
+            return Ok(());
         }
+        events.push_back(Event {
+            code,
+            message: Some(message.to_string()),
+            creation_time: Utc::now(),
+        });
+        while events.len() > MAX_NODE_EVENTS {
+            events.pop_front();
+        }
+        *lock_ptr!(self.node_transitions)?.entry(state).or_default() += 1;
+
         Ok(())
     }
 
@@ -370,8 +643,12 @@ impl Storage {
                     slots: exec.slots,
                     shim: exec.shim,
                     task_id: exec.task_id,
+                    bundle: vec![],
+                    task_start: None,
                     ssn_id: exec.ssn_id.clone(),
                     batch_index: exec.batch_index,
+                    preempted: false,
+                    generation: exec.generation,
                     creation_time: exec.creation_time,
                     state: exec.state,
                 });
@@ -385,13 +662,16 @@ impl Storage {
 
     pub async fn release_node(&self, node_name: &str) -> Result<(), FlameError> {
         self.engine.delete_node(node_name).await?;
+        lock_ptr!(self.node_loads)?.remove(node_name);
+        lock_ptr!(self.node_heartbeats)?.remove(node_name);
+        lock_ptr!(self.node_events)?.remove(node_name);
 
         let mut node_map = lock_ptr!(self.nodes)?;
         node_map.remove(node_name);
         Ok(())
     }
 
-    /// Deletes multiple executors and retries their running tasks.
+    /// Deletes multiple executors and dispatches their running tasks again.
     /// Returns the list of executor IDs that were successfully deleted.
     pub async fn delete_executors(
         &self,
@@ -402,37 +682,10 @@ impl Storage {
         let mut deleted_executor_ids = Vec::new();
 
         for executor in executors {
-            // If executor has a running task, retry it
-            if let (Some(task_id), Some(ref ssn_id)) = (executor.task_id, &executor.ssn_id) {
-                let gid = TaskGID {
-                    ssn_id: ssn_id.clone(),
-                    task_id,
-                };
-                match self.engine.retry_task(gid.clone()).await {
-                    Ok(task) => {
-                        // Update the in-memory session with the retried task
-                        if let Ok(ssn_ptr) = self.get_session_ptr(ssn_id.clone()) {
-                            if let Ok(mut ssn) = lock_ptr!(ssn_ptr) {
-                                let _ = ssn.update_task(&task);
-                            }
-                        }
-                        tracing::info!(
-                            "Retried task {} for session {} due to executor {} cleanup",
-                            task_id,
-                            ssn_id,
-                            executor.id
-                        );
-                    }
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to retry task {} for session {}: {}",
-                            task_id,
-                            ssn_id,
-                            e
-                        );
-                    }
-                }
-            }
+            // If executor has running tasks, retry them
+            let message = format!("executor <{}> was lost", executor.id);
+            self.redispatch_executor_tasks(executor, FailureReason::NodeLost, &message)
+                .await?;
 
             // Delete the executor
             if let Err(e) = self.delete_executor(executor.id.clone()).await {
@@ -447,8 +700,303 @@ impl Storage {
         Ok(deleted_executor_ids)
     }
 
+    /// Dispatch the running tasks of the executor again, i.e. its current task
+    /// and the ones bundled after it; returns the tasks dispatched again.
+    pub async fn redispatch_executor_tasks(
+        &self,
+        executor: &Executor,
+        reason: FailureReason,
+        message: &str,
+    ) -> Result<Vec<TaskGID>, FlameError> {
+        let (Some(task_id), Some(ssn_id)) = (executor.task_id, executor.ssn_id.clone()) else {
+            return Ok(vec![]);
+        };
+
+        let mut redispatched = vec![];
+        for task_id in std::iter::once(task_id).chain(executor.bundle.iter().copied()) {
+            let gid = TaskGID {
+                ssn_id: ssn_id.clone(),
+                task_id,
+            };
+            let running = match self.get_task_ptr(gid.clone()) {
+                Ok(task_ptr) => lock_ptr!(task_ptr)?.state == TaskState::Running,
+                Err(_) => false,
+            };
+            if !running {
+                continue;
+            }
+            match self
+                .redispatch_task(gid.clone(), reason, Some(message.to_string()))
+                .await
+            {
+                Ok(_) => redispatched.push(gid),
+                Err(e) => tracing::error!("Failed to dispatch task <{gid}> again: {e}"),
+            }
+        }
+
+        Ok(redispatched)
+    }
+
+    /// Dispatch the task again after its attempt failed by the infrastructure,
+    /// e.g. its executor was lost; the task is quarantined instead once it has
+    /// been attempted `max_task_attempts` times.
+    pub async fn redispatch_task(
+        &self,
+        gid: TaskGID,
+        reason: FailureReason,
+        message: Option<String>,
+    ) -> Result<Task, FlameError> {
+        trace_fn!("Storage::redispatch_task");
+        let ssn = self.get_session_ptr(gid.ssn_id.clone())?;
+        let task_ptr = self.get_task_ptr(gid.clone())?;
+
+        let failure = match message {
+            Some(message) => format!("{reason:?}: {message}"),
+            None => format!("{reason:?}"),
+        };
+
+        // The task of the closing session is failed instead, which closes the
+        // session once its running tasks are drained.
+        if lock_ptr!(ssn)?.status.state == SessionState::Closing {
+            tracing::info!("Task <{gid}> of the closing session failed: {failure}");
+            let task_result = TaskResult {
+                state: TaskState::Failed,
+                message: Some(failure),
+                failure_reason: Some(reason),
+                ..TaskResult::default()
+            };
+            Box::pin(self.update_task_result(ssn.clone(), task_ptr, task_result)).await?;
+
+            let ssn = lock_ptr!(ssn)?;
+            let task_ptr = ssn
+                .tasks
+                .get(&gid.task_id)
+                .ok_or(FlameError::NotFound(format!("task <{gid}>")))?;
+            let task = lock_ptr!(task_ptr)?.clone();
+            return Ok(task);
+        }
+
+        // The in-memory task is replaced by the updated one, so it is re-indexed
+        // by its new state.
+        let mut updated_task = match self.engine.retry_task(gid.clone()).await {
+            Ok(task) => task,
+            Err(FlameError::NotFound(_)) => {
+                let mut task = lock_ptr!(task_ptr)?.clone();
+                task.state = TaskState::Pending;
+                task.version += 1;
+                task.attempts += 1;
+                task.failure_reason = None;
+                task.usage = None;
+                task
+            }
+            Err(e) => return Err(e),
+        };
+
+        let max_attempts = self
+            .get_task_settings(gid.ssn_id.clone(), Some(gid.task_id))?
+            .max_attempts
+            .value;
+        let event = if updated_task.attempts >= max_attempts {
+            updated_task = match self
+                .engine
+                .update_task_state(gid.clone(), TaskState::Quarantined, None)
+                .await
+            {
+                Ok(task) => task,
+                Err(FlameError::NotFound(_)) => {
+                    let mut task = updated_task.clone();
+                    task.state = TaskState::Quarantined;
+                    task.version += 1;
+                    task
+                }
+                Err(e) => return Err(e),
+            };
+            tracing::warn!(
+                "Task <{gid}> was quarantined after {} attempts: {failure}",
+                updated_task.attempts
+            );
+            Event {
+                code: TaskState::Quarantined.into(),
+                message: Some(format!(
+                    "Task was quarantined after {} attempts, last failure <{failure}>",
+                    updated_task.attempts
+                )),
+                creation_time: Utc::now(),
+            }
+        } else {
+            tracing::info!(
+                "Task <{gid}> was dispatched again, attempt {}/{max_attempts}: {failure}",
+                updated_task.attempts
+            );
+            Event {
+                code: TaskState::Pending.into(),
+                message: Some(format!(
+                    "Task was dispatched again, attempt {}/{max_attempts}, last failure <{failure}>",
+                    updated_task.attempts
+                )),
+                creation_time: Utc::now(),
+            }
+        };
+
+        {
+            let mut ssn_ptr = lock_ptr!(ssn)?;
+            ssn_ptr.update_task(&updated_task)?;
+        }
+        self.event_manager
+            .record_event(EventOwner::from(updated_task.gid()), event)?;
+
+        Ok(updated_task)
+    }
+
+    /// Release the quarantined task back to pending, resetting its attempts.
+    pub async fn release_task(&self, gid: TaskGID) -> Result<Task, FlameError> {
+        trace_fn!("Storage::release_task");
+        let ssn = self.get_session_ptr(gid.ssn_id.clone())?;
+        let task_ptr = self.get_task_ptr(gid.clone())?;
+
+        let updated_task = match self.engine.release_task(gid.clone()).await {
+            Ok(task) => task,
+            Err(FlameError::NotFound(_)) => {
+                let mut task = lock_ptr!(task_ptr)?.clone();
+                if task.state != TaskState::Quarantined {
+                    return Err(FlameError::InvalidState(format!(
+                        "task <{gid}> is not quarantined"
+                    )));
+                }
+                task.state = TaskState::Pending;
+                task.version += 1;
+                task.attempts = 0;
+                task.completion_time = None;
+                task
+            }
+            Err(e) => return Err(e),
+        };
+
+        {
+            let mut ssn_ptr = lock_ptr!(ssn)?;
+            ssn_ptr.update_task(&updated_task)?;
+        }
+        self.event_manager.record_event(
+            EventOwner::from(updated_task.gid()),
+            Event {
+                code: TaskState::Pending.into(),
+                message: Some("Task was released from quarantine".to_string()),
+                creation_time: Utc::now(),
+            },
+        )?;
+
+        Ok(updated_task)
+    }
+
+    pub async fn checkpoint_task(
+        &self,
+        gid: TaskGID,
+        checkpoint: String,
+    ) -> Result<Task, FlameError> {
+        trace_fn!("Storage::checkpoint_task");
+        let ssn = self.get_session_ptr(gid.ssn_id.clone())?;
+        let task_ptr = self.get_task_ptr(gid.clone())?;
+
+        let updated_task = match self
+            .engine
+            .checkpoint_task(gid.clone(), checkpoint.clone())
+            .await
+        {
+            Ok(task) => task,
+            Err(FlameError::NotFound(_)) => {
+                let mut task = lock_ptr!(task_ptr)?.clone();
+                if task.state != TaskState::Running {
+                    return Err(FlameError::InvalidState(format!(
+                        "task <{gid}> is not running"
+                    )));
+                }
+                task.checkpoint = Some(checkpoint);
+                task.version += 1;
+                task
+            }
+            Err(e) => return Err(e),
+        };
+
+        {
+            let mut ssn_ptr = lock_ptr!(ssn)?;
+            ssn_ptr.update_task(&updated_task)?;
+        }
+
+        Ok(updated_task)
+    }
+
+    /// Applies the session defaults of the application to the attributes left
+    /// unspecified by the session.
+    fn apply_session_defaults(
+        &self,
+        attr: SessionAttributes,
+    ) -> Result<SessionAttributes, FlameError> {
+        let defaults = self.get_session_defaults(&attr.application)?;
+
+        attr.with_defaults(&defaults)
+    }
+
+    /// The session defaults of the application; the application is checked by
+    /// the engine, so the session without a registered application only takes
+    /// the defaults of Flame here.
+    fn get_session_defaults(&self, app_name: &str) -> Result<SessionDefaults, FlameError> {
+        let app = {
+            let app_map = lock_ptr!(self.applications)?;
+            app_map.get(app_name).cloned()
+        };
+
+        match app {
+            Some(app) => Ok(lock_ptr!(app)?.session_defaults.clone()),
+            None => Ok(SessionDefaults::default()),
+        }
+    }
+
+    /// Rejects the new session of the disabled application; its existing
+    /// sessions are not affected.
+    fn check_application_enabled(&self, app_name: &str) -> Result<(), FlameError> {
+        let app = {
+            let app_map = lock_ptr!(self.applications)?;
+            app_map.get(app_name).cloned()
+        };
+
+        match app {
+            Some(app) if lock_ptr!(app)?.state == ApplicationState::Disabled => Err(
+                FlameError::InvalidState(format!("application <{app_name}> is disabled")),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// The effective settings of the task, or of the tasks of the session
+    /// without their own settings if no task, see `controller::settings`.
+    pub fn get_task_settings(
+        &self,
+        ssn_id: SessionID,
+        task_id: Option<TaskID>,
+    ) -> Result<TaskSettings, FlameError> {
+        let ssn_ptr = self.get_session_ptr(ssn_id.clone())?;
+        let task = match task_id {
+            Some(task_id) => {
+                Some(lock_ptr!(self.get_task_ptr(TaskGID { ssn_id, task_id })?)?.clone())
+            }
+            None => None,
+        };
+
+        let ssn = lock_ptr!(ssn_ptr)?;
+        let defaults = self.get_session_defaults(&ssn.application)?;
+
+        Ok(settings::resolve(
+            &self.context.cluster,
+            &defaults,
+            &ssn,
+            task.as_ref(),
+        ))
+    }
+
     pub async fn create_session(&self, attr: SessionAttributes) -> Result<Session, FlameError> {
         trace_fn!("Storage::create_session");
+        self.check_application_enabled(&attr.application)?;
+        let attr = self.apply_session_defaults(attr)?;
         let ssn = self.engine.create_session(attr).await?;
 
         {
@@ -456,12 +1004,41 @@ impl Storage {
             ssn_map.insert(ssn.id.clone(), SessionPtr::new(ssn.clone().into()));
         }
 
+        self.record_session_created(&ssn)?;
         self.evict_sessions()?;
 
         Ok(ssn)
     }
 
-    pub async fn close_session(&self, id: SessionID) -> Result<Session, FlameError> {
+    /// Records the creation of the session with the trace ID of its request, so
+    /// the session is found by the ID in the logs and events.
+    fn record_session_created(&self, ssn: &Session) -> Result<(), FlameError> {
+        let message = match &ssn.trace_id {
+            Some(trace_id) => format!("Session was created by request <{trace_id}>"),
+            None => "Session was created".to_string(),
+        };
+        tracing::info!("Session <{}>: {message}.", ssn.id);
+
+        self.event_manager.record_event(
+            EventOwner::session(ssn.id.clone()),
+            Event {
+                code: SESSION_CREATED_EVENT,
+                message: Some(message),
+                creation_time: Utc::now(),
+            },
+        )
+    }
+
+    /// Close the session, and cancel its pending tasks; it's `Closing` until its
+    /// running tasks are completed, unless they're cancelled by `force`. Fails
+    /// with `FlameError::Conflict` if its version is not the expected one,
+    /// which is not checked if None.
+    pub async fn close_session(
+        &self,
+        id: SessionID,
+        expected_version: Option<u32>,
+        force: bool,
+    ) -> Result<Session, FlameError> {
         trace_fn!("Storage::close_session");
 
         let ssn_ptr = {
@@ -472,29 +1049,115 @@ impl Storage {
                 .ok_or(FlameError::NotFound(format!("session <{}>", id)))?
         };
 
-        let result_ssn = {
+        let (result_ssn, running, cancelled) = {
             let mut ssn = lock_ptr!(ssn_ptr)?;
-            ssn.status.state = SessionState::Closed;
-            ssn.completion_time = Some(Utc::now());
+            engine::check_version("session", &id, expected_version, ssn.version)?;
+
+            // The quarantined tasks are cancelled with the pending ones, and
+            // the running ones too by force.
+            let mut cancelled = vec![];
+            let mut running = 0;
+            for task_ptr in ssn.tasks.values() {
+                let mut task = lock_ptr!(task_ptr)?.clone();
+                match task.state {
+                    TaskState::Pending | TaskState::Quarantined => {}
+                    TaskState::Running if force => {}
+                    TaskState::Running => {
+                        running += 1;
+                        continue;
+                    }
+                    _ => continue,
+                }
+                let state = task.state;
+                task.state = TaskState::Cancelled;
+                task.completion_time = Some(Utc::now());
+                task.version += 1;
+                cancelled.push((state, task));
+            }
+            for (_, task) in &cancelled {
+                ssn.update_task(task)?;
+                self.export_completed(&ssn.application, task)?;
+            }
+
+            if running > 0 {
+                ssn.status.state = SessionState::Closing;
+            } else {
+                ssn.status.state = SessionState::Closed;
+                ssn.completion_time = Some(Utc::now());
+            }
+            ssn.status.unschedulable = None;
             ssn.version += 1;
-            ssn.clone()
+
+            let cancelled = cancelled
+                .iter()
+                .filter(|(state, _)| *state == TaskState::Running)
+                .count();
+            (ssn.clone(), running, cancelled)
         };
 
-        if let Err(e) = self.engine.close_session(id.clone()).await {
-            if !matches!(e, FlameError::NotFound(_)) {
-                return Err(e);
+        match self
+            .engine
+            .close_session(id.clone(), expected_version, force)
+            .await
+        {
+            Ok(_) | Err(FlameError::NotFound(_)) => {}
+            Err(FlameError::Conflict(e)) => {
+                // The cached session is behind the engine, e.g. it's updated by
+                // another session manager; reload its status for the retries.
+                let stored = self.engine.get_session(id).await?;
+                let mut ssn = lock_ptr!(ssn_ptr)?;
+                ssn.status = stored.status;
+                ssn.completion_time = stored.completion_time;
+                ssn.version = stored.version;
+                return Err(FlameError::Conflict(e));
             }
+            Err(e) => return Err(e),
         }
 
+        let message = match (result_ssn.status.state, cancelled) {
+            (SessionState::Closing, _) => {
+                format!("Session is closing, waiting for {running} running task(s)")
+            }
+            (_, 0) => "Session was closed".to_string(),
+            (_, cancelled) => format!("Session was closed, {cancelled} running task(s) cancelled"),
+        };
+        let code = match result_ssn.status.state {
+            SessionState::Closing => SESSION_CLOSING_EVENT,
+            _ => SESSION_CLOSED_EVENT,
+        };
+        tracing::info!("Session <{id}>: {message}.");
+        self.event_manager.record_event(
+            EventOwner::session(id.clone()),
+            Event {
+                code,
+                message: Some(message),
+                creation_time: Utc::now(),
+            },
+        )?;
+
         self.evict_sessions()?;
 
         Ok(result_ssn)
     }
 
+    /// The page of the events of the session and its tasks matching the filter.
+    pub fn query_events(
+        &self,
+        id: SessionID,
+        filter: &EventFilter,
+        page_size: u32,
+        page_token: Option<&str>,
+    ) -> Result<EventPage, FlameError> {
+        let events = self.event_manager.query_events(id, filter)?;
+        EventPage::new(events, page_size, page_token)
+    }
+
     pub fn get_session(&self, id: SessionID) -> Result<Session, FlameError> {
-        let ssn_ptr = self.get_session_ptr(id)?;
-        let ssn = lock_ptr!(ssn_ptr)?;
-        Ok(ssn.clone())
+        let ssn_ptr = self.get_session_ptr(id.clone())?;
+        let mut ssn = lock_ptr!(ssn_ptr)?.clone();
+        ssn.events = self.event_manager.find_events(EventOwner::session(id))?;
+
+        Ok(ssn)
     }
 
     pub fn get_session_ptr(&self, id: SessionID) -> Result<SessionPtr, FlameError> {
@@ -510,21 +1173,33 @@ impl Storage {
         &self,
         id: SessionID,
         spec: Option<SessionAttributes>,
+        replay: Option<ReplayPolicy>,
     ) -> Result<Session, FlameError> {
         trace_fn!("Storage::open_session");
+        let spec = spec
+            .map(|attr| self.apply_session_defaults(attr))
+            .transpose()?;
 
         // Check if session already exists in cache - if so, return it directly
         // to preserve in-memory task state
-        {
+        let cached = {
             let ssn_map = lock_ptr!(self.sessions)?;
-            if let Some(ssn_ptr) = ssn_map.get(&id) {
+            ssn_map.get(&id).cloned()
+        };
+        if let Some(ssn_ptr) = cached {
+            let closed = {
                 let ssn = lock_ptr!(ssn_ptr)?;
+                // If spec provided, validate it matches the existing session
+                if let Some(ref attr) = spec {
+                    ssn.validate_spec(attr)?;
+                }
+                if ssn.status.state == SessionState::Closing {
+                    return Err(FlameError::InvalidState(format!(
+                        "session <{id}> is closing"
+                    )));
+                }
                 // Verify the session is still open before returning cached version
                 if ssn.status.state == SessionState::Open {
-                    // If spec provided, validate it matches the existing session
-                    if let Some(ref attr) = spec {
-                        ssn.validate_spec(attr)?;
-                    }
                     tracing::debug!(
                         "Session <{}> already exists in cache with {} tasks, returning cached version",
                         id,
@@ -532,22 +1207,69 @@ impl Storage {
                     );
                     return Ok(ssn.clone());
                 }
+                ssn.is_closed()
+            };
+
+            if let (true, Some(policy)) = (closed, replay) {
+                return self.reopen_session(ssn_ptr, spec, policy).await;
             }
         }
 
         // Session not in cache or not open, delegate to engine for atomic get-or-create operation
-        let ssn = self.engine.open_session(id.clone(), spec).await?;
+        let trace_id = spec.as_ref().and_then(|attr| attr.trace_id.clone());
+        let mut ssn = self.engine.open_session(id.clone(), spec, replay).await?;
+        // The session may have been evicted from the cache, so load its tasks.
+        for task in self.engine.find_tasks(id.clone()).await? {
+            ssn.update_task(&task)?;
+        }
 
         {
             let mut ssn_map = lock_ptr!(self.sessions)?;
             ssn_map.insert(ssn.id.clone(), SessionPtr::new(ssn.clone().into()));
         }
 
+        // The trace ID of the request is stored only if the session was created by it.
+        if trace_id.is_some() && ssn.trace_id == trace_id {
+            self.record_session_created(&ssn)?;
+        }
         self.evict_sessions()?;
 
         Ok(ssn)
     }
 
+    /// Reopen the cached closed session, and replay its tasks by the policy.
+    async fn reopen_session(
+        &self,
+        ssn_ptr: SessionPtr,
+        spec: Option<SessionAttributes>,
+        policy: ReplayPolicy,
+    ) -> Result<Session, FlameError> {
+        let (result_ssn, task_ids) = {
+            let mut ssn = lock_ptr!(ssn_ptr)?;
+            let task_ids = ssn.reopen(policy)?;
+            (ssn.clone(), task_ids)
+        };
+
+        if let Err(e) = self
+            .engine
+            .open_session(result_ssn.id.clone(), spec, Some(policy))
+            .await
+        {
+            if !matches!(e, FlameError::NotFound(_)) {
+                return Err(e);
+            }
+        }
+
+        tracing::info!(
+            "Session <{}> was reopened with policy <{}>, {} task(s) to run again.",
+            result_ssn.id,
+            policy,
+            task_ids.len()
+        );
+
+        Ok(result_ssn)
+    }
+
     pub fn get_task_ptr(&self, gid: TaskGID) -> Result<TaskPtr, FlameError> {
         let ssn_map = lock_ptr!(self.sessions)?;
         let ssn_ptr = ssn_map
@@ -564,26 +1286,34 @@ impl Storage {
     }
 
     pub async fn delete_session(&self, id: SessionID) -> Result<Session, FlameError> {
-        let ssn = {
+        let cached = {
             let ssn_map = lock_ptr!(self.sessions)?;
-            let ssn_ptr = ssn_map
-                .get(&id)
-                .ok_or_else(|| FlameError::NotFound(format!("session <{}>", id)))?;
-            let ssn = lock_ptr!(ssn_ptr)?;
-            ssn.clone()
+            match ssn_map.get(&id) {
+                Some(ssn_ptr) => Some(lock_ptr!(ssn_ptr)?.clone()),
+                None => None,
+            }
         };
 
-        if let Err(e) = self.engine.delete_session(id.clone()).await {
-            if !matches!(e, FlameError::NotFound(_)) {
-                return Err(e);
-            }
-        }
+        // The closed session may have been evicted from the cache, so it's
+        // deleted from the engine only.
+        let deleted = match self.engine.delete_session(id.clone()).await {
+            Ok(ssn) => Some(ssn),
+            Err(FlameError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+        let ssn = cached
+            .or(deleted)
+            .ok_or_else(|| FlameError::NotFound(format!("session <{}>", id)))?;
 
         {
             let mut ssn_map = lock_ptr!(self.sessions)?;
             ssn_map.remove(&id);
         }
 
+        lock_ptr!(self.throttled_sessions)?.remove(&id);
+        lock_ptr!(self.leases)?.remove(&id);
+        lock_ptr!(self.shuffles)?.remove(&id);
+        lock_ptr!(self.slo)?.remove_session(&id);
         self.event_manager.remove_events(id)?;
 
         Ok(ssn)
@@ -601,6 +1331,17 @@ impl Storage {
         Ok(ssn_list)
     }
 
+    /// The IDs of all the sessions, including the closed ones evicted from the cache.
+    pub async fn list_session_ids(&self) -> Result<Vec<SessionID>, FlameError> {
+        let mut ids: HashSet<SessionID> = {
+            let ssn_map = lock_ptr!(self.sessions)?;
+            ssn_map.keys().cloned().collect()
+        };
+        ids.extend(self.engine.find_session_ids().await?);
+
+        Ok(ids.into_iter().collect())
+    }
+
     /// Lists executors with optional filtering.
     ///
     /// # Arguments
@@ -662,20 +1403,38 @@ impl Storage {
     pub async fn create_task(
         &self,
         ssn_id: SessionID,
-        task_input: Option<TaskInput>,
+        attr: TaskAttributes,
     ) -> Result<Task, FlameError> {
         trace_fn!("Storage::create_task");
-        let task = self.engine.create_task(ssn_id.clone(), task_input).await?;
+        if let Some(input_ref) = &attr.input_ref {
+            if attr.input.is_some() {
+                return Err(FlameError::InvalidConfig(
+                    "task can not have both input and input_ref".to_string(),
+                ));
+            }
+            input_ref.validate()?;
+        }
+        if !attr.overrides.is_empty() {
+            self.validate_task_overrides(ssn_id.clone(), &attr.overrides)?;
+        }
+
+        let task = self.engine.create_task(ssn_id.clone(), attr).await?;
 
         let ssn = self.get_session_ptr(ssn_id.clone())?;
         let mut ssn = lock_ptr!(ssn)?;
         ssn.update_task(&task)?;
+        lock_ptr!(self.slo)?.on_task_created(&ssn_id, task.creation_time);
 
+        let mut message = format!("Task was created with state <{:?}>", task.state);
+        if let Some(trace_id) = &task.trace_id {
+            message.push_str(&format!(" by request <{trace_id}>"));
+        }
+        tracing::debug!("Task <{}>: {message}.", task.gid());
         self.event_manager.record_event(
             EventOwner::from(&task),
             Event {
                 code: task.state.into(),
-                message: Some(format!("Task was created with state <{:?}>", task.state)),
+                message: Some(message),
                 creation_time: Utc::now(),
             },
         )?;
@@ -683,6 +1442,45 @@ impl Storage {
         Ok(task)
     }
 
+    fn validate_task_overrides(
+        &self,
+        ssn_id: SessionID,
+        overrides: &TaskOverrides,
+    ) -> Result<(), FlameError> {
+        let app = self.get_session_application(ssn_id)?;
+        let app = lock_ptr!(app)?;
+
+        app.validate_task_overrides(overrides)
+    }
+
+    /// Checks the input or output of a task of the session, tagged by its
+    /// codec if any, against the codec of the application of the session.
+    pub fn validate_task_payload(
+        &self,
+        ssn_id: SessionID,
+        codec: Option<PayloadCodec>,
+        payload: Option<&[u8]>,
+    ) -> Result<(), FlameError> {
+        let app = self.get_session_application(ssn_id)?;
+        let codec_of_app = lock_ptr!(app)?.codec;
+
+        codec_of_app.validate(codec, payload)
+    }
+
+    fn get_session_application(&self, ssn_id: SessionID) -> Result<ApplicationPtr, FlameError> {
+        let app_name = {
+            let ssn = self.get_session_ptr(ssn_id)?;
+            let ssn = lock_ptr!(ssn)?;
+            ssn.application.clone()
+        };
+
+        let app_map = lock_ptr!(self.applications)?;
+        app_map
+            .get(&app_name)
+            .cloned()
+            .ok_or(FlameError::NotFound(format!("application <{app_name}>")))
+    }
+
     pub fn get_task(&self, ssn_id: SessionID, id: TaskID) -> Result<Task, FlameError> {
         let ssn_map = lock_ptr!(self.sessions)?;
 
@@ -732,6 +1530,15 @@ impl Storage {
         name: String,
         attr: ApplicationAttributes,
     ) -> Result<(), FlameError> {
+        attr.session_defaults.validate()?;
+        if let Some(probe) = &attr.health_probe {
+            probe.validate()?;
+        }
+        attr.hooks.validate()?;
+        if let Some(env) = &attr.python_env {
+            env.validate()?;
+        }
+        attr.validate_codec()?;
         let app = self.engine.register_application(name, attr).await?;
 
         let mut app_map = lock_ptr!(self.applications)?;
@@ -764,19 +1571,99 @@ impl Storage {
         Ok(())
     }
 
+    /// Update the application; fails with `FlameError::Conflict` if its version
+    /// is not the expected one, which is not checked if None.
     pub async fn update_application(
         &self,
         name: String,
         attr: ApplicationAttributes,
+        expected_version: Option<u32>,
     ) -> Result<(), FlameError> {
-        let app = self.engine.update_application(name.clone(), attr).await?;
+        attr.session_defaults.validate()?;
+        if let Some(probe) = &attr.health_probe {
+            probe.validate()?;
+        }
+        attr.hooks.validate()?;
+        if let Some(env) = &attr.python_env {
+            env.validate()?;
+        }
+        attr.validate_codec()?;
+        let app = self
+            .engine
+            .update_application(name.clone(), attr, expected_version)
+            .await?;
 
-        let mut app_map = lock_ptr!(self.applications)?;
-        app_map.insert(name.clone(), stdng::new_ptr(app.clone()));
+        {
+            let mut app_map = lock_ptr!(self.applications)?;
+            app_map.insert(name.clone(), stdng::new_ptr(app.clone()));
+        }
+
+        // The executors of the application's sessions were bound with its previous
+        // definition; they're recycled, i.e. unbound after their current task and
+        // released, so that the new executors are created with the new definition.
+        let stale = self.bound_executors_of(&name)?;
+        if !stale.is_empty() {
+            tracing::info!(
+                "Recycle {} executors of application <{name}> for its update.",
+                stale.len()
+            );
+            lock_ptr!(self.stale_executors)?.extend(stale);
+        }
+
+        // The new definition is retried on the nodes where the previous one failed.
+        for apps in lock_ptr!(self.unavailable_apps)?.values_mut() {
+            apps.remove(&name);
+        }
 
         Ok(())
     }
 
+    /// Enables or disables the application; the disabled application rejects
+    /// new sessions, while its existing sessions are running to completion.
+    pub async fn update_application_state(
+        &self,
+        name: String,
+        state: ApplicationState,
+    ) -> Result<Application, FlameError> {
+        let app = self
+            .engine
+            .update_application_state(name.clone(), state)
+            .await?;
+
+        {
+            let mut app_map = lock_ptr!(self.applications)?;
+            app_map.insert(name, stdng::new_ptr(app.clone()));
+        }
+
+        Ok(app)
+    }
+
+    /// The executors binding or bound to the sessions of the application.
+    fn bound_executors_of(&self, app_name: &str) -> Result<Vec<ExecutorID>, FlameError> {
+        let ssn_ids = {
+            let ssn_map = lock_ptr!(self.sessions)?;
+            let mut ids = HashSet::new();
+            for (id, ssn) in ssn_map.iter() {
+                if lock_ptr!(ssn)?.application == app_name {
+                    ids.insert(id.clone());
+                }
+            }
+            ids
+        };
+
+        let exe_map = lock_ptr!(self.executors)?;
+        let mut res = vec![];
+        for exe in exe_map.values() {
+            let exe = lock_ptr!(exe)?;
+            let bound = matches!(exe.state, ExecutorState::Binding | ExecutorState::Bound);
+            if bound && exe.ssn_id.as_ref().is_some_and(|id| ssn_ids.contains(id)) {
+                res.push(exe.id.clone());
+            }
+        }
+
+        Ok(res)
+    }
+
     pub async fn list_application(&self) -> Result<Vec<Application>, FlameError> {
         self.engine.find_application().await
     }
@@ -817,6 +1704,7 @@ impl Storage {
 
         let mut ssn_ptr = lock_ptr!(ssn)?;
         ssn_ptr.update_task(&updated_task)?;
+        self.export_completed(&ssn_ptr.application, &updated_task)?;
 
         self.event_manager.record_event(
             EventOwner::from(updated_task.gid()),
@@ -848,11 +1736,77 @@ impl Storage {
             },
         };
 
+        // The result of the task cancelled by the forced close of its session
+        // is dropped.
+        if lock_ptr!(task)?.state == TaskState::Cancelled {
+            tracing::debug!("Ignore the result of the cancelled task <{gid}>.");
+            return Ok(());
+        }
+        let (closing, result_policy) = {
+            let ssn_ptr = lock_ptr!(ssn)?;
+            (
+                ssn_ptr.status.state == SessionState::Closing,
+                ssn_ptr.result_policy,
+            )
+        };
+
+        // The postmortem of the crashed instance is recorded before the task
+        // is dispatched again, so it's kept for diagnosing.
+        if let Some(postmortem) = task_result.postmortem.clone() {
+            self.update_task_postmortem(ssn.clone(), task.clone(), gid.clone(), postmortem)
+                .await?;
+        }
+
+        // The attempt failed by the infrastructure instead of the application,
+        // e.g. the shim crashed; dispatch the task again, unless the session is
+        // closing.
+        if let (TaskState::Failed, Some(reason)) = (task_result.state, task_result.failure_reason) {
+            if reason.is_infrastructure() && !closing {
+                self.redispatch_task(gid, reason, task_result.message)
+                    .await?;
+                return Ok(());
+            }
+        }
+
+        let mut task_result = task_result;
+        // The output which is not of the codec of the application fails the
+        // task, instead of being delivered to the clients which can't decode it.
+        if task_result.state == TaskState::Succeed {
+            if let Err(e) = self.validate_task_payload(
+                gid.ssn_id.clone(),
+                task_result.codec,
+                task_result.output.as_deref(),
+            ) {
+                tracing::warn!("The output of task <{gid}> is invalid: {e}");
+                task_result.state = TaskState::Failed;
+                task_result.failure_reason = Some(FailureReason::ApplicationError);
+                task_result.message = Some(format!("invalid output: {e}"));
+            }
+        }
         let task_state = task_result.state;
         let task_message = task_result.message.clone();
         let task_output = task_result.output.clone();
+        let task_artifacts = task_result.artifacts.clone();
+        let task_failure_reason = task_result.failure_reason;
+        let task_usage = task_result.usage;
+
+        // The outputs discarded by the session are only kept in memory until
+        // they're delivered to a watcher; the offloaded outputs are kept by
+        // their references, and the ones which failed to be offloaded by the
+        // executor manager are kept inline.
+        match result_policy {
+            ResultPolicy::Discard => {
+                task_result.output = None;
+                task_result.output_ref = None;
+            }
+            ResultPolicy::ObjectCache if task_result.output_ref.is_some() => {
+                task_result.output = None;
+            }
+            _ => {}
+        }
+        let task_output_ref = task_result.output_ref.clone();
 
-        let updated_task = match self
+        let mut updated_task = match self
             .engine
             .update_task_result(gid.clone(), task_result)
             .await
@@ -863,14 +1817,24 @@ impl Storage {
                 task_ptr.state = task_state;
                 task_ptr.version += 1;
                 task_ptr.completion_time = Some(Utc::now());
-                task_ptr.output = task_output;
+                task_ptr.output = task_output.clone();
+                task_ptr.output_ref = task_output_ref;
+                task_ptr.artifacts = task_artifacts;
+                task_ptr.failure_reason = task_failure_reason;
+                task_ptr.usage = task_usage;
                 task_ptr.clone()
             }
             Err(e) => return Err(e),
         };
+        if result_policy == ResultPolicy::Discard {
+            updated_task.output = task_output;
+        }
 
-        let mut ssn_ptr = lock_ptr!(ssn)?;
-        ssn_ptr.update_task(&updated_task)?;
+        {
+            let mut ssn_ptr = lock_ptr!(ssn)?;
+            ssn_ptr.update_task(&updated_task)?;
+            self.export_completed(&ssn_ptr.application, &updated_task)?;
+        }
 
         let event_message = match task_state {
             TaskState::Failed => {
@@ -888,6 +1852,67 @@ impl Storage {
             },
         )?;
 
+        // The closing session is closed once its running tasks are drained,
+        // and its progress is recorded until then.
+        if closing {
+            self.close_session(gid.ssn_id, None, false).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drops the output of the completed task in memory once it's delivered
+    /// to a watcher, if its session discards the task outputs, i.e. the
+    /// output was not persisted.
+    pub fn discard_task_output(&self, gid: TaskGID) -> Result<(), FlameError> {
+        let ssn_ptr = self.get_session_ptr(gid.ssn_id.clone())?;
+        if lock_ptr!(ssn_ptr)?.result_policy != ResultPolicy::Discard {
+            return Ok(());
+        }
+
+        let task_ptr = self.get_task_ptr(gid)?;
+        let mut task = lock_ptr!(task_ptr)?;
+        if task.is_completed() {
+            task.output = None;
+        }
+
+        Ok(())
+    }
+
+    async fn update_task_postmortem(
+        &self,
+        ssn: SessionPtr,
+        task: TaskPtr,
+        gid: TaskGID,
+        postmortem: TaskPostmortem,
+    ) -> Result<(), FlameError> {
+        trace_fn!("Storage::update_task_postmortem");
+        tracing::warn!(
+            "The instance of executor <{}> on node <{}> crashed running task <{gid}>: exit code <{:?}>, signal <{:?}>",
+            postmortem.executor_id,
+            postmortem.node,
+            postmortem.exit_code,
+            postmortem.signal
+        );
+
+        let updated_task = match self
+            .engine
+            .update_task_postmortem(gid, postmortem.clone())
+            .await
+        {
+            Ok(task) => task,
+            Err(FlameError::NotFound(_)) => {
+                let mut task_ptr = lock_ptr!(task)?;
+                task_ptr.postmortem = Some(postmortem);
+                task_ptr.version += 1;
+                task_ptr.clone()
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut ssn_ptr = lock_ptr!(ssn)?;
+        ssn_ptr.update_task(&updated_task)?;
+
         Ok(())
     }
 
@@ -908,16 +1933,29 @@ impl Storage {
             )
         };
 
+        // The generation is the creation time in microseconds, increased beyond
+        // the last one; so it differs from the one of the released executor of
+        // the same id, also across the restarts of the session manager.
+        let creation_time = Utc::now();
+        let generation = {
+            let mut last = lock_ptr!(self.last_generation)?;
+            *last = (creation_time.timestamp_micros() as u64).max(*last + 1);
+            *last
+        };
         let e = Executor {
-            id: Uuid::new_v4().to_string(),
+            id: self.next_executor_id(&node_name)?,
             node: node_name.clone(),
             resreq,
             slots,
             shim: Shim::default(),
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index,
-            creation_time: Utc::now(),
+            preempted: false,
+            generation,
+            creation_time,
             state: ExecutorState::Void,
         };
 
@@ -930,6 +1968,34 @@ impl Storage {
         Ok(e.clone())
     }
 
+    /// The id of a new executor on the node: the node name with the lowest slot
+    /// index not used by its executors, e.g. `node-1-0`; so the executors on a
+    /// node keep their identities across the restarts of the executor manager.
+    fn next_executor_id(&self, node_name: &str) -> Result<ExecutorID, FlameError> {
+        let prefix = format!("{node_name}-");
+
+        let exe_map = lock_ptr!(self.executors)?;
+        let mut used = HashSet::new();
+        for exe in exe_map.values() {
+            let exe = lock_ptr!(exe)?;
+            if exe.node != node_name {
+                continue;
+            }
+            if let Some(index) = exe
+                .id
+                .strip_prefix(&prefix)
+                .and_then(|index| index.parse::<u32>().ok())
+            {
+                used.insert(index);
+            }
+        }
+
+        let index = (0..)
+            .find(|index| !used.contains(index))
+            .unwrap_or_default();
+        Ok(format!("{prefix}{index}"))
+    }
+
     pub fn get_executor_ptr(&self, id: ExecutorID) -> Result<ExecutorPtr, FlameError> {
         let exe_map = lock_ptr!(self.executors)?;
         let exe = exe_map
@@ -948,9 +2014,18 @@ impl Storage {
             let mut exe = lock_ptr!(exe_ptr)?;
             exe.state = executor.state;
             exe.task_id = executor.task_id;
+            exe.bundle = executor.bundle.clone();
             exe.ssn_id = executor.ssn_id.clone();
         }
 
+        // The recycled executor is not bound to the previous definition anymore.
+        if !matches!(
+            executor.state,
+            ExecutorState::Binding | ExecutorState::Bound | ExecutorState::Unbinding
+        ) {
+            lock_ptr!(self.stale_executors)?.remove(&executor.id);
+        }
+
         Ok(())
     }
 
@@ -960,6 +2035,7 @@ impl Storage {
 
         let mut exe_map = lock_ptr!(self.executors)?;
         exe_map.remove(&id);
+        lock_ptr!(self.stale_executors)?.remove(&id);
 
         Ok(())
     }
@@ -968,8 +2044,392 @@ impl Storage {
         trace_fn!("Storage::record_event");
         self.event_manager.record_event(owner, event)
     }
+
+    /// Records that the session is throttled by the instance limits; the event is
+    /// only recorded when the session becomes throttled instead of in every cycle.
+    pub fn throttle_session(&self, id: &SessionID, reason: &str) -> Result<(), FlameError> {
+        if !lock_ptr!(self.throttled_sessions)?.insert(id.clone()) {
+            return Ok(());
+        }
+
+        tracing::info!("Session <{id}> is throttled: {reason}");
+        self.event_manager.record_event(
+            EventOwner::session(id.clone()),
+            Event {
+                code: SESSION_THROTTLED_EVENT,
+                message: Some(reason.to_string()),
+                creation_time: Utc::now(),
+            },
+        )
+    }
+
+    /// Records that the session is not throttled anymore, if it was.
+    pub fn resume_session(&self, id: &SessionID) -> Result<(), FlameError> {
+        if !lock_ptr!(self.throttled_sessions)?.remove(id) {
+            return Ok(());
+        }
+
+        tracing::info!("Session <{id}> is resumed from throttling.");
+        self.event_manager.record_event(
+            EventOwner::session(id.clone()),
+            Event {
+                code: SESSION_RESUMED_EVENT,
+                message: Some("Session was resumed from throttling".to_string()),
+                creation_time: Utc::now(),
+            },
+        )
+    }
+
+    /// Records that the instance of the application failed to start on the node,
+    /// e.g. its binary is missing or built for another architecture; the
+    /// application is not scheduled to the node until it's retried.
+    pub fn mark_app_unavailable(
+        &self,
+        node: &str,
+        app: &str,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        tracing::warn!("Application <{app}> is unavailable on node <{node}>: {reason}");
+        lock_ptr!(self.unavailable_apps)?
+            .entry(node.to_string())
+            .or_default()
+            .insert(app.to_string(), (reason.to_string(), Utc::now()));
+
+        Ok(())
+    }
+
+    /// Marks the session unschedulable as no ready node can run its application;
+    /// the event is only recorded when the session becomes unschedulable.
+    pub fn mark_unschedulable(&self, id: &SessionID, reason: &str) -> Result<(), FlameError> {
+        let ssn_ptr = self.get_session_ptr(id.clone())?;
+        {
+            let mut ssn = lock_ptr!(ssn_ptr)?;
+            if ssn.status.unschedulable.is_some() {
+                return Ok(());
+            }
+            ssn.status.unschedulable = Some(reason.to_string());
+        }
+
+        tracing::warn!("Session <{id}> is unschedulable: {reason}");
+        self.event_manager.record_event(
+            EventOwner::session(id.clone()),
+            Event {
+                code: SESSION_UNSCHEDULABLE_EVENT,
+                message: Some(reason.to_string()),
+                creation_time: Utc::now(),
+            },
+        )
+    }
+
+    /// Clears the unschedulable condition of the session, if any.
+    pub fn mark_schedulable(&self, id: &SessionID) -> Result<(), FlameError> {
+        let ssn_ptr = self.get_session_ptr(id.clone())?;
+        if lock_ptr!(ssn_ptr)?.status.unschedulable.take().is_none() {
+            return Ok(());
+        }
+
+        tracing::info!("Session <{id}> is schedulable again.");
+        self.event_manager.record_event(
+            EventOwner::session(id.clone()),
+            Event {
+                code: SESSION_SCHEDULABLE_EVENT,
+                message: Some("A ready node can run the application of the session".to_string()),
+                creation_time: Utc::now(),
+            },
+        )
+    }
+
+    /// Records the shuffle of the executor from one session to another, with the
+    /// reason of the decision as an event of both sessions; the sessions start
+    /// their cooldown. In the dry-run mode, the shuffle is only counted as intended.
+    pub fn record_shuffle(
+        &self,
+        exec_id: &ExecutorID,
+        from: &SessionID,
+        to: &SessionID,
+        reason: &str,
+    ) -> Result<(), FlameError> {
+        let message = if self.context.cluster.shuffle.dry_run {
+            let mut intended = lock_ptr!(self.intended_shuffles)?;
+            *intended += 1;
+            tracing::info!(
+                "Executor <{exec_id}> would be shuffled from session <{from}> to <{to}> ({} intended shuffles): {reason}",
+                *intended
+            );
+            format!("[dry-run] Executor <{exec_id}> would be shuffled from session <{from}> to <{to}>: {reason}")
+        } else {
+            tracing::info!(
+                "Executor <{exec_id}> is shuffled from session <{from}> to <{to}>: {reason}"
+            );
+            format!("Executor <{exec_id}> was shuffled from session <{from}> to <{to}>: {reason}")
+        };
+
+        let now = Utc::now();
+        {
+            let mut shuffles = lock_ptr!(self.shuffles)?;
+            shuffles.insert(from.clone(), now);
+            shuffles.insert(to.clone(), now);
+        }
+
+        for id in [from, to] {
+            self.event_manager.record_event(
+                EventOwner::session(id.clone()),
+                Event {
+                    code: SESSION_SHUFFLED_EVENT,
+                    message: Some(message.clone()),
+                    creation_time: now,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Observes the latency of the session's first executor bound, if it is; the
+    /// breach of the SLO is logged and recorded as an event of the session.
+    pub fn observe_bind_latency(&self, ssn_id: &SessionID) -> Result<(), FlameError> {
+        let now = Utc::now();
+        let slo = self.context.cluster.slo.bind_latency;
+
+        let breached = {
+            let mut tracker = lock_ptr!(self.slo)?;
+            let Some(since) = tracker.on_executor_bound(ssn_id) else {
+                return Ok(());
+            };
+            let latency = now - since;
+            tracker
+                .bind
+                .observe(latency, slo)
+                .then_some(latency.num_milliseconds())
+        };
+
+        if let (Some(latency), Some(slo)) = (breached, slo) {
+            let message = format!(
+                "The first executor of the session was bound in {latency}ms, above the SLO of {slo}ms"
+            );
+            tracing::warn!("Session <{ssn_id}>: {message}");
+            self.event_manager.record_event(
+                EventOwner::session(ssn_id.clone()),
+                Event {
+                    code: SLO_BREACHED_EVENT,
+                    message: Some(message),
+                    creation_time: now,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Observes the latencies of the dispatched tasks from their creation; the tasks
+    /// dispatched again after losing their executor are not observed. The breaches
+    /// of the SLO are logged and recorded as events of the tasks.
+    pub fn observe_dispatch_latency(&self, tasks: &[Task]) -> Result<(), FlameError> {
+        let now = Utc::now();
+        let slo = self.context.cluster.slo.dispatch_latency;
+
+        let mut breaches = vec![];
+        {
+            let mut tracker = lock_ptr!(self.slo)?;
+            for task in tasks.iter().filter(|task| task.attempts == 0) {
+                let latency = now - task.creation_time;
+                if tracker.dispatch.observe(latency, slo) {
+                    breaches.push((task, latency.num_milliseconds()));
+                }
+            }
+        }
+
+        let Some(slo) = slo else {
+            return Ok(());
+        };
+        for (task, latency) in breaches {
+            let message =
+                format!("The task was dispatched in {latency}ms, above the SLO of {slo}ms");
+            tracing::warn!("Task <{}/{}>: {message}", task.ssn_id, task.id);
+            self.event_manager.record_event(
+                EventOwner::from(task),
+                Event {
+                    code: SLO_BREACHED_EVENT,
+                    message: Some(message),
+                    creation_time: now,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// The summary of the scheduling latencies, e.g. for the logs.
+    pub fn latency_report(&self) -> Result<String, FlameError> {
+        let tracker = lock_ptr!(self.slo)?;
+        Ok(format!(
+            "bind latency: {}; dispatch latency: {}",
+            tracker.bind, tracker.dispatch
+        ))
+    }
+
+    /// Renew the client lease of the open session; returns when the lease expires.
+    pub fn renew_session_lease(&self, id: &SessionID) -> Result<DateTime<Utc>, FlameError> {
+        let ssn_ptr = self.get_session_ptr(id.clone())?;
+        if lock_ptr!(ssn_ptr)?.is_closed() {
+            return Err(FlameError::InvalidState(format!(
+                "session <{id}> is closed"
+            )));
+        }
+
+        let now = Utc::now();
+        lock_ptr!(self.leases)?.insert(id.clone(), now);
+
+        Ok(now + Duration::seconds(self.lease_grace_period() as i64))
+    }
+
+    /// The grace period in seconds of the client leases.
+    pub fn lease_grace_period(&self) -> u64 {
+        self.context.cluster.lease_grace_period
+    }
+
+    /// The resources of one slot of the cluster.
+    pub fn slot(&self) -> ResourceRequirement {
+        self.context.cluster.slot.clone()
+    }
+
+    /// Close the sessions whose client lease was not renewed within the grace period;
+    /// returns the closed sessions.
+    pub async fn expire_session_leases(&self) -> Result<Vec<SessionID>, FlameError> {
+        let deadline = Utc::now() - Duration::seconds(self.lease_grace_period() as i64);
+        let expired: Vec<SessionID> = {
+            let mut leases = lock_ptr!(self.leases)?;
+            let expired = leases
+                .iter()
+                .filter(|(_, renew_time)| **renew_time < deadline)
+                .map(|(id, _)| id.clone())
+                .collect::<Vec<_>>();
+            for id in &expired {
+                leases.remove(id);
+            }
+            expired
+        };
+
+        let mut closed = vec![];
+        for id in expired {
+            let ssn_ptr = match self.get_session_ptr(id.clone()) {
+                Ok(ssn_ptr) => ssn_ptr,
+                Err(_) => continue,
+            };
+            if lock_ptr!(ssn_ptr)?.status.state != SessionState::Open {
+                continue;
+            }
+
+            tracing::warn!("The client lease of session <{id}> expired, close it.");
+            self.event_manager.record_event(
+                EventOwner::session(id.clone()),
+                Event {
+                    code: SESSION_LEASE_EXPIRED_EVENT,
+                    message: Some(format!(
+                        "Client lease was not renewed in {}s",
+                        self.lease_grace_period()
+                    )),
+                    creation_time: Utc::now(),
+                },
+            )?;
+
+            match self.close_session(id.clone(), None, false).await {
+                Ok(_) => closed.push(id),
+                Err(e) => tracing::error!("Failed to close session <{id}> of expired lease: {e}"),
+            }
+        }
+
+        Ok(closed)
+    }
+
+    /// Delete the closed sessions whose TTL after completion expired, with their
+    /// tasks and events, including the ones evicted from the cache. Their objects
+    /// in the object cache are deleted by the executor managers once the
+    /// sessions are gone, see `flame_cache::gc`.
+    pub async fn expire_sessions(&self) -> Result<Vec<SessionID>, FlameError> {
+        let now = Utc::now();
+        let mut expired: HashSet<SessionID> = {
+            let ssn_map = lock_ptr!(self.sessions)?;
+            ssn_map
+                .iter()
+                .filter(|(_, ssn_ptr)| {
+                    lock_ptr!(ssn_ptr)
+                        .map(|ssn| ssn.is_expired(now))
+                        .unwrap_or(false)
+                })
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+        expired.extend(self.engine.find_expired_session_ids(now).await?);
+
+        let mut deleted = vec![];
+        for id in expired {
+            match self.delete_session(id.clone()).await {
+                Ok(_) => deleted.push(id),
+                Err(e) => tracing::error!("Failed to delete expired session <{id}>: {e}"),
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    /// Dispatch the running tasks again which exceeded their timeout by the grace
+    /// period, e.g. their executor hung instead of enforcing the timeout; they're
+    /// quarantined after their max attempts as the other retries. The hung
+    /// executors are deleted, so their nodes release them by the next sync;
+    /// returns the expired tasks.
+    pub async fn expire_running_tasks(
+        &self,
+        grace_period: u64,
+    ) -> Result<Vec<TaskGID>, FlameError> {
+        trace_fn!("Storage::expire_running_tasks");
+
+        let now = Utc::now();
+        let mut expired = vec![];
+        for executor in self.list_executor(None)? {
+            let (Some(task_id), Some(ssn_id), Some(task_start)) = (
+                executor.task_id,
+                executor.ssn_id.clone(),
+                executor.task_start,
+            ) else {
+                continue;
+            };
+            let timeout = match self.get_task_settings(ssn_id.clone(), Some(task_id)) {
+                Ok(settings) => settings.timeout.value,
+                Err(_) => continue,
+            };
+            let Some(timeout) = timeout else {
+                continue;
+            };
+            if now - task_start < Duration::seconds((timeout + grace_period) as i64) {
+                continue;
+            }
+
+            tracing::warn!(
+                "Executor <{}> did not complete task <{ssn_id}/{task_id}> in {timeout}s, dispatch its tasks again.",
+                executor.id
+            );
+            let message = format!(
+                "executor <{}> did not complete it in {timeout}s",
+                executor.id
+            );
+            expired.extend(
+                self.redispatch_executor_tasks(&executor, FailureReason::Timeout, &message)
+                    .await?,
+            );
+
+            if let Err(e) = self.delete_executor(executor.id.clone()).await {
+                tracing::warn!("Failed to delete executor <{}>: {e}", executor.id);
+            }
+        }
+
+        Ok(expired)
+    }
 }
 
+#[cfg(test)]
+mod fixtures;
+
 #[cfg(test)]
 mod node_tests;
 
@@ -984,3 +2444,36 @@ mod load_data_tests;
 
 #[cfg(test)]
 mod derive_events_path_tests;
+
+#[cfg(test)]
+mod task_overrides_tests;
+
+#[cfg(test)]
+mod session_reopen_tests;
+
+#[cfg(test)]
+mod session_lease_tests;
+
+#[cfg(test)]
+mod session_close_tests;
+
+#[cfg(test)]
+mod session_ttl_tests;
+
+#[cfg(test)]
+mod task_quarantine_tests;
+
+#[cfg(test)]
+mod task_watchdog_tests;
+
+#[cfg(test)]
+mod app_update_tests;
+
+#[cfg(test)]
+mod executor_id_tests;
+
+#[cfg(test)]
+mod task_input_ref_tests;
+
+#[cfg(test)]
+mod result_policy_tests;
@@ -49,6 +49,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -79,6 +80,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -121,6 +123,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
         tokio_test::block_on(storage.create_node(&node))?;
@@ -136,8 +139,12 @@ mod tests {
             slots: 2,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::Void,
         };
@@ -198,6 +205,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
         tokio_test::block_on(storage.create_node(&node))?;
@@ -214,8 +222,12 @@ mod tests {
                 slots: 2,
                 shim: Shim::Host,
                 task_id: None,
+                bundle: vec![],
+                task_start: None,
                 ssn_id: None,
                 batch_index: None,
+                preempted: false,
+                generation: 0,
                 creation_time: Utc::now(),
                 state: ExecutorState::Idle,
             };
@@ -269,8 +281,12 @@ mod tests {
             slots: 2,
             shim: Shim::Host,
             task_id: None,
+            bundle: vec![],
+            task_start: None,
             ssn_id: None,
             batch_index: None,
+            preempted: false,
+            generation: 0,
             creation_time: Utc::now(),
             state: ExecutorState::Void,
         };
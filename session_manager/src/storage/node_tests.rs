@@ -41,6 +41,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -75,6 +76,7 @@ mod tests {
             info: NodeInfo {
                 arch: "aarch64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -129,6 +131,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -161,6 +164,7 @@ mod tests {
             info: NodeInfo {
                 arch: "x86_64".to_string(),
                 os: "linux".to_string(),
+                ..Default::default()
             },
         };
 
@@ -222,11 +226,13 @@ mod tests {
         assert_eq!(NodeState::from(0i32), NodeState::Unknown);
         assert_eq!(NodeState::from(1i32), NodeState::Ready);
         assert_eq!(NodeState::from(2i32), NodeState::NotReady);
+        assert_eq!(NodeState::from(3i32), NodeState::Lost);
         assert_eq!(NodeState::from(99i32), NodeState::Unknown); // Invalid value
 
         // Test reverse conversions
         assert_eq!(i32::from(NodeState::Unknown), 0);
         assert_eq!(i32::from(NodeState::Ready), 1);
         assert_eq!(i32::from(NodeState::NotReady), 2);
+        assert_eq!(i32::from(NodeState::Lost), 3);
     }
 }
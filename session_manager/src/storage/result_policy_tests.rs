@@ -0,0 +1,128 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::storage::fixtures::{self, create_session, create_task, session_attributes};
+    use common::apis::{
+        ObjectReference, ResultPolicy, SessionAttributes, TaskGID, TaskResult, TaskState,
+    };
+    use common::ctx::FlameCluster;
+
+    async fn new_storage(result_policy: ResultPolicy) -> (storage::StoragePtr, TaskGID) {
+        let storage = fixtures::new_storage(FlameCluster::default()).await;
+
+        let attr = SessionAttributes {
+            result_policy,
+            ..session_attributes("ssn-1")
+        };
+        create_session(&storage, attr).await;
+        let gid = create_task(&storage, "ssn-1").await;
+
+        (storage, gid)
+    }
+
+    fn output_ref() -> ObjectReference {
+        ObjectReference {
+            endpoint: "grpc://127.0.0.1:9090".to_string(),
+            key: "test-app/ssn-1/output-1".to_string(),
+            checksum: Some("sha256:9f86d0".to_string()),
+        }
+    }
+
+    async fn complete_task(
+        storage: &storage::StoragePtr,
+        gid: &TaskGID,
+        output_ref: Option<ObjectReference>,
+    ) {
+        let output = match output_ref {
+            Some(_) => None,
+            None => Some(Bytes::from("output")),
+        };
+
+        let ssn = storage.get_session_ptr(gid.ssn_id.clone()).unwrap();
+        let task = storage.get_task_ptr(gid.clone()).unwrap();
+        storage
+            .update_task_result(
+                ssn,
+                task,
+                TaskResult {
+                    state: TaskState::Succeed,
+                    output,
+                    output_ref,
+                    message: None,
+                    artifacts: vec![],
+                    failure_reason: None,
+                    usage: None,
+                    postmortem: None,
+                    codec: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_inline_output() {
+        let (storage, gid) = new_storage(ResultPolicy::Inline).await;
+        complete_task(&storage, &gid, None).await;
+
+        storage.discard_task_output(gid.clone()).unwrap();
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Succeed);
+        assert_eq!(task.output, Some(Bytes::from("output")));
+        assert!(task.output_ref.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_discard_output_after_delivery() {
+        let (storage, gid) = new_storage(ResultPolicy::Discard).await;
+        complete_task(&storage, &gid, None).await;
+
+        // The output is kept until it's delivered to a watcher.
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.output, Some(Bytes::from("output")));
+
+        storage.discard_task_output(gid.clone()).unwrap();
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Succeed);
+        assert!(task.output.is_none());
+        assert!(task.output_ref.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_object_cache_output() {
+        let (storage, gid) = new_storage(ResultPolicy::ObjectCache).await;
+        complete_task(&storage, &gid, Some(output_ref())).await;
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert!(task.output.is_none());
+        assert_eq!(task.output_ref, Some(output_ref()));
+    }
+
+    #[tokio::test]
+    async fn test_object_cache_output_fallback_inline() {
+        let (storage, gid) = new_storage(ResultPolicy::ObjectCache).await;
+
+        // The executor manager failed to offload the output.
+        complete_task(&storage, &gid, None).await;
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.output, Some(Bytes::from("output")));
+        assert!(task.output_ref.is_none());
+    }
+}
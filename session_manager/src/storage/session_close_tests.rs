@@ -0,0 +1,166 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::model::Executor;
+    use crate::storage::fixtures::{self, create_session, create_task, session_attributes};
+    use common::apis::{
+        ExecutorState, FailureReason, SessionState, TaskGID, TaskResult, TaskState,
+        SESSION_CLOSED_EVENT, SESSION_CLOSING_EVENT,
+    };
+    use common::ctx::FlameCluster;
+
+    /// The storage with a session of a running task and a pending task.
+    async fn new_storage() -> (storage::StoragePtr, TaskGID, TaskGID) {
+        let storage = fixtures::new_storage(FlameCluster::default()).await;
+
+        create_session(&storage, session_attributes("ssn-1")).await;
+        let running = create_task(&storage, "ssn-1").await;
+        let pending = create_task(&storage, "ssn-1").await;
+
+        let ssn = storage.get_session_ptr("ssn-1".into()).unwrap();
+        let task = storage.get_task_ptr(running.clone()).unwrap();
+        storage
+            .update_task_state(ssn, task, TaskState::Running, None)
+            .await
+            .unwrap();
+
+        (storage, running, pending)
+    }
+
+    fn succeed_result() -> TaskResult {
+        TaskResult {
+            state: TaskState::Succeed,
+            output: None,
+            output_ref: None,
+            message: None,
+            artifacts: vec![],
+            failure_reason: None,
+            usage: None,
+            postmortem: None,
+            codec: None,
+        }
+    }
+
+    fn session_events(storage: &storage::StoragePtr, code: i32) -> Vec<String> {
+        let ssn = storage.get_session("ssn-1".into()).unwrap();
+        ssn.events
+            .into_iter()
+            .filter(|event| event.code == code)
+            .filter_map(|event| event.message)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_close_session_drains_running_tasks() {
+        let (storage, running, pending) = new_storage().await;
+
+        // The pending task is cancelled, and the session waits for the running one.
+        let ssn = storage
+            .close_session("ssn-1".into(), None, false)
+            .await
+            .unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closing);
+        assert!(ssn.completion_time.is_none());
+        let task = storage.get_task(pending.ssn_id, pending.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Cancelled);
+        assert_eq!(
+            session_events(&storage, SESSION_CLOSING_EVENT),
+            vec!["Session is closing, waiting for 1 running task(s)".to_string()]
+        );
+
+        // The closing session can't be opened again.
+        assert!(storage
+            .open_session("ssn-1".into(), None, None)
+            .await
+            .is_err());
+
+        // The session is closed once the running task is completed.
+        let ssn = storage.get_session_ptr("ssn-1".into()).unwrap();
+        let task = storage.get_task_ptr(running.clone()).unwrap();
+        storage
+            .update_task_result(ssn, task, succeed_result())
+            .await
+            .unwrap();
+
+        let ssn = storage.get_session("ssn-1".into()).unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closed);
+        assert!(ssn.completion_time.is_some());
+        let task = storage.get_task(running.ssn_id, running.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Succeed);
+        assert_eq!(
+            session_events(&storage, SESSION_CLOSED_EVENT),
+            vec!["Session was closed".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_force_close_session() {
+        let (storage, running, _) = new_storage().await;
+
+        let ssn = storage
+            .close_session("ssn-1".into(), None, true)
+            .await
+            .unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closed);
+        assert_eq!(
+            session_events(&storage, SESSION_CLOSED_EVENT),
+            vec!["Session was closed, 1 running task(s) cancelled".to_string()]
+        );
+
+        // The result of the cancelled task is dropped.
+        let ssn = storage.get_session_ptr("ssn-1".into()).unwrap();
+        let task = storage.get_task_ptr(running.clone()).unwrap();
+        storage
+            .update_task_result(ssn, task, succeed_result())
+            .await
+            .unwrap();
+
+        let task = storage.get_task(running.ssn_id, running.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_close_session_with_lost_executor() {
+        let (storage, running, _) = new_storage().await;
+
+        let exec = storage
+            .create_executor("node-1".to_string(), "ssn-1".into(), None)
+            .await
+            .unwrap();
+        let exec = Executor {
+            ssn_id: Some("ssn-1".into()),
+            task_id: Some(running.task_id),
+            state: ExecutorState::Bound,
+            ..exec
+        };
+        storage.update_executor(&exec).await.unwrap();
+
+        let ssn = storage
+            .close_session("ssn-1".into(), None, false)
+            .await
+            .unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closing);
+
+        // The task of the lost executor is failed instead of pending, so the
+        // closing session is closed.
+        storage.delete_executors(&[exec]).await.unwrap();
+
+        let task = storage.get_task(running.ssn_id, running.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.failure_reason, Some(FailureReason::NodeLost));
+        let ssn = storage.get_session("ssn-1".into()).unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closed);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use stdng::lock_ptr;
+
+    use crate::storage;
+    use crate::storage::fixtures::{self, create_session, session_attributes};
+    use common::apis::{SessionID, SessionState, SESSION_LEASE_EXPIRED_EVENT};
+    use common::ctx::FlameCluster;
+    use common::FlameError;
+
+    async fn new_storage() -> storage::StoragePtr {
+        let storage = fixtures::new_storage(FlameCluster {
+            lease_grace_period: 10,
+            ..Default::default()
+        })
+        .await;
+
+        for id in ["ssn-leased", "ssn-unleased"] {
+            create_session(&storage, session_attributes(id)).await;
+        }
+
+        storage
+    }
+
+    #[tokio::test]
+    async fn test_renew_session_lease() {
+        let storage = new_storage().await;
+
+        let before = Utc::now();
+        let expiration = storage.renew_session_lease(&"ssn-leased".into()).unwrap();
+        assert!(expiration >= before + Duration::seconds(10));
+
+        assert!(matches!(
+            storage.renew_session_lease(&"ssn-unknown".into()),
+            Err(FlameError::NotFound(_))
+        ));
+
+        storage
+            .close_session("ssn-unleased".into(), None, false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            storage.renew_session_lease(&"ssn-unleased".into()),
+            Err(FlameError::InvalidState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expire_session_leases() {
+        let storage = new_storage().await;
+        let id = SessionID::from("ssn-leased");
+
+        storage.renew_session_lease(&id).unwrap();
+        assert!(storage.expire_session_leases().await.unwrap().is_empty());
+
+        // The lease was not renewed within the grace period.
+        lock_ptr!(storage.leases)
+            .unwrap()
+            .insert(id.clone(), Utc::now() - Duration::seconds(11));
+        assert_eq!(
+            storage.expire_session_leases().await.unwrap(),
+            vec![id.clone()]
+        );
+
+        let ssn = storage.get_session(id.clone()).unwrap();
+        assert_eq!(ssn.status.state, SessionState::Closed);
+        let events: Vec<_> = ssn
+            .events
+            .iter()
+            .filter(|e| e.code == SESSION_LEASE_EXPIRED_EVENT)
+            .collect();
+        assert_eq!(events.len(), 1);
+
+        // The session without lease is never closed, and the expired one only once.
+        assert!(storage.expire_session_leases().await.unwrap().is_empty());
+        let ssn = storage.get_session("ssn-unleased".into()).unwrap();
+        assert_eq!(ssn.status.state, SessionState::Open);
+    }
+}
@@ -14,7 +14,7 @@ limitations under the License.
 #[cfg(test)]
 mod tests {
     use crate::storage;
-    use common::apis::{SessionAttributes, SessionState};
+    use common::apis::{ResultPolicy, SessionAttributes, SessionState};
     use common::ctx::{FlameCluster, FlameClusterContext, FlameLimits};
     use stdng::lock_ptr;
 
@@ -39,19 +39,30 @@ mod tests {
 
         for i in 0..5 {
             let attr = SessionAttributes {
-                id: format!("ssn-{}", i),
+                id: format!("ssn-{}", i).into(),
                 application: "test-app".to_string(),
                 slots: 1,
                 common_data: None,
                 min_instances: 1,
                 max_instances: None,
                 batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
             };
             storage.create_session(attr).await.unwrap();
         }
 
         for i in 0..3 {
-            storage.close_session(format!("ssn-{}", i)).await.unwrap();
+            storage
+                .close_session(format!("ssn-{}", i).into(), None, false)
+                .await
+                .unwrap();
         }
 
         let sessions = storage.list_session().unwrap();
@@ -65,13 +76,21 @@ mod tests {
 
         for i in 0..3 {
             let attr = SessionAttributes {
-                id: format!("ssn-{}", i),
+                id: format!("ssn-{}", i).into(),
                 application: "test-app".to_string(),
                 slots: 1,
                 common_data: None,
                 min_instances: 1,
                 max_instances: None,
                 batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
             };
             storage.create_session(attr).await.unwrap();
         }
@@ -79,7 +98,10 @@ mod tests {
         let sessions_before = storage.list_session().unwrap();
         assert_eq!(sessions_before.len(), 3);
 
-        storage.close_session("ssn-0".to_string()).await.unwrap();
+        storage
+            .close_session("ssn-0".into(), None, false)
+            .await
+            .unwrap();
 
         let sessions_after = storage.list_session().unwrap();
         assert_eq!(sessions_after.len(), 2);
@@ -95,18 +117,29 @@ mod tests {
 
         for i in 0..3 {
             let attr = SessionAttributes {
-                id: format!("ssn-{}", i),
+                id: format!("ssn-{}", i).into(),
                 application: "test-app".to_string(),
                 slots: 1,
                 common_data: None,
                 min_instances: 1,
                 max_instances: None,
                 batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
             };
             storage.create_session(attr).await.unwrap();
         }
 
-        storage.close_session("ssn-0".to_string()).await.unwrap();
+        storage
+            .close_session("ssn-0".into(), None, false)
+            .await
+            .unwrap();
 
         let sessions = storage.list_session().unwrap();
         assert_eq!(sessions.len(), 3);
@@ -119,18 +152,29 @@ mod tests {
 
         for i in 0..3 {
             let attr = SessionAttributes {
-                id: format!("ssn-{}", i),
+                id: format!("ssn-{}", i).into(),
                 application: "test-app".to_string(),
                 slots: 1,
                 common_data: None,
                 min_instances: 1,
                 max_instances: None,
                 batch_size: 1,
+                scratch_size: None,
+                max_task_attempts: None,
+                task_timeout: None,
+                task_priority: None,
+                priority: 0,
+                result_policy: ResultPolicy::Inline,
+                ttl_after_completion: None,
+                trace_id: None,
             };
             storage.create_session(attr).await.unwrap();
         }
 
-        storage.close_session("ssn-1".to_string()).await.unwrap();
+        storage
+            .close_session("ssn-1".into(), None, false)
+            .await
+            .unwrap();
 
         let sessions = storage.list_session().unwrap();
         assert_eq!(sessions.len(), 2);
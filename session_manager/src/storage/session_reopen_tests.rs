@@ -0,0 +1,141 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::storage;
+    use common::apis::{
+        ApplicationAttributes, ReplayPolicy, SessionAttributes, SessionState, TaskAttributes,
+        TaskGID, TaskState,
+    };
+    use common::ctx::{FlameCluster, FlameClusterContext};
+    use common::FlameError;
+
+    const SSN_ID: &str = "ssn-reopen";
+
+    fn test_context(url: &str) -> FlameClusterContext {
+        FlameClusterContext {
+            cluster: FlameCluster {
+                storage: url.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Create a closed session with a succeed task and a failed task.
+    async fn new_closed_session(url: &str) -> storage::StoragePtr {
+        let storage = storage::new_ptr(&test_context(url)).await.unwrap();
+        storage
+            .register_application("test-app".to_string(), ApplicationAttributes::default())
+            .await
+            .unwrap();
+        storage
+            .create_session(SessionAttributes {
+                id: SSN_ID.into(),
+                application: "test-app".to_string(),
+                slots: 1,
+                ..SessionAttributes::default()
+            })
+            .await
+            .unwrap();
+
+        for state in [TaskState::Succeed, TaskState::Failed] {
+            let task = storage
+                .create_task(SSN_ID.into(), TaskAttributes::default())
+                .await
+                .unwrap();
+            let ssn_ptr = storage.get_session_ptr(SSN_ID.into()).unwrap();
+            let task_ptr = storage
+                .get_task_ptr(TaskGID {
+                    ssn_id: SSN_ID.into(),
+                    task_id: task.id,
+                })
+                .unwrap();
+            storage
+                .update_task_state(ssn_ptr, task_ptr, state, None)
+                .await
+                .unwrap();
+        }
+
+        storage
+            .close_session(SSN_ID.into(), None, false)
+            .await
+            .unwrap();
+        storage
+    }
+
+    fn task_states(storage: &storage::StoragePtr) -> Vec<TaskState> {
+        let mut tasks = storage.list_task(SSN_ID.into()).unwrap();
+        tasks.sort_by_key(|t| t.id);
+        tasks.iter().map(|t| t.state).collect()
+    }
+
+    #[tokio::test]
+    async fn test_open_closed_session_without_replay() {
+        let url = common::temp_sqlite_url("flame_test_open_closed_session");
+        let storage = new_closed_session(&url).await;
+
+        let res = storage.open_session(SSN_ID.into(), None, None).await;
+        assert!(matches!(res, Err(FlameError::InvalidState(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reopen_session_keep_results() {
+        let url = common::temp_sqlite_url("flame_test_reopen_keep_results");
+        let storage = new_closed_session(&url).await;
+
+        let ssn = storage
+            .open_session(SSN_ID.into(), None, Some(ReplayPolicy::KeepResults))
+            .await
+            .unwrap();
+        assert_eq!(ssn.status.state, SessionState::Open);
+        assert!(ssn.completion_time.is_none());
+        assert_eq!(
+            task_states(&storage),
+            vec![TaskState::Succeed, TaskState::Failed]
+        );
+
+        // New tasks are accepted by the reopened session.
+        storage
+            .create_task(SSN_ID.into(), TaskAttributes::default())
+            .await
+            .unwrap();
+        assert_eq!(task_states(&storage).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reopen_session_retry_failed() {
+        let url = common::temp_sqlite_url("flame_test_reopen_retry_failed");
+        let storage = new_closed_session(&url).await;
+
+        storage
+            .open_session(SSN_ID.into(), None, Some(ReplayPolicy::RetryFailed))
+            .await
+            .unwrap();
+        assert_eq!(
+            task_states(&storage),
+            vec![TaskState::Succeed, TaskState::Pending]
+        );
+
+        // The reopened session and its tasks are persisted in the engine.
+        let storage = storage::new_ptr(&test_context(&url)).await.unwrap();
+        storage.load_data().await.unwrap();
+        let ssn = storage.get_session(SSN_ID.into()).unwrap();
+        assert_eq!(ssn.status.state, SessionState::Open);
+        assert_eq!(
+            task_states(&storage),
+            vec![TaskState::Succeed, TaskState::Pending]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::storage;
+    use crate::storage::fixtures::{self, create_task, new_storage, session_attributes};
+    use common::apis::SessionAttributes;
+    use common::ctx::FlameCluster;
+
+    async fn create_session(storage: &storage::StoragePtr, id: &str, ttl: Option<u64>) {
+        let attr = SessionAttributes {
+            ttl_after_completion: ttl,
+            ..session_attributes(id)
+        };
+        fixtures::create_session(storage, attr).await;
+        create_task(storage, id).await;
+    }
+
+    #[tokio::test]
+    async fn test_expire_closed_sessions() {
+        let storage = new_storage(FlameCluster::default()).await;
+        create_session(&storage, "ssn-expired", Some(0)).await;
+        create_session(&storage, "ssn-kept", Some(3600)).await;
+        create_session(&storage, "ssn-forever", None).await;
+        for id in ["ssn-expired", "ssn-kept", "ssn-forever"] {
+            storage.close_session(id.into(), None, false).await.unwrap();
+        }
+
+        let deleted = storage.expire_sessions().await.unwrap();
+        assert_eq!(deleted, vec!["ssn-expired".into()]);
+        assert!(storage.get_session("ssn-expired".into()).is_err());
+        assert!(storage.get_session("ssn-kept".into()).is_ok());
+        assert!(storage.get_session("ssn-forever".into()).is_ok());
+
+        assert!(storage.expire_sessions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_keep_open_sessions() {
+        let storage = new_storage(FlameCluster::default()).await;
+        create_session(&storage, "ssn-1", Some(0)).await;
+
+        assert!(storage.expire_sessions().await.unwrap().is_empty());
+        assert!(storage.get_session("ssn-1".into()).is_ok());
+    }
+}
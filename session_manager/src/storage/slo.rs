@@ -0,0 +1,171 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+
+use common::apis::SessionID;
+
+/// The upper bounds in milliseconds of the buckets of the latency histograms.
+const LATENCY_BUCKETS: [u64; 12] = [
+    10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000, 30000, 60000,
+];
+
+/// The histogram of a latency, with the observations above its SLO threshold.
+#[derive(Clone, Debug, Default)]
+pub struct LatencyHistogram {
+    /// The observations of each bucket; the last one is for the latencies
+    /// above all the buckets.
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    count: u64,
+    max: u64,
+    breaches: u64,
+}
+
+impl LatencyHistogram {
+    /// Observes the latency; returns whether it breached the SLO threshold in milliseconds.
+    pub fn observe(&mut self, latency: Duration, slo: Option<u64>) -> bool {
+        let ms = latency.num_milliseconds().max(0) as u64;
+
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.max = self.max.max(ms);
+
+        let breached = slo.is_some_and(|slo| ms > slo);
+        if breached {
+            self.breaches += 1;
+        }
+
+        breached
+    }
+
+    /// The upper bound in milliseconds of the bucket of the quantile, or the max
+    /// latency if it's above all the buckets.
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((self.count as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return Some(LATENCY_BUCKETS.get(i).copied().unwrap_or(self.max));
+            }
+        }
+
+        Some(self.max)
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.quantile(0.5), self.quantile(0.99)) {
+            (Some(p50), Some(p99)) => write!(
+                f,
+                "count={}, p50<={}ms, p99<={}ms, max={}ms, breaches={}",
+                self.count, p50, p99, self.max, self.breaches
+            ),
+            _ => write!(f, "count=0"),
+        }
+    }
+}
+
+/// The latencies of the scheduling: from a session becoming dispatchable to its
+/// first executor bound, and from a task's creation to its dispatch.
+#[derive(Default)]
+pub struct SloTracker {
+    pub bind: LatencyHistogram,
+    pub dispatch: LatencyHistogram,
+    /// The time each session became dispatchable, i.e. its first pending task
+    /// was created, until its first executor is bound.
+    dispatchable_since: HashMap<SessionID, DateTime<Utc>>,
+    /// The sessions whose first executor was bound.
+    bound: HashSet<SessionID>,
+}
+
+impl SloTracker {
+    pub fn on_task_created(&mut self, ssn_id: &SessionID, creation_time: DateTime<Utc>) {
+        if !self.bound.contains(ssn_id) {
+            self.dispatchable_since
+                .entry(ssn_id.clone())
+                .or_insert(creation_time);
+        }
+    }
+
+    /// Returns the time the session became dispatchable if this is its first
+    /// executor bound.
+    pub fn on_executor_bound(&mut self, ssn_id: &SessionID) -> Option<DateTime<Utc>> {
+        if !self.bound.insert(ssn_id.clone()) {
+            return None;
+        }
+
+        self.dispatchable_since.remove(ssn_id)
+    }
+
+    pub fn remove_session(&mut self, ssn_id: &SessionID) {
+        self.dispatchable_since.remove(ssn_id);
+        self.bound.remove(ssn_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histogram() {
+        let mut hist = LatencyHistogram::default();
+        assert_eq!(hist.quantile(0.5), None);
+
+        for ms in [5, 20, 30, 80, 800] {
+            assert!(!hist.observe(Duration::milliseconds(ms), Some(1000)));
+        }
+        assert!(hist.observe(Duration::milliseconds(90000), Some(1000)));
+
+        assert_eq!(hist.quantile(0.5), Some(50));
+        assert_eq!(hist.quantile(0.99), Some(90000));
+        assert_eq!(
+            hist.to_string(),
+            "count=6, p50<=50ms, p99<=90000ms, max=90000ms, breaches=1"
+        );
+    }
+
+    #[test]
+    fn test_slo_tracker() {
+        let mut tracker = SloTracker::default();
+        let ssn_id = SessionID::from("ssn-1");
+        let now = Utc::now();
+
+        assert_eq!(tracker.on_executor_bound(&ssn_id), None);
+        tracker.remove_session(&ssn_id);
+
+        tracker.on_task_created(&ssn_id, now - Duration::seconds(2));
+        tracker.on_task_created(&ssn_id, now);
+        assert_eq!(
+            tracker.on_executor_bound(&ssn_id),
+            Some(now - Duration::seconds(2))
+        );
+
+        // Only the first executor of the session is measured.
+        tracker.on_task_created(&ssn_id, now);
+        assert_eq!(tracker.on_executor_bound(&ssn_id), None);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::storage;
+    use crate::storage::fixtures::{self, create_session, session_attributes};
+    use common::apis::{ObjectReference, TaskAttributes};
+    use common::ctx::FlameCluster;
+    use common::FlameError;
+
+    async fn new_storage() -> storage::StoragePtr {
+        let storage = fixtures::new_storage(FlameCluster::default()).await;
+
+        storage
+            .register_application(fixtures::TEST_APP.to_string(), Default::default())
+            .await
+            .unwrap();
+        create_session(&storage, session_attributes("ssn-1")).await;
+
+        storage
+    }
+
+    fn input_ref() -> ObjectReference {
+        ObjectReference {
+            endpoint: "grpc://127.0.0.1:9090".to_string(),
+            key: "test-app/ssn-1/input-1".to_string(),
+            checksum: Some("sha256:9f86d0".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_task_with_input_ref() {
+        let storage = new_storage().await;
+
+        let task = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    input_ref: Some(input_ref()),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(task.input.is_none());
+        assert_eq!(task.input_ref, Some(input_ref()));
+    }
+
+    #[tokio::test]
+    async fn test_create_task_with_invalid_input_ref() {
+        let storage = new_storage().await;
+
+        // The input is either inline or in the object cache.
+        let res = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    input: Some(Bytes::from("input")),
+                    input_ref: Some(input_ref()),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await;
+        assert!(matches!(res, Err(FlameError::InvalidConfig(_))));
+
+        let res = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    input_ref: Some(ObjectReference {
+                        key: String::new(),
+                        ..input_ref()
+                    }),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await;
+        assert!(matches!(res, Err(FlameError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,99 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::storage;
+    use crate::storage::fixtures::{self, create_session, session_attributes};
+    use common::apis::{ApplicationAttributes, TaskAttributes, TaskOverrides};
+    use common::ctx::FlameCluster;
+    use common::FlameError;
+
+    async fn new_storage() -> storage::StoragePtr {
+        let storage = fixtures::new_storage(FlameCluster::default()).await;
+
+        storage
+            .register_application(
+                fixtures::TEST_APP.to_string(),
+                ApplicationAttributes {
+                    task_environments: vec!["LOG_LEVEL".to_string()],
+                    task_arguments: vec!["--level".to_string()],
+                    ..ApplicationAttributes::default()
+                },
+            )
+            .await
+            .unwrap();
+        create_session(&storage, session_attributes("ssn-1")).await;
+
+        storage
+    }
+
+    #[tokio::test]
+    async fn test_create_task_with_allowed_overrides() {
+        let storage = new_storage().await;
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+            arguments: vec!["--level=3".to_string()],
+            ..TaskOverrides::default()
+        };
+        let task = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    overrides: overrides.clone(),
+                    ..TaskAttributes::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(task.overrides, overrides);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_with_disallowed_overrides() {
+        let storage = new_storage().await;
+
+        let overrides = TaskOverrides {
+            environments: HashMap::from([("LD_PRELOAD".to_string(), "/tmp/x.so".to_string())]),
+            ..TaskOverrides::default()
+        };
+        let res = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    overrides,
+                    ..TaskAttributes::default()
+                },
+            )
+            .await;
+        assert!(matches!(res, Err(FlameError::InvalidConfig(_))));
+
+        let overrides = TaskOverrides {
+            arguments: vec!["--output=/etc/passwd".to_string()],
+            ..TaskOverrides::default()
+        };
+        let res = storage
+            .create_task(
+                "ssn-1".into(),
+                TaskAttributes {
+                    overrides,
+                    ..TaskAttributes::default()
+                },
+            )
+            .await;
+        assert!(matches!(res, Err(FlameError::InvalidConfig(_))));
+    }
+}
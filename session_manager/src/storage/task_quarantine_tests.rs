@@ -0,0 +1,134 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::fixtures::{self, create_session, create_task, session_attributes};
+    use common::apis::{FailureReason, TaskGID, TaskPostmortem, TaskResult, TaskState};
+    use common::ctx::FlameCluster;
+    use common::FlameError;
+
+    async fn new_storage() -> (storage::StoragePtr, TaskGID) {
+        let storage = fixtures::new_storage(FlameCluster {
+            max_task_attempts: 2,
+            ..Default::default()
+        })
+        .await;
+
+        create_session(&storage, session_attributes("ssn-1")).await;
+        let gid = create_task(&storage, "ssn-1").await;
+
+        (storage, gid)
+    }
+
+    fn failed_result(reason: FailureReason) -> TaskResult {
+        TaskResult {
+            state: TaskState::Failed,
+            output: None,
+            output_ref: None,
+            message: Some("connection reset; stderr of the service:\nsegfault".to_string()),
+            artifacts: vec![],
+            failure_reason: Some(reason),
+            usage: None,
+            postmortem: None,
+            codec: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_after_max_attempts() {
+        let (storage, gid) = new_storage().await;
+
+        let task = storage
+            .redispatch_task(gid.clone(), FailureReason::NodeLost, None)
+            .await
+            .unwrap();
+        assert_eq!(task.state, TaskState::Pending);
+        assert_eq!(task.attempts, 1);
+
+        let ssn = storage.get_session_ptr(gid.ssn_id.clone()).unwrap();
+        let task = storage.get_task_ptr(gid.clone()).unwrap();
+        storage
+            .update_task_result(ssn, task, failed_result(FailureReason::ShimCrash))
+            .await
+            .unwrap();
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Quarantined);
+        assert_eq!(task.attempts, 2);
+        let event = task.events.last().unwrap();
+        assert_eq!(event.code, i32::from(TaskState::Quarantined));
+        assert!(event.message.as_ref().unwrap().contains("segfault"));
+
+        let ssn = storage.get_session(gid.ssn_id.clone()).unwrap();
+        let count = |state| ssn.tasks_index.get(&state).map_or(0, |tasks| tasks.len());
+        assert_eq!(count(TaskState::Pending), 0);
+        assert_eq!(count(TaskState::Quarantined), 1);
+
+        let task = storage.release_task(gid.clone()).await.unwrap();
+        assert_eq!(task.state, TaskState::Pending);
+        assert_eq!(task.attempts, 0);
+        assert!(matches!(
+            storage.release_task(gid.clone()).await,
+            Err(FlameError::InvalidState(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_application_failure_is_not_redispatched() {
+        let (storage, gid) = new_storage().await;
+
+        let ssn = storage.get_session_ptr(gid.ssn_id.clone()).unwrap();
+        let task = storage.get_task_ptr(gid.clone()).unwrap();
+        storage
+            .update_task_result(ssn, task, failed_result(FailureReason::ApplicationError))
+            .await
+            .unwrap();
+
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_postmortem_of_crashed_instance() {
+        let (storage, gid) = new_storage().await;
+
+        let postmortem = TaskPostmortem {
+            executor_id: "executor-1".to_string(),
+            node: "node-1".to_string(),
+            signal: Some(11),
+            stderr_tail: Some("segfault".to_string()),
+            ..TaskPostmortem::default()
+        };
+        let ssn = storage.get_session_ptr(gid.ssn_id.clone()).unwrap();
+        let task = storage.get_task_ptr(gid.clone()).unwrap();
+        storage
+            .update_task_result(
+                ssn,
+                task,
+                TaskResult {
+                    postmortem: Some(postmortem.clone()),
+                    ..failed_result(FailureReason::ShimCrash)
+                },
+            )
+            .await
+            .unwrap();
+
+        // The task is dispatched again with the postmortem of the crash.
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Pending);
+        assert_eq!(task.attempts, 1);
+        assert_eq!(task.postmortem, Some(postmortem));
+    }
+}
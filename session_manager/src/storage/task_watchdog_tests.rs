@@ -0,0 +1,118 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use stdng::lock_ptr;
+
+    use crate::storage::fixtures::{self, create_session, create_task, session_attributes};
+    use common::apis::{FailureReason, SessionAttributes, TaskAttributes, TaskGID, TaskState};
+    use common::ctx::FlameCluster;
+    use common::FlameError;
+
+    async fn new_storage() -> (storage::StoragePtr, TaskGID) {
+        let storage = fixtures::new_storage(FlameCluster {
+            max_task_attempts: 2,
+            ..Default::default()
+        })
+        .await;
+
+        let attr = SessionAttributes {
+            task_timeout: Some(10),
+            ..session_attributes("ssn-1")
+        };
+        create_session(&storage, attr).await;
+        let gid = create_task(&storage, "ssn-1").await;
+
+        (storage, gid)
+    }
+
+    /// Runs the task by a new executor, which started it `elapsed` seconds ago.
+    async fn run_task(storage: &storage::StoragePtr, gid: &TaskGID, elapsed: i64) -> String {
+        let ssn_ptr = storage.get_session_ptr(gid.ssn_id.clone()).unwrap();
+        let task_ptr = storage.get_task_ptr(gid.clone()).unwrap();
+        storage
+            .update_task_state(ssn_ptr, task_ptr, TaskState::Running, None)
+            .await
+            .unwrap();
+
+        let executor = storage
+            .create_executor("node-1".to_string(), gid.ssn_id.clone(), None)
+            .await
+            .unwrap();
+        let exe_ptr = storage.get_executor_ptr(executor.id.clone()).unwrap();
+        let mut exe = lock_ptr!(exe_ptr).unwrap();
+        exe.task_id = Some(gid.task_id);
+        exe.ssn_id = Some(gid.ssn_id.clone());
+        exe.task_start = Some(Utc::now() - Duration::seconds(elapsed));
+
+        executor.id
+    }
+
+    #[tokio::test]
+    async fn test_expire_running_tasks() {
+        let (storage, gid) = new_storage().await;
+
+        // The task is still within its timeout and the grace period.
+        let id = run_task(&storage, &gid, 15).await;
+        assert!(storage.expire_running_tasks(10).await.unwrap().is_empty());
+        assert!(storage.get_executor_ptr(id.clone()).is_ok());
+
+        // The hung executor is deleted, and the task is dispatched again.
+        let expired = storage.expire_running_tasks(5).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert!(matches!(
+            storage.get_executor_ptr(id),
+            Err(FlameError::NotFound(_))
+        ));
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Pending);
+        assert_eq!(task.attempts, 1);
+        let event = task.events.last().unwrap();
+        assert_eq!(event.code, i32::from(TaskState::Pending));
+        assert!(event
+            .message
+            .as_ref()
+            .unwrap()
+            .contains(&format!("{:?}", FailureReason::Timeout)));
+
+        // The task is quarantined after its max attempts.
+        run_task(&storage, &gid, 60).await;
+        assert_eq!(storage.expire_running_tasks(5).await.unwrap().len(), 1);
+        let task = storage.get_task(gid.ssn_id.clone(), gid.task_id).unwrap();
+        assert_eq!(task.state, TaskState::Quarantined);
+        assert_eq!(task.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_task_without_timeout_is_not_expired() {
+        let (storage, _) = new_storage().await;
+        storage
+            .create_session(SessionAttributes {
+                id: "ssn-2".into(),
+                application: "test-app".to_string(),
+                ..SessionAttributes::default()
+            })
+            .await
+            .unwrap();
+        let task = storage
+            .create_task("ssn-2".into(), TaskAttributes::default())
+            .await
+            .unwrap();
+
+        run_task(&storage, &task.gid(), 3600).await;
+        assert!(storage.expire_running_tasks(0).await.unwrap().is_empty());
+        let task = storage.get_task("ssn-2".into(), task.id).unwrap();
+        assert_eq!(task.state, TaskState::Running);
+    }
+}
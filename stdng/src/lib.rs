@@ -18,6 +18,7 @@ use thiserror::Error;
 pub mod collections;
 pub mod logs;
 pub mod rand;
+pub mod sync;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -37,6 +38,7 @@ pub fn new_ptr<T>(t: T) -> MutexPtr<T> {
     Arc::new(Mutex::new(t))
 }
 
+#[cfg(not(feature = "lock-debug"))]
 #[macro_export]
 macro_rules! lock_ptr {
     ( $mutex_arc:expr ) => {
@@ -45,3 +47,12 @@ macro_rules! lock_ptr {
             .map_err(|_| $crate::Error::Internal("mutex ptr".to_string()))
     };
 }
+
+/// Lock the mutex with the lock ordering and hold time checks of [`sync`].
+#[cfg(feature = "lock-debug")]
+#[macro_export]
+macro_rules! lock_ptr {
+    ( $mutex_arc:expr ) => {
+        $crate::sync::lock(&$mutex_arc, concat!(file!(), ":", line!()))
+    };
+}
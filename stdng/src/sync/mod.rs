@@ -0,0 +1,241 @@
+/*
+Copyright 2025 The Flame Authors.
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+    http://www.apache.org/licenses/LICENSE-2.0
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! The instrumentation of the mutex locks, which `lock_ptr!` uses when the
+//! `lock-debug` feature is enabled. It warns when
+//!
+//! * two locks are acquired in both orders, i.e. a potential deadlock;
+//! * a lock is acquired again by the task holding it, i.e. a deadlock;
+//! * a lock is held across an await point, i.e. another task acquires a lock
+//!   on the same thread or the lock is released by another task;
+//! * a lock is held longer than `FLAME_LOCK_HOLD_WARN_MS` milliseconds.
+//!
+//! The locks are identified by their address, so a lock freed and reallocated
+//! at the same address may be reported as a false potential deadlock.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::task::Id as TaskId;
+
+use crate::Error;
+
+/// The environment variable of the hold time in milliseconds to warn at.
+pub const LOCK_HOLD_WARN_MS: &str = "FLAME_LOCK_HOLD_WARN_MS";
+const DEFAULT_LOCK_HOLD_WARN_MS: u64 = 100;
+
+struct HeldLock {
+    addr: usize,
+    location: &'static str,
+    task: Option<TaskId>,
+}
+
+thread_local! {
+    static HELD_LOCKS: RefCell<Vec<HeldLock>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The observed lock orders, i.e. the locks acquired while holding another one,
+/// with the locations of both.
+type LockOrders = HashMap<(usize, usize), (&'static str, &'static str)>;
+
+fn lock_orders() -> &'static Mutex<LockOrders> {
+    static ORDERS: OnceLock<Mutex<LockOrders>> = OnceLock::new();
+    ORDERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn hold_warn_threshold() -> Duration {
+    static THRESHOLD: OnceLock<Duration> = OnceLock::new();
+    *THRESHOLD.get_or_init(|| {
+        let ms = std::env::var(LOCK_HOLD_WARN_MS)
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .unwrap_or(DEFAULT_LOCK_HOLD_WARN_MS);
+        Duration::from_millis(ms)
+    })
+}
+
+/// The guard of a traced lock, which checks the hold time and the task when released.
+pub struct TracedGuard<'a, T> {
+    guard: MutexGuard<'a, T>,
+    addr: usize,
+    location: &'static str,
+    task: Option<TaskId>,
+    acquired: Instant,
+}
+
+/// Lock the mutex at the location, e.g. `file:line`, checking it against the
+/// locks held by the current thread.
+pub fn lock<'a, T>(
+    mutex: &'a Mutex<T>,
+    location: &'static str,
+) -> Result<TracedGuard<'a, T>, Error> {
+    let addr = mutex as *const Mutex<T> as usize;
+    let task = tokio::task::try_id();
+
+    for warning in check_lock(addr, location, task) {
+        tracing::warn!("{warning}");
+    }
+
+    let guard = mutex
+        .lock()
+        .map_err(|_| Error::Internal("mutex ptr".to_string()))?;
+    HELD_LOCKS.with(|held| {
+        held.borrow_mut().push(HeldLock {
+            addr,
+            location,
+            task,
+        })
+    });
+
+    Ok(TracedGuard {
+        guard,
+        addr,
+        location,
+        task,
+        acquired: Instant::now(),
+    })
+}
+
+/// Check the lock to acquire against the locks held by the current thread, and
+/// record the lock orders; returns the warnings.
+fn check_lock(addr: usize, location: &'static str, task: Option<TaskId>) -> Vec<String> {
+    HELD_LOCKS.with(|held| {
+        let held = held.borrow();
+        if held.is_empty() {
+            return vec![];
+        }
+
+        let mut warnings = vec![];
+        let Ok(mut orders) = lock_orders().lock() else {
+            return warnings;
+        };
+        for h in held.iter() {
+            if h.task != task {
+                warnings.push(format!(
+                    "Lock <{}> is held across an await point: another task acquires lock <{}> on the same thread.",
+                    h.location, location
+                ));
+                continue;
+            }
+            if h.addr == addr {
+                warnings.push(format!(
+                    "Deadlock: lock <{}> is acquired again at <{}> while it is held.",
+                    h.location, location
+                ));
+                continue;
+            }
+            if let Some((first, second)) = orders.get(&(addr, h.addr)) {
+                warnings.push(format!(
+                    "Potential deadlock: lock <{}> is acquired while holding lock <{}>, but lock <{}> was acquired while holding lock <{}>.",
+                    location, h.location, second, first
+                ));
+            }
+            orders.entry((h.addr, addr)).or_insert((h.location, location));
+        }
+
+        warnings
+    })
+}
+
+impl<T> Drop for TracedGuard<'_, T> {
+    fn drop(&mut self) {
+        let held = self.acquired.elapsed();
+        if held > hold_warn_threshold() {
+            tracing::warn!(
+                "Lock <{}> was held for {}ms.",
+                self.location,
+                held.as_millis()
+            );
+        }
+        if tokio::task::try_id() != self.task {
+            tracing::warn!(
+                "Lock <{}> is held across an await point: it is released by another task.",
+                self.location
+            );
+        }
+
+        HELD_LOCKS.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(i) = held.iter().rposition(|h| h.addr == self.addr) {
+                held.remove(i);
+            }
+        });
+    }
+}
+
+impl<T> Deref for TracedGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for TracedGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_order() {
+        let a = Mutex::new(0);
+        let b = Mutex::new(0);
+
+        {
+            let _a = lock(&a, "a:1").unwrap();
+            let mut b = lock(&b, "b:1").unwrap();
+            *b += 1;
+        }
+        assert_eq!(*lock(&b, "b:2").unwrap(), 1);
+
+        let _b = lock(&b, "b:3").unwrap();
+        let warnings = check_lock(&a as *const Mutex<i32> as usize, "a:2", None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Potential deadlock: lock <a:2>"));
+        assert!(warnings[0].contains("lock <b:1> was acquired while holding lock <a:1>"));
+    }
+
+    #[test]
+    fn test_lock_again() {
+        let a = Mutex::new(0);
+
+        let _a = lock(&a, "a:1").unwrap();
+        let warnings = check_lock(&a as *const Mutex<i32> as usize, "a:2", None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Deadlock"));
+    }
+
+    #[test]
+    fn test_lock_across_await() {
+        let a = Mutex::new(0);
+        let b = &Mutex::new(0) as *const Mutex<i32> as usize;
+
+        // The tasks of the current thread runtime run on this thread.
+        let _a = lock(&a, "a:1").unwrap();
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let task = rt.spawn(async move { check_lock(b, "b:1", tokio::task::try_id()) });
+        let warnings = rt.block_on(task).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("held across an await point"));
+    }
+}